@@ -0,0 +1,141 @@
+#!/usr/bin/env node
+import net from "node:net";
+import {
+	CLIPCTL_USAGE,
+	type ClipctlCommand,
+	parseClipctlArgs,
+} from "../lib/clipctl-args.js";
+import {
+	type ControlAuditEntry,
+	type ControlListItem,
+	type ControlRequest,
+	type ControlResponse,
+	resolveControlSocketPath,
+} from "../lib/control-protocol.js";
+
+const readStdin = async (): Promise<string> => {
+	const chunks: Buffer[] = [];
+	for await (const chunk of process.stdin) {
+		chunks.push(chunk as Buffer);
+	}
+	return Buffer.concat(chunks).toString("utf-8");
+};
+
+/**
+ * Sends one request and resolves to the app's response.
+ */
+const sendRequest = (
+	socketPath: string,
+	request: ControlRequest,
+): Promise<ControlResponse> =>
+	new Promise((resolve, reject) => {
+		let buffer = "";
+		const socket = net.createConnection(socketPath, () => {
+			socket.write(`${JSON.stringify(request)}\n`);
+		});
+		socket.setEncoding("utf-8");
+		socket.on("data", (chunk: string) => {
+			buffer += chunk;
+		});
+		socket.on("end", () => {
+			try {
+				resolve(JSON.parse(buffer) as ControlResponse);
+			} catch {
+				reject(new Error("Invalid response from Clipboard Manager"));
+			}
+		});
+		socket.on("error", (error: NodeJS.ErrnoException) => {
+			reject(
+				error.code === "ENOENT" || error.code === "ECONNREFUSED"
+					? new Error(`Clipboard Manager is not running (no ${socketPath})`)
+					: error,
+			);
+		});
+	});
+
+/**
+ * Companion CLI for the running app, over the control socket. Run with
+ * Node, or with the app's Electron binary in Node mode.
+ * @returns the process exit code
+ */
+const main = async (): Promise<number> => {
+	let command: ClipctlCommand;
+	try {
+		command = parseClipctlArgs(process.argv.slice(2));
+	} catch (error) {
+		console.error(error instanceof Error ? error.message : String(error));
+		console.error(CLIPCTL_USAGE);
+		return 2;
+	}
+
+	let request: ControlRequest;
+	switch (command.command) {
+		case "help":
+			console.log(CLIPCTL_USAGE);
+			return 0;
+		case "list":
+			request = { command: "list", limit: command.limit };
+			break;
+		case "get":
+		case "copy":
+			request = { command: command.command, id: command.id };
+			break;
+		case "add":
+			request = { command: "add", text: command.text ?? (await readStdin()) };
+			break;
+		case "audit":
+			request = {
+				command: "audit",
+				limit: command.limit,
+				since: command.since,
+			};
+			break;
+	}
+
+	const response = await sendRequest(resolveControlSocketPath(), request);
+	if (!response.ok) {
+		console.error(response.error);
+		return 1;
+	}
+
+	if (command.command === "list") {
+		const items = response.result as ControlListItem[];
+		if (command.json) {
+			console.log(JSON.stringify(items, null, 2));
+		} else {
+			for (const item of items) {
+				console.log(`${item.id}\t${item.type}\t${item.preview}`);
+			}
+		}
+	} else if (command.command === "get") {
+		process.stdout.write(response.result as string);
+	} else if (command.command === "add") {
+		console.log(response.result);
+	} else if (command.command === "audit") {
+		const entries = response.result as ControlAuditEntry[];
+		if (command.json) {
+			console.log(JSON.stringify(entries, null, 2));
+		} else {
+			for (const entry of entries) {
+				// Times are UTC, as stored
+				const subject =
+					entry.history_id === null
+						? (entry.detail ?? "")
+						: `item ${entry.history_id}`;
+				console.log(
+					`${entry.created_at}\t${entry.action}\t${entry.surface}\t${subject}`,
+				);
+			}
+		}
+	}
+	return 0;
+};
+
+main()
+	.then((code) => {
+		process.exitCode = code;
+	})
+	.catch((error) => {
+		console.error(error instanceof Error ? error.message : String(error));
+		process.exitCode = 1;
+	});
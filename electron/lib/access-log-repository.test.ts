@@ -0,0 +1,47 @@
+import { describe, expect, it } from "vitest";
+import { parseAccessLogQuery } from "./access-log-repository.js";
+
+describe("parseAccessLogQuery", () => {
+	it("matches everything by default", () => {
+		expect(parseAccessLogQuery()).toEqual({
+			since: null,
+			action: null,
+			surface: null,
+			historyId: null,
+			limit: 50,
+		});
+	});
+
+	it("stores since as UTC in the created_at format", () => {
+		expect(
+			parseAccessLogQuery({
+				since: "2026-03-01T09:30:00+02:00",
+				action: "paste",
+				surface: "cli",
+				historyId: 42,
+				limit: 10,
+			}),
+		).toEqual({
+			since: "2026-03-01 07:30:00",
+			action: "paste",
+			surface: "cli",
+			historyId: 42,
+			limit: 10,
+		});
+	});
+
+	it("rejects invalid filters", () => {
+		expect(() => parseAccessLogQuery({ since: "yesterday" })).toThrow(
+			"since must be an ISO 8601 time",
+		);
+		expect(() => parseAccessLogQuery({ action: "delete" })).toThrow(
+			"action must be one of reveal, copy, paste, export",
+		);
+		expect(() => parseAccessLogQuery({ surface: "web" })).toThrow(
+			"Invalid access log query",
+		);
+		expect(() => parseAccessLogQuery({ limit: 0 })).toThrow(
+			"limit must be an integer from 1 to 1000",
+		);
+	});
+});
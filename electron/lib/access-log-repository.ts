@@ -0,0 +1,206 @@
+import type Database from "better-sqlite3";
+import { isValidId } from "./history-repository.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * What was done with an item: its full content read, placed on the
+ * clipboard, pasted or typed into an app, or history written to a file.
+ */
+export const ACCESS_ACTIONS = ["reveal", "copy", "paste", "export"] as const;
+
+export type AccessAction = (typeof ACCESS_ACTIONS)[number];
+
+/**
+ * Where the request came from: the app itself (window, tray, hotkeys),
+ * `clipctl`, the local HTTP API, D-Bus or an automation URL.
+ */
+export const ACCESS_SURFACES = ["ui", "cli", "api", "dbus", "url"] as const;
+
+export type AccessSurface = (typeof ACCESS_SURFACES)[number];
+
+/**
+ * Represents a row from the access_log table.
+ */
+export type AccessLogEntry = {
+	id: number;
+	/** The item accessed; null for a history export */
+	history_id: number | null;
+	action: AccessAction;
+	surface: AccessSurface;
+	/** What an export wrote, e.g. `json: 120 items to /tmp/clips.json` */
+	detail: string | null;
+	created_at: string;
+};
+
+/**
+ * One access to record.
+ */
+export type AccessRecord = {
+	historyId: number | null;
+	action: AccessAction;
+	surface: AccessSurface;
+	detail?: string;
+};
+
+/**
+ * Validated access log filters.
+ */
+export type AccessLogQuery = {
+	/** Only entries at or after this time, as stored in `created_at` (UTC) */
+	since: string | null;
+	action: AccessAction | null;
+	surface: AccessSurface | null;
+	historyId: number | null;
+	limit: number;
+};
+
+export const DEFAULT_ACCESS_LOG_LIMIT = 50;
+
+const MAX_ACCESS_LOG_LIMIT = 1000;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid access log query: ${message}`);
+
+const isOneOf = <T extends string>(
+	values: readonly T[],
+	value: unknown,
+): value is T => values.includes(value as T);
+
+/**
+ * Validates access log filters. Missing filters match everything.
+ * Pure function.
+ *
+ * @throws if `since` is not an ISO 8601 time, `action` or `surface` is
+ *   unknown, or `historyId` or `limit` is out of range
+ */
+export const parseAccessLogQuery = (input: unknown = {}): AccessLogQuery => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { since, action, surface, historyId, limit } = input as Partial<
+		Record<keyof AccessLogQuery, unknown>
+	>;
+	const query: AccessLogQuery = {
+		since: null,
+		action: null,
+		surface: null,
+		historyId: null,
+		limit: DEFAULT_ACCESS_LOG_LIMIT,
+	};
+
+	if (since !== undefined && since !== null) {
+		const time = typeof since === "string" ? Date.parse(since) : Number.NaN;
+		if (Number.isNaN(time)) {
+			throw invalid("since must be an ISO 8601 time");
+		}
+		query.since = new Date(time).toISOString().slice(0, 19).replace("T", " ");
+	}
+
+	if (action !== undefined && action !== null) {
+		if (!isOneOf(ACCESS_ACTIONS, action)) {
+			throw invalid(`action must be one of ${ACCESS_ACTIONS.join(", ")}`);
+		}
+		query.action = action;
+	}
+
+	if (surface !== undefined && surface !== null) {
+		if (!isOneOf(ACCESS_SURFACES, surface)) {
+			throw invalid(`surface must be one of ${ACCESS_SURFACES.join(", ")}`);
+		}
+		query.surface = surface;
+	}
+
+	if (historyId !== undefined && historyId !== null) {
+		if (!isValidId(historyId)) {
+			throw invalid("historyId must be a positive integer");
+		}
+		query.historyId = historyId;
+	}
+
+	if (limit !== undefined) {
+		if (
+			typeof limit !== "number" ||
+			!Number.isInteger(limit) ||
+			limit < 1 ||
+			limit > MAX_ACCESS_LOG_LIMIT
+		) {
+			throw invalid(
+				`limit must be an integer from 1 to ${MAX_ACCESS_LOG_LIMIT}`,
+			);
+		}
+		query.limit = limit;
+	}
+
+	return query;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the access log repository.
+ * Owns every SQL statement against the `access_log` table, which triggers
+ * keep append-only: entries are never changed or deleted, and neither
+ * deleting an item nor wiping history removes its entries. Entries hold
+ * no content, only the item id.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createAccessLogRepository = (getDb: () => Database.Database) => {
+	const record = (access: AccessRecord): void => {
+		getDb()
+			.prepare(
+				"INSERT INTO access_log (history_id, action, surface, detail) VALUES (?, ?, ?, ?)",
+			)
+			.run(
+				access.historyId,
+				access.action,
+				access.surface,
+				access.detail ?? null,
+			);
+	};
+
+	/**
+	 * Lists matching entries, newest first.
+	 */
+	const list = (query: AccessLogQuery): AccessLogEntry[] => {
+		const conditions: string[] = [];
+		const params: Array<string | number> = [];
+		if (query.since !== null) {
+			conditions.push("created_at >= ?");
+			params.push(query.since);
+		}
+		if (query.action !== null) {
+			conditions.push("action = ?");
+			params.push(query.action);
+		}
+		if (query.surface !== null) {
+			conditions.push("surface = ?");
+			params.push(query.surface);
+		}
+		if (query.historyId !== null) {
+			conditions.push("history_id = ?");
+			params.push(query.historyId);
+		}
+		const where =
+			conditions.length > 0 ? `WHERE ${conditions.join(" AND ")}` : "";
+		return getDb()
+			.prepare(
+				`SELECT id, history_id, action, surface, detail, created_at FROM access_log ${where} ORDER BY id DESC LIMIT ?`,
+			)
+			.all(...params, query.limit) as AccessLogEntry[];
+	};
+
+	return { record, list };
+};
+
+export type AccessLogRepository = ReturnType<typeof createAccessLogRepository>;
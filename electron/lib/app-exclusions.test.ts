@@ -0,0 +1,114 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createAppExclusions,
+	getAppExclusionsPath,
+	isExcludedApp,
+	normalizeAppName,
+	parseAppExclusionSettings,
+} from "./app-exclusions.js";
+
+describe("normalizeAppName", () => {
+	it("ignores case, whitespace and a .exe suffix", () => {
+		expect(normalizeAppName(" KeePassXC.EXE ")).toBe("keepassxc");
+		expect(normalizeAppName("Bitwarden")).toBe("bitwarden");
+	});
+});
+
+describe("parseAppExclusionSettings", () => {
+	it("trims names and drops duplicates", () => {
+		expect(
+			parseAppExclusionSettings({
+				excludedApps: [" keepassxc.exe", "KeePassXC", "Bitwarden"],
+			}),
+		).toEqual({ excludedApps: ["keepassxc.exe", "Bitwarden"] });
+	});
+
+	it("keeps the current rules when excludedApps is missing", () => {
+		expect(
+			parseAppExclusionSettings({}, { excludedApps: ["1Password"] }),
+		).toEqual({ excludedApps: ["1Password"] });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseAppExclusionSettings(null)).toThrow(
+			"expected an object",
+		);
+		expect(() =>
+			parseAppExclusionSettings({ excludedApps: "Bitwarden" }),
+		).toThrow("must be an array");
+		expect(() => parseAppExclusionSettings({ excludedApps: [1] })).toThrow(
+			"must be strings",
+		);
+		expect(() => parseAppExclusionSettings({ excludedApps: [" "] })).toThrow(
+			"must be 1-256 characters",
+		);
+		const tooMany = Array.from({ length: 101 }, (_, i) => `app-${i}`);
+		expect(() =>
+			parseAppExclusionSettings({ excludedApps: tooMany }),
+		).toThrow("at most 100");
+	});
+});
+
+describe("isExcludedApp", () => {
+	it("matches regardless of case and .exe suffix", () => {
+		expect(isExcludedApp("KeePassXC.exe", ["keepassxc"])).toBe(true);
+		expect(isExcludedApp("Bitwarden", ["bitwarden.exe"])).toBe(true);
+		expect(isExcludedApp("Safari", ["bitwarden"])).toBe(false);
+	});
+
+	it("never matches an unknown app", () => {
+		expect(isExcludedApp(null, ["bitwarden"])).toBe(false);
+	});
+});
+
+describe("createAppExclusions", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-excl-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("excludes copies from listed apps", () => {
+		const exclusions = createAppExclusions({
+			userDataPath: createTempUserDataPath(),
+		});
+		expect(exclusions.isExcluded("Bitwarden")).toBe(false);
+
+		exclusions.updateSettings({ excludedApps: ["bitwarden"] });
+
+		expect(exclusions.isExcluded("Bitwarden")).toBe(true);
+		expect(exclusions.isExcluded("Safari")).toBe(false);
+		expect(exclusions.isExcluded(null)).toBe(false);
+	});
+
+	it("persists rules across restarts", () => {
+		const userDataPath = createTempUserDataPath();
+		const first = createAppExclusions({ userDataPath });
+		first.updateSettings({ excludedApps: ["keepassxc.exe"] });
+
+		const second = createAppExclusions({ userDataPath });
+		expect(second.getSettings()).toEqual({ excludedApps: ["keepassxc.exe"] });
+	});
+
+	it("falls back to no rules when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getAppExclusionsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+		const exclusions = createAppExclusions({ userDataPath });
+
+		expect(exclusions.getSettings()).toEqual({ excludedApps: [] });
+		errorSpy.mockRestore();
+	});
+});
@@ -0,0 +1,172 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted per-application exclusion rules.
+ */
+export type AppExclusionSettings = {
+	/** Process or app names whose copies are never recorded */
+	excludedApps: string[];
+};
+
+const DEFAULT_APP_EXCLUSION_SETTINGS: AppExclusionSettings = {
+	excludedApps: [],
+};
+
+const APP_EXCLUSIONS_FILENAME = "app-exclusions.json";
+
+const MAX_EXCLUDED_APPS = 100;
+
+const MAX_APP_NAME_LENGTH = 256;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Normalizes an app name for matching: trimmed, lowercased, without a
+ * trailing `.exe`, so `KeePassXC.exe` matches `keepassxc`.
+ * Pure function.
+ */
+export const normalizeAppName = (name: string): string =>
+	name.trim().toLowerCase().replace(/\.exe$/, "");
+
+/**
+ * Validates an exclusion settings update.
+ * Pure function. Names are trimmed and duplicates (after normalizing)
+ * are dropped, keeping the first spelling. Missing keys keep their
+ * current value.
+ *
+ * @throws if `excludedApps` is not an array of non-empty strings of up to
+ *   256 characters, or has more than 100 entries
+ */
+export const parseAppExclusionSettings = (
+	input: unknown,
+	current: AppExclusionSettings = DEFAULT_APP_EXCLUSION_SETTINGS,
+): AppExclusionSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid app exclusions: expected an object");
+	}
+
+	const { excludedApps } = input as Partial<
+		Record<keyof AppExclusionSettings, unknown>
+	>;
+	if (excludedApps === undefined) {
+		return { excludedApps: [...current.excludedApps] };
+	}
+
+	if (!Array.isArray(excludedApps)) {
+		throw new Error("Invalid app exclusions: excludedApps must be an array");
+	}
+
+	const seen = new Set<string>();
+	const names: string[] = [];
+	for (const entry of excludedApps) {
+		if (typeof entry !== "string") {
+			throw new Error("Invalid app exclusions: app names must be strings");
+		}
+		const name = entry.trim();
+		if (name === "" || name.length > MAX_APP_NAME_LENGTH) {
+			throw new Error(
+				`Invalid app exclusions: app names must be 1-${MAX_APP_NAME_LENGTH} characters`,
+			);
+		}
+		const key = normalizeAppName(name);
+		if (seen.has(key)) continue;
+		seen.add(key);
+		names.push(name);
+	}
+
+	if (names.length > MAX_EXCLUDED_APPS) {
+		throw new Error(
+			`Invalid app exclusions: at most ${MAX_EXCLUDED_APPS} apps can be excluded`,
+		);
+	}
+
+	return { excludedApps: names };
+};
+
+/**
+ * Checks whether an app matches any exclusion rule.
+ * Pure function. Matching ignores case and a `.exe` suffix.
+ */
+export const isExcludedApp = (
+	appName: string | null,
+	excludedApps: readonly string[],
+): boolean => {
+	if (!appName) return false;
+	const key = normalizeAppName(appName);
+	return excludedApps.some((name) => normalizeAppName(name) === key);
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getAppExclusionsPath = (userDataPath: string) =>
+	path.join(userDataPath, APP_EXCLUSIONS_FILENAME);
+
+/**
+ * Reads saved rules, falling back to defaults if missing or invalid.
+ */
+const readAppExclusionsFromFile = (filePath: string): AppExclusionSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { excludedApps: [] };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseAppExclusionSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read app exclusions, using defaults:", error);
+		return { excludedApps: [] };
+	}
+};
+
+const writeAppExclusionsToFile = (
+	filePath: string,
+	settings: AppExclusionSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// App Exclusions Module
+// ============================================================================
+
+export type AppExclusionsDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the per-application exclusion filter.
+ * The capture path checks the app each copy was made in and drops copies
+ * from excluded apps before they reach the database.
+ */
+export const createAppExclusions = (deps: AppExclusionsDeps) => {
+	const filePath = getAppExclusionsPath(deps.userDataPath);
+	let settings = readAppExclusionsFromFile(filePath);
+
+	const getSettings = (): AppExclusionSettings => ({
+		excludedApps: [...settings.excludedApps],
+	});
+
+	const updateSettings = (input: unknown): AppExclusionSettings => {
+		const next = parseAppExclusionSettings(input, settings);
+		writeAppExclusionsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Checks a copy's source app against the rules. Copies whose app could
+	 * not be resolved are recorded.
+	 */
+	const isExcluded = (appName: string | null): boolean =>
+		isExcludedApp(appName, settings.excludedApps);
+
+	return { getSettings, updateSettings, isExcluded };
+};
+
+export type AppExclusions = ReturnType<typeof createAppExclusions>;
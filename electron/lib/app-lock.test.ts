@@ -0,0 +1,207 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createAppLockModule,
+	DEFAULT_APP_LOCK_SETTINGS,
+	getAppLockSettingsPath,
+	parseAppLockSettings,
+	readAppLockSettingsFromFile,
+	shouldAutoLock,
+} from "./app-lock.js";
+
+const MINUTE_MS = 60 * 1000;
+
+describe("parseAppLockSettings", () => {
+	it("merges valid updates into the current settings", () => {
+		expect(parseAppLockSettings({ enabled: true })).toEqual({
+			...DEFAULT_APP_LOCK_SETTINGS,
+			enabled: true,
+		});
+		expect(
+			parseAppLockSettings(
+				{ autoLockMinutes: null },
+				{ enabled: true, autoLockMinutes: 5 },
+			),
+		).toEqual({ enabled: true, autoLockMinutes: null });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseAppLockSettings({ enabled: "yes" })).toThrow(
+			"enabled must be a boolean",
+		);
+		for (const value of [0, 1.5, 24 * 60 + 1, "5"]) {
+			expect(() => parseAppLockSettings({ autoLockMinutes: value })).toThrow(
+				"autoLockMinutes must be",
+			);
+		}
+	});
+});
+
+describe("shouldAutoLock", () => {
+	it("locks once the idle time reaches the limit", () => {
+		const state = { autoLockMinutes: 5, lastActivityAt: 0 };
+		expect(shouldAutoLock({ ...state, now: 5 * MINUTE_MS - 1 })).toBe(false);
+		expect(shouldAutoLock({ ...state, now: 5 * MINUTE_MS })).toBe(true);
+	});
+
+	it("never locks without a limit", () => {
+		expect(
+			shouldAutoLock({ autoLockMinutes: null, lastActivityAt: 0, now: 1e12 }),
+		).toBe(false);
+	});
+});
+
+describe("createAppLockModule", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-lock-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createEnabledLock = (
+		authenticate = vi.fn().mockResolvedValue(undefined),
+	) => {
+		let now = 0;
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(
+			getAppLockSettingsPath(userDataPath),
+			JSON.stringify({ enabled: true, autoLockMinutes: 5 }),
+		);
+		const onLockChanged = vi.fn();
+		const appLock = createAppLockModule({
+			userDataPath,
+			authenticate,
+			onLockChanged,
+			now: () => now,
+		});
+		return {
+			appLock,
+			authenticate,
+			onLockChanged,
+			advance: (ms: number) => {
+				now += ms;
+			},
+		};
+	};
+
+	it("starts locked when enabled and unlocks after authentication", async () => {
+		const { appLock, authenticate, onLockChanged } = createEnabledLock();
+
+		expect(appLock.getStatus().locked).toBe(true);
+		expect(() => appLock.assertUnlocked()).toThrow("History is locked");
+
+		await appLock.unlock();
+		expect(authenticate).toHaveBeenCalledTimes(1);
+		expect(onLockChanged).toHaveBeenCalledWith(false, "unlock");
+		expect(() => appLock.assertUnlocked()).not.toThrow();
+	});
+
+	it("stays locked when authentication fails", async () => {
+		const { appLock } = createEnabledLock(
+			vi.fn().mockRejectedValue(new Error("cancelled")),
+		);
+
+		await expect(appLock.unlock()).rejects.toThrow("cancelled");
+		expect(appLock.getStatus().locked).toBe(true);
+	});
+
+	it("auto-locks after inactivity", async () => {
+		const { appLock, advance, onLockChanged } = createEnabledLock();
+		await appLock.unlock();
+
+		advance(4 * MINUTE_MS);
+		appLock.assertUnlocked();
+		advance(4 * MINUTE_MS);
+		appLock.checkAutoLock();
+		expect(appLock.getStatus().locked).toBe(false);
+
+		advance(MINUTE_MS);
+		appLock.checkAutoLock();
+		expect(appLock.getStatus().locked).toBe(true);
+		expect(onLockChanged).toHaveBeenLastCalledWith(true, "idle");
+	});
+
+	it("counts window input as activity while unlocked", async () => {
+		const { appLock, advance, onLockChanged } = createEnabledLock();
+		await appLock.unlock();
+
+		advance(4 * MINUTE_MS);
+		appLock.recordActivity();
+		advance(4 * MINUTE_MS);
+		appLock.checkAutoLock();
+		expect(appLock.getStatus().locked).toBe(false);
+
+		appLock.lock("system");
+		expect(onLockChanged).toHaveBeenLastCalledWith(true, "system");
+		appLock.recordActivity();
+		expect(appLock.getStatus().locked).toBe(true);
+	});
+
+	it("requires authentication to turn the lock off", async () => {
+		const { appLock, authenticate } = createEnabledLock();
+		await appLock.unlock();
+
+		await appLock.updateSettings({ enabled: false });
+		expect(authenticate).toHaveBeenCalledTimes(2);
+		expect(appLock.getStatus().enabled).toBe(false);
+	});
+
+	it("does nothing when disabled", () => {
+		const appLock = createAppLockModule({
+			userDataPath: createTempUserDataPath(),
+			authenticate: vi.fn(),
+		});
+
+		expect(appLock.lock().locked).toBe(false);
+		expect(() => appLock.assertUnlocked()).not.toThrow();
+	});
+
+	it("reports an enabled lock without OS authentication as unprotected", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(
+			getAppLockSettingsPath(userDataPath),
+			JSON.stringify({ enabled: true, autoLockMinutes: 5 }),
+		);
+		const appLock = createAppLockModule({ userDataPath, authenticate: null });
+
+		expect(appLock.getStatus()).toMatchObject({
+			locked: false,
+			unprotected: true,
+		});
+	});
+
+	it("turns an unprotected lock off without authenticating", async () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(
+			getAppLockSettingsPath(userDataPath),
+			JSON.stringify({ enabled: true, autoLockMinutes: 5 }),
+		);
+		const appLock = createAppLockModule({ userDataPath, authenticate: null });
+
+		await expect(
+			appLock.updateSettings({ enabled: false }),
+		).resolves.toMatchObject({ enabled: false, unprotected: false });
+	});
+
+	it("cannot be enabled without OS authentication", async () => {
+		const userDataPath = createTempUserDataPath();
+		const appLock = createAppLockModule({ userDataPath, authenticate: null });
+
+		await expect(appLock.updateSettings({ enabled: true })).rejects.toThrow(
+			"OS authentication is not available",
+		);
+		expect(
+			readAppLockSettingsFromFile(getAppLockSettingsPath(userDataPath)),
+		).toEqual(DEFAULT_APP_LOCK_SETTINGS);
+	});
+});
@@ -0,0 +1,299 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { Authenticator } from "./os-auth.js";
+
+/**
+ * Persisted app lock settings.
+ */
+export type AppLockSettings = {
+	enabled: boolean;
+	/** Minutes of inactivity before history locks; null never auto-locks */
+	autoLockMinutes: number | null;
+};
+
+/**
+ * App lock state reported to the renderer.
+ */
+export type AppLockStatus = AppLockSettings & {
+	locked: boolean;
+	/** Whether this platform offers an OS authentication prompt */
+	available: boolean;
+	/**
+	 * Enabled, but with no OS prompt to unlock with, so history stays open
+	 * (the app is warned about it rather than locked out for good)
+	 */
+	unprotected: boolean;
+};
+
+/**
+ * Why the lock state changed: an unlock, a lock requested by the user
+ * (tray, settings), one by the system (screen lock, suspend) or the
+ * inactivity timer.
+ */
+export type LockChangeReason = "unlock" | "manual" | "system" | "idle";
+
+export const DEFAULT_APP_LOCK_SETTINGS = {
+	enabled: false,
+	autoLockMinutes: 5,
+} as const satisfies AppLockSettings;
+
+const APP_LOCK_FILENAME = "app-lock.json";
+
+/**
+ * Longest accepted auto-lock delay (one day).
+ */
+const MAX_AUTO_LOCK_MINUTES = 24 * 60;
+
+/**
+ * How often the auto-lock timer checks for inactivity (ms).
+ */
+const AUTO_LOCK_CHECK_INTERVAL_MS = 15 * 1000;
+
+const UNLOCK_REASON = "unlock your clipboard history";
+
+const AUTH_UNAVAILABLE_MESSAGE =
+	"OS authentication is not available on this system";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates an app lock settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or `autoLockMinutes` is not an
+ *   integer from 1 to 1440 or null
+ */
+export const parseAppLockSettings = (
+	input: unknown,
+	current: AppLockSettings = DEFAULT_APP_LOCK_SETTINGS,
+): AppLockSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid app lock settings: expected an object");
+	}
+
+	const { enabled, autoLockMinutes } = input as Partial<
+		Record<keyof AppLockSettings, unknown>
+	>;
+	const next: AppLockSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error(
+				"Invalid app lock settings: enabled must be a boolean",
+			);
+		}
+		next.enabled = enabled;
+	}
+
+	if (autoLockMinutes !== undefined) {
+		if (
+			autoLockMinutes !== null &&
+			!(
+				typeof autoLockMinutes === "number" &&
+				Number.isInteger(autoLockMinutes) &&
+				autoLockMinutes >= 1 &&
+				autoLockMinutes <= MAX_AUTO_LOCK_MINUTES
+			)
+		) {
+			throw new Error(
+				`Invalid app lock settings: autoLockMinutes must be 1-${MAX_AUTO_LOCK_MINUTES} or null`,
+			);
+		}
+		next.autoLockMinutes = autoLockMinutes;
+	}
+
+	return next;
+};
+
+/**
+ * Checks whether history has been idle long enough to lock.
+ * Pure function.
+ */
+export const shouldAutoLock = (state: {
+	autoLockMinutes: number | null;
+	lastActivityAt: number;
+	now: number;
+}): boolean =>
+	state.autoLockMinutes !== null &&
+	state.now - state.lastActivityAt >= state.autoLockMinutes * 60 * 1000;
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getAppLockSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, APP_LOCK_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+export const readAppLockSettingsFromFile = (
+	filePath: string,
+): AppLockSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_APP_LOCK_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseAppLockSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read app lock settings, using defaults:", error);
+		return { ...DEFAULT_APP_LOCK_SETTINGS };
+	}
+};
+
+const writeAppLockSettingsToFile = (
+	filePath: string,
+	settings: AppLockSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// App Lock Module
+// ============================================================================
+
+export type AppLockModuleDeps = {
+	userDataPath: string;
+	/** OS authentication prompt, or null if the platform has none */
+	authenticate: Authenticator | null;
+	/** Called whenever the lock state changes */
+	onLockChanged?: (locked: boolean, reason: LockChangeReason) => void;
+	now?: () => number;
+};
+
+/**
+ * Creates the app lock.
+ * While locked, guarded commands (listing, searching, restoring) throw
+ * until the user authenticates with the OS. History starts locked when
+ * the lock is enabled and the OS offers a prompt (without one it starts
+ * open and reports `unprotected`), and locks again after the configured
+ * inactivity:
+ * no guarded command and no `recordActivity` call, such as input in a
+ * window, for `autoLockMinutes`.
+ */
+export const createAppLockModule = (deps: AppLockModuleDeps) => {
+	const filePath = getAppLockSettingsPath(deps.userDataPath);
+	const now = deps.now ?? Date.now;
+	let settings = readAppLockSettingsFromFile(filePath);
+	let locked = settings.enabled && deps.authenticate !== null;
+	let lastActivityAt = now();
+	let timer: NodeJS.Timeout | null = null;
+
+	const setLocked = (next: boolean, reason: LockChangeReason): void => {
+		if (locked === next) return;
+		locked = next;
+		deps.onLockChanged?.(locked, reason);
+	};
+
+	const getStatus = (): AppLockStatus => ({
+		...settings,
+		locked,
+		available: deps.authenticate !== null,
+		unprotected: settings.enabled && deps.authenticate === null,
+	});
+
+	const authenticate = async (): Promise<void> => {
+		if (!deps.authenticate) {
+			throw new Error(AUTH_UNAVAILABLE_MESSAGE);
+		}
+		await deps.authenticate(UNLOCK_REASON);
+	};
+
+	/**
+	 * Throws while history is locked; otherwise records activity.
+	 */
+	const assertUnlocked = (): void => {
+		if (locked) {
+			throw new Error("History is locked");
+		}
+		lastActivityAt = now();
+	};
+
+	/**
+	 * Counts user interaction that runs no guarded command, such as
+	 * scrolling the history window, toward the inactivity timer. Does
+	 * nothing while locked.
+	 */
+	const recordActivity = (): void => {
+		if (!locked) lastActivityAt = now();
+	};
+
+	const lock = (reason: "manual" | "system" = "manual"): AppLockStatus => {
+		if (settings.enabled) setLocked(true, reason);
+		return getStatus();
+	};
+
+	const unlock = async (): Promise<AppLockStatus> => {
+		if (locked) {
+			await authenticate();
+			lastActivityAt = now();
+			setLocked(false, "unlock");
+		}
+		return getStatus();
+	};
+
+	/**
+	 * Saves new settings. Requires an unlocked history, and turning the
+	 * lock off requires authenticating again, unless there is no prompt
+	 * and so nothing the lock protected.
+	 */
+	const updateSettings = async (input: unknown): Promise<AppLockStatus> => {
+		assertUnlocked();
+		const next = parseAppLockSettings(input, settings);
+		if (next.enabled && !deps.authenticate) {
+			throw new Error(AUTH_UNAVAILABLE_MESSAGE);
+		}
+		if (settings.enabled && !next.enabled && deps.authenticate) {
+			await authenticate();
+		}
+
+		writeAppLockSettingsToFile(filePath, next);
+		settings = next;
+		return getStatus();
+	};
+
+	const checkAutoLock = (): void => {
+		if (
+			settings.enabled &&
+			!locked &&
+			shouldAutoLock({
+				autoLockMinutes: settings.autoLockMinutes,
+				lastActivityAt,
+				now: now(),
+			})
+		) {
+			setLocked(true, "idle");
+		}
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		timer = setInterval(checkAutoLock, AUTO_LOCK_CHECK_INTERVAL_MS);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	return {
+		getStatus,
+		assertUnlocked,
+		recordActivity,
+		lock,
+		unlock,
+		updateSettings,
+		checkAutoLock,
+		start,
+		stop,
+	};
+};
+
+export type AppLockModule = ReturnType<typeof createAppLockModule>;
@@ -0,0 +1,110 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createAppendCopy,
+	getAppendCopySettingsPath,
+	parseAppendCopySettings,
+} from "./append-copy.js";
+
+describe("parseAppendCopySettings", () => {
+	it("keeps the current separator when missing", () => {
+		expect(parseAppendCopySettings({}, { separator: ", " })).toEqual({
+			separator: ", ",
+		});
+		expect(parseAppendCopySettings({ separator: "" })).toEqual({
+			separator: "",
+		});
+	});
+
+	it("rejects invalid separators", () => {
+		expect(() => parseAppendCopySettings(null)).toThrow("expected an object");
+		for (const separator of [1, null, "x".repeat(21)]) {
+			expect(() => parseAppendCopySettings({ separator })).toThrow(
+				"separator must be a string",
+			);
+		}
+	});
+});
+
+describe("createAppendCopy", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-append-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("appends copies to the clipboard text present when activated", () => {
+		const appendCopy = createAppendCopy({
+			userDataPath: createTempUserDataPath(),
+			readText: () => "first",
+		});
+
+		expect(appendCopy.append("ignored")).toBeNull();
+		appendCopy.setActive(true);
+
+		expect(appendCopy.append("second")).toBe("first\nsecond");
+		expect(appendCopy.isCollected("first\nsecond")).toBe(true);
+		expect(appendCopy.append("third")).toBe("first\nsecond\nthird");
+	});
+
+	it("starts from the first copy when the clipboard was empty", () => {
+		const appendCopy = createAppendCopy({
+			userDataPath: createTempUserDataPath(),
+			readText: () => "",
+		});
+		appendCopy.updateSettings({ separator: ", " });
+		appendCopy.setActive(true);
+
+		expect(appendCopy.append("a")).toBeNull();
+		expect(appendCopy.append("b")).toBe("a, b");
+	});
+
+	it("notifies when toggled and forgets the collection when off", () => {
+		const onChange = vi.fn();
+		const appendCopy = createAppendCopy({
+			userDataPath: createTempUserDataPath(),
+			readText: () => "start",
+			onChange,
+		});
+
+		appendCopy.toggle();
+		appendCopy.setActive(true);
+		appendCopy.toggle();
+
+		expect(onChange.mock.calls).toEqual([
+			[{ separator: "\n", active: true }],
+			[{ separator: "\n", active: false }],
+		]);
+		expect(appendCopy.isCollected("start")).toBe(false);
+		expect(() => appendCopy.setActive("yes")).toThrow("expected a boolean");
+	});
+
+	it("persists the separator and falls back to defaults when invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		const readText = () => "";
+		createAppendCopy({ userDataPath, readText }).updateSettings({
+			separator: " | ",
+		});
+		expect(createAppendCopy({ userDataPath, readText }).getStatus()).toEqual({
+			separator: " | ",
+			active: false,
+		});
+
+		fs.writeFileSync(getAppendCopySettingsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+		expect(
+			createAppendCopy({ userDataPath, readText }).getStatus().separator,
+		).toBe("\n");
+		errorSpy.mockRestore();
+	});
+});
@@ -0,0 +1,192 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted append-copy settings.
+ */
+export type AppendCopySettings = {
+	/** Placed between the collected text and each appended copy */
+	separator: string;
+};
+
+/**
+ * Append-copy state reported to the renderer.
+ */
+export type AppendCopyStatus = AppendCopySettings & {
+	/** While true, each new text copy is appended to the clipboard */
+	active: boolean;
+};
+
+const DEFAULT_APPEND_COPY_SETTINGS: AppendCopySettings = {
+	separator: "\n",
+};
+
+const APPEND_COPY_FILENAME = "append-copy.json";
+
+/**
+ * Longest accepted separator (characters).
+ */
+const MAX_SEPARATOR_LENGTH = 20;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates an append-copy settings update.
+ * Pure function. Missing keys keep their current value; an empty separator
+ * joins copies directly.
+ *
+ * @throws if `separator` is not a string of at most 20 characters
+ */
+export const parseAppendCopySettings = (
+	input: unknown,
+	current: AppendCopySettings = DEFAULT_APPEND_COPY_SETTINGS,
+): AppendCopySettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid append-copy settings: expected an object");
+	}
+
+	const { separator } = input as Partial<
+		Record<keyof AppendCopySettings, unknown>
+	>;
+	const next: AppendCopySettings = { ...current };
+
+	if (separator !== undefined) {
+		if (
+			typeof separator !== "string" ||
+			separator.length > MAX_SEPARATOR_LENGTH
+		) {
+			throw new Error(
+				`Invalid append-copy settings: separator must be a string of at most ${MAX_SEPARATOR_LENGTH} characters`,
+			);
+		}
+		next.separator = separator;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getAppendCopySettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, APPEND_COPY_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readAppendCopySettingsFromFile = (
+	filePath: string,
+): AppendCopySettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_APPEND_COPY_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseAppendCopySettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read append-copy settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_APPEND_COPY_SETTINGS };
+	}
+};
+
+const writeAppendCopySettingsToFile = (
+	filePath: string,
+	settings: AppendCopySettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Append-Copy Module
+// ============================================================================
+
+export type AppendCopyDeps = {
+	userDataPath: string;
+	/** Reads the clipboard text the collection starts from */
+	readText: () => string;
+	/** Called whenever append mode is switched on or off */
+	onChange?: (status: AppendCopyStatus) => void;
+};
+
+/**
+ * Creates the append-copy mode: while active, each new text copy is added
+ * to what was already on the clipboard instead of replacing it, so several
+ * snippets can be collected into one paste. The mode itself is not
+ * persisted and is off when the app starts.
+ */
+export const createAppendCopy = (deps: AppendCopyDeps) => {
+	const filePath = getAppendCopySettingsPath(deps.userDataPath);
+	let settings = readAppendCopySettingsFromFile(filePath);
+	/** Text collected so far while active, or null when inactive */
+	let collected: string | null = null;
+
+	const getStatus = (): AppendCopyStatus => ({
+		...settings,
+		active: collected !== null,
+	});
+
+	/**
+	 * Switches append mode on (starting from the current clipboard text) or
+	 * off.
+	 * @throws if `value` is not a boolean
+	 */
+	const setActive = (value: unknown): AppendCopyStatus => {
+		if (typeof value !== "boolean") {
+			throw new Error("Invalid append-copy state: expected a boolean");
+		}
+		if (value === (collected !== null)) return getStatus();
+		collected = value ? deps.readText() : null;
+		deps.onChange?.(getStatus());
+		return getStatus();
+	};
+
+	const toggle = (): AppendCopyStatus => setActive(collected === null);
+
+	/**
+	 * Checks whether text is the collection written back by `append`, so the
+	 * watcher does not treat it as a new copy.
+	 */
+	const isCollected = (text: string): boolean =>
+		collected !== null && collected.length > 0 && text === collected;
+
+	/**
+	 * Appends a new copy to the collection.
+	 * @returns the text to place on the clipboard, or null if append mode is
+	 * off or there was nothing to append to yet
+	 */
+	const append = (text: string): string | null => {
+		if (collected === null || !text) return null;
+		if (!collected) {
+			collected = text;
+			return null;
+		}
+		collected = `${collected}${settings.separator}${text}`;
+		return collected;
+	};
+
+	const updateSettings = (input: unknown): AppendCopyStatus => {
+		const next = parseAppendCopySettings(input, settings);
+		writeAppendCopySettingsToFile(filePath, next);
+		settings = next;
+		return getStatus();
+	};
+
+	return {
+		getStatus,
+		setActive,
+		toggle,
+		isCollected,
+		append,
+		updateSettings,
+	};
+};
+
+export type AppendCopy = ReturnType<typeof createAppendCopy>;
@@ -0,0 +1,167 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createAttachmentStore,
+	decryptAttachment,
+	encryptAttachment,
+	getAttachmentKey,
+	type KeyProtector,
+	toAttachmentFileName,
+} from "./attachment-store.js";
+import { openTestDatabase } from "./test-database.js";
+
+const key = crypto.randomBytes(32);
+
+describe("getAttachmentKey", () => {
+	const protector = (available = true): KeyProtector => ({
+		isEncryptionAvailable: () => available,
+		encryptString: (text) => Buffer.from(`wrapped:${text}`),
+		decryptString: (data) => data.toString().replace(/^wrapped:/, ""),
+	});
+
+	const readStored = (db: ReturnType<typeof openTestDatabase>) =>
+		db.prepare("SELECT key, protected FROM attachment_key").get() as {
+			key: Buffer;
+			protected: number;
+		};
+
+	it("keeps the key encrypted by the keychain", () => {
+		const db = openTestDatabase();
+		const created = getAttachmentKey(db, protector());
+
+		expect(created).toHaveLength(32);
+		expect(getAttachmentKey(db, protector())).toEqual(created);
+		const stored = readStored(db);
+		expect(stored.protected).toBe(1);
+		expect(stored.key.includes(created)).toBe(false);
+		db.close();
+	});
+
+	it("protects a plain key once a keychain is available", () => {
+		const db = openTestDatabase();
+		const created = getAttachmentKey(db, protector(false));
+		expect(readStored(db)).toEqual({ key: created, protected: 0 });
+
+		expect(getAttachmentKey(db, protector())).toEqual(created);
+		expect(readStored(db).protected).toBe(1);
+		expect(() => getAttachmentKey(db, protector(false))).toThrow(
+			"OS keychain",
+		);
+		db.close();
+	});
+});
+
+describe("encryptAttachment", () => {
+	it("round-trips data bound to its hash", () => {
+		const data = Buffer.from("png bytes");
+		const file = encryptAttachment(key, "abc", data);
+
+		expect(file.includes(data)).toBe(false);
+		expect(decryptAttachment(key, "abc", file)).toEqual(data);
+		expect(() => decryptAttachment(key, "def", file)).toThrow();
+		expect(() =>
+			decryptAttachment(crypto.randomBytes(32), "abc", file),
+		).toThrow();
+	});
+});
+
+describe("toAttachmentFileName", () => {
+	it("depends on the key as well as the hash", () => {
+		const name = toAttachmentFileName(key, "abc");
+		expect(name).toMatch(/^[0-9a-f]{64}$/);
+		expect(name).not.toContain("abc");
+		expect(toAttachmentFileName(crypto.randomBytes(32), "abc")).not.toBe(
+			name,
+		);
+	});
+});
+
+describe("createAttachmentStore", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		vi.restoreAllMocks();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createStore = (storeKey = key) => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-attach-"));
+		tempDirs.push(dir);
+		const directory = path.join(dir, "attachments");
+		return {
+			directory,
+			store: createAttachmentStore({
+				getDirectory: () => directory,
+				getKey: () => storeKey,
+			}),
+		};
+	};
+
+	const listFiles = (directory: string): string[] =>
+		fs
+			.readdirSync(directory, { recursive: true, withFileTypes: true })
+			.filter((entry) => entry.isFile())
+			.map((entry) => entry.name);
+
+	it("writes encrypted files and reads them back", () => {
+		const { directory, store } = createStore();
+		const data = Buffer.from("image data");
+		store.write("abc", data);
+		store.write("abc", data);
+
+		const files = listFiles(directory);
+		expect(files).toEqual([toAttachmentFileName(key, "abc")]);
+		expect(store.read("abc")).toEqual(data);
+	});
+
+	it("streams large data in chunks and copies it to plain files", () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const { directory, store } = createStore();
+		const data = crypto.randomBytes(2.5 * 1024 * 1024);
+		store.write("big", data);
+
+		const name = toAttachmentFileName(key, "big");
+		const file = fs.readFileSync(path.join(directory, name.slice(0, 2), name));
+		expect(decryptAttachment(key, "big", file).equals(data)).toBe(true);
+		expect(store.read("big")?.equals(data)).toBe(true);
+
+		const target = path.join(directory, "image.png");
+		expect(store.copyTo("big", target)).toBe(true);
+		expect(fs.readFileSync(target).equals(data)).toBe(true);
+		expect(store.copyTo("missing", `${target}.2`)).toBe(false);
+		expect(fs.existsSync(`${target}.2.partial`)).toBe(false);
+	});
+
+	it("returns null for missing or unreadable files", () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const { directory, store } = createStore();
+		expect(store.read("missing")).toBeNull();
+
+		store.write("abc", Buffer.from("image data"));
+		const name = toAttachmentFileName(key, "abc");
+		fs.writeFileSync(path.join(directory, name.slice(0, 2), name), "junk");
+		expect(store.read("abc")).toBeNull();
+	});
+
+	it("sweeps files not kept, including partial writes", () => {
+		const { directory, store } = createStore();
+		expect(store.sweep([])).toBe(0);
+
+		store.write("keep", Buffer.from("a"));
+		store.write("drop", Buffer.from("b"));
+		const partial = `${toAttachmentFileName(key, "new")}.partial`;
+		fs.mkdirSync(path.join(directory, partial.slice(0, 2)), {
+			recursive: true,
+		});
+		fs.writeFileSync(path.join(directory, partial.slice(0, 2), partial), "");
+
+		expect(store.sweep(["keep"])).toBe(2);
+		expect(listFiles(directory)).toEqual([toAttachmentFileName(key, "keep")]);
+		expect(store.read("keep")).toEqual(Buffer.from("a"));
+	});
+});
@@ -0,0 +1,309 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+import type Database from "better-sqlite3";
+
+const KEY_BYTES = 32;
+const IV_BYTES = 12;
+const TAG_BYTES = 16;
+
+/**
+ * Files are spread over subfolders named by the first two characters of
+ * their name, so no folder grows too large.
+ */
+const SHARD_CHARS = 2;
+
+/**
+ * Files being written carry this suffix until renamed into place.
+ */
+const PARTIAL_SUFFIX = ".partial";
+
+/**
+ * Data encrypted or decrypted per step (bytes). Files are streamed in
+ * steps of this size, so a large image is never held in memory twice.
+ */
+const CHUNK_BYTES = 1024 * 1024;
+
+/**
+ * Encrypts the attachment key with a key the OS keychain holds: Electron's
+ * `safeStorage`.
+ */
+export type KeyProtector = {
+	isEncryptionAvailable: () => boolean;
+	encryptString: (plainText: string) => Buffer;
+	decryptString: (encrypted: Buffer) => string;
+};
+
+export type AttachmentStoreDeps = {
+	/** Folder the attachment files live in */
+	getDirectory: () => string;
+	/** Key for the open database; see `getAttachmentKey` */
+	getKey: () => Buffer;
+};
+
+/**
+ * Returns the database's attachment key, creating it on first use. The
+ * key is stored in the database encrypted by `protector`, so the
+ * database file alone does not open the attachments. Where no keychain
+ * is available it is stored as is, and encrypted the first time one is.
+ * @throws if the key is protected and the keychain cannot decrypt it
+ */
+export const getAttachmentKey = (
+	db: Database.Database,
+	protector: KeyProtector | null = null,
+): Buffer => {
+	db.prepare(
+		"INSERT OR IGNORE INTO attachment_key (id, key) VALUES (1, ?)",
+	).run(crypto.randomBytes(KEY_BYTES));
+	const row = db
+		.prepare("SELECT key, protected FROM attachment_key WHERE id = 1")
+		.get() as { key: Buffer; protected: number };
+	if (row.protected === 1) {
+		if (!protector?.isEncryptionAvailable()) {
+			throw new Error(
+				"Attachment key needs the OS keychain, which is unavailable",
+			);
+		}
+		return Buffer.from(protector.decryptString(row.key), "hex");
+	}
+	if (protector?.isEncryptionAvailable()) {
+		db.prepare(
+			"UPDATE attachment_key SET key = ?, protected = 1 WHERE id = 1",
+		).run(protector.encryptString(row.key.toString("hex")));
+	}
+	return row.key;
+};
+
+/**
+ * Name of the file holding `hash`. Keyed, so names do not reveal content
+ * hashes and a database restored with another key gets its own files.
+ * Pure function.
+ */
+export const toAttachmentFileName = (key: Buffer, hash: string): string =>
+	crypto.createHmac("sha256", key).update(hash).digest("hex");
+
+/**
+ * Encrypts attachment data with AES-256-GCM, bound to its content hash.
+ * Layout: IV (12 bytes), auth tag (16 bytes), ciphertext.
+ */
+export const encryptAttachment = (
+	key: Buffer,
+	hash: string,
+	data: Buffer,
+): Buffer => {
+	const iv = crypto.randomBytes(IV_BYTES);
+	const cipher = crypto.createCipheriv("aes-256-gcm", key, iv);
+	cipher.setAAD(Buffer.from(hash));
+	const ciphertext = Buffer.concat([cipher.update(data), cipher.final()]);
+	return Buffer.concat([iv, cipher.getAuthTag(), ciphertext]);
+};
+
+/**
+ * Decrypts a file written by `encryptAttachment`.
+ * @throws if the key or hash is wrong or the file was modified
+ */
+export const decryptAttachment = (
+	key: Buffer,
+	hash: string,
+	file: Buffer,
+): Buffer => {
+	const decipher = crypto.createDecipheriv(
+		"aes-256-gcm",
+		key,
+		file.subarray(0, IV_BYTES),
+	);
+	decipher.setAAD(Buffer.from(hash));
+	decipher.setAuthTag(file.subarray(IV_BYTES, IV_BYTES + TAG_BYTES));
+	return Buffer.concat([
+		decipher.update(file.subarray(IV_BYTES + TAG_BYTES)),
+		decipher.final(),
+	]);
+};
+
+/**
+ * Writes `data` to a file in the layout of `encryptAttachment`, one
+ * chunk at a time. The auth tag is only known at the end, so its place
+ * is filled in last.
+ */
+const writeEncryptedFile = (
+	filePath: string,
+	key: Buffer,
+	hash: string,
+	data: Buffer,
+): void => {
+	const iv = crypto.randomBytes(IV_BYTES);
+	const cipher = crypto.createCipheriv("aes-256-gcm", key, iv);
+	cipher.setAAD(Buffer.from(hash));
+	const fd = fs.openSync(filePath, "w", 0o600);
+	try {
+		fs.writeSync(fd, iv);
+		fs.writeSync(fd, Buffer.alloc(TAG_BYTES));
+		for (let offset = 0; offset < data.length; offset += CHUNK_BYTES) {
+			fs.writeSync(
+				fd,
+				cipher.update(data.subarray(offset, offset + CHUNK_BYTES)),
+			);
+		}
+		fs.writeSync(fd, cipher.final());
+		fs.writeSync(fd, cipher.getAuthTag(), 0, TAG_BYTES, IV_BYTES);
+	} finally {
+		fs.closeSync(fd);
+	}
+};
+
+/**
+ * Decrypts a file written by `writeEncryptedFile` one chunk at a time,
+ * handing each decrypted chunk to `onChunk`. Chunks are only
+ * authenticated once the whole file is read, so callers must discard
+ * what they got if this throws.
+ * @returns the decrypted size
+ * @throws if the key or hash is wrong or the file was modified
+ */
+const readEncryptedFile = (
+	filePath: string,
+	key: Buffer,
+	hash: string,
+	onChunk: (chunk: Buffer, offset: number) => void,
+): number => {
+	const fd = fs.openSync(filePath, "r");
+	try {
+		const header = Buffer.alloc(IV_BYTES + TAG_BYTES);
+		if (fs.readSync(fd, header, 0, header.length, 0) < header.length) {
+			throw new Error("Attachment file is truncated");
+		}
+		const decipher = crypto.createDecipheriv(
+			"aes-256-gcm",
+			key,
+			header.subarray(0, IV_BYTES),
+		);
+		decipher.setAAD(Buffer.from(hash));
+		decipher.setAuthTag(header.subarray(IV_BYTES));
+
+		const chunk = Buffer.alloc(CHUNK_BYTES);
+		let offset = 0;
+		for (;;) {
+			const bytesRead = fs.readSync(
+				fd,
+				chunk,
+				0,
+				CHUNK_BYTES,
+				header.length + offset,
+			);
+			if (bytesRead === 0) break;
+			onChunk(decipher.update(chunk.subarray(0, bytesRead)), offset);
+			offset += bytesRead;
+		}
+		decipher.final();
+		return offset;
+	} finally {
+		fs.closeSync(fd);
+	}
+};
+
+/**
+ * Creates the attachment store: encrypted files on disk holding binary
+ * item data, named by content hash. Which hashes are in use is tracked in
+ * the database; the store only reads, writes and sweeps files.
+ */
+export const createAttachmentStore = (deps: AttachmentStoreDeps) => {
+	const toPath = (name: string): string =>
+		path.join(deps.getDirectory(), name.slice(0, SHARD_CHARS), name);
+
+	/**
+	 * Stores data under its content hash. Data already stored is not
+	 * written again.
+	 */
+	const write = (hash: string, data: Buffer): void => {
+		const key = deps.getKey();
+		const file = toPath(toAttachmentFileName(key, hash));
+		if (fs.existsSync(file)) return;
+
+		fs.mkdirSync(path.dirname(file), { recursive: true, mode: 0o700 });
+		const partial = `${file}${PARTIAL_SUFFIX}`;
+		writeEncryptedFile(partial, key, hash, data);
+		fs.renameSync(partial, file);
+	};
+
+	/**
+	 * Reads the data stored under a content hash into one buffer of its
+	 * exact size.
+	 * @returns null if there is no such file or it cannot be decrypted
+	 */
+	const read = (hash: string): Buffer | null => {
+		const key = deps.getKey();
+		try {
+			const file = toPath(toAttachmentFileName(key, hash));
+			const size = fs.statSync(file).size - IV_BYTES - TAG_BYTES;
+			const data = Buffer.alloc(Math.max(size, 0));
+			readEncryptedFile(file, key, hash, (chunk, offset) => {
+				chunk.copy(data, offset);
+			});
+			return data;
+		} catch (error) {
+			console.error(`Failed to read attachment ${hash}:`, error);
+			return null;
+		}
+	};
+
+	/**
+	 * Decrypts the data stored under a content hash into a plain file,
+	 * chunk by chunk, e.g. for a thumbnailer that reads from disk. The
+	 * file only appears once the data is verified.
+	 * @returns false if there is no such file or it cannot be decrypted
+	 */
+	const copyTo = (hash: string, filePath: string): boolean => {
+		const key = deps.getKey();
+		const partial = `${filePath}${PARTIAL_SUFFIX}`;
+		try {
+			const fd = fs.openSync(partial, "w", 0o600);
+			try {
+				readEncryptedFile(
+					toPath(toAttachmentFileName(key, hash)),
+					key,
+					hash,
+					(chunk) => {
+						fs.writeSync(fd, chunk);
+					},
+				);
+			} finally {
+				fs.closeSync(fd);
+			}
+			fs.renameSync(partial, filePath);
+			return true;
+		} catch (error) {
+			fs.rmSync(partial, { force: true });
+			console.error(`Failed to copy attachment ${hash}:`, error);
+			return false;
+		}
+	};
+
+	/**
+	 * Deletes every file not holding one of `hashes`, including files left
+	 * half-written or encrypted with another database's key.
+	 * @returns number of files deleted
+	 */
+	const sweep = (hashes: Iterable<string>): number => {
+		const key = deps.getKey();
+		const keep = new Set(
+			[...hashes].map((hash) => toAttachmentFileName(key, hash)),
+		);
+		const directory = deps.getDirectory();
+		if (!fs.existsSync(directory)) return 0;
+
+		let deleted = 0;
+		for (const shard of fs.readdirSync(directory)) {
+			const shardPath = path.join(directory, shard);
+			if (!fs.statSync(shardPath).isDirectory()) continue;
+			for (const name of fs.readdirSync(shardPath)) {
+				if (keep.has(name)) continue;
+				fs.rmSync(path.join(shardPath, name), { force: true });
+				deleted += 1;
+			}
+		}
+		return deleted;
+	};
+
+	return { write, read, copyTo, sweep, getKey: deps.getKey };
+};
+
+export type AttachmentStore = ReturnType<typeof createAttachmentStore>;
@@ -0,0 +1,177 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	type BackupSettings,
+	createBackupModule,
+	decryptBackup,
+	encryptBackup,
+	formatBackupName,
+	getBackupSettingsPath,
+	isBackupDue,
+	parseBackupSettings,
+	selectExpiredBackups,
+} from "./backup.js";
+
+const settings = (overrides: Partial<BackupSettings> = {}): BackupSettings => ({
+	enabled: true,
+	directory: path.resolve("backups"),
+	passphrase: "correct horse",
+	intervalHours: 24,
+	keep: 7,
+	lastBackupAt: null,
+	...overrides,
+});
+
+describe("parseBackupSettings", () => {
+	it("applies valid updates and keeps missing keys", () => {
+		const current = settings({ enabled: false });
+		expect(
+			parseBackupSettings({ keep: 3, intervalHours: 6 }, current),
+		).toEqual({ ...current, keep: 3, intervalHours: 6 });
+	});
+
+	it("rejects invalid values", () => {
+		const current = settings({ enabled: false });
+		const cases: Array<[unknown, string]> = [
+			[null, "expected an object"],
+			[{ directory: "backups" }, "directory must be an absolute path"],
+			[{ passphrase: "short" }, "passphrase must be at least 8"],
+			[{ intervalHours: 0 }, "intervalHours must be an integer"],
+			[{ keep: 101 }, "keep must be an integer"],
+		];
+		for (const [input, message] of cases) {
+			expect(() => parseBackupSettings(input, current)).toThrow(message);
+		}
+	});
+
+	it("requires a folder and passphrase to enable backups", () => {
+		expect(() =>
+			parseBackupSettings({ enabled: true }, settings({ passphrase: "" })),
+		).toThrow("directory and passphrase are required");
+	});
+});
+
+describe("selectExpiredBackups", () => {
+	it("keeps the newest backups and ignores other files", () => {
+		const names = [
+			formatBackupName(new Date("2024-01-02T00:00:00Z")),
+			"notes.txt",
+			formatBackupName(new Date("2024-01-03T00:00:00Z")),
+			formatBackupName(new Date("2024-01-01T00:00:00Z")),
+		];
+		expect(names[0]).toBe("clipman-backup-20240102-000000.cmbak");
+		expect(selectExpiredBackups(names, 1)).toEqual([
+			"clipman-backup-20240102-000000.cmbak",
+			"clipman-backup-20240101-000000.cmbak",
+		]);
+	});
+});
+
+describe("isBackupDue", () => {
+	const now = Date.parse("2024-01-02T12:00:00Z");
+
+	it("is due once the interval has passed since the last backup", () => {
+		expect(isBackupDue(settings(), now)).toBe(true);
+		const last = settings({ lastBackupAt: "2024-01-02T00:00:00Z" });
+		expect(isBackupDue(last, now)).toBe(false);
+		expect(isBackupDue({ ...last, intervalHours: 12 }, now)).toBe(true);
+	});
+
+	it("waits after a failure and while disabled", () => {
+		expect(isBackupDue(settings(), now, now - 60_000)).toBe(false);
+		expect(isBackupDue(settings({ enabled: false }), now)).toBe(false);
+	});
+});
+
+describe("encryptBackup", () => {
+	it("round-trips with the passphrase only", () => {
+		const data = encryptBackup("correct horse", Buffer.from("history"));
+		expect(decryptBackup("correct horse", data).toString()).toBe("history");
+		expect(() => decryptBackup("wrong horse", data)).toThrow(
+			"wrong passphrase",
+		);
+		expect(() => decryptBackup("correct horse", Buffer.from("x"))).toThrow(
+			"not a backup file",
+		);
+	});
+});
+
+describe("createBackupModule", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		vi.useRealTimers();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-backup-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	it("writes rotated backups that restore to the snapshot", async () => {
+		const userDataPath = createTempDir();
+		const directory = path.join(createTempDir(), "backups");
+		const replaceDatabase = vi.fn();
+		const backup = createBackupModule({
+			userDataPath,
+			snapshot: () => Buffer.from("database image"),
+			replaceDatabase,
+		});
+		backup.updateSettings({ directory, passphrase: "correct horse", keep: 2 });
+
+		vi.useFakeTimers({ toFake: ["Date"] });
+		const written: string[] = [];
+		for (const day of ["01", "02", "03"]) {
+			vi.setSystemTime(new Date(`2024-01-${day}T00:00:00Z`));
+			written.push(await backup.backupNow());
+		}
+
+		expect(fs.readdirSync(directory).sort()).toEqual(
+			written.slice(1).map((file) => path.basename(file)),
+		);
+		expect(backup.getStatus()).toMatchObject({
+			hasPassphrase: true,
+			lastBackupAt: "2024-01-03T00:00:00.000Z",
+			lastError: null,
+		});
+		expect(backup.getStatus()).not.toHaveProperty("passphrase");
+
+		await backup.restoreBackup(written[2]);
+		expect(replaceDatabase).toHaveBeenCalledWith(Buffer.from("database image"));
+		await expect(
+			backup.restoreBackup(written[2], "wrong horse"),
+		).rejects.toThrow("wrong passphrase");
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"keeps the settings file private to the user",
+		() => {
+			const userDataPath = createTempDir();
+			createBackupModule({
+				userDataPath,
+				snapshot: () => Buffer.alloc(0),
+				replaceDatabase: () => {},
+			}).updateSettings({ passphrase: "correct horse" });
+
+			const { mode } = fs.statSync(getBackupSettingsPath(userDataPath));
+			expect(mode & 0o777).toBe(0o600);
+		},
+	);
+
+	it("requires a folder and passphrase to back up", async () => {
+		const backup = createBackupModule({
+			userDataPath: createTempDir(),
+			snapshot: () => Buffer.alloc(0),
+			replaceDatabase: () => {},
+		});
+		await expect(backup.backupNow()).rejects.toThrow(
+			"backup folder and passphrase are required",
+		);
+	});
+});
@@ -0,0 +1,476 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+import { promisify } from "node:util";
+import zlib from "node:zlib";
+
+/**
+ * Persisted backup settings.
+ */
+export type BackupSettings = {
+	/** Write backups on a schedule; off until the user opts in */
+	enabled: boolean;
+	/** Absolute path of the folder backups are written to */
+	directory: string;
+	/** Encrypts every backup; needed to restore one */
+	passphrase: string;
+	intervalHours: number;
+	/** Backups kept in the folder; older ones are deleted */
+	keep: number;
+	/** When the last backup was written (ISO time) */
+	lastBackupAt: string | null;
+};
+
+/**
+ * Backup settings without the passphrase, plus what backup is doing.
+ */
+export type BackupStatus = Omit<BackupSettings, "passphrase"> & {
+	hasPassphrase: boolean;
+	/** A backup or restore is in progress */
+	running: boolean;
+	/** Why the last backup failed, or null if it succeeded */
+	lastError: string | null;
+};
+
+const MIN_PASSPHRASE_LENGTH = 8;
+const MIN_INTERVAL_HOURS = 1;
+const MAX_INTERVAL_HOURS = 7 * 24;
+const MIN_KEEP = 1;
+const MAX_KEEP = 100;
+
+const BACKUP_SETTINGS_FILENAME = "backup.json";
+
+/**
+ * Names of backup files; UTC timestamps, so names sort by age.
+ */
+const BACKUP_FILE_PATTERN = /^clipman-backup-\d{8}-\d{6}\.cmbak$/;
+
+/**
+ * How often the scheduler checks whether a backup is due (ms).
+ */
+const DEFAULT_CHECK_INTERVAL_MS = 10 * 60 * 1000;
+
+/**
+ * Wait after a failed scheduled backup before trying again (ms).
+ */
+const RETRY_DELAY_MS = 60 * 60 * 1000;
+
+/**
+ * Start of every backup file: format name and version.
+ */
+const BACKUP_MAGIC = Buffer.from("CMB1", "latin1");
+const SALT_BYTES = 16;
+const IV_BYTES = 12;
+const TAG_BYTES = 16;
+const KEY_BYTES = 32;
+
+/**
+ * scrypt cost for the passphrase; a backup may be copied anywhere, so
+ * each offline guess should be expensive.
+ */
+const SCRYPT_OPTIONS = { N: 2 ** 15, r: 8, p: 1, maxmem: 64 * 1024 * 1024 };
+
+const gzip = promisify(zlib.gzip);
+const gunzip = promisify(zlib.gunzip);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid backup settings: ${message}`);
+
+const isIntegerInRange = (value: unknown, min: number, max: number) =>
+	typeof value === "number" &&
+	Number.isInteger(value) &&
+	value >= min &&
+	value <= max;
+
+/**
+ * Validates a backup settings update. `lastBackupAt` cannot be changed
+ * this way.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value is invalid, or if scheduled backups would be enabled
+ *   without a folder and passphrase
+ */
+export const parseBackupSettings = (
+	input: unknown,
+	current: BackupSettings,
+): BackupSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const values = input as Partial<Record<keyof BackupSettings, unknown>>;
+	const next: BackupSettings = { ...current };
+
+	if (values.enabled !== undefined) {
+		if (typeof values.enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = values.enabled;
+	}
+
+	if (values.directory !== undefined) {
+		const directory =
+			typeof values.directory === "string" ? values.directory.trim() : null;
+		if (directory === null || (directory && !path.isAbsolute(directory))) {
+			throw invalid("directory must be an absolute path");
+		}
+		next.directory = directory;
+	}
+
+	if (values.passphrase !== undefined) {
+		if (
+			typeof values.passphrase !== "string" ||
+			values.passphrase.length < MIN_PASSPHRASE_LENGTH
+		) {
+			throw invalid(
+				`passphrase must be at least ${MIN_PASSPHRASE_LENGTH} characters`,
+			);
+		}
+		next.passphrase = values.passphrase;
+	}
+
+	if (values.intervalHours !== undefined) {
+		if (
+			!isIntegerInRange(
+				values.intervalHours,
+				MIN_INTERVAL_HOURS,
+				MAX_INTERVAL_HOURS,
+			)
+		) {
+			throw invalid(
+				`intervalHours must be an integer from ${MIN_INTERVAL_HOURS} to ${MAX_INTERVAL_HOURS}`,
+			);
+		}
+		next.intervalHours = values.intervalHours as number;
+	}
+
+	if (values.keep !== undefined) {
+		if (!isIntegerInRange(values.keep, MIN_KEEP, MAX_KEEP)) {
+			throw invalid(`keep must be an integer from ${MIN_KEEP} to ${MAX_KEEP}`);
+		}
+		next.keep = values.keep as number;
+	}
+
+	if (next.enabled && (!next.directory || !next.passphrase)) {
+		throw invalid("directory and passphrase are required to enable backups");
+	}
+
+	return next;
+};
+
+/**
+ * File name of a backup written at `date`.
+ * Pure function.
+ */
+export const formatBackupName = (date: Date): string => {
+	const [day, time] = date
+		.toISOString()
+		.slice(0, 19)
+		.replace(/[-:]/g, "")
+		.split("T");
+	return `clipman-backup-${day}-${time}.cmbak`;
+};
+
+/**
+ * Picks the backups that rotation deletes: all but the newest `keep`.
+ * Other files in the folder are never selected.
+ * Pure function.
+ */
+export const selectExpiredBackups = (
+	names: readonly string[],
+	keep: number,
+): string[] =>
+	names
+		.filter((name) => BACKUP_FILE_PATTERN.test(name))
+		.sort()
+		.reverse()
+		.slice(keep);
+
+/**
+ * Decides whether a scheduled backup is due.
+ * Pure function.
+ *
+ * @param lastAttemptAt - Time of the last failed attempt (ms), if any
+ */
+export const isBackupDue = (
+	settings: BackupSettings,
+	now: number,
+	lastAttemptAt: number | null = null,
+): boolean => {
+	if (!settings.enabled || !settings.directory || !settings.passphrase) {
+		return false;
+	}
+	if (lastAttemptAt !== null && now - lastAttemptAt < RETRY_DELAY_MS) {
+		return false;
+	}
+	if (settings.lastBackupAt === null) return true;
+	const elapsed = now - Date.parse(settings.lastBackupAt);
+	return elapsed >= settings.intervalHours * 60 * 60 * 1000;
+};
+
+const deriveBackupKey = (passphrase: string, salt: Buffer): Buffer =>
+	crypto.scryptSync(passphrase, salt, KEY_BYTES, SCRYPT_OPTIONS);
+
+/**
+ * Encrypts backup data with AES-256-GCM under a key derived from the
+ * passphrase and a random salt, both stored in the header.
+ */
+export const encryptBackup = (
+	passphrase: string,
+	plaintext: Buffer,
+): Buffer => {
+	const salt = crypto.randomBytes(SALT_BYTES);
+	const iv = crypto.randomBytes(IV_BYTES);
+	const cipher = crypto.createCipheriv(
+		"aes-256-gcm",
+		deriveBackupKey(passphrase, salt),
+		iv,
+	);
+	cipher.setAAD(Buffer.concat([BACKUP_MAGIC, salt]));
+	const ciphertext = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+	return Buffer.concat([
+		BACKUP_MAGIC,
+		salt,
+		iv,
+		cipher.getAuthTag(),
+		ciphertext,
+	]);
+};
+
+/**
+ * Decrypts a file written by `encryptBackup`.
+ *
+ * @throws if it is not a backup, the passphrase is wrong or the file was
+ *   changed
+ */
+export const decryptBackup = (passphrase: string, data: Buffer): Buffer => {
+	const saltEnd = BACKUP_MAGIC.length + SALT_BYTES;
+	const headerLength = saltEnd + IV_BYTES + TAG_BYTES;
+	if (
+		data.length < headerLength ||
+		!data.subarray(0, BACKUP_MAGIC.length).equals(BACKUP_MAGIC)
+	) {
+		throw new Error("Invalid backup: not a backup file");
+	}
+	const salt = data.subarray(BACKUP_MAGIC.length, saltEnd);
+	const iv = data.subarray(saltEnd, saltEnd + IV_BYTES);
+	const tag = data.subarray(saltEnd + IV_BYTES, headerLength);
+	try {
+		const decipher = crypto.createDecipheriv(
+			"aes-256-gcm",
+			deriveBackupKey(passphrase, salt),
+			iv,
+		);
+		decipher.setAAD(data.subarray(0, saltEnd));
+		decipher.setAuthTag(tag);
+		return Buffer.concat([
+			decipher.update(data.subarray(headerLength)),
+			decipher.final(),
+		]);
+	} catch {
+		throw new Error("Invalid backup: wrong passphrase or corrupted file");
+	}
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getBackupSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, BACKUP_SETTINGS_FILENAME);
+
+const writeBackupSettingsToFile = (
+	filePath: string,
+	settings: BackupSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readBackupSettingsFromFile = (filePath: string): BackupSettings => {
+	const defaults: BackupSettings = {
+		enabled: false,
+		directory: "",
+		passphrase: "",
+		intervalHours: 24,
+		keep: 7,
+		lastBackupAt: null,
+	};
+	if (!fs.existsSync(filePath)) return defaults;
+
+	try {
+		const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		return {
+			...parseBackupSettings(raw, defaults),
+			lastBackupAt:
+				typeof raw.lastBackupAt === "string" &&
+				!Number.isNaN(Date.parse(raw.lastBackupAt))
+					? raw.lastBackupAt
+					: null,
+		};
+	} catch (error) {
+		console.error("Failed to read backup settings, using defaults:", error);
+		return defaults;
+	}
+};
+
+// ============================================================================
+// Backup Module
+// ============================================================================
+
+export type BackupModuleDeps = {
+	/** Where the settings are persisted */
+	userDataPath: string;
+	/** Returns a consistent copy of the database file */
+	snapshot: () => Buffer;
+	/**
+	 * Replaces the open database with a restored copy.
+	 * @throws if the data is not a history database
+	 */
+	replaceDatabase: (data: Buffer) => void;
+	/** Interval between schedule checks in milliseconds */
+	checkIntervalMs?: number;
+};
+
+/**
+ * Creates the backup module. Backups are compressed, encrypted snapshots
+ * of the database written to a folder the user picks; the settings file
+ * is readable by the current user only, since it holds the passphrase.
+ */
+export const createBackupModule = (deps: BackupModuleDeps) => {
+	const filePath = getBackupSettingsPath(deps.userDataPath);
+	const checkIntervalMs = deps.checkIntervalMs ?? DEFAULT_CHECK_INTERVAL_MS;
+	let settings = readBackupSettingsFromFile(filePath);
+	let running = false;
+	let lastError: string | null = null;
+	let lastFailedAt: number | null = null;
+	let timer: NodeJS.Timeout | null = null;
+
+	const getStatus = (): BackupStatus => {
+		const { passphrase, ...rest } = settings;
+		return {
+			...rest,
+			hasPassphrase: passphrase.length > 0,
+			running,
+			lastError,
+		};
+	};
+
+	const save = (next: BackupSettings): void => {
+		writeBackupSettingsToFile(filePath, next);
+		settings = next;
+	};
+
+	const updateSettings = (input: unknown): BackupStatus => {
+		save(parseBackupSettings(input, settings));
+		return getStatus();
+	};
+
+	/**
+	 * Writes a backup, then deletes the oldest beyond `keep`. The file is
+	 * written under a temporary name first, so an interrupted backup
+	 * never replaces a good one.
+	 * @returns path of the new backup
+	 * @throws if no folder or passphrase is set, or writing fails
+	 */
+	const backupNow = async (): Promise<string> => {
+		const { directory, passphrase, keep } = settings;
+		if (!directory || !passphrase) {
+			throw new Error("A backup folder and passphrase are required");
+		}
+		if (running) throw new Error("A backup is already in progress");
+
+		running = true;
+		const target = path.join(directory, formatBackupName(new Date()));
+		const partial = `${target}.partial`;
+		try {
+			const data = encryptBackup(passphrase, await gzip(deps.snapshot()));
+			await fs.promises.mkdir(directory, { recursive: true });
+			await fs.promises.writeFile(partial, data, { mode: 0o600 });
+			await fs.promises.rename(partial, target);
+
+			const names = await fs.promises.readdir(directory);
+			for (const name of selectExpiredBackups(names, keep)) {
+				await fs.promises.rm(path.join(directory, name), { force: true });
+			}
+			save({ ...settings, lastBackupAt: new Date().toISOString() });
+			lastError = null;
+			lastFailedAt = null;
+			return target;
+		} catch (error) {
+			await fs.promises.rm(partial, { force: true });
+			lastError = error instanceof Error ? error.message : String(error);
+			lastFailedAt = Date.now();
+			throw error;
+		} finally {
+			running = false;
+		}
+	};
+
+	/**
+	 * Replaces history with a backup. The saved passphrase is used unless
+	 * another is given, as when restoring on a new device.
+	 * @throws if the path is not absolute, the passphrase is wrong, or the
+	 *   file is not a backup
+	 */
+	const restoreBackup = async (
+		backupPath: unknown,
+		passphrase: unknown = settings.passphrase,
+	): Promise<void> => {
+		if (typeof backupPath !== "string" || !path.isAbsolute(backupPath)) {
+			throw new Error("Invalid backup: path must be absolute");
+		}
+		if (typeof passphrase !== "string" || !passphrase) {
+			throw new Error("Invalid backup: a passphrase is required");
+		}
+		if (running) throw new Error("A backup is already in progress");
+
+		running = true;
+		try {
+			const data = await fs.promises.readFile(backupPath);
+			deps.replaceDatabase(await gunzip(decryptBackup(passphrase, data)));
+		} finally {
+			running = false;
+		}
+	};
+
+	const tick = (): void => {
+		if (running || !isBackupDue(settings, Date.now(), lastFailedAt)) return;
+		backupNow().catch((error) => {
+			console.error("Scheduled backup failed:", error);
+		});
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		timer = setInterval(tick, checkIntervalMs);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	return {
+		getStatus,
+		updateSettings,
+		backupNow,
+		restoreBackup,
+		start,
+		stop,
+	};
+};
+
+export type BackupModule = ReturnType<typeof createBackupModule>;
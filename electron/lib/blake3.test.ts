@@ -0,0 +1,53 @@
+import { describe, expect, it } from "vitest";
+import { createBlake3Hash } from "./blake3.js";
+
+/**
+ * Input of the official test vectors: bytes counting 0 to 250, repeated.
+ */
+const testInput = (length: number) =>
+	Uint8Array.from({ length }, (_, index) => index % 251);
+
+const blake3Hex = (data: Uint8Array) => {
+	const hash = createBlake3Hash();
+	hash.update(data);
+	return hash.digest().toString("hex");
+};
+
+describe("createBlake3Hash", () => {
+	it("matches the official test vectors", () => {
+		const vectors: Array<[number, string]> = [
+			[0, "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"],
+			[1, "2d3adedff11b61f14c886e35afa036736dcd87a74d27b5c1510225d0f592e213"],
+			[
+				1023,
+				"10108970eeda3eb932baac1428c7a2163b0e924c9a9e25b35bba72b28f70bd11",
+			],
+			[
+				1024,
+				"42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+			],
+			[
+				1025,
+				"d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+			],
+			[
+				102400,
+				"bc3e3d41a1146b069abffad3c0d44860cf664390afce4d9661f7902e7943e085",
+			],
+		];
+
+		for (const [length, expected] of vectors) {
+			expect(blake3Hex(testInput(length))).toBe(expected);
+		}
+	});
+
+	it("gives the same digest however the input is split", () => {
+		const data = testInput(5000);
+		const hash = createBlake3Hash();
+		for (let offset = 0; offset < data.length; offset += 7) {
+			hash.update(data.subarray(offset, offset + 7));
+		}
+
+		expect(hash.digest().toString("hex")).toBe(blake3Hex(data));
+	});
+});
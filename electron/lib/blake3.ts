@@ -0,0 +1,222 @@
+/**
+ * BLAKE3 hashing (default 32-byte output, unkeyed), following the
+ * reference implementation. Node's crypto module has no BLAKE3.
+ */
+
+const IV = [
+	0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c,
+	0x1f83d9ab, 0x5be0cd19,
+];
+
+const MESSAGE_PERMUTATION = [
+	2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8,
+];
+
+const BLOCK_LENGTH = 64;
+const CHUNK_LENGTH = 1024;
+
+const CHUNK_START = 1;
+const CHUNK_END = 2;
+const PARENT = 4;
+const ROOT = 8;
+
+const rotateRight = (word: number, bits: number): number =>
+	(word >>> bits) | (word << (32 - bits));
+
+/**
+ * The quarter-round mixing function, on `state` in place.
+ */
+const mix = (
+	state: Uint32Array,
+	a: number,
+	b: number,
+	c: number,
+	d: number,
+	x: number,
+	y: number,
+) => {
+	state[a] = state[a] + state[b] + x;
+	state[d] = rotateRight(state[d] ^ state[a], 16);
+	state[c] = state[c] + state[d];
+	state[b] = rotateRight(state[b] ^ state[c], 12);
+	state[a] = state[a] + state[b] + y;
+	state[d] = rotateRight(state[d] ^ state[a], 8);
+	state[c] = state[c] + state[d];
+	state[b] = rotateRight(state[b] ^ state[c], 7);
+};
+
+/**
+ * Compresses one 64-byte block into a 16-word state. The first 8 words
+ * are the chaining value.
+ */
+const compress = (
+	chainingValue: Uint32Array,
+	blockWords: Uint32Array,
+	counter: number,
+	blockLength: number,
+	flags: number,
+): Uint32Array => {
+	const state = new Uint32Array(16);
+	state.set(chainingValue, 0);
+	state.set(IV.slice(0, 4), 8);
+	state[12] = counter >>> 0;
+	state[13] = Math.floor(counter / 0x1_0000_0000);
+	state[14] = blockLength;
+	state[15] = flags;
+
+	let message = blockWords;
+	for (let round = 0; round < 7; round++) {
+		const m = message;
+		mix(state, 0, 4, 8, 12, m[0], m[1]);
+		mix(state, 1, 5, 9, 13, m[2], m[3]);
+		mix(state, 2, 6, 10, 14, m[4], m[5]);
+		mix(state, 3, 7, 11, 15, m[6], m[7]);
+		mix(state, 0, 5, 10, 15, m[8], m[9]);
+		mix(state, 1, 6, 11, 12, m[10], m[11]);
+		mix(state, 2, 7, 8, 13, m[12], m[13]);
+		mix(state, 3, 4, 9, 14, m[14], m[15]);
+		message = Uint32Array.from(MESSAGE_PERMUTATION, (index) => m[index]);
+	}
+
+	for (let i = 0; i < 8; i++) {
+		state[i] ^= state[i + 8];
+		state[i + 8] ^= chainingValue[i];
+	}
+	return state;
+};
+
+const toWords = (block: Uint8Array): Uint32Array => {
+	const words = new Uint32Array(16);
+	const view = new DataView(block.buffer, block.byteOffset, block.byteLength);
+	for (let i = 0; i < 16; i++) {
+		words[i] = view.getUint32(i * 4, true);
+	}
+	return words;
+};
+
+/**
+ * The last compression of a chunk or parent, deferred until it is known
+ * whether it is the root.
+ */
+type Output = {
+	chainingValue: Uint32Array;
+	blockWords: Uint32Array;
+	counter: number;
+	blockLength: number;
+	flags: number;
+};
+
+const chainingValueOf = (output: Output): Uint32Array =>
+	compress(
+		output.chainingValue,
+		output.blockWords,
+		output.counter,
+		output.blockLength,
+		output.flags,
+	).slice(0, 8);
+
+const parentOutput = (left: Uint32Array, right: Uint32Array): Output => {
+	const blockWords = new Uint32Array(16);
+	blockWords.set(left, 0);
+	blockWords.set(right, 8);
+	return {
+		chainingValue: Uint32Array.from(IV),
+		blockWords,
+		counter: 0,
+		blockLength: BLOCK_LENGTH,
+		flags: PARENT,
+	};
+};
+
+/**
+ * Creates an incremental BLAKE3 hash: feed it with `update`, then read
+ * the 32-byte digest once with `digest`.
+ */
+export const createBlake3Hash = () => {
+	// Chaining values of completed subtrees, merged as chunks complete
+	const stack: Uint32Array[] = [];
+	let chainingValue = Uint32Array.from(IV);
+	let chunkCounter = 0;
+	let block = new Uint8Array(BLOCK_LENGTH);
+	let blockLength = 0;
+	let blocksCompressed = 0;
+
+	const startFlag = () => (blocksCompressed === 0 ? CHUNK_START : 0);
+	const chunkLength = () => blocksCompressed * BLOCK_LENGTH + blockLength;
+
+	const chunkOutput = (): Output => ({
+		chainingValue,
+		blockWords: toWords(block),
+		counter: chunkCounter,
+		blockLength,
+		flags: startFlag() | CHUNK_END,
+	});
+
+	const addChunkChainingValue = (value: Uint32Array, totalChunks: number) => {
+		let merged = value;
+		let chunks = totalChunks;
+		while ((chunks & 1) === 0) {
+			const left = stack.pop();
+			if (!left) break;
+			merged = chainingValueOf(parentOutput(left, merged));
+			chunks = Math.floor(chunks / 2);
+		}
+		stack.push(merged);
+	};
+
+	const update = (data: Uint8Array) => {
+		let offset = 0;
+		while (offset < data.length) {
+			if (chunkLength() === CHUNK_LENGTH) {
+				const totalChunks = chunkCounter + 1;
+				addChunkChainingValue(chainingValueOf(chunkOutput()), totalChunks);
+				chainingValue = Uint32Array.from(IV);
+				chunkCounter = totalChunks;
+				block = new Uint8Array(BLOCK_LENGTH);
+				blockLength = 0;
+				blocksCompressed = 0;
+			}
+			if (blockLength === BLOCK_LENGTH) {
+				chainingValue = compress(
+					chainingValue,
+					toWords(block),
+					chunkCounter,
+					BLOCK_LENGTH,
+					startFlag(),
+				).slice(0, 8);
+				blocksCompressed++;
+				block = new Uint8Array(BLOCK_LENGTH);
+				blockLength = 0;
+			}
+			const take = Math.min(
+				BLOCK_LENGTH - blockLength,
+				CHUNK_LENGTH - chunkLength(),
+				data.length - offset,
+			);
+			block.set(data.subarray(offset, offset + take), blockLength);
+			blockLength += take;
+			offset += take;
+		}
+	};
+
+	const digest = (): Buffer => {
+		let output = chunkOutput();
+		for (let i = stack.length - 1; i >= 0; i--) {
+			output = parentOutput(stack[i], chainingValueOf(output));
+		}
+		const words = compress(
+			output.chainingValue,
+			output.blockWords,
+			0,
+			output.blockLength,
+			output.flags | ROOT,
+		);
+		const bytes = Buffer.alloc(32);
+		for (let i = 0; i < 8; i++) {
+			bytes.writeUInt32LE(words[i], i * 4);
+		}
+		return bytes;
+	};
+
+	return { update, digest };
+};
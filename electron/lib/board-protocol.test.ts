@@ -0,0 +1,155 @@
+import { describe, expect, it } from "vitest";
+import {
+	type BoardItem,
+	decryptBoardItem,
+	deriveBoardKey,
+	encryptBoardItem,
+	formatBoardInvite,
+	generateBoardId,
+	generateBoardToken,
+	hashBoardToken,
+	isBoardTokenMatch,
+	isValidBoardId,
+	MAX_BOARD_ITEM_CHARS,
+	parseBoardInvite,
+	parseBoardItem,
+} from "./board-protocol.js";
+
+const boardId = "0123456789abcdef";
+
+const item: BoardItem = {
+	content: "kubectl get pods -n staging",
+	note: "Staging pods",
+	publishedBy: "laptop",
+	publishedAt: "2024-01-01T00:00:00.000Z",
+};
+
+describe("board ids and tokens", () => {
+	it("generates random hex ids and tokens", () => {
+		const id = generateBoardId();
+		expect(isValidBoardId(id)).toBe(true);
+		expect(generateBoardId()).not.toBe(id);
+		expect(generateBoardToken()).toMatch(/^[0-9a-f]{48}$/);
+	});
+
+	it("rejects malformed ids", () => {
+		expect(isValidBoardId("0123456789ABCDEF")).toBe(false);
+		expect(isValidBoardId("0123")).toBe(false);
+		expect(isValidBoardId(7)).toBe(false);
+	});
+
+	it("matches a token only against its own hash", () => {
+		const token = generateBoardToken();
+		const tokenHash = hashBoardToken(token);
+
+		expect(tokenHash).not.toContain(token);
+		expect(isBoardTokenMatch(token, tokenHash)).toBe(true);
+		expect(isBoardTokenMatch(generateBoardToken(), tokenHash)).toBe(false);
+		expect(isBoardTokenMatch(null, tokenHash)).toBe(false);
+		expect(isBoardTokenMatch(token, "")).toBe(false);
+	});
+});
+
+describe("parseBoardInvite", () => {
+	it("reads an invite made by formatBoardInvite", () => {
+		const invite = { url: "http://10.0.0.5:47815", boardId, token: "abc" };
+		const text = formatBoardInvite(invite);
+
+		expect(text).toMatch(/^clipman-board:[\w-]+$/);
+		expect(parseBoardInvite(`  ${text}\n`)).toEqual(invite);
+	});
+
+	it("leaves out anything but the address, board and token", () => {
+		const text = formatBoardInvite({
+			url: "http://10.0.0.5:47815",
+			boardId,
+			token: "abc",
+			passphrase: "secret passphrase",
+		} as Parameters<typeof formatBoardInvite>[0]);
+
+		expect(Buffer.from(text.slice(14), "base64url").toString()).not.toContain(
+			"secret",
+		);
+	});
+
+	it("strips trailing slashes from the address", () => {
+		const text = formatBoardInvite({
+			url: "https://boards.example.com/",
+			boardId,
+			token: "abc",
+		});
+		expect(parseBoardInvite(text).url).toBe("https://boards.example.com");
+	});
+
+	it("rejects anything else", () => {
+		const encode = (value: unknown) =>
+			`clipman-board:${Buffer.from(JSON.stringify(value)).toString(
+				"base64url",
+			)}`;
+
+		expect(() => parseBoardInvite("hello")).toThrow("Invalid board invite");
+		expect(() => parseBoardInvite("clipman-board:!!")).toThrow(
+			"Invalid board invite",
+		);
+		expect(() =>
+			parseBoardInvite(encode({ url: "ftp://x", boardId, token: "a" })),
+		).toThrow("Invalid board invite");
+		expect(() =>
+			parseBoardInvite(encode({ url: "http://x", boardId: "x", token: "a" })),
+		).toThrow("Invalid board invite");
+		expect(() =>
+			parseBoardInvite(encode({ url: "http://x", boardId, token: "" })),
+		).toThrow("Invalid board invite");
+	});
+});
+
+describe("parseBoardItem", () => {
+	it("accepts a complete item", () => {
+		expect(parseBoardItem(item)).toEqual(item);
+		expect(parseBoardItem({ ...item, note: null })).toEqual({
+			...item,
+			note: null,
+		});
+	});
+
+	it("rejects missing fields and text that is too long", () => {
+		expect(() => parseBoardItem(null)).toThrow("Invalid board item");
+		expect(() => parseBoardItem({ ...item, publishedBy: 1 })).toThrow(
+			"Invalid board item",
+		);
+		expect(() =>
+			parseBoardItem({
+				...item,
+				content: "a".repeat(MAX_BOARD_ITEM_CHARS + 1),
+			}),
+		).toThrow("Invalid board item");
+	});
+});
+
+describe("encryptBoardItem", () => {
+	const key = deriveBoardKey("correct horse", boardId);
+
+	it("round-trips with the board's key", () => {
+		const payload = encryptBoardItem(key, boardId, item);
+
+		expect(Buffer.from(payload, "base64").toString("latin1")).not.toContain(
+			"kubectl",
+		);
+		expect(decryptBoardItem(key, boardId, payload)).toEqual(item);
+	});
+
+	it("fails with another passphrase or board", () => {
+		const payload = encryptBoardItem(key, boardId, item);
+		const otherBoard = "fedcba9876543210";
+		const wrongKey = deriveBoardKey("wrong horse", boardId);
+
+		expect(() => decryptBoardItem(wrongKey, boardId, payload)).toThrow();
+		expect(() => decryptBoardItem(key, otherBoard, payload)).toThrow();
+	});
+
+	it("derives a different key for each board", () => {
+		expect(deriveBoardKey("correct horse", "fedcba9876543210")).not.toEqual(
+			key,
+		);
+	});
+});
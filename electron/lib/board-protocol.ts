@@ -0,0 +1,209 @@
+import crypto from "node:crypto";
+import {
+	decryptObject,
+	deriveRemoteKey,
+	encryptObject,
+} from "./remote-sync.js";
+
+/**
+ * A text item shared on a board, as its members see it. The board server
+ * only ever stores it encrypted.
+ */
+export type BoardItem = {
+	content: string;
+	note: string | null;
+	/** Device name of the member who published it */
+	publishedBy: string;
+	publishedAt: string;
+};
+
+/**
+ * An encrypted item as the board server stores and serves it; `seq`
+ * orders a board's items and lets clients ask for newer ones.
+ */
+export type BoardEntry = { seq: number; payload: string; createdAt: string };
+
+/**
+ * What a member needs to reach a board, apart from its passphrase.
+ */
+export type BoardInvite = {
+	/** Board server address, e.g. `http://10.0.0.5:47815` */
+	url: string;
+	boardId: string;
+	token: string;
+};
+
+/**
+ * Longest text that can be published (characters).
+ */
+export const MAX_BOARD_ITEM_CHARS = 256_000;
+
+const INVITE_PREFIX = "clipman-board:";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * A random id for a new board, 16 hex digits.
+ */
+export const generateBoardId = (): string =>
+	crypto.randomBytes(8).toString("hex");
+
+export const isValidBoardId = (value: unknown): value is string =>
+	typeof value === "string" && /^[0-9a-f]{16}$/.test(value);
+
+/**
+ * A random access token for a board, hex-encoded.
+ */
+export const generateBoardToken = (): string =>
+	crypto.randomBytes(24).toString("hex");
+
+/**
+ * The form a board's token is stored in on the server, so a copied
+ * settings file does not grant access.
+ * Pure function.
+ */
+export const hashBoardToken = (token: string): string =>
+	crypto.createHash("sha256").update(token, "utf-8").digest("hex");
+
+/**
+ * Compares a token from a client with a board's stored hash in constant
+ * time.
+ * Pure function.
+ */
+export const isBoardTokenMatch = (
+	token: string | null,
+	tokenHash: string,
+): boolean => {
+	if (!token || !tokenHash) return false;
+	const given = Buffer.from(hashBoardToken(token), "hex");
+	const expected = Buffer.from(tokenHash, "hex");
+	return (
+		given.length === expected.length && crypto.timingSafeEqual(given, expected)
+	);
+};
+
+/**
+ * Encodes an invite as one string to send to a new member.
+ * Pure function.
+ */
+export const formatBoardInvite = ({
+	url,
+	boardId,
+	token,
+}: BoardInvite): string =>
+	`${INVITE_PREFIX}${Buffer.from(
+		JSON.stringify({ url, boardId, token }),
+		"utf-8",
+	).toString("base64url")}`;
+
+/**
+ * Reads an invite made by {@link formatBoardInvite}.
+ * Pure function.
+ *
+ * @throws if the text is not an invite
+ */
+export const parseBoardInvite = (text: unknown): BoardInvite => {
+	const invalid = new Error("Invalid board invite");
+	if (typeof text !== "string" || !text.trim().startsWith(INVITE_PREFIX)) {
+		throw invalid;
+	}
+	let input: unknown;
+	try {
+		input = JSON.parse(
+			Buffer.from(
+				text.trim().slice(INVITE_PREFIX.length),
+				"base64url",
+			).toString("utf-8"),
+		);
+	} catch {
+		throw invalid;
+	}
+	const { url, boardId, token } = (input ?? {}) as Record<string, unknown>;
+	if (
+		typeof url !== "string" ||
+		!/^https?:\/\/[^/]/i.test(url) ||
+		!isValidBoardId(boardId) ||
+		typeof token !== "string" ||
+		token === ""
+	) {
+		throw invalid;
+	}
+	return { url: url.replace(/\/+$/, ""), boardId, token };
+};
+
+/**
+ * Validates a decrypted board item.
+ * Pure function.
+ *
+ * @throws if a field is missing or the text is too long
+ */
+export const parseBoardItem = (input: unknown): BoardItem => {
+	const { content, note, publishedBy, publishedAt } = (input ?? {}) as Record<
+		string,
+		unknown
+	>;
+	if (
+		typeof content !== "string" ||
+		content.length > MAX_BOARD_ITEM_CHARS ||
+		(note !== null && typeof note !== "string") ||
+		typeof publishedBy !== "string" ||
+		typeof publishedAt !== "string"
+	) {
+		throw new Error("Invalid board item");
+	}
+	return { content, note, publishedBy, publishedAt };
+};
+
+/**
+ * Derives a board's key from its passphrase; the board id is the salt,
+ * so one passphrase gives each board a different key.
+ */
+export const deriveBoardKey = (passphrase: string, boardId: string): Buffer =>
+	deriveRemoteKey(
+		passphrase,
+		crypto
+			.createHash("sha256")
+			.update(`${INVITE_PREFIX}${boardId}`)
+			.digest()
+			.subarray(0, 16),
+	);
+
+/**
+ * Encrypts an item for a board with AES-256-GCM, bound to the board so
+ * the server cannot move it to another.
+ *
+ * @returns the base64 payload the server stores
+ */
+export const encryptBoardItem = (
+	key: Buffer,
+	boardId: string,
+	item: BoardItem,
+): string =>
+	encryptObject(
+		key,
+		`board/${boardId}`,
+		Buffer.from(JSON.stringify(item), "utf-8"),
+	).toString("base64");
+
+/**
+ * Decrypts a payload written by {@link encryptBoardItem}.
+ *
+ * @throws if the passphrase is wrong, the payload was changed, or it
+ *   holds no valid item
+ */
+export const decryptBoardItem = (
+	key: Buffer,
+	boardId: string,
+	payload: string,
+): BoardItem =>
+	parseBoardItem(
+		JSON.parse(
+			decryptObject(
+				key,
+				`board/${boardId}`,
+				Buffer.from(payload, "base64"),
+			).toString("utf-8"),
+		),
+	);
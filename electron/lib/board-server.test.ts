@@ -0,0 +1,226 @@
+import fs from "node:fs";
+import http from "node:http";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import { hashBoardToken } from "./board-protocol.js";
+import {
+	createBoardServer,
+	matchBoardRoute,
+	parseBoardEntries,
+	parseEntriesParams,
+	parseEntryBody,
+} from "./board-server.js";
+import type { HostedBoard } from "./board-settings.js";
+
+const TOKEN = "0123456789abcdef0123456789abcdef";
+
+const board: HostedBoard = {
+	id: "0123456789abcdef",
+	name: "Team",
+	tokenHash: hashBoardToken(TOKEN),
+	createdAt: "2024-01-01T00:00:00.000Z",
+};
+
+const entriesPath = `/v1/boards/${board.id}/entries`;
+
+describe("matchBoardRoute", () => {
+	it("matches listing and adding entries", () => {
+		expect(matchBoardRoute("GET", entriesPath)).toEqual({
+			name: "listEntries",
+			boardId: board.id,
+		});
+		expect(matchBoardRoute("POST", entriesPath)).toEqual({
+			name: "addEntry",
+			boardId: board.id,
+		});
+	});
+
+	it("rejects unknown paths and wrong methods", () => {
+		expect(() => matchBoardRoute("GET", "/v1/boards/x/entries")).toThrow(
+			"Not found",
+		);
+		expect(() => matchBoardRoute("DELETE", entriesPath)).toThrow(
+			"Use GET or POST",
+		);
+	});
+});
+
+describe("parseEntriesParams", () => {
+	it("defaults to every entry without waiting", () => {
+		expect(parseEntriesParams(new URLSearchParams())).toEqual({
+			after: 0,
+			wait: false,
+		});
+		expect(parseEntriesParams(new URLSearchParams("after=4&wait=1"))).toEqual({
+			after: 4,
+			wait: true,
+		});
+	});
+
+	it("rejects an invalid sequence number", () => {
+		for (const after of ["-1", "1.5", "x"]) {
+			expect(() =>
+				parseEntriesParams(new URLSearchParams({ after })),
+			).toThrow("after must be a non-negative integer");
+		}
+	});
+});
+
+describe("parseEntryBody", () => {
+	it("accepts a base64 payload", () => {
+		expect(parseEntryBody('{"payload":"YWJj"}')).toBe("YWJj");
+	});
+
+	it("rejects anything else", () => {
+		expect(() => parseEntryBody("nope")).toThrow("Expected a JSON body");
+		expect(() => parseEntryBody("null")).toThrow("payload must be");
+		expect(() => parseEntryBody('{"payload":"not base64!"}')).toThrow(
+			"payload must be",
+		);
+	});
+});
+
+describe("parseBoardEntries", () => {
+	it("skips damaged lines", () => {
+		const entry = { seq: 1, payload: "YWJj", createdAt: "2024-01-01" };
+		expect(
+			parseBoardEntries(`${JSON.stringify(entry)}\n{"seq":\n{"seq":"2"}\n\n`),
+		).toEqual([entry]);
+	});
+});
+
+describe("createBoardServer", () => {
+	const stops: (() => Promise<void>)[] = [];
+	const tempDirs: string[] = [];
+
+	const startServer = async (boards: HostedBoard[] = [board]) => {
+		const storagePath = fs.mkdtempSync(
+			path.join(os.tmpdir(), "clipboard-board-server-"),
+		);
+		tempDirs.push(storagePath);
+		const server = createBoardServer({
+			storagePath,
+			getBoards: () => boards,
+		});
+		await server.start(0);
+		stops.push(server.stop);
+		return { server, storagePath, port: server.getPort() as number };
+	};
+
+	/**
+	 * Sends a request and resolves to the status and parsed body.
+	 */
+	const send = (
+		port: number,
+		options: { method?: string; path: string; token?: string; body?: string },
+	): Promise<{ status: number; body: unknown }> =>
+		new Promise((resolve, reject) => {
+			const request = http.request(
+				{
+					host: "127.0.0.1",
+					port,
+					method: options.method ?? "GET",
+					path: options.path,
+					headers: { Authorization: `Bearer ${options.token ?? TOKEN}` },
+				},
+				(response) => {
+					let data = "";
+					response.setEncoding("utf-8");
+					response.on("data", (chunk: string) => {
+						data += chunk;
+					});
+					response.on("end", () =>
+						resolve({
+							status: response.statusCode ?? 0,
+							body: JSON.parse(data),
+						}),
+					);
+				},
+			);
+			request.on("error", reject);
+			request.end(options.body);
+		});
+
+	afterEach(async () => {
+		for (const stop of stops.splice(0)) await stop();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("stores entries and lists those after a sequence number", async () => {
+		const { port, storagePath } = await startServer();
+
+		for (const payload of ["YQ==", "Yg=="]) {
+			await send(port, {
+				method: "POST",
+				path: entriesPath,
+				body: JSON.stringify({ payload }),
+			});
+		}
+
+		const { body } = await send(port, { path: `${entriesPath}?after=1` });
+		expect(body).toEqual({
+			entries: [{ seq: 2, payload: "Yg==", createdAt: expect.any(String) }],
+		});
+		expect(
+			parseBoardEntries(
+				fs.readFileSync(path.join(storagePath, `${board.id}.jsonl`), "utf-8"),
+			).map((entry) => entry.payload),
+		).toEqual(["YQ==", "Yg=="]);
+	});
+
+	it("answers a waiting request when an entry arrives", async () => {
+		const { port } = await startServer();
+
+		const waiting = send(port, { path: `${entriesPath}?after=0&wait=1` });
+		await new Promise((resolve) => setTimeout(resolve, 50));
+		await send(port, {
+			method: "POST",
+			path: entriesPath,
+			body: '{"payload":"YQ=="}',
+		});
+
+		await expect(waiting).resolves.toMatchObject({
+			status: 200,
+			body: { entries: [{ seq: 1, payload: "YQ==" }] },
+		});
+	});
+
+	it("rejects a wrong token and unknown boards alike", async () => {
+		const { port } = await startServer();
+
+		await expect(
+			send(port, { path: entriesPath, token: "wrong" }),
+		).resolves.toEqual({
+			status: 401,
+			body: { error: "Missing or invalid token" },
+		});
+		await expect(
+			send(port, { path: "/v1/boards/fedcba9876543210/entries" }),
+		).resolves.toEqual({
+			status: 401,
+			body: { error: "Missing or invalid token" },
+		});
+	});
+
+	it("removes a deleted board's entries", async () => {
+		const { port, server, storagePath } = await startServer();
+		await send(port, {
+			method: "POST",
+			path: entriesPath,
+			body: '{"payload":"YQ=="}',
+		});
+
+		server.removeBoard(board.id);
+
+		expect(fs.existsSync(path.join(storagePath, `${board.id}.jsonl`))).toBe(
+			false,
+		);
+		await expect(send(port, { path: entriesPath })).resolves.toEqual({
+			status: 200,
+			body: { entries: [] },
+		});
+	});
+});
@@ -0,0 +1,370 @@
+import fs from "node:fs";
+import http from "node:http";
+import path from "node:path";
+import {
+	type BoardEntry,
+	isBoardTokenMatch,
+	isValidBoardId,
+} from "./board-protocol.js";
+import type { HostedBoard } from "./board-settings.js";
+
+export type BoardServerDeps = {
+	/** Folder holding one `<board id>.jsonl` file per hosted board */
+	storagePath: string;
+	getBoards: () => HostedBoard[];
+};
+
+export type BoardRoute = {
+	name: "listEntries" | "addEntry";
+	boardId: string;
+};
+
+/**
+ * Items kept per board; older ones are dropped as new ones arrive.
+ */
+export const MAX_BOARD_ENTRIES = 500;
+
+/**
+ * How long a `wait=1` request is held open when there is nothing new.
+ */
+export const LONG_POLL_MS = 25_000;
+
+/**
+ * Enough for the longest item once encrypted and base64-encoded.
+ */
+const MAX_BODY_BYTES = 2_000_000;
+
+/**
+ * A failed request, answered with `status` and `{ error: message }`.
+ */
+type BoardServerError = Error & { status: number };
+
+const boardServerError = (status: number, message: string): BoardServerError =>
+	Object.assign(new Error(message), { status });
+
+const isBoardServerError = (error: unknown): error is BoardServerError =>
+	error instanceof Error &&
+	typeof (error as Partial<BoardServerError>).status === "number";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Maps a request to an endpoint.
+ * Pure function.
+ *
+ * @throws with status 404 for unknown paths, 405 for the wrong method
+ */
+export const matchBoardRoute = (
+	method: string,
+	pathname: string,
+): BoardRoute => {
+	const match = /^\/v1\/boards\/([^/]+)\/entries$/.exec(pathname);
+	if (!match || !isValidBoardId(match[1])) {
+		throw boardServerError(404, `Not found: ${pathname}`);
+	}
+	if (method === "GET") return { name: "listEntries", boardId: match[1] };
+	if (method === "POST") return { name: "addEntry", boardId: match[1] };
+	throw boardServerError(405, `Use GET or POST for ${pathname}`);
+};
+
+/**
+ * Reads `after` (the last sequence number a client has) and `wait` from
+ * a list query string.
+ * Pure function.
+ *
+ * @throws with status 400 if `after` is not a non-negative integer
+ */
+export const parseEntriesParams = (
+	params: URLSearchParams,
+): { after: number; wait: boolean } => {
+	const rawAfter = params.get("after") ?? "0";
+	const after = Number(rawAfter);
+	if (!/^\d+$/.test(rawAfter) || !Number.isSafeInteger(after)) {
+		throw boardServerError(400, "after must be a non-negative integer");
+	}
+	return { after, wait: params.get("wait") === "1" };
+};
+
+/**
+ * Validates a `POST` body: `{ "payload": "<base64>" }`. The server
+ * cannot read the payload, only check its form.
+ * Pure function.
+ *
+ * @throws with status 400 for anything else
+ */
+export const parseEntryBody = (body: string): string => {
+	let input: unknown;
+	try {
+		input = JSON.parse(body);
+	} catch {
+		throw boardServerError(400, "Expected a JSON body");
+	}
+	const payload = (input as { payload?: unknown } | null)?.payload;
+	if (
+		typeof payload !== "string" ||
+		payload === "" ||
+		!/^[A-Za-z0-9+/]+={0,2}$/.test(payload)
+	) {
+		throw boardServerError(400, "payload must be a base64 string");
+	}
+	return payload;
+};
+
+/**
+ * Reads a board's stored entries, skipping damaged lines.
+ * Pure function.
+ */
+export const parseBoardEntries = (data: string): BoardEntry[] =>
+	data.split("\n").flatMap((line): BoardEntry[] => {
+		if (!line.trim()) return [];
+		try {
+			const entry = JSON.parse(line);
+			return Number.isSafeInteger(entry.seq) &&
+				typeof entry.payload === "string" &&
+				typeof entry.createdAt === "string"
+				? [
+						{
+							seq: entry.seq,
+							payload: entry.payload,
+							createdAt: entry.createdAt,
+						},
+					]
+				: [];
+		} catch {
+			return [];
+		}
+	});
+
+// ============================================================================
+// Board Server
+// ============================================================================
+
+const readBody = (request: http.IncomingMessage): Promise<string> =>
+	new Promise((resolve, reject) => {
+		const chunks: Buffer[] = [];
+		let size = 0;
+		request.on("data", (chunk: Buffer) => {
+			size += chunk.length;
+			if (size > MAX_BODY_BYTES) {
+				reject(boardServerError(413, "Request body too large"));
+				// Drain the rest without buffering it
+				request.removeAllListeners("data");
+				request.resume();
+				return;
+			}
+			chunks.push(chunk);
+		});
+		request.on("end", () => resolve(Buffer.concat(chunks).toString("utf-8")));
+		request.on("error", reject);
+	});
+
+const sendJson = (
+	response: http.ServerResponse,
+	status: number,
+	body: unknown,
+): void => {
+	response.writeHead(status, {
+		"Content-Type": "application/json; charset=utf-8",
+		...(status === 401 ? { "WWW-Authenticate": "Bearer" } : {}),
+	});
+	response.end(JSON.stringify(body));
+};
+
+/**
+ * Creates the board server that hosts shared boards for other devices:
+ *
+ * - `GET /v1/boards/:id/entries?after=&wait=1` lists entries after a
+ *   sequence number, waiting up to 25 seconds for one when there are none
+ * - `POST /v1/boards/:id/entries` with `{ payload }` adds an entry
+ *
+ * Each board has its own bearer token. Entries are encrypted by members
+ * before they are sent; the server stores and serves them as they are
+ * and never holds a key, so it keeps working while the app is locked.
+ */
+export const createBoardServer = (deps: BoardServerDeps) => {
+	let server: http.Server | null = null;
+	let port: number | null = null;
+	/** Entries of each board read so far, oldest first */
+	const entries = new Map<string, BoardEntry[]>();
+	/** Requests waiting for a board's next entry */
+	const waiting = new Map<string, Set<() => void>>();
+
+	const filePathFor = (boardId: string) =>
+		path.join(deps.storagePath, `${boardId}.jsonl`);
+
+	const getEntries = (boardId: string): BoardEntry[] => {
+		let list = entries.get(boardId);
+		if (!list) {
+			const filePath = filePathFor(boardId);
+			list = fs.existsSync(filePath)
+				? parseBoardEntries(fs.readFileSync(filePath, "utf-8"))
+				: [];
+			entries.set(boardId, list);
+		}
+		return list;
+	};
+
+	/**
+	 * Appends an entry, rewriting the file without the oldest entries once
+	 * there are too many.
+	 */
+	const addEntry = (boardId: string, payload: string): BoardEntry => {
+		const list = getEntries(boardId);
+		const entry: BoardEntry = {
+			seq: (list[list.length - 1]?.seq ?? 0) + 1,
+			payload,
+			createdAt: new Date().toISOString(),
+		};
+		list.push(entry);
+		fs.mkdirSync(deps.storagePath, { recursive: true });
+		if (list.length > MAX_BOARD_ENTRIES) {
+			list.splice(0, list.length - MAX_BOARD_ENTRIES);
+			fs.writeFileSync(
+				filePathFor(boardId),
+				list.map((kept) => `${JSON.stringify(kept)}\n`).join(""),
+				{ encoding: "utf-8", mode: 0o600 },
+			);
+		} else {
+			fs.appendFileSync(filePathFor(boardId), `${JSON.stringify(entry)}\n`, {
+				encoding: "utf-8",
+				mode: 0o600,
+			});
+		}
+		const listeners = waiting.get(boardId);
+		waiting.delete(boardId);
+		for (const wake of listeners ?? []) wake();
+		return entry;
+	};
+
+	/**
+	 * Resolves once the board gets a new entry, the wait times out, the
+	 * client goes away or the server stops.
+	 */
+	const waitForEntry = (
+		boardId: string,
+		response: http.ServerResponse,
+	): Promise<void> =>
+		new Promise((resolve) => {
+			const listeners = waiting.get(boardId) ?? new Set<() => void>();
+			waiting.set(boardId, listeners);
+			const wake = () => {
+				clearTimeout(timer);
+				listeners.delete(wake);
+				response.off("close", wake);
+				resolve();
+			};
+			const timer = setTimeout(wake, LONG_POLL_MS);
+			listeners.add(wake);
+			response.once("close", wake);
+		});
+
+	const dispatch = async (
+		request: http.IncomingMessage,
+		response: http.ServerResponse,
+		url: URL,
+	): Promise<unknown> => {
+		const route = matchBoardRoute(request.method ?? "GET", url.pathname);
+		const board = deps.getBoards().find((entry) => entry.id === route.boardId);
+		const match = /^Bearer (.+)$/.exec(request.headers.authorization ?? "");
+		// Unknown boards look the same as a wrong token, so ids cannot be
+		// probed
+		if (
+			!board ||
+			!isBoardTokenMatch(match ? match[1] : null, board.tokenHash)
+		) {
+			throw boardServerError(401, "Missing or invalid token");
+		}
+
+		if (route.name === "addEntry") {
+			const payload = parseEntryBody(await readBody(request));
+			return { seq: addEntry(board.id, payload).seq };
+		}
+
+		const { after, wait } = parseEntriesParams(url.searchParams);
+		const newer = () =>
+			getEntries(board.id).filter((entry) => entry.seq > after);
+		if (wait && newer().length === 0) {
+			await waitForEntry(board.id, response);
+		}
+		return { entries: newer() };
+	};
+
+	const handleRequest = (
+		request: http.IncomingMessage,
+		response: http.ServerResponse,
+	): void => {
+		const url = new URL(request.url ?? "/", "http://localhost");
+		dispatch(request, response, url)
+			.then((result) => sendJson(response, 200, result))
+			.catch((error) => {
+				if (isBoardServerError(error)) {
+					sendJson(response, error.status, { error: error.message });
+					return;
+				}
+				console.error("Board request failed:", error);
+				sendJson(response, 500, {
+					error: error instanceof Error ? error.message : String(error),
+				});
+			});
+	};
+
+	/**
+	 * Forgets a deleted board's entries and removes its file.
+	 */
+	const removeBoard = (boardId: string): void => {
+		entries.delete(boardId);
+		fs.rmSync(filePathFor(boardId), { force: true });
+	};
+
+	const stop = (): Promise<void> =>
+		new Promise((resolve) => {
+			const current = server;
+			server = null;
+			port = null;
+			for (const listeners of waiting.values()) {
+				for (const wake of listeners) wake();
+			}
+			waiting.clear();
+			if (!current) {
+				resolve();
+				return;
+			}
+			current.close(() => resolve());
+			current.closeAllConnections();
+		});
+
+	/**
+	 * Starts listening on every interface at `port`, so other devices can
+	 * reach it, restarting if it was already running on another port.
+	 */
+	const start = async (nextPort: number): Promise<void> => {
+		if (server && port === nextPort) return;
+		await stop();
+
+		const next = http.createServer(handleRequest);
+		await new Promise<void>((resolve, reject) => {
+			next.once("error", reject);
+			next.listen(nextPort, () => {
+				next.off("error", reject);
+				resolve();
+			});
+		});
+		next.on("error", (error) => {
+			console.error("Board server error:", error);
+		});
+		server = next;
+		const address = next.address();
+		port = typeof address === "object" && address ? address.port : nextPort;
+	};
+
+	/**
+	 * Port the server is listening on, or null when stopped.
+	 */
+	const getPort = (): number | null => port;
+
+	return { start, stop, getPort, removeBoard };
+};
+
+export type BoardServer = ReturnType<typeof createBoardServer>;
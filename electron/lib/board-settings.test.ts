@@ -0,0 +1,186 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import { formatBoardInvite } from "./board-protocol.js";
+import {
+	type BoardServerSettings,
+	createBoardSettings,
+	DEFAULT_BOARD_PORT,
+	getBoardSettingsPath,
+	normalizeBoardName,
+	parseBoardServerSettings,
+	parseBoardSubscription,
+} from "./board-settings.js";
+
+const current: BoardServerSettings = {
+	enabled: false,
+	port: DEFAULT_BOARD_PORT,
+	boards: [],
+};
+
+const invite = formatBoardInvite({
+	url: "http://10.0.0.5:47815",
+	boardId: "0123456789abcdef",
+	token: "abc",
+});
+
+describe("normalizeBoardName", () => {
+	it("trims and collapses whitespace", () => {
+		expect(normalizeBoardName("  Team \n snippets ")).toBe("Team snippets");
+	});
+
+	it("rejects empty and overlong names", () => {
+		expect(() => normalizeBoardName(" ")).toThrow(
+			"name must be 1-64 characters",
+		);
+		expect(() => normalizeBoardName("a".repeat(65))).toThrow(
+			"name must be 1-64 characters",
+		);
+	});
+});
+
+describe("parseBoardServerSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(parseBoardServerSettings({}, current)).toEqual(current);
+		expect(
+			parseBoardServerSettings({ enabled: true, port: 8080 }, current),
+		).toEqual({ ...current, enabled: true, port: 8080 });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseBoardServerSettings(null, current)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseBoardServerSettings({ enabled: 1 }, current)).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parseBoardServerSettings({ port: 80 }, current)).toThrow(
+			"port must be an integer from 1024 to 65535",
+		);
+	});
+});
+
+describe("parseBoardSubscription", () => {
+	it("reads the invite and names the board after its id by default", () => {
+		expect(
+			parseBoardSubscription({ invite, passphrase: "correct horse" }),
+		).toEqual({
+			url: "http://10.0.0.5:47815",
+			boardId: "0123456789abcdef",
+			token: "abc",
+			name: "0123456789abcdef",
+			passphrase: "correct horse",
+		});
+		expect(
+			parseBoardSubscription({
+				invite,
+				passphrase: "correct horse",
+				name: " Team ",
+			}).name,
+		).toBe("Team");
+	});
+
+	it("rejects a short passphrase or a bad invite", () => {
+		expect(() =>
+			parseBoardSubscription({ invite, passphrase: "short" }),
+		).toThrow("passphrase must be at least 8 characters");
+		expect(() =>
+			parseBoardSubscription({ invite: "nope", passphrase: "correct horse" }),
+		).toThrow("Invalid board invite");
+	});
+});
+
+describe("createBoardSettings", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-boards-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("is off by default with nothing hosted or joined", () => {
+		const userDataPath = createTempUserDataPath();
+
+		expect(createBoardSettings({ userDataPath }).getSettings()).toEqual({
+			server: { enabled: false, port: DEFAULT_BOARD_PORT, boards: [] },
+			subscriptions: [],
+		});
+		expect(fs.existsSync(getBoardSettingsPath(userDataPath))).toBe(false);
+	});
+
+	it("stores a hash of each hosted board's token", () => {
+		const userDataPath = createTempUserDataPath();
+		const settings = createBoardSettings({ userDataPath });
+
+		const { board, token } = settings.createBoard(" Team ");
+		expect(board).toMatchObject({ name: "Team" });
+		expect(token).toMatch(/^[0-9a-f]{48}$/);
+		expect(
+			fs.readFileSync(getBoardSettingsPath(userDataPath), "utf-8"),
+		).not.toContain(token);
+
+		const next = settings.regenerateBoardToken(board.id);
+		expect(next).not.toBe(token);
+		expect(settings.getSettings().server.boards[0].tokenHash).not.toBe(
+			board.tokenHash,
+		);
+
+		expect(createBoardSettings({ userDataPath }).getSettings()).toEqual(
+			settings.getSettings(),
+		);
+
+		settings.deleteBoard(board.id);
+		expect(settings.getSettings().server.boards).toEqual([]);
+		expect(() => settings.deleteBoard(board.id)).toThrow("Board not found");
+	});
+
+	it("replaces an earlier subscription to the same board", () => {
+		const userDataPath = createTempUserDataPath();
+		const settings = createBoardSettings({ userDataPath });
+
+		settings.addSubscription({ invite, passphrase: "correct horse" });
+		settings.addSubscription({
+			invite,
+			passphrase: "battery staple",
+			name: "Team",
+		});
+		expect(settings.getSettings().subscriptions).toEqual([
+			expect.objectContaining({ name: "Team", passphrase: "battery staple" }),
+		]);
+
+		settings.removeSubscription("0123456789abcdef");
+		expect(settings.getSettings().subscriptions).toEqual([]);
+		expect(() => settings.removeSubscription("0123456789abcdef")).toThrow(
+			"Not a member of board",
+		);
+	});
+
+	it("falls back to defaults for an unreadable file", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getBoardSettingsPath(userDataPath), "{");
+
+		expect(createBoardSettings({ userDataPath }).getSettings()).toEqual({
+			server: { enabled: false, port: DEFAULT_BOARD_PORT, boards: [] },
+			subscriptions: [],
+		});
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"keeps the settings file private to the user",
+		() => {
+			const userDataPath = createTempUserDataPath();
+			createBoardSettings({ userDataPath }).updateServer({ enabled: true });
+
+			const { mode } = fs.statSync(getBoardSettingsPath(userDataPath));
+			expect(mode & 0o777).toBe(0o600);
+		},
+	);
+});
@@ -0,0 +1,371 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	type BoardInvite,
+	generateBoardId,
+	generateBoardToken,
+	hashBoardToken,
+	isValidBoardId,
+	parseBoardInvite,
+} from "./board-protocol.js";
+
+/**
+ * A board this device hosts in server mode.
+ */
+export type HostedBoard = {
+	id: string;
+	name: string;
+	/** SHA-256 of the board's access token, which is only shown once */
+	tokenHash: string;
+	createdAt: string;
+};
+
+export type BoardServerSettings = {
+	/** Host boards for other devices; off until the user opts in */
+	enabled: boolean;
+	port: number;
+	boards: HostedBoard[];
+};
+
+/**
+ * A board this device is a member of, on its own or another device's
+ * server.
+ */
+export type BoardSubscription = BoardInvite & {
+	name: string;
+	/** Encrypts and decrypts the board's items; never sent to the server */
+	passphrase: string;
+};
+
+/**
+ * Persisted board settings.
+ */
+export type BoardSettings = {
+	server: BoardServerSettings;
+	subscriptions: BoardSubscription[];
+};
+
+export const DEFAULT_BOARD_PORT = 47815;
+
+const MIN_PORT = 1024;
+const MAX_PORT = 65535;
+const MAX_BOARD_NAME_LENGTH = 64;
+const MIN_PASSPHRASE_LENGTH = 8;
+
+const BOARDS_FILENAME = "boards.json";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid board settings: ${message}`);
+
+/**
+ * Trims a board name and collapses its whitespace.
+ * Pure function.
+ *
+ * @throws if the name is empty or longer than 64 characters
+ */
+export const normalizeBoardName = (name: unknown): string => {
+	const normalized =
+		typeof name === "string" ? name.trim().replace(/\s+/g, " ") : "";
+	if (!normalized || normalized.length > MAX_BOARD_NAME_LENGTH) {
+		throw invalid(`name must be 1-${MAX_BOARD_NAME_LENGTH} characters`);
+	}
+	return normalized;
+};
+
+/**
+ * Validates a server settings update. Boards are added and removed on
+ * their own, not through it.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or `port` is not an integer from
+ *   1024 to 65535
+ */
+export const parseBoardServerSettings = (
+	input: unknown,
+	current: BoardServerSettings,
+): BoardServerSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+	const { enabled, port } = input as Partial<
+		Record<keyof BoardServerSettings, unknown>
+	>;
+	const next: BoardServerSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (port !== undefined) {
+		if (
+			typeof port !== "number" ||
+			!Number.isInteger(port) ||
+			port < MIN_PORT ||
+			port > MAX_PORT
+		) {
+			throw invalid(`port must be an integer from ${MIN_PORT} to ${MAX_PORT}`);
+		}
+		next.port = port;
+	}
+
+	return next;
+};
+
+/**
+ * Checks the passphrase a board's items are encrypted with.
+ * Pure function.
+ *
+ * @throws if it is shorter than eight characters
+ */
+export const parseBoardPassphrase = (passphrase: unknown): string => {
+	if (
+		typeof passphrase !== "string" ||
+		passphrase.length < MIN_PASSPHRASE_LENGTH
+	) {
+		throw invalid(
+			`passphrase must be at least ${MIN_PASSPHRASE_LENGTH} characters`,
+		);
+	}
+	return passphrase;
+};
+
+/**
+ * Validates a request to join a board: its invite, the passphrase its
+ * members share and, optionally, a local name for it.
+ * Pure function.
+ *
+ * @throws if the invite is invalid or the passphrase too short
+ */
+export const parseBoardSubscription = (input: unknown): BoardSubscription => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+	const { invite, passphrase, name } = input as Record<string, unknown>;
+	const parsed = parseBoardInvite(invite);
+	return {
+		...parsed,
+		name:
+			name === undefined || name === null
+				? parsed.boardId
+				: normalizeBoardName(name),
+		passphrase: parseBoardPassphrase(passphrase),
+	};
+};
+
+/**
+ * Reads stored hosted boards, dropping malformed entries.
+ * Pure function.
+ */
+const parseHostedBoards = (input: unknown): HostedBoard[] =>
+	Array.isArray(input)
+		? input.filter(
+				(board): board is HostedBoard =>
+					isValidBoardId(board?.id) &&
+					typeof board.name === "string" &&
+					typeof board.tokenHash === "string" &&
+					typeof board.createdAt === "string",
+			)
+		: [];
+
+/**
+ * Reads stored subscriptions, dropping malformed entries.
+ * Pure function.
+ */
+const parseSubscriptions = (input: unknown): BoardSubscription[] =>
+	Array.isArray(input)
+		? input.filter(
+				(entry): entry is BoardSubscription =>
+					isValidBoardId(entry?.boardId) &&
+					typeof entry.url === "string" &&
+					typeof entry.token === "string" &&
+					typeof entry.name === "string" &&
+					typeof entry.passphrase === "string",
+			)
+		: [];
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getBoardSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, BOARDS_FILENAME);
+
+const writeBoardSettingsToFile = (
+	filePath: string,
+	settings: BoardSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readBoardSettingsFromFile = (filePath: string): BoardSettings => {
+	const defaults: BoardSettings = {
+		server: { enabled: false, port: DEFAULT_BOARD_PORT, boards: [] },
+		subscriptions: [],
+	};
+	if (!fs.existsSync(filePath)) return defaults;
+	try {
+		const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		return {
+			server: {
+				...parseBoardServerSettings(raw.server ?? {}, defaults.server),
+				boards: parseHostedBoards(raw.server?.boards),
+			},
+			subscriptions: parseSubscriptions(raw.subscriptions),
+		};
+	} catch (error) {
+		console.error("Failed to read board settings, using defaults:", error);
+		return defaults;
+	}
+};
+
+// ============================================================================
+// Board Settings Module
+// ============================================================================
+
+/**
+ * Creates the board settings store. The file is readable by the current
+ * user only, since it holds member tokens and passphrases.
+ */
+export const createBoardSettings = (deps: { userDataPath: string }) => {
+	const filePath = getBoardSettingsPath(deps.userDataPath);
+	let settings = readBoardSettingsFromFile(filePath);
+
+	const getSettings = (): BoardSettings => ({
+		server: { ...settings.server, boards: [...settings.server.boards] },
+		subscriptions: [...settings.subscriptions],
+	});
+
+	const save = (next: BoardSettings): BoardSettings => {
+		writeBoardSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const findHostedBoard = (id: unknown): HostedBoard => {
+		const board = settings.server.boards.find((entry) => entry.id === id);
+		if (!board) {
+			throw new Error(`Board not found: ${String(id)}`);
+		}
+		return board;
+	};
+
+	const updateServer = (input: unknown): BoardSettings =>
+		save({
+			...settings,
+			server: parseBoardServerSettings(input, settings.server),
+		});
+
+	/**
+	 * Hosts a new board.
+	 *
+	 * @returns the board and its access token, which is shown only now
+	 */
+	const createBoard = (
+		name: unknown,
+	): { board: HostedBoard; token: string } => {
+		const token = generateBoardToken();
+		const board: HostedBoard = {
+			id: generateBoardId(),
+			name: normalizeBoardName(name),
+			tokenHash: hashBoardToken(token),
+			createdAt: new Date().toISOString(),
+		};
+		save({
+			...settings,
+			server: {
+				...settings.server,
+				boards: [...settings.server.boards, board],
+			},
+		});
+		return { board, token };
+	};
+
+	/**
+	 * Replaces a hosted board's token, so members using the old one are
+	 * rejected.
+	 *
+	 * @returns the new token
+	 */
+	const regenerateBoardToken = (id: unknown): string => {
+		const board = findHostedBoard(id);
+		const token = generateBoardToken();
+		save({
+			...settings,
+			server: {
+				...settings.server,
+				boards: settings.server.boards.map((entry) =>
+					entry === board
+						? { ...board, tokenHash: hashBoardToken(token) }
+						: entry,
+				),
+			},
+		});
+		return token;
+	};
+
+	const deleteBoard = (id: unknown): void => {
+		const board = findHostedBoard(id);
+		save({
+			...settings,
+			server: {
+				...settings.server,
+				boards: settings.server.boards.filter((entry) => entry !== board),
+			},
+		});
+	};
+
+	/**
+	 * Joins a board, replacing an earlier subscription to it.
+	 */
+	const addSubscription = (input: unknown): BoardSubscription => {
+		const subscription = parseBoardSubscription(input);
+		save({
+			...settings,
+			subscriptions: [
+				...settings.subscriptions.filter(
+					(entry) => entry.boardId !== subscription.boardId,
+				),
+				subscription,
+			],
+		});
+		return subscription;
+	};
+
+	const removeSubscription = (boardId: unknown): void => {
+		if (!settings.subscriptions.some((entry) => entry.boardId === boardId)) {
+			throw new Error(`Not a member of board: ${String(boardId)}`);
+		}
+		save({
+			...settings,
+			subscriptions: settings.subscriptions.filter(
+				(entry) => entry.boardId !== boardId,
+			),
+		});
+	};
+
+	return {
+		getSettings,
+		updateServer,
+		createBoard,
+		regenerateBoardToken,
+		deleteBoard,
+		addSubscription,
+		removeSubscription,
+	};
+};
+
+export type BoardSettingsStore = ReturnType<typeof createBoardSettings>;
@@ -0,0 +1,188 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	formatBoardInvite,
+	generateBoardToken,
+	hashBoardToken,
+} from "./board-protocol.js";
+import { createBoardServer } from "./board-server.js";
+import { createBoardSettings } from "./board-settings.js";
+import {
+	createBoards,
+	parseEntriesResponse,
+	pickLanAddress,
+} from "./boards.js";
+
+const address = (
+	ip: string,
+	family: "IPv4" | "IPv6",
+	internal: boolean,
+): os.NetworkInterfaceInfo =>
+	({
+		address: ip,
+		family,
+		internal,
+		netmask: "",
+		mac: "",
+		cidr: null,
+	}) as os.NetworkInterfaceInfo;
+
+describe("pickLanAddress", () => {
+	it("picks the first external IPv4 address", () => {
+		expect(
+			pickLanAddress({
+				lo: [address("127.0.0.1", "IPv4", true)],
+				en0: [
+					address("fe80::1", "IPv6", false),
+					address("192.168.1.20", "IPv4", false),
+				],
+			}),
+		).toBe("192.168.1.20");
+	});
+
+	it("returns null when offline", () => {
+		expect(pickLanAddress({ lo: [address("127.0.0.1", "IPv4", true)] })).toBe(
+			null,
+		);
+	});
+});
+
+describe("parseEntriesResponse", () => {
+	it("reads the entries", () => {
+		const entry = { seq: 1, payload: "YQ==", createdAt: "2024-01-01" };
+		expect(parseEntriesResponse({ entries: [entry] })).toEqual([entry]);
+	});
+
+	it("rejects anything else", () => {
+		expect(() => parseEntriesResponse(null)).toThrow("Unexpected reply");
+		expect(() => parseEntriesResponse({ entries: [{ seq: "1" }] })).toThrow(
+			"Unexpected reply",
+		);
+	});
+});
+
+describe("createBoards", () => {
+	const tempDirs: string[] = [];
+	const stops: (() => Promise<void>)[] = [];
+
+	const setup = (isLocked = () => false) => {
+		const userDataPath = fs.mkdtempSync(
+			path.join(os.tmpdir(), "clipboard-boards-"),
+		);
+		tempDirs.push(userDataPath);
+		const settings = createBoardSettings({ userDataPath });
+		const onUpdated = vi.fn();
+		const boards = createBoards({
+			settings,
+			storagePath: path.join(userDataPath, "boards"),
+			deviceName: "laptop",
+			getLanAddress: () => "192.168.1.20",
+			isLocked,
+			onUpdated,
+		});
+		stops.push(boards.stop);
+		return { settings, boards, onUpdated, userDataPath };
+	};
+
+	/**
+	 * Hosts a board on a server of its own, as another device would.
+	 */
+	const startRemoteBoard = async (storagePath: string) => {
+		const token = generateBoardToken();
+		const board = {
+			id: "0123456789abcdef",
+			name: "Team",
+			tokenHash: hashBoardToken(token),
+			createdAt: "2024-01-01T00:00:00.000Z",
+		};
+		const server = createBoardServer({
+			storagePath,
+			getBoards: () => [board],
+		});
+		await server.start(0);
+		stops.push(server.stop);
+		return formatBoardInvite({
+			url: `http://127.0.0.1:${server.getPort()}`,
+			boardId: board.id,
+			token,
+		});
+	};
+
+	afterEach(async () => {
+		for (const stop of stops.splice(0)) await stop();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("needs the board server running to create a board", () => {
+		const { settings, boards } = setup();
+
+		expect(() => boards.createBoard("Team", "correct horse")).toThrow(
+			"Board server is not running",
+		);
+		expect(settings.getSettings().server.boards).toEqual([]);
+	});
+
+	it("publishes encrypted items and receives them from the board", async () => {
+		const { boards, onUpdated, userDataPath } = setup();
+		const storagePath = path.join(userDataPath, "remote");
+		const invite = await startRemoteBoard(storagePath);
+
+		boards.join({ invite, passphrase: "correct horse", name: "Team" });
+		await expect(
+			boards.publish("0123456789abcdef", "kubectl get pods", "Pods"),
+		).resolves.toBe(1);
+
+		await vi.waitFor(() =>
+			expect(boards.listItems("0123456789abcdef")).toHaveLength(1),
+		);
+		expect(boards.getItem("0123456789abcdef", 1)).toMatchObject({
+			seq: 1,
+			content: "kubectl get pods",
+			note: "Pods",
+			publishedBy: "laptop",
+		});
+		expect(onUpdated).toHaveBeenCalledWith("0123456789abcdef");
+		expect(
+			fs.readFileSync(
+				path.join(storagePath, "0123456789abcdef.jsonl"),
+				"utf-8",
+			),
+		).not.toContain("kubectl");
+	});
+
+	it("reports memberships without their tokens or passphrases", async () => {
+		const { boards, userDataPath } = setup();
+		const invite = await startRemoteBoard(path.join(userDataPath, "remote"));
+
+		boards.join({ invite, passphrase: "correct horse", name: "Team" });
+
+		const status = boards.getStatus();
+		expect(status.memberships).toEqual([
+			expect.objectContaining({
+				boardId: "0123456789abcdef",
+				name: "Team",
+				itemCount: 0,
+			}),
+		]);
+		expect(JSON.stringify(status)).not.toContain("correct horse");
+		expect(JSON.stringify(status)).not.toContain(invite.slice(14));
+	});
+
+	it("refuses to publish while locked or to boards not joined", async () => {
+		const { boards, userDataPath } = setup(() => true);
+		const invite = await startRemoteBoard(path.join(userDataPath, "remote"));
+
+		await expect(
+			boards.publish("0123456789abcdef", "text", null),
+		).rejects.toThrow("Not a member of board");
+
+		boards.join({ invite, passphrase: "correct horse" });
+		await expect(
+			boards.publish("0123456789abcdef", "text", null),
+		).rejects.toThrow("History is locked");
+	});
+});
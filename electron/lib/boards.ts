@@ -0,0 +1,527 @@
+import type os from "node:os";
+import {
+	type BoardEntry,
+	type BoardItem,
+	decryptBoardItem,
+	deriveBoardKey,
+	encryptBoardItem,
+	formatBoardInvite,
+	MAX_BOARD_ITEM_CHARS,
+} from "./board-protocol.js";
+import { createBoardServer, MAX_BOARD_ENTRIES } from "./board-server.js";
+import {
+	type BoardSettingsStore,
+	type BoardSubscription,
+	parseBoardPassphrase,
+} from "./board-settings.js";
+
+export type BoardsDeps = {
+	settings: BoardSettingsStore;
+	/** Folder the board server keeps hosted boards' entries in */
+	storagePath: string;
+	/** Shown to other members as who published an item */
+	deviceName: string;
+	/** Address other devices reach this one at, or null if offline */
+	getLanAddress: () => string | null;
+	/** Boards are not polled or published to while the app is locked */
+	isLocked: () => boolean;
+	/** Called when a board gets new items */
+	onUpdated: (boardId: string) => void;
+};
+
+/**
+ * A board item with its position on the board.
+ */
+export type BoardListItem = BoardItem & { seq: number };
+
+export type BoardMembershipStatus = {
+	boardId: string;
+	name: string;
+	url: string;
+	/** The last request to the board server succeeded */
+	connected: boolean;
+	lastError: string | null;
+	itemCount: number;
+};
+
+/**
+ * Board settings without tokens or passphrases, plus what is happening
+ * right now.
+ */
+export type BoardsStatus = {
+	server: {
+		enabled: boolean;
+		port: number;
+		running: boolean;
+		boards: Array<{ id: string; name: string; createdAt: string }>;
+	};
+	memberships: BoardMembershipStatus[];
+};
+
+/**
+ * How long to wait for the board server, a little longer than it holds
+ * a waiting request.
+ */
+const REQUEST_TIMEOUT_MS = 35_000;
+
+const MIN_RETRY_MS = 5_000;
+const MAX_RETRY_MS = 5 * 60 * 1000;
+
+/**
+ * How often a paused membership checks whether the app was unlocked.
+ */
+const LOCKED_CHECK_MS = 10_000;
+
+type Membership = {
+	subscription: BoardSubscription;
+	/** Derived on first use, since it takes a moment */
+	key: Buffer | null;
+	/** Sequence number of the newest entry received */
+	after: number;
+	/** Newest first */
+	items: BoardListItem[];
+	connected: boolean;
+	lastError: string | null;
+	stopped: boolean;
+	/** Ends the current wait between requests early */
+	cancelWait: (() => void) | null;
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * The first external IPv4 address, which invites point other devices at.
+ * Pure function.
+ */
+export const pickLanAddress = (
+	interfaces: NodeJS.Dict<os.NetworkInterfaceInfo[]>,
+): string | null => {
+	for (const addresses of Object.values(interfaces)) {
+		const address = addresses?.find(
+			(entry) => entry.family === "IPv4" && !entry.internal,
+		);
+		if (address) return address.address;
+	}
+	return null;
+};
+
+/**
+ * Reads the entries in a board server's list response.
+ * Pure function.
+ *
+ * @throws if the response is not a list of entries
+ */
+export const parseEntriesResponse = (input: unknown): BoardEntry[] => {
+	const entries = (input as { entries?: unknown } | null)?.entries;
+	if (
+		!Array.isArray(entries) ||
+		!entries.every(
+			(entry) =>
+				Number.isSafeInteger(entry?.seq) &&
+				typeof entry.payload === "string" &&
+				typeof entry.createdAt === "string",
+		)
+	) {
+		throw new Error("Unexpected reply from the board server");
+	}
+	return entries;
+};
+
+// ============================================================================
+// Requests
+// ============================================================================
+
+const entriesUrl = (subscription: BoardSubscription): string =>
+	`${subscription.url}/v1/boards/${subscription.boardId}/entries`;
+
+/**
+ * Sends a request to a board server and reads its JSON reply.
+ *
+ * @throws with the server's error, or a hint when the token is refused
+ */
+const requestBoard = async (
+	subscription: BoardSubscription,
+	url: string,
+	init: RequestInit = {},
+): Promise<unknown> => {
+	const response = await fetch(url, {
+		...init,
+		headers: {
+			Authorization: `Bearer ${subscription.token}`,
+			"Content-Type": "application/json",
+		},
+		signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+	});
+	if (response.status === 401) {
+		throw new Error("The board server refused the invite's token");
+	}
+	if (!response.ok) {
+		const body = (await response.json().catch(() => null)) as {
+			error?: unknown;
+		} | null;
+		throw new Error(
+			typeof body?.error === "string"
+				? body.error
+				: `Board request failed: HTTP ${response.status}`,
+		);
+	}
+	return response.json();
+};
+
+// ============================================================================
+// Boards
+// ============================================================================
+
+/**
+ * Creates shared boards: hosts boards for other devices when server mode
+ * is on, and keeps every board this device is a member of up to date by
+ * long polling its server. Items are encrypted with the board's
+ * passphrase before they leave a member, so the server, even when it is
+ * another member's device, only ever stores ciphertext.
+ */
+export const createBoards = (deps: BoardsDeps) => {
+	const server = createBoardServer({
+		storagePath: deps.storagePath,
+		getBoards: () => deps.settings.getSettings().server.boards,
+	});
+	const memberships = new Map<string, Membership>();
+
+	const getKey = (membership: Membership): Buffer => {
+		if (!membership.key) {
+			const { passphrase, boardId } = membership.subscription;
+			membership.key = deriveBoardKey(passphrase, boardId);
+		}
+		return membership.key;
+	};
+
+	const wait = (membership: Membership, ms: number): Promise<void> =>
+		new Promise((resolve) => {
+			const timer = setTimeout(() => {
+				membership.cancelWait = null;
+				resolve();
+			}, ms);
+			membership.cancelWait = () => {
+				clearTimeout(timer);
+				membership.cancelWait = null;
+				resolve();
+			};
+		});
+
+	/**
+	 * Decrypts new entries and adds them to the membership's items.
+	 * Entries that do not decrypt are skipped, since a member with another
+	 * passphrase may have published them.
+	 */
+	const receive = (membership: Membership, entries: BoardEntry[]): void => {
+		const { boardId } = membership.subscription;
+		const received: BoardListItem[] = [];
+		let failed = 0;
+		for (const entry of entries) {
+			membership.after = Math.max(membership.after, entry.seq);
+			try {
+				const key = getKey(membership);
+				const item = decryptBoardItem(key, boardId, entry.payload);
+				received.push({ ...item, seq: entry.seq });
+			} catch {
+				failed += 1;
+			}
+		}
+		membership.lastError =
+			failed > 0
+				? `${failed} item(s) could not be decrypted: check the passphrase`
+				: null;
+		if (received.length === 0) return;
+		membership.items = [...received.reverse(), ...membership.items].slice(
+			0,
+			MAX_BOARD_ENTRIES,
+		);
+		deps.onUpdated(boardId);
+	};
+
+	/**
+	 * Asks the board server for newer entries until the membership ends,
+	 * backing off after failures and pausing while the app is locked.
+	 */
+	const poll = async (membership: Membership): Promise<void> => {
+		const { subscription } = membership;
+		let retryMs = MIN_RETRY_MS;
+		while (!membership.stopped) {
+			if (deps.isLocked()) {
+				await wait(membership, LOCKED_CHECK_MS);
+				continue;
+			}
+			try {
+				const reply = await requestBoard(
+					subscription,
+					`${entriesUrl(subscription)}?after=${membership.after}&wait=1`,
+				);
+				if (membership.stopped) return;
+				membership.connected = true;
+				receive(membership, parseEntriesResponse(reply));
+				retryMs = MIN_RETRY_MS;
+			} catch (error) {
+				if (membership.stopped) return;
+				membership.connected = false;
+				membership.lastError =
+					error instanceof Error ? error.message : String(error);
+				await wait(membership, retryMs);
+				retryMs = Math.min(retryMs * 2, MAX_RETRY_MS);
+			}
+		}
+	};
+
+	const stopMembership = (boardId: string): void => {
+		const membership = memberships.get(boardId);
+		if (!membership) return;
+		membership.stopped = true;
+		membership.cancelWait?.();
+		memberships.delete(boardId);
+	};
+
+	const startMembership = (subscription: BoardSubscription): void => {
+		stopMembership(subscription.boardId);
+		const membership: Membership = {
+			subscription,
+			key: null,
+			after: 0,
+			items: [],
+			connected: false,
+			lastError: null,
+			stopped: false,
+			cancelWait: null,
+		};
+		memberships.set(subscription.boardId, membership);
+		void poll(membership);
+	};
+
+	const getMembership = (boardId: unknown): Membership => {
+		const membership =
+			typeof boardId === "string" ? memberships.get(boardId) : undefined;
+		if (!membership) {
+			throw new Error(`Not a member of board: ${String(boardId)}`);
+		}
+		return membership;
+	};
+
+	/**
+	 * The invite for a hosted board, pointing at this device's network
+	 * address.
+	 */
+	const toInvite = (boardId: string, token: string): string => {
+		const port = server.getPort();
+		if (port === null) {
+			throw new Error("Board server is not running");
+		}
+		const address = deps.getLanAddress() ?? "127.0.0.1";
+		return formatBoardInvite({
+			url: `http://${address}:${port}`,
+			boardId,
+			token,
+		});
+	};
+
+	/**
+	 * Starts or stops the board server and polling to match the settings.
+	 */
+	const apply = async (): Promise<void> => {
+		const settings = deps.settings.getSettings();
+		for (const boardId of [...memberships.keys()]) {
+			if (!settings.subscriptions.some((entry) => entry.boardId === boardId)) {
+				stopMembership(boardId);
+			}
+		}
+		for (const subscription of settings.subscriptions) {
+			const current = memberships.get(subscription.boardId);
+			if (current?.subscription !== subscription) {
+				startMembership(subscription);
+			}
+		}
+		if (settings.server.enabled) {
+			await server.start(settings.server.port);
+		} else {
+			await server.stop();
+		}
+	};
+
+	const stop = async (): Promise<void> => {
+		for (const boardId of [...memberships.keys()]) stopMembership(boardId);
+		await server.stop();
+	};
+
+	const getStatus = (): BoardsStatus => {
+		const settings = deps.settings.getSettings();
+		return {
+			server: {
+				enabled: settings.server.enabled,
+				port: settings.server.port,
+				running: server.getPort() !== null,
+				boards: settings.server.boards.map(({ id, name, createdAt }) => ({
+					id,
+					name,
+					createdAt,
+				})),
+			},
+			memberships: settings.subscriptions.map((subscription) => {
+				const membership = memberships.get(subscription.boardId);
+				return {
+					boardId: subscription.boardId,
+					name: subscription.name,
+					url: subscription.url,
+					connected: membership?.connected ?? false,
+					lastError: membership?.lastError ?? null,
+					itemCount: membership?.items.length ?? 0,
+				};
+			}),
+		};
+	};
+
+	/**
+	 * Hosts a new board and joins it from this device.
+	 *
+	 * @returns the invite to share with other members; it holds the
+	 *   board's token and is only shown now
+	 * @throws if the board server is not running, or the name or
+	 *   passphrase is invalid
+	 */
+	const createBoard = (name: unknown, passphrase: unknown): string => {
+		const port = server.getPort();
+		if (port === null) {
+			throw new Error("Board server is not running");
+		}
+		const checked = parseBoardPassphrase(passphrase);
+		const { board, token } = deps.settings.createBoard(name);
+		const subscription = deps.settings.addSubscription({
+			invite: formatBoardInvite({
+				url: `http://127.0.0.1:${port}`,
+				boardId: board.id,
+				token,
+			}),
+			passphrase: checked,
+			name: board.name,
+		});
+		startMembership(subscription);
+		return toInvite(board.id, token);
+	};
+
+	/**
+	 * Gives a hosted board a new token; members must join again with the
+	 * returned invite.
+	 */
+	const regenerateBoardToken = (boardId: unknown): string => {
+		const token = deps.settings.regenerateBoardToken(boardId);
+		const id = String(boardId);
+		const own = memberships.get(id)?.subscription;
+		if (own) {
+			startMembership(
+				deps.settings.addSubscription({
+					invite: formatBoardInvite({ url: own.url, boardId: id, token }),
+					passphrase: own.passphrase,
+					name: own.name,
+				}),
+			);
+		}
+		return toInvite(id, token);
+	};
+
+	/**
+	 * Joins a board from an invite and starts receiving its items.
+	 */
+	const join = (input: unknown): void => {
+		startMembership(deps.settings.addSubscription(input));
+	};
+
+	const leave = (boardId: unknown): void => {
+		deps.settings.removeSubscription(boardId);
+		stopMembership(String(boardId));
+	};
+
+	/**
+	 * Stops hosting a board and deletes its items from this device.
+	 */
+	const deleteBoard = (boardId: unknown): void => {
+		deps.settings.deleteBoard(boardId);
+		server.removeBoard(String(boardId));
+		if (memberships.has(String(boardId))) leave(boardId);
+	};
+
+	/**
+	 * Encrypts text and adds it to a board.
+	 *
+	 * @returns the item's sequence number on the board
+	 * @throws if this device is not a member, the app is locked, the text
+	 *   is too long, or the board server refuses it
+	 */
+	const publish = async (
+		boardId: unknown,
+		content: string,
+		note: string | null,
+	): Promise<number> => {
+		const membership = getMembership(boardId);
+		if (deps.isLocked()) {
+			throw new Error("History is locked");
+		}
+		if (content.length > MAX_BOARD_ITEM_CHARS) {
+			throw new Error(
+				`Item is too long to publish: more than ${MAX_BOARD_ITEM_CHARS} characters`,
+			);
+		}
+		const { subscription } = membership;
+		const payload = encryptBoardItem(
+			getKey(membership),
+			subscription.boardId,
+			{
+				content,
+				note,
+				publishedBy: deps.deviceName,
+				publishedAt: new Date().toISOString(),
+			},
+		);
+		const reply = await requestBoard(subscription, entriesUrl(subscription), {
+			method: "POST",
+			body: JSON.stringify({ payload }),
+		});
+		const seq = (reply as { seq?: unknown } | null)?.seq;
+		if (!Number.isSafeInteger(seq)) {
+			throw new Error("Unexpected reply from the board server");
+		}
+		return seq as number;
+	};
+
+	/**
+	 * Items received from a board, newest first.
+	 */
+	const listItems = (boardId: unknown): BoardListItem[] => [
+		...getMembership(boardId).items,
+	];
+
+	/**
+	 * @throws if the item is not on the board, or no longer kept
+	 */
+	const getItem = (boardId: unknown, seq: unknown): BoardListItem => {
+		const item = getMembership(boardId).items.find(
+			(entry) => entry.seq === seq,
+		);
+		if (!item) {
+			throw new Error(`Board item not found: ${String(seq)}`);
+		}
+		return item;
+	};
+
+	return {
+		apply,
+		stop,
+		getStatus,
+		createBoard,
+		regenerateBoardToken,
+		deleteBoard,
+		join,
+		leave,
+		publish,
+		listItems,
+		getItem,
+	};
+};
+
+export type Boards = ReturnType<typeof createBoards>;
@@ -0,0 +1,103 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createCaptureBatcher } from "./capture-batcher.js";
+
+describe("createCaptureBatcher", () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	it("writes a burst of captures as one batch", () => {
+		const flush = vi.fn();
+		const batcher = createCaptureBatcher<string>({ flush, flushDelayMs: 50 });
+
+		batcher.push("a");
+		vi.advanceTimersByTime(30);
+		batcher.push("b");
+		batcher.push("c");
+		expect(flush).not.toHaveBeenCalled();
+
+		// The delay runs from the first capture, not the latest
+		vi.advanceTimersByTime(20);
+		expect(flush).toHaveBeenCalledExactlyOnceWith(["a", "b", "c"]);
+		expect(batcher.getPendingCount()).toBe(0);
+	});
+
+	it("writes a full batch at once", () => {
+		const flush = vi.fn();
+		const batcher = createCaptureBatcher<number>({ flush, maxBatchSize: 2 });
+
+		batcher.push(1);
+		batcher.push(2);
+		batcher.push(3);
+
+		expect(flush).toHaveBeenCalledExactlyOnceWith([1, 2]);
+		vi.advanceTimersByTime(50);
+		expect(flush).toHaveBeenLastCalledWith([3]);
+	});
+
+	it("flushes on demand and keeps going after a failed write", () => {
+		const flush = vi
+			.fn()
+			.mockImplementationOnce(() => {
+				throw new Error("disk full");
+			})
+			.mockImplementation(() => {});
+		const batcher = createCaptureBatcher<string>({ flush });
+
+		batcher.push("lost");
+		batcher.flush();
+		batcher.push("kept");
+		batcher.flush();
+		batcher.flush();
+
+		expect(flush.mock.calls).toEqual([[["lost"]], [["kept"]]]);
+		vi.advanceTimersByTime(50);
+		expect(flush).toHaveBeenCalledTimes(2);
+	});
+
+	it("writes a copy reported several times once, with its newest formats", () => {
+		const flush = vi.fn();
+		const batcher = createCaptureBatcher<{ text: string; html?: string }>({
+			flush,
+			isRepeat: (previous, next) => previous.text === next.text,
+			repeatWindowMs: 500,
+		});
+
+		batcher.push({ text: "cell" });
+		batcher.push({ text: "cell", html: "<td>cell</td>" });
+		vi.advanceTimersByTime(50);
+		expect(flush).toHaveBeenCalledExactlyOnceWith([
+			{ text: "cell", html: "<td>cell</td>" },
+		]);
+
+		// Already written: later repeats in the window are dropped
+		vi.advanceTimersByTime(400);
+		batcher.push({ text: "cell" });
+		vi.advanceTimersByTime(400);
+		batcher.push({ text: "cell" });
+		vi.advanceTimersByTime(50);
+		expect(flush).toHaveBeenCalledTimes(1);
+	});
+
+	it("records the same content again after the repeat window", () => {
+		const flush = vi.fn();
+		const batcher = createCaptureBatcher<string>({
+			flush,
+			isRepeat: (previous, next) => previous === next,
+			repeatWindowMs: 500,
+		});
+
+		batcher.push("a");
+		batcher.push("b");
+		batcher.push("a");
+		vi.advanceTimersByTime(600);
+		batcher.push("a");
+		vi.advanceTimersByTime(50);
+
+		expect(flush.mock.calls).toEqual([[["a", "b", "a"]], [["a"]]]);
+	});
+});
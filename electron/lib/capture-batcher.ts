@@ -0,0 +1,100 @@
+/**
+ * How long the first capture of a burst waits for more before the batch
+ * is written (ms). Short enough that a single copy still shows at once.
+ */
+const DEFAULT_FLUSH_DELAY_MS = 50;
+
+/**
+ * Captures written in one transaction at most; a full batch is written
+ * without waiting for the delay.
+ */
+const DEFAULT_MAX_BATCH_SIZE = 100;
+
+/**
+ * How soon after a capture the same content again counts as a repeat of
+ * it (ms). Excel and some terminals update the clipboard several times
+ * for one copy, a few milliseconds apart.
+ */
+const DEFAULT_REPEAT_WINDOW_MS = 500;
+
+export type CaptureBatcherDeps<T> = {
+	/** Writes a batch, oldest capture first */
+	flush: (batch: T[]) => void;
+	/** Whether `next` holds the same content as `previous` */
+	isRepeat?: (previous: T, next: T) => boolean;
+	flushDelayMs?: number;
+	maxBatchSize?: number;
+	repeatWindowMs?: number;
+};
+
+/**
+ * Creates the buffer between clipboard capture and the database.
+ * Copies arriving in a burst (a scripted `xclip` loop, a macro) are
+ * collected and handed to `flush` together, so they cost one transaction
+ * and one history refresh instead of one each. The delay starts at the
+ * first capture of a batch and is not extended by later ones, so a
+ * steady stream still gets written every interval.
+ *
+ * A capture repeating the previous one within the repeat window is one
+ * copy reported several times: it replaces the previous capture while
+ * that is pending, so the newest formats are written once, and is
+ * dropped once that was written. Each repeat extends the window.
+ */
+export const createCaptureBatcher = <T>(deps: CaptureBatcherDeps<T>) => {
+	const flushDelayMs = deps.flushDelayMs ?? DEFAULT_FLUSH_DELAY_MS;
+	const maxBatchSize = deps.maxBatchSize ?? DEFAULT_MAX_BATCH_SIZE;
+	const repeatWindowMs = deps.repeatWindowMs ?? DEFAULT_REPEAT_WINDOW_MS;
+	let pending: T[] = [];
+	let timer: NodeJS.Timeout | null = null;
+	/** The latest capture pushed, written or not, and when it arrived */
+	let last: { capture: T; at: number } | null = null;
+
+	/**
+	 * Writes everything pending now, e.g. before quitting.
+	 */
+	const flush = (): void => {
+		if (timer) {
+			clearTimeout(timer);
+			timer = null;
+		}
+		if (pending.length === 0) return;
+		const batch = pending;
+		pending = [];
+		try {
+			deps.flush(batch);
+		} catch (error) {
+			console.error("Failed to record captured clips:", error);
+		}
+	};
+
+	/**
+	 * Folds a repeat of the latest capture into it.
+	 * @returns false if `capture` is not a repeat
+	 */
+	const mergeRepeat = (capture: T, now: number): boolean => {
+		if (!last || !deps.isRepeat || now - last.at > repeatWindowMs) {
+			return false;
+		}
+		if (!deps.isRepeat(last.capture, capture)) return false;
+		const index = pending.lastIndexOf(last.capture);
+		if (index !== -1) pending[index] = capture;
+		last = { capture, at: now };
+		return true;
+	};
+
+	const push = (capture: T): void => {
+		const now = Date.now();
+		if (mergeRepeat(capture, now)) return;
+		last = { capture, at: now };
+		pending.push(capture);
+		if (pending.length >= maxBatchSize) {
+			flush();
+		} else if (!timer) {
+			timer = setTimeout(flush, flushDelayMs);
+		}
+	};
+
+	const getPendingCount = (): number => pending.length;
+
+	return { push, flush, getPendingCount };
+};
@@ -0,0 +1,119 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	applyCaptureLimits,
+	type CaptureLimitSettings,
+	createCaptureLimits,
+	getCaptureLimitsPath,
+	parseCaptureLimitSettings,
+} from "./capture-limits.js";
+
+const limits = (
+	policy: CaptureLimitSettings["text"]["policy"],
+): CaptureLimitSettings => ({
+	text: { maxBytes: 1024, policy },
+	richText: { maxBytes: 2048, policy: "drop" },
+	image: { maxBytes: 4096 },
+});
+
+describe("parseCaptureLimitSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(
+			parseCaptureLimitSettings(
+				{ text: { policy: "skip" }, image: { maxBytes: 8192 } },
+				limits("truncate"),
+			),
+		).toEqual({
+			text: { maxBytes: 1024, policy: "skip" },
+			richText: { maxBytes: 2048, policy: "drop" },
+			image: { maxBytes: 8192 },
+		});
+	});
+
+	it("rejects invalid limits and policies", () => {
+		expect(() => parseCaptureLimitSettings(null)).toThrow("expected an object");
+		expect(() => parseCaptureLimitSettings({ text: 5 })).toThrow(
+			"text must be an object",
+		);
+		for (const maxBytes of [100, 1.5, 2_000_000, "1024"]) {
+			expect(() => parseCaptureLimitSettings({ text: { maxBytes } })).toThrow(
+				"text.maxBytes must be an integer",
+			);
+		}
+		expect(() =>
+			parseCaptureLimitSettings({ richText: { policy: "truncate" } }),
+		).toThrow("richText.policy must be one of skip, drop");
+	});
+});
+
+describe("applyCaptureLimits", () => {
+	const huge = "é".repeat(5000);
+
+	it("leaves copies within the limits alone", () => {
+		const clip = { text: "hello", html: "<b>hello</b>" };
+		expect(applyCaptureLimits(clip, limits("skip"))).toEqual(clip);
+	});
+
+	it("skips, truncates or keeps the full text as an attachment", () => {
+		expect(applyCaptureLimits({ text: huge }, limits("skip"))).toBeNull();
+
+		const truncated = applyCaptureLimits(
+			{ text: huge, html: "<p>x</p>" },
+			limits("truncate"),
+		);
+		expect(truncated?.text).toMatch(/^é+\n\n\[Truncated: 9\.8 KB copied/);
+		expect(Buffer.byteLength(truncated?.text ?? "")).toBeLessThanOrEqual(1024);
+		expect(truncated?.html).toBeUndefined();
+		expect(truncated?.fullText).toBeUndefined();
+
+		const preview = applyCaptureLimits({ text: huge }, limits("attachment"));
+		expect(preview?.text).toContain("the full 9.8 KB is kept as an attachment");
+		expect(preview?.fullText).toBe(huge);
+	});
+
+	it("drops or skips rich formats over their limit", () => {
+		const clip = { text: "x", rtf: "r".repeat(3000), html: "<i>x</i>" };
+		expect(applyCaptureLimits(clip, limits("skip"))).toEqual({
+			text: "x",
+			rtf: undefined,
+			html: "<i>x</i>",
+		});
+
+		const settings = limits("skip");
+		settings.richText.policy = "skip";
+		expect(applyCaptureLimits(clip, settings)).toBeNull();
+	});
+});
+
+describe("createCaptureLimits", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("persists updates and applies them to copies", () => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-limits-"));
+		tempDirs.push(dir);
+		const captureLimits = createCaptureLimits({ userDataPath: dir });
+		expect(captureLimits.allowsImage(10 * 1024 * 1024)).toBe(true);
+
+		captureLimits.updateSettings({
+			text: { maxBytes: 1024, policy: "skip" },
+			image: { maxBytes: 4096 },
+		});
+
+		expect(captureLimits.limitText({ text: "x".repeat(2000) })).toBeNull();
+		expect(captureLimits.allowsImage(5000)).toBe(false);
+		const saved = JSON.parse(
+			fs.readFileSync(getCaptureLimitsPath(dir), "utf-8"),
+		);
+		expect(createCaptureLimits({ userDataPath: dir }).getSettings()).toEqual(
+			saved,
+		);
+	});
+});
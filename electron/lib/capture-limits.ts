@@ -0,0 +1,353 @@
+import fs from "node:fs";
+import path from "node:path";
+import { MAX_CLIP_CHARS } from "./history-repository.js";
+
+/**
+ * What happens to text over its size limit: dropped, cut down to the
+ * limit with a marker, or cut down with the full text kept as an
+ * attachment that is restored when the item is copied again.
+ */
+export type TextLimitPolicy = "skip" | "truncate" | "attachment";
+
+/**
+ * What happens to an HTML or RTF version over its size limit: the whole
+ * copy is dropped, or only that format is, keeping the plain text.
+ */
+export type RichTextLimitPolicy = "skip" | "drop";
+
+/**
+ * Persisted capture size limits, per content type.
+ */
+export type CaptureLimitSettings = {
+	text: { maxBytes: number; policy: TextLimitPolicy };
+	richText: { maxBytes: number; policy: RichTextLimitPolicy };
+	/** Larger images are not recorded */
+	image: { maxBytes: number };
+};
+
+/**
+ * The text formats of a copy, before or after its limits are applied.
+ */
+export type LimitedClip = {
+	text: string;
+	rtf?: string;
+	html?: string;
+	/** The complete text when `text` is a preview, kept as an attachment */
+	fullText?: string;
+};
+
+const TEXT_POLICIES: readonly TextLimitPolicy[] = [
+	"skip",
+	"truncate",
+	"attachment",
+];
+
+const RICH_TEXT_POLICIES: readonly RichTextLimitPolicy[] = ["skip", "drop"];
+
+/**
+ * Smallest accepted limit (bytes), leaving room for the marker.
+ */
+const MIN_LIMIT_BYTES = 1024;
+
+/**
+ * Largest accepted text and rich text limit (bytes); the history
+ * database refuses longer content.
+ */
+const MAX_TEXT_LIMIT_BYTES = MAX_CLIP_CHARS;
+
+/**
+ * Largest accepted image limit (bytes).
+ */
+const MAX_IMAGE_LIMIT_BYTES = 50 * 1024 * 1024;
+
+/**
+ * Longest text kept as an attachment (bytes); anything longer is
+ * truncated instead, as writing it would stall the app.
+ */
+const MAX_TEXT_ATTACHMENT_BYTES = 256 * 1024 * 1024;
+
+const DEFAULT_CAPTURE_LIMIT_SETTINGS: CaptureLimitSettings = {
+	text: { maxBytes: MAX_TEXT_LIMIT_BYTES, policy: "attachment" },
+	richText: { maxBytes: MAX_TEXT_LIMIT_BYTES, policy: "drop" },
+	image: { maxBytes: MAX_IMAGE_LIMIT_BYTES },
+};
+
+const CAPTURE_LIMITS_FILENAME = "capture-limits.json";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const cloneSettings = (
+	settings: CaptureLimitSettings,
+): CaptureLimitSettings => ({
+	text: { ...settings.text },
+	richText: { ...settings.richText },
+	image: { ...settings.image },
+});
+
+const parseMaxBytes = (
+	value: unknown,
+	type: keyof CaptureLimitSettings,
+	max: number,
+): number => {
+	if (
+		typeof value !== "number" ||
+		!Number.isInteger(value) ||
+		value < MIN_LIMIT_BYTES ||
+		value > max
+	) {
+		throw new Error(
+			`Invalid capture limits: ${type}.maxBytes must be an integer from ${MIN_LIMIT_BYTES} to ${max}`,
+		);
+	}
+	return value;
+};
+
+const parsePolicy = <T extends string>(
+	value: unknown,
+	type: keyof CaptureLimitSettings,
+	policies: readonly T[],
+): T => {
+	if (!policies.includes(value as T)) {
+		throw new Error(
+			`Invalid capture limits: ${type}.policy must be one of ${policies.join(", ")}`,
+		);
+	}
+	return value as T;
+};
+
+const toSection = (
+	value: unknown,
+	type: keyof CaptureLimitSettings,
+): Record<string, unknown> => {
+	if (typeof value !== "object" || value === null) {
+		throw new Error(`Invalid capture limits: ${type} must be an object`);
+	}
+	return value as Record<string, unknown>;
+};
+
+/**
+ * Validates a capture limits update.
+ * Pure function. Missing types and keys keep their current value.
+ *
+ * @throws if a limit is out of range or a policy is unknown
+ */
+export const parseCaptureLimitSettings = (
+	input: unknown,
+	current: CaptureLimitSettings = DEFAULT_CAPTURE_LIMIT_SETTINGS,
+): CaptureLimitSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid capture limits: expected an object");
+	}
+
+	const { text, richText, image } = input as Partial<
+		Record<keyof CaptureLimitSettings, unknown>
+	>;
+	const next = cloneSettings(current);
+
+	if (text !== undefined) {
+		const section = toSection(text, "text");
+		if (section.maxBytes !== undefined) {
+			next.text.maxBytes = parseMaxBytes(
+				section.maxBytes,
+				"text",
+				MAX_TEXT_LIMIT_BYTES,
+			);
+		}
+		if (section.policy !== undefined) {
+			next.text.policy = parsePolicy(section.policy, "text", TEXT_POLICIES);
+		}
+	}
+
+	if (richText !== undefined) {
+		const section = toSection(richText, "richText");
+		if (section.maxBytes !== undefined) {
+			next.richText.maxBytes = parseMaxBytes(
+				section.maxBytes,
+				"richText",
+				MAX_TEXT_LIMIT_BYTES,
+			);
+		}
+		if (section.policy !== undefined) {
+			next.richText.policy = parsePolicy(
+				section.policy,
+				"richText",
+				RICH_TEXT_POLICIES,
+			);
+		}
+	}
+
+	if (image !== undefined) {
+		const section = toSection(image, "image");
+		if (section.maxBytes !== undefined) {
+			next.image.maxBytes = parseMaxBytes(
+				section.maxBytes,
+				"image",
+				MAX_IMAGE_LIMIT_BYTES,
+			);
+		}
+	}
+
+	return next;
+};
+
+/**
+ * Size in bytes as shown in markers, e.g. "300.0 MB".
+ * Pure function.
+ */
+const formatByteSize = (bytes: number): string => {
+	if (bytes < 1024) return `${bytes} B`;
+	if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+	return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+};
+
+/**
+ * Checks whether text takes more than `limit` bytes as UTF-8, without
+ * encoding a huge copy: every UTF-16 unit takes at least one byte.
+ * Pure function.
+ */
+const exceedsBytes = (text: string, limit: number): boolean =>
+	text.length > limit || Buffer.byteLength(text, "utf8") > limit;
+
+/**
+ * Cuts text to at most `maxBytes` of UTF-8 without splitting a
+ * character.
+ * Pure function.
+ */
+const truncateToBytes = (text: string, maxBytes: number): string => {
+	let slice = text.slice(0, maxBytes);
+	// Half of a surrogate pair would be stored as a replacement character
+	const last = slice.charCodeAt(slice.length - 1);
+	if (last >= 0xd800 && last <= 0xdbff) slice = slice.slice(0, -1);
+	const encoded = Buffer.from(slice, "utf8");
+	if (encoded.length <= maxBytes) return slice;
+	let end = maxBytes;
+	// Back up to the first byte of the character the cut falls in
+	while (end > 0 && (encoded[end] & 0xc0) === 0x80) end--;
+	return encoded.subarray(0, end).toString("utf8");
+};
+
+/**
+ * Applies the size limits to the text formats of a copy.
+ * Pure function. Text over its limit is cut down to it, marker
+ * included, and its rich formats are dropped since they no longer match
+ * it. Rich formats are also dropped when all formats together exceed
+ * what the history database accepts.
+ *
+ * @returns the formats to record, or null if the copy is skipped
+ */
+export const applyCaptureLimits = (
+	clip: LimitedClip,
+	settings: CaptureLimitSettings,
+): LimitedClip | null => {
+	const { maxBytes, policy } = settings.text;
+	let { text, rtf, html } = clip;
+	let fullText: string | undefined;
+
+	if (exceedsBytes(text, maxBytes)) {
+		if (policy === "skip") return null;
+		const totalBytes = Buffer.byteLength(text, "utf8");
+		const keepFull =
+			policy === "attachment" && totalBytes <= MAX_TEXT_ATTACHMENT_BYTES;
+		const marker = keepFull
+			? `\n\n[Preview: the full ${formatByteSize(totalBytes)} is kept as an attachment]`
+			: `\n\n[Truncated: ${formatByteSize(totalBytes)} copied, the first ${formatByteSize(maxBytes)} kept]`;
+		if (keepFull) fullText = text;
+		const budget = maxBytes - Buffer.byteLength(marker, "utf8");
+		text = `${truncateToBytes(text, budget)}${marker}`;
+		rtf = undefined;
+		html = undefined;
+	}
+
+	for (const format of ["rtf", "html"] as const) {
+		const value = format === "rtf" ? rtf : html;
+		if (!value || !exceedsBytes(value, settings.richText.maxBytes)) continue;
+		if (settings.richText.policy === "skip") return null;
+		if (format === "rtf") rtf = undefined;
+		else html = undefined;
+	}
+
+	if (text.length + (rtf?.length ?? 0) + (html?.length ?? 0) > MAX_CLIP_CHARS) {
+		rtf = undefined;
+		html = undefined;
+	}
+
+	return { text, rtf, html, ...(fullText !== undefined && { fullText }) };
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getCaptureLimitsPath = (userDataPath: string) =>
+	path.join(userDataPath, CAPTURE_LIMITS_FILENAME);
+
+/**
+ * Reads saved limits, falling back to defaults if missing or invalid.
+ */
+const readCaptureLimitsFromFile = (filePath: string): CaptureLimitSettings => {
+	if (!fs.existsSync(filePath)) {
+		return cloneSettings(DEFAULT_CAPTURE_LIMIT_SETTINGS);
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseCaptureLimitSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read capture limits, using defaults:", error);
+		return cloneSettings(DEFAULT_CAPTURE_LIMIT_SETTINGS);
+	}
+};
+
+const writeCaptureLimitsToFile = (
+	filePath: string,
+	settings: CaptureLimitSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Capture Limits Module
+// ============================================================================
+
+export type CaptureLimitsDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the size limits applied to every copy before it is recorded,
+ * so a copy of a huge file's text does not stall the capture rules,
+ * search index and history list.
+ */
+export const createCaptureLimits = (deps: CaptureLimitsDeps) => {
+	const filePath = getCaptureLimitsPath(deps.userDataPath);
+	let settings = readCaptureLimitsFromFile(filePath);
+
+	const getSettings = (): CaptureLimitSettings => cloneSettings(settings);
+
+	const updateSettings = (input: unknown): CaptureLimitSettings => {
+		const next = parseCaptureLimitSettings(input, settings);
+		writeCaptureLimitsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Applies the text and rich text limits to a copy.
+	 * @returns the formats to record, or null if the copy is skipped
+	 */
+	const limitText = (clip: LimitedClip): LimitedClip | null =>
+		applyCaptureLimits(clip, settings);
+
+	/**
+	 * Checks whether an encoded image is within the image limit.
+	 */
+	const allowsImage = (bytes: number): boolean =>
+		bytes <= settings.image.maxBytes;
+
+	return { getSettings, updateSettings, limitText, allowsImage };
+};
+
+export type CaptureLimits = ReturnType<typeof createCaptureLimits>;
@@ -0,0 +1,79 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createCapturePause } from "./capture-pause.js";
+
+describe("createCapturePause", () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	it("starts capturing", () => {
+		expect(createCapturePause().getStatus()).toEqual({
+			paused: false,
+			resumesAt: null,
+		});
+	});
+
+	it("notifies only when the state changes", () => {
+		const onChange = vi.fn();
+		const capture = createCapturePause({ onChange });
+
+		expect(capture.setPaused(true).paused).toBe(true);
+		capture.setPaused(true);
+		expect(capture.toggle().paused).toBe(false);
+
+		expect(onChange.mock.calls.map(([status]) => status.paused)).toEqual([
+			true,
+			false,
+		]);
+	});
+
+	it("rejects non-boolean states", () => {
+		const capture = createCapturePause();
+
+		expect(() => capture.setPaused("yes")).toThrow("expected a boolean");
+		expect(capture.isPaused()).toBe(false);
+	});
+
+	it("resumes capture when a snooze ends", () => {
+		const onChange = vi.fn();
+		const capture = createCapturePause({ onChange, now: () => 1_000 });
+
+		expect(capture.snooze(15)).toEqual({
+			paused: true,
+			resumesAt: 1_000 + 15 * 60 * 1000,
+		});
+
+		vi.advanceTimersByTime(15 * 60 * 1000 - 1);
+		expect(capture.isPaused()).toBe(true);
+		vi.advanceTimersByTime(1);
+
+		expect(capture.getStatus()).toEqual({ paused: false, resumesAt: null });
+		expect(onChange).toHaveBeenLastCalledWith({
+			paused: false,
+			resumesAt: null,
+		});
+	});
+
+	it("cancels the snooze when capture is set directly", () => {
+		const capture = createCapturePause();
+		capture.snooze(30);
+
+		expect(capture.setPaused(true)).toEqual({ paused: true, resumesAt: null });
+		vi.advanceTimersByTime(30 * 60 * 1000);
+
+		expect(capture.isPaused()).toBe(true);
+	});
+
+	it("rejects invalid snooze durations", () => {
+		const capture = createCapturePause();
+
+		for (const minutes of [0, 1.5, 1441, "15"]) {
+			expect(() => capture.snooze(minutes)).toThrow("minutes must be 1-1440");
+		}
+		expect(capture.isPaused()).toBe(false);
+	});
+});
@@ -0,0 +1,107 @@
+/**
+ * Capture pause state reported to the renderer.
+ */
+export type CapturePauseStatus = {
+	/** While true, clipboard changes are not recorded */
+	paused: boolean;
+	/** When a snooze ends and capture resumes (epoch ms), or null */
+	resumesAt: number | null;
+};
+
+export type CapturePauseDeps = {
+	/** Called whenever capture is paused or resumed */
+	onChange?: (status: CapturePauseStatus) => void;
+	now?: () => number;
+};
+
+/**
+ * Snooze durations offered in the tray (minutes).
+ */
+export const SNOOZE_PRESETS_MINUTES = [15, 30, 60] as const;
+
+/**
+ * Longest accepted snooze (one day).
+ */
+const MAX_SNOOZE_MINUTES = 24 * 60;
+
+/**
+ * Creates the capture pause (incognito) switch.
+ * While paused the clipboard watcher keeps running, so resuming does not
+ * record whatever was copied in the meantime. A snooze pauses capture and
+ * resumes it automatically when its timer fires. The state is not
+ * persisted: capture always resumes when the app restarts.
+ */
+export const createCapturePause = (deps: CapturePauseDeps = {}) => {
+	const now = deps.now ?? Date.now;
+	let paused = false;
+	let resumesAt: number | null = null;
+	let timer: NodeJS.Timeout | null = null;
+
+	const isPaused = (): boolean => paused;
+
+	const getStatus = (): CapturePauseStatus => ({ paused, resumesAt });
+
+	const clearSnooze = (): void => {
+		if (timer) {
+			clearTimeout(timer);
+			timer = null;
+		}
+		resumesAt = null;
+	};
+
+	/**
+	 * Pauses or resumes indefinitely, cancelling any snooze.
+	 * @throws if `value` is not a boolean
+	 */
+	const setPaused = (value: unknown): CapturePauseStatus => {
+		if (typeof value !== "boolean") {
+			throw new Error("Invalid capture pause state: expected a boolean");
+		}
+		const changed = paused !== value || resumesAt !== null;
+		clearSnooze();
+		paused = value;
+		if (changed) deps.onChange?.(getStatus());
+		return getStatus();
+	};
+
+	const toggle = (): CapturePauseStatus => setPaused(!paused);
+
+	/**
+	 * Pauses capture for `minutes`, replacing any earlier snooze.
+	 * @throws if `minutes` is not an integer from 1 to 1440
+	 */
+	const snooze = (minutes: unknown): CapturePauseStatus => {
+		if (
+			typeof minutes !== "number" ||
+			!Number.isInteger(minutes) ||
+			minutes < 1 ||
+			minutes > MAX_SNOOZE_MINUTES
+		) {
+			throw new Error(
+				`Invalid snooze duration: minutes must be 1-${MAX_SNOOZE_MINUTES}`,
+			);
+		}
+
+		clearSnooze();
+		const delayMs = minutes * 60 * 1000;
+		paused = true;
+		resumesAt = now() + delayMs;
+		timer = setTimeout(() => {
+			timer = null;
+			resumesAt = null;
+			paused = false;
+			deps.onChange?.(getStatus());
+		}, delayMs);
+		deps.onChange?.(getStatus());
+		return getStatus();
+	};
+
+	/**
+	 * Cancels a pending snooze timer (on quit).
+	 */
+	const stop = (): void => clearSnooze();
+
+	return { isPaused, getStatus, setPaused, toggle, snooze, stop };
+};
+
+export type CapturePause = ReturnType<typeof createCapturePause>;
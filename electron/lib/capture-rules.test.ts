@@ -0,0 +1,235 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	type CaptureRule,
+	createCaptureRules,
+	evaluateCaptureRules,
+	getCaptureRulesPath,
+	parseCaptureRuleSettings,
+} from "./capture-rules.js";
+
+const rule = (overrides: Partial<CaptureRule>): CaptureRule => ({
+	name: "Rule",
+	enabled: true,
+	priority: 0,
+	conditions: { type: "text" },
+	actions: [{ type: "notify" }],
+	...overrides,
+});
+
+describe("parseCaptureRuleSettings", () => {
+	it("fills in defaults and normalizes tags", () => {
+		expect(
+			parseCaptureRuleSettings({
+				rules: [
+					{
+						name: " Tickets ",
+						conditions: { pattern: "JIRA-\\d+" },
+						actions: [{ type: "tag", tag: " Work " }],
+					},
+				],
+			}),
+		).toEqual({
+			rules: [
+				{
+					name: "Tickets",
+					enabled: true,
+					priority: 0,
+					conditions: { pattern: "JIRA-\\d+" },
+					actions: [{ type: "tag", tag: "work" }],
+				},
+			],
+		});
+	});
+
+	it("keeps the current rules when none are given", () => {
+		const current = { rules: [rule({ name: "Kept" })] };
+
+		expect(parseCaptureRuleSettings({}, current)).toEqual(current);
+	});
+
+	it("rejects invalid rules", () => {
+		const parse = (value: Record<string, unknown>) =>
+			parseCaptureRuleSettings({
+				rules: [{ name: "Bad", actions: [{ type: "notify" }], ...value }],
+			});
+
+		expect(() => parse({ conditions: {} })).toThrow(
+			'Invalid capture rules: rule "Bad": at least one of pattern, type or kind is required',
+		);
+		expect(() => parse({ conditions: { pattern: "(" } })).toThrow(
+			'rule "Bad": Invalid regular expression',
+		);
+		expect(() => parse({ conditions: { kind: "music" } })).toThrow(
+			"unknown content kind",
+		);
+		expect(() =>
+			parse({ conditions: { type: "text" }, actions: [{ type: "delete" }] }),
+		).toThrow("action type must be one of");
+		expect(() =>
+			parse({
+				conditions: { type: "text" },
+				actions: [{ type: "expire", minutes: 0 }],
+			}),
+		).toThrow("expire minutes must be 1-10080");
+		expect(() =>
+			parse({
+				conditions: { type: "text" },
+				actions: [{ type: "transform", transform: "reverse" }],
+			}),
+		).toThrow("Invalid text transformation");
+	});
+});
+
+describe("evaluateCaptureRules", () => {
+	it("runs matching rules by priority and collects their actions", () => {
+		const rules = [
+			rule({
+				name: "Trim",
+				conditions: { pattern: "^\\s" },
+				actions: [{ type: "transform", transform: "trim" }],
+			}),
+			rule({
+				name: "Links",
+				priority: 10,
+				conditions: { kind: "url" },
+				actions: [
+					{ type: "tag", tag: "link" },
+					{ type: "expire", minutes: 60 },
+				],
+			}),
+			rule({
+				name: "Docs",
+				conditions: { pattern: "EXAMPLE", ignoreCase: true },
+				actions: [{ type: "expire", minutes: 5 }, { type: "notify" }],
+			}),
+		];
+
+		expect(
+			evaluateCaptureRules(
+				{ type: "text", text: "  https://example.com" },
+				rules,
+			),
+		).toEqual({
+			ignored: false,
+			text: "https://example.com",
+			tags: ["link"],
+			expireAfterMinutes: 5,
+			notify: ["Docs"],
+		});
+	});
+
+	it("lets transforms feed later rules", () => {
+		const rules = [
+			rule({
+				conditions: { pattern: "^\\s" },
+				actions: [{ type: "transform", transform: "trim" }],
+			}),
+			rule({
+				conditions: { kind: "url" },
+				actions: [{ type: "tag", tag: "link" }],
+			}),
+		];
+
+		expect(
+			evaluateCaptureRules({ type: "text", text: " https://a.io" }, rules)
+				.tags,
+		).toEqual(["link"]);
+	});
+
+	it("stops at an ignore rule", () => {
+		const rules = [
+			rule({
+				name: "Drop OTPs",
+				priority: 5,
+				conditions: { pattern: "^\\d{6}$" },
+				actions: [{ type: "ignore" }],
+			}),
+			rule({ actions: [{ type: "tag", tag: "all" }] }),
+		];
+
+		expect(
+			evaluateCaptureRules({ type: "text", text: "123456" }, rules).ignored,
+		).toBe(true);
+		expect(
+			evaluateCaptureRules({ type: "text", text: "hello" }, rules),
+		).toMatchObject({ ignored: false, tags: ["all"] });
+	});
+
+	it("matches images and files by type only", () => {
+		const rules = [
+			rule({
+				name: "Screenshots",
+				conditions: { type: "image" },
+				actions: [{ type: "tag", tag: "screenshot" }],
+			}),
+			rule({
+				name: "Pattern",
+				conditions: { pattern: "." },
+				actions: [{ type: "ignore" }],
+			}),
+			rule({ enabled: false, conditions: { type: "image" } }),
+		];
+
+		expect(evaluateCaptureRules({ type: "image", text: "" }, rules)).toEqual({
+			ignored: false,
+			text: "",
+			tags: ["screenshot"],
+			expireAfterMinutes: null,
+			notify: [],
+		});
+	});
+
+	it("keeps the text when a transform fails", () => {
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const rules = [
+			rule({ actions: [{ type: "transform", transform: "base64Decode" }] }),
+		];
+
+		expect(
+			evaluateCaptureRules({ type: "text", text: "not base64!" }, rules).text,
+		).toBe("not base64!");
+		vi.restoreAllMocks();
+	});
+});
+
+describe("createCaptureRules", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-rules-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("persists rules across restarts", () => {
+		const userDataPath = createTempUserDataPath();
+		const rules = [rule({ name: "Saved" })];
+		createCaptureRules({ userDataPath }).updateSettings({ rules });
+
+		const second = createCaptureRules({ userDataPath });
+		expect(second.getSettings().rules).toEqual(rules);
+		expect(second.evaluate({ type: "text", text: "x" }).notify).toEqual([
+			"Saved",
+		]);
+	});
+
+	it("starts with no rules when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getCaptureRulesPath(userDataPath), "not json");
+		vi.spyOn(console, "error").mockImplementation(() => {});
+
+		expect(createCaptureRules({ userDataPath }).getSettings()).toEqual({
+			rules: [],
+		});
+		vi.restoreAllMocks();
+	});
+});
@@ -0,0 +1,456 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	type ContentKind,
+	classifyContent,
+	isContentKind,
+} from "./content-kind.js";
+import { createRegexMatcher, validateSearchPattern } from "./regex-search.js";
+import { assertValidTagName } from "./tags.js";
+import {
+	applyTextTransform,
+	parseTextTransformRequest,
+	type TextTransform,
+} from "./text-transforms.js";
+
+const ITEM_TYPES = ["text", "image", "files"] as const;
+
+export type CaptureItemType = (typeof ITEM_TYPES)[number];
+
+/**
+ * What a rule checks a capture against. Every condition given must hold;
+ * `pattern` and `kind` only match text.
+ */
+export type CaptureRuleConditions = {
+	/** Regular expression source matched against the text */
+	pattern?: string;
+	ignoreCase?: boolean;
+	type?: CaptureItemType;
+	kind?: ContentKind;
+};
+
+/**
+ * What a matching rule does with the capture.
+ */
+export type CaptureRuleAction =
+	| { type: "tag"; tag: string }
+	| { type: "ignore" }
+	| { type: "transform"; transform: TextTransform }
+	| { type: "expire"; minutes: number }
+	| { type: "notify" };
+
+export type CaptureRule = {
+	name: string;
+	enabled: boolean;
+	/** Rules with a higher priority run first; ties keep list order */
+	priority: number;
+	conditions: CaptureRuleConditions;
+	actions: CaptureRuleAction[];
+};
+
+/**
+ * Persisted capture rules.
+ */
+export type CaptureRuleSettings = {
+	rules: CaptureRule[];
+};
+
+/**
+ * A capture as the rules see it.
+ */
+export type CaptureSubject = {
+	type: CaptureItemType;
+	text: string;
+};
+
+/**
+ * The combined effect of the rules that matched a capture.
+ */
+export type CaptureRuleOutcome = {
+	/** Whether an `ignore` rule matched; nothing is recorded */
+	ignored: boolean;
+	/** The text after any transforms */
+	text: string;
+	tags: string[];
+	/** Shortest expiry of the matching rules, or null */
+	expireAfterMinutes: number | null;
+	/** Names of matching rules that asked for a notification */
+	notify: string[];
+};
+
+const DEFAULT_CAPTURE_RULE_SETTINGS: CaptureRuleSettings = { rules: [] };
+
+const CAPTURE_RULES_FILENAME = "capture-rules.json";
+
+const MAX_CAPTURE_RULES = 100;
+
+const MAX_RULE_NAME_LENGTH = 64;
+
+const MAX_RULE_ACTIONS = 10;
+
+const MAX_PRIORITY = 1000;
+
+/**
+ * Longest accepted expiry (one week).
+ */
+const MAX_EXPIRE_AFTER_MINUTES = 7 * 24 * 60;
+
+/**
+ * Time one rule's pattern may spend matching a capture (ms); a pattern
+ * that runs longer counts as no match.
+ */
+const RULE_TIMEOUT_MS = 50;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid capture rules: ${message}`);
+
+const invalidRule = (ruleName: string, error: unknown) =>
+	invalid(
+		`rule "${ruleName}": ${error instanceof Error ? error.message : String(error)}`,
+	);
+
+const parseConditions = (
+	conditions: unknown,
+	ruleName: string,
+): CaptureRuleConditions => {
+	if (typeof conditions !== "object" || conditions === null) {
+		throw invalid(`rule "${ruleName}": conditions must be an object`);
+	}
+	const { pattern, ignoreCase, type, kind } = conditions as Record<
+		string,
+		unknown
+	>;
+	const parsed: CaptureRuleConditions = {};
+	if (pattern !== undefined) {
+		try {
+			validateSearchPattern(pattern as string);
+		} catch (error) {
+			throw invalidRule(ruleName, error);
+		}
+		parsed.pattern = pattern as string;
+	}
+	if (ignoreCase !== undefined) {
+		if (typeof ignoreCase !== "boolean") {
+			throw invalid(`rule "${ruleName}": ignoreCase must be a boolean`);
+		}
+		parsed.ignoreCase = ignoreCase;
+	}
+	if (type !== undefined) {
+		if (!ITEM_TYPES.includes(type as CaptureItemType)) {
+			throw invalid(
+				`rule "${ruleName}": type must be one of ${ITEM_TYPES.join(", ")}`,
+			);
+		}
+		parsed.type = type as CaptureItemType;
+	}
+	if (kind !== undefined) {
+		if (!isContentKind(kind)) {
+			throw invalid(`rule "${ruleName}": unknown content kind`);
+		}
+		parsed.kind = kind;
+	}
+	if (parsed.pattern === undefined && !parsed.type && !parsed.kind) {
+		throw invalid(
+			`rule "${ruleName}": at least one of pattern, type or kind is required`,
+		);
+	}
+	return parsed;
+};
+
+const parseAction = (action: unknown, ruleName: string): CaptureRuleAction => {
+	if (typeof action !== "object" || action === null) {
+		throw invalid(`rule "${ruleName}": each action must be an object`);
+	}
+	const { type, tag, transform, minutes } = action as Record<string, unknown>;
+	switch (type) {
+		case "ignore":
+		case "notify":
+			return { type };
+		case "tag":
+			try {
+				return { type, tag: assertValidTagName(tag) };
+			} catch (error) {
+				throw invalidRule(ruleName, error);
+			}
+		case "transform":
+			try {
+				return {
+					type,
+					transform: parseTextTransformRequest({ transform }).transform,
+				};
+			} catch (error) {
+				throw invalidRule(ruleName, error);
+			}
+		case "expire":
+			if (
+				typeof minutes !== "number" ||
+				!Number.isInteger(minutes) ||
+				minutes < 1 ||
+				minutes > MAX_EXPIRE_AFTER_MINUTES
+			) {
+				throw invalid(
+					`rule "${ruleName}": expire minutes must be 1-${MAX_EXPIRE_AFTER_MINUTES}`,
+				);
+			}
+			return { type, minutes };
+		default:
+			throw invalid(
+				`rule "${ruleName}": action type must be one of tag, ignore, transform, expire, notify`,
+			);
+	}
+};
+
+const parseRule = (rule: unknown): CaptureRule => {
+	if (typeof rule !== "object" || rule === null) {
+		throw invalid("each rule must be an object");
+	}
+	const {
+		name,
+		enabled = true,
+		priority = 0,
+		conditions,
+		actions,
+	} = rule as Record<string, unknown>;
+	if (
+		typeof name !== "string" ||
+		name.trim() === "" ||
+		name.trim().length > MAX_RULE_NAME_LENGTH
+	) {
+		throw invalid(`rule names must be 1-${MAX_RULE_NAME_LENGTH} characters`);
+	}
+	const ruleName = name.trim();
+	if (typeof enabled !== "boolean") {
+		throw invalid(`rule "${ruleName}": enabled must be a boolean`);
+	}
+	if (
+		typeof priority !== "number" ||
+		!Number.isInteger(priority) ||
+		Math.abs(priority) > MAX_PRIORITY
+	) {
+		throw invalid(
+			`rule "${ruleName}": priority must be an integer from -${MAX_PRIORITY} to ${MAX_PRIORITY}`,
+		);
+	}
+	if (
+		!Array.isArray(actions) ||
+		actions.length === 0 ||
+		actions.length > MAX_RULE_ACTIONS
+	) {
+		throw invalid(
+			`rule "${ruleName}": actions must be a list of 1-${MAX_RULE_ACTIONS} actions`,
+		);
+	}
+	return {
+		name: ruleName,
+		enabled,
+		priority,
+		conditions: parseConditions(conditions, ruleName),
+		actions: actions.map((action) => parseAction(action, ruleName)),
+	};
+};
+
+const cloneRule = (rule: CaptureRule): CaptureRule => ({
+	...rule,
+	conditions: { ...rule.conditions },
+	actions: rule.actions.map((action) => ({ ...action })),
+});
+
+/**
+ * Validates a capture rules update.
+ * Pure function. Names are trimmed, `enabled` defaults to true and
+ * `priority` to 0. A missing `rules` keeps the current list; otherwise it
+ * replaces the whole list.
+ *
+ * @throws if there are more than 100 rules, or a rule has no name, no
+ *   conditions, an invalid pattern or an invalid action
+ */
+export const parseCaptureRuleSettings = (
+	input: unknown,
+	current: CaptureRuleSettings = DEFAULT_CAPTURE_RULE_SETTINGS,
+): CaptureRuleSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { rules } = input as Partial<
+		Record<keyof CaptureRuleSettings, unknown>
+	>;
+	if (rules === undefined) {
+		return { rules: current.rules.map(cloneRule) };
+	}
+	if (!Array.isArray(rules)) {
+		throw invalid("rules must be an array");
+	}
+	if (rules.length > MAX_CAPTURE_RULES) {
+		throw invalid(`at most ${MAX_CAPTURE_RULES} rules are allowed`);
+	}
+	return { rules: rules.map(parseRule) };
+};
+
+/**
+ * Whether `text` matches a rule's pattern, with a time limit; a pattern
+ * that times out does not match.
+ */
+const matchesPattern = (rule: CaptureRule, text: string): boolean => {
+	const { pattern, ignoreCase } = rule.conditions;
+	if (pattern === undefined) return true;
+	try {
+		const match = createRegexMatcher({
+			pattern,
+			flags: ignoreCase ? "i" : "",
+			timeoutMs: RULE_TIMEOUT_MS,
+		});
+		return match([text])[0];
+	} catch (error) {
+		console.warn(`Capture rule "${rule.name}" skipped:`, error);
+		return false;
+	}
+};
+
+/**
+ * Runs the enabled rules over a capture, highest priority first.
+ * Each matching rule applies its actions in order: transforms change the
+ * text later rules see (a transform that fails leaves it as is), and an
+ * `ignore` stops evaluation. Pattern and kind conditions never match
+ * images or files.
+ */
+export const evaluateCaptureRules = (
+	subject: CaptureSubject,
+	rules: readonly CaptureRule[],
+): CaptureRuleOutcome => {
+	const outcome: CaptureRuleOutcome = {
+		ignored: false,
+		text: subject.text,
+		tags: [],
+		expireAfterMinutes: null,
+		notify: [],
+	};
+	let kind: ContentKind | null = null;
+	const ordered = rules
+		.filter((rule) => rule.enabled)
+		.map((rule, index) => ({ rule, index }))
+		.sort((a, b) => b.rule.priority - a.rule.priority || a.index - b.index);
+
+	for (const { rule } of ordered) {
+		const { pattern, type, kind: wantedKind } = rule.conditions;
+		if (type && type !== subject.type) continue;
+		if (subject.type !== "text" && (pattern !== undefined || wantedKind)) {
+			continue;
+		}
+		if (wantedKind) {
+			if (kind === null) kind = classifyContent(outcome.text);
+			if (kind !== wantedKind) continue;
+		}
+		if (!matchesPattern(rule, outcome.text)) continue;
+
+		for (const action of rule.actions) {
+			switch (action.type) {
+				case "ignore":
+					outcome.ignored = true;
+					return outcome;
+				case "tag":
+					if (!outcome.tags.includes(action.tag)) {
+						outcome.tags.push(action.tag);
+					}
+					break;
+				case "transform":
+					if (subject.type !== "text") break;
+					try {
+						outcome.text = applyTextTransform(outcome.text, action.transform);
+						kind = null;
+					} catch (error) {
+						console.warn(
+							`Capture rule "${rule.name}" transform failed:`,
+							error,
+						);
+					}
+					break;
+				case "expire":
+					outcome.expireAfterMinutes = Math.min(
+						outcome.expireAfterMinutes ?? action.minutes,
+						action.minutes,
+					);
+					break;
+				case "notify":
+					if (!outcome.notify.includes(rule.name)) {
+						outcome.notify.push(rule.name);
+					}
+					break;
+			}
+		}
+	}
+	return outcome;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getCaptureRulesPath = (userDataPath: string) =>
+	path.join(userDataPath, CAPTURE_RULES_FILENAME);
+
+/**
+ * Reads saved rules, falling back to none if missing or invalid.
+ */
+const readCaptureRulesFromFile = (filePath: string): CaptureRuleSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { rules: [] };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseCaptureRuleSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read capture rules, using none:", error);
+		return { rules: [] };
+	}
+};
+
+const writeCaptureRulesToFile = (
+	filePath: string,
+	settings: CaptureRuleSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Capture Rules Module
+// ============================================================================
+
+export type CaptureRulesDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the capture rules engine.
+ * The capture path evaluates each copy against the rules before it is
+ * recorded, and applies the outcome (dropping, rewriting, tagging,
+ * expiring or announcing the item).
+ */
+export const createCaptureRules = (deps: CaptureRulesDeps) => {
+	const filePath = getCaptureRulesPath(deps.userDataPath);
+	let settings = readCaptureRulesFromFile(filePath);
+
+	const getSettings = (): CaptureRuleSettings => ({
+		rules: settings.rules.map(cloneRule),
+	});
+
+	const updateSettings = (input: unknown): CaptureRuleSettings => {
+		const next = parseCaptureRuleSettings(input, settings);
+		writeCaptureRulesToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const evaluate = (subject: CaptureSubject): CaptureRuleOutcome =>
+		evaluateCaptureRules(subject, settings.rules);
+
+	return { getSettings, updateSettings, evaluate };
+};
+
+export type CaptureRules = ReturnType<typeof createCaptureRules>;
@@ -0,0 +1,115 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createCaptureSounds,
+	getPlaySoundCommand,
+	parseCaptureSoundSettings,
+	resolveSoundFile,
+} from "./capture-sounds.js";
+
+describe("parseCaptureSoundSettings", () => {
+	it("is off with the platform sounds by default", () => {
+		expect(parseCaptureSoundSettings({})).toEqual({
+			enabled: false,
+			sounds: { capture: "default", pause: "default", resume: "default" },
+		});
+	});
+
+	it("updates single sounds and rejects anything but sound files", () => {
+		expect(
+			parseCaptureSoundSettings({
+				sounds: { capture: "/home/me/ding.WAV", pause: "none" },
+			}).sounds,
+		).toEqual({
+			capture: "/home/me/ding.WAV",
+			pause: "none",
+			resume: "default",
+		});
+		expect(() =>
+			parseCaptureSoundSettings({ sounds: { capture: "ding.wav" } }),
+		).toThrow("sounds.capture must be default, none or an absolute path");
+		expect(() =>
+			parseCaptureSoundSettings({ sounds: { resume: "/bin/sh" } }),
+		).toThrow("sounds.resume must be");
+	});
+});
+
+describe("resolveSoundFile", () => {
+	it("maps default to the platform sound and none to nothing", () => {
+		expect(resolveSoundFile("default", "capture", "darwin")).toBe(
+			"/System/Library/Sounds/Tink.aiff",
+		);
+		expect(resolveSoundFile("default", "pause", "freebsd")).toBeNull();
+		expect(resolveSoundFile("none", "pause", "darwin")).toBeNull();
+		expect(resolveSoundFile("/tmp/a.wav", "pause", "linux")).toBe("/tmp/a.wav");
+	});
+});
+
+describe("getPlaySoundCommand", () => {
+	it("quotes the file for PowerShell", () => {
+		expect(getPlaySoundCommand("win32", "C:\\it's.wav")?.args[3]).toBe(
+			"(New-Object System.Media.SoundPlayer 'C:\\it''s.wav').PlaySync()",
+		);
+		expect(getPlaySoundCommand("linux", "/tmp/a.oga")).toEqual({
+			command: "paplay",
+			args: ["/tmp/a.oga"],
+		});
+	});
+});
+
+describe("createCaptureSounds", () => {
+	let userDataPath: string;
+
+	beforeEach(() => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "capture-sounds-"));
+	});
+
+	afterEach(() => {
+		fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("plays only while enabled and once per burst", () => {
+		let time = 0;
+		const run = vi.fn().mockResolvedValue(undefined);
+		const sounds = createCaptureSounds({
+			userDataPath,
+			platform: "darwin",
+			run,
+			now: () => time,
+		});
+
+		sounds.play("capture");
+		expect(run).not.toHaveBeenCalled();
+
+		sounds.updateSettings({ enabled: true });
+		sounds.play("capture");
+		time = 100;
+		sounds.play("capture");
+		sounds.play("pause");
+		time = 400;
+		sounds.play("capture");
+
+		expect(run.mock.calls).toEqual([
+			["afplay", ["/System/Library/Sounds/Tink.aiff"]],
+			["afplay", ["/System/Library/Sounds/Pop.aiff"]],
+			["afplay", ["/System/Library/Sounds/Tink.aiff"]],
+		]);
+	});
+
+	it("previews a sound while disabled", () => {
+		const run = vi.fn().mockResolvedValue(undefined);
+		const sounds = createCaptureSounds({
+			userDataPath,
+			platform: "linux",
+			run,
+		});
+
+		sounds.preview("resume");
+
+		expect(run).toHaveBeenCalledWith("paplay", [
+			"/usr/share/sounds/freedesktop/stereo/device-added.oga",
+		]);
+	});
+});
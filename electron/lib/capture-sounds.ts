@@ -0,0 +1,299 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Events that can play a sound.
+ */
+export const SOUND_EVENTS = ["capture", "pause", "resume"] as const;
+
+export type SoundEvent = (typeof SOUND_EVENTS)[number];
+
+/**
+ * What an event plays: the platform's default sound, nothing, or an
+ * absolute path to a sound file.
+ */
+export type SoundChoice = "default" | "none" | (string & {});
+
+/**
+ * Persisted capture sound settings.
+ */
+export type CaptureSoundSettings = {
+	/** Off by default */
+	enabled: boolean;
+	sounds: Record<SoundEvent, SoundChoice>;
+};
+
+/**
+ * Runs a command to completion; injectable for tests.
+ */
+export type SoundCommandRunner = (
+	command: string,
+	args: string[],
+) => Promise<void>;
+
+const DEFAULT_CAPTURE_SOUND_SETTINGS: CaptureSoundSettings = {
+	enabled: false,
+	sounds: { capture: "default", pause: "default", resume: "default" },
+};
+
+const CAPTURE_SOUNDS_FILENAME = "capture-sounds.json";
+
+const SOUND_EXTENSIONS = [
+	".wav",
+	".aif",
+	".aiff",
+	".mp3",
+	".m4a",
+	".ogg",
+	".oga",
+];
+
+const MAX_SOUND_PATH_LENGTH = 1024;
+
+/**
+ * Shortest gap between two sounds for the same event (ms), so a burst
+ * of copies plays once.
+ */
+const MIN_REPEAT_MS = 250;
+
+/**
+ * Sounds that ship with each platform.
+ */
+const PLATFORM_SOUNDS: Partial<
+	Record<NodeJS.Platform, Record<SoundEvent, string>>
+> = {
+	darwin: {
+		capture: "/System/Library/Sounds/Tink.aiff",
+		pause: "/System/Library/Sounds/Pop.aiff",
+		resume: "/System/Library/Sounds/Purr.aiff",
+	},
+	linux: {
+		capture: "/usr/share/sounds/freedesktop/stereo/message.oga",
+		pause: "/usr/share/sounds/freedesktop/stereo/device-removed.oga",
+		resume: "/usr/share/sounds/freedesktop/stereo/device-added.oga",
+	},
+	win32: {
+		capture: "C:\\Windows\\Media\\Windows Navigation Start.wav",
+		pause: "C:\\Windows\\Media\\Speech Off.wav",
+		resume: "C:\\Windows\\Media\\Speech On.wav",
+	},
+};
+
+const runCommand: SoundCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(command, args, (error) => (error ? reject(error) : resolve()));
+	});
+
+const invalid = (message: string) =>
+	new Error(`Invalid capture sound settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+export const isSoundEvent = (value: unknown): value is SoundEvent =>
+	SOUND_EVENTS.includes(value as SoundEvent);
+
+const isSoundChoice = (value: unknown): value is SoundChoice =>
+	value === "default" ||
+	value === "none" ||
+	(typeof value === "string" &&
+		value.length <= MAX_SOUND_PATH_LENGTH &&
+		path.isAbsolute(value) &&
+		SOUND_EXTENSIONS.includes(path.extname(value).toLowerCase()));
+
+/**
+ * Validates a capture sound settings update.
+ * Pure function. Missing keys, including single events in `sounds`,
+ * keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or a sound is not `default`,
+ *   `none` or an absolute path to a sound file
+ */
+export const parseCaptureSoundSettings = (
+	input: unknown,
+	current: CaptureSoundSettings = DEFAULT_CAPTURE_SOUND_SETTINGS,
+): CaptureSoundSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, sounds } = input as Partial<
+		Record<keyof CaptureSoundSettings, unknown>
+	>;
+	const next: CaptureSoundSettings = {
+		...current,
+		sounds: { ...current.sounds },
+	};
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (sounds !== undefined) {
+		if (typeof sounds !== "object" || sounds === null) {
+			throw invalid("sounds must be an object");
+		}
+		const values = sounds as Partial<Record<SoundEvent, unknown>>;
+		for (const event of SOUND_EVENTS) {
+			const value = values[event];
+			if (value === undefined) continue;
+			if (!isSoundChoice(value)) {
+				throw invalid(
+					`sounds.${event} must be default, none or an absolute path to a ${SOUND_EXTENSIONS.join("/")} file`,
+				);
+			}
+			next.sounds[event] = value;
+		}
+	}
+
+	return next;
+};
+
+/**
+ * The file an event plays, or null if it plays nothing or the platform
+ * has no default sound.
+ * Pure function.
+ */
+export const resolveSoundFile = (
+	choice: SoundChoice,
+	event: SoundEvent,
+	platform: NodeJS.Platform,
+): string | null => {
+	if (choice === "none") return null;
+	if (choice === "default") return PLATFORM_SOUNDS[platform]?.[event] ?? null;
+	return choice;
+};
+
+/**
+ * Command that plays a sound file, or null if the platform is
+ * unsupported.
+ * - macOS: `afplay`
+ * - Windows: `System.Media.SoundPlayer` through PowerShell (WAV only)
+ * - Linux: `paplay` (PulseAudio or PipeWire)
+ * Pure function.
+ */
+export const getPlaySoundCommand = (
+	platform: NodeJS.Platform,
+	file: string,
+): { command: string; args: string[] } | null => {
+	switch (platform) {
+		case "darwin":
+			return { command: "afplay", args: [file] };
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-Command",
+					`(New-Object System.Media.SoundPlayer '${file.replace(/'/g, "''")}').PlaySync()`,
+				],
+			};
+		case "linux":
+			return { command: "paplay", args: [file] };
+		default:
+			return null;
+	}
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getCaptureSoundsPath = (userDataPath: string) =>
+	path.join(userDataPath, CAPTURE_SOUNDS_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readCaptureSoundsFromFile = (filePath: string): CaptureSoundSettings => {
+	if (!fs.existsSync(filePath)) {
+		return parseCaptureSoundSettings({});
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseCaptureSoundSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read capture sound settings:", error);
+		return parseCaptureSoundSettings({});
+	}
+};
+
+const writeCaptureSoundsToFile = (
+	filePath: string,
+	settings: CaptureSoundSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Capture Sounds Module
+// ============================================================================
+
+export type CaptureSoundsDeps = {
+	userDataPath: string;
+	platform?: NodeJS.Platform;
+	run?: SoundCommandRunner;
+	now?: () => number;
+};
+
+/**
+ * Creates the capture sounds module: while enabled, a short sound
+ * confirms captures and pausing or resuming capture without looking at
+ * the tray. Sounds are played by the platform's player in the
+ * background, so a missing player or file only logs an error.
+ */
+export const createCaptureSounds = (deps: CaptureSoundsDeps) => {
+	const filePath = getCaptureSoundsPath(deps.userDataPath);
+	const platform = deps.platform ?? process.platform;
+	const run = deps.run ?? runCommand;
+	const now = deps.now ?? Date.now;
+	let settings = readCaptureSoundsFromFile(filePath);
+	const lastPlayedAt = new Map<SoundEvent, number>();
+
+	/**
+	 * Plays an event's sound, whether or not sounds are enabled.
+	 */
+	const preview = (event: SoundEvent): void => {
+		const file = resolveSoundFile(settings.sounds[event], event, platform);
+		const command = file ? getPlaySoundCommand(platform, file) : null;
+		if (!command) return;
+		lastPlayedAt.set(event, now());
+		run(command.command, command.args).catch((error) => {
+			console.error(`Failed to play the ${event} sound:`, error);
+		});
+	};
+
+	/**
+	 * Plays an event's sound if enabled and it did not just play.
+	 */
+	const play = (event: SoundEvent): void => {
+		if (!settings.enabled) return;
+		const last = lastPlayedAt.get(event);
+		if (last !== undefined && now() - last < MIN_REPEAT_MS) return;
+		preview(event);
+	};
+
+	const getSettings = (): CaptureSoundSettings => ({
+		...settings,
+		sounds: { ...settings.sounds },
+	});
+
+	const updateSettings = (input: unknown): CaptureSoundSettings => {
+		const next = parseCaptureSoundSettings(input, settings);
+		writeCaptureSoundsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, play, preview };
+};
+
+export type CaptureSounds = ReturnType<typeof createCaptureSounds>;
@@ -0,0 +1,120 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createClipboardAutoClear,
+	DEFAULT_AUTO_CLEAR_SETTINGS,
+	getAutoClearSettingsPath,
+	parseAutoClearSettings,
+} from "./clipboard-auto-clear.js";
+
+describe("parseAutoClearSettings", () => {
+	it("merges valid updates", () => {
+		expect(parseAutoClearSettings({ clearAfterSeconds: 10 })).toEqual({
+			...DEFAULT_AUTO_CLEAR_SETTINGS,
+			clearAfterSeconds: 10,
+		});
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseAutoClearSettings({ enabled: 1 })).toThrow(
+			"enabled must be a boolean",
+		);
+		for (const value of [0, 2.5, 3601, null]) {
+			expect(() =>
+				parseAutoClearSettings({ clearAfterSeconds: value }),
+			).toThrow("clearAfterSeconds must be");
+		}
+	});
+});
+
+describe("createClipboardAutoClear", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-clear-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createAutoClear = (readText: () => string) => {
+		const clear = vi.fn();
+		const autoClear = createClipboardAutoClear({
+			userDataPath: createTempUserDataPath(),
+			readText,
+			clear,
+		});
+		return { autoClear, clear };
+	};
+
+	it("clears the clipboard after the delay", () => {
+		const { autoClear, clear } = createAutoClear(() => "hunter2");
+
+		autoClear.schedule("hunter2");
+		expect(autoClear.getStatus().pendingClearAt).not.toBeNull();
+
+		vi.advanceTimersByTime(29_999);
+		expect(clear).not.toHaveBeenCalled();
+		vi.advanceTimersByTime(1);
+		expect(clear).toHaveBeenCalledTimes(1);
+		expect(autoClear.getStatus().pendingClearAt).toBeNull();
+	});
+
+	it("leaves newer clipboard contents alone", () => {
+		const { autoClear, clear } = createAutoClear(() => "something else");
+
+		autoClear.schedule("hunter2");
+		vi.advanceTimersByTime(30_000);
+		expect(clear).not.toHaveBeenCalled();
+	});
+
+	it("can be cancelled", () => {
+		const { autoClear, clear } = createAutoClear(() => "hunter2");
+
+		autoClear.schedule("hunter2");
+		autoClear.cancel();
+		vi.advanceTimersByTime(30_000);
+		expect(clear).not.toHaveBeenCalled();
+	});
+
+	it("uses and persists the configured delay", () => {
+		const userDataPath = createTempUserDataPath();
+		const clear = vi.fn();
+		const autoClear = createClipboardAutoClear({
+			userDataPath,
+			readText: () => "hunter2",
+			clear,
+		});
+
+		autoClear.updateSettings({ clearAfterSeconds: 5 });
+		autoClear.schedule("hunter2");
+		vi.advanceTimersByTime(5_000);
+		expect(clear).toHaveBeenCalledTimes(1);
+
+		const saved = JSON.parse(
+			fs.readFileSync(getAutoClearSettingsPath(userDataPath), "utf-8"),
+		);
+		expect(saved.clearAfterSeconds).toBe(5);
+	});
+
+	it("does nothing when disabled", () => {
+		const { autoClear, clear } = createAutoClear(() => "hunter2");
+
+		autoClear.updateSettings({ enabled: false });
+		autoClear.schedule("hunter2");
+		vi.advanceTimersByTime(60_000);
+		expect(clear).not.toHaveBeenCalled();
+	});
+});
@@ -0,0 +1,183 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted auto-clear settings.
+ */
+export type AutoClearSettings = {
+	enabled: boolean;
+	/** Seconds a sensitive copy stays on the system clipboard */
+	clearAfterSeconds: number;
+};
+
+/**
+ * Auto-clear state reported to the renderer.
+ */
+export type AutoClearStatus = AutoClearSettings & {
+	/** When the pending clear fires (epoch ms), or null if none is pending */
+	pendingClearAt: number | null;
+};
+
+export const DEFAULT_AUTO_CLEAR_SETTINGS = {
+	enabled: true,
+	clearAfterSeconds: 30,
+} as const satisfies AutoClearSettings;
+
+const AUTO_CLEAR_FILENAME = "auto-clear.json";
+
+/**
+ * Longest accepted clear delay (one hour).
+ */
+const MAX_CLEAR_AFTER_SECONDS = 60 * 60;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates an auto-clear settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or `clearAfterSeconds` is not an
+ *   integer from 1 to 3600
+ */
+export const parseAutoClearSettings = (
+	input: unknown,
+	current: AutoClearSettings = DEFAULT_AUTO_CLEAR_SETTINGS,
+): AutoClearSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid auto-clear settings: expected an object");
+	}
+
+	const { enabled, clearAfterSeconds } = input as Partial<
+		Record<keyof AutoClearSettings, unknown>
+	>;
+	const next: AutoClearSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error("Invalid auto-clear settings: enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (clearAfterSeconds !== undefined) {
+		if (
+			typeof clearAfterSeconds !== "number" ||
+			!Number.isInteger(clearAfterSeconds) ||
+			clearAfterSeconds < 1 ||
+			clearAfterSeconds > MAX_CLEAR_AFTER_SECONDS
+		) {
+			throw new Error(
+				`Invalid auto-clear settings: clearAfterSeconds must be 1-${MAX_CLEAR_AFTER_SECONDS}`,
+			);
+		}
+		next.clearAfterSeconds = clearAfterSeconds;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getAutoClearSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, AUTO_CLEAR_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readAutoClearSettingsFromFile = (filePath: string): AutoClearSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_AUTO_CLEAR_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseAutoClearSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read auto-clear settings, using defaults:", error);
+		return { ...DEFAULT_AUTO_CLEAR_SETTINGS };
+	}
+};
+
+const writeAutoClearSettingsToFile = (
+	filePath: string,
+	settings: AutoClearSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Auto-Clear Module
+// ============================================================================
+
+export type ClipboardAutoClearDeps = {
+	userDataPath: string;
+	/** Reads the current clipboard text */
+	readText: () => string;
+	/** Empties the system clipboard */
+	clear: () => void;
+	now?: () => number;
+};
+
+/**
+ * Creates the clipboard auto-clear timer.
+ * After a sensitive copy, the system clipboard is emptied once the delay
+ * passes, unless something else has been copied in the meantime.
+ */
+export const createClipboardAutoClear = (deps: ClipboardAutoClearDeps) => {
+	const filePath = getAutoClearSettingsPath(deps.userDataPath);
+	const now = deps.now ?? Date.now;
+	let settings = readAutoClearSettingsFromFile(filePath);
+	let timer: NodeJS.Timeout | null = null;
+	let pendingClearAt: number | null = null;
+
+	const getStatus = (): AutoClearStatus => ({ ...settings, pendingClearAt });
+
+	/**
+	 * Cancels the pending clear, if any.
+	 */
+	const cancel = (): AutoClearStatus => {
+		if (timer) {
+			clearTimeout(timer);
+			timer = null;
+		}
+		pendingClearAt = null;
+		return getStatus();
+	};
+
+	/**
+	 * Starts the countdown for a sensitive copy, replacing any pending one.
+	 */
+	const schedule = (text: string): void => {
+		cancel();
+		if (!settings.enabled) return;
+
+		const delayMs = settings.clearAfterSeconds * 1000;
+		pendingClearAt = now() + delayMs;
+		timer = setTimeout(() => {
+			timer = null;
+			pendingClearAt = null;
+			try {
+				if (deps.readText() === text) deps.clear();
+			} catch (error) {
+				console.error("Failed to auto-clear clipboard:", error);
+			}
+		}, delayMs);
+	};
+
+	const updateSettings = (input: unknown): AutoClearStatus => {
+		const next = parseAutoClearSettings(input, settings);
+		writeAutoClearSettingsToFile(filePath, next);
+		settings = next;
+		if (!settings.enabled) cancel();
+		return getStatus();
+	};
+
+	return { getStatus, schedule, cancel, updateSettings };
+};
+
+export type ClipboardAutoClear = ReturnType<typeof createClipboardAutoClear>;
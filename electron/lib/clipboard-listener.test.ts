@@ -0,0 +1,27 @@
+import { describe, expect, it } from "vitest";
+import { getClipboardListenerCommand } from "./clipboard-listener.js";
+
+describe("getClipboardListenerCommand", () => {
+	it("uses the platform's change notifications", () => {
+		expect(getClipboardListenerCommand("darwin")?.args[3]).toContain(
+			"changeCount",
+		);
+		const windows = getClipboardListenerCommand("win32");
+		expect(windows?.command).toBe("powershell.exe");
+		const script = Buffer.from(windows?.args[3] ?? "", "base64").toString(
+			"utf16le",
+		);
+		expect(script).toContain("AddClipboardFormatListener");
+		expect(getClipboardListenerCommand("freebsd")).toBeNull();
+	});
+
+	it("picks clipnotify on X11 and wl-paste on Wayland", () => {
+		expect(getClipboardListenerCommand("linux", {})?.args[1]).toContain(
+			"while clipnotify",
+		);
+		expect(
+			getClipboardListenerCommand("linux", { WAYLAND_DISPLAY: "wayland-0" })
+				?.args[1],
+		).toContain("wl-paste --watch");
+	});
+});
@@ -0,0 +1,215 @@
+import { type ChildProcess, spawn } from "node:child_process";
+import readline from "node:readline";
+import type { ClipboardChangeListener } from "./clipboard-watcher.js";
+import { isWaylandSession } from "./wayland-clipboard.js";
+
+/**
+ * Starts a long-running listener process; injectable for tests.
+ */
+export type ClipboardListenerSpawner = (
+	command: string,
+	args: string[],
+) => ChildProcess;
+
+/**
+ * A message window registered with `AddClipboardFormatListener`, in C#
+ * compiled by PowerShell. Prints `ready` once registered and `change`
+ * on every `WM_CLIPBOARDUPDATE`.
+ */
+const WINDOWS_LISTENER_SOURCE = `
+using System;
+using System.Runtime.InteropServices;
+using System.Windows.Forms;
+public class ClipmanClipboardListener : NativeWindow {
+	[DllImport("user32.dll")] static extern bool AddClipboardFormatListener(IntPtr hwnd);
+	const int WM_CLIPBOARDUPDATE = 0x031D;
+	static void Emit(string line) {
+		try { Console.Out.WriteLine(line); Console.Out.Flush(); } catch { Environment.Exit(0); }
+	}
+	protected override void WndProc(ref Message m) {
+		if (m.Msg == WM_CLIPBOARDUPDATE) Emit("change");
+		base.WndProc(ref m);
+	}
+	public static void Run() {
+		ClipmanClipboardListener listener = new ClipmanClipboardListener();
+		CreateParams parameters = new CreateParams();
+		parameters.Parent = new IntPtr(-3);
+		listener.CreateHandle(parameters);
+		if (!AddClipboardFormatListener(listener.Handle)) Environment.Exit(1);
+		Emit("ready");
+		Application.Run();
+	}
+}
+`;
+
+/**
+ * The listener script as `-EncodedCommand` (Base64 of UTF-16LE), which
+ * keeps its quotes and line breaks intact on the command line.
+ */
+const WINDOWS_LISTENER_COMMAND = Buffer.from(
+	`Add-Type -ReferencedAssemblies System.Windows.Forms -TypeDefinition @'\n${WINDOWS_LISTENER_SOURCE}\n'@\n[ClipmanClipboardListener]::Run()`,
+	"utf16le",
+).toString("base64");
+
+/**
+ * JavaScript for Automation: macOS has no pasteboard change event, but
+ * reading `changeCount` costs next to nothing, unlike reading the
+ * clipboard. Checks every 0.1s after a change, since copies come in
+ * bursts, and backs off to once a second while the pasteboard is idle.
+ */
+const MAC_LISTENER_SCRIPT = `
+ObjC.import("AppKit");
+const out = $.NSFileHandle.fileHandleWithStandardOutput;
+const emit = (line) =>
+	out.writeData($(line + "\\n").dataUsingEncoding($.NSUTF8StringEncoding));
+const pasteboard = $.NSPasteboard.generalPasteboard;
+let count = pasteboard.changeCount;
+let wait = 0.1;
+emit("ready");
+for (;;) {
+	delay(wait);
+	const next = pasteboard.changeCount;
+	if (next !== count) {
+		count = next;
+		wait = 0.1;
+		emit("change");
+	} else {
+		wait = Math.min(wait * 2, 1);
+	}
+}
+`;
+
+/**
+ * X11: `clipnotify` waits for one XFixes selection event and exits, so
+ * it runs in a loop. Missing `clipnotify` exits before `ready`.
+ */
+const X11_LISTENER_SCRIPT =
+	"command -v clipnotify >/dev/null || exit 127; echo ready; while clipnotify; do echo change; done";
+
+/**
+ * Wayland: `wl-paste --watch` runs a command per change, where the
+ * compositor supports the data-control protocol (wlroots, KDE).
+ */
+const WAYLAND_LISTENER_SCRIPT =
+	"command -v wl-paste >/dev/null || exit 127; echo ready; exec wl-paste --watch echo change";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Command that runs the clipboard change listener, or null if the
+ * platform has none.
+ * - macOS: a JavaScript for Automation loop over `changeCount`
+ * - Windows: `AddClipboardFormatListener` through PowerShell
+ * - Linux: `clipnotify` on X11, `wl-paste --watch` on Wayland
+ * Pure function.
+ */
+export const getClipboardListenerCommand = (
+	platform: NodeJS.Platform,
+	env: NodeJS.ProcessEnv = {},
+): { command: string; args: string[] } | null => {
+	switch (platform) {
+		case "darwin":
+			return {
+				command: "osascript",
+				args: ["-l", "JavaScript", "-e", MAC_LISTENER_SCRIPT],
+			};
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-EncodedCommand",
+					WINDOWS_LISTENER_COMMAND,
+				],
+			};
+		case "linux":
+			return {
+				command: "sh",
+				args: [
+					"-c",
+					isWaylandSession(env)
+						? WAYLAND_LISTENER_SCRIPT
+						: X11_LISTENER_SCRIPT,
+				],
+			};
+		default:
+			return null;
+	}
+};
+
+// ============================================================================
+// Clipboard Listener Module
+// ============================================================================
+
+const spawnListener: ClipboardListenerSpawner = (command, args) =>
+	spawn(command, args, { stdio: ["ignore", "pipe", "pipe"] });
+
+/**
+ * Creates the clipboard change listener for the current platform: a
+ * helper process that prints a line per change, so the watcher reads
+ * the clipboard only when it changed instead of every second. On
+ * unsupported platforms `start` reports an exit at once.
+ */
+export const createClipboardListener = (deps: {
+	platform: NodeJS.Platform;
+	env?: NodeJS.ProcessEnv;
+	spawn?: ClipboardListenerSpawner;
+}): ClipboardChangeListener => {
+	const spawnProcess = deps.spawn ?? spawnListener;
+	let child: ChildProcess | null = null;
+
+	const start: ClipboardChangeListener["start"] = (events) => {
+		if (child) return;
+		const command = getClipboardListenerCommand(deps.platform, deps.env);
+		if (!command) {
+			events.onExit(
+				new Error("Clipboard change events are not supported on this platform"),
+			);
+			return;
+		}
+
+		const listener = spawnProcess(command.command, command.args);
+		child = listener;
+		let stderr = "";
+		listener.stderr?.on("data", (chunk: Buffer) => {
+			stderr = (stderr + chunk.toString("utf-8")).slice(-1000);
+		});
+		if (listener.stdout) {
+			const lines = readline.createInterface({ input: listener.stdout });
+			lines.on("line", (line) => {
+				if (child !== listener) return;
+				const message = line.trim();
+				if (message === "ready") events.onReady();
+				else if (message === "change") events.onChange();
+			});
+		}
+
+		const exited = (error: Error) => {
+			if (child !== listener) return;
+			child = null;
+			events.onExit(error);
+		};
+		listener.on("error", (error) =>
+			exited(new Error(`Clipboard listener failed: ${error.message}`)),
+		);
+		listener.on("exit", (code) => {
+			const detail = stderr.trim();
+			exited(
+				new Error(
+					`Clipboard listener exited with code ${code}${detail ? `: ${detail}` : ""}`,
+				),
+			);
+		});
+	};
+
+	const stop = () => {
+		const listener = child;
+		child = null;
+		listener?.kill();
+	};
+
+	return { start, stop };
+};
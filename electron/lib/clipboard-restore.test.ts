@@ -0,0 +1,81 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createClipboardRestore,
+	getClipboardRestorePath,
+	parseClipboardRestoreSettings,
+} from "./clipboard-restore.js";
+
+describe("parseClipboardRestoreSettings", () => {
+	it("is off by default", () => {
+		expect(parseClipboardRestoreSettings({})).toEqual({ enabled: false });
+		expect(parseClipboardRestoreSettings({ enabled: true })).toEqual({
+			enabled: true,
+		});
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseClipboardRestoreSettings(null)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseClipboardRestoreSettings({ enabled: "yes" })).toThrow(
+			"enabled must be a boolean",
+		);
+	});
+});
+
+describe("createClipboardRestore", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	const setup = (clipboardItem: number | null, empty = true) => {
+		const restoreItem = vi.fn(() => true);
+		const clipboardRestore = createClipboardRestore({
+			userDataPath,
+			findClipboardItem: () => clipboardItem,
+			isClipboardEmpty: () => empty,
+			restoreItem,
+		});
+		return { clipboardRestore, restoreItem };
+	};
+
+	it("restores the item on the clipboard at the last quit, once", () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-restore-"));
+		const first = setup(42);
+		first.clipboardRestore.updateSettings({ enabled: true });
+		first.clipboardRestore.save();
+
+		const { clipboardRestore, restoreItem } = setup(null);
+		expect(clipboardRestore.restore()).toBe(true);
+		expect(restoreItem).toHaveBeenCalledWith(42);
+		expect(clipboardRestore.restore()).toBe(false);
+
+		const next = setup(null);
+		expect(next.clipboardRestore.restore()).toBe(false);
+		expect(next.restoreItem).not.toHaveBeenCalled();
+	});
+
+	it("leaves a clipboard that already holds something", () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-restore-"));
+		const first = setup(42);
+		first.clipboardRestore.updateSettings({ enabled: true });
+		first.clipboardRestore.save();
+
+		const { clipboardRestore, restoreItem } = setup(null, false);
+		expect(clipboardRestore.restore()).toBe(false);
+		expect(restoreItem).not.toHaveBeenCalled();
+	});
+
+	it("saves nothing while disabled", () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-restore-"));
+		setup(42).clipboardRestore.save();
+
+		expect(fs.existsSync(getClipboardRestorePath(userDataPath))).toBe(false);
+		expect(setup(null).clipboardRestore.restore()).toBe(false);
+	});
+});
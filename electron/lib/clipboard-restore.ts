@@ -0,0 +1,197 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted clipboard restore settings.
+ */
+export type ClipboardRestoreSettings = {
+	/** Off by default: puts the last copy back on the clipboard at startup */
+	enabled: boolean;
+};
+
+/**
+ * Settings plus the copy kept from the last quit, as saved to disk.
+ */
+type ClipboardRestoreFile = ClipboardRestoreSettings & {
+	/** History item on the clipboard at the last quit */
+	savedItemId: number | null;
+};
+
+const DEFAULT_CLIPBOARD_RESTORE_SETTINGS: ClipboardRestoreSettings = {
+	enabled: false,
+};
+
+const CLIPBOARD_RESTORE_FILENAME = "clipboard-restore.json";
+
+const invalid = (message: string) =>
+	new Error(`Invalid clipboard restore settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a clipboard restore settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean
+ */
+export const parseClipboardRestoreSettings = (
+	input: unknown,
+	current: ClipboardRestoreSettings = DEFAULT_CLIPBOARD_RESTORE_SETTINGS,
+): ClipboardRestoreSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled } = input as Partial<
+		Record<keyof ClipboardRestoreSettings, unknown>
+	>;
+	const next: ClipboardRestoreSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	return next;
+};
+
+/**
+ * The saved item id from a settings file, if it is a valid history id.
+ * Pure function.
+ */
+const parseSavedItemId = (value: unknown): number | null =>
+	typeof value === "number" && Number.isInteger(value) && value > 0
+		? value
+		: null;
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getClipboardRestorePath = (userDataPath: string) =>
+	path.join(userDataPath, CLIPBOARD_RESTORE_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readClipboardRestoreFromFile = (
+	filePath: string,
+): ClipboardRestoreFile => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_CLIPBOARD_RESTORE_SETTINGS, savedItemId: null };
+	}
+
+	try {
+		const parsed: unknown = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		return {
+			...parseClipboardRestoreSettings(parsed),
+			savedItemId: parseSavedItemId(
+				(parsed as { savedItemId?: unknown }).savedItemId,
+			),
+		};
+	} catch (error) {
+		console.error(
+			"Failed to read clipboard restore settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_CLIPBOARD_RESTORE_SETTINGS, savedItemId: null };
+	}
+};
+
+const writeClipboardRestoreToFile = (
+	filePath: string,
+	file: ClipboardRestoreFile,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(file, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Clipboard Restore Module
+// ============================================================================
+
+export type ClipboardRestoreDeps = {
+	userDataPath: string;
+	/** History item the clipboard holds, or null if it is not in history */
+	findClipboardItem: () => number | null;
+	isClipboardEmpty: () => boolean;
+	/**
+	 * Places a history item on the clipboard.
+	 * @returns false if the item no longer exists
+	 */
+	restoreItem: (id: number) => boolean;
+};
+
+/**
+ * Creates the clipboard restore: while enabled, the history item on the
+ * clipboard at quit is remembered and put back at the next startup if
+ * the clipboard is empty then, as it is after a reboot. Only a reference
+ * is saved; the contents and all their formats come from history, so
+ * copies that were never recorded are not kept.
+ */
+export const createClipboardRestore = (deps: ClipboardRestoreDeps) => {
+	const filePath = getClipboardRestorePath(deps.userDataPath);
+	const saved = readClipboardRestoreFromFile(filePath);
+	let settings: ClipboardRestoreSettings = { enabled: saved.enabled };
+	// Read once: a later startup must not bring back an older copy
+	let pendingItemId = saved.savedItemId;
+
+	const write = (savedItemId: number | null) =>
+		writeClipboardRestoreToFile(filePath, { ...settings, savedItemId });
+
+	if (pendingItemId !== null) {
+		try {
+			write(null);
+		} catch (error) {
+			console.error("Failed to clear the saved clipboard:", error);
+		}
+	}
+
+	const getSettings = (): ClipboardRestoreSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): ClipboardRestoreSettings => {
+		const next = parseClipboardRestoreSettings(input, settings);
+		settings = next;
+		write(null);
+		return getSettings();
+	};
+
+	/**
+	 * Remembers the history item on the clipboard, on quit. Does nothing
+	 * while disabled. Failures are logged, so quitting goes on.
+	 */
+	const save = (): void => {
+		if (!settings.enabled) return;
+		try {
+			write(deps.findClipboardItem());
+		} catch (error) {
+			console.error("Failed to save the clipboard:", error);
+		}
+	};
+
+	/**
+	 * Puts the item saved at the last quit back on the clipboard, once. A
+	 * clipboard that already holds something is left alone.
+	 * @returns whether the item was restored
+	 */
+	const restore = (): boolean => {
+		const id = pendingItemId;
+		pendingItemId = null;
+		if (id === null || !settings.enabled) return false;
+		try {
+			if (!deps.isClipboardEmpty()) return false;
+			return deps.restoreItem(id);
+		} catch (error) {
+			console.error("Failed to restore the clipboard:", error);
+			return false;
+		}
+	};
+
+	return { getSettings, updateSettings, save, restore };
+};
+
+export type ClipboardRestore = ReturnType<typeof createClipboardRestore>;
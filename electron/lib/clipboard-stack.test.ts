@@ -0,0 +1,32 @@
+import { describe, expect, it } from "vitest";
+import { createClipboardStack } from "./clipboard-stack.js";
+
+describe("createClipboardStack", () => {
+	it("pops entries newest first", () => {
+		const stack = createClipboardStack();
+		stack.push({ text: "first" });
+		stack.push({ text: "second", html: "<b>second</b>" });
+
+		expect(stack.pop()).toEqual({ text: "second", html: "<b>second</b>" });
+		expect(stack.getEntries()).toEqual([{ text: "first" }]);
+		expect(stack.pop()).toEqual({ text: "first" });
+		expect(stack.pop()).toBeNull();
+	});
+
+	it("ignores entries without text", () => {
+		const stack = createClipboardStack();
+
+		expect(stack.push({ text: "" })).toBe(false);
+		expect(stack.getEntries()).toEqual([]);
+	});
+
+	it("drops the oldest entry beyond the limit", () => {
+		const stack = createClipboardStack();
+		for (let i = 1; i <= 51; i++) stack.push({ text: `entry ${i}` });
+
+		const entries = stack.getEntries();
+		expect(entries).toHaveLength(50);
+		expect(entries[0]).toEqual({ text: "entry 51" });
+		expect(entries[49]).toEqual({ text: "entry 2" });
+	});
+});
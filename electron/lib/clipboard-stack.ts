@@ -0,0 +1,55 @@
+/**
+ * Deepest clipboard stack kept; pushing beyond it drops the oldest entry.
+ */
+const MAX_STACK_DEPTH = 50;
+
+/**
+ * A clipboard saved on the stack: its plain text and the rich formats
+ * copied alongside it.
+ */
+export type ClipboardStackEntry = {
+	text: string;
+	rtf?: string;
+	html?: string;
+};
+
+/**
+ * Creates the clipboard stack: an Emacs-style kill ring the user pushes the
+ * current clipboard onto and pops back off to paste, newest first. Held in
+ * the main process and not persisted.
+ */
+export const createClipboardStack = () => {
+	let entries: ClipboardStackEntry[] = [];
+
+	const getEntries = (): ClipboardStackEntry[] =>
+		entries.map((entry) => ({ ...entry }));
+
+	/**
+	 * Pushes an entry on top of the stack, dropping the oldest beyond 50.
+	 * Returns false and leaves the stack unchanged for an entry without text.
+	 */
+	const push = (entry: ClipboardStackEntry): boolean => {
+		if (!entry.text) return false;
+		entries = [{ ...entry }, ...entries].slice(0, MAX_STACK_DEPTH);
+		return true;
+	};
+
+	/**
+	 * Removes and returns the top entry, or null if the stack is empty.
+	 */
+	const pop = (): ClipboardStackEntry | null => {
+		const [top, ...rest] = entries;
+		if (top === undefined) return null;
+		entries = rest;
+		return top;
+	};
+
+	const clear = (): ClipboardStackEntry[] => {
+		entries = [];
+		return [];
+	};
+
+	return { getEntries, push, pop, clear };
+};
+
+export type ClipboardStack = ReturnType<typeof createClipboardStack>;
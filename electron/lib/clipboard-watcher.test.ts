@@ -0,0 +1,275 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	type ClipboardSnapshot,
+	createClipboardWatcher,
+	hasClipboardChanged,
+	hasSensitiveFormat,
+} from "./clipboard-watcher.js";
+
+describe("hasSensitiveFormat", () => {
+	const dword = (value: number) => {
+		const data = Buffer.alloc(4);
+		data.writeUInt32LE(value);
+		return data;
+	};
+	const check = (format: string, value: Buffer) =>
+		hasSensitiveFormat(["text/plain", format], () => value);
+
+	it("recognizes password manager markers", () => {
+		const read = () => Buffer.alloc(0);
+		expect(
+			hasSensitiveFormat(
+				["text/plain", "org.nspasteboard.ConcealedType"],
+				read,
+			),
+		).toBe(true);
+		expect(
+			hasSensitiveFormat(
+				["ExcludeClipboardContentFromMonitorProcessing"],
+				read,
+			),
+		).toBe(true);
+		expect(hasSensitiveFormat(["text/plain", "text/html"], read)).toBe(false);
+	});
+
+	it("excludes a Windows copy only when history is disallowed", () => {
+		expect(check("CanIncludeInClipboardHistory", dword(0))).toBe(true);
+		expect(check("CanIncludeInClipboardHistory", dword(1))).toBe(false);
+		expect(check("CanIncludeInClipboardHistory", Buffer.alloc(0))).toBe(
+			false,
+		);
+	});
+
+	it("excludes a KDE copy only when its hint is secret", () => {
+		expect(check("x-kde-passwordManagerHint", Buffer.from("secret"))).toBe(
+			true,
+		);
+		expect(check("x-kde-passwordManagerHint", Buffer.from("public"))).toBe(
+			false,
+		);
+	});
+});
+
+describe("hasClipboardChanged", () => {
+	it("treats the first snapshot as a change", () => {
+		expect(hasClipboardChanged({ text: "hello" }, null)).toBe(true);
+	});
+
+	it("detects text, RTF and HTML changes", () => {
+		expect(hasClipboardChanged({ text: "a" }, { text: "b" })).toBe(true);
+		expect(
+			hasClipboardChanged({ text: "a", rtf: "{\\rtf1 a}" }, { text: "a" }),
+		).toBe(true);
+		expect(
+			hasClipboardChanged({ text: "a", html: "<b>a</b>" }, { text: "a" }),
+		).toBe(true);
+	});
+
+	it("detects a different image by its hash", () => {
+		const image = (hash: string) => ({
+			hash,
+			width: 1,
+			height: 1,
+			toPNG: () => Buffer.alloc(0),
+		});
+
+		expect(
+			hasClipboardChanged(
+				{ text: "", image: image("a") },
+				{ text: "", image: image("b") },
+			),
+		).toBe(true);
+		expect(
+			hasClipboardChanged(
+				{ text: "", image: image("a") },
+				{ text: "", image: image("a") },
+			),
+		).toBe(false);
+	});
+
+	it("detects a different file list", () => {
+		expect(
+			hasClipboardChanged(
+				{ text: "a.txt", files: ["/tmp/a.txt", "/tmp/b.txt"] },
+				{ text: "a.txt", files: ["/tmp/a.txt"] },
+			),
+		).toBe(true);
+	});
+
+	it("ignores identical snapshots", () => {
+		expect(
+			hasClipboardChanged(
+				{ text: "a", rtf: undefined },
+				{ text: "a", rtf: undefined },
+			),
+		).toBe(false);
+	});
+});
+
+describe("createClipboardWatcher", () => {
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	const setup = (initial: ClipboardSnapshot) => {
+		let current = initial;
+		const onChange = vi.fn();
+		const watcher = createClipboardWatcher({
+			readClipboard: () => current,
+			onChange,
+			intervalMs: 100,
+		});
+		return {
+			watcher,
+			onChange,
+			copy: (snapshot: ClipboardSnapshot) => {
+				current = snapshot;
+			},
+		};
+	};
+
+	it("does not record clipboard contents present at startup", () => {
+		const { watcher, onChange } = setup({ text: "before launch" });
+
+		watcher.start();
+		vi.advanceTimersByTime(500);
+
+		expect(onChange).not.toHaveBeenCalled();
+		watcher.stop();
+	});
+
+	it("reports each new copy once", () => {
+		const { watcher, onChange, copy } = setup({ text: "" });
+
+		watcher.start();
+		copy({ text: "first" });
+		vi.advanceTimersByTime(300);
+		copy({ text: "second", rtf: "{\\rtf1 second}" });
+		vi.advanceTimersByTime(100);
+
+		expect(onChange).toHaveBeenCalledTimes(2);
+		expect(onChange).toHaveBeenLastCalledWith({
+			text: "second",
+			rtf: "{\\rtf1 second}",
+		});
+		watcher.stop();
+	});
+
+	it("ignores empty clipboard contents", () => {
+		const { watcher, onChange, copy } = setup({ text: "seen" });
+
+		watcher.start();
+		copy({ text: "" });
+		vi.advanceTimersByTime(100);
+		copy({ text: "seen" });
+		vi.advanceTimersByTime(100);
+
+		expect(onChange).not.toHaveBeenCalled();
+		watcher.stop();
+	});
+
+	it("keeps running when the change handler throws", () => {
+		const { watcher, onChange, copy } = setup({ text: "" });
+		const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+		onChange.mockImplementationOnce(() => {
+			throw new Error("insert failed");
+		});
+
+		watcher.start();
+		copy({ text: "one" });
+		vi.advanceTimersByTime(100);
+		copy({ text: "two" });
+		vi.advanceTimersByTime(100);
+
+		expect(onChange).toHaveBeenCalledTimes(2);
+		expect(watcher.isRunning()).toBe(true);
+		watcher.stop();
+		expect(watcher.isRunning()).toBe(false);
+		consoleSpy.mockRestore();
+	});
+
+	it("reads on change events once the listener is ready", () => {
+		let current: ClipboardSnapshot = { text: "" };
+		const listener = { start: vi.fn(), stop: vi.fn() };
+		const readClipboard = vi.fn(() => current);
+		const onChange = vi.fn();
+		const watcher = createClipboardWatcher({
+			readClipboard,
+			onChange,
+			intervalMs: 100,
+			listener,
+		});
+
+		watcher.start();
+		const events = listener.start.mock.calls[0][0];
+		current = { text: "while starting" };
+		vi.advanceTimersByTime(100);
+		expect(onChange).toHaveBeenCalledTimes(1);
+
+		events.onReady();
+		expect(watcher.isListening()).toBe(true);
+		readClipboard.mockClear();
+		vi.advanceTimersByTime(1000);
+		expect(readClipboard).not.toHaveBeenCalled();
+
+		current = { text: "copied" };
+		events.onChange();
+		events.onChange();
+		vi.advanceTimersByTime(50);
+		expect(readClipboard).toHaveBeenCalledTimes(1);
+		expect(onChange).toHaveBeenLastCalledWith({ text: "copied" });
+
+		watcher.stop();
+		expect(listener.stop).toHaveBeenCalled();
+	});
+
+	it("falls back to polling when the listener stops", () => {
+		const listener = { start: vi.fn(), stop: vi.fn() };
+		const consoleSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+		let current: ClipboardSnapshot = { text: "" };
+		const onChange = vi.fn();
+		const watcher = createClipboardWatcher({
+			readClipboard: () => current,
+			onChange,
+			intervalMs: 100,
+			listener,
+		});
+
+		watcher.start();
+		const events = listener.start.mock.calls[0][0];
+		events.onReady();
+		events.onExit(new Error("clipnotify not found"));
+		current = { text: "polled" };
+		vi.advanceTimersByTime(100);
+
+		expect(watcher.isListening()).toBe(false);
+		expect(onChange).toHaveBeenCalledWith({ text: "polled" });
+		expect(consoleSpy).toHaveBeenCalled();
+		watcher.stop();
+		consoleSpy.mockRestore();
+	});
+
+	it("polls at a new interval at once", () => {
+		let current: ClipboardSnapshot = { text: "" };
+		const onChange = vi.fn();
+		const watcher = createClipboardWatcher({
+			readClipboard: () => current,
+			onChange,
+			intervalMs: 100,
+		});
+
+		watcher.start();
+		watcher.setPollInterval(500);
+		current = { text: "slow" };
+		vi.advanceTimersByTime(400);
+		expect(onChange).not.toHaveBeenCalled();
+
+		vi.advanceTimersByTime(100);
+		expect(onChange).toHaveBeenCalledWith({ text: "slow" });
+		watcher.stop();
+	});
+});
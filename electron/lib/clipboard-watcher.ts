@@ -0,0 +1,258 @@
+import type { ClipboardFormat } from "./windows-formats.js";
+
+/**
+ * Image currently on the clipboard.
+ * Encoding is deferred so unchanged images are only hashed, never re-encoded.
+ */
+export type ClipboardImage = {
+	/** Digest of the raw bitmap, used for change detection */
+	hash: string;
+	width: number;
+	height: number;
+	/** Encodes the image as PNG bytes for storage */
+	toPNG: () => Buffer;
+};
+
+/**
+ * Formats the watcher reads from the system clipboard on every tick.
+ */
+export type ClipboardSnapshot = {
+	text: string;
+	rtf?: string;
+	html?: string;
+	image?: ClipboardImage;
+	/** Paths of copied files, when a file manager owns the clipboard */
+	files?: string[];
+	/** Set when the source app marked the copy as secret (password managers) */
+	sensitive?: boolean;
+	/** Set when the copy came from another Apple device (Universal Clipboard) */
+	remote?: boolean;
+	/** Registered Windows formats, such as Office's, kept raw */
+	formats?: ClipboardFormat[];
+};
+
+/**
+ * Clipboard formats password managers add to mark a copy as secret and
+ * ask clipboard managers not to record it.
+ */
+const SENSITIVE_CLIPBOARD_FORMATS = [
+	// macOS (nspasteboard.org convention; 1Password, Bitwarden, KeePassXC)
+	"org.nspasteboard.ConcealedType",
+	// Windows: asks clipboard monitors to skip it
+	"ExcludeClipboardContentFromMonitorProcessing",
+];
+
+/**
+ * Clipboard formats whose value says whether a copy is secret; present
+ * with any other value, they allow recording it.
+ */
+const SENSITIVE_FORMAT_VALUES = new Map<string, (data: Buffer) => boolean>([
+	// Windows: a DWORD; 0 keeps the copy out of clipboard history
+	[
+		"CanIncludeInClipboardHistory",
+		(data) => data.length >= 4 && data.readUInt32LE(0) === 0,
+	],
+	// KDE / KeePassXC on Linux
+	[
+		"x-kde-passwordManagerHint",
+		(data) => data.toString("utf-8").trim() === "secret",
+	],
+]);
+
+/**
+ * Default interval between clipboard reads (ms).
+ * Electron exposes no clipboard change event, so the main process polls.
+ */
+export const DEFAULT_WATCH_INTERVAL_MS = 1000;
+
+/**
+ * Interval of the backup read while change events arrive (ms), catching
+ * a change that fell between two of the listener's event waits.
+ */
+const LISTENING_INTERVAL_MS = 10_000;
+
+/**
+ * Delay between a change event and reading the clipboard (ms). Apps
+ * write their formats one after another, and one copy can raise
+ * several events; they are read together once it settles.
+ */
+const CHANGE_SETTLE_MS = 50;
+
+/**
+ * Notifies the watcher of clipboard changes, so it does not have to read
+ * the clipboard on every tick.
+ */
+export type ClipboardChangeListener = {
+	/**
+	 * Starts listening. `onReady` is called once changes are being
+	 * reported, `onChange` on each change, and `onExit` if the listener
+	 * fails or stops on its own.
+	 */
+	start: (events: {
+		onReady: () => void;
+		onChange: () => void;
+		onExit: (error: Error) => void;
+	}) => void;
+	stop: () => void;
+};
+
+/**
+ * Checks whether a snapshot carries anything worth recording.
+ * Pure function.
+ */
+export const isCapturableSnapshot = (snapshot: ClipboardSnapshot): boolean =>
+	snapshot.text.length > 0 ||
+	snapshot.image !== undefined ||
+	(snapshot.files?.length ?? 0) > 0;
+
+/**
+ * Checks whether a clipboard's formats mark it as sensitive. Formats
+ * that carry a value are read with `readFormat` and count only for the
+ * value that marks a secret.
+ */
+export const hasSensitiveFormat = (
+	formats: readonly string[],
+	readFormat: (format: string) => Buffer,
+): boolean =>
+	formats.some((format) => {
+		if (SENSITIVE_CLIPBOARD_FORMATS.includes(format)) return true;
+		const isSecret = SENSITIVE_FORMAT_VALUES.get(format);
+		return isSecret?.(readFormat(format)) ?? false;
+	});
+
+/**
+ * Checks whether the clipboard changed between two snapshots.
+ * Pure function - compares every tracked format.
+ */
+export const hasClipboardChanged = (
+	current: ClipboardSnapshot,
+	previous: ClipboardSnapshot | null,
+): boolean =>
+	previous === null ||
+	current.text !== previous.text ||
+	(current.rtf ?? null) !== (previous.rtf ?? null) ||
+	(current.html ?? null) !== (previous.html ?? null) ||
+	(current.image?.hash ?? null) !== (previous.image?.hash ?? null) ||
+	(current.files ?? []).join("\n") !== (previous.files ?? []).join("\n");
+
+export type ClipboardWatcherDeps = {
+	/** Reads the current system clipboard */
+	readClipboard: () => ClipboardSnapshot;
+	/** Called once per detected change with the new snapshot */
+	onChange: (snapshot: ClipboardSnapshot) => void;
+	/** Polling interval in milliseconds */
+	intervalMs?: number;
+	/** Change events to read on instead of polling, where available */
+	listener?: ClipboardChangeListener | null;
+};
+
+/**
+ * Creates a clipboard watcher that runs in the main process.
+ * Captures keep working while the renderer is hidden or throttled.
+ *
+ * With a listener, the clipboard is read when it reports a change (and
+ * rarely otherwise) instead of every interval, which keeps the app idle
+ * while nothing is copied. Polling runs until the listener is ready and
+ * again if it stops.
+ *
+ * The clipboard contents present at start are treated as already seen,
+ * so launching the app does not record whatever was copied before it.
+ */
+export const createClipboardWatcher = (deps: ClipboardWatcherDeps) => {
+	let intervalMs = deps.intervalMs ?? DEFAULT_WATCH_INTERVAL_MS;
+	let timer: NodeJS.Timeout | null = null;
+	let settleTimer: NodeJS.Timeout | null = null;
+	let running = false;
+	let listening = false;
+	let previous: ClipboardSnapshot | null = null;
+
+	const tick = (): void => {
+		try {
+			const current = deps.readClipboard();
+			if (!isCapturableSnapshot(current)) return;
+			if (!hasClipboardChanged(current, previous)) return;
+
+			previous = current;
+			deps.onChange(current);
+		} catch (error) {
+			console.error("Clipboard watcher failed to process change:", error);
+		}
+	};
+
+	const schedule = (interval: number): void => {
+		if (timer) clearInterval(timer);
+		timer = setInterval(tick, interval);
+	};
+
+	const onChange = (): void => {
+		if (!running || settleTimer) return;
+		settleTimer = setTimeout(() => {
+			settleTimer = null;
+			tick();
+		}, CHANGE_SETTLE_MS);
+	};
+
+	const start = (): void => {
+		if (running) return;
+		running = true;
+
+		try {
+			const initial = deps.readClipboard();
+			previous = isCapturableSnapshot(initial) ? initial : null;
+		} catch (error) {
+			console.error("Clipboard watcher failed to read initial state:", error);
+		}
+
+		schedule(intervalMs);
+		deps.listener?.start({
+			onReady: () => {
+				if (!running) return;
+				listening = true;
+				schedule(LISTENING_INTERVAL_MS);
+				// Anything copied while the listener started
+				tick();
+			},
+			onChange,
+			onExit: (error) => {
+				if (!running) return;
+				console.warn("Clipboard listener stopped, polling instead:", error);
+				listening = false;
+				schedule(intervalMs);
+			},
+		});
+	};
+
+	const stop = (): void => {
+		running = false;
+		listening = false;
+		deps.listener?.stop();
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+		if (settleTimer) {
+			clearTimeout(settleTimer);
+			settleTimer = null;
+		}
+	};
+
+	const isRunning = (): boolean => running;
+
+	/**
+	 * Changes the polling interval, e.g. to poll less often on battery.
+	 * Applies at once while polling; while listening it applies if the
+	 * listener stops.
+	 */
+	const setPollInterval = (ms: number): void => {
+		if (ms === intervalMs) return;
+		intervalMs = ms;
+		if (running && !listening) schedule(intervalMs);
+	};
+
+	/**
+	 * Whether changes currently arrive as events rather than by polling.
+	 */
+	const isListening = (): boolean => listening;
+
+	return { start, stop, isRunning, isListening, setPollInterval };
+};
@@ -0,0 +1,77 @@
+import { describe, expect, it } from "vitest";
+import { parseClipctlArgs } from "./clipctl-args.js";
+
+describe("parseClipctlArgs", () => {
+	it("shows help without a command", () => {
+		expect(parseClipctlArgs([])).toEqual({ command: "help" });
+		expect(parseClipctlArgs(["--help"])).toEqual({ command: "help" });
+	});
+
+	it("parses list options", () => {
+		expect(parseClipctlArgs(["list"])).toEqual({
+			command: "list",
+			limit: 20,
+			json: false,
+		});
+		expect(parseClipctlArgs(["list", "-n", "5", "--json"])).toEqual({
+			command: "list",
+			limit: 5,
+			json: true,
+		});
+		expect(() => parseClipctlArgs(["list", "-n"])).toThrow(
+			"Invalid limit: (missing)",
+		);
+		expect(() => parseClipctlArgs(["list", "--all"])).toThrow(
+			"Unknown option for list: --all",
+		);
+	});
+
+	it("parses item ids", () => {
+		expect(parseClipctlArgs(["get", "12"])).toEqual({ command: "get", id: 12 });
+		expect(parseClipctlArgs(["copy", "3"])).toEqual({ command: "copy", id: 3 });
+		expect(() => parseClipctlArgs(["get", "x"])).toThrow("Invalid item id: x");
+		expect(() => parseClipctlArgs(["copy"])).toThrow(
+			"Invalid item id: (missing)",
+		);
+	});
+
+	it("parses audit options, counting durations back from now", () => {
+		const now = Date.parse("2026-03-01T12:00:00Z");
+		expect(parseClipctlArgs(["audit"], now)).toEqual({
+			command: "audit",
+			limit: 20,
+			since: null,
+			json: false,
+		});
+		expect(
+			parseClipctlArgs(["audit", "--since", "8h", "-n", "5"], now),
+		).toEqual({
+			command: "audit",
+			limit: 5,
+			since: "2026-03-01T04:00:00.000Z",
+			json: false,
+		});
+		expect(
+			parseClipctlArgs(["audit", "--since", "2026-02-28", "--json"], now),
+		).toMatchObject({ since: "2026-02-28", json: true });
+		expect(() => parseClipctlArgs(["audit", "--since", "soon"])).toThrow(
+			"Invalid time: soon",
+		);
+	});
+
+	it("reads added text from stdin for -", () => {
+		expect(parseClipctlArgs(["add", "-"])).toEqual({
+			command: "add",
+			text: null,
+		});
+		expect(parseClipctlArgs(["add", "hello", "world"])).toEqual({
+			command: "add",
+			text: "hello world",
+		});
+		expect(() => parseClipctlArgs(["add"])).toThrow("add needs text");
+	});
+
+	it("rejects unknown commands", () => {
+		expect(() => parseClipctlArgs(["paste"])).toThrow("Unknown command: paste");
+	});
+});
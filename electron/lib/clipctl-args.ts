@@ -0,0 +1,137 @@
+import { DEFAULT_LIST_LIMIT } from "./control-protocol.js";
+
+/**
+ * A parsed `clipctl` command line.
+ */
+export type ClipctlCommand =
+	| { command: "help" }
+	| { command: "list"; limit: number; json: boolean }
+	| { command: "get"; id: number }
+	| { command: "copy"; id: number }
+	/** `text` is null for `add -`, which reads it from stdin */
+	| { command: "add"; text: string | null }
+	/** `since` is an ISO 8601 time, or null for the whole log */
+	| { command: "audit"; limit: number; since: string | null; json: boolean };
+
+export const CLIPCTL_USAGE = `Usage: clipctl <command>
+
+Commands:
+  list [-n N] [--json]  List the N most recent items (default ${DEFAULT_LIST_LIMIT})
+  get <id>              Print an item's content
+  copy <id>             Place an item on the clipboard
+  add -                 Add text from stdin to history
+  add <text>            Add text to history
+  audit [--since T] [-n N] [--json]
+                        List recent reveals, copies, pastes and exports;
+                        T is an ISO 8601 time or a duration (30m, 8h, 2d)
+  help                  Show this message
+
+Talks to the running Clipboard Manager; set CLIPCTL_SOCKET to use another
+socket.`;
+
+const DURATION_UNITS_MS: Record<string, number> = {
+	m: 60_000,
+	h: 3_600_000,
+	d: 86_400_000,
+};
+
+/**
+ * Reads `--since`: a duration ago such as `8h`, or an ISO 8601 time.
+ */
+const parseSinceArg = (value: string | undefined, now: number): string => {
+	const duration = value?.match(/^(\d+)([mhd])$/);
+	if (duration) {
+		const ago = Number(duration[1]) * DURATION_UNITS_MS[duration[2]];
+		return new Date(now - ago).toISOString();
+	}
+	if (!value || Number.isNaN(Date.parse(value))) {
+		throw new Error(`Invalid time: ${value ?? "(missing)"}`);
+	}
+	return value;
+};
+
+const parseLimitArg = (value: string | undefined): number => {
+	const limit = Number(value);
+	if (!Number.isInteger(limit) || limit < 1) {
+		throw new Error(`Invalid limit: ${value ?? "(missing)"}`);
+	}
+	return limit;
+};
+
+const parseIdArg = (value: string | undefined): number => {
+	const id = Number(value);
+	if (!value || !Number.isInteger(id) || id <= 0) {
+		throw new Error(`Invalid item id: ${value ?? "(missing)"}`);
+	}
+	return id;
+};
+
+/**
+ * Parses the arguments after `clipctl`.
+ * Pure function.
+ *
+ * @param now - Time durations in `--since` count back from (ms)
+ * @throws with a message for the user if the arguments are invalid
+ */
+export const parseClipctlArgs = (
+	args: readonly string[],
+	now = Date.now(),
+): ClipctlCommand => {
+	const [command, ...rest] = args;
+	switch (command) {
+		case undefined:
+		case "help":
+		case "-h":
+		case "--help":
+			return { command: "help" };
+		case "list": {
+			let limit = DEFAULT_LIST_LIMIT;
+			let json = false;
+			for (let i = 0; i < rest.length; i++) {
+				const arg = rest[i];
+				if (arg === "--json") {
+					json = true;
+				} else if (arg === "-n" || arg === "--limit") {
+					limit = parseLimitArg(rest[++i]);
+				} else {
+					throw new Error(`Unknown option for list: ${arg}`);
+				}
+			}
+			return { command, limit, json };
+		}
+		case "audit": {
+			let limit = DEFAULT_LIST_LIMIT;
+			let since: string | null = null;
+			let json = false;
+			for (let i = 0; i < rest.length; i++) {
+				const arg = rest[i];
+				if (arg === "--json") {
+					json = true;
+				} else if (arg === "-n" || arg === "--limit") {
+					limit = parseLimitArg(rest[++i]);
+				} else if (arg === "--since") {
+					since = parseSinceArg(rest[++i], now);
+				} else {
+					throw new Error(`Unknown option for audit: ${arg}`);
+				}
+			}
+			return { command, limit, since, json };
+		}
+		case "get":
+		case "copy":
+			if (rest.length > 1) {
+				throw new Error(`${command} takes one item id`);
+			}
+			return { command, id: parseIdArg(rest[0]) };
+		case "add":
+			if (rest.length === 0) {
+				throw new Error("add needs text, or - to read stdin");
+			}
+			return {
+				command,
+				text: rest.length === 1 && rest[0] === "-" ? null : rest.join(" "),
+			};
+		default:
+			throw new Error(`Unknown command: ${command}`);
+	}
+};
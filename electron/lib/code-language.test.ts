@@ -0,0 +1,60 @@
+import { describe, expect, it } from "vitest";
+import {
+	type CodeLanguage,
+	detectCodeLanguage,
+	isCodeLanguage,
+} from "./code-language.js";
+
+const expectLanguage = (lines: string[], language: CodeLanguage | null) =>
+	expect(detectCodeLanguage(lines.join("\n"))).toBe(language);
+
+describe("detectCodeLanguage", () => {
+	it("recognizes common languages", () => {
+		expectLanguage(
+			['const a = require("x");', "console.log(a);"],
+			"javascript",
+		);
+		expectLanguage(["def add(a, b):", "    return a + b"], "python");
+		expectLanguage(
+			["package main", "func main() {", "\tx := 1", "\tfmt.Println(x)", "}"],
+			"go",
+		);
+		expectLanguage(
+			["fn main() {", "    let mut x = 5;", '    println!("{}", x);', "}"],
+			"rust",
+		);
+		expectLanguage(["SELECT id, name FROM users WHERE id = 1;"], "sql");
+		expectLanguage([".btn {", "  color: #fff;", "  padding: 4px;", "}"], "css");
+	});
+
+	it("prefers a superset only when its own syntax appears", () => {
+		expectLanguage(
+			["interface User {", "  name: string;", "}", "const u = 1;"],
+			"typescript",
+		);
+		expectLanguage(
+			["#include <stdio.h>", "int main(void) {", '  printf("hi");', "}"],
+			"c",
+		);
+		expectLanguage(
+			["#include <iostream>", "int main() {", '  std::cout << "hi";', "}"],
+			"cpp",
+		);
+	});
+
+	it("trusts a shebang line", () => {
+		expectLanguage(["#!/usr/bin/env python3", "x = 1"], "python");
+		expectLanguage(["#!/bin/bash", "ls"], "shell");
+	});
+
+	it("gives up on weak evidence", () => {
+		expectLanguage(["foo;", "bar;"], null);
+	});
+});
+
+describe("isCodeLanguage", () => {
+	it("accepts only known languages", () => {
+		expect(isCodeLanguage("rust")).toBe(true);
+		expect(isCodeLanguage("cobol")).toBe(false);
+	});
+});
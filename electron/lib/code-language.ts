@@ -0,0 +1,287 @@
+/**
+ * Programming languages guessed for code items.
+ */
+export const CODE_LANGUAGES = [
+	"javascript",
+	"typescript",
+	"python",
+	"ruby",
+	"php",
+	"shell",
+	"go",
+	"rust",
+	"java",
+	"kotlin",
+	"csharp",
+	"swift",
+	"c",
+	"cpp",
+	"sql",
+	"html",
+	"css",
+] as const;
+
+export type CodeLanguage = (typeof CODE_LANGUAGES)[number];
+
+/**
+ * How strongly a pattern points at a language when it matches anywhere
+ * in the text.
+ */
+type LanguageSignal = [pattern: RegExp, weight: number];
+
+type LanguageRule = {
+	signals: LanguageSignal[];
+	/** Language whose signals also count, for supersets like C++ over C */
+	extends?: CodeLanguage;
+};
+
+/**
+ * Lowest score a language needs to be reported; one weak hint is not
+ * enough.
+ */
+const MIN_LANGUAGE_SCORE = 3;
+
+/**
+ * Characters of a code item looked at; the start of a snippet gives the
+ * language away as well as the whole of it.
+ */
+const MAX_DETECT_CHARS = 20_000;
+
+const SHEBANG_LANGUAGES: Array<[RegExp, CodeLanguage]> = [
+	[/^#!.*\b(?:ba|z|k|da)?sh\b/, "shell"],
+	[/^#!.*\bpython[\d.]*\b/, "python"],
+	[/^#!.*\bnode\b/, "javascript"],
+	[/^#!.*\bruby\b/, "ruby"],
+	[/^#!.*\bphp\b/, "php"],
+];
+
+/**
+ * Signals per language. Weights are rough: 3 for constructs found in one
+ * language only, 1-2 for ones a few languages share. Listed in tie-break
+ * order, with a base language before the one extending it.
+ */
+const LANGUAGE_RULES: Record<CodeLanguage, LanguageRule> = {
+	javascript: {
+		signals: [
+			[/\b(?:const|let|var)\s+[\w${}[\], ]+\s*=/, 1],
+			[/=>/, 1],
+			[/\bfunction\s*\w*\s*\(/, 2],
+			[/\bconsole\.\w+\(/, 3],
+			[/\brequire\(["'][^"']+["']\)/, 3],
+			[/\bimport\s[^;\n]*\bfrom\s+["'][^"']+["']/, 3],
+			[/\bexport\s+(?:default|const|function)\b/, 2],
+			[/\b(?:document|window)\.\w+/, 2],
+			[/[!=]==/, 2],
+		],
+	},
+	typescript: {
+		extends: "javascript",
+		signals: [
+			[/\binterface\s+\w+\s*(?:<[^>]*>\s*)?\{/, 3],
+			[/^\s*(?:export\s+)?type\s+\w+(?:<[^>]*>)?\s*=/m, 3],
+			[
+				/[\w)]\??\s*:\s*(?:string|number|boolean|void|unknown|any|never)\b/,
+				3,
+			],
+			[/\bas\s+(?:const|unknown|string|number)\b/, 2],
+			[/\b(?:readonly|keyof|implements)\s/, 2],
+		],
+	},
+	python: {
+		signals: [
+			[/^\s*def\s+\w+\s*\([^)]*\)\s*(?:->\s*[\w[\], .]+)?:\s*$/m, 3],
+			[/^\s*class\s+\w+(?:\([^)]*\))?:\s*$/m, 3],
+			[/^\s*(?:from\s+[\w.]+\s+)?import\s+[\w.]+(?:\s+as\s+\w+)?\s*$/m, 1],
+			[/^\s*(?:elif\b|except\b|with\s.+\bas\s+\w+:)/m, 3],
+			[/^\s*(?:if|for|while)\s.+:\s*$/m, 2],
+			[/\bself\.\w+/, 2],
+			[/\bprint\(/, 1],
+			[/\b(?:None|True|False)\b/, 1],
+			[/^\s*@\w+/m, 1],
+		],
+	},
+	ruby: {
+		signals: [
+			[/^\s*def\s+\w+[?!]?(?:\([^)]*\))?\s*$/m, 2],
+			[/^\s*end\s*$/m, 2],
+			[/\bputs\s/, 3],
+			[/\brequire\s+["'][^"']+["']\s*$/m, 2],
+			[/\.each\s+do\s*\|/, 3],
+			[/\battr_(?:accessor|reader|writer)\b/, 3],
+			[/@\w+\s*=/, 1],
+		],
+	},
+	php: {
+		signals: [
+			[/<\?php/, 5],
+			[/\$\w+\s*=/, 1],
+			[/\$\w+->\w+/, 3],
+			[/\becho\s/, 1],
+			[/\b(?:public|private)\s+function\b/, 2],
+		],
+	},
+	shell: {
+		signals: [
+			[/^\s*(?:sudo|apt(?:-get)?|brew|npm|pnpm|yarn|pip|cd|export)\s/m, 2],
+			[/^\s*(?:fi|done|esac)\s*$/m, 3],
+			[/^\s*(?:if|while)\s+\[\[?\s/m, 3],
+			[/\$\{?\w+\}?/, 1],
+			[/\|\s*(?:grep|awk|sed|xargs|sort|head|tail)\b/, 3],
+			[/^\s*echo\s/m, 1],
+			[/^\s*\w+=\S/m, 1],
+		],
+	},
+	go: {
+		signals: [
+			[/^\s*package\s+\w+\s*$/m, 2],
+			[/\bfunc\s+(?:\([^)]*\)\s*)?\w+\s*\(/, 3],
+			[/:=/, 2],
+			[/\bfmt\.\w+\(/, 3],
+			[/^\s*import\s+\(/m, 3],
+			[/\bif\s+err\s*!=\s*nil\b/, 3],
+		],
+	},
+	rust: {
+		signals: [
+			[/\bfn\s+\w+\s*(?:<[^>]*>)?\s*\(/, 3],
+			[/\blet\s+mut\b/, 3],
+			[/\bimpl\b(?:\s*<[^>]*>)?\s+\w+/, 3],
+			[/\b\w+!\(/, 1],
+			[/\buse\s+[\w:]+::/, 2],
+			[/&(?:mut\s|str\b|'\w+)/, 2],
+			[/\bpub(?:\(crate\))?\s+(?:fn|struct|enum|mod)\b/, 3],
+		],
+	},
+	java: {
+		signals: [
+			[/\bpublic\s+(?:static\s+)?(?:final\s+)?(?:class|void|interface)\b/, 2],
+			[/\bSystem\.out\.print/, 3],
+			[/^\s*import\s+java\./m, 3],
+			[/@Override\b/, 2],
+			[
+				/\b(?:private|protected)\s+(?:final\s+)?\w+(?:<[^>]*>)?\s+\w+\s*[;=]/,
+				2,
+			],
+			[/\bnew\s+\w+(?:<[^>]*>)?\(/, 1],
+		],
+	},
+	kotlin: {
+		signals: [
+			[/\bfun\s+(?:<[^>]*>\s*)?[\w.]+\s*\(/, 3],
+			[/\bval\s+\w+(?:\s*:\s*\w+)?\s*=/, 2],
+			[/\b(?:data|sealed|companion)\s+(?:class|object)\b/, 3],
+			[/\bprintln\(/, 1],
+		],
+	},
+	csharp: {
+		signals: [
+			[/^\s*using\s+System(?:\.\w+)*;/m, 3],
+			[/^\s*namespace\s+[\w.]+/m, 2],
+			[/\bConsole\.Write(?:Line)?\(/, 3],
+			[/\{\s*get;\s*(?:private\s+)?set;\s*\}/, 3],
+			[/\bpublic\s+(?:static\s+)?(?:class|void|async)\b/, 1],
+			[/\bvar\s+\w+\s*=\s*new\b/, 1],
+		],
+	},
+	swift: {
+		signals: [
+			[/\bfunc\s+\w+\s*\([^)]*\)\s*(?:->|\{)/, 2],
+			[/^\s*import\s+(?:UIKit|Foundation|SwiftUI|AppKit)\s*$/m, 3],
+			[/\b(?:guard|if)\s+let\b/, 3],
+			[/\b(?:let|var)\s+\w+\s*:\s*[A-Z]\w*/, 1],
+			[/\bstruct\s+\w+\s*:\s*View\b/, 3],
+		],
+	},
+	c: {
+		signals: [
+			[/^\s*#include\s*[<"][\w/]+\.h[>"]/m, 3],
+			[/\bint\s+main\s*\(/, 2],
+			[/\bprintf\s*\(/, 2],
+			[/\b(?:malloc|free|sizeof)\s*\(/, 2],
+			[/^\s*#define\s+\w+/m, 2],
+			[/\b(?:unsigned|char|void|int)\s*\*+\s*\w+/, 1],
+		],
+	},
+	cpp: {
+		extends: "c",
+		signals: [
+			[/\bstd::\w+/, 3],
+			[/^\s*#include\s*<\w+>/m, 3],
+			[/\b(?:cout|cin|cerr)\s*<</, 3],
+			[/\btemplate\s*</, 3],
+			[/\bnullptr\b/, 2],
+			[/\bnamespace\s+\w+\s*\{/, 2],
+		],
+	},
+	sql: {
+		signals: [
+			[/^\s*SELECT\s[\s\S]+?\sFROM\s/i, 4],
+			[/^\s*(?:INSERT\s+INTO|DELETE\s+FROM|UPDATE\s+\w+\s+SET)\s/im, 4],
+			[/^\s*(?:CREATE|ALTER|DROP)\s+(?:TABLE|INDEX|VIEW)\s/im, 4],
+			[/\b(?:WHERE|JOIN|GROUP\s+BY|ORDER\s+BY)\b/, 1],
+		],
+	},
+	html: {
+		signals: [
+			[/<!DOCTYPE\s+html/i, 5],
+			[/<\/(?:html|head|body|div|span|p|a|ul|li|table|section)>/i, 3],
+			[/<\w+(?:\s+[\w-]+=["'][^"']*["'])+\s*\/?>/, 1],
+		],
+	},
+	css: {
+		signals: [
+			[/^\s*[.#]?[\w-]+(?:\s*[,>+~ ]\s*[.#:]?[\w-]+)*\s*\{\s*$/m, 1],
+			[/^\s*[a-z-]+\s*:\s*[^;{}]+;\s*$/m, 1],
+			[/^\s*@(?:media|import|keyframes|font-face)\b/m, 3],
+			[/:\s*(?:#[0-9a-f]{3,8}|\d+(?:px|em|rem|%))\s*;/i, 2],
+		],
+	},
+};
+
+/**
+ * Sum of the weights of the signals matching `text`.
+ * Pure function.
+ */
+const scoreSignals = (text: string, signals: LanguageSignal[]): number =>
+	signals.reduce(
+		(score, [pattern, weight]) => (pattern.test(text) ? score + weight : score),
+		0,
+	);
+
+/**
+ * Guesses the programming language of a code snippet from a shebang line
+ * or from keyword and syntax patterns. A language that extends another
+ * (TypeScript, C++) wins only when its own signals match.
+ * Pure function.
+ *
+ * @returns null if no language scores high enough
+ */
+export const detectCodeLanguage = (code: string): CodeLanguage | null => {
+	const text = code.slice(0, MAX_DETECT_CHARS).trim();
+	for (const [pattern, language] of SHEBANG_LANGUAGES) {
+		if (pattern.test(text)) return language;
+	}
+
+	let best: CodeLanguage | null = null;
+	let bestScore = MIN_LANGUAGE_SCORE - 1;
+	const scores = new Map<CodeLanguage, number>();
+	for (const language of CODE_LANGUAGES) {
+		const rule = LANGUAGE_RULES[language];
+		const score =
+			scoreSignals(text, rule.signals) +
+			(rule.extends ? (scores.get(rule.extends) ?? 0) : 0);
+		scores.set(language, score);
+		if (score > bestScore) {
+			best = language;
+			bestScore = score;
+		}
+	}
+	return best;
+};
+
+/**
+ * Whether a value names a code language.
+ * Pure function.
+ */
+export const isCodeLanguage = (value: unknown): value is CodeLanguage =>
+	CODE_LANGUAGES.includes(value as CodeLanguage);
@@ -0,0 +1,32 @@
+import { describe, expect, it } from "vitest";
+import {
+	isSameIdSet,
+	normalizeCollectionName,
+} from "./collection-repository.js";
+
+describe("normalizeCollectionName", () => {
+	it("trims and collapses whitespace but keeps case", () => {
+		expect(normalizeCollectionName("  Email   replies ")).toBe(
+			"Email replies",
+		);
+	});
+
+	it("rejects empty, oversized, and non-string names", () => {
+		expect(normalizeCollectionName(" ")).toBeNull();
+		expect(normalizeCollectionName("x".repeat(101))).toBeNull();
+		expect(normalizeCollectionName(null)).toBeNull();
+	});
+});
+
+describe("isSameIdSet", () => {
+	it("accepts any order of the same ids", () => {
+		expect(isSameIdSet([3, 1, 2], [1, 2, 3])).toBe(true);
+		expect(isSameIdSet([], [])).toBe(true);
+	});
+
+	it("rejects missing, extra, or repeated ids", () => {
+		expect(isSameIdSet([1, 2], [1, 2, 3])).toBe(false);
+		expect(isSameIdSet([1, 2, 4], [1, 2, 3])).toBe(false);
+		expect(isSameIdSet([1, 1, 2], [1, 2, 3])).toBe(false);
+	});
+});
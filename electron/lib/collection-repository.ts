@@ -0,0 +1,309 @@
+import type Database from "better-sqlite3";
+import {
+	type HistoryRow,
+	isValidId,
+	qualifiedHistoryColumns,
+} from "./history-repository.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Represents a row from the collections table, with its item count.
+ */
+export type CollectionRow = {
+	id: number;
+	name: string;
+	created_at: string;
+	item_count: number;
+};
+
+/**
+ * Request to move an item from one collection to another.
+ */
+export type MoveCollectionItemOptions = {
+	itemId: number;
+	fromCollectionId: number;
+	toCollectionId: number;
+};
+
+/**
+ * Maximum collection name length (characters).
+ */
+const MAX_COLLECTION_NAME_LENGTH = 100;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Normalizes a collection name for storage.
+ * Pure function.
+ *
+ * Names are trimmed and have internal whitespace collapsed; case is kept
+ * for display, but uniqueness is case-insensitive.
+ *
+ * @returns The normalized name, or null if it is empty or too long
+ */
+export const normalizeCollectionName = (name: unknown): string | null => {
+	if (typeof name !== "string") return null;
+	const normalized = name.trim().replace(/\s+/g, " ");
+	if (
+		normalized.length === 0 ||
+		normalized.length > MAX_COLLECTION_NAME_LENGTH
+	) {
+		return null;
+	}
+	return normalized;
+};
+
+/**
+ * Checks that two id lists contain the same ids, each exactly once.
+ * Pure function.
+ */
+export const isSameIdSet = (
+	ids: readonly number[],
+	expected: readonly number[],
+): boolean => {
+	if (ids.length !== expected.length) return false;
+	const remaining = new Set(expected);
+	for (const id of ids) {
+		if (!remaining.delete(id)) return false;
+	}
+	return remaining.size === 0;
+};
+
+/**
+ * Throws if an id is not a valid row id.
+ */
+const assertValidId = (id: unknown, label: string): number => {
+	if (!isValidId(id)) {
+		throw new Error(`Invalid ${label} id: ${id}`);
+	}
+	return id;
+};
+
+/**
+ * Throws if a collection name cannot be normalized.
+ */
+const assertValidName = (name: unknown): string => {
+	const normalized = normalizeCollectionName(name);
+	if (normalized === null) {
+		throw new Error(
+			`Invalid collection name: must be 1-${MAX_COLLECTION_NAME_LENGTH} characters`,
+		);
+	}
+	return normalized;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the collection repository.
+ * Owns every SQL statement against the `collections` and `collection_items`
+ * tables. Items are referenced, not copied: adding an item to a collection
+ * keeps it in history, and deleting it from history removes it everywhere.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createCollectionRepository = (getDb: () => Database.Database) => {
+	const getCollection = (id: number): CollectionRow | undefined =>
+		getDb()
+			.prepare(
+				"SELECT c.id, c.name, c.created_at, COUNT(ci.history_id) AS item_count FROM collections c LEFT JOIN collection_items ci ON ci.collection_id = c.id WHERE c.id = ? GROUP BY c.id",
+			)
+			.get(id) as CollectionRow | undefined;
+
+	const assertCollectionExists = (id: unknown): number => {
+		const validId = assertValidId(id, "collection");
+		const existing = getDb()
+			.prepare("SELECT id FROM collections WHERE id = ?")
+			.get(validId);
+		if (!existing) {
+			throw new Error(`Collection not found: ${validId}`);
+		}
+		return validId;
+	};
+
+	const assertItemExists = (id: unknown): number => {
+		const validId = assertValidId(id, "history item");
+		const existing = getDb()
+			.prepare("SELECT id FROM history WHERE id = ?")
+			.get(validId);
+		if (!existing) {
+			throw new Error(`History item not found: ${validId}`);
+		}
+		return validId;
+	};
+
+	/**
+	 * Rejects a name already used by another collection (case-insensitive).
+	 */
+	const assertNameAvailable = (name: string, exceptId?: number): void => {
+		const existing = getDb()
+			.prepare("SELECT id FROM collections WHERE name = ?")
+			.get(name) as { id: number } | undefined;
+		if (existing && existing.id !== exceptId) {
+			throw new Error(`Collection already exists: ${name}`);
+		}
+	};
+
+	/**
+	 * Appends an item to a collection. Items already in it keep their place.
+	 */
+	const appendItem = (collectionId: number, itemId: number): void => {
+		getDb()
+			.prepare(
+				"INSERT OR IGNORE INTO collection_items (collection_id, history_id, position) SELECT ?, ?, COALESCE(MAX(position) + 1, 0) FROM collection_items WHERE collection_id = ?",
+			)
+			.run(collectionId, itemId, collectionId);
+	};
+
+	/**
+	 * Lists collections alphabetically with their item counts.
+	 */
+	const listCollections = (): CollectionRow[] =>
+		getDb()
+			.prepare(
+				"SELECT c.id, c.name, c.created_at, COUNT(ci.history_id) AS item_count FROM collections c LEFT JOIN collection_items ci ON ci.collection_id = c.id GROUP BY c.id ORDER BY c.name COLLATE NOCASE",
+			)
+			.all() as CollectionRow[];
+
+	const createCollection = (name: string): CollectionRow => {
+		const validName = assertValidName(name);
+		assertNameAvailable(validName);
+
+		const { lastInsertRowid } = getDb()
+			.prepare("INSERT INTO collections (name) VALUES (?)")
+			.run(validName);
+		const created = getCollection(Number(lastInsertRowid));
+		if (!created) {
+			throw new Error("Failed to create collection");
+		}
+		return created;
+	};
+
+	const renameCollection = (id: number, name: string): CollectionRow => {
+		const validId = assertCollectionExists(id);
+		const validName = assertValidName(name);
+		assertNameAvailable(validName, validId);
+
+		getDb()
+			.prepare("UPDATE collections SET name = ? WHERE id = ?")
+			.run(validName, validId);
+		return getCollection(validId) as CollectionRow;
+	};
+
+	/**
+	 * Deletes a collection. Its items stay in history.
+	 */
+	const deleteCollection = (id: number): void => {
+		getDb()
+			.prepare("DELETE FROM collections WHERE id = ?")
+			.run(assertValidId(id, "collection"));
+	};
+
+	/**
+	 * Lists the items of a collection in their saved order.
+	 */
+	const listCollectionItems = (collectionId: number): HistoryRow[] =>
+		getDb()
+			.prepare(
+				`SELECT ${qualifiedHistoryColumns("h")} FROM collection_items ci JOIN history h ON h.id = ci.history_id WHERE ci.collection_id = ? ORDER BY ci.position, h.id`,
+			)
+			.all(assertCollectionExists(collectionId)) as HistoryRow[];
+
+	/**
+	 * Copies an item into a collection, at the end.
+	 */
+	const addItem = (collectionId: number, itemId: number): void => {
+		appendItem(
+			assertCollectionExists(collectionId),
+			assertItemExists(itemId),
+		);
+	};
+
+	const removeItem = (collectionId: number, itemId: number): void => {
+		getDb()
+			.prepare(
+				"DELETE FROM collection_items WHERE collection_id = ? AND history_id = ?",
+			)
+			.run(
+				assertValidId(collectionId, "collection"),
+				assertValidId(itemId, "history item"),
+			);
+	};
+
+	/**
+	 * Moves an item out of one collection and onto the end of another.
+	 */
+	const moveItem = (options: MoveCollectionItemOptions): void => {
+		const fromId = assertCollectionExists(options.fromCollectionId);
+		const toId = assertCollectionExists(options.toCollectionId);
+		const itemId = assertItemExists(options.itemId);
+		if (fromId === toId) return;
+
+		const db = getDb();
+		db.transaction(() => {
+			const { changes } = db
+				.prepare(
+					"DELETE FROM collection_items WHERE collection_id = ? AND history_id = ?",
+				)
+				.run(fromId, itemId);
+			if (changes === 0) {
+				throw new Error(`Item ${itemId} is not in collection ${fromId}`);
+			}
+			appendItem(toId, itemId);
+		})();
+	};
+
+	/**
+	 * Saves a new order for a collection.
+	 * `itemIds` must list every item in the collection exactly once.
+	 */
+	const reorderItems = (collectionId: number, itemIds: number[]): void => {
+		const validId = assertCollectionExists(collectionId);
+		const db = getDb();
+		const current = (
+			db
+				.prepare(
+					"SELECT history_id FROM collection_items WHERE collection_id = ?",
+				)
+				.all(validId) as Array<{ history_id: number }>
+		).map((row) => row.history_id);
+
+		if (!Array.isArray(itemIds) || !isSameIdSet(itemIds, current)) {
+			throw new Error(
+				"Invalid collection order: must list every item in the collection once",
+			);
+		}
+
+		const update = db.prepare(
+			"UPDATE collection_items SET position = ? WHERE collection_id = ? AND history_id = ?",
+		);
+		db.transaction(() => {
+			itemIds.forEach((itemId, position) => {
+				update.run(position, validId, itemId);
+			});
+		})();
+	};
+
+	return {
+		listCollections,
+		createCollection,
+		renameCollection,
+		deleteCollection,
+		listCollectionItems,
+		addItem,
+		removeItem,
+		moveItem,
+		reorderItems,
+	};
+};
+
+export type CollectionRepository = ReturnType<
+	typeof createCollectionRepository
+>;
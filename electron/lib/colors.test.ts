@@ -0,0 +1,91 @@
+import { describe, expect, it } from "vitest";
+import {
+	convertColor,
+	formatColor,
+	isColorFormat,
+	normalizeColor,
+	parseColor,
+} from "./colors.js";
+
+describe("parseColor", () => {
+	it("reads hex in short and long forms, with alpha", () => {
+		expect(parseColor("#F80")).toEqual({ r: 255, g: 136, b: 0, a: 1 });
+		expect(parseColor(" #ff880080 ")).toEqual({
+			r: 255,
+			g: 136,
+			b: 0,
+			a: 128 / 255,
+		});
+		expect(parseColor("#f808")).toEqual({ r: 255, g: 136, b: 0, a: 136 / 255 });
+	});
+
+	it("reads rgb() and hsl() with commas or spaces and a slash", () => {
+		expect(parseColor("rgba(255, 0, 0, 0.5)")).toEqual({
+			r: 255,
+			g: 0,
+			b: 0,
+			a: 0.5,
+		});
+		expect(parseColor("rgb(100% 0% 0% / 50%)")).toEqual({
+			r: 255,
+			g: 0,
+			b: 0,
+			a: 0.5,
+		});
+		expect(parseColor("hsl(120deg, 100%, 25%)")).toEqual({
+			r: 0,
+			g: 128,
+			b: 0,
+			a: 1,
+		});
+	});
+
+	it("clamps out-of-range values and rejects other text", () => {
+		expect(parseColor("rgb(300, -5, 0)")).toEqual({ r: 255, g: 0, b: 0, a: 1 });
+		expect(parseColor("#ff88001")).toBeNull();
+		expect(parseColor("hsl(0, 100, 50)")).toBeNull();
+		expect(parseColor("rgb(1deg, 0, 0)")).toBeNull();
+		expect(parseColor("red")).toBeNull();
+	});
+});
+
+describe("formatColor", () => {
+	it("writes each notation, with alpha only when translucent", () => {
+		const orange = { r: 255, g: 136, b: 0, a: 1 };
+		expect(formatColor(orange, "hex")).toBe("#ff8800");
+		expect(formatColor(orange, "rgb")).toBe("rgb(255, 136, 0)");
+		expect(formatColor(orange, "hsl")).toBe("hsl(32, 100%, 50%)");
+
+		const translucent = { ...orange, a: 0.5 };
+		expect(formatColor(translucent, "hex")).toBe("#ff880080");
+		expect(formatColor(translucent, "rgb")).toBe("rgba(255, 136, 0, 0.5)");
+		expect(formatColor(translucent, "hsl")).toBe("hsla(32, 100%, 50%, 0.5)");
+	});
+});
+
+describe("normalizeColor", () => {
+	it("stores every notation of a color as the same hex", () => {
+		expect(normalizeColor("#F00")).toBe("#ff0000");
+		expect(normalizeColor("rgb(255, 0, 0)")).toBe("#ff0000");
+		expect(normalizeColor("hsl(0, 100%, 50%)")).toBe("#ff0000");
+		expect(normalizeColor("not a color")).toBeNull();
+	});
+});
+
+describe("convertColor", () => {
+	it("derives every notation from a stored value", () => {
+		expect(convertColor("#808080")).toEqual({
+			hex: "#808080",
+			rgb: "rgb(128, 128, 128)",
+			hsl: "hsl(0, 0%, 50%)",
+		});
+		expect(() => convertColor("blue")).toThrow("Invalid color: blue");
+	});
+});
+
+describe("isColorFormat", () => {
+	it("accepts only known notations", () => {
+		expect(isColorFormat("hsl")).toBe(true);
+		expect(isColorFormat("cmyk")).toBe(false);
+	});
+});
@@ -0,0 +1,198 @@
+/**
+ * Notations a color item can be copied in.
+ */
+export const COLOR_FORMATS = ["hex", "rgb", "hsl"] as const;
+
+export type ColorFormat = (typeof COLOR_FORMATS)[number];
+
+/**
+ * A color as 0-255 channels and an alpha from 0 to 1.
+ */
+export type Rgba = { r: number; g: number; b: number; a: number };
+
+const HEX_PATTERN = /^#([0-9a-f]{3,4}|[0-9a-f]{6}|[0-9a-f]{8})$/i;
+
+/** `rgb(…)`, `rgba(…)`, `hsl(…)` or `hsla(…)` and its arguments */
+const FUNCTION_PATTERN = /^(rgba?|hsla?)\(\s*([^)]*?)\s*\)$/i;
+
+/** A number, optionally a percentage or a hue in degrees */
+const ARGUMENT_PATTERN = /^(-?(?:\d+\.?\d*|\.\d+))(%|deg)?$/i;
+
+const clamp = (value: number, min: number, max: number): number =>
+	Math.min(max, Math.max(min, value));
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Splits function arguments given with commas (`255, 0, 0, 0.5`) or
+ * spaces and a slash (`255 0 0 / 50%`).
+ */
+const splitArguments = (text: string): string[] =>
+	text.includes(",")
+		? text.split(",").map((part) => part.trim())
+		: text.split(/\s*\/\s*|\s+/).filter(Boolean);
+
+/**
+ * Reads an alpha given as 0-1 or a percentage.
+ */
+const parseAlpha = (value: string | undefined): number | null => {
+	if (value === undefined) return 1;
+	const match = ARGUMENT_PATTERN.exec(value);
+	if (!match || match[2]?.toLowerCase() === "deg") return null;
+	const amount = Number(match[1]);
+	return clamp(match[2] ? amount / 100 : amount, 0, 1);
+};
+
+const hslToRgb = (
+	h: number,
+	s: number,
+	l: number,
+): [number, number, number] => {
+	const chroma = (1 - Math.abs(2 * l - 1)) * s;
+	const channel = (n: number) => {
+		const k = (n + h / 30) % 12;
+		return l - (chroma / 2) * Math.max(-1, Math.min(k - 3, 9 - k, 1));
+	};
+	return [channel(0), channel(8), channel(4)].map((value) =>
+		Math.round(value * 255),
+	) as [number, number, number];
+};
+
+/**
+ * Parses a hex (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`), `rgb()`,
+ * `rgba()`, `hsl()` or `hsla()` color; out-of-range values are clamped.
+ * Pure function.
+ *
+ * @returns the color, or null if the text is not one
+ */
+export const parseColor = (text: string): Rgba | null => {
+	const trimmed = text.trim();
+	const hex = HEX_PATTERN.exec(trimmed);
+	if (hex) {
+		const digits =
+			hex[1].length <= 4
+				? hex[1].replace(/./g, (digit) => digit + digit)
+				: hex[1];
+		const byte = (index: number) =>
+			Number.parseInt(digits.slice(index * 2, index * 2 + 2), 16);
+		return {
+			r: byte(0),
+			g: byte(1),
+			b: byte(2),
+			a: digits.length === 8 ? byte(3) / 255 : 1,
+		};
+	}
+
+	const call = FUNCTION_PATTERN.exec(trimmed);
+	if (!call) return null;
+	const args = splitArguments(call[2]);
+	if (args.length !== 3 && args.length !== 4) return null;
+	const parts = args.slice(0, 3).map((arg) => ARGUMENT_PATTERN.exec(arg));
+	const a = parseAlpha(args[3]);
+	if (parts.some((part) => part === null) || a === null) return null;
+	const [first, second, third] = parts as RegExpExecArray[];
+
+	if (call[1].toLowerCase().startsWith("rgb")) {
+		if ([first, second, third].some((part) => part[2] && part[2] !== "%")) {
+			return null;
+		}
+		const [r, g, b] = [first, second, third].map((part) => {
+			const value = Number(part[1]);
+			return Math.round(clamp(part[2] ? (value / 100) * 255 : value, 0, 255));
+		});
+		return { r, g, b, a };
+	}
+
+	if (first[2] === "%" || second[2] !== "%" || third[2] !== "%") {
+		return null;
+	}
+	const hue = ((Number(first[1]) % 360) + 360) % 360;
+	const [r, g, b] = hslToRgb(
+		hue,
+		clamp(Number(second[1]) / 100, 0, 1),
+		clamp(Number(third[1]) / 100, 0, 1),
+	);
+	return { r, g, b, a };
+};
+
+const toHexByte = (value: number): string =>
+	Math.round(value).toString(16).padStart(2, "0");
+
+/**
+ * Rounds an alpha to two decimals for `rgba()` and `hsla()`.
+ */
+const formatAlpha = (a: number): string => String(Math.round(a * 100) / 100);
+
+/**
+ * Writes a color in one notation: lowercase `#rrggbb` (`#rrggbbaa` when
+ * translucent), `rgb()`/`rgba()` or `hsl()`/`hsla()` with whole numbers.
+ * Pure function.
+ */
+export const formatColor = (color: Rgba, format: ColorFormat): string => {
+	const { r, g, b, a } = color;
+	switch (format) {
+		case "hex": {
+			const alpha = a < 1 ? toHexByte(a * 255) : "";
+			return `#${toHexByte(r)}${toHexByte(g)}${toHexByte(b)}${alpha}`;
+		}
+		case "rgb":
+			return a < 1
+				? `rgba(${r}, ${g}, ${b}, ${formatAlpha(a)})`
+				: `rgb(${r}, ${g}, ${b})`;
+		case "hsl": {
+			const [red, green, blue] = [r / 255, g / 255, b / 255];
+			const max = Math.max(red, green, blue);
+			const min = Math.min(red, green, blue);
+			const l = (max + min) / 2;
+			const delta = max - min;
+			const s = delta === 0 ? 0 : delta / (1 - Math.abs(2 * l - 1));
+			let h = 0;
+			if (delta !== 0) {
+				if (max === red) h = ((green - blue) / delta) % 6;
+				else if (max === green) h = (blue - red) / delta + 2;
+				else h = (red - green) / delta + 4;
+			}
+			const hue = Math.round((h * 60 + 360) % 360);
+			const values = `${hue}, ${Math.round(s * 100)}%, ${Math.round(l * 100)}%`;
+			return a < 1 ? `hsla(${values}, ${formatAlpha(a)})` : `hsl(${values})`;
+		}
+	}
+};
+
+/**
+ * The canonical value stored for a color item: its lowercase hex form.
+ * Pure function.
+ *
+ * @returns the value, or null if the text is not a color
+ */
+export const normalizeColor = (text: string): string | null => {
+	const color = parseColor(text);
+	return color ? formatColor(color, "hex") : null;
+};
+
+/**
+ * A stored color in every notation.
+ * Pure function.
+ *
+ * @throws if the value is not a color
+ */
+export const convertColor = (value: string): Record<ColorFormat, string> => {
+	const color = parseColor(value);
+	if (!color) {
+		throw new Error(`Invalid color: ${value}`);
+	}
+	return {
+		hex: formatColor(color, "hex"),
+		rgb: formatColor(color, "rgb"),
+		hsl: formatColor(color, "hsl"),
+	};
+};
+
+/**
+ * Whether a value names a color notation.
+ * Pure function.
+ */
+export const isColorFormat = (value: unknown): value is ColorFormat =>
+	COLOR_FORMATS.includes(value as ColorFormat);
@@ -0,0 +1,247 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	type CommandInvocation,
+	createCommandActions,
+	describeCommandError,
+	expandCommandArgs,
+	getCommandActionsPath,
+	parseCommandActionSettings,
+} from "./command-actions.js";
+
+const invocation: CommandInvocation = {
+	command: "code",
+	args: [],
+	stdin: null,
+	timeoutMs: 30_000,
+};
+
+describe("parseCommandActionSettings", () => {
+	it("fills in defaults and trims names", () => {
+		expect(
+			parseCommandActionSettings({
+				actions: [{ name: " Open in VS Code ", command: "code" }],
+			}),
+		).toEqual({
+			actions: [
+				{
+					name: "Open in VS Code",
+					command: "code",
+					args: [],
+					stdin: false,
+					captureOutput: false,
+					timeoutSeconds: 30,
+				},
+			],
+		});
+	});
+
+	it("keeps the current actions when none are given", () => {
+		const current = parseCommandActionSettings({
+			actions: [{ name: "Kept", command: "code", args: ["{path}"] }],
+		});
+
+		expect(parseCommandActionSettings({}, current)).toEqual(current);
+	});
+
+	it("rejects invalid actions", () => {
+		const parse = (value: Record<string, unknown>) =>
+			parseCommandActionSettings({
+				actions: [{ name: "Bad", command: "code", ...value }],
+			});
+
+		expect(() => parse({ command: " " })).toThrow(
+			'Invalid command actions: action "Bad": command must be 1-1024 characters',
+		);
+		expect(() => parse({ args: ["{file}"] })).toThrow(
+			"unknown placeholder {file}",
+		);
+		expect(() => parse({ args: ["--files={paths}"] })).toThrow(
+			"{paths} must be a whole argument",
+		);
+		expect(() => parse({ timeoutSeconds: 301 })).toThrow(
+			"timeoutSeconds must be 1-300",
+		);
+		expect(() =>
+			parseCommandActionSettings({
+				actions: [
+					{ name: "Twice", command: "a" },
+					{ name: "Twice", command: "b" },
+				],
+			}),
+		).toThrow("action names must be unique");
+	});
+});
+
+describe("expandCommandArgs", () => {
+	it("fills each placeholder into a single argument", () => {
+		expect(
+			expandCommandArgs(["-sL", "{text}", "--id={id}"], {
+				id: 7,
+				text: "https://example.com/a b; rm -rf ~",
+				paths: [],
+			}),
+		).toEqual(["-sL", "https://example.com/a b; rm -rf ~", "--id=7"]);
+	});
+
+	it("expands {path} and {paths} for file items", () => {
+		const item = { id: 1, text: "/a\n/b", paths: ["/a", "/b"] };
+
+		expect(expandCommandArgs(["--", "{paths}"], item)).toEqual([
+			"--",
+			"/a",
+			"/b",
+		]);
+		expect(() => expandCommandArgs(["{path}"], item)).toThrow(
+			"Only items with a single file path can use {path}",
+		);
+		expect(() =>
+			expandCommandArgs(["{paths}"], { id: 1, text: "hi", paths: [] }),
+		).toThrow("Only items with file paths can use {paths}");
+	});
+
+	it("refuses values that would be read as options", () => {
+		const item = { id: 1, text: "--upload-file=/etc/passwd", paths: [] };
+
+		expect(() => expandCommandArgs(["{text}"], item)).toThrow(
+			"would be read as an option",
+		);
+		expect(expandCommandArgs(["--data={text}"], item)).toEqual([
+			"--data=--upload-file=/etc/passwd",
+		]);
+		expect(expandCommandArgs(["--", "{text}"], item)).toEqual([
+			"--",
+			"--upload-file=/etc/passwd",
+		]);
+		expect(() =>
+			expandCommandArgs(["{text}"], { id: 1, text: "a\0b", paths: [] }),
+		).toThrow("Item contains a NUL character");
+	});
+});
+
+describe("describeCommandError", () => {
+	it("explains missing commands, timeouts and exit codes", () => {
+		expect(
+			describeCommandError(
+				{ name: "Error", message: "", code: "ENOENT" },
+				invocation,
+			).message,
+		).toBe("Command not found: code");
+		expect(
+			describeCommandError(
+				{ name: "Error", message: "", killed: true },
+				invocation,
+			).message,
+		).toBe("Command timed out after 30 seconds");
+		expect(
+			describeCommandError(
+				{ name: "Error", message: "", code: 2, stderr: " no such file \n" },
+				invocation,
+			).message,
+		).toBe("Command failed with exit code 2: no such file");
+	});
+});
+
+describe("createCommandActions", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-commands-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("runs an action and records its output", async () => {
+		const runCommand = vi.fn().mockResolvedValue("HELLO\n");
+		const recordOutput = vi.fn();
+		const commands = createCommandActions({
+			userDataPath: createTempUserDataPath(),
+			recordOutput,
+			runCommand,
+		});
+		commands.updateSettings({
+			actions: [
+				{
+					name: "Upper",
+					command: "tr",
+					args: ["a-z", "A-Z"],
+					stdin: true,
+					captureOutput: true,
+					timeoutSeconds: 5,
+				},
+			],
+		});
+
+		const result = await commands.runAction("Upper", {
+			id: 3,
+			text: "hello",
+			paths: [],
+		});
+
+		expect(runCommand).toHaveBeenCalledWith({
+			command: "tr",
+			args: ["a-z", "A-Z"],
+			stdin: "hello",
+			timeoutMs: 5000,
+		});
+		expect(result).toEqual({ output: "HELLO", recorded: true });
+		expect(recordOutput).toHaveBeenCalledWith("HELLO");
+	});
+
+	it("does not record output unless asked to", async () => {
+		const recordOutput = vi.fn();
+		const commands = createCommandActions({
+			userDataPath: createTempUserDataPath(),
+			recordOutput,
+			runCommand: vi.fn().mockResolvedValue("done"),
+		});
+		commands.updateSettings({
+			actions: [{ name: "Open", command: "code", args: ["{path}"] }],
+		});
+
+		const result = await commands.runAction("Open", {
+			id: 1,
+			text: "/tmp/a.txt",
+			paths: ["/tmp/a.txt"],
+		});
+
+		expect(result).toEqual({ output: "done", recorded: false });
+		expect(recordOutput).not.toHaveBeenCalled();
+		await expect(
+			commands.runAction("Missing", { id: 1, text: "", paths: [] }),
+		).rejects.toThrow("Command action not found: Missing");
+	});
+
+	it("persists actions across restarts", () => {
+		const userDataPath = createTempUserDataPath();
+		const deps = { userDataPath, recordOutput: vi.fn() };
+		createCommandActions(deps).updateSettings({
+			actions: [{ name: "Open", command: "code", args: ["{path}"] }],
+		});
+
+		expect(createCommandActions(deps).getSettings().actions).toMatchObject([
+			{ name: "Open", command: "code", args: ["{path}"] },
+		]);
+	});
+
+	it("starts with no actions when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getCommandActionsPath(userDataPath), "not json");
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const commands = createCommandActions({
+			userDataPath,
+			recordOutput: vi.fn(),
+		});
+
+		expect(commands.getSettings()).toEqual({ actions: [] });
+		vi.restoreAllMocks();
+	});
+});
@@ -0,0 +1,443 @@
+import { type ExecFileException, execFile } from "node:child_process";
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * A user-defined action that hands an item to an external command, e.g.
+ * `code {path}` or `curl -sL {text}`.
+ */
+export type CommandAction = {
+	name: string;
+	/** Executable name or path, run directly without a shell */
+	command: string;
+	/** Argument templates; see `expandCommandArgs` for placeholders */
+	args: string[];
+	/** Whether the item's text is also written to the command's stdin */
+	stdin: boolean;
+	/** Whether the command's output is added to history as a new item */
+	captureOutput: boolean;
+	timeoutSeconds: number;
+};
+
+/**
+ * Persisted command actions.
+ */
+export type CommandActionSettings = {
+	actions: CommandAction[];
+};
+
+/**
+ * The values of an item that argument templates can use.
+ */
+export type CommandItem = {
+	id: number;
+	/** The item's text; the paths of a files item, one per line */
+	text: string;
+	/** Paths of a files item, or of a text item that is a path */
+	paths: string[];
+};
+
+/**
+ * One command invocation, with its arguments already expanded.
+ */
+export type CommandInvocation = {
+	command: string;
+	args: string[];
+	stdin: string | null;
+	timeoutMs: number;
+};
+
+/**
+ * Runs a command and resolves with its stdout; injectable for tests.
+ * Rejects if the command fails, times out or writes too much.
+ */
+export type CommandRunner = (invocation: CommandInvocation) => Promise<string>;
+
+/**
+ * What running an action produced.
+ */
+export type CommandActionResult = {
+	output: string;
+	/** Whether the output was added to history */
+	recorded: boolean;
+};
+
+const DEFAULT_COMMAND_ACTION_SETTINGS: CommandActionSettings = {
+	actions: [],
+};
+
+const COMMAND_ACTIONS_FILENAME = "command-actions.json";
+
+const MAX_COMMAND_ACTIONS = 50;
+
+const MAX_ACTION_NAME_LENGTH = 64;
+
+const MAX_COMMAND_LENGTH = 1024;
+
+const MAX_COMMAND_ARGS = 50;
+
+const MAX_ARG_LENGTH = 4096;
+
+const DEFAULT_TIMEOUT_SECONDS = 30;
+
+const MAX_TIMEOUT_SECONDS = 300;
+
+/**
+ * Most output read from a command (1 MB); more fails the run.
+ */
+const MAX_OUTPUT_BYTES = 1024 * 1024;
+
+/**
+ * Characters of a failed command's stderr included in its error.
+ */
+const MAX_ERROR_DETAIL_CHARS = 200;
+
+const PLACEHOLDER_PATTERN = /\{(\w+)\}/g;
+
+const PLACEHOLDERS = ["text", "path", "paths", "id"];
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid command actions: ${message}`);
+
+const isPositiveInteger = (value: unknown, max: number): value is number =>
+	typeof value === "number" &&
+	Number.isInteger(value) &&
+	value >= 1 &&
+	value <= max;
+
+/**
+ * Validates the placeholders of an argument template.
+ *
+ * @throws if it names an unknown placeholder, or uses `{paths}` other
+ *   than as the whole argument
+ */
+const assertValidTemplate = (template: string, actionName: string) => {
+	for (const [placeholder, name] of template.matchAll(PLACEHOLDER_PATTERN)) {
+		if (!PLACEHOLDERS.includes(name)) {
+			throw invalid(
+				`action "${actionName}": unknown placeholder ${placeholder}; use one of {${PLACEHOLDERS.join("}, {")}}`,
+			);
+		}
+		if (name === "paths" && template !== "{paths}") {
+			throw invalid(`action "${actionName}": {paths} must be a whole argument`);
+		}
+	}
+};
+
+const parseCommandAction = (action: unknown): CommandAction => {
+	if (typeof action !== "object" || action === null) {
+		throw invalid("each action must be an object");
+	}
+	const {
+		name,
+		command,
+		args = [],
+		stdin = false,
+		captureOutput = false,
+		timeoutSeconds = DEFAULT_TIMEOUT_SECONDS,
+	} = action as Record<string, unknown>;
+	if (
+		typeof name !== "string" ||
+		name.trim() === "" ||
+		name.trim().length > MAX_ACTION_NAME_LENGTH
+	) {
+		throw invalid(
+			`action names must be 1-${MAX_ACTION_NAME_LENGTH} characters`,
+		);
+	}
+	const actionName = name.trim();
+	if (
+		typeof command !== "string" ||
+		command.trim() === "" ||
+		command.length > MAX_COMMAND_LENGTH
+	) {
+		throw invalid(
+			`action "${actionName}": command must be 1-${MAX_COMMAND_LENGTH} characters`,
+		);
+	}
+	if (
+		!Array.isArray(args) ||
+		args.length > MAX_COMMAND_ARGS ||
+		!args.every(
+			(arg) => typeof arg === "string" && arg.length <= MAX_ARG_LENGTH,
+		)
+	) {
+		throw invalid(
+			`action "${actionName}": args must be at most ${MAX_COMMAND_ARGS} strings of up to ${MAX_ARG_LENGTH} characters`,
+		);
+	}
+	for (const arg of args as string[]) {
+		assertValidTemplate(arg, actionName);
+	}
+	if (typeof stdin !== "boolean" || typeof captureOutput !== "boolean") {
+		throw invalid(
+			`action "${actionName}": stdin and captureOutput must be booleans`,
+		);
+	}
+	if (!isPositiveInteger(timeoutSeconds, MAX_TIMEOUT_SECONDS)) {
+		throw invalid(
+			`action "${actionName}": timeoutSeconds must be 1-${MAX_TIMEOUT_SECONDS}`,
+		);
+	}
+	return {
+		name: actionName,
+		command: command.trim(),
+		args: [...(args as string[])],
+		stdin,
+		captureOutput,
+		timeoutSeconds,
+	};
+};
+
+/**
+ * Validates a command actions update.
+ * Pure function. Names are trimmed; `args` defaults to none, `stdin` and
+ * `captureOutput` to false and `timeoutSeconds` to 30. A missing
+ * `actions` keeps the current list; otherwise it replaces the whole list.
+ *
+ * @throws if there are more than 50 actions, names repeat, or an action
+ *   has no name or command, an unknown placeholder or a timeout outside
+ *   1-300 seconds
+ */
+export const parseCommandActionSettings = (
+	input: unknown,
+	current: CommandActionSettings = DEFAULT_COMMAND_ACTION_SETTINGS,
+): CommandActionSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { actions } = input as Partial<
+		Record<keyof CommandActionSettings, unknown>
+	>;
+	if (actions === undefined) {
+		return {
+			actions: current.actions.map((action) => ({
+				...action,
+				args: [...action.args],
+			})),
+		};
+	}
+	if (!Array.isArray(actions)) {
+		throw invalid("actions must be an array");
+	}
+	if (actions.length > MAX_COMMAND_ACTIONS) {
+		throw invalid(`at most ${MAX_COMMAND_ACTIONS} actions are allowed`);
+	}
+	const parsed = actions.map(parseCommandAction);
+	if (new Set(parsed.map((action) => action.name)).size !== parsed.length) {
+		throw invalid("action names must be unique");
+	}
+	return { actions: parsed };
+};
+
+/**
+ * Expands argument templates for an item.
+ * Pure function. Each template becomes one argument, never split or read
+ * by a shell: `{text}` is the item's text, `{path}` its single file path,
+ * `{id}` its history id, and an argument of just `{paths}` becomes one
+ * argument per file path.
+ *
+ * @throws if `{path}` or `{paths}` is used for an item without paths (or
+ *   `{path}` for several), a value contains a NUL character, or a value
+ *   at the start of an argument before any `--` argument begins with
+ *   `-`, where the command would read it as an option
+ */
+export const expandCommandArgs = (
+	templates: readonly string[],
+	item: CommandItem,
+): string[] => {
+	const valueOf = (name: string): string => {
+		switch (name) {
+			case "text":
+				return item.text;
+			case "id":
+				return String(item.id);
+			default:
+				if (item.paths.length !== 1) {
+					throw new Error("Only items with a single file path can use {path}");
+				}
+				return item.paths[0];
+		}
+	};
+	let afterOptions = false;
+	const checkValue = (value: string, atStart: boolean): string => {
+		if (value.includes("\0")) {
+			throw new Error("Item contains a NUL character");
+		}
+		if (atStart && !afterOptions && value.startsWith("-")) {
+			throw new Error("Item starts with '-' and would be read as an option");
+		}
+		return value;
+	};
+
+	return templates.flatMap((template) => {
+		if (template === "--") {
+			afterOptions = true;
+			return [template];
+		}
+		if (template === "{paths}") {
+			if (item.paths.length === 0) {
+				throw new Error("Only items with file paths can use {paths}");
+			}
+			return item.paths.map((itemPath) => checkValue(itemPath, true));
+		}
+		return [
+			template.replace(
+				PLACEHOLDER_PATTERN,
+				(_match, name: string, offset: number) =>
+					checkValue(valueOf(name), offset === 0),
+			),
+		];
+	});
+};
+
+/**
+ * Turns a command failure into a readable error.
+ * Pure function.
+ */
+export const describeCommandError = (
+	error: ExecFileException & { stderr?: string },
+	invocation: CommandInvocation,
+): Error => {
+	if (error.code === "ENOENT") {
+		return new Error(`Command not found: ${invocation.command}`);
+	}
+	if (error.code === "ERR_CHILD_PROCESS_STDIO_MAXBUFFER") {
+		return new Error("Command wrote more than 1 MB of output");
+	}
+	if (error.killed) {
+		return new Error(
+			`Command timed out after ${invocation.timeoutMs / 1000} seconds`,
+		);
+	}
+	const detail = (error.stderr ?? "").trim().slice(0, MAX_ERROR_DETAIL_CHARS);
+	const exit =
+		typeof error.code === "number" ? ` with exit code ${error.code}` : "";
+	return new Error(`Command failed${exit}${detail ? `: ${detail}` : ""}`);
+};
+
+// ============================================================================
+// Command Running
+// ============================================================================
+
+const runCommand: CommandRunner = (invocation) =>
+	new Promise((resolve, reject) => {
+		const child = execFile(
+			invocation.command,
+			invocation.args,
+			{
+				timeout: invocation.timeoutMs,
+				maxBuffer: MAX_OUTPUT_BYTES,
+				encoding: "utf-8",
+				windowsHide: true,
+			},
+			(error, stdout, stderr) =>
+				error
+					? reject(describeCommandError({ ...error, stderr }, invocation))
+					: resolve(stdout),
+		);
+		// A command that exits without reading stdin is not an error
+		child.stdin?.on("error", () => {});
+		child.stdin?.end(invocation.stdin ?? undefined);
+	});
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getCommandActionsPath = (userDataPath: string) =>
+	path.join(userDataPath, COMMAND_ACTIONS_FILENAME);
+
+/**
+ * Reads saved actions, falling back to none if missing or invalid.
+ */
+const readCommandActionsFromFile = (
+	filePath: string,
+): CommandActionSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { actions: [] };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseCommandActionSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read command actions, using none:", error);
+		return { actions: [] };
+	}
+};
+
+const writeCommandActionsToFile = (
+	filePath: string,
+	settings: CommandActionSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Command Actions Module
+// ============================================================================
+
+export type CommandActionsDeps = {
+	userDataPath: string;
+	/** Adds a command's output to history */
+	recordOutput: (text: string) => void;
+	runCommand?: CommandRunner;
+};
+
+/**
+ * Creates the command actions store and runner.
+ */
+export const createCommandActions = (deps: CommandActionsDeps) => {
+	const filePath = getCommandActionsPath(deps.userDataPath);
+	const run = deps.runCommand ?? runCommand;
+	let settings = readCommandActionsFromFile(filePath);
+
+	const getSettings = (): CommandActionSettings =>
+		parseCommandActionSettings({}, settings);
+
+	const updateSettings = (input: unknown): CommandActionSettings => {
+		const next = parseCommandActionSettings(input, settings);
+		writeCommandActionsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Runs an action on an item. Output with text in it is added to
+	 * history if the action captures output; a final line break is
+	 * dropped.
+	 *
+	 * @throws if no action has the name, its arguments cannot be expanded
+	 *   for the item, or the command fails
+	 */
+	const runAction = async (
+		name: string,
+		item: CommandItem,
+	): Promise<CommandActionResult> => {
+		const action = settings.actions.find((entry) => entry.name === name);
+		if (!action) {
+			throw new Error(`Command action not found: ${name}`);
+		}
+		const stdout = await run({
+			command: action.command,
+			args: expandCommandArgs(action.args, item),
+			stdin: action.stdin ? item.text : null,
+			timeoutMs: action.timeoutSeconds * 1000,
+		});
+		const output = stdout.replace(/\r?\n$/, "");
+		const recorded = action.captureOutput && output.trim() !== "";
+		if (recorded) deps.recordOutput(output);
+		return { output, recorded };
+	};
+
+	return { getSettings, updateSettings, runAction };
+};
+
+export type CommandActions = ReturnType<typeof createCommandActions>;
@@ -0,0 +1,49 @@
+import { describe, expect, it } from "vitest";
+import { classifyContent, isContentKind } from "./content-kind.js";
+
+describe("classifyContent", () => {
+	it("detects single-line values", () => {
+		expect(classifyContent(" https://example.com/a?b=1 ")).toBe("url");
+		expect(classifyContent("www.example.com")).toBe("url");
+		expect(classifyContent("jane@example.com")).toBe("email");
+		expect(classifyContent("192.168.1.10:8080")).toBe("ip");
+		expect(classifyContent("::1")).toBe("ip");
+		expect(classifyContent("#ff8800")).toBe("color");
+		expect(classifyContent("rgb(10, 20, 30)")).toBe("color");
+		expect(classifyContent("~/Documents/notes.txt")).toBe("path");
+		expect(classifyContent("C:\\Users\\me")).toBe("path");
+		expect(classifyContent("+1 (555) 123-4567")).toBe("phone");
+	});
+
+	it("does not mistake dates and plain numbers for phone numbers", () => {
+		expect(classifyContent("2024-01-15")).toBe("text");
+		expect(classifyContent("1234567")).toBe("text");
+	});
+
+	it("detects JSON and code", () => {
+		expect(classifyContent('{"a": [1, 2]}')).toBe("json");
+		expect(classifyContent("{not json}")).toBe("text");
+		expect(
+			classifyContent("const a = 1;\nfunction f() {\n  return a;\n}"),
+		).toBe("code");
+		expect(classifyContent("SELECT id FROM history WHERE id = 1")).toBe(
+			"code",
+		);
+		expect(classifyContent("<div><p>hi</p></div>")).toBe("code");
+	});
+
+	it("falls back to text", () => {
+		expect(classifyContent("")).toBe("text");
+		expect(classifyContent("Meeting moved to 3pm.\nSee you there.")).toBe(
+			"text",
+		);
+	});
+});
+
+describe("isContentKind", () => {
+	it("accepts only known kinds", () => {
+		expect(isContentKind("url")).toBe(true);
+		expect(isContentKind("recipe")).toBe(false);
+		expect(isContentKind(undefined)).toBe(false);
+	});
+});
@@ -0,0 +1,130 @@
+import net from "node:net";
+
+/**
+ * What a text item holds, detected when it is captured.
+ */
+export const CONTENT_KINDS = [
+	"url",
+	"email",
+	"phone",
+	"path",
+	"color",
+	"json",
+	"code",
+	"ip",
+	"text",
+] as const;
+
+export type ContentKind = (typeof CONTENT_KINDS)[number];
+
+/**
+ * Longest text checked for JSON; parsing bigger items on every capture
+ * would slow it down.
+ */
+const MAX_JSON_CHARS = 100_000;
+
+/**
+ * Lines looked at when deciding whether text is code.
+ */
+const MAX_CODE_LINES = 200;
+
+/**
+ * Share of non-blank lines that must look like code.
+ */
+const CODE_LINE_RATIO = 0.4;
+
+const URL_PATTERN = /^(?:(?:https?|ftp):\/\/|www\.)[^\s/$.?#][^\s]*$/i;
+
+const EMAIL_PATTERN = /^(?:mailto:)?[^\s@<>()]+@[^\s@<>()]+\.[a-z]{2,}$/i;
+
+const COLOR_PATTERN =
+	/^(?:#(?:[0-9a-f]{3,4}|[0-9a-f]{6}|[0-9a-f]{8})|(?:rgba?|hsla?)\(\s*[\d.]+%?\s*(?:[,\s]\s*[\d.]+%?\s*){2}(?:[,/]\s*[\d.]+%?\s*)?\))$/i;
+
+/** POSIX or home-relative paths and Windows drive or UNC paths */
+const PATH_PATTERN =
+	/^(?:~?\/[^\s/][^\n]*|\.{1,2}\/[^\n]+|[a-z]:\\[^\n]*|\\\\[^\s\\]+\\[^\n]*)$/i;
+
+const PHONE_PATTERN = /^\+?\(?\d[\d\s().-]*\d$/;
+
+const ISO_DATE_PATTERN = /^\d{4}-\d{2}-\d{2}$/;
+
+/** A line ending like a statement or block, or starting with a keyword */
+const CODE_LINE_PATTERN =
+	/(?:[;{}]\s*$|^\s*(?:import|export|from|const|let|var|function|def|class|return|if|elif|else|for|while|switch|case|try|catch|public|private|protected|static|package|fn|func|struct|impl|use|#include|#!)\b)/;
+
+const SQL_PATTERN =
+	/^\s*(?:SELECT\s[\s\S]+\sFROM|INSERT\s+INTO|UPDATE\s+\S+\s+SET|DELETE\s+FROM|CREATE\s+(?:TABLE|INDEX|VIEW))\s/i;
+
+const MARKUP_PATTERN = /^<([a-z][\w-]*)[^>]*>[\s\S]*<\/\1>$/i;
+
+/**
+ * Whether text is an IPv4 or IPv6 address, optionally with a port or
+ * prefix length.
+ */
+const isIpAddress = (text: string): boolean => {
+	const bracketed = /^\[([^\]]+)\]:\d{1,5}$/.exec(text);
+	if (bracketed) return net.isIPv6(bracketed[1]);
+	const suffixed = /^(.+?)(?:\/\d{1,3}|:\d{1,5})$/.exec(text);
+	return (
+		net.isIP(text) !== 0 || (suffixed !== null && net.isIPv4(suffixed[1]))
+	);
+};
+
+const isPhoneNumber = (text: string): boolean => {
+	if (!PHONE_PATTERN.test(text) || ISO_DATE_PATTERN.test(text)) return false;
+	const digits = text.replace(/\D/g, "").length;
+	// Separators or a leading + tell numbers apart from plain integers
+	return digits >= 7 && digits <= 15 && /[+\s().-]/.test(text);
+};
+
+const isJson = (text: string): boolean => {
+	if (text.length > MAX_JSON_CHARS || !/^[[{][\s\S]*[\]}]$/.test(text)) {
+		return false;
+	}
+	try {
+		JSON.parse(text);
+		return true;
+	} catch {
+		return false;
+	}
+};
+
+const isCode = (text: string): boolean => {
+	if (SQL_PATTERN.test(text) || MARKUP_PATTERN.test(text)) return true;
+	const lines = text
+		.split("\n", MAX_CODE_LINES)
+		.filter((line) => line.trim() !== "");
+	if (lines.length < 2) return false;
+	const codeLines = lines.filter((line) => CODE_LINE_PATTERN.test(line));
+	return codeLines.length >= Math.max(2, lines.length * CODE_LINE_RATIO);
+};
+
+/**
+ * Detects what a text item holds. Single-line values (links, addresses,
+ * paths, colors, numbers) must make up the whole trimmed text; anything
+ * else is JSON, code or plain text.
+ * Pure function.
+ */
+export const classifyContent = (text: string): ContentKind => {
+	const trimmed = text.trim();
+	if (trimmed === "") return "text";
+
+	if (!trimmed.includes("\n")) {
+		if (URL_PATTERN.test(trimmed)) return "url";
+		if (EMAIL_PATTERN.test(trimmed)) return "email";
+		if (isIpAddress(trimmed)) return "ip";
+		if (COLOR_PATTERN.test(trimmed)) return "color";
+		if (PATH_PATTERN.test(trimmed)) return "path";
+		if (isPhoneNumber(trimmed)) return "phone";
+	}
+	if (isJson(trimmed)) return "json";
+	if (isCode(trimmed)) return "code";
+	return "text";
+};
+
+/**
+ * Whether a value names a content kind.
+ * Pure function.
+ */
+export const isContentKind = (value: unknown): value is ContentKind =>
+	CONTENT_KINDS.includes(value as ContentKind);
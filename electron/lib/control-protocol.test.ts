@@ -0,0 +1,99 @@
+import { describe, expect, it } from "vitest";
+import {
+	formatControlResponse,
+	getControlSocketPath,
+	parseControlRequest,
+} from "./control-protocol.js";
+
+describe("getControlSocketPath", () => {
+	const context = {
+		platform: "darwin" as NodeJS.Platform,
+		env: {},
+		tmpdir: "/var/folders/xy/T",
+		uid: 501,
+		username: "me",
+	};
+
+	it("uses a per-user socket in the temp or runtime directory", () => {
+		expect(getControlSocketPath(context)).toBe(
+			"/var/folders/xy/T/clipboard-manager-501.sock",
+		);
+		expect(
+			getControlSocketPath({
+				...context,
+				platform: "linux",
+				env: { XDG_RUNTIME_DIR: "/run/user/1000" },
+			}),
+		).toBe("/run/user/1000/clipboard-manager.sock");
+	});
+
+	it("uses a named pipe on Windows", () => {
+		expect(getControlSocketPath({ ...context, platform: "win32" })).toBe(
+			"\\\\.\\pipe\\clipboard-manager-me",
+		);
+	});
+
+	it("honors CLIPCTL_SOCKET", () => {
+		expect(
+			getControlSocketPath({
+				...context,
+				env: { CLIPCTL_SOCKET: "/tmp/custom.sock" },
+			}),
+		).toBe("/tmp/custom.sock");
+	});
+});
+
+describe("parseControlRequest", () => {
+	it("parses each command", () => {
+		expect(parseControlRequest('{"command":"list"}')).toEqual({
+			command: "list",
+			limit: 20,
+		});
+		expect(parseControlRequest('{"command":"list","limit":5}')).toEqual({
+			command: "list",
+			limit: 5,
+		});
+		expect(parseControlRequest('{"command":"get","id":3}')).toEqual({
+			command: "get",
+			id: 3,
+		});
+		expect(parseControlRequest('{"command":"copy","id":3}')).toEqual({
+			command: "copy",
+			id: 3,
+		});
+		expect(parseControlRequest('{"command":"add","text":"hi"}')).toEqual({
+			command: "add",
+			text: "hi",
+		});
+		expect(
+			parseControlRequest(
+				'{"command":"audit","since":"2026-03-01T08:00:00Z","limit":5}',
+			),
+		).toEqual({ command: "audit", limit: 5, since: "2026-03-01T08:00:00Z" });
+	});
+
+	it("rejects malformed requests", () => {
+		expect(() => parseControlRequest("list")).toThrow("expected JSON");
+		expect(() => parseControlRequest("[]")).toThrow("Unknown command");
+		expect(() => parseControlRequest('{"command":"rm"}')).toThrow(
+			"Unknown command: rm",
+		);
+		expect(() => parseControlRequest('{"command":"get","id":0}')).toThrow(
+			"id must be a positive integer",
+		);
+		expect(() => parseControlRequest('{"command":"list","limit":1e6}')).toThrow(
+			"limit must be an integer from 1 to 1000",
+		);
+		expect(() => parseControlRequest('{"command":"add"}')).toThrow(
+			"text must be a string",
+		);
+	});
+});
+
+describe("formatControlResponse", () => {
+	it("writes one line of JSON", () => {
+		expect(formatControlResponse({ ok: false, error: "Locked" })).toBe(
+			'{"ok":false,"error":"Locked"}\n',
+		);
+	});
+});
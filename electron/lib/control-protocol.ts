@@ -0,0 +1,173 @@
+import os from "node:os";
+import path from "node:path";
+
+/**
+ * Requests `clipctl` sends to the running app, one JSON object per line.
+ */
+export type ControlRequest =
+	| { command: "list"; limit: number }
+	| { command: "get"; id: number }
+	| { command: "copy"; id: number }
+	| { command: "add"; text: string }
+	/** `since` is an ISO 8601 time, or null for the whole log */
+	| { command: "audit"; limit: number; since: string | null };
+
+/**
+ * Reply to a request, also one JSON object per line.
+ */
+export type ControlResponse =
+	| { ok: true; result: unknown }
+	| { ok: false; error: string };
+
+/**
+ * History item as listed by `clipctl list`.
+ */
+export type ControlListItem = {
+	id: number;
+	type: string;
+	/** One-line preview of the content */
+	preview: string;
+	created_at: string;
+};
+
+/**
+ * Access log entry as listed by `clipctl audit`.
+ */
+export type ControlAuditEntry = {
+	id: number;
+	/** The item accessed; null for a history export */
+	history_id: number | null;
+	action: string;
+	/** Where the request came from: ui, cli, api, dbus or url */
+	surface: string;
+	detail: string | null;
+	created_at: string;
+};
+
+export const DEFAULT_LIST_LIMIT = 20;
+
+const MAX_LIST_LIMIT = 1000;
+
+/**
+ * Longest request line accepted (characters); room for the largest clip
+ * history stores once JSON-escaped.
+ */
+export const MAX_REQUEST_LENGTH = 8_000_000;
+
+/**
+ * Environment variable that overrides the socket path, for both ends.
+ */
+const SOCKET_ENV_VAR = "CLIPCTL_SOCKET";
+
+const SOCKET_NAME = "clipboard-manager";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Path of the control socket: a named pipe on Windows, otherwise a Unix
+ * socket in the user's runtime directory (`$XDG_RUNTIME_DIR`, or the
+ * per-user temp directory with the uid in the name).
+ * Pure function. Both the app and `clipctl` compute it, so neither needs
+ * Electron's userData path.
+ */
+export const getControlSocketPath = (context: {
+	platform: NodeJS.Platform;
+	env: Record<string, string | undefined>;
+	tmpdir: string;
+	/** Numeric user id; -1 where unavailable (Windows) */
+	uid: number;
+	username: string;
+}): string => {
+	const override = context.env[SOCKET_ENV_VAR];
+	if (override) return override;
+
+	if (context.platform === "win32") {
+		return `\\\\.\\pipe\\${SOCKET_NAME}-${context.username}`;
+	}
+	const runtimeDir = context.env.XDG_RUNTIME_DIR;
+	return runtimeDir
+		? path.join(runtimeDir, `${SOCKET_NAME}.sock`)
+		: path.join(context.tmpdir, `${SOCKET_NAME}-${context.uid}.sock`);
+};
+
+/**
+ * Socket path for the current process and user.
+ */
+export const resolveControlSocketPath = (): string =>
+	getControlSocketPath({
+		platform: process.platform,
+		env: process.env,
+		tmpdir: os.tmpdir(),
+		uid: process.getuid?.() ?? -1,
+		username: os.userInfo().username,
+	});
+
+const parseId = (value: unknown): number => {
+	if (typeof value !== "number" || !Number.isInteger(value) || value <= 0) {
+		throw new Error("Invalid request: id must be a positive integer");
+	}
+	return value;
+};
+
+const parseLimit = (value: unknown): number => {
+	if (value === undefined) return DEFAULT_LIST_LIMIT;
+	if (
+		typeof value !== "number" ||
+		!Number.isInteger(value) ||
+		value < 1 ||
+		value > MAX_LIST_LIMIT
+	) {
+		throw new Error(
+			`Invalid request: limit must be an integer from 1 to ${MAX_LIST_LIMIT}`,
+		);
+	}
+	return value;
+};
+
+/**
+ * Validates one request line.
+ * Pure function.
+ *
+ * @throws with a message for the client if the line is not a valid request
+ */
+export const parseControlRequest = (line: string): ControlRequest => {
+	let input: unknown;
+	try {
+		input = JSON.parse(line);
+	} catch {
+		throw new Error("Invalid request: expected JSON");
+	}
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid request: expected an object");
+	}
+
+	const { command, limit, id, text, since } = input as Record<string, unknown>;
+	switch (command) {
+		case "list":
+			return { command, limit: parseLimit(limit) };
+		case "get":
+		case "copy":
+			return { command, id: parseId(id) };
+		case "add":
+			if (typeof text !== "string") {
+				throw new Error("Invalid request: text must be a string");
+			}
+			return { command, text };
+		case "audit":
+			if (since !== undefined && since !== null && typeof since !== "string") {
+				throw new Error("Invalid request: since must be a string");
+			}
+			return { command, limit: parseLimit(limit), since: since ?? null };
+		default:
+			throw new Error(`Unknown command: ${String(command)}`);
+	}
+};
+
+/**
+ * Serializes a response as one line.
+ * Pure function.
+ */
+export const formatControlResponse = (response: ControlResponse): string =>
+	`${JSON.stringify(response)}\n`;
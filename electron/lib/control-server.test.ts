@@ -0,0 +1,103 @@
+import fs from "node:fs";
+import net from "node:net";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { type ControlHandlers, createControlServer } from "./control-server.js";
+
+describe.skipIf(process.platform === "win32")("createControlServer", () => {
+	const tempDirs: string[] = [];
+	const stops: (() => void)[] = [];
+
+	const createSocketPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-ctl-"));
+		tempDirs.push(dir);
+		return path.join(dir, "ctl.sock");
+	};
+
+	const createHandlers = (): ControlHandlers => ({
+		list: vi.fn().mockReturnValue([]),
+		get: vi.fn().mockReturnValue("content"),
+		copy: vi.fn(),
+		add: vi.fn().mockReturnValue(7),
+		audit: vi.fn().mockReturnValue([]),
+	});
+
+	const startServer = async (handlers: ControlHandlers) => {
+		const socketPath = createSocketPath();
+		const server = createControlServer({
+			socketPath,
+			handlers,
+			platform: process.platform,
+		});
+		await server.start();
+		stops.push(server.stop);
+		return socketPath;
+	};
+
+	/**
+	 * Sends a raw line and resolves to the parsed response.
+	 */
+	const send = (socketPath: string, line: string): Promise<unknown> =>
+		new Promise((resolve, reject) => {
+			let buffer = "";
+			const socket = net.createConnection(socketPath, () => {
+				socket.write(line);
+			});
+			socket.setEncoding("utf-8");
+			socket.on("data", (chunk: string) => {
+				buffer += chunk;
+			});
+			socket.on("end", () => resolve(JSON.parse(buffer)));
+			socket.on("error", reject);
+		});
+
+	afterEach(() => {
+		for (const stop of stops.splice(0)) stop();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("answers requests from the handlers", async () => {
+		const handlers = createHandlers();
+		const socketPath = await startServer(handlers);
+
+		await expect(
+			send(socketPath, '{"command":"get","id":4}\n'),
+		).resolves.toEqual({ ok: true, result: "content" });
+		await expect(
+			send(socketPath, '{"command":"add","text":"hi"}\n'),
+		).resolves.toEqual({ ok: true, result: 7 });
+		expect(handlers.add).toHaveBeenCalledWith("hi");
+	});
+
+	it("reports invalid requests and handler errors", async () => {
+		const handlers = createHandlers();
+		vi.mocked(handlers.copy).mockImplementation(() => {
+			throw new Error("History is locked");
+		});
+		const socketPath = await startServer(handlers);
+
+		await expect(send(socketPath, "nonsense\n")).resolves.toEqual({
+			ok: false,
+			error: "Invalid request: expected JSON",
+		});
+		await expect(
+			send(socketPath, '{"command":"copy","id":1}\n'),
+		).resolves.toEqual({ ok: false, error: "History is locked" });
+	});
+
+	it("restricts the socket to the current user", async () => {
+		const socketPath = await startServer(createHandlers());
+
+		expect(fs.statSync(socketPath).mode & 0o777).toBe(0o600);
+	});
+
+	it("restores the process umask after creating the socket", async () => {
+		const umask = process.umask(0o022);
+		await startServer(createHandlers());
+
+		expect(process.umask(umask)).toBe(0o022);
+	});
+});
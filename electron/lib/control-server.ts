@@ -0,0 +1,145 @@
+import fs from "node:fs";
+import net from "node:net";
+import {
+	type ControlAuditEntry,
+	type ControlListItem,
+	type ControlRequest,
+	type ControlResponse,
+	formatControlResponse,
+	MAX_REQUEST_LENGTH,
+	parseControlRequest,
+} from "./control-protocol.js";
+
+/**
+ * What the app does for each `clipctl` command.
+ */
+export type ControlHandlers = {
+	list: (limit: number) => ControlListItem[];
+	/** Full content of an item */
+	get: (id: number) => string;
+	/** Places an item on the clipboard */
+	copy: (id: number) => void;
+	/** Adds text to history; returns the item's id */
+	add: (text: string) => number;
+	/** Access log entries, newest first */
+	audit: (limit: number, since: string | null) => ControlAuditEntry[];
+};
+
+export type ControlServerDeps = {
+	socketPath: string;
+	handlers: ControlHandlers;
+	platform: NodeJS.Platform;
+};
+
+/**
+ * Runs a request against the handlers.
+ */
+const dispatch = (
+	handlers: ControlHandlers,
+	request: ControlRequest,
+): unknown => {
+	switch (request.command) {
+		case "list":
+			return handlers.list(request.limit);
+		case "get":
+			return handlers.get(request.id);
+		case "copy":
+			handlers.copy(request.id);
+			return null;
+		case "add":
+			return handlers.add(request.text);
+		case "audit":
+			return handlers.audit(request.limit, request.since);
+	}
+};
+
+/**
+ * Creates the local control server `clipctl` talks to. Each connection
+ * sends one request line and receives one response line. The Unix socket
+ * is only accessible to the current user.
+ */
+export const createControlServer = (deps: ControlServerDeps) => {
+	let server: net.Server | null = null;
+
+	const respond = (socket: net.Socket, line: string): void => {
+		let response: ControlResponse;
+		try {
+			const request = parseControlRequest(line);
+			response = { ok: true, result: dispatch(deps.handlers, request) };
+		} catch (error) {
+			response = {
+				ok: false,
+				error: error instanceof Error ? error.message : String(error),
+			};
+		}
+		socket.end(formatControlResponse(response));
+	};
+
+	const handleConnection = (socket: net.Socket): void => {
+		let buffer = "";
+		socket.setEncoding("utf-8");
+		socket.on("data", (chunk: string) => {
+			buffer += chunk;
+			const newline = buffer.indexOf("\n");
+			if (newline !== -1) {
+				socket.removeAllListeners("data");
+				respond(socket, buffer.slice(0, newline));
+			} else if (buffer.length > MAX_REQUEST_LENGTH) {
+				socket.removeAllListeners("data");
+				socket.end(
+					formatControlResponse({ ok: false, error: "Request too large" }),
+				);
+			}
+		});
+		socket.on("error", (error) => {
+			console.error("Control connection failed:", error);
+		});
+	};
+
+	/**
+	 * Starts listening, replacing a socket file left behind by a previous
+	 * run.
+	 */
+	const start = (): Promise<void> =>
+		new Promise((resolve, reject) => {
+			if (server) {
+				resolve();
+				return;
+			}
+			if (deps.platform !== "win32" && fs.existsSync(deps.socketPath)) {
+				fs.unlinkSync(deps.socketPath);
+			}
+
+			const next = net.createServer(handleConnection);
+			next.once("error", reject);
+			// The socket file is created with the umask's permissions, so
+			// only the current user can connect from the first moment on,
+			// not just once the chmod below has run
+			const previousUmask =
+				deps.platform === "win32" ? null : process.umask(0o177);
+			try {
+				next.listen(deps.socketPath, () => {
+					next.off("error", reject);
+					next.on("error", (error) => {
+						console.error("Control server error:", error);
+					});
+					if (deps.platform !== "win32") {
+						fs.chmodSync(deps.socketPath, 0o600);
+					}
+					server = next;
+					resolve();
+				});
+			} finally {
+				if (previousUmask !== null) process.umask(previousUmask);
+			}
+		});
+
+	const stop = (): void => {
+		server?.close();
+		server = null;
+	};
+
+	return { start, stop };
+};
+
+export type ControlServer = ReturnType<typeof createControlServer>;
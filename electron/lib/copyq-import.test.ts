@@ -0,0 +1,247 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	importCopyQ,
+	parseCopyQTab,
+	resolveCopyQMime,
+	toImportItem,
+} from "./copyq-import.js";
+import type { ImportHistoryItem } from "./history-repository.js";
+
+// QDataStream encoding, as CopyQ writes it
+const int32 = (value: number): Buffer => {
+	const buffer = Buffer.alloc(4);
+	buffer.writeInt32BE(value);
+	return buffer;
+};
+const byteArray = (data: Buffer | string): Buffer => {
+	const bytes = Buffer.from(data);
+	return Buffer.concat([int32(bytes.length), bytes]);
+};
+const qString = (text: string): Buffer => {
+	const bytes = Buffer.from(text, "utf16le").swap16();
+	return Buffer.concat([int32(bytes.length), bytes]);
+};
+const qCompress = (text: string): Buffer => {
+	const bytes = Buffer.from(text);
+	return Buffer.concat([int32(bytes.length), zlib.deflateSync(bytes)]);
+};
+
+/**
+ * Current layout; every other value is compressed.
+ */
+const item = (formats: Array<[string, string]>): Buffer =>
+	Buffer.concat([
+		int32(-2),
+		int32(formats.length),
+		...formats.flatMap(([mime, value], index) => [
+			byteArray(mime),
+			Buffer.from([index % 2]),
+			byteArray(index % 2 === 1 ? qCompress(value) : value),
+		]),
+	]);
+
+/**
+ * Original layout: UTF-16 names and compressed values.
+ */
+const originalItem = (formats: Array<[string, string]>): Buffer =>
+	Buffer.concat([
+		int32(formats.length),
+		...formats.flatMap(([mime, value]) => [
+			qString(mime),
+			byteArray(qCompress(value)),
+		]),
+	]);
+
+const tab = (items: Buffer[]): Buffer =>
+	Buffer.concat([int32(items.length), ...items]);
+
+const formats = (entries: Array<[string, string]>) =>
+	new Map(entries.map(([mime, value]) => [mime, Buffer.from(value)]));
+
+describe("resolveCopyQMime", () => {
+	it("expands shortened names of known formats", () => {
+		expect(resolveCopyQMime("text/plain")).toBe("text/plain");
+		expect(resolveCopyQMime("2plain")).toBe("text/plain");
+		expect(resolveCopyQMime("1-notes")).toBe("application/x-copyq-item-notes");
+		expect(resolveCopyQMime("3x-copyq-tags")).toBe("application/x-copyq-tags");
+		expect(resolveCopyQMime("4png")).toBe("4png");
+	});
+});
+
+describe("parseCopyQTab", () => {
+	it("reads items in the current and original layouts", () => {
+		const data = tab([
+			item([
+				["2plain", "hello"],
+				["3x-copyq-tags", "work, sql"],
+			]),
+			originalItem([
+				["text/plain", "older"],
+				["application/x-copyq-item-pinned", ""],
+			]),
+		]);
+
+		expect(parseCopyQTab(data)).toEqual([
+			formats([
+				["text/plain", "hello"],
+				["application/x-copyq-tags", "work, sql"],
+			]),
+			formats([
+				["text/plain", "older"],
+				["application/x-copyq-item-pinned", ""],
+			]),
+		]);
+	});
+
+	it("skips a header string", () => {
+		const data = Buffer.concat([
+			qString("CopyQ v3"),
+			tab([item([["text/plain", "a"]])]),
+		]);
+		expect(parseCopyQTab(data)).toEqual([formats([["text/plain", "a"]])]);
+	});
+
+	it("rejects truncated data", () => {
+		const data = tab([item([["text/plain", "hello"]])]);
+		expect(() => parseCopyQTab(data.subarray(0, -2))).toThrow(
+			"unexpected end of data",
+		);
+	});
+});
+
+describe("toImportItem", () => {
+	const createdAt = "2024-01-01 00:00:00";
+
+	it("maps text with its HTML, tags, note and pinned state", () => {
+		const copyqItem = formats([
+			["text/plain", "hello"],
+			["text/html", "<b>hello</b>"],
+			["application/x-copyq-tags", "work, sql"],
+			["application/x-copyq-item-notes", "greeting"],
+			["application/x-copyq-item-pinned", ""],
+		]);
+
+		expect(toImportItem(copyqItem, createdAt)).toEqual({
+			type: "text",
+			content: "hello",
+			created_at: createdAt,
+			favorite: false,
+			pinned: true,
+			use_count: 1,
+			tags: ["work", "sql"],
+			note: "greeting",
+			source: null,
+			rtf: null,
+			html: "<b>hello</b>",
+			image: null,
+		});
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"maps a URI list to a file item",
+		() => {
+			const files = formats([["text/uri-list", "file:///tmp/a.txt\r\n"]]);
+			expect(toImportItem(files, createdAt)).toMatchObject({
+				type: "files",
+				content: "/tmp/a.txt",
+			});
+		},
+	);
+
+	it("skips items without text or files", () => {
+		const image = formats([["image/png", "png"]]);
+		expect(toImportItem(image, createdAt)).toBeNull();
+	});
+});
+
+describe("importCopyQ", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-copyq-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	const createImportItems = () => {
+		const stored: ImportHistoryItem[] = [];
+		const importItems = (items: ImportHistoryItem[]) => {
+			stored.push(...items);
+			return items.length;
+		};
+		return { importItems, stored };
+	};
+
+	it("imports every tab in the configuration directory", async () => {
+		const dir = createTempDir();
+		const tabFile = path.join(dir, "copyq_tab_JmNsaXBib2FyZA==.dat");
+		fs.writeFileSync(
+			tabFile,
+			tab([
+				item([["text/plain", "newest"]]),
+				item([["image/png", "png"]]),
+				item([["text/plain", "oldest"]]),
+			]),
+		);
+		fs.utimesSync(tabFile, new Date(), new Date("2024-01-01T12:00:00Z"));
+		fs.writeFileSync(path.join(dir, "copyq.conf"), "[Options]");
+		const { importItems, stored } = createImportItems();
+
+		expect(await importCopyQ(importItems, dir)).toEqual({
+			imported: 2,
+			skipped: 1,
+		});
+		expect(stored.map((entry) => [entry.content, entry.created_at])).toEqual([
+			["newest", "2024-01-01 12:00:00"],
+			["oldest", "2024-01-01 11:59:58"],
+		]);
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"imports a synchronized tab directory",
+		async () => {
+			const dir = createTempDir();
+			fs.writeFileSync(path.join(dir, "copyq_0001.txt"), "note to self");
+			fs.writeFileSync(path.join(dir, "copyq_0001_note.txt"), "a note");
+			fs.writeFileSync(
+				path.join(dir, "copyq_0001_copyq.dat"),
+				item([["3x-copyq-tags", "todo"]]),
+			);
+			fs.writeFileSync(path.join(dir, "copyq_0002.uri"), "file:///tmp/b.txt");
+			fs.writeFileSync(path.join(dir, "photo.png"), "png");
+			const { importItems, stored } = createImportItems();
+
+			expect(await importCopyQ(importItems, dir)).toEqual({
+				imported: 2,
+				skipped: 0,
+			});
+			expect(stored).toEqual(
+				expect.arrayContaining([
+					expect.objectContaining({
+						content: "note to self",
+						note: "a note",
+						tags: ["todo"],
+					}),
+					expect.objectContaining({ type: "files" }),
+				]),
+			);
+		},
+	);
+
+	it("rejects a relative path", async () => {
+		const { importItems } = createImportItems();
+		await expect(importCopyQ(importItems, "copyq")).rejects.toThrow(
+			"path must be absolute",
+		);
+	});
+});
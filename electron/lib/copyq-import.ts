@@ -0,0 +1,346 @@
+import fs from "node:fs";
+import path from "node:path";
+import zlib from "node:zlib";
+import { formatStoredFileList, parseUriList } from "./file-lists.js";
+import {
+	type HistoryImportReport,
+	type ImportItems,
+	importInBatches,
+	readImportFile,
+	toHistoryTimestamp,
+} from "./history-import.js";
+import type { ImportHistoryItem } from "./history-repository.js";
+
+/**
+ * One CopyQ item: its data by MIME type.
+ */
+export type CopyQItem = Map<string, Buffer>;
+
+/**
+ * A CopyQ item with the time it is imported as.
+ */
+type DatedCopyQItem = { item: CopyQItem; createdAt: string };
+
+/**
+ * Item formats the importer maps; everything else (images, app-specific
+ * data) is dropped.
+ */
+const MIME_TEXT = "text/plain";
+const MIME_HTML = "text/html";
+const MIME_URI_LIST = "text/uri-list";
+const MIME_NOTES = "application/x-copyq-item-notes";
+const MIME_TAGS = "application/x-copyq-tags";
+const MIME_PINNED = "application/x-copyq-item-pinned";
+
+/**
+ * Current CopyQ item data starts with this marker instead of a count.
+ */
+const ITEM_FORMAT_V2 = -2;
+
+const NULL_LENGTH = 0xffffffff;
+
+/**
+ * Tab data files in CopyQ's configuration directory.
+ */
+const TAB_FILE_PATTERN = /^copyq_tab_.*\.dat$/;
+
+/**
+ * Files of a synchronized tab directory, by the format they hold.
+ */
+const SYNC_DATA_SUFFIX = "_copyq.dat";
+const SYNC_NOTE_SUFFIX = "_note.txt";
+const SYNC_EXTENSIONS: Record<string, string> = {
+	".txt": MIME_TEXT,
+	".html": MIME_HTML,
+	".uri": MIME_URI_LIST,
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid CopyQ data: ${message}`);
+
+/**
+ * Reads the big-endian Qt `QDataStream` encoding CopyQ saves items in.
+ */
+const createDataReader = (data: Buffer) => {
+	let offset = 0;
+
+	const take = (length: number): Buffer => {
+		if (offset + length > data.length) {
+			throw invalid("unexpected end of data");
+		}
+		const bytes = data.subarray(offset, offset + length);
+		offset += length;
+		return bytes;
+	};
+
+	const readInt32 = (): number => take(4).readInt32BE(0);
+	const readBool = (): boolean => take(1)[0] !== 0;
+
+	/** `QByteArray`; null arrays read as empty */
+	const readBytes = (): Buffer => {
+		const length = take(4).readUInt32BE(0);
+		return length === NULL_LENGTH ? Buffer.alloc(0) : take(length);
+	};
+
+	/**
+	 * A MIME name. Older versions save it as a UTF-16 `QString`, newer
+	 * ones as a UTF-8 `QByteArray`; both start with a byte length, and
+	 * UTF-16 MIME names are ASCII with every other byte zero.
+	 */
+	const readName = (): string => {
+		const bytes = readBytes();
+		const isUtf16 =
+			bytes.length % 2 === 0 &&
+			bytes.length > 0 &&
+			bytes.every((byte, index) => index % 2 === 1 || byte === 0);
+		return isUtf16
+			? Buffer.from(bytes).swap16().toString("utf16le")
+			: bytes.toString("utf-8");
+	};
+
+	const atEnd = (): boolean => offset >= data.length;
+
+	return { readInt32, readBool, readBytes, readName, atEnd };
+};
+
+type DataReader = ReturnType<typeof createDataReader>;
+
+/**
+ * Inflates data compressed with Qt's `qCompress`: a 4-byte expected size
+ * followed by a zlib stream.
+ */
+const qUncompress = (data: Buffer): Buffer =>
+	data.length <= 4 ? Buffer.alloc(0) : zlib.inflateSync(data.subarray(4));
+
+/**
+ * Maps a saved MIME name to one the importer reads. Newer CopyQ versions
+ * replace a common prefix ("text/", "application/x-copyq-item", ...)
+ * with a digit when saving and keep the rest, so a known format is one
+ * whose name ends with that rest.
+ * Pure function.
+ */
+export const resolveCopyQMime = (name: string): string => {
+	const rest = /^\d/.test(name) ? name.slice(1) : name;
+	const known = [
+		MIME_TEXT,
+		MIME_HTML,
+		MIME_URI_LIST,
+		MIME_NOTES,
+		MIME_TAGS,
+		MIME_PINNED,
+	];
+	return (
+		known.find((mime) => rest.length >= 4 && mime.endsWith(rest)) ?? name
+	);
+};
+
+/**
+ * Reads one item's formats, in either the current or the original layout.
+ */
+const readItem = (reader: DataReader): CopyQItem => {
+	const item: CopyQItem = new Map();
+	const marker = reader.readInt32();
+	if (marker === ITEM_FORMAT_V2) {
+		const count = reader.readInt32();
+		for (let index = 0; index < count; index++) {
+			const mime = resolveCopyQMime(reader.readName());
+			const compressed = reader.readBool();
+			const bytes = reader.readBytes();
+			item.set(mime, compressed ? qUncompress(bytes) : bytes);
+		}
+		return item;
+	}
+	if (marker < 0) throw invalid("unknown item format");
+
+	// Original layout: every value is compressed
+	for (let index = 0; index < marker; index++) {
+		const mime = resolveCopyQMime(reader.readName());
+		item.set(mime, qUncompress(reader.readBytes()));
+	}
+	return item;
+};
+
+/**
+ * Reads the items of a CopyQ tab data file, top of the tab first.
+ * Pure function.
+ *
+ * @throws if the data is not a CopyQ tab
+ */
+export const parseCopyQTab = (data: Buffer): CopyQItem[] => {
+	const reader = createDataReader(data);
+
+	// Some versions start with a "CopyQ ..." header string
+	const header = data.subarray(4, 14);
+	if (header.equals(Buffer.from("\0C\0o\0p\0y\0Q", "latin1"))) {
+		reader.readName();
+	}
+
+	const count = reader.readInt32();
+	if (count < 0) throw invalid("negative item count");
+	const items: CopyQItem[] = [];
+	for (let index = 0; index < count && !reader.atEnd(); index++) {
+		items.push(readItem(reader));
+	}
+	return items;
+};
+
+/**
+ * Reads the formats saved in a synchronized item's `_copyq.dat` file.
+ * Pure function.
+ */
+export const parseCopyQItemData = (data: Buffer): CopyQItem =>
+	readItem(createDataReader(data));
+
+/**
+ * Maps a CopyQ item to a history item. Text items keep their HTML; items
+ * with only a URI list become file items.
+ * Pure function.
+ *
+ * @returns null for items with neither text nor files, such as images
+ */
+export const toImportItem = (
+	item: CopyQItem,
+	createdAt: string,
+): ImportHistoryItem | null => {
+	const read = (mime: string): string | null =>
+		item.get(mime)?.toString("utf-8") || null;
+
+	const text = read(MIME_TEXT);
+	const files = parseUriList(read(MIME_URI_LIST) ?? "");
+	const tags = (read(MIME_TAGS) ?? "")
+		.split(/[,\n]/)
+		.map((tag) => tag.trim())
+		.filter((tag) => tag.length > 0);
+	const common = {
+		created_at: createdAt,
+		favorite: false,
+		pinned: item.has(MIME_PINNED),
+		use_count: 1,
+		tags,
+		note: read(MIME_NOTES),
+		source: null,
+		rtf: null,
+		image: null,
+	};
+
+	if (text?.trim()) {
+		return { ...common, type: "text", content: text, html: read(MIME_HTML) };
+	}
+	if (files.length > 0) {
+		const content = formatStoredFileList(files);
+		return { ...common, type: "files", content, html: null };
+	}
+	return null;
+};
+
+/**
+ * Dates a tab's items: CopyQ keeps no copy times, so the top item takes
+ * the file's modification time and each one below it a second earlier,
+ * keeping the tab's order.
+ * Pure function.
+ */
+const dateTabItems = (
+	items: CopyQItem[],
+	modifiedAt: number,
+): DatedCopyQItem[] =>
+	items.map((item, index) => ({
+		item,
+		createdAt: toHistoryTimestamp(modifiedAt - index * 1000),
+	}));
+
+// ============================================================================
+// Import
+// ============================================================================
+
+const readTabFile = async (filePath: string): Promise<DatedCopyQItem[]> => {
+	const data = await readImportFile(filePath);
+	const { mtimeMs } = await fs.promises.stat(filePath);
+	return dateTabItems(parseCopyQTab(data), mtimeMs);
+};
+
+/**
+ * Reads a synchronized tab directory: each item is a group of files
+ * sharing a base name, dated by their newest modification time.
+ */
+const readSyncDirectory = async (
+	directory: string,
+): Promise<DatedCopyQItem[]> => {
+	const groups = new Map<string, { item: CopyQItem; modifiedAt: number }>();
+	const entries = await fs.promises.readdir(directory, { withFileTypes: true });
+
+	for (const entry of entries) {
+		if (!entry.isFile()) continue;
+		const name = entry.name;
+		let base: string;
+		let formats: CopyQItem;
+		const filePath = path.join(directory, name);
+
+		if (name.endsWith(SYNC_DATA_SUFFIX)) {
+			base = name.slice(0, -SYNC_DATA_SUFFIX.length);
+			formats = parseCopyQItemData(await readImportFile(filePath));
+		} else if (name.endsWith(SYNC_NOTE_SUFFIX)) {
+			base = name.slice(0, -SYNC_NOTE_SUFFIX.length);
+			formats = new Map([[MIME_NOTES, await readImportFile(filePath)]]);
+		} else {
+			const mime = SYNC_EXTENSIONS[path.extname(name).toLowerCase()];
+			if (!mime) continue;
+			base = name.slice(0, -path.extname(name).length);
+			formats = new Map([[mime, await readImportFile(filePath)]]);
+		}
+
+		const { mtimeMs } = await fs.promises.stat(filePath);
+		const group = groups.get(base) ?? { item: new Map(), modifiedAt: 0 };
+		for (const [mime, bytes] of formats) group.item.set(mime, bytes);
+		group.modifiedAt = Math.max(group.modifiedAt, mtimeMs);
+		groups.set(base, group);
+	}
+
+	return [...groups.values()].map(({ item, modifiedAt }) => ({
+		item,
+		createdAt: toHistoryTimestamp(modifiedAt),
+	}));
+};
+
+/**
+ * Imports CopyQ history. `sourcePath` is one of:
+ * - a tab data file (`copyq_tab_*.dat`)
+ * - CopyQ's configuration directory, importing every tab in it
+ * - a synchronized tab directory, with items stored as files
+ *
+ * Items already in history, images and other formats without text or
+ * files are skipped. Tags, notes and pinned state are kept; CopyQ tabs
+ * themselves are not.
+ *
+ * @throws if the path is not absolute or does not hold CopyQ data
+ */
+export const importCopyQ = async (
+	importItems: ImportItems,
+	sourcePath: unknown,
+): Promise<HistoryImportReport> => {
+	if (typeof sourcePath !== "string" || !path.isAbsolute(sourcePath)) {
+		throw new Error("Invalid import: path must be absolute");
+	}
+
+	let items: DatedCopyQItem[] = [];
+	if (!(await fs.promises.stat(sourcePath)).isDirectory()) {
+		items = await readTabFile(sourcePath);
+	} else {
+		const tabFiles = (await fs.promises.readdir(sourcePath)).filter((name) =>
+			TAB_FILE_PATTERN.test(name),
+		);
+		for (const name of tabFiles) {
+			items.push(...(await readTabFile(path.join(sourcePath, name))));
+		}
+		if (tabFiles.length === 0) {
+			items = await readSyncDirectory(sourcePath);
+		}
+	}
+
+	return importInBatches(importItems, items, ({ item, createdAt }) =>
+		toImportItem(item, createdAt),
+	);
+};
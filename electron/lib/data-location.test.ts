@@ -0,0 +1,158 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createDataLocation,
+	getDataLocationFilePath,
+	parseDataLocationFile,
+	parseMoveTarget,
+} from "./data-location.js";
+
+describe("parseMoveTarget", () => {
+	const dataPath = path.resolve("/data/clipboard");
+
+	it("accepts an absolute folder outside the current one", () => {
+		expect(parseMoveTarget(path.resolve("/backup/clips/"), dataPath)).toBe(
+			path.resolve("/backup/clips"),
+		);
+		expect(parseMoveTarget(path.resolve("/data/clipboard-2"), dataPath)).toBe(
+			path.resolve("/data/clipboard-2"),
+		);
+	});
+
+	it("rejects relative paths and nested folders", () => {
+		expect(() => parseMoveTarget("clips", dataPath)).toThrow(
+			"expected an absolute path",
+		);
+		for (const target of ["/data", "/data/clipboard/new"]) {
+			expect(() => parseMoveTarget(path.resolve(target), dataPath)).toThrow(
+				"cannot contain or be inside the current one",
+			);
+		}
+	});
+});
+
+describe("parseDataLocationFile", () => {
+	it("ignores values that are not absolute paths", () => {
+		expect(
+			parseDataLocationFile({ dataPath: "relative", pendingMove: 1 }),
+		).toEqual({ dataPath: null, pendingMove: null, lastError: null });
+	});
+});
+
+describe("createDataLocation", () => {
+	const tempDirs: string[] = [];
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-location-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * A default data folder holding a database and an attachment, and an
+	 * executable folder without portable data.
+	 */
+	const createFolders = () => {
+		const root = createTempDir();
+		const defaultPath = path.join(root, "userData");
+		fs.mkdirSync(path.join(defaultPath, "attachments"), { recursive: true });
+		fs.writeFileSync(path.join(defaultPath, "clipboard.db"), "db");
+		fs.writeFileSync(path.join(defaultPath, "attachments", "1.bin"), "png");
+		fs.writeFileSync(path.join(defaultPath, "SingletonLock"), "");
+		const executableDir = path.join(root, "app");
+		fs.mkdirSync(executableDir);
+		return { root, defaultPath, executableDir };
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("prefers the flag, then portable data, then the default", () => {
+		const { defaultPath, executableDir } = createFolders();
+		const deps = { defaultPath, executableDir, portable: false };
+
+		expect(
+			createDataLocation({ ...deps, dataDirFlag: "/clips" }).getStatus(),
+		).toMatchObject({ dataPath: path.resolve("/clips"), source: "flag" });
+		expect(
+			createDataLocation({ ...deps, dataDirFlag: null }).getStatus(),
+		).toMatchObject({ dataPath: defaultPath, source: "default" });
+
+		fs.mkdirSync(path.join(executableDir, "data"));
+		expect(
+			createDataLocation({ ...deps, dataDirFlag: null }).getStatus(),
+		).toMatchObject({
+			dataPath: path.join(executableDir, "data"),
+			source: "portable",
+		});
+	});
+
+	it("moves the data at the next start and remembers the folder", () => {
+		const { root, defaultPath, executableDir } = createFolders();
+		const deps = { defaultPath, executableDir, dataDirFlag: null };
+		const target = path.join(root, "moved");
+
+		const location = createDataLocation({ ...deps, portable: false });
+		expect(location.requestMove(target).pendingMove).toBe(target);
+
+		const next = createDataLocation({ ...deps, portable: false });
+		next.applyPendingMove();
+
+		expect(next.getStatus()).toEqual({
+			dataPath: target,
+			source: "config",
+			pendingMove: null,
+			lastError: null,
+		});
+		expect(fs.readFileSync(path.join(target, "clipboard.db"), "utf-8")).toBe(
+			"db",
+		);
+		expect(fs.existsSync(path.join(target, "attachments", "1.bin"))).toBe(
+			true,
+		);
+		expect(fs.readdirSync(defaultPath).sort()).toEqual([
+			"SingletonLock",
+			"data-location.json",
+		]);
+	});
+
+	it("keeps the data in place when the target is not empty", () => {
+		const { root, defaultPath, executableDir } = createFolders();
+		const target = path.join(root, "taken");
+		fs.mkdirSync(target);
+		fs.writeFileSync(path.join(target, "notes.txt"), "mine");
+		const deps = { defaultPath, executableDir, dataDirFlag: null };
+		createDataLocation({ ...deps, portable: false }).requestMove(target);
+
+		const next = createDataLocation({ ...deps, portable: false });
+		next.applyPendingMove();
+
+		expect(next.getStatus()).toMatchObject({
+			dataPath: defaultPath,
+			pendingMove: null,
+			lastError: `${target} is not empty`,
+		});
+		expect(fs.existsSync(path.join(defaultPath, "clipboard.db"))).toBe(true);
+		expect(fs.readdirSync(target)).toEqual(["notes.txt"]);
+		expect(fs.existsSync(getDataLocationFilePath(defaultPath))).toBe(true);
+	});
+
+	it("refuses to move a folder chosen by the flag or portable mode", () => {
+		const { root, defaultPath, executableDir } = createFolders();
+		const location = createDataLocation({
+			defaultPath,
+			executableDir,
+			dataDirFlag: null,
+			portable: true,
+		});
+
+		expect(() => location.requestMove(path.join(root, "moved"))).toThrow(
+			"The data folder is set by portable mode",
+		);
+	});
+});
@@ -0,0 +1,299 @@
+import fs from "node:fs";
+import path from "node:path";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * How the data folder was chosen, in order of precedence.
+ */
+export type DataLocationSource = "flag" | "portable" | "config" | "default";
+
+/**
+ * Where profiles, databases, attachments and settings are kept.
+ */
+export type DataLocationStatus = {
+	dataPath: string;
+	source: DataLocationSource;
+	/** Folder the data moves to on the next start; null if none */
+	pendingMove: string | null;
+	/** Why the last move failed; null once one succeeds */
+	lastError: string | null;
+};
+
+/**
+ * Contents of `data-location.json`, which always stays in the default
+ * app data folder so it can be found before the data folder is known.
+ */
+type DataLocationFile = {
+	/** Chosen data folder; null for the default */
+	dataPath: string | null;
+	pendingMove: string | null;
+	lastError: string | null;
+};
+
+const DATA_LOCATION_FILENAME = "data-location.json";
+
+/**
+ * Folder next to the executable holding data in portable mode. Its
+ * presence turns portable mode on without the flag.
+ */
+const PORTABLE_DIRECTORY = "data";
+
+/**
+ * Files left behind by a move: the location file itself and Chromium's
+ * single instance lock, held by the running app.
+ */
+const UNMOVED_FILE_PATTERN = /^(?:data-location\.json|Singleton\w*)$/;
+
+const EMPTY_DATA_LOCATION_FILE: DataLocationFile = {
+	dataPath: null,
+	pendingMove: null,
+	lastError: null,
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Whether `child` is `parent` or inside it.
+ * Pure function.
+ */
+const isWithin = (parent: string, child: string): boolean => {
+	const relative = path.relative(parent, child);
+	return (
+		relative === "" ||
+		(!relative.startsWith("..") && !path.isAbsolute(relative))
+	);
+};
+
+/**
+ * Validates the folder to move data to.
+ * Pure function. Whether it exists and is empty is checked when moving.
+ *
+ * @throws if the path is not absolute, or contains or sits inside the
+ *   current data folder
+ */
+export const parseMoveTarget = (input: unknown, dataPath: string): string => {
+	if (typeof input !== "string" || !path.isAbsolute(input)) {
+		throw new Error("Invalid data folder: expected an absolute path");
+	}
+	const target = path.resolve(input);
+	if (isWithin(dataPath, target) || isWithin(target, dataPath)) {
+		throw new Error(
+			"Invalid data folder: it cannot contain or be inside the current one",
+		);
+	}
+	return target;
+};
+
+/**
+ * Reads `data-location.json`, ignoring values that are not absolute
+ * paths.
+ * Pure function.
+ */
+export const parseDataLocationFile = (input: unknown): DataLocationFile => {
+	if (typeof input !== "object" || input === null) {
+		return { ...EMPTY_DATA_LOCATION_FILE };
+	}
+	const { dataPath, pendingMove, lastError } = input as Partial<
+		Record<keyof DataLocationFile, unknown>
+	>;
+	const absolute = (value: unknown): string | null =>
+		typeof value === "string" && path.isAbsolute(value) ? value : null;
+	return {
+		dataPath: absolute(dataPath),
+		pendingMove: absolute(pendingMove),
+		lastError: typeof lastError === "string" ? lastError : null,
+	};
+};
+
+// ============================================================================
+// Data Location File
+// ============================================================================
+
+export const getDataLocationFilePath = (defaultPath: string) =>
+	path.join(defaultPath, DATA_LOCATION_FILENAME);
+
+/**
+ * Reads the saved location, falling back to the default if missing or
+ * invalid.
+ */
+const readDataLocationFromFile = (filePath: string): DataLocationFile => {
+	if (!fs.existsSync(filePath)) return { ...EMPTY_DATA_LOCATION_FILE };
+
+	try {
+		return parseDataLocationFile(
+			JSON.parse(fs.readFileSync(filePath, "utf-8")),
+		);
+	} catch (error) {
+		console.error("Failed to read data location, using defaults:", error);
+		return { ...EMPTY_DATA_LOCATION_FILE };
+	}
+};
+
+const writeDataLocationToFile = (
+	filePath: string,
+	file: DataLocationFile,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(file, null, 2), "utf-8");
+};
+
+const isMovedEntry = (name: string): boolean =>
+	!UNMOVED_FILE_PATTERN.test(name);
+
+/**
+ * Lists a folder's entries that a move carries over.
+ */
+const listMovedEntries = (folder: string): string[] =>
+	fs.existsSync(folder)
+		? fs.readdirSync(folder).filter(isMovedEntry)
+		: [];
+
+/**
+ * Copies the data folder's entries into an empty or missing target, then
+ * deletes them from the source. Nothing is deleted unless every entry was
+ * copied; a failed copy removes what it wrote.
+ *
+ * @throws if the target holds data or the copy fails
+ */
+const moveDataFolder = (source: string, target: string): void => {
+	if (listMovedEntries(target).length > 0) {
+		throw new Error(`${target} is not empty`);
+	}
+	const entries = listMovedEntries(source);
+
+	fs.mkdirSync(target, { recursive: true });
+	try {
+		for (const name of entries) {
+			fs.cpSync(path.join(source, name), path.join(target, name), {
+				recursive: true,
+				errorOnExist: true,
+				force: false,
+				preserveTimestamps: true,
+			});
+		}
+	} catch (error) {
+		for (const name of entries) {
+			fs.rmSync(path.join(target, name), { recursive: true, force: true });
+		}
+		throw error;
+	}
+
+	for (const name of entries) {
+		fs.rmSync(path.join(source, name), { recursive: true, force: true });
+	}
+};
+
+// ============================================================================
+// Data Location Module
+// ============================================================================
+
+export type DataLocationDeps = {
+	/** Electron's default userData folder */
+	defaultPath: string;
+	/** Folder of the executable, holding the portable data folder */
+	executableDir: string;
+	/** `--data-dir`, resolved against the working directory */
+	dataDirFlag: string | null;
+	/** `--portable` */
+	portable: boolean;
+};
+
+/**
+ * Creates the data location: the folder holding every profile's data.
+ * It is `--data-dir`, else the portable folder next to the executable
+ * (with `--portable`, or once it exists), else a folder chosen with
+ * `requestMove`, else Electron's userData. Data is moved at the next
+ * start, before anything opens it.
+ */
+export const createDataLocation = (deps: DataLocationDeps) => {
+	const filePath = getDataLocationFilePath(deps.defaultPath);
+	let file = readDataLocationFromFile(filePath);
+	const portablePath = path.join(deps.executableDir, PORTABLE_DIRECTORY);
+
+	const getSource = (): DataLocationSource => {
+		if (deps.dataDirFlag !== null) return "flag";
+		if (deps.portable || fs.existsSync(portablePath)) return "portable";
+		return file.dataPath === null ? "default" : "config";
+	};
+
+	const getDataPath = (): string => {
+		if (deps.dataDirFlag !== null) return path.resolve(deps.dataDirFlag);
+		if (getSource() === "portable") return portablePath;
+		return file.dataPath ?? deps.defaultPath;
+	};
+
+	/** Whether the folder comes from the saved location, which can move */
+	const isMovable = (): boolean => {
+		const source = getSource();
+		return source === "default" || source === "config";
+	};
+
+	const getStatus = (): DataLocationStatus => ({
+		dataPath: getDataPath(),
+		source: getSource(),
+		pendingMove: file.pendingMove,
+		lastError: file.lastError,
+	});
+
+	const save = (next: DataLocationFile): void => {
+		writeDataLocationToFile(filePath, next);
+		file = next;
+	};
+
+	/**
+	 * Schedules moving the data to another folder at the next start; the
+	 * caller relaunches the app. Passing the current folder cancels a
+	 * pending move.
+	 *
+	 * @throws if the folder is chosen by a flag or portable mode, or the
+	 *   target is invalid
+	 */
+	const requestMove = (input: unknown): DataLocationStatus => {
+		if (!isMovable()) {
+			throw new Error(
+				`The data folder is set by ${getSource() === "flag" ? "--data-dir" : "portable mode"}`,
+			);
+		}
+		const dataPath = getDataPath();
+		if (typeof input === "string" && path.resolve(input) === dataPath) {
+			save({ ...file, pendingMove: null });
+		} else {
+			save({ ...file, pendingMove: parseMoveTarget(input, dataPath) });
+		}
+		return getStatus();
+	};
+
+	/**
+	 * Runs a pending move. Call once at startup, in the only running
+	 * instance, before anything reads the data folder. A failed move
+	 * leaves the data where it was and is reported in `lastError`.
+	 */
+	const applyPendingMove = (): void => {
+		const target = file.pendingMove;
+		if (target === null || !isMovable()) return;
+		try {
+			moveDataFolder(getDataPath(), target);
+			save({
+				dataPath: target === path.resolve(deps.defaultPath) ? null : target,
+				pendingMove: null,
+				lastError: null,
+			});
+		} catch (error) {
+			console.error("Failed to move the data folder:", error);
+			save({
+				...file,
+				pendingMove: null,
+				lastError: error instanceof Error ? error.message : String(error),
+			});
+		}
+	};
+
+	return { getStatus, requestMove, applyPendingMove };
+};
+
+export type DataLocation = ReturnType<typeof createDataLocation>;
@@ -0,0 +1,55 @@
+import type Database from "better-sqlite3";
+
+/**
+ * How long a statement waits for another connection's lock (an
+ * external tool, or a backup reading the file) before failing with
+ * "database is locked" (ms).
+ */
+const BUSY_TIMEOUT_MS = 5000;
+
+/**
+ * Page cache per connection; negative sizes are in KiB.
+ */
+const CACHE_SIZE_KIB = 16 * 1024;
+
+/**
+ * Tunes a read-write connection to the history database.
+ *
+ * WAL lets readers keep reading while a write is in progress instead of
+ * waiting on it, and makes commits a single append, so `synchronous =
+ * NORMAL` is safe: a power loss can drop the last commits but never
+ * corrupts the file. WAL mode is stored in the file, so this only has
+ * work to do on first open. Some file systems (network shares) cannot
+ * hold the WAL index; the connection then keeps the rollback journal.
+ *
+ * better-sqlite3 runs every statement synchronously on the main thread,
+ * so a pool of connections would not let anything run in parallel; one
+ * connection with these settings serves the app.
+ *
+ * @returns The journal mode in effect
+ */
+export const configureConnection = (db: Database.Database): string => {
+	db.pragma(`busy_timeout = ${BUSY_TIMEOUT_MS}`);
+	let journalMode = String(db.pragma("journal_mode", { simple: true }));
+	if (journalMode !== "wal" && !db.memory) {
+		journalMode = String(db.pragma("journal_mode = WAL", { simple: true }));
+		if (journalMode !== "wal") {
+			console.warn(
+				`WAL is not available for this database; using ${journalMode} journal mode`,
+			);
+		}
+	}
+	db.pragma(`synchronous = ${journalMode === "wal" ? "NORMAL" : "FULL"}`);
+	db.pragma(`cache_size = -${CACHE_SIZE_KIB}`);
+	db.pragma("temp_store = MEMORY");
+	return journalMode;
+};
+
+/**
+ * Copies the write-ahead log into the database file and empties it, so
+ * the file alone holds every change: before the file is copied or
+ * deleted data must be gone from disk. Does nothing outside WAL mode.
+ */
+export const checkpointWal = (db: Database.Database): void => {
+	db.pragma("wal_checkpoint(TRUNCATE)");
+};
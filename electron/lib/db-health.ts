@@ -0,0 +1,342 @@
+import fs from "node:fs";
+import type Database from "better-sqlite3";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Result of checking the database.
+ */
+export type DatabaseCheck = {
+	ok: boolean;
+	/** Problems reported by SQLite's integrity check; empty when ok */
+	integrityErrors: string[];
+	/** Full-text indexes that no longer match their content */
+	ftsErrors: string[];
+	/** Whether `quick_check` ran instead of the full `integrity_check` */
+	quick: boolean;
+	durationMs: number;
+};
+
+/**
+ * Rows copied from one table of a damaged database.
+ */
+export type SalvagedTable = {
+	name: string;
+	rows: number;
+	/** Why copying stopped early; null when every row was read */
+	error: string | null;
+};
+
+/**
+ * What recovering the database did: nothing, rebuilt its indexes in
+ * place, or copied what could be read into a fresh file.
+ */
+export type DatabaseRecovery = {
+	action: "none" | "repaired" | "salvaged";
+	/** The check that found the problem */
+	before: DatabaseCheck;
+	/** The check after recovering */
+	after: DatabaseCheck;
+	/** Tables copied; null unless salvaged */
+	salvaged: SalvagedTable[] | null;
+	/** Where the damaged file was kept; null unless salvaged */
+	corruptPath: string | null;
+};
+
+type PragmaRow = Record<string, unknown>;
+
+type SchemaEntry = {
+	type: "table" | "index" | "view" | "trigger";
+	name: string;
+	sql: string | null;
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/**
+ * Problems listed at most; SQLite stops checking after this many.
+ */
+const MAX_INTEGRITY_ERRORS = 100;
+
+const FTS5_PATTERN = /^CREATE\s+VIRTUAL\s+TABLE\s+.*\bUSING\s+fts5\b/is;
+
+/**
+ * FTS5 tables indexing another table; only these can be rebuilt.
+ */
+const EXTERNAL_CONTENT_PATTERN = /\bcontent\s*=\s*'[^']+'/i;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const describeError = (error: unknown): string =>
+	error instanceof Error ? error.message : String(error);
+
+const quoteIdentifier = (name: string): string =>
+	`"${name.replace(/"/g, '""')}"`;
+
+const readSchema = (db: Database.Database): SchemaEntry[] =>
+	db
+		.prepare(
+			"SELECT type, name, sql FROM sqlite_master WHERE name NOT LIKE 'sqlite_%' ORDER BY rowid",
+		)
+		.all() as SchemaEntry[];
+
+const listFtsTables = (schema: SchemaEntry[]): SchemaEntry[] =>
+	schema.filter(
+		(entry) =>
+			entry.type === "table" &&
+			entry.sql !== null &&
+			FTS5_PATTERN.test(entry.sql),
+	);
+
+/**
+ * Runs SQLite's integrity check and checks every FTS5 index against its
+ * content table. The quick check skips index contents, taking seconds
+ * instead of minutes on large databases, so it suits startup.
+ */
+export const checkDatabase = (
+	db: Database.Database,
+	options: { quick?: boolean } = {},
+): DatabaseCheck => {
+	const startedAt = Date.now();
+	const quick = options.quick ?? false;
+	const pragma = quick ? "quick_check" : "integrity_check";
+	let integrityErrors: string[];
+	try {
+		const rows = db.pragma(`${pragma}(${MAX_INTEGRITY_ERRORS})`) as PragmaRow[];
+		integrityErrors = rows
+			.map((row) => String(Object.values(row)[0]))
+			.filter((message) => message !== "ok");
+	} catch (error) {
+		integrityErrors = [describeError(error)];
+	}
+
+	const ftsErrors: string[] = [];
+	try {
+		for (const { name } of listFtsTables(readSchema(db))) {
+			const table = quoteIdentifier(name);
+			try {
+				// rank = 1 also compares the index with its content table
+				db.exec(
+					`INSERT INTO ${table}(${table}, rank) VALUES ('integrity-check', 1)`,
+				);
+			} catch (error) {
+				ftsErrors.push(`${name}: ${describeError(error)}`);
+			}
+		}
+	} catch (error) {
+		ftsErrors.push(describeError(error));
+	}
+
+	return {
+		ok: integrityErrors.length === 0 && ftsErrors.length === 0,
+		integrityErrors,
+		ftsErrors,
+		quick,
+		durationMs: Date.now() - startedAt,
+	};
+};
+
+/**
+ * Rebuilds every index and full-text index in place, which fixes
+ * damage confined to them, then runs a full check. Failures are left
+ * for the check to report.
+ */
+export const repairDatabase = (db: Database.Database): DatabaseCheck => {
+	try {
+		db.exec("REINDEX");
+	} catch (error) {
+		console.error("Failed to rebuild indexes:", error);
+	}
+	try {
+		for (const { name, sql } of listFtsTables(readSchema(db))) {
+			if (!EXTERNAL_CONTENT_PATTERN.test(sql ?? "")) continue;
+			const table = quoteIdentifier(name);
+			db.exec(`INSERT INTO ${table}(${table}) VALUES ('rebuild')`);
+		}
+	} catch (error) {
+		console.error("Failed to rebuild full-text index:", error);
+	}
+	return checkDatabase(db);
+};
+
+/**
+ * Copies everything readable from a damaged database into an empty one
+ * with the same schema. Tables are created and filled first, then
+ * indexes, views and triggers, so triggers do not fire on copied rows
+ * (counting attachment references twice, for one); full-text indexes
+ * are rebuilt from their content. A table stops at its first unreadable
+ * row and the rest are still copied.
+ */
+export const salvageDatabase = (
+	source: Database.Database,
+	target: Database.Database,
+): SalvagedTable[] => {
+	const schema = readSchema(source);
+	const ftsTables = listFtsTables(schema);
+	// FTS5 keeps its index in shadow tables it creates itself
+	const isShadowTable = (name: string) =>
+		ftsTables.some((fts) => name.startsWith(`${fts.name}_`));
+	const tables = schema.filter(
+		(entry) =>
+			entry.type === "table" &&
+			entry.sql !== null &&
+			!FTS5_PATTERN.test(entry.sql) &&
+			!isShadowTable(entry.name),
+	);
+
+	target.pragma("foreign_keys = OFF");
+	const salvaged: SalvagedTable[] = [];
+	for (const table of tables) {
+		target.exec(table.sql as string);
+		const name = quoteIdentifier(table.name);
+		let rows = 0;
+		let error: string | null = null;
+		target.exec("BEGIN");
+		try {
+			const select = source.prepare(`SELECT * FROM ${name}`).raw();
+			const columns = select.columns().length;
+			const insert = target.prepare(
+				`INSERT OR IGNORE INTO ${name} VALUES (${Array(columns).fill("?").join(", ")})`,
+			);
+			for (const row of select.iterate() as IterableIterator<unknown[]>) {
+				insert.run(row);
+				rows++;
+			}
+		} catch (copyError) {
+			error = describeError(copyError);
+		}
+		target.exec("COMMIT");
+		salvaged.push({ name: table.name, rows, error });
+	}
+
+	try {
+		const sequences = source
+			.prepare("SELECT name, seq FROM sqlite_sequence")
+			.all() as { name: string; seq: number }[];
+		const update = target.prepare(
+			"INSERT INTO sqlite_sequence (name, seq) VALUES (?, ?)",
+		);
+		target.exec("DELETE FROM sqlite_sequence");
+		for (const sequence of sequences) update.run(sequence.name, sequence.seq);
+	} catch (error) {
+		// No AUTOINCREMENT tables, or the sequences are unreadable; ids
+		// then continue from the largest copied one
+		console.warn("Sequences not salvaged:", describeError(error));
+	}
+	target.pragma(
+		`user_version = ${Number(source.pragma("user_version", { simple: true }))}`,
+	);
+
+	for (const fts of ftsTables) {
+		target.exec(fts.sql as string);
+		if (EXTERNAL_CONTENT_PATTERN.test(fts.sql as string)) {
+			const table = quoteIdentifier(fts.name);
+			target.exec(`INSERT INTO ${table}(${table}) VALUES ('rebuild')`);
+		}
+	}
+	for (const type of ["index", "view", "trigger"] as const) {
+		for (const entry of schema) {
+			// Automatic indexes for UNIQUE and PRIMARY KEY have no SQL
+			if (entry.type !== type || entry.sql === null) continue;
+			try {
+				target.exec(entry.sql);
+			} catch (error) {
+				// A unique index whose damaged copy let duplicates in
+				console.error(`Failed to recreate ${type} ${entry.name}:`, error);
+			}
+		}
+	}
+	target.pragma("foreign_keys = ON");
+	return salvaged;
+};
+
+// ============================================================================
+// Recovery
+// ============================================================================
+
+/**
+ * Path the damaged file is kept at after salvaging.
+ */
+const getCorruptPath = (dbPath: string, time: Date): string =>
+	`${dbPath}.corrupt-${time.toISOString().replace(/[:.]/g, "-")}`;
+
+const moveDatabaseFile = (from: string, to: string): void => {
+	fs.renameSync(from, to);
+	for (const suffix of ["-wal", "-shm", "-journal"]) {
+		if (fs.existsSync(`${from}${suffix}`)) {
+			fs.renameSync(`${from}${suffix}`, `${to}${suffix}`);
+		}
+	}
+};
+
+export type RecoverDatabaseDeps = {
+	/** The open connection to the damaged file; closed when salvaging */
+	db: Database.Database;
+	dbPath: string;
+	/** Opens a connection the way the app does */
+	connect: (file: string) => Database.Database;
+	/** The check that found the problem */
+	check: DatabaseCheck;
+	now?: () => Date;
+};
+
+/**
+ * Recovers a database that failed its check: first by rebuilding its
+ * indexes, and if that is not enough by salvaging its rows into a fresh
+ * file that takes its place. The damaged file is kept next to it.
+ * Returns the connection to use from now on.
+ */
+export const recoverDatabase = (
+	deps: RecoverDatabaseDeps,
+): { db: Database.Database; recovery: DatabaseRecovery } => {
+	const repaired = repairDatabase(deps.db);
+	if (repaired.ok) {
+		return {
+			db: deps.db,
+			recovery: {
+				action: "repaired",
+				before: deps.check,
+				after: repaired,
+				salvaged: null,
+				corruptPath: null,
+			},
+		};
+	}
+
+	const salvagePath = `${deps.dbPath}.salvage`;
+	for (const suffix of ["", "-wal", "-shm", "-journal"]) {
+		fs.rmSync(`${salvagePath}${suffix}`, { force: true });
+	}
+	const target = deps.connect(salvagePath);
+	let salvaged: SalvagedTable[];
+	try {
+		salvaged = salvageDatabase(deps.db, target);
+	} finally {
+		target.close();
+	}
+	deps.db.close();
+
+	const corruptPath = getCorruptPath(
+		deps.dbPath,
+		(deps.now ?? (() => new Date()))(),
+	);
+	moveDatabaseFile(deps.dbPath, corruptPath);
+	moveDatabaseFile(salvagePath, deps.dbPath);
+	const db = deps.connect(deps.dbPath);
+	return {
+		db,
+		recovery: {
+			action: "salvaged",
+			before: deps.check,
+			after: checkDatabase(db),
+			salvaged,
+			corruptPath,
+		},
+	};
+};
@@ -0,0 +1,244 @@
+import fs from "node:fs";
+import net from "node:net";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createDBusService,
+	DBUS_INTERFACE,
+	DBUS_OBJECT_PATH,
+	type DBusHandlers,
+	getSessionBusPath,
+	parseBusAddress,
+} from "./dbus-service.js";
+import {
+	type DBusMessage,
+	DBusMessageType,
+	marshalMessage,
+	unmarshalMessage,
+} from "./dbus-wire.js";
+
+describe("parseBusAddress", () => {
+	it("reads unix path and abstract addresses", () => {
+		expect(parseBusAddress("unix:path=/run/user/1000/bus")).toBe(
+			"/run/user/1000/bus",
+		);
+		expect(parseBusAddress("unix:abstract=/tmp/dbus-x,guid=ab12")).toBe(
+			"\0/tmp/dbus-x",
+		);
+		expect(parseBusAddress("tcp:host=localhost;unix:path=/tmp/a%20b")).toBe(
+			"/tmp/a b",
+		);
+	});
+
+	it("returns null without a unix socket", () => {
+		expect(parseBusAddress("tcp:host=localhost,port=1234")).toBeNull();
+	});
+});
+
+describe("getSessionBusPath", () => {
+	it("falls back to the runtime directory socket", () => {
+		expect(getSessionBusPath({}, 1000)).toBe("/run/user/1000/bus");
+		expect(
+			getSessionBusPath({ DBUS_SESSION_BUS_ADDRESS: "unix:path=/b" }, 1000),
+		).toBe("/b");
+	});
+});
+
+/**
+ * Tiny stand-in for dbus-daemon: accepts any EXTERNAL auth, answers Hello
+ * and RequestName, and records what the service sends.
+ */
+const createFakeBus = (socketPath: string, requestNameResult = 1) => {
+	const received: DBusMessage[] = [];
+	let client: net.Socket | null = null;
+	const listeners: ((message: DBusMessage) => void)[] = [];
+
+	const server = net.createServer((socket) => {
+		client = socket;
+		let buffer = Buffer.alloc(0);
+		let authenticated = false;
+		socket.on("data", (chunk: Buffer) => {
+			buffer = Buffer.concat([buffer, chunk]);
+			if (!authenticated) {
+				const begin = buffer.indexOf("BEGIN\r\n");
+				if (buffer.includes("AUTH EXTERNAL") && begin === -1) {
+					if (!buffer.includes("\r\n")) return;
+					socket.write("OK 0123456789abcdef\r\n");
+					buffer = Buffer.alloc(0);
+					return;
+				}
+				if (begin === -1) return;
+				authenticated = true;
+				buffer = buffer.subarray(begin + 7);
+			}
+			for (;;) {
+				const result = unmarshalMessage(buffer);
+				if (!result) break;
+				buffer = buffer.subarray(result.size);
+				const { message } = result;
+				received.push(message);
+				for (const listener of listeners) listener(message);
+				if (message.member === "Hello" || message.member === "RequestName") {
+					socket.write(
+						marshalMessage({
+							type: DBusMessageType.methodReturn,
+							flags: 0,
+							serial: 1000 + message.serial,
+							replySerial: message.serial,
+							signature: message.member === "Hello" ? "s" : "u",
+							body: [message.member === "Hello" ? ":1.1" : requestNameResult],
+						}),
+					);
+				}
+			}
+		});
+	});
+
+	return {
+		listen: () =>
+			new Promise<void>((resolve) => server.listen(socketPath, resolve)),
+		close: () => server.close(),
+		received,
+		send: (message: DBusMessage) => client?.write(marshalMessage(message)),
+		nextMessage: () =>
+			new Promise<DBusMessage>((resolve) => {
+				listeners.push(resolve);
+			}),
+	};
+};
+
+describe.skipIf(process.platform === "win32")("createDBusService", () => {
+	const cleanups: (() => void)[] = [];
+
+	const createHandlers = (): DBusHandlers => ({
+		list: vi.fn().mockReturnValue([
+			{ id: 3, type: "text", preview: "hi", created_at: "2024-01-01" },
+		]),
+		search: vi.fn().mockReturnValue([]),
+		get: vi.fn().mockReturnValue("content"),
+		copy: vi.fn(),
+		paste: vi.fn().mockResolvedValue(undefined),
+	});
+
+	const setup = async (requestNameResult = 1) => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-dbus-"));
+		const socketPath = path.join(dir, "bus");
+		const bus = createFakeBus(socketPath, requestNameResult);
+		await bus.listen();
+		const handlers = createHandlers();
+		const service = createDBusService({
+			handlers,
+			env: { DBUS_SESSION_BUS_ADDRESS: `unix:path=${socketPath}` },
+			uid: 1000,
+		});
+		cleanups.push(() => {
+			service.stop();
+			bus.close();
+			fs.rmSync(dir, { recursive: true, force: true });
+		});
+		return { bus, handlers, service };
+	};
+
+	afterEach(() => {
+		for (const cleanup of cleanups.splice(0)) cleanup();
+	});
+
+	it("claims the name and answers method calls", async () => {
+		const { bus, handlers, service } = await setup();
+		await service.start();
+
+		expect(bus.received.map((message) => message.member)).toEqual([
+			"Hello",
+			"RequestName",
+		]);
+		expect(bus.received[1].body).toEqual(["org.clipboardmanager", 4]);
+
+		const reply = bus.nextMessage();
+		bus.send({
+			type: DBusMessageType.methodCall,
+			flags: 0,
+			serial: 5,
+			sender: ":1.9",
+			path: DBUS_OBJECT_PATH,
+			interface: DBUS_INTERFACE,
+			member: "List",
+			signature: "u",
+			body: [10],
+		});
+
+		await expect(reply).resolves.toMatchObject({
+			type: DBusMessageType.methodReturn,
+			replySerial: 5,
+			destination: ":1.9",
+			signature: "a(isss)",
+			body: [[[3, "text", "hi", "2024-01-01"]]],
+		});
+		expect(handlers.list).toHaveBeenCalledWith(10);
+	});
+
+	it("replies with errors for bad calls and handler failures", async () => {
+		const { bus, handlers, service } = await setup();
+		vi.mocked(handlers.get).mockImplementation(() => {
+			throw new Error("History is locked");
+		});
+		await service.start();
+
+		const invalid = bus.nextMessage();
+		bus.send({
+			type: DBusMessageType.methodCall,
+			flags: 0,
+			serial: 6,
+			path: DBUS_OBJECT_PATH,
+			interface: DBUS_INTERFACE,
+			member: "Get",
+			signature: "s",
+			body: ["1"],
+		});
+		await expect(invalid).resolves.toMatchObject({
+			type: DBusMessageType.error,
+			errorName: "org.freedesktop.DBus.Error.InvalidArgs",
+		});
+
+		const failed = bus.nextMessage();
+		bus.send({
+			type: DBusMessageType.methodCall,
+			flags: 0,
+			serial: 7,
+			path: DBUS_OBJECT_PATH,
+			interface: DBUS_INTERFACE,
+			member: "Get",
+			signature: "u",
+			body: [1],
+		});
+		await expect(failed).resolves.toMatchObject({
+			errorName: "org.clipboardmanager.Error.Failed",
+			body: ["History is locked"],
+		});
+	});
+
+	it("emits ItemAdded signals", async () => {
+		const { bus, service } = await setup();
+		await service.start();
+
+		const signal = bus.nextMessage();
+		service.emitItemAdded({
+			id: 4,
+			type: "text",
+			preview: "new",
+			created_at: "2024-01-02",
+		});
+
+		await expect(signal).resolves.toMatchObject({
+			type: DBusMessageType.signal,
+			member: "ItemAdded",
+			body: [[4, "text", "new", "2024-01-02"]],
+		});
+	});
+
+	it("fails when another process owns the name", async () => {
+		const { service } = await setup(3);
+
+		await expect(service.start()).rejects.toThrow("already owned");
+	});
+});
@@ -0,0 +1,413 @@
+import net from "node:net";
+import {
+	type DBusMessage,
+	DBusMessageType,
+	marshalMessage,
+	NO_REPLY_EXPECTED,
+	unmarshalMessage,
+} from "./dbus-wire.js";
+import type { HttpApiItem } from "./http-api-server.js";
+
+export const DBUS_SERVICE_NAME = "org.clipboardmanager";
+export const DBUS_OBJECT_PATH = "/org/clipboardmanager";
+export const DBUS_INTERFACE = "org.clipboardmanager.History";
+
+/**
+ * Signature of an item on the bus: (id, type, preview, created_at).
+ */
+const ITEM_SIGNATURE = "(isss)";
+
+const BUS_NAME = "org.freedesktop.DBus";
+const BUS_PATH = "/org/freedesktop/DBus";
+const ERROR_FAILED = "org.clipboardmanager.Error.Failed";
+const ERROR_UNKNOWN_METHOD = "org.freedesktop.DBus.Error.UnknownMethod";
+const ERROR_INVALID_ARGS = "org.freedesktop.DBus.Error.InvalidArgs";
+
+/**
+ * `RequestName` flag: fail instead of queueing behind another owner.
+ */
+const DO_NOT_QUEUE = 0x4;
+const PRIMARY_OWNER = 1;
+
+/**
+ * What the app does for each method of `org.clipboardmanager.History`.
+ */
+export type DBusHandlers = {
+	/** Most recently copied items */
+	list: (limit: number) => HttpApiItem[];
+	search: (query: string, limit: number) => HttpApiItem[];
+	/** Text content of an item */
+	get: (id: number) => string;
+	/** Places an item on the clipboard */
+	copy: (id: number) => void;
+	/** Places an item on the clipboard and pastes it into the focused app */
+	paste: (id: number) => Promise<void>;
+};
+
+export type DBusServiceDeps = {
+	handlers: DBusHandlers;
+	env: Record<string, string | undefined>;
+	uid: number;
+};
+
+/**
+ * Methods by member name, with their argument and reply signatures.
+ */
+const METHODS: Record<string, { args: string; reply: string }> = {
+	List: { args: "u", reply: `a${ITEM_SIGNATURE}` },
+	Search: { args: "su", reply: `a${ITEM_SIGNATURE}` },
+	Get: { args: "u", reply: "s" },
+	Copy: { args: "u", reply: "" },
+	Paste: { args: "u", reply: "" },
+};
+
+const INTROSPECTION_XML = `<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node>
+  <interface name="${DBUS_INTERFACE}">
+    <method name="List">
+      <arg name="limit" type="u" direction="in"/>
+      <arg name="items" type="a${ITEM_SIGNATURE}" direction="out"/>
+    </method>
+    <method name="Search">
+      <arg name="query" type="s" direction="in"/>
+      <arg name="limit" type="u" direction="in"/>
+      <arg name="items" type="a${ITEM_SIGNATURE}" direction="out"/>
+    </method>
+    <method name="Get">
+      <arg name="id" type="u" direction="in"/>
+      <arg name="content" type="s" direction="out"/>
+    </method>
+    <method name="Copy">
+      <arg name="id" type="u" direction="in"/>
+    </method>
+    <method name="Paste">
+      <arg name="id" type="u" direction="in"/>
+    </method>
+    <signal name="ItemAdded">
+      <arg name="item" type="${ITEM_SIGNATURE}"/>
+    </signal>
+  </interface>
+  <interface name="org.freedesktop.DBus.Introspectable">
+    <method name="Introspect">
+      <arg name="xml" type="s" direction="out"/>
+    </method>
+  </interface>
+  <interface name="org.freedesktop.DBus.Peer">
+    <method name="Ping"/>
+  </interface>
+</node>
+`;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Finds the socket path in a D-Bus address such as
+ * `unix:path=/run/user/1000/bus`; abstract sockets get a leading NUL.
+ * Pure function.
+ *
+ * @returns the first usable path, or null if there is none
+ */
+export const parseBusAddress = (address: string): string | null => {
+	for (const entry of address.split(";")) {
+		const [transport, params = ""] = entry.split(/:(.*)/s);
+		if (transport !== "unix") continue;
+
+		for (const param of params.split(",")) {
+			const [key, value] = param.split(/=(.*)/s);
+			if (!value) continue;
+			if (key === "path") return decodeURIComponent(value);
+			if (key === "abstract") return `\0${decodeURIComponent(value)}`;
+		}
+	}
+	return null;
+};
+
+/**
+ * Session bus socket for the current user.
+ * Pure function.
+ */
+export const getSessionBusPath = (
+	env: Record<string, string | undefined>,
+	uid: number,
+): string | null => {
+	const address = env.DBUS_SESSION_BUS_ADDRESS;
+	return address ? parseBusAddress(address) : `/run/user/${uid}/bus`;
+};
+
+const toBusItem = (item: HttpApiItem): [number, string, string, string] => [
+	item.id,
+	item.type,
+	item.preview,
+	item.created_at,
+];
+
+// ============================================================================
+// D-Bus Service
+// ============================================================================
+
+/**
+ * Creates the `org.clipboardmanager` session bus service for GNOME and KDE
+ * shells and scripts. Authenticates with SASL EXTERNAL, then owns the name
+ * and serves `/org/clipboardmanager`.
+ */
+export const createDBusService = (deps: DBusServiceDeps) => {
+	let socket: net.Socket | null = null;
+	let serial = 0;
+	const pending = new Map<
+		number,
+		{ resolve: (body: unknown[]) => void; reject: (error: Error) => void }
+	>();
+
+	const send = (message: Omit<DBusMessage, "serial" | "flags">): number => {
+		serial += 1;
+		socket?.write(marshalMessage({ flags: 0, serial, ...message }));
+		return serial;
+	};
+
+	const call = (
+		member: string,
+		signature: string,
+		body: unknown[],
+	): Promise<unknown[]> =>
+		new Promise((resolve, reject) => {
+			const id = send({
+				type: DBusMessageType.methodCall,
+				destination: BUS_NAME,
+				path: BUS_PATH,
+				interface: BUS_NAME,
+				member,
+				signature,
+				body,
+			});
+			pending.set(id, { resolve, reject });
+		});
+
+	const reply = (
+		request: DBusMessage,
+		signature: string,
+		body: unknown[],
+	): void => {
+		send({
+			type: DBusMessageType.methodReturn,
+			replySerial: request.serial,
+			destination: request.sender,
+			signature,
+			body,
+		});
+	};
+
+	const replyError = (
+		request: DBusMessage,
+		name: string,
+		text: string,
+	): void => {
+		send({
+			type: DBusMessageType.error,
+			replySerial: request.serial,
+			destination: request.sender,
+			errorName: name,
+			signature: "s",
+			body: [text],
+		});
+	};
+
+	/**
+	 * Runs one of our methods; resolves to the reply body.
+	 */
+	const invoke = async (
+		member: string,
+		args: unknown[],
+	): Promise<unknown[]> => {
+		switch (member) {
+			case "List":
+				return [deps.handlers.list(args[0] as number).map(toBusItem)];
+			case "Search": {
+				const [query, limit] = args as [string, number];
+				return [deps.handlers.search(query, limit).map(toBusItem)];
+			}
+			case "Get":
+				return [deps.handlers.get(args[0] as number)];
+			case "Copy":
+				deps.handlers.copy(args[0] as number);
+				return [];
+			case "Paste":
+				await deps.handlers.paste(args[0] as number);
+				return [];
+			default:
+				return [];
+		}
+	};
+
+	const handleMethodCall = (message: DBusMessage): void => {
+		const wantsReply = (message.flags & NO_REPLY_EXPECTED) === 0;
+		const respond = (signature: string, body: unknown[]) => {
+			if (wantsReply) reply(message, signature, body);
+		};
+		const fail = (name: string, text: string) => {
+			if (wantsReply) replyError(message, name, text);
+		};
+
+		if (message.interface === "org.freedesktop.DBus.Peer") {
+			if (message.member === "Ping") respond("", []);
+			else fail(ERROR_UNKNOWN_METHOD, `Unknown method: ${message.member}`);
+			return;
+		}
+		if (message.path !== DBUS_OBJECT_PATH) {
+			fail(ERROR_UNKNOWN_METHOD, `No object at ${message.path}`);
+			return;
+		}
+		if (
+			message.interface === "org.freedesktop.DBus.Introspectable" &&
+			message.member === "Introspect"
+		) {
+			respond("s", [INTROSPECTION_XML]);
+			return;
+		}
+
+		const method =
+			message.interface === DBUS_INTERFACE || !message.interface
+				? METHODS[message.member ?? ""]
+				: undefined;
+		if (!method) {
+			fail(ERROR_UNKNOWN_METHOD, `Unknown method: ${message.member}`);
+			return;
+		}
+		if ((message.signature ?? "") !== method.args) {
+			fail(ERROR_INVALID_ARGS, `Expected arguments (${method.args})`);
+			return;
+		}
+
+		invoke(message.member as string, message.body)
+			.then((body) => respond(method.reply, body))
+			.catch((error) => {
+				fail(
+					ERROR_FAILED,
+					error instanceof Error ? error.message : String(error),
+				);
+			});
+	};
+
+	const handleMessage = (message: DBusMessage): void => {
+		if (message.type === DBusMessageType.methodCall) {
+			handleMethodCall(message);
+			return;
+		}
+		const waiting =
+			message.replySerial === undefined
+				? undefined
+				: pending.get(message.replySerial);
+		if (!waiting) return;
+		pending.delete(message.replySerial as number);
+		if (message.type === DBusMessageType.error) {
+			waiting.reject(
+				new Error(`${message.errorName}: ${String(message.body[0] ?? "")}`),
+			);
+		} else {
+			waiting.resolve(message.body);
+		}
+	};
+
+	/**
+	 * Connects and authenticates, resolving once the bus accepts messages.
+	 */
+	const connect = (path: string): Promise<net.Socket> =>
+		new Promise((resolve, reject) => {
+			const next = net.createConnection(path, () => {
+				const uid = Buffer.from(String(deps.uid)).toString("hex");
+				next.write(`\0AUTH EXTERNAL ${uid}\r\n`);
+			});
+			let authenticated = false;
+			let buffer = Buffer.alloc(0);
+
+			next.on("data", (chunk: Buffer) => {
+				buffer = Buffer.concat([buffer, chunk]);
+				if (!authenticated) {
+					const end = buffer.indexOf("\r\n");
+					if (end === -1) return;
+					const line = buffer.subarray(0, end).toString("utf-8");
+					buffer = buffer.subarray(end + 2);
+					if (!line.startsWith("OK ")) {
+						next.destroy();
+						reject(new Error(`D-Bus authentication failed: ${line}`));
+						return;
+					}
+					authenticated = true;
+					next.write("BEGIN\r\n");
+					resolve(next);
+				}
+				try {
+					for (;;) {
+						const result = unmarshalMessage(buffer);
+						if (!result) break;
+						buffer = buffer.subarray(result.size);
+						handleMessage(result.message);
+					}
+				} catch (error) {
+					console.error("Invalid D-Bus message, disconnecting:", error);
+					next.destroy();
+				}
+			});
+			next.once("error", reject);
+			next.on("close", () => {
+				if (socket === next) socket = null;
+				for (const waiting of pending.values()) {
+					waiting.reject(new Error("D-Bus connection closed"));
+				}
+				pending.clear();
+			});
+		});
+
+	const stop = (): void => {
+		socket?.end();
+		socket = null;
+	};
+
+	/**
+	 * Connects to the session bus and claims `org.clipboardmanager`.
+	 *
+	 * @throws if there is no session bus or another process owns the name
+	 */
+	const start = async (): Promise<void> => {
+		if (socket) return;
+		const path = getSessionBusPath(deps.env, deps.uid);
+		if (!path) {
+			throw new Error("No D-Bus session bus address");
+		}
+
+		socket = await connect(path);
+		socket.on("error", (error) => {
+			console.error("D-Bus connection error:", error);
+		});
+		await call("Hello", "", []);
+		const [result] = await call("RequestName", "su", [
+			DBUS_SERVICE_NAME,
+			DO_NOT_QUEUE,
+		]);
+		if (result !== PRIMARY_OWNER) {
+			stop();
+			throw new Error(`${DBUS_SERVICE_NAME} is already owned on the bus`);
+		}
+	};
+
+	/**
+	 * Emits the `ItemAdded` signal for a new capture.
+	 */
+	const emitItemAdded = (item: HttpApiItem): void => {
+		if (!socket) return;
+		send({
+			type: DBusMessageType.signal,
+			path: DBUS_OBJECT_PATH,
+			interface: DBUS_INTERFACE,
+			member: "ItemAdded",
+			signature: ITEM_SIGNATURE,
+			body: [toBusItem(item)],
+		});
+	};
+
+	return { start, stop, emitItemAdded };
+};
+
+export type DBusService = ReturnType<typeof createDBusService>;
@@ -0,0 +1,109 @@
+import { describe, expect, it } from "vitest";
+import {
+	type DBusMessage,
+	DBusMessageType,
+	marshalMessage,
+	parseSignature,
+	unmarshalMessage,
+} from "./dbus-wire.js";
+
+describe("parseSignature", () => {
+	it("parses basic, array and struct types", () => {
+		expect(parseSignature("su")).toEqual([
+			{ kind: "basic", code: "s" },
+			{ kind: "basic", code: "u" },
+		]);
+		expect(parseSignature("a(is)")).toEqual([
+			{
+				kind: "array",
+				element: {
+					kind: "struct",
+					fields: [
+						{ kind: "basic", code: "i" },
+						{ kind: "basic", code: "s" },
+					],
+				},
+			},
+		]);
+	});
+
+	it("rejects malformed and unsupported signatures", () => {
+		expect(() => parseSignature("a")).toThrow("Invalid D-Bus signature");
+		expect(() => parseSignature("(is")).toThrow("Invalid D-Bus signature");
+		expect(() => parseSignature("()")).toThrow("Invalid D-Bus signature");
+		expect(() => parseSignature("a{sv}")).toThrow("Invalid D-Bus signature");
+	});
+});
+
+describe("marshalMessage", () => {
+	const call: DBusMessage = {
+		type: DBusMessageType.methodCall,
+		flags: 0,
+		serial: 7,
+		path: "/org/clipboardmanager",
+		interface: "org.clipboardmanager.History",
+		member: "Search",
+		destination: "org.clipboardmanager",
+		signature: "su",
+		body: ["hello", 5],
+	};
+
+	it("writes a little-endian header with 8-aligned body", () => {
+		const bytes = marshalMessage(call);
+
+		expect(bytes[0]).toBe("l".charCodeAt(0));
+		expect(bytes[1]).toBe(DBusMessageType.methodCall);
+		expect(bytes[3]).toBe(1);
+		expect(bytes.readUInt32LE(8)).toBe(7);
+		const bodyLength = bytes.readUInt32LE(4);
+		expect((bytes.length - bodyLength) % 8).toBe(0);
+	});
+
+	it("round-trips through unmarshalMessage", () => {
+		const bytes = marshalMessage(call);
+
+		expect(unmarshalMessage(bytes)).toEqual({
+			message: call,
+			size: bytes.length,
+		});
+	});
+
+	it("round-trips arrays of structs", () => {
+		const reply: DBusMessage = {
+			type: DBusMessageType.methodReturn,
+			flags: 0,
+			serial: 8,
+			replySerial: 7,
+			signature: "a(isss)",
+			body: [
+				[
+					[1, "text", "héllo", "2024-01-01"],
+					[-2, "image", "Image 2x2", "2024-01-02"],
+				],
+			],
+		};
+
+		expect(unmarshalMessage(marshalMessage(reply))?.message).toEqual(reply);
+	});
+});
+
+describe("unmarshalMessage", () => {
+	it("waits for a whole message", () => {
+		const bytes = marshalMessage({
+			type: DBusMessageType.signal,
+			flags: 0,
+			serial: 1,
+			path: "/a",
+			interface: "a.b",
+			member: "C",
+			signature: "s",
+			body: ["x"],
+		});
+
+		expect(unmarshalMessage(bytes.subarray(0, 10))).toBeNull();
+		expect(unmarshalMessage(bytes.subarray(0, bytes.length - 1))).toBeNull();
+		expect(
+			unmarshalMessage(Buffer.concat([bytes, Buffer.from([0x6c])]))?.size,
+		).toBe(bytes.length);
+	});
+});
@@ -0,0 +1,404 @@
+/**
+ * Minimal D-Bus wire format: enough of the spec to own a name on the
+ * session bus, answer method calls and emit signals. Supports the basic
+ * types `y b i u s o g`, arrays, structs and variants, little-endian only.
+ */
+
+export type DBusType =
+	| { kind: "basic"; code: "y" | "b" | "i" | "u" | "s" | "o" | "g" }
+	| { kind: "array"; element: DBusType }
+	| { kind: "struct"; fields: DBusType[] }
+	| { kind: "variant" };
+
+/**
+ * A variant value; `signature` is a single complete type.
+ */
+export type DBusVariant = { signature: string; value: unknown };
+
+export const DBusMessageType = {
+	methodCall: 1,
+	methodReturn: 2,
+	error: 3,
+	signal: 4,
+} as const;
+
+/**
+ * Flag on method calls whose caller does not want a reply.
+ */
+export const NO_REPLY_EXPECTED = 0x1;
+
+export type DBusMessage = {
+	type: number;
+	flags: number;
+	serial: number;
+	path?: string;
+	interface?: string;
+	member?: string;
+	errorName?: string;
+	replySerial?: number;
+	destination?: string;
+	sender?: string;
+	/** Signature of `body`; empty or missing for no arguments */
+	signature?: string;
+	body: unknown[];
+};
+
+/**
+ * Header field codes and the type of each field's value.
+ */
+const HEADER_FIELDS = [
+	{ code: 1, key: "path", signature: "o" },
+	{ code: 2, key: "interface", signature: "s" },
+	{ code: 3, key: "member", signature: "s" },
+	{ code: 4, key: "errorName", signature: "s" },
+	{ code: 5, key: "replySerial", signature: "u" },
+	{ code: 6, key: "destination", signature: "s" },
+	{ code: 7, key: "sender", signature: "s" },
+	{ code: 8, key: "signature", signature: "g" },
+] as const;
+
+const HEADER_FIELDS_TYPE: DBusType = {
+	kind: "array",
+	element: {
+		kind: "struct",
+		fields: [{ kind: "basic", code: "y" }, { kind: "variant" }],
+	},
+};
+
+/**
+ * Length of the fixed part of the header, up to the header fields array.
+ */
+const FIXED_HEADER_LENGTH = 16;
+
+const LITTLE_ENDIAN = "l".charCodeAt(0);
+const PROTOCOL_VERSION = 1;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Parses a signature into its complete types.
+ * Pure function.
+ *
+ * @throws if the signature is malformed or uses an unsupported type
+ */
+export const parseSignature = (signature: string): DBusType[] => {
+	let index = 0;
+
+	const parseOne = (): DBusType => {
+		const code = signature[index++];
+		switch (code) {
+			case "y":
+			case "b":
+			case "i":
+			case "u":
+			case "s":
+			case "o":
+			case "g":
+				return { kind: "basic", code };
+			case "v":
+				return { kind: "variant" };
+			case "a":
+				return { kind: "array", element: parseOne() };
+			case "(": {
+				const fields: DBusType[] = [];
+				while (signature[index] !== ")") {
+					if (index >= signature.length) {
+						throw new Error(`Invalid D-Bus signature: ${signature}`);
+					}
+					fields.push(parseOne());
+				}
+				index++;
+				if (fields.length === 0) {
+					throw new Error(`Invalid D-Bus signature: ${signature}`);
+				}
+				return { kind: "struct", fields };
+			}
+			default:
+				throw new Error(`Invalid D-Bus signature: ${signature}`);
+		}
+	};
+
+	const types: DBusType[] = [];
+	while (index < signature.length) types.push(parseOne());
+	return types;
+};
+
+const alignmentOf = (type: DBusType): number => {
+	if (type.kind === "struct") return 8;
+	if (type.kind === "array") return 4;
+	if (type.kind === "variant") return 1;
+	return type.code === "y" || type.code === "g" ? 1 : 4;
+};
+
+const padding = (offset: number, alignment: number): number =>
+	(alignment - (offset % alignment)) % alignment;
+
+/**
+ * Marshals values into a buffer; offsets are relative to the message start,
+ * which keeps alignment right as long as the body starts 8-aligned.
+ */
+const createWriter = () => {
+	let buffer = Buffer.alloc(256);
+	let offset = 0;
+
+	const ensure = (size: number): void => {
+		if (offset + size <= buffer.length) return;
+		const next = Buffer.alloc(Math.max(buffer.length * 2, offset + size));
+		buffer.copy(next, 0, 0, offset);
+		buffer = next;
+	};
+
+	const align = (alignment: number): void => {
+		const pad = padding(offset, alignment);
+		ensure(pad);
+		buffer.fill(0, offset, offset + pad);
+		offset += pad;
+	};
+
+	const writeUInt32 = (value: number): void => {
+		align(4);
+		ensure(4);
+		buffer.writeUInt32LE(value, offset);
+		offset += 4;
+	};
+
+	const writeBytes = (bytes: Buffer): void => {
+		ensure(bytes.length);
+		bytes.copy(buffer, offset);
+		offset += bytes.length;
+	};
+
+	const writeValue = (type: DBusType, value: unknown): void => {
+		switch (type.kind) {
+			case "basic":
+				switch (type.code) {
+					case "y":
+						writeBytes(Buffer.from([Number(value) & 0xff]));
+						return;
+					case "b":
+						writeUInt32(value ? 1 : 0);
+						return;
+					case "i":
+						align(4);
+						ensure(4);
+						buffer.writeInt32LE(Number(value), offset);
+						offset += 4;
+						return;
+					case "u":
+						writeUInt32(Number(value));
+						return;
+					case "s":
+					case "o": {
+						const bytes = Buffer.from(String(value), "utf-8");
+						writeUInt32(bytes.length);
+						writeBytes(Buffer.concat([bytes, Buffer.from([0])]));
+						return;
+					}
+					case "g": {
+						const bytes = Buffer.from(String(value), "utf-8");
+						writeBytes(Buffer.from([bytes.length]));
+						writeBytes(Buffer.concat([bytes, Buffer.from([0])]));
+						return;
+					}
+				}
+				return;
+			case "array": {
+				writeUInt32(0);
+				const lengthOffset = offset - 4;
+				align(alignmentOf(type.element));
+				const start = offset;
+				for (const element of value as unknown[]) {
+					writeValue(type.element, element);
+				}
+				buffer.writeUInt32LE(offset - start, lengthOffset);
+				return;
+			}
+			case "struct": {
+				align(8);
+				const fields = value as unknown[];
+				for (let i = 0; i < type.fields.length; i++) {
+					writeValue(type.fields[i], fields[i]);
+				}
+				return;
+			}
+			case "variant": {
+				const variant = value as DBusVariant;
+				const [inner] = parseSignature(variant.signature);
+				writeValue({ kind: "basic", code: "g" }, variant.signature);
+				writeValue(inner, variant.value);
+				return;
+			}
+		}
+	};
+
+	return { writeValue, align, finish: () => buffer.subarray(0, offset) };
+};
+
+/**
+ * Unmarshals values from a buffer that starts at the message start.
+ */
+const createReader = (buffer: Buffer, start = 0) => {
+	let offset = start;
+
+	const need = (size: number): void => {
+		if (offset + size > buffer.length) {
+			throw new Error("Truncated D-Bus message");
+		}
+	};
+
+	const align = (alignment: number): void => {
+		offset += padding(offset, alignment);
+	};
+
+	const readUInt32 = (): number => {
+		align(4);
+		need(4);
+		const value = buffer.readUInt32LE(offset);
+		offset += 4;
+		return value;
+	};
+
+	const readValue = (type: DBusType): unknown => {
+		switch (type.kind) {
+			case "basic":
+				switch (type.code) {
+					case "y":
+						need(1);
+						return buffer[offset++];
+					case "b":
+						return readUInt32() !== 0;
+					case "i": {
+						align(4);
+						need(4);
+						const value = buffer.readInt32LE(offset);
+						offset += 4;
+						return value;
+					}
+					case "u":
+						return readUInt32();
+					case "s":
+					case "o": {
+						const length = readUInt32();
+						need(length + 1);
+						const value = buffer.toString("utf-8", offset, offset + length);
+						offset += length + 1;
+						return value;
+					}
+					case "g": {
+						need(1);
+						const length = buffer[offset++];
+						need(length + 1);
+						const value = buffer.toString("utf-8", offset, offset + length);
+						offset += length + 1;
+						return value;
+					}
+				}
+				return undefined;
+			case "array": {
+				const length = readUInt32();
+				align(alignmentOf(type.element));
+				need(length);
+				const end = offset + length;
+				const values: unknown[] = [];
+				while (offset < end) values.push(readValue(type.element));
+				return values;
+			}
+			case "struct":
+				align(8);
+				return type.fields.map((field) => readValue(field));
+			case "variant": {
+				const signature = readValue({ kind: "basic", code: "g" }) as string;
+				const [inner] = parseSignature(signature);
+				return { signature, value: readValue(inner) } as DBusVariant;
+			}
+		}
+	};
+
+	return { readValue };
+};
+
+/**
+ * Serializes a message.
+ * Pure function.
+ */
+export const marshalMessage = (message: DBusMessage): Buffer => {
+	const body = createWriter();
+	const types = parseSignature(message.signature ?? "");
+	for (let i = 0; i < types.length; i++) {
+		body.writeValue(types[i], message.body[i]);
+	}
+	const bodyBytes = body.finish();
+
+	const fields: [number, DBusVariant][] = [];
+	for (const field of HEADER_FIELDS) {
+		const value = message[field.key];
+		if (value === undefined || (field.key === "signature" && !value)) continue;
+		fields.push([field.code, { signature: field.signature, value }]);
+	}
+
+	const header = createWriter();
+	header.writeValue({ kind: "basic", code: "y" }, LITTLE_ENDIAN);
+	header.writeValue({ kind: "basic", code: "y" }, message.type);
+	header.writeValue({ kind: "basic", code: "y" }, message.flags);
+	header.writeValue({ kind: "basic", code: "y" }, PROTOCOL_VERSION);
+	header.writeValue({ kind: "basic", code: "u" }, bodyBytes.length);
+	header.writeValue({ kind: "basic", code: "u" }, message.serial);
+	header.writeValue(HEADER_FIELDS_TYPE, fields);
+	header.align(8);
+
+	return Buffer.concat([header.finish(), bodyBytes]);
+};
+
+/**
+ * Reads the first message in a buffer of bus data.
+ * Pure function.
+ *
+ * @returns the message and its length in bytes, or null if the buffer does
+ *   not hold a whole message yet
+ * @throws if the message is malformed or big-endian
+ */
+export const unmarshalMessage = (
+	buffer: Buffer,
+): { message: DBusMessage; size: number } | null => {
+	if (buffer.length < FIXED_HEADER_LENGTH) return null;
+	if (buffer[0] !== LITTLE_ENDIAN) {
+		throw new Error("Unsupported D-Bus message: big-endian");
+	}
+
+	const bodyLength = buffer.readUInt32LE(4);
+	const fieldsLength = buffer.readUInt32LE(12);
+	const headerLength =
+		FIXED_HEADER_LENGTH +
+		fieldsLength +
+		padding(FIXED_HEADER_LENGTH + fieldsLength, 8);
+	const size = headerLength + bodyLength;
+	if (buffer.length < size) return null;
+
+	const message = buffer.subarray(0, size);
+	const header = createReader(message, 12);
+	const fields = header.readValue(HEADER_FIELDS_TYPE) as [
+		number,
+		DBusVariant,
+	][];
+
+	const result: DBusMessage = {
+		type: message[1],
+		flags: message[2],
+		serial: message.readUInt32LE(8),
+		body: [],
+	};
+	for (const [code, variant] of fields) {
+		const field = HEADER_FIELDS.find((candidate) => candidate.code === code);
+		if (field) {
+			(result as Record<string, unknown>)[field.key] = variant.value;
+		}
+	}
+
+	const body = createReader(message, headerLength);
+	result.body = parseSignature(result.signature ?? "").map((type) =>
+		body.readValue(type),
+	);
+	return { message: result, size };
+};
@@ -0,0 +1,310 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { LogEntry } from "./logger.js";
+import { createZipArchive, type ZipEntry } from "./zip.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * What went wrong: an error nothing caught in the main process, a
+ * rejected promise nothing handled, or a renderer or helper process
+ * (GPU, utility) that died.
+ */
+export type CrashKind =
+	| "uncaughtException"
+	| "unhandledRejection"
+	| "renderer"
+	| "childProcess";
+
+/**
+ * Versions and platform included in every report and bundle.
+ */
+export type SystemInfo = {
+	appVersion: string;
+	electronVersion: string;
+	chromeVersion: string;
+	platform: NodeJS.Platform;
+	arch: string;
+	osRelease: string;
+};
+
+/**
+ * A crash report, written as JSON to the `crashes` folder.
+ */
+export type CrashReport = SystemInfo & {
+	time: string;
+	kind: CrashKind;
+	message: string;
+	stack: string | null;
+	/** The log entries leading up to the crash, oldest first */
+	recentLogs: LogEntry[];
+};
+
+/**
+ * A crash report as listed, without its stack and logs.
+ */
+export type CrashReportSummary = {
+	fileName: string;
+	time: string;
+	kind: CrashKind;
+	message: string;
+};
+
+/**
+ * Result of writing a diagnostics bundle.
+ */
+export type DiagnosticsExport = {
+	filePath: string;
+	/** Names of the files in the zip */
+	files: string[];
+};
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+const CRASHES_DIRECTORY = "crashes";
+
+/**
+ * Crash reports kept; older ones are deleted as new ones are written.
+ */
+const MAX_CRASH_REPORTS = 20;
+
+/**
+ * Log entries included in a crash report.
+ */
+const CRASH_REPORT_LOG_ENTRIES = 100;
+
+const CRASH_FILE_PATTERN = /^crash-[\dTZ-]+\.json$/;
+
+/**
+ * Setting keys whose values are replaced, in JSON (`"token": "…"`), TOML
+ * (`token = "…"`) and query strings (`?token=…`).
+ */
+const SECRET_VALUE_PATTERN =
+	/((?:"|\b)(?:[a-z_-]*(?:token|secret|password|passphrase|api[-_]?key)|cookie)"?\s*[:=]\s*)("(?:[^"\\]|\\.)*"|[^\s"&,}\]]+)/gi;
+
+const AUTH_HEADER_PATTERN = /\b(Bearer|Basic)\s+[\w\-.~+/]+=*/g;
+
+const URL_CREDENTIALS_PATTERN =
+	/\b([a-z][a-z\d+.-]*:\/\/)[^\s/@:]+:[^\s/@]+@/gi;
+
+const EMAIL_PATTERN = /[\w.+-]+@[\w-]+(?:\.[\w-]+)+/g;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Message and stack of anything thrown or rejected.
+ */
+const describeCrash = (
+	error: unknown,
+): { message: string; stack: string | null } => {
+	if (error instanceof Error) {
+		return { message: error.message, stack: error.stack ?? null };
+	}
+	return { message: String(error), stack: null };
+};
+
+/**
+ * Removes what identifies the user or lets someone act as them: the
+ * home folder in paths (which also hides the account name), secrets in
+ * settings, URL credentials, authorization headers and email addresses.
+ * Clipboard contents are never logged, so they need no redaction here.
+ */
+export const redactDiagnosticText = (text: string, homeDir: string): string => {
+	let redacted = text;
+	if (homeDir.length > 1) {
+		// Windows paths appear with escaped backslashes inside JSON
+		for (const form of [JSON.stringify(homeDir).slice(1, -1), homeDir]) {
+			redacted = redacted.split(form).join("~");
+		}
+	}
+	return redacted
+		.replace(URL_CREDENTIALS_PATTERN, "$1<redacted>@")
+		.replace(AUTH_HEADER_PATTERN, "$1 <redacted>")
+		.replace(SECRET_VALUE_PATTERN, (_match, key: string, value: string) =>
+			value.startsWith('"') ? `${key}"<redacted>"` : `${key}<redacted>`,
+		)
+		.replace(EMAIL_PATTERN, "<email>");
+};
+
+/**
+ * File name of a crash report; reports of the same millisecond are
+ * numbered.
+ */
+const getCrashFileName = (time: Date, attempt: number): string => {
+	const stamp = time.toISOString().replace(/[:.]/g, "-");
+	return attempt === 0
+		? `crash-${stamp}.json`
+		: `crash-${stamp}-${attempt}.json`;
+};
+
+// ============================================================================
+// Diagnostics Module
+// ============================================================================
+
+export type DiagnosticsDeps = {
+	userDataPath: string;
+	logDirectory: string;
+	homeDir: string;
+	system: SystemInfo;
+	getRecentLogs: () => LogEntry[];
+	/** Settings files in userData added to bundles, after redaction */
+	settingsFiles?: string[];
+	now?: () => Date;
+};
+
+/**
+ * Creates the crash recorder and diagnostics exporter. Crash reports
+ * are written synchronously so they land before the process exits;
+ * bundles zip the system details, log files, crash reports and chosen
+ * settings, every file redacted.
+ */
+export const createDiagnostics = (deps: DiagnosticsDeps) => {
+	const crashesPath = path.join(deps.userDataPath, CRASHES_DIRECTORY);
+	const now = deps.now ?? (() => new Date());
+
+	const listCrashFiles = (): string[] => {
+		try {
+			return fs
+				.readdirSync(crashesPath)
+				.filter((name) => CRASH_FILE_PATTERN.test(name))
+				.sort()
+				.reverse();
+		} catch (error) {
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") return [];
+			throw error;
+		}
+	};
+
+	/**
+	 * Writes a crash report and deletes the oldest beyond the limit.
+	 * Never throws: it runs while something else has already failed.
+	 * Returns the report's path, or null if it could not be written.
+	 */
+	const recordCrash = (kind: CrashKind, error: unknown): string | null => {
+		try {
+			const time = now();
+			const recentLogs = deps.getRecentLogs();
+			const report: CrashReport = {
+				time: time.toISOString(),
+				kind,
+				...describeCrash(error),
+				...deps.system,
+				recentLogs: recentLogs.slice(
+					Math.max(recentLogs.length - CRASH_REPORT_LOG_ENTRIES, 0),
+				),
+			};
+			fs.mkdirSync(crashesPath, { recursive: true });
+			let attempt = 0;
+			let filePath = path.join(crashesPath, getCrashFileName(time, attempt));
+			while (fs.existsSync(filePath)) {
+				attempt++;
+				filePath = path.join(crashesPath, getCrashFileName(time, attempt));
+			}
+			fs.writeFileSync(filePath, JSON.stringify(report, null, 2), "utf-8");
+			for (const name of listCrashFiles().slice(MAX_CRASH_REPORTS)) {
+				fs.rmSync(path.join(crashesPath, name), { force: true });
+			}
+			return filePath;
+		} catch (writeError) {
+			console.error("Failed to write crash report:", writeError);
+			return null;
+		}
+	};
+
+	/**
+	 * Crash reports, newest first. Unreadable reports are skipped.
+	 */
+	const listCrashReports = (): CrashReportSummary[] =>
+		listCrashFiles().flatMap((fileName) => {
+			try {
+				const report = JSON.parse(
+					fs.readFileSync(path.join(crashesPath, fileName), "utf-8"),
+				) as CrashReport;
+				return [
+					{
+						fileName,
+						time: report.time,
+						kind: report.kind,
+						message: redactDiagnosticText(report.message, deps.homeDir),
+					},
+				];
+			} catch (error) {
+				console.error(`Failed to read crash report ${fileName}:`, error);
+				return [];
+			}
+		});
+
+	const readRedacted = (filePath: string): string | null => {
+		try {
+			return redactDiagnosticText(
+				fs.readFileSync(filePath, "utf-8"),
+				deps.homeDir,
+			);
+		} catch (error) {
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") return null;
+			throw error;
+		}
+	};
+
+	/**
+	 * Writes the diagnostics zip to an absolute path.
+	 */
+	const exportBundle = async (
+		filePath: unknown,
+	): Promise<DiagnosticsExport> => {
+		if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+			throw new Error("Invalid diagnostics export: path must be absolute");
+		}
+		const exportedAt = now();
+		const entries: ZipEntry[] = [
+			{
+				name: "system.json",
+				data: redactDiagnosticText(
+					JSON.stringify(
+						{ exportedAt: exportedAt.toISOString(), ...deps.system },
+						null,
+						2,
+					),
+					deps.homeDir,
+				),
+			},
+		];
+		const addFile = (name: string, source: string): void => {
+			const data = readRedacted(source);
+			if (data !== null) entries.push({ name, data });
+		};
+
+		const logFiles = fs.existsSync(deps.logDirectory)
+			? fs
+					.readdirSync(deps.logDirectory)
+					.filter((name) => name.endsWith(".log"))
+					.sort()
+			: [];
+		for (const name of logFiles) {
+			addFile(`logs/${name}`, path.join(deps.logDirectory, name));
+		}
+		for (const name of listCrashFiles()) {
+			addFile(`crashes/${name}`, path.join(crashesPath, name));
+		}
+		for (const name of deps.settingsFiles ?? []) {
+			addFile(`settings/${name}`, path.join(deps.userDataPath, name));
+		}
+
+		await fs.promises.mkdir(path.dirname(filePath), { recursive: true });
+		await fs.promises.writeFile(
+			filePath,
+			createZipArchive(entries, exportedAt),
+		);
+		return { filePath, files: entries.map((entry) => entry.name) };
+	};
+
+	return { recordCrash, listCrashReports, exportBundle };
+};
+
+export type Diagnostics = ReturnType<typeof createDiagnostics>;
@@ -0,0 +1,204 @@
+import path from "node:path";
+import type Database from "better-sqlite3";
+import { describe, expect, it } from "vitest";
+import {
+	type Bitmap,
+	decodeDib,
+	importDitto,
+	parseCfHtml,
+	toImportItem,
+} from "./ditto-import.js";
+import type { ImportHistoryItem } from "./history-repository.js";
+
+/**
+ * Bitmap with a `BITMAPINFOHEADER`; `rows` are listed as stored.
+ */
+const dib = (
+	width: number,
+	height: number,
+	bitCount: number,
+	rows: number[][],
+): Buffer => {
+	const header = Buffer.alloc(40);
+	header.writeUInt32LE(40, 0);
+	header.writeInt32LE(width, 4);
+	header.writeInt32LE(height, 8);
+	header.writeUInt16LE(1, 12);
+	header.writeUInt16LE(bitCount, 14);
+	const stride = Math.ceil((width * bitCount) / 32) * 4;
+	const pixels = rows.map((row) => {
+		const bytes = Buffer.alloc(stride);
+		Buffer.from(row).copy(bytes);
+		return bytes;
+	});
+	return Buffer.concat([header, ...pixels]);
+};
+
+/**
+ * Windows `HTML Format` data: a header of byte offsets, then the markup.
+ */
+const cfHtml = (markup: string): Buffer => {
+	const offset = (value: number) => String(value).padStart(10, "0");
+	const header = (start: number, end: number) =>
+		`Version:0.9\r\nStartHTML:${offset(start)}\r\nEndHTML:${offset(end)}\r\n`;
+	const start = header(0, 0).length;
+	const end = start + Buffer.byteLength(markup);
+	return Buffer.from(`${header(start, end)}${markup}`);
+};
+
+const unicode = (text: string): Buffer => Buffer.from(`${text}\0`, "utf16le");
+
+const encodePng = (bitmap: Bitmap): Buffer =>
+	Buffer.from(`png ${bitmap.width}x${bitmap.height}`);
+
+describe("decodeDib", () => {
+	it("flips bottom-up 24-bit rows and drops row padding", () => {
+		const data = dib(1, 2, 24, [
+			[1, 2, 3],
+			[4, 5, 6],
+		]);
+		expect(decodeDib(data)).toEqual({
+			width: 1,
+			height: 2,
+			pixels: Buffer.from([4, 5, 6, 255, 1, 2, 3, 255]),
+		});
+	});
+
+	it("keeps 32-bit alpha and treats an unused alpha byte as opaque", () => {
+		expect(decodeDib(dib(1, -1, 32, [[1, 2, 3, 128]]))?.pixels).toEqual(
+			Buffer.from([1, 2, 3, 128]),
+		);
+		expect(decodeDib(dib(1, -1, 32, [[1, 2, 3, 0]]))?.pixels).toEqual(
+			Buffer.from([1, 2, 3, 255]),
+		);
+	});
+
+	it("rejects unsupported and truncated bitmaps", () => {
+		expect(decodeDib(dib(1, 1, 8, [[0]]))).toBeNull();
+		expect(decodeDib(dib(2, 2, 24, [[0, 0, 0]]))).toBeNull();
+	});
+});
+
+describe("parseCfHtml", () => {
+	it("extracts the markup after the header", () => {
+		expect(parseCfHtml(cfHtml("<b>héllo</b>"))).toBe("<b>héllo</b>");
+		expect(parseCfHtml(Buffer.from("<b>no header</b>"))).toBeNull();
+	});
+});
+
+describe("toImportItem", () => {
+	const date = Date.UTC(2024, 0, 1, 12) / 1000;
+
+	it("maps text with its HTML and original copy time", () => {
+		const clip = {
+			id: 1,
+			date,
+			formats: new Map([
+				["CF_UNICODETEXT", unicode("héllo")],
+				["HTML Format", cfHtml("<b>héllo</b>")],
+			]),
+		};
+		expect(toImportItem(clip, encodePng)).toEqual({
+			type: "text",
+			content: "héllo",
+			created_at: "2024-01-01 12:00:00",
+			favorite: false,
+			pinned: false,
+			use_count: 1,
+			tags: [],
+			note: null,
+			source: null,
+			rtf: null,
+			html: "<b>héllo</b>",
+			image: null,
+		});
+	});
+
+	it("maps bitmaps to image items", () => {
+		const clip = {
+			id: 1,
+			date,
+			formats: new Map([["CF_DIB", dib(1, 1, 24, [[1, 2, 3]])]]),
+		};
+		expect(toImportItem(clip, encodePng)).toMatchObject({
+			type: "image",
+			content: "",
+			image: { png: Buffer.from("png 1x1"), width: 1, height: 1 },
+		});
+	});
+
+	it("skips clips with neither text nor an image", () => {
+		const clip = { id: 1, date, formats: new Map([["CF_HDROP", unicode("")]]) };
+		expect(toImportItem(clip, encodePng)).toBeNull();
+	});
+});
+
+describe("importDitto", () => {
+	/**
+	 * Answers the importer's queries from `clips` in place of Ditto.db.
+	 */
+	const createConnect =
+		(clips: Array<{ id: number; text: string }>, tables = ["Main", "Data"]) =>
+		() =>
+			({
+				prepare: (sql: string) => ({
+					all: (...params: number[]) => {
+						if (sql.includes("sqlite_master")) {
+							return tables.map((name) => ({ name }));
+						}
+						if (sql.includes("FROM Main")) {
+							const [afterId, limit] = params;
+							return clips
+								.filter((clip) => clip.id > afterId)
+								.slice(0, limit)
+								.map((clip) => ({ id: clip.id, date: 0 }));
+						}
+						const [first, last] = params;
+						return clips
+							.filter((clip) => clip.id >= first && clip.id <= last)
+							.map((clip) => ({
+								clipId: clip.id,
+								format: "CF_UNICODETEXT",
+								data: unicode(clip.text),
+							}));
+					},
+				}),
+				close: () => {},
+			}) as unknown as Database.Database;
+
+	const dbPath = path.resolve("Ditto.db");
+
+	it("imports every clip in batches", async () => {
+		const clips = Array.from({ length: 600 }, (_, index) => ({
+			id: index + 1,
+			text: index === 0 ? " " : `clip ${index}`,
+		}));
+		const stored: ImportHistoryItem[] = [];
+		const importItems = (items: ImportHistoryItem[]) => {
+			stored.push(...items);
+			return items.length;
+		};
+
+		const report = await importDitto(
+			{ importItems, connect: createConnect(clips), encodePng },
+			dbPath,
+		);
+
+		expect(report).toEqual({ imported: 599, skipped: 1 });
+		expect(stored[598].content).toBe("clip 599");
+	});
+
+	it("rejects relative paths and other databases", async () => {
+		const deps = {
+			importItems: () => 0,
+			connect: createConnect([], ["history"]),
+			encodePng,
+		};
+		await expect(importDitto(deps, "Ditto.db")).rejects.toThrow(
+			"path must be absolute",
+		);
+		await expect(importDitto(deps, dbPath)).rejects.toThrow(
+			"not a Ditto database",
+		);
+	});
+});
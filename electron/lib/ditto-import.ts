@@ -0,0 +1,328 @@
+import path from "node:path";
+import type Database from "better-sqlite3";
+import {
+	type HistoryImportReport,
+	type ImportItems,
+	importInBatches,
+	toHistoryTimestamp,
+} from "./history-import.js";
+import type { ImportHistoryItem, StoredImage } from "./history-repository.js";
+import type { ImageSize } from "./images.js";
+
+/**
+ * Uncompressed pixels: 4 bytes per pixel (BGRA), rows top to bottom.
+ */
+export type Bitmap = ImageSize & { pixels: Buffer };
+
+/**
+ * One Ditto clip with the clipboard formats the importer reads.
+ */
+export type DittoClip = {
+	id: number;
+	/** Copy time, seconds since the epoch */
+	date: number;
+	formats: Map<string, Buffer>;
+};
+
+/**
+ * Dependencies of a Ditto import.
+ */
+export type DittoImportDeps = {
+	importItems: ImportItems;
+	/** Opens Ditto's database read-only */
+	connect: (file: string) => Database.Database;
+	/** Encodes pixels decoded from a `CF_DIB` clip as PNG */
+	encodePng: (bitmap: Bitmap) => Buffer;
+};
+
+/**
+ * Windows clipboard formats, as Ditto names them in its `Data` table.
+ */
+const FORMAT_UNICODE_TEXT = "CF_UNICODETEXT";
+const FORMAT_TEXT = "CF_TEXT";
+const FORMAT_HTML = "HTML Format";
+const FORMAT_RTF = "Rich Text Format";
+const FORMAT_PNG = "PNG";
+const FORMAT_DIB = "CF_DIB";
+
+const IMPORTED_FORMATS = [
+	FORMAT_UNICODE_TEXT,
+	FORMAT_TEXT,
+	FORMAT_HTML,
+	FORMAT_RTF,
+	FORMAT_PNG,
+	FORMAT_DIB,
+];
+
+/**
+ * Clips read from Ditto's database at a time; images make rows large.
+ */
+const DITTO_BATCH_SIZE = 500;
+
+const PNG_SIGNATURE = Buffer.from([
+	0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a,
+]);
+
+/** `BITMAPINFOHEADER` size; later header versions extend it */
+const DIB_HEADER_BYTES = 40;
+const BI_RGB = 0;
+const BI_BITFIELDS = 3;
+
+/** Channel masks of 32-bit BGRA pixels, as stored after the header */
+const BGRA_MASKS = [0x00ff0000, 0x0000ff00, 0x000000ff];
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Reads text up to its terminating NUL.
+ */
+const readNulTerminated = (
+	data: Buffer,
+	encoding: "utf16le" | "latin1",
+): string => data.toString(encoding).split("\0")[0];
+
+/**
+ * Extracts the markup from Windows `HTML Format` data, which starts with
+ * a header of byte offsets (`StartHTML:`, `EndHTML:`, ...).
+ * Pure function.
+ *
+ * @returns null if the data has no usable offsets
+ */
+export const parseCfHtml = (data: Buffer): string | null => {
+	const header = data.subarray(0, 512).toString("latin1");
+	const offset = (name: string): number => {
+		const match = new RegExp(`^${name}:(-?\\d+)`, "m").exec(header);
+		return match ? Number(match[1]) : -1;
+	};
+
+	let start = offset("StartHTML");
+	let end = offset("EndHTML");
+	if (start < 0 || end < 0) {
+		start = offset("StartFragment");
+		end = offset("EndFragment");
+	}
+	if (start < 0 || end <= start || end > data.length) return null;
+	return data.subarray(start, end).toString("utf-8") || null;
+};
+
+/**
+ * Reads the size of PNG data from its header.
+ */
+const readPngSize = (data: Buffer): ImageSize | null =>
+	data.length >= 24 && data.subarray(0, 8).equals(PNG_SIGNATURE)
+		? { width: data.readUInt32BE(16), height: data.readUInt32BE(20) }
+		: null;
+
+/**
+ * Decodes an uncompressed 24- or 32-bit device-independent bitmap
+ * (`CF_DIB`): a `BITMAPINFOHEADER` followed by padded rows, bottom-up
+ * unless the height is negative.
+ * Pure function.
+ *
+ * @returns null for other bit depths, compressed or truncated bitmaps
+ */
+export const decodeDib = (data: Buffer): Bitmap | null => {
+	if (data.length < DIB_HEADER_BYTES) return null;
+	const headerSize = data.readUInt32LE(0);
+	const width = data.readInt32LE(4);
+	const storedHeight = data.readInt32LE(8);
+	const bitCount = data.readUInt16LE(14);
+	const compression = data.readUInt32LE(16);
+	const height = Math.abs(storedHeight);
+
+	if (headerSize < DIB_HEADER_BYTES || width <= 0 || height === 0) return null;
+	if (bitCount !== 24 && bitCount !== 32) return null;
+	let pixelsStart = headerSize;
+	if (compression === BI_BITFIELDS) {
+		// Masks follow a plain BITMAPINFOHEADER and are part of later ones
+		if (bitCount !== 32 || data.length < DIB_HEADER_BYTES + 12) return null;
+		const masks = [0, 1, 2].map((index) =>
+			data.readUInt32LE(DIB_HEADER_BYTES + index * 4),
+		);
+		if (masks.some((mask, index) => mask !== BGRA_MASKS[index])) return null;
+		if (headerSize === DIB_HEADER_BYTES) pixelsStart += 12;
+	} else if (compression !== BI_RGB) {
+		return null;
+	}
+
+	const bytesPerPixel = bitCount / 8;
+	const stride = Math.ceil((width * bitCount) / 32) * 4;
+	if (pixelsStart + stride * height > data.length) return null;
+
+	const pixels = Buffer.alloc(width * height * 4);
+	let hasAlpha = false;
+	for (let y = 0; y < height; y++) {
+		const row = storedHeight > 0 ? height - 1 - y : y;
+		for (let x = 0; x < width; x++) {
+			const source = pixelsStart + row * stride + x * bytesPerPixel;
+			const target = (y * width + x) * 4;
+			data.copy(pixels, target, source, source + 3);
+			pixels[target + 3] = bitCount === 32 ? data[source + 3] : 0xff;
+			if (pixels[target + 3] !== 0) hasAlpha = true;
+		}
+	}
+	// Most 32-bit bitmaps leave the alpha byte unused; show them opaque
+	if (!hasAlpha) {
+		for (let index = 3; index < pixels.length; index += 4) {
+			pixels[index] = 0xff;
+		}
+	}
+	return { pixels, width, height };
+};
+
+/**
+ * Reads a clip's image, preferring PNG data over a bitmap.
+ */
+const readImage = (
+	formats: Map<string, Buffer>,
+	encodePng: (bitmap: Bitmap) => Buffer,
+): StoredImage | null => {
+	const png = formats.get(FORMAT_PNG);
+	const size = png ? readPngSize(png) : null;
+	if (png && size) return { png, ...size };
+
+	const dib = formats.get(FORMAT_DIB);
+	const bitmap = dib ? decodeDib(dib) : null;
+	if (!bitmap) return null;
+	return { png: encodePng(bitmap), width: bitmap.width, height: bitmap.height };
+};
+
+/**
+ * Maps a Ditto clip to a history item, keeping its copy time. Text keeps
+ * its HTML and RTF; clips without text become image items.
+ * Pure function, apart from `encodePng`.
+ *
+ * @returns null for clips with neither text nor a readable image
+ */
+export const toImportItem = (
+	clip: DittoClip,
+	encodePng: (bitmap: Bitmap) => Buffer,
+): ImportHistoryItem | null => {
+	const { formats } = clip;
+	const common = {
+		created_at: toHistoryTimestamp(clip.date * 1000),
+		favorite: false,
+		pinned: false,
+		use_count: 1,
+		tags: [],
+		note: null,
+		source: null,
+	};
+
+	const unicode = formats.get(FORMAT_UNICODE_TEXT);
+	const ansi = formats.get(FORMAT_TEXT);
+	const text = unicode
+		? readNulTerminated(unicode, "utf16le")
+		: ansi
+			? readNulTerminated(ansi, "latin1")
+			: "";
+	if (text.trim()) {
+		const rtf = formats.get(FORMAT_RTF);
+		const html = formats.get(FORMAT_HTML);
+		return {
+			...common,
+			type: "text",
+			content: text,
+			rtf: rtf ? readNulTerminated(rtf, "latin1") || null : null,
+			html: html ? parseCfHtml(html) : null,
+			image: null,
+		};
+	}
+
+	const image = readImage(formats, encodePng);
+	if (!image) return null;
+	return {
+		...common,
+		type: "image",
+		content: "",
+		rtf: null,
+		html: null,
+		image,
+	};
+};
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/**
+ * Reads the clips after `afterId`, oldest first, with their formats.
+ * Groups are skipped.
+ */
+const readClips = (
+	db: Database.Database,
+	afterId: number,
+	limit: number,
+): DittoClip[] => {
+	const clips = db
+		.prepare(
+			"SELECT lID AS id, lDate AS date FROM Main WHERE bIsGroup = 0 AND lID > ? ORDER BY lID LIMIT ?",
+		)
+		.all(afterId, limit) as Array<{ id: number; date: number }>;
+	if (clips.length === 0) return [];
+
+	const placeholders = IMPORTED_FORMATS.map(() => "?").join(", ");
+	const rows = db
+		.prepare(
+			`SELECT lParentID AS clipId, strClipBoardFormat AS format, ooData AS data FROM Data WHERE lParentID BETWEEN ? AND ? AND strClipBoardFormat IN (${placeholders})`,
+		)
+		.all(
+			clips[0].id,
+			clips[clips.length - 1].id,
+			...IMPORTED_FORMATS,
+		) as Array<{ clipId: number; format: string; data: Buffer | null }>;
+
+	const byId = new Map(
+		clips.map((clip) => [clip.id, { ...clip, formats: new Map() }]),
+	);
+	for (const row of rows) {
+		if (row.data) byId.get(row.clipId)?.formats.set(row.format, row.data);
+	}
+	return [...byId.values()];
+};
+
+/**
+ * Imports the clips of a Ditto database (`Ditto.db`) with their original
+ * copy times. Text and images are imported; file lists, groups and other
+ * formats are not, and clips already in history are skipped.
+ *
+ * @throws if the path is not absolute or is not a Ditto database
+ */
+export const importDitto = async (
+	deps: DittoImportDeps,
+	dbPath: unknown,
+): Promise<HistoryImportReport> => {
+	if (typeof dbPath !== "string" || !path.isAbsolute(dbPath)) {
+		throw new Error("Invalid import: path must be absolute");
+	}
+
+	const db = deps.connect(dbPath);
+	try {
+		const tables = db
+			.prepare(
+				"SELECT name FROM sqlite_master WHERE type = 'table' AND name IN ('Main', 'Data')",
+			)
+			.all();
+		if (tables.length !== 2) {
+			throw new Error("Invalid import: not a Ditto database");
+		}
+
+		const report: HistoryImportReport = { imported: 0, skipped: 0 };
+		let afterId = 0;
+		while (true) {
+			const clips = readClips(db, afterId, DITTO_BATCH_SIZE);
+			if (clips.length === 0) return report;
+			afterId = clips[clips.length - 1].id;
+
+			const batch = await importInBatches(deps.importItems, clips, (clip) =>
+				toImportItem(clip, deps.encodePng),
+			);
+			report.imported += batch.imported;
+			report.skipped += batch.skipped;
+		}
+	} finally {
+		db.close();
+	}
+};
@@ -0,0 +1,114 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	applyCopyKey,
+	createDoubleCopy,
+	parseDoubleCopySettings,
+} from "./double-copy.js";
+import type { KeyHook, TypedKey } from "./key-hook.js";
+
+describe("parseDoubleCopySettings", () => {
+	it("is off by default with a 400 ms window", () => {
+		expect(parseDoubleCopySettings({})).toEqual({
+			enabled: false,
+			intervalMs: 400,
+		});
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseDoubleCopySettings({ enabled: "yes" })).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parseDoubleCopySettings({ intervalMs: 50 })).toThrow(
+			"intervalMs must be an integer from 150 to 1000",
+		);
+	});
+});
+
+describe("applyCopyKey", () => {
+	const copy: TypedKey = { type: "copy" };
+
+	it("completes the gesture on a second copy within the window", () => {
+		expect(applyCopyKey(null, copy, 1000, 400)).toEqual({
+			triggered: false,
+			lastCopyAt: 1000,
+		});
+		expect(applyCopyKey(1000, copy, 1400, 400)).toEqual({
+			triggered: true,
+			lastCopyAt: null,
+		});
+		expect(applyCopyKey(1000, copy, 1401, 400)).toEqual({
+			triggered: false,
+			lastCopyAt: 1401,
+		});
+	});
+
+	it("cancels on any other key", () => {
+		expect(applyCopyKey(1000, { type: "reset" }, 1100, 400)).toEqual({
+			triggered: false,
+			lastCopyAt: null,
+		});
+	});
+});
+
+describe("createDoubleCopy", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	/**
+	 * A hook whose copies are sent by the test, at the given times.
+	 */
+	const createFakeHook = () => {
+		let listener: ((key: TypedKey) => void) | null = null;
+		let time = 0;
+		const hook: KeyHook = {
+			start: async (onKey) => {
+				listener = onKey;
+			},
+			stop: () => {
+				listener = null;
+			},
+		};
+		const copyAt = (...times: number[]) => {
+			for (const at of times) {
+				time = at;
+				listener?.({ type: "copy" });
+			}
+		};
+		return {
+			hook,
+			copyAt,
+			now: () => time,
+			isListening: () => listener !== null,
+		};
+	};
+
+	it("opens the picker on a double copy once enabled", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "double-copy-"));
+		const { hook, copyAt, now, isListening } = createFakeHook();
+		const onDoubleCopy = vi.fn();
+		const doubleCopy = createDoubleCopy({
+			userDataPath,
+			hook,
+			onDoubleCopy,
+			now,
+		});
+
+		await doubleCopy.start();
+		expect(isListening()).toBe(false);
+		expect((await doubleCopy.updateSettings({ enabled: true })).running).toBe(
+			true,
+		);
+
+		copyAt(0, 300, 600, 900);
+
+		expect(onDoubleCopy).toHaveBeenCalledTimes(2);
+		await doubleCopy.updateSettings({ enabled: false });
+		expect(isListening()).toBe(false);
+	});
+});
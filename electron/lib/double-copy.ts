@@ -0,0 +1,222 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { KeyHook, TypedKey } from "./key-hook.js";
+
+/**
+ * Persisted double-copy gesture settings.
+ */
+export type DoubleCopySettings = {
+	/** Off by default: listening to every keystroke is opt-in */
+	enabled: boolean;
+	/** Longest gap between the two copies that opens the picker (ms) */
+	intervalMs: number;
+};
+
+/**
+ * Settings plus what the listener is doing, as shown in settings.
+ */
+export type DoubleCopyStatus = DoubleCopySettings & {
+	running: boolean;
+	/** Why the listener stopped or could not start */
+	lastError: string | null;
+};
+
+const DEFAULT_DOUBLE_COPY_SETTINGS: DoubleCopySettings = {
+	enabled: false,
+	intervalMs: 400,
+};
+
+const DOUBLE_COPY_FILENAME = "double-copy.json";
+
+const INTERVAL_RANGE = { min: 150, max: 1000 };
+
+const invalid = (message: string) =>
+	new Error(`Invalid double-copy settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a double-copy settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or `intervalMs` is out of range
+ */
+export const parseDoubleCopySettings = (
+	input: unknown,
+	current: DoubleCopySettings = DEFAULT_DOUBLE_COPY_SETTINGS,
+): DoubleCopySettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, intervalMs } = input as Partial<
+		Record<keyof DoubleCopySettings, unknown>
+	>;
+	const next: DoubleCopySettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (intervalMs !== undefined) {
+		if (
+			typeof intervalMs !== "number" ||
+			!Number.isInteger(intervalMs) ||
+			intervalMs < INTERVAL_RANGE.min ||
+			intervalMs > INTERVAL_RANGE.max
+		) {
+			throw invalid(
+				`intervalMs must be an integer from ${INTERVAL_RANGE.min} to ${INTERVAL_RANGE.max}`,
+			);
+		}
+		next.intervalMs = intervalMs;
+	}
+
+	return next;
+};
+
+/**
+ * Applies a keystroke to the time of the last copy not yet part of a
+ * gesture.
+ * Pure function. A copy within `intervalMs` of the previous one
+ * completes the gesture; any other key in between cancels it.
+ *
+ * @returns whether the gesture completed, and the copy to pair the next
+ *   one with (none right after a gesture, so a third copy starts over)
+ */
+export const applyCopyKey = (
+	lastCopyAt: number | null,
+	key: TypedKey,
+	now: number,
+	intervalMs: number,
+): { triggered: boolean; lastCopyAt: number | null } => {
+	if (key.type !== "copy") return { triggered: false, lastCopyAt: null };
+	if (lastCopyAt !== null && now - lastCopyAt <= intervalMs) {
+		return { triggered: true, lastCopyAt: null };
+	}
+	return { triggered: false, lastCopyAt: now };
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getDoubleCopyPath = (userDataPath: string) =>
+	path.join(userDataPath, DOUBLE_COPY_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readDoubleCopyFromFile = (filePath: string): DoubleCopySettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_DOUBLE_COPY_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseDoubleCopySettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read double-copy settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_DOUBLE_COPY_SETTINGS };
+	}
+};
+
+const writeDoubleCopyToFile = (
+	filePath: string,
+	settings: DoubleCopySettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Double Copy Module
+// ============================================================================
+
+export type DoubleCopyDeps = {
+	userDataPath: string;
+	hook: KeyHook;
+	/** Called when the copy shortcut is pressed twice in quick succession */
+	onDoubleCopy: () => void;
+	now?: () => number;
+};
+
+/**
+ * Creates the double-copy gesture: while enabled, a keyboard listener
+ * watches for the copy shortcut pressed twice within `intervalMs` in any
+ * app, as Paste and Ditto offer, and opens the picker. Only copy
+ * keystrokes are looked at; nothing typed is kept.
+ */
+export const createDoubleCopy = (deps: DoubleCopyDeps) => {
+	const filePath = getDoubleCopyPath(deps.userDataPath);
+	const now = deps.now ?? Date.now;
+	let settings = readDoubleCopyFromFile(filePath);
+	let lastCopyAt: number | null = null;
+	let running = false;
+	let lastError: string | null = null;
+
+	const getStatus = (): DoubleCopyStatus => ({
+		...settings,
+		running,
+		lastError,
+	});
+
+	const onKey = (key: TypedKey) => {
+		const next = applyCopyKey(lastCopyAt, key, now(), settings.intervalMs);
+		lastCopyAt = next.lastCopyAt;
+		if (next.triggered) deps.onDoubleCopy();
+	};
+
+	const onExit = (error: Error) => {
+		running = false;
+		lastError = error.message;
+		console.error("Double-copy listener stopped:", error);
+	};
+
+	/**
+	 * Starts listening if enabled. Failures are reported in the status.
+	 */
+	const start = async (): Promise<void> => {
+		if (!settings.enabled || running) return;
+		lastCopyAt = null;
+		try {
+			running = true;
+			await deps.hook.start(onKey, onExit);
+			lastError = null;
+		} catch (error) {
+			running = false;
+			lastError = error instanceof Error ? error.message : String(error);
+			console.error("Failed to start double-copy listener:", error);
+		}
+	};
+
+	const stop = () => {
+		deps.hook.stop();
+		running = false;
+		lastCopyAt = null;
+	};
+
+	const updateSettings = async (input: unknown): Promise<DoubleCopyStatus> => {
+		const next = parseDoubleCopySettings(input, settings);
+		writeDoubleCopyToFile(filePath, next);
+		settings = next;
+		if (settings.enabled) {
+			await start();
+		} else {
+			stop();
+		}
+		return getStatus();
+	};
+
+	return { getStatus, updateSettings, start, stop };
+};
+
+export type DoubleCopy = ReturnType<typeof createDoubleCopy>;
@@ -0,0 +1,84 @@
+import { describe, expect, it } from "vitest";
+import {
+	decodeWebSocketFrame,
+	encodeWebSocketFrame,
+	getWebSocketAccept,
+	isWebSocketUpgrade,
+} from "./event-stream.js";
+
+/**
+ * Builds a masked client frame, as browsers and tools send them.
+ */
+const maskedFrame = (opcode: number, text: string): Buffer => {
+	const payload = Buffer.from(text);
+	const mask = Buffer.from([1, 2, 3, 4]);
+	const masked = payload.map((byte, i) => byte ^ mask[i % 4]);
+	return Buffer.concat([
+		Buffer.from([0x80 | opcode, 0x80 | payload.length]),
+		mask,
+		masked,
+	]);
+};
+
+describe("getWebSocketAccept", () => {
+	it("matches the RFC 6455 example", () => {
+		expect(getWebSocketAccept("dGhlIHNhbXBsZSBub25jZQ==")).toBe(
+			"s3pPLMBiTxaQ9kYGzzhZRbK+xOo=",
+		);
+	});
+});
+
+describe("encodeWebSocketFrame", () => {
+	it("uses the short, 16-bit and 64-bit length forms", () => {
+		expect([...encodeWebSocketFrame(0x1, Buffer.from("hi"))]).toEqual([
+			0x81, 2, 0x68, 0x69,
+		]);
+		expect(encodeWebSocketFrame(0x1, Buffer.alloc(300)).subarray(0, 4)).toEqual(
+			Buffer.from([0x81, 126, 0x01, 0x2c]),
+		);
+		const large = encodeWebSocketFrame(0x1, Buffer.alloc(70_000));
+		expect(large[1]).toBe(127);
+		expect(large.length).toBe(70_010);
+	});
+});
+
+describe("decodeWebSocketFrame", () => {
+	it("unmasks a client frame", () => {
+		const frame = decodeWebSocketFrame(maskedFrame(0x9, "ping"));
+
+		expect(frame?.opcode).toBe(0x9);
+		expect(frame?.payload.toString()).toBe("ping");
+		expect(frame?.size).toBe(10);
+	});
+
+	it("waits for the rest of a partial frame", () => {
+		const whole = maskedFrame(0x1, "hello");
+
+		expect(decodeWebSocketFrame(whole.subarray(0, 1))).toBeNull();
+		expect(decodeWebSocketFrame(whole.subarray(0, 8))).toBeNull();
+	});
+
+	it("decodes what encodeWebSocketFrame produces", () => {
+		const frame = decodeWebSocketFrame(
+			encodeWebSocketFrame(0x1, Buffer.alloc(300, "a")),
+		);
+
+		expect(frame?.payload.toString()).toBe("a".repeat(300));
+	});
+});
+
+describe("isWebSocketUpgrade", () => {
+	it("requires the upgrade headers and version 13", () => {
+		const headers = {
+			upgrade: "WebSocket",
+			"sec-websocket-key": "dGhlIHNhbXBsZSBub25jZQ==",
+			"sec-websocket-version": "13",
+		};
+
+		expect(isWebSocketUpgrade(headers)).toBe(true);
+		expect(
+			isWebSocketUpgrade({ ...headers, "sec-websocket-version": "8" }),
+		).toBe(false);
+		expect(isWebSocketUpgrade({ upgrade: "websocket" })).toBe(false);
+	});
+});
@@ -0,0 +1,204 @@
+import crypto from "node:crypto";
+import type http from "node:http";
+import type { Duplex } from "node:stream";
+import type { HttpApiItem } from "./http-api-server.js";
+
+/**
+ * History change broadcast to event stream clients, one JSON text message
+ * each.
+ */
+export type HistoryEvent =
+	| { type: "item.added"; item: HttpApiItem }
+	| { type: "item.deleted"; id: number }
+	| { type: "item.pinned"; id: number; pinned: boolean }
+	| { type: "history.cleared" };
+
+/**
+ * Magic GUID from RFC 6455 used to derive `Sec-WebSocket-Accept`.
+ */
+const WEBSOCKET_GUID = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_TEXT = 0x1;
+const OPCODE_CLOSE = 0x8;
+const OPCODE_PING = 0x9;
+const OPCODE_PONG = 0xa;
+
+/**
+ * Clients only send control frames; anything larger is a misbehaving
+ * client and the connection is dropped.
+ */
+const MAX_CLIENT_FRAME_BYTES = 64 * 1024;
+
+/**
+ * A frame read from a client, with how many bytes of the buffer it used.
+ */
+export type WebSocketFrame = {
+	opcode: number;
+	payload: Buffer;
+	size: number;
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Computes the `Sec-WebSocket-Accept` value for a handshake key.
+ * Pure function.
+ */
+export const getWebSocketAccept = (key: string): string =>
+	crypto
+		.createHash("sha1")
+		.update(key + WEBSOCKET_GUID)
+		.digest("base64");
+
+/**
+ * Encodes a single unmasked (server-to-client) frame.
+ * Pure function.
+ */
+export const encodeWebSocketFrame = (
+	opcode: number,
+	payload: Buffer,
+): Buffer => {
+	const first = 0x80 | opcode;
+	if (payload.length < 126) {
+		return Buffer.concat([Buffer.from([first, payload.length]), payload]);
+	}
+	if (payload.length < 0x10000) {
+		const header = Buffer.from([first, 126, 0, 0]);
+		header.writeUInt16BE(payload.length, 2);
+		return Buffer.concat([header, payload]);
+	}
+	const header = Buffer.alloc(10);
+	header[0] = first;
+	header[1] = 127;
+	header.writeBigUInt64BE(BigInt(payload.length), 2);
+	return Buffer.concat([header, payload]);
+};
+
+/**
+ * Decodes the first frame in a buffer of client data, unmasking its
+ * payload.
+ * Pure function.
+ *
+ * @returns the frame, or null if the buffer does not hold a whole frame yet
+ */
+export const decodeWebSocketFrame = (buffer: Buffer): WebSocketFrame | null => {
+	if (buffer.length < 2) return null;
+	const opcode = buffer[0] & 0x0f;
+	const masked = (buffer[1] & 0x80) !== 0;
+	let length = buffer[1] & 0x7f;
+	let offset = 2;
+
+	if (length === 126) {
+		if (buffer.length < 4) return null;
+		length = buffer.readUInt16BE(2);
+		offset = 4;
+	} else if (length === 127) {
+		if (buffer.length < 10) return null;
+		length = Number(buffer.readBigUInt64BE(2));
+		offset = 10;
+	}
+
+	const mask = masked ? buffer.subarray(offset, offset + 4) : null;
+	if (mask) offset += 4;
+	if (buffer.length < offset + length) return null;
+
+	const payload = Buffer.from(buffer.subarray(offset, offset + length));
+	if (mask) {
+		for (let i = 0; i < payload.length; i++) {
+			payload[i] ^= mask[i % 4];
+		}
+	}
+	return { opcode, payload, size: offset + length };
+};
+
+/**
+ * Whether a request asks to upgrade to a version 13 WebSocket.
+ * Pure function.
+ */
+export const isWebSocketUpgrade = (headers: http.IncomingHttpHeaders) =>
+	headers.upgrade?.toLowerCase() === "websocket" &&
+	typeof headers["sec-websocket-key"] === "string" &&
+	headers["sec-websocket-version"] === "13";
+
+// ============================================================================
+// Event Stream
+// ============================================================================
+
+/**
+ * Creates the WebSocket event stream. Messages only go from the app to
+ * clients; pings are answered and a close frame ends the connection.
+ */
+export const createEventStream = () => {
+	const clients = new Set<Duplex>();
+
+	const handleData = (socket: Duplex, buffered: Buffer): Buffer => {
+		let buffer = buffered;
+		for (;;) {
+			const frame = decodeWebSocketFrame(buffer);
+			if (!frame) break;
+			buffer = buffer.subarray(frame.size);
+
+			if (frame.opcode === OPCODE_CLOSE) {
+				socket.end(encodeWebSocketFrame(OPCODE_CLOSE, Buffer.alloc(0)));
+				break;
+			}
+			if (frame.opcode === OPCODE_PING) {
+				socket.write(encodeWebSocketFrame(OPCODE_PONG, frame.payload));
+			}
+		}
+		if (buffer.length > MAX_CLIENT_FRAME_BYTES) socket.destroy();
+		return buffer;
+	};
+
+	/**
+	 * Completes the handshake for an upgrade request that has already been
+	 * authorized, and adds the client.
+	 */
+	const accept = (request: http.IncomingMessage, socket: Duplex): void => {
+		const key = request.headers["sec-websocket-key"] as string;
+		socket.write(
+			[
+				"HTTP/1.1 101 Switching Protocols",
+				"Upgrade: websocket",
+				"Connection: Upgrade",
+				`Sec-WebSocket-Accept: ${getWebSocketAccept(key)}`,
+				"",
+				"",
+			].join("\r\n"),
+		);
+
+		clients.add(socket);
+		let buffer = Buffer.alloc(0);
+		socket.on("data", (chunk: Buffer) => {
+			buffer = handleData(socket, Buffer.concat([buffer, chunk]));
+		});
+		socket.on("close", () => clients.delete(socket));
+		socket.on("error", () => clients.delete(socket));
+	};
+
+	/**
+	 * Sends an event to every connected client.
+	 */
+	const broadcast = (event: HistoryEvent): void => {
+		if (clients.size === 0) return;
+		const frame = encodeWebSocketFrame(
+			OPCODE_TEXT,
+			Buffer.from(JSON.stringify(event), "utf-8"),
+		);
+		for (const socket of clients) socket.write(frame);
+	};
+
+	/**
+	 * Disconnects every client, e.g. when the API is turned off.
+	 */
+	const closeAll = (): void => {
+		for (const socket of clients) socket.destroy();
+		clients.clear();
+	};
+
+	return { accept, broadcast, closeAll };
+};
+
+export type EventStream = ReturnType<typeof createEventStream>;
@@ -0,0 +1,172 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createFaviconCache,
+	findIconUrl,
+	getFaviconCachePath,
+	normalizeFaviconDomain,
+} from "./favicons.js";
+
+describe("normalizeFaviconDomain", () => {
+	it("lowercases public host names", () => {
+		expect(normalizeFaviconDomain("GitHub.com")).toBe("github.com");
+	});
+
+	it("rejects paths, local hosts and non-strings", () => {
+		expect(() => normalizeFaviconDomain("github.com/org")).toThrow(
+			"Invalid favicon domain: expected a host name",
+		);
+		expect(() => normalizeFaviconDomain("localhost")).toThrow(
+			"localhost is not a public host",
+		);
+		expect(() => normalizeFaviconDomain("10.0.0.1")).toThrow("not a public host");
+		expect(() => normalizeFaviconDomain(42)).toThrow("expected a host name");
+	});
+});
+
+describe("findIconUrl", () => {
+	it("prefers icon links over touch icons", () => {
+		const html = `<head>
+			<link rel="apple-touch-icon" href="/touch.png">
+			<link href="/static/favicon.svg" rel="shortcut icon" type="image/svg+xml">
+		</head>`;
+
+		expect(findIconUrl(html, "https://example.com/docs/")).toBe(
+			"https://example.com/static/favicon.svg",
+		);
+	});
+
+	it("falls back to a touch icon and skips other schemes", () => {
+		const touchOnly = '<link rel="apple-touch-icon" href="touch.png">';
+		const dataIcon = '<link rel="icon" href="data:image/png;base64,AA==">';
+
+		expect(findIconUrl(touchOnly, "https://example.com/a/")).toBe(
+			"https://example.com/a/touch.png",
+		);
+		expect(findIconUrl(dataIcon, "https://example.com/")).toBeNull();
+	});
+});
+
+describe("createFaviconCache", () => {
+	const tempDirs: string[] = [];
+	const png = Buffer.from([0x89, 0x50, 0x4e, 0x47]);
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-favicons-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const respond = (body: string | Buffer, type: string, status = 200) =>
+		new Response(body, { status, headers: { "content-type": type } });
+
+	it("fetches the linked icon once and serves it from disk", async () => {
+		const userDataPath = createTempUserDataPath();
+		const fetch = vi.fn(async (url: string | URL | Request) =>
+			String(url) === "https://example.com/"
+				? respond('<link rel="icon" href="/icon.png">', "text/html")
+				: respond(png, "image/png"),
+		);
+		const cache = createFaviconCache({
+			userDataPath,
+			isEnabled: () => true,
+			fetch,
+		});
+		const dataUrl = `data:image/png;base64,${png.toString("base64")}`;
+
+		expect(await cache.get("example.com")).toBe(dataUrl);
+		expect(fetch).toHaveBeenLastCalledWith(
+			"https://example.com/icon.png",
+			expect.objectContaining({ signal: expect.any(AbortSignal) }),
+		);
+		expect(
+			fs.existsSync(
+				path.join(getFaviconCachePath(userDataPath), "example.com.png"),
+			),
+		).toBe(true);
+
+		const restarted = createFaviconCache({
+			userDataPath,
+			isEnabled: () => false,
+			fetch,
+		});
+		expect(await restarted.get("example.com")).toBe(dataUrl);
+		expect(fetch).toHaveBeenCalledTimes(2);
+	});
+
+	it("does not follow redirects to local hosts", async () => {
+		const fetch = vi.fn(
+			async () =>
+				new Response(null, {
+					status: 301,
+					headers: { location: "http://192.168.1.1/icon.png" },
+				}),
+		);
+		const cache = createFaviconCache({
+			userDataPath: createTempUserDataPath(),
+			isEnabled: () => true,
+			fetch,
+		});
+
+		expect(await cache.get("example.net")).toBeNull();
+		expect(fetch).toHaveBeenCalledTimes(2);
+		expect(fetch).not.toHaveBeenCalledWith(
+			"http://192.168.1.1/icon.png",
+			expect.anything(),
+		);
+	});
+
+	it("falls back to /favicon.ico and remembers missing icons", async () => {
+		let now = Date.now();
+		const fetch = vi.fn(async () => respond("missing", "text/plain", 404));
+		const cache = createFaviconCache({
+			userDataPath: createTempUserDataPath(),
+			isEnabled: () => true,
+			fetch,
+			now: () => now,
+		});
+
+		expect(await cache.get("example.org")).toBeNull();
+		expect(fetch).toHaveBeenCalledWith(
+			"https://example.org/favicon.ico",
+			expect.anything(),
+		);
+		expect(await cache.get("example.org")).toBeNull();
+		expect(fetch).toHaveBeenCalledTimes(2);
+
+		now += 25 * 60 * 60 * 1000;
+		await cache.get("example.org");
+		expect(fetch).toHaveBeenCalledTimes(4);
+	});
+
+	it("does not contact sites while disabled", async () => {
+		const fetch = vi.fn();
+		const cache = createFaviconCache({
+			userDataPath: createTempUserDataPath(),
+			isEnabled: () => false,
+			fetch,
+		});
+
+		expect(await cache.get("example.com")).toBeNull();
+		expect(fetch).not.toHaveBeenCalled();
+	});
+
+	it("rejects icons that are not images", async () => {
+		const fetch = vi.fn(async () => respond("<html></html>", "text/html"));
+		const cache = createFaviconCache({
+			userDataPath: createTempUserDataPath(),
+			isEnabled: () => true,
+			fetch,
+		});
+
+		expect(await cache.get("example.net")).toBeNull();
+	});
+});
@@ -0,0 +1,264 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+import {
+	fetchPublicUrl,
+	parseTagAttributes,
+	readBody,
+	type SendRequest,
+	sendToPublicHost,
+	toPreviewUrl,
+} from "./link-previews.js";
+
+const FAVICONS_DIRNAME = "favicons";
+
+/**
+ * Upper bound on each request while resolving an icon (ms).
+ */
+const FETCH_TIMEOUT_MS = 5_000;
+
+/**
+ * Bytes of a site's home page searched for icon links.
+ */
+const MAX_PAGE_BYTES = 256 * 1024;
+
+/**
+ * Largest icon kept; bigger files are treated as missing.
+ */
+const MAX_ICON_BYTES = 100 * 1024;
+
+/**
+ * How long a domain without an icon is left alone before trying again
+ * (one day).
+ */
+const MISS_RETRY_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * Image types accepted as icons, by the file extension they are cached
+ * under.
+ */
+const ICON_TYPES: Record<string, string> = {
+	ico: "image/x-icon",
+	png: "image/png",
+	svg: "image/svg+xml",
+	gif: "image/gif",
+	jpg: "image/jpeg",
+	webp: "image/webp",
+};
+
+const ICON_TYPE_ALIASES: Record<string, string> = {
+	"image/vnd.microsoft.icon": "ico",
+	"image/ico": "ico",
+	"image/jpg": "jpg",
+};
+
+/**
+ * Marker file extension for domains found to have no icon.
+ */
+const MISS_EXTENSION = "missing";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a domain an icon is requested for.
+ * Pure function. The domain is lowercased.
+ *
+ * @throws if it is not a bare public host name
+ */
+export const normalizeFaviconDomain = (domain: unknown): string => {
+	if (typeof domain !== "string" || !/^[\w.-]+$/.test(domain)) {
+		throw new Error("Invalid favicon domain: expected a host name");
+	}
+	const host = domain.toLowerCase().replace(/\.$/, "");
+	if (toPreviewUrl(`https://${host}/`) === null) {
+		throw new Error(`Invalid favicon domain: ${host} is not a public host`);
+	}
+	return host;
+};
+
+/**
+ * URL of the icon a page links to, preferring `icon` over
+ * `apple-touch-icon`, resolved against `pageUrl`.
+ * Pure function.
+ *
+ * @returns null if the page links no http(s) icon
+ */
+export const findIconUrl = (html: string, pageUrl: string): string | null => {
+	let touchIcon: string | null = null;
+	for (const [tag] of html.matchAll(/<link\s[^>]*>/gi)) {
+		const attributes = parseTagAttributes(tag);
+		const rel = (attributes.get("rel") ?? "").toLowerCase().split(/\s+/);
+		const href = attributes.get("href")?.trim();
+		if (!href) continue;
+		let url: URL;
+		try {
+			url = new URL(href, pageUrl);
+		} catch {
+			continue;
+		}
+		if (!/^https?:$/.test(url.protocol)) continue;
+		if (rel.includes("icon")) return url.href;
+		if (rel.includes("apple-touch-icon") && touchIcon === null) {
+			touchIcon = url.href;
+		}
+	}
+	return touchIcon;
+};
+
+/**
+ * Cache file extension for an icon's content type, or null if the type is
+ * not an accepted image.
+ * Pure function.
+ */
+const toIconExtension = (contentType: string): string | null => {
+	const type = contentType.split(";")[0].trim().toLowerCase();
+	if (ICON_TYPE_ALIASES[type]) return ICON_TYPE_ALIASES[type];
+	const extension = Object.keys(ICON_TYPES).find(
+		(key) => ICON_TYPES[key] === type,
+	);
+	return extension ?? null;
+};
+
+const toDataUrl = (extension: string, data: Buffer): string =>
+	`data:${ICON_TYPES[extension]};base64,${data.toString("base64")}`;
+
+// ============================================================================
+// Favicon Cache
+// ============================================================================
+
+export const getFaviconCachePath = (userDataPath: string) =>
+	path.join(userDataPath, FAVICONS_DIRNAME);
+
+export type FaviconCacheDeps = {
+	userDataPath: string;
+	/** Whether sites may be contacted; cached icons are served regardless */
+	isEnabled: () => boolean;
+	fetch?: SendRequest;
+	now?: () => number;
+};
+
+/**
+ * Creates the favicon cache. Icons are resolved from the `<link>` tags of
+ * a site's home page, falling back to `/favicon.ico`, and kept on disk
+ * under `favicons/` so the list shows them without going to the network
+ * again. Domains without an icon are retried after a day.
+ */
+export const createFaviconCache = (deps: FaviconCacheDeps) => {
+	const dir = getFaviconCachePath(deps.userDataPath);
+	const send = deps.fetch ?? sendToPublicHost;
+	const now = deps.now ?? Date.now;
+	const pending = new Map<string, Promise<string | null>>();
+
+	const fileFor = (domain: string, extension: string) =>
+		path.join(dir, `${domain}.${extension}`);
+
+	const request = (url: string, accept: string) =>
+		fetchPublicUrl(send, url, {
+			headers: { accept },
+			signal: AbortSignal.timeout(FETCH_TIMEOUT_MS),
+		});
+
+	/**
+	 * Cached icon of a domain as a data URL; undefined if none is cached,
+	 * null if the domain recently had none.
+	 */
+	const readCached = async (
+		domain: string,
+	): Promise<string | null | undefined> => {
+		for (const extension of Object.keys(ICON_TYPES)) {
+			try {
+				return toDataUrl(
+					extension,
+					await fs.readFile(fileFor(domain, extension)),
+				);
+			} catch {
+				// Not cached under this extension
+			}
+		}
+		try {
+			const { mtimeMs } = await fs.stat(fileFor(domain, MISS_EXTENSION));
+			if (now() - mtimeMs < MISS_RETRY_MS) return null;
+		} catch {
+			// Never looked up
+		}
+		return undefined;
+	};
+
+	/**
+	 * Downloads an icon, if it is an accepted image within the size limit.
+	 */
+	const download = async (
+		url: string,
+	): Promise<{ data: Buffer; extension: string } | null> => {
+		const response = await request(url, "image/*");
+		const extension = toIconExtension(
+			response.headers.get("content-type") ?? "",
+		);
+		if (!response.ok || extension === null) {
+			await response.body?.cancel().catch(() => undefined);
+			return null;
+		}
+		const data = await readBody(response, MAX_ICON_BYTES + 1);
+		if (data.length === 0 || data.length > MAX_ICON_BYTES) return null;
+		return { data, extension };
+	};
+
+	const resolve = async (domain: string) => {
+		const home = `https://${domain}/`;
+		const candidates: string[] = [];
+		try {
+			const response = await request(home, "text/html");
+			const html = (await readBody(response, MAX_PAGE_BYTES)).toString();
+			const linked = findIconUrl(html, response.url || home);
+			if (response.ok && linked) candidates.push(linked);
+		} catch (error) {
+			console.warn(`Failed to load ${home} for its icon:`, error);
+		}
+		candidates.push(new URL("/favicon.ico", home).href);
+
+		for (const url of candidates) {
+			try {
+				const icon = await download(url);
+				if (icon) return icon;
+			} catch (error) {
+				console.warn(`Failed to download icon ${url}:`, error);
+			}
+		}
+		return null;
+	};
+
+	const lookUp = async (domain: string): Promise<string | null> => {
+		const cached = await readCached(domain);
+		if (cached !== undefined || !deps.isEnabled()) return cached ?? null;
+
+		const icon = await resolve(domain);
+		await fs.mkdir(dir, { recursive: true });
+		if (!icon) {
+			await fs.writeFile(fileFor(domain, MISS_EXTENSION), "");
+			return null;
+		}
+		await fs.writeFile(fileFor(domain, icon.extension), icon.data);
+		await fs.rm(fileFor(domain, MISS_EXTENSION), { force: true });
+		return toDataUrl(icon.extension, icon.data);
+	};
+
+	/**
+	 * Icon of a domain as a data URL, fetched and cached on first use while
+	 * enabled. Concurrent requests for a domain share one lookup.
+	 *
+	 * @returns null if the domain has no icon, or none is cached while
+	 *   disabled
+	 * @throws if the domain is invalid
+	 */
+	const get = (domain: unknown): Promise<string | null> => {
+		const host = normalizeFaviconDomain(domain);
+		const inFlight = pending.get(host);
+		if (inFlight) return inFlight;
+		const lookup = lookUp(host).finally(() => pending.delete(host));
+		pending.set(host, lookup);
+		return lookup;
+	};
+
+	return { get };
+};
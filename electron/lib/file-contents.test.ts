@@ -0,0 +1,76 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import {
+	decodeFileContent,
+	parseFileRestoreMode,
+	readFileContent,
+} from "./file-contents.js";
+
+describe("parseFileRestoreMode", () => {
+	it("restores paths by default and rejects unknown modes", () => {
+		expect(parseFileRestoreMode(undefined)).toBe("paths");
+		expect(parseFileRestoreMode("contents")).toBe("contents");
+		expect(() => parseFileRestoreMode("bytes")).toThrow(
+			"Invalid file restore mode: must be one of paths, contents",
+		);
+	});
+});
+
+describe("decodeFileContent", () => {
+	it("reads images by extension and text as UTF-8 without a BOM", () => {
+		const png = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0]);
+		expect(decodeFileContent("/tmp/shot.PNG", png)).toEqual({
+			type: "image",
+			data: png,
+		});
+		expect(
+			decodeFileContent("/tmp/notes.txt", Buffer.from("\ufeffhé", "utf-8")),
+		).toEqual({ type: "text", text: "hé" });
+	});
+
+	it("rejects binary and invalid UTF-8 files", () => {
+		expect(() =>
+			decodeFileContent("/tmp/app.bin", Buffer.from([0x41, 0, 0x42])),
+		).toThrow("Not a text or image file: app.bin");
+		expect(() =>
+			decodeFileContent("/tmp/latin1.txt", Buffer.from([0x68, 0xe9])),
+		).toThrow("Not a text or image file: latin1.txt");
+	});
+});
+
+describe("readFileContent", () => {
+	let dir: string;
+
+	beforeEach(() => {
+		dir = fs.mkdtempSync(path.join(os.tmpdir(), "file-contents-"));
+	});
+
+	afterEach(() => {
+		fs.rmSync(dir, { recursive: true, force: true });
+	});
+
+	it("reads a single small file", () => {
+		const file = path.join(dir, "a.md");
+		fs.writeFileSync(file, "# Notes");
+
+		expect(readFileContent([file])).toEqual({ type: "text", text: "# Notes" });
+		expect(() => readFileContent([file], 3)).toThrow(
+			"File is too large to restore its contents: 7 bytes (max: 3)",
+		);
+	});
+
+	it("refuses several files, folders and missing files", () => {
+		const file = path.join(dir, "a.md");
+		fs.writeFileSync(file, "a");
+
+		expect(() => readFileContent([file, file])).toThrow(
+			"Only the contents of a single file can be restored",
+		);
+		expect(() => readFileContent([dir])).toThrow(`Not a file: ${dir}`);
+		expect(() => readFileContent([path.join(dir, "gone.txt")])).toThrow(
+			"Not a file:",
+		);
+	});
+});
@@ -0,0 +1,105 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * How a files item goes back on the clipboard: as references to the
+ * files, for pasting into a file manager, or as the text or image inside
+ * a single file.
+ */
+export const FILE_RESTORE_MODES = ["paths", "contents"] as const;
+
+export type FileRestoreMode = (typeof FILE_RESTORE_MODES)[number];
+
+/**
+ * Largest file whose contents are placed on the clipboard (bytes).
+ */
+export const MAX_FILE_CONTENT_BYTES = 5 * 1024 * 1024;
+
+/**
+ * Extensions restored as images; Electron decodes PNG and JPEG on every
+ * platform.
+ */
+const IMAGE_EXTENSIONS = [".png", ".jpg", ".jpeg"];
+
+/**
+ * What a file holds, ready for the clipboard.
+ */
+export type FileContent =
+	| { type: "text"; text: string }
+	| { type: "image"; data: Buffer };
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a restore mode; files are restored as paths when unset.
+ * Pure function.
+ *
+ * @throws if the mode is not one of {@link FILE_RESTORE_MODES}
+ */
+export const parseFileRestoreMode = (value: unknown): FileRestoreMode => {
+	if (value === undefined) return "paths";
+	if (!FILE_RESTORE_MODES.includes(value as FileRestoreMode)) {
+		throw new Error(
+			`Invalid file restore mode: must be one of ${FILE_RESTORE_MODES.join(", ")}`,
+		);
+	}
+	return value as FileRestoreMode;
+};
+
+/**
+ * Reads a file's bytes as an image (by extension) or UTF-8 text, without
+ * a byte order mark.
+ * Pure function.
+ *
+ * @throws if the file is neither an image nor valid UTF-8 text
+ */
+export const decodeFileContent = (
+	filePath: string,
+	data: Buffer,
+): FileContent => {
+	if (IMAGE_EXTENSIONS.includes(path.extname(filePath).toLowerCase())) {
+		return { type: "image", data };
+	}
+	const notText = new Error(
+		`Not a text or image file: ${path.basename(filePath)}`,
+	);
+	if (data.includes(0)) throw notText;
+	try {
+		const text = new TextDecoder("utf-8", { fatal: true }).decode(data);
+		return { type: "text", text };
+	} catch {
+		throw notText;
+	}
+};
+
+// ============================================================================
+// File Reading
+// ============================================================================
+
+/**
+ * Reads the contents of a files item's only file.
+ *
+ * @throws if the item lists more than one file, the path is not a file,
+ *   it is over `maxBytes`, or it is neither text nor an image
+ */
+export const readFileContent = (
+	paths: readonly string[],
+	maxBytes = MAX_FILE_CONTENT_BYTES,
+): FileContent => {
+	if (paths.length !== 1) {
+		throw new Error("Only the contents of a single file can be restored");
+	}
+	const [filePath] = paths;
+	const stats = fs.statSync(filePath, { throwIfNoEntry: false });
+	if (!stats?.isFile()) {
+		throw new Error(`Not a file: ${filePath}`);
+	}
+	if (stats.size > maxBytes) {
+		throw new Error(
+			`File is too large to restore its contents: ${stats.size} bytes (max: ${maxBytes})`,
+		);
+	}
+	return decodeFileContent(filePath, fs.readFileSync(filePath));
+};
@@ -0,0 +1,111 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	type ClipboardBuffers,
+	FILE_LIST_FORMATS,
+	formatStoredFileList,
+	parseFileNameW,
+	parseFilenamesPlist,
+	parseGnomeCopiedFiles,
+	parseStoredFileList,
+	parseUriList,
+	readFileList,
+	toFileNameW,
+	toFilenamesPlist,
+	toUriList,
+	writeFileList,
+} from "./file-lists.js";
+
+describe("stored file lists", () => {
+	it("round-trips paths through the content column", () => {
+		const paths = ["/home/me/a.txt", "/home/me/b c.png"];
+		expect(parseStoredFileList(formatStoredFileList(paths))).toEqual(paths);
+	});
+});
+
+describe("uri lists", () => {
+	it("parses file URIs and skips comments and other schemes", () => {
+		const payload =
+			"# copied\r\nfile:///tmp/a%20b.txt\r\nhttps://example.com\r\nfile:///tmp/c\r\n";
+		expect(parseUriList(payload)).toEqual(["/tmp/a b.txt", "/tmp/c"]);
+	});
+
+	it("parses GNOME copied files, ignoring the operation line", () => {
+		expect(parseGnomeCopiedFiles("cut\nfile:///tmp/a\nfile:///tmp/b")).toEqual(
+			["/tmp/a", "/tmp/b"],
+		);
+	});
+
+	it("encodes paths as percent-encoded file URIs", () => {
+		expect(toUriList(["/tmp/a b.txt"])).toBe("file:///tmp/a%20b.txt\r\n");
+	});
+});
+
+describe("macOS filenames plist", () => {
+	it("round-trips paths with XML special characters", () => {
+		const paths = ["/Users/me/R&D <draft>.txt", "/Users/me/b.png"];
+		expect(parseFilenamesPlist(toFilenamesPlist(paths))).toEqual(paths);
+	});
+});
+
+describe("Windows FileNameW", () => {
+	it("round-trips a UTF-16 path", () => {
+		expect(parseFileNameW(toFileNameW("C:\\Users\\me\\ü.txt"))).toEqual([
+			"C:\\Users\\me\\ü.txt",
+		]);
+		expect(parseFileNameW(Buffer.alloc(0))).toEqual([]);
+	});
+});
+
+describe("readFileList / writeFileList", () => {
+	const createClipboard = (formats: Record<string, string> = {}) => {
+		const clipboard: ClipboardBuffers = {
+			readBuffer: (format) => Buffer.from(formats[format] ?? "", "utf8"),
+			writeBuffer: vi.fn(),
+		};
+		return clipboard;
+	};
+
+	it("prefers the GNOME format on Linux and falls back to uri-list", () => {
+		expect(
+			readFileList(
+				createClipboard({
+					[FILE_LIST_FORMATS.gnome]: "copy\nfile:///tmp/a",
+					[FILE_LIST_FORMATS.uriList]: "file:///tmp/b",
+				}),
+				"linux",
+			),
+		).toEqual(["/tmp/a"]);
+		expect(
+			readFileList(
+				createClipboard({ [FILE_LIST_FORMATS.uriList]: "file:///tmp/b" }),
+				"linux",
+			),
+		).toEqual(["/tmp/b"]);
+	});
+
+	it("returns no files when the clipboard holds none", () => {
+		expect(readFileList(createClipboard(), "linux")).toEqual([]);
+		expect(readFileList(createClipboard(), "darwin")).toEqual([]);
+	});
+
+	it("writes a uri-list on Linux", () => {
+		const clipboard = createClipboard();
+
+		writeFileList({ clipboard, platform: "linux", paths: ["/tmp/a"] });
+
+		expect(clipboard.writeBuffer).toHaveBeenCalledWith(
+			FILE_LIST_FORMATS.uriList,
+			Buffer.from("file:///tmp/a\r\n", "utf8"),
+		);
+	});
+
+	it("rejects an empty file list", () => {
+		expect(() =>
+			writeFileList({
+				clipboard: createClipboard(),
+				platform: "linux",
+				paths: [],
+			}),
+		).toThrow("empty file list");
+	});
+});
@@ -0,0 +1,207 @@
+import { fileURLToPath, pathToFileURL } from "node:url";
+
+/**
+ * Raw clipboard access needed to read and write file lists.
+ * Matches the shape of Electron's `clipboard` so it can be passed directly.
+ */
+export type ClipboardBuffers = {
+	readBuffer: (format: string) => Buffer;
+	writeBuffer: (format: string, buffer: Buffer) => void;
+};
+
+/**
+ * Platform-specific clipboard formats that carry copied files.
+ */
+export const FILE_LIST_FORMATS = {
+	/** macOS Finder: XML property list of POSIX paths */
+	darwin: "NSFilenamesPboardType",
+	/** Windows Explorer: UTF-16LE path of the first copied file */
+	win32: "FileNameW",
+	/** GNOME/Nautilus: "copy" or "cut" followed by file:// URIs */
+	gnome: "x-special/gnome-copied-files",
+	/** freedesktop.org standard used by KDE and most other file managers */
+	uriList: "text/uri-list",
+} as const;
+
+/**
+ * Separator between paths in a stored file-list item.
+ * Newlines cannot appear in paths produced by any supported file manager.
+ */
+const STORED_PATH_SEPARATOR = "\n";
+
+/**
+ * Serializes paths for storage in the history `content` column.
+ * Pure function.
+ */
+export const formatStoredFileList = (paths: readonly string[]): string =>
+	paths.join(STORED_PATH_SEPARATOR);
+
+/**
+ * Parses a stored file-list item back into paths.
+ * Pure function.
+ */
+export const parseStoredFileList = (content: string): string[] =>
+	content.split(STORED_PATH_SEPARATOR).filter((line) => line.length > 0);
+
+/**
+ * Converts file:// URI lines to local paths, skipping comments and
+ * non-file URIs.
+ * Pure function.
+ */
+const fileUrisToPaths = (lines: readonly string[]): string[] =>
+	lines
+		.map((line) => line.trim())
+		.filter((line) => line.startsWith("file://"))
+		.flatMap((uri) => {
+			try {
+				return [fileURLToPath(uri)];
+			} catch {
+				return [];
+			}
+		});
+
+/**
+ * Parses a `text/uri-list` payload (RFC 2483) into local paths.
+ * Pure function.
+ */
+export const parseUriList = (text: string): string[] =>
+	fileUrisToPaths(text.split(/\r?\n/));
+
+/**
+ * Parses an `x-special/gnome-copied-files` payload into local paths.
+ * The first line is the operation ("copy" or "cut") and is ignored.
+ * Pure function.
+ */
+export const parseGnomeCopiedFiles = (text: string): string[] =>
+	fileUrisToPaths(text.split(/\r?\n/).slice(1));
+
+/**
+ * Encodes paths as a `text/uri-list` payload.
+ * Pure function.
+ */
+export const toUriList = (paths: readonly string[]): string =>
+	paths.map((p) => `${pathToFileURL(p).href}\r\n`).join("");
+
+const XML_ENTITIES: Record<string, string> = {
+	"&amp;": "&",
+	"&lt;": "<",
+	"&gt;": ">",
+	"&quot;": '"',
+	"&apos;": "'",
+};
+
+const unescapeXml = (text: string): string =>
+	text.replace(/&(amp|lt|gt|quot|apos);/g, (entity) => XML_ENTITIES[entity]);
+
+const escapeXml = (text: string): string =>
+	text
+		.replace(/&/g, "&amp;")
+		.replace(/</g, "&lt;")
+		.replace(/>/g, "&gt;")
+		.replace(/"/g, "&quot;");
+
+/**
+ * Parses the macOS `NSFilenamesPboardType` property list into paths.
+ * The plist is a flat array of strings, so no full plist parser is needed.
+ * Pure function.
+ */
+export const parseFilenamesPlist = (xml: string): string[] =>
+	Array.from(xml.matchAll(/<string>([^<]*)<\/string>/g), (match) =>
+		unescapeXml(match[1]),
+	).filter((p) => p.length > 0);
+
+/**
+ * Encodes paths as a macOS `NSFilenamesPboardType` property list.
+ * Pure function.
+ */
+export const toFilenamesPlist = (paths: readonly string[]): string =>
+	[
+		'<?xml version="1.0" encoding="UTF-8"?>',
+		'<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">',
+		'<plist version="1.0">',
+		"<array>",
+		...paths.map((p) => `\t<string>${escapeXml(p)}</string>`),
+		"</array>",
+		"</plist>",
+	].join("\n");
+
+/**
+ * Decodes the Windows `FileNameW` format (null-terminated UTF-16LE).
+ * Pure function.
+ */
+export const parseFileNameW = (buffer: Buffer): string[] => {
+	const [path] = buffer.toString("utf16le").split("\0");
+	return path ? [path] : [];
+};
+
+/**
+ * Encodes a path in the Windows `FileNameW` format.
+ * Pure function.
+ */
+export const toFileNameW = (path: string): Buffer =>
+	Buffer.from(`${path}\0`, "utf16le");
+
+/**
+ * Reads the list of copied files from the clipboard, if any.
+ *
+ * Electron cannot read `CF_HDROP` directly, so on Windows only the first
+ * file of a multi-file copy is available (via `FileNameW`).
+ */
+export const readFileList = (
+	clipboard: Pick<ClipboardBuffers, "readBuffer">,
+	platform: NodeJS.Platform,
+): string[] => {
+	switch (platform) {
+		case "darwin":
+			return parseFilenamesPlist(
+				clipboard.readBuffer(FILE_LIST_FORMATS.darwin).toString("utf8"),
+			);
+		case "win32":
+			return parseFileNameW(clipboard.readBuffer(FILE_LIST_FORMATS.win32));
+		default: {
+			const gnome = clipboard
+				.readBuffer(FILE_LIST_FORMATS.gnome)
+				.toString("utf8");
+			if (gnome) return parseGnomeCopiedFiles(gnome);
+			return parseUriList(
+				clipboard.readBuffer(FILE_LIST_FORMATS.uriList).toString("utf8"),
+			);
+		}
+	}
+};
+
+export type WriteFileListOptions = {
+	clipboard: ClipboardBuffers;
+	platform: NodeJS.Platform;
+	paths: readonly string[];
+};
+
+/**
+ * Places a list of files on the clipboard so a file manager can paste them.
+ *
+ * Each `writeBuffer` call replaces the clipboard, so a single format is
+ * written per platform. On Windows only the first path can be restored.
+ */
+export const writeFileList = (options: WriteFileListOptions): void => {
+	const { clipboard, platform, paths } = options;
+	if (paths.length === 0) {
+		throw new Error("Cannot restore an empty file list");
+	}
+
+	switch (platform) {
+		case "darwin":
+			clipboard.writeBuffer(
+				FILE_LIST_FORMATS.darwin,
+				Buffer.from(toFilenamesPlist(paths), "utf8"),
+			);
+			return;
+		case "win32":
+			clipboard.writeBuffer(FILE_LIST_FORMATS.win32, toFileNameW(paths[0]));
+			return;
+		default:
+			clipboard.writeBuffer(
+				FILE_LIST_FORMATS.uriList,
+				Buffer.from(toUriList(paths), "utf8"),
+			);
+	}
+};
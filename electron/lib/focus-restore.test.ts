@@ -0,0 +1,71 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	createFocusTracker,
+	getFocusCommands,
+	parseFocusHandle,
+} from "./focus-restore.js";
+
+describe("parseFocusHandle", () => {
+	it("accepts positive integer handles", () => {
+		expect(parseFocusHandle("83886087\n")).toBe("83886087");
+	});
+
+	it("rejects anything else", () => {
+		expect(parseFocusHandle("")).toBeNull();
+		expect(parseFocusHandle("0")).toBeNull();
+		expect(parseFocusHandle("-1")).toBeNull();
+		expect(parseFocusHandle("12; rm -rf /")).toBeNull();
+	});
+});
+
+describe("getFocusCommands", () => {
+	it("activates the remembered window on Linux", () => {
+		expect(getFocusCommands("linux")?.activate("42")).toEqual({
+			command: "xdotool",
+			args: ["windowactivate", "--sync", "42"],
+		});
+	});
+
+	it("is unsupported elsewhere", () => {
+		expect(getFocusCommands("freebsd")).toBeNull();
+	});
+});
+
+describe("createFocusTracker", () => {
+	it("re-activates the remembered window once", async () => {
+		const run = vi.fn().mockResolvedValue("42\n");
+		const tracker = createFocusTracker({ platform: "linux", run });
+
+		await tracker.remember();
+
+		expect(await tracker.restore()).toBe(true);
+		expect(run).toHaveBeenLastCalledWith("xdotool", [
+			"windowactivate",
+			"--sync",
+			"42",
+		]);
+		expect(await tracker.restore()).toBe(false);
+	});
+
+	it("remembers nothing when the lookup fails", async () => {
+		const run = vi.fn().mockRejectedValueOnce(new Error("no display"));
+		const tracker = createFocusTracker({ platform: "linux", run });
+
+		await tracker.remember();
+
+		expect(await tracker.restore()).toBe(false);
+		expect(run).toHaveBeenCalledTimes(1);
+	});
+
+	it("reports a failed re-activation", async () => {
+		const run = vi
+			.fn()
+			.mockResolvedValueOnce("1234")
+			.mockRejectedValueOnce(new Error("window gone"));
+		const tracker = createFocusTracker({ platform: "darwin", run });
+
+		await tracker.remember();
+
+		expect(await tracker.restore()).toBe(false);
+	});
+});
@@ -0,0 +1,154 @@
+import { execFile } from "node:child_process";
+import type { OutputCommandRunner } from "./foreground-app.js";
+
+/**
+ * Upper bound on remembering or restoring focus (ms); the picker waits for
+ * the lookup before showing, so it must stay short.
+ */
+const FOCUS_COMMAND_TIMEOUT_MS = 500;
+
+/**
+ * Prints the handle of the foreground window.
+ */
+const WINDOWS_GET_FOREGROUND_SCRIPT =
+	"Add-Type -Name Win32 -Namespace Native -MemberDefinition '[DllImport(\"user32.dll\")] public static extern IntPtr GetForegroundWindow();'; [Native.Win32]::GetForegroundWindow().ToInt64()";
+
+const runCommand: OutputCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(
+			command,
+			args,
+			{ timeout: FOCUS_COMMAND_TIMEOUT_MS, encoding: "utf-8" },
+			(error, stdout) => (error ? reject(error) : resolve(stdout)),
+		);
+	});
+
+type FocusCommand = { command: string; args: string[] };
+
+type FocusCommands = {
+	capture: FocusCommand;
+	activate: (handle: string) => FocusCommand;
+};
+
+/**
+ * Commands that read the focused window's handle and re-activate a
+ * remembered handle, or null if the platform is unsupported.
+ * - macOS: the frontmost app's pid, through System Events
+ * - Windows: the foreground window handle, through user32
+ * - Linux (X11): the active window id, through `xdotool`
+ * Pure function.
+ */
+export const getFocusCommands = (
+	platform: NodeJS.Platform,
+): FocusCommands | null => {
+	switch (platform) {
+		case "darwin":
+			return {
+				capture: {
+					command: "osascript",
+					args: [
+						"-e",
+						'tell application "System Events" to return unix id of first application process whose frontmost is true',
+					],
+				},
+				activate: (handle) => ({
+					command: "osascript",
+					args: [
+						"-e",
+						`tell application "System Events" to set frontmost of (first application process whose unix id is ${handle}) to true`,
+					],
+				}),
+			};
+		case "win32":
+			return {
+				capture: {
+					command: "powershell.exe",
+					args: [
+						"-NoProfile",
+						"-NonInteractive",
+						"-Command",
+						WINDOWS_GET_FOREGROUND_SCRIPT,
+					],
+				},
+				activate: (handle) => ({
+					command: "powershell.exe",
+					args: [
+						"-NoProfile",
+						"-NonInteractive",
+						"-Command",
+						`Add-Type -Name Win32 -Namespace Native -MemberDefinition '[DllImport("user32.dll")] public static extern bool SetForegroundWindow(IntPtr hWnd);'; $null = [Native.Win32]::SetForegroundWindow([IntPtr]${handle})`,
+					],
+				}),
+			};
+		case "linux":
+			return {
+				capture: { command: "xdotool", args: ["getactivewindow"] },
+				activate: (handle) => ({
+					command: "xdotool",
+					args: ["windowactivate", "--sync", handle],
+				}),
+			};
+		default:
+			return null;
+	}
+};
+
+/**
+ * Extracts a window handle from command output. Handles are positive
+ * integers on every platform, which also keeps them safe to splice into
+ * the activate commands.
+ * Pure function.
+ */
+export const parseFocusHandle = (output: string): string | null => {
+	const handle = output.trim();
+	return /^[1-9][0-9]*$/.test(handle) ? handle : null;
+};
+
+/**
+ * Creates the focus tracker: `remember` records the focused window before
+ * the picker opens and `restore` re-activates it before a paste, so the
+ * keystroke lands where the user was typing.
+ * Lookups that fail leave nothing remembered; the OS then decides where
+ * focus goes, as before.
+ */
+export const createFocusTracker = (deps: {
+	platform: NodeJS.Platform;
+	run?: OutputCommandRunner;
+}) => {
+	const run = deps.run ?? runCommand;
+	const commands = getFocusCommands(deps.platform);
+	let handle: string | null = null;
+
+	const remember = async (): Promise<void> => {
+		handle = null;
+		if (!commands) return;
+		try {
+			const { command, args } = commands.capture;
+			handle = parseFocusHandle(await run(command, args));
+		} catch {
+			handle = null;
+		}
+	};
+
+	/**
+	 * Re-activates the remembered window once.
+	 *
+	 * @returns whether a window was re-activated
+	 */
+	const restore = async (): Promise<boolean> => {
+		const target = handle;
+		handle = null;
+		if (!commands || target === null) return false;
+		try {
+			const { command, args } = commands.activate(target);
+			await run(command, args);
+			return true;
+		} catch {
+			return false;
+		}
+	};
+
+	return { remember, restore };
+};
+
+export type FocusTracker = ReturnType<typeof createFocusTracker>;
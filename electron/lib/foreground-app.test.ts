@@ -0,0 +1,136 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	createForegroundAppResolver,
+	type OutputCommandRunner,
+	parseLsappinfoName,
+	parseWindowsForegroundOutput,
+} from "./foreground-app.js";
+
+describe("parseLsappinfoName", () => {
+	it("extracts the display name", () => {
+		expect(parseLsappinfoName('"LSDisplayName"="Bitwarden"\n')).toBe(
+			"Bitwarden",
+		);
+	});
+
+	it("returns null when the name is missing", () => {
+		expect(parseLsappinfoName("")).toBeNull();
+		expect(parseLsappinfoName('"LSDisplayName"=""')).toBeNull();
+	});
+});
+
+describe("parseWindowsForegroundOutput", () => {
+	it("splits the process name and window title", () => {
+		const output = "Code.exe\r\nmain.ts - Visual Studio Code\r\n";
+
+		expect(parseWindowsForegroundOutput(output)).toEqual({
+			name: "Code.exe",
+			title: "main.ts - Visual Studio Code",
+		});
+	});
+
+	it("treats a blank title as missing", () => {
+		expect(parseWindowsForegroundOutput("KeePassXC.exe\r\n\r\n")).toEqual({
+			name: "KeePassXC.exe",
+			title: null,
+		});
+		expect(parseWindowsForegroundOutput("")).toBeNull();
+	});
+});
+
+describe("createForegroundAppResolver", () => {
+	it("uses lsappinfo and AppleScript on macOS", async () => {
+		const run = vi.fn<OutputCommandRunner>(async (command, args) => {
+			if (command === "lsappinfo") {
+				return args[0] === "front"
+					? "ASN:0x0-0x1a01a\n"
+					: '"LSDisplayName"="Safari"\n';
+			}
+			return args[1].includes("System Events")
+				? "Release notes\n"
+				: "https://example.com/notes\n";
+		});
+		const resolve = createForegroundAppResolver({ platform: "darwin", run });
+
+		await expect(resolve()).resolves.toEqual({
+			name: "Safari",
+			title: "Release notes",
+			url: "https://example.com/notes",
+		});
+		expect(run).toHaveBeenCalledWith("lsappinfo", [
+			"info",
+			"-only",
+			"name",
+			"ASN:0x0-0x1a01a",
+		]);
+	});
+
+	it("only looks up URLs for known browsers", async () => {
+		const run = vi.fn<OutputCommandRunner>(async (command, args) => {
+			if (command === "lsappinfo") {
+				return args[0] === "front"
+					? "ASN:0x0-0x2\n"
+					: '"LSDisplayName"="Slack"';
+			}
+			throw new Error("Not authorized to send Apple events");
+		});
+		const resolve = createForegroundAppResolver({ platform: "darwin", run });
+
+		await expect(resolve()).resolves.toEqual({
+			name: "Slack",
+			title: null,
+			url: null,
+		});
+		expect(run).toHaveBeenCalledTimes(3);
+	});
+
+	it("reads the foreground process on Windows", async () => {
+		const run = vi.fn<OutputCommandRunner>(
+			async () => "KeePassXC.exe\r\nPasswords.kdbx\r\n",
+		);
+		const resolve = createForegroundAppResolver({ platform: "win32", run });
+
+		await expect(resolve()).resolves.toEqual({
+			name: "KeePassXC.exe",
+			title: "Passwords.kdbx",
+			url: null,
+		});
+		expect(run.mock.calls[0][0]).toBe("powershell.exe");
+	});
+
+	it("reads /proc for the active window pid on Linux", async () => {
+		const run = vi.fn<OutputCommandRunner>(async (_command, args) =>
+			args[1] === "getwindowpid" ? "4242\n" : "Terminal\n",
+		);
+		const readProcName = vi.fn(async () => "keepassxc\n");
+		const resolve = createForegroundAppResolver({
+			platform: "linux",
+			run,
+			readProcName,
+		});
+
+		await expect(resolve()).resolves.toEqual({
+			name: "keepassxc",
+			title: "Terminal",
+			url: null,
+		});
+		expect(readProcName).toHaveBeenCalledWith(4242);
+	});
+
+	it("resolves to null when the lookup fails", async () => {
+		const run = vi.fn<OutputCommandRunner>(async () => {
+			throw new Error("xdotool: not found");
+		});
+		const resolve = createForegroundAppResolver({ platform: "linux", run });
+
+		await expect(resolve()).resolves.toBeNull();
+	});
+
+	it("resolves to null on unsupported platforms", async () => {
+		const run = vi.fn<OutputCommandRunner>();
+		const resolve = createForegroundAppResolver({ platform: "aix", run });
+
+		await expect(resolve()).resolves.toBeNull();
+		expect(run).not.toHaveBeenCalled();
+	});
+});
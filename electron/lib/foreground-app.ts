@@ -0,0 +1,192 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+
+/**
+ * Runs a command and resolves with its stdout; injectable for tests.
+ * Rejects if the command fails or times out.
+ */
+export type OutputCommandRunner = (
+	command: string,
+	args: string[],
+) => Promise<string>;
+
+/**
+ * Where a copy was made.
+ */
+export type SourceApp = {
+	/** App or process name, e.g. `Slack` or `Code.exe` */
+	name: string;
+	/** Title of the focused window, if it could be read */
+	title: string | null;
+	/** Page URL, for supported browsers on macOS */
+	url: string | null;
+};
+
+/**
+ * Resolves the application in the foreground, or null if it cannot be
+ * determined.
+ */
+export type ForegroundAppResolver = () => Promise<SourceApp | null>;
+
+/**
+ * Upper bound on a single lookup (ms); a slow lookup must not stall
+ * capture indefinitely.
+ */
+const LOOKUP_TIMEOUT_MS = 2000;
+
+/**
+ * Prints the executable name of the process owning the foreground window,
+ * then the window title on the next line.
+ */
+const WINDOWS_FOREGROUND_SCRIPT = [
+	'Add-Type -Name Win32 -Namespace Native -MemberDefinition \'[DllImport("user32.dll")] public static extern IntPtr GetForegroundWindow(); [DllImport("user32.dll")] public static extern uint GetWindowThreadProcessId(IntPtr hWnd, out uint processId);\'',
+	"$processId = 0",
+	"$null = [Native.Win32]::GetWindowThreadProcessId([Native.Win32]::GetForegroundWindow(), [ref]$processId)",
+	"$process = Get-Process -Id $processId",
+	"$process.ProcessName + '.exe'",
+	"$process.MainWindowTitle",
+].join("; ");
+
+/**
+ * Reads the front window title through System Events; needs the
+ * Accessibility permission the app already asks for to paste.
+ */
+const MAC_WINDOW_TITLE_SCRIPT =
+	'tell application "System Events" to tell (first application process whose frontmost is true) to return name of front window';
+
+/**
+ * AppleScript returning the front tab URL, by browser app name.
+ * Each browser asks for Automation permission the first time.
+ */
+const MAC_BROWSER_URL_SCRIPTS = new Map<string, string>([
+	["Safari", 'tell application "Safari" to return URL of front document'],
+	...["Google Chrome", "Brave Browser", "Microsoft Edge", "Arc"].map(
+		(browser): [string, string] => [
+			browser,
+			`tell application "${browser}" to return URL of active tab of front window`,
+		],
+	),
+]);
+
+const runCommand: OutputCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(
+			command,
+			args,
+			{ timeout: LOOKUP_TIMEOUT_MS, encoding: "utf-8" },
+			(error, stdout) => (error ? reject(error) : resolve(stdout)),
+		);
+	});
+
+/**
+ * Extracts the display name from `lsappinfo info -only name` output,
+ * e.g. `"LSDisplayName"="Bitwarden"`.
+ * Pure function.
+ */
+export const parseLsappinfoName = (output: string): string | null => {
+	const match = /"LSDisplayName"="([^"]*)"/.exec(output);
+	return match?.[1] || null;
+};
+
+/**
+ * Splits the Windows lookup output into process name and window title.
+ * Pure function.
+ */
+export const parseWindowsForegroundOutput = (
+	output: string,
+): { name: string; title: string | null } | null => {
+	const [name = "", title = ""] = output.split(/\r?\n/);
+	if (!name.trim()) return null;
+	return { name: name.trim(), title: title.trim() || null };
+};
+
+/**
+ * Creates a resolver for the current platform.
+ * - macOS: `lsappinfo` for the app (no permission needed), System Events
+ *   for the window title, and AppleScript for browser URLs
+ * - Windows: foreground window owner via user32, e.g. `KeePassXC.exe`
+ * - Linux (X11): `xdotool` window pid, then `/proc/<pid>/comm`
+ *
+ * A failed app lookup resolves to null; a failed title or URL lookup
+ * leaves just that field null.
+ */
+export const createForegroundAppResolver = (deps: {
+	platform: NodeJS.Platform;
+	run?: OutputCommandRunner;
+	readProcName?: (pid: number) => Promise<string>;
+}): ForegroundAppResolver => {
+	const run = deps.run ?? runCommand;
+	const readProcName =
+		deps.readProcName ??
+		((pid: number) => fs.readFile(`/proc/${pid}/comm`, "utf-8"));
+
+	/**
+	 * Runs an optional lookup, resolving to null if it fails or is empty.
+	 */
+	const tryRead = async (
+		command: string,
+		args: string[],
+	): Promise<string | null> => {
+		try {
+			return (await run(command, args)).trim() || null;
+		} catch {
+			return null;
+		}
+	};
+
+	const lookup = async (): Promise<SourceApp | null> => {
+		if (deps.platform === "darwin") {
+			const asn = (await run("lsappinfo", ["front"])).trim();
+			if (!asn) return null;
+			const name = parseLsappinfoName(
+				await run("lsappinfo", ["info", "-only", "name", asn]),
+			);
+			if (!name) return null;
+
+			const urlScript = MAC_BROWSER_URL_SCRIPTS.get(name);
+			const [title, url] = await Promise.all([
+				tryRead("osascript", ["-e", MAC_WINDOW_TITLE_SCRIPT]),
+				urlScript ? tryRead("osascript", ["-e", urlScript]) : null,
+			]);
+			return { name, title, url };
+		}
+
+		if (deps.platform === "win32") {
+			const parsed = parseWindowsForegroundOutput(
+				await run("powershell.exe", [
+					"-NoProfile",
+					"-NonInteractive",
+					"-Command",
+					WINDOWS_FOREGROUND_SCRIPT,
+				]),
+			);
+			return parsed && { ...parsed, url: null };
+		}
+
+		if (deps.platform === "linux") {
+			const pid = Number.parseInt(
+				await run("xdotool", ["getactivewindow", "getwindowpid"]),
+				10,
+			);
+			if (!Number.isInteger(pid) || pid <= 0) return null;
+			const name = (await readProcName(pid)).trim();
+			if (!name) return null;
+
+			const title = await tryRead("xdotool", [
+				"getactivewindow",
+				"getwindowname",
+			]);
+			return { name, title, url: null };
+		}
+
+		return null;
+	};
+
+	return async () => {
+		try {
+			return await lookup();
+		} catch {
+			return null;
+		}
+	};
+};
@@ -0,0 +1,122 @@
+import { describe, expect, it } from "vitest";
+import { formatText, parseFormatRequest } from "./formatters.js";
+
+describe("parseFormatRequest", () => {
+	it("defaults the target to a new item", () => {
+		expect(parseFormatRequest({ format: "json-pretty" })).toEqual({
+			format: "json-pretty",
+			target: "item",
+		});
+	});
+
+	it("rejects unknown formats and targets", () => {
+		expect(() => parseFormatRequest({ format: "yaml-pretty" })).toThrow(
+			"Invalid format request: format must be one of json-pretty",
+		);
+		expect(() =>
+			parseFormatRequest({ format: "sql-minify", target: "file" }),
+		).toThrow("target must be one of item, clipboard");
+		expect(() => parseFormatRequest(null)).toThrow("expected an object");
+	});
+});
+
+describe("formatText", () => {
+	it("pretty-prints JSON, keeping large numbers and strings verbatim", () => {
+		const text =
+			'{"a":1,"b":[2,{"c":"x\\"y, {z}"}],"e":{},"big":12345678901234567890}';
+
+		expect(formatText(text, "json-pretty")).toBe(
+			[
+				"{",
+				'  "a": 1,',
+				'  "b": [',
+				"    2,",
+				"    {",
+				'      "c": "x\\"y, {z}"',
+				"    }",
+				"  ],",
+				'  "e": {},',
+				'  "big": 12345678901234567890',
+				"}",
+			].join("\n"),
+		);
+	});
+
+	it("minifies JSON and rejects invalid JSON", () => {
+		const text = '{\n  "a" : [ 1 , 2 ],\n  "s": "a b"\n}';
+
+		expect(formatText(text, "json-minify")).toBe('{"a":[1,2],"s":"a b"}');
+		expect(() => formatText('{"a":', "json-pretty")).toThrow("Not valid JSON");
+	});
+
+	it("indents XML, keeping comments, CDATA and inline text", () => {
+		const text =
+			'<?xml version="1.0"?><root><!-- c --><item id="1"><name>A &amp; B</name><empty/></item><item><![CDATA[x<y]]></item></root>';
+
+		expect(formatText(text, "xml-pretty")).toBe(
+			[
+				'<?xml version="1.0"?>',
+				"<root>",
+				"  <!-- c -->",
+				'  <item id="1">',
+				"    <name>A &amp; B</name>",
+				"    <empty/>",
+				"  </item>",
+				"  <item>",
+				"    <![CDATA[x<y]]>",
+				"  </item>",
+				"</root>",
+			].join("\n"),
+		);
+	});
+
+	it("minifies XML and rejects mismatched tags", () => {
+		const text = "<root>\n  <a>1</a>\n  <b>two words</b>\n</root>";
+
+		expect(formatText(text, "xml-minify")).toBe(
+			"<root><a>1</a><b>two words</b></root>",
+		);
+		expect(() => formatText("<a><b></a>", "xml-pretty")).toThrow(
+			"Not well-formed XML: unexpected </a>",
+		);
+	});
+
+	it("lays out SQL clauses, lists, conditions and subqueries", () => {
+		const text =
+			"select u.id, count(*) as n from users u left outer join orders o on o.user_id = u.id and o.status = 'paid' where u.id in (select user_id from bans) group by u.id; update t set a = 1, b = 'it''s'";
+
+		expect(formatText(text, "sql-pretty")).toBe(
+			[
+				"SELECT",
+				"  u.id,",
+				"  count(*) AS n",
+				"FROM users u",
+				"LEFT OUTER JOIN orders o",
+				"  ON o.user_id = u.id",
+				"    AND o.status = 'paid'",
+				"WHERE u.id IN (",
+				"  SELECT",
+				"    user_id",
+				"  FROM bans",
+				")",
+				"GROUP BY u.id;",
+				"",
+				"UPDATE t",
+				"SET",
+				"  a = 1,",
+				"  b = 'it''s'",
+			].join("\n"),
+		);
+	});
+
+	it("minifies SQL without its comments", () => {
+		const text = "SELECT a,\n  b -- comment\nFROM t\nWHERE x = 'y'";
+
+		expect(formatText(text, "sql-minify")).toBe(
+			"SELECT a, b FROM t WHERE x = 'y'",
+		);
+		expect(() => formatText("select 'abc", "sql-pretty")).toThrow(
+			"Not valid SQL: unterminated quote",
+		);
+	});
+});
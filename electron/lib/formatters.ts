@@ -0,0 +1,521 @@
+import { isTransformTarget, type TransformTarget } from "./text-transforms.js";
+
+const ITEM_FORMATS = [
+	"json-pretty",
+	"json-minify",
+	"xml-pretty",
+	"xml-minify",
+	"sql-pretty",
+	"sql-minify",
+] as const;
+
+export type ItemFormat = (typeof ITEM_FORMATS)[number];
+
+/**
+ * A validated request to reformat a text item.
+ */
+export type FormatRequest = {
+	format: ItemFormat;
+	target: TransformTarget;
+};
+
+const INDENT = "  ";
+
+// ============================================================================
+// Request Validation
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid format request: ${message}`);
+
+/**
+ * Validates a format request from the renderer.
+ * Pure function. `target` defaults to a new item.
+ *
+ * @throws if the format or target is unknown
+ */
+export const parseFormatRequest = (value: unknown): FormatRequest => {
+	if (typeof value !== "object" || value === null) {
+		throw invalid("expected an object");
+	}
+	const { format, target = "item" } = value as Record<string, unknown>;
+	if (!ITEM_FORMATS.includes(format as ItemFormat)) {
+		throw invalid(`format must be one of ${ITEM_FORMATS.join(", ")}`);
+	}
+	if (!isTransformTarget(target)) {
+		throw invalid("target must be one of item, clipboard");
+	}
+	return { format: format as ItemFormat, target };
+};
+
+// ============================================================================
+// JSON
+// ============================================================================
+
+/**
+ * Re-indents JSON, or strips its whitespace when `indent` is null.
+ * Tokens are copied as written, so large numbers keep their digits.
+ * Pure function.
+ *
+ * @throws if the text is not valid JSON
+ */
+const formatJson = (text: string, indent: string | null): string => {
+	try {
+		JSON.parse(text);
+	} catch (error) {
+		throw new Error(
+			`Not valid JSON: ${error instanceof Error ? error.message : String(error)}`,
+		);
+	}
+
+	let out = "";
+	let depth = 0;
+	const newline = () => (indent === null ? "" : `\n${indent.repeat(depth)}`);
+	for (let i = 0; i < text.length; i++) {
+		const char = text[i];
+		if (char === '"') {
+			const start = i;
+			for (i++; text[i] !== '"'; i++) {
+				if (text[i] === "\\") i++;
+			}
+			out += text.slice(start, i + 1);
+		} else if (char === "{" || char === "[") {
+			const rest = text.slice(i + 1).trimStart();
+			if (rest[0] === (char === "{" ? "}" : "]")) {
+				out += char === "{" ? "{}" : "[]";
+				i = text.length - rest.length;
+			} else {
+				depth++;
+				out += char + newline();
+			}
+		} else if (char === "}" || char === "]") {
+			depth--;
+			out += newline() + char;
+		} else if (char === ",") {
+			out += `,${newline()}`;
+		} else if (char === ":") {
+			out += indent === null ? ":" : ": ";
+		} else if (!/\s/.test(char)) {
+			out += char;
+		}
+	}
+	return out;
+};
+
+// ============================================================================
+// XML
+// ============================================================================
+
+type XmlToken = {
+	kind: "open" | "close" | "empty" | "text" | "other";
+	raw: string;
+	/** Element name of open and close tags */
+	name?: string;
+};
+
+/**
+ * Splits XML into tags, text and other markup (comments, CDATA,
+ * declarations, processing instructions).
+ * Pure function.
+ *
+ * @throws if markup is unterminated or elements are not balanced
+ */
+const tokenizeXml = (text: string): XmlToken[] => {
+	const tokens: XmlToken[] = [];
+	const open: string[] = [];
+	let i = 0;
+	while (i < text.length) {
+		if (text[i] !== "<") {
+			const end = text.indexOf("<", i);
+			const raw = text.slice(i, end === -1 ? text.length : end);
+			tokens.push({ kind: "text", raw });
+			i += raw.length;
+			continue;
+		}
+
+		const terminator = text.startsWith("<!--", i)
+			? "-->"
+			: text.startsWith("<![CDATA[", i)
+				? "]]>"
+				: text.startsWith("<?", i)
+					? "?>"
+					: ">";
+		const end = text.indexOf(terminator, i + 1);
+		if (end === -1) {
+			throw new Error("Not well-formed XML: unterminated markup");
+		}
+		const raw = text.slice(i, end + terminator.length);
+		i += raw.length;
+
+		const tag = /^<(\/?)([\w:.-]+)[\s\S]*?(\/?)>$/.exec(raw);
+		if (terminator !== ">" || raw.startsWith("<!") || !tag) {
+			tokens.push({ kind: "other", raw });
+		} else if (tag[1]) {
+			if (open.pop() !== tag[2]) {
+				throw new Error(`Not well-formed XML: unexpected </${tag[2]}>`);
+			}
+			tokens.push({ kind: "close", raw, name: tag[2] });
+		} else if (tag[3]) {
+			tokens.push({ kind: "empty", raw });
+		} else {
+			open.push(tag[2]);
+			tokens.push({ kind: "open", raw, name: tag[2] });
+		}
+	}
+	if (open.length > 0) {
+		throw new Error(
+			`Not well-formed XML: <${open[open.length - 1]}> not closed`,
+		);
+	}
+	return tokens;
+};
+
+/**
+ * Puts each element on its own line, indented by depth, or joins them
+ * without the whitespace between tags when `indent` is null. Elements
+ * holding only text stay on one line.
+ * Pure function.
+ *
+ * @throws if the text is not well-formed XML
+ */
+const formatXml = (text: string, indent: string | null): string => {
+	const tokens = tokenizeXml(text.trim()).filter(
+		(token) => token.kind !== "text" || token.raw.trim() !== "",
+	);
+	if (indent === null) return tokens.map((token) => token.raw).join("");
+
+	const lines: string[] = [];
+	let depth = 0;
+	for (let i = 0; i < tokens.length; i++) {
+		const token = tokens[i];
+		const next = tokens[i + 1];
+		const after = tokens[i + 2];
+		if (token.kind === "close") depth--;
+		const prefix = indent.repeat(Math.max(depth, 0));
+		if (
+			token.kind === "open" &&
+			next?.kind === "text" &&
+			after?.kind === "close" &&
+			after.name === token.name
+		) {
+			lines.push(prefix + token.raw + next.raw.trim() + after.raw);
+			i += 2;
+			continue;
+		}
+		lines.push(prefix + (token.kind === "text" ? token.raw.trim() : token.raw));
+		if (token.kind === "open") depth++;
+	}
+	return lines.join("\n");
+};
+
+// ============================================================================
+// SQL
+// ============================================================================
+
+type SqlToken = {
+	kind: "word" | "quoted" | "comment" | "punct";
+	text: string;
+};
+
+const SQL_TOKEN_PATTERN =
+	/\s+|(--[^\n]*|\/\*[\s\S]*?\*\/)|('(?:[^']|'')*'|"(?:[^"]|"")*"|`[^`]*`|\[[^\]]*\])|([\w$@:.]+)|(<>|<=|>=|!=|\|\||::|[^\s\w'"`])/g;
+
+/**
+ * Clauses starting a new line, longest first so `LEFT OUTER JOIN` wins
+ * over `LEFT JOIN`.
+ */
+const SQL_CLAUSES = [
+	"LEFT OUTER JOIN",
+	"RIGHT OUTER JOIN",
+	"FULL OUTER JOIN",
+	"INSERT INTO",
+	"DELETE FROM",
+	"GROUP BY",
+	"ORDER BY",
+	"UNION ALL",
+	"INNER JOIN",
+	"LEFT JOIN",
+	"RIGHT JOIN",
+	"FULL JOIN",
+	"CROSS JOIN",
+	"WITH",
+	"SELECT",
+	"FROM",
+	"WHERE",
+	"HAVING",
+	"LIMIT",
+	"OFFSET",
+	"UNION",
+	"INTERSECT",
+	"EXCEPT",
+	"VALUES",
+	"UPDATE",
+	"SET",
+	"JOIN",
+	"RETURNING",
+].map((clause) => clause.split(" "));
+
+/** Clauses whose comma-separated items each get a line */
+const SQL_LIST_CLAUSES = new Set(["SELECT", "SET"]);
+
+/** Clauses whose AND and OR conditions each get a line */
+const SQL_CONDITION_CLAUSES = new Set(["WHERE", "HAVING", "ON"]);
+
+const SQL_KEYWORDS = new Set([
+	...SQL_CLAUSES.flat(),
+	"AND",
+	"OR",
+	"NOT",
+	"ON",
+	"AS",
+	"IN",
+	"IS",
+	"NULL",
+	"LIKE",
+	"BETWEEN",
+	"EXISTS",
+	"DISTINCT",
+	"CASE",
+	"WHEN",
+	"THEN",
+	"ELSE",
+	"END",
+	"ASC",
+	"DESC",
+	"ALL",
+	"ANY",
+	"OUTER",
+	"INTO",
+	"BY",
+	"TABLE",
+	"CREATE",
+	"ALTER",
+	"DROP",
+	"INDEX",
+	"VIEW",
+	"IF",
+	"PRIMARY",
+	"KEY",
+	"DEFAULT",
+]);
+
+/**
+ * Splits SQL into words, quoted strings and identifiers, comments and
+ * punctuation, dropping whitespace.
+ * Pure function.
+ *
+ * @throws if a quote is not closed
+ */
+const tokenizeSql = (text: string): SqlToken[] => {
+	const tokens: SqlToken[] = [];
+	let end = 0;
+	for (const match of text.matchAll(SQL_TOKEN_PATTERN)) {
+		if (match.index !== end) break;
+		end += match[0].length;
+		const [, comment, quoted, word, punct] = match;
+		if (comment) tokens.push({ kind: "comment", text: comment });
+		else if (quoted) tokens.push({ kind: "quoted", text: quoted });
+		else if (word) tokens.push({ kind: "word", text: word });
+		else if (punct) tokens.push({ kind: "punct", text: punct });
+	}
+	if (end !== text.length) {
+		throw new Error("Not valid SQL: unterminated quote");
+	}
+	return tokens;
+};
+
+/**
+ * Whether a space goes between two adjacent tokens.
+ */
+const needsSpace = (previous: SqlToken, token: SqlToken): boolean => {
+	if ([",", ")", ";", "."].includes(token.text) || previous.text === ".") {
+		return false;
+	}
+	if (previous.text === "(") return false;
+	// Function calls such as count(*) keep the bracket on the name
+	return !(
+		token.text === "(" &&
+		previous.kind === "word" &&
+		!SQL_KEYWORDS.has(previous.text.toUpperCase())
+	);
+};
+
+/**
+ * Joins SQL tokens on one line with single spaces, dropping comments.
+ * Pure function.
+ */
+const minifySql = (tokens: SqlToken[]): string => {
+	let out = "";
+	let previous: SqlToken | null = null;
+	for (const token of tokens) {
+		if (token.kind === "comment") continue;
+		if (previous && needsSpace(previous, token)) out += " ";
+		out += token.text;
+		previous = token;
+	}
+	return out;
+};
+
+/**
+ * Lays SQL out one clause per line with keywords in upper case. Items of
+ * SELECT and SET lists and AND/OR conditions get their own indented
+ * lines, and subqueries are indented a level.
+ * Pure function.
+ */
+const prettySql = (tokens: SqlToken[]): string => {
+	const lines: string[] = [];
+	let line = "";
+	let previous: SqlToken | null = null;
+	let level = 0;
+	let clause = "";
+	/**
+	 * Open brackets, each recording whether it holds a subquery and the
+	 * clause state to return to when it closes
+	 */
+	const brackets: Array<{
+		subquery: boolean;
+		clause: string;
+		clauseDepth: number;
+	}> = [];
+	/** Brackets opened since the current clause started */
+	let clauseDepth = 0;
+
+	const breakLine = (indent: number) => {
+		if (line.trim() !== "") lines.push(line);
+		line = INDENT.repeat(indent);
+		previous = null;
+	};
+	const write = (token: SqlToken) => {
+		if (previous && needsSpace(previous, token)) line += " ";
+		line += token.text;
+		previous = token;
+	};
+
+	for (let i = 0; i < tokens.length; i++) {
+		const token = tokens[i];
+		const upper = token.kind === "word" ? token.text.toUpperCase() : "";
+
+		const matched =
+			token.kind === "word" &&
+			SQL_CLAUSES.find((words) =>
+				words.every((word, k) => tokens[i + k]?.text.toUpperCase() === word),
+			);
+		if (matched && clauseDepth === 0) {
+			clause = matched.join(" ");
+			breakLine(level);
+			write({ kind: "word", text: clause });
+			i += matched.length - 1;
+			if (SQL_LIST_CLAUSES.has(clause)) breakLine(level + 1);
+			continue;
+		}
+
+		if (token.kind === "comment") {
+			write(token);
+			breakLine(level + (clause ? 1 : 0));
+			continue;
+		}
+
+		if (token.text === "(") {
+			const subquery = /^(?:SELECT|WITH)$/i.test(tokens[i + 1]?.text ?? "");
+			write(token);
+			brackets.push({ subquery, clause, clauseDepth });
+			if (subquery) {
+				level++;
+				clauseDepth = 0;
+			} else {
+				clauseDepth++;
+			}
+			continue;
+		}
+
+		if (token.text === ")") {
+			const bracket = brackets.pop();
+			if (bracket?.subquery) {
+				level--;
+				clause = bracket.clause;
+				clauseDepth = bracket.clauseDepth;
+				breakLine(level);
+			} else if (clauseDepth > 0) {
+				clauseDepth--;
+			}
+			write(token);
+			continue;
+		}
+
+		if (token.text === ";") {
+			write(token);
+			breakLine(0);
+			lines.push("");
+			clause = "";
+			level = 0;
+			continue;
+		}
+
+		if (clauseDepth === 0 && upper === "ON") {
+			clause = "ON";
+			breakLine(level + 1);
+			write({ kind: "word", text: upper });
+			continue;
+		}
+
+		if (
+			clauseDepth === 0 &&
+			(upper === "AND" || upper === "OR") &&
+			SQL_CONDITION_CLAUSES.has(clause)
+		) {
+			breakLine(level + (clause === "ON" ? 2 : 1));
+			write({ kind: "word", text: upper });
+			continue;
+		}
+
+		if (
+			clauseDepth === 0 &&
+			token.text === "," &&
+			SQL_LIST_CLAUSES.has(clause)
+		) {
+			write(token);
+			breakLine(level + 1);
+			continue;
+		}
+
+		write(SQL_KEYWORDS.has(upper) ? { kind: "word", text: upper } : token);
+	}
+	breakLine(0);
+	while (lines[lines.length - 1] === "") lines.pop();
+	return lines.join("\n");
+};
+
+const formatSql = (text: string, pretty: boolean): string => {
+	const tokens = tokenizeSql(text.trim());
+	if (tokens.length === 0) {
+		throw new Error("Not valid SQL: no statements");
+	}
+	return pretty ? prettySql(tokens) : minifySql(tokens);
+};
+
+// ============================================================================
+// Formatting
+// ============================================================================
+
+/**
+ * Pretty-prints or minifies JSON, XML or SQL text.
+ * Pure function.
+ *
+ * @throws if the text is not valid in the format's language
+ */
+export const formatText = (text: string, format: ItemFormat): string => {
+	switch (format) {
+		case "json-pretty":
+			return formatJson(text, INDENT);
+		case "json-minify":
+			return formatJson(text, null);
+		case "xml-pretty":
+			return formatXml(text, INDENT);
+		case "xml-minify":
+			return formatXml(text, null);
+		case "sql-pretty":
+			return formatSql(text, true);
+		case "sql-minify":
+			return formatSql(text, false);
+	}
+};
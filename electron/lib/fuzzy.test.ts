@@ -0,0 +1,51 @@
+import { describe, expect, it } from "vitest";
+import {
+	matchFuzzy,
+	parseFuzzyQuery,
+	scoreFuzzy,
+	scoreFuzzyTerm,
+} from "./fuzzy.js";
+
+describe("scoreFuzzyTerm", () => {
+	it("matches ordered subsequences case-insensitively", () => {
+		expect(scoreFuzzyTerm("gthb", "GitHub token")).not.toBeNull();
+		expect(scoreFuzzyTerm("bhtg", "GitHub token")).toBeNull();
+	});
+
+	it("prefers consecutive matches at word starts", () => {
+		const token = scoreFuzzyTerm("tok", "my token") ?? 0;
+		const scattered = scoreFuzzyTerm("tok", "take ok") ?? 0;
+		expect(token).toBeGreaterThan(scattered);
+	});
+
+	it("rewards camelCase boundaries", () => {
+		const camel = scoreFuzzyTerm("tk", "getToken") ?? 0;
+		const inner = scoreFuzzyTerm("tk", "sticker") ?? 0;
+		expect(camel).toBeGreaterThan(inner);
+	});
+});
+
+describe("scoreFuzzy", () => {
+	it("requires every term to match", () => {
+		const terms = parseFuzzyQuery("gthb tok");
+		expect(scoreFuzzy(terms, "GitHub personal access token")).not.toBeNull();
+		expect(scoreFuzzy(terms, "GitHub password")).toBeNull();
+	});
+
+	it("never matches an empty query", () => {
+		expect(scoreFuzzy(parseFuzzyQuery("   "), "anything")).toBeNull();
+	});
+});
+
+describe("matchFuzzy", () => {
+	it("returns the matched characters as ranges", () => {
+		const match = matchFuzzy(parseFuzzyQuery("gthb tok"), "GitHub token");
+		expect(match?.score).toBe(scoreFuzzy(["gthb", "tok"], "GitHub token"));
+		expect(match?.ranges).toEqual([
+			[0, 1],
+			[2, 4],
+			[5, 6],
+			[7, 10],
+		]);
+	});
+});
@@ -0,0 +1,189 @@
+/**
+ * Fuzzy matching for history search.
+ *
+ * Each query term must appear in the text as an ordered subsequence
+ * ("gthb" matches "GitHub"). Matches score higher when characters are
+ * consecutive or start a word, so "tok" ranks "token" above "take ok".
+ */
+
+import { type MatchRange, mergeRanges } from "./match-ranges.js";
+
+/**
+ * Only the start of long items is scored; matches further in are rare
+ * and scanning them dominates search time.
+ */
+export const MAX_FUZZY_TEXT_LENGTH = 4096;
+
+/**
+ * Number of candidate start positions tried per term.
+ * Bounds the cost of pathological inputs such as long runs of one letter.
+ */
+const MAX_START_POSITIONS = 16;
+
+const SCORE_MATCH = 1;
+const BONUS_CONSECUTIVE = 5;
+const BONUS_WORD_START = 8;
+const PENALTY_GAP = 1;
+const MAX_GAP_PENALTY = 10;
+
+/**
+ * Text being scored, with its lowercase form computed once per item.
+ */
+type FuzzySubject = {
+	text: string;
+	lower: string;
+};
+
+/**
+ * A term's score and the indexes of the characters it matched.
+ */
+type TermMatch = {
+	score: number;
+	indexes: number[];
+};
+
+/**
+ * How well text matched a query, and where.
+ */
+export type FuzzyMatch = {
+	score: number;
+	ranges: MatchRange[];
+};
+
+const toSubject = (text: string): FuzzySubject => ({
+	text,
+	lower: text.toLowerCase(),
+});
+
+const isWordChar = (char: string): boolean => /[\p{L}\p{N}]/u.test(char);
+
+/**
+ * Checks whether the character at `index` starts a word.
+ * Pure function.
+ */
+const isWordStart = (text: string, index: number): boolean => {
+	if (index === 0) return true;
+	const previous = text[index - 1];
+	const current = text[index];
+	if (!isWordChar(previous)) return true;
+	// camelCase boundary
+	return (
+		previous === previous.toLowerCase() &&
+		current !== current.toLowerCase() &&
+		current === current.toUpperCase()
+	);
+};
+
+/**
+ * Greedily matches `term` as a subsequence of `text` from `start`.
+ * Pure function.
+ *
+ * @returns The match, or null if the term does not match
+ */
+const scoreFrom = (
+	term: string,
+	subject: FuzzySubject & { start: number },
+): TermMatch | null => {
+	let score = 0;
+	const indexes: number[] = [];
+	let previousIndex = -1;
+	let position = subject.start;
+
+	for (const char of term) {
+		const index = subject.lower.indexOf(char, position);
+		if (index === -1) return null;
+
+		score += SCORE_MATCH;
+		if (isWordStart(subject.text, index)) score += BONUS_WORD_START;
+		if (previousIndex !== -1) {
+			const gap = index - previousIndex - 1;
+			score +=
+				gap === 0
+					? BONUS_CONSECUTIVE
+					: -Math.min(gap * PENALTY_GAP, MAX_GAP_PENALTY);
+		}
+
+		indexes.push(index);
+		previousIndex = index;
+		position = index + 1;
+	}
+
+	return { score, indexes };
+};
+
+/**
+ * Scores a single lowercase term against prepared text.
+ * Pure function.
+ *
+ * @returns The best match over the first few start positions, or null if
+ *   the term is not a subsequence of the text
+ */
+const scoreTerm = (term: string, subject: FuzzySubject): TermMatch | null => {
+	if (term.length === 0) return { score: 0, indexes: [] };
+
+	let best: TermMatch | null = null;
+	let start = subject.lower.indexOf(term[0]);
+	for (let tries = 0; start !== -1 && tries < MAX_START_POSITIONS; tries++) {
+		const match = scoreFrom(term, { ...subject, start });
+		// Later starts only see a suffix, so once one fails all later ones do
+		if (match === null) break;
+		if (best === null || match.score > best.score) best = match;
+		start = subject.lower.indexOf(term[0], start + 1);
+	}
+	return best;
+};
+
+/**
+ * Scores a single lowercase term against text.
+ * Pure function.
+ *
+ * @returns The match score, or null if the term is not a subsequence
+ */
+export const scoreFuzzyTerm = (term: string, text: string): number | null =>
+	scoreTerm(term, toSubject(text))?.score ?? null;
+
+/**
+ * Splits a fuzzy query into lowercase terms.
+ * Pure function.
+ */
+export const parseFuzzyQuery = (query: string): string[] =>
+	query
+		.toLowerCase()
+		.split(/\s+/)
+		.filter((term) => term.length > 0);
+
+/**
+ * Matches text against every query term.
+ * Pure function.
+ *
+ * @returns The combined score and the matched characters, or null if any
+ *   term fails to match
+ */
+export const matchFuzzy = (
+	terms: readonly string[],
+	text: string,
+): FuzzyMatch | null => {
+	if (terms.length === 0) return null;
+
+	const subject = toSubject(text.slice(0, MAX_FUZZY_TEXT_LENGTH));
+	let score = 0;
+	const ranges: MatchRange[] = [];
+	for (const term of terms) {
+		const match = scoreTerm(term, subject);
+		if (match === null) return null;
+		score += match.score;
+		for (const index of match.indexes) ranges.push([index, index + 1]);
+	}
+	return { score, ranges: mergeRanges(ranges) };
+};
+
+/**
+ * Scores text against every query term.
+ * Pure function.
+ *
+ * @returns The combined score, or null if any term fails to match
+ */
+export const scoreFuzzy = (
+	terms: readonly string[],
+	text: string,
+): number | null => matchFuzzy(terms, text)?.score ?? null;
@@ -0,0 +1,35 @@
+import { describe, expect, it, vi } from "vitest";
+import { guardHandlers } from "./handler-guards.js";
+
+describe("guardHandlers", () => {
+	const createHandlers = () => ({
+		list: vi.fn(() => []),
+		create: vi.fn((_event: unknown, name: string) => ({ name })),
+		delete: vi.fn(async (_event: unknown, _id: number) => true),
+	});
+
+	it("refuses every handler in the group while the check throws", () => {
+		const handlers = createHandlers();
+		const guarded = guardHandlers(handlers, () => {
+			throw new Error("History is locked");
+		});
+
+		const calls = Object.values(guarded) as ((...args: unknown[]) => unknown)[];
+		expect(calls).toHaveLength(3);
+		for (const call of calls) {
+			expect(() => call({}, 1)).toThrow("History is locked");
+		}
+		expect(handlers.list).not.toHaveBeenCalled();
+		expect(handlers.create).not.toHaveBeenCalled();
+		expect(handlers.delete).not.toHaveBeenCalled();
+	});
+
+	it("passes calls through while the check passes", async () => {
+		const handlers = createHandlers();
+		const guarded = guardHandlers(handlers, () => {});
+
+		expect(guarded.create({}, "Work")).toEqual({ name: "Work" });
+		await expect(guarded.delete({}, 3)).resolves.toBe(true);
+		expect(handlers.delete).toHaveBeenCalledWith({}, 3);
+	});
+});
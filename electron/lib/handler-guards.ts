@@ -0,0 +1,22 @@
+/**
+ * Wraps every function of an IPC handler group so each first calls
+ * `assert`, which throws to refuse the call. The history, tag and
+ * collection groups are guarded whole, so a handler added later cannot
+ * miss the lock check. Other properties are passed through.
+ * Pure function: the group itself is left as it is.
+ */
+export const guardHandlers = <T extends object>(
+	handlers: T,
+	assert: () => void,
+): T => {
+	const guarded = { ...handlers };
+	for (const name of Object.keys(handlers) as (keyof T)[]) {
+		const handler = handlers[name];
+		if (typeof handler !== "function") continue;
+		guarded[name] = ((...args: unknown[]) => {
+			assert();
+			return handler(...args);
+		}) as T[keyof T];
+	}
+	return guarded;
+};
@@ -0,0 +1,194 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	exportHistory,
+	formatCsvField,
+	formatCsvRecord,
+	parseHistoryExportRequest,
+	toExportedItem,
+} from "./history-export.js";
+import type { ExportHistoryRow } from "./history-repository.js";
+
+const row = (id: number, content = `item ${id}`): ExportHistoryRow => ({
+	id,
+	content,
+	type: "text",
+	created_at: "2024-01-01 00:00:00",
+	is_favorite: 0,
+	pinned: 0,
+	rtf: null,
+	html: null,
+	image_width: null,
+	image_height: null,
+	use_count: 1,
+	paste_count: 0,
+	last_used_at: null,
+	note: null,
+	source_app: null,
+	source_title: null,
+	source_url: null,
+	sync_excluded: 0,
+	content_size: null,
+	qr_payload: null,
+	content_kind: null,
+	code_language: null,
+	color_value: null,
+	secret_match: null,
+	expires_at: null,
+	link_title: null,
+	link_description: null,
+	link_image: null,
+	summary_title: null,
+	summary_text: null,
+	byte_size: null,
+	char_count: null,
+	word_count: null,
+	line_count: null,
+	is_primary: 0,
+	paste_once: 0,
+	tags: [],
+});
+
+describe("parseHistoryExportRequest", () => {
+	it("accepts a format, an absolute path and filters", () => {
+		const filePath = path.resolve("history.csv");
+		expect(
+			parseHistoryExportRequest("csv", filePath, {
+				pinnedOnly: true,
+				tags: [" Work "],
+			}),
+		).toEqual({
+			format: "csv",
+			filePath,
+			filters: { pinnedOnly: true, tags: ["work"] },
+		});
+		expect(parseHistoryExportRequest("json", filePath).filters).toEqual({});
+	});
+
+	it("rejects invalid requests", () => {
+		const filePath = path.resolve("history.json");
+		const cases: Array<[unknown[], string]> = [
+			[["xml", filePath], "format must be json or csv"],
+			[["json", "history.json"], "path must be absolute"],
+			[["json", filePath, null], "filters must be an object"],
+			[["json", filePath, { pinnedOnly: 1 }], "pinnedOnly must be a boolean"],
+			[["json", filePath, { tags: "work" }], "tags must be an array"],
+			[["json", filePath, { sourceApp: 1 }], "sourceApp must be a string"],
+		];
+		for (const [args, message] of cases) {
+			expect(() =>
+				parseHistoryExportRequest(...(args as [unknown, unknown, unknown])),
+			).toThrow(message);
+		}
+	});
+});
+
+describe("formatCsvField", () => {
+	it("quotes fields with separators, quotes or line breaks", () => {
+		expect(formatCsvField("plain")).toBe("plain");
+		expect(formatCsvField("a,b")).toBe('"a,b"');
+		expect(formatCsvField('say "hi"')).toBe('"say ""hi"""');
+		expect(formatCsvField("two\nlines")).toBe('"two\nlines"');
+	});
+});
+
+describe("formatCsvRecord", () => {
+	it("writes one line per item with empty nulls and joined tags", () => {
+		const item = toExportedItem({
+			...row(7, "hello, world"),
+			pinned: 1,
+			tags: ["code", "work"],
+			source_app: "Terminal",
+		});
+		expect(formatCsvRecord(item)).toBe(
+			'7,text,2024-01-01 00:00:00,"hello, world",false,true,1,"code, work",,Terminal,,\r\n',
+		);
+	});
+});
+
+describe("exportHistory", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-export-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * Fake repository query over `count` items, recording each batch read.
+	 */
+	const createListItems = (count: number) => {
+		const rows = Array.from({ length: count }, (_, index) => row(index + 1));
+		const reads: number[] = [];
+		const listItems = (_filters: unknown, afterId: number, limit: number) => {
+			reads.push(afterId);
+			return rows.filter((entry) => entry.id > afterId).slice(0, limit);
+		};
+		return { listItems, reads };
+	};
+
+	it("streams every matching item to a JSON array in batches", async () => {
+		const filePath = path.join(createTempDir(), "history.json");
+		const { listItems, reads } = createListItems(1200);
+
+		const count = await exportHistory(listItems, {
+			format: "json",
+			filePath,
+			filters: {},
+		});
+
+		expect(count).toBe(1200);
+		expect(reads).toEqual([0, 500, 1000, 1200]);
+		const items = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		expect(items).toHaveLength(1200);
+		expect(items[0]).toMatchObject({ id: 1, content: "item 1", tags: [] });
+		expect(fs.existsSync(`${filePath}.partial`)).toBe(false);
+	});
+
+	it("writes an empty JSON array when nothing matches", async () => {
+		const filePath = path.join(createTempDir(), "history.json");
+		const { listItems } = createListItems(0);
+
+		await exportHistory(listItems, { format: "json", filePath, filters: {} });
+
+		expect(JSON.parse(fs.readFileSync(filePath, "utf-8"))).toEqual([]);
+	});
+
+	it("writes a CSV header and one record per item", async () => {
+		const filePath = path.join(createTempDir(), "history.csv");
+		const { listItems } = createListItems(2);
+
+		await exportHistory(listItems, { format: "csv", filePath, filters: {} });
+
+		const lines = fs.readFileSync(filePath, "utf-8").split("\r\n");
+		expect(lines[0]).toBe(
+			"\uFEFFid,type,created_at,content,favorite,pinned,use_count,tags,note,source_app,source_title,source_url",
+		);
+		expect(lines.slice(1)).toEqual([
+			"1,text,2024-01-01 00:00:00,item 1,false,false,1,,,,,",
+			"2,text,2024-01-01 00:00:00,item 2,false,false,1,,,,,",
+			"",
+		]);
+	});
+
+	it("leaves no file behind when reading history fails", async () => {
+		const filePath = path.join(createTempDir(), "history.json");
+		const listItems = () => {
+			throw new Error("database is locked");
+		};
+
+		await expect(
+			exportHistory(listItems, { format: "json", filePath, filters: {} }),
+		).rejects.toThrow("database is locked");
+		expect(fs.readdirSync(path.dirname(filePath))).toEqual([]);
+	});
+});
@@ -0,0 +1,244 @@
+import fs from "node:fs";
+import path from "node:path";
+import { Readable } from "node:stream";
+import { pipeline } from "node:stream/promises";
+import type {
+	ExportHistoryFilters,
+	ExportHistoryRow,
+} from "./history-repository.js";
+import { assertValidTagName } from "./tags.js";
+
+const HISTORY_EXPORT_FORMATS = ["json", "csv"] as const;
+
+export type HistoryExportFormat = (typeof HISTORY_EXPORT_FORMATS)[number];
+
+/**
+ * A validated export request.
+ */
+export type HistoryExportRequest = {
+	format: HistoryExportFormat;
+	/** Absolute path of the file to write */
+	filePath: string;
+	filters: ExportHistoryFilters;
+};
+
+/**
+ * One exported history item. Image bytes are not exported.
+ */
+export type ExportedItem = {
+	id: number;
+	type: string;
+	content: string;
+	created_at: string;
+	favorite: boolean;
+	pinned: boolean;
+	use_count: number;
+	tags: string[];
+	note: string | null;
+	source_app: string | null;
+	source_title: string | null;
+	source_url: string | null;
+	rtf: string | null;
+	html: string | null;
+};
+
+/**
+ * Reads matching items with an id above `afterId`, oldest first.
+ */
+export type ListExportItems = (
+	filters: ExportHistoryFilters,
+	afterId: number,
+	limit: number,
+) => ExportHistoryRow[];
+
+/**
+ * Items read and written per step, so history is never held in memory
+ * all at once.
+ */
+const EXPORT_BATCH_SIZE = 500;
+
+/**
+ * CSV columns, in order. Rich text and HTML are left to the JSON export.
+ */
+const CSV_COLUMNS = [
+	"id",
+	"type",
+	"created_at",
+	"content",
+	"favorite",
+	"pinned",
+	"use_count",
+	"tags",
+	"note",
+	"source_app",
+	"source_title",
+	"source_url",
+] as const;
+
+/**
+ * Byte order mark so spreadsheet apps read the CSV as UTF-8.
+ */
+const UTF8_BOM = "\uFEFF";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid history export: ${message}`);
+
+/**
+ * Validates an export request from the renderer.
+ * Pure function.
+ *
+ * @throws if the format, path or filters are invalid
+ */
+export const parseHistoryExportRequest = (
+	format: unknown,
+	filePath: unknown,
+	filters: unknown = {},
+): HistoryExportRequest => {
+	if (!HISTORY_EXPORT_FORMATS.includes(format as HistoryExportFormat)) {
+		throw invalid(`format must be ${HISTORY_EXPORT_FORMATS.join(" or ")}`);
+	}
+	if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+		throw invalid("path must be absolute");
+	}
+	if (typeof filters !== "object" || filters === null) {
+		throw invalid("filters must be an object");
+	}
+
+	const values = filters as Record<keyof ExportHistoryFilters, unknown>;
+	const parsed: ExportHistoryFilters = {};
+	for (const key of ["pinnedOnly", "favoritesOnly"] as const) {
+		const value = values[key];
+		if (value === undefined) continue;
+		if (typeof value !== "boolean") {
+			throw invalid(`${key} must be a boolean`);
+		}
+		parsed[key] = value;
+	}
+	if (values.tags !== undefined) {
+		if (!Array.isArray(values.tags)) {
+			throw invalid("tags must be an array");
+		}
+		parsed.tags = values.tags.map(assertValidTagName);
+	}
+	if (values.sourceApp !== undefined) {
+		if (typeof values.sourceApp !== "string") {
+			throw invalid("sourceApp must be a string");
+		}
+		parsed.sourceApp = values.sourceApp;
+	}
+
+	return { format: format as HistoryExportFormat, filePath, filters: parsed };
+};
+
+/**
+ * Converts a history row to its exported form.
+ * Pure function.
+ */
+export const toExportedItem = (row: ExportHistoryRow): ExportedItem => ({
+	id: row.id,
+	type: row.type,
+	content: row.content,
+	created_at: row.created_at,
+	favorite: row.is_favorite === 1,
+	pinned: row.pinned === 1,
+	use_count: row.use_count,
+	tags: row.tags,
+	note: row.note,
+	source_app: row.source_app,
+	source_title: row.source_title,
+	source_url: row.source_url,
+	rtf: row.rtf,
+	html: row.html,
+});
+
+/**
+ * Quotes a CSV field when it holds a comma, quote or line break (RFC 4180).
+ * Pure function.
+ */
+export const formatCsvField = (value: string): string =>
+	/[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+
+/**
+ * Formats an exported item as one CSV record, including its line break.
+ * Pure function.
+ */
+export const formatCsvRecord = (item: ExportedItem): string => {
+	const fields = CSV_COLUMNS.map((column) => {
+		const value = item[column];
+		if (value === null) return "";
+		if (Array.isArray(value)) return formatCsvField(value.join(", "));
+		return formatCsvField(String(value));
+	});
+	return `${fields.join(",")}\r\n`;
+};
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/**
+ * Yields an export's file contents a batch at a time.
+ */
+function* formatExport(
+	listItems: ListExportItems,
+	request: HistoryExportRequest,
+	onItems: (count: number) => void,
+): Generator<string> {
+	const isJson = request.format === "json";
+	yield isJson ? "[" : `${UTF8_BOM}${CSV_COLUMNS.join(",")}\r\n`;
+
+	let afterId = 0;
+	let first = true;
+	while (true) {
+		const rows = listItems(request.filters, afterId, EXPORT_BATCH_SIZE);
+		if (rows.length === 0) break;
+		const items = rows.map(toExportedItem);
+		if (isJson) {
+			yield items
+				.map((item) => `${first ? "" : ","}\n  ${JSON.stringify(item)}`)
+				.join("");
+		} else {
+			yield items.map(formatCsvRecord).join("");
+		}
+		first = false;
+		onItems(rows.length);
+		afterId = rows[rows.length - 1].id;
+	}
+
+	if (isJson) yield first ? "]\n" : "\n]\n";
+}
+
+/**
+ * Writes the matching history to a JSON array or a CSV file, streaming
+ * it in batches. The file is written next to the target and renamed into
+ * place once complete, so a failed export leaves no partial file. It is
+ * readable by the current user only, like the history itself.
+ *
+ * @returns number of items exported
+ */
+export const exportHistory = async (
+	listItems: ListExportItems,
+	request: HistoryExportRequest,
+): Promise<number> => {
+	const tempPath = `${request.filePath}.partial`;
+	let count = 0;
+	try {
+		await pipeline(
+			Readable.from(
+				formatExport(listItems, request, (added) => {
+					count += added;
+				}),
+			),
+			fs.createWriteStream(tempPath, { mode: 0o600 }),
+		);
+		fs.renameSync(tempPath, request.filePath);
+		return count;
+	} catch (error) {
+		fs.rmSync(tempPath, { force: true });
+		throw error;
+	}
+};
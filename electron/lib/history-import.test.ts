@@ -0,0 +1,149 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import type { ExportedItem } from "./history-export.js";
+import {
+	importHistory,
+	parseBackupItem,
+	parseHistoryBackup,
+} from "./history-import.js";
+import type { ImportHistoryItem } from "./history-repository.js";
+
+const exported = (content: string): ExportedItem => ({
+	id: 1,
+	type: "text",
+	content,
+	created_at: "2024-01-01 00:00:00",
+	favorite: false,
+	pinned: true,
+	use_count: 3,
+	tags: ["work"],
+	note: null,
+	source_app: "Terminal",
+	source_title: "zsh",
+	source_url: null,
+	rtf: null,
+	html: "<b>hi</b>",
+});
+
+describe("parseBackupItem", () => {
+	it("reads an exported item", () => {
+		expect(parseBackupItem(exported("hi"))).toEqual({
+			type: "text",
+			content: "hi",
+			created_at: "2024-01-01 00:00:00",
+			favorite: false,
+			pinned: true,
+			use_count: 3,
+			tags: ["work"],
+			note: null,
+			source: { name: "Terminal", title: "zsh", url: null },
+			rtf: null,
+			html: "<b>hi</b>",
+			image: null,
+		});
+	});
+
+	it("defaults optional fields", () => {
+		expect(
+			parseBackupItem({
+				type: "files",
+				content: "/tmp/a.txt",
+				created_at: "2024-01-01 00:00:00",
+				html: "ignored",
+			}),
+		).toMatchObject({
+			favorite: false,
+			use_count: 1,
+			tags: [],
+			source: null,
+			html: null,
+		});
+	});
+
+	it("skips image items and malformed entries", () => {
+		const cases: unknown[] = [
+			null,
+			{ ...exported("x"), type: "image" },
+			{ ...exported("x"), content: 1 },
+			{ ...exported("x"), created_at: "yesterday" },
+			{ ...exported("x"), pinned: "yes" },
+			{ ...exported("x"), use_count: 0 },
+			{ ...exported("x"), tags: [1] },
+			{ ...exported("x"), note: 5 },
+		];
+		for (const input of cases) {
+			expect(parseBackupItem(input)).toBeNull();
+		}
+	});
+});
+
+describe("parseHistoryBackup", () => {
+	it("requires a JSON array", () => {
+		expect(parseHistoryBackup("[1, 2]")).toEqual([1, 2]);
+		expect(() => parseHistoryBackup("[")).toThrow("expected JSON");
+		expect(() => parseHistoryBackup("{}")).toThrow("expected an array");
+	});
+});
+
+describe("importHistory", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const writeBackup = (contents: unknown): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-import-"));
+		tempDirs.push(dir);
+		const filePath = path.join(dir, "history.json");
+		fs.writeFileSync(filePath, JSON.stringify(contents));
+		return filePath;
+	};
+
+	/**
+	 * Fake repository that already holds `existing` and skips duplicates.
+	 */
+	const createImportItems = (existing: string[]) => {
+		const contents = new Set(existing);
+		const batches: number[] = [];
+		const importItems = (items: ImportHistoryItem[]) => {
+			batches.push(items.length);
+			let added = 0;
+			for (const item of items) {
+				if (contents.has(item.content)) continue;
+				contents.add(item.content);
+				added += 1;
+			}
+			return added;
+		};
+		return { importItems, batches };
+	};
+
+	it("imports new items in batches and skips the rest", async () => {
+		const entries = Array.from({ length: 1000 }, (_, index) =>
+			exported(`item ${index}`),
+		);
+		entries.push({ ...exported("image"), type: "image" });
+		const { importItems, batches } = createImportItems(["item 0", "item 1"]);
+
+		const report = await importHistory(importItems, writeBackup(entries));
+
+		expect(report).toEqual({ imported: 998, skipped: 3 });
+		expect(batches).toEqual([500, 500]);
+	});
+
+	it("rejects relative paths and files that are not backups", async () => {
+		const { importItems } = createImportItems([]);
+
+		await expect(importHistory(importItems, "history.json")).rejects.toThrow(
+			"path must be absolute",
+		);
+		await expect(importHistory(importItems, writeBackup({}))).rejects.toThrow(
+			"expected an array",
+		);
+	});
+});
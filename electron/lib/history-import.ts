@@ -0,0 +1,190 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { ImportHistoryItem } from "./history-repository.js";
+
+/**
+ * Outcome of an import.
+ */
+export type HistoryImportReport = {
+	/** Items added to history */
+	imported: number;
+	/** Duplicates of existing items, image items and malformed entries */
+	skipped: number;
+};
+
+/**
+ * Stores a batch of items; returns how many were stored.
+ */
+export type ImportItems = (items: ImportHistoryItem[]) => number;
+
+/**
+ * Largest file imported (bytes); the whole file is parsed in memory.
+ */
+const MAX_IMPORT_BYTES = 256 * 1024 * 1024;
+
+/**
+ * Items stored per transaction. The main process yields between
+ * batches so a large import does not freeze the app.
+ */
+const IMPORT_BATCH_SIZE = 500;
+
+const TIMESTAMP_PATTERN = /^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}$/;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid history backup: ${message}`);
+
+const isOptionalString = (value: unknown): value is string | null | undefined =>
+	value === undefined || value === null || typeof value === "string";
+
+/**
+ * Reads one entry of a JSON export (see `history-export.ts`).
+ * Pure function.
+ *
+ * @returns null for image items, which carry no image data, and for
+ *   malformed entries
+ */
+export const parseBackupItem = (input: unknown): ImportHistoryItem | null => {
+	if (typeof input !== "object" || input === null) return null;
+	const entry = input as Record<string, unknown>;
+
+	if (entry.type !== "text" && entry.type !== "files") return null;
+	if (typeof entry.content !== "string") return null;
+	if (
+		typeof entry.created_at !== "string" ||
+		!TIMESTAMP_PATTERN.test(entry.created_at)
+	) {
+		return null;
+	}
+	for (const key of ["favorite", "pinned"]) {
+		if (entry[key] !== undefined && typeof entry[key] !== "boolean") {
+			return null;
+		}
+	}
+	const useCount = entry.use_count ?? 1;
+	if (!Number.isInteger(useCount) || (useCount as number) < 1) return null;
+	const tags = entry.tags ?? [];
+	if (!Array.isArray(tags) || !tags.every((tag) => typeof tag === "string")) {
+		return null;
+	}
+	const strings = [
+		"note",
+		"source_app",
+		"source_title",
+		"source_url",
+		"rtf",
+		"html",
+	];
+	if (!strings.every((key) => isOptionalString(entry[key]))) return null;
+
+	const text = (key: string) => (entry[key] as string | null) ?? null;
+	const sourceApp = text("source_app");
+	return {
+		type: entry.type,
+		content: entry.content,
+		created_at: entry.created_at,
+		favorite: entry.favorite === true,
+		pinned: entry.pinned === true,
+		use_count: useCount as number,
+		tags,
+		note: text("note"),
+		source: sourceApp
+			? {
+					name: sourceApp,
+					title: text("source_title"),
+					url: text("source_url"),
+				}
+			: null,
+		rtf: entry.type === "text" ? text("rtf") : null,
+		html: entry.type === "text" ? text("html") : null,
+		image: null,
+	};
+};
+
+/**
+ * Formats a time as a history timestamp, `YYYY-MM-DD hh:mm:ss` UTC.
+ * Pure function.
+ */
+export const toHistoryTimestamp = (time: number): string =>
+	new Date(time).toISOString().slice(0, 19).replace("T", " ");
+
+/**
+ * Reads the entries of a JSON export.
+ * Pure function.
+ *
+ * @throws if the text is not a JSON array
+ */
+export const parseHistoryBackup = (text: string): unknown[] => {
+	let input: unknown;
+	try {
+		input = JSON.parse(text);
+	} catch {
+		throw invalid("expected JSON");
+	}
+	if (!Array.isArray(input)) {
+		throw invalid("expected an array of items");
+	}
+	return input;
+};
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/**
+ * Stores items in batches, yielding between them. Null entries (ones
+ * that could not be read) count as skipped.
+ */
+export const importInBatches = async <Entry>(
+	importItems: ImportItems,
+	entries: readonly Entry[],
+	toItem: (entry: Entry) => ImportHistoryItem | null,
+): Promise<HistoryImportReport> => {
+	let imported = 0;
+	for (let start = 0; start < entries.length; start += IMPORT_BATCH_SIZE) {
+		const items = entries
+			.slice(start, start + IMPORT_BATCH_SIZE)
+			.map(toItem)
+			.filter((item) => item !== null);
+		if (items.length > 0) imported += importItems(items);
+		await new Promise((resolve) => setImmediate(resolve));
+	}
+	return { imported, skipped: entries.length - imported };
+};
+
+/**
+ * Reads a file to import, checking its path and size.
+ *
+ * @throws if the path is not absolute or the file is too large
+ */
+export const readImportFile = async (filePath: unknown): Promise<Buffer> => {
+	if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+		throw new Error("Invalid import: path must be absolute");
+	}
+	const { size } = await fs.promises.stat(filePath);
+	if (size > MAX_IMPORT_BYTES) {
+		throw new Error(
+			`Invalid import: file is larger than ${MAX_IMPORT_BYTES / 1024 / 1024} MB`,
+		);
+	}
+	return fs.promises.readFile(filePath);
+};
+
+/**
+ * Imports a JSON export into history, in batches. Entries that cannot be
+ * imported are counted as skipped rather than failing the import.
+ *
+ * @throws if the path is not absolute, or the file is too large or not
+ *   a JSON export
+ */
+export const importHistory = async (
+	importItems: ImportItems,
+	filePath: unknown,
+): Promise<HistoryImportReport> => {
+	const data = await readImportFile(filePath);
+	const entries = parseHistoryBackup(data.toString("utf-8"));
+	return importInBatches(importItems, entries, parseBackupItem);
+};
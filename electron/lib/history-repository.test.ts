@@ -0,0 +1,635 @@
+import type Database from "better-sqlite3";
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import type { AttachmentStore } from "./attachment-store.js";
+import {
+	buildFtsMatchQuery,
+	buildHistoryQuery,
+	buildPageQuery,
+	buildPrunableCondition,
+	buildRankedPageQuery,
+	buildSearchQuery,
+	computeContentHash,
+	createHistoryRepository,
+	decodeCursor,
+	encodeCursor,
+	groupRowsByBucket,
+	isValidId,
+	isValidPaginationParams,
+	parseBatchRequest,
+	parseDuplicateMergeRequest,
+	parseItemExpiry,
+	parseMergeRequest,
+	toSearchResult,
+} from "./history-repository.js";
+import type { DateBucket, HistoryRow } from "./history-repository.js";
+import { openTestDatabase } from "./test-database.js";
+
+describe("computeContentHash", () => {
+	it("treats whitespace-only text differences as duplicates", () => {
+		expect(computeContentHash("text", "hello  world\n")).toBe(
+			computeContentHash("text", "hello world"),
+		);
+	});
+
+	it("distinguishes different content", () => {
+		expect(computeContentHash("text", "hello world")).not.toBe(
+			computeContentHash("text", "hello there"),
+		);
+	});
+
+	it("distinguishes item types with the same payload", () => {
+		expect(computeContentHash("files", "/tmp/a")).not.toBe(
+			computeContentHash("text", "/tmp/a"),
+		);
+	});
+
+	it("hashes image bytes exactly", () => {
+		const png = Buffer.from([1, 2, 3]);
+		expect(computeContentHash("image", png)).toBe(
+			computeContentHash("image", Buffer.from([1, 2, 3])),
+		);
+		expect(computeContentHash("image", png)).not.toBe(
+			computeContentHash("image", Buffer.from([1, 2, 4])),
+		);
+	});
+});
+
+describe("isValidId", () => {
+	it("accepts positive integers only", () => {
+		expect(isValidId(1)).toBe(true);
+		expect(isValidId(0)).toBe(false);
+		expect(isValidId(1.5)).toBe(false);
+		expect(isValidId("1")).toBe(false);
+	});
+});
+
+describe("parseMergeRequest", () => {
+	it("keeps the ids in the order given", () => {
+		expect(parseMergeRequest([3, 1, 2], ", ")).toEqual({
+			ids: [3, 1, 2],
+			separator: ", ",
+		});
+		expect(parseMergeRequest([1, 2], "").separator).toBe("");
+	});
+
+	it("rejects too few, repeated, or invalid ids", () => {
+		for (const ids of [[1], [1, 1], [1, 0], "1,2", null]) {
+			expect(() => parseMergeRequest(ids, "\n")).toThrow("Invalid merge");
+		}
+	});
+
+	it("rejects invalid separators", () => {
+		expect(() => parseMergeRequest([1, 2], null)).toThrow(
+			"Invalid merge separator",
+		);
+		expect(() => parseMergeRequest([1, 2], "x".repeat(101))).toThrow(
+			"Invalid merge separator",
+		);
+	});
+});
+
+describe("parseDuplicateMergeRequest", () => {
+	it("keeps the item to keep first", () => {
+		expect(parseDuplicateMergeRequest([7, 3, 5])).toEqual([7, 3, 5]);
+	});
+
+	it("rejects too few, repeated, or invalid ids", () => {
+		for (const ids of [[1], [2, 2], [1, -1], undefined]) {
+			expect(() => parseDuplicateMergeRequest(ids)).toThrow("Invalid merge");
+		}
+	});
+});
+
+describe("parseItemExpiry", () => {
+	const now = Date.parse("2026-10-14T12:00:00Z");
+
+	it("converts a future time to UTC as stored", () => {
+		expect(parseItemExpiry("2026-10-14T15:30:00+02:00", now)).toBe(
+			"2026-10-14 13:30:00",
+		);
+		expect(parseItemExpiry(null, now)).toBeNull();
+	});
+
+	it("rejects past and invalid times", () => {
+		for (const value of ["2026-10-14T11:59:59Z", "soon", 3600, undefined]) {
+			expect(() => parseItemExpiry(value, now)).toThrow("Invalid item expiry");
+		}
+	});
+});
+
+describe("parseBatchRequest", () => {
+	it("accepts each operation and normalizes tag names", () => {
+		expect(parseBatchRequest([2, 1], { type: "delete" })).toEqual({
+			ids: [2, 1],
+			operation: { type: "delete" },
+		});
+		expect(
+			parseBatchRequest([1], { type: "pin", pinned: false }).operation,
+		).toEqual({ type: "pin", pinned: false });
+		expect(
+			parseBatchRequest([1], { type: "tag", name: " Work " }).operation,
+		).toEqual({ type: "tag", name: "work" });
+		expect(
+			parseBatchRequest([1], { type: "move", collectionId: 3 }).operation,
+		).toEqual({ type: "move", collectionId: 3, fromCollectionId: undefined });
+	});
+
+	it("rejects empty, repeated, or invalid ids", () => {
+		for (const ids of [[], [1, 1], [0], "1,2", null]) {
+			expect(() => parseBatchRequest(ids, { type: "delete" })).toThrow(
+				"Invalid batch",
+			);
+		}
+	});
+
+	it("rejects unknown or incomplete operations", () => {
+		const operations = [
+			null,
+			{ type: "archive" },
+			{ type: "pin" },
+			{ type: "move", collectionId: 0 },
+			{ type: "move", collectionId: 1, fromCollectionId: "2" },
+		];
+		for (const operation of operations) {
+			expect(() => parseBatchRequest([1], operation)).toThrow(
+				"Invalid batch operation",
+			);
+		}
+		expect(() => parseBatchRequest([1], { type: "tag", name: "" })).toThrow(
+			"Invalid tag name",
+		);
+	});
+});
+
+describe("isValidPaginationParams", () => {
+	it("accepts omitted or non-negative integer values", () => {
+		expect(isValidPaginationParams(undefined, undefined)).toBe(true);
+		expect(isValidPaginationParams(10, 0)).toBe(true);
+	});
+
+	it("rejects zero limits and negative offsets", () => {
+		expect(isValidPaginationParams(0, 0)).toBe(false);
+		expect(isValidPaginationParams(10, -1)).toBe(false);
+	});
+});
+
+describe("buildHistoryQuery", () => {
+	it("orders pinned items first, then newest first", () => {
+		const { sql, params } = buildHistoryQuery({});
+
+		expect(sql).toContain(
+			"ORDER BY pinned DESC, created_at DESC LIMIT ? OFFSET ?",
+		);
+		expect(sql).not.toContain("WHERE");
+		expect(params).toEqual([50, 0]);
+	});
+
+	it("combines search and favorites filters", () => {
+		const { sql, params } = buildHistoryQuery({
+			query: "foo",
+			favoritesOnly: true,
+			limit: 20,
+			offset: 40,
+		});
+
+		expect(sql).toContain(
+			"WHERE (content LIKE ? OR note LIKE ? OR ocr_text LIKE ?) AND is_favorite = 1",
+		);
+		expect(params).toEqual(["%foo%", "%foo%", "%foo%", 20, 40]);
+	});
+
+	it("requires every tag in the tag filter", () => {
+		const { sql, params } = buildHistoryQuery({ tags: ["Work", "sql"] });
+
+		expect(sql.match(/id IN \(SELECT ht\.history_id/g)).toHaveLength(2);
+		expect(params).toEqual(["work", "sql", 50, 0]);
+	});
+
+	it("filters by source app regardless of case", () => {
+		const { sql, params } = buildHistoryQuery({ sourceApp: "Slack" });
+
+		expect(sql).toContain("WHERE source_app = ? COLLATE NOCASE");
+		expect(params).toEqual(["Slack", 50, 0]);
+	});
+
+	it("clamps the limit to the allowed range", () => {
+		expect(buildHistoryQuery({ limit: 10_000 }).params).toEqual([200, 0]);
+		expect(buildHistoryQuery({ limit: 2.7 }).params).toEqual([2, 0]);
+	});
+
+	it("orders by frecency when asked", () => {
+		const { sql, params } = buildHistoryQuery({ order: "frecency" });
+
+		expect(sql).toContain(
+			"ORDER BY pinned DESC, use_count / (1.0 + MAX(julianday('now') - julianday(created_at), 0) / 3) DESC, created_at DESC",
+		);
+		expect(params).toEqual([50, 0]);
+	});
+
+	it("orders by paste count for most used", () => {
+		expect(buildHistoryQuery({ order: "usage" }).sql).toContain(
+			"ORDER BY pinned DESC, paste_count DESC, created_at DESC",
+		);
+	});
+});
+
+describe("buildFtsMatchQuery", () => {
+	it("prefix-matches every term", () => {
+		expect(buildFtsMatchQuery("  hello wor ")).toBe('"hello"* "wor"*');
+	});
+
+	it("treats FTS5 syntax in the input literally", () => {
+		expect(buildFtsMatchQuery('say "hi" OR NEAR(x')).toBe(
+			'"say"* """hi"""* "OR"* "NEAR(x"*',
+		);
+	});
+
+	it("returns null when there is nothing to match", () => {
+		expect(buildFtsMatchQuery("")).toBeNull();
+		expect(buildFtsMatchQuery("# -- !")).toBeNull();
+	});
+});
+
+describe("buildSearchQuery", () => {
+	it("ranks matches and applies filters and pagination", () => {
+		const search = buildSearchQuery({
+			query: "foo",
+			favoritesOnly: true,
+			limit: 20,
+			offset: 40,
+		});
+
+		expect(search?.sql).toContain("WHERE history_fts MATCH ?");
+		expect(search?.sql).toContain("AND h.is_favorite = 1");
+		expect(search?.sql).toContain("ORDER BY h.pinned DESC, history_fts.rank");
+		expect(search?.sql).toContain("highlight(history_fts, 0,");
+		expect(search?.params).toEqual(['"foo"*', 20, 40]);
+	});
+
+	it("qualifies tag filters with the joined table alias", () => {
+		const search = buildSearchQuery({ query: "foo", tags: ["sql"] });
+
+		expect(search?.sql).toContain("AND h.id IN (SELECT ht.history_id");
+		expect(search?.params).toEqual(['"foo"*', "sql", 50, 0]);
+	});
+
+	it("falls back when the query has no searchable terms", () => {
+		expect(buildSearchQuery({ query: "%%" })).toBeNull();
+	});
+});
+
+describe("history cursors", () => {
+	it("round-trips a keyset position", () => {
+		const cursor = {
+			pinned: true,
+			createdAt: "2026-01-11 15:42:10",
+			id: 42,
+		};
+		expect(decodeCursor(encodeCursor(cursor))).toEqual(cursor);
+	});
+
+	it("round-trips a ranked position", () => {
+		const cursor = {
+			pinned: false,
+			createdAt: "2026-01-11 15:42:10",
+			id: 42,
+			order: "frecency" as const,
+			score: 0.1 + 0.2,
+			scoredAt: "2026-01-11T15:42:10.000Z",
+		};
+		expect(decodeCursor(encodeCursor(cursor))).toEqual(cursor);
+	});
+
+	it("rejects malformed cursors", () => {
+		expect(decodeCursor("not a cursor")).toBeNull();
+		expect(
+			decodeCursor(Buffer.from('[0, "x", -1]').toString("base64url")),
+		).toBeNull();
+		expect(
+			decodeCursor(
+				Buffer.from('[0, "x", 1, "usage", 2, "soon"]').toString("base64url"),
+			),
+		).toBeNull();
+	});
+});
+
+describe("buildPageQuery", () => {
+	it("fetches one extra row to detect the next page", () => {
+		const { sql, params } = buildPageQuery({ cursor: null, pageSize: 25 });
+
+		expect(sql).toContain(
+			"ORDER BY pinned DESC, created_at DESC, id DESC LIMIT ?",
+		);
+		expect(sql).not.toContain("WHERE");
+		expect(params).toEqual([26]);
+	});
+
+	it("continues after the cursor position", () => {
+		const { sql, params } = buildPageQuery({
+			cursor: { pinned: true, createdAt: "2026-01-11 15:42:10", id: 42 },
+			favoritesOnly: true,
+		});
+
+		expect(sql).toContain(
+			"WHERE (pinned < ? OR (pinned = ? AND (created_at < ? OR (created_at = ? AND id < ?)))) AND is_favorite = 1",
+		);
+		expect(params).toEqual([
+			1,
+			1,
+			"2026-01-11 15:42:10",
+			"2026-01-11 15:42:10",
+			42,
+			51,
+		]);
+	});
+
+	it("adds each row's date bucket when grouping", () => {
+		const { sql } = buildPageQuery({ cursor: null, withDateBucket: true });
+
+		expect(sql).toContain("CASE WHEN pinned = 1 THEN 'pinned'");
+		expect(sql).toContain("'thisWeek' ELSE 'older' END AS bucket");
+	});
+});
+
+describe("groupRowsByBucket", () => {
+	it("groups consecutive rows of a bucket in order", () => {
+		const row = (id: number, bucket: DateBucket) =>
+			({ id, bucket }) as HistoryRow & { bucket: DateBucket };
+
+		const groups = groupRowsByBucket([
+			row(1, "pinned"),
+			row(2, "today"),
+			row(3, "today"),
+			row(4, "older"),
+		]);
+
+		expect(groups.map((group) => group.bucket)).toEqual([
+			"pinned",
+			"today",
+			"older",
+		]);
+		expect(groups[1].items.map((item) => item.id)).toEqual([2, 3]);
+		expect(groups[1].items[0]).not.toHaveProperty("bucket");
+	});
+});
+
+describe("buildRankedPageQuery", () => {
+	const scoredAt = "2026-01-11T15:42:10.000Z";
+
+	it("scores every frecency page as of the same time", () => {
+		const { sql, params } = buildRankedPageQuery({
+			order: "frecency",
+			cursor: {
+				pinned: false,
+				createdAt: "2026-01-11 15:42:10",
+				id: 42,
+				order: "frecency",
+				score: 2.5,
+				scoredAt,
+			},
+			scoredAt,
+			sourceApp: "Slack",
+			pageSize: 25,
+		});
+
+		expect(sql).toContain(
+			"AS score FROM history WHERE source_app = ? COLLATE NOCASE)",
+		);
+		expect(sql).toContain(
+			"WHERE (pinned < ? OR (pinned = ? AND (score < ? OR (score = ? AND (created_at < ? OR (created_at = ? AND id < ?)))))) ORDER BY pinned DESC, score DESC, created_at DESC, id DESC LIMIT ?",
+		);
+		expect(params).toEqual([
+			scoredAt,
+			"Slack",
+			0,
+			0,
+			2.5,
+			2.5,
+			"2026-01-11 15:42:10",
+			"2026-01-11 15:42:10",
+			42,
+			26,
+		]);
+	});
+
+	it("ranks by paste count for most used", () => {
+		const { sql, params } = buildRankedPageQuery({
+			order: "usage",
+			cursor: null,
+			scoredAt,
+		});
+
+		expect(sql).toContain("paste_count AS score FROM history)");
+		expect(params).toEqual([51]);
+	});
+});
+
+describe("buildPrunableCondition", () => {
+	it("never matches pinned items or items in a collection", () => {
+		const { sql, params } = buildPrunableCondition(null);
+
+		expect(sql).toBe(
+			"pinned = 0 AND id NOT IN (SELECT history_id FROM collection_items)",
+		);
+		expect(params).toEqual([]);
+	});
+
+	it("spares often pasted items when asked", () => {
+		const { sql, params } = buildPrunableCondition(5);
+
+		expect(sql).toContain("pinned = 0 AND id NOT IN");
+		expect(sql).toMatch(/AND paste_count < \?$/);
+		expect(params).toEqual([5]);
+	});
+});
+
+describe("toSearchResult", () => {
+	it("gives masked secrets no match ranges", () => {
+		const row = (secret: string | null) =>
+			({ id: 1, secret_match: secret }) as HistoryRow;
+
+		expect(toSearchResult(row(null), [[0, 4]]).match_ranges).toEqual([
+			[0, 4],
+		]);
+		expect(toSearchResult(row("AWS key"), [[0, 4]]).match_ranges).toEqual([]);
+	});
+});
+
+describe("createHistoryRepository", () => {
+	let db: Database.Database;
+
+	beforeEach(() => {
+		db = openTestDatabase();
+	});
+
+	afterEach(() => {
+		db.close();
+	});
+
+	const setup = () =>
+		createHistoryRepository(() => db, {
+			sweep: () => 0,
+		} as Partial<AttachmentStore> as AttachmentStore);
+
+	/**
+	 * Adds text items, then pins, favorites and collects the ones named so.
+	 */
+	const addItems = (texts: string[]) => {
+		const repository = setup();
+		for (const text of texts) repository.addItem({ text });
+		db.exec("UPDATE history SET pinned = 1 WHERE content = 'pinned'");
+		db.exec("UPDATE history SET is_favorite = 1 WHERE content = 'favorite'");
+		db.exec("INSERT INTO collections (name) VALUES ('Snippets')");
+		db.exec(
+			"INSERT INTO collection_items (collection_id, history_id, position) SELECT 1, id, 0 FROM history WHERE content = 'collected'",
+		);
+		return repository;
+	};
+
+	const remaining = () =>
+		db.prepare("SELECT content FROM history ORDER BY id").pluck().all();
+
+	const getExpiry = (id: number) =>
+		db.prepare("SELECT expires_at FROM history WHERE id = ?").pluck().get(id);
+
+	it("returns the id of the row written or moved up", () => {
+		const repository = setup();
+		const first = repository.addItem({ text: "first" });
+		const second = repository.addItem({ text: "second" });
+		expect(second).toBe(repository.getLastItemId());
+
+		// "second" keeps sorting first after the bump, as a tie can
+		db.exec("UPDATE history SET created_at = '2099-01-01 00:00:00'");
+		expect(repository.addItem({ text: "first" })).toBe(first);
+		expect(repository.addItem({ text: " " })).toBeNull();
+	});
+
+	it("keeps an item's expiry when the same text is copied again", () => {
+		const repository = setup();
+		repository.addItem({ text: "hello" });
+		const id = repository.getLastItemId();
+		repository.setItemExpiry(id, "2099-01-01T00:00:00Z");
+
+		repository.addItem({ text: "hello" });
+
+		expect(repository.getLastItemId()).toBe(id);
+		expect(getExpiry(id)).toBe("2099-01-01 00:00:00");
+	});
+
+	it("brings an expiry forward when the copy is a flagged secret", () => {
+		const repository = setup();
+		repository.addItem({ text: "hello" });
+		const id = repository.getLastItemId();
+		repository.setItemExpiry(id, "2099-01-01T00:00:00Z");
+
+		repository.addItem({
+			text: "hello",
+			secret: { rule: "AWS access key", expireAfterMinutes: 5 },
+		});
+
+		const expiresSoon = db
+			.prepare(
+				"SELECT expires_at <= datetime('now', '+5 minutes') FROM history WHERE id = ?",
+			)
+			.pluck()
+			.get(id);
+		expect(expiresSoon).toBe(1);
+	});
+
+	it("expires pinned and collected items too", () => {
+		const repository = addItems(["plain", "pinned", "collected", "later"]);
+		db.exec("UPDATE history SET expires_at = datetime('now', '-1 minute')");
+		db.exec(
+			"UPDATE history SET expires_at = datetime('now', '+1 hour') WHERE content = 'later'",
+		);
+
+		expect(repository.expireItems()).toEqual(
+			["plain", "pinned", "collected"].map((text) =>
+				computeContentHash("text", text),
+			),
+		);
+		expect(remaining()).toEqual(["later"]);
+	});
+
+	it("prunes old items but spares pinned and collected ones", () => {
+		const repository = addItems([
+			"plain",
+			"pinned",
+			"favorite",
+			"collected",
+			"pasted",
+			"new",
+		]);
+		db.exec(
+			"UPDATE history SET created_at = datetime('now', '-30 days') WHERE content != 'new'",
+		);
+		db.exec("UPDATE history SET paste_count = 5 WHERE content = 'pasted'");
+
+		const deleted = repository.pruneItems({
+			maxItems: null,
+			maxAgeDays: 7,
+			maxDatabaseMb: null,
+			trashDays: null,
+			keepPastedAtLeast: 5,
+		});
+
+		// Favorites are not exempt
+		expect(deleted).toBe(2);
+		expect(remaining()).toEqual(["pinned", "collected", "pasted", "new"]);
+	});
+
+	it("does not count kept items toward the item limit", () => {
+		const repository = addItems(["first", "pinned", "collected", "second"]);
+
+		const deleted = repository.pruneItems({
+			maxItems: 1,
+			maxAgeDays: null,
+			maxDatabaseMb: null,
+			trashDays: null,
+			keepPastedAtLeast: null,
+		});
+
+		expect(deleted).toBe(1);
+		expect(remaining()).toEqual(["pinned", "collected", "second"]);
+	});
+
+	it("syncs a restored item to a device that applied its deletion", () => {
+		// Copied an hour ago and deleted a minute ago
+		const repository = addItems(["hello"]);
+		db.exec("UPDATE history SET created_at = datetime('now', '-1 hour')");
+		repository.deleteItem(repository.getLastItemId());
+		db.exec(
+			"UPDATE sync_tombstones SET deleted_at = datetime('now', '-1 minute')",
+		);
+		const tombstones = repository.listSyncTombstones(0, 10);
+		repository.restoreFromTrash(repository.listTrash()[0].id);
+
+		const peerDb = openTestDatabase();
+		const peer = createHistoryRepository(() => peerDb, {} as AttachmentStore);
+		peer.applySyncTombstones(tombstones, "desktop");
+		const added = peer.addSyncedItems(
+			repository.listSyncItems(0, 10),
+			"desktop",
+		);
+		peerDb.close();
+
+		expect(added).toBe(1);
+	});
+
+	it("leaves sync tombstones for the items history is cleared of", () => {
+		const repository = addItems(["first", "second", "private"]);
+		db.exec("UPDATE history SET sync_excluded = 1 WHERE content = 'private'");
+
+		repository.clearAll();
+
+		expect(remaining()).toEqual([]);
+		expect(repository.listTrash()).toHaveLength(3);
+		const hashes = repository.listSyncTombstones(0, 10).map(({ hash }) => hash);
+		expect(hashes.sort()).toEqual(
+			[
+				computeContentHash("text", "first"),
+				computeContentHash("text", "second"),
+			].sort(),
+		);
+	});
+});
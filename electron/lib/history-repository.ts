@@ -0,0 +1,3341 @@
+import { createHash } from "node:crypto";
+import fs from "node:fs";
+import Database from "better-sqlite3";
+import type { AttachmentStore } from "./attachment-store.js";
+import {
+	type CodeLanguage,
+	detectCodeLanguage,
+	isCodeLanguage,
+} from "./code-language.js";
+import { normalizeColor } from "./colors.js";
+import {
+	type ContentKind,
+	classifyContent,
+	isContentKind,
+} from "./content-kind.js";
+import { formatStoredFileList } from "./file-lists.js";
+import { type FuzzyMatch, matchFuzzy, parseFuzzyQuery } from "./fuzzy.js";
+import { stripPngMetadata } from "./images.js";
+import type { LinkCandidate, LinkPreview } from "./link-previews.js";
+import {
+	findSubstringRanges,
+	type MatchRange,
+	parseHighlightRanges,
+} from "./match-ranges.js";
+import { createRegexRangeMatcher } from "./regex-search.js";
+import type { ItemSummary, SummaryCandidate } from "./item-summaries.js";
+import type { SecretFinding } from "./secret-scanning.js";
+import {
+	decodeVector,
+	type EmbeddingCandidate,
+	encodeVector,
+	type StoredEmbedding,
+} from "./semantic-search.js";
+import { computeTextSimhash } from "./simhash.js";
+import type { SourceApp } from "./foreground-app.js";
+import type { ImageCandidate } from "./ocr.js";
+import { groupNearDuplicates } from "./perceptual-hash.js";
+import type { RetentionPolicy } from "./retention.js";
+import type { SyncItem, SyncTombstone } from "./sync-protocol.js";
+import { assertValidTagName, normalizeTagName } from "./tags.js";
+import {
+	COMPRESS_MIN_CHARS,
+	compressText,
+	fullTextExpression,
+} from "./text-compression.js";
+import { computeTextStats } from "./text-stats.js";
+import {
+	type ClipboardFormat,
+	packFormats,
+	unpackFormats,
+} from "./windows-formats.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Represents a row from the history table.
+ * Used for explicit typing of database query results.
+ */
+export type HistoryRow = {
+	id: number;
+	content: string;
+	type: string;
+	created_at: string;
+	is_favorite: number;
+	/** 1 if pinned above the rest of history */
+	pinned: number;
+	rtf: string | null;
+	html: string | null;
+	image_width: number | null;
+	image_height: number | null;
+	/** Number of times this content has been copied */
+	use_count: number;
+	/** Number of times the item was restored, pasted or typed out */
+	paste_count: number;
+	/** When the item was last restored, pasted or typed out */
+	last_used_at: string | null;
+	/** User annotation, searched along with content */
+	note: string | null;
+	/** App the newest copy was made in */
+	source_app: string | null;
+	/** Window title of the source app at copy time */
+	source_title: string | null;
+	/** Page URL when copied from a supported browser */
+	source_url: string | null;
+	/** 1 if LAN sync keeps this item on this device */
+	sync_excluded: number;
+	/** Original size in bytes of text stored compressed; null otherwise */
+	content_size: number | null;
+	/**
+	 * Text of a QR code in an image item; empty if it has none, null
+	 * until scanned
+	 */
+	qr_payload: string | null;
+	/** What a text item holds, e.g. `url` or `json`; null for other types */
+	content_kind: ContentKind | null;
+	/** Programming language guessed for a code item; null if unknown */
+	code_language: CodeLanguage | null;
+	/** Lowercase hex form of a color item (`#rrggbb` or `#rrggbbaa`) */
+	color_value: string | null;
+	/** Secret scanning rule a text capture matched; null if none */
+	secret_match: string | null;
+	/** When the item is deleted (UTC); null keeps it */
+	expires_at: string | null;
+	/**
+	 * Title of the page a URL item links to; empty if none was found, null
+	 * until fetched
+	 */
+	link_title: string | null;
+	link_description: string | null;
+	/** Preview image URL of the linked page */
+	link_image: string | null;
+	/**
+	 * Short title a language model wrote for a long text item; empty if
+	 * it gave none, null until summarized
+	 */
+	summary_title: string | null;
+	summary_text: string | null;
+	/** UTF-8 size of a text item, or PNG size of an image item */
+	byte_size: number | null;
+	/** Character, word and line counts of a text item */
+	char_count: number | null;
+	word_count: number | null;
+	line_count: number | null;
+	/**
+	 * 1 if captured from the PRIMARY selection (Linux) and not copied to
+	 * the clipboard since
+	 */
+	is_primary: number;
+	/** 1 if the item is deleted the first time it is used */
+	paste_once: number;
+};
+
+/**
+ * Encoded image stored with an image history item.
+ */
+export type StoredImage = {
+	png: Buffer;
+	width: number;
+	height: number;
+};
+
+/**
+ * The image of an image item before it is read, so large images can be
+ * written to a file for a thumbnailer instead of being held in memory.
+ */
+export type ImageSource = {
+	width: number;
+	height: number;
+	/** Encoded size (bytes) */
+	size: number;
+	/** Reads the PNG into memory */
+	read: () => Buffer | undefined;
+	/**
+	 * Writes the PNG to a file, chunk by chunk
+	 * @returns false if the image data is missing
+	 */
+	writeTo: (filePath: string) => boolean;
+};
+
+/**
+ * Data required to insert a new history item.
+ * Copied files are stored as `files` items (paths in `content`), and items
+ * with an image and no text as `image` items.
+ */
+export type NewHistoryItem = {
+	text: string;
+	rtf?: string;
+	html?: string;
+	image?: StoredImage;
+	files?: string[];
+	/** Where the copy was made, if it could be resolved */
+	source?: SourceApp | null;
+	/** Secret the text matched, flagging the item */
+	secret?: SecretFinding | null;
+	/**
+	 * The complete text when `text` is a preview of a copy over the size
+	 * limit; kept in the attachment store and restored on copy
+	 */
+	fullText?: string;
+	/** Text selected rather than copied, from the PRIMARY selection */
+	primary?: boolean;
+	/** Registered Windows formats kept raw, written back on restore */
+	formats?: ClipboardFormat[];
+};
+
+/**
+ * How history is ordered after pinned items: `recent` is newest first,
+ * `frecency` ranks items copied or pasted often and lately first, and
+ * `usage` the items pasted most often first.
+ */
+export type HistoryOrder = "recent" | "frecency" | "usage";
+
+/**
+ * Orders that rank items by a score, then newest first.
+ */
+type RankedOrder = Exclude<HistoryOrder, "recent">;
+
+/**
+ * Where a text item was captured from: the clipboard, or the PRIMARY
+ * selection Linux keeps for selected text (middle-click paste).
+ */
+export type SelectionSource = "clipboard" | "primary";
+
+/**
+ * Filters and pagination accepted by history list queries.
+ */
+export type ListHistoryOptions = {
+	query?: string;
+	limit?: number;
+	/** Defaults to `recent` */
+	order?: HistoryOrder;
+	favoritesOnly?: boolean;
+	/** Only items carrying every one of these tags */
+	tags?: string[];
+	/** Only items copied from this app (case-insensitive) */
+	sourceApp?: string;
+	/** Only text items of this kind */
+	kind?: ContentKind;
+	/** Only code items in this language */
+	language?: CodeLanguage;
+	/** Only items from this selection; both when omitted */
+	selection?: SelectionSource;
+	offset?: number;
+};
+
+/**
+ * Keyset pagination request for browsing history, newest first unless
+ * another order is asked for.
+ */
+export type ListPageOptions = {
+	/**
+	 * Opaque cursor from a previous page; omit for the first page. Pages
+	 * after the first must keep the order the cursor was made with.
+	 */
+	cursor?: string | null;
+	pageSize?: number;
+	/** Defaults to `recent` */
+	order?: HistoryOrder;
+	favoritesOnly?: boolean;
+	/** Only items carrying every one of these tags */
+	tags?: string[];
+	/** Only items copied from this app (case-insensitive) */
+	sourceApp?: string;
+	/** Only text items of this kind */
+	kind?: ContentKind;
+	/** Only code items in this language */
+	language?: CodeLanguage;
+	/** Only items from this selection; both when omitted */
+	selection?: SelectionSource;
+};
+
+/**
+ * A page of history items with the cursor for the next page.
+ */
+export type HistoryPage = {
+	items: HistoryRow[];
+	/** Cursor for the following page, or null if this is the last page */
+	nextCursor: string | null;
+};
+
+/**
+ * Where an item falls in a date-grouped list, by its local copy date:
+ * pinned items come first, then today, yesterday, earlier this week
+ * (from Monday) and older.
+ */
+export type DateBucket =
+	| "pinned"
+	| "today"
+	| "yesterday"
+	| "thisWeek"
+	| "older";
+
+/**
+ * Consecutive items of one date bucket.
+ */
+export type HistoryGroup = {
+	bucket: DateBucket;
+	items: HistoryRow[];
+};
+
+/**
+ * A page of history grouped into date buckets. A bucket cut off by the
+ * page size continues as the first group of the following page.
+ */
+export type GroupedHistoryPage = {
+	groups: HistoryGroup[];
+	nextCursor: string | null;
+};
+
+/**
+ * Position in the `pinned DESC, created_at DESC, id DESC` ordering.
+ */
+export type HistoryCursor = {
+	pinned: boolean;
+	createdAt: string;
+	id: number;
+};
+
+/**
+ * Position in a ranked `pinned DESC, score DESC, created_at DESC, id DESC`
+ * ordering, the score being the frecency or paste count. Frecency pages
+ * are scored as of `scoredAt`, when the first page was read, so items
+ * don't move between pages as their scores decay.
+ */
+export type RankedCursor = HistoryCursor & {
+	order: RankedOrder;
+	score: number;
+	scoredAt: string;
+};
+
+/**
+ * An app items were copied from, with how many items came from it.
+ */
+export type SourceAppCount = {
+	name: string;
+	count: number;
+};
+
+/**
+ * Full-text search request. Results are ordered by relevance.
+ */
+export type SearchHistoryOptions = Omit<
+	ListHistoryOptions,
+	"query" | "order"
+> & {
+	query: string;
+};
+
+/**
+ * A search result with where the query matched its content.
+ */
+export type SearchResultRow = HistoryRow & { match_ranges: MatchRange[] };
+
+/**
+ * Which items a history export includes.
+ */
+export type ExportHistoryFilters = Pick<
+	ListHistoryOptions,
+	"favoritesOnly" | "tags" | "sourceApp"
+> & {
+	pinnedOnly?: boolean;
+};
+
+/**
+ * A history row with the names of its tags, as exported.
+ */
+export type ExportHistoryRow = HistoryRow & { tags: string[] };
+
+/**
+ * Export query row; tag names are a JSON array.
+ */
+type ExportQueryRow = HistoryRow & { tag_names: string };
+
+/**
+ * Full-text search row; `highlighted` is its content with matches marked.
+ */
+type HighlightedRow = HistoryRow & { highlighted: string | null };
+
+/**
+ * Change applied to every item of a batch update. `move` appends the
+ * items to a collection, and removes them from `fromCollectionId` if set.
+ */
+export type BatchOperation =
+	| { type: "delete" }
+	| { type: "pin"; pinned: boolean }
+	| { type: "tag"; name: string }
+	| { type: "move"; collectionId: number; fromCollectionId?: number };
+
+/**
+ * A deleted item in the trash, with the names of its tags. `id` is the
+ * trash entry's; a restored item gets a new history id.
+ */
+export type TrashRow = HistoryRow & { deleted_at: string; tags: string[] };
+
+/**
+ * A history item read from a backup.
+ */
+export type ImportHistoryItem = {
+	type: "text" | "files" | "image";
+	/** Empty for images */
+	content: string;
+	/** `YYYY-MM-DD hh:mm:ss` UTC; later than now is clamped to now */
+	created_at: string;
+	favorite: boolean;
+	pinned: boolean;
+	use_count: number;
+	tags: string[];
+	note: string | null;
+	source: SourceApp | null;
+	rtf: string | null;
+	html: string | null;
+	/** Encoded image of `image` items */
+	image: StoredImage | null;
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Normalizes whitespace in text for near-duplicate detection.
+ * Pure function - no side effects.
+ *
+ * @param text - Input text to normalize
+ * @returns Text with trimmed edges and collapsed internal whitespace
+ */
+export const normalizeWhitespace = (text: string): string =>
+	text.trim().replace(/\s+/g, " ");
+
+/**
+ * Computes the deduplication hash of a history item's content.
+ * Pure function.
+ *
+ * Text is hashed after whitespace normalization, so copies that differ only
+ * in whitespace collapse into one entry. The item type is part of the hash,
+ * so a file list never matches text with the same paths.
+ *
+ * @param type - History item type (`text`, `image`, `files`)
+ * @param payload - Text content, stored file list, or encoded image bytes
+ * @returns Hex-encoded SHA-256 digest
+ */
+export const computeContentHash = (
+	type: string,
+	payload: string | Buffer,
+): string =>
+	createHash("sha256")
+		.update(type)
+		.update("\0")
+		.update(type === "text" ? normalizeWhitespace(String(payload)) : payload)
+		.digest("hex");
+
+/**
+ * Checks if text is empty or whitespace-only.
+ * Pure function.
+ */
+export const isEmptyText = (text: string | null | undefined): boolean =>
+	!text || text.trim().length === 0;
+
+/**
+ * Maximum allowed clipboard content characters.
+ * Prevents storing excessively large content.
+ */
+export const MAX_CLIP_CHARS = 1_000_000;
+
+/**
+ * How long sync remembers a deletion. A device offline for longer may
+ * bring the deleted item back.
+ */
+const SYNC_TOMBSTONE_MAX_AGE_DAYS = 90;
+
+/**
+ * Validates that an id is a positive integer.
+ * Pure function.
+ */
+export const isValidId = (id: unknown): id is number =>
+	typeof id === "number" && Number.isInteger(id) && id > 0;
+
+/**
+ * Most items a single merge can combine, and the longest joiner accepted.
+ */
+const MAX_MERGE_ITEMS = 100;
+const MAX_MERGE_SEPARATOR_LENGTH = 100;
+
+/**
+ * Validates a merge request: 2-100 distinct item ids, in the order they
+ * are joined, and a separator of at most 100 characters.
+ * Pure function.
+ *
+ * @throws if the ids or separator are invalid
+ */
+export const parseMergeRequest = (
+	ids: unknown,
+	separator: unknown,
+): { ids: number[]; separator: string } => {
+	if (
+		!Array.isArray(ids) ||
+		ids.length < 2 ||
+		ids.length > MAX_MERGE_ITEMS ||
+		!ids.every(isValidId) ||
+		new Set(ids).size !== ids.length
+	) {
+		throw new Error(
+			`Invalid merge: expected 2-${MAX_MERGE_ITEMS} distinct item ids`,
+		);
+	}
+	if (
+		typeof separator !== "string" ||
+		separator.length > MAX_MERGE_SEPARATOR_LENGTH
+	) {
+		throw new Error(
+			`Invalid merge separator: expected at most ${MAX_MERGE_SEPARATOR_LENGTH} characters`,
+		);
+	}
+	return { ids, separator };
+};
+
+/**
+ * Validates a duplicate merge: 2-100 distinct item ids, the one to keep
+ * first.
+ * Pure function.
+ *
+ * @throws if the ids are invalid
+ */
+export const parseDuplicateMergeRequest = (ids: unknown): number[] => {
+	if (
+		!Array.isArray(ids) ||
+		ids.length < 2 ||
+		ids.length > MAX_MERGE_ITEMS ||
+		!ids.every(isValidId) ||
+		new Set(ids).size !== ids.length
+	) {
+		throw new Error(
+			`Invalid merge: expected 2-${MAX_MERGE_ITEMS} distinct item ids`,
+		);
+	}
+	return ids;
+};
+
+/**
+ * Validates the time an item is to be deleted at.
+ * Pure function.
+ *
+ * @param value - ISO 8601 time, or null to keep the item
+ * @returns the time as stored in `expires_at` (UTC), or null
+ * @throws if the time is invalid or not in the future
+ */
+export const parseItemExpiry = (
+	value: unknown,
+	now = Date.now(),
+): string | null => {
+	if (value === null) return null;
+	const time = typeof value === "string" ? Date.parse(value) : Number.NaN;
+	if (Number.isNaN(time)) {
+		throw new Error("Invalid item expiry: expected an ISO 8601 time or null");
+	}
+	if (time <= now) {
+		throw new Error("Invalid item expiry: the time must be in the future");
+	}
+	return new Date(time).toISOString().slice(0, 19).replace("T", " ");
+};
+
+/**
+ * Most items a single batch update can change.
+ */
+const MAX_BATCH_ITEMS = 1000;
+
+/**
+ * Validates a batch update: 1-1000 distinct item ids and a known
+ * operation. Tag names are normalized.
+ * Pure function.
+ *
+ * @throws if the ids or operation are invalid
+ */
+export const parseBatchRequest = (
+	ids: unknown,
+	operation: unknown,
+): { ids: number[]; operation: BatchOperation } => {
+	if (
+		!Array.isArray(ids) ||
+		ids.length === 0 ||
+		ids.length > MAX_BATCH_ITEMS ||
+		!ids.every(isValidId) ||
+		new Set(ids).size !== ids.length
+	) {
+		throw new Error(
+			`Invalid batch: expected 1-${MAX_BATCH_ITEMS} distinct item ids`,
+		);
+	}
+
+	const op = (
+		typeof operation === "object" && operation !== null ? operation : {}
+	) as Record<string, unknown>;
+	switch (op.type) {
+		case "delete":
+			return { ids, operation: { type: "delete" } };
+		case "pin":
+			if (typeof op.pinned !== "boolean") break;
+			return { ids, operation: { type: "pin", pinned: op.pinned } };
+		case "tag":
+			return {
+				ids,
+				operation: { type: "tag", name: assertValidTagName(op.name) },
+			};
+		case "move":
+			if (
+				!isValidId(op.collectionId) ||
+				(op.fromCollectionId !== undefined && !isValidId(op.fromCollectionId))
+			) {
+				break;
+			}
+			return {
+				ids,
+				operation: {
+					type: "move",
+					collectionId: op.collectionId,
+					fromCollectionId: op.fromCollectionId,
+				},
+			};
+	}
+	throw new Error(`Invalid batch operation: ${JSON.stringify(operation)}`);
+};
+
+/**
+ * Validates pagination parameters.
+ * Pure function.
+ */
+export const isValidPaginationParams = (
+	limit: unknown,
+	offset: unknown,
+): boolean =>
+	(limit === undefined ||
+		(typeof limit === "number" && Number.isInteger(limit) && limit > 0)) &&
+	(offset === undefined ||
+		(typeof offset === "number" && Number.isInteger(offset) && offset >= 0));
+
+/**
+ * Maximum rows scanned by a fuzzy search, newest first.
+ * Keeps worst-case latency bounded on very large histories.
+ */
+const MAX_FUZZY_CANDIDATES = 20_000;
+
+/**
+ * Rows matched per regex batch. Matching stops as soon as a page is full.
+ */
+const REGEX_BATCH_SIZE = 250;
+
+/**
+ * Columns holding what was read from an image item: NULL until read,
+ * empty if nothing was found.
+ */
+type ImageReadingColumn = "ocr_text" | "qr_payload" | "image_phash";
+
+/**
+ * Days after its last copy or paste at which an item's frecency weight
+ * has halved; it is a third after twice as long, and so on.
+ */
+const FRECENCY_DECAY_DAYS = 3;
+
+/**
+ * Pagination limits for history queries.
+ */
+const DEFAULT_LIMIT = 50;
+const MIN_LIMIT = 1;
+const MAX_LIMIT = 200;
+
+/**
+ * Columns returned by every history list query.
+ * Image bytes are deliberately excluded; fetch them per item instead.
+ * Compressed text is read back in full in place of `content`.
+ */
+const HISTORY_COLUMN_NAMES = [
+	"id",
+	"content",
+	"type",
+	"created_at",
+	"is_favorite",
+	"pinned",
+	"rtf",
+	"html",
+	"image_width",
+	"image_height",
+	"use_count",
+	"paste_count",
+	"last_used_at",
+	"note",
+	"source_app",
+	"source_title",
+	"source_url",
+	"sync_excluded",
+	"content_size",
+	"qr_payload",
+	"content_kind",
+	"code_language",
+	"color_value",
+	"secret_match",
+	"expires_at",
+	"link_title",
+	"link_description",
+	"link_image",
+	"summary_title",
+	"summary_text",
+	"byte_size",
+	"char_count",
+	"word_count",
+	"line_count",
+	"is_primary",
+	"paste_once",
+] as const;
+
+/**
+ * Select expression for a list column, optionally qualified by `prefix`.
+ * Pure function.
+ */
+const toColumnExpression = (
+	name: (typeof HISTORY_COLUMN_NAMES)[number],
+	prefix = "",
+): string =>
+	name === "content"
+		? `${fullTextExpression(prefix)} AS content`
+		: `${prefix}${name}`;
+
+const HISTORY_COLUMNS = HISTORY_COLUMN_NAMES.map((name) =>
+	toColumnExpression(name),
+).join(", ");
+
+/**
+ * Item columns copied to the trash on delete and back on restore.
+ */
+const TRASHED_COLUMNS = [
+	...HISTORY_COLUMN_NAMES.filter((name) => name !== "id"),
+	"content_zstd",
+	"image",
+	"image_hash",
+	"text_hash",
+	"formats",
+	"thumbnail",
+	"ocr_text",
+	"image_phash",
+	"text_simhash",
+	"content_hash",
+	"synced_from",
+].join(", ");
+
+/**
+ * History list columns qualified with a table alias, for joined queries.
+ * Pure function.
+ */
+export const qualifiedHistoryColumns = (alias: string): string =>
+	HISTORY_COLUMN_NAMES.map((name) =>
+		toColumnExpression(name, `${alias}.`),
+	).join(", ");
+
+/**
+ * Text items classified per step when backfilling content kinds.
+ */
+const CLASSIFY_BATCH_SIZE = 500;
+
+/**
+ * Text items counted per step when backfilling text stats.
+ */
+const TEXT_STATS_BATCH_SIZE = 500;
+
+/**
+ * Text items hashed per step when computing similarity hashes.
+ */
+const SIMHASH_BATCH_SIZE = 500;
+
+/**
+ * Rows deleted per step while shrinking history to a size cap.
+ */
+const PRUNE_BATCH_SIZE = 100;
+
+
+/**
+ * Maximum note length (characters).
+ */
+const MAX_NOTE_CHARS = 10_000;
+
+/**
+ * Maximum length of a source app filter (characters).
+ */
+const MAX_SOURCE_APP_CHARS = 256;
+
+/**
+ * Longest source app name, window title, or URL stored (characters).
+ */
+const MAX_SOURCE_FIELD_CHARS = 2048;
+
+/**
+ * Maximum encoded image size accepted for storage (bytes).
+ */
+const MAX_IMAGE_BYTES = 50 * 1024 * 1024;
+
+/**
+ * Safely coerces a value to a non-negative integer.
+ * Returns the default if the value is not a valid number.
+ * Pure function.
+ */
+const toSafeInt = (value: unknown, defaultValue: number): number => {
+	if (typeof value !== "number" || !Number.isFinite(value)) {
+		return defaultValue;
+	}
+	return Math.floor(value);
+};
+
+/**
+ * Coerces and clamps pagination parameters to safe values.
+ * Pure function.
+ *
+ * - limit: coerced to integer, clamped to [1, 200], defaults to 50
+ * - offset: coerced to integer, clamped to >= 0, defaults to 0
+ */
+const sanitizePagination = (
+	options: Pick<ListHistoryOptions, "limit" | "offset">,
+): { limit: number; offset: number } => {
+	const rawLimit = toSafeInt(options.limit, DEFAULT_LIMIT);
+	const rawOffset = toSafeInt(options.offset, 0);
+	return {
+		limit: Math.min(Math.max(rawLimit, MIN_LIMIT), MAX_LIMIT),
+		offset: Math.max(rawOffset, 0),
+	};
+};
+
+/**
+ * Filters shared by every history query.
+ */
+type HistoryFilters = Pick<
+	ListHistoryOptions,
+	"favoritesOnly" | "tags" | "sourceApp" | "kind" | "language" | "selection"
+>;
+
+/**
+ * Builds WHERE conditions for the favorites, tag, source app, content
+ * kind, code language and selection filters.
+ * Pure function.
+ *
+ * @param alias - Table alias to qualify columns with when history is joined
+ */
+const buildFilterConditions = (
+	filters: HistoryFilters,
+	alias?: string,
+): { conditions: string[]; params: string[] } => {
+	const prefix = alias ? `${alias}.` : "";
+	const conditions: string[] = [];
+	const params: string[] = [];
+
+	if (filters.favoritesOnly) {
+		conditions.push(`${prefix}is_favorite = 1`);
+	}
+
+	for (const tag of filters.tags ?? []) {
+		conditions.push(
+			`${prefix}id IN (SELECT ht.history_id FROM history_tags ht JOIN tags t ON t.id = ht.tag_id WHERE t.name = ?)`,
+		);
+		params.push(normalizeTagName(tag) ?? tag);
+	}
+
+	if (filters.sourceApp) {
+		conditions.push(`${prefix}source_app = ? COLLATE NOCASE`);
+		params.push(filters.sourceApp);
+	}
+
+	if (filters.kind) {
+		conditions.push(`${prefix}content_kind = ?`);
+		params.push(filters.kind);
+	}
+
+	if (filters.language) {
+		conditions.push(`${prefix}code_language = ?`);
+		params.push(filters.language);
+	}
+
+	if (filters.selection) {
+		const primary = filters.selection === "primary" ? 1 : 0;
+		conditions.push(`${prefix}is_primary = ${primary}`);
+	}
+
+	return { conditions, params };
+};
+
+/**
+ * Classifies text as `content_kind, code_language, color_value` column
+ * values; only code gets a language and only colors a value.
+ * Pure function.
+ */
+const classifyText = (
+	text: string,
+): [ContentKind, CodeLanguage | null, string | null] => {
+	const kind = classifyContent(text);
+	return [
+		kind,
+		kind === "code" ? detectCodeLanguage(text) : null,
+		kind === "color" ? normalizeColor(text) : null,
+	];
+};
+
+/**
+ * Converts an item's text, or an image's PNG, to `byte_size, char_count,
+ * word_count, line_count` column values; images only get a size, and
+ * other items (null) none.
+ * Pure function.
+ */
+const toStatsParams = (
+	content: string | Buffer | null,
+): [number | null, number | null, number | null, number | null] => {
+	if (content === null) return [null, null, null, null];
+	if (Buffer.isBuffer(content)) return [content.length, null, null, null];
+	const stats = computeTextStats(content);
+	return [stats.bytes, stats.chars, stats.words, stats.lines];
+};
+
+/**
+ * Converts a secret finding to `secret_match` and the `datetime('now', ?)`
+ * modifier for `expires_at`; a null modifier leaves it NULL.
+ * Pure function.
+ */
+const toSecretParams = (
+	secret: SecretFinding | null | undefined,
+): [string | null, string | null] => [
+	secret?.rule ?? null,
+	secret?.expireAfterMinutes ? `+${secret.expireAfterMinutes} minutes` : null,
+];
+
+/**
+ * Converts source metadata to `source_app, source_title, source_url`
+ * column values, truncating overlong fields.
+ * Pure function.
+ */
+const toSourceParams = (
+	source: SourceApp | null | undefined,
+): [string | null, string | null, string | null] => {
+	const clip = (value: string | null | undefined) =>
+		value ? value.slice(0, MAX_SOURCE_FIELD_CHARS) : null;
+	return [clip(source?.name), clip(source?.title), clip(source?.url)];
+};
+
+/**
+ * Joins conditions into a WHERE clause (empty when there are none).
+ * Pure function.
+ */
+const toWhereClause = (conditions: readonly string[]): string =>
+	conditions.length > 0 ? ` WHERE ${conditions.join(" AND ")}` : "";
+
+/**
+ * SQL expression for an item's frecency score as of `reference`, an SQL
+ * time value such as `'now'` or `?`: its `use_count` (every repeat copy,
+ * including pasting it from history, adds one) divided by
+ * `1 + days since last use / 3`. An item used ten times last month ranks
+ * below one used three times today.
+ * Pure function.
+ */
+export const frecencyExpression = (reference: string): string =>
+	`use_count / (1.0 + MAX(julianday(${reference}) - julianday(created_at), 0) / ${FRECENCY_DECAY_DAYS})`;
+
+/**
+ * SQL expression for the score a ranked order sorts by; `reference` is
+ * the time frecency is scored at.
+ * Pure function.
+ */
+const rankingScore = (order: RankedOrder, reference: string): string =>
+	order === "frecency" ? frecencyExpression(reference) : "paste_count";
+
+/**
+ * SQL expression for an item's date bucket as of `reference`, an SQL
+ * time value such as `'now'`, comparing local dates.
+ * Pure function.
+ */
+export const dateBucketExpression = (reference: string): string => {
+	const day = "date(created_at, 'localtime')";
+	const today = `date(${reference}, 'localtime')`;
+	return `CASE WHEN pinned = 1 THEN 'pinned' WHEN ${day} = ${today} THEN 'today' WHEN ${day} = date(${reference}, 'localtime', '-1 day') THEN 'yesterday' WHEN ${day} >= date(${reference}, 'localtime', 'weekday 0', '-6 days') THEN 'thisWeek' ELSE 'older' END`;
+};
+
+/**
+ * Groups consecutive rows of the same date bucket, keeping their order.
+ * Pure function.
+ */
+export const groupRowsByBucket = (
+	rows: readonly (HistoryRow & { bucket: DateBucket })[],
+): HistoryGroup[] => {
+	const groups: HistoryGroup[] = [];
+	for (const { bucket, ...row } of rows) {
+		const current = groups[groups.length - 1];
+		if (current && current.bucket === bucket) {
+			current.items.push(row);
+		} else {
+			groups.push({ bucket, items: [row] });
+		}
+	}
+	return groups;
+};
+
+/**
+ * Builds a SQL query for history with optional filters.
+ * Pure function - returns query string and params.
+ * Pagination parameters are sanitized with `sanitizePagination`.
+ */
+export const buildHistoryQuery = (
+	options: ListHistoryOptions,
+): { sql: string; params: (string | number)[] } => {
+	const { query = "" } = options;
+	const { limit, offset } = sanitizePagination(options);
+
+	const conditions: string[] = [];
+	const params: (string | number)[] = [];
+
+	if (query.trim()) {
+		conditions.push("(content LIKE ? OR note LIKE ? OR ocr_text LIKE ?)");
+		params.push(`%${query}%`, `%${query}%`, `%${query}%`);
+	}
+
+	const filters = buildFilterConditions(options);
+	conditions.push(...filters.conditions);
+	params.push(...filters.params);
+
+	const whereClause = toWhereClause(conditions);
+	const ordering =
+		options.order === "recent" || options.order === undefined
+			? "created_at DESC"
+			: `${rankingScore(options.order, "'now'")} DESC, created_at DESC`;
+	const sql = `SELECT ${HISTORY_COLUMNS} FROM history${whereClause} ORDER BY pinned DESC, ${ordering} LIMIT ? OFFSET ?`;
+
+	params.push(limit, offset);
+
+	return { sql, params };
+};
+
+/**
+ * Converts free-form user input into an FTS5 MATCH expression.
+ * Pure function.
+ *
+ * Each whitespace-separated term is quoted (so FTS5 operators in the input
+ * are treated literally) and prefix-matched, and all terms must match.
+ *
+ * @returns The MATCH expression, or null if the input has no searchable terms
+ */
+export const buildFtsMatchQuery = (input: string): string | null => {
+	const terms = input
+		.split(/\s+/)
+		.filter((term) => /[\p{L}\p{N}]/u.test(term))
+		.map((term) => `"${term.replace(/"/g, '""')}"*`);
+
+	return terms.length > 0 ? terms.join(" ") : null;
+};
+
+/**
+ * Builds a ranked full-text search query over history content.
+ * Pure function - returns query string and params, or null when the input
+ * has no searchable terms (callers fall back to a substring search).
+ */
+export const buildSearchQuery = (
+	options: SearchHistoryOptions,
+): { sql: string; params: (string | number)[] } | null => {
+	const match = buildFtsMatchQuery(options.query);
+	if (match === null) return null;
+
+	const { limit, offset } = sanitizePagination(options);
+	const columns = qualifiedHistoryColumns("h");
+	const filters = buildFilterConditions(options, "h");
+	const filterClause = filters.conditions
+		.map((condition) => ` AND ${condition}`)
+		.join("");
+	const sql = `SELECT ${columns}, highlight(history_fts, 0, char(57344), char(57345)) AS highlighted FROM history_fts JOIN history h ON h.id = history_fts.rowid WHERE history_fts MATCH ?${filterClause} ORDER BY h.pinned DESC, history_fts.rank, h.created_at DESC LIMIT ? OFFSET ?`;
+
+	return { sql, params: [match, ...filters.params, limit, offset] };
+};
+
+/**
+ * Adds where the query matched to a search result. Masked secrets get no
+ * ranges, which would give away their length and layout.
+ * Pure function.
+ */
+export const toSearchResult = (
+	row: HistoryRow,
+	ranges: MatchRange[],
+): SearchResultRow => ({
+	...row,
+	match_ranges: row.secret_match === null ? ranges : [],
+});
+
+/**
+ * Encodes a keyset position as an opaque cursor string.
+ * Pure function.
+ */
+export const encodeCursor = (cursor: HistoryCursor | RankedCursor): string => {
+	const position = [cursor.pinned ? 1 : 0, cursor.createdAt, cursor.id];
+	return Buffer.from(
+		JSON.stringify(
+			"order" in cursor
+				? [...position, cursor.order, cursor.score, cursor.scoredAt]
+				: position,
+		),
+	).toString("base64url");
+};
+
+/**
+ * Decodes a cursor produced by `encodeCursor`.
+ * Pure function.
+ *
+ * @returns The keyset position, or null if the cursor is malformed
+ */
+export const decodeCursor = (
+	cursor: string,
+): HistoryCursor | RankedCursor | null => {
+	try {
+		const decoded: unknown = JSON.parse(
+			Buffer.from(cursor, "base64url").toString("utf8"),
+		);
+		if (
+			!Array.isArray(decoded) ||
+			(decoded[0] !== 0 && decoded[0] !== 1) ||
+			typeof decoded[1] !== "string" ||
+			!isValidId(decoded[2])
+		) {
+			return null;
+		}
+		const position: HistoryCursor = {
+			pinned: decoded[0] === 1,
+			createdAt: decoded[1],
+			id: decoded[2],
+		};
+		if (decoded.length === 3) return position;
+		if (
+			decoded.length === 6 &&
+			(decoded[3] === "frecency" || decoded[3] === "usage") &&
+			Number.isFinite(decoded[4]) &&
+			typeof decoded[5] === "string" &&
+			!Number.isNaN(Date.parse(decoded[5]))
+		) {
+			return {
+				...position,
+				order: decoded[3],
+				score: decoded[4],
+				scoredAt: decoded[5],
+			};
+		}
+		return null;
+	} catch {
+		return null;
+	}
+};
+
+/**
+ * Builds a keyset-paginated history query.
+ * Pure function - returns query string and params.
+ *
+ * Rows are ordered by `pinned DESC, created_at DESC, id DESC` and one
+ * extra row is requested so the caller can tell whether another page exists.
+ * The page size is clamped like `limit` in `buildHistoryQuery`. With
+ * `withDateBucket`, rows also carry their date bucket as of now as
+ * `bucket`.
+ */
+export const buildPageQuery = (
+	options: HistoryFilters & {
+		cursor: HistoryCursor | null;
+		pageSize?: number;
+		withDateBucket?: boolean;
+	},
+): { sql: string; params: (string | number)[]; pageSize: number } => {
+	const { limit: pageSize } = sanitizePagination({ limit: options.pageSize });
+	const conditions: string[] = [];
+	const params: (string | number)[] = [];
+
+	if (options.cursor) {
+		conditions.push(
+			"(pinned < ? OR (pinned = ? AND (created_at < ? OR (created_at = ? AND id < ?))))",
+		);
+		const { createdAt, id } = options.cursor;
+		const pinned = options.cursor.pinned ? 1 : 0;
+		params.push(pinned, pinned, createdAt, createdAt, id);
+	}
+
+	const filters = buildFilterConditions(options);
+	conditions.push(...filters.conditions);
+	params.push(...filters.params);
+
+	const whereClause = toWhereClause(conditions);
+	const columns = options.withDateBucket
+		? `${HISTORY_COLUMNS}, ${dateBucketExpression("'now'")} AS bucket`
+		: HISTORY_COLUMNS;
+	const sql = `SELECT ${columns} FROM history${whereClause} ORDER BY pinned DESC, created_at DESC, id DESC LIMIT ?`;
+	params.push(pageSize + 1);
+
+	return { sql, params, pageSize };
+};
+
+/**
+ * Builds a keyset-paginated history query in a ranked order, with
+ * frecency scored as of `scoredAt`.
+ * Pure function - returns query string and params.
+ *
+ * Rows are ordered by `pinned DESC, score DESC, created_at DESC, id DESC`
+ * and carry their score as `score`; like `buildPageQuery`, one extra row
+ * is requested.
+ */
+export const buildRankedPageQuery = (
+	options: HistoryFilters & {
+		order: RankedOrder;
+		cursor: RankedCursor | null;
+		scoredAt: string;
+		pageSize?: number;
+	},
+): { sql: string; params: (string | number)[]; pageSize: number } => {
+	const { limit: pageSize } = sanitizePagination({ limit: options.pageSize });
+	const filters = buildFilterConditions(options);
+	const params: (string | number)[] =
+		options.order === "frecency" ? [options.scoredAt] : [];
+	params.push(...filters.params);
+
+	let keyset = "";
+	if (options.cursor) {
+		keyset =
+			" WHERE (pinned < ? OR (pinned = ? AND (score < ? OR (score = ? AND (created_at < ? OR (created_at = ? AND id < ?))))))";
+		const { score, createdAt, id } = options.cursor;
+		const pinned = options.cursor.pinned ? 1 : 0;
+		params.push(pinned, pinned, score, score, createdAt, createdAt, id);
+	}
+
+	const scored = `SELECT ${HISTORY_COLUMNS}, ${rankingScore(options.order, "?")} AS score FROM history${toWhereClause(filters.conditions)}`;
+	const sql = `SELECT * FROM (${scored})${keyset} ORDER BY pinned DESC, score DESC, created_at DESC, id DESC LIMIT ?`;
+	params.push(pageSize + 1);
+
+	return { sql, params, pageSize };
+};
+
+/**
+ * Builds the condition matching items retention may delete. Pinned items
+ * and items in a collection are always kept, and with
+ * `keepPastedAtLeast` set, items pasted that many times too.
+ * Pure function.
+ */
+export const buildPrunableCondition = (
+	keepPastedAtLeast: number | null,
+): { sql: string; params: number[] } => {
+	const conditions = [
+		"pinned = 0",
+		"id NOT IN (SELECT history_id FROM collection_items)",
+	];
+	if (keepPastedAtLeast === null) {
+		return { sql: conditions.join(" AND "), params: [] };
+	}
+	conditions.push("paste_count < ?");
+	return { sql: conditions.join(" AND "), params: [keepPastedAtLeast] };
+};
+
+/**
+ * Throws if an id is not a valid history item id.
+ */
+const assertValidId = (id: unknown): number => {
+	if (!isValidId(id)) {
+		throw new Error(`Invalid history item id: ${id}`);
+	}
+	return id;
+};
+
+/**
+ * Throws if a tag filter is not a list of valid tag names.
+ */
+const assertValidTagFilter = (tags: unknown): void => {
+	if (tags === undefined) return;
+	if (!Array.isArray(tags)) {
+		throw new Error("Invalid tag filter: expected a list of tag names");
+	}
+	for (const tag of tags) assertValidTagName(tag);
+};
+
+/**
+ * Throws if a source app filter is not a non-empty app name.
+ */
+const assertValidSourceAppFilter = (sourceApp: unknown): void => {
+	if (sourceApp === undefined) return;
+	if (
+		typeof sourceApp !== "string" ||
+		sourceApp.trim() === "" ||
+		sourceApp.length > MAX_SOURCE_APP_CHARS
+	) {
+		throw new Error(`Invalid source app filter: ${String(sourceApp)}`);
+	}
+};
+
+/**
+ * Throws if a content kind filter does not name a known kind.
+ */
+const assertValidKindFilter = (kind: unknown): void => {
+	if (kind !== undefined && !isContentKind(kind)) {
+		throw new Error(`Invalid content kind filter: ${String(kind)}`);
+	}
+};
+
+/**
+ * Throws if a code language filter does not name a known language.
+ */
+const assertValidLanguageFilter = (language: unknown): void => {
+	if (language !== undefined && !isCodeLanguage(language)) {
+		throw new Error(`Invalid code language filter: ${String(language)}`);
+	}
+};
+
+/**
+ * Throws if a selection filter is neither the clipboard nor PRIMARY.
+ */
+const assertValidSelectionFilter = (selection: unknown): void => {
+	if (
+		selection !== undefined &&
+		selection !== "clipboard" &&
+		selection !== "primary"
+	) {
+		throw new Error(`Invalid selection filter: ${String(selection)}`);
+	}
+};
+
+/**
+ * Throws if a history order is not one of the known orders.
+ */
+const assertValidOrder = (order: unknown): void => {
+	if (
+		order !== undefined &&
+		order !== "recent" &&
+		order !== "frecency" &&
+		order !== "usage"
+	) {
+		throw new Error(`Invalid history order: ${String(order)}`);
+	}
+};
+
+/**
+ * Throws if a search request has a non-string query, bad pagination, or
+ * an invalid tag or source app filter.
+ */
+const assertValidSearchOptions = (options: SearchHistoryOptions): void => {
+	if (typeof options.query !== "string") {
+		throw new Error(`Invalid search query: ${String(options.query)}`);
+	}
+	if (!isValidPaginationParams(options.limit, options.offset)) {
+		throw new Error(
+			`Invalid pagination parameters: limit=${options.limit}, offset=${options.offset}`,
+		);
+	}
+	assertValidTagFilter(options.tags);
+	assertValidSourceAppFilter(options.sourceApp);
+	assertValidKindFilter(options.kind);
+	assertValidLanguageFilter(options.language);
+	assertValidSelectionFilter(options.selection);
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the history repository.
+ * Owns every SQL statement against the `history` table and validates
+ * inputs before they reach the database, so IPC handlers stay thin.
+ *
+ * @param getDb - Accessor for the open database connection
+ * @param attachments - Store holding image data outside the database
+ */
+export const createHistoryRepository = (
+	getDb: () => Database.Database,
+	attachments: AttachmentStore,
+) => {
+	/**
+	 * Finds the newest row whose content hash matches.
+	 */
+	const findIdByHash = (hash: string): number | undefined =>
+		(
+			getDb()
+				.prepare(
+					"SELECT id FROM history WHERE content_hash = ? ORDER BY created_at DESC LIMIT 1",
+				)
+				.get(hash) as { id: number } | undefined
+		)?.id;
+
+	/**
+	 * Moves an existing row to the top of history and counts the repeat copy.
+	 * A resolved source replaces the stored one, so items show where they
+	 * were last copied from.
+	 * @returns the row's id
+	 */
+	const bumpItem = (id: number, source?: SourceApp | null): number => {
+		const db = getDb();
+		db.prepare(
+			"UPDATE history SET created_at = datetime('now'), use_count = use_count + 1 WHERE id = ?",
+		).run(id);
+		if (source) {
+			db.prepare(
+				"UPDATE history SET source_app = ?, source_title = ?, source_url = ? WHERE id = ?",
+			).run(...toSourceParams(source), id);
+		}
+		return id;
+	};
+
+	/**
+	 * Records image data or full text in the attachment store under its
+	 * content hash. A row referencing the hash must be inserted right
+	 * after, or the data is collected as garbage.
+	 */
+	const storeAttachment = (hash: string, data: Buffer): void => {
+		getDb()
+			.prepare("INSERT OR IGNORE INTO attachments (hash, size) VALUES (?, ?)")
+			.run(hash, data.length);
+		attachments.write(hash, data);
+	};
+
+	/**
+	 * Inserts an image-only clip, or bumps the existing row for the same image.
+	 * Metadata such as EXIF is removed first, so location and device details
+	 * are never stored.
+	 * @returns id of the row written or moved up
+	 */
+	const addImageItem = (
+		image: StoredImage,
+		source?: SourceApp | null,
+	): number => {
+		if (image.png.length > MAX_IMAGE_BYTES) {
+			throw new Error(
+				`Clipboard image too large: ${image.png.length} bytes (max: ${MAX_IMAGE_BYTES})`,
+			);
+		}
+
+		const png = stripPngMetadata(image.png);
+		const hash = computeContentHash("image", png);
+		const existingId = findIdByHash(hash);
+		if (existingId !== undefined) {
+			return bumpItem(existingId, source);
+		}
+
+		storeAttachment(hash, png);
+		const result = getDb()
+			.prepare(
+				"INSERT INTO history (content, type, image_hash, image_width, image_height, byte_size, content_hash, source_app, source_title, source_url) VALUES ('', 'image', ?, ?, ?, ?, ?, ?, ?, ?)",
+			)
+			.run(
+				hash,
+				image.width,
+				image.height,
+				png.length,
+				hash,
+				...toSourceParams(source),
+			);
+		return Number(result.lastInsertRowid);
+	};
+
+	/**
+	 * Inserts a copied file list, or bumps the existing row for the same files.
+	 * @returns id of the row written or moved up
+	 */
+	const addFilesItem = (
+		files: string[],
+		source?: SourceApp | null,
+	): number => {
+		const content = formatStoredFileList(files);
+		if (content.length > MAX_CLIP_CHARS) {
+			throw new Error(
+				`Clipboard file list too large: ${content.length} chars (max: ${MAX_CLIP_CHARS})`,
+			);
+		}
+
+		const hash = computeContentHash("files", content);
+		const existingId = findIdByHash(hash);
+		if (existingId !== undefined) {
+			return bumpItem(existingId, source);
+		}
+
+		const result = getDb()
+			.prepare(
+				"INSERT INTO history (content, type, content_hash, source_app, source_title, source_url) VALUES (?, 'files', ?, ?, ?, ?)",
+			)
+			.run(content, hash, ...toSourceParams(source));
+		return Number(result.lastInsertRowid);
+	};
+
+	/**
+	 * Inserts a captured clip. Content already in history is bumped to the
+	 * top instead, taking the formats and secret flag of the newest copy.
+	 * A preview with `fullText` is found by the hash of the full text.
+	 * Use the returned id rather than the newest row: a copy bumped in the
+	 * same second as another need not sort first.
+	 * @returns id of the row written or moved up, or null if nothing was
+	 *   recorded
+	 */
+	const addItem = (item: NewHistoryItem): number | null => {
+		const {
+			text,
+			rtf,
+			html,
+			image,
+			files,
+			source,
+			secret,
+			fullText,
+			primary,
+			formats,
+		} = item;
+		if (files && files.length > 0) {
+			return addFilesItem(files, source);
+		}
+		if (isEmptyText(text)) {
+			return image ? addImageItem(image, source) : null;
+		}
+
+		// Validate combined content size (text + RTF + HTML)
+		const totalSize = text.length + (rtf?.length ?? 0) + (html?.length ?? 0);
+		if (totalSize > MAX_CLIP_CHARS) {
+			throw new Error(
+				`Clipboard content too large: ${totalSize} chars (max: ${MAX_CLIP_CHARS})`,
+			);
+		}
+
+		const db = getDb();
+		const hash = computeContentHash("text", fullText ?? text);
+		const textHash = fullText !== undefined ? hash : null;
+		const existingId = findIdByHash(hash);
+		const stored = compressText(text);
+		const packedFormats = formats?.length ? packFormats(formats) : null;
+		if (fullText !== undefined) {
+			storeAttachment(hash, Buffer.from(fullText, "utf8"));
+		}
+		if (existingId !== undefined && primary) {
+			// Selecting text already in history only moves it up, keeping
+			// its rich formats and where it was copied from
+			return bumpItem(existingId, source);
+		}
+		if (existingId !== undefined) {
+			const [secretRule, expireAfter] = toSecretParams(secret);
+			db.transaction(() => {
+				// A copy never postpones an expiry already set; a flagged
+				// secret may bring it forward
+				db.prepare(
+					"UPDATE history SET is_primary = 0, content = ?, content_zstd = ?, content_size = ?, rtf = ?, html = ?, formats = ?, text_hash = ?, content_kind = ?, code_language = ?, color_value = ?, byte_size = ?, char_count = ?, word_count = ?, line_count = ?, secret_match = ?, expires_at = COALESCE(MIN(expires_at, datetime('now', ?)), expires_at, datetime('now', ?)) WHERE id = ?",
+				).run(
+					stored.content,
+					stored.compressed,
+					stored.size,
+					rtf || null,
+					html || null,
+					packedFormats,
+					textHash,
+					...classifyText(text),
+					...toStatsParams(text),
+					secretRule,
+					expireAfter,
+					expireAfter,
+					existingId,
+				);
+				bumpItem(existingId, source);
+			})();
+			return existingId;
+		}
+
+		const result = db.prepare(
+			"INSERT INTO history (content, content_zstd, content_size, type, rtf, html, formats, text_hash, content_kind, code_language, color_value, byte_size, char_count, word_count, line_count, secret_match, expires_at, content_hash, source_app, source_title, source_url, is_primary) VALUES (?, ?, ?, 'text', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, datetime('now', ?), ?, ?, ?, ?, ?)",
+		).run(
+			stored.content,
+			stored.compressed,
+			stored.size,
+			rtf || null,
+			html || null,
+			packedFormats,
+			textHash,
+			...classifyText(text),
+			...toStatsParams(text),
+			...toSecretParams(secret),
+			hash,
+			...toSourceParams(source),
+			primary ? 1 : 0,
+		);
+		return Number(result.lastInsertRowid);
+	};
+
+	/**
+	 * Computes content hashes for rows stored before hashing existed.
+	 * Runs once per row; rows that already have a hash are skipped.
+	 * @returns number of rows updated
+	 */
+	const backfillContentHashes = (): number => {
+		const db = getDb();
+		const rows = db
+			.prepare(
+				"SELECT id, type, content, image FROM history WHERE content_hash IS NULL",
+			)
+			.all() as Array<{
+			id: number;
+			type: string;
+			content: string;
+			image: Buffer | null;
+		}>;
+		if (rows.length === 0) return 0;
+
+		const update = db.prepare(
+			"UPDATE history SET content_hash = ? WHERE id = ?",
+		);
+		db.transaction(() => {
+			for (const row of rows) {
+				const payload = row.type === "image" ? row.image : row.content;
+				update.run(computeContentHash(row.type, payload ?? ""), row.id);
+			}
+		})();
+		return rows.length;
+	};
+
+	/**
+	 * Compresses large text items stored before compression existed.
+	 * @returns number of rows compressed
+	 */
+	const compressLargeItems = (): number => {
+		const db = getDb();
+		const rows = db
+			.prepare(
+				"SELECT id, content FROM history WHERE type = 'text' AND content_zstd IS NULL AND length(content) > ?",
+			)
+			.all(COMPRESS_MIN_CHARS) as Array<{ id: number; content: string }>;
+		const update = db.prepare(
+			"UPDATE history SET content = ?, content_zstd = ?, content_size = ? WHERE id = ?",
+		);
+		let compressed = 0;
+		db.transaction(() => {
+			for (const row of rows) {
+				const stored = compressText(row.content);
+				if (stored.compressed === null) continue;
+				update.run(stored.content, stored.compressed, stored.size, row.id);
+				compressed += 1;
+			}
+		})();
+		return compressed;
+	};
+
+	/**
+	 * Detects the content kind (and language of code, value of colors) of
+	 * text items stored before classification existed, a batch at a time
+	 * so large histories are not read at once.
+	 * @returns number of rows classified
+	 */
+	const classifyItems = (): number => {
+		const db = getDb();
+		const select = db.prepare(
+			`SELECT id, ${fullTextExpression()} AS content FROM history WHERE type = 'text' AND content_kind IS NULL LIMIT ?`,
+		);
+		const update = db.prepare(
+			"UPDATE history SET content_kind = ?, code_language = ?, color_value = ? WHERE id = ?",
+		);
+		let classified = 0;
+		for (;;) {
+			const rows = select.all(CLASSIFY_BATCH_SIZE) as Array<{
+				id: number;
+				content: string;
+			}>;
+			if (rows.length === 0) return classified;
+			db.transaction(() => {
+				for (const row of rows) {
+					update.run(...classifyText(row.content), row.id);
+				}
+			})();
+			classified += rows.length;
+		}
+	};
+
+	/**
+	 * Computes the size and counts of items stored before text stats
+	 * existed, a batch at a time. Images stored then keep no size.
+	 * @returns number of rows counted
+	 */
+	const backfillTextStats = (): number => {
+		const db = getDb();
+		const select = db.prepare(
+			`SELECT id, ${fullTextExpression()} AS content FROM history WHERE type = 'text' AND char_count IS NULL LIMIT ?`,
+		);
+		const update = db.prepare(
+			"UPDATE history SET byte_size = ?, char_count = ?, word_count = ?, line_count = ? WHERE id = ?",
+		);
+		let counted = 0;
+		for (;;) {
+			const rows = select.all(TEXT_STATS_BATCH_SIZE) as Array<{
+				id: number;
+				content: string;
+			}>;
+			if (rows.length === 0) return counted;
+			db.transaction(() => {
+				for (const row of rows) {
+					update.run(...toStatsParams(row.content), row.id);
+				}
+			})();
+			counted += rows.length;
+		}
+	};
+
+	/**
+	 * Computes the similarity hash of text items not hashed yet, a batch
+	 * at a time: those stored before similarity hashes existed and those
+	 * captured, imported or synced since the last run.
+	 * @returns number of rows hashed
+	 */
+	const backfillTextSimhashes = (): number => {
+		const db = getDb();
+		const select = db.prepare(
+			`SELECT id, ${fullTextExpression()} AS content FROM history WHERE type = 'text' AND text_simhash IS NULL LIMIT ?`,
+		);
+		const update = db.prepare(
+			"UPDATE history SET text_simhash = ? WHERE id = ?",
+		);
+		let hashed = 0;
+		for (;;) {
+			const rows = select.all(SIMHASH_BATCH_SIZE) as Array<{
+				id: number;
+				content: string;
+			}>;
+			if (rows.length === 0) return hashed;
+			db.transaction(() => {
+				for (const row of rows) {
+					update.run(computeTextSimhash(row.content), row.id);
+				}
+			})();
+			hashed += rows.length;
+		}
+	};
+
+	/**
+	 * Moves image data stored in the database, by versions before the
+	 * attachment store or by a restored backup, into the store.
+	 * @returns number of rows moved
+	 */
+	const moveImagesToAttachments = (): number => {
+		const db = getDb();
+		let moved = 0;
+		for (const table of ["history", "trash"]) {
+			const ids = db
+				.prepare(`SELECT id FROM ${table} WHERE image IS NOT NULL`)
+				.pluck()
+				.all() as number[];
+			const select = db.prepare(
+				`SELECT image, image_hash FROM ${table} WHERE id = ?`,
+			);
+			const update = db.prepare(
+				`UPDATE ${table} SET image = NULL, image_hash = ? WHERE id = ?`,
+			);
+			// One row at a time, so at most one image is held in memory
+			for (const id of ids) {
+				const row = select.get(id) as {
+					image: Buffer;
+					image_hash: string | null;
+				};
+				const hash = row.image_hash ?? computeContentHash("image", row.image);
+				db.transaction(() => {
+					storeAttachment(hash, row.image);
+					update.run(hash, id);
+				})();
+				moved += 1;
+			}
+		}
+		return moved;
+	};
+
+	/**
+	 * Forgets attachments no history or trash row uses any more and deletes
+	 * their files, along with any other file the database does not know.
+	 * @returns number of files deleted
+	 */
+	const collectAttachmentGarbage = (): number => {
+		const db = getDb();
+		db.prepare("DELETE FROM attachments WHERE ref_count <= 0").run();
+		const hashes = db
+			.prepare("SELECT hash FROM attachments")
+			.pluck()
+			.all() as string[];
+		return attachments.sweep(hashes);
+	};
+
+	/**
+	 * Serializes the database with image data copied back in from the
+	 * attachment store, and the attachment key unprotected, so the result
+	 * stands alone, e.g. as a backup.
+	 * `moveImagesToAttachments` moves it out again once restored.
+	 */
+	const serializeWithImages = (): Buffer => {
+		const db = getDb();
+		const hashes = db
+			.prepare("SELECT hash FROM attachments WHERE ref_count > 0")
+			.pluck()
+			.all() as string[];
+		const copy = new Database(db.serialize());
+		try {
+			const inline = (table: string) =>
+				copy.prepare(
+					`UPDATE ${table} SET image = ? WHERE image_hash = ? AND image IS NULL`,
+				);
+			const updates = [inline("history"), inline("trash")];
+			for (const hash of hashes) {
+				const png = attachments.read(hash);
+				if (!png) continue;
+				for (const update of updates) update.run(png, hash);
+			}
+			// Backups are encrypted themselves; a plain key restores on a
+			// machine whose keychain cannot decrypt this one's
+			copy
+				.prepare("UPDATE attachment_key SET key = ?, protected = 0")
+				.run(attachments.getKey());
+			return copy.serialize();
+		} finally {
+			copy.close();
+		}
+	};
+
+	/**
+	 * Lists history items, pinned first then newest first, with optional
+	 * filters.
+	 */
+	const listItems = (options: ListHistoryOptions = {}): HistoryRow[] => {
+		// Validate pagination parameters
+		if (!isValidPaginationParams(options.limit, options.offset)) {
+			throw new Error(
+				`Invalid pagination parameters: limit=${options.limit}, offset=${options.offset}`,
+			);
+		}
+		assertValidOrder(options.order);
+		assertValidTagFilter(options.tags);
+		assertValidSourceAppFilter(options.sourceApp);
+		assertValidKindFilter(options.kind);
+		assertValidLanguageFilter(options.language);
+		assertValidSelectionFilter(options.selection);
+
+		const { sql, params } = buildHistoryQuery(options);
+		return getDb().prepare(sql).all(...params) as HistoryRow[];
+	};
+
+	/**
+	 * Lists one page of history in a ranked order, with frecency scored
+	 * as of when the first page was read.
+	 */
+	const listRankedPage = (
+		order: RankedOrder,
+		filters: HistoryFilters,
+		cursor: RankedCursor | null,
+		pageSize: number | undefined,
+	): HistoryPage => {
+		const scoredAt = cursor?.scoredAt ?? new Date().toISOString();
+		const query = buildRankedPageQuery({
+			...filters,
+			order,
+			cursor,
+			scoredAt,
+			pageSize,
+		});
+		const rows = getDb()
+			.prepare(query.sql)
+			.all(...query.params) as (HistoryRow & { score: number })[];
+
+		const page = rows.slice(0, query.pageSize);
+		const last = page[page.length - 1];
+		const nextCursor =
+			rows.length > query.pageSize && last
+				? encodeCursor({
+						pinned: last.pinned === 1,
+						createdAt: last.created_at,
+						id: last.id,
+						order,
+						score: last.score,
+						scoredAt,
+					})
+				: null;
+
+		return {
+			items: page.map(({ score: _score, ...row }) => row),
+			nextCursor,
+		};
+	};
+
+	/**
+	 * Validates a page request and splits it into its order, decoded
+	 * cursor and filters.
+	 */
+	const parsePageOptions = (options: ListPageOptions) => {
+		const { pageSize } = options;
+		if (
+			pageSize !== undefined &&
+			!(typeof pageSize === "number" && Number.isInteger(pageSize))
+		) {
+			throw new Error(`Invalid page size: ${pageSize}`);
+		}
+
+		assertValidOrder(options.order);
+		const order = options.order ?? "recent";
+		const cursor = options.cursor ? decodeCursor(options.cursor) : null;
+		const cursorOrder = cursor && "order" in cursor ? cursor.order : "recent";
+		if (options.cursor && (!cursor || cursorOrder !== order)) {
+			throw new Error("Invalid history cursor");
+		}
+		assertValidTagFilter(options.tags);
+		assertValidSourceAppFilter(options.sourceApp);
+		assertValidKindFilter(options.kind);
+		assertValidLanguageFilter(options.language);
+		assertValidSelectionFilter(options.selection);
+
+		const filters: HistoryFilters = {
+			favoritesOnly: options.favoritesOnly,
+			tags: options.tags,
+			sourceApp: options.sourceApp,
+			kind: options.kind,
+			language: options.language,
+			selection: options.selection,
+		};
+		return { order, cursor, filters };
+	};
+
+	/**
+	 * Cursor after the last row of a recent-order page, or null if no
+	 * extra row showed another page follows.
+	 */
+	const recentCursorAfter = (
+		rows: readonly HistoryRow[],
+		pageSize: number,
+	): string | null => {
+		const last = rows[pageSize - 1];
+		return rows.length > pageSize && last
+			? encodeCursor({
+					pinned: last.pinned === 1,
+					createdAt: last.created_at,
+					id: last.id,
+				})
+			: null;
+	};
+
+	/**
+	 * Lists one page of history, pinned first then newest first (or in
+	 * a ranked order), using keyset pagination.
+	 * Cursors stay valid while rows are inserted or deleted, and page cost
+	 * does not grow with depth the way OFFSET does.
+	 */
+	const listPage = (options: ListPageOptions = {}): HistoryPage => {
+		const { order, cursor, filters } = parsePageOptions(options);
+		if (order !== "recent") {
+			return listRankedPage(
+				order,
+				filters,
+				cursor as RankedCursor | null,
+				options.pageSize,
+			);
+		}
+
+		const query = buildPageQuery({
+			...filters,
+			cursor: cursor as HistoryCursor | null,
+			pageSize: options.pageSize,
+		});
+		const rows = getDb()
+			.prepare(query.sql)
+			.all(...query.params) as HistoryRow[];
+
+		return {
+			items: rows.slice(0, query.pageSize),
+			nextCursor: recentCursorAfter(rows, query.pageSize),
+		};
+	};
+
+	/**
+	 * Lists one page of history newest first, grouped into date buckets
+	 * in local time, so the list can show "Today" or "Older" headers
+	 * without regrouping what it loaded. Cursors chain like `listPage`
+	 * cursors in the recent order.
+	 */
+	const listGroupedPage = (
+		options: Omit<ListPageOptions, "order"> = {},
+	): GroupedHistoryPage => {
+		const { cursor, filters } = parsePageOptions({
+			...options,
+			order: "recent",
+		});
+		const query = buildPageQuery({
+			...filters,
+			cursor: cursor as HistoryCursor | null,
+			pageSize: options.pageSize,
+			withDateBucket: true,
+		});
+		const rows = getDb()
+			.prepare(query.sql)
+			.all(...query.params) as (HistoryRow & { bucket: DateBucket })[];
+
+		return {
+			groups: groupRowsByBucket(rows.slice(0, query.pageSize)),
+			nextCursor: recentCursorAfter(rows, query.pageSize),
+		};
+	};
+
+	/**
+	 * Searches history content with the full-text index, pinned items first,
+	 * then best match.
+	 * Input without searchable terms (e.g. only punctuation) falls back to
+	 * a substring search, newest first.
+	 */
+	const searchItems = (options: SearchHistoryOptions): SearchResultRow[] => {
+		assertValidSearchOptions(options);
+
+		const search = buildSearchQuery(options);
+		if (!search) {
+			return listItems(options).map((row) =>
+				toSearchResult(row, findSubstringRanges(row.content, options.query)),
+			);
+		}
+
+		const rows = getDb()
+			.prepare(search.sql)
+			.all(...search.params) as HighlightedRow[];
+		// Only content is highlighted; matches in notes and OCR text add none
+		return rows.map(({ highlighted, ...row }) =>
+			toSearchResult(row, parseHighlightRanges(highlighted ?? "")),
+		);
+	};
+
+	/**
+	 * Ranks history items by fuzzy match score, best match first after
+	 * pinned items.
+	 * Every query term must match as an ordered subsequence of the content.
+	 */
+	const fuzzySearchItems = (
+		options: SearchHistoryOptions,
+	): SearchResultRow[] => {
+		assertValidSearchOptions(options);
+
+		const terms = parseFuzzyQuery(options.query);
+		if (terms.length === 0) {
+			return listItems(options).map((row) => toSearchResult(row, []));
+		}
+
+		const { limit, offset } = sanitizePagination(options);
+		const filters = buildFilterConditions(options);
+		const candidates = getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS} FROM history${toWhereClause(filters.conditions)} ORDER BY pinned DESC, created_at DESC LIMIT ?`,
+			)
+			.iterate(
+				...filters.params,
+				MAX_FUZZY_CANDIDATES,
+			) as IterableIterator<HistoryRow>;
+
+		const matches: Array<FuzzyMatch & { row: HistoryRow }> = [];
+		for (const row of candidates) {
+			const match = matchFuzzy(terms, row.content);
+			if (match !== null) matches.push({ row, ...match });
+		}
+
+		// Stable sort keeps newest-first order among equal scores
+		return matches
+			.sort((a, b) => b.row.pinned - a.row.pinned || b.score - a.score)
+			.slice(offset, offset + limit)
+			.map(({ row, ranges }) => toSearchResult(row, ranges));
+	};
+
+	/**
+	 * Finds history items whose content matches a regular expression,
+	 * pinned first then newest first. The query is the pattern; matching is case-insensitive.
+	 *
+	 * Rows are streamed from the database in batches and matching stops once
+	 * the requested page is filled. Patterns that exhaust the time budget
+	 * (catastrophic backtracking) are rejected with a timeout error.
+	 */
+	const regexSearchItems = (
+		options: SearchHistoryOptions,
+	): SearchResultRow[] => {
+		assertValidSearchOptions(options);
+
+		const match = createRegexRangeMatcher({ pattern: options.query });
+		const { limit, offset } = sanitizePagination(options);
+		const filters = buildFilterConditions(options);
+		const rows = getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS} FROM history${toWhereClause(filters.conditions)} ORDER BY pinned DESC, created_at DESC`,
+			)
+			.iterate(...filters.params) as IterableIterator<HistoryRow>;
+
+		const matches: SearchResultRow[] = [];
+		const wanted = offset + limit;
+		let batch: HistoryRow[] = [];
+
+		const flush = (): void => {
+			const results = match(batch.map((row) => row.content));
+			batch.forEach((row, index) => {
+				const ranges = results[index];
+				if (ranges !== null) matches.push(toSearchResult(row, ranges));
+			});
+			batch = [];
+		};
+
+		for (const row of rows) {
+			batch.push(row);
+			if (batch.length === REGEX_BATCH_SIZE) {
+				flush();
+				if (matches.length >= wanted) break;
+			}
+		}
+		if (batch.length > 0 && matches.length < wanted) flush();
+
+		return matches.slice(offset, wanted);
+	};
+
+	/**
+	 * Fetches a single history item by id.
+	 */
+	const getItem = (id: number): HistoryRow | undefined =>
+		getDb()
+			.prepare(`SELECT ${HISTORY_COLUMNS} FROM history WHERE id = ?`)
+			.get(assertValidId(id)) as HistoryRow | undefined;
+
+	/**
+	 * Fetches the item at `index` in copy order, 0 being the most recently
+	 * copied; pinned items are not moved to the front.
+	 */
+	const getRecentItem = (index: number): HistoryRow | undefined => {
+		if (!Number.isInteger(index) || index < 0) {
+			throw new Error(`Invalid recent item index: ${index}`);
+		}
+		return getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS} FROM history ORDER BY created_at DESC, id DESC LIMIT 1 OFFSET ?`,
+			)
+			.get(index) as HistoryRow | undefined;
+	};
+
+	/**
+	 * Lists the most recently copied items, newest first; pinned items are
+	 * not moved to the front.
+	 */
+	const listRecentItems = (limit: number): HistoryRow[] => {
+		if (!isValidPaginationParams(limit, 0)) {
+			throw new Error(`Invalid recent item limit: ${limit}`);
+		}
+		return getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS} FROM history ORDER BY created_at DESC, id DESC LIMIT ?`,
+			)
+			.all(limit) as HistoryRow[];
+	};
+
+	const countItems = (): number => {
+		const row = getDb()
+			.prepare("SELECT COUNT(*) AS count FROM history")
+			.get() as { count: number };
+		return row.count;
+	};
+
+	/**
+	 * Joins text items, in the given order, into a new item at the top of
+	 * history. A result already in history is bumped instead.
+	 * @returns the merged item
+	 * @throws if an item is missing or is not text
+	 */
+	const mergeItems = (ids: unknown, separator: unknown): HistoryRow => {
+		const request = parseMergeRequest(ids, separator);
+		const contents = request.ids.map((id) => {
+			const item = getItem(id);
+			if (!item) {
+				throw new Error(`History item not found: ${id}`);
+			}
+			if (item.type !== "text") {
+				throw new Error(`Only text items can be merged: ${id} is ${item.type}`);
+			}
+			return item.content;
+		});
+
+		const text = contents.join(request.separator);
+		const mergedId = addItem({ text });
+		const merged = mergedId === null ? undefined : getItem(mergedId);
+		if (!merged) {
+			throw new Error("Failed to merge items");
+		}
+		return merged;
+	};
+
+	/**
+	 * Keeps the first of several duplicate items and moves the others to
+	 * the trash. The kept item gains their tags, and becomes a favorite or
+	 * pinned if any of them was.
+	 * @returns the kept item
+	 * @throws if the kept item is missing
+	 */
+	const mergeDuplicates = (ids: unknown): HistoryRow => {
+		const [keepId, ...others] = parseDuplicateMergeRequest(ids);
+		const placeholders = others.map(() => "?").join(", ");
+		const db = getDb();
+		db.transaction(() => {
+			if (!getItem(keepId)) {
+				throw new Error(`History item not found: ${keepId}`);
+			}
+			const flags = db
+				.prepare(
+					`SELECT MAX(is_favorite) AS favorite, MAX(pinned) AS pinned FROM history WHERE id IN (${placeholders})`,
+				)
+				.get(...others) as { favorite: number | null; pinned: number | null };
+			db.prepare(
+				"UPDATE history SET is_favorite = MAX(is_favorite, ?), pinned = MAX(pinned, ?) WHERE id = ?",
+			).run(flags.favorite ?? 0, flags.pinned ?? 0, keepId);
+			db.prepare(
+				`INSERT OR IGNORE INTO history_tags (history_id, tag_id) SELECT ?, tag_id FROM history_tags WHERE history_id IN (${placeholders})`,
+			).run(keepId, ...others);
+			for (const id of others) deleteItem(id);
+		})();
+		return getItem(keepId) as HistoryRow;
+	};
+
+	/**
+	 * Groups image items that look alike by their perceptual hashes,
+	 * newest first within and across groups. Images not hashed yet are
+	 * left out.
+	 * @param maxDistance - Most differing hash bits between neighbors
+	 */
+	const findNearDuplicateImages = (maxDistance: number): number[][] => {
+		const items = getDb()
+			.prepare(
+				"SELECT id, image_phash AS hash FROM history WHERE type = 'image' AND image_phash != '' ORDER BY created_at DESC, id DESC",
+			)
+			.all() as { id: number; hash: string }[];
+		return groupNearDuplicates(items, maxDistance);
+	};
+
+	/**
+	 * Groups text items that are nearly the same, e.g. successive edits of
+	 * one paragraph, by their similarity hashes, newest first within and
+	 * across groups. Texts not hashed yet are hashed first; texts too
+	 * short to compare are left out.
+	 * @param maxDistance - Most differing hash bits between neighbors
+	 */
+	const findNearDuplicateTexts = (maxDistance: number): number[][] => {
+		backfillTextSimhashes();
+		const items = getDb()
+			.prepare(
+				"SELECT id, text_simhash AS hash FROM history WHERE type = 'text' AND text_simhash != '' ORDER BY created_at DESC, id DESC",
+			)
+			.all() as { id: number; hash: string }[];
+		return groupNearDuplicates(items, maxDistance);
+	};
+
+	/**
+	 * Fetches the stored image of an image item.
+	 */
+	const getImage = (id: number): StoredImage | undefined => {
+		const row = getDb()
+			.prepare(
+				"SELECT image, image_hash, image_width, image_height FROM history WHERE id = ? AND (image IS NOT NULL OR image_hash IS NOT NULL)",
+			)
+			.get(assertValidId(id)) as
+			| {
+					image: Buffer | null;
+					image_hash: string | null;
+					image_width: number;
+					image_height: number;
+			  }
+			| undefined;
+		if (!row) return undefined;
+
+		// Images not yet moved to the attachment store are read in place
+		const png =
+			row.image ?? attachments.read(row.image_hash as string) ?? undefined;
+		return png
+			? { png, width: row.image_width, height: row.image_height }
+			: undefined;
+	};
+
+	/**
+	 * Fetches the complete text of an item stored as a preview because it
+	 * was over the capture size limit.
+	 * @returns the full text, or undefined if the item is not a preview or
+	 * its attachment is missing
+	 */
+	const getFullText = (id: number): string | undefined => {
+		const hash = getDb()
+			.prepare("SELECT text_hash FROM history WHERE id = ?")
+			.pluck()
+			.get(assertValidId(id)) as string | null | undefined;
+		if (!hash) return undefined;
+		return attachments.read(hash)?.toString("utf8");
+	};
+
+	/**
+	 * Fetches the extra Windows formats stored with a text item.
+	 * @returns an empty list if it has none
+	 * @throws if the stored formats are corrupt
+	 */
+	const getFormats = (id: number): ClipboardFormat[] => {
+		const packed = getDb()
+			.prepare("SELECT formats FROM history WHERE id = ?")
+			.pluck()
+			.get(assertValidId(id)) as Buffer | null | undefined;
+		return packed ? unpackFormats(packed) : [];
+	};
+
+	/**
+	 * Looks up the image of an image item without reading its data.
+	 */
+	const getImageSource = (id: number): ImageSource | undefined => {
+		const validId = assertValidId(id);
+		// length() of a blob comes from its header, not its contents
+		const row = getDb()
+			.prepare(
+				"SELECT image_hash, image_width, image_height, COALESCE(length(image), (SELECT size FROM attachments WHERE hash = image_hash), 0) AS size FROM history WHERE id = ? AND (image IS NOT NULL OR image_hash IS NOT NULL)",
+			)
+			.get(validId) as
+			| {
+					image_hash: string | null;
+					image_width: number;
+					image_height: number;
+					size: number;
+			  }
+			| undefined;
+		if (!row) return undefined;
+
+		const hash = row.image_hash;
+		return {
+			width: row.image_width,
+			height: row.image_height,
+			size: row.size,
+			read: () => getImage(validId)?.png,
+			writeTo: (filePath) => {
+				if (hash) return attachments.copyTo(hash, filePath);
+				// Images not yet moved to the attachment store are small
+				const png = getImage(validId)?.png;
+				if (!png) return false;
+				fs.writeFileSync(filePath, png, { mode: 0o600 });
+				return true;
+			},
+		};
+	};
+
+	/**
+	 * Returns the cached thumbnail of an image item, rendering it from the
+	 * full image and caching it on first request.
+	 * @param render - Encodes a thumbnail of an image, e.g. as a data URL;
+	 * null if the image data could not be read
+	 * @returns the thumbnail, or null if the item has no image
+	 */
+	const getThumbnail = async (
+		id: number,
+		render: (image: ImageSource) => Promise<string | null>,
+	): Promise<string | null> => {
+		const validId = assertValidId(id);
+		const row = getDb()
+			.prepare("SELECT thumbnail FROM history WHERE id = ? AND type = 'image'")
+			.get(validId) as { thumbnail: string | null } | undefined;
+		if (!row) return null;
+		if (row.thumbnail !== null) return row.thumbnail;
+
+		const source = getImageSource(validId);
+		if (!source) return null;
+		const thumbnail = await render(source);
+		if (thumbnail === null) return null;
+		getDb()
+			.prepare("UPDATE history SET thumbnail = ? WHERE id = ?")
+			.run(thumbnail, validId);
+		return thumbnail;
+	};
+
+	/**
+	 * Removes metadata such as EXIF from a stored image item, e.g. one
+	 * imported or synced before capture stripped it. The cleaned image
+	 * replaces the original under its new hash.
+	 * @returns false if the image had no metadata
+	 * @throws if the item has no image data
+	 */
+	const stripImageMetadata = (id: number): boolean => {
+		const validId = assertValidId(id);
+		const image = getImage(validId);
+		if (!image) {
+			throw new Error(`Image data missing for history item: ${validId}`);
+		}
+		const png = stripPngMetadata(image.png);
+		if (png === image.png) return false;
+
+		const hash = computeContentHash("image", png);
+		const db = getDb();
+		db.transaction(() => {
+			storeAttachment(hash, png);
+			db.prepare(
+				"UPDATE history SET image = NULL, image_hash = ?, content_hash = ? WHERE id = ?",
+			).run(hash, hash, validId);
+		})();
+		collectAttachmentGarbage();
+		return true;
+	};
+
+	/**
+	 * Stores what was read from an image item. Does nothing if the item
+	 * was deleted meanwhile.
+	 */
+	const saveImageReading = (
+		column: ImageReadingColumn,
+		id: number,
+		value: string,
+	): void => {
+		getDb()
+			.prepare(`UPDATE history SET ${column} = ? WHERE id = ?`)
+			.run(value, assertValidId(id));
+	};
+
+	/**
+	 * Finds the newest image item not read into `column` yet. Items whose
+	 * image data is missing are stored as empty.
+	 */
+	const nextUnreadImage = (
+		column: ImageReadingColumn,
+	): ImageCandidate | undefined => {
+		const select = getDb()
+			.prepare(
+				`SELECT id FROM history WHERE type = 'image' AND ${column} IS NULL ORDER BY id DESC LIMIT 1`,
+			)
+			.pluck();
+		for (;;) {
+			const id = select.get() as number | undefined;
+			if (id === undefined) return undefined;
+			const image = getImage(id);
+			if (image) return { id, png: image.png };
+			saveImageReading(column, id, "");
+		}
+	};
+
+	/**
+	 * Stores the text recognized in an image item, indexing it for search.
+	 */
+	const setOcrText = (id: number, text: string): void =>
+		saveImageReading("ocr_text", id, text);
+
+	const nextOcrCandidate = (): ImageCandidate | undefined =>
+		nextUnreadImage("ocr_text");
+
+	/**
+	 * Stores the decoded QR code of an image item; empty if it has none.
+	 */
+	const setQrPayload = (id: number, payload: string): void =>
+		saveImageReading("qr_payload", id, payload);
+
+	const nextQrCandidate = (): ImageCandidate | undefined =>
+		nextUnreadImage("qr_payload");
+
+	/**
+	 * Stores the perceptual hash of an image item; empty if it could not be
+	 * hashed.
+	 */
+	const setImagePhash = (id: number, hash: string): void =>
+		saveImageReading("image_phash", id, hash);
+
+	const nextPhashCandidate = (): ImageCandidate | undefined =>
+		nextUnreadImage("image_phash");
+
+	/**
+	 * Finds the newest URL item whose page has not been fetched yet.
+	 * Flagged secrets are left alone.
+	 */
+	const nextLinkPreviewCandidate = (): LinkCandidate | undefined =>
+		getDb()
+			.prepare(
+				"SELECT id, content AS url FROM history WHERE content_kind = 'url' AND link_title IS NULL AND secret_match IS NULL ORDER BY id DESC LIMIT 1",
+			)
+			.get() as LinkCandidate | undefined;
+
+	/**
+	 * Stores the preview of the page a URL item links to. Does nothing if
+	 * the item was deleted meanwhile.
+	 */
+	const setLinkPreview = (id: number, preview: LinkPreview): void => {
+		getDb()
+			.prepare(
+				"UPDATE history SET link_title = ?, link_description = ?, link_image = ? WHERE id = ?",
+			)
+			.run(
+				preview.title,
+				preview.description,
+				preview.image,
+				assertValidId(id),
+			);
+	};
+
+	/**
+	 * Fetches the newest text item of at least `minChars` characters not
+	 * summarized yet. Flagged secrets are left out, so they are never sent
+	 * to the model.
+	 */
+	const nextSummaryCandidate = (
+		minChars: number,
+	): SummaryCandidate | undefined =>
+		getDb()
+			.prepare(
+				"SELECT id, content FROM history WHERE type = 'text' AND summary_title IS NULL AND secret_match IS NULL AND char_count >= ? ORDER BY id DESC LIMIT 1",
+			)
+			.get(minChars) as SummaryCandidate | undefined;
+
+	/**
+	 * Stores the summary of a text item. Does nothing if the item was
+	 * deleted meanwhile.
+	 */
+	const setItemSummary = (id: number, summary: ItemSummary): void => {
+		getDb()
+			.prepare(
+				"UPDATE history SET summary_title = ?, summary_text = ? WHERE id = ?",
+			)
+			.run(summary.title, summary.summary, assertValidId(id));
+	};
+
+	/**
+	 * Lists the newest text items not embedded with `model` yet. Flagged
+	 * secrets are left out, so they are never sent to the endpoint.
+	 */
+	const nextEmbeddingCandidates = (
+		model: string,
+		limit: number,
+	): EmbeddingCandidate[] =>
+		getDb()
+			.prepare(
+				"SELECT id, content FROM history h WHERE type = 'text' AND secret_match IS NULL AND content != '' AND NOT EXISTS (SELECT 1 FROM history_embeddings e WHERE e.history_id = h.id AND e.model = ?) ORDER BY id DESC LIMIT ?",
+			)
+			.all(model, limit) as EmbeddingCandidate[];
+
+	/**
+	 * Stores an item's embedding, replacing one from another model. Does
+	 * nothing if the item was deleted meanwhile.
+	 */
+	const saveEmbedding = (
+		id: number,
+		model: string,
+		vector: Float32Array,
+	): void => {
+		getDb()
+			.prepare(
+				"INSERT OR REPLACE INTO history_embeddings (history_id, model, vector) SELECT id, ?, ? FROM history WHERE id = ?",
+			)
+			.run(model, encodeVector(vector), assertValidId(id));
+	};
+
+	/**
+	 * Streams the item vectors embedded with `model`.
+	 */
+	const listEmbeddings = (model: string): Iterable<StoredEmbedding> => {
+		const rows = getDb()
+			.prepare(
+				"SELECT history_id AS id, vector FROM history_embeddings WHERE model = ?",
+			)
+			.iterate(model) as IterableIterator<{ id: number; vector: Buffer }>;
+		return {
+			*[Symbol.iterator]() {
+				for (const { id, vector } of rows) {
+					yield { id, vector: decodeVector(vector) };
+				}
+			},
+		};
+	};
+
+	/**
+	 * Fetches items by id, in the order given; ids of deleted items are
+	 * skipped.
+	 */
+	const getItemsByIds = (ids: readonly number[]): HistoryRow[] => {
+		const rows = getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS} FROM history WHERE id IN (SELECT value FROM json_each(?))`,
+			)
+			.all(JSON.stringify(ids.map(assertValidId))) as HistoryRow[];
+		const byId = new Map(rows.map((row) => [row.id, row]));
+		return ids.flatMap((id) => byId.get(id) ?? []);
+	};
+
+	/**
+	 * Returns the decoded QR code of an item.
+	 * @throws if the item has no decoded QR code
+	 */
+	const getQrPayload = (id: number): string => {
+		const validId = assertValidId(id);
+		const payload = getDb()
+			.prepare("SELECT qr_payload FROM history WHERE id = ?")
+			.pluck()
+			.get(validId) as string | null | undefined;
+		if (!payload) {
+			throw new Error(`No QR code found in history item: ${validId}`);
+		}
+		return payload;
+	};
+
+	/**
+	 * Copies matching items to the trash with their tag names and
+	 * collection ids; the caller deletes them in the same transaction.
+	 */
+	const copyToTrash = (conditions: string[], params: number[]): void => {
+		getDb()
+			.prepare(
+				`INSERT INTO trash (${TRASHED_COLUMNS}, tags, collections) SELECT ${TRASHED_COLUMNS}, (SELECT json_group_array(t.name) FROM history_tags ht JOIN tags t ON t.id = ht.tag_id WHERE ht.history_id = history.id), (SELECT json_group_array(ci.collection_id) FROM collection_items ci WHERE ci.history_id = history.id) FROM history${toWhereClause(conditions)}`,
+			)
+			.run(...params);
+	};
+
+	/**
+	 * Deletes an item from history. A text item sync may have sent leaves
+	 * a tombstone, so sync deletes it on other devices too. The caller runs
+	 * it in a transaction.
+	 */
+	const removeItem = (id: number): void => {
+		const db = getDb();
+		const row = db
+			.prepare(
+				"SELECT content_hash FROM history WHERE id = ? AND type = 'text' AND sync_excluded = 0",
+			)
+			.get(id) as { content_hash: string | null } | undefined;
+		db.prepare("DELETE FROM history WHERE id = ?").run(id);
+		if (row?.content_hash) {
+			// REPLACE gives a repeated deletion a new id, so it is sent again
+			db.prepare(
+				"INSERT OR REPLACE INTO sync_tombstones (content_hash, deleted_at) VALUES (?, datetime('now'))",
+			).run(row.content_hash);
+		}
+	};
+
+	/**
+	 * Moves an item to the trash, leaving a sync tombstone like
+	 * `removeItem`.
+	 */
+	const deleteItem = (id: number): void => {
+		const validId = assertValidId(id);
+		getDb().transaction(() => {
+			copyToTrash(["id = ?"], [validId]);
+			removeItem(validId);
+		})();
+	};
+
+	/**
+	 * Moves every item to the trash, leaving sync tombstones like
+	 * `removeItem`.
+	 */
+	const clearAll = (): void => {
+		const db = getDb();
+		db.transaction(() => {
+			copyToTrash([], []);
+			db.prepare(
+				"INSERT OR REPLACE INTO sync_tombstones (content_hash, deleted_at) SELECT DISTINCT content_hash, datetime('now') FROM history WHERE type = 'text' AND sync_excluded = 0 AND content_hash IS NOT NULL",
+			).run();
+			db.prepare("DELETE FROM history").run();
+		})();
+	};
+
+	/**
+	 * Lists the trash, most recently deleted first.
+	 */
+	const listTrash = (
+		options: Pick<ListHistoryOptions, "limit" | "offset"> = {},
+	): TrashRow[] => {
+		const { limit, offset } = sanitizePagination(options);
+		const rows = getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS}, deleted_at, tags FROM trash ORDER BY deleted_at DESC, id DESC LIMIT ? OFFSET ?`,
+			)
+			.all(limit, offset) as Array<
+			HistoryRow & { deleted_at: string; tags: string }
+		>;
+		return rows.map((row) => ({
+			...row,
+			tags: JSON.parse(row.tags) as string[],
+		}));
+	};
+
+	/**
+	 * Moves an item from the trash back into history with its tags, and
+	 * into those of its collections that still exist. It gets a new id and
+	 * is dated now, so sync sends it again and devices that applied its
+	 * deletion take it back; its deletion tombstone is dropped. If its
+	 * content was copied again meanwhile, the trash entry is merged into
+	 * that item instead.
+	 * @returns id of the restored item
+	 * @throws if there is no such trash entry
+	 */
+	const restoreFromTrash = (id: number): number => {
+		const validId = assertValidId(id);
+		const db = getDb();
+		return db.transaction(() => {
+			const entry = db
+				.prepare(
+					"SELECT content_hash, tags, collections FROM trash WHERE id = ?",
+				)
+				.get(validId) as
+				| { content_hash: string | null; tags: string; collections: string }
+				| undefined;
+			if (!entry) {
+				throw new Error(`Trash entry not found: ${validId}`);
+			}
+
+			let itemId =
+				entry.content_hash === null
+					? undefined
+					: findIdByHash(entry.content_hash);
+			if (itemId === undefined) {
+				itemId = Number(
+					db
+						.prepare(
+							`INSERT INTO history (${TRASHED_COLUMNS}) SELECT ${TRASHED_COLUMNS} FROM trash WHERE id = ?`,
+						)
+						.run(validId).lastInsertRowid,
+				);
+				db.prepare(
+					"UPDATE history SET created_at = datetime('now') WHERE id = ?",
+				).run(itemId);
+				db.prepare("DELETE FROM sync_tombstones WHERE content_hash = ?").run(
+					entry.content_hash,
+				);
+			}
+
+			db.prepare(
+				"INSERT OR IGNORE INTO tags (name) SELECT value FROM json_each(?)",
+			).run(entry.tags);
+			db.prepare(
+				"INSERT OR IGNORE INTO history_tags (history_id, tag_id) SELECT ?, id FROM tags WHERE name IN (SELECT value FROM json_each(?))",
+			).run(itemId, entry.tags);
+			db.prepare(
+				"INSERT OR IGNORE INTO collection_items (collection_id, history_id, position) SELECT c.id, ?, (SELECT COALESCE(MAX(position) + 1, 0) FROM collection_items WHERE collection_id = c.id) FROM collections c WHERE c.id IN (SELECT value FROM json_each(?))",
+			).run(itemId, entry.collections);
+			db.prepare("DELETE FROM trash WHERE id = ?").run(validId);
+			return itemId;
+		})();
+	};
+
+	/**
+	 * Permanently deletes everything in the trash, with image files no
+	 * longer used.
+	 * @returns number of trash entries deleted
+	 */
+	const emptyTrash = (): number => {
+		const { changes } = getDb().prepare("DELETE FROM trash").run();
+		collectAttachmentGarbage();
+		return changes;
+	};
+
+	/**
+	 * Applies one operation to many items in a single transaction: all of
+	 * them change or, if any is missing, none do. Deleted items go to the
+	 * trash as with `deleteItem`.
+	 * @returns number of items changed
+	 * @throws if the request is invalid or an item or collection is missing
+	 */
+	const batchUpdate = (ids: unknown, operation: unknown): number => {
+		const request = parseBatchRequest(ids, operation);
+		const op = request.operation;
+		const idList = JSON.stringify(request.ids);
+		const db = getDb();
+
+		db.transaction(() => {
+			const { count } = db
+				.prepare(
+					"SELECT COUNT(*) AS count FROM history WHERE id IN (SELECT value FROM json_each(?))",
+				)
+				.get(idList) as { count: number };
+			if (count !== request.ids.length) {
+				throw new Error("History item not found in batch");
+			}
+
+			switch (op.type) {
+				case "delete":
+					for (const id of request.ids) deleteItem(id);
+					break;
+				case "pin":
+					db.prepare(
+						"UPDATE history SET pinned = ? WHERE id IN (SELECT value FROM json_each(?))",
+					).run(op.pinned ? 1 : 0, idList);
+					break;
+				case "tag":
+					db.prepare("INSERT OR IGNORE INTO tags (name) VALUES (?)").run(
+						op.name,
+					);
+					db.prepare(
+						"INSERT OR IGNORE INTO history_tags (history_id, tag_id) SELECT value, (SELECT id FROM tags WHERE name = ?) FROM json_each(?)",
+					).run(op.name, idList);
+					break;
+				case "move": {
+					const target = db
+						.prepare("SELECT id FROM collections WHERE id = ?")
+						.get(op.collectionId);
+					if (!target) {
+						throw new Error(`Collection not found: ${op.collectionId}`);
+					}
+					if (
+						op.fromCollectionId !== undefined &&
+						op.fromCollectionId !== op.collectionId
+					) {
+						db.prepare(
+							"DELETE FROM collection_items WHERE collection_id = ? AND history_id IN (SELECT value FROM json_each(?))",
+						).run(op.fromCollectionId, idList);
+					}
+					const append = db.prepare(
+						"INSERT OR IGNORE INTO collection_items (collection_id, history_id, position) SELECT ?, ?, COALESCE(MAX(position) + 1, 0) FROM collection_items WHERE collection_id = ?",
+					);
+					for (const id of request.ids) {
+						append.run(op.collectionId, id, op.collectionId);
+					}
+					break;
+				}
+			}
+		})();
+		return request.ids.length;
+	};
+
+	/**
+	 * Flips a 0/1 flag column on an item, treating NULL as 0.
+	 * @returns the new flag state
+	 */
+	const toggleFlag = (
+		id: number,
+		column: "is_favorite" | "pinned" | "sync_excluded" | "paste_once",
+	): boolean => {
+		const validId = assertValidId(id);
+		const db = getDb();
+
+		// Check if item exists first
+		const existing = db
+			.prepare("SELECT id FROM history WHERE id = ?")
+			.get(validId) as { id: number } | undefined;
+
+		if (!existing) {
+			throw new Error(`History item not found: ${validId}`);
+		}
+
+		// NULL -> 1, 0 -> 1, 1 -> 0
+		db.prepare(
+			`UPDATE history SET ${column} = CASE WHEN COALESCE(${column}, 0) = 1 THEN 0 ELSE 1 END WHERE id = ?`,
+		).run(validId);
+
+		// Return the new state
+		const result = db
+			.prepare(`SELECT ${column} AS flag FROM history WHERE id = ?`)
+			.get(validId) as { flag: number } | undefined;
+		return Boolean(result?.flag);
+	};
+
+	/**
+	 * Counts a restore, paste or type-out of an item and records when it
+	 * happened. A one-time item is deleted instead, skipping the trash.
+	 * Missing items are ignored.
+	 * @returns true if the item was one-time and is now deleted
+	 */
+	const recordUse = (id: number): boolean => {
+		const validId = assertValidId(id);
+		const db = getDb();
+		return db.transaction(() => {
+			const oneTime = db
+				.prepare("SELECT 1 FROM history WHERE id = ? AND paste_once = 1")
+				.get(validId);
+			if (oneTime) {
+				removeItem(validId);
+				return true;
+			}
+			db.prepare(
+				"UPDATE history SET paste_count = paste_count + 1, last_used_at = datetime('now') WHERE id = ?",
+			).run(validId);
+			return false;
+		})();
+	};
+
+	/**
+	 * Toggles favorite status of an item.
+	 * @returns the new favorite state
+	 */
+	const toggleFavorite = (id: number): boolean =>
+		toggleFlag(id, "is_favorite");
+
+	/**
+	 * Toggles whether an item is pinned to the top of history.
+	 * @returns the new pinned state
+	 */
+	const togglePin = (id: number): boolean => toggleFlag(id, "pinned");
+
+	/**
+	 * Toggles whether LAN sync keeps an item on this device.
+	 * @returns the new excluded state
+	 */
+	const toggleSyncExcluded = (id: number): boolean =>
+		toggleFlag(id, "sync_excluded");
+
+	/**
+	 * Toggles whether an item is deleted the first time it is used.
+	 * @returns the new one-time state
+	 */
+	const togglePasteOnce = (id: number): boolean =>
+		toggleFlag(id, "paste_once");
+
+	/**
+	 * Sets or clears the note on an item. Blank text clears it.
+	 */
+	const setItemNote = (id: number, text: string): void => {
+		const validId = assertValidId(id);
+		if (typeof text !== "string") {
+			throw new Error("Invalid note: expected text");
+		}
+		const note = text.trim();
+		if (note.length > MAX_NOTE_CHARS) {
+			throw new Error(
+				`Note too long: ${note.length} chars (max: ${MAX_NOTE_CHARS})`,
+			);
+		}
+
+		const { changes } = getDb()
+			.prepare("UPDATE history SET note = ? WHERE id = ?")
+			.run(note || null, validId);
+		if (changes === 0) {
+			throw new Error(`History item not found: ${validId}`);
+		}
+	};
+
+	/**
+	 * Clears the secret flag and expiry of an item the user trusts.
+	 * @returns false if the item was not flagged
+	 */
+	const dismissSecret = (id: number): boolean => {
+		const validId = assertValidId(id);
+		const { changes } = getDb()
+			.prepare(
+				"UPDATE history SET secret_match = NULL, expires_at = NULL WHERE id = ? AND secret_match IS NOT NULL",
+			)
+			.run(validId);
+		return changes > 0;
+	};
+
+	/**
+	 * Sets the time an item is deleted at, e.g. an hour from now; null
+	 * keeps it.
+	 * @param expiresAt - ISO 8601 time in the future, or null
+	 */
+	const setItemExpiry = (id: number, expiresAt: unknown): void => {
+		const validId = assertValidId(id);
+		const { changes } = getDb()
+			.prepare("UPDATE history SET expires_at = ? WHERE id = ?")
+			.run(parseItemExpiry(expiresAt), validId);
+		if (changes === 0) {
+			throw new Error(`History item not found: ${validId}`);
+		}
+	};
+
+	/**
+	 * Deletes an item `minutes` from now, unless it already expires sooner.
+	 */
+	const expireItem = (id: number, minutes: number): void => {
+		const validId = assertValidId(id);
+		getDb()
+			.prepare(
+				"UPDATE history SET expires_at = MIN(COALESCE(expires_at, datetime('now', ?)), datetime('now', ?)) WHERE id = ?",
+			)
+			.run(`+${minutes} minutes`, `+${minutes} minutes`, validId);
+	};
+
+	/**
+	 * Lists the apps history items were copied from, most items first.
+	 * Names differing only in case are grouped.
+	 */
+	const listSourceApps = (): SourceAppCount[] =>
+		getDb()
+			.prepare(
+				"SELECT source_app AS name, COUNT(*) AS count FROM history WHERE source_app IS NOT NULL GROUP BY source_app COLLATE NOCASE ORDER BY count DESC, name COLLATE NOCASE",
+			)
+			.all() as SourceAppCount[];
+
+	/**
+	 * Lists text items captured on this device after `afterId` that LAN
+	 * sync may send, oldest first. Items received from a peer and items
+	 * kept local-only are skipped.
+	 */
+	const listSyncItems = (
+		afterId: number,
+		limit: number,
+	): Array<SyncItem & { id: number }> =>
+		getDb()
+			.prepare(
+				`SELECT id, ${fullTextExpression()} AS content, rtf, html, created_at FROM history WHERE id > ? AND type = 'text' AND sync_excluded = 0 AND synced_from IS NULL ORDER BY id LIMIT ?`,
+			)
+			.all(afterId, limit) as Array<SyncItem & { id: number }>;
+
+	/**
+	 * Id of the newest row, or 0 for an empty history.
+	 */
+	const getLastItemId = (): number => {
+		const row = getDb()
+			.prepare("SELECT COALESCE(MAX(id), 0) AS id FROM history")
+			.get() as { id: number };
+		return row.id;
+	};
+
+	/**
+	 * Stores text items received from a paired device. Content already in
+	 * history is left where it is, and content deleted at or after the
+	 * item's timestamp stays deleted. Peer timestamps are kept, but never
+	 * later than now, so a fast clock cannot hold items at the top.
+	 * @returns number of items stored
+	 */
+	const addSyncedItems = (items: SyncItem[], deviceId: string): number => {
+		const db = getDb();
+		const insert = db.prepare(
+			"INSERT INTO history (content, content_zstd, content_size, type, rtf, html, content_kind, code_language, color_value, byte_size, char_count, word_count, line_count, content_hash, created_at, synced_from) VALUES (?, ?, ?, 'text', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, MIN(COALESCE(datetime(?), datetime('now')), datetime('now')), ?)",
+		);
+		const isDeleted = db.prepare(
+			"SELECT 1 FROM sync_tombstones WHERE content_hash = ? AND deleted_at >= MIN(COALESCE(datetime(?), datetime('now')), datetime('now'))",
+		);
+		return db.transaction(() => {
+			let added = 0;
+			for (const item of items) {
+				const size =
+					item.content.length +
+					(item.rtf?.length ?? 0) +
+					(item.html?.length ?? 0);
+				if (isEmptyText(item.content) || size > MAX_CLIP_CHARS) continue;
+
+				const hash = computeContentHash("text", item.content);
+				if (findIdByHash(hash) !== undefined) continue;
+				if (isDeleted.get(hash, item.created_at)) continue;
+				const stored = compressText(item.content);
+				insert.run(
+					stored.content,
+					stored.compressed,
+					stored.size,
+					item.rtf || null,
+					item.html || null,
+					...classifyText(item.content),
+					...toStatsParams(item.content),
+					hash,
+					item.created_at,
+					deviceId,
+				);
+				added += 1;
+			}
+			return added;
+		})();
+	};
+
+	/**
+	 * Lists items matching an export's filters with an id above `afterId`,
+	 * oldest first. Exports read history in these batches so no statement
+	 * stays open (and blocks captures) while a batch is written out.
+	 */
+	const listExportItems = (
+		filters: ExportHistoryFilters,
+		afterId: number,
+		limit: number,
+	): ExportHistoryRow[] => {
+		const { conditions, params } = buildFilterConditions(filters);
+		if (filters.pinnedOnly) conditions.push("pinned = 1");
+		const rows = getDb()
+			.prepare(
+				`SELECT ${HISTORY_COLUMNS}, (SELECT json_group_array(t.name) FROM history_tags ht JOIN tags t ON t.id = ht.tag_id WHERE ht.history_id = history.id) AS tag_names FROM history${toWhereClause(["id > ?", ...conditions])} ORDER BY id LIMIT ?`,
+			)
+			.all(afterId, ...params, limit) as ExportQueryRow[];
+		return rows.map(({ tag_names, ...row }) => ({
+			...row,
+			tags: (JSON.parse(tag_names) as string[]).sort(),
+		}));
+	};
+
+	/**
+	 * Stores items from a backup in one transaction, keeping their
+	 * timestamps, flags, notes and tags. Items whose content is already in
+	 * history (or earlier in the backup) are skipped, as are empty and
+	 * oversized ones and images without image data. Image metadata such as
+	 * EXIF is removed.
+	 * @returns number of items stored
+	 */
+	const importItems = (items: ImportHistoryItem[]): number => {
+		const db = getDb();
+		const insert = db.prepare(
+			"INSERT INTO history (content, content_zstd, content_size, type, rtf, html, content_kind, code_language, color_value, image_hash, image_width, image_height, byte_size, char_count, word_count, line_count, content_hash, created_at, is_favorite, pinned, use_count, note, source_app, source_title, source_url) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, MIN(COALESCE(datetime(?), datetime('now')), datetime('now')), ?, ?, ?, ?, ?, ?, ?)",
+		);
+		const insertTag = db.prepare(
+			"INSERT OR IGNORE INTO tags (name) VALUES (?)",
+		);
+		const tagItem = db.prepare(
+			"INSERT OR IGNORE INTO history_tags (history_id, tag_id) SELECT ?, id FROM tags WHERE name = ?",
+		);
+		return db.transaction(() => {
+			let imported = 0;
+			for (const item of items) {
+				const image = item.type === "image" ? item.image : null;
+				if (image) {
+					if (image.png.length > MAX_IMAGE_BYTES) continue;
+				} else {
+					const size =
+						item.content.length +
+						(item.rtf?.length ?? 0) +
+						(item.html?.length ?? 0);
+					if (isEmptyText(item.content) || size > MAX_CLIP_CHARS) continue;
+				}
+
+				const png = image && stripPngMetadata(image.png);
+				const hash = computeContentHash(item.type, png ?? item.content);
+				if (findIdByHash(hash) !== undefined) continue;
+				if (png) storeAttachment(hash, png);
+				const stored = compressText(image ? "" : item.content);
+				const { lastInsertRowid } = insert.run(
+					stored.content,
+					stored.compressed,
+					stored.size,
+					item.type,
+					item.rtf || null,
+					item.html || null,
+					...(item.type === "text"
+						? classifyText(item.content)
+						: [null, null, null]),
+					image ? hash : null,
+					image?.width ?? null,
+					image?.height ?? null,
+					...toStatsParams(png ?? (item.type === "text" ? item.content : null)),
+					hash,
+					item.created_at,
+					item.favorite ? 1 : 0,
+					item.pinned ? 1 : 0,
+					item.use_count,
+					item.note?.slice(0, MAX_NOTE_CHARS) || null,
+					...toSourceParams(item.source),
+				);
+				for (const tag of item.tags) {
+					const name = normalizeTagName(tag);
+					if (name === null) continue;
+					insertTag.run(name);
+					tagItem.run(lastInsertRowid, name);
+				}
+				imported += 1;
+			}
+			return imported;
+		})();
+	};
+
+	/**
+	 * Lists deletions made on this device after `afterId` that sync may
+	 * send, oldest first. Deletions received from a peer are skipped.
+	 */
+	const listSyncTombstones = (
+		afterId: number,
+		limit: number,
+	): Array<SyncTombstone & { id: number }> =>
+		getDb()
+			.prepare(
+				"SELECT id, content_hash AS hash, deleted_at FROM sync_tombstones WHERE id > ? AND synced_from IS NULL ORDER BY id LIMIT ?",
+			)
+			.all(afterId, limit) as Array<SyncTombstone & { id: number }>;
+
+	/**
+	 * Id of the newest tombstone, or 0 if there are none.
+	 */
+	const getLastTombstoneId = (): number => {
+		const row = getDb()
+			.prepare("SELECT COALESCE(MAX(id), 0) AS id FROM sync_tombstones")
+			.get() as { id: number };
+		return row.id;
+	};
+
+	/**
+	 * Applies deletions received from a paired device: items with the same
+	 * content copied at or before the deletion are deleted, later copies
+	 * are kept (last writer wins). The newest deletion of each content is
+	 * remembered, so older copies arriving later are refused.
+	 * @returns ids of the deleted items
+	 */
+	const applySyncTombstones = (
+		tombstones: SyncTombstone[],
+		deviceId: string,
+	): number[] => {
+		const db = getDb();
+		const remember = db.prepare(
+			"INSERT INTO sync_tombstones (content_hash, deleted_at, synced_from) VALUES (?, MIN(datetime(?), datetime('now')), ?) ON CONFLICT(content_hash) DO UPDATE SET deleted_at = excluded.deleted_at, synced_from = excluded.synced_from WHERE excluded.deleted_at > sync_tombstones.deleted_at",
+		);
+		const findDeleted = db.prepare(
+			"SELECT h.id FROM history h JOIN sync_tombstones t ON t.content_hash = h.content_hash WHERE h.content_hash = ? AND h.created_at <= t.deleted_at",
+		);
+		const remove = db.prepare("DELETE FROM history WHERE id = ?");
+		return db.transaction(() => {
+			const deleted: number[] = [];
+			for (const tombstone of tombstones) {
+				remember.run(tombstone.hash, tombstone.deleted_at, deviceId);
+				const rows = findDeleted.all(tombstone.hash) as Array<{ id: number }>;
+				for (const { id } of rows) {
+					remove.run(id);
+					deleted.push(id);
+				}
+			}
+			return deleted;
+		})();
+	};
+
+	/**
+	 * Forgets deletions older than sync remembers them.
+	 * @returns number of tombstones removed
+	 */
+	const pruneSyncTombstones = (): number =>
+		getDb()
+			.prepare(
+				"DELETE FROM sync_tombstones WHERE deleted_at < datetime('now', ?)",
+			)
+			.run(`-${SYNC_TOMBSTONE_MAX_AGE_DAYS} days`).changes;
+
+	/**
+	 * Deletes items outside the retention limits: older than `maxAgeDays`,
+	 * beyond the newest `maxItems`, then oldest-first while the database
+	 * holds more than `maxDatabaseMb` of data.
+	 * Pinned items, items in a collection and items pasted at least
+	 * `keepPastedAtLeast` times are never deleted and do not count toward
+	 * `maxItems` (`buildPrunableCondition`).
+	 * Trash entries older than `trashDays` are purged, and the trash is
+	 * emptied oldest-first before any item is deleted for size.
+	 * Pruned items skip the trash, and image files no longer used are
+	 * deleted afterwards.
+	 * @returns number of items deleted
+	 */
+	const pruneItems = (limits: RetentionPolicy): number => {
+		const db = getDb();
+		const pruned = db.transaction(() => {
+			let deleted = 0;
+
+			if (limits.trashDays !== null) {
+				db.prepare(
+					"DELETE FROM trash WHERE deleted_at < datetime('now', ?)",
+				).run(`-${limits.trashDays} days`);
+			}
+
+			const { sql: prunable, params: keepParams } = buildPrunableCondition(
+				limits.keepPastedAtLeast,
+			);
+
+			if (limits.maxAgeDays !== null) {
+				deleted += db
+					.prepare(
+						`DELETE FROM history WHERE ${prunable} AND created_at < datetime('now', ?)`,
+					)
+					.run(...keepParams, `-${limits.maxAgeDays} days`).changes;
+			}
+
+			if (limits.maxItems !== null) {
+				deleted += db
+					.prepare(
+						`DELETE FROM history WHERE ${prunable} AND id NOT IN (SELECT id FROM history WHERE ${prunable} ORDER BY created_at DESC, id DESC LIMIT ?)`,
+					)
+					.run(...keepParams, ...keepParams, limits.maxItems).changes;
+			}
+
+			if (limits.maxDatabaseMb !== null) {
+				const maxBytes = limits.maxDatabaseMb * 1024 * 1024;
+				// Free pages are reused by SQLite, so only pages in use count,
+				// plus attachments still in use
+				const attachmentBytes = db
+					.prepare(
+						"SELECT COALESCE(SUM(size), 0) FROM attachments WHERE ref_count > 0",
+					)
+					.pluck();
+				const pageBytes = () =>
+					((db.pragma("page_count", { simple: true }) as number) -
+						(db.pragma("freelist_count", { simple: true }) as number)) *
+					(db.pragma("page_size", { simple: true }) as number);
+				const usedBytes = () => pageBytes() + (attachmentBytes.get() as number);
+				const deleteOldestTrash = db.prepare(
+					"DELETE FROM trash WHERE id IN (SELECT id FROM trash ORDER BY deleted_at ASC, id ASC LIMIT ?)",
+				);
+				const deleteOldest = db.prepare(
+					`DELETE FROM history WHERE id IN (SELECT id FROM history WHERE ${prunable} ORDER BY created_at ASC, id ASC LIMIT ?)`,
+				);
+
+				while (usedBytes() > maxBytes) {
+					if (deleteOldestTrash.run(PRUNE_BATCH_SIZE).changes > 0) continue;
+					const { changes } = deleteOldest.run(...keepParams, PRUNE_BATCH_SIZE);
+					if (changes === 0) break;
+					deleted += changes;
+				}
+			}
+
+			return deleted;
+		})();
+		collectAttachmentGarbage();
+		return pruned;
+	};
+
+	/**
+	 * Deletes items, and trashed ones, past their expiry: flagged secrets
+	 * and items given an expiry. Unlike retention limits, an expiry was
+	 * asked for, so it deletes pinned and collected items too; expired
+	 * items skip the trash.
+	 * @returns content hashes of the history items deleted, null for
+	 *   those not hashed yet
+	 */
+	const expireItems = (): Array<string | null> => {
+		const db = getDb();
+		const expired = "expires_at <= datetime('now')";
+		return db.transaction(() => {
+			db.prepare("DELETE FROM trash WHERE expires_at <= datetime('now')").run();
+			const hashes = db
+				.prepare(`SELECT content_hash FROM history WHERE ${expired}`)
+				.pluck()
+				.all() as Array<string | null>;
+			db.prepare(`DELETE FROM history WHERE ${expired}`).run();
+			return hashes;
+		})();
+	};
+
+	return {
+		addItem,
+		backfillContentHashes,
+		compressLargeItems,
+		classifyItems,
+		backfillTextStats,
+		backfillTextSimhashes,
+		moveImagesToAttachments,
+		collectAttachmentGarbage,
+		serializeWithImages,
+		listItems,
+		listPage,
+		listGroupedPage,
+		searchItems,
+		fuzzySearchItems,
+		regexSearchItems,
+		getItem,
+		findIdByHash,
+		getRecentItem,
+		listRecentItems,
+		countItems,
+		mergeItems,
+		mergeDuplicates,
+		findNearDuplicateImages,
+		findNearDuplicateTexts,
+		getImage,
+		getImageSource,
+		getFullText,
+		getFormats,
+		getThumbnail,
+		stripImageMetadata,
+		nextOcrCandidate,
+		setOcrText,
+		nextQrCandidate,
+		setQrPayload,
+		getQrPayload,
+		nextPhashCandidate,
+		setImagePhash,
+		nextLinkPreviewCandidate,
+		setLinkPreview,
+		nextSummaryCandidate,
+		setItemSummary,
+		nextEmbeddingCandidates,
+		saveEmbedding,
+		listEmbeddings,
+		getItemsByIds,
+		deleteItem,
+		clearAll,
+		listTrash,
+		restoreFromTrash,
+		emptyTrash,
+		batchUpdate,
+		recordUse,
+		toggleFavorite,
+		togglePin,
+		toggleSyncExcluded,
+		togglePasteOnce,
+		setItemNote,
+		dismissSecret,
+		setItemExpiry,
+		expireItem,
+		listSourceApps,
+		listExportItems,
+		importItems,
+		listSyncItems,
+		getLastItemId,
+		addSyncedItems,
+		listSyncTombstones,
+		getLastTombstoneId,
+		applySyncTombstones,
+		pruneSyncTombstones,
+		pruneItems,
+		expireItems,
+	};
+};
+
+export type HistoryRepository = ReturnType<typeof createHistoryRepository>;
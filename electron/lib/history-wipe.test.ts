@@ -0,0 +1,64 @@
+import type Database from "better-sqlite3";
+import { describe, expect, it } from "vitest";
+import { createWipeConfirmation, wipeHistoryDatabase } from "./history-wipe.js";
+
+describe("createWipeConfirmation", () => {
+	it("accepts the issued token once", () => {
+		const confirmation = createWipeConfirmation();
+		const { token } = confirmation.issue();
+
+		expect(confirmation.confirm(token)).toBe(true);
+		expect(confirmation.confirm(token)).toBe(false);
+	});
+
+	it("refuses a wrong token and invalidates the pending one", () => {
+		const confirmation = createWipeConfirmation();
+		const { token } = confirmation.issue();
+
+		expect(confirmation.confirm("00")).toBe(false);
+		expect(confirmation.confirm(undefined)).toBe(false);
+		expect(confirmation.confirm(token)).toBe(false);
+	});
+
+	it("refuses expired and replaced tokens", () => {
+		let now = 0;
+		const confirmation = createWipeConfirmation({
+			ttlMs: 1000,
+			now: () => now,
+		});
+
+		const expired = confirmation.issue();
+		expect(expired.expiresAt).toBe("1970-01-01T00:00:01.000Z");
+		now = 1001;
+		expect(confirmation.confirm(expired.token)).toBe(false);
+
+		const replaced = confirmation.issue();
+		confirmation.issue();
+		expect(confirmation.confirm(replaced.token)).toBe(false);
+	});
+});
+
+describe("wipeHistoryDatabase", () => {
+	it("empties every table with secure delete on, then vacuums", () => {
+		const statements: string[] = [];
+		const db = {
+			pragma: (sql: string) => statements.push(`PRAGMA ${sql}`),
+			prepare: (sql: string) => ({ run: () => statements.push(sql) }),
+			exec: (sql: string) => statements.push(sql),
+			transaction: (fn: () => void) => fn,
+		} as unknown as Database.Database;
+
+		wipeHistoryDatabase(db);
+
+		expect(statements[0]).toBe("PRAGMA secure_delete = ON");
+		expect(statements).toContain("DELETE FROM history");
+		expect(statements).toContain("DELETE FROM collections");
+		expect(statements).toContain("DELETE FROM sync_tombstones");
+		expect(statements).toContain("DELETE FROM attachment_key");
+		expect(statements.slice(-3)).toEqual([
+			"VACUUM",
+			"PRAGMA wal_checkpoint(TRUNCATE)",
+			"PRAGMA secure_delete = OFF",
+		]);
+	});
+});
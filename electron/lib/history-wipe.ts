@@ -0,0 +1,110 @@
+import { randomBytes, timingSafeEqual } from "node:crypto";
+import type Database from "better-sqlite3";
+import { checkpointWal } from "./db-connection.js";
+
+/**
+ * How long a wipe confirmation token stays valid.
+ */
+const WIPE_TOKEN_TTL_MS = 60_000;
+
+/**
+ * Tables emptied by a wipe, children before their parents. Image files
+ * are left to attachment garbage collection; dropping the attachment key
+ * makes them unreadable even if recovered from disk.
+ */
+const WIPED_TABLES = [
+	"history_tags",
+	"collection_items",
+	"history",
+	"tags",
+	"collections",
+	"registers",
+	"sync_tombstones",
+	"trash",
+	"attachments",
+	"attachment_key",
+];
+
+/**
+ * A token the renderer must hand back to confirm a wipe.
+ */
+export type WipeConfirmation = {
+	token: string;
+	/** ISO 8601 time after which the token is refused */
+	expiresAt: string;
+};
+
+// ============================================================================
+// Confirmation
+// ============================================================================
+
+/**
+ * Creates the wipe confirmation: a single-use token the renderer requests
+ * when the user opens the wipe dialog and sends back with the wipe, so a
+ * stray or repeated call cannot wipe history on its own. Issuing a token
+ * replaces any earlier one.
+ */
+export const createWipeConfirmation = (
+	options: { ttlMs?: number; now?: () => number } = {},
+) => {
+	const ttlMs = options.ttlMs ?? WIPE_TOKEN_TTL_MS;
+	const now = options.now ?? Date.now;
+	let pending: { token: Buffer; expiresAt: number } | null = null;
+
+	const issue = (): WipeConfirmation => {
+		const token = randomBytes(32);
+		const expiresAt = now() + ttlMs;
+		pending = { token, expiresAt };
+		return {
+			token: token.toString("hex"),
+			expiresAt: new Date(expiresAt).toISOString(),
+		};
+	};
+
+	/**
+	 * Uses up the pending token. Any attempt, right or wrong, invalidates it,
+	 * so a token cannot be guessed or replayed.
+	 * @returns whether `token` is the pending token and has not expired
+	 */
+	const confirm = (token: unknown): boolean => {
+		const current = pending;
+		pending = null;
+		if (!current || typeof token !== "string") return false;
+		if (now() > current.expiresAt) return false;
+		const given = Buffer.from(token, "hex");
+		return (
+			given.length === current.token.length &&
+			timingSafeEqual(given, current.token)
+		);
+	};
+
+	return { issue, confirm };
+};
+
+// ============================================================================
+// Wipe
+// ============================================================================
+
+/**
+ * Deletes every history item with its tags, collections, registers, sync
+ * tombstones and the trash. `secure_delete` overwrites the freed pages,
+ * the search index is rebuilt so no old terms remain, and VACUUM then
+ * rewrites the file so nothing deleted is left in it. The checkpoint
+ * empties the write-ahead log, which would otherwise still hold pages
+ * written before the wipe.
+ */
+export const wipeHistoryDatabase = (db: Database.Database): void => {
+	db.pragma("secure_delete = ON");
+	try {
+		db.transaction(() => {
+			for (const table of WIPED_TABLES) {
+				db.prepare(`DELETE FROM ${table}`).run();
+			}
+			db.exec("INSERT INTO history_fts(history_fts) VALUES ('rebuild')");
+		})();
+		db.exec("VACUUM");
+		checkpointWal(db);
+	} finally {
+		db.pragma("secure_delete = OFF");
+	}
+};
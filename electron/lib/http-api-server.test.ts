@@ -0,0 +1,280 @@
+import http from "node:http";
+import net from "node:net";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createHttpApiServer,
+	DEFAULT_SEARCH_LIMIT,
+	type HttpApiHandlers,
+	isAuthorized,
+	matchHttpApiRoute,
+	parseClipboardBody,
+	parseSearchParams,
+	toApiItem,
+} from "./http-api-server.js";
+
+const TOKEN = "0123456789abcdef0123456789abcdef";
+
+describe("matchHttpApiRoute", () => {
+	it("matches each endpoint", () => {
+		expect(matchHttpApiRoute("GET", "/v1/items")).toEqual({ name: "search" });
+		expect(matchHttpApiRoute("GET", "/v1/items/12")).toEqual({
+			name: "getItem",
+			id: 12,
+		});
+		expect(matchHttpApiRoute("POST", "/v1/clipboard")).toEqual({
+			name: "setClipboard",
+		});
+	});
+
+	it("rejects unknown paths and wrong methods", () => {
+		expect(() => matchHttpApiRoute("GET", "/v1/items/0")).toThrow("Not found");
+		expect(() => matchHttpApiRoute("GET", "/")).toThrow("Not found");
+		expect(() => matchHttpApiRoute("GET", "/v1/clipboard")).toThrow("Use POST");
+	});
+});
+
+describe("isAuthorized", () => {
+	it("accepts only the exact bearer token", () => {
+		expect(isAuthorized(`Bearer ${TOKEN}`, TOKEN)).toBe(true);
+		expect(isAuthorized(`Bearer ${TOKEN}x`, TOKEN)).toBe(false);
+		expect(isAuthorized(TOKEN, TOKEN)).toBe(false);
+		expect(isAuthorized(undefined, TOKEN)).toBe(false);
+		expect(isAuthorized("Bearer x", "")).toBe(false);
+	});
+});
+
+describe("parseSearchParams", () => {
+	it("defaults to the most recent items", () => {
+		expect(parseSearchParams(new URLSearchParams())).toEqual({
+			query: "",
+			limit: DEFAULT_SEARCH_LIMIT,
+		});
+		expect(parseSearchParams(new URLSearchParams("q=foo&limit=5"))).toEqual({
+			query: "foo",
+			limit: 5,
+		});
+	});
+
+	it("rejects an out-of-range limit", () => {
+		expect(() => parseSearchParams(new URLSearchParams("limit=0"))).toThrow(
+			"limit must be an integer from 1 to 200",
+		);
+	});
+});
+
+describe("parseClipboardBody", () => {
+	it("accepts an id or text", () => {
+		expect(parseClipboardBody('{"id":3}')).toEqual({ id: 3 });
+		expect(parseClipboardBody('{"text":"hi"}')).toEqual({ text: "hi" });
+	});
+
+	it("rejects anything else", () => {
+		expect(() => parseClipboardBody("nope")).toThrow("Expected a JSON body");
+		expect(() => parseClipboardBody('{"id":1,"text":"x"}')).toThrow(
+			"either id or text",
+		);
+		expect(() => parseClipboardBody('{"id":-1}')).toThrow(
+			"id must be a positive integer",
+		);
+		expect(() => parseClipboardBody("{}")).toThrow("Expected id or text");
+	});
+});
+
+describe("toApiItem", () => {
+	const row = (content: string, secret_match: string | null) => ({
+		id: 7,
+		type: "text",
+		content,
+		image_width: null,
+		image_height: null,
+		secret_match,
+		created_at: "2024-01-01 10:00:00",
+	});
+
+	it("previews text on one line", () => {
+		expect(toApiItem(row("SELECT *\nFROM users", null))).toEqual({
+			id: 7,
+			type: "text",
+			preview: "SELECT * FROM users",
+			created_at: "2024-01-01 10:00:00",
+		});
+	});
+
+	// Also what D-Bus, `clipctl list` and automation URL searches return
+	it("masks flagged secrets", () => {
+		expect(toApiItem(row("ghp_abcdef123456", "GitHub token")).preview).toBe(
+			"ghp_****",
+		);
+	});
+});
+
+describe("createHttpApiServer", () => {
+	const stops: (() => Promise<void>)[] = [];
+
+	const createHandlers = (): HttpApiHandlers => ({
+		search: vi.fn().mockReturnValue([]),
+		getItem: vi.fn().mockReturnValue(undefined),
+		copyItem: vi.fn().mockReturnValue(true),
+		setClipboardText: vi.fn(),
+	});
+
+	const startServer = async (
+		handlers: HttpApiHandlers,
+		isLocked = () => false,
+	) => {
+		const server = createHttpApiServer({
+			handlers,
+			getToken: () => TOKEN,
+			isLocked,
+		});
+		await server.start(0);
+		stops.push(server.stop);
+		return { server, port: server.getPort() as number };
+	};
+
+	/**
+	 * Sends a request and resolves to the status and parsed body.
+	 */
+	const send = (
+		port: number,
+		options: { method?: string; path: string; token?: string; body?: string },
+	): Promise<{ status: number; body: unknown }> =>
+		new Promise((resolve, reject) => {
+			const request = http.request(
+				{
+					host: "127.0.0.1",
+					port,
+					method: options.method ?? "GET",
+					path: options.path,
+					headers: { Authorization: `Bearer ${options.token ?? TOKEN}` },
+				},
+				(response) => {
+					let data = "";
+					response.setEncoding("utf-8");
+					response.on("data", (chunk: string) => {
+						data += chunk;
+					});
+					response.on("end", () =>
+						resolve({
+							status: response.statusCode ?? 0,
+							body: JSON.parse(data),
+						}),
+					);
+				},
+			);
+			request.on("error", reject);
+			request.end(options.body);
+		});
+
+	/**
+	 * Opens a WebSocket with a raw handshake and resolves to the socket and
+	 * the response head.
+	 */
+	const connectEvents = (
+		port: number,
+		path: string,
+	): Promise<{ socket: net.Socket; head: string }> =>
+		new Promise((resolve, reject) => {
+			const socket = net.createConnection(port, "127.0.0.1", () => {
+				socket.write(
+					[
+						`GET ${path} HTTP/1.1`,
+						"Host: 127.0.0.1",
+						"Upgrade: websocket",
+						"Connection: Upgrade",
+						"Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==",
+						"Sec-WebSocket-Version: 13",
+						"",
+						"",
+					].join("\r\n"),
+				);
+			});
+			socket.once("data", (chunk: Buffer) => {
+				resolve({ socket, head: chunk.toString("utf-8") });
+			});
+			socket.on("error", reject);
+		});
+
+	afterEach(async () => {
+		for (const stop of stops.splice(0)) await stop();
+	});
+
+	it("answers requests from the handlers", async () => {
+		const handlers = createHandlers();
+		vi.mocked(handlers.search).mockReturnValue([
+			{ id: 1, type: "text", preview: "foo", created_at: "2024-01-01" },
+		]);
+		const { port } = await startServer(handlers);
+
+		await expect(
+			send(port, { path: "/v1/items?q=foo&limit=5" }),
+		).resolves.toEqual({
+			status: 200,
+			body: [{ id: 1, type: "text", preview: "foo", created_at: "2024-01-01" }],
+		});
+		expect(handlers.search).toHaveBeenCalledWith("foo", 5);
+
+		await expect(
+			send(port, {
+				method: "POST",
+				path: "/v1/clipboard",
+				body: '{"text":"hi"}',
+			}),
+		).resolves.toEqual({ status: 200, body: { ok: true } });
+		expect(handlers.setClipboardText).toHaveBeenCalledWith("hi");
+	});
+
+	it("rejects requests without the token", async () => {
+		const handlers = createHandlers();
+		const { port } = await startServer(handlers);
+
+		await expect(
+			send(port, { path: "/v1/items", token: "wrong" }),
+		).resolves.toEqual({
+			status: 401,
+			body: { error: "Missing or invalid token" },
+		});
+		expect(handlers.search).not.toHaveBeenCalled();
+	});
+
+	it("reports missing items and a locked history", async () => {
+		let locked = false;
+		const { port } = await startServer(createHandlers(), () => locked);
+
+		await expect(send(port, { path: "/v1/items/9" })).resolves.toEqual({
+			status: 404,
+			body: { error: "History item not found: 9" },
+		});
+
+		locked = true;
+		await expect(send(port, { path: "/v1/items" })).resolves.toEqual({
+			status: 423,
+			body: { error: "History is locked" },
+		});
+	});
+
+	it("streams events to authorized WebSocket clients", async () => {
+		const { server, port } = await startServer(createHandlers());
+
+		const rejected = await connectEvents(port, "/v1/events?token=wrong");
+		expect(rejected.head).toMatch(/^HTTP\/1.1 401/);
+
+		const { socket, head } = await connectEvents(
+			port,
+			`/v1/events?token=${TOKEN}`,
+		);
+		expect(head).toContain("101 Switching Protocols");
+		expect(head).toContain(
+			"Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=",
+		);
+
+		const message = new Promise<string>((resolve) => {
+			socket.once("data", (chunk: Buffer) => {
+				resolve(chunk.subarray(2).toString("utf-8"));
+			});
+		});
+		server.broadcast({ type: "item.deleted", id: 3 });
+		await expect(message).resolves.toBe('{"type":"item.deleted","id":3}');
+		socket.destroy();
+	});
+});
@@ -0,0 +1,412 @@
+import crypto from "node:crypto";
+import http from "node:http";
+import type { Duplex } from "node:stream";
+import {
+	createEventStream,
+	type HistoryEvent,
+	isWebSocketUpgrade,
+} from "./event-stream.js";
+import type { HistoryRow } from "./history-repository.js";
+import { describeItem } from "./tray-recent.js";
+
+/**
+ * History item as returned by the HTTP API.
+ */
+export type HttpApiItem = {
+	id: number;
+	type: string;
+	/** One-line preview of the content */
+	preview: string;
+	created_at: string;
+};
+
+/**
+ * A single item with its content: the text (or one path per line for
+ * files), or null for images.
+ */
+export type HttpApiItemDetail = HttpApiItem & { content: string | null };
+
+/**
+ * What the app does for each endpoint.
+ */
+export type HttpApiHandlers = {
+	/** Matching items, best first; the most recent ones for an empty query */
+	search: (query: string, limit: number) => HttpApiItem[];
+	getItem: (id: number) => HttpApiItemDetail | undefined;
+	/** Places a stored item on the clipboard; false if it does not exist */
+	copyItem: (id: number) => boolean;
+	setClipboardText: (text: string) => void;
+};
+
+export type HttpApiRoute =
+	| { name: "search" }
+	| { name: "getItem"; id: number }
+	| { name: "setClipboard" };
+
+export type HttpApiServerDeps = {
+	handlers: HttpApiHandlers;
+	getToken: () => string;
+	/** History endpoints answer 423 while the app is locked */
+	isLocked: () => boolean;
+};
+
+/**
+ * Only bound to the loopback interface, never the network.
+ */
+const HOST = "127.0.0.1";
+
+export const DEFAULT_SEARCH_LIMIT = 20;
+const MAX_SEARCH_LIMIT = 200;
+const MAX_BODY_BYTES = 8_000_000;
+
+/**
+ * A failed request, answered with `status` and `{ error: message }`.
+ */
+type HttpApiError = Error & { status: number };
+
+const httpApiError = (status: number, message: string): HttpApiError =>
+	Object.assign(new Error(message), { status });
+
+const isHttpApiError = (error: unknown): error is HttpApiError =>
+	error instanceof Error &&
+	typeof (error as Partial<HttpApiError>).status === "number";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Describes a history item for the HTTP API, D-Bus, `clipctl list` and
+ * automation URLs.
+ * Pure function. Flagged secrets are previewed masked.
+ */
+export const toApiItem = (
+	item: Pick<
+		HistoryRow,
+		| "id"
+		| "type"
+		| "content"
+		| "image_width"
+		| "image_height"
+		| "secret_match"
+		| "created_at"
+	>,
+): HttpApiItem => ({
+	id: item.id,
+	type: item.type,
+	preview: describeItem(item),
+	created_at: item.created_at,
+});
+
+/**
+ * Maps a request to an endpoint.
+ * Pure function.
+ *
+ * @throws with status 404 for unknown paths, 405 for the wrong method
+ */
+export const matchHttpApiRoute = (
+	method: string,
+	pathname: string,
+): HttpApiRoute => {
+	const requireMethod = (
+		allowed: string,
+		route: HttpApiRoute,
+	): HttpApiRoute => {
+		if (method !== allowed) {
+			throw httpApiError(405, `Use ${allowed} for ${pathname}`);
+		}
+		return route;
+	};
+
+	if (pathname === "/v1/items") {
+		return requireMethod("GET", { name: "search" });
+	}
+	if (pathname === "/v1/clipboard") {
+		return requireMethod("POST", { name: "setClipboard" });
+	}
+	const item = /^\/v1\/items\/(\d+)$/.exec(pathname);
+	if (item) {
+		const id = Number(item[1]);
+		if (Number.isSafeInteger(id) && id > 0) {
+			return requireMethod("GET", { name: "getItem", id });
+		}
+	}
+	throw httpApiError(404, `Not found: ${pathname}`);
+};
+
+/**
+ * Compares a token from a client with the expected one in constant time.
+ * Pure function.
+ */
+const isTokenMatch = (given: string | null, token: string): boolean => {
+	if (!given || !token) return false;
+	const givenBytes = Buffer.from(given);
+	const expectedBytes = Buffer.from(token);
+	return (
+		givenBytes.length === expectedBytes.length &&
+		crypto.timingSafeEqual(givenBytes, expectedBytes)
+	);
+};
+
+/**
+ * Checks an `Authorization: Bearer <token>` header.
+ * Pure function.
+ */
+export const isAuthorized = (
+	header: string | undefined,
+	token: string,
+): boolean => {
+	const match = /^Bearer (.+)$/.exec(header ?? "");
+	return isTokenMatch(match ? match[1] : null, token);
+};
+
+/**
+ * Reads `q` and `limit` from a search query string.
+ * Pure function.
+ *
+ * @throws with status 400 if `limit` is not an integer from 1 to 200
+ */
+export const parseSearchParams = (
+	params: URLSearchParams,
+): { query: string; limit: number } => {
+	const query = params.get("q") ?? "";
+	const rawLimit = params.get("limit");
+	if (rawLimit === null) {
+		return { query, limit: DEFAULT_SEARCH_LIMIT };
+	}
+	const limit = Number(rawLimit);
+	if (!Number.isInteger(limit) || limit < 1 || limit > MAX_SEARCH_LIMIT) {
+		throw httpApiError(
+			400,
+			`limit must be an integer from 1 to ${MAX_SEARCH_LIMIT}`,
+		);
+	}
+	return { query, limit };
+};
+
+/**
+ * Validates a `POST /v1/clipboard` body: `{ "id": n }` to copy a stored
+ * item, or `{ "text": "..." }` to copy new text.
+ * Pure function.
+ *
+ * @throws with status 400 for anything else
+ */
+export const parseClipboardBody = (
+	body: string,
+): { id: number } | { text: string } => {
+	let input: unknown;
+	try {
+		input = JSON.parse(body);
+	} catch {
+		throw httpApiError(400, "Expected a JSON body");
+	}
+	if (typeof input !== "object" || input === null) {
+		throw httpApiError(400, "Expected a JSON object");
+	}
+
+	const { id, text } = input as Record<string, unknown>;
+	if (id !== undefined && text !== undefined) {
+		throw httpApiError(400, "Send either id or text, not both");
+	}
+	if (id !== undefined) {
+		if (typeof id !== "number" || !Number.isInteger(id) || id <= 0) {
+			throw httpApiError(400, "id must be a positive integer");
+		}
+		return { id };
+	}
+	if (typeof text !== "string") {
+		throw httpApiError(400, "Expected id or text");
+	}
+	return { text };
+};
+
+// ============================================================================
+// HTTP API Server
+// ============================================================================
+
+const readBody = (request: http.IncomingMessage): Promise<string> =>
+	new Promise((resolve, reject) => {
+		const chunks: Buffer[] = [];
+		let size = 0;
+		request.on("data", (chunk: Buffer) => {
+			size += chunk.length;
+			if (size > MAX_BODY_BYTES) {
+				reject(httpApiError(413, "Request body too large"));
+				// Drain the rest without buffering it
+				request.removeAllListeners("data");
+				request.resume();
+				return;
+			}
+			chunks.push(chunk);
+		});
+		request.on("end", () => resolve(Buffer.concat(chunks).toString("utf-8")));
+		request.on("error", reject);
+	});
+
+const sendJson = (
+	response: http.ServerResponse,
+	status: number,
+	body: unknown,
+): void => {
+	response.writeHead(status, {
+		"Content-Type": "application/json; charset=utf-8",
+		...(status === 401 ? { "WWW-Authenticate": "Bearer" } : {}),
+	});
+	response.end(JSON.stringify(body));
+};
+
+/**
+ * Creates the opt-in local HTTP API for launchers and other tools:
+ *
+ * - `GET /v1/items?q=&limit=` searches history
+ * - `GET /v1/items/:id` returns one item with its content
+ * - `POST /v1/clipboard` with `{ id }` or `{ text }` sets the clipboard
+ * - `GET /v1/events` upgrades to a WebSocket streaming `HistoryEvent`s
+ *
+ * Every request needs the bearer token. No CORS headers are sent, so web
+ * pages cannot call it.
+ */
+export const createHttpApiServer = (deps: HttpApiServerDeps) => {
+	let server: http.Server | null = null;
+	let port: number | null = null;
+	const eventStream = createEventStream();
+
+	const dispatch = async (
+		request: http.IncomingMessage,
+		url: URL,
+	): Promise<unknown> => {
+		const route = matchHttpApiRoute(request.method ?? "GET", url.pathname);
+		if (deps.isLocked()) {
+			throw httpApiError(423, "History is locked");
+		}
+
+		switch (route.name) {
+			case "search": {
+				const { query, limit } = parseSearchParams(url.searchParams);
+				return deps.handlers.search(query, limit);
+			}
+			case "getItem": {
+				const item = deps.handlers.getItem(route.id);
+				if (!item) {
+					throw httpApiError(404, `History item not found: ${route.id}`);
+				}
+				return item;
+			}
+			case "setClipboard": {
+				const body = parseClipboardBody(await readBody(request));
+				if ("id" in body) {
+					if (!deps.handlers.copyItem(body.id)) {
+						throw httpApiError(404, `History item not found: ${body.id}`);
+					}
+				} else {
+					deps.handlers.setClipboardText(body.text);
+				}
+				return { ok: true };
+			}
+		}
+	};
+
+	const handleRequest = (
+		request: http.IncomingMessage,
+		response: http.ServerResponse,
+	): void => {
+		if (!isAuthorized(request.headers.authorization, deps.getToken())) {
+			sendJson(response, 401, { error: "Missing or invalid token" });
+			return;
+		}
+
+		const url = new URL(request.url ?? "/", `http://${HOST}`);
+		dispatch(request, url)
+			.then((result) => sendJson(response, 200, result))
+			.catch((error) => {
+				if (isHttpApiError(error)) {
+					sendJson(response, error.status, { error: error.message });
+					return;
+				}
+				console.error("HTTP API request failed:", error);
+				sendJson(response, 500, {
+					error: error instanceof Error ? error.message : String(error),
+				});
+			});
+	};
+
+	/**
+	 * Accepts `/v1/events` WebSocket connections. Clients that cannot set
+	 * headers, like browser-based plugins, may pass `?token=` instead.
+	 */
+	const handleUpgrade = (request: http.IncomingMessage, socket: Duplex) => {
+		const url = new URL(request.url ?? "/", `http://${HOST}`);
+		const token = deps.getToken();
+		const reject = (status: number, reason: string): void => {
+			socket.end(`HTTP/1.1 ${status} ${reason}\r\nConnection: close\r\n\r\n`);
+		};
+
+		if (url.pathname !== "/v1/events" || !isWebSocketUpgrade(request.headers)) {
+			reject(404, "Not Found");
+		} else if (
+			!isAuthorized(request.headers.authorization, token) &&
+			!isTokenMatch(url.searchParams.get("token"), token)
+		) {
+			reject(401, "Unauthorized");
+		} else if (deps.isLocked()) {
+			reject(423, "Locked");
+		} else {
+			eventStream.accept(request, socket);
+		}
+	};
+
+	const stop = (): Promise<void> =>
+		new Promise((resolve) => {
+			const current = server;
+			server = null;
+			port = null;
+			if (!current) {
+				resolve();
+				return;
+			}
+			eventStream.closeAll();
+			current.close(() => resolve());
+			current.closeAllConnections();
+		});
+
+	/**
+	 * Starts listening on `127.0.0.1:<port>`, restarting if it was already
+	 * running on another port.
+	 */
+	const start = async (nextPort: number): Promise<void> => {
+		if (server && port === nextPort) return;
+		await stop();
+
+		const next = http.createServer(handleRequest);
+		next.on("upgrade", handleUpgrade);
+		await new Promise<void>((resolve, reject) => {
+			next.once("error", reject);
+			next.listen(nextPort, HOST, () => {
+				next.off("error", reject);
+				resolve();
+			});
+		});
+		next.on("error", (error) => {
+			console.error("HTTP API server error:", error);
+		});
+		server = next;
+		const address = next.address();
+		port = typeof address === "object" && address ? address.port : nextPort;
+	};
+
+	/**
+	 * Port the API is listening on, or null when stopped.
+	 */
+	const getPort = (): number | null => port;
+
+	/**
+	 * Sends a history change to every event stream client.
+	 */
+	const broadcast = (event: HistoryEvent): void => {
+		eventStream.broadcast(event);
+	};
+
+	return { start, stop, getPort, broadcast };
+};
+
+export type HttpApiServer = ReturnType<typeof createHttpApiServer>;
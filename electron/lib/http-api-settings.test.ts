@@ -0,0 +1,95 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createHttpApiSettings,
+	DEFAULT_HTTP_API_PORT,
+	getHttpApiSettingsPath,
+	type HttpApiSettings,
+	parseHttpApiSettings,
+} from "./http-api-settings.js";
+
+const current: HttpApiSettings = {
+	enabled: false,
+	port: DEFAULT_HTTP_API_PORT,
+	token: "a".repeat(32),
+};
+
+describe("parseHttpApiSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(parseHttpApiSettings({}, current)).toEqual(current);
+		expect(
+			parseHttpApiSettings({ enabled: true, port: 8080 }, current),
+		).toEqual({ ...current, enabled: true, port: 8080 });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseHttpApiSettings(null, current)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseHttpApiSettings({ enabled: 1 }, current)).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parseHttpApiSettings({ port: 80 }, current)).toThrow(
+			"port must be an integer from 1024 to 65535",
+		);
+		expect(() => parseHttpApiSettings({ token: "short" }, current)).toThrow(
+			"token must be 16-256 characters",
+		);
+	});
+});
+
+describe("createHttpApiSettings", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-http-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("is off by default with a saved random token", () => {
+		const userDataPath = createTempUserDataPath();
+		const settings = createHttpApiSettings({ userDataPath }).getSettings();
+
+		expect(settings).toMatchObject({
+			enabled: false,
+			port: DEFAULT_HTTP_API_PORT,
+		});
+		expect(settings.token).toMatch(/^[0-9a-f]{48}$/);
+		expect(createHttpApiSettings({ userDataPath }).getSettings()).toEqual(
+			settings,
+		);
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"keeps the settings file private to the user",
+		() => {
+			const userDataPath = createTempUserDataPath();
+			createHttpApiSettings({ userDataPath });
+
+			const { mode } = fs.statSync(getHttpApiSettingsPath(userDataPath));
+			expect(mode & 0o777).toBe(0o600);
+		},
+	);
+
+	it("regenerates the token", () => {
+		const userDataPath = createTempUserDataPath();
+		const store = createHttpApiSettings({ userDataPath });
+		const { token } = store.updateSettings({ enabled: true });
+
+		const next = store.regenerateToken();
+		expect(next.token).not.toBe(token);
+		expect(next.enabled).toBe(true);
+		expect(createHttpApiSettings({ userDataPath }).getSettings().token).toBe(
+			next.token,
+		);
+	});
+});
@@ -0,0 +1,173 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted settings for the local HTTP API.
+ */
+export type HttpApiSettings = {
+	/** Serve the API on localhost; off until the user opts in */
+	enabled: boolean;
+	port: number;
+	/** Bearer token every request must send */
+	token: string;
+};
+
+export const DEFAULT_HTTP_API_PORT = 47813;
+
+const MIN_PORT = 1024;
+const MAX_PORT = 65535;
+const MIN_TOKEN_LENGTH = 16;
+const MAX_TOKEN_LENGTH = 256;
+
+const HTTP_API_FILENAME = "http-api.json";
+
+/**
+ * Creates a random token for API clients.
+ */
+const generateToken = (): string => crypto.randomBytes(24).toString("hex");
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates an HTTP API settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean, `port` is not an integer from
+ *   1024 to 65535, or `token` is not a 16-256 character string
+ */
+export const parseHttpApiSettings = (
+	input: unknown,
+	current: HttpApiSettings,
+): HttpApiSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid HTTP API settings: expected an object");
+	}
+
+	const { enabled, port, token } = input as Partial<
+		Record<keyof HttpApiSettings, unknown>
+	>;
+	const next: HttpApiSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error("Invalid HTTP API settings: enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (port !== undefined) {
+		if (
+			typeof port !== "number" ||
+			!Number.isInteger(port) ||
+			port < MIN_PORT ||
+			port > MAX_PORT
+		) {
+			throw new Error(
+				`Invalid HTTP API settings: port must be an integer from ${MIN_PORT} to ${MAX_PORT}`,
+			);
+		}
+		next.port = port;
+	}
+
+	if (token !== undefined) {
+		if (
+			typeof token !== "string" ||
+			token.length < MIN_TOKEN_LENGTH ||
+			token.length > MAX_TOKEN_LENGTH
+		) {
+			throw new Error(
+				`Invalid HTTP API settings: token must be ${MIN_TOKEN_LENGTH}-${MAX_TOKEN_LENGTH} characters`,
+			);
+		}
+		next.token = token;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getHttpApiSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, HTTP_API_FILENAME);
+
+const writeHttpApiSettingsToFile = (
+	filePath: string,
+	settings: HttpApiSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ * A fresh token is generated and saved when none is stored yet.
+ */
+const readHttpApiSettingsFromFile = (filePath: string): HttpApiSettings => {
+	const defaults: HttpApiSettings = {
+		enabled: false,
+		port: DEFAULT_HTTP_API_PORT,
+		token: "",
+	};
+
+	let settings = defaults;
+	if (fs.existsSync(filePath)) {
+		try {
+			const raw = fs.readFileSync(filePath, "utf-8");
+			settings = parseHttpApiSettings(JSON.parse(raw), defaults);
+		} catch (error) {
+			console.error("Failed to read HTTP API settings, using defaults:", error);
+		}
+	}
+
+	if (!settings.token) {
+		settings = { ...settings, token: generateToken() };
+		writeHttpApiSettingsToFile(filePath, settings);
+	}
+	return settings;
+};
+
+// ============================================================================
+// HTTP API Settings Module
+// ============================================================================
+
+export type HttpApiSettingsDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the HTTP API settings store. The file is readable by the current
+ * user only, since it holds the token.
+ */
+export const createHttpApiSettings = (deps: HttpApiSettingsDeps) => {
+	const filePath = getHttpApiSettingsPath(deps.userDataPath);
+	let settings = readHttpApiSettingsFromFile(filePath);
+
+	const getSettings = (): HttpApiSettings => ({ ...settings });
+
+	const save = (next: HttpApiSettings): HttpApiSettings => {
+		writeHttpApiSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const updateSettings = (input: unknown): HttpApiSettings =>
+		save(parseHttpApiSettings(input, settings));
+
+	/**
+	 * Replaces the token, so clients using the old one are rejected.
+	 */
+	const regenerateToken = (): HttpApiSettings =>
+		save({ ...settings, token: generateToken() });
+
+	return { getSettings, updateSettings, regenerateToken };
+};
+
+export type HttpApiSettingsStore = ReturnType<typeof createHttpApiSettings>;
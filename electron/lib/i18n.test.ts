@@ -0,0 +1,85 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createI18n,
+	createTranslator,
+	getLanguagePath,
+	parseLanguageSetting,
+	resolveLanguage,
+} from "./i18n.js";
+
+describe("resolveLanguage", () => {
+	it("uses the first supported system language for system", () => {
+		expect(resolveLanguage("system", ["ja-JP", "de-AT", "fr"])).toBe("de");
+		expect(resolveLanguage("system", ["es_MX"])).toBe("es");
+		expect(resolveLanguage("system", ["ja-JP"])).toBe("en");
+		expect(resolveLanguage("fr", ["de-DE"])).toBe("fr");
+	});
+});
+
+describe("parseLanguageSetting", () => {
+	it("rejects unsupported languages", () => {
+		expect(parseLanguageSetting("system")).toBe("system");
+		expect(() => parseLanguageSetting("xx")).toThrow(
+			"Invalid language: must be system or one of en, de, fr, es",
+		);
+	});
+});
+
+describe("createTranslator", () => {
+	it("fills placeholders and picks the plural form", () => {
+		const t = createTranslator("de");
+
+		expect(t("tray.snoozedUntil", { time: "14:30" })).toBe(
+			"Ausgesetzt bis 14:30",
+		);
+		expect(t("tray.itemCount", { count: 1 })).toBe("1 Eintrag");
+		expect(t("tray.itemCount", { count: 3 })).toBe("3 Einträge");
+		expect(createTranslator("fr")("tray.itemCount", { count: 0 })).toBe(
+			"0 élément",
+		);
+	});
+});
+
+describe("createI18n", () => {
+	let userDataPath: string;
+
+	beforeEach(() => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "i18n-"));
+	});
+
+	afterEach(() => {
+		fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("follows the system language until one is set", () => {
+		const onChange = vi.fn();
+		const i18n = createI18n({
+			userDataPath,
+			getSystemLocales: () => ["es-ES"],
+			onChange,
+		});
+		expect(i18n.t("tray.quit")).toBe("Salir");
+
+		expect(i18n.setLanguage("fr")).toEqual({ language: "fr", active: "fr" });
+		expect(i18n.t("tray.quit")).toBe("Quitter");
+		expect(onChange).toHaveBeenCalledTimes(1);
+
+		i18n.setLanguage("fr");
+		expect(onChange).toHaveBeenCalledTimes(1);
+		expect(
+			createI18n({ userDataPath, getSystemLocales: () => [] }).getStatus(),
+		).toEqual({ language: "fr", active: "fr" });
+	});
+
+	it("follows the system when the saved setting is invalid", () => {
+		fs.writeFileSync(getLanguagePath(userDataPath), '{"language":"xx"}');
+		vi.spyOn(console, "error").mockImplementation(() => {});
+
+		const i18n = createI18n({ userDataPath, getSystemLocales: () => ["de"] });
+
+		expect(i18n.getStatus()).toEqual({ language: "system", active: "de" });
+	});
+});
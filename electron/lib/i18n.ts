@@ -0,0 +1,369 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Languages the tray, menus and notifications are translated into.
+ */
+export const LANGUAGES = ["en", "de", "fr", "es"] as const;
+
+export type Language = (typeof LANGUAGES)[number];
+
+/**
+ * A translated string, or one per plural category chosen by `count`.
+ * `{name}` placeholders are filled from the parameters.
+ */
+type Message = string | { one: string; other: string };
+
+const EN_MESSAGES = {
+	"tray.open": "Open",
+	"tray.pauseCapture": "Pause Capture",
+	"tray.snoozeCapture": "Snooze Capture",
+	"tray.snoozedUntil": "Snoozed Until {time}",
+	"tray.minutes": { one: "{count} Minute", other: "{count} Minutes" },
+	"tray.keepOnTop": "Keep Window on Top",
+	"tray.appendCopies": "Append Copies",
+	"tray.profile": "Profile: {name}",
+	"tray.quit": "Quit",
+	"tray.status": "Clipboard Manager",
+	"tray.statusPaused": "Clipboard Manager (capture paused)",
+	"tray.statusSnoozed": "Clipboard Manager (capture snoozed until {time})",
+	"tray.latest": "Latest: {preview}",
+	"tray.itemCount": { one: "{count} item", other: "{count} items" },
+	"tray.lockUnprotected": "App Lock Off: No OS Authentication",
+	"update.checking": "Checking for Updates…",
+	"update.download": "Download Update {version}",
+	"update.downloading": "Downloading Update… {percent}%",
+	"update.install": "Install Update {version}",
+	"update.installing": "Installing Update…",
+	"update.check": "Check for Updates…",
+	"notification.secretCopied.title": "A possible {name} was just copied",
+	"notification.secretCopied.body": "It is masked in history.",
+	"notification.syncCompleted.title": {
+		one: "Sync completed with {count} new item",
+		other: "Sync completed with {count} new items",
+	},
+	"notification.syncCompleted.body": "Received from your other devices.",
+	"notification.repaired.title": "Clipboard history was repaired",
+	"notification.repaired.body":
+		"The database was damaged. What could be read was kept; the damaged file was saved next to it.",
+	"notification.lockUnprotected.title": "Clipboard history is not locked",
+	"notification.lockUnprotected.body":
+		"The app lock is on, but this system offers no OS authentication to unlock it with.",
+	"jumpList.pinned": "Pinned",
+	"jumpList.recent": "Recent",
+	"jumpList.copy": "Copy to the clipboard",
+	"jumpList.pauseCapture": "Pause capture",
+	"jumpList.pauseCaptureDescription": "Stop recording copies",
+	"jumpList.resumeCapture": "Resume capture",
+	"jumpList.resumeCaptureDescription": "Record copies again",
+	"jumpList.openHistory": "Open history",
+	"jumpList.openHistoryDescription": "Show the history window",
+} satisfies Record<string, Message>;
+
+export type MessageId = keyof typeof EN_MESSAGES;
+
+type Messages = Record<MessageId, Message>;
+
+const MESSAGES: Record<Language, Messages> = {
+	en: EN_MESSAGES,
+	de: {
+		"tray.open": "Öffnen",
+		"tray.pauseCapture": "Erfassung pausieren",
+		"tray.snoozeCapture": "Erfassung aussetzen",
+		"tray.snoozedUntil": "Ausgesetzt bis {time}",
+		"tray.minutes": { one: "{count} Minute", other: "{count} Minuten" },
+		"tray.keepOnTop": "Fenster im Vordergrund halten",
+		"tray.appendCopies": "Kopien anhängen",
+		"tray.profile": "Profil: {name}",
+		"tray.quit": "Beenden",
+		"tray.status": "Clipboard Manager",
+		"tray.statusPaused": "Clipboard Manager (Erfassung pausiert)",
+		"tray.statusSnoozed": "Clipboard Manager (Erfassung ausgesetzt bis {time})",
+		"tray.latest": "Zuletzt: {preview}",
+		"tray.itemCount": { one: "{count} Eintrag", other: "{count} Einträge" },
+		"tray.lockUnprotected": "App-Sperre aus: Keine OS-Anmeldung",
+		"update.checking": "Suche nach Updates…",
+		"update.download": "Update {version} laden",
+		"update.downloading": "Update wird geladen… {percent} %",
+		"update.install": "Update {version} installieren",
+		"update.installing": "Update wird installiert…",
+		"update.check": "Nach Updates suchen…",
+		"notification.secretCopied.title": "Mögliches Geheimnis kopiert: {name}",
+		"notification.secretCopied.body": "Es wird im Verlauf maskiert.",
+		"notification.syncCompleted.title": {
+			one: "Synchronisierung abgeschlossen: {count} neuer Eintrag",
+			other: "Synchronisierung abgeschlossen: {count} neue Einträge",
+		},
+		"notification.syncCompleted.body": "Von deinen anderen Geräten empfangen.",
+		"notification.repaired.title": "Der Verlauf wurde repariert",
+		"notification.repaired.body":
+			"Die Datenbank war beschädigt. Was lesbar war, wurde behalten; die beschädigte Datei liegt daneben.",
+		"notification.lockUnprotected.title": "Der Verlauf ist nicht gesperrt",
+		"notification.lockUnprotected.body":
+			"Die App-Sperre ist an, aber dieses System bietet keine OS-Anmeldung zum Entsperren.",
+		"jumpList.pinned": "Angeheftet",
+		"jumpList.recent": "Zuletzt",
+		"jumpList.copy": "In die Zwischenablage kopieren",
+		"jumpList.pauseCapture": "Erfassung pausieren",
+		"jumpList.pauseCaptureDescription": "Keine Kopien mehr aufzeichnen",
+		"jumpList.resumeCapture": "Erfassung fortsetzen",
+		"jumpList.resumeCaptureDescription": "Kopien wieder aufzeichnen",
+		"jumpList.openHistory": "Verlauf öffnen",
+		"jumpList.openHistoryDescription": "Das Verlaufsfenster anzeigen",
+	},
+	fr: {
+		"tray.open": "Ouvrir",
+		"tray.pauseCapture": "Suspendre la capture",
+		"tray.snoozeCapture": "Mettre la capture en veille",
+		"tray.snoozedUntil": "En veille jusqu’à {time}",
+		"tray.minutes": { one: "{count} minute", other: "{count} minutes" },
+		"tray.keepOnTop": "Garder la fenêtre au premier plan",
+		"tray.appendCopies": "Ajouter les copies",
+		"tray.profile": "Profil : {name}",
+		"tray.quit": "Quitter",
+		"tray.status": "Clipboard Manager",
+		"tray.statusPaused": "Clipboard Manager (capture suspendue)",
+		"tray.statusSnoozed":
+			"Clipboard Manager (capture en veille jusqu’à {time})",
+		"tray.latest": "Dernier : {preview}",
+		"tray.itemCount": { one: "{count} élément", other: "{count} éléments" },
+		"tray.lockUnprotected": "Verrouillage inactif : aucune authentification",
+		"update.checking": "Recherche de mises à jour…",
+		"update.download": "Télécharger la mise à jour {version}",
+		"update.downloading": "Téléchargement de la mise à jour… {percent} %",
+		"update.install": "Installer la mise à jour {version}",
+		"update.installing": "Installation de la mise à jour…",
+		"update.check": "Rechercher des mises à jour…",
+		"notification.secretCopied.title": "Secret possible copié : {name}",
+		"notification.secretCopied.body": "Il est masqué dans l’historique.",
+		"notification.syncCompleted.title": {
+			one: "Synchronisation terminée : {count} nouvel élément",
+			other: "Synchronisation terminée : {count} nouveaux éléments",
+		},
+		"notification.syncCompleted.body": "Reçus de vos autres appareils.",
+		"notification.repaired.title": "L’historique a été réparé",
+		"notification.repaired.body":
+			"La base de données était endommagée. Ce qui pouvait être lu a été conservé ; le fichier endommagé a été enregistré à côté.",
+		"notification.lockUnprotected.title": "L’historique n’est pas verrouillé",
+		"notification.lockUnprotected.body":
+			"Le verrouillage est activé, mais ce système n’offre aucune authentification pour le déverrouiller.",
+		"jumpList.pinned": "Épinglés",
+		"jumpList.recent": "Récents",
+		"jumpList.copy": "Copier dans le presse-papiers",
+		"jumpList.pauseCapture": "Suspendre la capture",
+		"jumpList.pauseCaptureDescription": "Ne plus enregistrer les copies",
+		"jumpList.resumeCapture": "Reprendre la capture",
+		"jumpList.resumeCaptureDescription": "Enregistrer à nouveau les copies",
+		"jumpList.openHistory": "Ouvrir l’historique",
+		"jumpList.openHistoryDescription": "Afficher la fenêtre de l’historique",
+	},
+	es: {
+		"tray.open": "Abrir",
+		"tray.pauseCapture": "Pausar captura",
+		"tray.snoozeCapture": "Posponer captura",
+		"tray.snoozedUntil": "Pospuesta hasta las {time}",
+		"tray.minutes": { one: "{count} minuto", other: "{count} minutos" },
+		"tray.keepOnTop": "Mantener la ventana encima",
+		"tray.appendCopies": "Anexar copias",
+		"tray.profile": "Perfil: {name}",
+		"tray.quit": "Salir",
+		"tray.status": "Clipboard Manager",
+		"tray.statusPaused": "Clipboard Manager (captura en pausa)",
+		"tray.statusSnoozed":
+			"Clipboard Manager (captura pospuesta hasta las {time})",
+		"tray.latest": "Último: {preview}",
+		"tray.itemCount": { one: "{count} elemento", other: "{count} elementos" },
+		"tray.lockUnprotected": "Bloqueo inactivo: sin autenticación",
+		"update.checking": "Buscando actualizaciones…",
+		"update.download": "Descargar la actualización {version}",
+		"update.downloading": "Descargando la actualización… {percent} %",
+		"update.install": "Instalar la actualización {version}",
+		"update.installing": "Instalando la actualización…",
+		"update.check": "Buscar actualizaciones…",
+		"notification.secretCopied.title": "Posible secreto copiado: {name}",
+		"notification.secretCopied.body": "Aparece oculto en el historial.",
+		"notification.syncCompleted.title": {
+			one: "Sincronización completada con {count} elemento nuevo",
+			other: "Sincronización completada con {count} elementos nuevos",
+		},
+		"notification.syncCompleted.body": "Recibidos de tus otros dispositivos.",
+		"notification.repaired.title": "Se reparó el historial",
+		"notification.repaired.body":
+			"La base de datos estaba dañada. Se conservó lo que se pudo leer; el archivo dañado se guardó junto a ella.",
+		"notification.lockUnprotected.title": "El historial no está bloqueado",
+		"notification.lockUnprotected.body":
+			"El bloqueo está activado, pero este sistema no ofrece autenticación para desbloquearlo.",
+		"jumpList.pinned": "Fijados",
+		"jumpList.recent": "Recientes",
+		"jumpList.copy": "Copiar al portapapeles",
+		"jumpList.pauseCapture": "Pausar captura",
+		"jumpList.pauseCaptureDescription": "Dejar de registrar copias",
+		"jumpList.resumeCapture": "Reanudar captura",
+		"jumpList.resumeCaptureDescription": "Volver a registrar copias",
+		"jumpList.openHistory": "Abrir historial",
+		"jumpList.openHistoryDescription": "Mostrar la ventana del historial",
+	},
+};
+
+/**
+ * Looks up a message in the active language.
+ */
+export type Translate = (
+	id: MessageId,
+	params?: Record<string, string | number>,
+) => string;
+
+/**
+ * The language setting: follow the system, or one of {@link LANGUAGES}.
+ */
+export type LanguageSetting = Language | "system";
+
+/**
+ * The saved setting plus the language it resolved to.
+ */
+export type LanguageStatus = {
+	language: LanguageSetting;
+	active: Language;
+};
+
+const LANGUAGE_FILENAME = "language.json";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const isLanguage = (value: unknown): value is Language =>
+	LANGUAGES.includes(value as Language);
+
+/**
+ * Validates a language setting.
+ * Pure function.
+ *
+ * @throws if it is neither `system` nor a supported language
+ */
+export const parseLanguageSetting = (input: unknown): LanguageSetting => {
+	if (input === "system" || isLanguage(input)) return input;
+	throw new Error(
+		`Invalid language: must be system or one of ${LANGUAGES.join(", ")}`,
+	);
+};
+
+/**
+ * The language to show: the setting, or for `system` the first
+ * preferred system locale with a supported language (`de-AT` is `de`),
+ * falling back to English.
+ * Pure function.
+ */
+export const resolveLanguage = (
+	setting: LanguageSetting,
+	systemLocales: readonly string[],
+): Language => {
+	if (setting !== "system") return setting;
+	for (const locale of systemLocales) {
+		const base = locale.split(/[-_]/)[0].toLowerCase();
+		if (isLanguage(base)) return base;
+	}
+	return "en";
+};
+
+/**
+ * Creates a translator for a language; `count` also picks the plural
+ * form.
+ * Pure function.
+ */
+export const createTranslator = (language: Language): Translate => {
+	const messages = MESSAGES[language];
+	const plurals = new Intl.PluralRules(language);
+	return (id, params = {}) => {
+		const message = messages[id];
+		const template =
+			typeof message === "string"
+				? message
+				: plurals.select(Number(params.count)) === "one"
+					? message.one
+					: message.other;
+		return template.replace(/\{(\w+)\}/g, (placeholder, name: string) =>
+			name in params ? String(params[name]) : placeholder,
+		);
+	};
+};
+
+/**
+ * English strings, used wherever no translator is passed.
+ */
+export const translateEnglish = createTranslator("en");
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getLanguagePath = (userDataPath: string) =>
+	path.join(userDataPath, LANGUAGE_FILENAME);
+
+/**
+ * Reads the saved setting, following the system if missing or invalid.
+ */
+const readLanguageFromFile = (filePath: string): LanguageSetting => {
+	if (!fs.existsSync(filePath)) return "system";
+
+	try {
+		const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		return parseLanguageSetting(raw?.language);
+	} catch (error) {
+		console.error("Failed to read language setting:", error);
+		return "system";
+	}
+};
+
+const writeLanguageToFile = (filePath: string, language: LanguageSetting) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify({ language }, null, 2), "utf-8");
+};
+
+// ============================================================================
+// I18n Module
+// ============================================================================
+
+export type I18nDeps = {
+	userDataPath: string;
+	/** Preferred system locales, most preferred first */
+	getSystemLocales: () => string[];
+	/** Called after the language changed, to rebuild the tray and menus */
+	onChange?: (status: LanguageStatus) => void;
+};
+
+/**
+ * Creates the main process's translations for the tray, menus, jump
+ * list and notifications. The renderer is not translated here.
+ */
+export const createI18n = (deps: I18nDeps) => {
+	const filePath = getLanguagePath(deps.userDataPath);
+	let language = readLanguageFromFile(filePath);
+	let active = resolveLanguage(language, deps.getSystemLocales());
+	let translate = createTranslator(active);
+
+	const getStatus = (): LanguageStatus => ({ language, active });
+
+	const t: Translate = (id, params) => translate(id, params);
+
+	/**
+	 * Saves the setting and switches to the language it resolves to.
+	 * @throws if the language is not supported
+	 */
+	const setLanguage = (input: unknown): LanguageStatus => {
+		const next = parseLanguageSetting(input);
+		writeLanguageToFile(filePath, next);
+		language = next;
+		const resolved = resolveLanguage(language, deps.getSystemLocales());
+		if (resolved !== active) {
+			active = resolved;
+			translate = createTranslator(active);
+			deps.onChange?.(getStatus());
+		}
+		return getStatus();
+	};
+
+	return { getStatus, setLanguage, t };
+};
+
+export type I18n = ReturnType<typeof createI18n>;
@@ -0,0 +1,99 @@
+import fs from "node:fs";
+import { describe, expect, it, vi } from "vitest";
+import {
+	createImageConverter,
+	imageFormatForPath,
+	parseImageConversion,
+	toImageClipboardFormat,
+} from "./image-conversion.js";
+
+describe("parseImageConversion", () => {
+	it("defaults the quality", () => {
+		expect(parseImageConversion({ format: "jpeg" })).toEqual({
+			format: "jpeg",
+			quality: 90,
+		});
+		expect(parseImageConversion({ format: "webp", quality: 40 })).toEqual({
+			format: "webp",
+			quality: 40,
+		});
+	});
+
+	it("rejects unknown formats and out-of-range qualities", () => {
+		expect(() => parseImageConversion({ format: "gif" })).toThrow(
+			"Invalid image conversion: format must be one of png, jpeg, webp",
+		);
+		for (const quality of [0, 101, 50.5, "80"]) {
+			expect(() => parseImageConversion({ format: "png", quality })).toThrow(
+				"quality must be an integer from 1 to 100",
+			);
+		}
+		expect(() => parseImageConversion(null)).toThrow("expected an object");
+	});
+});
+
+describe("imageFormatForPath", () => {
+	it("reads the format from the extension, PNG otherwise", () => {
+		expect(imageFormatForPath("/tmp/photo.JPG")).toBe("jpeg");
+		expect(imageFormatForPath("/tmp/photo.webp")).toBe("webp");
+		expect(imageFormatForPath("/tmp/photo.gif")).toBe("png");
+		expect(imageFormatForPath("/tmp/photo")).toBe("png");
+	});
+});
+
+describe("toImageClipboardFormat", () => {
+	it("names formats the way each platform does", () => {
+		expect(toImageClipboardFormat("darwin", "jpeg")).toBe("public.jpeg");
+		expect(toImageClipboardFormat("win32", "jpeg")).toBe("JFIF");
+		expect(toImageClipboardFormat("freebsd", "webp")).toBe("image/webp");
+	});
+});
+
+describe("createImageConverter", () => {
+	const png = Buffer.from("png");
+
+	it("keeps PNG data and encodes JPEG with the given quality", async () => {
+		const toJpeg = vi.fn(() => Buffer.from("jpeg"));
+		const convert = createImageConverter({ toJpeg });
+
+		expect(await convert(png, { format: "png", quality: 90 })).toBe(png);
+		expect(await convert(png, { format: "jpeg", quality: 70 })).toEqual(
+			Buffer.from("jpeg"),
+		);
+		expect(toJpeg).toHaveBeenCalledWith(png, 70);
+	});
+
+	it("encodes WebP with cwebp in a temporary directory", async () => {
+		let input = "";
+		const run = vi.fn(async (_command: string, args: string[]) => {
+			input = args[3];
+			expect(fs.readFileSync(input)).toEqual(png);
+			fs.writeFileSync(args[5], "webp");
+			return "";
+		});
+		const convert = createImageConverter({ toJpeg: vi.fn(), run });
+
+		expect(await convert(png, { format: "webp", quality: 75 })).toEqual(
+			Buffer.from("webp"),
+		);
+		expect(run).toHaveBeenCalledWith("cwebp", [
+			"-quiet",
+			"-q",
+			"75",
+			input,
+			"-o",
+			expect.stringMatching(/image\.webp$/),
+		]);
+		expect(fs.existsSync(input)).toBe(false);
+	});
+
+	it("explains a missing cwebp command", async () => {
+		const missing = Object.assign(new Error("spawn"), { code: "ENOENT" });
+		const run = vi.fn().mockRejectedValue(missing);
+		const convert = createImageConverter({ toJpeg: vi.fn(), run });
+
+		await expect(convert(png, { format: "webp", quality: 90 })).rejects.toThrow(
+			"WebP conversion needs the cwebp command",
+		);
+	});
+});
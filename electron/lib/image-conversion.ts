@@ -0,0 +1,162 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import path from "node:path";
+import type { OutputCommandRunner } from "./foreground-app.js";
+import { isEngineMissing, withTempImage } from "./ocr.js";
+
+const IMAGE_FORMATS = ["png", "jpeg", "webp"] as const;
+
+export type ImageFormat = (typeof IMAGE_FORMATS)[number];
+
+/**
+ * A validated request to re-encode an image.
+ */
+export type ImageConversion = {
+	format: ImageFormat;
+	/** Quality of lossy formats, 1-100; ignored for PNG */
+	quality: number;
+};
+
+/**
+ * Quality used when a conversion does not set one.
+ */
+const DEFAULT_IMAGE_QUALITY = 90;
+
+/**
+ * Upper bound on encoding one image (ms).
+ */
+const ENCODE_TIMEOUT_MS = 30_000;
+
+/**
+ * Clipboard formats carrying encoded image files, by platform: UTIs on
+ * macOS, registered format names on Windows and MIME types elsewhere.
+ */
+const IMAGE_CLIPBOARD_FORMATS: Record<
+	"darwin" | "win32" | "linux",
+	Record<ImageFormat, string>
+> = {
+	darwin: {
+		png: "public.png",
+		jpeg: "public.jpeg",
+		webp: "org.webmproject.webp",
+	},
+	win32: { png: "PNG", jpeg: "JFIF", webp: "image/webp" },
+	linux: { png: "image/png", jpeg: "image/jpeg", webp: "image/webp" },
+};
+
+const runCommand: OutputCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(
+			command,
+			args,
+			{ timeout: ENCODE_TIMEOUT_MS, encoding: "utf-8" },
+			(error, stdout) => (error ? reject(error) : resolve(stdout)),
+		);
+	});
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid image conversion: ${message}`);
+
+/**
+ * Validates a conversion request from the renderer.
+ * Pure function.
+ *
+ * @throws if the format or quality is invalid
+ */
+export const parseImageConversion = (value: unknown): ImageConversion => {
+	if (typeof value !== "object" || value === null) {
+		throw invalid("expected an object");
+	}
+	const { format, quality = DEFAULT_IMAGE_QUALITY } = value as Record<
+		string,
+		unknown
+	>;
+	if (!IMAGE_FORMATS.includes(format as ImageFormat)) {
+		throw invalid(`format must be one of ${IMAGE_FORMATS.join(", ")}`);
+	}
+	if (
+		typeof quality !== "number" ||
+		!Number.isInteger(quality) ||
+		quality < 1 ||
+		quality > 100
+	) {
+		throw invalid("quality must be an integer from 1 to 100");
+	}
+	return { format: format as ImageFormat, quality };
+};
+
+/**
+ * The format a file name asks for by its extension, PNG if it names
+ * none of {@link IMAGE_FORMATS}.
+ * Pure function.
+ */
+export const imageFormatForPath = (filePath: string): ImageFormat => {
+	const extension = path.extname(filePath).slice(1).toLowerCase();
+	if (extension === "jpg" || extension === "jpeg") return "jpeg";
+	return extension === "webp" ? "webp" : "png";
+};
+
+/**
+ * Clipboard format name for an encoded image on a platform.
+ * Pure function.
+ */
+export const toImageClipboardFormat = (
+	platform: NodeJS.Platform,
+	format: ImageFormat,
+): string =>
+	IMAGE_CLIPBOARD_FORMATS[
+		platform === "darwin" || platform === "win32" ? platform : "linux"
+	][format];
+
+// ============================================================================
+// Converter
+// ============================================================================
+
+/**
+ * Re-encodes PNG image data in another format.
+ */
+export type ImageConverter = (
+	png: Buffer,
+	conversion: ImageConversion,
+) => Promise<Buffer>;
+
+/**
+ * Creates an image converter. PNG data is returned unchanged; JPEG is
+ * encoded by `toJpeg` (Electron's encoder in the app); WebP needs the
+ * `cwebp` command (libwebp), since Electron cannot encode it.
+ */
+export const createImageConverter = (deps: {
+	toJpeg: (png: Buffer, quality: number) => Buffer;
+	run?: OutputCommandRunner;
+}): ImageConverter => {
+	const run = deps.run ?? runCommand;
+
+	return async (png, { format, quality }) => {
+		if (format === "png") return png;
+		if (format === "jpeg") return deps.toJpeg(png, quality);
+
+		return withTempImage(png, async (file) => {
+			const output = path.join(path.dirname(file), "image.webp");
+			try {
+				await run("cwebp", [
+					"-quiet",
+					"-q",
+					String(quality),
+					file,
+					"-o",
+					output,
+				]);
+			} catch (error) {
+				if (isEngineMissing(error)) {
+					throw new Error("WebP conversion needs the cwebp command (libwebp)");
+				}
+				throw error;
+			}
+			return fs.readFile(output);
+		});
+	};
+};
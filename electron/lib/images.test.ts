@@ -0,0 +1,94 @@
+import { describe, expect, it } from "vitest";
+import {
+	fitWithin,
+	flattenOnWhite,
+	isOpaque,
+	stripPngMetadata,
+} from "./images.js";
+
+describe("fitWithin", () => {
+	it("keeps images that already fit", () => {
+		expect(fitWithin({ width: 100, height: 50 }, 320)).toEqual({
+			width: 100,
+			height: 50,
+		});
+	});
+
+	it("scales landscape and portrait images by their longest edge", () => {
+		expect(fitWithin({ width: 1920, height: 1080 }, 320)).toEqual({
+			width: 320,
+			height: 180,
+		});
+		expect(fitWithin({ width: 1080, height: 1920 }, 320)).toEqual({
+			width: 180,
+			height: 320,
+		});
+	});
+
+	it("never collapses a dimension to zero", () => {
+		expect(fitWithin({ width: 10_000, height: 1 }, 320)).toEqual({
+			width: 320,
+			height: 1,
+		});
+	});
+});
+
+describe("isOpaque", () => {
+	it("is true only when every pixel has full alpha", () => {
+		expect(isOpaque(Buffer.from([1, 2, 3, 255, 4, 5, 6, 255]))).toBe(true);
+		expect(isOpaque(Buffer.from([1, 2, 3, 255, 4, 5, 6, 128]))).toBe(false);
+		expect(isOpaque(Buffer.alloc(0))).toBe(true);
+	});
+});
+
+describe("flattenOnWhite", () => {
+	it("blends transparent pixels with white", () => {
+		const bitmap = Buffer.from([10, 20, 30, 255, 0, 0, 0, 0, 0, 64, 128, 128]);
+		expect([...flattenOnWhite(bitmap)]).toEqual([
+			10, 20, 30, 255, 255, 255, 255, 255, 127, 191, 255, 255,
+		]);
+		expect(bitmap[7]).toBe(0);
+	});
+});
+
+describe("stripPngMetadata", () => {
+	const chunk = (type: string, data: string) => {
+		const length = Buffer.alloc(4);
+		length.writeUInt32BE(data.length);
+		return Buffer.concat([
+			length,
+			Buffer.from(type + data, "latin1"),
+			Buffer.alloc(4),
+		]);
+	};
+	const signature = Buffer.from([137, 80, 78, 71, 13, 10, 26, 10]);
+	const header = chunk("IHDR", "x".repeat(13));
+	const pixels = chunk("IDAT", "pixels");
+	const end = chunk("IEND", "");
+
+	it("drops EXIF, text and time chunks and keeps the rest", () => {
+		const png = Buffer.concat([
+			signature,
+			header,
+			chunk("sRGB", "\0"),
+			chunk("eXIf", "GPS 51.5N"),
+			chunk("tEXt", "Author\0me"),
+			pixels,
+			chunk("tIME", "1234567"),
+			end,
+		]);
+		expect(stripPngMetadata(png)).toEqual(
+			Buffer.concat([signature, header, chunk("sRGB", "\0"), pixels, end]),
+		);
+	});
+
+	it("returns clean or malformed data unchanged", () => {
+		const clean = Buffer.concat([signature, header, pixels, end]);
+		expect(stripPngMetadata(clean)).toBe(clean);
+
+		const truncated = Buffer.concat([signature, header]).subarray(0, 20);
+		expect(stripPngMetadata(truncated)).toBe(truncated);
+		const jpeg = Buffer.from([0xff, 0xd8, 0xff]);
+		expect(stripPngMetadata(jpeg)).toBe(jpeg);
+	});
+});
@@ -0,0 +1,114 @@
+/**
+ * Pixel dimensions of an image.
+ */
+export type ImageSize = {
+	width: number;
+	height: number;
+};
+
+/**
+ * Longest edge of image previews sent to the renderer (px).
+ */
+export const PREVIEW_MAX_EDGE = 320;
+
+/**
+ * Longest edge of cached list thumbnails (px); twice the list's display
+ * size, for high-DPI screens.
+ */
+export const THUMBNAIL_MAX_EDGE = 192;
+
+/**
+ * JPEG quality of opaque thumbnails (0-100).
+ */
+export const THUMBNAIL_JPEG_QUALITY = 80;
+
+/**
+ * Encoded images larger than this (bytes) are downscaled from a
+ * temporary file where the system can, instead of being decoded whole:
+ * a 20 MB PNG can take half a gigabyte as a bitmap.
+ */
+export const STREAMED_IMAGE_BYTES = 8 * 1024 * 1024;
+
+/**
+ * Scales a size down to fit within a square bounding box, keeping aspect ratio.
+ * Sizes that already fit are returned unchanged; images are never upscaled.
+ * Pure function.
+ *
+ * @param size - Original image size
+ * @param maxEdge - Maximum width and height of the result
+ */
+export const fitWithin = (size: ImageSize, maxEdge: number): ImageSize => {
+	const longestEdge = Math.max(size.width, size.height);
+	if (longestEdge <= maxEdge) return size;
+
+	const scale = maxEdge / longestEdge;
+	return {
+		width: Math.max(1, Math.round(size.width * scale)),
+		height: Math.max(1, Math.round(size.height * scale)),
+	};
+};
+
+/**
+ * Whether a 32-bit bitmap (4 bytes per pixel, alpha last) has no
+ * transparent pixels, so it can be encoded as JPEG without losing any.
+ * Pure function.
+ */
+export const isOpaque = (bitmap: Buffer): boolean => {
+	for (let i = 3; i < bitmap.length; i += 4) {
+		if (bitmap[i] !== 255) return false;
+	}
+	return true;
+};
+
+/**
+ * Composites a 32-bit bitmap with premultiplied alpha (as Electron
+ * returns it) onto white and makes it opaque, for formats without
+ * transparency. Without this, transparent areas turn black as JPEG.
+ * Pure function.
+ */
+export const flattenOnWhite = (bitmap: Buffer): Buffer => {
+	const result = Buffer.from(bitmap);
+	for (let i = 0; i < result.length; i += 4) {
+		const transparency = 255 - result[i + 3];
+		for (let channel = i; channel < i + 3; channel++) {
+			result[channel] = Math.min(255, result[channel] + transparency);
+		}
+		result[i + 3] = 255;
+	}
+	return result;
+};
+
+const PNG_SIGNATURE = Buffer.from([137, 80, 78, 71, 13, 10, 26, 10]);
+
+/**
+ * PNG chunks that describe where an image came from rather than how it
+ * looks: EXIF (camera, GPS), text (author, software, comments) and the
+ * modification time. Color and transparency chunks are kept.
+ */
+const PNG_METADATA_CHUNKS = new Set(["eXIf", "tEXt", "zTXt", "iTXt", "tIME"]);
+
+/**
+ * Removes metadata chunks from PNG data.
+ * Pure function. Returns the same buffer when there is nothing to remove
+ * or the data is not a well-formed PNG.
+ */
+export const stripPngMetadata = (png: Buffer): Buffer => {
+	if (!png.subarray(0, 8).equals(PNG_SIGNATURE)) return png;
+
+	const kept: Buffer[] = [png.subarray(0, 8)];
+	let stripped = false;
+	for (let offset = 8; offset < png.length; ) {
+		// Length, type, data and CRC
+		if (offset + 12 > png.length) return png;
+		const end = offset + 12 + png.readUInt32BE(offset);
+		if (end > png.length) return png;
+		const type = png.toString("latin1", offset + 4, offset + 8);
+		if (PNG_METADATA_CHUNKS.has(type)) {
+			stripped = true;
+		} else {
+			kept.push(png.subarray(offset, end));
+		}
+		offset = end;
+	}
+	return stripped ? Buffer.concat(kept) : png;
+};
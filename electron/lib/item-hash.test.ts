@@ -0,0 +1,66 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import { hashBytes, hashFile, parseHashAlgorithm } from "./item-hash.js";
+
+describe("parseHashAlgorithm", () => {
+	it("accepts the supported algorithms", () => {
+		expect(parseHashAlgorithm("blake3")).toBe("blake3");
+	});
+
+	it("rejects anything else", () => {
+		expect(() => parseHashAlgorithm("crc32")).toThrow(
+			"Invalid hash algorithm: must be one of md5, sha1, sha256, blake3",
+		);
+		expect(() => parseHashAlgorithm(undefined)).toThrow("must be one of");
+	});
+});
+
+describe("hashBytes", () => {
+	it("returns lowercase hex digests", () => {
+		const data = Buffer.from("hello");
+
+		expect(hashBytes(data, "md5")).toBe("5d41402abc4b2a76b9719d911017c592");
+		expect(hashBytes(data, "sha1")).toBe(
+			"aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d",
+		);
+		expect(hashBytes(data, "sha256")).toBe(
+			"2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+		);
+		expect(hashBytes(Buffer.from("abc"), "blake3")).toBe(
+			"6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+		);
+	});
+});
+
+describe("hashFile", () => {
+	const tempDirs: string[] = [];
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-hash-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("hashes a file's contents", async () => {
+		const filePath = path.join(createTempDir(), "download.bin");
+		const data = Buffer.alloc(200_000, 7);
+		fs.writeFileSync(filePath, data);
+
+		expect(await hashFile(filePath, "sha256")).toBe(hashBytes(data, "sha256"));
+		expect(await hashFile(filePath, "blake3")).toBe(hashBytes(data, "blake3"));
+	});
+
+	it("rejects directories", async () => {
+		const dir = createTempDir();
+
+		await expect(hashFile(dir, "md5")).rejects.toThrow(`Not a file: ${dir}`);
+	});
+});
@@ -0,0 +1,75 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import { createBlake3Hash } from "./blake3.js";
+
+const HASH_ALGORITHMS = ["md5", "sha1", "sha256", "blake3"] as const;
+
+export type HashAlgorithm = (typeof HASH_ALGORITHMS)[number];
+
+/**
+ * What hashing needs of Node's hashes and the BLAKE3 one.
+ */
+type Hash = {
+	update: (data: Uint8Array) => unknown;
+	digest: () => Buffer;
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a hash algorithm name from the renderer.
+ * Pure function.
+ *
+ * @throws if the algorithm is unknown
+ */
+export const parseHashAlgorithm = (value: unknown): HashAlgorithm => {
+	if (!HASH_ALGORITHMS.includes(value as HashAlgorithm)) {
+		throw new Error(
+			`Invalid hash algorithm: must be one of ${HASH_ALGORITHMS.join(", ")}`,
+		);
+	}
+	return value as HashAlgorithm;
+};
+
+const createHash = (algorithm: HashAlgorithm): Hash =>
+	algorithm === "blake3" ? createBlake3Hash() : crypto.createHash(algorithm);
+
+/**
+ * Lowercase hex digest of some bytes.
+ * Pure function.
+ */
+export const hashBytes = (
+	data: Uint8Array,
+	algorithm: HashAlgorithm,
+): string => {
+	const hash = createHash(algorithm);
+	hash.update(data);
+	return hash.digest().toString("hex");
+};
+
+// ============================================================================
+// Files
+// ============================================================================
+
+/**
+ * Lowercase hex digest of a file's contents, read as a stream so large
+ * downloads are not held in memory.
+ *
+ * @throws if the path is not a readable regular file
+ */
+export const hashFile = async (
+	filePath: string,
+	algorithm: HashAlgorithm,
+): Promise<string> => {
+	const stats = await fs.promises.stat(filePath);
+	if (!stats.isFile()) {
+		throw new Error(`Not a file: ${filePath}`);
+	}
+	const hash = createHash(algorithm);
+	for await (const chunk of fs.createReadStream(filePath)) {
+		hash.update(chunk as Buffer);
+	}
+	return hash.digest().toString("hex");
+};
@@ -0,0 +1,130 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createItemSummaries,
+	createSummarizer,
+	type ItemSummary,
+	parseChatResponse,
+	parseItemSummarySettings,
+	parseSummaryReply,
+	type Summarizer,
+	type SummaryCandidate,
+} from "./item-summaries.js";
+
+describe("parseItemSummarySettings", () => {
+	it("is off by default, pointing at a local model", () => {
+		expect(parseItemSummarySettings({})).toEqual({
+			enabled: false,
+			endpoint: "http://127.0.0.1:11434/v1/chat/completions",
+			model: "llama3.2",
+			apiKey: "",
+			minChars: 500,
+		});
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseItemSummarySettings({ enabled: 1 })).toThrow(
+			"Invalid item summary settings",
+		);
+		expect(() => parseItemSummarySettings({ endpoint: "localhost" })).toThrow(
+			"endpoint must be an http or https URL",
+		);
+		expect(() => parseItemSummarySettings({ minChars: 10 })).toThrow(
+			"minChars must be an integer",
+		);
+	});
+});
+
+describe("parseSummaryReply", () => {
+	it("reads the title and summary lines", () => {
+		expect(
+			parseSummaryReply(
+				'**Title:** "Rust linker error"\n\nSummary: The build fails on an undefined symbol.',
+			),
+		).toEqual({
+			title: "Rust linker error",
+			summary: "The build fails on an undefined symbol.",
+		});
+	});
+
+	it("keeps a title without a summary and cuts long ones", () => {
+		expect(parseSummaryReply("- Meeting notes\n")).toEqual({
+			title: "Meeting notes",
+			summary: null,
+		});
+		expect(parseSummaryReply("word ".repeat(40)).title).toHaveLength(80);
+		expect(parseSummaryReply("  \n")).toEqual({ title: "", summary: null });
+	});
+});
+
+describe("parseChatResponse", () => {
+	it("reads OpenAI and Ollama responses", () => {
+		expect(
+			parseChatResponse({ choices: [{ message: { content: "A\nB" } }] }),
+		).toBe("A\nB");
+		expect(parseChatResponse({ message: { content: "A" } })).toBe("A");
+		expect(() => parseChatResponse({ error: "model not found" })).toThrow(
+			"no reply text",
+		);
+	});
+});
+
+describe("createSummarizer", () => {
+	it("posts the model and text with the API key", async () => {
+		const fetch = vi.fn().mockResolvedValue(
+			Response.json({ choices: [{ message: { content: "Title" } }] }),
+		);
+		const summarize = createSummarizer({ fetch });
+
+		const reply = await summarize(
+			{ ...parseItemSummarySettings({}), apiKey: "sk-test" },
+			"long text",
+		);
+
+		expect(reply).toBe("Title");
+		const [url, init] = fetch.mock.calls[0];
+		expect(url).toBe("http://127.0.0.1:11434/v1/chat/completions");
+		expect(init.headers.authorization).toBe("Bearer sk-test");
+		const body = JSON.parse(init.body);
+		expect(body.model).toBe("llama3.2");
+		expect(body.messages[1]).toEqual({ role: "user", content: "long text" });
+	});
+});
+
+describe("createItemSummaries", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("summarizes pending items once enabled", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "summaries-"));
+		const pending: SummaryCandidate[] = [
+			{ id: 1, content: "stack trace" },
+			{ id: 2, content: "essay" },
+		];
+		const saved = new Map<number, ItemSummary>();
+		const summarize = vi.fn<Summarizer>(
+			async (_settings, text) => `About ${text}\nA ${text}.`,
+		);
+		const summaries = createItemSummaries({
+			userDataPath,
+			nextCandidate: () => pending.find(({ id }) => !saved.has(id)),
+			save: (id, summary) => saved.set(id, summary),
+			summarize,
+		});
+
+		summaries.schedule();
+		expect(summarize).not.toHaveBeenCalled();
+
+		summaries.updateSettings({ enabled: true });
+		await vi.waitFor(() => expect(saved.size).toBe(2));
+		expect(saved.get(1)).toEqual({
+			title: "About stack trace",
+			summary: "A stack trace.",
+		});
+	});
+});
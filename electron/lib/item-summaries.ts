@@ -0,0 +1,378 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * A short description of a long text item, shown in the list in place
+ * of its first lines.
+ */
+export type ItemSummary = {
+	/** A few words naming what the text is; empty if none was generated */
+	title: string;
+	/** One sentence on what it says; null if the reply had none */
+	summary: string | null;
+};
+
+/**
+ * A long text item not summarized yet.
+ */
+export type SummaryCandidate = {
+	id: number;
+	content: string;
+};
+
+/**
+ * Persisted item summary settings.
+ */
+export type ItemSummarySettings = {
+	/** Summarize long text items in the background; off until opted in */
+	enabled: boolean;
+	/**
+	 * Chat endpoint: any OpenAI-compatible `/v1/chat/completions`, or
+	 * Ollama's `/api/chat`
+	 */
+	endpoint: string;
+	/** Model the endpoint runs, e.g. `llama3.2` */
+	model: string;
+	/** Sent as a bearer token if set; hosted endpoints need one */
+	apiKey: string;
+	/** Shortest text summarized (characters) */
+	minChars: number;
+};
+
+/**
+ * Asks the model for a summary of one text.
+ */
+export type Summarizer = (
+	settings: ItemSummarySettings,
+	text: string,
+) => Promise<string>;
+
+/**
+ * Off until the user opts in, pointing at a model served by Ollama on
+ * this machine, so no text leaves it unless another endpoint is set.
+ */
+const DEFAULT_ITEM_SUMMARY_SETTINGS: ItemSummarySettings = {
+	enabled: false,
+	endpoint: "http://127.0.0.1:11434/v1/chat/completions",
+	model: "llama3.2",
+	apiKey: "",
+	minChars: 500,
+};
+
+const ITEM_SUMMARIES_FILENAME = "item-summaries.json";
+
+/**
+ * Instructions sent with every text.
+ */
+const SUMMARY_PROMPT =
+	"You label clipboard history items. Reply with two lines and nothing else: first a title of at most 8 words naming what the text is, then one sentence summarizing it. Use the text's language.";
+
+/**
+ * Characters of an item sent; the start of a text says what it is.
+ */
+const MAX_SUMMARY_INPUT_CHARS = 4_000;
+
+/**
+ * Upper bound on one request (ms); the first one may wait for the model
+ * to load.
+ */
+const SUMMARIZE_TIMEOUT_MS = 60_000;
+
+const MAX_TITLE_LENGTH = 80;
+
+const MAX_SUMMARY_LENGTH = 300;
+
+const MIN_CHARS_RANGE = { min: 100, max: 100_000 };
+
+const MAX_MODEL_LENGTH = 200;
+
+const MAX_API_KEY_LENGTH = 1_000;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid item summary settings: ${message}`);
+
+/**
+ * Validates an item summary settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value has the wrong type, the endpoint is not an http or
+ *   https URL, the model name is empty or `minChars` is out of range
+ */
+export const parseItemSummarySettings = (
+	input: unknown,
+	current: ItemSummarySettings = DEFAULT_ITEM_SUMMARY_SETTINGS,
+): ItemSummarySettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, endpoint, model, apiKey, minChars } = input as Partial<
+		Record<keyof ItemSummarySettings, unknown>
+	>;
+	const next: ItemSummarySettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (endpoint !== undefined) {
+		if (typeof endpoint !== "string" || !/^https?:\/\/[^/]/i.test(endpoint)) {
+			throw invalid("endpoint must be an http or https URL");
+		}
+		next.endpoint = endpoint;
+	}
+
+	if (model !== undefined) {
+		if (
+			typeof model !== "string" ||
+			model.trim().length === 0 ||
+			model.length > MAX_MODEL_LENGTH
+		) {
+			throw invalid(`model must be a name of 1-${MAX_MODEL_LENGTH} characters`);
+		}
+		next.model = model.trim();
+	}
+
+	if (apiKey !== undefined) {
+		if (typeof apiKey !== "string" || apiKey.length > MAX_API_KEY_LENGTH) {
+			throw invalid(
+				`apiKey must be a string of at most ${MAX_API_KEY_LENGTH} characters`,
+			);
+		}
+		next.apiKey = apiKey;
+	}
+
+	if (minChars !== undefined) {
+		if (
+			typeof minChars !== "number" ||
+			!Number.isInteger(minChars) ||
+			minChars < MIN_CHARS_RANGE.min ||
+			minChars > MIN_CHARS_RANGE.max
+		) {
+			throw invalid(
+				`minChars must be an integer from ${MIN_CHARS_RANGE.min} to ${MIN_CHARS_RANGE.max}`,
+			);
+		}
+		next.minChars = minChars;
+	}
+
+	return next;
+};
+
+/**
+ * Strips what models wrap a line in: bold markers, list markers, a
+ * `Title:` label and quotes.
+ * Pure function.
+ */
+const cleanLine = (line: string): string =>
+	line
+		.replace(/\*\*/g, "")
+		.trim()
+		.replace(/^(?:[-*#>]+\s+|\d+[.)]\s+)/, "")
+		.replace(/^(?:title|summary)\s*:\s*/i, "")
+		.replace(/^["'“”](.*)["'“”]$/, "$1")
+		.trim();
+
+/**
+ * Cuts text to `maxLength` characters, ending in an ellipsis if cut.
+ * Pure function.
+ */
+const truncate = (text: string, maxLength: number): string =>
+	text.length > maxLength ? `${text.slice(0, maxLength - 1)}…` : text;
+
+/**
+ * Reads the title and summary out of a model's reply: the first line is
+ * the title, the rest the summary.
+ * Pure function.
+ */
+export const parseSummaryReply = (reply: string): ItemSummary => {
+	const lines = reply
+		.split("\n")
+		.map(cleanLine)
+		.filter((line) => line.length > 0);
+	const [title = "", ...rest] = lines;
+	const summary = rest.join(" ");
+	return {
+		title: truncate(title, MAX_TITLE_LENGTH),
+		summary: summary ? truncate(summary, MAX_SUMMARY_LENGTH) : null,
+	};
+};
+
+/**
+ * Reads the reply text out of a chat response, in OpenAI's
+ * (`choices[0].message`) or Ollama's (`message`) shape.
+ * Pure function.
+ *
+ * @throws if the body holds no reply text
+ */
+export const parseChatResponse = (body: unknown): string => {
+	const { choices, message } = (body ?? {}) as {
+		choices?: Array<{ message?: { content?: unknown } }>;
+		message?: { content?: unknown };
+	};
+	const content = Array.isArray(choices)
+		? choices[0]?.message?.content
+		: message?.content;
+	if (typeof content !== "string") {
+		throw new Error("Invalid chat response: no reply text");
+	}
+	return content;
+};
+
+// ============================================================================
+// Summarizer
+// ============================================================================
+
+/**
+ * Creates a summarizer posting one chat request per text to the
+ * settings' endpoint.
+ *
+ * @throws from the returned summarizer if the request fails or the
+ *   response holds no reply
+ */
+export const createSummarizer = (
+	deps: { fetch?: typeof fetch; timeoutMs?: number } = {},
+): Summarizer => {
+	const send = deps.fetch ?? fetch;
+	const timeoutMs = deps.timeoutMs ?? SUMMARIZE_TIMEOUT_MS;
+
+	return async (settings, text) => {
+		const response = await send(settings.endpoint, {
+			method: "POST",
+			headers: {
+				"content-type": "application/json",
+				...(settings.apiKey
+					? { authorization: `Bearer ${settings.apiKey}` }
+					: {}),
+			},
+			body: JSON.stringify({
+				model: settings.model,
+				messages: [
+					{ role: "system", content: SUMMARY_PROMPT },
+					{ role: "user", content: text.slice(0, MAX_SUMMARY_INPUT_CHARS) },
+				],
+				stream: false,
+				temperature: 0.2,
+			}),
+			signal: AbortSignal.timeout(timeoutMs),
+		});
+		if (!response.ok) {
+			throw new Error(`HTTP ${response.status}`);
+		}
+		return parseChatResponse(await response.json());
+	};
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getItemSummariesPath = (userDataPath: string) =>
+	path.join(userDataPath, ITEM_SUMMARIES_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readItemSummariesFromFile = (filePath: string): ItemSummarySettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_ITEM_SUMMARY_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseItemSummarySettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read item summary settings:", error);
+		return { ...DEFAULT_ITEM_SUMMARY_SETTINGS };
+	}
+};
+
+const writeItemSummariesToFile = (
+	filePath: string,
+	settings: ItemSummarySettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Item Summaries Module
+// ============================================================================
+
+export type ItemSummariesDeps = {
+	userDataPath: string;
+	/** Newest text item of at least `minChars` not summarized yet, if any */
+	nextCandidate: (minChars: number) => SummaryCandidate | undefined;
+	save: (id: number, summary: ItemSummary) => void;
+	summarize?: Summarizer;
+	/** While true, pending items wait for a later `schedule` */
+	shouldDefer?: () => boolean;
+};
+
+/**
+ * Creates the item summaries module.
+ * While enabled, long text items are summarized one at a time in the
+ * background, newest first. A reply without a title is stored with an
+ * empty one so the item is not asked about again; a failed request stops
+ * until the next `schedule`, and the item is tried again then.
+ */
+export const createItemSummaries = (deps: ItemSummariesDeps) => {
+	const filePath = getItemSummariesPath(deps.userDataPath);
+	const summarize = deps.summarize ?? createSummarizer();
+	let settings = readItemSummariesFromFile(filePath);
+	let running = false;
+	let rerun = false;
+
+	const drain = async (): Promise<void> => {
+		while (settings.enabled && !deps.shouldDefer?.()) {
+			const current = settings;
+			const candidate = deps.nextCandidate(current.minChars);
+			if (!candidate) return;
+			const reply = await summarize(current, candidate.content);
+			deps.save(candidate.id, parseSummaryReply(reply));
+		}
+	};
+
+	/**
+	 * Starts summarizing pending items, or has the current run look again
+	 * once it ends. Does nothing while disabled or deferred.
+	 */
+	const schedule = (): void => {
+		if (!settings.enabled || deps.shouldDefer?.()) return;
+		if (running) {
+			rerun = true;
+			return;
+		}
+		running = true;
+		void drain()
+			.catch((error) => console.warn("Item summaries stopped:", error))
+			.finally(() => {
+				running = false;
+				if (rerun) {
+					rerun = false;
+					schedule();
+				}
+			});
+	};
+
+	const getSettings = (): ItemSummarySettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): ItemSummarySettings => {
+		const next = parseItemSummarySettings(input, settings);
+		writeItemSummariesToFile(filePath, next);
+		settings = next;
+		schedule();
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, schedule };
+};
+
+export type ItemSummaries = ReturnType<typeof createItemSummaries>;
@@ -0,0 +1,99 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	buildJumpList,
+	createJumpList,
+	formatCommandLine,
+	type JumpListInput,
+} from "./jump-list.js";
+
+const row = (
+	id: number,
+	content: string,
+	secret_match: string | null = null,
+) => ({
+	id,
+	type: "text" as const,
+	content,
+	image_width: null,
+	image_height: null,
+	secret_match,
+});
+
+const launch = {
+	program: "C:\\Program Files\\Clipboard Manager\\Clipboard Manager.exe",
+	args: ["--profile=default"],
+};
+
+describe("formatCommandLine", () => {
+	it("quotes arguments with spaces or quotes", () => {
+		expect(formatCommandLine(["a", "b c", 'say "hi"', ""])).toBe(
+			'a "b c" "say \\"hi\\"" ""',
+		);
+	});
+});
+
+describe("buildJumpList", () => {
+	it("lists pinned then other recent items, masking secrets", () => {
+		const [pinned, recent] = buildJumpList({
+			items: {
+				recent: [row(3, "newest"), row(2, "ghp_0123456789", "GitHub token")],
+				pinned: [row(3, "newest")],
+			},
+			paused: false,
+			launch,
+		});
+
+		expect(pinned).toMatchObject({ type: "custom", name: "Pinned" });
+		expect(pinned.items?.map((item) => item.title)).toEqual(["newest"]);
+		expect(recent.items).toMatchObject([
+			{
+				title: "ghp_****",
+				program: launch.program,
+				args: "--profile=default --copy-item=2",
+			},
+		]);
+	});
+
+	it("offers the capture task that matches the pause state", () => {
+		const tasks = (paused: boolean) =>
+			buildJumpList({ items: null, paused, launch })
+				.find((category) => category.type === "tasks")
+				?.items?.map((item) => item.title);
+
+		expect(tasks(false)).toEqual(["Pause capture", "Open history"]);
+		expect(tasks(true)).toEqual(["Resume capture", "Open history"]);
+		expect(
+			buildJumpList({ items: null, paused: false, launch }),
+		).toHaveLength(1);
+	});
+});
+
+describe("createJumpList", () => {
+	it("hands Windows the list only when it changed", () => {
+		const input: JumpListInput = { items: null, paused: false, launch };
+		const setJumpList = vi.fn(() => "ok");
+		const jumpList = createJumpList({
+			platform: "win32",
+			setJumpList,
+			read: () => input,
+		});
+
+		jumpList.update();
+		jumpList.update();
+		input.paused = true;
+		jumpList.update();
+
+		expect(setJumpList).toHaveBeenCalledTimes(2);
+	});
+
+	it("does nothing on other platforms", () => {
+		const setJumpList = vi.fn(() => "ok");
+		createJumpList({
+			platform: "darwin",
+			setJumpList,
+			read: () => ({ items: null, paused: false, launch }),
+		}).update();
+
+		expect(setJumpList).not.toHaveBeenCalled();
+	});
+});
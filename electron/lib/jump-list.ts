@@ -0,0 +1,159 @@
+import type { JumpListCategory, JumpListItem } from "electron";
+import type { HistoryRow } from "./history-repository.js";
+import { type Translate, translateEnglish } from "./i18n.js";
+import { describeItem } from "./tray-recent.js";
+
+/**
+ * Most items listed per jump list category.
+ */
+export const JUMP_LIST_ITEMS = 10;
+
+type JumpListRow = Pick<
+	HistoryRow,
+	"id" | "type" | "content" | "image_width" | "image_height" | "secret_match"
+>;
+
+/**
+ * How jump list entries start the app: the executable and the arguments
+ * that come before each entry's own (the app folder in development and
+ * the active profile).
+ */
+export type JumpListLaunch = { program: string; args: string[] };
+
+export type JumpListInput = {
+	/** Newest and pinned items, or null while history is locked */
+	items: { recent: JumpListRow[]; pinned: JumpListRow[] } | null;
+	paused: boolean;
+	launch: JumpListLaunch;
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Joins arguments into a Windows command line, quoting those with spaces
+ * or quotes.
+ * Pure function.
+ */
+export const formatCommandLine = (args: readonly string[]): string =>
+	args
+		.map((arg) =>
+			arg === "" || /[\s"]/.test(arg) ? `"${arg.replace(/"/g, '\\"')}"` : arg,
+		)
+		.join(" ");
+
+/**
+ * Builds the taskbar jump list: pinned items, the newest other items, and
+ * tasks to pause or resume capture and open history. Each entry starts
+ * the app with `--copy-item=<id>` or the `clipman://toggle-capture`
+ * automation URL, which a running instance receives as a second launch.
+ * Pure function. Secrets are listed masked, and no items are listed
+ * while history is locked.
+ */
+export const buildJumpList = (
+	{ items, paused, launch }: JumpListInput,
+	t: Translate = translateEnglish,
+): JumpListCategory[] => {
+	const task = (
+		title: string,
+		description: string,
+		arg: string | null,
+	): JumpListItem => ({
+		type: "task",
+		title,
+		description,
+		program: launch.program,
+		args: formatCommandLine(arg ? [...launch.args, arg] : launch.args),
+		iconPath: launch.program,
+		iconIndex: 0,
+	});
+	const itemTask = (item: JumpListRow): JumpListItem =>
+		task(
+			describeItem(item),
+			t("jumpList.copy"),
+			// Not a copy URL: those ask first and are off by default
+			`--copy-item=${item.id}`,
+		);
+
+	const categories: JumpListCategory[] = [];
+	const pinned = items?.pinned.slice(0, JUMP_LIST_ITEMS) ?? [];
+	if (pinned.length > 0) {
+		categories.push({
+			type: "custom",
+			name: t("jumpList.pinned"),
+			items: pinned.map(itemTask),
+		});
+	}
+	const pinnedIds = new Set(pinned.map(({ id }) => id));
+	const recent = (items?.recent ?? [])
+		.filter(({ id }) => !pinnedIds.has(id))
+		.slice(0, JUMP_LIST_ITEMS);
+	if (recent.length > 0) {
+		categories.push({
+			type: "custom",
+			name: t("jumpList.recent"),
+			items: recent.map(itemTask),
+		});
+	}
+	categories.push({
+		type: "tasks",
+		items: [
+			task(
+				t(paused ? "jumpList.resumeCapture" : "jumpList.pauseCapture"),
+				t(
+					paused
+						? "jumpList.resumeCaptureDescription"
+						: "jumpList.pauseCaptureDescription",
+				),
+				"clipman://toggle-capture",
+			),
+			task(
+				t("jumpList.openHistory"),
+				t("jumpList.openHistoryDescription"),
+				null,
+			),
+		],
+	});
+	return categories;
+};
+
+// ============================================================================
+// Jump List Module
+// ============================================================================
+
+export type JumpListDeps = {
+	platform: NodeJS.Platform;
+	/** `app.setJumpList`; returns "ok" or why Windows refused the list */
+	setJumpList: (categories: JumpListCategory[]) => string;
+	read: () => JumpListInput;
+	/** Translates titles; English if omitted */
+	t?: Translate;
+};
+
+/**
+ * Creates the Windows jump list, rebuilt with the tray menu whenever
+ * history, capture pause, the lock or the language change. The list is
+ * only handed to Windows when it differs from the last one. Does nothing
+ * on other platforms.
+ */
+export const createJumpList = (deps: JumpListDeps) => {
+	let last: string | null = null;
+
+	const update = (): void => {
+		if (deps.platform !== "win32") return;
+		const categories = buildJumpList(deps.read(), deps.t);
+		const serialized = JSON.stringify(categories);
+		if (serialized === last) return;
+		const result = deps.setJumpList(categories);
+		if (result === "ok") {
+			last = serialized;
+		} else {
+			console.error(`Failed to update the jump list: ${result}`);
+		}
+	};
+
+	return { update };
+};
+
+export type JumpList = ReturnType<typeof createJumpList>;
@@ -0,0 +1,90 @@
+import { describe, expect, it } from "vitest";
+import {
+	createXinputTranslator,
+	parseKeyHookLine,
+	parseXmodmapKeymap,
+} from "./key-hook.js";
+
+describe("parseKeyHookLine", () => {
+	it("reads typed characters as UTF-16 units", () => {
+		expect(parseKeyHookLine("char 003b")).toEqual({ type: "char", text: ";" });
+		expect(parseKeyHookLine("char d83d de00")).toEqual({
+			type: "char",
+			text: "😀",
+		});
+	});
+
+	it("reads Backspace, copies and resets, and skips anything else", () => {
+		expect(parseKeyHookLine("backspace\r")).toEqual({ type: "backspace" });
+		expect(parseKeyHookLine("reset")).toEqual({ type: "reset" });
+		expect(parseKeyHookLine("copy")).toEqual({ type: "copy" });
+		expect(parseKeyHookLine("char zz")).toBeNull();
+		expect(parseKeyHookLine("")).toBeNull();
+	});
+});
+
+describe("createXinputTranslator", () => {
+	const keymap = parseXmodmapKeymap(
+		[
+			"keycode  10 = 1 exclam 1 exclam",
+			"keycode  22 = BackSpace BackSpace",
+			"keycode  36 = Return NoSymbol Return",
+			"keycode  37 = Control_L NoSymbol Control_L",
+			"keycode  38 = a A a A",
+			"keycode  47 = semicolon colon semicolon colon",
+			"keycode  50 = Shift_L NoSymbol Shift_L",
+			"keycode  54 = c C c C",
+			"keycode  64 = Alt_L Meta_L Alt_L Meta_L",
+			"keycode  66 = Caps_Lock NoSymbol Caps_Lock",
+			"keycode   8 =",
+		].join("\n"),
+	);
+
+	const createTranslator = () => {
+		const translate = createXinputTranslator(keymap);
+		return (event: string, detail: number) =>
+			[
+				`EVENT type 13 (${event})`,
+				"    device: 3 (3)",
+				`    detail: ${detail}`,
+				"    valuators:",
+			]
+				.map(translate)
+				.filter((key) => key !== null);
+	};
+
+	it("types characters with Shift and Caps Lock", () => {
+		const send = createTranslator();
+
+		expect(send("RawKeyPress", 47)).toEqual([{ type: "char", text: ";" }]);
+		expect(send("RawKeyPress", 50)).toEqual([]);
+		expect(send("RawKeyPress", 38)).toEqual([{ type: "char", text: "A" }]);
+		send("RawKeyRelease", 50);
+		send("RawKeyPress", 66);
+		expect(send("RawKeyPress", 38)).toEqual([{ type: "char", text: "A" }]);
+		expect(send("RawKeyPress", 10)).toEqual([{ type: "char", text: "1" }]);
+	});
+
+	it("resets on shortcuts, other keys and clicks", () => {
+		const send = createTranslator();
+
+		expect(send("RawKeyPress", 22)).toEqual([{ type: "backspace" }]);
+		expect(send("RawKeyPress", 36)).toEqual([{ type: "reset" }]);
+		send("RawKeyPress", 37);
+		expect(send("RawKeyPress", 38)).toEqual([{ type: "reset" }]);
+		send("RawKeyRelease", 37);
+		expect(send("RawButtonPress", 1)).toEqual([{ type: "reset" }]);
+		// Scrolling leaves the caret alone
+		expect(send("RawButtonPress", 4)).toEqual([]);
+	});
+
+	it("reads C with only Control held as a copy", () => {
+		const send = createTranslator();
+
+		send("RawKeyPress", 37);
+		expect(send("RawKeyPress", 54)).toEqual([{ type: "copy" }]);
+		expect(send("RawKeyPress", 54)).toEqual([{ type: "copy" }]);
+		send("RawKeyPress", 64);
+		expect(send("RawKeyPress", 54)).toEqual([{ type: "reset" }]);
+	});
+});
@@ -0,0 +1,479 @@
+import { type ChildProcess, execFile, spawn } from "node:child_process";
+import readline from "node:readline";
+
+/**
+ * A keystroke seen by the keyboard hook, reduced to what text expansion
+ * and the double-copy gesture need: a typed character, Backspace, the
+ * copy shortcut (Cmd+C on macOS, Ctrl+C elsewhere), or anything else
+ * that moves the caret or ends a word in an unknown way (Enter, arrows,
+ * other shortcuts, clicks).
+ */
+export type TypedKey =
+	| { type: "char"; text: string }
+	| { type: "backspace" }
+	| { type: "copy" }
+	| { type: "reset" };
+
+/**
+ * Starts a long-running listener process; injectable for tests.
+ */
+export type KeyHookSpawner = (command: string, args: string[]) => ChildProcess;
+
+/**
+ * Listens for keys typed in any application.
+ */
+export type KeyHook = {
+	/**
+	 * Starts listening; `onExit` is called with an error if the listener
+	 * fails or stops on its own.
+	 */
+	start: (
+		onKey: (key: TypedKey) => void,
+		onExit: (error: Error) => void,
+	) => Promise<void>;
+	stop: () => void;
+};
+
+/**
+ * Low-level keyboard and mouse hooks in C#, compiled by PowerShell.
+ * Prints `char <hex UTF-16 units>`, `backspace`, `copy` (Ctrl+C, once
+ * until C is released) or `reset` per key down; keys injected by other
+ * programs (including our own paste) are skipped.
+ */
+const WINDOWS_HOOK_SOURCE = `
+using System;
+using System.Diagnostics;
+using System.Runtime.InteropServices;
+using System.Text;
+public static class ClipmanKeyHook {
+	delegate IntPtr HookProc(int code, IntPtr wParam, IntPtr lParam);
+	[StructLayout(LayoutKind.Sequential)]
+	struct MSG { public IntPtr hwnd; public uint message; public IntPtr wParam; public IntPtr lParam; public uint time; public int x; public int y; }
+	[DllImport("user32.dll")] static extern IntPtr SetWindowsHookEx(int id, HookProc proc, IntPtr module, uint thread);
+	[DllImport("user32.dll")] static extern IntPtr CallNextHookEx(IntPtr hook, int code, IntPtr wParam, IntPtr lParam);
+	[DllImport("user32.dll")] static extern int GetMessage(out MSG msg, IntPtr hwnd, uint min, uint max);
+	[DllImport("user32.dll")] static extern short GetKeyState(int key);
+	[DllImport("user32.dll")] static extern IntPtr GetForegroundWindow();
+	[DllImport("user32.dll")] static extern uint GetWindowThreadProcessId(IntPtr hwnd, IntPtr processId);
+	[DllImport("user32.dll")] static extern IntPtr GetKeyboardLayout(uint thread);
+	[DllImport("user32.dll")] static extern int ToUnicodeEx(uint key, uint scan, byte[] state, StringBuilder text, int size, uint flags, IntPtr layout);
+	[DllImport("kernel32.dll")] static extern IntPtr GetModuleHandle(string name);
+	static readonly HookProc keyboard = OnKey;
+	static readonly HookProc mouse = OnMouse;
+	static bool copyHeld = false;
+	static void Emit(string line) {
+		try { Console.Out.WriteLine(line); Console.Out.Flush(); } catch { Environment.Exit(0); }
+	}
+	static bool IsDown(int key) {
+		return (GetKeyState(key) & 0x8000) != 0;
+	}
+	static bool IsModifier(uint key) {
+		return key == 0x10 || key == 0x11 || key == 0x12 || key == 0x14 || (key >= 0xA0 && key <= 0xA5) || key == 0x5B || key == 0x5C;
+	}
+	static IntPtr OnKey(int code, IntPtr wParam, IntPtr lParam) {
+		int message = wParam.ToInt32();
+		uint flags = (uint)Marshal.ReadInt32(lParam, 8);
+		if (code >= 0 && (message == 0x101 || message == 0x105) && Marshal.ReadInt32(lParam) == 0x43) copyHeld = false;
+		if (code >= 0 && (message == 0x100 || message == 0x104) && (flags & 0x10) == 0) {
+			uint key = (uint)Marshal.ReadInt32(lParam);
+			uint scan = (uint)Marshal.ReadInt32(lParam, 4);
+			if (key == 0x08) Emit("backspace");
+			else if (key == 0x43 && IsDown(0x11) && !IsDown(0x12)) {
+				Emit(copyHeld ? "reset" : "copy");
+				copyHeld = true;
+			}
+			else if (!IsModifier(key)) Emit(Translate(key, scan));
+		}
+		return CallNextHookEx(IntPtr.Zero, code, wParam, lParam);
+	}
+	static IntPtr OnMouse(int code, IntPtr wParam, IntPtr lParam) {
+		int message = wParam.ToInt32();
+		if (code >= 0 && (message == 0x201 || message == 0x204 || message == 0x207)) Emit("reset");
+		return CallNextHookEx(IntPtr.Zero, code, wParam, lParam);
+	}
+	static string Translate(uint key, uint scan) {
+		byte[] state = new byte[256];
+		foreach (int modifier in new int[] { 0x10, 0x11, 0x12, 0x14 }) {
+			short down = GetKeyState(modifier);
+			state[modifier] = (byte)(((down & 0x8000) != 0 ? 0x80 : 0) | (down & 1));
+		}
+		IntPtr layout = GetKeyboardLayout(GetWindowThreadProcessId(GetForegroundWindow(), IntPtr.Zero));
+		StringBuilder text = new StringBuilder(8);
+		int length = ToUnicodeEx(key, scan, state, text, text.Capacity, 4, layout);
+		if (length <= 0) return "reset";
+		StringBuilder line = new StringBuilder("char");
+		for (int i = 0; i < length; i++) {
+			if (text[i] < 0x20 || text[i] == 0x7F) return "reset";
+			line.Append(' ').Append(((int)text[i]).ToString("x4"));
+		}
+		return line.ToString();
+	}
+	public static void Run() {
+		IntPtr module = GetModuleHandle(Process.GetCurrentProcess().MainModule.ModuleName);
+		SetWindowsHookEx(13, keyboard, module, 0);
+		SetWindowsHookEx(14, mouse, module, 0);
+		MSG msg;
+		while (GetMessage(out msg, IntPtr.Zero, 0, 0) > 0) { }
+	}
+}
+`;
+
+/**
+ * The hook script as `-EncodedCommand` (Base64 of UTF-16LE), which keeps
+ * its quotes and line breaks intact on the command line.
+ */
+const WINDOWS_HOOK_COMMAND = Buffer.from(
+	`Add-Type -TypeDefinition @'\n${WINDOWS_HOOK_SOURCE}\n'@\n[ClipmanKeyHook]::Run()`,
+	"utf16le",
+).toString("base64");
+
+/**
+ * JavaScript for Automation: a global NSEvent monitor that prints the
+ * same lines as the Windows hook. It needs the Accessibility permission
+ * the app already asks for to paste. Key codes: 51 is Delete (Backspace),
+ * 8 is C (held down, it repeats without counting as another copy); the
+ * reset keys are Return, Tab, Escape, Enter, Home, Page Up, Forward
+ * Delete, End, Page Down and the arrows. Modifier flags: Control is
+ * 1 << 18, Command is 1 << 20.
+ */
+const MAC_HOOK_SCRIPT = `
+ObjC.import("AppKit");
+const out = $.NSFileHandle.fileHandleWithStandardOutput;
+const emit = (line) =>
+	out.writeData($(line + "\\n").dataUsingEncoding($.NSUTF8StringEncoding));
+const RESET_KEYS = [36, 48, 53, 76, 115, 116, 117, 119, 121, 123, 124, 125, 126];
+const KEY_DOWN = 10;
+$.NSEvent.addGlobalMonitorForEventsMatchingMaskHandler(
+	(1 << 10) | (1 << 1) | (1 << 3),
+	(event) => {
+		if (event.type !== KEY_DOWN) return emit("reset");
+		if (event.keyCode === 51) return emit("backspace");
+		if (event.keyCode === 8 && (event.modifierFlags & (1 << 20))) {
+			return emit(event.isARepeat ? "reset" : "copy");
+		}
+		if (RESET_KEYS.includes(event.keyCode) || (event.modifierFlags & ((1 << 18) | (1 << 20)))) {
+			return emit("reset");
+		}
+		const text = event.characters.js;
+		const units = [];
+		for (let i = 0; i < text.length; i++) {
+			const unit = text.charCodeAt(i);
+			if (unit < 0x20 || unit === 0x7f || (unit >= 0xf700 && unit <= 0xf8ff)) {
+				return emit("reset");
+			}
+			units.push(unit.toString(16));
+		}
+		emit(units.length ? "char " + units.join(" ") : "reset");
+	},
+);
+$.NSApplication.sharedApplication.run;
+`;
+
+/**
+ * X keysym names of printable ASCII punctuation, for keysyms that are not
+ * the character itself.
+ */
+const PUNCTUATION_KEYSYMS = new Map<string, string>([
+	["space", " "],
+	["exclam", "!"],
+	["quotedbl", '"'],
+	["numbersign", "#"],
+	["dollar", "$"],
+	["percent", "%"],
+	["ampersand", "&"],
+	["apostrophe", "'"],
+	["parenleft", "("],
+	["parenright", ")"],
+	["asterisk", "*"],
+	["plus", "+"],
+	["comma", ","],
+	["minus", "-"],
+	["period", "."],
+	["slash", "/"],
+	["colon", ":"],
+	["semicolon", ";"],
+	["less", "<"],
+	["equal", "="],
+	["greater", ">"],
+	["question", "?"],
+	["at", "@"],
+	["bracketleft", "["],
+	["backslash", "\\"],
+	["bracketright", "]"],
+	["asciicircum", "^"],
+	["underscore", "_"],
+	["grave", "`"],
+	["braceleft", "{"],
+	["bar", "|"],
+	["braceright", "}"],
+	["asciitilde", "~"],
+]);
+
+const SHIFT_KEYSYMS = new Set(["Shift_L", "Shift_R"]);
+
+/**
+ * Modifiers that turn a key into a shortcut rather than typed text.
+ */
+const SHORTCUT_KEYSYMS = new Set([
+	"Control_L",
+	"Control_R",
+	"Alt_L",
+	"Alt_R",
+	"Meta_L",
+	"Meta_R",
+	"Super_L",
+	"Super_R",
+]);
+
+const CONTROL_KEYSYMS = new Set(["Control_L", "Control_R"]);
+
+/**
+ * Mouse buttons that move the caret: left, middle (paste) and right.
+ */
+const CLICK_BUTTONS = new Set([1, 2, 3]);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Parses a line printed by the Windows or macOS listener.
+ * Pure function.
+ *
+ * @returns the key, or null for an unrecognized or empty line
+ */
+export const parseKeyHookLine = (line: string): TypedKey | null => {
+	const [command, ...units] = line.trim().split(/\s+/);
+	if (command === "backspace") return { type: "backspace" };
+	if (command === "copy") return { type: "copy" };
+	if (command === "reset") return { type: "reset" };
+	if (command !== "char" || units.length === 0) return null;
+	const codes = units.map((unit) => Number.parseInt(unit, 16));
+	if (codes.some((code) => !Number.isInteger(code) || code > 0xffff)) {
+		return null;
+	}
+	return { type: "char", text: String.fromCharCode(...codes) };
+};
+
+/**
+ * Parses `xmodmap -pke` output into the first two keysyms (plain and
+ * with Shift) of each keycode, e.g. `keycode  38 = a A a A`.
+ * Pure function.
+ */
+export const parseXmodmapKeymap = (
+	output: string,
+): Map<number, [string, string]> => {
+	const keymap = new Map<number, [string, string]>();
+	for (const line of output.split("\n")) {
+		const match = /^keycode\s+(\d+)\s*=\s*(\S+)(?:\s+(\S+))?/.exec(line);
+		if (!match) continue;
+		keymap.set(Number(match[1]), [match[2], match[3] ?? match[2]]);
+	}
+	return keymap;
+};
+
+/**
+ * The character a keysym types, or null if it is not a printable
+ * character.
+ * Pure function.
+ */
+const keysymToText = (keysym: string): string | null => {
+	if ([...keysym].length === 1) return keysym;
+	return PUNCTUATION_KEYSYMS.get(keysym) ?? null;
+};
+
+/**
+ * Creates a translator from `xinput test-xi2 --root` output lines to
+ * typed keys, tracking Shift, Caps Lock and shortcut modifiers. C with
+ * only Control held is the copy shortcut.
+ * Raw events carry keycodes, so characters come from `keymap`; layouts
+ * beyond its first two levels (e.g. AltGr) are not translated.
+ *
+ * @returns a function that takes each output line and returns the key
+ *   it completes, if any
+ */
+export const createXinputTranslator = (
+	keymap: ReadonlyMap<number, [string, string]>,
+): ((line: string) => TypedKey | null) => {
+	let event = "";
+	let shift = 0;
+	let shortcut = 0;
+	let control = 0;
+	let capsLock = false;
+
+	const press = (keycode: number): TypedKey | null => {
+		const [plain, shifted] = keymap.get(keycode) ?? ["", ""];
+		if (SHIFT_KEYSYMS.has(plain)) {
+			shift++;
+			return null;
+		}
+		if (SHORTCUT_KEYSYMS.has(plain)) {
+			shortcut++;
+			if (CONTROL_KEYSYMS.has(plain)) control++;
+			return null;
+		}
+		if (plain === "Caps_Lock") {
+			capsLock = !capsLock;
+			return null;
+		}
+		if (plain === "BackSpace") return { type: "backspace" };
+		if (shortcut > 0) {
+			const isCopy = plain === "c" && control === shortcut;
+			return { type: isCopy ? "copy" : "reset" };
+		}
+
+		const isLetter =
+			plain.length === 1 && plain.toLowerCase() !== plain.toUpperCase();
+		const useShifted = isLetter ? shift > 0 !== capsLock : shift > 0;
+		const text = keysymToText(useShifted ? shifted : plain);
+		return text === null ? { type: "reset" } : { type: "char", text };
+	};
+
+	const release = (keycode: number): void => {
+		const [plain] = keymap.get(keycode) ?? [""];
+		if (SHIFT_KEYSYMS.has(plain)) shift = Math.max(0, shift - 1);
+		if (SHORTCUT_KEYSYMS.has(plain)) shortcut = Math.max(0, shortcut - 1);
+		if (CONTROL_KEYSYMS.has(plain)) control = Math.max(0, control - 1);
+	};
+
+	return (line) => {
+		const header = /^EVENT type \d+ \((\w+)\)/.exec(line);
+		if (header) {
+			event = header[1];
+			return null;
+		}
+		const detail = /^\s+detail: (\d+)/.exec(line);
+		if (!detail) return null;
+
+		const code = Number(detail[1]);
+		switch (event) {
+			case "RawKeyPress":
+				return press(code);
+			case "RawKeyRelease":
+				release(code);
+				return null;
+			case "RawButtonPress":
+				return CLICK_BUTTONS.has(code) ? { type: "reset" } : null;
+			default:
+				return null;
+		}
+	};
+};
+
+/**
+ * Command that runs the keyboard listener, or null if the platform is
+ * unsupported.
+ * - macOS: a JavaScript for Automation event monitor
+ * - Windows: low-level hooks compiled by PowerShell
+ * - Linux (X11): raw events from `xinput test-xi2 --root`
+ * Pure function.
+ */
+export const getKeyHookCommand = (
+	platform: NodeJS.Platform,
+): { command: string; args: string[] } | null => {
+	switch (platform) {
+		case "darwin":
+			return {
+				command: "osascript",
+				args: ["-l", "JavaScript", "-e", MAC_HOOK_SCRIPT],
+			};
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-EncodedCommand",
+					WINDOWS_HOOK_COMMAND,
+				],
+			};
+		case "linux":
+			// Line-buffered, so each event arrives as it happens
+			return {
+				command: "stdbuf",
+				args: ["-oL", "xinput", "test-xi2", "--root"],
+			};
+		default:
+			return null;
+	}
+};
+
+// ============================================================================
+// Key Hook Module
+// ============================================================================
+
+const spawnListener: KeyHookSpawner = (command, args) =>
+	spawn(command, args, { stdio: ["ignore", "pipe", "pipe"] });
+
+const readXmodmapKeymap = (): Promise<string> =>
+	new Promise((resolve, reject) => {
+		execFile("xmodmap", ["-pke"], { encoding: "utf-8" }, (error, stdout) =>
+			error ? reject(error) : resolve(stdout),
+		);
+	});
+
+/**
+ * Creates the keyboard hook for the current platform.
+ * On unsupported platforms `start` rejects.
+ */
+export const createKeyHook = (deps: {
+	platform: NodeJS.Platform;
+	spawn?: KeyHookSpawner;
+	readKeymap?: () => Promise<string>;
+}): KeyHook => {
+	const spawnProcess = deps.spawn ?? spawnListener;
+	const readKeymap = deps.readKeymap ?? readXmodmapKeymap;
+	let child: ChildProcess | null = null;
+
+	const start: KeyHook["start"] = async (onKey, onExit) => {
+		if (child) return;
+		const command = getKeyHookCommand(deps.platform);
+		if (!command) {
+			throw new Error("Keyboard listening is not supported on this platform");
+		}
+
+		const translate =
+			deps.platform === "linux"
+				? createXinputTranslator(parseXmodmapKeymap(await readKeymap()))
+				: parseKeyHookLine;
+
+		const listener = spawnProcess(command.command, command.args);
+		child = listener;
+		let stderr = "";
+		listener.stderr?.on("data", (chunk: Buffer) => {
+			stderr = (stderr + chunk.toString("utf-8")).slice(-1000);
+		});
+		if (listener.stdout) {
+			const lines = readline.createInterface({ input: listener.stdout });
+			lines.on("line", (line) => {
+				const key = translate(line);
+				if (key) onKey(key);
+			});
+		}
+
+		const exited = (error: Error) => {
+			if (child !== listener) return;
+			child = null;
+			onExit(error);
+		};
+		listener.on("error", (error) =>
+			exited(new Error(`Keyboard listener failed: ${error.message}`)),
+		);
+		listener.on("exit", (code) => {
+			const detail = stderr.trim();
+			exited(
+				new Error(
+					`Keyboard listener exited with code ${code}${detail ? `: ${detail}` : ""}`,
+				),
+			);
+		});
+	};
+
+	const stop = () => {
+		const listener = child;
+		child = null;
+		listener?.kill();
+	};
+
+	return { start, stop };
+};
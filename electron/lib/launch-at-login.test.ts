@@ -21,13 +21,18 @@ const createMockPreferencesStore = (options?: {
 
 	return {
 		readWithMeta: vi.fn(() => ({
-			preferences: { launchAtLogin } satisfies AppPreferences,
+			preferences: {
+				launchAtLogin,
+				startMinimized: true,
+			} satisfies AppPreferences,
 			fileExisted,
 		})),
 		readLaunchAtLogin: vi.fn(() => launchAtLogin),
 		writeLaunchAtLogin: vi.fn((enabled: boolean) => {
 			launchAtLogin = enabled;
 		}),
+		readStartMinimized: vi.fn(() => true),
+		writeStartMinimized: vi.fn(),
 	};
 };
 
@@ -1,6 +1,7 @@
 import {
 	type AppPreferences,
 	type createPreferencesStore,
+	DEFAULT_PREFERENCES,
 	getPreferencesPath,
 	writePreferencesToFile,
 } from "./preferences.js";
@@ -142,6 +143,7 @@ const applyReconcileResult = (
 
 	if (result.persistInitialPreferences) {
 		writePreferencesToFile(getPreferencesPath(deps.userDataPath), {
+			...DEFAULT_PREFERENCES,
 			launchAtLogin: result.effective,
 		} satisfies AppPreferences);
 	}
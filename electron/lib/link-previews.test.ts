@@ -0,0 +1,290 @@
+import fs from "node:fs";
+import http from "node:http";
+import type { AddressInfo } from "node:net";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createLinkPreviewFetcher,
+	createLinkPreviews,
+	fetchPublicUrl,
+	isPublicAddress,
+	type LinkCandidate,
+	parseLinkPreview,
+	parseLinkPreviewSettings,
+	sendToPublicHost,
+	toPreviewUrl,
+} from "./link-previews.js";
+
+describe("parseLinkPreview", () => {
+	it("prefers Open Graph tags and resolves the image URL", () => {
+		const html = `<html><head>
+			<title>GitHub</title>
+			<meta property="og:title" content="Fix capture &amp; paste &#8211; PR #12">
+			<meta name='description' content='Plain description'>
+			<meta content="/images/card.png" property="og:image" />
+		</head></html>`;
+
+		expect(parseLinkPreview(html, "https://github.com/org/repo")).toEqual({
+			title: "Fix capture & paste – PR #12",
+			description: "Plain description",
+			image: "https://github.com/images/card.png",
+		});
+	});
+
+	it("falls back to the title tag and leaves missing fields empty", () => {
+		expect(
+			parseLinkPreview(
+				"<title>\n  Example &lt;Domain&gt;\n</title>",
+				"https://example.com",
+			),
+		).toEqual({ title: "Example <Domain>", description: null, image: null });
+		expect(parseLinkPreview("<p>no head</p>", "https://example.com")).toEqual({
+			title: "",
+			description: null,
+			image: null,
+		});
+	});
+
+	it("drops images that are not web URLs", () => {
+		const html = '<meta property="og:image" content="javascript:alert(1)">';
+
+		expect(parseLinkPreview(html, "https://example.com").image).toBeNull();
+	});
+});
+
+describe("toPreviewUrl", () => {
+	it("accepts public web links, adding https to bare www links", () => {
+		expect(toPreviewUrl(" https://github.com/org/repo ")).toBe(
+			"https://github.com/org/repo",
+		);
+		expect(toPreviewUrl("www.example.com/page")).toBe(
+			"https://www.example.com/page",
+		);
+	});
+
+	it("skips other schemes and local hosts", () => {
+		for (const url of [
+			"ftp://example.com/file",
+			"http://localhost:3000",
+			"http://192.168.1.1/admin",
+			"https://[::1]/",
+			"https://printer.local/",
+			"not a url",
+		]) {
+			expect(toPreviewUrl(url)).toBeNull();
+		}
+	});
+});
+
+describe("isPublicAddress", () => {
+	it("accepts public addresses", () => {
+		for (const address of ["93.184.215.14", "2606:2800:21f:cb07::1"]) {
+			expect(isPublicAddress(address)).toBe(true);
+		}
+	});
+
+	it("refuses loopback, private and link-local addresses", () => {
+		for (const address of [
+			"127.0.0.1",
+			"10.1.2.3",
+			"172.16.0.1",
+			"192.168.1.1",
+			"169.254.169.254",
+			"100.64.0.1",
+			"0.0.0.0",
+			"::1",
+			"::",
+			"fd00::1",
+			"fe80::1",
+			"::ffff:127.0.0.1",
+			"not an address",
+		]) {
+			expect(isPublicAddress(address)).toBe(false);
+		}
+	});
+});
+
+describe("sendToPublicHost", () => {
+	it("refuses a host name that resolves to the local machine", async () => {
+		const server = http.createServer((_, res) => res.end("local"));
+		await new Promise<void>((resolve) => server.listen(0, resolve));
+		const { port } = server.address() as AddressInfo;
+
+		try {
+			await expect(
+				sendToPublicHost(`http://localhost:${port}/`, {}),
+			).rejects.toThrow("is not public");
+		} finally {
+			server.close();
+		}
+	});
+});
+
+describe("parseLinkPreviewSettings", () => {
+	it("is off by default and validates updates", () => {
+		expect(parseLinkPreviewSettings({})).toEqual({ enabled: false });
+		expect(parseLinkPreviewSettings({ enabled: true })).toEqual({
+			enabled: true,
+		});
+		expect(() => parseLinkPreviewSettings({ enabled: "yes" })).toThrow(
+			"Invalid link preview settings: enabled must be a boolean",
+		);
+		expect(() => parseLinkPreviewSettings(null)).toThrow("expected an object");
+	});
+});
+
+describe("fetchPublicUrl", () => {
+	const redirect = (location: string) =>
+		new Response(null, { status: 302, headers: { location } });
+
+	it("follows redirects to public URLs one hop at a time", async () => {
+		const fetch = vi
+			.fn()
+			.mockResolvedValueOnce(redirect("/moved"))
+			.mockResolvedValueOnce(new Response("page"));
+
+		const response = await fetchPublicUrl(fetch, "https://example.com/a");
+
+		expect(await response.text()).toBe("page");
+		expect(fetch).toHaveBeenNthCalledWith(1, "https://example.com/a", {
+			redirect: "manual",
+		});
+		expect(fetch).toHaveBeenNthCalledWith(2, "https://example.com/moved", {
+			redirect: "manual",
+		});
+	});
+
+	it("refuses redirects to the local machine or network", async () => {
+		for (const location of [
+			"http://127.0.0.1/admin",
+			"http://router.lan/",
+			"file:///etc/passwd",
+		]) {
+			const fetch = vi.fn().mockResolvedValueOnce(redirect(location));
+
+			await expect(
+				fetchPublicUrl(fetch, "https://example.com/"),
+			).rejects.toThrow("not public");
+			expect(fetch).toHaveBeenCalledTimes(1);
+		}
+	});
+
+	it("gives up after 5 redirects", async () => {
+		const fetch = vi.fn(async () => redirect("https://example.com/loop"));
+
+		await expect(
+			fetchPublicUrl(fetch, "https://example.com/loop"),
+		).rejects.toThrow("Too many redirects");
+		expect(fetch).toHaveBeenCalledTimes(6);
+	});
+});
+
+describe("createLinkPreviewFetcher", () => {
+	it("reads the preview of an HTML page", async () => {
+		const fetch = vi.fn(
+			async () =>
+				new Response("<title>Docs</title>", {
+					headers: { "content-type": "text/html; charset=utf-8" },
+				}),
+		);
+		const fetchPreview = createLinkPreviewFetcher({ fetch });
+
+		expect(await fetchPreview("https://example.com/docs")).toEqual({
+			title: "Docs",
+			description: null,
+			image: null,
+		});
+		expect(fetch).toHaveBeenCalledWith(
+			"https://example.com/docs",
+			expect.objectContaining({ signal: expect.any(AbortSignal) }),
+		);
+	});
+
+	it("rejects error responses and other content types", async () => {
+		const fetch = vi
+			.fn()
+			.mockResolvedValueOnce(new Response("", { status: 404 }))
+			.mockResolvedValueOnce(
+				new Response("{}", { headers: { "content-type": "application/json" } }),
+			);
+		const fetchPreview = createLinkPreviewFetcher({ fetch });
+
+		await expect(fetchPreview("https://example.com/a")).rejects.toThrow(
+			"HTTP 404",
+		);
+		await expect(fetchPreview("https://example.com/b")).rejects.toThrow(
+			"Not an HTML page: application/json",
+		);
+	});
+});
+
+describe("createLinkPreviews", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-links-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createQueue = (candidates: LinkCandidate[]) => {
+		const save = vi.fn((id: number) => {
+			candidates.splice(
+				candidates.findIndex((candidate) => candidate.id === id),
+				1,
+			);
+		});
+		const fetchPreview = vi.fn(async (url: string) => {
+			if (url.includes("broken")) throw new Error("timed out");
+			return { title: `Title of ${url}`, description: null, image: null };
+		});
+		const linkPreviews = createLinkPreviews({
+			userDataPath: createTempUserDataPath(),
+			nextCandidate: () => candidates[0],
+			save,
+			fetchPreview,
+		});
+		return { linkPreviews, save, fetchPreview };
+	};
+
+	it("fetches nothing until enabled", () => {
+		const { linkPreviews, fetchPreview } = createQueue([
+			{ id: 1, url: "https://example.com" },
+		]);
+
+		linkPreviews.schedule();
+
+		expect(linkPreviews.getSettings()).toEqual({ enabled: false });
+		expect(fetchPreview).not.toHaveBeenCalled();
+	});
+
+	it("stores skipped and failed links with an empty title", async () => {
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const { linkPreviews, save, fetchPreview } = createQueue([
+			{ id: 3, url: "https://example.com/broken" },
+			{ id: 2, url: "http://localhost:8080" },
+			{ id: 1, url: "https://example.com/" },
+		]);
+
+		linkPreviews.updateSettings({ enabled: true });
+
+		await vi.waitFor(() => expect(save).toHaveBeenCalledTimes(3));
+		const empty = { title: "", description: null, image: null };
+		expect(save).toHaveBeenNthCalledWith(1, 3, empty);
+		expect(save).toHaveBeenNthCalledWith(2, 2, empty);
+		expect(save).toHaveBeenNthCalledWith(3, 1, {
+			title: "Title of https://example.com/",
+			description: null,
+			image: null,
+		});
+		expect(fetchPreview).toHaveBeenCalledTimes(2);
+		vi.restoreAllMocks();
+	});
+});
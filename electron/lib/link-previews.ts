@@ -0,0 +1,565 @@
+import dns from "node:dns";
+import fs from "node:fs";
+import http from "node:http";
+import https from "node:https";
+import net from "node:net";
+import path from "node:path";
+import { Readable } from "node:stream";
+
+/**
+ * What a web page says about itself, stored with the URL item linking to
+ * it.
+ */
+export type LinkPreview = {
+	/** Page title; empty if the page had none or could not be fetched */
+	title: string;
+	description: string | null;
+	/** Absolute URL of the page's preview image */
+	image: string | null;
+};
+
+/**
+ * A URL item whose page has not been fetched yet.
+ */
+export type LinkCandidate = {
+	id: number;
+	url: string;
+};
+
+/**
+ * Persisted link preview settings.
+ */
+export type LinkPreviewSettings = {
+	enabled: boolean;
+};
+
+/**
+ * Off until the user opts in: fetching a copied link tells its server the
+ * link was copied, and opening a one-time link (a sign-in or reset link)
+ * may use it up.
+ */
+const DEFAULT_LINK_PREVIEW_SETTINGS: LinkPreviewSettings = {
+	enabled: false,
+};
+
+const LINK_PREVIEWS_FILENAME = "link-previews.json";
+
+/**
+ * Upper bound on fetching one page (ms).
+ */
+const FETCH_TIMEOUT_MS = 5_000;
+
+/**
+ * Bytes of a page read; the tags a preview needs sit in its head.
+ */
+const MAX_PAGE_BYTES = 512 * 1024;
+
+/**
+ * Most redirects followed for one request.
+ */
+const MAX_REDIRECTS = 5;
+
+const REDIRECT_STATUSES = new Set([301, 302, 303, 307, 308]);
+
+/**
+ * Statuses whose responses have no body.
+ */
+const NULL_BODY_STATUSES = new Set([101, 204, 205, 304]);
+
+/**
+ * Loopback, private, link-local, shared, multicast and reserved ranges:
+ * no preview is fetched from them. IPv4-mapped IPv6 addresses match
+ * their IPv4 range.
+ */
+const NON_PUBLIC_RANGES: ReadonlyArray<[string, number, "ipv4" | "ipv6"]> = [
+	["0.0.0.0", 8, "ipv4"],
+	["10.0.0.0", 8, "ipv4"],
+	["100.64.0.0", 10, "ipv4"],
+	["127.0.0.0", 8, "ipv4"],
+	["169.254.0.0", 16, "ipv4"],
+	["172.16.0.0", 12, "ipv4"],
+	["192.0.0.0", 24, "ipv4"],
+	["192.168.0.0", 16, "ipv4"],
+	["198.18.0.0", 15, "ipv4"],
+	["224.0.0.0", 4, "ipv4"],
+	["240.0.0.0", 4, "ipv4"],
+	["::", 128, "ipv6"],
+	["::1", 128, "ipv6"],
+	["fc00::", 7, "ipv6"],
+	["fe80::", 10, "ipv6"],
+	["ff00::", 8, "ipv6"],
+];
+
+const nonPublicAddresses = new net.BlockList();
+for (const [network, prefix, type] of NON_PUBLIC_RANGES) {
+	nonPublicAddresses.addSubnet(network, prefix, type);
+}
+
+const MAX_TITLE_LENGTH = 300;
+
+const MAX_DESCRIPTION_LENGTH = 1_000;
+
+const EMPTY_PREVIEW: LinkPreview = {
+	title: "",
+	description: null,
+	image: null,
+};
+
+const NAMED_ENTITIES: Record<string, string> = {
+	amp: "&",
+	lt: "<",
+	gt: ">",
+	quot: '"',
+	apos: "'",
+	nbsp: " ",
+	ndash: "–",
+	mdash: "—",
+	hellip: "…",
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Decodes the character references in a piece of HTML text; unknown named
+ * references are kept as written.
+ * Pure function.
+ */
+export const decodeEntities = (html: string): string =>
+	html.replace(
+		/&(?:#(\d+)|#x([\da-f]+)|(\w+));/gi,
+		(entity, dec, hex, name) => {
+			if (name) return NAMED_ENTITIES[name.toLowerCase()] ?? entity;
+			const code = dec ? Number(dec) : Number.parseInt(hex, 16);
+			return code > 0 && code <= 0x10ffff ? String.fromCodePoint(code) : "";
+		},
+	);
+
+/**
+ * Decodes character references and collapses whitespace in a piece of
+ * HTML text, cutting it to `maxLength` characters.
+ * Pure function.
+ */
+const toPlainText = (html: string, maxLength: number): string => {
+	const text = decodeEntities(html).replace(/\s+/g, " ").trim();
+	return text.length > maxLength ? `${text.slice(0, maxLength - 1)}…` : text;
+};
+
+/**
+ * Attributes of one HTML tag, keyed by lowercased name.
+ * Pure function.
+ */
+export const parseTagAttributes = (tag: string): Map<string, string> => {
+	const attributes = new Map<string, string>();
+	const pattern = /([\w:-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))/g;
+	for (const match of tag.matchAll(pattern)) {
+		attributes.set(match[1].toLowerCase(), match[2] ?? match[3] ?? match[4]);
+	}
+	return attributes;
+};
+
+/**
+ * Reads the title, description and image of a page from its Open Graph
+ * and Twitter card tags, falling back to `<title>` and the description
+ * meta tag. Relative image URLs are resolved against `pageUrl`.
+ * Pure function.
+ */
+export const parseLinkPreview = (
+	html: string,
+	pageUrl: string,
+): LinkPreview => {
+	const meta = new Map<string, string>();
+	for (const [tag] of html.matchAll(/<meta\s[^>]*>/gi)) {
+		const attributes = parseTagAttributes(tag);
+		const key = attributes.get("property") ?? attributes.get("name");
+		const content = attributes.get("content");
+		if (key && content?.trim() && !meta.has(key.toLowerCase())) {
+			meta.set(key.toLowerCase(), content);
+		}
+	}
+	const first = (...keys: string[]) =>
+		keys.map((key) => meta.get(key)).find((value) => value !== undefined);
+
+	const titleTag = /<title[^>]*>([\s\S]*?)<\/title>/i.exec(html)?.[1];
+	const title = first("og:title", "twitter:title") ?? titleTag ?? "";
+	const description = first(
+		"og:description",
+		"description",
+		"twitter:description",
+	);
+
+	let image: string | null = null;
+	const imageUrl = first("og:image", "og:image:url", "twitter:image");
+	if (imageUrl) {
+		try {
+			const resolved = new URL(toPlainText(imageUrl, Infinity), pageUrl);
+			if (/^https?:$/.test(resolved.protocol)) image = resolved.href;
+		} catch {
+			// Unparseable image URLs are left out
+		}
+	}
+
+	return {
+		title: toPlainText(title, MAX_TITLE_LENGTH),
+		description: description
+			? toPlainText(description, MAX_DESCRIPTION_LENGTH) || null
+			: null,
+		image,
+	};
+};
+
+/**
+ * URL to fetch for a copied link, or null if it should not be fetched:
+ * anything but http(s), and hosts on the local machine or network, which
+ * have no public preview and may act on a plain GET.
+ * Pure function.
+ */
+export const toPreviewUrl = (text: string): string | null => {
+	const trimmed = text.trim();
+	let url: URL;
+	try {
+		url = new URL(/^www\./i.test(trimmed) ? `https://${trimmed}` : trimmed);
+	} catch {
+		return null;
+	}
+	if (!/^https?:$/.test(url.protocol)) return null;
+	const host = url.hostname.replace(/^\[|\]$/g, "").toLowerCase();
+	if (
+		net.isIP(host) !== 0 ||
+		!host.includes(".") ||
+		/\.(?:local|localhost|internal|lan|home\.arpa)$/.test(host)
+	) {
+		return null;
+	}
+	return url.href;
+};
+
+/**
+ * Whether an IP address is on the public internet, outside the ranges of
+ * the local machine and network.
+ * Pure function.
+ */
+export const isPublicAddress = (address: string): boolean => {
+	const family = net.isIP(address);
+	if (family === 0) return false;
+	return !nonPublicAddresses.check(address, family === 6 ? "ipv6" : "ipv4");
+};
+
+const invalid = (message: string) =>
+	new Error(`Invalid link preview settings: ${message}`);
+
+/**
+ * Validates a link preview settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean
+ */
+export const parseLinkPreviewSettings = (
+	input: unknown,
+	current: LinkPreviewSettings = DEFAULT_LINK_PREVIEW_SETTINGS,
+): LinkPreviewSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled } = input as Partial<
+		Record<keyof LinkPreviewSettings, unknown>
+	>;
+	const next: LinkPreviewSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Fetcher
+// ============================================================================
+
+/**
+ * Fetches the preview of a web page.
+ */
+export type LinkPreviewFetcher = (url: string) => Promise<LinkPreview>;
+
+/**
+ * Sends one request without following redirects, like `fetch` with
+ * `redirect: "manual"`.
+ */
+export type SendRequest = (url: string, init: RequestInit) => Promise<Response>;
+
+/**
+ * `lookup` for outgoing connections: resolves a host name, and refuses
+ * it unless every address it has is public. The connection is made to
+ * the addresses checked here, so a host cannot pass the check with one
+ * address and be reached at another (DNS rebinding).
+ */
+export const lookupPublicAddress: net.LookupFunction = (
+	hostname,
+	options,
+	callback,
+) => {
+	dns.lookup(hostname, { ...options, all: true }, (error, addresses) => {
+		if (error) {
+			callback(error, "");
+			return;
+		}
+		const refused = addresses.find(({ address }) => !isPublicAddress(address));
+		if (refused || addresses.length === 0) {
+			callback(
+				new Error(
+					`Refused to connect to ${hostname}: ${refused?.address ?? "no address"} is not public`,
+				),
+				"",
+			);
+			return;
+		}
+		if (options.all) {
+			callback(null, addresses);
+		} else {
+			callback(null, addresses[0].address, addresses[0].family);
+		}
+	});
+};
+
+/**
+ * Sends a GET over `node:http(s)`, connecting only to public addresses
+ * (`lookupPublicAddress`). Redirects are returned, not followed.
+ */
+export const sendToPublicHost: SendRequest = (url, init) =>
+	new Promise((resolve, reject) => {
+		const target = new URL(url);
+		const client = target.protocol === "https:" ? https : http;
+		const request = client.get(
+			target,
+			{
+				headers: Object.fromEntries(new Headers(init.headers)),
+				signal: init.signal ?? undefined,
+				lookup: lookupPublicAddress,
+			},
+			(message) => {
+				try {
+					const headers = new Headers();
+					for (let i = 0; i < message.rawHeaders.length; i += 2) {
+						headers.append(message.rawHeaders[i], message.rawHeaders[i + 1]);
+					}
+					const status = message.statusCode ?? 0;
+					const body = NULL_BODY_STATUSES.has(status)
+						? null
+						: Readable.toWeb(message);
+					const response = new Response(body, { status, headers });
+					// Like fetch, so relative URLs resolve against it
+					Object.defineProperty(response, "url", { value: url });
+					resolve(response);
+				} catch (error) {
+					message.destroy();
+					reject(error);
+				}
+			},
+		);
+		request.on("error", reject);
+	});
+
+/**
+ * Reads at most `maxBytes` of a response body, cancelling the rest of the
+ * download.
+ */
+export const readBody = async (
+	response: Response,
+	maxBytes: number,
+): Promise<Buffer> => {
+	if (!response.body) return Buffer.alloc(0);
+	const reader = response.body.getReader();
+	const chunks: Uint8Array[] = [];
+	let size = 0;
+	while (size < maxBytes) {
+		const { done, value } = await reader.read();
+		if (done) break;
+		chunks.push(value);
+		size += value.byteLength;
+	}
+	await reader.cancel().catch(() => undefined);
+	return Buffer.concat(chunks).subarray(0, maxBytes);
+};
+
+/**
+ * Fetches a public web URL, following redirects one hop at a time so
+ * every URL on the way passes `toPreviewUrl`: a public page cannot send
+ * the request on to the local machine or network. Sent with
+ * `sendToPublicHost`, no host name can resolve there either.
+ *
+ * @throws if a URL on the way may not be fetched, or after 5 redirects
+ */
+export const fetchPublicUrl = async (
+	send: SendRequest,
+	url: string,
+	init: Omit<RequestInit, "redirect"> = {},
+): Promise<Response> => {
+	let target = toPreviewUrl(url);
+	for (let redirects = 0; ; redirects++) {
+		if (target === null) {
+			throw new Error("Refused to fetch a URL that is not public");
+		}
+		const response = await send(target, { ...init, redirect: "manual" });
+		const location = response.headers.get("location");
+		if (!REDIRECT_STATUSES.has(response.status) || location === null) {
+			return response;
+		}
+		await response.body?.cancel().catch(() => undefined);
+		if (redirects === MAX_REDIRECTS) {
+			throw new Error(`Too many redirects from ${url}`);
+		}
+		try {
+			target = toPreviewUrl(new URL(location, target).href);
+		} catch {
+			target = null;
+		}
+	}
+};
+
+/**
+ * Creates a fetcher reading previews from the first 512 KB of HTML pages,
+ * giving up after 5 seconds.
+ *
+ * @throws from the returned fetcher if the request fails or the page is
+ *   not HTML
+ */
+export const createLinkPreviewFetcher = (
+	deps: { fetch?: SendRequest; timeoutMs?: number } = {},
+): LinkPreviewFetcher => {
+	const send = deps.fetch ?? sendToPublicHost;
+	const timeoutMs = deps.timeoutMs ?? FETCH_TIMEOUT_MS;
+
+	return async (url) => {
+		const response = await fetchPublicUrl(send, url, {
+			headers: { accept: "text/html,application/xhtml+xml" },
+			signal: AbortSignal.timeout(timeoutMs),
+		});
+		if (!response.ok) {
+			throw new Error(`HTTP ${response.status}`);
+		}
+		const type = response.headers.get("content-type") ?? "";
+		if (!/^(?:text\/html|application\/xhtml\+xml)\b/i.test(type)) {
+			await response.body?.cancel().catch(() => undefined);
+			throw new Error(`Not an HTML page: ${type || "unknown type"}`);
+		}
+		const body = await readBody(response, MAX_PAGE_BYTES);
+		return parseLinkPreview(body.toString("utf-8"), response.url || url);
+	};
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getLinkPreviewsPath = (userDataPath: string) =>
+	path.join(userDataPath, LINK_PREVIEWS_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readLinkPreviewsFromFile = (filePath: string): LinkPreviewSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_LINK_PREVIEW_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseLinkPreviewSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read link preview settings:", error);
+		return { ...DEFAULT_LINK_PREVIEW_SETTINGS };
+	}
+};
+
+const writeLinkPreviewsToFile = (
+	filePath: string,
+	settings: LinkPreviewSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Link Previews Module
+// ============================================================================
+
+export type LinkPreviewsDeps = {
+	userDataPath: string;
+	/** Next URL item not fetched yet, if any */
+	nextCandidate: () => LinkCandidate | undefined;
+	save: (id: number, preview: LinkPreview) => void;
+	fetchPreview?: LinkPreviewFetcher;
+	/** While true, pending links wait for a later `schedule` */
+	shouldDefer?: () => boolean;
+};
+
+/**
+ * Creates the link preview module.
+ * While enabled, URL items are fetched one at a time in the background,
+ * newest first. A link that is skipped or fails is stored with an empty
+ * title so it is not fetched again.
+ */
+export const createLinkPreviews = (deps: LinkPreviewsDeps) => {
+	const filePath = getLinkPreviewsPath(deps.userDataPath);
+	const fetchPreview = deps.fetchPreview ?? createLinkPreviewFetcher();
+	let settings = readLinkPreviewsFromFile(filePath);
+	let running = false;
+	let rerun = false;
+
+	const drain = async (): Promise<void> => {
+		while (settings.enabled && !deps.shouldDefer?.()) {
+			const candidate = deps.nextCandidate();
+			if (!candidate) return;
+			let preview = EMPTY_PREVIEW;
+			const url = toPreviewUrl(candidate.url);
+			if (url) {
+				try {
+					preview = await fetchPreview(url);
+				} catch (error) {
+					console.warn(`Link preview failed for item ${candidate.id}:`, error);
+				}
+			}
+			deps.save(candidate.id, preview);
+		}
+	};
+
+	/**
+	 * Starts fetching pending links, or has the current run look again
+	 * once it ends. Does nothing while disabled or deferred.
+	 */
+	const schedule = (): void => {
+		if (!settings.enabled || deps.shouldDefer?.()) return;
+		if (running) {
+			rerun = true;
+			return;
+		}
+		running = true;
+		void drain()
+			.catch((error) => console.error("Link previews stopped:", error))
+			.finally(() => {
+				running = false;
+				if (rerun) {
+					rerun = false;
+					schedule();
+				}
+			});
+	};
+
+	const getSettings = (): LinkPreviewSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): LinkPreviewSettings => {
+		const next = parseLinkPreviewSettings(input, settings);
+		writeLinkPreviewsToFile(filePath, next);
+		settings = next;
+		schedule();
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, schedule };
+};
@@ -0,0 +1,168 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createLogger,
+	filterRecentLogs,
+	findLoggingModule,
+	type LogEntry,
+	type LoggingSettings,
+	parseLoggingSettings,
+	parseRecentLogsQuery,
+	shouldLog,
+} from "./logger.js";
+
+describe("parseLoggingSettings", () => {
+	it("replaces module levels and drops cleared ones", () => {
+		const current = parseLoggingSettings({ modules: { sync: "debug" } });
+
+		expect(current).toEqual({ level: "info", modules: { sync: "debug" } });
+		expect(
+			parseLoggingSettings({ level: "warn", modules: { main: "error" } }),
+		).toEqual({ level: "warn", modules: { main: "error" } });
+		expect(
+			parseLoggingSettings({ modules: { sync: null } }, current).modules,
+		).toEqual({});
+	});
+
+	it("rejects unknown levels and module names", () => {
+		expect(() => parseLoggingSettings({ level: "verbose" })).toThrow(
+			"Invalid log level",
+		);
+		expect(() =>
+			parseLoggingSettings({ modules: { "../sync": "debug" } }),
+		).toThrow("unknown module ../sync");
+	});
+});
+
+describe("shouldLog", () => {
+	it("applies the module's level, else the default", () => {
+		const settings: LoggingSettings = {
+			level: "warn",
+			modules: { sync: "debug" },
+		};
+
+		expect(shouldLog(settings, "main", "error")).toBe(true);
+		expect(shouldLog(settings, "main", "info")).toBe(false);
+		expect(shouldLog(settings, "sync", "debug")).toBe(true);
+	});
+});
+
+describe("findLoggingModule", () => {
+	it("names the first file outside the logger", () => {
+		const stack = [
+			"Error",
+			"    at console.error (/app/electron-dist/lib/logger.js:310:36)",
+			"    at Object.download (/app/app.asar/electron-dist/lib/sync.js:574:11)",
+			"    at file:///app/electron-dist/main.js:10:5",
+		].join("\n");
+
+		expect(findLoggingModule(stack)).toBe("sync");
+		expect(findLoggingModule("Error\n    at node:internal/x:1:1")).toBe("main");
+		expect(findLoggingModule(undefined)).toBe("main");
+	});
+});
+
+describe("filterRecentLogs", () => {
+	const entry = (level: LogEntry["level"], module: string): LogEntry => ({
+		time: "2026-10-14T09:00:00.000Z",
+		level,
+		module,
+		message: `${module} ${level}`,
+	});
+	const entries = [
+		entry("error", "sync"),
+		entry("info", "sync"),
+		entry("warn", "main"),
+		entry("debug", "main"),
+	];
+
+	it("keeps the newest entries at least as severe as the level", () => {
+		expect(
+			filterRecentLogs(entries, parseRecentLogsQuery({ level: "warn" })),
+		).toEqual([entry("error", "sync"), entry("warn", "main")]);
+		expect(
+			filterRecentLogs(
+				entries,
+				parseRecentLogsQuery({ module: "main", limit: 1 }),
+			),
+		).toEqual([entry("debug", "main")]);
+		expect(() => parseRecentLogsQuery({ limit: 0 })).toThrow(
+			"limit must be a positive integer",
+		);
+	});
+});
+
+describe("createLogger", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-logs-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("writes JSON lines and rotates full files", () => {
+		const userDataPath = createTempUserDataPath();
+		const logger = createLogger({
+			userDataPath,
+			maxFileBytes: 100,
+			now: () => new Date("2026-10-14T09:00:00.000Z"),
+		});
+		const sync = logger.forModule("sync");
+
+		for (let i = 0; i < 3; i++) sync.error("Sync failed:", i);
+		sync.debug("Not recorded at the default level");
+
+		const logDirectory = logger.getLogDirectory();
+		expect(fs.readdirSync(logDirectory).sort()).toEqual([
+			"main.1.log",
+			"main.2.log",
+			"main.log",
+		]);
+		expect(
+			JSON.parse(fs.readFileSync(path.join(logDirectory, "main.log"), "utf-8")),
+		).toEqual({
+			time: "2026-10-14T09:00:00.000Z",
+			level: "error",
+			module: "sync",
+			message: "Sync failed: 2",
+		});
+		expect(logger.getRecentLogs().map((entry) => entry.message)).toEqual([
+			"Sync failed: 0",
+			"Sync failed: 1",
+			"Sync failed: 2",
+		]);
+	});
+
+	it("records console calls and changes levels at runtime", () => {
+		const logger = createLogger({ userDataPath: createTempUserDataPath() });
+		const target = {
+			error: vi.fn(),
+			warn: vi.fn(),
+			info: vi.fn(),
+			log: vi.fn(),
+			debug: vi.fn(),
+		};
+		const { debug } = target;
+		const restore = logger.captureConsole(target);
+
+		target.debug("hidden");
+		logger.updateSettings({ modules: { "logger.test": "debug" } });
+		target.debug("shown");
+		restore();
+
+		expect(debug).toHaveBeenCalledExactlyOnceWith("shown");
+		expect(logger.getRecentLogs()).toMatchObject([
+			{ level: "debug", module: "logger.test", message: "shown" },
+		]);
+		expect(target.debug).toBe(debug);
+	});
+});
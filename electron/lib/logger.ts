@@ -0,0 +1,438 @@
+import fs from "node:fs";
+import path from "node:path";
+import { format } from "node:util";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Severity of a log entry, most severe first.
+ */
+export type LogLevel = "error" | "warn" | "info" | "debug";
+
+/**
+ * One structured log entry, written to the log file as a JSON line.
+ */
+export type LogEntry = {
+	time: string;
+	level: LogLevel;
+	/** Source file the entry came from, such as `sync` or `main` */
+	module: string;
+	message: string;
+};
+
+/**
+ * Persisted log levels: entries less severe than their module's level
+ * are dropped.
+ */
+export type LoggingSettings = {
+	level: LogLevel;
+	/** Levels for single modules, overriding `level` */
+	modules: Record<string, LogLevel>;
+};
+
+/**
+ * Filters for reading recent entries.
+ */
+export type RecentLogsQuery = {
+	/** Newest entries returned, at most */
+	limit: number;
+	/** Least severe level returned; null for all */
+	level: LogLevel | null;
+	module: string | null;
+};
+
+/**
+ * Console methods the logger records.
+ */
+type ConsoleMethod = "error" | "warn" | "info" | "log" | "debug";
+
+type ConsoleLike = Record<ConsoleMethod, (...args: unknown[]) => void>;
+
+const LOG_LEVELS: readonly LogLevel[] = ["error", "warn", "info", "debug"];
+
+const CONSOLE_LEVELS: Record<ConsoleMethod, LogLevel> = {
+	error: "error",
+	warn: "warn",
+	info: "info",
+	log: "info",
+	debug: "debug",
+};
+
+const DEFAULT_LOGGING_SETTINGS: LoggingSettings = {
+	level: "info",
+	modules: {},
+};
+
+const LOGGING_FILENAME = "logging.json";
+
+/**
+ * Name of the log files: `main.log`, rotated to `main.<n>.log`.
+ */
+const LOG_BASENAME = "main";
+
+/**
+ * Size a log file grows to before it is rotated (bytes).
+ */
+const MAX_LOG_FILE_BYTES = 1024 * 1024;
+
+/**
+ * Log files kept, the current one included: `main.log`, then `main.1.log`
+ * (the newest rotated) to `main.4.log`.
+ */
+const MAX_LOG_FILES = 5;
+
+/**
+ * Entries kept in memory for the diagnostics view.
+ */
+const RECENT_LOG_CAPACITY = 1000;
+
+const DEFAULT_RECENT_LOGS_LIMIT = 200;
+
+const MODULE_NAME_PATTERN = /^[a-z0-9][a-z0-9._-]{0,63}$/;
+
+const DEFAULT_MODULE = "main";
+
+/**
+ * Stack frames skipped when finding who logged: the logger itself and
+ * Node's own console code.
+ */
+const INTERNAL_FRAME_PATTERN = /[/\\]logger\.[cm]?[jt]s:|node:internal|\(node:/;
+
+const FRAME_FILE_PATTERN = /([^/\\()\s]+?)\.[cm]?[jt]sx?:\d+:\d+\)?$/;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a log level.
+ * Pure function.
+ *
+ * @throws if the level is not known
+ */
+export const parseLogLevel = (input: unknown): LogLevel => {
+	if (!LOG_LEVELS.includes(input as LogLevel)) {
+		throw new Error(
+			`Invalid log level: expected one of ${LOG_LEVELS.join(", ")}`,
+		);
+	}
+	return input as LogLevel;
+};
+
+/**
+ * Validates a logging settings update.
+ * Pure function. Missing keys keep their current value; `modules`
+ * replaces the current overrides, and a module set to null loses its
+ * override.
+ *
+ * @throws if a level or module name is invalid
+ */
+export const parseLoggingSettings = (
+	input: unknown,
+	current: LoggingSettings = DEFAULT_LOGGING_SETTINGS,
+): LoggingSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid logging settings: expected an object");
+	}
+
+	const { level, modules } = input as Partial<
+		Record<keyof LoggingSettings, unknown>
+	>;
+	const next: LoggingSettings = {
+		level: current.level,
+		modules: { ...current.modules },
+	};
+
+	if (level !== undefined) next.level = parseLogLevel(level);
+	if (modules !== undefined) {
+		if (typeof modules !== "object" || modules === null) {
+			throw new Error("Invalid logging settings: modules must be an object");
+		}
+		next.modules = {};
+		for (const [name, moduleLevel] of Object.entries(modules)) {
+			if (!MODULE_NAME_PATTERN.test(name)) {
+				throw new Error(`Invalid logging settings: unknown module ${name}`);
+			}
+			if (moduleLevel !== null) next.modules[name] = parseLogLevel(moduleLevel);
+		}
+	}
+
+	return next;
+};
+
+/**
+ * Whether an entry is at least as severe as its module's level.
+ * Pure function.
+ */
+export const shouldLog = (
+	settings: LoggingSettings,
+	module: string,
+	level: LogLevel,
+): boolean => {
+	const threshold = settings.modules[module] ?? settings.level;
+	return LOG_LEVELS.indexOf(level) <= LOG_LEVELS.indexOf(threshold);
+};
+
+/**
+ * Names the module that logged from a stack trace: the file of the first
+ * frame outside the logger and Node, such as `sync` for `lib/sync.js`.
+ * Pure function. Falls back to `main`.
+ */
+export const findLoggingModule = (stack: string | undefined): string => {
+	const frames = (stack ?? "").split("\n").slice(1);
+	for (const frame of frames) {
+		if (INTERNAL_FRAME_PATTERN.test(frame)) continue;
+		const match = FRAME_FILE_PATTERN.exec(frame.trim());
+		if (match) return match[1].toLowerCase();
+	}
+	return DEFAULT_MODULE;
+};
+
+/**
+ * Validates a recent logs query.
+ * Pure function. Missing keys take their default: the newest 200
+ * entries, of every level and module.
+ *
+ * @throws if the limit is not a positive integer, or the level is unknown
+ */
+export const parseRecentLogsQuery = (input: unknown): RecentLogsQuery => {
+	const { limit, level, module } = (input ?? {}) as Partial<
+		Record<keyof RecentLogsQuery, unknown>
+	>;
+	if (
+		limit !== undefined &&
+		(typeof limit !== "number" || !Number.isInteger(limit) || limit < 1)
+	) {
+		throw new Error("Invalid logs query: limit must be a positive integer");
+	}
+	if (module !== undefined && module !== null && typeof module !== "string") {
+		throw new Error("Invalid logs query: module must be a string");
+	}
+	return {
+		limit: Math.min(
+			(limit as number | undefined) ?? DEFAULT_RECENT_LOGS_LIMIT,
+			RECENT_LOG_CAPACITY,
+		),
+		level: level === undefined || level === null ? null : parseLogLevel(level),
+		module: (module as string | null | undefined) ?? null,
+	};
+};
+
+/**
+ * Picks the newest entries matching a query, oldest first.
+ * Pure function.
+ */
+export const filterRecentLogs = (
+	entries: readonly LogEntry[],
+	query: RecentLogsQuery,
+): LogEntry[] => {
+	const matching = entries.filter(
+		(entry) =>
+			(query.level === null ||
+				LOG_LEVELS.indexOf(entry.level) <= LOG_LEVELS.indexOf(query.level)) &&
+			(query.module === null || entry.module === query.module),
+	);
+	return matching.slice(-query.limit);
+};
+
+/**
+ * Path of a log file: `main.log` for the current one, `main.<n>.log` for
+ * rotated ones.
+ */
+const getLogFilePath = (logDirectory: string, index: number): string =>
+	path.join(
+		logDirectory,
+		index === 0 ? `${LOG_BASENAME}.log` : `${LOG_BASENAME}.${index}.log`,
+	);
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+const getLoggingSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, LOGGING_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readLoggingSettingsFromFile = (filePath: string): LoggingSettings => {
+	if (!fs.existsSync(filePath)) return { ...DEFAULT_LOGGING_SETTINGS };
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseLoggingSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read logging settings, using defaults:", error);
+		return { ...DEFAULT_LOGGING_SETTINGS };
+	}
+};
+
+const writeLoggingSettingsToFile = (
+	filePath: string,
+	settings: LoggingSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Logger Module
+// ============================================================================
+
+export type LoggerDeps = {
+	userDataPath: string;
+	/** Folder of the log files; `logs` in userData by default */
+	logDirectory?: string;
+	maxFileBytes?: number;
+	now?: () => Date;
+};
+
+/**
+ * Creates the logger. Entries are JSON lines appended to `main.log`,
+ * which is rotated at 1 MB keeping four older files, and the newest
+ * 1000 are kept in memory for the diagnostics view. Levels apply per
+ * module and change at runtime. With `captureConsole`, the existing
+ * console calls across the main process become entries, named after
+ * the file they came from.
+ */
+export const createLogger = (deps: LoggerDeps) => {
+	const settingsPath = getLoggingSettingsPath(deps.userDataPath);
+	const logDirectory =
+		deps.logDirectory ?? path.join(deps.userDataPath, "logs");
+	const maxFileBytes = deps.maxFileBytes ?? MAX_LOG_FILE_BYTES;
+	const now = deps.now ?? (() => new Date());
+	let settings = readLoggingSettingsFromFile(settingsPath);
+	const recent: LogEntry[] = [];
+	/** Size of the current log file, read when first written */
+	let fileBytes: number | null = null;
+	/** Console methods before capture, which report the logger's own errors */
+	let original: ConsoleLike | null = null;
+	let writeFailed = false;
+
+	const reportFailure = (error: unknown): void => {
+		if (writeFailed) return;
+		writeFailed = true;
+		(original?.error ?? console.error)("Failed to write log file:", error);
+	};
+
+	const rotate = (): void => {
+		fs.rmSync(getLogFilePath(logDirectory, MAX_LOG_FILES - 1), {
+			force: true,
+		});
+		for (let index = MAX_LOG_FILES - 2; index >= 0; index--) {
+			const from = getLogFilePath(logDirectory, index);
+			if (fs.existsSync(from)) {
+				fs.renameSync(from, getLogFilePath(logDirectory, index + 1));
+			}
+		}
+		fileBytes = 0;
+	};
+
+	const append = (entry: LogEntry): void => {
+		const line = `${JSON.stringify(entry)}\n`;
+		const bytes = Buffer.byteLength(line);
+		try {
+			const filePath = getLogFilePath(logDirectory, 0);
+			if (fileBytes === null) {
+				fs.mkdirSync(logDirectory, { recursive: true });
+				fileBytes = fs.existsSync(filePath) ? fs.statSync(filePath).size : 0;
+			}
+			if (fileBytes > 0 && fileBytes + bytes > maxFileBytes) rotate();
+			fs.appendFileSync(filePath, line, "utf-8");
+			fileBytes += bytes;
+			writeFailed = false;
+		} catch (error) {
+			reportFailure(error);
+		}
+	};
+
+	/**
+	 * Records an entry if its module's level allows it. Returns whether it
+	 * was recorded.
+	 */
+	const log = (
+		level: LogLevel,
+		module: string,
+		...args: unknown[]
+	): boolean => {
+		if (!shouldLog(settings, module, level)) return false;
+		const entry: LogEntry = {
+			time: now().toISOString(),
+			level,
+			module,
+			message: format(...args),
+		};
+		recent.push(entry);
+		if (recent.length > RECENT_LOG_CAPACITY) recent.shift();
+		append(entry);
+		return true;
+	};
+
+	/**
+	 * Logging functions for one module.
+	 */
+	const forModule = (module: string) => ({
+		error: (...args: unknown[]) => log("error", module, ...args),
+		warn: (...args: unknown[]) => log("warn", module, ...args),
+		info: (...args: unknown[]) => log("info", module, ...args),
+		debug: (...args: unknown[]) => log("debug", module, ...args),
+	});
+
+	/**
+	 * Routes console calls through the logger: each is recorded under the
+	 * module that made it and still printed, unless its level is off for
+	 * that module. Returns a function restoring the console.
+	 */
+	const captureConsole = (target: ConsoleLike): (() => void) => {
+		const methods = Object.keys(CONSOLE_LEVELS) as ConsoleMethod[];
+		const saved = Object.fromEntries(
+			methods.map((method) => [method, target[method]]),
+		) as ConsoleLike;
+		original = saved;
+		for (const method of methods) {
+			target[method] = (...args: unknown[]) => {
+				const module = findLoggingModule(new Error().stack);
+				if (log(CONSOLE_LEVELS[method], module, ...args)) {
+					saved[method](...args);
+				}
+			};
+		}
+		return () => {
+			Object.assign(target, saved);
+			original = null;
+		};
+	};
+
+	const getRecentLogs = (input?: unknown): LogEntry[] =>
+		filterRecentLogs(recent, parseRecentLogsQuery(input)).map((entry) => ({
+			...entry,
+		}));
+
+	const getSettings = (): LoggingSettings => ({
+		level: settings.level,
+		modules: { ...settings.modules },
+	});
+
+	const updateSettings = (input: unknown): LoggingSettings => {
+		const next = parseLoggingSettings(input, settings);
+		writeLoggingSettingsToFile(settingsPath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const getLogDirectory = (): string => logDirectory;
+
+	return {
+		log,
+		forModule,
+		captureConsole,
+		getRecentLogs,
+		getSettings,
+		updateSettings,
+		getLogDirectory,
+	};
+};
+
+export type Logger = ReturnType<typeof createLogger>;
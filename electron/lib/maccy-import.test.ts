@@ -0,0 +1,134 @@
+import path from "node:path";
+import type Database from "better-sqlite3";
+import { describe, expect, it } from "vitest";
+import type { ImportHistoryItem } from "./history-repository.js";
+import { importMaccy, type MaccyItem, toImportItem } from "./maccy-import.js";
+
+const maccyItem = (
+	id: number,
+	contents: Array<[string, string]>,
+): MaccyItem => ({
+	id,
+	// 2024-01-01 12:00:00 UTC
+	lastCopiedAt: 725803200,
+	numberOfCopies: 3,
+	pin: null,
+	contents: new Map(
+		contents.map(([type, value]) => [type, Buffer.from(value)]),
+	),
+});
+
+const decodeImage = (data: Buffer) =>
+	data.toString() === "png" ? { png: data, width: 2, height: 1 } : null;
+
+describe("toImportItem", () => {
+	it("maps text with its copy time, count and pinned state", () => {
+		const item = {
+			...maccyItem(1, [
+				["public.utf8-plain-text", "hello"],
+				["public.html", "<b>hello</b>"],
+			]),
+			pin: "b",
+		};
+		expect(toImportItem(item, decodeImage)).toEqual({
+			type: "text",
+			content: "hello",
+			created_at: "2024-01-01 12:00:00",
+			favorite: false,
+			pinned: true,
+			use_count: 3,
+			tags: [],
+			note: null,
+			source: null,
+			rtf: null,
+			html: "<b>hello</b>",
+			image: null,
+		});
+	});
+
+	it("maps images and skips items without text or a readable image", () => {
+		const image = maccyItem(1, [["public.png", "png"]]);
+		const unreadable = maccyItem(2, [["public.tiff", "bad"]]);
+		const file = maccyItem(3, [["public.file-url", "file:///a"]]);
+
+		expect(toImportItem(image, decodeImage)).toMatchObject({
+			type: "image",
+			content: "",
+			image: { width: 2 },
+		});
+		expect(toImportItem(unreadable, decodeImage)).toBeNull();
+		expect(toImportItem(file, decodeImage)).toBeNull();
+	});
+});
+
+describe("importMaccy", () => {
+	/**
+	 * Answers the importer's queries from `items` in place of Maccy's store.
+	 */
+	const createConnect =
+		(items: MaccyItem[], tables = ["ZHISTORYITEM", "ZHISTORYITEMCONTENT"]) =>
+		() =>
+			({
+				prepare: (sql: string) => ({
+					all: (...params: number[]) => {
+						if (sql.includes("sqlite_master")) {
+							return tables.map((name) => ({ name }));
+						}
+						if (sql.includes("FROM ZHISTORYITEM ")) {
+							const [afterId, limit] = params;
+							return items
+								.filter((item) => item.id > afterId)
+								.slice(0, limit)
+								.map(({ contents, ...item }) => item);
+						}
+						const [first, last] = params;
+						return items
+							.filter((item) => item.id >= first && item.id <= last)
+							.flatMap((item) =>
+								[...item.contents].map(([type, value]) => ({
+									itemId: item.id,
+									type,
+									value,
+								})),
+							);
+					},
+				}),
+				close: () => {},
+			}) as unknown as Database.Database;
+
+	const dbPath = path.resolve("Storage.sqlite");
+
+	it("imports every item in batches", async () => {
+		const items = Array.from({ length: 600 }, (_, index) =>
+			maccyItem(index + 1, [["public.utf8-plain-text", `item ${index}`]]),
+		);
+		items.push(maccyItem(601, [["public.tiff", "bad"]]));
+		const stored: ImportHistoryItem[] = [];
+		const importItems = (batch: ImportHistoryItem[]) => {
+			stored.push(...batch);
+			return batch.length;
+		};
+
+		const report = await importMaccy(
+			{ importItems, connect: createConnect(items), decodeImage },
+			dbPath,
+		);
+
+		expect(report).toEqual({ imported: 600, skipped: 1 });
+		expect(stored[599].content).toBe("item 599");
+	});
+
+	it("rejects relative paths and other databases", async () => {
+		const deps = {
+			importItems: () => 0,
+			connect: createConnect([], ["history"]),
+			decodeImage,
+		};
+		await expect(importMaccy(deps, "Storage.sqlite")).rejects.toThrow(
+			"path must be absolute",
+		);
+		await expect(importMaccy(deps, dbPath)).rejects.toThrow(
+			"not a Maccy store",
+		);
+	});
+});
@@ -0,0 +1,193 @@
+import path from "node:path";
+import type Database from "better-sqlite3";
+import {
+	type HistoryImportReport,
+	type ImportItems,
+	importInBatches,
+	toHistoryTimestamp,
+} from "./history-import.js";
+import type { ImportHistoryItem, StoredImage } from "./history-repository.js";
+
+/**
+ * One Maccy history item with its pasteboard contents.
+ */
+export type MaccyItem = {
+	id: number;
+	/** Core Data timestamp: seconds since 2001-01-01 UTC */
+	lastCopiedAt: number | null;
+	numberOfCopies: number | null;
+	/** Pin shortcut key; set for pinned items */
+	pin: string | null;
+	/** Data by pasteboard type */
+	contents: Map<string, Buffer>;
+};
+
+/**
+ * Dependencies of a Maccy import.
+ */
+export type MaccyImportDeps = {
+	importItems: ImportItems;
+	/** Opens Maccy's store read-only */
+	connect: (file: string) => Database.Database;
+	/** Decodes PNG or TIFF data; null if it is not a readable image */
+	decodeImage: (data: Buffer) => StoredImage | null;
+};
+
+/**
+ * Pasteboard types, as Maccy names them in `ZHISTORYITEMCONTENT`.
+ */
+const TYPE_TEXT = "public.utf8-plain-text";
+const TYPE_HTML = "public.html";
+const TYPE_RTF = "public.rtf";
+const TYPE_PNG = "public.png";
+const TYPE_TIFF = "public.tiff";
+
+const IMPORTED_TYPES = [TYPE_TEXT, TYPE_HTML, TYPE_RTF, TYPE_PNG, TYPE_TIFF];
+
+/**
+ * Items read from Maccy's store at a time; images make rows large.
+ */
+const MACCY_BATCH_SIZE = 500;
+
+/**
+ * Start of Core Data time, 2001-01-01 UTC (ms since the Unix epoch).
+ */
+const CORE_DATA_EPOCH_MS = Date.UTC(2001, 0, 1);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Maps a Maccy item to a history item, keeping its last copy time, copy
+ * count and pinned state. Text keeps its HTML and RTF; items without text
+ * become image items.
+ * Pure function, apart from `decodeImage`.
+ *
+ * @returns null for items with neither text nor a readable image
+ */
+export const toImportItem = (
+	item: MaccyItem,
+	decodeImage: (data: Buffer) => StoredImage | null,
+): ImportHistoryItem | null => {
+	const read = (type: string): string | null =>
+		item.contents.get(type)?.toString("utf-8") || null;
+	const common = {
+		created_at: toHistoryTimestamp(
+			CORE_DATA_EPOCH_MS + (item.lastCopiedAt ?? 0) * 1000,
+		),
+		favorite: false,
+		pinned: item.pin !== null,
+		use_count: Math.max(1, Math.floor(item.numberOfCopies ?? 1)),
+		tags: [],
+		note: null,
+		source: null,
+	};
+
+	const text = read(TYPE_TEXT);
+	if (text?.trim()) {
+		return {
+			...common,
+			type: "text",
+			content: text,
+			rtf: read(TYPE_RTF),
+			html: read(TYPE_HTML),
+			image: null,
+		};
+	}
+
+	const data = item.contents.get(TYPE_PNG) ?? item.contents.get(TYPE_TIFF);
+	const image = data ? decodeImage(data) : null;
+	if (!image) return null;
+	return {
+		...common,
+		type: "image",
+		content: "",
+		rtf: null,
+		html: null,
+		image,
+	};
+};
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/**
+ * Reads the items after `afterId`, oldest first, with their contents.
+ */
+const readItems = (
+	db: Database.Database,
+	afterId: number,
+	limit: number,
+): MaccyItem[] => {
+	const items = db
+		.prepare(
+			"SELECT Z_PK AS id, COALESCE(ZLASTCOPIEDAT, ZFIRSTCOPIEDAT) AS lastCopiedAt, ZNUMBEROFCOPIES AS numberOfCopies, ZPIN AS pin FROM ZHISTORYITEM WHERE Z_PK > ? ORDER BY Z_PK LIMIT ?",
+		)
+		.all(afterId, limit) as Array<Omit<MaccyItem, "contents">>;
+	if (items.length === 0) return [];
+
+	const placeholders = IMPORTED_TYPES.map(() => "?").join(", ");
+	const rows = db
+		.prepare(
+			`SELECT ZITEM AS itemId, ZTYPE AS type, ZVALUE AS value FROM ZHISTORYITEMCONTENT WHERE ZITEM BETWEEN ? AND ? AND ZTYPE IN (${placeholders})`,
+		)
+		.all(
+			items[0].id,
+			items[items.length - 1].id,
+			...IMPORTED_TYPES,
+		) as Array<{ itemId: number; type: string; value: Buffer | null }>;
+
+	const byId = new Map(
+		items.map((item) => [item.id, { ...item, contents: new Map() }]),
+	);
+	for (const row of rows) {
+		if (row.value) byId.get(row.itemId)?.contents.set(row.type, row.value);
+	}
+	return [...byId.values()];
+};
+
+/**
+ * Imports the history in Maccy's store (`Storage.sqlite`) with the
+ * original copy times. Text and images are imported; other pasteboard
+ * types are not, and items already in history are skipped.
+ *
+ * @throws if the path is not absolute or is not a Maccy store
+ */
+export const importMaccy = async (
+	deps: MaccyImportDeps,
+	dbPath: unknown,
+): Promise<HistoryImportReport> => {
+	if (typeof dbPath !== "string" || !path.isAbsolute(dbPath)) {
+		throw new Error("Invalid import: path must be absolute");
+	}
+
+	const db = deps.connect(dbPath);
+	try {
+		const tables = db
+			.prepare(
+				"SELECT name FROM sqlite_master WHERE type = 'table' AND name IN ('ZHISTORYITEM', 'ZHISTORYITEMCONTENT')",
+			)
+			.all();
+		if (tables.length !== 2) {
+			throw new Error("Invalid import: not a Maccy store");
+		}
+
+		const report: HistoryImportReport = { imported: 0, skipped: 0 };
+		let afterId = 0;
+		while (true) {
+			const items = readItems(db, afterId, MACCY_BATCH_SIZE);
+			if (items.length === 0) return report;
+			afterId = items[items.length - 1].id;
+
+			const batch = await importInBatches(deps.importItems, items, (item) =>
+				toImportItem(item, deps.decodeImage),
+			);
+			report.imported += batch.imported;
+			report.skipped += batch.skipped;
+		}
+	} finally {
+		db.close();
+	}
+};
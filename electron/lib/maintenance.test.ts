@@ -0,0 +1,28 @@
+import { describe, expect, it } from "vitest";
+import { shouldRunMaintenance } from "./maintenance.js";
+
+const HOUR_MS = 60 * 60 * 1000;
+
+describe("shouldRunMaintenance", () => {
+	const base = {
+		idleSeconds: 600,
+		idleThresholdSeconds: 300,
+		lastRunAt: null,
+		runIntervalMs: 24 * HOUR_MS,
+		now: 100 * HOUR_MS,
+	};
+
+	it("runs the first pass once the system is idle", () => {
+		expect(shouldRunMaintenance(base)).toBe(true);
+		expect(shouldRunMaintenance({ ...base, idleSeconds: 299 })).toBe(false);
+	});
+
+	it("waits a full run interval between passes", () => {
+		expect(
+			shouldRunMaintenance({ ...base, lastRunAt: base.now - 23 * HOUR_MS }),
+		).toBe(false);
+		expect(
+			shouldRunMaintenance({ ...base, lastRunAt: base.now - 24 * HOUR_MS }),
+		).toBe(true);
+	});
+});
@@ -0,0 +1,164 @@
+import type Database from "better-sqlite3";
+import { checkpointWal } from "./db-connection.js";
+
+/**
+ * Result of one maintenance pass.
+ */
+export type MaintenanceReport = {
+	/** Database size before the pass (bytes) */
+	sizeBefore: number;
+	/** Database size after the pass (bytes) */
+	sizeAfter: number;
+	/** Space returned to the filesystem (bytes) */
+	reclaimedBytes: number;
+	durationMs: number;
+};
+
+/**
+ * How often the scheduler checks whether the system is idle (ms).
+ */
+const DEFAULT_CHECK_INTERVAL_MS = 10 * 60 * 1000;
+
+/**
+ * Minimum time between scheduled passes (ms).
+ */
+const DEFAULT_RUN_INTERVAL_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * System idle time required before a scheduled pass starts (seconds).
+ */
+const DEFAULT_IDLE_THRESHOLD_SECONDS = 5 * 60;
+
+/**
+ * SQLite `auto_vacuum` mode that lets free pages be released on demand.
+ */
+const AUTO_VACUUM_INCREMENTAL = 2;
+
+/**
+ * Decides whether a scheduled maintenance pass is due.
+ * Pure function.
+ */
+export const shouldRunMaintenance = (state: {
+	idleSeconds: number;
+	idleThresholdSeconds: number;
+	lastRunAt: number | null;
+	runIntervalMs: number;
+	now: number;
+}): boolean =>
+	state.idleSeconds >= state.idleThresholdSeconds &&
+	(state.lastRunAt === null ||
+		state.now - state.lastRunAt >= state.runIntervalMs);
+
+const getDatabaseSize = (db: Database.Database): number =>
+	(db.pragma("page_count", { simple: true }) as number) *
+	(db.pragma("page_size", { simple: true }) as number);
+
+/**
+ * Compacts and re-optimizes the database.
+ *
+ * Merges FTS index segments and rebuilds indexes, then returns free pages
+ * to the filesystem. Databases created before incremental vacuum was
+ * enabled get one full VACUUM to switch modes; later passes only release
+ * free pages. Finishes by refreshing query planner statistics.
+ */
+const runDatabaseMaintenance = (db: Database.Database): MaintenanceReport => {
+	const startedAt = Date.now();
+	const sizeBefore = getDatabaseSize(db);
+
+	db.exec("INSERT INTO history_fts(history_fts) VALUES('optimize')");
+	db.exec("REINDEX");
+
+	const autoVacuum = db.pragma("auto_vacuum", { simple: true });
+	if (autoVacuum !== AUTO_VACUUM_INCREMENTAL) {
+		db.pragma("auto_vacuum = INCREMENTAL");
+		db.exec("VACUUM");
+	} else {
+		db.exec("PRAGMA incremental_vacuum");
+	}
+	// The file only shrinks once the log is copied back into it
+	checkpointWal(db);
+
+	db.exec("ANALYZE");
+
+	const sizeAfter = getDatabaseSize(db);
+	return {
+		sizeBefore,
+		sizeAfter,
+		reclaimedBytes: Math.max(0, sizeBefore - sizeAfter),
+		durationMs: Date.now() - startedAt,
+	};
+};
+
+export type MaintenanceModuleDeps = {
+	getDb: () => Database.Database;
+	/** Seconds since the user last interacted with the system */
+	getIdleSeconds: () => number;
+	/** Interval between idle checks in milliseconds */
+	checkIntervalMs?: number;
+	/** Minimum time between scheduled passes in milliseconds */
+	runIntervalMs?: number;
+	idleThresholdSeconds?: number;
+	/** While true, scheduled passes wait (e.g. on battery) */
+	shouldDefer?: () => boolean;
+};
+
+/**
+ * Creates the maintenance scheduler.
+ * A pass runs at most once per run interval, and only after the system
+ * has been idle for a while so VACUUM never stalls active use.
+ */
+export const createMaintenanceModule = (deps: MaintenanceModuleDeps) => {
+	const checkIntervalMs = deps.checkIntervalMs ?? DEFAULT_CHECK_INTERVAL_MS;
+	const runIntervalMs = deps.runIntervalMs ?? DEFAULT_RUN_INTERVAL_MS;
+	const idleThresholdSeconds =
+		deps.idleThresholdSeconds ?? DEFAULT_IDLE_THRESHOLD_SECONDS;
+	let timer: NodeJS.Timeout | null = null;
+	let lastRunAt: number | null = null;
+
+	/**
+	 * Runs a maintenance pass immediately.
+	 */
+	const runNow = (): MaintenanceReport => {
+		const report = runDatabaseMaintenance(deps.getDb());
+		lastRunAt = Date.now();
+		return report;
+	};
+
+	const tick = (): void => {
+		if (deps.shouldDefer?.()) return;
+		const due = shouldRunMaintenance({
+			idleSeconds: deps.getIdleSeconds(),
+			idleThresholdSeconds,
+			lastRunAt,
+			runIntervalMs,
+			now: Date.now(),
+		});
+		if (!due) return;
+
+		try {
+			const report = runNow();
+			// Debug level: the logger drops it unless maintenance logging is raised
+			console.debug(
+				`Database maintenance reclaimed ${report.reclaimedBytes} bytes in ${report.durationMs}ms`,
+			);
+		} catch (error) {
+			console.error("Database maintenance failed:", error);
+			// Wait a full interval before retrying a failing pass
+			lastRunAt = Date.now();
+		}
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		timer = setInterval(tick, checkIntervalMs);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	return { runNow, start, stop };
+};
@@ -0,0 +1,176 @@
+import { describe, expect, it } from "vitest";
+import {
+	htmlToMarkdown,
+	markdownToHtml,
+	parseMarkupConversionRequest,
+} from "./markdown.js";
+
+describe("parseMarkupConversionRequest", () => {
+	it("defaults the target to a new item", () => {
+		expect(
+			parseMarkupConversionRequest({ conversion: "htmlToMarkdown" }),
+		).toEqual({ conversion: "htmlToMarkdown", target: "item" });
+	});
+
+	it("rejects unknown conversions and targets", () => {
+		expect(() =>
+			parseMarkupConversionRequest({ conversion: "markdownToPdf" }),
+		).toThrow(
+			"Invalid markup conversion: conversion must be one of markdownToHtml",
+		);
+		expect(() =>
+			parseMarkupConversionRequest({
+				conversion: "markdownToHtml",
+				target: "file",
+			}),
+		).toThrow("target must be one of item, clipboard");
+	});
+});
+
+describe("markdownToHtml", () => {
+	it("converts headings, paragraphs and inline formatting", () => {
+		const markdown = [
+			"# Notes",
+			"",
+			"Some *em*, **bold**, ~~old~~ and `a < b`, see",
+			'[the docs](https://example.com/?a=1&b=2 "Docs").',
+		].join("\n");
+
+		expect(markdownToHtml(markdown)).toBe(
+			[
+				"<h1>Notes</h1>",
+				"<p>Some <em>em</em>, <strong>bold</strong>, <del>old</del> and <code>a &lt; b</code>, see",
+				'<a href="https://example.com/?a=1&amp;b=2" title="Docs">the docs</a>.</p>',
+			].join("\n"),
+		);
+	});
+
+	it("nests lists and keeps code blocks verbatim", () => {
+		const markdown = [
+			"- one",
+			"  - nested",
+			"- two",
+			"",
+			"```js",
+			"if (a < b) go();",
+			"```",
+		].join("\n");
+
+		expect(markdownToHtml(markdown)).toBe(
+			[
+				"<ul>",
+				"<li>one",
+				"<ul>",
+				"<li>nested</li>",
+				"</ul></li>",
+				"<li>two</li>",
+				"</ul>",
+				'<pre><code class="language-js">if (a &lt; b) go();</code></pre>',
+			].join("\n"),
+		);
+	});
+
+	it("converts quotes, tables and numbered lists", () => {
+		const markdown = [
+			"> quoted",
+			"",
+			"| Name | Qty |",
+			"|:-----|----:|",
+			"| Pear | 3 |",
+			"",
+			"3. third",
+			"4. fourth",
+		].join("\n");
+
+		expect(markdownToHtml(markdown)).toBe(
+			[
+				"<blockquote>",
+				"<p>quoted</p>",
+				"</blockquote>",
+				"<table>",
+				"<thead>",
+				'<tr><th style="text-align: left">Name</th><th style="text-align: right">Qty</th></tr>',
+				"</thead>",
+				"<tbody>",
+				'<tr><td style="text-align: left">Pear</td><td style="text-align: right">3</td></tr>',
+				"</tbody>",
+				"</table>",
+				'<ol start="3">',
+				"<li>third</li>",
+				"<li>fourth</li>",
+				"</ol>",
+			].join("\n"),
+		);
+	});
+
+	it("escapes raw HTML and drops script links", () => {
+		expect(
+			markdownToHtml("<script>x()</script> [click](javascript:void)"),
+		).toBe("<p>&lt;script&gt;x()&lt;/script&gt; click</p>");
+	});
+});
+
+describe("htmlToMarkdown", () => {
+	it("converts a browser fragment to Markdown", () => {
+		const html = `<html><head><style>p { color: red }</style></head><body>
+			<!--StartFragment--><h2>Hello <em>world</em></h2>
+			<p>Text with <b>bold </b>and <a href="https://example.com/?a=1&amp;b=2">a link</a>.<br>
+			Next line</p>
+			<ul><li>One<li>Two<ul><li>Nested</li></ul></li></ul>
+			<!--EndFragment--></body></html>`;
+
+		expect(htmlToMarkdown(html)).toBe(
+			[
+				"## Hello *world*",
+				"",
+				"Text with **bold** and [a link](https://example.com/?a=1&b=2).  ",
+				"Next line",
+				"",
+				"- One",
+				"- Two",
+				"  - Nested",
+			].join("\n"),
+		);
+	});
+
+	it("converts code, quotes and tables", () => {
+		const html = [
+			'<pre><code class="language-js">if (a &lt; b) {\n  go();\n}\n</code></pre>',
+			"<blockquote><p>First</p><p>Second</p></blockquote>",
+			"<table><tr><th>A</th><th>B</th></tr><tr><td>1</td><td>x | y</td></tr></table>",
+		].join("");
+
+		expect(htmlToMarkdown(html)).toBe(
+			[
+				"```js",
+				"if (a < b) {",
+				"  go();",
+				"}",
+				"```",
+				"",
+				"> First",
+				">",
+				"> Second",
+				"",
+				"| A | B |",
+				"| --- | --- |",
+				"| 1 | x \\| y |",
+			].join("\n"),
+		);
+	});
+
+	it("reads Google Docs styles and escapes Markdown characters", () => {
+		const html =
+			'<b style="font-weight:normal" id="docs-internal-guid-1"><p><span style="font-weight:700">Bold</span> <span style="font-style:italic">it</span> costs *5*</p></b>';
+
+		expect(htmlToMarkdown(html)).toBe("**Bold** *it* costs \\*5\\*");
+	});
+
+	it("drops script links and inline images", () => {
+		expect(
+			htmlToMarkdown(
+				'<p><a href="javascript:x()">bad</a> <img src="data:image/png;base64,AA"></p>',
+			),
+		).toBe("bad");
+	});
+});
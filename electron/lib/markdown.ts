@@ -0,0 +1,752 @@
+import { decodeEntities, parseTagAttributes } from "./link-previews.js";
+import { isTransformTarget, type TransformTarget } from "./text-transforms.js";
+
+const MARKUP_CONVERSIONS = ["markdownToHtml", "htmlToMarkdown"] as const;
+
+export type MarkupConversion = (typeof MARKUP_CONVERSIONS)[number];
+
+/**
+ * A validated request to convert a text item between Markdown and HTML.
+ */
+export type MarkupConversionRequest = {
+	conversion: MarkupConversion;
+	target: TransformTarget;
+};
+
+/**
+ * Elements whose content is never part of the converted text.
+ */
+const SKIPPED_ELEMENTS = new Set([
+	"head",
+	"script",
+	"style",
+	"template",
+	"noscript",
+	"title",
+]);
+
+const VOID_ELEMENTS = new Set([
+	"br",
+	"hr",
+	"img",
+	"input",
+	"meta",
+	"link",
+	"col",
+	"area",
+	"base",
+	"source",
+	"wbr",
+]);
+
+/**
+ * Elements converted as blocks, separated from their neighbours by a
+ * blank line.
+ */
+const BLOCK_ELEMENTS = new Set([
+	"address",
+	"article",
+	"aside",
+	"blockquote",
+	"body",
+	"dd",
+	"div",
+	"dl",
+	"dt",
+	"figure",
+	"footer",
+	"h1",
+	"h2",
+	"h3",
+	"h4",
+	"h5",
+	"h6",
+	"header",
+	"hr",
+	"html",
+	"li",
+	"main",
+	"nav",
+	"ol",
+	"p",
+	"pre",
+	"section",
+	"table",
+	"tbody",
+	"td",
+	"tfoot",
+	"th",
+	"thead",
+	"tr",
+	"ul",
+]);
+
+const LIST_ITEM_PATTERN = /^( {0,3})([-*+]|\d{1,9}[.)])( +|$)(.*)$/;
+const FENCE_PATTERN = /^ {0,3}(`{3,}|~{3,})\s*([\w+#.-]*)/;
+const HEADING_PATTERN = /^ {0,3}(#{1,6})(?:\s+(.*?))?(?:\s+#+)?\s*$/;
+const RULE_PATTERN = /^ {0,3}([-*_])(?:\s*\1){2,}\s*$/;
+const QUOTE_PATTERN = /^ {0,3}> ?/;
+const SETEXT_PATTERN = /^ {0,3}(=+|-+)\s*$/;
+const TABLE_DELIMITER_PATTERN =
+	/^\s*\|?\s*:?-+:?\s*(?:\|\s*:?-+:?\s*)*\|?\s*$/;
+
+// ============================================================================
+// Request Validation
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid markup conversion: ${message}`);
+
+/**
+ * Validates a conversion request from the renderer.
+ * Pure function. `target` defaults to a new item.
+ *
+ * @throws if the conversion or target is unknown
+ */
+export const parseMarkupConversionRequest = (
+	value: unknown,
+): MarkupConversionRequest => {
+	if (typeof value !== "object" || value === null) {
+		throw invalid("expected an object");
+	}
+	const { conversion, target = "item" } = value as Record<string, unknown>;
+	if (!MARKUP_CONVERSIONS.includes(conversion as MarkupConversion)) {
+		throw invalid(
+			`conversion must be one of ${MARKUP_CONVERSIONS.join(", ")}`,
+		);
+	}
+	if (!isTransformTarget(target)) {
+		throw invalid("target must be one of item, clipboard");
+	}
+	return { conversion: conversion as MarkupConversion, target };
+};
+
+// ============================================================================
+// Markdown to HTML
+// ============================================================================
+
+const escapeHtml = (text: string): string =>
+	text
+		.replace(/&/g, "&amp;")
+		.replace(/</g, "&lt;")
+		.replace(/>/g, "&gt;")
+		.replace(/"/g, "&quot;");
+
+/**
+ * Whether a link target may be carried into the HTML: relative, or one of
+ * the schemes a mail or document editor opens safely.
+ */
+const isSafeUrl = (url: string): boolean =>
+	!/^[a-z][\w+.-]*:/i.test(url) || /^(?:https?|mailto|tel):/i.test(url);
+
+const emphasize = (html: string): string =>
+	html
+		.replace(/\*\*\*(?=\S)([\s\S]*?\S)\*\*\*/g, "<strong><em>$1</em></strong>")
+		.replace(/(\*\*|__)(?=\S)([\s\S]*?\S)\1/g, "<strong>$2</strong>")
+		.replace(/~~(?=\S)([\s\S]*?\S)~~/g, "<del>$1</del>")
+		.replace(/\*(?=\S)([\s\S]*?\S)\*/g, "<em>$1</em>")
+		.replace(/(?<!\w)_(?=\S)([\s\S]*?\S)_(?!\w)/g, "<em>$1</em>");
+
+/**
+ * Converts the inline Markdown of one block: code spans, links, images,
+ * emphasis, strikethrough and hard line breaks. Raw HTML is escaped.
+ */
+const renderInline = (text: string): string => {
+	// Finished HTML is held back so later rules do not rewrite it
+	const held: string[] = [];
+	const hold = (html: string) => `\u0000${held.push(html) - 1}\u0000`;
+
+	const titleOf = (title?: string) => (title ? ` title="${title}"` : "");
+
+	const html = escapeHtml(
+		text
+			.replace(/(`+)([\s\S]*?[^`])\1(?!`)/g, (_match, _ticks, code: string) => {
+				const content = code.replace(/\n/g, " ").replace(/^ (.+) $/, "$1");
+				return hold(`<code>${escapeHtml(content)}</code>`);
+			})
+			.replace(/<(https?:\/\/[^\s<>]+)>/g, (_match, url: string) =>
+				hold(`<a href="${escapeHtml(url)}">${escapeHtml(url)}</a>`),
+			)
+			.replace(/\\([!-/:-@[-`{-~])/g, (_match, char: string) =>
+				hold(escapeHtml(char)),
+			),
+	)
+		.replace(
+			/!\[([^\]]*)\]\(([^)\s]+)(?:\s+&quot;(.*?)&quot;)?\)/g,
+			(match, alt: string, url: string, title?: string) =>
+				isSafeUrl(url)
+					? hold(`<img src="${url}" alt="${alt}"${titleOf(title)}>`)
+					: match,
+		)
+		.replace(
+			/\[([^\]]+)\]\(([^)\s]+)(?:\s+&quot;(.*?)&quot;)?\)/g,
+			(_match, label: string, url: string, title?: string) =>
+				isSafeUrl(url)
+					? hold(`<a href="${url}"${titleOf(title)}>${emphasize(label)}</a>`)
+					: emphasize(label),
+		);
+
+	return emphasize(html)
+		.replace(/(?: {2,}|\\)\n/g, "<br>\n")
+		.replace(/\u0000(\d+)\u0000/g, (_match, index: string) =>
+			held[Number(index)],
+		);
+};
+
+const indentOf = (line: string): number => line.match(/^ */)?.[0].length ?? 0;
+
+const splitTableRow = (line: string): string[] =>
+	line
+		.trim()
+		.replace(/^\|/, "")
+		.replace(/(?<!\\)\|$/, "")
+		.split(/(?<!\\)\|/)
+		.map((cell) => cell.trim().replace(/\\\|/g, "|"));
+
+const isTableStart = (lines: string[], index: number): boolean =>
+	lines[index].includes("|") &&
+	(lines[index + 1] ?? "").includes("|") &&
+	TABLE_DELIMITER_PATTERN.test(lines[index + 1] ?? "");
+
+/**
+ * Whether a line starts a block that ends a paragraph before it.
+ */
+const interruptsParagraph = (line: string): boolean =>
+	FENCE_PATTERN.test(line) ||
+	HEADING_PATTERN.test(line) ||
+	RULE_PATTERN.test(line) ||
+	QUOTE_PATTERN.test(line) ||
+	LIST_ITEM_PATTERN.test(line);
+
+const renderTable = (rows: string[][], delimiter: string[]): string => {
+	const alignments = delimiter.map((cell) => {
+		if (cell.startsWith(":") && cell.endsWith(":")) return "center";
+		if (cell.endsWith(":")) return "right";
+		return cell.startsWith(":") ? "left" : null;
+	});
+	const renderRow = (cells: string[], tag: "th" | "td") =>
+		`<tr>${alignments
+			.map((alignment, index) => {
+				const style = alignment ? ` style="text-align: ${alignment}"` : "";
+				return `<${tag}${style}>${renderInline(cells[index] ?? "")}</${tag}>`;
+			})
+			.join("")}</tr>`;
+
+	const [header, ...body] = rows;
+	const parts = ["<table>", "<thead>", renderRow(header, "th"), "</thead>"];
+	if (body.length > 0) {
+		parts.push("<tbody>", ...body.map((row) => renderRow(row, "td")));
+		parts.push("</tbody>");
+	}
+	parts.push("</table>");
+	return parts.join("\n");
+};
+
+/**
+ * Converts a list starting at `start`, returning its HTML and the index of
+ * the first line after it. Items continue on lines indented to their
+ * content; a blank line between items makes the list loose, wrapping
+ * item text in paragraphs.
+ */
+const renderList = (
+	lines: string[],
+	start: number,
+): { html: string; end: number } => {
+	const first = LIST_ITEM_PATTERN.exec(lines[start]);
+	const ordered = first !== null && /\d/.test(first[2]);
+	const items: string[][] = [];
+	let loose = false;
+	let index = start;
+
+	while (index < lines.length) {
+		const match = LIST_ITEM_PATTERN.exec(lines[index]);
+		if (!match || /\d/.test(match[2]) !== ordered) break;
+		const markerIndent = match[1].length;
+		const offset = markerIndent + match[2].length + (match[3].length || 1);
+		const item = [match[4]];
+		index++;
+
+		while (index < lines.length) {
+			const line = lines[index];
+			if (line.trim() === "") {
+				const next = lines[index + 1];
+				if (next === undefined || indentOf(next) < offset) break;
+				item.push("");
+				index++;
+				continue;
+			}
+			const indent = indentOf(line);
+			const nested = indent > markerIndent && LIST_ITEM_PATTERN.test(line);
+			if (indent >= offset || nested) {
+				item.push(line.slice(Math.min(indent, offset)));
+			} else if (!interruptsParagraph(line) && item[item.length - 1] !== "") {
+				// A lazy continuation of the item's paragraph
+				item.push(line.trim());
+			} else {
+				break;
+			}
+			index++;
+		}
+		items.push(item);
+
+		let next = index;
+		while (next < lines.length && lines[next].trim() === "") next++;
+		const following = LIST_ITEM_PATTERN.exec(lines[next] ?? "");
+		if (next === index || !following) continue;
+		if (/\d/.test(following[2]) !== ordered) break;
+		loose = true;
+		index = next;
+	}
+
+	const startNumber = first && ordered ? Number.parseInt(first[2], 10) : 1;
+	const open = ordered
+		? `<ol${startNumber !== 1 ? ` start="${startNumber}"` : ""}>`
+		: "<ul>";
+	const html = [
+		open,
+		...items.map((item) => `<li>${renderBlocks(item, !loose)}</li>`),
+		ordered ? "</ol>" : "</ul>",
+	].join("\n");
+	return { html, end: index };
+};
+
+/**
+ * Converts Markdown block structure; `tight` leaves paragraph text
+ * unwrapped, as inside the items of a tight list.
+ */
+const renderBlocks = (lines: string[], tight = false): string => {
+	const blocks: string[] = [];
+	let index = 0;
+
+	while (index < lines.length) {
+		const line = lines[index];
+		if (line.trim() === "") {
+			index++;
+			continue;
+		}
+
+		const fence = FENCE_PATTERN.exec(line);
+		if (fence) {
+			const [marker] = fence[1];
+			const closing = new RegExp(`^ {0,3}${marker}{${fence[1].length},}\\s*$`);
+			const code: string[] = [];
+			index++;
+			while (index < lines.length && !closing.test(lines[index])) {
+				code.push(lines[index]);
+				index++;
+			}
+			index++;
+			const language = fence[2]
+				? ` class="language-${escapeHtml(fence[2])}"`
+				: "";
+			blocks.push(
+				`<pre><code${language}>${escapeHtml(code.join("\n"))}</code></pre>`,
+			);
+			continue;
+		}
+
+		const heading = HEADING_PATTERN.exec(line);
+		if (heading) {
+			const level = heading[1].length;
+			blocks.push(`<h${level}>${renderInline(heading[2] ?? "")}</h${level}>`);
+			index++;
+			continue;
+		}
+
+		if (RULE_PATTERN.test(line)) {
+			blocks.push("<hr>");
+			index++;
+			continue;
+		}
+
+		if (QUOTE_PATTERN.test(line)) {
+			const quoted: string[] = [];
+			while (index < lines.length && QUOTE_PATTERN.test(lines[index])) {
+				quoted.push(lines[index].replace(QUOTE_PATTERN, ""));
+				index++;
+			}
+			blocks.push(`<blockquote>\n${renderBlocks(quoted)}\n</blockquote>`);
+			continue;
+		}
+
+		if (LIST_ITEM_PATTERN.test(line)) {
+			const list = renderList(lines, index);
+			blocks.push(list.html);
+			index = list.end;
+			continue;
+		}
+
+		if (isTableStart(lines, index)) {
+			const delimiter = splitTableRow(lines[index + 1]);
+			const rows = [splitTableRow(line)];
+			index += 2;
+			while (index < lines.length && lines[index].includes("|")) {
+				rows.push(splitTableRow(lines[index]));
+				index++;
+			}
+			blocks.push(renderTable(rows, delimiter));
+			continue;
+		}
+
+		if (/^(?: {4}|\t)/.test(line)) {
+			const code: string[] = [];
+			while (
+				index < lines.length &&
+				(/^(?: {4}|\t)/.test(lines[index]) || lines[index].trim() === "")
+			) {
+				code.push(lines[index].replace(/^(?: {4}|\t)/, ""));
+				index++;
+			}
+			while (code.length > 0 && code[code.length - 1].trim() === "") {
+				code.pop();
+			}
+			blocks.push(`<pre><code>${escapeHtml(code.join("\n"))}</code></pre>`);
+			continue;
+		}
+
+		const paragraph = [line.trimStart()];
+		index++;
+		let setextLevel = 0;
+		while (index < lines.length && lines[index].trim() !== "") {
+			const setext = SETEXT_PATTERN.exec(lines[index]);
+			if (setext) {
+				setextLevel = setext[1][0] === "=" ? 1 : 2;
+				index++;
+				break;
+			}
+			if (interruptsParagraph(lines[index])) break;
+			paragraph.push(lines[index].trimStart());
+			index++;
+		}
+		const content = renderInline(paragraph.join("\n").trimEnd());
+		if (setextLevel > 0) {
+			blocks.push(`<h${setextLevel}>${content}</h${setextLevel}>`);
+		} else {
+			blocks.push(tight ? content : `<p>${content}</p>`);
+		}
+	}
+
+	return blocks.join("\n");
+};
+
+/**
+ * Converts Markdown to HTML: headings, paragraphs, emphasis, code, links,
+ * images, block quotes, nested lists, tables and rules, as CommonMark and
+ * GitHub tables write them. Raw HTML in the Markdown is escaped, and links
+ * to schemes other than http(s), mailto and tel are dropped.
+ * Pure function.
+ */
+export const markdownToHtml = (markdown: string): string =>
+	renderBlocks(
+		markdown
+			.replace(/\u0000/g, "")
+			.replace(/^\t+/gm, (tabs) => "    ".repeat(tabs.length))
+			.split(/\r?\n/),
+	);
+
+// ============================================================================
+// HTML to Markdown
+// ============================================================================
+
+type HtmlNode =
+	| { kind: "text"; text: string }
+	| {
+			kind: "element";
+			tag: string;
+			attributes: Map<string, string>;
+			children: HtmlNode[];
+		};
+
+type HtmlElement = Extract<HtmlNode, { kind: "element" }>;
+
+/**
+ * Elements closed implicitly when a sibling of the listed kinds opens.
+ */
+const IMPLIED_END: Record<string, string[]> = {
+	p: [
+		"blockquote",
+		"div",
+		"h1",
+		"h2",
+		"h3",
+		"h4",
+		"h5",
+		"h6",
+		"hr",
+		"ol",
+		"p",
+		"pre",
+		"table",
+		"ul",
+	],
+	li: ["li"],
+	td: ["td", "th", "tr"],
+	th: ["td", "th", "tr"],
+	tr: ["tr"],
+};
+
+/**
+ * Parses HTML into a tree, tolerating the unclosed and stray tags of
+ * clipboard fragments. Comments, doctypes and processing instructions
+ * are dropped.
+ */
+const parseHtml = (html: string): HtmlNode[] => {
+	const root: HtmlElement = {
+		kind: "element",
+		tag: "#root",
+		attributes: new Map(),
+		children: [],
+	};
+	const stack: HtmlElement[] = [root];
+	const pattern =
+		/<!--[\s\S]*?(?:-->|$)|<[!?][^>]*>|<(\/?)([a-zA-Z][\w:-]*)((?:"[^"]*"|'[^']*'|[^'">])*)>|[^<]+|</g;
+
+	for (const match of html.matchAll(pattern)) {
+		const [token, closing, name, rest] = match;
+		const parent = stack[stack.length - 1];
+		if (!name) {
+			if (!token.startsWith("<!") && !token.startsWith("<?")) {
+				parent.children.push({ kind: "text", text: decodeEntities(token) });
+			}
+			continue;
+		}
+		const tag = name.toLowerCase();
+		if (closing) {
+			const open = stack.map((element) => element.tag).lastIndexOf(tag);
+			if (open > 0) stack.length = open;
+			continue;
+		}
+		if (IMPLIED_END[parent.tag]?.includes(tag)) stack.pop();
+		const element: HtmlElement = {
+			kind: "element",
+			tag,
+			attributes: parseTagAttributes(rest),
+			children: [],
+		};
+		stack[stack.length - 1].children.push(element);
+		if (!VOID_ELEMENTS.has(tag) && !rest.trimEnd().endsWith("/")) {
+			stack.push(element);
+		}
+	}
+	return root.children;
+};
+
+const isBlock = (node: HtmlNode | undefined): boolean =>
+	node !== undefined && node.kind === "element" && BLOCK_ELEMENTS.has(node.tag);
+
+const textOf = (node: HtmlNode): string =>
+	node.kind === "text" ? node.text : node.children.map(textOf).join("");
+
+/**
+ * Escapes text that Markdown would otherwise read as formatting.
+ */
+const escapeMarkdown = (text: string): string =>
+	text
+		.replace(/([\\`*[\]])/g, "\\$1")
+		.replace(/(^|\W)_|_(?=\W|$)/g, (match) => match.replace("_", "\\_"))
+		.replace(/^(\s*)([#>+-]|\d+\.)(?=\s)/, "$1\\$2");
+
+/**
+ * Wraps inline content in a Markdown delimiter, keeping its surrounding
+ * whitespace outside so `<b> bold </b>` becomes ` **bold** `.
+ */
+const wrapInline = (content: string, delimiter: string): string => {
+	const match = /^(\s*)([\s\S]*?)(\s*)$/.exec(content);
+	if (!match || match[2] === "") return content;
+	return `${match[1]}${delimiter}${match[2]}${delimiter}${match[3]}`;
+};
+
+const codeSpan = (code: string): string => {
+	const runs = code.match(/`+/g) ?? [];
+	const longest = Math.max(0, ...runs.map((run) => run.length));
+	const ticks = "`".repeat(longest + 1);
+	const padding = longest > 0 ? " " : "";
+	return `${ticks}${padding}${code}${padding}${ticks}`;
+};
+
+/**
+ * Style of a Google Docs span, which marks bold and italic inline rather
+ * than with `<b>` and `<i>`.
+ */
+const inlineStyleOf = (element: HtmlElement) => {
+	const style = (element.attributes.get("style") ?? "").toLowerCase();
+	return {
+		bold: /font-weight:\s*(?:bold|[6-9]00)/.test(style),
+		normal: /font-weight:\s*(?:normal|[1-4]00)/.test(style),
+		italic: /font-style:\s*italic/.test(style),
+	};
+};
+
+const renderChildren = (nodes: HtmlNode[], inPre: boolean): string =>
+	nodes
+		.map((node, index) => {
+			if (node.kind === "element") return renderNode(node, inPre);
+			if (inPre) return node.text;
+			const previous = nodes[index - 1];
+			// Whitespace between blocks is layout, not text
+			if (
+				node.text.trim() === "" &&
+				(isBlock(previous) || isBlock(nodes[index + 1]))
+			) {
+				return "";
+			}
+			const text = node.text.replace(/\s+/g, " ");
+			// A line after a break starts at the margin
+			const afterBreak = previous?.kind === "element" && previous.tag === "br";
+			return escapeMarkdown(afterBreak ? text.trimStart() : text);
+		})
+		.join("");
+
+const block = (content: string): string =>
+	content.trim() === "" ? "" : `\n\n${content.trim()}\n\n`;
+
+const prefixLines = (text: string, first: string, rest: string): string =>
+	text
+		.split("\n")
+		.map((line, index) => (index === 0 ? first : line ? rest : "") + line)
+		.join("\n");
+
+const renderListElement = (element: HtmlElement): string => {
+	const ordered = element.tag === "ol";
+	let number = Number.parseInt(element.attributes.get("start") ?? "1", 10);
+	if (Number.isNaN(number)) number = 1;
+	const items = element.children
+		.filter((child): child is HtmlElement => child.kind === "element")
+		.filter((child) => child.tag === "li")
+		.map((item) => {
+			const marker = ordered ? `${number++}. ` : "- ";
+			const content = renderChildren(item.children, false)
+				.trim()
+				.replace(/\n{2,}/g, "\n");
+			return prefixLines(content, marker, " ".repeat(marker.length));
+		});
+	return block(items.join("\n"));
+};
+
+const renderTableElement = (element: HtmlElement): string => {
+	const rows: HtmlElement[] = [];
+	const collectRows = (node: HtmlNode) => {
+		if (node.kind !== "element") return;
+		if (node.tag === "tr") rows.push(node);
+		else if (node.tag !== "table") node.children.forEach(collectRows);
+	};
+	element.children.forEach(collectRows);
+	const cells = rows.map((row) =>
+		row.children
+			.filter((child): child is HtmlElement => child.kind === "element")
+			.filter((child) => child.tag === "td" || child.tag === "th")
+			.map((cell) =>
+				renderChildren(cell.children, false)
+					.replace(/\s+/g, " ")
+					.trim()
+					.replace(/\|/g, "\\|"),
+			),
+	);
+	if (cells.length === 0) return "";
+	const width = Math.max(...cells.map((row) => row.length));
+	const line = (row: string[]) =>
+		`| ${Array.from({ length: width }, (_, i) => row[i] ?? "").join(" | ")} |`;
+	const [header, ...body] = cells;
+	return block(
+		[line(header), line(Array(width).fill("---")), ...body.map(line)].join(
+			"\n",
+		),
+	);
+};
+
+const renderNode = (element: HtmlElement, inPre: boolean): string => {
+	const { tag } = element;
+	if (SKIPPED_ELEMENTS.has(tag)) return "";
+	const inner = () => renderChildren(element.children, inPre);
+
+	switch (tag) {
+		case "h1":
+		case "h2":
+		case "h3":
+		case "h4":
+		case "h5":
+		case "h6": {
+			const text = inner().replace(/\s+/g, " ").trim();
+			return text ? block(`${"#".repeat(Number(tag[1]))} ${text}`) : "";
+		}
+		case "br":
+			return inPre ? "\n" : "  \n";
+		case "hr":
+			return block("---");
+		case "pre": {
+			const code = element.children.find(
+				(child): child is HtmlElement =>
+					child.kind === "element" && child.tag === "code",
+			);
+			const language =
+				/language-([\w+#.-]+)/.exec(code?.attributes.get("class") ?? "")?.[1] ??
+				"";
+			const text = textOf(element).replace(/^\n/, "").replace(/\n+$/, "");
+			const fence = text.includes("```") ? "~~~" : "```";
+			return block(`${fence}${language}\n${text}\n${fence}`);
+		}
+		case "blockquote": {
+			const quoted = inner().trim().replace(/\n{3,}/g, "\n\n");
+			return block(prefixLines(quoted, "> ", "> ").replace(/^$/gm, ">"));
+		}
+		case "ul":
+		case "ol":
+			return renderListElement(element);
+		case "table":
+			return renderTableElement(element);
+		case "strong":
+		case "b":
+			if (inlineStyleOf(element).normal) return inner();
+			return wrapInline(inner(), "**");
+		case "em":
+		case "i":
+			return wrapInline(inner(), "*");
+		case "del":
+		case "s":
+		case "strike":
+			return wrapInline(inner(), "~~");
+		case "code":
+			return inPre ? inner() : codeSpan(textOf(element));
+		case "a": {
+			const text = inner();
+			const href = decodeEntities(element.attributes.get("href") ?? "").trim();
+			if (!href || href.startsWith("#") || !isSafeUrl(href)) return text;
+			if (text.trim() === "") return "";
+			if (textOf(element).trim() === href) return `<${href}>`;
+			return `[${text.trim()}](${href.replace(/[ ()]/g, encodeURIComponent)})`;
+		}
+		case "img": {
+			const src = decodeEntities(element.attributes.get("src") ?? "").trim();
+			if (!src || !isSafeUrl(src)) return "";
+			const alt = escapeMarkdown(
+				decodeEntities(element.attributes.get("alt") ?? ""),
+			);
+			return `![${alt}](${src.replace(/[ ()]/g, encodeURIComponent)})`;
+		}
+		case "span": {
+			const style = inlineStyleOf(element);
+			let text = inner();
+			if (style.italic) text = wrapInline(text, "*");
+			if (style.bold) text = wrapInline(text, "**");
+			return text;
+		}
+		default: {
+			const text = inner();
+			return BLOCK_ELEMENTS.has(tag) ? block(text) : text;
+		}
+	}
+};
+
+/**
+ * Converts HTML, such as the rich text of a copy from a browser or
+ * document editor, to Markdown. Structure and inline formatting are kept;
+ * styles, scripts and unsupported elements are dropped, leaving their
+ * text.
+ * Pure function.
+ */
+export const htmlToMarkdown = (html: string): string =>
+	renderChildren(parseHtml(html), false)
+		.replace(/^[ \t]+$/gm, "")
+		.replace(/(\S) \n/g, "$1\n")
+		.replace(/\n{3,}/g, "\n\n")
+		.trim();
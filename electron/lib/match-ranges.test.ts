@@ -0,0 +1,71 @@
+import { describe, expect, it } from "vitest";
+import {
+	findSubstringRanges,
+	HIGHLIGHT_CLOSE,
+	HIGHLIGHT_OPEN,
+	MAX_MATCH_RANGES,
+	type MatchRange,
+	mergeRanges,
+	parseHighlightRanges,
+} from "./match-ranges.js";
+
+describe("mergeRanges", () => {
+	it("sorts and joins overlapping and touching ranges", () => {
+		expect(
+			mergeRanges([
+				[8, 10],
+				[0, 1],
+				[1, 2],
+				[9, 12],
+				[4, 5],
+			]),
+		).toEqual([
+			[0, 2],
+			[4, 5],
+			[8, 12],
+		]);
+	});
+
+	it("keeps at most the first 100", () => {
+		const ranges = Array.from(
+			{ length: 150 },
+			(_, i): MatchRange => [i * 2, i * 2 + 1],
+		);
+		const merged = mergeRanges(ranges);
+		expect(merged).toHaveLength(MAX_MATCH_RANGES);
+		expect(merged[0]).toEqual([0, 1]);
+	});
+});
+
+describe("parseHighlightRanges", () => {
+	it("gives offsets in the unmarked text", () => {
+		const mark = (text: string) => `${HIGHLIGHT_OPEN}${text}${HIGHLIGHT_CLOSE}`;
+		const marked = `say ${mark("hello")} to the ${mark("world")}`;
+
+		const ranges = parseHighlightRanges(marked);
+		expect(ranges).toEqual([
+			[4, 9],
+			[17, 22],
+		]);
+		const text = "say hello to the world";
+		expect(ranges.map(([start, end]) => text.slice(start, end))).toEqual([
+			"hello",
+			"world",
+		]);
+		expect(parseHighlightRanges("no match")).toEqual([]);
+	});
+});
+
+describe("findSubstringRanges", () => {
+	it("finds every occurrence, ignoring case", () => {
+		expect(findSubstringRanges("50% off, now 50%!", "50%")).toEqual([
+			[0, 3],
+			[13, 16],
+		]);
+		expect(findSubstringRanges("Token TOKEN", "token")).toEqual([
+			[0, 5],
+			[6, 11],
+		]);
+		expect(findSubstringRanges("anything", "")).toEqual([]);
+	});
+});
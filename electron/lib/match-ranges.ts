@@ -0,0 +1,95 @@
+/**
+ * Where a search matched an item's text, so the list can highlight it
+ * without matching again.
+ */
+
+/**
+ * A matched part of a text: start and end offsets in UTF-16 code units
+ * (JavaScript string indexes), end exclusive.
+ */
+export type MatchRange = [start: number, end: number];
+
+/**
+ * Most ranges kept per item; a text matching all over is cut short.
+ */
+export const MAX_MATCH_RANGES = 100;
+
+/**
+ * Characters the FTS5 `highlight()` function wraps matches in: private
+ * use code points, which copied text almost never holds.
+ */
+export const HIGHLIGHT_OPEN = "\uE000";
+export const HIGHLIGHT_CLOSE = "\uE001";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Sorts ranges and joins those that overlap or touch.
+ * Pure function.
+ *
+ * @returns at most `MAX_MATCH_RANGES` ranges, first ones first
+ */
+export const mergeRanges = (ranges: readonly MatchRange[]): MatchRange[] => {
+	const sorted = [...ranges].sort((a, b) => a[0] - b[0] || a[1] - b[1]);
+	const merged: MatchRange[] = [];
+	for (const [start, end] of sorted) {
+		const last = merged[merged.length - 1];
+		if (last && start <= last[1]) {
+			last[1] = Math.max(last[1], end);
+		} else {
+			if (merged.length === MAX_MATCH_RANGES) break;
+			merged.push([start, end]);
+		}
+	}
+	return merged;
+};
+
+/**
+ * Reads the ranges out of text marked by `highlight()`. The offsets are
+ * in the text without the markers.
+ * Pure function.
+ */
+export const parseHighlightRanges = (marked: string): MatchRange[] => {
+	const ranges: MatchRange[] = [];
+	let offset = 0;
+	let start: number | null = null;
+	for (let i = 0; i < marked.length; i++) {
+		const char = marked[i];
+		if (char === HIGHLIGHT_OPEN) {
+			start = offset;
+		} else if (char === HIGHLIGHT_CLOSE) {
+			if (start !== null && offset > start) ranges.push([start, offset]);
+			start = null;
+		} else {
+			offset += 1;
+		}
+	}
+	return mergeRanges(ranges);
+};
+
+/**
+ * Finds the occurrences of `query` in `text`, ignoring case, as the
+ * substring search matches them.
+ * Pure function.
+ */
+export const findSubstringRanges = (
+	text: string,
+	query: string,
+): MatchRange[] => {
+	if (query.length === 0) return [];
+	const lower = text.toLowerCase();
+	// Offsets only carry over if lowercasing kept every character's length
+	const [haystack, needle] =
+		lower.length === text.length
+			? [lower, query.toLowerCase()]
+			: [text, query];
+	const ranges: MatchRange[] = [];
+	let index = haystack.indexOf(needle);
+	while (index !== -1 && ranges.length < MAX_MATCH_RANGES) {
+		ranges.push([index, index + needle.length]);
+		index = haystack.indexOf(needle, index + needle.length);
+	}
+	return ranges;
+};
@@ -0,0 +1,109 @@
+import { describe, expect, it } from "vitest";
+import {
+	buildServiceRecords,
+	DnsRecordType,
+	decodeDnsMessage,
+	encodeDnsMessage,
+	findServiceInstances,
+} from "./mdns.js";
+
+const SERVICE = "_clipman-sync._tcp.local";
+
+const advertisement = {
+	instance: "0123456789abcdef",
+	port: 47814,
+	txt: { id: "0123456789abcdef", name: "Laptop" },
+};
+
+describe("encodeDnsMessage / decodeDnsMessage", () => {
+	it("round-trips queries and service records", () => {
+		const query = {
+			id: 0,
+			response: false,
+			questions: [{ name: SERVICE, type: DnsRecordType.PTR }],
+			answers: [],
+		};
+		expect(decodeDnsMessage(encodeDnsMessage(query))).toEqual(query);
+
+		const response = {
+			id: 0,
+			response: true,
+			questions: [],
+			answers: buildServiceRecords(SERVICE, "laptop.local", advertisement, 120),
+		};
+		expect(decodeDnsMessage(encodeDnsMessage(response))).toEqual(response);
+	});
+
+	it("follows compressed names", () => {
+		const header = Buffer.from([0, 0, 0x84, 0, 0, 1, 0, 1, 0, 0, 0, 0]);
+		// A question for "_svc._tcp.local" at offset 12, then a PTR record
+		// named by a pointer to it, whose data is "inst" plus the pointer
+		const name = Buffer.from("\x04_svc\x04_tcp\x05local\x00", "binary");
+		const question = Buffer.from([0, 12, 0, 1]);
+		const record = Buffer.from([0xc0, 12, 0, 12, 0, 1, 0, 0, 0, 60, 0, 7]);
+		const data = Buffer.from([4, ...Buffer.from("inst"), 0xc0, 12]);
+
+		const message = decodeDnsMessage(
+			Buffer.concat([header, name, question, record, data]),
+		);
+		expect(message.questions).toEqual([
+			{ name: "_svc._tcp.local", type: DnsRecordType.PTR },
+		]);
+		expect(message.answers).toEqual([
+			{
+				name: "_svc._tcp.local",
+				type: DnsRecordType.PTR,
+				ttl: 60,
+				data: "inst._svc._tcp.local",
+			},
+		]);
+	});
+
+	it("rejects truncated and looping messages", () => {
+		expect(() => decodeDnsMessage(Buffer.alloc(4))).toThrow("Truncated");
+
+		const header = Buffer.from([0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+		const loop = Buffer.from([0xc0, 12, 0, 12, 0, 1]);
+		expect(() => decodeDnsMessage(Buffer.concat([header, loop]))).toThrow(
+			"Invalid DNS name pointer",
+		);
+	});
+});
+
+describe("findServiceInstances", () => {
+	const respond = (ttl: number) => ({
+		id: 0,
+		response: true,
+		questions: [],
+		answers: buildServiceRecords(SERVICE, "laptop.local", advertisement, ttl),
+	});
+
+	it("reads instances with their port, TXT entries and address", () => {
+		const peers = findServiceInstances(respond(120), SERVICE, "192.168.1.20");
+		expect(peers).toEqual([
+			{
+				instance: "0123456789abcdef",
+				txt: { id: "0123456789abcdef", name: "Laptop" },
+				address: "192.168.1.20",
+				port: 47814,
+				ttl: 120,
+			},
+		]);
+	});
+
+	it("reports goodbyes and ignores other services", () => {
+		const peers = findServiceInstances(respond(0), SERVICE, "192.168.1.20");
+		expect(peers).toEqual([
+			{
+				instance: "0123456789abcdef",
+				txt: {},
+				address: "192.168.1.20",
+				port: 0,
+				ttl: 0,
+			},
+		]);
+		expect(
+			findServiceInstances(respond(120), "_other._tcp.local", "192.168.1.20"),
+		).toEqual([]);
+	});
+});
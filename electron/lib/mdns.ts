@@ -0,0 +1,504 @@
+import dgram from "node:dgram";
+
+/**
+ * Minimal multicast DNS (RFC 6762) service discovery: enough to advertise
+ * one service instance and find others of the same type on the local
+ * network. Only PTR, SRV and TXT records are understood; peers are
+ * addressed by the source address of their responses, so A/AAAA records
+ * are not needed.
+ */
+
+export const DnsRecordType = {
+	A: 1,
+	PTR: 12,
+	TXT: 16,
+	SRV: 33,
+} as const;
+
+export type DnsQuestion = { name: string; type: number };
+
+export type DnsRecord =
+	| { name: string; type: typeof DnsRecordType.PTR; ttl: number; data: string }
+	| {
+			name: string;
+			type: typeof DnsRecordType.SRV;
+			ttl: number;
+			data: { port: number; target: string };
+	  }
+	| {
+			name: string;
+			type: typeof DnsRecordType.TXT;
+			ttl: number;
+			/** `key=value` strings */
+			data: string[];
+	  }
+	/** Any other record type, kept so messages can be skipped over */
+	| { name: string; type: number; ttl: number; data: null };
+
+export type DnsMessage = {
+	id: number;
+	/** Set on responses, clear on queries */
+	response: boolean;
+	questions: DnsQuestion[];
+	/** Answer and additional records together */
+	answers: DnsRecord[];
+};
+
+/**
+ * A service instance seen on the network.
+ */
+export type MdnsPeer = {
+	instance: string;
+	txt: Record<string, string>;
+	address: string;
+	port: number;
+	/** Seconds the announcement stays valid; 0 when the peer said goodbye */
+	ttl: number;
+};
+
+/**
+ * What this device announces for its service type.
+ */
+export type MdnsAdvertisement = {
+	/** Instance label, unique on the network (at most 63 bytes) */
+	instance: string;
+	port: number;
+	txt: Record<string, string>;
+};
+
+export type MdnsDiscoveryDeps = {
+	/** Service type, e.g. `_clipman-sync._tcp.local` */
+	serviceType: string;
+	/** Host name given as the SRV target */
+	hostname: string;
+	/** Current advertisement, or null to stay silent */
+	getAdvertisement: () => MdnsAdvertisement | null;
+	onPeer: (peer: MdnsPeer) => void;
+};
+
+const MDNS_ADDRESS = "224.0.0.251";
+const MDNS_PORT = 5353;
+
+/**
+ * TTL of our records; peers that stop announcing drop out after this.
+ */
+const RECORD_TTL_SECONDS = 120;
+
+/**
+ * How often to query for peers, well inside the record TTL.
+ */
+const QUERY_INTERVAL_MS = 30_000;
+
+const CLASS_IN = 1;
+/** Top bit of a record class: replace cached records for this name */
+const CACHE_FLUSH = 0x8000;
+const FLAG_RESPONSE = 0x8000;
+/** Authoritative answer, as required for mDNS responses */
+const FLAG_AUTHORITATIVE = 0x0400;
+
+const MAX_LABEL_BYTES = 63;
+const MAX_POINTER_JUMPS = 32;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const encodeName = (name: string): Buffer => {
+	const parts: Buffer[] = [];
+	for (const label of name.split(".").filter(Boolean)) {
+		const bytes = Buffer.from(label, "utf-8");
+		if (bytes.length > MAX_LABEL_BYTES) {
+			throw new Error(`DNS label too long: ${label}`);
+		}
+		parts.push(Buffer.from([bytes.length]), bytes);
+	}
+	parts.push(Buffer.from([0]));
+	return Buffer.concat(parts);
+};
+
+const encodeRecordData = (record: DnsRecord): Buffer => {
+	switch (record.type) {
+		case DnsRecordType.PTR:
+			return encodeName(record.data as string);
+		case DnsRecordType.SRV: {
+			const { port, target } = record.data as { port: number; target: string };
+			const header = Buffer.alloc(6);
+			header.writeUInt16BE(port, 4);
+			return Buffer.concat([header, encodeName(target)]);
+		}
+		case DnsRecordType.TXT: {
+			const strings = (record.data as string[]).map((entry) => {
+				const bytes = Buffer.from(entry, "utf-8").subarray(0, 255);
+				return Buffer.concat([Buffer.from([bytes.length]), bytes]);
+			});
+			// An empty TXT record still holds one empty string
+			return strings.length > 0 ? Buffer.concat(strings) : Buffer.from([0]);
+		}
+		default:
+			return Buffer.alloc(0);
+	}
+};
+
+/**
+ * Serializes a message without name compression.
+ * Pure function.
+ */
+export const encodeDnsMessage = (message: DnsMessage): Buffer => {
+	const header = Buffer.alloc(12);
+	header.writeUInt16BE(message.id, 0);
+	header.writeUInt16BE(
+		message.response ? FLAG_RESPONSE | FLAG_AUTHORITATIVE : 0,
+		2,
+	);
+	header.writeUInt16BE(message.questions.length, 4);
+	header.writeUInt16BE(message.answers.length, 6);
+
+	const parts = [header];
+	for (const question of message.questions) {
+		const fixed = Buffer.alloc(4);
+		fixed.writeUInt16BE(question.type, 0);
+		fixed.writeUInt16BE(CLASS_IN, 2);
+		parts.push(encodeName(question.name), fixed);
+	}
+	for (const record of message.answers) {
+		const data = encodeRecordData(record);
+		const fixed = Buffer.alloc(10);
+		fixed.writeUInt16BE(record.type, 0);
+		// Shared PTR records must not flush other responders' entries
+		fixed.writeUInt16BE(
+			record.type === DnsRecordType.PTR ? CLASS_IN : CLASS_IN | CACHE_FLUSH,
+			2,
+		);
+		fixed.writeUInt32BE(record.ttl, 4);
+		fixed.writeUInt16BE(data.length, 8);
+		parts.push(encodeName(record.name), fixed, data);
+	}
+	return Buffer.concat(parts);
+};
+
+/**
+ * Reads a possibly compressed name at `offset`.
+ * @returns the name and the offset just past it in the original position
+ */
+const decodeName = (
+	buffer: Buffer,
+	offset: number,
+): { name: string; end: number } => {
+	const labels: string[] = [];
+	let position = offset;
+	let end: number | null = null;
+
+	for (let jumps = 0; ; ) {
+		if (position >= buffer.length) {
+			throw new Error("Truncated DNS name");
+		}
+		const length = buffer[position];
+		if (length === 0) break;
+		if ((length & 0xc0) === 0xc0) {
+			if (position + 1 >= buffer.length || ++jumps > MAX_POINTER_JUMPS) {
+				throw new Error("Invalid DNS name pointer");
+			}
+			end ??= position + 2;
+			position = ((length & 0x3f) << 8) | buffer[position + 1];
+			continue;
+		}
+		if (position + 1 + length > buffer.length) {
+			throw new Error("Truncated DNS name");
+		}
+		labels.push(buffer.toString("utf-8", position + 1, position + 1 + length));
+		position += 1 + length;
+	}
+	return { name: labels.join("."), end: end ?? position + 1 };
+};
+
+const decodeRecordData = (
+	buffer: Buffer,
+	type: number,
+	start: number,
+	length: number,
+): DnsRecord["data"] => {
+	switch (type) {
+		case DnsRecordType.PTR:
+			return decodeName(buffer, start).name;
+		case DnsRecordType.SRV:
+			if (length < 7) throw new Error("Truncated SRV record");
+			return {
+				port: buffer.readUInt16BE(start + 4),
+				target: decodeName(buffer, start + 6).name,
+			};
+		case DnsRecordType.TXT: {
+			const strings: string[] = [];
+			let position = start;
+			while (position < start + length) {
+				const size = buffer[position];
+				if (size > 0) {
+					strings.push(
+						buffer.toString("utf-8", position + 1, position + 1 + size),
+					);
+				}
+				position += 1 + size;
+			}
+			return strings;
+		}
+		default:
+			return null;
+	}
+};
+
+/**
+ * Parses a message received on the mDNS port.
+ * Pure function.
+ *
+ * @throws if the message is truncated or malformed
+ */
+export const decodeDnsMessage = (buffer: Buffer): DnsMessage => {
+	if (buffer.length < 12) {
+		throw new Error("Truncated DNS message");
+	}
+	const questionCount = buffer.readUInt16BE(4);
+	const recordCount =
+		buffer.readUInt16BE(6) + buffer.readUInt16BE(8) + buffer.readUInt16BE(10);
+	let offset = 12;
+
+	const questions: DnsQuestion[] = [];
+	for (let i = 0; i < questionCount; i++) {
+		const { name, end } = decodeName(buffer, offset);
+		if (end + 4 > buffer.length) throw new Error("Truncated DNS question");
+		questions.push({ name, type: buffer.readUInt16BE(end) });
+		offset = end + 4;
+	}
+
+	const answers: DnsRecord[] = [];
+	for (let i = 0; i < recordCount; i++) {
+		const { name, end } = decodeName(buffer, offset);
+		if (end + 10 > buffer.length) throw new Error("Truncated DNS record");
+		const type = buffer.readUInt16BE(end);
+		const ttl = buffer.readUInt32BE(end + 4);
+		const length = buffer.readUInt16BE(end + 8);
+		const start = end + 10;
+		if (start + length > buffer.length) {
+			throw new Error("Truncated DNS record");
+		}
+		const data = decodeRecordData(buffer, type, start, length);
+		answers.push({ name, type, ttl, data } as DnsRecord);
+		offset = start + length;
+	}
+
+	return {
+		id: buffer.readUInt16BE(0),
+		response: (buffer.readUInt16BE(2) & FLAG_RESPONSE) !== 0,
+		questions,
+		answers,
+	};
+};
+
+/**
+ * Builds the PTR, SRV and TXT records announcing a service instance.
+ * Pure function. A TTL of 0 announces that the instance is going away.
+ */
+export const buildServiceRecords = (
+	serviceType: string,
+	hostname: string,
+	advertisement: MdnsAdvertisement,
+	ttl: number,
+): DnsRecord[] => {
+	const name = `${advertisement.instance}.${serviceType}`;
+	return [
+		{ name: serviceType, type: DnsRecordType.PTR, ttl, data: name },
+		{
+			name,
+			type: DnsRecordType.SRV,
+			ttl,
+			data: { port: advertisement.port, target: hostname },
+		},
+		{
+			name,
+			type: DnsRecordType.TXT,
+			ttl,
+			data: Object.entries(advertisement.txt).map(
+				([key, value]) => `${key}=${value}`,
+			),
+		},
+	];
+};
+
+/**
+ * Finds the service instances announced in a response.
+ * Pure function. Instances without both an SRV and a TXT record are
+ * skipped, except goodbyes (TTL 0), which only need the PTR.
+ */
+export const findServiceInstances = (
+	message: DnsMessage,
+	serviceType: string,
+	address: string,
+): MdnsPeer[] => {
+	const peers: MdnsPeer[] = [];
+	const suffix = `.${serviceType}`.toLowerCase();
+
+	for (const record of message.answers) {
+		if (
+			record.type !== DnsRecordType.PTR ||
+			record.name.toLowerCase() !== serviceType.toLowerCase()
+		) {
+			continue;
+		}
+		const name = record.data as string;
+		if (!name.toLowerCase().endsWith(suffix)) continue;
+		const instance = name.slice(0, -suffix.length);
+		const lowerName = name.toLowerCase();
+		const srv = message.answers.find(
+			(other) =>
+				other.type === DnsRecordType.SRV &&
+				other.name.toLowerCase() === lowerName,
+		);
+		const txt = message.answers.find(
+			(other) =>
+				other.type === DnsRecordType.TXT &&
+				other.name.toLowerCase() === lowerName,
+		);
+
+		if (record.ttl === 0) {
+			peers.push({ instance, txt: {}, address, port: 0, ttl: 0 });
+			continue;
+		}
+		if (!srv || !txt) continue;
+
+		const entries: Record<string, string> = {};
+		for (const entry of txt.data as string[]) {
+			const [key, value = ""] = entry.split(/=(.*)/s);
+			if (key) entries[key.toLowerCase()] = value;
+		}
+		peers.push({
+			instance,
+			txt: entries,
+			address,
+			port: (srv.data as { port: number }).port,
+			ttl: Math.min(record.ttl, srv.ttl),
+		});
+	}
+	return peers;
+};
+
+// ============================================================================
+// mDNS Discovery
+// ============================================================================
+
+/**
+ * Creates an mDNS responder and browser for one service type. Answers
+ * queries for the type with the current advertisement and queries for
+ * other instances periodically.
+ */
+export const createMdnsDiscovery = (deps: MdnsDiscoveryDeps) => {
+	let socket: dgram.Socket | null = null;
+	let queryTimer: NodeJS.Timeout | null = null;
+
+	const send = (message: DnsMessage): void => {
+		socket?.send(encodeDnsMessage(message), MDNS_PORT, MDNS_ADDRESS);
+	};
+
+	/**
+	 * Multicasts the current advertisement (or a goodbye with `ttl` 0).
+	 */
+	const announce = (ttl = RECORD_TTL_SECONDS): void => {
+		const advertisement = deps.getAdvertisement();
+		if (!advertisement) return;
+		send({
+			id: 0,
+			response: true,
+			questions: [],
+			answers: buildServiceRecords(
+				deps.serviceType,
+				deps.hostname,
+				advertisement,
+				ttl,
+			),
+		});
+	};
+
+	/**
+	 * Asks every instance of the service type to announce itself.
+	 */
+	const query = (): void => {
+		send({
+			id: 0,
+			response: false,
+			questions: [{ name: deps.serviceType, type: DnsRecordType.PTR }],
+			answers: [],
+		});
+	};
+
+	const handleMessage = (buffer: Buffer, remote: dgram.RemoteInfo): void => {
+		let message: DnsMessage;
+		try {
+			message = decodeDnsMessage(buffer);
+		} catch {
+			// Other software shares the port; ignore what we cannot parse
+			return;
+		}
+
+		if (!message.response) {
+			const asked = message.questions.some(
+				(question) =>
+					question.name.toLowerCase() === deps.serviceType.toLowerCase() &&
+					question.type === DnsRecordType.PTR,
+			);
+			if (asked) announce();
+			return;
+		}
+
+		const own = deps.getAdvertisement()?.instance;
+		for (const peer of findServiceInstances(
+			message,
+			deps.serviceType,
+			remote.address,
+		)) {
+			if (peer.instance !== own) deps.onPeer(peer);
+		}
+	};
+
+	const stop = (): void => {
+		if (queryTimer) {
+			clearInterval(queryTimer);
+			queryTimer = null;
+		}
+		const current = socket;
+		if (!current) return;
+		announce(0);
+		socket = null;
+		// Let the goodbye go out before closing
+		setTimeout(() => current.close(), 100);
+	};
+
+	/**
+	 * Joins the mDNS group, announces this device and looks for peers.
+	 *
+	 * @throws if the mDNS port cannot be bound
+	 */
+	const start = async (): Promise<void> => {
+		if (socket) return;
+		const next = dgram.createSocket({ type: "udp4", reuseAddr: true });
+		await new Promise<void>((resolve, reject) => {
+			next.once("error", reject);
+			next.bind(MDNS_PORT, () => {
+				next.off("error", reject);
+				resolve();
+			});
+		});
+		next.addMembership(MDNS_ADDRESS);
+		next.setMulticastTTL(255);
+		next.setMulticastLoopback(true);
+		next.on("message", handleMessage);
+		next.on("error", (error) => {
+			console.error("mDNS socket error:", error);
+		});
+		socket = next;
+
+		announce();
+		query();
+		queryTimer = setInterval(query, QUERY_INTERVAL_MS);
+	};
+
+	return { start, stop, announce, query };
+};
+
+export type MdnsDiscovery = ReturnType<typeof createMdnsDiscovery>;
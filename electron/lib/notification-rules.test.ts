@@ -0,0 +1,83 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createNotificationRules,
+	describeNotification,
+	parseNotificationSettings,
+} from "./notification-rules.js";
+
+describe("parseNotificationSettings", () => {
+	it("keeps capture rule and secret notifications on by default", () => {
+		expect(parseNotificationSettings({})).toEqual({
+			captureRule: true,
+			secretCopied: true,
+			syncCompleted: false,
+		});
+	});
+
+	it("rejects toggles that are not booleans", () => {
+		expect(() => parseNotificationSettings({ syncCompleted: "yes" })).toThrow(
+			"syncCompleted must be a boolean",
+		);
+	});
+});
+
+describe("describeNotification", () => {
+	it("names the secret rule and counts synced items", () => {
+		expect(
+			describeNotification({
+				rule: "secretCopied",
+				secretName: "AWS access key",
+			}).title,
+		).toBe("A possible AWS access key was just copied");
+		expect(
+			describeNotification({ rule: "syncCompleted", received: 12 }).title,
+		).toBe("Sync completed with 12 new items");
+		expect(
+			describeNotification({ rule: "syncCompleted", received: 1 }).title,
+		).toBe("Sync completed with 1 new item");
+	});
+});
+
+describe("createNotificationRules", () => {
+	let userDataPath: string;
+
+	beforeEach(() => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "notifications-"));
+	});
+
+	afterEach(() => {
+		fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("only shows notifications for rules that are on", () => {
+		const show = vi.fn();
+		const rules = createNotificationRules({ userDataPath, show });
+
+		rules.notify({ rule: "syncCompleted", received: 3 });
+		expect(show).not.toHaveBeenCalled();
+
+		rules.updateSettings({ syncCompleted: true, secretCopied: false });
+		rules.notify({ rule: "syncCompleted", received: 3 });
+		rules.notify({ rule: "secretCopied", secretName: "GitHub token" });
+
+		expect(show).toHaveBeenCalledTimes(1);
+		expect(show).toHaveBeenCalledWith({
+			title: "Sync completed with 3 new items",
+			body: "Received from your other devices.",
+		});
+	});
+
+	it("persists settings", () => {
+		createNotificationRules({ userDataPath, show: vi.fn() }).updateSettings({
+			captureRule: false,
+		});
+
+		expect(
+			createNotificationRules({ userDataPath, show: vi.fn() }).getSettings()
+				.captureRule,
+		).toBe(false);
+	});
+});
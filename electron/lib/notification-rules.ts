@@ -0,0 +1,182 @@
+import fs from "node:fs";
+import path from "node:path";
+import { type Translate, translateEnglish } from "./i18n.js";
+
+/**
+ * Events the app can tell the user about with an OS notification.
+ */
+export const NOTIFICATION_RULES = [
+	"captureRule",
+	"secretCopied",
+	"syncCompleted",
+] as const;
+
+export type NotificationRule = (typeof NOTIFICATION_RULES)[number];
+
+/**
+ * Whether each rule shows a notification.
+ */
+export type NotificationSettings = Record<NotificationRule, boolean>;
+
+/**
+ * Something that happened, with what its notification says.
+ */
+export type NotificationEvent =
+	/** A copy matched capture rules with a `notify` action */
+	| { rule: "captureRule"; ruleNames: string[]; preview: string }
+	/** A copy was flagged by a secret scanning rule */
+	| { rule: "secretCopied"; secretName: string }
+	/** A remote sync finished and brought items from other devices */
+	| { rule: "syncCompleted"; received: number };
+
+export type NotificationContent = { title: string; body: string };
+
+/**
+ * Capture rule notifications stay on as before the toggles existed;
+ * secret warnings are on, sync reports are opt-in.
+ */
+const DEFAULT_NOTIFICATION_SETTINGS: NotificationSettings = {
+	captureRule: true,
+	secretCopied: true,
+	syncCompleted: false,
+};
+
+const NOTIFICATIONS_FILENAME = "notifications.json";
+
+const invalid = (message: string) =>
+	new Error(`Invalid notification settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a notification settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a toggle is not a boolean
+ */
+export const parseNotificationSettings = (
+	input: unknown,
+	current: NotificationSettings = DEFAULT_NOTIFICATION_SETTINGS,
+): NotificationSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const values = input as Partial<Record<NotificationRule, unknown>>;
+	const next: NotificationSettings = { ...current };
+
+	for (const rule of NOTIFICATION_RULES) {
+		const value = values[rule];
+		if (value === undefined) continue;
+		if (typeof value !== "boolean") {
+			throw invalid(`${rule} must be a boolean`);
+		}
+		next[rule] = value;
+	}
+
+	return next;
+};
+
+/**
+ * The title and body shown for an event.
+ * Pure function. Nothing copied is included for secrets, only the name
+ * of the rule that flagged them.
+ */
+export const describeNotification = (
+	event: NotificationEvent,
+	t: Translate = translateEnglish,
+): NotificationContent => {
+	switch (event.rule) {
+		case "captureRule":
+			return { title: event.ruleNames.join(", "), body: event.preview };
+		case "secretCopied":
+			return {
+				title: t("notification.secretCopied.title", { name: event.secretName }),
+				body: t("notification.secretCopied.body"),
+			};
+		case "syncCompleted":
+			return {
+				title: t("notification.syncCompleted.title", {
+					count: event.received,
+				}),
+				body: t("notification.syncCompleted.body"),
+			};
+	}
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getNotificationsPath = (userDataPath: string) =>
+	path.join(userDataPath, NOTIFICATIONS_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readNotificationsFromFile = (filePath: string): NotificationSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_NOTIFICATION_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseNotificationSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read notification settings:", error);
+		return { ...DEFAULT_NOTIFICATION_SETTINGS };
+	}
+};
+
+const writeNotificationsToFile = (
+	filePath: string,
+	settings: NotificationSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Notification Rules Module
+// ============================================================================
+
+export type NotificationRulesDeps = {
+	userDataPath: string;
+	/** Shows an OS notification */
+	show: (content: NotificationContent) => void;
+	/** Translates notification texts; English if omitted */
+	t?: Translate;
+};
+
+/**
+ * Creates the notification rules module, which decides which events the
+ * backend reports with an OS notification.
+ */
+export const createNotificationRules = (deps: NotificationRulesDeps) => {
+	const filePath = getNotificationsPath(deps.userDataPath);
+	let settings = readNotificationsFromFile(filePath);
+
+	/**
+	 * Shows the event's notification if its rule is on.
+	 */
+	const notify = (event: NotificationEvent): void => {
+		if (settings[event.rule]) {
+			deps.show(describeNotification(event, deps.t));
+		}
+	};
+
+	const getSettings = (): NotificationSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): NotificationSettings => {
+		const next = parseNotificationSettings(input, settings);
+		writeNotificationsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, notify };
+};
+
+export type NotificationRules = ReturnType<typeof createNotificationRules>;
@@ -0,0 +1,130 @@
+import fs from "node:fs";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createOcrEngine,
+	createRecognitionQueue,
+	type ImageCandidate,
+	isEngineMissing,
+	normalizeOcrText,
+} from "./ocr.js";
+
+const missing = () => Object.assign(new Error("spawn"), { code: "ENOENT" });
+
+describe("normalizeOcrText", () => {
+	it("collapses whitespace and drops blank lines", () => {
+		expect(normalizeOcrText("  Total:   42 \r\n\n\f\nInvoice\t#7\n")).toBe(
+			"Total: 42\nInvoice #7",
+		);
+	});
+
+	it("caps the stored length", () => {
+		expect(normalizeOcrText("a".repeat(30_000))).toHaveLength(20_000);
+	});
+});
+
+describe("isEngineMissing", () => {
+	it("detects a command that does not exist", () => {
+		expect(isEngineMissing(missing())).toBe(true);
+		expect(isEngineMissing(new Error("timed out"))).toBe(false);
+		expect(isEngineMissing(null)).toBe(false);
+	});
+});
+
+describe("createOcrEngine", () => {
+	it("runs tesseract on a temporary copy and removes it", async () => {
+		let seen = "";
+		const run = vi.fn(async (_command: string, args: string[]) => {
+			seen = args[0];
+			expect(fs.readFileSync(seen)).toEqual(Buffer.from("png"));
+			return " hello \n\n";
+		});
+		const recognize = createOcrEngine({ platform: "linux", run });
+
+		expect(await recognize(Buffer.from("png"))).toBe("hello");
+		expect(run).toHaveBeenCalledWith("tesseract", [seen, "stdout"]);
+		expect(fs.existsSync(seen)).toBe(false);
+	});
+
+	it("uses the Vision framework on macOS", async () => {
+		const run = vi.fn().mockResolvedValue("");
+		await createOcrEngine({ platform: "darwin", run })(Buffer.from("png"));
+
+		const [command, args] = run.mock.calls[0];
+		expect(command).toBe("osascript");
+		expect(args.slice(0, 3)).toEqual(["-l", "JavaScript", "-e"]);
+		expect(args[3]).toContain("VNRecognizeTextRequest");
+	});
+});
+
+describe("createRecognitionQueue", () => {
+	afterEach(() => {
+		vi.restoreAllMocks();
+	});
+
+	const createPending = (ids: number[]) => {
+		const pending = [...ids];
+		return {
+			nextCandidate: (): ImageCandidate | undefined => {
+				const id = pending[0];
+				return id === undefined ? undefined : { id, png: Buffer.from([id]) };
+			},
+			save: vi.fn((id: number) => {
+				pending.splice(pending.indexOf(id), 1);
+			}),
+		};
+	};
+
+	it("reads every pending image", async () => {
+		const pending = createPending([2, 1]);
+		const read = vi.fn(async (png: Buffer) => `text ${png[0]}`);
+		createRecognitionQueue({ label: "OCR", read, ...pending }).schedule();
+
+		await vi.waitFor(() => expect(pending.save).toHaveBeenCalledTimes(2));
+		expect(pending.save).toHaveBeenCalledWith(2, "text 2");
+		expect(pending.save).toHaveBeenCalledWith(1, "text 1");
+	});
+
+	it("stores an empty result for a failed image", async () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const pending = createPending([1]);
+		const read = vi.fn().mockRejectedValue(new Error("bad image"));
+		createRecognitionQueue({ label: "OCR", read, ...pending }).schedule();
+
+		await vi.waitFor(() => expect(pending.save).toHaveBeenCalledWith(1, ""));
+	});
+
+	it("stops without storing anything when no engine is installed", async () => {
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const pending = createPending([1]);
+		const read = vi.fn().mockRejectedValue(missing());
+		const queue = createRecognitionQueue({ label: "OCR", read, ...pending });
+
+		queue.schedule();
+		await vi.waitFor(() => expect(read).toHaveBeenCalledTimes(1));
+		await new Promise((resolve) => setTimeout(resolve, 0));
+		queue.schedule();
+		expect(read).toHaveBeenCalledTimes(1);
+		expect(pending.save).not.toHaveBeenCalled();
+	});
+
+	it("leaves pending images while deferred", async () => {
+		const pending = createPending([1]);
+		const read = vi.fn(async () => "text");
+		let deferred = true;
+		const queue = createRecognitionQueue({
+			label: "OCR",
+			read,
+			...pending,
+			shouldDefer: () => deferred,
+		});
+
+		queue.schedule();
+		expect(read).not.toHaveBeenCalled();
+
+		deferred = false;
+		queue.schedule();
+		await vi.waitFor(() =>
+			expect(pending.save).toHaveBeenCalledWith(1, "text"),
+		);
+	});
+});
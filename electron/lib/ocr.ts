@@ -0,0 +1,185 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import type { OutputCommandRunner } from "./foreground-app.js";
+
+/**
+ * Reads something from a PNG image, such as its text; resolves with an
+ * empty string when there is nothing to read.
+ */
+export type ImageReader = (png: Buffer) => Promise<string>;
+
+/**
+ * An image item waiting to be read.
+ */
+export type ImageCandidate = {
+	id: number;
+	png: Buffer;
+};
+
+/**
+ * Upper bound on recognizing one image (ms).
+ */
+const OCR_TIMEOUT_MS = 30_000;
+
+/**
+ * Longest recognized text stored per item (characters).
+ */
+const MAX_OCR_CHARS = 20_000;
+
+/**
+ * Recognizes text with the Vision framework through JavaScript for
+ * Automation; prints one line per text block. Available on macOS 10.15+.
+ */
+const MAC_VISION_SCRIPT = `ObjC.import("Vision");
+function run(argv) {
+	const url = $.NSURL.fileURLWithPath(argv[0]);
+	const handler = $.VNImageRequestHandler.alloc.initWithURLOptions(url, $({}));
+	const request = $.VNRecognizeTextRequest.alloc.init;
+	request.usesLanguageCorrection = true;
+	handler.performRequestsError($([request]), null);
+	const lines = [];
+	const results = request.results;
+	for (let i = 0; i < results.count; i++) {
+		const candidates = results.objectAtIndex(i).topCandidates(1);
+		if (candidates.count > 0) lines.push(candidates.objectAtIndex(0).string.js);
+	}
+	return lines.join("\\n");
+}`;
+
+const runCommand: OutputCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(
+			command,
+			args,
+			{ timeout: OCR_TIMEOUT_MS, encoding: "utf-8" },
+			(error, stdout) => (error ? reject(error) : resolve(stdout)),
+		);
+	});
+
+/**
+ * Tidies recognized text for storage: trims each line, drops blank ones
+ * and caps the length.
+ * Pure function.
+ */
+export const normalizeOcrText = (text: string): string =>
+	text
+		.split(/\r?\n/)
+		.map((line) => line.replace(/\s+/g, " ").trim())
+		.filter((line) => line.length > 0)
+		.join("\n")
+		.slice(0, MAX_OCR_CHARS);
+
+/**
+ * Whether a recognition failure means the engine is not installed, so
+ * later images would fail the same way.
+ * Pure function.
+ */
+export const isEngineMissing = (error: unknown): boolean =>
+	(error as NodeJS.ErrnoException | null)?.code === "ENOENT";
+
+/**
+ * Hands an image to an external command in a private temporary file,
+ * deleted as soon as `read` settles.
+ */
+export const withTempImage = async <T>(
+	png: Buffer,
+	read: (file: string) => Promise<T>,
+): Promise<T> => {
+	const dir = await fs.mkdtemp(path.join(os.tmpdir(), "clipboard-image-"));
+	try {
+		const file = path.join(dir, "image.png");
+		await fs.writeFile(file, png, { mode: 0o600 });
+		return await read(file);
+	} finally {
+		await fs.rm(dir, { recursive: true, force: true });
+	}
+};
+
+/**
+ * Creates the OCR engine for the current platform.
+ * - macOS: the built-in Vision framework
+ * - Windows and Linux: the `tesseract` command, if installed
+ *
+ * Resolves with the text tidied by `normalizeOcrText`.
+ */
+export const createOcrEngine = (deps: {
+	platform: NodeJS.Platform;
+	run?: OutputCommandRunner;
+}): ImageReader => {
+	const run = deps.run ?? runCommand;
+
+	return async (png) => {
+		const text = await withTempImage(png, (file) =>
+			deps.platform === "darwin"
+				? run("osascript", ["-l", "JavaScript", "-e", MAC_VISION_SCRIPT, file])
+				: run("tesseract", [file, "stdout"]),
+		);
+		return normalizeOcrText(text);
+	};
+};
+
+/**
+ * Creates a queue that reads image items one at a time in the background,
+ * newest first, until none are left. A failed image is stored as empty
+ * so it is not retried; a missing engine stops the queue for the session
+ * and leaves images for a later run.
+ */
+export const createRecognitionQueue = (deps: {
+	/** What is read, for log messages, e.g. `Text recognition` */
+	label: string;
+	read: ImageReader;
+	/** Next image item not read yet, if any */
+	nextCandidate: () => ImageCandidate | undefined;
+	save: (id: number, result: string) => void;
+	/** While true, pending images wait for a later `schedule` */
+	shouldDefer?: () => boolean;
+}) => {
+	let running = false;
+	let rerun = false;
+	let disabled = false;
+
+	const drain = async (): Promise<void> => {
+		while (!deps.shouldDefer?.()) {
+			const candidate = deps.nextCandidate();
+			if (!candidate) return;
+			let result = "";
+			try {
+				result = await deps.read(candidate.png);
+			} catch (error) {
+				if (isEngineMissing(error)) {
+					console.warn(`${deps.label} unavailable: no engine found`);
+					disabled = true;
+					return;
+				}
+				console.error(`${deps.label} failed for item ${candidate.id}:`, error);
+			}
+			deps.save(candidate.id, result);
+		}
+	};
+
+	/**
+	 * Starts working through pending images, or has the current run look
+	 * again once it ends. Does nothing while deferred.
+	 */
+	const schedule = (): void => {
+		if (disabled || deps.shouldDefer?.()) return;
+		if (running) {
+			rerun = true;
+			return;
+		}
+		running = true;
+		void drain()
+			.catch((error) => console.error(`${deps.label} stopped:`, error))
+			.finally(() => {
+				running = false;
+				if (rerun) {
+					rerun = false;
+					schedule();
+				}
+			});
+	};
+
+	return { schedule };
+};
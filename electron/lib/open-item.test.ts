@@ -0,0 +1,63 @@
+import { describe, expect, it } from "vitest";
+import type { ContentKind } from "./content-kind.js";
+import { formatStoredFileList } from "./file-lists.js";
+import { resolveOpenUrl, resolveRevealPath } from "./open-item.js";
+
+const text = (content: string, content_kind: ContentKind) => ({
+	type: "text" as const,
+	content,
+	content_kind,
+});
+
+describe("resolveOpenUrl", () => {
+	it("opens links and addresses with an allowed scheme", () => {
+		expect(resolveOpenUrl(text(" https://example.com/a ", "url"))).toBe(
+			"https://example.com/a",
+		);
+		expect(resolveOpenUrl(text("www.example.com", "url"))).toBe(
+			"https://www.example.com/",
+		);
+		expect(resolveOpenUrl(text("me@example.com", "email"))).toBe(
+			"mailto:me@example.com",
+		);
+	});
+
+	it("refuses other items and schemes", () => {
+		expect(() => resolveOpenUrl(text("hello", "text"))).toThrow(
+			"Only link and email items can be opened",
+		);
+		expect(() => resolveOpenUrl(text("file:///etc/passwd", "url"))).toThrow(
+			"Links with the file scheme cannot be opened",
+		);
+		expect(() => resolveOpenUrl(text("javascript:alert(1)", "url"))).toThrow(
+			"Links with the javascript scheme cannot be opened",
+		);
+	});
+});
+
+describe("resolveRevealPath", () => {
+	it("shows the first file or the path with ~ expanded", () => {
+		expect(
+			resolveRevealPath(
+				{
+					type: "files",
+					content: formatStoredFileList(["/tmp/a.txt", "/tmp/b.txt"]),
+					content_kind: null,
+				},
+				"/home/me",
+			),
+		).toBe("/tmp/a.txt");
+		expect(resolveRevealPath(text("~/notes.md", "path"), "/home/me")).toBe(
+			"/home/me/notes.md",
+		);
+	});
+
+	it("refuses relative paths and other items", () => {
+		expect(() =>
+			resolveRevealPath(text("./notes.md", "path"), "/home/me"),
+		).toThrow("Cannot show a relative path: ./notes.md");
+		expect(() =>
+			resolveRevealPath(text("https://example.com", "url"), "/home/me"),
+		).toThrow("Only file and path items can be shown in a folder");
+	});
+});
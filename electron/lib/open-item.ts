@@ -0,0 +1,79 @@
+import path from "node:path";
+import { parseStoredFileList } from "./file-lists.js";
+import type { HistoryRow } from "./history-repository.js";
+
+/**
+ * URL schemes an item may be opened with. Anything else (`file:`,
+ * `javascript:`, or an app's own protocol) is refused, so opening a
+ * copied link never launches an arbitrary handler.
+ */
+export const OPEN_URL_SCHEMES = ["http", "https", "mailto", "ftp"] as const;
+
+type OpenableItem = Pick<HistoryRow, "type" | "content" | "content_kind">;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * The URL a link or email item opens: `www.` links get `https://` and
+ * addresses `mailto:`.
+ * Pure function.
+ *
+ * @throws if the item is not a link or email, or its scheme is not one
+ *   of {@link OPEN_URL_SCHEMES}
+ */
+export const resolveOpenUrl = (item: OpenableItem): string => {
+	if (
+		item.type !== "text" ||
+		(item.content_kind !== "url" && item.content_kind !== "email")
+	) {
+		throw new Error("Only link and email items can be opened");
+	}
+	let text = item.content.trim();
+	if (item.content_kind === "email" && !/^mailto:/i.test(text)) {
+		text = `mailto:${text}`;
+	} else if (/^www\./i.test(text)) {
+		text = `https://${text}`;
+	}
+
+	let url: URL;
+	try {
+		url = new URL(text);
+	} catch {
+		throw new Error(`Invalid link: ${text}`);
+	}
+	const scheme = url.protocol.replace(/:$/, "").toLowerCase();
+	if (!(OPEN_URL_SCHEMES as readonly string[]).includes(scheme)) {
+		throw new Error(
+			`Links with the ${scheme} scheme cannot be opened; allowed are ${OPEN_URL_SCHEMES.join(", ")}`,
+		);
+	}
+	return url.href;
+};
+
+/**
+ * The file a files item or path item shows in the file manager: the
+ * first of its files, or the path with `~` expanded.
+ * Pure function.
+ *
+ * @throws if the item is neither, or the path is not absolute
+ */
+export const resolveRevealPath = (
+	item: OpenableItem,
+	homeDir: string,
+): string => {
+	let target: string | undefined;
+	if (item.type === "files") {
+		[target] = parseStoredFileList(item.content);
+	} else if (item.type === "text" && item.content_kind === "path") {
+		target = item.content.trim().replace(/^~(?=$|[\\/])/, homeDir);
+	}
+	if (!target) {
+		throw new Error("Only file and path items can be shown in a folder");
+	}
+	if (!path.isAbsolute(target)) {
+		throw new Error(`Cannot show a relative path: ${target}`);
+	}
+	return target;
+};
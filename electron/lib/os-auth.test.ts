@@ -0,0 +1,79 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	type AsyncCommandRunner,
+	createPlatformAuthenticator,
+} from "./os-auth.js";
+
+const touchId = {
+	canPromptTouchID: () => true,
+	promptTouchID: vi.fn().mockResolvedValue(undefined),
+};
+
+describe("createPlatformAuthenticator", () => {
+	it("uses Touch ID on macOS when available", async () => {
+		const authenticate = createPlatformAuthenticator({
+			platform: "darwin",
+			touchId,
+		});
+
+		await authenticate?.("unlock");
+		expect(touchId.promptTouchID).toHaveBeenCalledWith("unlock");
+		expect(
+			createPlatformAuthenticator({
+				platform: "darwin",
+				touchId: { ...touchId, canPromptTouchID: () => false },
+			}),
+		).toBeNull();
+	});
+
+	it("passes the Windows Hello reason through the environment", async () => {
+		const run = vi.fn<AsyncCommandRunner>().mockResolvedValue(0);
+		const authenticate = createPlatformAuthenticator({
+			platform: "win32",
+			touchId,
+			run,
+		});
+
+		await authenticate?.("unlock history");
+		const [command, args, env] = run.mock.calls[0];
+		expect(command).toBe("powershell.exe");
+		expect(args.join(" ")).not.toContain("unlock history");
+		expect(env).toEqual({ CLIPBOARD_AUTH_REASON: "unlock history" });
+	});
+
+	it("checks the app's polkit action for the user's own password", async () => {
+		const run = vi.fn<AsyncCommandRunner>().mockResolvedValue(0);
+		const authenticate = createPlatformAuthenticator({
+			platform: "linux",
+			touchId,
+			run,
+			pid: 42,
+		});
+
+		await authenticate?.("unlock");
+		expect(run).toHaveBeenLastCalledWith("pkcheck", [
+			"--action-id",
+			"com.erezsobel.clipboard-manager.unlock",
+			"--process",
+			"42",
+			"--allow-user-interaction",
+		]);
+	});
+
+	it("falls back to pkexec until the polkit action is installed", async () => {
+		const run = vi
+			.fn<AsyncCommandRunner>()
+			.mockResolvedValueOnce(1)
+			.mockResolvedValueOnce(126);
+		const authenticate = createPlatformAuthenticator({
+			platform: "linux",
+			touchId,
+			run,
+		});
+
+		await expect(authenticate?.("unlock")).rejects.toThrow(
+			"Authentication failed",
+		);
+		expect(run).toHaveBeenLastCalledWith("pkexec", ["/bin/true"]);
+	});
+});
@@ -0,0 +1,125 @@
+import { execFile } from "node:child_process";
+
+/**
+ * Asks the user to prove their identity with the OS.
+ * Resolves once verified; rejects if the user cancels or fails.
+ */
+export type Authenticator = (reason: string) => Promise<void>;
+
+/**
+ * Subset of Electron's `systemPreferences` used for Touch ID.
+ */
+export type TouchIdPrompter = {
+	canPromptTouchID: () => boolean;
+	promptTouchID: (reason: string) => Promise<void>;
+};
+
+/**
+ * Runs a command and resolves with its exit code; injectable for tests.
+ * `env` is added to the current environment.
+ */
+export type AsyncCommandRunner = (
+	command: string,
+	args: string[],
+	env?: Record<string, string>,
+) => Promise<number>;
+
+/**
+ * Windows Hello via the WinRT UserConsentVerifier, driven from PowerShell.
+ * The reason is passed through an environment variable so it is never
+ * interpolated into the script.
+ */
+const WINDOWS_HELLO_SCRIPT = [
+	"Add-Type -AssemblyName System.Runtime.WindowsRuntime",
+	"$asTask = [System.WindowsRuntimeSystemExtensions].GetMethods() | Where-Object { $_.Name -eq 'AsTask' -and $_.GetParameters().Count -eq 1 -and $_.GetParameters()[0].ParameterType.Name -eq 'IAsyncOperation`1' } | Select-Object -First 1",
+	"$null = [Windows.Security.Credentials.UI.UserConsentVerifier, Windows.Security.Credentials.UI, ContentType = WindowsRuntime]",
+	"$operation = [Windows.Security.Credentials.UI.UserConsentVerifier]::RequestVerificationAsync($env:CLIPBOARD_AUTH_REASON)",
+	"$task = $asTask.MakeGenericMethod([Windows.Security.Credentials.UI.UserConsentVerificationResult]).Invoke($null, @($operation))",
+	"if ($task.Result -eq 'Verified') { exit 0 } else { exit 1 }",
+].join("; ");
+
+const WINDOWS_HELLO_ARGS = [
+	"-NoProfile",
+	"-NonInteractive",
+	"-Command",
+	WINDOWS_HELLO_SCRIPT,
+];
+
+const runCommand: AsyncCommandRunner = (command, args, env) =>
+	new Promise((resolve, reject) => {
+		execFile(command, args, { env: { ...process.env, ...env } }, (error) => {
+			if (!error) return resolve(0);
+			if (typeof error.code === "number") return resolve(error.code);
+			reject(error);
+		});
+	});
+
+/**
+ * polkit action of `build/linux/com.erezsobel.clipboard-manager.policy`,
+ * which asks for the user's own password (`auth_self`).
+ */
+const POLKIT_ACTION_ID = "com.erezsobel.clipboard-manager.unlock";
+
+const assertVerified = async (exitCode: Promise<number>): Promise<void> => {
+	if ((await exitCode) !== 0) {
+		throw new Error("Authentication failed or was cancelled");
+	}
+};
+
+/**
+ * Picks the OS authentication prompt for a platform: Touch ID on macOS,
+ * Windows Hello on Windows, and polkit on Linux. On Linux the app's polkit
+ * action asks for the user's own password once its policy is installed;
+ * until then `pkexec` is the only prompt, and it asks for an
+ * administrator's.
+ *
+ * @returns null if the platform offers no supported prompt
+ */
+export const createPlatformAuthenticator = (deps: {
+	platform: NodeJS.Platform;
+	touchId: TouchIdPrompter;
+	run?: AsyncCommandRunner;
+	/** Process polkit checks; defaults to this one */
+	pid?: number;
+}): Authenticator | null => {
+	const run = deps.run ?? runCommand;
+	const pid = deps.pid ?? process.pid;
+
+	if (deps.platform === "darwin") {
+		if (!deps.touchId.canPromptTouchID()) return null;
+		return (reason) => deps.touchId.promptTouchID(reason);
+	}
+
+	if (deps.platform === "win32") {
+		return (reason) =>
+			assertVerified(
+				run("powershell.exe", WINDOWS_HELLO_ARGS, {
+					CLIPBOARD_AUTH_REASON: reason,
+				}),
+			);
+	}
+
+	if (deps.platform === "linux") {
+		return async () => {
+			const registered = await run("pkaction", [
+				"--action-id",
+				POLKIT_ACTION_ID,
+			]).catch(() => 1);
+			if (registered !== 0) {
+				// pkexec shows the desktop's polkit agent; /bin/true does nothing
+				return assertVerified(run("pkexec", ["/bin/true"]));
+			}
+			return assertVerified(
+				run("pkcheck", [
+					"--action-id",
+					POLKIT_ACTION_ID,
+					"--process",
+					String(pid),
+					"--allow-user-interaction",
+				]),
+			);
+		};
+	}
+
+	return null;
+};
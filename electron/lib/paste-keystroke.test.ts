@@ -0,0 +1,89 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	createKeyRepeater,
+	createPasteKeystroke,
+	getPasteCommand,
+	getRepeatedKeyCommand,
+} from "./paste-keystroke.js";
+
+describe("getPasteCommand", () => {
+	it("uses the platform paste keystroke", () => {
+		expect(getPasteCommand("darwin")?.command).toBe("osascript");
+		expect(getPasteCommand("win32")?.args).toContain(
+			"Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('^v')",
+		);
+		expect(getPasteCommand("linux")).toEqual({
+			command: "xdotool",
+			args: ["key", "--clearmodifiers", "ctrl+v"],
+		});
+	});
+
+	it("is unsupported elsewhere", () => {
+		expect(getPasteCommand("freebsd")).toBeNull();
+	});
+});
+
+describe("createPasteKeystroke", () => {
+	it("runs the platform command", async () => {
+		const run = vi.fn().mockResolvedValue(undefined);
+
+		await createPasteKeystroke({ platform: "linux", run })();
+
+		expect(run).toHaveBeenCalledWith("xdotool", [
+			"key",
+			"--clearmodifiers",
+			"ctrl+v",
+		]);
+	});
+
+	it("does nothing on unsupported platforms", async () => {
+		const run = vi.fn();
+
+		await createPasteKeystroke({ platform: "freebsd", run })();
+
+		expect(run).not.toHaveBeenCalled();
+	});
+
+	it("rejects when the command fails", async () => {
+		const run = vi.fn().mockRejectedValue(new Error("xdotool not found"));
+
+		await expect(
+			createPasteKeystroke({ platform: "linux", run })(),
+		).rejects.toThrow("xdotool not found");
+	});
+});
+
+describe("getRepeatedKeyCommand", () => {
+	it("presses the key the given number of times", () => {
+		expect(getRepeatedKeyCommand("darwin", "left", 3)?.args).toEqual(
+			expect.arrayContaining(["repeat 3 times", "key code 123"]),
+		);
+		expect(getRepeatedKeyCommand("win32", "backspace", 3)?.args).toContain(
+			"Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('{BACKSPACE 3}')",
+		);
+		expect(getRepeatedKeyCommand("linux", "left", 3)).toEqual({
+			command: "xdotool",
+			args: ["key", "--clearmodifiers", "--repeat", "3", "Left"],
+		});
+		expect(getRepeatedKeyCommand("freebsd", "left", 3)).toBeNull();
+	});
+});
+
+describe("createKeyRepeater", () => {
+	it("only runs for a positive count", async () => {
+		const run = vi.fn().mockResolvedValue(undefined);
+		const pressKey = createKeyRepeater({ platform: "linux", run });
+
+		await pressKey("backspace", 0);
+		await pressKey("backspace", 2);
+
+		expect(run).toHaveBeenCalledTimes(1);
+		expect(run).toHaveBeenCalledWith("xdotool", [
+			"key",
+			"--clearmodifiers",
+			"--repeat",
+			"2",
+			"BackSpace",
+		]);
+	});
+});
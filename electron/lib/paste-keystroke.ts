@@ -0,0 +1,173 @@
+import { execFile } from "node:child_process";
+
+/**
+ * Runs a command to completion; injectable for tests.
+ */
+export type KeystrokeCommandRunner = (
+	command: string,
+	args: string[],
+) => Promise<void>;
+
+/**
+ * Sends Ctrl+V to the foreground window; `^` is Ctrl in SendKeys syntax.
+ */
+const WINDOWS_PASTE_SCRIPT =
+	"Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('^v')";
+
+const runCommand: KeystrokeCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(command, args, (error) => (error ? reject(error) : resolve()));
+	});
+
+/**
+ * Command that injects the platform paste keystroke into the focused
+ * application, or null if the platform is unsupported.
+ * - macOS: Cmd+V through System Events (needs Accessibility permission)
+ * - Windows: Ctrl+V through SendKeys
+ * - Linux (X11): Ctrl+V through `xdotool`
+ * Pure function.
+ */
+export const getPasteCommand = (
+	platform: NodeJS.Platform,
+): { command: string; args: string[] } | null => {
+	switch (platform) {
+		case "darwin":
+			return {
+				command: "osascript",
+				args: [
+					"-e",
+					'tell application "System Events" to keystroke "v" using command down',
+				],
+			};
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-Command",
+					WINDOWS_PASTE_SCRIPT,
+				],
+			};
+		case "linux":
+			return {
+				command: "xdotool",
+				args: ["key", "--clearmodifiers", "ctrl+v"],
+			};
+		default:
+			return null;
+	}
+};
+
+/**
+ * Keys that can be pressed repeatedly with {@link createKeyRepeater}.
+ */
+export type RepeatedKey = "left" | "backspace";
+
+/**
+ * Key names per tool: macOS key codes, SendKeys names and X keysyms.
+ */
+const REPEATED_KEYS: Record<
+	RepeatedKey,
+	{ macKeyCode: number; sendKeys: string; keysym: string }
+> = {
+	left: { macKeyCode: 123, sendKeys: "LEFT", keysym: "Left" },
+	backspace: { macKeyCode: 51, sendKeys: "BACKSPACE", keysym: "BackSpace" },
+};
+
+/**
+ * Command that presses `key` `count` times in the focused application,
+ * or null if the platform is unsupported. Uses the same tools as
+ * {@link getPasteCommand}.
+ * Pure function.
+ */
+export const getRepeatedKeyCommand = (
+	platform: NodeJS.Platform,
+	key: RepeatedKey,
+	count: number,
+): { command: string; args: string[] } | null => {
+	const names = REPEATED_KEYS[key];
+	switch (platform) {
+		case "darwin":
+			return {
+				command: "osascript",
+				args: [
+					"-e",
+					'tell application "System Events"',
+					"-e",
+					`repeat ${count} times`,
+					"-e",
+					`key code ${names.macKeyCode}`,
+					"-e",
+					"end repeat",
+					"-e",
+					"end tell",
+				],
+			};
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-Command",
+					`Add-Type -AssemblyName System.Windows.Forms; [System.Windows.Forms.SendKeys]::SendWait('{${names.sendKeys} ${count}}')`,
+				],
+			};
+		case "linux":
+			return {
+				command: "xdotool",
+				args: [
+					"key",
+					"--clearmodifiers",
+					"--repeat",
+					String(count),
+					names.keysym,
+				],
+			};
+		default:
+			return null;
+	}
+};
+
+/**
+ * Creates the paste simulator for the current platform.
+ * On unsupported platforms it resolves without pasting.
+ *
+ * @returns a function that resolves once the keystroke is sent and rejects
+ *   if the command fails
+ */
+export const createPasteKeystroke = (deps: {
+	platform: NodeJS.Platform;
+	run?: KeystrokeCommandRunner;
+}): (() => Promise<void>) => {
+	const run = deps.run ?? runCommand;
+	const pasteCommand = getPasteCommand(deps.platform);
+
+	return async () => {
+		if (!pasteCommand) return;
+		await run(pasteCommand.command, pasteCommand.args);
+	};
+};
+
+/**
+ * Creates the key repeater used to move the caret after pasting a
+ * snippet with `{cursor}` and to erase an expanded abbreviation.
+ * On unsupported platforms, or for a count below 1, it does nothing.
+ *
+ * @returns a function that resolves once the keystrokes are sent and
+ *   rejects if the command fails
+ */
+export const createKeyRepeater = (deps: {
+	platform: NodeJS.Platform;
+	run?: KeystrokeCommandRunner;
+}): ((key: RepeatedKey, count: number) => Promise<void>) => {
+	const run = deps.run ?? runCommand;
+
+	return async (key, count) => {
+		if (!Number.isInteger(count) || count < 1) return;
+		const command = getRepeatedKeyCommand(deps.platform, key, count);
+		if (!command) return;
+		await run(command.command, command.args);
+	};
+};
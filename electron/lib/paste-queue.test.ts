@@ -0,0 +1,52 @@
+import { describe, expect, it, vi } from "vitest";
+import { createPasteQueue } from "./paste-queue.js";
+
+describe("createPasteQueue", () => {
+	it("consumes items in the order they were queued", () => {
+		const queue = createPasteQueue();
+		queue.toggle(3);
+		queue.toggle(1);
+		queue.toggle(2);
+
+		expect(queue.shift()).toBe(3);
+		expect(queue.shift()).toBe(1);
+		expect(queue.getItems()).toEqual([2]);
+		expect(queue.shift()).toBe(2);
+		expect(queue.shift()).toBeNull();
+	});
+
+	it("removes an item that is toggled again", () => {
+		const queue = createPasteQueue();
+		queue.toggle(1);
+		queue.toggle(2);
+
+		expect(queue.toggle(1)).toEqual([2]);
+	});
+
+	it("notifies on every change", () => {
+		const onChange = vi.fn();
+		const queue = createPasteQueue({ onChange });
+
+		queue.toggle(5);
+		queue.shift();
+		queue.clear();
+
+		expect(onChange.mock.calls).toEqual([[[5]], [[]]]);
+	});
+
+	it("rejects invalid ids", () => {
+		const queue = createPasteQueue();
+
+		for (const id of [0, -1, 1.5, "1"]) {
+			expect(() => queue.toggle(id)).toThrow("Invalid paste queue item");
+		}
+		expect(queue.getItems()).toEqual([]);
+	});
+
+	it("rejects items beyond the limit", () => {
+		const queue = createPasteQueue();
+		for (let id = 1; id <= 100; id++) queue.toggle(id);
+
+		expect(() => queue.toggle(101)).toThrow("Paste queue is full");
+	});
+});
@@ -0,0 +1,73 @@
+/**
+ * Longest paste queue accepted.
+ */
+const MAX_QUEUE_LENGTH = 100;
+
+export type PasteQueueDeps = {
+	/** Called with the queued item ids whenever the queue changes */
+	onChange?: (itemIds: number[]) => void;
+};
+
+/**
+ * Checks that a value is a history item id.
+ * Pure function.
+ *
+ * @throws if the value is not a positive integer
+ */
+const assertValidItemId = (value: unknown): number => {
+	if (typeof value !== "number" || !Number.isInteger(value) || value <= 0) {
+		throw new Error(`Invalid paste queue item: ${String(value)}`);
+	}
+	return value;
+};
+
+/**
+ * Creates the paste queue: history items lined up to be pasted one after
+ * another, each paste consuming the next. Held in the main process, so it
+ * survives the window being hidden and shown; not persisted.
+ */
+export const createPasteQueue = (deps: PasteQueueDeps = {}) => {
+	let itemIds: number[] = [];
+
+	const getItems = (): number[] => [...itemIds];
+
+	const update = (next: number[]): number[] => {
+		itemIds = next;
+		deps.onChange?.(getItems());
+		return getItems();
+	};
+
+	/**
+	 * Adds an item to the end of the queue, or removes it if already queued.
+	 *
+	 * @throws if the id is invalid or the queue already holds 100 items
+	 */
+	const toggle = (value: unknown): number[] => {
+		const id = assertValidItemId(value);
+		if (itemIds.includes(id)) {
+			return update(itemIds.filter((queued) => queued !== id));
+		}
+		if (itemIds.length >= MAX_QUEUE_LENGTH) {
+			throw new Error(
+				`Paste queue is full: at most ${MAX_QUEUE_LENGTH} items can be queued`,
+			);
+		}
+		return update([...itemIds, id]);
+	};
+
+	/**
+	 * Removes and returns the next item, or null if the queue is empty.
+	 */
+	const shift = (): number | null => {
+		const [next, ...rest] = itemIds;
+		if (next === undefined) return null;
+		update(rest);
+		return next;
+	};
+
+	const clear = (): number[] => (itemIds.length > 0 ? update([]) : []);
+
+	return { getItems, toggle, shift, clear };
+};
+
+export type PasteQueue = ReturnType<typeof createPasteQueue>;
@@ -0,0 +1,94 @@
+import { describe, expect, it } from "vitest";
+import {
+	computeDifferenceHash,
+	groupNearDuplicates,
+	hammingDistance,
+	parseNearDuplicateDistance,
+} from "./perceptual-hash.js";
+
+/**
+ * 9×8 BGRA bitmap with a gray level per pixel.
+ */
+const grid = (level: (x: number, y: number) => number): Buffer => {
+	const bitmap = Buffer.alloc(9 * 8 * 4, 255);
+	for (let y = 0; y < 8; y++) {
+		for (let x = 0; x < 9; x++) {
+			bitmap.fill(level(x, y), (y * 9 + x) * 4, (y * 9 + x) * 4 + 3);
+		}
+	}
+	return bitmap;
+};
+
+describe("computeDifferenceHash", () => {
+	it("sets a bit where brightness falls to the right", () => {
+		expect(computeDifferenceHash(grid((x) => 255 - x * 20))).toBe(
+			"ffffffffffffffff",
+		);
+		expect(computeDifferenceHash(grid((x) => x * 20))).toBe(
+			"0000000000000000",
+		);
+		const corner = grid((x, y) => (x === 0 && y === 0 ? 1 : 0));
+		expect(computeDifferenceHash(corner)).toBe("8000000000000000");
+	});
+
+	it("ignores a uniform change in brightness", () => {
+		const gradient = (x: number, y: number) => ((x * 37 + y * 11) % 9) * 25;
+		expect(computeDifferenceHash(grid((x, y) => gradient(x, y) + 3))).toBe(
+			computeDifferenceHash(grid(gradient)),
+		);
+	});
+
+	it("rejects bitmaps of another size", () => {
+		expect(() => computeDifferenceHash(Buffer.alloc(16))).toThrow(
+			"Invalid hash bitmap",
+		);
+	});
+});
+
+describe("hammingDistance", () => {
+	it("counts differing bits", () => {
+		expect(hammingDistance("ffffffffffffffff", "ffffffffffffffff")).toBe(0);
+		expect(hammingDistance("0000000000000000", "ffffffffffffffff")).toBe(64);
+		expect(hammingDistance("8000000000000001", "0000000000000000")).toBe(2);
+	});
+});
+
+describe("parseNearDuplicateDistance", () => {
+	it("defaults to 5 and accepts 0-16", () => {
+		expect(parseNearDuplicateDistance(undefined)).toBe(5);
+		expect(parseNearDuplicateDistance(0)).toBe(0);
+		expect(parseNearDuplicateDistance(16)).toBe(16);
+		for (const value of [-1, 17, 2.5, "3"]) {
+			expect(() => parseNearDuplicateDistance(value)).toThrow(
+				"Invalid near-duplicate distance",
+			);
+		}
+	});
+});
+
+describe("groupNearDuplicates", () => {
+	it("groups hashes within the distance, transitively and in order", () => {
+		const items = [
+			{ id: 9, hash: "00000000000000ff" },
+			{ id: 8, hash: "ffffffffffffffff" },
+			{ id: 7, hash: "000000000000000f" },
+			{ id: 6, hash: "0000000000000000" },
+			{ id: 5, hash: "fffffffffffffffe" },
+			{ id: 4, hash: "0f0f0f0f0f0f0f0f" },
+		];
+		expect(groupNearDuplicates(items, 4)).toEqual([
+			[9, 7, 6],
+			[8, 5],
+		]);
+		expect(groupNearDuplicates(items, 0)).toEqual([]);
+	});
+
+	it("finds hashes that differ in every slice", () => {
+		const items = [
+			{ id: 2, hash: "1111111111111111" },
+			{ id: 1, hash: "0000000000000000" },
+		];
+		expect(groupNearDuplicates(items, 15)).toEqual([]);
+		expect(groupNearDuplicates(items, 16)).toEqual([[2, 1]]);
+	});
+});
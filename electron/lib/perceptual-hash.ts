@@ -0,0 +1,152 @@
+/**
+ * Size an image is shrunk to before hashing: one column wider than tall,
+ * so each of the 8 rows gives 8 left-right brightness comparisons.
+ */
+export const PERCEPTUAL_HASH_GRID = { width: 9, height: 8 } as const;
+
+/**
+ * Differing bits up to which two images count as near-duplicates.
+ * Re-encoded or slightly rescaled copies of a screenshot are usually
+ * within 2-3 bits; unrelated images differ in about 32.
+ */
+export const NEAR_DUPLICATE_MAX_DISTANCE = 5;
+
+/**
+ * Largest distance a caller may ask for; beyond it unrelated images start
+ * to match.
+ */
+const MAX_DISTANCE = 16;
+
+/**
+ * Computes the difference hash (dHash) of an image shrunk to
+ * `PERCEPTUAL_HASH_GRID`: one bit per pixel pair, set when the left pixel
+ * is brighter. Similar images get hashes a few bits apart.
+ * Pure function.
+ *
+ * @param bitmap - 9×8 pixels, 4 bytes each in BGRA order
+ * @returns 16 hex digits
+ */
+export const computeDifferenceHash = (bitmap: Buffer): string => {
+	const { width, height } = PERCEPTUAL_HASH_GRID;
+	if (bitmap.length !== width * height * 4) {
+		throw new Error(
+			`Invalid hash bitmap: expected ${width}×${height} pixels, got ${bitmap.length} bytes`,
+		);
+	}
+
+	const brightness = (x: number, y: number) => {
+		const i = (y * width + x) * 4;
+		return bitmap[i] * 0.114 + bitmap[i + 1] * 0.587 + bitmap[i + 2] * 0.299;
+	};
+	let hash = "";
+	for (let y = 0; y < height; y++) {
+		let byte = 0;
+		for (let x = 0; x < width - 1; x++) {
+			byte = (byte << 1) | (brightness(x, y) > brightness(x + 1, y) ? 1 : 0);
+		}
+		hash += byte.toString(16).padStart(2, "0");
+	}
+	return hash;
+};
+
+/**
+ * Number of bits in which two hashes differ.
+ * Pure function.
+ */
+export const hammingDistance = (a: string, b: string): number => {
+	let distance = 0;
+	for (let i = 0; i < a.length; i += 8) {
+		let bits =
+			Number.parseInt(a.slice(i, i + 8), 16) ^
+			Number.parseInt(b.slice(i, i + 8), 16);
+		for (; bits !== 0; bits &= bits - 1) distance += 1;
+	}
+	return distance;
+};
+
+/**
+ * Validates the distance of a near-duplicate search.
+ * Pure function.
+ *
+ * @throws if the distance is not an integer from 0 to 16
+ */
+export const parseNearDuplicateDistance = (
+	value: unknown = NEAR_DUPLICATE_MAX_DISTANCE,
+): number => {
+	if (
+		typeof value !== "number" ||
+		!Number.isInteger(value) ||
+		value < 0 ||
+		value > MAX_DISTANCE
+	) {
+		throw new Error(
+			`Invalid near-duplicate distance: expected an integer from 0 to ${MAX_DISTANCE}`,
+		);
+	}
+	return value;
+};
+
+/**
+ * Groups items whose hashes are within `maxDistance` of each other,
+ * directly or through other items in the group. Hashes within the
+ * distance agree on at least one of `maxDistance + 1` slices, so only
+ * items sharing a slice are compared.
+ * Pure function.
+ *
+ * @param items - Hashed items, in the order groups should list them
+ * @returns groups of two or more ids, each in input order, ordered by
+ *   their first item
+ */
+export const groupNearDuplicates = (
+	items: readonly { id: number; hash: string }[],
+	maxDistance: number,
+): number[][] => {
+	// Union-find over item indexes
+	const parent = items.map((_, i) => i);
+	const find = (i: number): number => {
+		while (parent[i] !== i) {
+			parent[i] = parent[parent[i]];
+			i = parent[i];
+		}
+		return i;
+	};
+	const digits = items[0]?.hash.length ?? 0;
+	const slices = maxDistance + 1;
+	// Hex digits are the smallest slice; past that every pair is compared
+	const buckets = new Map<string, number[]>();
+	if (slices > digits) {
+		buckets.set("", Array.from(items.keys()));
+	} else {
+		for (let slice = 0; slice < slices; slice++) {
+			const start = Math.floor((slice * digits) / slices);
+			const end = Math.floor(((slice + 1) * digits) / slices);
+			items.forEach((item, i) => {
+				const key = `${slice}:${item.hash.slice(start, end)}`;
+				const bucket = buckets.get(key) ?? [];
+				bucket.push(i);
+				buckets.set(key, bucket);
+			});
+		}
+	}
+	for (const bucket of buckets.values()) {
+		for (let i = 0; i < bucket.length; i++) {
+			for (let j = i + 1; j < bucket.length; j++) {
+				const [x, y] = [bucket[i], bucket[j]];
+				if (hammingDistance(items[x].hash, items[y].hash) <= maxDistance) {
+					const [a, b] = [find(x), find(y)];
+					// The earlier item stays the root, so groups keep input order
+					if (a !== b) parent[Math.max(a, b)] = Math.min(a, b);
+				}
+			}
+		}
+	}
+
+	const groups = new Map<number, number[]>();
+	items.forEach((item, i) => {
+		const root = find(i);
+		const group = groups.get(root) ?? [];
+		group.push(item.id);
+		groups.set(root, group);
+	});
+	return [...groups.values()].filter((group) => group.length > 1);
+};
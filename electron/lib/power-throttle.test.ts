@@ -0,0 +1,136 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createPowerThrottle,
+	getPowerThrottlePath,
+	getThrottleReason,
+	type PowerThrottleSettings,
+	parsePowerThrottleSettings,
+} from "./power-throttle.js";
+
+const settings: PowerThrottleSettings = {
+	onBattery: true,
+	idleMinutes: 10,
+	pollIntervalMs: 5000,
+	deferJobs: true,
+};
+
+describe("parsePowerThrottleSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(
+			parsePowerThrottleSettings(
+				{ idleMinutes: 0, deferJobs: false },
+				settings,
+			),
+		).toEqual({ ...settings, idleMinutes: 0, deferJobs: false });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parsePowerThrottleSettings(null)).toThrow(
+			"expected an object",
+		);
+		expect(() => parsePowerThrottleSettings({ onBattery: "yes" })).toThrow(
+			"onBattery must be a boolean",
+		);
+		for (const pollIntervalMs of [500, 90_000, 1500.5]) {
+			expect(() => parsePowerThrottleSettings({ pollIntervalMs })).toThrow(
+				"pollIntervalMs must be an integer from 1000 to 60000",
+			);
+		}
+		expect(() => parsePowerThrottleSettings({ idleMinutes: -1 })).toThrow(
+			"idleMinutes must be an integer",
+		);
+	});
+});
+
+describe("getThrottleReason", () => {
+	it("throttles on battery first, then when idle", () => {
+		expect(
+			getThrottleReason({ onBattery: true, idleSeconds: 3600 }, settings),
+		).toBe("battery");
+		expect(
+			getThrottleReason({ onBattery: false, idleSeconds: 600 }, settings),
+		).toBe("idle");
+		expect(
+			getThrottleReason({ onBattery: false, idleSeconds: 599 }, settings),
+		).toBeNull();
+		expect(
+			getThrottleReason(
+				{ onBattery: true, idleSeconds: 3600 },
+				{ ...settings, onBattery: false, idleMinutes: 0 },
+			),
+		).toBeNull();
+	});
+});
+
+describe("createPowerThrottle", () => {
+	const tempDirs: string[] = [];
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("reports throttling as the power state changes", () => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-power-"));
+		tempDirs.push(dir);
+		let onBattery = false;
+		let idleSeconds = 0;
+		const onChange = vi.fn();
+		const throttle = createPowerThrottle({
+			userDataPath: dir,
+			isOnBattery: () => onBattery,
+			getIdleSeconds: () => idleSeconds,
+			onChange,
+			checkIntervalMs: 1000,
+		});
+
+		throttle.start();
+		expect(onChange).not.toHaveBeenCalled();
+
+		onBattery = true;
+		throttle.refresh();
+		expect(onChange).toHaveBeenLastCalledWith({
+			reason: "battery",
+			pollIntervalMs: 5000,
+			deferJobs: true,
+		});
+		expect(throttle.shouldDeferJobs()).toBe(true);
+
+		// A new interval applies while still throttled
+		throttle.updateSettings({ pollIntervalMs: 10_000, deferJobs: false });
+		expect(onChange).toHaveBeenLastCalledWith({
+			reason: "battery",
+			pollIntervalMs: 10_000,
+			deferJobs: false,
+		});
+
+		onBattery = false;
+		idleSeconds = 600;
+		vi.advanceTimersByTime(1000);
+		expect(throttle.getStatus().reason).toBe("idle");
+
+		idleSeconds = 0;
+		vi.advanceTimersByTime(1000);
+		expect(onChange).toHaveBeenLastCalledWith({
+			reason: null,
+			pollIntervalMs: null,
+			deferJobs: false,
+		});
+		expect(onChange).toHaveBeenCalledTimes(4);
+		throttle.stop();
+
+		const saved = JSON.parse(
+			fs.readFileSync(getPowerThrottlePath(dir), "utf-8"),
+		);
+		expect(saved.pollIntervalMs).toBe(10_000);
+	});
+});
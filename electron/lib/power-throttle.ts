@@ -0,0 +1,279 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted power-aware throttling settings.
+ */
+export type PowerThrottleSettings = {
+	/** Throttle while the computer runs on battery */
+	onBattery: boolean;
+	/** Throttle once the system is idle this long (minutes); 0 never */
+	idleMinutes: number;
+	/** Clipboard polling interval while throttled (ms) */
+	pollIntervalMs: number;
+	/** Hold back OCR, link previews and maintenance while throttled */
+	deferJobs: boolean;
+};
+
+/**
+ * Why capture is throttled.
+ */
+export type ThrottleReason = "battery" | "idle";
+
+/**
+ * Throttling state reported to the renderer and applied by the main
+ * process.
+ */
+export type PowerThrottleStatus = {
+	/** Null while not throttled */
+	reason: ThrottleReason | null;
+	/** Polling interval to use, or null for the normal one */
+	pollIntervalMs: number | null;
+	/** Whether background jobs wait for throttling to end */
+	deferJobs: boolean;
+};
+
+const DEFAULT_POWER_THROTTLE_SETTINGS: PowerThrottleSettings = {
+	onBattery: true,
+	idleMinutes: 10,
+	pollIntervalMs: 5000,
+	deferJobs: true,
+};
+
+const POWER_THROTTLE_FILENAME = "power-throttle.json";
+
+const MIN_POLL_INTERVAL_MS = 1000;
+
+const MAX_POLL_INTERVAL_MS = 60_000;
+
+const MAX_IDLE_MINUTES = 24 * 60;
+
+/**
+ * How often idle time is checked (ms). Battery changes are also
+ * reported by the system as they happen.
+ */
+const DEFAULT_CHECK_INTERVAL_MS = 15_000;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const parseInteger = (
+	value: unknown,
+	key: keyof PowerThrottleSettings,
+	min: number,
+	max: number,
+): number => {
+	if (
+		typeof value !== "number" ||
+		!Number.isInteger(value) ||
+		value < min ||
+		value > max
+	) {
+		throw new Error(
+			`Invalid power throttle settings: ${key} must be an integer from ${min} to ${max}`,
+		);
+	}
+	return value;
+};
+
+const parseBoolean = (
+	value: unknown,
+	key: keyof PowerThrottleSettings,
+): boolean => {
+	if (typeof value !== "boolean") {
+		throw new Error(
+			`Invalid power throttle settings: ${key} must be a boolean`,
+		);
+	}
+	return value;
+};
+
+/**
+ * Validates a power throttle settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value is of the wrong type or out of range
+ */
+export const parsePowerThrottleSettings = (
+	input: unknown,
+	current: PowerThrottleSettings = DEFAULT_POWER_THROTTLE_SETTINGS,
+): PowerThrottleSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid power throttle settings: expected an object");
+	}
+
+	const { onBattery, idleMinutes, pollIntervalMs, deferJobs } =
+		input as Partial<Record<keyof PowerThrottleSettings, unknown>>;
+	const next: PowerThrottleSettings = { ...current };
+
+	if (onBattery !== undefined) {
+		next.onBattery = parseBoolean(onBattery, "onBattery");
+	}
+	if (idleMinutes !== undefined) {
+		next.idleMinutes = parseInteger(
+			idleMinutes,
+			"idleMinutes",
+			0,
+			MAX_IDLE_MINUTES,
+		);
+	}
+	if (pollIntervalMs !== undefined) {
+		next.pollIntervalMs = parseInteger(
+			pollIntervalMs,
+			"pollIntervalMs",
+			MIN_POLL_INTERVAL_MS,
+			MAX_POLL_INTERVAL_MS,
+		);
+	}
+	if (deferJobs !== undefined) {
+		next.deferJobs = parseBoolean(deferJobs, "deferJobs");
+	}
+
+	return next;
+};
+
+/**
+ * Why capture should be throttled now, or null if it should not be.
+ * Pure function. Battery takes precedence over idle.
+ */
+export const getThrottleReason = (
+	power: { onBattery: boolean; idleSeconds: number },
+	settings: PowerThrottleSettings,
+): ThrottleReason | null => {
+	if (settings.onBattery && power.onBattery) return "battery";
+	if (
+		settings.idleMinutes > 0 &&
+		power.idleSeconds >= settings.idleMinutes * 60
+	) {
+		return "idle";
+	}
+	return null;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getPowerThrottlePath = (userDataPath: string) =>
+	path.join(userDataPath, POWER_THROTTLE_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readPowerThrottleFromFile = (filePath: string): PowerThrottleSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_POWER_THROTTLE_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parsePowerThrottleSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read power throttle settings:", error);
+		return { ...DEFAULT_POWER_THROTTLE_SETTINGS };
+	}
+};
+
+const writePowerThrottleToFile = (
+	filePath: string,
+	settings: PowerThrottleSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Power Throttle Module
+// ============================================================================
+
+export type PowerThrottleDeps = {
+	userDataPath: string;
+	isOnBattery: () => boolean;
+	/** Seconds since the user last interacted with the system */
+	getIdleSeconds: () => number;
+	/** Called when throttling starts, ends or its settings change */
+	onChange: (status: PowerThrottleStatus) => void;
+	checkIntervalMs?: number;
+};
+
+/**
+ * Creates the power-aware throttle: on battery, or once the system has
+ * been idle a while, the clipboard is polled less often and background
+ * jobs wait, so the app costs less battery while nobody is copying.
+ */
+export const createPowerThrottle = (deps: PowerThrottleDeps) => {
+	const filePath = getPowerThrottlePath(deps.userDataPath);
+	const checkIntervalMs = deps.checkIntervalMs ?? DEFAULT_CHECK_INTERVAL_MS;
+	let settings = readPowerThrottleFromFile(filePath);
+	let reason: ThrottleReason | null = null;
+	let timer: NodeJS.Timeout | null = null;
+
+	const getStatus = (): PowerThrottleStatus => ({
+		reason,
+		pollIntervalMs: reason ? settings.pollIntervalMs : null,
+		deferJobs: reason !== null && settings.deferJobs,
+	});
+
+	/**
+	 * Re-reads the power state, e.g. when the system reports a switch to
+	 * or from battery, and reports a change in throttling.
+	 */
+	const refresh = (): void => {
+		let next: ThrottleReason | null = null;
+		try {
+			next = getThrottleReason(
+				{ onBattery: deps.isOnBattery(), idleSeconds: deps.getIdleSeconds() },
+				settings,
+			);
+		} catch (error) {
+			console.error("Failed to read the power state:", error);
+		}
+		if (next === reason) return;
+		reason = next;
+		deps.onChange(getStatus());
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		refresh();
+		timer = setInterval(refresh, checkIntervalMs);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	/**
+	 * Whether background jobs should wait for now.
+	 */
+	const shouldDeferJobs = (): boolean => getStatus().deferJobs;
+
+	const getSettings = (): PowerThrottleSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): PowerThrottleSettings => {
+		const next = parsePowerThrottleSettings(input, settings);
+		writePowerThrottleToFile(filePath, next);
+		settings = next;
+		const previous = reason;
+		refresh();
+		// A new interval or job setting applies while already throttled
+		if (reason !== null && reason === previous) deps.onChange(getStatus());
+		return getSettings();
+	};
+
+	return {
+		start,
+		stop,
+		refresh,
+		getStatus,
+		shouldDeferJobs,
+		getSettings,
+		updateSettings,
+	};
+};
+
+export type PowerThrottle = ReturnType<typeof createPowerThrottle>;
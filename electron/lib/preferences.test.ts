@@ -3,6 +3,7 @@ import os from "node:os";
 import path from "node:path";
 import { afterEach, describe, expect, it } from "vitest";
 import {
+	createPreferencesStore,
 	DEFAULT_PREFERENCES,
 	getPreferencesPath,
 	readPreferencesFromFile,
@@ -36,11 +37,27 @@ describe("preferences", () => {
 		const userDataPath = createTempUserDataPath();
 		const filePath = getPreferencesPath(userDataPath);
 
-		writePreferencesToFile(filePath, { launchAtLogin: false });
-		expect(readPreferencesFromFile(filePath)).toEqual({ launchAtLogin: false });
+		const off = { ...DEFAULT_PREFERENCES, launchAtLogin: false };
+		writePreferencesToFile(filePath, off);
+		expect(readPreferencesFromFile(filePath)).toEqual(off);
 
-		writePreferencesToFile(filePath, { launchAtLogin: true });
-		expect(readPreferencesFromFile(filePath)).toEqual({ launchAtLogin: true });
+		const on = { ...DEFAULT_PREFERENCES, launchAtLogin: true };
+		writePreferencesToFile(filePath, on);
+		expect(readPreferencesFromFile(filePath)).toEqual(on);
+	});
+
+	it("keeps startMinimized apart from launchAtLogin", () => {
+		const userDataPath = createTempUserDataPath();
+		const store = createPreferencesStore(userDataPath);
+
+		expect(store.readStartMinimized()).toBe(true);
+		store.writeStartMinimized(false);
+		store.writeLaunchAtLogin(false);
+
+		expect(readPreferencesFromFile(getPreferencesPath(userDataPath))).toEqual({
+			launchAtLogin: false,
+			startMinimized: false,
+		});
 	});
 
 	it("reports whether the preferences file existed before read", () => {
@@ -52,11 +69,15 @@ describe("preferences", () => {
 
 		writePreferencesToFile(getPreferencesPath(userDataPath), {
 			launchAtLogin: false,
+			startMinimized: false,
 		});
 
 		const existing = readPreferencesWithMeta(userDataPath);
 		expect(existing.fileExisted).toBe(true);
-		expect(existing.preferences).toEqual({ launchAtLogin: false });
+		expect(existing.preferences).toEqual({
+			launchAtLogin: false,
+			startMinimized: false,
+		});
 	});
 
 	it("falls back to defaults when preferences file is invalid JSON", () => {
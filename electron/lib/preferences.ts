@@ -3,10 +3,13 @@ import path from "node:path";
 
 export type AppPreferences = {
 	launchAtLogin: boolean;
+	/** Start with only the tray icon instead of showing the history window */
+	startMinimized: boolean;
 };
 
 export const DEFAULT_PREFERENCES = {
 	launchAtLogin: true,
+	startMinimized: true,
 } as const satisfies AppPreferences;
 
 const PREFERENCES_FILENAME = "preferences.json";
@@ -27,6 +30,10 @@ export const readPreferencesFromFile = (filePath: string) => {
 				typeof parsed.launchAtLogin === "boolean"
 					? parsed.launchAtLogin
 					: DEFAULT_PREFERENCES.launchAtLogin,
+			startMinimized:
+				typeof parsed.startMinimized === "boolean"
+					? parsed.startMinimized
+					: DEFAULT_PREFERENCES.startMinimized,
 		};
 	} catch (error) {
 		console.error("Failed to read preferences file, using defaults:", error);
@@ -63,5 +70,13 @@ export const createPreferencesStore = (userDataPath: string) => {
 				launchAtLogin: enabled,
 			});
 		},
+		readStartMinimized: () => readPreferencesFromFile(filePath).startMinimized,
+		writeStartMinimized: (enabled: boolean) => {
+			const current = readPreferencesFromFile(filePath);
+			writePreferencesToFile(filePath, {
+				...current,
+				startMinimized: enabled,
+			});
+		},
 	};
 };
@@ -0,0 +1,129 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createPrimarySelection,
+	getDefaultSelectionFilter,
+	getPrimarySelectionSettingsPath,
+	parsePrimarySelectionSettings,
+} from "./primary-selection.js";
+
+describe("parsePrimarySelectionSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(
+			parsePrimarySelectionSettings(
+				{ enabled: true },
+				{ enabled: false, mode: "merged" },
+			),
+		).toEqual({ enabled: true, mode: "merged" });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parsePrimarySelectionSettings("on")).toThrow(
+			"expected an object",
+		);
+		expect(() => parsePrimarySelectionSettings({ enabled: 1 })).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parsePrimarySelectionSettings({ mode: "both" })).toThrow(
+			"mode must be one of merged, separate",
+		);
+	});
+
+	it("filters lists to copies only when kept separate", () => {
+		expect(
+			getDefaultSelectionFilter({ enabled: true, mode: "separate" }),
+		).toBe("clipboard");
+		expect(
+			getDefaultSelectionFilter({ enabled: true, mode: "merged" }),
+		).toBeUndefined();
+	});
+});
+
+describe("createPrimarySelection", () => {
+	const tempDirs: string[] = [];
+
+	const createTempDir = () => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-primary-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("records a selection once it stops changing", () => {
+		let selection = "before start";
+		const onSelection = vi.fn();
+		const primary = createPrimarySelection({
+			userDataPath: createTempDir(),
+			platform: "linux",
+			readSelection: () => selection,
+			onSelection,
+			intervalMs: 100,
+		});
+		primary.updateSettings({ enabled: true });
+		primary.start();
+
+		// Dragging across text changes the selection on every read
+		for (const step of ["he", "hell", "hello"]) {
+			selection = step;
+			vi.advanceTimersByTime(100);
+		}
+		expect(onSelection).not.toHaveBeenCalled();
+
+		vi.advanceTimersByTime(300);
+		expect(onSelection).toHaveBeenCalledExactlyOnceWith("hello");
+
+		primary.stop();
+		selection = "after stop";
+		vi.advanceTimersByTime(300);
+		expect(onSelection).toHaveBeenCalledTimes(1);
+	});
+
+	it("stays off until enabled, and only on Linux", () => {
+		const dir = createTempDir();
+		const onSelection = vi.fn();
+		let selection = "";
+		const primary = createPrimarySelection({
+			userDataPath: dir,
+			platform: "linux",
+			readSelection: () => selection,
+			onSelection,
+			intervalMs: 100,
+		});
+		primary.start();
+		selection = "ignored";
+		vi.advanceTimersByTime(300);
+		expect(onSelection).not.toHaveBeenCalled();
+
+		// The selection made while capture was off counts as seen
+		primary.updateSettings({ enabled: true, mode: "merged" });
+		vi.advanceTimersByTime(300);
+		expect(onSelection).not.toHaveBeenCalled();
+		const saved = JSON.parse(
+			fs.readFileSync(getPrimarySelectionSettingsPath(dir), "utf-8"),
+		);
+		expect(saved).toEqual({ enabled: true, mode: "merged" });
+
+		const mac = createPrimarySelection({
+			userDataPath: createTempDir(),
+			platform: "darwin",
+			readSelection: () => "",
+			onSelection,
+		});
+		expect(() => mac.updateSettings({ enabled: true })).toThrow(
+			"only available on Linux",
+		);
+		expect(mac.getListSelection()).toBe("clipboard");
+	});
+});
@@ -0,0 +1,236 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { SelectionSource } from "./history-repository.js";
+
+/**
+ * How selections appear in history: in one list with copies, or in a
+ * list of their own that the normal history leaves out.
+ */
+export type PrimarySelectionMode = "merged" | "separate";
+
+/**
+ * Persisted PRIMARY selection capture settings.
+ */
+export type PrimarySelectionSettings = {
+	/** Records selected text, not only copied text */
+	enabled: boolean;
+	mode: PrimarySelectionMode;
+};
+
+const DEFAULT_PRIMARY_SELECTION_SETTINGS: PrimarySelectionSettings = {
+	enabled: false,
+	mode: "separate",
+};
+
+const PRIMARY_SELECTION_FILENAME = "primary-selection.json";
+
+const MODES: readonly PrimarySelectionMode[] = ["merged", "separate"];
+
+/**
+ * Interval between reads of the selection (ms). A selection is recorded
+ * once it is unchanged for one interval, so dragging across text does
+ * not record every step of it.
+ */
+const DEFAULT_INTERVAL_MS = 500;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a PRIMARY selection settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean or `mode` is unknown
+ */
+export const parsePrimarySelectionSettings = (
+	input: unknown,
+	current: PrimarySelectionSettings = DEFAULT_PRIMARY_SELECTION_SETTINGS,
+): PrimarySelectionSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid PRIMARY selection settings: expected an object");
+	}
+
+	const { enabled, mode } = input as Partial<
+		Record<keyof PrimarySelectionSettings, unknown>
+	>;
+	const next: PrimarySelectionSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error(
+				"Invalid PRIMARY selection settings: enabled must be a boolean",
+			);
+		}
+		next.enabled = enabled;
+	}
+
+	if (mode !== undefined) {
+		if (!MODES.includes(mode as PrimarySelectionMode)) {
+			throw new Error(
+				`Invalid PRIMARY selection settings: mode must be one of ${MODES.join(", ")}`,
+			);
+		}
+		next.mode = mode as PrimarySelectionMode;
+	}
+
+	return next;
+};
+
+/**
+ * The selection history lists show when they ask for none: only copies
+ * when selections are kept separate, both when merged.
+ * Pure function.
+ */
+export const getDefaultSelectionFilter = (
+	settings: PrimarySelectionSettings,
+): SelectionSource | undefined =>
+	settings.mode === "separate" ? "clipboard" : undefined;
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getPrimarySelectionSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, PRIMARY_SELECTION_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readPrimarySelectionSettingsFromFile = (
+	filePath: string,
+): PrimarySelectionSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_PRIMARY_SELECTION_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parsePrimarySelectionSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read PRIMARY selection settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_PRIMARY_SELECTION_SETTINGS };
+	}
+};
+
+const writePrimarySelectionSettingsToFile = (
+	filePath: string,
+	settings: PrimarySelectionSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// PRIMARY Selection Module
+// ============================================================================
+
+export type PrimarySelectionDeps = {
+	userDataPath: string;
+	platform: NodeJS.Platform;
+	/** Reads the text of the PRIMARY selection */
+	readSelection: () => string;
+	/** Called with each selection to record */
+	onSelection: (text: string) => void;
+	intervalMs?: number;
+};
+
+/**
+ * Creates the PRIMARY selection capture. On Linux, selecting text places
+ * it in the PRIMARY selection (pasted with a middle click), apart from
+ * the clipboard. When turned on, selections are read every interval and
+ * recorded once they stop changing; Electron has no change event for
+ * them. The selection present when capture starts counts as seen.
+ */
+export const createPrimarySelection = (deps: PrimarySelectionDeps) => {
+	const filePath = getPrimarySelectionSettingsPath(deps.userDataPath);
+	const intervalMs = deps.intervalMs ?? DEFAULT_INTERVAL_MS;
+	const supported = deps.platform === "linux";
+	let settings = readPrimarySelectionSettingsFromFile(filePath);
+	let started = false;
+	let timer: NodeJS.Timeout | null = null;
+	/** Last selection recorded or seen at start */
+	let recorded = "";
+	/** A new selection waiting to stay unchanged for one interval */
+	let candidate: string | null = null;
+
+	const tick = (): void => {
+		try {
+			const text = deps.readSelection();
+			if (text === recorded) {
+				candidate = null;
+				return;
+			}
+			if (text !== candidate) {
+				candidate = text;
+				return;
+			}
+			candidate = null;
+			recorded = text;
+			if (text.trim().length > 0) deps.onSelection(text);
+		} catch (error) {
+			console.error("Failed to read the PRIMARY selection:", error);
+		}
+	};
+
+	/**
+	 * Starts or stops reading to match the settings.
+	 */
+	const apply = (): void => {
+		const active = started && supported && settings.enabled;
+		if (active && !timer) {
+			try {
+				recorded = deps.readSelection();
+			} catch (error) {
+				console.error("Failed to read the PRIMARY selection:", error);
+			}
+			candidate = null;
+			timer = setInterval(tick, intervalMs);
+		} else if (!active && timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	const start = (): void => {
+		started = true;
+		apply();
+	};
+
+	const stop = (): void => {
+		started = false;
+		apply();
+	};
+
+	const getSettings = (): PrimarySelectionSettings => ({ ...settings });
+
+	/**
+	 * @throws if the update is invalid, or turns capture on anywhere but
+	 * Linux
+	 */
+	const updateSettings = (input: unknown): PrimarySelectionSettings => {
+		const next = parsePrimarySelectionSettings(input, settings);
+		if (next.enabled && !supported) {
+			throw new Error(
+				"Invalid PRIMARY selection settings: capture is only available on Linux",
+			);
+		}
+		writePrimarySelectionSettingsToFile(filePath, next);
+		settings = next;
+		apply();
+		return getSettings();
+	};
+
+	/**
+	 * The selection filter history lists use when they ask for none.
+	 */
+	const getListSelection = (): SelectionSource | undefined =>
+		getDefaultSelectionFilter(settings);
+
+	return { start, stop, getSettings, updateSettings, getListSelection };
+};
+
+export type PrimarySelection = ReturnType<typeof createPrimarySelection>;
@@ -0,0 +1,114 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createProfiles,
+	getProfileDataPath,
+	getProfilesFilePath,
+	parseProfileName,
+	parseProfilesFile,
+} from "./profiles.js";
+
+describe("parseProfileName", () => {
+	it("trims and lowercases names", () => {
+		expect(parseProfileName(" Work ")).toBe("work");
+		expect(parseProfileName("side_project-2")).toBe("side_project-2");
+	});
+
+	it("rejects names that are not safe folder names", () => {
+		for (const name of ["", "-work", "../work", "a".repeat(33), "my work"]) {
+			expect(() => parseProfileName(name)).toThrow("Invalid profile name");
+		}
+		expect(() => parseProfileName(1)).toThrow("expected a string");
+	});
+});
+
+describe("parseProfilesFile", () => {
+	it("lists the default profile first and drops invalid names", () => {
+		expect(
+			parseProfilesFile({
+				profiles: ["work", "../etc", "personal", "work"],
+				lastUsed: "work",
+			}),
+		).toEqual({
+			profiles: ["default", "personal", "work"],
+			lastUsed: "work",
+		});
+	});
+
+	it("falls back to the default profile", () => {
+		expect(parseProfilesFile(null)).toEqual({
+			profiles: ["default"],
+			lastUsed: "default",
+		});
+		expect(parseProfilesFile({ lastUsed: "gone" }).lastUsed).toBe("default");
+	});
+});
+
+describe("getProfileDataPath", () => {
+	it("keeps the default profile in the app data folder", () => {
+		expect(getProfileDataPath("/data", "default")).toBe("/data");
+		expect(getProfileDataPath("/data", "work")).toBe(
+			path.join("/data", "profiles", "work"),
+		);
+	});
+});
+
+describe("createProfiles", () => {
+	const tempDirs: string[] = [];
+
+	const createTempRootPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-profiles-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("opens a requested profile and remembers it for the next start", () => {
+		const rootPath = createTempRootPath();
+
+		expect(createProfiles({ rootPath }).open("Work")).toBe("work");
+
+		const profiles = createProfiles({ rootPath });
+		expect(profiles.open(null)).toBe("work");
+		expect(profiles.getStatus()).toEqual({
+			active: "work",
+			profiles: ["default", "work"],
+		});
+	});
+
+	it("creates profiles once", () => {
+		const profiles = createProfiles({ rootPath: createTempRootPath() });
+		profiles.open(null);
+
+		expect(profiles.create("personal").profiles).toEqual([
+			"default",
+			"personal",
+		]);
+		expect(() => profiles.create("Personal")).toThrow(
+			"Profile personal already exists",
+		);
+	});
+
+	it("deletes a profile's data but never the running or default one", () => {
+		const rootPath = createTempRootPath();
+		const profiles = createProfiles({ rootPath });
+		profiles.open(null);
+		profiles.create("work");
+		const workPath = profiles.getDataPath("work");
+		fs.mkdirSync(workPath, { recursive: true });
+		fs.writeFileSync(path.join(workPath, "clipboard.db"), "");
+
+		expect(profiles.remove("work").profiles).toEqual(["default"]);
+		expect(fs.existsSync(workPath)).toBe(false);
+		expect(fs.existsSync(getProfilesFilePath(rootPath))).toBe(true);
+		expect(() => profiles.remove("default")).toThrow("cannot be deleted");
+		expect(() => profiles.remove("work")).toThrow("Unknown profile: work");
+	});
+});
@@ -0,0 +1,234 @@
+import fs from "node:fs";
+import path from "node:path";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Named profiles, each with its own database and settings.
+ */
+export type ProfilesStatus = {
+	/** Profile this instance runs; switching relaunches the app */
+	active: string;
+	/** Every profile, the default one first */
+	profiles: string[];
+};
+
+/**
+ * Contents of `profiles.json`.
+ */
+type ProfilesFile = {
+	profiles: string[];
+	/** Opened when the app starts without `--profile` */
+	lastUsed: string;
+};
+
+/**
+ * Profile whose data stays directly in the app data folder, as it did
+ * before profiles existed.
+ */
+export const DEFAULT_PROFILE = "default";
+
+const PROFILES_FILENAME = "profiles.json";
+
+/**
+ * Folder under the app data folder holding one folder per other profile.
+ */
+const PROFILES_DIRECTORY = "profiles";
+
+const PROFILE_NAME_PATTERN = /^[a-z0-9][a-z0-9_-]{0,31}$/;
+
+const isProfileName = (value: unknown): value is string =>
+	typeof value === "string" && PROFILE_NAME_PATTERN.test(value);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a profile name. Names are lowercased, as they name folders
+ * and some file systems ignore case.
+ * Pure function.
+ *
+ * @throws if the name is not 1-32 letters, digits, `-` or `_` starting
+ *   with a letter or digit
+ */
+export const parseProfileName = (input: unknown): string => {
+	if (typeof input !== "string") {
+		throw new Error("Invalid profile name: expected a string");
+	}
+	const name = input.trim().toLowerCase();
+	if (!isProfileName(name)) {
+		throw new Error(
+			`Invalid profile name: ${JSON.stringify(input)} must be 1-32 letters, digits, - or _`,
+		);
+	}
+	return name;
+};
+
+/**
+ * Orders profiles for display: the default one first, then by name.
+ * Pure function.
+ */
+const sortProfiles = (names: Iterable<string>): string[] => [
+	DEFAULT_PROFILE,
+	...[...new Set(names)].filter((name) => name !== DEFAULT_PROFILE).sort(),
+];
+
+/**
+ * Reads saved profiles, dropping invalid names. The default profile is
+ * always listed, and is last used if the saved one is unknown.
+ * Pure function.
+ */
+export const parseProfilesFile = (input: unknown): ProfilesFile => {
+	const { profiles, lastUsed } = (
+		typeof input === "object" && input !== null ? input : {}
+	) as Partial<Record<keyof ProfilesFile, unknown>>;
+	const names = sortProfiles(
+		Array.isArray(profiles) ? profiles.filter(isProfileName) : [],
+	);
+	return {
+		profiles: names,
+		lastUsed:
+			isProfileName(lastUsed) && names.includes(lastUsed)
+				? lastUsed
+				: DEFAULT_PROFILE,
+	};
+};
+
+/**
+ * The folder holding a profile's database, settings and attachments.
+ * Pure function.
+ */
+export const getProfileDataPath = (rootPath: string, name: string): string =>
+	name === DEFAULT_PROFILE
+		? rootPath
+		: path.join(rootPath, PROFILES_DIRECTORY, name);
+
+// ============================================================================
+// Profiles File
+// ============================================================================
+
+export const getProfilesFilePath = (rootPath: string) =>
+	path.join(rootPath, PROFILES_FILENAME);
+
+/**
+ * Reads saved profiles, falling back to the default profile alone if
+ * missing or invalid.
+ */
+const readProfilesFromFile = (filePath: string): ProfilesFile => {
+	if (!fs.existsSync(filePath)) return parseProfilesFile({});
+
+	try {
+		return parseProfilesFile(JSON.parse(fs.readFileSync(filePath, "utf-8")));
+	} catch (error) {
+		console.error("Failed to read profiles, using defaults:", error);
+		return parseProfilesFile({});
+	}
+};
+
+const writeProfilesToFile = (filePath: string, file: ProfilesFile) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(file, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Profiles Module
+// ============================================================================
+
+export type ProfilesDeps = {
+	/** App data folder; the default profile keeps its data here */
+	rootPath: string;
+};
+
+/**
+ * Creates the profile list, kept in `profiles.json` in the app data
+ * folder. Each profile is a separate data folder, so its database,
+ * attachments and settings never mix with another's. One profile runs at
+ * a time; switching marks the next profile and relaunches the app.
+ */
+export const createProfiles = (deps: ProfilesDeps) => {
+	const filePath = getProfilesFilePath(deps.rootPath);
+	let file = readProfilesFromFile(filePath);
+	let active = DEFAULT_PROFILE;
+
+	const getStatus = (): ProfilesStatus => ({
+		active,
+		profiles: [...file.profiles],
+	});
+
+	const save = (next: ProfilesFile): void => {
+		writeProfilesToFile(filePath, next);
+		file = next;
+	};
+
+	/**
+	 * Marks a profile, created if new, to open on the next start.
+	 *
+	 * @throws if the name is invalid
+	 */
+	const select = (input: unknown): string => {
+		const name = parseProfileName(input);
+		save({
+			profiles: sortProfiles([...file.profiles, name]),
+			lastUsed: name,
+		});
+		return name;
+	};
+
+	/**
+	 * Picks the profile this instance runs: the requested one, else the
+	 * last used. Call once at startup, before anything reads the data
+	 * folder.
+	 *
+	 * @throws if the requested name is invalid
+	 */
+	const open = (requested: string | null): string => {
+		active = select(requested ?? file.lastUsed);
+		return active;
+	};
+
+	/**
+	 * @throws if the name is invalid or taken
+	 */
+	const create = (input: unknown): ProfilesStatus => {
+		const name = parseProfileName(input);
+		if (file.profiles.includes(name)) {
+			throw new Error(`Profile ${name} already exists`);
+		}
+		save({ ...file, profiles: sortProfiles([...file.profiles, name]) });
+		return getStatus();
+	};
+
+	/**
+	 * Deletes a profile with its data folder.
+	 *
+	 * @throws for the default profile, the running one, or an unknown name
+	 */
+	const remove = (input: unknown): ProfilesStatus => {
+		const name = parseProfileName(input);
+		if (name === DEFAULT_PROFILE || name === active) {
+			throw new Error(`Profile ${name} cannot be deleted`);
+		}
+		if (!file.profiles.includes(name)) {
+			throw new Error(`Unknown profile: ${name}`);
+		}
+		fs.rmSync(getProfileDataPath(deps.rootPath, name), {
+			recursive: true,
+			force: true,
+		});
+		save({
+			profiles: file.profiles.filter((profile) => profile !== name),
+			lastUsed: file.lastUsed === name ? active : file.lastUsed,
+		});
+		return getStatus();
+	};
+
+	const getDataPath = (name: string): string =>
+		getProfileDataPath(deps.rootPath, name);
+
+	return { getStatus, getDataPath, open, select, create, remove };
+};
+
+export type Profiles = ReturnType<typeof createProfiles>;
@@ -0,0 +1,55 @@
+import { describe, expect, it, vi } from "vitest";
+import { createQrDecoder, parseQrOutput } from "./qr-codes.js";
+
+describe("parseQrOutput", () => {
+	it("removes only the trailing newline", () => {
+		expect(parseQrOutput("BEGIN:VCARD\nFN:Ada\nEND:VCARD\n")).toBe(
+			"BEGIN:VCARD\nFN:Ada\nEND:VCARD",
+		);
+		expect(parseQrOutput("https://example.com\r\n")).toBe(
+			"https://example.com",
+		);
+		expect(parseQrOutput("")).toBe("");
+	});
+});
+
+describe("createQrDecoder", () => {
+	it("scans with zbarimg outside macOS", async () => {
+		const run = vi.fn().mockResolvedValue("WIFI:S:home;;\n");
+		const decode = createQrDecoder({ platform: "linux", run });
+
+		expect(await decode(Buffer.from("png"))).toBe("WIFI:S:home;;");
+		expect(run).toHaveBeenCalledWith("zbarimg", [
+			"--quiet",
+			"--raw",
+			"-Sdisable",
+			"-Sqrcode.enable",
+			expect.stringMatching(/image\.png$/),
+		]);
+	});
+
+	it("resolves empty when zbarimg finds no code", async () => {
+		const notFound = Object.assign(new Error(), { code: 4 });
+		const run = vi.fn().mockRejectedValue(notFound);
+		const decode = createQrDecoder({ platform: "win32", run });
+
+		expect(await decode(Buffer.from("png"))).toBe("");
+	});
+
+	it("passes other failures on", async () => {
+		const missing = Object.assign(new Error(), { code: "ENOENT" });
+		const run = vi.fn().mockRejectedValue(missing);
+		const decode = createQrDecoder({ platform: "linux", run });
+
+		await expect(decode(Buffer.from("png"))).rejects.toBe(missing);
+	});
+
+	it("uses the Vision framework on macOS", async () => {
+		const run = vi.fn().mockResolvedValue("hello\n");
+		const decode = createQrDecoder({ platform: "darwin", run });
+
+		expect(await decode(Buffer.from("png"))).toBe("hello");
+		expect(run.mock.calls[0][0]).toBe("osascript");
+		expect(run.mock.calls[0][1][3]).toContain("VNDetectBarcodesRequest");
+	});
+});
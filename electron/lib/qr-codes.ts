@@ -0,0 +1,99 @@
+import { execFile } from "node:child_process";
+import type { OutputCommandRunner } from "./foreground-app.js";
+import { type ImageReader, withTempImage } from "./ocr.js";
+
+/**
+ * Upper bound on scanning one image (ms).
+ */
+const SCAN_TIMEOUT_MS = 15_000;
+
+/**
+ * Longest decoded payload stored per item (characters). A QR code holds
+ * at most about 7,000 characters.
+ */
+const MAX_PAYLOAD_CHARS = 8000;
+
+/**
+ * Exit status of `zbarimg` when the image holds no barcode.
+ */
+const ZBAR_NOT_FOUND_STATUS = 4;
+
+/**
+ * Scans for QR codes with the Vision framework through JavaScript for
+ * Automation; prints the payload of the first one found.
+ */
+const MAC_QR_SCRIPT = `ObjC.import("Vision");
+function run(argv) {
+	const url = $.NSURL.fileURLWithPath(argv[0]);
+	const handler = $.VNImageRequestHandler.alloc.initWithURLOptions(url, $({}));
+	const request = $.VNDetectBarcodesRequest.alloc.init;
+	request.symbologies = $([$.VNBarcodeSymbologyQR]);
+	handler.performRequestsError($([request]), null);
+	const results = request.results;
+	for (let i = 0; i < results.count; i++) {
+		const payload = results.objectAtIndex(i).payloadStringValue;
+		if (!payload.isNil()) return payload.js;
+	}
+	return "";
+}`;
+
+const runCommand: OutputCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(
+			command,
+			args,
+			{ timeout: SCAN_TIMEOUT_MS, encoding: "utf-8" },
+			(error, stdout) => (error ? reject(error) : resolve(stdout)),
+		);
+	});
+
+/**
+ * Extracts the payload from scanner output, which ends with a newline.
+ * Payloads may span lines, so only that last newline is removed.
+ * Pure function.
+ */
+export const parseQrOutput = (output: string): string =>
+	output.replace(/\r?\n$/, "").slice(0, MAX_PAYLOAD_CHARS);
+
+/**
+ * Creates the QR code decoder for the current platform; it resolves with
+ * the payload of a QR code in the image, or an empty string if there is
+ * none.
+ * - macOS: the built-in Vision framework
+ * - Windows and Linux: the `zbarimg` command (ZBar), if installed
+ */
+export const createQrDecoder = (deps: {
+	platform: NodeJS.Platform;
+	run?: OutputCommandRunner;
+}): ImageReader => {
+	const run = deps.run ?? runCommand;
+
+	return async (png) => {
+		const output = await withTempImage(png, async (file) => {
+			if (deps.platform === "darwin") {
+				return run("osascript", [
+					"-l",
+					"JavaScript",
+					"-e",
+					MAC_QR_SCRIPT,
+					file,
+				]);
+			}
+			try {
+				return await run("zbarimg", [
+					"--quiet",
+					"--raw",
+					"-Sdisable",
+					"-Sqrcode.enable",
+					file,
+				]);
+			} catch (error) {
+				if ((error as { code?: unknown }).code === ZBAR_NOT_FOUND_STATUS) {
+					return "";
+				}
+				throw error;
+			}
+		});
+		return parseQrOutput(output);
+	};
+};
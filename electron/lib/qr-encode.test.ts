@@ -0,0 +1,79 @@
+import { describe, expect, it } from "vitest";
+import {
+	computeEccCodewords,
+	encodeQr,
+	MAX_QR_BYTES,
+	type QrMatrix,
+	renderQrBitmap,
+} from "./qr-encode.js";
+
+describe("computeEccCodewords", () => {
+	it("matches the standard's worked example", () => {
+		const data = [
+			32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+		];
+		expect(computeEccCodewords(data, 10)).toEqual([
+			196, 35, 39, 119, 235, 215, 231, 226, 93, 23,
+		]);
+	});
+});
+
+describe("encodeQr", () => {
+	const at =
+		({ size, modules }: QrMatrix) =>
+		(x: number, y: number) =>
+			modules[y * size + x];
+
+	it("picks the smallest version that fits", () => {
+		expect(encodeQr("a".repeat(14)).size).toBe(21);
+		expect(encodeQr("a".repeat(15)).size).toBe(25);
+		expect(encodeQr("a".repeat(213)).size).toBe(57);
+		expect(encodeQr("a".repeat(MAX_QR_BYTES)).size).toBe(177);
+	});
+
+	it("draws finder, timing and format patterns", () => {
+		const matrix = encodeQr("https://example.com");
+		const dark = at(matrix);
+		const { size } = matrix;
+
+		for (const [x, y] of [
+			[0, 0],
+			[size - 7, 0],
+			[0, size - 7],
+		]) {
+			expect(dark(x, y)).toBe(true);
+			expect(dark(x + 1, y + 1)).toBe(false);
+			expect(dark(x + 3, y + 3)).toBe(true);
+		}
+		for (let i = 8; i < size - 8; i++) {
+			expect(dark(i, 6)).toBe(i % 2 === 0);
+			expect(dark(6, i)).toBe(i % 2 === 0);
+		}
+		expect(dark(8, size - 8)).toBe(true);
+
+		// Both copies of the format information carry level M (bits 00)
+		expect([dark(0, 8), dark(1, 8)]).toEqual([true, false]);
+		expect([dark(8, size - 1), dark(8, size - 2)]).toEqual([true, false]);
+	});
+
+	it("counts length in UTF-8 bytes", () => {
+		expect(() => encodeQr("é".repeat(1166))).toThrow(
+			"Text too long for a QR code: 2332 bytes (max: 2331)",
+		);
+	});
+});
+
+describe("renderQrBitmap", () => {
+	it("scales modules and adds a light quiet zone", () => {
+		const bitmap = renderQrBitmap({ size: 1, modules: [true] }, 2);
+		expect(bitmap.width).toBe(18);
+		expect(bitmap.height).toBe(18);
+
+		const pixel = (x: number, y: number) =>
+			[...bitmap.data.subarray((y * 18 + x) * 4, (y * 18 + x) * 4 + 4)];
+		expect(pixel(7, 7)).toEqual([255, 255, 255, 255]);
+		expect(pixel(8, 8)).toEqual([0, 0, 0, 255]);
+		expect(pixel(9, 9)).toEqual([0, 0, 0, 255]);
+		expect(pixel(10, 10)).toEqual([255, 255, 255, 255]);
+	});
+});
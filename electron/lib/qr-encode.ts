@@ -0,0 +1,467 @@
+/**
+ * QR code encoder (ISO/IEC 18004): byte mode, error correction level M,
+ * versions 1 to 40. Level M survives about 15% damage, plenty for a code
+ * read off a screen.
+ */
+
+/**
+ * Square grid of modules, row by row; true is dark.
+ */
+export type QrMatrix = {
+	size: number;
+	modules: boolean[];
+};
+
+/**
+ * Pixels of a rendered QR code, 4 bytes per pixel in BGRA order.
+ */
+export type QrBitmap = {
+	width: number;
+	height: number;
+	data: Buffer;
+};
+
+/**
+ * Error correction codewords per block at level M, by version (index 0
+ * unused).
+ */
+const ECC_CODEWORDS_PER_BLOCK = [
+	-1, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26,
+	26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+	28, 28, 28,
+];
+
+/**
+ * Error correction blocks at level M, by version (index 0 unused).
+ */
+const ECC_BLOCKS = [
+	-1, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17,
+	17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+
+/**
+ * Format information bits for level M.
+ */
+const LEVEL_M_BITS = 0b00;
+
+const MIN_VERSION = 1;
+const MAX_VERSION = 40;
+
+/**
+ * Modules of light border around a rendered code; the standard asks for
+ * at least four.
+ */
+const QUIET_ZONE_MODULES = 4;
+
+/**
+ * Longest text that fits in a QR code at level M (UTF-8 bytes).
+ */
+export const MAX_QR_BYTES = 2331;
+
+// ============================================================================
+// Reed-Solomon error correction over GF(2^8)
+// ============================================================================
+
+/**
+ * Multiplies two field elements modulo x^8 + x^4 + x^3 + x^2 + 1.
+ */
+const multiply = (x: number, y: number): number => {
+	let z = 0;
+	for (let i = 7; i >= 0; i--) {
+		z = (z << 1) ^ ((z >>> 7) * 0x11d);
+		z ^= ((y >>> i) & 1) * x;
+	}
+	return z;
+};
+
+/**
+ * Coefficients of the generator polynomial of the given degree, highest
+ * power first and the leading 1 left out.
+ */
+const computeDivisor = (degree: number): number[] => {
+	const result = new Array<number>(degree).fill(0);
+	result[degree - 1] = 1;
+	let root = 1;
+	for (let i = 0; i < degree; i++) {
+		for (let j = 0; j < result.length; j++) {
+			result[j] = multiply(result[j], root);
+			if (j + 1 < result.length) result[j] ^= result[j + 1];
+		}
+		root = multiply(root, 0x02);
+	}
+	return result;
+};
+
+/**
+ * Error correction codewords for a block of data codewords.
+ * Pure function.
+ */
+export const computeEccCodewords = (
+	data: readonly number[],
+	degree: number,
+): number[] => {
+	const divisor = computeDivisor(degree);
+	const result = new Array<number>(degree).fill(0);
+	for (const byte of data) {
+		const factor = byte ^ (result.shift() as number);
+		result.push(0);
+		for (let i = 0; i < divisor.length; i++) {
+			result[i] ^= multiply(divisor[i], factor);
+		}
+	}
+	return result;
+};
+
+// ============================================================================
+// Codewords
+// ============================================================================
+
+/**
+ * Modules available for data and error correction in a version, after
+ * the function patterns.
+ */
+const countDataModules = (version: number): number => {
+	let result = (16 * version + 128) * version + 64;
+	if (version >= 2) {
+		const alignments = Math.floor(version / 7) + 2;
+		result -= (25 * alignments - 10) * alignments - 55;
+		if (version >= 7) result -= 36;
+	}
+	return result;
+};
+
+const countDataCodewords = (version: number): number =>
+	Math.floor(countDataModules(version) / 8) -
+	ECC_CODEWORDS_PER_BLOCK[version] * ECC_BLOCKS[version];
+
+/**
+ * Width of the character count field in byte mode.
+ */
+const countBits = (version: number): number => (version < 10 ? 8 : 16);
+
+/**
+ * Smallest version that holds `length` bytes, or null if none does.
+ */
+const pickVersion = (length: number): number | null => {
+	for (let version = MIN_VERSION; version <= MAX_VERSION; version++) {
+		const bits = 4 + countBits(version) + length * 8;
+		if (bits <= countDataCodewords(version) * 8) return version;
+	}
+	return null;
+};
+
+/**
+ * Data codewords: mode, length, the bytes, a terminator and padding.
+ */
+const buildDataCodewords = (data: Buffer, version: number): number[] => {
+	const bits: number[] = [];
+	const append = (value: number, length: number) => {
+		for (let i = length - 1; i >= 0; i--) bits.push((value >>> i) & 1);
+	};
+	append(0b0100, 4);
+	append(data.length, countBits(version));
+	for (const byte of data) append(byte, 8);
+
+	const capacity = countDataCodewords(version) * 8;
+	append(0, Math.min(4, capacity - bits.length));
+	append(0, (8 - (bits.length % 8)) % 8);
+
+	const codewords: number[] = [];
+	for (let i = 0; i < bits.length; i += 8) {
+		codewords.push(bits.slice(i, i + 8).reduce((acc, bit) => (acc << 1) | bit));
+	}
+	for (let pad = 0xec; codewords.length < capacity / 8; pad ^= 0xec ^ 0x11) {
+		codewords.push(pad);
+	}
+	return codewords;
+};
+
+/**
+ * Splits data into blocks, appends each block's error correction and
+ * interleaves the result. Later blocks are one codeword longer when the
+ * data does not divide evenly.
+ */
+const addEccAndInterleave = (data: number[], version: number): number[] => {
+	const blockCount = ECC_BLOCKS[version];
+	const eccLength = ECC_CODEWORDS_PER_BLOCK[version];
+	const rawCodewords = Math.floor(countDataModules(version) / 8);
+	const shortBlocks = blockCount - (rawCodewords % blockCount);
+	const shortBlockLength = Math.floor(rawCodewords / blockCount);
+
+	const blocks: number[][] = [];
+	for (let i = 0, offset = 0; i < blockCount; i++) {
+		const length = shortBlockLength - eccLength + (i < shortBlocks ? 0 : 1);
+		const block = data.slice(offset, offset + length);
+		offset += length;
+		const ecc = computeEccCodewords(block, eccLength);
+		// Placeholder keeping columns aligned; skipped when interleaving
+		if (i < shortBlocks) block.push(0);
+		blocks.push([...block, ...ecc]);
+	}
+
+	const result: number[] = [];
+	for (let i = 0; i < blocks[0].length; i++) {
+		for (let j = 0; j < blocks.length; j++) {
+			if (i !== shortBlockLength - eccLength || j >= shortBlocks) {
+				result.push(blocks[j][i]);
+			}
+		}
+	}
+	return result;
+};
+
+// ============================================================================
+// Matrix
+// ============================================================================
+
+/**
+ * Centers of the alignment patterns along each axis.
+ */
+const alignmentPositions = (version: number): number[] => {
+	if (version === 1) return [];
+	const count = Math.floor(version / 7) + 2;
+	const step =
+		version === 32 ? 26 : Math.ceil((version * 4 + 4) / (count * 2 - 2)) * 2;
+	const result = [6];
+	for (let pos = version * 4 + 10; result.length < count; pos -= step) {
+		result.splice(1, 0, pos);
+	}
+	return result;
+};
+
+/**
+ * Inverts a module under the given mask pattern.
+ */
+const MASKS: ReadonlyArray<(x: number, y: number) => boolean> = [
+	(x, y) => (x + y) % 2 === 0,
+	(_x, y) => y % 2 === 0,
+	(x) => x % 3 === 0,
+	(x, y) => (x + y) % 3 === 0,
+	(x, y) => (Math.floor(x / 3) + Math.floor(y / 2)) % 2 === 0,
+	(x, y) => ((x * y) % 2) + ((x * y) % 3) === 0,
+	(x, y) => (((x * y) % 2) + ((x * y) % 3)) % 2 === 0,
+	(x, y) => (((x + y) % 2) + ((x * y) % 3)) % 2 === 0,
+];
+
+/**
+ * Penalty of a finished matrix under the standard's four rules; the mask
+ * with the lowest penalty is used.
+ */
+const scorePenalty = ({ size, modules }: QrMatrix): number => {
+	const at = (x: number, y: number) => modules[y * size + x];
+	let penalty = 0;
+
+	// Rows and columns: runs of five or more, and finder-like patterns
+	const finderLike = [
+		[true, false, true, true, true, false, true, false, false, false, false],
+		[false, false, false, false, true, false, true, true, true, false, true],
+	];
+	for (const read of [at, (y: number, x: number) => at(x, y)]) {
+		for (let y = 0; y < size; y++) {
+			let run = 1;
+			for (let x = 1; x < size; x++) {
+				if (read(x, y) === read(x - 1, y)) {
+					run += 1;
+				} else {
+					if (run >= 5) penalty += run - 2;
+					run = 1;
+				}
+			}
+			if (run >= 5) penalty += run - 2;
+
+			for (let x = 0; x + 11 <= size; x++) {
+				for (const pattern of finderLike) {
+					if (pattern.every((dark, i) => read(x + i, y) === dark)) {
+						penalty += 40;
+					}
+				}
+			}
+		}
+	}
+
+	// 2×2 blocks of one color
+	for (let y = 0; y + 1 < size; y++) {
+		for (let x = 0; x + 1 < size; x++) {
+			const color = at(x, y);
+			if (
+				at(x + 1, y) === color &&
+				at(x, y + 1) === color &&
+				at(x + 1, y + 1) === color
+			) {
+				penalty += 3;
+			}
+		}
+	}
+
+	// Balance of dark and light modules, in 5% steps away from half
+	const dark = modules.filter(Boolean).length;
+	const total = size * size;
+	penalty += (Math.ceil(Math.abs(dark * 20 - total * 10) / total) - 1) * 10;
+	return penalty;
+};
+
+/**
+ * Builds the matrix for data and error correction codewords with one
+ * mask pattern.
+ */
+const buildMatrix = (
+	version: number,
+	codewords: number[],
+	mask: number,
+): QrMatrix => {
+	const size = version * 4 + 17;
+	const modules = new Array<boolean>(size * size).fill(false);
+	const reserved = new Array<boolean>(size * size).fill(false);
+	const set = (x: number, y: number, dark: boolean) => {
+		modules[y * size + x] = dark;
+		reserved[y * size + x] = true;
+	};
+
+	// Timing patterns
+	for (let i = 0; i < size; i++) {
+		set(6, i, i % 2 === 0);
+		set(i, 6, i % 2 === 0);
+	}
+
+	// Finder patterns with their separators
+	for (const [cx, cy] of [
+		[3, 3],
+		[size - 4, 3],
+		[3, size - 4],
+	]) {
+		for (let dy = -4; dy <= 4; dy++) {
+			for (let dx = -4; dx <= 4; dx++) {
+				const x = cx + dx;
+				const y = cy + dy;
+				if (x < 0 || x >= size || y < 0 || y >= size) continue;
+				const ring = Math.max(Math.abs(dx), Math.abs(dy));
+				set(x, y, ring !== 2 && ring !== 4);
+			}
+		}
+	}
+
+	// Alignment patterns, except where they would overlap a finder
+	const positions = alignmentPositions(version);
+	const last = positions.length - 1;
+	positions.forEach((cy, i) => {
+		positions.forEach((cx, j) => {
+			if ((i === 0 && j === 0) || (i === 0 && j === last)) return;
+			if (i === last && j === 0) return;
+			for (let dy = -2; dy <= 2; dy++) {
+				for (let dx = -2; dx <= 2; dx++) {
+					set(cx + dx, cy + dy, Math.max(Math.abs(dx), Math.abs(dy)) !== 1);
+				}
+			}
+		});
+	});
+
+	// Format information: level, mask and a BCH code, in two copies
+	const formatData = (LEVEL_M_BITS << 3) | mask;
+	let remainder = formatData;
+	for (let i = 0; i < 10; i++) {
+		remainder = (remainder << 1) ^ ((remainder >>> 9) * 0x537);
+	}
+	const format = ((formatData << 10) | remainder) ^ 0x5412;
+	const formatBit = (i: number) => ((format >>> i) & 1) !== 0;
+	for (let i = 0; i <= 5; i++) set(8, i, formatBit(i));
+	set(8, 7, formatBit(6));
+	set(8, 8, formatBit(7));
+	set(7, 8, formatBit(8));
+	for (let i = 9; i < 15; i++) set(14 - i, 8, formatBit(i));
+	for (let i = 0; i < 8; i++) set(size - 1 - i, 8, formatBit(i));
+	for (let i = 8; i < 15; i++) set(8, size - 15 + i, formatBit(i));
+	set(8, size - 8, true);
+
+	// Version information, from version 7
+	if (version >= 7) {
+		let rem = version;
+		for (let i = 0; i < 12; i++) rem = (rem << 1) ^ ((rem >>> 11) * 0x1f25);
+		const bits = (version << 12) | rem;
+		for (let i = 0; i < 18; i++) {
+			const dark = ((bits >>> i) & 1) !== 0;
+			const a = size - 11 + (i % 3);
+			const b = Math.floor(i / 3);
+			set(a, b, dark);
+			set(b, a, dark);
+		}
+	}
+
+	// Codewords in a zigzag of two-module columns from the bottom right,
+	// skipping the vertical timing column
+	let bit = 0;
+	for (let right = size - 1; right >= 1; right -= 2) {
+		if (right === 6) right = 5;
+		for (let vert = 0; vert < size; vert++) {
+			for (let j = 0; j < 2; j++) {
+				const x = right - j;
+				const upward = ((right + 1) & 2) === 0;
+				const y = upward ? size - 1 - vert : vert;
+				const index = y * size + x;
+				if (reserved[index] || bit >= codewords.length * 8) continue;
+				modules[index] = ((codewords[bit >>> 3] >>> (7 - (bit & 7))) & 1) !== 0;
+				bit += 1;
+			}
+		}
+	}
+
+	const invert = MASKS[mask];
+	for (let y = 0; y < size; y++) {
+		for (let x = 0; x < size; x++) {
+			const index = y * size + x;
+			if (!reserved[index] && invert(x, y)) modules[index] = !modules[index];
+		}
+	}
+	return { size, modules };
+};
+
+/**
+ * Encodes text as a QR code, picking the smallest version that fits and
+ * the mask with the lowest penalty.
+ * Pure function.
+ * @throws if the text is longer than `MAX_QR_BYTES` in UTF-8
+ */
+export const encodeQr = (text: string): QrMatrix => {
+	const data = Buffer.from(text, "utf-8");
+	const version = pickVersion(data.length);
+	if (version === null) {
+		throw new Error(
+			`Text too long for a QR code: ${data.length} bytes (max: ${MAX_QR_BYTES})`,
+		);
+	}
+
+	const codewords = addEccAndInterleave(
+		buildDataCodewords(data, version),
+		version,
+	);
+	let best: { matrix: QrMatrix; penalty: number } | null = null;
+	for (let mask = 0; mask < MASKS.length; mask++) {
+		const matrix = buildMatrix(version, codewords, mask);
+		const penalty = scorePenalty(matrix);
+		if (!best || penalty < best.penalty) best = { matrix, penalty };
+	}
+	return (best as { matrix: QrMatrix }).matrix;
+};
+
+/**
+ * Draws a QR code as black on white pixels with a quiet zone,
+ * `scale` pixels per module.
+ * Pure function.
+ */
+export const renderQrBitmap = (matrix: QrMatrix, scale: number): QrBitmap => {
+	const width = (matrix.size + QUIET_ZONE_MODULES * 2) * scale;
+	const data = Buffer.alloc(width * width * 4, 0xff);
+	for (let y = 0; y < matrix.size; y++) {
+		for (let x = 0; x < matrix.size; x++) {
+			if (!matrix.modules[y * matrix.size + x]) continue;
+			for (let dy = 0; dy < scale; dy++) {
+				const row = (y + QUIET_ZONE_MODULES) * scale + dy;
+				const start = (row * width + (x + QUIET_ZONE_MODULES) * scale) * 4;
+				for (let i = 0; i < scale; i++) {
+					// Alpha stays opaque
+					data.fill(0, start + i * 4, start + i * 4 + 3);
+				}
+			}
+		}
+	}
+	return { width, height: width, data };
+};
@@ -0,0 +1,165 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createQuickPaste,
+	getQuickPasteAccelerator,
+	getQuickPasteSettingsPath,
+	parseQuickPasteSettings,
+} from "./quick-paste.js";
+import type { ShortcutRegistrar } from "./shortcuts.js";
+
+describe("getQuickPasteAccelerator", () => {
+	it("binds the slot digit with Cmd/Ctrl+Alt", () => {
+		expect(getQuickPasteAccelerator(3)).toBe("CommandOrControl+Alt+3");
+	});
+});
+
+describe("parseQuickPasteSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(
+			parseQuickPasteSettings(
+				{ pasteAfterCopy: true },
+				{ enabled: false, pasteAfterCopy: false },
+			),
+		).toEqual({ enabled: false, pasteAfterCopy: true });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseQuickPasteSettings(null)).toThrow("expected an object");
+		expect(() => parseQuickPasteSettings({ enabled: "yes" })).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parseQuickPasteSettings({ pasteAfterCopy: 1 })).toThrow(
+			"pasteAfterCopy must be a boolean",
+		);
+	});
+});
+
+describe("createQuickPaste", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-quick-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * Fake OS registry; accelerators in `taken` belong to other apps.
+	 */
+	const createRegistrar = (taken: string[] = []) => {
+		const active = new Map<string, () => void>();
+		const registrar: ShortcutRegistrar = {
+			register: (accelerator, callback) => {
+				if (taken.includes(accelerator)) return false;
+				active.set(accelerator, callback);
+				return true;
+			},
+			unregister: (accelerator) => {
+				active.delete(accelerator);
+			},
+		};
+		return { registrar, active };
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("registers nine slots mapped to recent items", () => {
+		const { registrar, active } = createRegistrar();
+		const onSlot = vi.fn();
+		const quickPaste = createQuickPaste({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			onSlot,
+		});
+
+		quickPaste.registerAll();
+		active.get("CommandOrControl+Alt+1")?.();
+		active.get("CommandOrControl+Alt+9")?.();
+
+		expect(active.size).toBe(9);
+		expect(onSlot.mock.calls).toEqual([
+			[0, false],
+			[8, false],
+		]);
+	});
+
+	it("pastes after copying when enabled", () => {
+		const { registrar, active } = createRegistrar();
+		const onSlot = vi.fn();
+		const quickPaste = createQuickPaste({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			onSlot,
+		});
+		quickPaste.registerAll();
+
+		quickPaste.updateSettings({ pasteAfterCopy: true });
+		active.get("CommandOrControl+Alt+2")?.();
+
+		expect(onSlot).toHaveBeenCalledWith(1, true);
+	});
+
+	it("unregisters when disabled and persists the choice", () => {
+		const userDataPath = createTempUserDataPath();
+		const { registrar, active } = createRegistrar();
+		const quickPaste = createQuickPaste({
+			userDataPath,
+			registrar,
+			onSlot: vi.fn(),
+		});
+		quickPaste.registerAll();
+
+		quickPaste.updateSettings({ enabled: false });
+		expect(active.size).toBe(0);
+
+		const restarted = createQuickPaste({
+			userDataPath,
+			registrar,
+			onSlot: vi.fn(),
+		});
+		restarted.registerAll();
+		expect(restarted.getSettings().enabled).toBe(false);
+		expect(active.size).toBe(0);
+	});
+
+	it("skips slots taken by other applications", () => {
+		const { registrar, active } = createRegistrar(["CommandOrControl+Alt+5"]);
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+		const quickPaste = createQuickPaste({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			onSlot: vi.fn(),
+		});
+
+		quickPaste.registerAll();
+
+		expect(active.size).toBe(8);
+		expect(active.has("CommandOrControl+Alt+5")).toBe(false);
+		errorSpy.mockRestore();
+	});
+
+	it("falls back to defaults when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getQuickPasteSettingsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+		const quickPaste = createQuickPaste({
+			userDataPath,
+			registrar: createRegistrar().registrar,
+			onSlot: vi.fn(),
+		});
+
+		expect(quickPaste.getSettings()).toEqual({
+			enabled: true,
+			pasteAfterCopy: false,
+		});
+		errorSpy.mockRestore();
+	});
+});
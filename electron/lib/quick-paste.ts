@@ -0,0 +1,187 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { ShortcutRegistrar } from "./shortcuts.js";
+
+/**
+ * Persisted quick-paste settings.
+ */
+export type QuickPasteSettings = {
+	/** Whether Cmd/Ctrl+Alt+1..9 are registered */
+	enabled: boolean;
+	/** Paste into the focused app after placing the item on the clipboard */
+	pasteAfterCopy: boolean;
+};
+
+const DEFAULT_QUICK_PASTE_SETTINGS: QuickPasteSettings = {
+	enabled: true,
+	pasteAfterCopy: false,
+};
+
+const QUICK_PASTE_FILENAME = "quick-paste.json";
+
+/**
+ * Number of quick-paste slots, one per digit key.
+ */
+const QUICK_PASTE_SLOTS = 9;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Accelerator for a 1-based slot, e.g. `CommandOrControl+Alt+3`.
+ * Pure function.
+ */
+export const getQuickPasteAccelerator = (slot: number): string =>
+	`CommandOrControl+Alt+${slot}`;
+
+/**
+ * Validates a quick-paste settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` or `pasteAfterCopy` is not a boolean
+ */
+export const parseQuickPasteSettings = (
+	input: unknown,
+	current: QuickPasteSettings = DEFAULT_QUICK_PASTE_SETTINGS,
+): QuickPasteSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid quick-paste settings: expected an object");
+	}
+
+	const { enabled, pasteAfterCopy } = input as Partial<
+		Record<keyof QuickPasteSettings, unknown>
+	>;
+	const next: QuickPasteSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error(
+				"Invalid quick-paste settings: enabled must be a boolean",
+			);
+		}
+		next.enabled = enabled;
+	}
+
+	if (pasteAfterCopy !== undefined) {
+		if (typeof pasteAfterCopy !== "boolean") {
+			throw new Error(
+				"Invalid quick-paste settings: pasteAfterCopy must be a boolean",
+			);
+		}
+		next.pasteAfterCopy = pasteAfterCopy;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getQuickPasteSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, QUICK_PASTE_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readQuickPasteSettingsFromFile = (
+	filePath: string,
+): QuickPasteSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_QUICK_PASTE_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseQuickPasteSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read quick-paste settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_QUICK_PASTE_SETTINGS };
+	}
+};
+
+const writeQuickPasteSettingsToFile = (
+	filePath: string,
+	settings: QuickPasteSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Quick-Paste Module
+// ============================================================================
+
+export type QuickPasteDeps = {
+	userDataPath: string;
+	registrar: ShortcutRegistrar;
+	/**
+	 * Places the item at `index` (0 = most recent) on the clipboard, then
+	 * pastes it if `paste` is set.
+	 */
+	onSlot: (index: number, paste: boolean) => void;
+};
+
+/**
+ * Creates the quick-paste hotkeys: Cmd/Ctrl+Alt+N places the Nth most
+ * recent history item on the clipboard. Handled in the main process, so
+ * they work while the window is closed.
+ */
+export const createQuickPaste = (deps: QuickPasteDeps) => {
+	const filePath = getQuickPasteSettingsPath(deps.userDataPath);
+	let settings = readQuickPasteSettingsFromFile(filePath);
+	/** Accelerators currently held with the OS */
+	const registered: string[] = [];
+
+	const getSettings = (): QuickPasteSettings => ({ ...settings });
+
+	/**
+	 * Registers every slot when enabled. A slot taken by another
+	 * application or shortcut is logged and skipped.
+	 */
+	const registerAll = (): void => {
+		if (!settings.enabled || registered.length > 0) return;
+		for (let slot = 1; slot <= QUICK_PASTE_SLOTS; slot++) {
+			const accelerator = getQuickPasteAccelerator(slot);
+			const ok = deps.registrar.register(accelerator, () =>
+				deps.onSlot(slot - 1, settings.pasteAfterCopy),
+			);
+			if (ok) {
+				registered.push(accelerator);
+			} else {
+				console.error(
+					`Quick-paste shortcut registration failed for "${accelerator}".`,
+				);
+			}
+		}
+	};
+
+	/**
+	 * Releases every slot, e.g. before quitting.
+	 */
+	const unregisterAll = (): void => {
+		for (const accelerator of registered.splice(0)) {
+			deps.registrar.unregister(accelerator);
+		}
+	};
+
+	const updateSettings = (input: unknown): QuickPasteSettings => {
+		const next = parseQuickPasteSettings(input, settings);
+		writeQuickPasteSettingsToFile(filePath, next);
+		settings = next;
+		if (settings.enabled) {
+			registerAll();
+		} else {
+			unregisterAll();
+		}
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, registerAll, unregisterAll };
+};
+
+export type QuickPaste = ReturnType<typeof createQuickPaste>;
@@ -0,0 +1,137 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createReadOnlyMode,
+	getReadOnlyPath,
+	guardWrites,
+	parseReadOnlySettings,
+} from "./read-only.js";
+
+describe("parseReadOnlySettings", () => {
+	it("is off by default", () => {
+		expect(parseReadOnlySettings({})).toEqual({ enabled: false });
+		expect(parseReadOnlySettings({ enabled: true })).toEqual({
+			enabled: true,
+		});
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseReadOnlySettings(null)).toThrow("expected an object");
+		expect(() => parseReadOnlySettings({ enabled: 1 })).toThrow(
+			"enabled must be a boolean",
+		);
+	});
+});
+
+describe("guardWrites", () => {
+	it("checks before the named methods and passes others through", () => {
+		const repository = {
+			getItem: vi.fn((id: number) => ({ id })),
+			deleteItem: vi.fn((_id: number) => true),
+		};
+		let writable = true;
+		const guarded = guardWrites(repository, ["deleteItem"], () => {
+			if (!writable) throw new Error("History is read-only");
+		});
+
+		expect(guarded.deleteItem(1)).toBe(true);
+		writable = false;
+		expect(() => guarded.deleteItem(2)).toThrow("History is read-only");
+		expect(repository.deleteItem).toHaveBeenCalledTimes(1);
+		expect(guarded.getItem(3)).toEqual({ id: 3 });
+	});
+});
+
+describe("createReadOnlyMode", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	const allow = () => Promise.resolve();
+
+	it("refuses writes once enabled and reports the change", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "read-only-"));
+		const onChange = vi.fn();
+		const readOnly = createReadOnlyMode({
+			userDataPath,
+			forced: false,
+			authenticate: allow,
+			onChange,
+		});
+
+		expect(() => readOnly.assertWritable()).not.toThrow();
+		await expect(readOnly.updateSettings({ enabled: true })).resolves.toEqual({
+			enabled: true,
+			forced: false,
+			active: true,
+		});
+		expect(() => readOnly.assertWritable()).toThrow("History is read-only");
+		expect(onChange).toHaveBeenCalledTimes(1);
+
+		expect(
+			createReadOnlyMode({
+				userDataPath,
+				forced: false,
+				authenticate: allow,
+			}).isActive(),
+		).toBe(true);
+		expect(fs.existsSync(getReadOnlyPath(userDataPath))).toBe(true);
+	});
+
+	it("asks for authentication before turning off", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "read-only-"));
+		const authenticate = vi.fn(() =>
+			Promise.reject(new Error("Authentication cancelled")),
+		);
+		const readOnly = createReadOnlyMode({
+			userDataPath,
+			forced: false,
+			authenticate,
+		});
+
+		await readOnly.updateSettings({ enabled: true });
+		expect(authenticate).not.toHaveBeenCalled();
+		await expect(readOnly.updateSettings({ enabled: false })).rejects.toThrow(
+			"Authentication cancelled",
+		);
+		expect(authenticate).toHaveBeenCalledWith("turn off read-only mode");
+		expect(readOnly.isActive()).toBe(true);
+	});
+
+	it("cannot be turned on without OS authentication", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "read-only-"));
+		const readOnly = createReadOnlyMode({
+			userDataPath,
+			forced: false,
+			authenticate: null,
+		});
+
+		await expect(readOnly.updateSettings({ enabled: true })).rejects.toThrow(
+			"OS authentication is not available",
+		);
+		expect(readOnly.isActive()).toBe(false);
+	});
+
+	it("stays on for the run when started with --read-only", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "read-only-"));
+		const onChange = vi.fn();
+		const readOnly = createReadOnlyMode({
+			userDataPath,
+			forced: true,
+			authenticate: allow,
+			onChange,
+		});
+
+		await expect(readOnly.updateSettings({ enabled: false })).resolves.toEqual({
+			enabled: false,
+			forced: true,
+			active: true,
+		});
+		expect(readOnly.isActive()).toBe(true);
+		expect(onChange).not.toHaveBeenCalled();
+	});
+});
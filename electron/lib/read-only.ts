@@ -0,0 +1,179 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { Authenticator } from "./os-auth.js";
+
+/**
+ * Persisted read-only mode settings.
+ */
+export type ReadOnlySettings = {
+	enabled: boolean;
+};
+
+/**
+ * Read-only mode state reported to the renderer.
+ */
+export type ReadOnlyStatus = ReadOnlySettings & {
+	/** Started with `--read-only`, which keeps the mode on until a restart */
+	forced: boolean;
+	/** Whether history is read-only now */
+	active: boolean;
+};
+
+const DEFAULT_READ_ONLY_SETTINGS: ReadOnlySettings = {
+	enabled: false,
+};
+
+const READ_ONLY_FILENAME = "read-only.json";
+
+const READ_ONLY_MESSAGE = "History is read-only";
+
+const TURN_OFF_REASON = "turn off read-only mode";
+
+const AUTH_UNAVAILABLE_MESSAGE =
+	"OS authentication is not available on this system";
+
+const invalid = (message: string) =>
+	new Error(`Invalid read-only settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a read-only settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean
+ */
+export const parseReadOnlySettings = (
+	input: unknown,
+	current: ReadOnlySettings = DEFAULT_READ_ONLY_SETTINGS,
+): ReadOnlySettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled } = input as Partial<Record<keyof ReadOnlySettings, unknown>>;
+	const next: ReadOnlySettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	return next;
+};
+
+/**
+ * Wraps the named methods of a repository so each first calls `assert`,
+ * which throws to refuse the write. Other methods are passed through.
+ * Pure function: the repository itself is left as it is.
+ */
+export const guardWrites = <T extends object>(
+	target: T,
+	methods: readonly (keyof T)[],
+	assert: () => void,
+): T => {
+	const guarded = { ...target };
+	for (const name of methods) {
+		const method = target[name];
+		if (typeof method !== "function") continue;
+		guarded[name] = ((...args: unknown[]) => {
+			assert();
+			return method(...args);
+		}) as T[keyof T];
+	}
+	return guarded;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getReadOnlyPath = (userDataPath: string) =>
+	path.join(userDataPath, READ_ONLY_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readReadOnlyFromFile = (filePath: string): ReadOnlySettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_READ_ONLY_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseReadOnlySettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read read-only settings, using defaults:", error);
+		return { ...DEFAULT_READ_ONLY_SETTINGS };
+	}
+};
+
+const writeReadOnlyToFile = (filePath: string, settings: ReadOnlySettings) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Read-Only Module
+// ============================================================================
+
+export type ReadOnlyDeps = {
+	userDataPath: string;
+	/** Whether the app was started with `--read-only` */
+	forced: boolean;
+	/** OS authentication prompt, or null if the platform has none */
+	authenticate: Authenticator | null;
+	/** Called whenever the mode turns on or off */
+	onChange?: (status: ReadOnlyStatus) => void;
+};
+
+/**
+ * Creates the read-only (guest) mode: while active, capture is off and
+ * history can be browsed, copied and pasted but not added to, edited or
+ * deleted from. On with the saved setting or for the whole run with
+ * `--read-only`. Turning the setting off asks for OS authentication, so a
+ * guest cannot simply switch it off, and it can only be turned on where
+ * that prompt exists.
+ */
+export const createReadOnlyMode = (deps: ReadOnlyDeps) => {
+	const filePath = getReadOnlyPath(deps.userDataPath);
+	let settings = readReadOnlyFromFile(filePath);
+
+	const isActive = (): boolean => deps.forced || settings.enabled;
+
+	const getStatus = (): ReadOnlyStatus => ({
+		...settings,
+		forced: deps.forced,
+		active: isActive(),
+	});
+
+	/**
+	 * @throws "History is read-only" while the mode is on
+	 */
+	const assertWritable = (): void => {
+		if (isActive()) throw new Error(READ_ONLY_MESSAGE);
+	};
+
+	const updateSettings = async (input: unknown): Promise<ReadOnlyStatus> => {
+		const wasActive = isActive();
+		const next = parseReadOnlySettings(input, settings);
+		if (next.enabled !== settings.enabled) {
+			if (!deps.authenticate) {
+				throw new Error(AUTH_UNAVAILABLE_MESSAGE);
+			}
+			if (!next.enabled) await deps.authenticate(TURN_OFF_REASON);
+		}
+		writeReadOnlyToFile(filePath, next);
+		settings = next;
+		if (isActive() !== wasActive) deps.onChange?.(getStatus());
+		return getStatus();
+	};
+
+	return { isActive, getStatus, assertWritable, updateSettings };
+};
+
+export type ReadOnlyMode = ReturnType<typeof createReadOnlyMode>;
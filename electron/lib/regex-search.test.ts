@@ -0,0 +1,72 @@
+import { describe, expect, it } from "vitest";
+import {
+	createRegexMatcher,
+	createRegexRangeMatcher,
+	validateSearchPattern,
+} from "./regex-search.js";
+
+describe("validateSearchPattern", () => {
+	it("accepts valid patterns", () => {
+		expect(() => validateSearchPattern("\\d+\\.\\d+")).not.toThrow();
+	});
+
+	it("rejects empty, invalid, and oversized patterns", () => {
+		expect(() => validateSearchPattern("")).toThrow("requires a pattern");
+		expect(() => validateSearchPattern("(unclosed")).toThrow(
+			"Invalid regular expression",
+		);
+		expect(() => validateSearchPattern("a".repeat(1001))).toThrow(
+			"too long",
+		);
+	});
+});
+
+describe("createRegexMatcher", () => {
+	it("matches case-insensitively across batches", () => {
+		const match = createRegexMatcher({
+			pattern: "\\b\\d{1,3}(\\.\\d{1,3}){3}\\b",
+		});
+
+		expect(match(["server at 10.0.0.1", "no address"])).toEqual([true, false]);
+		expect(match(["ping 192.168.1.20"])).toEqual([true]);
+		expect(createRegexMatcher({ pattern: "token" })(["API TOKEN"])).toEqual([
+			true,
+		]);
+	});
+
+	it("aborts catastrophic backtracking", () => {
+		const match = createRegexMatcher({ pattern: "(a+)+$", timeoutMs: 50 });
+
+		expect(() => match([`${"a".repeat(40)}!`])).toThrow(
+			"Regex search timed out",
+		);
+	});
+
+	it("stops once the shared budget is spent", () => {
+		let time = 0;
+		const match = createRegexMatcher({
+			pattern: "a",
+			timeoutMs: 100,
+			now: () => time,
+		});
+
+		expect(match(["a"])).toEqual([true]);
+		time = 100;
+		expect(() => match(["a"])).toThrow("Regex search timed out");
+	});
+});
+
+describe("createRegexRangeMatcher", () => {
+	it("reports every match, or null when there is none", () => {
+		const match = createRegexRangeMatcher({ pattern: "\\d+" });
+
+		expect(match(["call 555 or 0800", "none"])).toEqual([
+			[
+				[5, 8],
+				[12, 16],
+			],
+			null,
+		]);
+		expect(createRegexRangeMatcher({ pattern: "x*" })(["abc"])).toEqual([[]]);
+	});
+});
@@ -0,0 +1,150 @@
+import vm from "node:vm";
+import { MAX_MATCH_RANGES, type MatchRange } from "./match-ranges.js";
+
+/**
+ * Maximum accepted pattern length.
+ */
+export const MAX_REGEX_PATTERN_LENGTH = 1000;
+
+/**
+ * Total time a single regex search may spend matching (ms).
+ * Catastrophic patterns such as `(a+)+$` are interrupted once it is spent.
+ */
+export const REGEX_SEARCH_TIMEOUT_MS = 1000;
+
+/**
+ * Only the start of long items is matched, mirroring fuzzy search.
+ */
+const MAX_REGEX_TEXT_LENGTH = 100_000;
+
+/**
+ * Matching runs inside a vm script so V8 can interrupt it on timeout;
+ * a regex running on the main thread cannot otherwise be stopped.
+ */
+const MATCH_SCRIPT = new vm.Script(
+	"(() => { const re = new RegExp(pattern, flags); return texts.map((text) => re.test(text)); })()",
+);
+
+/**
+ * Like `MATCH_SCRIPT`, but collects where each text matched: up to
+ * `limit` ranges, or null if it did not match. Empty matches count as a
+ * match but add no range.
+ */
+const RANGES_SCRIPT = new vm.Script(
+	'(() => { const re = new RegExp(pattern, flags.includes("g") ? flags : flags + "g"); return texts.map((text) => { const ranges = []; let matched = false; let match; re.lastIndex = 0; while (ranges.length < limit && (match = re.exec(text)) !== null) { matched = true; if (match[0].length === 0) { re.lastIndex += 1; continue; } ranges.push([match.index, match.index + match[0].length]); } return matched ? ranges : null; }); })()',
+);
+
+export type RegexMatcherOptions = {
+	pattern: string;
+	/** Regular expression flags; case-insensitive by default */
+	flags?: string;
+	/** Total matching budget across all batches (ms) */
+	timeoutMs?: number;
+	/** Clock used to track the budget; injectable for tests */
+	now?: () => number;
+};
+
+/**
+ * Validates and compiles a search pattern without running it.
+ * Matching is case-insensitive.
+ *
+ * @throws if the pattern is too long or not a valid regular expression
+ */
+export const validateSearchPattern = (pattern: string): void => {
+	if (typeof pattern !== "string" || pattern.length === 0) {
+		throw new Error("Regex search requires a pattern");
+	}
+	if (pattern.length > MAX_REGEX_PATTERN_LENGTH) {
+		throw new Error(
+			`Regex pattern too long: ${pattern.length} chars (max: ${MAX_REGEX_PATTERN_LENGTH})`,
+		);
+	}
+	try {
+		new RegExp(pattern, "i");
+	} catch (error) {
+		throw new Error(
+			`Invalid regular expression: ${error instanceof Error ? error.message : String(error)}`,
+		);
+	}
+};
+
+/**
+ * Runs a matching script over batches of text within one time budget.
+ *
+ * @returns A function running the script on texts; it throws "Regex
+ *   search timed out" once the budget is exhausted
+ */
+const createScriptRunner = (
+	script: vm.Script,
+	options: RegexMatcherOptions,
+) => {
+	const { pattern, flags = "i", timeoutMs = REGEX_SEARCH_TIMEOUT_MS } = options;
+	const now = options.now ?? Date.now;
+	validateSearchPattern(pattern);
+
+	const context = vm.createContext({
+		pattern,
+		flags,
+		limit: MAX_MATCH_RANGES,
+		texts: [],
+	});
+	const deadline = now() + timeoutMs;
+
+	return (texts: readonly string[]): unknown[] => {
+		const remaining = deadline - now();
+		if (remaining <= 0) {
+			throw new Error("Regex search timed out");
+		}
+
+		context.texts = texts.map((text) => text.slice(0, MAX_REGEX_TEXT_LENGTH));
+		try {
+			const results = script.runInContext(context, {
+				timeout: Math.ceil(remaining),
+			}) as ArrayLike<unknown>;
+			return Array.from(results);
+		} catch (error) {
+			if (
+				error instanceof Error &&
+				"code" in error &&
+				error.code === "ERR_SCRIPT_EXECUTION_TIMEOUT"
+			) {
+				throw new Error("Regex search timed out");
+			}
+			throw error;
+		} finally {
+			context.texts = [];
+		}
+	};
+};
+
+/**
+ * Creates a matcher that tests batches of text against a pattern while
+ * enforcing a shared time budget.
+ *
+ * @returns A function mapping texts to whether each matches; it throws
+ *   "Regex search timed out" once the budget is exhausted
+ */
+export const createRegexMatcher = (options: RegexMatcherOptions) => {
+	const run = createScriptRunner(MATCH_SCRIPT, options);
+	return (texts: readonly string[]): boolean[] => run(texts) as boolean[];
+};
+
+export type RegexMatcher = ReturnType<typeof createRegexMatcher>;
+
+/**
+ * Creates a matcher like `createRegexMatcher` that also reports where
+ * each text matched.
+ *
+ * @returns A function mapping texts to their match ranges, or null for
+ *   those that do not match
+ */
+export const createRegexRangeMatcher = (options: RegexMatcherOptions) => {
+	const run = createScriptRunner(RANGES_SCRIPT, options);
+	return (texts: readonly string[]): Array<MatchRange[] | null> =>
+		(run(texts) as Array<ArrayLike<ArrayLike<number>> | null>).map(
+			(ranges) =>
+				ranges === null
+					? null
+					: Array.from(ranges, (range): MatchRange => [range[0], range[1]]),
+		);
+};
@@ -0,0 +1,68 @@
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createRegisterPrompt } from "./register-prompt.js";
+import type { ShortcutRegistrar } from "./shortcuts.js";
+
+describe("createRegisterPrompt", () => {
+	const createRegistrar = (taken: string[] = []) => {
+		const active = new Map<string, () => void>();
+		const registrar: ShortcutRegistrar = {
+			register: vi.fn((accelerator: string, callback: () => void) => {
+				if (taken.includes(accelerator)) return false;
+				active.set(accelerator, callback);
+				return true;
+			}),
+			unregister: vi.fn((accelerator: string) => {
+				active.delete(accelerator);
+			}),
+		};
+		return { registrar, active };
+	};
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+	});
+
+	it("selects the register of the next key and releases the keys", () => {
+		const { registrar, active } = createRegistrar();
+		const onSelect = vi.fn();
+		const prompt = createRegisterPrompt({ registrar });
+
+		expect(prompt.arm(onSelect)).toBe(true);
+		expect(active.size).toBe(36);
+		active.get("Q")?.();
+
+		expect(onSelect).toHaveBeenCalledWith("q");
+		expect(active.size).toBe(0);
+	});
+
+	it("releases the keys when no register is chosen in time", () => {
+		const { registrar, active } = createRegistrar();
+		const onSelect = vi.fn();
+		createRegisterPrompt({ registrar, timeoutMs: 500 }).arm(onSelect);
+
+		vi.advanceTimersByTime(500);
+
+		expect(active.size).toBe(0);
+		expect(onSelect).not.toHaveBeenCalled();
+	});
+
+	it("skips keys taken by another application", () => {
+		const { registrar, active } = createRegistrar(["A"]);
+		createRegisterPrompt({ registrar }).arm(vi.fn());
+
+		expect(active.has("A")).toBe(false);
+		expect(active.has("B")).toBe(true);
+	});
+
+	it("fails when no key can be grabbed", () => {
+		const { registrar } = createRegistrar([
+			..."ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789",
+		]);
+
+		expect(createRegisterPrompt({ registrar }).arm(vi.fn())).toBe(false);
+	});
+});
@@ -0,0 +1,64 @@
+import { REGISTER_NAMES } from "./register-repository.js";
+import type { ShortcutRegistrar } from "./shortcuts.js";
+
+/**
+ * How long the prompt waits for a register key before giving up (ms).
+ */
+const REGISTER_PROMPT_TIMEOUT_MS = 2000;
+
+export type RegisterPromptDeps = {
+	registrar: ShortcutRegistrar;
+	timeoutMs?: number;
+};
+
+/**
+ * Creates the register prompt behind the register hotkeys, like Vim's `"a`:
+ * after the copy or paste hotkey, the next letter or digit key names the
+ * register. Those keys are only grabbed from the OS until one is pressed or
+ * the prompt times out, so normal typing is not affected.
+ */
+export const createRegisterPrompt = (deps: RegisterPromptDeps) => {
+	const timeoutMs = deps.timeoutMs ?? REGISTER_PROMPT_TIMEOUT_MS;
+	/** Accelerators currently held with the OS */
+	const registered: string[] = [];
+	let timer: ReturnType<typeof setTimeout> | null = null;
+
+	/**
+	 * Releases the register keys without selecting a register.
+	 */
+	const cancel = (): void => {
+		if (timer) {
+			clearTimeout(timer);
+			timer = null;
+		}
+		for (const accelerator of registered.splice(0)) {
+			deps.registrar.unregister(accelerator);
+		}
+	};
+
+	/**
+	 * Waits for a register key and passes its register name to `onSelect`.
+	 * Arming again replaces a pending prompt. Keys taken by another
+	 * application are skipped.
+	 *
+	 * @returns false if no register key could be grabbed
+	 */
+	const arm = (onSelect: (name: string) => void): boolean => {
+		cancel();
+		for (const name of REGISTER_NAMES) {
+			const accelerator = name.toUpperCase();
+			const ok = deps.registrar.register(accelerator, () => {
+				cancel();
+				onSelect(name);
+			});
+			if (ok) registered.push(accelerator);
+		}
+		if (registered.length === 0) return false;
+		timer = setTimeout(cancel, timeoutMs);
+		return true;
+	};
+
+	return { arm, cancel };
+};
+
+export type RegisterPrompt = ReturnType<typeof createRegisterPrompt>;
@@ -0,0 +1,18 @@
+import { describe, expect, it } from "vitest";
+import { normalizeRegisterName } from "./register-repository.js";
+
+describe("normalizeRegisterName", () => {
+	it("accepts letters in either case and digits", () => {
+		expect(normalizeRegisterName("a")).toBe("a");
+		expect(normalizeRegisterName("Z")).toBe("z");
+		expect(normalizeRegisterName("7")).toBe("7");
+	});
+
+	it("rejects anything else", () => {
+		for (const name of ["", "ab", "-", " a", 1, null]) {
+			expect(() => normalizeRegisterName(name)).toThrow(
+				"Invalid register name",
+			);
+		}
+	});
+});
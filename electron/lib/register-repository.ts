@@ -0,0 +1,114 @@
+import type Database from "better-sqlite3";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Represents a row from the registers table.
+ */
+export type RegisterRow = {
+	name: string;
+	content: string;
+	rtf: string | null;
+	html: string | null;
+	updated_at: string;
+};
+
+/**
+ * Clipboard contents saved into a register.
+ */
+export type RegisterContent = {
+	text: string;
+	rtf?: string;
+	html?: string;
+};
+
+/**
+ * Register names, in the order the listing and register hotkeys use.
+ */
+export const REGISTER_NAMES: readonly string[] = [
+	..."abcdefghijklmnopqrstuvwxyz",
+	..."0123456789",
+];
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Normalizes a register name: a single letter (case-insensitive) or digit.
+ * Pure function.
+ *
+ * @throws if the value is not a register name
+ */
+export const normalizeRegisterName = (value: unknown): string => {
+	const name = typeof value === "string" ? value.toLowerCase() : "";
+	if (!REGISTER_NAMES.includes(name)) {
+		throw new Error(
+			`Invalid register name: ${String(value)} (expected a-z or 0-9)`,
+		);
+	}
+	return name;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the register repository.
+ * Owns every SQL statement against the `registers` table. Registers hold a
+ * copy of what was saved, so deleting or pruning history leaves them intact.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createRegisterRepository = (getDb: () => Database.Database) => {
+	/**
+	 * Lists the filled registers by name, letters before digits.
+	 */
+	const listRegisters = (): RegisterRow[] => {
+		const rows = getDb()
+			.prepare("SELECT name, content, rtf, html, updated_at FROM registers")
+			.all() as RegisterRow[];
+		return rows.sort(
+			(a, b) =>
+				REGISTER_NAMES.indexOf(a.name) - REGISTER_NAMES.indexOf(b.name),
+		);
+	};
+
+	const getRegister = (name: string): RegisterRow | undefined =>
+		getDb()
+			.prepare(
+				"SELECT name, content, rtf, html, updated_at FROM registers WHERE name = ?",
+			)
+			.get(normalizeRegisterName(name)) as RegisterRow | undefined;
+
+	/**
+	 * Saves content into a register, replacing what it held.
+	 *
+	 * @throws if the name is invalid or the content has no text
+	 */
+	const setRegister = (name: string, content: RegisterContent): RegisterRow => {
+		const validName = normalizeRegisterName(name);
+		if (!content.text) {
+			throw new Error("Nothing to save: the clipboard has no text");
+		}
+		getDb()
+			.prepare(
+				"INSERT INTO registers (name, content, rtf, html) VALUES (?, ?, ?, ?) ON CONFLICT(name) DO UPDATE SET content = excluded.content, rtf = excluded.rtf, html = excluded.html, updated_at = datetime('now')",
+			)
+			.run(validName, content.text, content.rtf ?? null, content.html ?? null);
+		return getRegister(validName) as RegisterRow;
+	};
+
+	const deleteRegister = (name: string): void => {
+		getDb()
+			.prepare("DELETE FROM registers WHERE name = ?")
+			.run(normalizeRegisterName(name));
+	};
+
+	return { listRegisters, getRegister, setRegister, deleteRegister };
+};
+
+export type RegisterRepository = ReturnType<typeof createRegisterRepository>;
@@ -0,0 +1,369 @@
+import crypto from "node:crypto";
+
+/**
+ * Storage the remote sync changelog lives in. Object names are relative to
+ * the configured folder and use `/` between directories.
+ */
+export type RemoteStore = {
+	/** @returns null if the object does not exist */
+	get: (name: string) => Promise<Buffer | null>;
+	put: (name: string, body: Buffer) => Promise<void>;
+	/** Names of the objects directly inside `directory` */
+	list: (directory: string) => Promise<string[]>;
+};
+
+export type WebDavStoreOptions = {
+	/** Collection URL, e.g. a Nextcloud `remote.php/dav/files/<user>/` */
+	url: string;
+	/** Folder inside the collection, created on first upload */
+	prefix: string;
+	username: string;
+	password: string;
+};
+
+export type S3Credentials = {
+	accessKeyId: string;
+	secretAccessKey: string;
+	region: string;
+};
+
+export type S3StoreOptions = S3Credentials & {
+	/** Service endpoint, e.g. `https://s3.eu-west-1.amazonaws.com` */
+	endpoint: string;
+	bucket: string;
+	/** Key prefix objects are stored under */
+	prefix: string;
+};
+
+/**
+ * How long to wait for the server before giving up on a request.
+ */
+const REQUEST_TIMEOUT_MS = 30_000;
+
+/**
+ * SHA-256 of an empty body, sent with S3 requests that have none.
+ */
+const EMPTY_PAYLOAD_HASH = crypto.createHash("sha256").digest("hex");
+
+const PROPFIND_BODY =
+	'<?xml version="1.0" encoding="utf-8"?><propfind xmlns="DAV:"><prop><resourcetype/></prop></propfind>';
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const XML_ENTITIES: Record<string, string> = {
+	amp: "&",
+	lt: "<",
+	gt: ">",
+	quot: '"',
+	apos: "'",
+};
+
+const decodeXmlText = (text: string): string =>
+	text.replace(
+		/&(amp|lt|gt|quot|apos);/g,
+		(_match, name: string) => XML_ENTITIES[name],
+	);
+
+/**
+ * Percent-encodes a query component the way SigV4 canonicalizes it.
+ */
+const encodeRfc3986 = (value: string): string =>
+	encodeURIComponent(value).replace(
+		/[!'()*]/g,
+		(char) => `%${char.charCodeAt(0).toString(16).toUpperCase()}`,
+	);
+
+const sha256Hex = (data: string | Buffer): string =>
+	crypto.createHash("sha256").update(data).digest("hex");
+
+const compare = (a: string, b: string): number =>
+	a < b ? -1 : a > b ? 1 : 0;
+
+const hmac = (key: string | Buffer, data: string): Buffer =>
+	crypto.createHmac("sha256", key).update(data).digest();
+
+/**
+ * Signs an S3 request with AWS Signature Version 4.
+ * Pure function.
+ *
+ * @returns the request headers plus `x-amz-date`, `x-amz-content-sha256`
+ *   and `authorization`, all lower-case
+ */
+export const signS3Request = (
+	request: {
+		method: string;
+		url: URL;
+		headers?: Record<string, string>;
+		/** Hex SHA-256 of the body */
+		payloadHash: string;
+	},
+	credentials: S3Credentials,
+	date: Date,
+): Record<string, string> => {
+	const amzDate = date.toISOString().replace(/[-:]|\.\d{3}/g, "");
+	const day = amzDate.slice(0, 8);
+	const headers: Record<string, string> = {
+		host: request.url.host,
+		"x-amz-content-sha256": request.payloadHash,
+		"x-amz-date": amzDate,
+	};
+	for (const [name, value] of Object.entries(request.headers ?? {})) {
+		headers[name.toLowerCase()] = value.trim();
+	}
+
+	const headerNames = Object.keys(headers).sort();
+	const signedHeaders = headerNames.join(";");
+	const query = [...request.url.searchParams]
+		.map(([name, value]) => [encodeRfc3986(name), encodeRfc3986(value)])
+		.sort(([a, x], [b, y]) => (a === b ? compare(x, y) : compare(a, b)))
+		.map(([name, value]) => `${name}=${value}`)
+		.join("&");
+	const canonicalRequest = [
+		request.method,
+		request.url.pathname,
+		query,
+		headerNames.map((name) => `${name}:${headers[name]}\n`).join(""),
+		signedHeaders,
+		request.payloadHash,
+	].join("\n");
+
+	const scope = `${day}/${credentials.region}/s3/aws4_request`;
+	const stringToSign = [
+		"AWS4-HMAC-SHA256",
+		amzDate,
+		scope,
+		sha256Hex(canonicalRequest),
+	].join("\n");
+	const dayKey = hmac(`AWS4${credentials.secretAccessKey}`, day);
+	const regionKey = hmac(dayKey, credentials.region);
+	const signingKey = hmac(hmac(regionKey, "s3"), "aws4_request");
+	const signature = crypto
+		.createHmac("sha256", signingKey)
+		.update(stringToSign)
+		.digest("hex");
+
+	return {
+		...headers,
+		authorization: `AWS4-HMAC-SHA256 Credential=${credentials.accessKeyId}/${scope}, SignedHeaders=${signedHeaders}, Signature=${signature}`,
+	};
+};
+
+/**
+ * Reads the keys and continuation token from a ListObjectsV2 response.
+ * Pure function.
+ */
+export const parseS3ListResponse = (
+	xml: string,
+): { keys: string[]; nextToken: string | null } => {
+	const keys = [...xml.matchAll(/<Key>([^<]*)<\/Key>/g)].map((match) =>
+		decodeXmlText(match[1]),
+	);
+	const truncated = /<IsTruncated>\s*true\s*<\/IsTruncated>/.test(xml);
+	const token = xml.match(
+		/<NextContinuationToken>([^<]*)<\/NextContinuationToken>/,
+	);
+	return {
+		keys,
+		nextToken: truncated && token ? decodeXmlText(token[1]) : null,
+	};
+};
+
+/**
+ * Reads the names of a collection's members from a `Depth: 1` PROPFIND
+ * response, skipping the collection itself and sub-collections.
+ * Pure function. Hrefs may be absolute URLs or paths, with any namespace
+ * prefix on the element.
+ */
+export const parseWebDavListing = (
+	xml: string,
+	collectionPath: string,
+): string[] => {
+	const own = decodeURIComponent(collectionPath).replace(/\/+$/, "");
+	const pattern = /<(?:[\w-]+:)?href[^>]*>([^<]*)<\/(?:[\w-]+:)?href>/g;
+	return [...xml.matchAll(pattern)].flatMap((match) => {
+		const href = decodeXmlText(match[1]).trim();
+		const pathname = decodeURIComponent(
+			/^[a-z]+:\/\//i.test(href) ? new URL(href).pathname : href,
+		);
+		if (pathname.endsWith("/") || pathname === own) return [];
+		const name = pathname.slice(pathname.lastIndexOf("/") + 1);
+		return name ? [name] : [];
+	});
+};
+
+/**
+ * Joins URL path pieces with single slashes, encoding each segment.
+ * Pure function.
+ */
+export const joinUrlPath = (base: string, ...parts: string[]): string => {
+	const segments = parts
+		.flatMap((part) => part.split("/"))
+		.filter(Boolean)
+		.map(encodeURIComponent);
+	return [base.replace(/\/+$/, ""), ...segments].join("/");
+};
+
+// ============================================================================
+// Backends
+// ============================================================================
+
+const send = (url: string, init: RequestInit): Promise<Response> =>
+	fetch(url, { ...init, signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS) });
+
+const assertOk = (response: Response, action: string): void => {
+	if (!response.ok) {
+		throw new Error(`Failed to ${action}: HTTP ${response.status}`);
+	}
+};
+
+/**
+ * WebDAV backend, e.g. Nextcloud, ownCloud or a NAS.
+ */
+export const createWebDavStore = (options: WebDavStoreOptions): RemoteStore => {
+	const authorization = `Basic ${Buffer.from(
+		`${options.username}:${options.password}`,
+	).toString("base64")}`;
+	const urlFor = (name: string) =>
+		joinUrlPath(options.url, options.prefix, name);
+
+	/**
+	 * Creates the folder and each parent of `name` inside the collection.
+	 * MKCOL answers 405 for a collection that already exists.
+	 */
+	const createCollections = async (name: string): Promise<void> => {
+		const parts = [
+			...options.prefix.split("/"),
+			...name.split("/").slice(0, -1),
+		].filter(Boolean);
+		for (let depth = 1; depth <= parts.length; depth++) {
+			const url = `${joinUrlPath(options.url, ...parts.slice(0, depth))}/`;
+			const response = await send(url, {
+				method: "MKCOL",
+				headers: { authorization },
+			});
+			if (!response.ok && response.status !== 405) {
+				assertOk(response, `create folder ${parts[depth - 1]}`);
+			}
+		}
+	};
+
+	const get = async (name: string): Promise<Buffer | null> => {
+		const response = await send(urlFor(name), { headers: { authorization } });
+		if (response.status === 404) return null;
+		assertOk(response, `download ${name}`);
+		return Buffer.from(await response.arrayBuffer());
+	};
+
+	const put = async (name: string, body: Buffer): Promise<void> => {
+		const upload = () =>
+			send(urlFor(name), {
+				method: "PUT",
+				headers: {
+					authorization,
+					"content-type": "application/octet-stream",
+				},
+				body: new Uint8Array(body),
+			});
+		let response = await upload();
+		// Missing parent collections are reported as 409 Conflict
+		if (response.status === 409 || response.status === 404) {
+			await createCollections(name);
+			response = await upload();
+		}
+		assertOk(response, `upload ${name}`);
+	};
+
+	const list = async (directory: string): Promise<string[]> => {
+		const url = `${urlFor(directory)}/`;
+		const response = await send(url, {
+			method: "PROPFIND",
+			headers: {
+				authorization,
+				depth: "1",
+				"content-type": "application/xml",
+			},
+			body: PROPFIND_BODY,
+		});
+		if (response.status === 404) return [];
+		assertOk(response, `list ${directory}`);
+		return parseWebDavListing(await response.text(), new URL(url).pathname);
+	};
+
+	return { get, put, list };
+};
+
+/**
+ * S3-compatible backend (AWS, MinIO, Cloudflare R2, Backblaze B2, ...),
+ * addressed path-style as `<endpoint>/<bucket>/<key>`.
+ */
+export const createS3Store = (options: S3StoreOptions): RemoteStore => {
+	const keyFor = (name: string) =>
+		[...options.prefix.split("/"), ...name.split("/")]
+			.filter(Boolean)
+			.join("/");
+
+	const signedFetch = (
+		method: string,
+		url: URL,
+		body?: Buffer,
+	): Promise<Response> => {
+		const headers = signS3Request(
+			{
+				method,
+				url,
+				payloadHash: body ? sha256Hex(body) : EMPTY_PAYLOAD_HASH,
+			},
+			options,
+			new Date(),
+		);
+		// fetch sets Host itself from the URL
+		const sent = Object.entries(headers).filter(([name]) => name !== "host");
+		return send(url.toString(), {
+			method,
+			headers: Object.fromEntries(sent),
+			body: body ? new Uint8Array(body) : undefined,
+		});
+	};
+
+	const objectUrl = (name: string) =>
+		new URL(joinUrlPath(options.endpoint, options.bucket, keyFor(name)));
+
+	const get = async (name: string): Promise<Buffer | null> => {
+		const response = await signedFetch("GET", objectUrl(name));
+		if (response.status === 404) return null;
+		assertOk(response, `download ${name}`);
+		return Buffer.from(await response.arrayBuffer());
+	};
+
+	const put = async (name: string, body: Buffer): Promise<void> => {
+		const response = await signedFetch("PUT", objectUrl(name), body);
+		assertOk(response, `upload ${name}`);
+	};
+
+	const list = async (directory: string): Promise<string[]> => {
+		const prefix = `${keyFor(directory)}/`;
+		const names: string[] = [];
+		let token: string | null = null;
+		do {
+			const url = new URL(joinUrlPath(options.endpoint, options.bucket));
+			url.searchParams.set("list-type", "2");
+			url.searchParams.set("prefix", prefix);
+			if (token) url.searchParams.set("continuation-token", token);
+			const response = await signedFetch("GET", url);
+			assertOk(response, `list ${directory}`);
+			const page = parseS3ListResponse(await response.text());
+			for (const key of page.keys) {
+				const name = key.slice(prefix.length);
+				if (key.startsWith(prefix) && name && !name.includes("/")) {
+					names.push(name);
+				}
+			}
+			token = page.nextToken;
+		} while (token);
+		return names;
+	};
+
+	return { get, put, list };
+};
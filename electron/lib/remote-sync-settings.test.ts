@@ -0,0 +1,177 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createRemoteSyncSettings,
+	getRemoteSyncSettingsPath,
+	isDifferentRemote,
+	parseAppliedSegments,
+	parseRemoteSyncSettings,
+	type RemoteSyncSettings,
+} from "./remote-sync-settings.js";
+
+const current: RemoteSyncSettings = {
+	enabled: false,
+	backend: "webdav",
+	url: "https://cloud.example.com/dav/",
+	bucket: "",
+	region: "",
+	prefix: "clipman",
+	username: "me",
+	password: "secret",
+	passphrase: "correct horse",
+	intervalMinutes: 5,
+	deviceId: "0123456789abcdef",
+	lastSentId: 10,
+	lastSentTombstoneId: 4,
+	applied: { fedcba9876543210: 3 },
+};
+
+describe("parseRemoteSyncSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(parseRemoteSyncSettings({}, current)).toEqual(current);
+		expect(
+			parseRemoteSyncSettings(
+				{ enabled: true, prefix: " /sync/clips/ ", intervalMinutes: 15 },
+				current,
+			),
+		).toEqual({
+			...current,
+			enabled: true,
+			prefix: "sync/clips",
+			intervalMinutes: 15,
+		});
+	});
+
+	it("ignores attempts to change sync progress", () => {
+		const input = { deviceId: "ffffffffffffffff", lastSentId: 0, applied: {} };
+		expect(parseRemoteSyncSettings(input, current)).toEqual(current);
+	});
+
+	it("rejects invalid values", () => {
+		const cases: Array<[unknown, string]> = [
+			[null, "expected an object"],
+			[{ enabled: "yes" }, "enabled must be a boolean"],
+			[{ backend: "ftp" }, "backend must be webdav or s3"],
+			[{ url: "ftp://host" }, "url must be an http or https URL"],
+			[{ bucket: 1 }, "bucket must be a string"],
+			[{ passphrase: "short" }, "passphrase must be at least 8 characters"],
+			[{ intervalMinutes: 0 }, "intervalMinutes must be an integer from 1"],
+		];
+		for (const [input, message] of cases) {
+			expect(() => parseRemoteSyncSettings(input, current)).toThrow(message);
+		}
+	});
+
+	it("requires a complete remote to enable sync", () => {
+		expect(() =>
+			parseRemoteSyncSettings({ enabled: true, url: "" }, current),
+		).toThrow("url and passphrase are required");
+		expect(() =>
+			parseRemoteSyncSettings({ enabled: true, backend: "s3" }, current),
+		).toThrow("bucket and region are required for S3");
+	});
+});
+
+describe("isDifferentRemote", () => {
+	it("compares the settings naming the remote", () => {
+		expect(isDifferentRemote(current, { ...current, enabled: true })).toBe(
+			false,
+		);
+		expect(isDifferentRemote(current, { ...current, password: "new" })).toBe(
+			false,
+		);
+		expect(isDifferentRemote(current, { ...current, prefix: "other" })).toBe(
+			true,
+		);
+		expect(
+			isDifferentRemote(current, { ...current, passphrase: "another one" }),
+		).toBe(true);
+	});
+});
+
+describe("parseAppliedSegments", () => {
+	it("drops malformed entries", () => {
+		expect(
+			parseAppliedSegments({
+				fedcba9876543210: 3,
+				"not-a-device": 1,
+				"0123456789abcdef": -1,
+			}),
+		).toEqual({ fedcba9876543210: 3 });
+		expect(parseAppliedSegments(null)).toEqual({});
+	});
+});
+
+describe("createRemoteSyncSettings", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-remote-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("is off by default with a saved random device id", () => {
+		const userDataPath = createTempUserDataPath();
+		const settings = createRemoteSyncSettings({ userDataPath }).getSettings();
+
+		expect(settings).toMatchObject({
+			enabled: false,
+			backend: "webdav",
+			prefix: "clipman",
+			lastSentId: null,
+			lastSentTombstoneId: null,
+			applied: {},
+		});
+		expect(settings.deviceId).toMatch(/^[0-9a-f]{16}$/);
+		expect(createRemoteSyncSettings({ userDataPath }).getSettings()).toEqual(
+			settings,
+		);
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"keeps the settings file private to the user",
+		() => {
+			const userDataPath = createTempUserDataPath();
+			createRemoteSyncSettings({ userDataPath });
+
+			const { mode } = fs.statSync(getRemoteSyncSettingsPath(userDataPath));
+			expect(mode & 0o777).toBe(0o600);
+		},
+	);
+
+	it("saves progress and forgets it when the remote changes", () => {
+		const userDataPath = createTempUserDataPath();
+		const store = createRemoteSyncSettings({ userDataPath });
+		const reload = () =>
+			createRemoteSyncSettings({ userDataPath }).getSettings();
+		store.updateSettings({
+			url: "https://cloud.example.com/dav/",
+			passphrase: "correct horse",
+		});
+		store.setProgress({ lastSentId: 42, lastSentTombstoneId: 3 });
+		store.setApplied("fedcba9876543210", 7);
+
+		store.updateSettings({ password: "rotated", intervalMinutes: 10 });
+		expect(reload()).toMatchObject({
+			lastSentId: 42,
+			lastSentTombstoneId: 3,
+			applied: { fedcba9876543210: 7 },
+		});
+
+		store.updateSettings({ prefix: "elsewhere" });
+		expect(reload()).toMatchObject({
+			lastSentId: null,
+			lastSentTombstoneId: null,
+			applied: {},
+		});
+	});
+});
@@ -0,0 +1,326 @@
+import fs from "node:fs";
+import path from "node:path";
+import { generateDeviceId, isValidDeviceId } from "./sync-protocol.js";
+
+const REMOTE_SYNC_BACKENDS = ["webdav", "s3"] as const;
+
+export type RemoteSyncBackend = (typeof REMOTE_SYNC_BACKENDS)[number];
+
+/**
+ * Persisted settings and progress for remote sync.
+ */
+export type RemoteSyncSettings = {
+	/** Upload and download the changelog; off until the user opts in */
+	enabled: boolean;
+	backend: RemoteSyncBackend;
+	/** WebDAV collection URL, or the S3 endpoint */
+	url: string;
+	/** S3 bucket; unused for WebDAV */
+	bucket: string;
+	/** S3 region; unused for WebDAV */
+	region: string;
+	/** Folder (WebDAV) or key prefix (S3) the changelog is kept under */
+	prefix: string;
+	/** WebDAV user name, or S3 access key id */
+	username: string;
+	/** WebDAV password, or S3 secret access key */
+	password: string;
+	/** Encrypts everything uploaded; every device uses the same one */
+	passphrase: string;
+	intervalMinutes: number;
+	/** Random id naming this device's changelog segments */
+	deviceId: string;
+	/** Newest local history id already uploaded; null until first enabled */
+	lastSentId: number | null;
+	/** Newest local deletion already uploaded; null until first enabled */
+	lastSentTombstoneId: number | null;
+	/** Newest segment applied from each other device */
+	applied: Record<string, number>;
+};
+
+const MIN_PASSPHRASE_LENGTH = 8;
+const MIN_INTERVAL_MINUTES = 1;
+const MAX_INTERVAL_MINUTES = 24 * 60;
+
+const REMOTE_SYNC_FILENAME = "remote-sync.json";
+
+/**
+ * Settings naming the remote; changing one starts over with a new one.
+ */
+const REMOTE_KEYS: ReadonlyArray<keyof RemoteSyncSettings> = [
+	"backend",
+	"url",
+	"bucket",
+	"prefix",
+	"passphrase",
+];
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid remote sync settings: ${message}`);
+
+/**
+ * Validates a remote sync settings update. Progress (`deviceId`, what was
+ * uploaded and what was applied) cannot be changed this way.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value is invalid, or if sync would be enabled without a URL,
+ *   passphrase or (for S3) bucket
+ */
+export const parseRemoteSyncSettings = (
+	input: unknown,
+	current: RemoteSyncSettings,
+): RemoteSyncSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const values = input as Partial<Record<keyof RemoteSyncSettings, unknown>>;
+	const next: RemoteSyncSettings = { ...current };
+
+	if (values.enabled !== undefined) {
+		if (typeof values.enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = values.enabled;
+	}
+
+	if (values.backend !== undefined) {
+		if (!REMOTE_SYNC_BACKENDS.includes(values.backend as RemoteSyncBackend)) {
+			throw invalid(`backend must be ${REMOTE_SYNC_BACKENDS.join(" or ")}`);
+		}
+		next.backend = values.backend as RemoteSyncBackend;
+	}
+
+	if (values.url !== undefined) {
+		const url = typeof values.url === "string" ? values.url.trim() : null;
+		if (url === null || (url && !/^https?:\/\/[^/]/i.test(url))) {
+			throw invalid("url must be an http or https URL");
+		}
+		next.url = url;
+	}
+
+	for (const key of ["bucket", "region", "username", "password"] as const) {
+		const value = values[key];
+		if (value === undefined) continue;
+		if (typeof value !== "string") {
+			throw invalid(`${key} must be a string`);
+		}
+		next[key] = key === "password" ? value : value.trim();
+	}
+
+	if (values.prefix !== undefined) {
+		if (typeof values.prefix !== "string") {
+			throw invalid("prefix must be a string");
+		}
+		next.prefix = values.prefix.trim().replace(/^\/+|\/+$/g, "");
+	}
+
+	if (values.passphrase !== undefined) {
+		if (
+			typeof values.passphrase !== "string" ||
+			values.passphrase.length < MIN_PASSPHRASE_LENGTH
+		) {
+			throw invalid(
+				`passphrase must be at least ${MIN_PASSPHRASE_LENGTH} characters`,
+			);
+		}
+		next.passphrase = values.passphrase;
+	}
+
+	if (values.intervalMinutes !== undefined) {
+		const minutes = values.intervalMinutes;
+		if (
+			typeof minutes !== "number" ||
+			!Number.isInteger(minutes) ||
+			minutes < MIN_INTERVAL_MINUTES ||
+			minutes > MAX_INTERVAL_MINUTES
+		) {
+			throw invalid(
+				`intervalMinutes must be an integer from ${MIN_INTERVAL_MINUTES} to ${MAX_INTERVAL_MINUTES}`,
+			);
+		}
+		next.intervalMinutes = minutes;
+	}
+
+	if (next.enabled) {
+		if (!next.url || !next.passphrase) {
+			throw invalid("url and passphrase are required to enable sync");
+		}
+		if (next.backend === "s3" && (!next.bucket || !next.region)) {
+			throw invalid("bucket and region are required for S3");
+		}
+	}
+
+	return next;
+};
+
+/**
+ * Whether two settings point at different remotes, so progress recorded
+ * against one means nothing for the other.
+ * Pure function.
+ */
+export const isDifferentRemote = (
+	a: RemoteSyncSettings,
+	b: RemoteSyncSettings,
+): boolean => REMOTE_KEYS.some((key) => a[key] !== b[key]);
+
+const parseProgressId = (value: unknown): number | null =>
+	typeof value === "number" && Number.isInteger(value) && value >= 0
+		? value
+		: null;
+
+/**
+ * Reads stored per-device progress, dropping malformed entries.
+ * Pure function.
+ */
+export const parseAppliedSegments = (
+	input: unknown,
+): Record<string, number> => {
+	if (typeof input !== "object" || input === null) return {};
+	return Object.fromEntries(
+		Object.entries(input).filter(
+			([deviceId, segment]) =>
+				isValidDeviceId(deviceId) &&
+				Number.isInteger(segment) &&
+				(segment as number) > 0,
+		),
+	);
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getRemoteSyncSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, REMOTE_SYNC_FILENAME);
+
+const writeRemoteSyncSettingsToFile = (
+	filePath: string,
+	settings: RemoteSyncSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ * A device id is generated and saved when none is stored yet.
+ */
+const readRemoteSyncSettingsFromFile = (
+	filePath: string,
+): RemoteSyncSettings => {
+	const defaults: RemoteSyncSettings = {
+		enabled: false,
+		backend: "webdav",
+		url: "",
+		bucket: "",
+		region: "",
+		prefix: "clipman",
+		username: "",
+		password: "",
+		passphrase: "",
+		intervalMinutes: 5,
+		deviceId: "",
+		lastSentId: null,
+		lastSentTombstoneId: null,
+		applied: {},
+	};
+
+	let settings = defaults;
+	if (fs.existsSync(filePath)) {
+		try {
+			const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+			settings = {
+				...parseRemoteSyncSettings(raw, defaults),
+				deviceId: isValidDeviceId(raw.deviceId) ? raw.deviceId : "",
+				lastSentId: parseProgressId(raw.lastSentId),
+				lastSentTombstoneId: parseProgressId(raw.lastSentTombstoneId),
+				applied: parseAppliedSegments(raw.applied),
+			};
+		} catch (error) {
+			console.error(
+				"Failed to read remote sync settings, using defaults:",
+				error,
+			);
+		}
+	}
+
+	if (!settings.deviceId) {
+		settings = { ...settings, deviceId: generateDeviceId() };
+		writeRemoteSyncSettingsToFile(filePath, settings);
+	}
+	return settings;
+};
+
+// ============================================================================
+// Remote Sync Settings Module
+// ============================================================================
+
+/**
+ * Creates the remote sync settings store. The file is readable by the
+ * current user only, since it holds the credentials and passphrase.
+ */
+export const createRemoteSyncSettings = (deps: { userDataPath: string }) => {
+	const filePath = getRemoteSyncSettingsPath(deps.userDataPath);
+	let settings = readRemoteSyncSettingsFromFile(filePath);
+
+	const getSettings = (): RemoteSyncSettings => ({
+		...settings,
+		applied: { ...settings.applied },
+	});
+
+	const save = (next: RemoteSyncSettings): RemoteSyncSettings => {
+		writeRemoteSyncSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Applies a settings update. Pointing at a different remote forgets
+	 * what was uploaded to and applied from the previous one.
+	 */
+	const updateSettings = (input: unknown): RemoteSyncSettings => {
+		const next = parseRemoteSyncSettings(input, settings);
+		if (isDifferentRemote(settings, next)) {
+			return save({
+				...next,
+				lastSentId: null,
+				lastSentTombstoneId: null,
+				applied: {},
+			});
+		}
+		return save(next);
+	};
+
+	/**
+	 * Records how far history and deletions have been uploaded.
+	 */
+	const setProgress = (
+		progress: Partial<
+			Pick<RemoteSyncSettings, "lastSentId" | "lastSentTombstoneId">
+		>,
+	): void => {
+		save({ ...settings, ...progress });
+	};
+
+	/**
+	 * Records the newest segment applied from another device.
+	 */
+	const setApplied = (deviceId: string, segment: number): void => {
+		const applied = { ...settings.applied, [deviceId]: segment };
+		save({ ...settings, applied });
+	};
+
+	return { getSettings, updateSettings, setProgress, setApplied };
+};
+
+export type RemoteSyncSettingsStore = ReturnType<
+	typeof createRemoteSyncSettings
+>;
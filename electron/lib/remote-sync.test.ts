@@ -0,0 +1,278 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import type { RemoteStore } from "./remote-store.js";
+import {
+	createRemoteSync,
+	decryptObject,
+	deriveRemoteKey,
+	encryptObject,
+	formatSegmentName,
+	KEY_FILE,
+	parseSegment,
+	parseSegmentName,
+	planSegments,
+} from "./remote-sync.js";
+import { createRemoteSyncSettings } from "./remote-sync-settings.js";
+import type { SyncHandlers } from "./sync.js";
+import type { SyncItem, SyncTombstone } from "./sync-protocol.js";
+
+const laptop = "0123456789abcdef";
+const desktop = "fedcba9876543210";
+
+const item = (content: string): SyncItem => ({
+	content,
+	rtf: null,
+	html: null,
+	created_at: "2024-01-01 00:00:00",
+});
+
+describe("encryptObject", () => {
+	const key = deriveRemoteKey("correct horse", Buffer.alloc(16, 1));
+	const plaintext = Buffer.from("hello");
+
+	it("round-trips under the same name", () => {
+		const data = encryptObject(key, "log/a.bin", plaintext);
+		expect(data.includes(plaintext)).toBe(false);
+		expect(decryptObject(key, "log/a.bin", data)).toEqual(plaintext);
+	});
+
+	it("rejects a wrong key, a renamed object or changed data", () => {
+		const data = encryptObject(key, "log/a.bin", plaintext);
+		const otherKey = deriveRemoteKey("wrong horse", Buffer.alloc(16, 1));
+		const changed = Buffer.from(data);
+		changed[changed.length - 1] ^= 1;
+
+		const message = "wrong passphrase or corrupted data";
+		expect(() => decryptObject(otherKey, "log/a.bin", data)).toThrow(message);
+		expect(() => decryptObject(key, "log/b.bin", data)).toThrow(message);
+		expect(() => decryptObject(key, "log/a.bin", changed)).toThrow(message);
+		expect(() => decryptObject(key, "log/a.bin", plaintext)).toThrow(
+			"not a sync object",
+		);
+	});
+});
+
+describe("segment names", () => {
+	it("round-trip and reject other files", () => {
+		const name = formatSegmentName(laptop, 12);
+		expect(name).toBe("0123456789abcdef-0000000012.bin");
+		expect(parseSegmentName(name)).toEqual({ deviceId: laptop, segment: 12 });
+		expect(parseSegmentName("0123456789abcdef-0000000000.bin")).toBeNull();
+		expect(parseSegmentName("notes.txt")).toBeNull();
+	});
+});
+
+describe("planSegments", () => {
+	it("lists unapplied segments in order and numbers the next upload", () => {
+		const names = [
+			formatSegmentName(desktop, 3),
+			formatSegmentName(laptop, 1),
+			formatSegmentName(desktop, 1),
+			formatSegmentName(desktop, 2),
+			formatSegmentName(laptop, 2),
+			"readme.txt",
+		];
+
+		expect(planSegments(names, laptop, { [desktop]: 1 })).toEqual({
+			pending: [
+				{ deviceId: desktop, segment: 2, name: `log/${names[3]}` },
+				{ deviceId: desktop, segment: 3, name: `log/${names[0]}` },
+			],
+			nextSegment: 3,
+		});
+		expect(planSegments([], laptop, {}).nextSegment).toBe(1);
+	});
+});
+
+describe("parseSegment", () => {
+	const tombstone: SyncTombstone = {
+		hash: "a".repeat(64),
+		deleted_at: "2024-01-02 00:00:00",
+	};
+	const encode = (value: unknown) => Buffer.from(JSON.stringify(value));
+
+	it("validates items and deletions", () => {
+		expect(
+			parseSegment(encode({ items: [item("a")], deletions: [tombstone] })),
+		).toEqual({ items: [item("a")], tombstones: [tombstone] });
+		expect(() => parseSegment(Buffer.from("{"))).toThrow("expected JSON");
+		expect(() => parseSegment(Buffer.from("{}"))).toThrow("at most");
+		expect(() =>
+			parseSegment(encode({ items: [], deletions: [{ hash: "x" }] })),
+		).toThrow();
+	});
+
+	it("reads segments written without deletions", () => {
+		expect(parseSegment(encode({ items: [item("a")] }))).toEqual({
+			items: [item("a")],
+			tombstones: [],
+		});
+	});
+});
+
+describe("createRemoteSync", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createMemoryStore = () => {
+		const objects = new Map<string, Buffer>();
+		return {
+			objects,
+			get: async (name: string) => objects.get(name) ?? null,
+			put: async (name: string, body: Buffer) => {
+				objects.set(name, body);
+			},
+			list: async (directory: string) =>
+				[...objects.keys()]
+					.filter((name) => name.startsWith(`${directory}/`))
+					.map((name) => name.slice(directory.length + 1)),
+		};
+	};
+
+	/**
+	 * A device with an in-memory history syncing through `store`.
+	 */
+	const createDevice = (store: RemoteStore, passphrase = "correct horse") => {
+		const userDataPath = fs.mkdtempSync(
+			path.join(os.tmpdir(), "clipboard-remote-sync-"),
+		);
+		tempDirs.push(userDataPath);
+		const history: Array<SyncItem & { id: number; from: string | null }> = [];
+		const tombstones: Array<SyncTombstone & { id: number }> = [];
+		let nextId = 1;
+		const add = (content: string, from: string | null = null) => {
+			history.push({ ...item(content), id: nextId++, from });
+		};
+		// Content stands in for the hash; every item has the same timestamp
+		const remove = (content: string) => {
+			history.splice(
+				history.findIndex((entry) => entry.content === content),
+				1,
+			);
+			tombstones.push({
+				hash: content,
+				deleted_at: "2024-01-01 00:00:00",
+				id: tombstones.length + 1,
+			});
+		};
+		const handlers: SyncHandlers = {
+			listItemsAfter: (afterId, limit) =>
+				history
+					.filter((entry) => entry.id > afterId && entry.from === null)
+					.slice(0, limit),
+			getLastItemId: () => nextId - 1,
+			receiveItems: (items, deviceId) => {
+				for (const received of items) add(received.content, deviceId);
+				return items.length;
+			},
+			listTombstonesAfter: (afterId, limit) =>
+				tombstones.filter((entry) => entry.id > afterId).slice(0, limit),
+			getLastTombstoneId: () => tombstones.length,
+			receiveTombstones: (received) => {
+				const hashes = new Set(received.map((entry) => entry.hash));
+				const before = history.length;
+				history.splice(
+					0,
+					history.length,
+					...history.filter((entry) => !hashes.has(entry.content)),
+				);
+				return before - history.length;
+			},
+		};
+
+		const settings = createRemoteSyncSettings({ userDataPath });
+		settings.updateSettings({
+			enabled: true,
+			url: "https://cloud.example.com/dav/",
+			passphrase,
+		});
+		const onSynced = vi.fn();
+		const sync = createRemoteSync({
+			settings,
+			handlers,
+			isLocked: () => false,
+			openStore: () => store,
+			onSynced,
+		});
+		return { sync, add, remove, history, settings, onSynced };
+	};
+
+	it("exchanges new items through the encrypted changelog", async () => {
+		const store = createMemoryStore();
+		const a = createDevice(store);
+		const b = createDevice(store);
+		a.add("before sync was enabled");
+		await a.sync.syncNow();
+		await b.sync.syncNow();
+
+		a.add("from a");
+		b.add("from b");
+		await a.sync.syncNow();
+		await b.sync.syncNow();
+		await a.sync.syncNow();
+
+		expect(a.history.map((entry) => entry.content)).toEqual([
+			"before sync was enabled",
+			"from a",
+			"from b",
+		]);
+		expect(b.history.map((entry) => entry.content)).toEqual([
+			"from b",
+			"from a",
+		]);
+		expect(b.sync.getStatus()).toMatchObject({ lastError: null });
+		expect(b.onSynced.mock.calls).toEqual([[1]]);
+		expect(store.objects.has(KEY_FILE)).toBe(true);
+		for (const data of store.objects.values()) {
+			expect(data.includes(Buffer.from("from a"))).toBe(false);
+		}
+	});
+
+	it("deletes on other devices what was deleted on one", async () => {
+		const store = createMemoryStore();
+		const a = createDevice(store);
+		const b = createDevice(store);
+		await a.sync.syncNow();
+		await b.sync.syncNow();
+
+		a.add("kept");
+		a.add("deleted");
+		await a.sync.syncNow();
+		await b.sync.syncNow();
+		a.remove("deleted");
+		await a.sync.syncNow();
+		await b.sync.syncNow();
+
+		expect(b.history.map((entry) => entry.content)).toEqual(["kept"]);
+	});
+
+	it("reports a wrong passphrase without touching the folder", async () => {
+		const store = createMemoryStore();
+		await createDevice(store).sync.syncNow();
+
+		const b = createDevice(store, "another passphrase");
+		b.add("y");
+		await b.sync.syncNow();
+
+		expect(b.sync.getStatus()).toMatchObject({
+			lastSyncAt: null,
+			lastError: "Wrong passphrase for this sync folder",
+		});
+		expect([...store.objects.keys()]).toEqual([KEY_FILE]);
+	});
+
+	it("hides credentials from the status", () => {
+		const { sync } = createDevice(createMemoryStore());
+		const status = sync.getStatus();
+
+		expect(status).toMatchObject({ hasPassphrase: true, hasPassword: false });
+		expect(JSON.stringify(status)).not.toContain("correct horse");
+	});
+});
@@ -0,0 +1,542 @@
+import crypto from "node:crypto";
+import {
+	createS3Store,
+	createWebDavStore,
+	type RemoteStore,
+} from "./remote-store.js";
+import type {
+	RemoteSyncBackend,
+	RemoteSyncSettings,
+	RemoteSyncSettingsStore,
+} from "./remote-sync-settings.js";
+import type { SyncHandlers } from "./sync.js";
+import {
+	parseSyncItem,
+	parseSyncTombstone,
+	SYNC_TOMBSTONE_BATCH_SIZE,
+	type SyncItem,
+	type SyncTombstone,
+} from "./sync-protocol.js";
+
+export type RemoteSyncDeps = {
+	settings: RemoteSyncSettingsStore;
+	handlers: SyncHandlers;
+	/** Nothing is uploaded or applied while the app is locked */
+	isLocked: () => boolean;
+	/** Opens the configured storage; injectable for tests */
+	openStore?: (settings: RemoteSyncSettings) => RemoteStore;
+	/** Called after a sync that added items from other devices */
+	onSynced?: (received: number) => void;
+};
+
+/**
+ * Remote sync settings without secrets, plus what sync is doing.
+ */
+export type RemoteSyncStatus = {
+	enabled: boolean;
+	backend: RemoteSyncBackend;
+	url: string;
+	bucket: string;
+	region: string;
+	prefix: string;
+	username: string;
+	hasPassword: boolean;
+	hasPassphrase: boolean;
+	intervalMinutes: number;
+	deviceId: string;
+	/** A sync is in progress */
+	syncing: boolean;
+	lastSyncAt: string | null;
+	/** Why the last sync failed, or null if it succeeded */
+	lastError: string | null;
+};
+
+/**
+ * Object in the sync folder holding the key derivation salt, plus a
+ * value encrypted with the key so a wrong passphrase is caught up front.
+ */
+export const KEY_FILE = "clipman-sync.json";
+
+/**
+ * Directory holding every device's changelog segments.
+ */
+const LOG_DIRECTORY = "log";
+
+/**
+ * Items per changelog segment; each also carries up to
+ * `SYNC_TOMBSTONE_BATCH_SIZE` deletions.
+ */
+const SEGMENT_SIZE = 100;
+
+/**
+ * Start of every encrypted object: format name and version.
+ */
+const OBJECT_MAGIC = Buffer.from("CMS1", "latin1");
+const IV_BYTES = 12;
+const TAG_BYTES = 16;
+const KEY_BYTES = 32;
+const SALT_BYTES = 16;
+
+/**
+ * scrypt cost for the passphrase: the key file is readable by whoever
+ * can read the bucket, so each offline guess should be expensive.
+ */
+const SCRYPT_OPTIONS = { N: 2 ** 15, r: 8, p: 1, maxmem: 64 * 1024 * 1024 };
+
+const KEY_CHECK = "clipman-sync";
+
+/**
+ * Delay between a capture and uploading it; batches quick copies and
+ * leaves time to mark an item local-only.
+ */
+const PUSH_DELAY_MS = 30_000;
+
+/**
+ * A changelog segment another device uploaded.
+ */
+export type SegmentRef = { deviceId: string; segment: number; name: string };
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Derives the folder key from the passphrase and the folder's salt.
+ */
+export const deriveRemoteKey = (passphrase: string, salt: Buffer): Buffer =>
+	crypto.scryptSync(passphrase, salt, KEY_BYTES, SCRYPT_OPTIONS);
+
+/**
+ * Encrypts an object with AES-256-GCM. The object's name is authenticated
+ * too, so the server cannot swap one segment for another.
+ */
+export const encryptObject = (
+	key: Buffer,
+	name: string,
+	plaintext: Buffer,
+): Buffer => {
+	const iv = crypto.randomBytes(IV_BYTES);
+	const cipher = crypto.createCipheriv("aes-256-gcm", key, iv);
+	cipher.setAAD(Buffer.from(name, "utf-8"));
+	const ciphertext = Buffer.concat([cipher.update(plaintext), cipher.final()]);
+	return Buffer.concat([OBJECT_MAGIC, iv, cipher.getAuthTag(), ciphertext]);
+};
+
+/**
+ * Decrypts an object written by `encryptObject` under the same name.
+ *
+ * @throws if the key is wrong or the data was changed
+ */
+export const decryptObject = (
+	key: Buffer,
+	name: string,
+	data: Buffer,
+): Buffer => {
+	const headerLength = OBJECT_MAGIC.length + IV_BYTES + TAG_BYTES;
+	if (
+		data.length < headerLength ||
+		!data.subarray(0, OBJECT_MAGIC.length).equals(OBJECT_MAGIC)
+	) {
+		throw new Error(`Failed to decrypt ${name}: not a sync object`);
+	}
+	const iv = data.subarray(OBJECT_MAGIC.length, OBJECT_MAGIC.length + IV_BYTES);
+	const tag = data.subarray(OBJECT_MAGIC.length + IV_BYTES, headerLength);
+	try {
+		const decipher = crypto.createDecipheriv("aes-256-gcm", key, iv);
+		decipher.setAAD(Buffer.from(name, "utf-8"));
+		decipher.setAuthTag(tag);
+		return Buffer.concat([
+			decipher.update(data.subarray(headerLength)),
+			decipher.final(),
+		]);
+	} catch {
+		throw new Error(
+			`Failed to decrypt ${name}: wrong passphrase or corrupted data`,
+		);
+	}
+};
+
+/**
+ * File name of a device's changelog segment; zero-padded so names sort.
+ * Pure function.
+ */
+export const formatSegmentName = (deviceId: string, segment: number): string =>
+	`${deviceId}-${String(segment).padStart(10, "0")}.bin`;
+
+/**
+ * Reads the device and number from a segment file name.
+ * Pure function.
+ *
+ * @returns null for names that are not segments
+ */
+export const parseSegmentName = (
+	name: string,
+): { deviceId: string; segment: number } | null => {
+	const match = name.match(/^([0-9a-f]{16})-(\d{10})\.bin$/);
+	if (!match) return null;
+	const segment = Number(match[2]);
+	return segment > 0 ? { deviceId: match[1], segment } : null;
+};
+
+/**
+ * Works out which segments in the log directory still need applying,
+ * oldest first per device, and the number this device uploads next.
+ * Pure function.
+ */
+export const planSegments = (
+	names: string[],
+	ownDeviceId: string,
+	applied: Record<string, number>,
+): { pending: SegmentRef[]; nextSegment: number } => {
+	const pending: SegmentRef[] = [];
+	let lastOwn = 0;
+	for (const name of names) {
+		const parsed = parseSegmentName(name);
+		if (!parsed) continue;
+		if (parsed.deviceId === ownDeviceId) {
+			lastOwn = Math.max(lastOwn, parsed.segment);
+		} else if (parsed.segment > (applied[parsed.deviceId] ?? 0)) {
+			pending.push({ ...parsed, name: `${LOG_DIRECTORY}/${name}` });
+		}
+	}
+	pending.sort((a, b) =>
+		a.deviceId === b.deviceId
+			? a.segment - b.segment
+			: a.deviceId.localeCompare(b.deviceId),
+	);
+	return { pending, nextSegment: lastOwn + 1 };
+};
+
+/**
+ * Reads a decrypted segment. Segments written before deletions were
+ * synced have no `deletions`.
+ * Pure function.
+ *
+ * @throws if the segment is malformed
+ */
+export const parseSegment = (
+	plaintext: Buffer,
+): { items: SyncItem[]; tombstones: SyncTombstone[] } => {
+	let input: unknown;
+	try {
+		input = JSON.parse(plaintext.toString("utf-8"));
+	} catch {
+		throw new Error("Invalid segment: expected JSON");
+	}
+	const { items, deletions = [] } =
+		(input as { items?: unknown; deletions?: unknown } | null) ?? {};
+	if (!Array.isArray(items) || items.length > SEGMENT_SIZE) {
+		throw new Error(`Invalid segment: expected at most ${SEGMENT_SIZE} items`);
+	}
+	if (
+		!Array.isArray(deletions) ||
+		deletions.length > SYNC_TOMBSTONE_BATCH_SIZE
+	) {
+		throw new Error(
+			`Invalid segment: expected at most ${SYNC_TOMBSTONE_BATCH_SIZE} deletions`,
+		);
+	}
+	return {
+		items: items.map(parseSyncItem),
+		tombstones: deletions.map(parseSyncTombstone),
+	};
+};
+
+/**
+ * Reads the salt and check value from the folder's key file.
+ * Pure function.
+ *
+ * @throws if the file is malformed
+ */
+export const parseKeyFile = (data: Buffer): { salt: Buffer; check: Buffer } => {
+	try {
+		const { version, salt, check } = JSON.parse(data.toString("utf-8"));
+		if (
+			version === 1 &&
+			typeof salt === "string" &&
+			typeof check === "string"
+		) {
+			return {
+				salt: Buffer.from(salt, "base64"),
+				check: Buffer.from(check, "base64"),
+			};
+		}
+	} catch {
+		// Reported below
+	}
+	throw new Error(`Invalid ${KEY_FILE} in the sync folder`);
+};
+
+// ============================================================================
+// Remote Sync
+// ============================================================================
+
+const openConfiguredStore = (settings: RemoteSyncSettings): RemoteStore =>
+	settings.backend === "s3"
+		? createS3Store({
+				endpoint: settings.url,
+				bucket: settings.bucket,
+				region: settings.region,
+				prefix: settings.prefix,
+				accessKeyId: settings.username,
+				secretAccessKey: settings.password,
+			})
+		: createWebDavStore({
+				url: settings.url,
+				prefix: settings.prefix,
+				username: settings.username,
+				password: settings.password,
+			});
+
+/**
+ * Creates remote sync: every device appends encrypted changelog segments
+ * of its own captures and deletions to a shared WebDAV folder or S3
+ * prefix, and applies the segments other devices wrote. Everything is
+ * encrypted here with a key derived from the passphrase; the server only
+ * sees ciphertext and segment names.
+ */
+export const createRemoteSync = (deps: RemoteSyncDeps) => {
+	const openStore = deps.openStore ?? openConfiguredStore;
+	let intervalTimer: NodeJS.Timeout | null = null;
+	let pushTimer: NodeJS.Timeout | null = null;
+	let running: Promise<void> | null = null;
+	let runAgain = false;
+	let lastSyncAt: string | null = null;
+	let lastError: string | null = null;
+	/** Derived key for the passphrase and salt last used */
+	let cachedKey: { id: string; key: Buffer } | null = null;
+
+	const deriveCached = (passphrase: string, salt: Buffer): Buffer => {
+		const id = `${salt.toString("hex")}:${passphrase}`;
+		if (cachedKey?.id !== id) {
+			cachedKey = { id, key: deriveRemoteKey(passphrase, salt) };
+		}
+		return cachedKey.key;
+	};
+
+	/**
+	 * Marks the current history and deletions as already uploaded, so a
+	 * newly enabled remote only receives changes made from now on.
+	 */
+	const startFromNow = () => {
+		const progress = {
+			lastSentId: deps.handlers.getLastItemId(),
+			lastSentTombstoneId: deps.handlers.getLastTombstoneId(),
+		};
+		deps.settings.setProgress(progress);
+		return progress;
+	};
+
+	/**
+	 * Derives the folder key, creating the key file in an empty folder.
+	 *
+	 * @throws if the passphrase does not match the folder's
+	 */
+	const unlockFolder = async (
+		store: RemoteStore,
+		passphrase: string,
+	): Promise<Buffer> => {
+		const file = await store.get(KEY_FILE);
+		if (!file) {
+			const salt = crypto.randomBytes(SALT_BYTES);
+			const key = deriveCached(passphrase, salt);
+			const check = encryptObject(key, KEY_FILE, Buffer.from(KEY_CHECK));
+			const contents = {
+				version: 1,
+				salt: salt.toString("base64"),
+				check: check.toString("base64"),
+			};
+			await store.put(KEY_FILE, Buffer.from(JSON.stringify(contents)));
+			return key;
+		}
+
+		const { salt, check } = parseKeyFile(file);
+		const key = deriveCached(passphrase, salt);
+		try {
+			decryptObject(key, KEY_FILE, check);
+		} catch {
+			throw new Error("Wrong passphrase for this sync folder");
+		}
+		return key;
+	};
+
+	/**
+	 * Applies other devices' new segments, then uploads local items and
+	 * deletions not uploaded yet. A segment's items are applied before its
+	 * deletions, so an item copied and then deleted stays deleted.
+	 *
+	 * @returns how many items from other devices were added
+	 */
+	const syncOnce = async (): Promise<number> => {
+		const settings = deps.settings.getSettings();
+		if (!settings.enabled || deps.isLocked()) return 0;
+
+		const store = openStore(settings);
+		const key = await unlockFolder(store, settings.passphrase);
+		const plan = planSegments(
+			await store.list(LOG_DIRECTORY),
+			settings.deviceId,
+			settings.applied,
+		);
+
+		let received = 0;
+		for (const ref of plan.pending) {
+			if (deps.isLocked()) return received;
+			const data = await store.get(ref.name);
+			if (!data) continue;
+			const segment = parseSegment(decryptObject(key, ref.name, data));
+			received += deps.handlers.receiveItems(segment.items, ref.deviceId);
+			deps.handlers.receiveTombstones(segment.tombstones, ref.deviceId);
+			deps.settings.setApplied(ref.deviceId, ref.segment);
+		}
+
+		const progress =
+			settings.lastSentId === null || settings.lastSentTombstoneId === null
+				? startFromNow()
+				: {
+						lastSentId: settings.lastSentId,
+						lastSentTombstoneId: settings.lastSentTombstoneId,
+					};
+		let segment = plan.nextSegment;
+		while (!deps.isLocked()) {
+			const items = deps.handlers.listItemsAfter(
+				progress.lastSentId,
+				SEGMENT_SIZE,
+			);
+			const tombstones = deps.handlers.listTombstonesAfter(
+				progress.lastSentTombstoneId,
+				SYNC_TOMBSTONE_BATCH_SIZE,
+			);
+			if (items.length === 0 && tombstones.length === 0) break;
+
+			const file = formatSegmentName(settings.deviceId, segment);
+			const name = `${LOG_DIRECTORY}/${file}`;
+			const plaintext = JSON.stringify({
+				items: items.map(({ content, rtf, html, created_at }) => ({
+					content,
+					rtf,
+					html,
+					created_at,
+				})),
+				deletions: tombstones.map(({ hash, deleted_at }) => ({
+					hash,
+					deleted_at,
+				})),
+			});
+			await store.put(name, encryptObject(key, name, Buffer.from(plaintext)));
+			if (items.length > 0) {
+				progress.lastSentId = items[items.length - 1].id;
+			}
+			if (tombstones.length > 0) {
+				progress.lastSentTombstoneId = tombstones[tombstones.length - 1].id;
+			}
+			deps.settings.setProgress(progress);
+			segment += 1;
+		}
+		return received;
+	};
+
+	/**
+	 * Runs a sync unless one is in progress, in which case another runs
+	 * once it finishes.
+	 */
+	const run = (): Promise<void> => {
+		if (running) {
+			runAgain = true;
+			return running;
+		}
+		running = syncOnce()
+			.then((received) => {
+				lastSyncAt = new Date().toISOString();
+				lastError = null;
+				if (received > 0) deps.onSynced?.(received);
+			})
+			.catch((error) => {
+				lastError = error instanceof Error ? error.message : String(error);
+				console.error("Remote sync failed:", error);
+			})
+			.finally(() => {
+				running = null;
+				if (runAgain) {
+					runAgain = false;
+					void run();
+				}
+			});
+		return running;
+	};
+
+	/**
+	 * Uploads a new capture or deletion shortly after it is made.
+	 */
+	const notifyLocalChange = (): void => {
+		if (pushTimer || !intervalTimer) return;
+		pushTimer = setTimeout(() => {
+			pushTimer = null;
+			void run();
+		}, PUSH_DELAY_MS);
+	};
+
+	const stop = (): void => {
+		if (intervalTimer) {
+			clearInterval(intervalTimer);
+			intervalTimer = null;
+		}
+		if (pushTimer) {
+			clearTimeout(pushTimer);
+			pushTimer = null;
+		}
+	};
+
+	/**
+	 * Starts, restarts or stops periodic sync to match its settings.
+	 */
+	const apply = (): void => {
+		stop();
+		const settings = deps.settings.getSettings();
+		if (!settings.enabled) return;
+		if (settings.lastSentId === null || settings.lastSentTombstoneId === null) {
+			startFromNow();
+		}
+		intervalTimer = setInterval(
+			() => void run(),
+			settings.intervalMinutes * 60 * 1000,
+		);
+		void run();
+	};
+
+	/**
+	 * Syncs right away and waits for it to finish.
+	 *
+	 * @throws if sync is not enabled
+	 */
+	const syncNow = async (): Promise<void> => {
+		if (!deps.settings.getSettings().enabled) {
+			throw new Error("Remote sync is not enabled");
+		}
+		await run();
+	};
+
+	const getStatus = (): RemoteSyncStatus => {
+		const settings = deps.settings.getSettings();
+		return {
+			enabled: settings.enabled,
+			backend: settings.backend,
+			url: settings.url,
+			bucket: settings.bucket,
+			region: settings.region,
+			prefix: settings.prefix,
+			username: settings.username,
+			hasPassword: settings.password !== "",
+			hasPassphrase: settings.passphrase !== "",
+			intervalMinutes: settings.intervalMinutes,
+			deviceId: settings.deviceId,
+			syncing: running !== null,
+			lastSyncAt,
+			lastError,
+		};
+	};
+
+	return { apply, stop, syncNow, getStatus, notifyLocalChange };
+};
+
+export type RemoteSync = ReturnType<typeof createRemoteSync>;
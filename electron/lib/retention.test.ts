@@ -0,0 +1,186 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createRetentionModule,
+	DEFAULT_RETENTION_POLICY,
+	getRetentionPolicyPath,
+	hasRetentionLimits,
+	parseRetentionPolicy,
+	readRetentionPolicyFromFile,
+	writeRetentionPolicyToFile,
+} from "./retention.js";
+
+describe("parseRetentionPolicy", () => {
+	it("accepts positive integers and null", () => {
+		const policy = {
+			maxItems: 500,
+			maxAgeDays: null,
+			maxDatabaseMb: 8,
+			trashDays: 7,
+			keepPastedAtLeast: 5,
+		};
+		expect(parseRetentionPolicy(policy)).toEqual(policy);
+	});
+
+	it("keeps current values for missing keys", () => {
+		const current = {
+			maxItems: 100,
+			maxAgeDays: 30,
+			maxDatabaseMb: null,
+			trashDays: null,
+			keepPastedAtLeast: 3,
+		};
+		expect(parseRetentionPolicy({ maxAgeDays: 7 }, current)).toEqual({
+			maxItems: 100,
+			maxAgeDays: 7,
+			maxDatabaseMb: null,
+			trashDays: null,
+			keepPastedAtLeast: 3,
+		});
+	});
+
+	it("rejects zero, negative, fractional, and non-numeric limits", () => {
+		for (const value of [0, -1, 1.5, "10", Number.NaN]) {
+			expect(() => parseRetentionPolicy({ maxItems: value })).toThrow(
+				"maxItems must be a positive integer or null",
+			);
+		}
+		expect(() => parseRetentionPolicy(null)).toThrow("expected an object");
+	});
+});
+
+describe("hasRetentionLimits", () => {
+	it("is false only when every limit is null", () => {
+		const unlimited = { ...DEFAULT_RETENTION_POLICY, trashDays: null };
+		expect(hasRetentionLimits(unlimited)).toBe(false);
+		expect(hasRetentionLimits({ ...unlimited, maxAgeDays: 1 })).toBe(true);
+		expect(hasRetentionLimits({ ...unlimited, keepPastedAtLeast: 3 })).toBe(
+			false,
+		);
+	});
+
+	it("purges the trash by default", () => {
+		expect(hasRetentionLimits(DEFAULT_RETENTION_POLICY)).toBe(true);
+	});
+});
+
+describe("retention policy file", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(
+			path.join(os.tmpdir(), "clipboard-retention-"),
+		);
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		vi.restoreAllMocks();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("returns defaults when the file is missing", () => {
+		const filePath = getRetentionPolicyPath(createTempUserDataPath());
+		expect(readRetentionPolicyFromFile(filePath)).toEqual(
+			DEFAULT_RETENTION_POLICY,
+		);
+	});
+
+	it("returns defaults when the file is invalid", () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const filePath = getRetentionPolicyPath(createTempUserDataPath());
+		fs.writeFileSync(filePath, JSON.stringify({ maxItems: -5 }), "utf-8");
+
+		expect(readRetentionPolicyFromFile(filePath)).toEqual(
+			DEFAULT_RETENTION_POLICY,
+		);
+	});
+
+	it("round-trips a saved policy", () => {
+		const filePath = getRetentionPolicyPath(createTempUserDataPath());
+		const policy = {
+			maxItems: 1000,
+			maxAgeDays: 90,
+			maxDatabaseMb: null,
+			trashDays: 30,
+			keepPastedAtLeast: null,
+		};
+
+		writeRetentionPolicyToFile(filePath, policy);
+		expect(readRetentionPolicyFromFile(filePath)).toEqual(policy);
+	});
+
+	it("does not prune while every limit is off", () => {
+		const userDataPath = createTempUserDataPath();
+		writeRetentionPolicyToFile(getRetentionPolicyPath(userDataPath), {
+			...DEFAULT_RETENTION_POLICY,
+			trashDays: null,
+		});
+		const prune = vi.fn().mockReturnValue(0);
+		const retention = createRetentionModule({ userDataPath, prune });
+
+		expect(retention.enforce()).toBe(0);
+		expect(prune).not.toHaveBeenCalled();
+	});
+
+	it("saves and enforces policy updates", () => {
+		const userDataPath = createTempUserDataPath();
+		const prune = vi.fn().mockReturnValue(3);
+		const onPruned = vi.fn();
+		const retention = createRetentionModule({ userDataPath, prune, onPruned });
+
+		const policy = retention.updatePolicy({ maxItems: 50 });
+
+		expect(policy).toEqual({ ...DEFAULT_RETENTION_POLICY, maxItems: 50 });
+		expect(prune).toHaveBeenCalledWith(policy);
+		expect(onPruned).toHaveBeenCalledWith(3);
+		expect(
+			readRetentionPolicyFromFile(getRetentionPolicyPath(userDataPath)),
+		).toEqual(policy);
+	});
+
+	it("keeps the previous policy when an update is invalid", () => {
+		const retention = createRetentionModule({
+			userDataPath: createTempUserDataPath(),
+			prune: vi.fn().mockReturnValue(0),
+		});
+		retention.updatePolicy({ maxAgeDays: 30 });
+
+		expect(() => retention.updatePolicy({ maxAgeDays: 0 })).toThrow();
+		expect(retention.getPolicy().maxAgeDays).toBe(30);
+	});
+
+	it("enforces periodically once started", () => {
+		vi.useFakeTimers();
+		try {
+			const userDataPath = createTempUserDataPath();
+			writeRetentionPolicyToFile(getRetentionPolicyPath(userDataPath), {
+				...DEFAULT_RETENTION_POLICY,
+				maxAgeDays: 7,
+			});
+			const prune = vi.fn().mockReturnValue(0);
+			const retention = createRetentionModule({
+				userDataPath,
+				prune,
+				intervalMs: 1000,
+			});
+
+			retention.start();
+			expect(prune).toHaveBeenCalledTimes(1);
+
+			vi.advanceTimersByTime(2000);
+			expect(prune).toHaveBeenCalledTimes(3);
+
+			retention.stop();
+			vi.advanceTimersByTime(2000);
+			expect(prune).toHaveBeenCalledTimes(3);
+		} finally {
+			vi.useRealTimers();
+		}
+	});
+});
@@ -0,0 +1,194 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Limits applied to history by the retention task.
+ * A null limit is not enforced. Pinned items and items in a collection
+ * are never pruned.
+ */
+export type RetentionPolicy = {
+	/** Maximum number of prunable items kept */
+	maxItems: number | null;
+	/** Prunable items older than this many days are deleted */
+	maxAgeDays: number | null;
+	/** Oldest prunable items are deleted while the database is larger (MB) */
+	maxDatabaseMb: number | null;
+	/** Trash entries deleted more than this many days ago are purged */
+	trashDays: number | null;
+	/**
+	 * Items pasted from history at least this many times are kept like
+	 * pinned ones; null prunes them like any other item
+	 */
+	keepPastedAtLeast: number | null;
+};
+
+/**
+ * History is kept forever unless the user sets a limit; deleted items stay
+ * in the trash for 30 days.
+ */
+export const DEFAULT_RETENTION_POLICY = {
+	maxItems: null,
+	maxAgeDays: null,
+	maxDatabaseMb: null,
+	trashDays: 30,
+	keepPastedAtLeast: null,
+} as const satisfies RetentionPolicy;
+
+/**
+ * Interval between retention passes (ms).
+ */
+const DEFAULT_RETENTION_INTERVAL_MS = 60 * 60 * 1000;
+
+const RETENTION_FILENAME = "retention.json";
+
+const RETENTION_LIMIT_KEYS = [
+	"maxItems",
+	"maxAgeDays",
+	"maxDatabaseMb",
+	"trashDays",
+] as const;
+
+/**
+ * Every policy key; all of them are positive integers or null.
+ */
+const RETENTION_KEYS = [...RETENTION_LIMIT_KEYS, "keepPastedAtLeast"] as const;
+
+/**
+ * Validates a retention policy update.
+ * Pure function. Limits must be positive integers or null; missing keys
+ * keep their current value.
+ *
+ * @throws if any limit is not a positive integer or null
+ */
+export const parseRetentionPolicy = (
+	input: unknown,
+	current: RetentionPolicy = DEFAULT_RETENTION_POLICY,
+): RetentionPolicy => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid retention policy: expected an object");
+	}
+
+	const policy: RetentionPolicy = { ...current };
+	const values = input as Partial<Record<keyof RetentionPolicy, unknown>>;
+	for (const key of RETENTION_KEYS) {
+		const value = values[key];
+		if (value === undefined) continue;
+		if (
+			value !== null &&
+			!(typeof value === "number" && Number.isInteger(value) && value > 0)
+		) {
+			throw new Error(
+				`Invalid retention policy: ${key} must be a positive integer or null`,
+			);
+		}
+		policy[key] = value;
+	}
+	return policy;
+};
+
+/**
+ * Checks whether a policy enforces any limit; keeping often pasted items
+ * alone prunes nothing.
+ * Pure function.
+ */
+export const hasRetentionLimits = (policy: RetentionPolicy): boolean =>
+	RETENTION_LIMIT_KEYS.some((key) => policy[key] !== null);
+
+export const getRetentionPolicyPath = (userDataPath: string) =>
+	path.join(userDataPath, RETENTION_FILENAME);
+
+/**
+ * Reads the saved policy, falling back to defaults if it is missing or invalid.
+ */
+export const readRetentionPolicyFromFile = (
+	filePath: string,
+): RetentionPolicy => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_RETENTION_POLICY };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseRetentionPolicy(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read retention policy, using defaults:", error);
+		return { ...DEFAULT_RETENTION_POLICY };
+	}
+};
+
+export const writeRetentionPolicyToFile = (
+	filePath: string,
+	policy: RetentionPolicy,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(policy, null, 2), "utf-8");
+};
+
+export type RetentionModuleDeps = {
+	/** Where the policy is persisted */
+	userDataPath: string;
+	/** Deletes items outside the policy; returns the number deleted */
+	prune: (policy: RetentionPolicy) => number;
+	/** Called after a pass that deleted items */
+	onPruned?: (deleted: number) => void;
+	/** Interval between passes in milliseconds */
+	intervalMs?: number;
+};
+
+/**
+ * Creates the retention module.
+ * Enforces the saved policy once on start, then periodically in the
+ * background, and again whenever the policy is updated.
+ */
+export const createRetentionModule = (deps: RetentionModuleDeps) => {
+	const filePath = getRetentionPolicyPath(deps.userDataPath);
+	const intervalMs = deps.intervalMs ?? DEFAULT_RETENTION_INTERVAL_MS;
+	let policy = readRetentionPolicyFromFile(filePath);
+	let timer: NodeJS.Timeout | null = null;
+
+	/**
+	 * Runs one retention pass.
+	 * @returns number of items deleted
+	 */
+	const enforce = (): number => {
+		if (!hasRetentionLimits(policy)) return 0;
+		try {
+			const deleted = deps.prune(policy);
+			if (deleted > 0) deps.onPruned?.(deleted);
+			return deleted;
+		} catch (error) {
+			console.error("Retention pass failed:", error);
+			return 0;
+		}
+	};
+
+	const getPolicy = (): RetentionPolicy => ({ ...policy });
+
+	/**
+	 * Validates, saves, and immediately enforces a policy update.
+	 */
+	const updatePolicy = (input: unknown): RetentionPolicy => {
+		const next = parseRetentionPolicy(input, policy);
+		writeRetentionPolicyToFile(filePath, next);
+		policy = next;
+		enforce();
+		return getPolicy();
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		enforce();
+		timer = setInterval(enforce, intervalMs);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	return { getPolicy, updatePolicy, enforce, start, stop };
+};
+
+export type RetentionModule = ReturnType<typeof createRetentionModule>;
@@ -0,0 +1,350 @@
+import fs from "node:fs";
+import path from "node:path";
+import { createRegexMatcher, validateSearchPattern } from "./regex-search.js";
+
+/**
+ * A named pattern for text that looks like a credential.
+ */
+export type SecretRule = {
+	name: string;
+	/** Case-sensitive regular expression source */
+	pattern: string;
+};
+
+/**
+ * Persisted secret scanning settings.
+ */
+export type SecretScanningSettings = {
+	enabled: boolean;
+	/** Minutes a flagged item is kept before it is deleted; null keeps it */
+	expireAfterMinutes: number | null;
+	rules: SecretRule[];
+};
+
+/**
+ * What a capture matched: the rule and how long the item may be kept.
+ */
+export type SecretFinding = {
+	rule: string;
+	expireAfterMinutes: number | null;
+};
+
+const DEFAULT_SECRET_RULES: SecretRule[] = [
+	{ name: "AWS access key", pattern: "\\b(?:AKIA|ASIA)[0-9A-Z]{16}\\b" },
+	{
+		name: "GitHub token",
+		pattern: "\\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_\\w{22,})\\b",
+	},
+	{
+		name: "Private key",
+		pattern: "-----BEGIN (?:[A-Z0-9]+ )*PRIVATE KEY-----",
+	},
+	{
+		name: "JSON Web Token",
+		pattern: "\\beyJ[\\w-]{5,}\\.eyJ[\\w-]{5,}\\.[\\w-]+",
+	},
+];
+
+const DEFAULT_SECRET_SCANNING_SETTINGS: SecretScanningSettings = {
+	enabled: true,
+	expireAfterMinutes: null,
+	rules: DEFAULT_SECRET_RULES,
+};
+
+const SECRET_SCANNING_FILENAME = "secret-scanning.json";
+
+/**
+ * Longest accepted expiry (one week).
+ */
+const MAX_EXPIRE_AFTER_MINUTES = 7 * 24 * 60;
+
+const MAX_SECRET_RULES = 50;
+
+const MAX_RULE_NAME_LENGTH = 64;
+
+/**
+ * Time one rule may spend matching a capture (ms); a pattern that runs
+ * longer counts as no match.
+ */
+const RULE_TIMEOUT_MS = 50;
+
+/**
+ * Interval between passes deleting expired items (one minute).
+ */
+const DEFAULT_EXPIRY_INTERVAL_MS = 60 * 1000;
+
+/**
+ * Characters of a flagged item shown before the mask, enough to tell
+ * `ghp_` from `AKIA` without giving any of the secret away.
+ */
+const MASK_VISIBLE_CHARS = 4;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid secret scanning settings: ${message}`);
+
+/**
+ * Validates a list of rules.
+ * Pure function. Names are trimmed.
+ *
+ * @throws if a rule has no name, a name over 64 characters or an invalid
+ *   pattern, or there are more than 50 rules
+ */
+const parseSecretRules = (rules: unknown): SecretRule[] => {
+	if (!Array.isArray(rules)) {
+		throw invalid("rules must be an array");
+	}
+	if (rules.length > MAX_SECRET_RULES) {
+		throw invalid(`at most ${MAX_SECRET_RULES} rules are allowed`);
+	}
+	return rules.map((rule): SecretRule => {
+		if (typeof rule !== "object" || rule === null) {
+			throw invalid("each rule must be an object");
+		}
+		const { name, pattern } = rule as Record<string, unknown>;
+		if (
+			typeof name !== "string" ||
+			name.trim() === "" ||
+			name.trim().length > MAX_RULE_NAME_LENGTH
+		) {
+			throw invalid(`rule names must be 1-${MAX_RULE_NAME_LENGTH} characters`);
+		}
+		try {
+			validateSearchPattern(pattern as string);
+		} catch (error) {
+			throw invalid(
+				`rule "${name.trim()}": ${error instanceof Error ? error.message : String(error)}`,
+			);
+		}
+		return { name: name.trim(), pattern: pattern as string };
+	});
+};
+
+/**
+ * Validates a secret scanning settings update.
+ * Pure function. Missing keys keep their current value; `rules` replaces
+ * the whole list.
+ *
+ * @throws if `enabled` is not a boolean, `expireAfterMinutes` is not null
+ *   or an integer from 1 to 10080, or a rule is invalid
+ */
+export const parseSecretScanningSettings = (
+	input: unknown,
+	current: SecretScanningSettings = DEFAULT_SECRET_SCANNING_SETTINGS,
+): SecretScanningSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, expireAfterMinutes, rules } = input as Partial<
+		Record<keyof SecretScanningSettings, unknown>
+	>;
+	const next: SecretScanningSettings = {
+		...current,
+		rules: [...current.rules],
+	};
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (expireAfterMinutes !== undefined) {
+		if (
+			expireAfterMinutes !== null &&
+			(typeof expireAfterMinutes !== "number" ||
+				!Number.isInteger(expireAfterMinutes) ||
+				expireAfterMinutes < 1 ||
+				expireAfterMinutes > MAX_EXPIRE_AFTER_MINUTES)
+		) {
+			throw invalid(
+				`expireAfterMinutes must be null or 1-${MAX_EXPIRE_AFTER_MINUTES}`,
+			);
+		}
+		next.expireAfterMinutes = expireAfterMinutes;
+	}
+
+	if (rules !== undefined) {
+		next.rules = parseSecretRules(rules);
+	}
+
+	return next;
+};
+
+/**
+ * Name of the first rule matching `text`, or null if none does.
+ * Rules are matched case-sensitively with a time limit each; a rule that
+ * times out is skipped.
+ */
+export const findSecret = (
+	text: string,
+	rules: readonly SecretRule[],
+): string | null => {
+	for (const rule of rules) {
+		try {
+			const match = createRegexMatcher({
+				pattern: rule.pattern,
+				flags: "",
+				timeoutMs: RULE_TIMEOUT_MS,
+			});
+			if (match([text])[0]) return rule.name;
+		} catch (error) {
+			console.warn(`Secret rule "${rule.name}" skipped:`, error);
+		}
+	}
+	return null;
+};
+
+/**
+ * Masked preview of flagged text, e.g. `ghp_****`.
+ * Pure function.
+ */
+export const maskSecretPreview = (text: string): string =>
+	`${text.trim().slice(0, MASK_VISIBLE_CHARS)}****`;
+
+/**
+ * Replaces the content of a flagged row with a masked preview and drops
+ * its rich formats, so the full text only leaves the main process when
+ * revealed. Other rows are returned as is.
+ * Pure function.
+ */
+export const redactSecret = <
+	T extends {
+		content: string;
+		rtf: string | null;
+		html: string | null;
+		secret_match: string | null;
+	},
+>(
+	row: T,
+): T =>
+	row.secret_match === null
+		? row
+		: {
+				...row,
+				content: maskSecretPreview(row.content),
+				rtf: null,
+				html: null,
+			};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getSecretScanningPath = (userDataPath: string) =>
+	path.join(userDataPath, SECRET_SCANNING_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readSecretScanningFromFile = (
+	filePath: string,
+): SecretScanningSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_SECRET_SCANNING_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseSecretScanningSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read secret scanning settings:", error);
+		return { ...DEFAULT_SECRET_SCANNING_SETTINGS };
+	}
+};
+
+const writeSecretScanningToFile = (
+	filePath: string,
+	settings: SecretScanningSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Secret Scanner Module
+// ============================================================================
+
+export type SecretScannerDeps = {
+	userDataPath: string;
+	/** Deletes items past their expiry; returns the number deleted */
+	expire: () => number;
+	/** Called after a pass that deleted items */
+	onExpired?: (deleted: number) => void;
+	/** Interval between expiry passes in milliseconds */
+	intervalMs?: number;
+};
+
+/**
+ * Creates the secret scanner.
+ * The capture path scans each text copy against the rules and flags
+ * matching items; items past their expiry, flagged or given one by the
+ * user, are deleted on start and then every minute.
+ */
+export const createSecretScanner = (deps: SecretScannerDeps) => {
+	const filePath = getSecretScanningPath(deps.userDataPath);
+	const intervalMs = deps.intervalMs ?? DEFAULT_EXPIRY_INTERVAL_MS;
+	let settings = readSecretScanningFromFile(filePath);
+	let timer: NodeJS.Timeout | null = null;
+
+	const getSettings = (): SecretScanningSettings => ({
+		...settings,
+		rules: settings.rules.map((rule) => ({ ...rule })),
+	});
+
+	const updateSettings = (input: unknown): SecretScanningSettings => {
+		const next = parseSecretScanningSettings(input, settings);
+		writeSecretScanningToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Checks captured text against the rules.
+	 * @returns the finding, or null if scanning is off or nothing matched
+	 */
+	const scan = (text: string): SecretFinding | null => {
+		if (!settings.enabled || text.trim() === "") return null;
+		const rule = findSecret(text, settings.rules);
+		return rule === null
+			? null
+			: { rule, expireAfterMinutes: settings.expireAfterMinutes };
+	};
+
+	/**
+	 * Runs one expiry pass.
+	 * @returns number of items deleted
+	 */
+	const expire = (): number => {
+		try {
+			const deleted = deps.expire();
+			if (deleted > 0) deps.onExpired?.(deleted);
+			return deleted;
+		} catch (error) {
+			console.error("Secret expiry pass failed:", error);
+			return 0;
+		}
+	};
+
+	const start = (): void => {
+		if (timer) return;
+		expire();
+		timer = setInterval(expire, intervalMs);
+	};
+
+	const stop = (): void => {
+		if (timer) {
+			clearInterval(timer);
+			timer = null;
+		}
+	};
+
+	return { getSettings, updateSettings, scan, expire, start, stop };
+};
+
+export type SecretScanner = ReturnType<typeof createSecretScanner>;
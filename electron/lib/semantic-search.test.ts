@@ -0,0 +1,165 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createEmbedder,
+	createSemanticSearch,
+	decodeVector,
+	type Embedder,
+	type EmbeddingCandidate,
+	encodeVector,
+	normalizeVector,
+	parseEmbeddingResponse,
+	parseSemanticSearchSettings,
+	rankBySimilarity,
+	type StoredEmbedding,
+} from "./semantic-search.js";
+
+describe("parseSemanticSearchSettings", () => {
+	it("is off by default, pointing at a local model", () => {
+		expect(parseSemanticSearchSettings({})).toEqual({
+			enabled: false,
+			endpoint: "http://127.0.0.1:11434/api/embed",
+			model: "nomic-embed-text",
+			apiKey: "",
+		});
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseSemanticSearchSettings({ enabled: "yes" })).toThrow(
+			"Invalid semantic search settings",
+		);
+		expect(() =>
+			parseSemanticSearchSettings({ endpoint: "file:///etc/passwd" }),
+		).toThrow("endpoint must be an http or https URL");
+		expect(() => parseSemanticSearchSettings({ model: " " })).toThrow(
+			"model must be a name",
+		);
+	});
+});
+
+describe("parseEmbeddingResponse", () => {
+	it("reads Ollama and OpenAI responses", () => {
+		expect(parseEmbeddingResponse({ embeddings: [[1, 2]] }, 1)).toEqual([
+			[1, 2],
+		]);
+		expect(
+			parseEmbeddingResponse(
+				{
+					data: [
+						{ index: 1, embedding: [3, 4] },
+						{ index: 0, embedding: [1, 2] },
+					],
+				},
+				2,
+			),
+		).toEqual([
+			[1, 2],
+			[3, 4],
+		]);
+	});
+
+	it("rejects missing or malformed vectors", () => {
+		expect(() => parseEmbeddingResponse({ error: "no model" }, 1)).toThrow(
+			"Invalid embedding response",
+		);
+		expect(() => parseEmbeddingResponse({ embeddings: [[1]] }, 2)).toThrow(
+			"expected 2 vectors",
+		);
+		expect(() =>
+			parseEmbeddingResponse({ embeddings: [[1, 2], [3]] }, 2),
+		).toThrow("one length");
+	});
+});
+
+describe("vectors", () => {
+	it("round-trips normalized vectors through storage", () => {
+		const vector = normalizeVector([3, 4]);
+
+		expect(vector[0]).toBeCloseTo(0.6);
+		expect(vector[1]).toBeCloseTo(0.8);
+		expect(decodeVector(encodeVector(vector))).toEqual(vector);
+	});
+
+	it("ranks by similarity, skipping distant and mismatched vectors", () => {
+		const embeddings: StoredEmbedding[] = [
+			{ id: 1, vector: normalizeVector([0, 1]) },
+			{ id: 2, vector: normalizeVector([1, 0.1]) },
+			{ id: 3, vector: normalizeVector([1, 1]) },
+			{ id: 4, vector: normalizeVector([1, 0, 0]) },
+		];
+
+		const matches = rankBySimilarity(normalizeVector([1, 0]), embeddings, 10);
+		expect(matches.map((match) => match.id)).toEqual([2, 3]);
+		expect(rankBySimilarity(normalizeVector([1, 0]), embeddings, 1)).toEqual([
+			matches[0],
+		]);
+	});
+});
+
+describe("createEmbedder", () => {
+	it("posts the model and input with the API key", async () => {
+		const fetch = vi
+			.fn()
+			.mockResolvedValue(Response.json({ embeddings: [[0.5, 0.5]] }));
+		const embed = createEmbedder({ fetch });
+
+		const vectors = await embed(
+			{
+				...parseSemanticSearchSettings({}),
+				endpoint: "https://api.example.com/v1/embeddings",
+				apiKey: "sk-test",
+			},
+			["hello"],
+		);
+
+		expect(vectors).toEqual([[0.5, 0.5]]);
+		const [url, init] = fetch.mock.calls[0];
+		expect(url).toBe("https://api.example.com/v1/embeddings");
+		expect(init.headers.authorization).toBe("Bearer sk-test");
+		expect(JSON.parse(init.body)).toEqual({
+			model: "nomic-embed-text",
+			input: ["hello"],
+		});
+	});
+});
+
+describe("createSemanticSearch", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("indexes pending items while enabled and searches them", async () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "semantic-"));
+		const pending: EmbeddingCandidate[] = [
+			{ id: 1, content: "undefined symbol _main" },
+			{ id: 2, content: "grocery list" },
+		];
+		const stored = new Map<number, Float32Array>();
+		const embed = vi.fn<Embedder>(async (_settings, texts) =>
+			texts.map((text) => (/symbol|linker/.test(text) ? [1, 0] : [0, 1])),
+		);
+		const semantic = createSemanticSearch({
+			userDataPath,
+			nextCandidates: (_model, limit) =>
+				pending.filter(({ id }) => !stored.has(id)).slice(0, limit),
+			save: (id, _model, vector) => stored.set(id, vector),
+			listEmbeddings: () => [...stored].map(([id, vector]) => ({ id, vector })),
+			embed,
+		});
+
+		semantic.schedule();
+		expect(embed).not.toHaveBeenCalled();
+		await expect(semantic.search("linker error", 10)).rejects.toThrow(
+			"disabled",
+		);
+
+		semantic.updateSettings({ enabled: true });
+		await vi.waitFor(() => expect(stored.size).toBe(2));
+		const matches = await semantic.search("linker error", 10);
+		expect(matches.map((match) => match.id)).toEqual([1]);
+	});
+});
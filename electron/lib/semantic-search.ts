@@ -0,0 +1,453 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted semantic search settings.
+ */
+export type SemanticSearchSettings = {
+	/** Index text items and answer semantic searches; off until opted in */
+	enabled: boolean;
+	/**
+	 * Embeddings endpoint taking `{ model, input }`: Ollama's `/api/embed`
+	 * or any OpenAI-compatible `/v1/embeddings`
+	 */
+	endpoint: string;
+	/** Embedding model the endpoint runs, e.g. `nomic-embed-text` */
+	model: string;
+	/** Sent as a bearer token if set; hosted endpoints need one */
+	apiKey: string;
+};
+
+/**
+ * A text item not embedded with the current model yet.
+ */
+export type EmbeddingCandidate = {
+	id: number;
+	content: string;
+};
+
+/**
+ * A stored item vector, unit length.
+ */
+export type StoredEmbedding = {
+	id: number;
+	vector: Float32Array;
+};
+
+/**
+ * An item ranked by how close its meaning is to the query.
+ */
+export type SemanticMatch = {
+	id: number;
+	/** Cosine similarity, 1 for the same meaning */
+	score: number;
+};
+
+/**
+ * Turns texts into embedding vectors, one per text in order.
+ */
+export type Embedder = (
+	settings: SemanticSearchSettings,
+	texts: readonly string[],
+) => Promise<number[][]>;
+
+/**
+ * Off until the user opts in, pointing at a model served by Ollama on
+ * this machine, so no text leaves it unless another endpoint is set.
+ */
+const DEFAULT_SEMANTIC_SEARCH_SETTINGS: SemanticSearchSettings = {
+	enabled: false,
+	endpoint: "http://127.0.0.1:11434/api/embed",
+	model: "nomic-embed-text",
+	apiKey: "",
+};
+
+const SEMANTIC_SEARCH_FILENAME = "semantic-search.json";
+
+/**
+ * Characters of an item embedded; small models read little more, and
+ * the start of a text says what it is about.
+ */
+export const MAX_EMBEDDING_CHARS = 2_000;
+
+/**
+ * Items embedded per request while indexing.
+ */
+const EMBEDDING_BATCH_SIZE = 16;
+
+/**
+ * Upper bound on one embeddings request (ms); the first one may wait
+ * for the model to load.
+ */
+const EMBED_TIMEOUT_MS = 60_000;
+
+/**
+ * Similarity below which an item is not returned at all.
+ */
+export const MIN_SEMANTIC_SCORE = 0.3;
+
+/**
+ * Most items one semantic search returns.
+ */
+export const MAX_SEMANTIC_RESULTS = 100;
+
+const MAX_MODEL_LENGTH = 200;
+
+const MAX_API_KEY_LENGTH = 1_000;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid semantic search settings: ${message}`);
+
+const invalidResponse = (message: string) =>
+	new Error(`Invalid embedding response: ${message}`);
+
+/**
+ * Validates a semantic search settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value has the wrong type, the endpoint is not an http or
+ *   https URL, or the model name is empty
+ */
+export const parseSemanticSearchSettings = (
+	input: unknown,
+	current: SemanticSearchSettings = DEFAULT_SEMANTIC_SEARCH_SETTINGS,
+): SemanticSearchSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, endpoint, model, apiKey } = input as Partial<
+		Record<keyof SemanticSearchSettings, unknown>
+	>;
+	const next: SemanticSearchSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (endpoint !== undefined) {
+		if (typeof endpoint !== "string" || !/^https?:\/\/[^/]/i.test(endpoint)) {
+			throw invalid("endpoint must be an http or https URL");
+		}
+		next.endpoint = endpoint;
+	}
+
+	if (model !== undefined) {
+		if (
+			typeof model !== "string" ||
+			model.trim().length === 0 ||
+			model.length > MAX_MODEL_LENGTH
+		) {
+			throw invalid(`model must be a name of 1-${MAX_MODEL_LENGTH} characters`);
+		}
+		next.model = model.trim();
+	}
+
+	if (apiKey !== undefined) {
+		if (typeof apiKey !== "string" || apiKey.length > MAX_API_KEY_LENGTH) {
+			throw invalid(
+				`apiKey must be a string of at most ${MAX_API_KEY_LENGTH} characters`,
+			);
+		}
+		next.apiKey = apiKey;
+	}
+
+	return next;
+};
+
+/**
+ * Reads the vectors out of an embeddings response, in Ollama's
+ * (`embeddings`) or OpenAI's (`data[].embedding`) shape.
+ * Pure function.
+ *
+ * @throws if the body holds anything but `count` numeric vectors of one
+ *   length
+ */
+export const parseEmbeddingResponse = (
+	body: unknown,
+	count: number,
+): number[][] => {
+	if (typeof body !== "object" || body === null) {
+		throw invalidResponse("expected an object");
+	}
+
+	const { embeddings, data } = body as { embeddings?: unknown; data?: unknown };
+	let vectors: unknown[];
+	if (Array.isArray(embeddings)) {
+		vectors = embeddings;
+	} else if (Array.isArray(data)) {
+		// OpenAI-compatible servers give each vector the index of its input
+		vectors = [...data]
+			.sort((a, b) => (a?.index ?? 0) - (b?.index ?? 0))
+			.map((entry) => entry?.embedding);
+	} else {
+		throw invalidResponse("no embeddings");
+	}
+
+	if (vectors.length !== count) {
+		throw invalidResponse(`expected ${count} vectors, got ${vectors.length}`);
+	}
+	const length = Array.isArray(vectors[0]) ? vectors[0].length : 0;
+	for (const vector of vectors) {
+		if (
+			!Array.isArray(vector) ||
+			vector.length === 0 ||
+			vector.length !== length ||
+			!vector.every((value) => Number.isFinite(value))
+		) {
+			throw invalidResponse("vectors must be lists of numbers of one length");
+		}
+	}
+	return vectors as number[][];
+};
+
+/**
+ * Scales a vector to unit length, so similarity is a dot product.
+ * Pure function.
+ */
+export const normalizeVector = (values: readonly number[]): Float32Array => {
+	const vector = Float32Array.from(values);
+	let norm = 0;
+	for (const value of vector) norm += value * value;
+	norm = Math.sqrt(norm);
+	if (norm > 0) {
+		for (let i = 0; i < vector.length; i++) vector[i] /= norm;
+	}
+	return vector;
+};
+
+/**
+ * Stores a vector as little-endian 32-bit floats.
+ * Pure function.
+ */
+export const encodeVector = (vector: Float32Array): Buffer => {
+	const blob = Buffer.alloc(vector.length * 4);
+	vector.forEach((value, i) => blob.writeFloatLE(value, i * 4));
+	return blob;
+};
+
+/**
+ * Reads a vector stored by `encodeVector`.
+ * Pure function.
+ */
+export const decodeVector = (blob: Buffer): Float32Array => {
+	const vector = new Float32Array(blob.byteLength / 4);
+	for (let i = 0; i < vector.length; i++) vector[i] = blob.readFloatLE(i * 4);
+	return vector;
+};
+
+/**
+ * Ranks stored vectors by cosine similarity to the query vector, best
+ * first, leaving out those below `MIN_SEMANTIC_SCORE` and those of a
+ * different length (embedded by another model).
+ * Pure function.
+ */
+export const rankBySimilarity = (
+	query: Float32Array,
+	embeddings: Iterable<StoredEmbedding>,
+	limit: number,
+): SemanticMatch[] => {
+	const matches: SemanticMatch[] = [];
+	for (const { id, vector } of embeddings) {
+		if (vector.length !== query.length) continue;
+		let score = 0;
+		for (let i = 0; i < vector.length; i++) score += vector[i] * query[i];
+		if (score >= MIN_SEMANTIC_SCORE) matches.push({ id, score });
+	}
+	return matches.sort((a, b) => b.score - a.score).slice(0, limit);
+};
+
+// ============================================================================
+// Embedder
+// ============================================================================
+
+/**
+ * Creates an embedder posting `{ model, input }` to the settings'
+ * endpoint.
+ *
+ * @throws from the returned embedder if the request fails or the
+ *   response holds no usable vectors
+ */
+export const createEmbedder = (
+	deps: { fetch?: typeof fetch; timeoutMs?: number } = {},
+): Embedder => {
+	const send = deps.fetch ?? fetch;
+	const timeoutMs = deps.timeoutMs ?? EMBED_TIMEOUT_MS;
+
+	return async (settings, texts) => {
+		const response = await send(settings.endpoint, {
+			method: "POST",
+			headers: {
+				"content-type": "application/json",
+				...(settings.apiKey
+					? { authorization: `Bearer ${settings.apiKey}` }
+					: {}),
+			},
+			body: JSON.stringify({ model: settings.model, input: texts }),
+			signal: AbortSignal.timeout(timeoutMs),
+		});
+		if (!response.ok) {
+			throw new Error(`HTTP ${response.status}`);
+		}
+		return parseEmbeddingResponse(await response.json(), texts.length);
+	};
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getSemanticSearchPath = (userDataPath: string) =>
+	path.join(userDataPath, SEMANTIC_SEARCH_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readSemanticSearchFromFile = (
+	filePath: string,
+): SemanticSearchSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_SEMANTIC_SEARCH_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseSemanticSearchSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read semantic search settings:", error);
+		return { ...DEFAULT_SEMANTIC_SEARCH_SETTINGS };
+	}
+};
+
+const writeSemanticSearchToFile = (
+	filePath: string,
+	settings: SemanticSearchSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Semantic Search Module
+// ============================================================================
+
+export type SemanticSearchDeps = {
+	userDataPath: string;
+	/** Newest text items not embedded with `model` yet */
+	nextCandidates: (model: string, limit: number) => EmbeddingCandidate[];
+	save: (id: number, model: string, vector: Float32Array) => void;
+	/** Every item vector embedded with `model` */
+	listEmbeddings: (model: string) => Iterable<StoredEmbedding>;
+	embed?: Embedder;
+	/** While true, indexing waits for a later `schedule` */
+	shouldDefer?: () => boolean;
+};
+
+/**
+ * Creates the semantic search module.
+ * While enabled, text items are embedded in the background, newest
+ * first, 16 to a request. A failed request stops indexing until the next
+ * `schedule`; the same items are tried again then. Vectors are kept per
+ * model, so switching models reindexes everything.
+ */
+export const createSemanticSearch = (deps: SemanticSearchDeps) => {
+	const filePath = getSemanticSearchPath(deps.userDataPath);
+	const embed = deps.embed ?? createEmbedder();
+	let settings = readSemanticSearchFromFile(filePath);
+	let running = false;
+	let rerun = false;
+
+	const drain = async (): Promise<void> => {
+		while (settings.enabled && !deps.shouldDefer?.()) {
+			const current = settings;
+			const candidates = deps.nextCandidates(
+				current.model,
+				EMBEDDING_BATCH_SIZE,
+			);
+			if (candidates.length === 0) return;
+			const vectors = await embed(
+				current,
+				candidates.map(({ content }) => content.slice(0, MAX_EMBEDDING_CHARS)),
+			);
+			candidates.forEach(({ id }, index) => {
+				deps.save(id, current.model, normalizeVector(vectors[index]));
+			});
+		}
+	};
+
+	/**
+	 * Starts embedding pending items, or has the current run look again
+	 * once it ends. Does nothing while disabled or deferred.
+	 */
+	const schedule = (): void => {
+		if (!settings.enabled || deps.shouldDefer?.()) return;
+		if (running) {
+			rerun = true;
+			return;
+		}
+		running = true;
+		void drain()
+			.catch((error) => console.warn("Semantic indexing stopped:", error))
+			.finally(() => {
+				running = false;
+				if (rerun) {
+					rerun = false;
+					schedule();
+				}
+			});
+	};
+
+	/**
+	 * Finds the indexed items closest in meaning to `query`, best first.
+	 * Items not indexed yet are not found.
+	 *
+	 * @throws if semantic search is off, the query is empty, the limit is
+	 *   not 1-100 or the endpoint fails
+	 */
+	const search = async (
+		query: string,
+		limit: number,
+	): Promise<SemanticMatch[]> => {
+		if (!settings.enabled) {
+			throw new Error("Semantic search is disabled");
+		}
+		if (typeof query !== "string" || query.trim().length === 0) {
+			throw new Error("Invalid semantic search query: expected text");
+		}
+		if (!Number.isInteger(limit) || limit < 1 || limit > MAX_SEMANTIC_RESULTS) {
+			throw new Error(
+				`Invalid semantic search limit: ${limit} (max: ${MAX_SEMANTIC_RESULTS})`,
+			);
+		}
+		const current = settings;
+		const [vector] = await embed(current, [
+			query.slice(0, MAX_EMBEDDING_CHARS),
+		]);
+		return rankBySimilarity(
+			normalizeVector(vector),
+			deps.listEmbeddings(current.model),
+			limit,
+		);
+	};
+
+	const getSettings = (): SemanticSearchSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): SemanticSearchSettings => {
+		const next = parseSemanticSearchSettings(input, settings);
+		writeSemanticSearchToFile(filePath, next);
+		settings = next;
+		schedule();
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, schedule, search };
+};
+
+export type SemanticSearch = ReturnType<typeof createSemanticSearch>;
@@ -0,0 +1,175 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	type AppSettings,
+	createSettingsFile,
+	formatSettingsToml,
+	getSettingsFilePath,
+	type ModuleSettings,
+	parseSettingsToml,
+	resolveAppSettings,
+} from "./settings-file.js";
+
+const defaults = (): AppSettings => resolveAppSettings({});
+
+describe("resolveAppSettings", () => {
+	it("applies sections over the current settings", () => {
+		const current = defaults();
+		const next = resolveAppSettings(
+			{ retention: { maxItems: 500 }, appearance: { theme: "dark" } },
+			current,
+		);
+
+		expect(next.retention).toEqual({ ...current.retention, maxItems: 500 });
+		expect(next.appearance).toEqual({ theme: "dark", compactList: false });
+		expect(next.shortcuts).toEqual(current.shortcuts);
+	});
+
+	it("rejects unknown sections and invalid values", () => {
+		expect(() => resolveAppSettings({ theme: {} })).toThrow(
+			"Invalid settings: unknown section theme",
+		);
+		expect(() =>
+			resolveAppSettings({ appearance: { theme: "sepia" } }),
+		).toThrow("theme must be one of system, light, dark");
+		expect(() => resolveAppSettings({ retention: { maxItems: -1 } })).toThrow(
+			"Invalid retention policy",
+		);
+	});
+});
+
+describe("parseSettingsToml", () => {
+	it("reads back what formatSettingsToml writes", () => {
+		const settings = resolveAppSettings({
+			shortcuts: { togglePicker: null, toggleCapture: "Alt+Shift+P" },
+			retention: { maxItems: 1000, trashDays: null },
+			exclusions: { excludedApps: ["1Password", "Google Chrome"] },
+			window: { hideOnBlur: false },
+			appearance: { compactList: true },
+		});
+		const text = formatSettingsToml(settings);
+
+		expect(text).toContain('togglePicker = ""');
+		expect(text).toContain("trashDays = 0");
+		expect(parseSettingsToml(text)).toEqual(settings);
+	});
+
+	it("gives missing sections and keys their default", () => {
+		const settings = parseSettingsToml(
+			'version = 1\n[appearance]\ntheme = "light"\n',
+		);
+
+		expect(settings).toEqual({
+			...defaults(),
+			appearance: { theme: "light", compactList: false },
+		});
+	});
+
+	it("rejects files from newer versions and unknown sections", () => {
+		expect(() => parseSettingsToml("version = 2")).toThrow(
+			"version 2 is newer than this app supports (1)",
+		);
+		expect(() => parseSettingsToml("[sync]\nenabled = true")).toThrow(
+			"unknown section sync",
+		);
+		expect(() => parseSettingsToml("appearance = 1")).toThrow(
+			"appearance must be a table",
+		);
+	});
+});
+
+describe("createSettingsFile", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-settings-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * Settings file over in-memory module settings.
+	 */
+	const createFile = (userDataPath: string) => {
+		const { appearance: _appearance, ...initial } = defaults();
+		let modules: ModuleSettings = initial;
+		const applyModuleSettings = vi.fn((changes: Partial<ModuleSettings>) => {
+			modules = { ...modules, ...changes };
+		});
+		const file = createSettingsFile({
+			userDataPath,
+			getModuleSettings: () => modules,
+			applyModuleSettings,
+		});
+		return { file, applyModuleSettings };
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("creates the file from the current settings", () => {
+		const userDataPath = createTempUserDataPath();
+		const { file, applyModuleSettings } = createFile(userDataPath);
+
+		file.start();
+		file.stop();
+
+		const text = fs.readFileSync(getSettingsFilePath(userDataPath), "utf-8");
+		expect(parseSettingsToml(text)).toEqual(defaults());
+		expect(applyModuleSettings).not.toHaveBeenCalled();
+	});
+
+	it("applies only the sections an existing file changes", () => {
+		const userDataPath = createTempUserDataPath();
+		const settings = resolveAppSettings({ retention: { maxItems: 200 } });
+		fs.writeFileSync(
+			getSettingsFilePath(userDataPath),
+			formatSettingsToml(settings),
+		);
+		const { file, applyModuleSettings } = createFile(userDataPath);
+
+		file.start();
+		file.stop();
+
+		expect(applyModuleSettings).toHaveBeenCalledExactlyOnceWith({
+			retention: settings.retention,
+		});
+		expect(file.getStatus().lastError).toBeNull();
+	});
+
+	it("keeps an invalid file and reports why it was rejected", () => {
+		const userDataPath = createTempUserDataPath();
+		const filePath = getSettingsFilePath(userDataPath);
+		fs.writeFileSync(filePath, "[retention]\nmaxItems = ");
+		const { file, applyModuleSettings } = createFile(userDataPath);
+
+		file.start();
+		file.save();
+		file.stop();
+
+		expect(file.getStatus().lastError).toBe(
+			"Invalid TOML on line 2: expected a value",
+		);
+		expect(applyModuleSettings).not.toHaveBeenCalled();
+		expect(fs.readFileSync(filePath, "utf-8")).toBe("[retention]\nmaxItems = ");
+	});
+
+	it("saves updates made in the app", () => {
+		const userDataPath = createTempUserDataPath();
+		const { file } = createFile(userDataPath);
+
+		const status = file.update({ appearance: { theme: "dark" } });
+
+		expect(status.settings.appearance.theme).toBe("dark");
+		expect(
+			parseSettingsToml(
+				fs.readFileSync(getSettingsFilePath(userDataPath), "utf-8"),
+			).appearance.theme,
+		).toBe("dark");
+	});
+});
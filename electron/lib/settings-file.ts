@@ -0,0 +1,436 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	type AppExclusionSettings,
+	parseAppExclusionSettings,
+} from "./app-exclusions.js";
+import { parseRetentionPolicy, type RetentionPolicy } from "./retention.js";
+import { parseShortcutSettings, type ShortcutSettings } from "./shortcuts.js";
+import {
+	formatTomlKey,
+	formatTomlValue,
+	parseToml,
+	type TomlValue,
+} from "./toml.js";
+import {
+	parseWindowBehaviorSettings,
+	type WindowBehaviorSettings,
+} from "./window-behavior.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+export type Theme = "system" | "light" | "dark";
+
+/**
+ * Display preferences the renderer applies; the main process only stores
+ * them.
+ */
+export type AppearanceSettings = {
+	theme: Theme;
+	/** Tighter list rows, fitting more items on screen */
+	compactList: boolean;
+};
+
+/**
+ * Everything the settings file holds, one section per table.
+ */
+export type AppSettings = {
+	shortcuts: ShortcutSettings;
+	retention: RetentionPolicy;
+	exclusions: AppExclusionSettings;
+	window: WindowBehaviorSettings;
+	appearance: AppearanceSettings;
+};
+
+/**
+ * Sections owned by other modules, which keep their own files in step.
+ */
+export type ModuleSettings = Omit<AppSettings, "appearance">;
+
+/**
+ * Settings as shown in settings, with the file they live in.
+ */
+export type SettingsFileStatus = {
+	path: string;
+	settings: AppSettings;
+	/** Why the file was last rejected; null once it loads */
+	lastError: string | null;
+};
+
+/**
+ * Format version written to the file; files from newer versions are not
+ * loaded.
+ */
+const SETTINGS_VERSION = 1;
+
+const SETTINGS_FILENAME = "settings.toml";
+
+/**
+ * Delay after the last change on disk before reloading, as editors often
+ * save in several writes.
+ */
+const RELOAD_DELAY_MS = 200;
+
+const SECTION_KEYS = [
+	"shortcuts",
+	"retention",
+	"exclusions",
+	"window",
+	"appearance",
+] as const;
+
+const THEMES: readonly Theme[] = ["system", "light", "dark"];
+
+const DEFAULT_APPEARANCE_SETTINGS: AppearanceSettings = {
+	theme: "system",
+	compactList: false,
+};
+
+const FILE_HEADER = `# Clipboard Manager settings
+# Changes apply as soon as the file is saved; invalid files are ignored
+# and the error is shown in settings.`;
+
+const SECTION_COMMENTS: Record<keyof AppSettings, string> = {
+	shortcuts:
+		'# Global shortcuts such as "CommandOrControl+Shift+V"; "" leaves an action unbound',
+	retention:
+		"# History limits; 0 means no limit. Pinned items are never pruned",
+	exclusions: "# Apps whose copies are never recorded",
+	window: "# Hide the history and quick picker windows when they lose focus",
+	appearance: '# theme is "system", "light" or "dark"',
+};
+
+/**
+ * How sections spell "none", as TOML has no null: `""` for an unbound
+ * shortcut and `0` for no retention limit.
+ */
+const NONE_VALUES: Partial<Record<keyof AppSettings, TomlValue>> = {
+	shortcuts: "",
+	retention: 0,
+};
+
+const invalid = (message: string) => new Error(`Invalid settings: ${message}`);
+
+const isSectionKey = (key: string): key is keyof AppSettings =>
+	(SECTION_KEYS as readonly string[]).includes(key);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates an appearance settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `theme` is not a known theme or `compactList` not a boolean
+ */
+export const parseAppearanceSettings = (
+	input: unknown,
+	current: AppearanceSettings = DEFAULT_APPEARANCE_SETTINGS,
+): AppearanceSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid appearance settings: expected an object");
+	}
+
+	const { theme, compactList } = input as Partial<
+		Record<keyof AppearanceSettings, unknown>
+	>;
+	const next: AppearanceSettings = { ...current };
+
+	if (theme !== undefined) {
+		if (!THEMES.includes(theme as Theme)) {
+			throw new Error(
+				`Invalid appearance settings: theme must be one of ${THEMES.join(", ")}`,
+			);
+		}
+		next.theme = theme as Theme;
+	}
+	if (compactList !== undefined) {
+		if (typeof compactList !== "boolean") {
+			throw new Error(
+				"Invalid appearance settings: compactList must be a boolean",
+			);
+		}
+		next.compactList = compactList;
+	}
+
+	return next;
+};
+
+/**
+ * Validates a settings update, one object per section.
+ * Pure function. Missing sections and keys keep their current value;
+ * without `current`, they take their default.
+ *
+ * @throws if a section is unknown or any value is invalid
+ */
+export const resolveAppSettings = (
+	input: unknown,
+	current?: AppSettings,
+): AppSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const sections = input as Record<string, unknown>;
+	for (const key of Object.keys(sections)) {
+		if (!isSectionKey(key)) throw invalid(`unknown section ${key}`);
+	}
+
+	return {
+		shortcuts: parseShortcutSettings(
+			sections.shortcuts ?? {},
+			current?.shortcuts,
+		),
+		retention: parseRetentionPolicy(
+			sections.retention ?? {},
+			current?.retention,
+		),
+		exclusions: parseAppExclusionSettings(
+			sections.exclusions ?? {},
+			current?.exclusions,
+		),
+		window: parseWindowBehaviorSettings(sections.window ?? {}, current?.window),
+		appearance: parseAppearanceSettings(
+			sections.appearance ?? {},
+			current?.appearance,
+		),
+	};
+};
+
+/**
+ * Reads a section's values, turning its spelling of "none" into null.
+ */
+const fromTomlSection = (
+	key: keyof AppSettings,
+	section: Record<string, TomlValue>,
+): Record<string, unknown> => {
+	const none = NONE_VALUES[key];
+	if (none === undefined) return section;
+	return Object.fromEntries(
+		Object.entries(section).map(([name, value]) => [
+			name,
+			value === none ? null : value,
+		]),
+	);
+};
+
+/**
+ * Reads the settings file.
+ * Pure function. The file describes settings in full: missing sections
+ * and keys take their default.
+ *
+ * @throws if the file is not valid TOML, is from a newer version, or
+ *   holds an invalid value
+ */
+export const parseSettingsToml = (text: string): AppSettings => {
+	const { version = SETTINGS_VERSION, ...tables } = parseToml(text);
+	if (typeof version !== "number" || !Number.isInteger(version)) {
+		throw invalid("version must be an integer");
+	}
+	if (version > SETTINGS_VERSION) {
+		throw invalid(
+			`version ${version} is newer than this app supports (${SETTINGS_VERSION})`,
+		);
+	}
+
+	const sections: Record<string, unknown> = {};
+	for (const [key, table] of Object.entries(tables)) {
+		if (!isSectionKey(key)) throw invalid(`unknown section ${key}`);
+		if (typeof table !== "object" || Array.isArray(table)) {
+			throw invalid(`${key} must be a table`);
+		}
+		sections[key] = fromTomlSection(key, table);
+	}
+	return resolveAppSettings(sections);
+};
+
+/**
+ * Writes settings as the settings file, with a comment per section.
+ * Pure function.
+ */
+export const formatSettingsToml = (settings: AppSettings): string => {
+	const lines = [FILE_HEADER, "", `version = ${SETTINGS_VERSION}`];
+	for (const key of SECTION_KEYS) {
+		lines.push("", `[${key}]`, SECTION_COMMENTS[key]);
+		for (const [name, value] of Object.entries(settings[key])) {
+			const written = (value ?? NONE_VALUES[key]) as TomlValue;
+			lines.push(`${formatTomlKey(name)} = ${formatTomlValue(written)}`);
+		}
+	}
+	return `${lines.join("\n")}\n`;
+};
+
+/**
+ * Lists the module sections that differ between two settings.
+ * Pure function.
+ */
+export const changedSections = (
+	previous: ModuleSettings,
+	next: ModuleSettings,
+): Partial<ModuleSettings> => {
+	const changes: Partial<Record<keyof ModuleSettings, unknown>> = {};
+	for (const key of Object.keys(next) as (keyof ModuleSettings)[]) {
+		if (JSON.stringify(previous[key]) !== JSON.stringify(next[key])) {
+			changes[key] = next[key];
+		}
+	}
+	return changes as Partial<ModuleSettings>;
+};
+
+// ============================================================================
+// Settings File Module
+// ============================================================================
+
+export const getSettingsFilePath = (userDataPath: string) =>
+	path.join(userDataPath, SETTINGS_FILENAME);
+
+export type SettingsFileDeps = {
+	userDataPath: string;
+	/** Current shortcut, retention, exclusion and window settings */
+	getModuleSettings: () => ModuleSettings;
+	/** Hands changed sections to their modules, which apply them live */
+	applyModuleSettings: (changes: Partial<ModuleSettings>) => void;
+	/** Called after settings change from the file or an update */
+	onChange?: (status: SettingsFileStatus) => void;
+};
+
+/**
+ * Creates the settings file: a versioned TOML file gathering shortcuts,
+ * retention, app exclusions, window behavior and appearance, so settings
+ * can be edited by hand or kept in dotfiles.
+ * The file is watched; a saved change is validated as a whole and then
+ * applied live (shortcuts are re-registered at once). The modules keep
+ * their own JSON files, and the TOML file is rewritten when settings
+ * change in the app.
+ */
+export const createSettingsFile = (deps: SettingsFileDeps) => {
+	const filePath = getSettingsFilePath(deps.userDataPath);
+	let appearance = { ...DEFAULT_APPEARANCE_SETTINGS };
+	let lastError: string | null = null;
+	/** Text last written or loaded, so our own writes are not reloaded */
+	let lastText: string | null = null;
+	let watcher: fs.FSWatcher | null = null;
+	let reloadTimer: NodeJS.Timeout | null = null;
+
+	const getSettings = (): AppSettings => ({
+		...deps.getModuleSettings(),
+		appearance: { ...appearance },
+	});
+
+	const getStatus = (): SettingsFileStatus => ({
+		path: filePath,
+		settings: getSettings(),
+		lastError,
+	});
+
+	const apply = (next: AppSettings): void => {
+		const { appearance: nextAppearance, ...modules } = next;
+		const changes = changedSections(deps.getModuleSettings(), modules);
+		if (Object.keys(changes).length > 0) deps.applyModuleSettings(changes);
+		appearance = { ...nextAppearance };
+	};
+
+	/**
+	 * Rewrites the file from the current settings; call after a module's
+	 * settings change elsewhere in the app. A file that failed to load is
+	 * left alone so hand edits are not lost while they are being fixed.
+	 */
+	const save = (): void => {
+		if (lastError !== null) return;
+		const text = formatSettingsToml(getSettings());
+		if (text === lastText) return;
+		fs.mkdirSync(path.dirname(filePath), { recursive: true });
+		fs.writeFileSync(filePath, text, "utf-8");
+		lastText = text;
+	};
+
+	/**
+	 * Loads the file if it changed. An invalid file is reported in the
+	 * status and leaves the current settings in place.
+	 */
+	const reload = (): void => {
+		let text: string;
+		try {
+			text = fs.readFileSync(filePath, "utf-8");
+		} catch (error) {
+			// Deleted, or mid-save; the next change event reloads it
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") return;
+			throw error;
+		}
+		if (text === lastText) return;
+
+		try {
+			apply(parseSettingsToml(text));
+			lastError = null;
+		} catch (error) {
+			lastError = error instanceof Error ? error.message : String(error);
+			console.error("Failed to load settings file:", error);
+		}
+		lastText = text;
+		deps.onChange?.(getStatus());
+	};
+
+	/**
+	 * Validates, applies and saves a settings update.
+	 */
+	const update = (input: unknown): SettingsFileStatus => {
+		const next = resolveAppSettings(input, getSettings());
+		apply(next);
+		lastError = null;
+		save();
+		deps.onChange?.(getStatus());
+		return getStatus();
+	};
+
+	/**
+	 * Loads the file, or creates it from the current settings, and starts
+	 * watching it.
+	 */
+	const start = (): void => {
+		if (fs.existsSync(filePath)) {
+			try {
+				reload();
+			} catch (error) {
+				console.error("Failed to read settings file:", error);
+			}
+		} else {
+			save();
+		}
+
+		if (watcher) return;
+		const onChange = (_event: string, filename: string | null) => {
+			if (filename !== SETTINGS_FILENAME) return;
+			if (reloadTimer) clearTimeout(reloadTimer);
+			reloadTimer = setTimeout(() => {
+				reloadTimer = null;
+				try {
+					reload();
+				} catch (error) {
+					console.error("Failed to reload settings file:", error);
+				}
+			}, RELOAD_DELAY_MS);
+		};
+		try {
+			// The folder is watched, as editors often replace the file on save
+			watcher = fs.watch(path.dirname(filePath), onChange);
+		} catch (error) {
+			console.error("Failed to watch settings file:", error);
+		}
+	};
+
+	const stop = (): void => {
+		watcher?.close();
+		watcher = null;
+		if (reloadTimer) {
+			clearTimeout(reloadTimer);
+			reloadTimer = null;
+		}
+	};
+
+	return { getStatus, update, save, start, stop };
+};
+
+export type SettingsFile = ReturnType<typeof createSettingsFile>;
@@ -0,0 +1,182 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import {
+	createChordManager,
+	getPasteRecentIndex,
+	groupChordsByPrefix,
+	parseChordSettings,
+} from "./shortcut-chords.js";
+import type { ShortcutRegistrar } from "./shortcuts.js";
+
+describe("parseChordSettings", () => {
+	it("accepts bare second keys and trims accelerators", () => {
+		expect(
+			parseChordSettings({
+				chords: [
+					{ prefix: " Alt+Shift+V ", key: "3", action: "pasteRecent3" },
+					{ prefix: "Alt+Shift+V", key: "Shift+P", action: "togglePicker" },
+				],
+			}),
+		).toEqual({
+			chords: [
+				{ prefix: "Alt+Shift+V", key: "3", action: "pasteRecent3" },
+				{ prefix: "Alt+Shift+V", key: "Shift+P", action: "togglePicker" },
+			],
+		});
+		expect(parseChordSettings({}).chords).toEqual([]);
+	});
+
+	it("rejects invalid chords", () => {
+		expect(() =>
+			parseChordSettings({
+				chords: [{ prefix: "V", key: "3", action: "togglePicker" }],
+			}),
+		).toThrow("prefix must be an accelerator");
+		expect(() =>
+			parseChordSettings({
+				chords: [{ prefix: "Alt+V", key: "33", action: "togglePicker" }],
+			}),
+		).toThrow("key must be a key");
+		expect(() =>
+			parseChordSettings({
+				chords: [{ prefix: "Alt+V", key: "3", action: "pasteRecent0" }],
+			}),
+		).toThrow("unknown action pasteRecent0");
+		expect(() =>
+			parseChordSettings({
+				chords: [
+					{ prefix: "Alt+V", key: "p", action: "togglePicker" },
+					{ prefix: "alt+v", key: "P", action: "toggleCapture" },
+				],
+			}),
+		).toThrow("alt+v then P is listed twice");
+	});
+});
+
+describe("getPasteRecentIndex", () => {
+	it("counts recent items from 0", () => {
+		expect(getPasteRecentIndex("pasteRecent1")).toBe(0);
+		expect(getPasteRecentIndex("pasteRecent9")).toBe(8);
+		expect(getPasteRecentIndex("togglePicker")).toBeNull();
+	});
+});
+
+describe("groupChordsByPrefix", () => {
+	it("groups prefixes ignoring case", () => {
+		const groups = groupChordsByPrefix([
+			{ prefix: "Alt+V", key: "1", action: "pasteRecent1" },
+			{ prefix: "Alt+Shift+V", key: "1", action: "togglePicker" },
+			{ prefix: "alt+v", key: "2", action: "pasteRecent2" },
+		]);
+
+		expect([...groups.keys()]).toEqual(["Alt+V", "Alt+Shift+V"]);
+		expect(groups.get("Alt+V")?.map(({ key }) => key)).toEqual(["1", "2"]);
+	});
+});
+
+describe("createChordManager", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-chords-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * Fake OS registry; accelerators in `taken` belong to other apps.
+	 */
+	const createRegistrar = (taken: string[] = []) => {
+		const active = new Map<string, () => void>();
+		const registrar: ShortcutRegistrar = {
+			register: vi.fn((accelerator: string, callback: () => void) => {
+				if (taken.includes(accelerator)) return false;
+				active.set(accelerator, callback);
+				return true;
+			}),
+			unregister: vi.fn((accelerator: string) => {
+				active.delete(accelerator);
+			}),
+		};
+		return { registrar, active };
+	};
+
+	const chords = [
+		{ prefix: "CommandOrControl+Alt+V", key: "3", action: "pasteRecent3" },
+		{ prefix: "CommandOrControl+Alt+V", key: "P", action: "togglePicker" },
+	];
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("runs the action of the key pressed after the prefix", () => {
+		const { registrar, active } = createRegistrar();
+		const run = vi.fn();
+		const manager = createChordManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			run,
+		});
+
+		manager.updateSettings({ chords });
+		expect([...active.keys()]).toEqual(["CommandOrControl+Alt+V"]);
+
+		active.get("CommandOrControl+Alt+V")?.();
+		expect([...active.keys()]).toEqual(["CommandOrControl+Alt+V", "3", "P"]);
+		active.get("3")?.();
+
+		expect(run).toHaveBeenCalledWith("pasteRecent3");
+		expect([...active.keys()]).toEqual(["CommandOrControl+Alt+V"]);
+	});
+
+	it("releases the second keys when none is pressed in time", () => {
+		const { registrar, active } = createRegistrar();
+		const run = vi.fn();
+		const manager = createChordManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			run,
+			timeoutMs: 500,
+		});
+		manager.updateSettings({ chords });
+
+		active.get("CommandOrControl+Alt+V")?.();
+		vi.advanceTimersByTime(500);
+
+		expect(active.size).toBe(1);
+		expect(run).not.toHaveBeenCalled();
+	});
+
+	it("reports prefixes taken by another application", () => {
+		const userDataPath = createTempUserDataPath();
+		createChordManager({
+			userDataPath,
+			registrar: createRegistrar().registrar,
+			run: vi.fn(),
+		}).updateSettings({ chords });
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+		const manager = createChordManager({
+			userDataPath,
+			registrar: createRegistrar(["CommandOrControl+Alt+V"]).registrar,
+			run: vi.fn(),
+		});
+		manager.registerAll();
+
+		expect(manager.getStatus()).toEqual({
+			chords,
+			unavailablePrefixes: ["CommandOrControl+Alt+V"],
+		});
+		errorSpy.mockRestore();
+	});
+});
@@ -0,0 +1,284 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	isShortcutAction,
+	isValidAccelerator,
+	isValidChordKey,
+	type ShortcutAction,
+	type ShortcutRegistrar,
+} from "./shortcuts.js";
+
+/**
+ * What a chord does: any global shortcut action, or paste the Nth most
+ * recent item like quick paste.
+ */
+export type ChordAction =
+	| ShortcutAction
+	| `pasteRecent${1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9}`;
+
+/**
+ * A two-step shortcut, e.g. `CommandOrControl+Alt+V` then `3`.
+ */
+export type ShortcutChord = {
+	/** Global accelerator that starts the chord */
+	prefix: string;
+	/** Key pressed next; modifiers are optional */
+	key: string;
+	action: ChordAction;
+};
+
+/**
+ * Persisted chord settings.
+ */
+export type ChordSettings = {
+	chords: ShortcutChord[];
+};
+
+/**
+ * Settings plus the prefixes that could not be registered.
+ */
+export type ChordStatus = ChordSettings & {
+	/** Prefixes another application or a global shortcut already holds */
+	unavailablePrefixes: string[];
+};
+
+/**
+ * How long a pressed prefix waits for its second key (ms).
+ */
+const CHORD_TIMEOUT_MS = 2000;
+
+const MAX_CHORDS = 100;
+
+const CHORDS_FILENAME = "shortcut-chords.json";
+
+const PASTE_RECENT_ACTION = /^pasteRecent[1-9]$/;
+
+const invalid = (message: string) =>
+	new Error(`Invalid shortcut chords: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const isChordAction = (value: unknown): value is ChordAction =>
+	isShortcutAction(value) ||
+	(typeof value === "string" && PASTE_RECENT_ACTION.test(value));
+
+/**
+ * Validates a chord settings update.
+ * Pure function. Accelerators are trimmed; missing keys keep their
+ * current value.
+ *
+ * @throws if a prefix is not an accelerator, a key is not a key, an
+ *   action is unknown, the same prefix and key appear twice, or there
+ *   are more than 100 chords
+ */
+export const parseChordSettings = (
+	input: unknown,
+	current: ChordSettings = { chords: [] },
+): ChordSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { chords } = input as Partial<Record<keyof ChordSettings, unknown>>;
+	if (chords === undefined) return { chords: [...current.chords] };
+	if (!Array.isArray(chords) || chords.length > MAX_CHORDS) {
+		throw invalid(`chords must be a list of at most ${MAX_CHORDS}`);
+	}
+
+	const seen = new Set<string>();
+	const next: ShortcutChord[] = [];
+	for (const entry of chords) {
+		const { prefix, key, action } = (entry ?? {}) as Partial<
+			Record<keyof ShortcutChord, unknown>
+		>;
+		if (typeof prefix !== "string" || !isValidAccelerator(prefix.trim())) {
+			throw invalid(
+				`prefix must be an accelerator like CommandOrControl+Alt+V, got ${String(prefix)}`,
+			);
+		}
+		if (typeof key !== "string" || !isValidChordKey(key.trim())) {
+			throw invalid(`key must be a key like 3 or P, got ${String(key)}`);
+		}
+		if (!isChordAction(action)) {
+			throw invalid(`unknown action ${String(action)}`);
+		}
+		const chord = { prefix: prefix.trim(), key: key.trim(), action };
+		const id = `${chord.prefix} ${chord.key}`.toLowerCase();
+		if (seen.has(id)) {
+			throw invalid(`${chord.prefix} then ${chord.key} is listed twice`);
+		}
+		seen.add(id);
+		next.push(chord);
+	}
+
+	return { chords: next };
+};
+
+/**
+ * The recent item a `pasteRecentN` action pastes, counting from 0, or
+ * null for a shortcut action.
+ * Pure function.
+ */
+export const getPasteRecentIndex = (action: ChordAction): number | null =>
+	PASTE_RECENT_ACTION.test(action) ? Number(action.slice(-1)) - 1 : null;
+
+/**
+ * Groups chords by prefix, ignoring case; each group is registered as
+ * one global shortcut under its first spelling.
+ * Pure function.
+ */
+export const groupChordsByPrefix = (
+	chords: readonly ShortcutChord[],
+): Map<string, ShortcutChord[]> => {
+	const groups = new Map<string, ShortcutChord[]>();
+	const spellings = new Map<string, string>();
+	for (const chord of chords) {
+		const id = chord.prefix.toLowerCase();
+		const prefix = spellings.get(id) ?? chord.prefix;
+		spellings.set(id, prefix);
+		groups.set(prefix, [...(groups.get(prefix) ?? []), chord]);
+	}
+	return groups;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getChordsPath = (userDataPath: string) =>
+	path.join(userDataPath, CHORDS_FILENAME);
+
+/**
+ * Reads saved chords, falling back to none if missing or invalid.
+ */
+const readChordsFromFile = (filePath: string): ChordSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { chords: [] };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseChordSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read shortcut chords:", error);
+		return { chords: [] };
+	}
+};
+
+const writeChordsToFile = (filePath: string, settings: ChordSettings) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Chord Manager
+// ============================================================================
+
+export type ChordManagerDeps = {
+	userDataPath: string;
+	registrar: ShortcutRegistrar;
+	/** Runs a chord's action once its second key is pressed */
+	run: (action: ChordAction) => void;
+	timeoutMs?: number;
+};
+
+/**
+ * Creates the chord manager, so one prefix hotkey can fan out to many
+ * actions without using up global accelerators. Only prefixes are held
+ * with the OS; pressing one arms the chord: its second keys are grabbed
+ * like the register prompt's, until one is pressed, the prefix is
+ * pressed again or 2 seconds pass, so typing is otherwise unaffected.
+ */
+export const createChordManager = (deps: ChordManagerDeps) => {
+	const filePath = getChordsPath(deps.userDataPath);
+	const timeoutMs = deps.timeoutMs ?? CHORD_TIMEOUT_MS;
+	let settings = readChordsFromFile(filePath);
+	/** Prefixes currently held with the OS */
+	const prefixes: string[] = [];
+	let unavailablePrefixes: string[] = [];
+	/** Second keys grabbed while a chord is armed */
+	const armedKeys: string[] = [];
+	let timer: ReturnType<typeof setTimeout> | null = null;
+
+	const getStatus = (): ChordStatus => ({
+		chords: [...settings.chords],
+		unavailablePrefixes: [...unavailablePrefixes],
+	});
+
+	/**
+	 * Releases the second keys without running anything.
+	 */
+	const disarm = (): void => {
+		if (timer) {
+			clearTimeout(timer);
+			timer = null;
+		}
+		for (const key of armedKeys.splice(0)) {
+			deps.registrar.unregister(key);
+		}
+	};
+
+	/**
+	 * Waits for one of the second keys of a prefix's chords. The keys of
+	 * the pending chord are released first, so pressing the prefix again
+	 * starts over. Keys taken by another application are skipped.
+	 */
+	const arm = (chords: readonly ShortcutChord[]): void => {
+		disarm();
+		for (const chord of chords) {
+			const ok = deps.registrar.register(chord.key, () => {
+				disarm();
+				deps.run(chord.action);
+			});
+			if (ok) armedKeys.push(chord.key);
+		}
+		if (armedKeys.length > 0) {
+			timer = setTimeout(disarm, timeoutMs);
+		}
+	};
+
+	const unregisterAll = (): void => {
+		disarm();
+		for (const prefix of prefixes.splice(0)) {
+			deps.registrar.unregister(prefix);
+		}
+	};
+
+	/**
+	 * Registers every prefix, releasing those held before. One taken by
+	 * another application, or by a global shortcut, is logged and
+	 * reported in the status.
+	 */
+	const registerAll = (): void => {
+		unregisterAll();
+		unavailablePrefixes = [];
+		for (const [prefix, chords] of groupChordsByPrefix(settings.chords)) {
+			if (deps.registrar.register(prefix, () => arm(chords))) {
+				prefixes.push(prefix);
+			} else {
+				unavailablePrefixes.push(prefix);
+				console.error(
+					`Chord prefix registration failed for "${prefix}". ` +
+						"The shortcut may already be in use.",
+				);
+			}
+		}
+	};
+
+	/**
+	 * Replaces the chords and re-registers their prefixes at once.
+	 */
+	const updateSettings = (input: unknown): ChordStatus => {
+		const next = parseChordSettings(input, settings);
+		writeChordsToFile(filePath, next);
+		settings = next;
+		registerAll();
+		return getStatus();
+	};
+
+	return { getStatus, registerAll, updateSettings, unregisterAll };
+};
+
+export type ChordManager = ReturnType<typeof createChordManager>;
@@ -0,0 +1,370 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createShortcutManager,
+	getShortcutsPath,
+	isValidAccelerator,
+	parseShortcutFallbacks,
+	parseShortcutSettings,
+	type ShortcutRegistrar,
+} from "./shortcuts.js";
+
+describe("isValidAccelerator", () => {
+	it("accepts modifiers followed by one key", () => {
+		expect(isValidAccelerator("CommandOrControl+Shift+V")).toBe(true);
+		expect(isValidAccelerator("Alt+Space")).toBe(true);
+		expect(isValidAccelerator("ctrl+alt+/")).toBe(true);
+		expect(isValidAccelerator("Super+num5")).toBe(true);
+		expect(isValidAccelerator("F13")).toBe(true);
+	});
+
+	it("rejects malformed accelerators", () => {
+		expect(isValidAccelerator("")).toBe(false);
+		expect(isValidAccelerator("V")).toBe(false);
+		expect(isValidAccelerator("Shift+Shift+V")).toBe(false);
+		expect(isValidAccelerator("Ctrl+Hyper+V")).toBe(false);
+		expect(isValidAccelerator("Ctrl+Shift")).toBe(false);
+		expect(isValidAccelerator("Ctrl+VV")).toBe(false);
+		expect(isValidAccelerator("Ctrl+F25")).toBe(false);
+	});
+});
+
+describe("parseShortcutSettings", () => {
+	it("applies updates over the current bindings", () => {
+		expect(
+			parseShortcutSettings({
+				pasteLastItem: " Alt+Shift+V ",
+				togglePicker: null,
+			}),
+		).toEqual({
+			togglePicker: null,
+			toggleQuickPicker: "CommandOrControl+Shift+Space",
+			pasteLastItem: "Alt+Shift+V",
+			pastePlainText: null,
+			pasteNextQueued: "CommandOrControl+Alt+V",
+			pushClipboard: null,
+			popClipboard: null,
+			copyToRegister: null,
+			pasteRegister: null,
+			toggleAppendCopy: null,
+			toggleCapture: null,
+		});
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseShortcutSettings("Ctrl+V")).toThrow("expected an object");
+		expect(() => parseShortcutSettings({ toggleCapture: "P" })).toThrow(
+			"toggleCapture must be an accelerator",
+		);
+		expect(() => parseShortcutSettings({ toggleCapture: 1 })).toThrow(
+			"toggleCapture must be an accelerator",
+		);
+	});
+
+	it("rejects an accelerator bound to two actions", () => {
+		expect(() =>
+			parseShortcutSettings({ toggleCapture: "commandorcontrol+shift+v" }),
+		).toThrow("already bound to togglePicker");
+	});
+});
+
+describe("parseShortcutFallbacks", () => {
+	it("replaces the lists given", () => {
+		const fallbacks = parseShortcutFallbacks({
+			togglePicker: [" Alt+Shift+V ", "F13"],
+			pasteNextQueued: [],
+		});
+
+		expect(fallbacks.togglePicker).toEqual(["Alt+Shift+V", "F13"]);
+		expect(fallbacks.pasteNextQueued).toEqual([]);
+		expect(fallbacks.toggleQuickPicker).toEqual(["CommandOrControl+Alt+Space"]);
+	});
+
+	it("rejects invalid lists", () => {
+		expect(() => parseShortcutFallbacks({ togglePicker: "F13" })).toThrow(
+			"togglePicker must be a list of at most 3 accelerators",
+		);
+		expect(() =>
+			parseShortcutFallbacks({ togglePicker: ["F1", "F2", "F3", "F4"] }),
+		).toThrow("at most 3 accelerators");
+		expect(() =>
+			parseShortcutFallbacks({ togglePicker: ["F1", "f1"] }),
+		).toThrow("lists an accelerator twice");
+	});
+});
+
+describe("createShortcutManager", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-keys-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * Fake OS registry; accelerators in `taken` belong to other apps.
+	 */
+	const createRegistrar = (taken: string[] = []) => {
+		const active = new Map<string, () => void>();
+		const registrar: ShortcutRegistrar = {
+			register: vi.fn((accelerator: string, callback: () => void) => {
+				if (taken.includes(accelerator) || active.has(accelerator)) {
+					return false;
+				}
+				active.set(accelerator, callback);
+				return true;
+			}),
+			unregister: vi.fn((accelerator: string) => {
+				active.delete(accelerator);
+			}),
+		};
+		return { registrar, active };
+	};
+
+	const createHandlers = () => ({
+		togglePicker: vi.fn(),
+		toggleQuickPicker: vi.fn(),
+		pasteLastItem: vi.fn(),
+		pastePlainText: vi.fn(),
+		pasteNextQueued: vi.fn(),
+		pushClipboard: vi.fn(),
+		popClipboard: vi.fn(),
+		copyToRegister: vi.fn(),
+		pasteRegister: vi.fn(),
+		toggleAppendCopy: vi.fn(),
+		toggleCapture: vi.fn(),
+	});
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("registers the default shortcuts", () => {
+		const { registrar, active } = createRegistrar();
+		const handlers = createHandlers();
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			handlers,
+		});
+
+		manager.registerAll();
+		active.get("CommandOrControl+Shift+V")?.();
+
+		expect([...active.keys()]).toEqual([
+			"CommandOrControl+Shift+V",
+			"CommandOrControl+Shift+Space",
+			"CommandOrControl+Alt+V",
+		]);
+		expect(handlers.togglePicker).toHaveBeenCalledOnce();
+	});
+
+	it("uses a fallback when another app holds the binding", () => {
+		const { registrar, active } = createRegistrar(["CommandOrControl+Shift+V"]);
+		const handlers = createHandlers();
+		const onStatusChanged = vi.fn();
+		const warnSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			handlers,
+			onStatusChanged,
+		});
+
+		manager.registerAll();
+		active.get("CommandOrControl+Alt+Shift+V")?.();
+
+		expect(handlers.togglePicker).toHaveBeenCalledOnce();
+		const status = manager.getStatus();
+		expect(status.togglePicker).toEqual({
+			active: "CommandOrControl+Alt+Shift+V",
+			conflicts: ["CommandOrControl+Shift+V"],
+		});
+		expect(status.toggleQuickPicker).toEqual({
+			active: "CommandOrControl+Shift+Space",
+			conflicts: [],
+		});
+		expect(onStatusChanged).toHaveBeenCalledWith(status);
+		warnSpy.mockRestore();
+	});
+
+	it("skips fallbacks bound to other actions and retries new ones", () => {
+		const userDataPath = createTempUserDataPath();
+		const { registrar, active } = createRegistrar([
+			"CommandOrControl+Shift+V",
+			"CommandOrControl+Alt+Shift+V",
+		]);
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+		const warnSpy = vi.spyOn(console, "warn").mockImplementation(() => {});
+		const manager = createShortcutManager({
+			userDataPath,
+			registrar,
+			handlers: createHandlers(),
+		});
+		manager.registerAll();
+
+		expect(manager.getStatus().togglePicker).toEqual({
+			active: null,
+			conflicts: ["CommandOrControl+Shift+V", "CommandOrControl+Alt+Shift+V"],
+		});
+
+		manager.setFallbacks("togglePicker", [
+			"CommandOrControl+Shift+Space",
+			"Alt+Shift+V",
+		]);
+
+		expect(manager.getStatus().togglePicker.active).toBe("Alt+Shift+V");
+		expect(active.size).toBe(3);
+		expect(
+			createShortcutManager({
+				userDataPath,
+				registrar: createRegistrar().registrar,
+				handlers: createHandlers(),
+			}).getFallbacks().togglePicker,
+		).toEqual(["CommandOrControl+Shift+Space", "Alt+Shift+V"]);
+		errorSpy.mockRestore();
+		warnSpy.mockRestore();
+	});
+
+	it("rebinds an action at runtime and persists it", () => {
+		const userDataPath = createTempUserDataPath();
+		const { registrar, active } = createRegistrar();
+		const handlers = createHandlers();
+		const manager = createShortcutManager({
+			userDataPath,
+			registrar,
+			handlers,
+		});
+		manager.registerAll();
+
+		manager.setShortcut("togglePicker", "Alt+Space");
+		manager.setShortcut("toggleCapture", "Alt+Shift+P");
+		active.get("Alt+Shift+P")?.();
+
+		expect([...active.keys()].sort()).toEqual([
+			"Alt+Shift+P",
+			"Alt+Space",
+			"CommandOrControl+Alt+V",
+			"CommandOrControl+Shift+Space",
+		]);
+		expect(handlers.toggleCapture).toHaveBeenCalledOnce();
+		expect(
+			createShortcutManager({
+				userDataPath,
+				registrar: createRegistrar().registrar,
+				handlers,
+			}).getSettings(),
+		).toEqual({
+			togglePicker: "Alt+Space",
+			toggleQuickPicker: "CommandOrControl+Shift+Space",
+			pasteLastItem: null,
+			pastePlainText: null,
+			pasteNextQueued: "CommandOrControl+Alt+V",
+			pushClipboard: null,
+			popClipboard: null,
+			copyToRegister: null,
+			pasteRegister: null,
+			toggleAppendCopy: null,
+			toggleCapture: "Alt+Shift+P",
+		});
+	});
+
+	it("unbinds an action with null", () => {
+		const { registrar, active } = createRegistrar();
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			handlers: createHandlers(),
+		});
+		manager.registerAll();
+
+		expect(manager.setShortcut("togglePicker", null).togglePicker).toBeNull();
+		expect(active.has("CommandOrControl+Shift+V")).toBe(false);
+	});
+
+	it("swaps two bindings in one update", () => {
+		const { registrar, active } = createRegistrar();
+		const handlers = createHandlers();
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			handlers,
+		});
+		manager.registerAll();
+
+		manager.updateSettings({
+			togglePicker: "CommandOrControl+Shift+Space",
+			toggleQuickPicker: "CommandOrControl+Shift+V",
+		});
+		active.get("CommandOrControl+Shift+V")?.();
+
+		expect(handlers.toggleQuickPicker).toHaveBeenCalledOnce();
+		expect(handlers.togglePicker).not.toHaveBeenCalled();
+	});
+
+	it("keeps the previous binding when the accelerator is taken", () => {
+		const userDataPath = createTempUserDataPath();
+		const { registrar, active } = createRegistrar(["Alt+Space"]);
+		const manager = createShortcutManager({
+			userDataPath,
+			registrar,
+			handlers: createHandlers(),
+		});
+		manager.registerAll();
+
+		expect(() => manager.setShortcut("togglePicker", "Alt+Space")).toThrow(
+			"Shortcut Alt+Space is unavailable",
+		);
+
+		expect(active.has("CommandOrControl+Shift+V")).toBe(true);
+		expect(manager.getSettings().togglePicker).toBe("CommandOrControl+Shift+V");
+		expect(fs.existsSync(getShortcutsPath(userDataPath))).toBe(false);
+	});
+
+	it("rejects unknown actions", () => {
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar: createRegistrar().registrar,
+			handlers: createHandlers(),
+		});
+
+		expect(() => manager.setShortcut("quit", "Alt+Q")).toThrow(
+			"Invalid shortcut action: quit",
+		);
+	});
+
+	it("releases every registration", () => {
+		const { registrar, active } = createRegistrar();
+		const manager = createShortcutManager({
+			userDataPath: createTempUserDataPath(),
+			registrar,
+			handlers: createHandlers(),
+		});
+		manager.registerAll();
+		manager.setShortcut("pasteLastItem", "Alt+Shift+V");
+
+		manager.unregisterAll();
+
+		expect(active.size).toBe(0);
+	});
+
+	it("falls back to defaults when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getShortcutsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+		const manager = createShortcutManager({
+			userDataPath,
+			registrar: createRegistrar().registrar,
+			handlers: createHandlers(),
+		});
+
+		expect(manager.getSettings().togglePicker).toBe("CommandOrControl+Shift+V");
+		errorSpy.mockRestore();
+	});
+});
@@ -0,0 +1,572 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Actions that can be bound to a global shortcut.
+ */
+export type ShortcutAction =
+	| "togglePicker"
+	| "toggleQuickPicker"
+	| "pasteLastItem"
+	| "pastePlainText"
+	| "pasteNextQueued"
+	| "pushClipboard"
+	| "popClipboard"
+	| "copyToRegister"
+	| "pasteRegister"
+	| "toggleAppendCopy"
+	| "toggleCapture";
+
+/**
+ * Persisted accelerator per action; null leaves the action unbound.
+ */
+export type ShortcutSettings = Record<ShortcutAction, string | null>;
+
+/**
+ * Accelerators tried in order when an action's own is taken by another
+ * application.
+ */
+export type ShortcutFallbacks = Record<ShortcutAction, string[]>;
+
+/**
+ * What an action's shortcut ended up as with the OS.
+ */
+export type ShortcutRegistration = {
+	/** Accelerator held for the action: its binding, a fallback or none */
+	active: string | null;
+	/** Accelerators refused because another application holds them */
+	conflicts: string[];
+};
+
+export type ShortcutStatus = Record<ShortcutAction, ShortcutRegistration>;
+
+/**
+ * Registers accelerators with the OS; Electron's `globalShortcut` in the
+ * app, a fake in tests.
+ */
+export type ShortcutRegistrar = {
+	/** Resolves false if the accelerator is taken by another application */
+	register: (accelerator: string, callback: () => void) => boolean;
+	unregister: (accelerator: string) => void;
+};
+
+const SHORTCUT_ACTIONS: readonly ShortcutAction[] = [
+	"togglePicker",
+	"toggleQuickPicker",
+	"pasteLastItem",
+	"pastePlainText",
+	"pasteNextQueued",
+	"pushClipboard",
+	"popClipboard",
+	"copyToRegister",
+	"pasteRegister",
+	"toggleAppendCopy",
+	"toggleCapture",
+];
+
+const DEFAULT_SHORTCUT_SETTINGS: ShortcutSettings = {
+	togglePicker: "CommandOrControl+Shift+V",
+	toggleQuickPicker: "CommandOrControl+Shift+Space",
+	pasteLastItem: null,
+	pastePlainText: null,
+	pasteNextQueued: "CommandOrControl+Alt+V",
+	pushClipboard: null,
+	popClipboard: null,
+	copyToRegister: null,
+	pasteRegister: null,
+	toggleAppendCopy: null,
+	toggleCapture: null,
+};
+
+/**
+ * Used if the default bindings are taken, e.g. by another clipboard
+ * manager.
+ */
+const DEFAULT_SHORTCUT_FALLBACKS: ShortcutFallbacks = {
+	togglePicker: ["CommandOrControl+Alt+Shift+V"],
+	toggleQuickPicker: ["CommandOrControl+Alt+Space"],
+	pasteLastItem: [],
+	pastePlainText: [],
+	pasteNextQueued: ["CommandOrControl+Alt+Shift+N"],
+	pushClipboard: [],
+	popClipboard: [],
+	copyToRegister: [],
+	pasteRegister: [],
+	toggleAppendCopy: [],
+	toggleCapture: [],
+};
+
+const MAX_SHORTCUT_FALLBACKS = 3;
+
+const SHORTCUTS_FILENAME = "shortcuts.json";
+
+const MODIFIERS = new Set([
+	"command",
+	"cmd",
+	"control",
+	"ctrl",
+	"commandorcontrol",
+	"cmdorctrl",
+	"alt",
+	"option",
+	"altgr",
+	"shift",
+	"super",
+	"meta",
+]);
+
+const NAMED_KEYS = new Set([
+	"plus",
+	"space",
+	"tab",
+	"capslock",
+	"numlock",
+	"scrolllock",
+	"backspace",
+	"delete",
+	"insert",
+	"return",
+	"enter",
+	"up",
+	"down",
+	"left",
+	"right",
+	"home",
+	"end",
+	"pageup",
+	"pagedown",
+	"escape",
+	"esc",
+	"volumeup",
+	"volumedown",
+	"volumemute",
+	"medianexttrack",
+	"mediaprevioustrack",
+	"mediastop",
+	"mediaplaypause",
+	"printscreen",
+	"numdec",
+	"numadd",
+	"numsub",
+	"nummult",
+	"numdiv",
+]);
+
+/**
+ * Single-character keys: letters, digits and the punctuation Electron
+ * accepts. `+` is spelled `Plus`, since it separates the parts.
+ */
+const CHARACTER_KEY = /^[a-z0-9)!@#$%^&*(:;<=>,_\-.?/~`{}[\]|\\'"]$/;
+
+const FUNCTION_KEY = /^f([1-9]|1[0-9]|2[0-4])$/;
+
+const NUMPAD_DIGIT_KEY = /^num[0-9]$/;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const checkAccelerator = (
+	accelerator: string,
+	allowBareKey: boolean,
+): boolean => {
+	const parts = accelerator.toLowerCase().split("+");
+	const key = parts.pop() ?? "";
+	if (parts.some((part) => !MODIFIERS.has(part))) return false;
+	if (new Set(parts).size !== parts.length) return false;
+
+	if (FUNCTION_KEY.test(key)) return true;
+	if (parts.length === 0 && !allowBareKey) return false;
+	return (
+		NAMED_KEYS.has(key) || NUMPAD_DIGIT_KEY.test(key) || CHARACTER_KEY.test(key)
+	);
+};
+
+/**
+ * Checks an accelerator string against Electron's grammar, e.g.
+ * `CommandOrControl+Shift+V`: distinct modifiers, then exactly one key.
+ * Pure function. Matching ignores case. Only function keys may be bound
+ * without a modifier, so a global shortcut cannot swallow plain typing.
+ */
+export const isValidAccelerator = (accelerator: string): boolean =>
+	checkAccelerator(accelerator, false);
+
+/**
+ * Checks the key pressed after a chord's prefix: an accelerator, or a
+ * key without modifiers, since it is only grabbed for a moment after
+ * the prefix.
+ * Pure function.
+ */
+export const isValidChordKey = (key: string): boolean =>
+	checkAccelerator(key, true);
+
+/**
+ * Checks whether a value names a shortcut action.
+ * Pure function.
+ */
+export const isShortcutAction = (value: unknown): value is ShortcutAction =>
+	SHORTCUT_ACTIONS.includes(value as ShortcutAction);
+
+/**
+ * Validates a shortcut settings update.
+ * Pure function. Accelerators are trimmed; missing keys keep their
+ * current value.
+ *
+ * @throws if an accelerator is not a valid accelerator string or null, or
+ *   the same accelerator is bound to two actions
+ */
+export const parseShortcutSettings = (
+	input: unknown,
+	current: ShortcutSettings = DEFAULT_SHORTCUT_SETTINGS,
+): ShortcutSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid shortcuts: expected an object");
+	}
+
+	const values = input as Partial<Record<ShortcutAction, unknown>>;
+	const next: ShortcutSettings = { ...current };
+
+	for (const action of SHORTCUT_ACTIONS) {
+		const value = values[action];
+		if (value === undefined) continue;
+		if (value === null) {
+			next[action] = null;
+			continue;
+		}
+		if (typeof value !== "string" || !isValidAccelerator(value.trim())) {
+			throw new Error(
+				`Invalid shortcuts: ${action} must be an accelerator like CommandOrControl+Shift+V`,
+			);
+		}
+		next[action] = value.trim();
+	}
+
+	const seen = new Map<string, ShortcutAction>();
+	for (const action of SHORTCUT_ACTIONS) {
+		const accelerator = next[action];
+		if (accelerator === null) continue;
+		const other = seen.get(accelerator.toLowerCase());
+		if (other) {
+			throw new Error(
+				`Invalid shortcuts: ${accelerator} is already bound to ${other}`,
+			);
+		}
+		seen.set(accelerator.toLowerCase(), action);
+	}
+
+	return next;
+};
+
+/**
+ * Validates a shortcut fallbacks update.
+ * Pure function. Accelerators are trimmed; missing keys keep their
+ * current list.
+ *
+ * @throws if a list is not an array of at most 3 distinct accelerators
+ */
+export const parseShortcutFallbacks = (
+	input: unknown,
+	current: ShortcutFallbacks = DEFAULT_SHORTCUT_FALLBACKS,
+): ShortcutFallbacks => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid shortcut fallbacks: expected an object");
+	}
+
+	const values = input as Partial<Record<ShortcutAction, unknown>>;
+	const next: ShortcutFallbacks = { ...current };
+
+	for (const action of SHORTCUT_ACTIONS) {
+		const value = values[action];
+		if (value === undefined) continue;
+		if (
+			!Array.isArray(value) ||
+			value.length > MAX_SHORTCUT_FALLBACKS ||
+			!value.every(
+				(item) => typeof item === "string" && isValidAccelerator(item.trim()),
+			)
+		) {
+			throw new Error(
+				`Invalid shortcut fallbacks: ${action} must be a list of at most ${MAX_SHORTCUT_FALLBACKS} accelerators`,
+			);
+		}
+		const accelerators = value.map((item: string) => item.trim());
+		const distinct = new Set(accelerators.map((item) => item.toLowerCase()));
+		if (distinct.size !== accelerators.length) {
+			throw new Error(
+				`Invalid shortcut fallbacks: ${action} lists an accelerator twice`,
+			);
+		}
+		next[action] = accelerators;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getShortcutsPath = (userDataPath: string) =>
+	path.join(userDataPath, SHORTCUTS_FILENAME);
+
+/**
+ * Bindings and fallbacks as saved together: the bindings at the top
+ * level, the fallbacks under `fallbacks`.
+ */
+type ShortcutsFile = {
+	settings: ShortcutSettings;
+	fallbacks: ShortcutFallbacks;
+};
+
+/**
+ * Reads saved shortcuts, falling back to defaults if missing or invalid.
+ */
+const readShortcutsFromFile = (filePath: string): ShortcutsFile => {
+	const defaults = {
+		settings: { ...DEFAULT_SHORTCUT_SETTINGS },
+		fallbacks: { ...DEFAULT_SHORTCUT_FALLBACKS },
+	};
+	if (!fs.existsSync(filePath)) {
+		return defaults;
+	}
+
+	try {
+		const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+		const { fallbacks } = (raw ?? {}) as { fallbacks?: unknown };
+		return {
+			settings: parseShortcutSettings(raw),
+			fallbacks:
+				fallbacks === undefined
+					? defaults.fallbacks
+					: parseShortcutFallbacks(fallbacks),
+		};
+	} catch (error) {
+		console.error("Failed to read shortcuts, using defaults:", error);
+		return defaults;
+	}
+};
+
+const writeShortcutsToFile = (filePath: string, file: ShortcutsFile) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(
+		filePath,
+		JSON.stringify({ ...file.settings, fallbacks: file.fallbacks }, null, 2),
+		"utf-8",
+	);
+};
+
+// ============================================================================
+// Shortcut Manager
+// ============================================================================
+
+export type ShortcutManagerDeps = {
+	userDataPath: string;
+	registrar: ShortcutRegistrar;
+	/** What each action does when its shortcut is pressed */
+	handlers: Record<ShortcutAction, () => void>;
+	/** Called after registering, with what each action ended up holding */
+	onStatusChanged?: (status: ShortcutStatus) => void;
+};
+
+/**
+ * Creates the global shortcut manager.
+ * Keeps the OS registrations in step with the saved bindings, so a
+ * rebinding takes effect immediately without a restart. A binding taken
+ * by another application falls back to the first of the action's
+ * fallbacks the OS grants; the refused ones are reported in the status.
+ */
+export const createShortcutManager = (deps: ShortcutManagerDeps) => {
+	const filePath = getShortcutsPath(deps.userDataPath);
+	let { settings, fallbacks } = readShortcutsFromFile(filePath);
+	/** Accelerators currently held with the OS, by action */
+	const registered = new Map<ShortcutAction, string>();
+	/** Accelerators other applications refused to give up, by action */
+	const conflicts = new Map<ShortcutAction, string[]>();
+
+	const getSettings = (): ShortcutSettings => ({ ...settings });
+
+	const getFallbacks = (): ShortcutFallbacks => ({ ...fallbacks });
+
+	const getStatus = (): ShortcutStatus =>
+		Object.fromEntries(
+			SHORTCUT_ACTIONS.map((action) => [
+				action,
+				{
+					active: registered.get(action) ?? null,
+					conflicts: [...(conflicts.get(action) ?? [])],
+				},
+			]),
+		) as ShortcutStatus;
+
+	const register = (action: ShortcutAction, accelerator: string): boolean => {
+		const ok = deps.registrar.register(accelerator, deps.handlers[action]);
+		if (ok) registered.set(action, accelerator);
+		return ok;
+	};
+
+	const release = (action: ShortcutAction): void => {
+		conflicts.delete(action);
+		const accelerator = registered.get(action);
+		if (accelerator === undefined) return;
+		deps.registrar.unregister(accelerator);
+		registered.delete(action);
+	};
+
+	/**
+	 * Whether another action is bound to or holds an accelerator, so a
+	 * fallback never takes a combination this app already uses.
+	 */
+	const isUsedByOtherAction = (
+		action: ShortcutAction,
+		accelerator: string,
+	): boolean => {
+		const key = accelerator.toLowerCase();
+		return SHORTCUT_ACTIONS.some(
+			(other) =>
+				other !== action &&
+				(settings[other]?.toLowerCase() === key ||
+					registered.get(other)?.toLowerCase() === key),
+		);
+	};
+
+	/**
+	 * Registers an action's binding, or else the first of its fallbacks
+	 * the OS grants, and records the accelerators that were refused.
+	 */
+	const registerWithFallbacks = (
+		action: ShortcutAction,
+		accelerator: string,
+	): void => {
+		const refused: string[] = [];
+		for (const candidate of [accelerator, ...fallbacks[action]]) {
+			if (candidate !== accelerator && isUsedByOtherAction(action, candidate)) {
+				continue;
+			}
+			if (register(action, candidate)) break;
+			refused.push(candidate);
+		}
+		if (refused.length === 0) return;
+
+		conflicts.set(action, refused);
+		const active = registered.get(action);
+		if (active) {
+			console.warn(
+				`Global shortcut "${accelerator}" is in use by another application; ${action} uses "${active}" instead.`,
+			);
+		} else {
+			console.error(
+				`Global shortcut registration failed for "${accelerator}" and its fallbacks. ` +
+					"The shortcut may already be in use by another application.",
+			);
+		}
+	};
+
+	/**
+	 * Registers every bound action not registered yet. A shortcut taken by
+	 * another application is replaced by a fallback if one is free; the
+	 * binding is kept for the next launch either way.
+	 */
+	const registerAll = (): void => {
+		for (const action of SHORTCUT_ACTIONS) {
+			const accelerator = settings[action];
+			if (accelerator === null || registered.has(action)) continue;
+			registerWithFallbacks(action, accelerator);
+		}
+		deps.onStatusChanged?.(getStatus());
+	};
+
+	/**
+	 * Binds an action to an accelerator, or unbinds it with null.
+	 * If the OS refuses the new accelerator, fallbacks are not tried: the
+	 * previous registration is restored and nothing is saved.
+	 *
+	 * @throws if the action or accelerator is invalid, already bound to
+	 *   another action, or in use by another application
+	 */
+	const setShortcut = (
+		action: unknown,
+		accelerator: unknown,
+	): ShortcutSettings => {
+		if (!isShortcutAction(action)) {
+			throw new Error(`Invalid shortcut action: ${String(action)}`);
+		}
+		const next = parseShortcutSettings({ [action]: accelerator }, settings);
+		const target = next[action];
+
+		if (target !== registered.get(action)) {
+			const held = registered.get(action);
+			const refused = conflicts.get(action);
+			release(action);
+			if (target !== null && !register(action, target)) {
+				if (held !== undefined) register(action, held);
+				if (refused) conflicts.set(action, refused);
+				throw new Error(
+					`Shortcut ${target} is unavailable; it may be in use by another application`,
+				);
+			}
+		}
+
+		writeShortcutsToFile(filePath, { settings: next, fallbacks });
+		settings = next;
+		deps.onStatusChanged?.(getStatus());
+		return getSettings();
+	};
+
+	/**
+	 * Replaces the fallbacks of an action. An action left without a
+	 * shortcut by a conflict tries the new ones at once.
+	 *
+	 * @throws if the action or the list is invalid
+	 */
+	const setFallbacks = (
+		action: unknown,
+		accelerators: unknown,
+	): ShortcutFallbacks => {
+		if (!isShortcutAction(action)) {
+			throw new Error(`Invalid shortcut action: ${String(action)}`);
+		}
+		const next = parseShortcutFallbacks({ [action]: accelerators }, fallbacks);
+		writeShortcutsToFile(filePath, { settings, fallbacks: next });
+		fallbacks = next;
+		registerAll();
+		return getFallbacks();
+	};
+
+	/**
+	 * Replaces several bindings at once, e.g. from the settings file, so
+	 * two actions can swap accelerators. Unlike `setShortcut`, a shortcut
+	 * taken by another application is logged and kept, as at startup.
+	 *
+	 * @throws if an accelerator is invalid or bound to two actions
+	 */
+	const updateSettings = (input: unknown): ShortcutSettings => {
+		const next = parseShortcutSettings(input, settings);
+		for (const action of SHORTCUT_ACTIONS) {
+			if (next[action] !== registered.get(action)) release(action);
+		}
+		writeShortcutsToFile(filePath, { settings: next, fallbacks });
+		settings = next;
+		registerAll();
+		return getSettings();
+	};
+
+	/**
+	 * Releases every registration, e.g. before quitting.
+	 */
+	const unregisterAll = (): void => {
+		for (const action of SHORTCUT_ACTIONS) release(action);
+	};
+
+	return {
+		getSettings,
+		getFallbacks,
+		getStatus,
+		registerAll,
+		setShortcut,
+		setFallbacks,
+		updateSettings,
+		unregisterAll,
+	};
+};
+
+export type ShortcutManager = ReturnType<typeof createShortcutManager>;
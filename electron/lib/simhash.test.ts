@@ -0,0 +1,34 @@
+import { describe, expect, it } from "vitest";
+import { hammingDistance } from "./perceptual-hash.js";
+import { computeTextSimhash, SIMILAR_TEXT_MAX_DISTANCE } from "./simhash.js";
+
+const paragraph =
+	"The quarterly report is due on Friday. Please send your section to the shared folder by Wednesday evening, so there is time to review the numbers and fix the charts before it goes out to the board.";
+
+describe("computeTextSimhash", () => {
+	it("keeps edits of a paragraph a few bits apart", () => {
+		const hash = computeTextSimhash(paragraph);
+		expect(hash).toMatch(/^[0-9a-f]{16}$/);
+
+		const edited = paragraph.replace("the numbers", "all the numbers");
+		expect(
+			hammingDistance(hash, computeTextSimhash(edited)),
+		).toBeLessThanOrEqual(SIMILAR_TEXT_MAX_DISTANCE);
+
+		const other =
+			"Install the dependencies with npm, then run the build script. The output goes to the dist folder, and the tests can be run separately from the same terminal window.";
+		expect(
+			hammingDistance(hash, computeTextSimhash(other)),
+		).toBeGreaterThan(SIMILAR_TEXT_MAX_DISTANCE * 2);
+	});
+
+	it("ignores case and whitespace", () => {
+		expect(computeTextSimhash(`  ${paragraph.toUpperCase()}\n`)).toBe(
+			computeTextSimhash(paragraph.replace(/ /g, "\t ")),
+		);
+	});
+
+	it("leaves short texts unhashed", () => {
+		expect(computeTextSimhash("Meeting moved to 3pm")).toBe("");
+	});
+});
@@ -0,0 +1,85 @@
+/**
+ * Differing bits up to which two texts count as near-duplicates. Two
+ * edits of a paragraph that share about 95% of their text are usually
+ * within 8 bits; unrelated texts differ in 25 or more.
+ */
+export const SIMILAR_TEXT_MAX_DISTANCE = 8;
+
+/**
+ * Shortest text hashed (characters, after normalizing whitespace); in
+ * shorter ones a one-word edit changes too much of the text to compare.
+ */
+const MIN_SIMHASH_CHARS = 64;
+
+/**
+ * Characters of a text hashed; the rest of a longer one is left out.
+ */
+const MAX_SIMHASH_CHARS = 100_000;
+
+/**
+ * Length of the overlapping character runs a text is hashed by.
+ */
+const SHINGLE_CHARS = 5;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * 32-bit FNV-1a hash of a string's UTF-16 code units.
+ */
+const fnv1a = (value: string): number => {
+	let hash = 0x811c9dc5;
+	for (let i = 0; i < value.length; i++) {
+		hash = Math.imul(hash ^ value.charCodeAt(i), 0x01000193);
+	}
+	return hash >>> 0;
+};
+
+/**
+ * Spreads the bits of a 32-bit hash (MurmurHash3's finalizer).
+ */
+const mix = (value: number): number => {
+	let hash = value;
+	hash = Math.imul(hash ^ (hash >>> 16), 0x85ebca6b);
+	hash = Math.imul(hash ^ (hash >>> 13), 0xc2b2ae35);
+	return (hash ^ (hash >>> 16)) >>> 0;
+};
+
+/**
+ * Computes the simhash of a text: every run of 5 characters votes on
+ * each of 64 bits, and a bit is set where most runs agree. Texts that
+ * share most of their runs get hashes a few bits apart. Case and
+ * whitespace are ignored.
+ * Pure function.
+ *
+ * @returns 16 hex digits, or an empty string if the text is too short
+ */
+export const computeTextSimhash = (text: string): string => {
+	const normalized = text
+		.slice(0, MAX_SIMHASH_CHARS)
+		.toLowerCase()
+		.replace(/\s+/g, " ")
+		.trim();
+	if (normalized.length < MIN_SIMHASH_CHARS) return "";
+
+	const votes = new Int32Array(64);
+	for (let i = 0; i + SHINGLE_CHARS <= normalized.length; i++) {
+		const hash = fnv1a(normalized.slice(i, i + SHINGLE_CHARS));
+		const halves = [mix(hash), mix(hash ^ 0x9e3779b9)];
+		for (let bit = 0; bit < 64; bit++) {
+			const half = halves[bit >> 5];
+			votes[bit] += (half >>> (31 - (bit & 31))) & 1 ? 1 : -1;
+		}
+	}
+
+	let hex = "";
+	for (let half = 0; half < 2; half++) {
+		let word = 0;
+		for (let bit = 0; bit < 32; bit++) {
+			word = (word << 1) | (votes[half * 32 + bit] > 0 ? 1 : 0);
+		}
+		hex += (word >>> 0).toString(16).padStart(8, "0");
+	}
+	return hex;
+};
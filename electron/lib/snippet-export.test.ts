@@ -0,0 +1,158 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	exportSnippets,
+	formatRaycastSnippets,
+	parseSnippetExportRequest,
+	toAlfredSnippetEntries,
+	toExportedSnippets,
+} from "./snippet-export.js";
+
+const item = (id: number, content: string, note: string | null = null) => ({
+	id,
+	type: "text" as const,
+	content,
+	note,
+});
+
+describe("parseSnippetExportRequest", () => {
+	it("accepts pinned items or a collection", () => {
+		expect(
+			parseSnippetExportRequest("raycast", "/tmp/s.json", "pinned"),
+		).toEqual({ format: "raycast", filePath: "/tmp/s.json", source: "pinned" });
+		expect(
+			parseSnippetExportRequest("alfred", "/tmp/s.alfredsnippets", {
+				collectionId: 3,
+			}).source,
+		).toEqual({ collectionId: 3 });
+	});
+
+	it("rejects invalid requests", () => {
+		expect(() =>
+			parseSnippetExportRequest("espanso", "/tmp/s.yml", "pinned"),
+		).toThrow("Invalid snippet export: format must be alfred or raycast");
+		expect(() =>
+			parseSnippetExportRequest("raycast", "s.json", "pinned"),
+		).toThrow("Invalid snippet export: path must be absolute");
+		expect(() =>
+			parseSnippetExportRequest("raycast", "/tmp/s.json", { collectionId: 0 }),
+		).toThrow('Invalid snippet export: source must be "pinned"');
+	});
+});
+
+describe("toExportedSnippets", () => {
+	it("names snippets by note or first line and skips other items", () => {
+		const snippets = toExportedSnippets([
+			item(1, "\n  Dear team,\nThanks"),
+			item(2, "SELECT 1", "Health check"),
+			item(3, "x".repeat(80)),
+			{ id: 4, type: "image", content: "", note: null },
+			item(5, "   "),
+		]);
+
+		expect(snippets.map((snippet) => snippet.name)).toEqual([
+			"Dear team,",
+			"Health check",
+			`${"x".repeat(59)}…`,
+		]);
+		expect(snippets[0].text).toBe("\n  Dear team,\nThanks");
+	});
+
+	it("gives an item the same UUID in every export", () => {
+		const [first] = toExportedSnippets([item(1, "a")]);
+		const [again] = toExportedSnippets([item(1, "b")]);
+		const [other] = toExportedSnippets([item(2, "a")]);
+
+		expect(first.uid).toMatch(
+			/^[0-9A-F]{8}-[0-9A-F]{4}-4[0-9A-F]{3}-[89AB][0-9A-F]{3}-[0-9A-F]{12}$/,
+		);
+		expect(again.uid).toBe(first.uid);
+		expect(other.uid).not.toBe(first.uid);
+	});
+});
+
+describe("formatRaycastSnippets", () => {
+	it("writes a name and text per snippet", () => {
+		expect(
+			JSON.parse(
+				formatRaycastSnippets([{ uid: "U", name: "Hi", text: "Hello" }]),
+			),
+		).toEqual([{ name: "Hi", text: "Hello" }]);
+	});
+});
+
+describe("toAlfredSnippetEntries", () => {
+	it("writes one JSON file per snippet with a safe name", () => {
+		const [entry] = toAlfredSnippetEntries([
+			{ uid: "U", name: "a/b: c?", text: "Hello" },
+		]);
+
+		expect(entry.name).toBe("a-b- c- [U].json");
+		expect(JSON.parse(entry.data as string)).toEqual({
+			alfredsnippet: {
+				snippet: "Hello",
+				uid: "U",
+				name: "a/b: c?",
+				keyword: "",
+			},
+		});
+	});
+});
+
+describe("exportSnippets", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "snippet-export-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	it("writes Raycast JSON and counts the snippets", async () => {
+		const filePath = path.join(createTempDir(), "snippets.json");
+
+		const count = await exportSnippets([item(1, "a"), item(2, "b")], {
+			format: "raycast",
+			filePath,
+			source: "pinned",
+		});
+
+		expect(count).toBe(2);
+		expect(JSON.parse(fs.readFileSync(filePath, "utf-8"))).toHaveLength(2);
+		expect(fs.existsSync(`${filePath}.partial`)).toBe(false);
+	});
+
+	it("writes an Alfred collection as a zip of snippet files", async () => {
+		const filePath = path.join(createTempDir(), "Pinned.alfredsnippets");
+
+		await exportSnippets([item(1, "Hello", "Greeting")], {
+			format: "alfred",
+			filePath,
+			source: { collectionId: 1 },
+		});
+
+		const archive = fs.readFileSync(filePath);
+		expect(archive.readUInt32LE(0)).toBe(0x04034b50);
+		const nameLength = archive.readUInt16LE(26);
+		const name = archive.toString("utf-8", 30, 30 + nameLength);
+		expect(name).toMatch(/^Greeting \[[0-9A-F-]{36}\]\.json$/);
+		const size = archive.readUInt32LE(18);
+		const start = 30 + nameLength + archive.readUInt16LE(28);
+		const snippet = JSON.parse(
+			zlib.inflateRawSync(archive.subarray(start, start + size)).toString(),
+		);
+		expect(snippet.alfredsnippet).toMatchObject({
+			snippet: "Hello",
+			name: "Greeting",
+		});
+	});
+});
@@ -0,0 +1,202 @@
+import { createHash } from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+import type { HistoryRow } from "./history-repository.js";
+import { createZipArchive, type ZipEntry } from "./zip.js";
+
+/**
+ * Launcher snippet formats: an Alfred snippet collection
+ * (`.alfredsnippets`, a zip of one JSON file per snippet) or a Raycast
+ * snippets JSON array.
+ */
+export const SNIPPET_EXPORT_FORMATS = ["alfred", "raycast"] as const;
+
+export type SnippetExportFormat = (typeof SNIPPET_EXPORT_FORMATS)[number];
+
+/**
+ * Which items become snippets: all pinned items, or one collection in
+ * its saved order.
+ */
+export type SnippetExportSource = "pinned" | { collectionId: number };
+
+/**
+ * A validated snippet export request.
+ */
+export type SnippetExportRequest = {
+	format: SnippetExportFormat;
+	/** Absolute path of the file to write */
+	filePath: string;
+	source: SnippetExportSource;
+};
+
+/**
+ * A text item as a launcher snippet.
+ */
+export type ExportedSnippet = {
+	/** Stable across exports of the same item, so a re-import updates it */
+	uid: string;
+	name: string;
+	text: string;
+};
+
+type SnippetSourceRow = Pick<HistoryRow, "id" | "type" | "content" | "note">;
+
+/**
+ * Longest snippet name taken from an item's first line (characters).
+ */
+const MAX_SNIPPET_NAME_CHARS = 60;
+
+/** Characters Alfred's snippet file names cannot contain */
+const UNSAFE_FILE_NAME_PATTERN = /[\\/:*?"<>|\p{Cc}]/gu;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid snippet export: ${message}`);
+
+/**
+ * Validates a snippet export request from the renderer.
+ * Pure function.
+ *
+ * @throws if the format, path or source is invalid
+ */
+export const parseSnippetExportRequest = (
+	format: unknown,
+	filePath: unknown,
+	source: unknown,
+): SnippetExportRequest => {
+	if (!SNIPPET_EXPORT_FORMATS.includes(format as SnippetExportFormat)) {
+		throw invalid(`format must be ${SNIPPET_EXPORT_FORMATS.join(" or ")}`);
+	}
+	if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+		throw invalid("path must be absolute");
+	}
+	if (source === "pinned") {
+		return { format: format as SnippetExportFormat, filePath, source };
+	}
+	const collectionId =
+		typeof source === "object" && source !== null
+			? (source as { collectionId?: unknown }).collectionId
+			: undefined;
+	if (
+		typeof collectionId !== "number" ||
+		!Number.isInteger(collectionId) ||
+		collectionId <= 0
+	) {
+		throw invalid('source must be "pinned" or { collectionId }');
+	}
+	return {
+		format: format as SnippetExportFormat,
+		filePath,
+		source: { collectionId },
+	};
+};
+
+/**
+ * A UUID derived from the item id, in the uppercase form Alfred writes.
+ * Pure function.
+ */
+const toSnippetUid = (id: number): string => {
+	const hex = createHash("sha256")
+		.update(`clipboard-manager-snippet:${id}`)
+		.digest("hex")
+		.toUpperCase();
+	return [
+		hex.slice(0, 8),
+		hex.slice(8, 12),
+		`4${hex.slice(13, 16)}`,
+		`${"89AB"[Number.parseInt(hex[16], 16) % 4]}${hex.slice(17, 20)}`,
+		hex.slice(20, 32),
+	].join("-");
+};
+
+/**
+ * Converts text items to snippets named by their note, or else their
+ * first non-blank line (shortened); other items are left out.
+ * Pure function.
+ */
+export const toExportedSnippets = (
+	items: readonly SnippetSourceRow[],
+): ExportedSnippet[] =>
+	items
+		.filter((item) => item.type === "text" && item.content.trim() !== "")
+		.map((item) => {
+			const firstLine =
+				item.content
+					.split(/\r\n|\r|\n/)
+					.find((line) => line.trim() !== "")
+					?.trim() ?? "";
+			const note = item.note?.trim();
+			const chars = Array.from(firstLine);
+			const name =
+				note ||
+				(chars.length > MAX_SNIPPET_NAME_CHARS
+					? `${chars.slice(0, MAX_SNIPPET_NAME_CHARS - 1).join("")}…`
+					: firstLine);
+			return { uid: toSnippetUid(item.id), name, text: item.content };
+		});
+
+/**
+ * Formats snippets as the JSON array Raycast's Import Snippets command
+ * reads.
+ * Pure function.
+ */
+export const formatRaycastSnippets = (
+	snippets: readonly ExportedSnippet[],
+): string =>
+	`${JSON.stringify(
+		snippets.map(({ name, text }) => ({ name, text })),
+		null,
+		2,
+	)}\n`;
+
+/**
+ * The files of an Alfred snippet collection: `Name [UID].json` with an
+ * `alfredsnippet` object per snippet, without a keyword.
+ * Pure function.
+ */
+export const toAlfredSnippetEntries = (
+	snippets: readonly ExportedSnippet[],
+): ZipEntry[] =>
+	snippets.map(({ uid, name, text }) => ({
+		name: `${name.replace(UNSAFE_FILE_NAME_PATTERN, "-")} [${uid}].json`,
+		data: `${JSON.stringify(
+			{ alfredsnippet: { snippet: text, uid, name, keyword: "" } },
+			null,
+			2,
+		)}\n`,
+	}));
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/**
+ * Writes items' text as launcher snippets. The file is written next to
+ * the target and renamed into place once complete, and is readable by
+ * the current user only, like the history itself.
+ *
+ * @returns number of snippets exported
+ */
+export const exportSnippets = async (
+	items: readonly SnippetSourceRow[],
+	request: SnippetExportRequest,
+	now = new Date(),
+): Promise<number> => {
+	const snippets = toExportedSnippets(items);
+	const data =
+		request.format === "raycast"
+			? formatRaycastSnippets(snippets)
+			: createZipArchive(toAlfredSnippetEntries(snippets), now);
+	const tempPath = `${request.filePath}.partial`;
+	try {
+		await fs.promises.writeFile(tempPath, data, { mode: 0o600 });
+		await fs.promises.rename(tempPath, request.filePath);
+		return snippets.length;
+	} catch (error) {
+		await fs.promises.rm(tempPath, { force: true });
+		throw error;
+	}
+};
@@ -0,0 +1,33 @@
+import { describe, expect, it } from "vitest";
+import {
+	normalizeSnippetAbbreviation,
+	normalizeSnippetName,
+} from "./snippet-repository.js";
+
+describe("normalizeSnippetName", () => {
+	it("trims and collapses whitespace, keeping case", () => {
+		expect(normalizeSnippetName("  Bug  Report\n")).toBe("Bug Report");
+	});
+
+	it("rejects empty, overlong and non-text names", () => {
+		for (const name of ["", "   ", "x".repeat(101), 1, null]) {
+			expect(normalizeSnippetName(name)).toBeNull();
+		}
+	});
+});
+
+describe("normalizeSnippetAbbreviation", () => {
+	it("trims and keeps case, treating empty text as none", () => {
+		expect(normalizeSnippetAbbreviation(" ;Addr ")).toBe(";Addr");
+		expect(normalizeSnippetAbbreviation("")).toBeNull();
+		expect(normalizeSnippetAbbreviation(null)).toBeNull();
+	});
+
+	it("rejects spaces and bad lengths", () => {
+		for (const abbreviation of ["a", "; addr", "x".repeat(33)]) {
+			expect(() => normalizeSnippetAbbreviation(abbreviation)).toThrow(
+				"Invalid snippet abbreviation",
+			);
+		}
+	});
+});
@@ -0,0 +1,301 @@
+import type Database from "better-sqlite3";
+import { isValidId } from "./history-repository.js";
+import { validateSnippetPlaceholders } from "./snippets.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Represents a row from the snippets table.
+ */
+export type SnippetRow = {
+	id: number;
+	name: string;
+	/** Text with placeholders such as `{clipboard}` or `{date:%Y-%m-%d}` */
+	content: string;
+	/** Typed text the text expander replaces with the snippet */
+	abbreviation: string | null;
+	/** Last value pasted for `{counter}`; 0 before the first paste */
+	counter: number;
+	created_at: string;
+	updated_at: string;
+};
+
+/**
+ * Fields of a snippet to create, or to change on update.
+ */
+export type SnippetInput = {
+	name: string;
+	content: string;
+	/** Omitted or null for none */
+	abbreviation?: string | null;
+};
+
+/**
+ * Maximum snippet name length (characters).
+ */
+const MAX_SNIPPET_NAME_LENGTH = 100;
+
+/**
+ * Maximum snippet content length (characters).
+ */
+const MAX_SNIPPET_CONTENT_LENGTH = 100_000;
+
+/**
+ * Abbreviation length limits (characters).
+ */
+const MIN_ABBREVIATION_LENGTH = 2;
+const MAX_ABBREVIATION_LENGTH = 32;
+
+const SNIPPET_COLUMNS =
+	"id, name, content, abbreviation, counter, created_at, updated_at";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Normalizes a snippet name for storage.
+ * Pure function.
+ *
+ * Names are trimmed and have internal whitespace collapsed; case is kept
+ * for display, but uniqueness is case-insensitive.
+ *
+ * @returns The normalized name, or null if it is empty or too long
+ */
+export const normalizeSnippetName = (name: unknown): string | null => {
+	if (typeof name !== "string") return null;
+	const normalized = name.trim().replace(/\s+/g, " ");
+	if (normalized.length === 0 || normalized.length > MAX_SNIPPET_NAME_LENGTH) {
+		return null;
+	}
+	return normalized;
+};
+
+/**
+ * Normalizes a snippet abbreviation for storage.
+ * Pure function. Abbreviations are trimmed and matched case-sensitively;
+ * empty text means none.
+ *
+ * @throws if it contains whitespace or is not 2-32 characters
+ */
+export const normalizeSnippetAbbreviation = (
+	abbreviation: unknown,
+): string | null => {
+	if (abbreviation === null || abbreviation === undefined) return null;
+	if (typeof abbreviation !== "string") {
+		throw new Error("Invalid snippet abbreviation: expected text");
+	}
+	const trimmed = abbreviation.trim();
+	if (trimmed === "") return null;
+	const length = [...trimmed].length;
+	if (
+		/\s/.test(trimmed) ||
+		length < MIN_ABBREVIATION_LENGTH ||
+		length > MAX_ABBREVIATION_LENGTH
+	) {
+		throw new Error(
+			`Invalid snippet abbreviation: must be ${MIN_ABBREVIATION_LENGTH}-${MAX_ABBREVIATION_LENGTH} characters without spaces`,
+		);
+	}
+	return trimmed;
+};
+
+const assertValidId = (id: unknown): number => {
+	if (!isValidId(id)) {
+		throw new Error(`Invalid snippet id: ${id}`);
+	}
+	return id;
+};
+
+const assertValidName = (name: unknown): string => {
+	const normalized = normalizeSnippetName(name);
+	if (normalized === null) {
+		throw new Error(
+			`Invalid snippet name: must be 1-${MAX_SNIPPET_NAME_LENGTH} characters`,
+		);
+	}
+	return normalized;
+};
+
+/**
+ * Throws unless content is non-blank text with valid placeholders.
+ * Content is stored as given, without trimming.
+ */
+const assertValidContent = (content: unknown): string => {
+	if (
+		typeof content !== "string" ||
+		content.trim() === "" ||
+		content.length > MAX_SNIPPET_CONTENT_LENGTH
+	) {
+		throw new Error(
+			`Invalid snippet content: must be 1-${MAX_SNIPPET_CONTENT_LENGTH} characters of text`,
+		);
+	}
+	validateSnippetPlaceholders(content);
+	return content;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the snippet repository.
+ * Owns every SQL statement against the `snippets` table. Snippets are
+ * saved text kept apart from history, so clearing or pruning history
+ * leaves them intact.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createSnippetRepository = (getDb: () => Database.Database) => {
+	const getSnippet = (id: number): SnippetRow | undefined =>
+		getDb()
+			.prepare(`SELECT ${SNIPPET_COLUMNS} FROM snippets WHERE id = ?`)
+			.get(assertValidId(id)) as SnippetRow | undefined;
+
+	/**
+	 * Rejects a name already used by another snippet (case-insensitive).
+	 */
+	const assertNameAvailable = (name: string, exceptId?: number): void => {
+		const existing = getDb()
+			.prepare("SELECT id FROM snippets WHERE name = ?")
+			.get(name) as { id: number } | undefined;
+		if (existing && existing.id !== exceptId) {
+			throw new Error(`Snippet already exists: ${name}`);
+		}
+	};
+
+	/**
+	 * Rejects an abbreviation already used by another snippet.
+	 */
+	const assertAbbreviationAvailable = (
+		abbreviation: string | null,
+		exceptId?: number,
+	): void => {
+		if (abbreviation === null) return;
+		const existing = getDb()
+			.prepare("SELECT id FROM snippets WHERE abbreviation = ?")
+			.get(abbreviation) as { id: number } | undefined;
+		if (existing && existing.id !== exceptId) {
+			throw new Error(`Abbreviation already in use: ${abbreviation}`);
+		}
+	};
+
+	/**
+	 * Lists snippets by name, optionally only those whose name or content
+	 * contains `query`.
+	 */
+	const listSnippets = (query = ""): SnippetRow[] => {
+		const term = typeof query === "string" ? query.trim() : "";
+		if (!term) {
+			return getDb()
+				.prepare(
+					`SELECT ${SNIPPET_COLUMNS} FROM snippets ORDER BY name COLLATE NOCASE`,
+				)
+				.all() as SnippetRow[];
+		}
+		return getDb()
+			.prepare(
+				`SELECT ${SNIPPET_COLUMNS} FROM snippets WHERE name LIKE ? OR content LIKE ? ORDER BY name COLLATE NOCASE`,
+			)
+			.all(`%${term}%`, `%${term}%`) as SnippetRow[];
+	};
+
+	const createSnippet = (input: SnippetInput): SnippetRow => {
+		const name = assertValidName(input?.name);
+		const content = assertValidContent(input?.content);
+		const abbreviation = normalizeSnippetAbbreviation(input?.abbreviation);
+		assertNameAvailable(name);
+		assertAbbreviationAvailable(abbreviation);
+
+		const { lastInsertRowid } = getDb()
+			.prepare(
+				"INSERT INTO snippets (name, content, abbreviation) VALUES (?, ?, ?)",
+			)
+			.run(name, content, abbreviation);
+		const created = getSnippet(Number(lastInsertRowid));
+		if (!created) {
+			throw new Error("Failed to create snippet");
+		}
+		return created;
+	};
+
+	/**
+	 * Renames a snippet or replaces its content or abbreviation; missing
+	 * fields are kept, and a null abbreviation removes it.
+	 */
+	const updateSnippet = (
+		id: number,
+		input: Partial<SnippetInput>,
+	): SnippetRow => {
+		const existing = getSnippet(id);
+		if (!existing) {
+			throw new Error(`Snippet not found: ${id}`);
+		}
+		const name =
+			input?.name === undefined ? existing.name : assertValidName(input.name);
+		const content =
+			input?.content === undefined
+				? existing.content
+				: assertValidContent(input.content);
+		const abbreviation =
+			input?.abbreviation === undefined
+				? existing.abbreviation
+				: normalizeSnippetAbbreviation(input.abbreviation);
+		assertNameAvailable(name, existing.id);
+		assertAbbreviationAvailable(abbreviation, existing.id);
+
+		getDb()
+			.prepare(
+				"UPDATE snippets SET name = ?, content = ?, abbreviation = ?, updated_at = datetime('now') WHERE id = ?",
+			)
+			.run(name, content, abbreviation, existing.id);
+		return getSnippet(existing.id) as SnippetRow;
+	};
+
+	/**
+	 * Adds one to a snippet's `{counter}` and returns the new value.
+	 * Editing the snippet keeps its count.
+	 */
+	const incrementCounter = (id: number): number => {
+		const row = getDb()
+			.prepare(
+				"UPDATE snippets SET counter = counter + 1 WHERE id = ? RETURNING counter",
+			)
+			.get(assertValidId(id)) as { counter: number } | undefined;
+		if (!row) {
+			throw new Error(`Snippet not found: ${id}`);
+		}
+		return row.counter;
+	};
+
+	/**
+	 * Lists the snippets the text expander watches for, by abbreviation.
+	 */
+	const listAbbreviations = (): { id: number; abbreviation: string }[] =>
+		getDb()
+			.prepare(
+				"SELECT id, abbreviation FROM snippets WHERE abbreviation IS NOT NULL",
+			)
+			.all() as { id: number; abbreviation: string }[];
+
+	const deleteSnippet = (id: number): void => {
+		getDb()
+			.prepare("DELETE FROM snippets WHERE id = ?")
+			.run(assertValidId(id));
+	};
+
+	return {
+		listSnippets,
+		getSnippet,
+		createSnippet,
+		updateSnippet,
+		incrementCounter,
+		listAbbreviations,
+		deleteSnippet,
+	};
+};
+
+export type SnippetRepository = ReturnType<typeof createSnippetRepository>;
@@ -0,0 +1,129 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	formatSnippetDate,
+	listSnippetFields,
+	parseSnippetFieldValues,
+	resolveSnippet,
+	validateSnippetPlaceholders,
+} from "./snippets.js";
+
+describe("formatSnippetDate", () => {
+	it("formats strftime-style directives in local time", () => {
+		const date = new Date(2024, 2, 5, 14, 7, 9);
+
+		expect(formatSnippetDate(date, "%Y-%m-%d %H:%M:%S")).toBe(
+			"2024-03-05 14:07:09",
+		);
+		expect(formatSnippetDate(date, "%a %e %b %y, %I%p (day %j) 100%%")).toBe(
+			"Tue 5 Mar 24, 02PM (day 065) 100%",
+		);
+	});
+
+	it("rejects unsupported directives", () => {
+		expect(() => formatSnippetDate(new Date(), "%Q")).toThrow(
+			"unsupported date directive %Q",
+		);
+	});
+});
+
+describe("listSnippetFields", () => {
+	it("lists each field once, in order", () => {
+		expect(
+			listSnippetFields("Hi {field:Name}, re {field:Ticket} ({field: Name })"),
+		).toEqual(["Name", "Ticket"]);
+	});
+});
+
+describe("validateSnippetPlaceholders", () => {
+	it("accepts literal braces", () => {
+		expect(() =>
+			validateSnippetPlaceholders("function f() { return {cursor}; }"),
+		).not.toThrow();
+	});
+
+	it("rejects a second cursor and bad field names", () => {
+		expect(() => validateSnippetPlaceholders("{cursor}{cursor}")).toThrow(
+			"only one {cursor} is allowed",
+		);
+		expect(() => validateSnippetPlaceholders("{field: }")).toThrow(
+			"field names must be 1-32 characters",
+		);
+		expect(() => validateSnippetPlaceholders("{date:%d.%k}")).toThrow(
+			"unsupported date directive %k",
+		);
+	});
+});
+
+describe("parseSnippetFieldValues", () => {
+	it("accepts an object of text values", () => {
+		expect(parseSnippetFieldValues(undefined)).toEqual({});
+		expect(parseSnippetFieldValues({ " Name ": "Ada" })).toEqual({
+			Name: "Ada",
+		});
+	});
+
+	it("rejects anything else", () => {
+		expect(() => parseSnippetFieldValues(["Ada"])).toThrow(
+			"Invalid snippet fields: expected an object",
+		);
+		expect(() => parseSnippetFieldValues({ Name: 1 })).toThrow(
+			'"Name" must be text',
+		);
+	});
+});
+
+describe("resolveSnippet", () => {
+	const context = {
+		clipboard: "https://example.com",
+		fields: {},
+		now: new Date(2024, 0, 31, 9, 5),
+		nextCounter: () => 1,
+		randomUUID: () => "00000000-0000-4000-8000-000000000000",
+	};
+
+	it("fills in the clipboard and fields", () => {
+		expect(
+			resolveSnippet("[{field:Title}]({clipboard})", {
+				...context,
+				fields: { Title: "Example" },
+			}),
+		).toEqual({ text: "[Example](https://example.com)", caretSteps: 0 });
+	});
+
+	it("fills in the date, counter and UUID", () => {
+		const nextCounter = vi.fn().mockReturnValue(42);
+
+		expect(
+			resolveSnippet(
+				"#{counter} {date} {time} ({date:%d/%m}) {uuid} #{counter}",
+				{ ...context, nextCounter },
+			).text,
+		).toBe(
+			"#42 2024-01-31 09:05 (31/01) 00000000-0000-4000-8000-000000000000 #42",
+		);
+		expect(nextCounter).toHaveBeenCalledTimes(1);
+	});
+
+	it("counts caret steps back to the cursor", () => {
+		expect(resolveSnippet("<b>{cursor}</b>", context)).toEqual({
+			text: "<b></b>",
+			caretSteps: 4,
+		});
+		expect(resolveSnippet("{cursor}é😀\r\n", context).caretSteps).toBe(3);
+	});
+
+	it("leaves a distant cursor at the end", () => {
+		expect(
+			resolveSnippet(`{cursor}${"x".repeat(1001)}`, context).caretSteps,
+		).toBe(0);
+	});
+
+	it("requires a value for each field", () => {
+		const nextCounter = vi.fn();
+
+		expect(() =>
+			resolveSnippet("#{counter} {field:Name}", { ...context, nextCounter }),
+		).toThrow('Missing value for snippet field "Name"');
+		expect(nextCounter).not.toHaveBeenCalled();
+	});
+});
@@ -0,0 +1,273 @@
+/**
+ * What a snippet's placeholders are filled with when it is pasted.
+ */
+export type SnippetContext = {
+	/** Clipboard text at paste time, for `{clipboard}` */
+	clipboard: string;
+	/** Values for `{field:Name}` placeholders, by name */
+	fields: Record<string, string>;
+	/** Paste time, for `{date}` and `{time}` */
+	now: Date;
+	/**
+	 * Advances the snippet's counter and returns the new value; called
+	 * once per paste, only if the snippet uses `{counter}`
+	 */
+	nextCounter: () => number;
+	/** Called once per paste, only if the snippet uses `{uuid}` */
+	randomUUID: () => string;
+};
+
+/**
+ * A snippet ready to paste.
+ */
+export type ResolvedSnippet = {
+	text: string;
+	/**
+	 * Left arrow presses that move the caret from the end of the pasted
+	 * text back to `{cursor}`; 0 leaves it at the end
+	 */
+	caretSteps: number;
+};
+
+/**
+ * `{cursor}`, `{clipboard}`, `{time}`, `{uuid}`, `{counter}`,
+ * `{date}` or `{date:FORMAT}`, or `{field:Name}`; other braces are literal.
+ */
+const PLACEHOLDER_PATTERN =
+	/\{(cursor|clipboard|time|uuid|counter|date(?::([^{}]*))?|field:([^{}]*))\}/g;
+
+const DEFAULT_DATE_FORMAT = "%Y-%m-%d";
+
+const TIME_FORMAT = "%H:%M";
+
+const MONTH_NAMES = [
+	"January",
+	"February",
+	"March",
+	"April",
+	"May",
+	"June",
+	"July",
+	"August",
+	"September",
+	"October",
+	"November",
+	"December",
+];
+
+const DAY_NAMES = [
+	"Sunday",
+	"Monday",
+	"Tuesday",
+	"Wednesday",
+	"Thursday",
+	"Friday",
+	"Saturday",
+];
+
+const pad = (value: number, length = 2): string =>
+	String(value).padStart(length, "0");
+
+/**
+ * strftime-style directives for `{date:FORMAT}`, in local time.
+ */
+const DATE_DIRECTIVES: Record<string, (date: Date) => string> = {
+	Y: (date) => String(date.getFullYear()),
+	y: (date) => pad(date.getFullYear() % 100),
+	m: (date) => pad(date.getMonth() + 1),
+	d: (date) => pad(date.getDate()),
+	e: (date) => String(date.getDate()),
+	H: (date) => pad(date.getHours()),
+	I: (date) => pad(date.getHours() % 12 || 12),
+	M: (date) => pad(date.getMinutes()),
+	S: (date) => pad(date.getSeconds()),
+	p: (date) => (date.getHours() < 12 ? "AM" : "PM"),
+	a: (date) => DAY_NAMES[date.getDay()].slice(0, 3),
+	A: (date) => DAY_NAMES[date.getDay()],
+	b: (date) => MONTH_NAMES[date.getMonth()].slice(0, 3),
+	B: (date) => MONTH_NAMES[date.getMonth()],
+	j: (date) => {
+		const start = new Date(date.getFullYear(), 0, 1);
+		const day = new Date(date.getFullYear(), date.getMonth(), date.getDate());
+		// Rounded, as days around a DST change are not exactly 24 hours
+		const elapsed = Math.round((day.getTime() - start.getTime()) / 86_400_000);
+		return pad(elapsed + 1, 3);
+	},
+	z: (date) => {
+		const offset = -date.getTimezoneOffset();
+		const sign = offset < 0 ? "-" : "+";
+		const minutes = Math.abs(offset);
+		return `${sign}${pad(Math.floor(minutes / 60))}${pad(minutes % 60)}`;
+	},
+	"%": () => "%",
+};
+
+const DATE_DIRECTIVE_PATTERN = /%(.?)/g;
+
+const MAX_FIELD_NAME_LENGTH = 32;
+
+const MAX_FIELD_VALUE_LENGTH = 10_000;
+
+const MAX_SNIPPET_FIELDS = 20;
+
+/**
+ * Most caret moves after a paste; a `{cursor}` further from the end is
+ * left at the end rather than sending thousands of keystrokes.
+ */
+const MAX_CARET_STEPS = 1000;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Formats a date with strftime-style directives such as `%Y-%m-%d` or
+ * `%a %e %b %H:%M`, in local time; month and day names are English.
+ * Pure function.
+ *
+ * @throws if the format uses an unsupported directive
+ */
+export const formatSnippetDate = (date: Date, format: string): string =>
+	format.replace(DATE_DIRECTIVE_PATTERN, (_match, directive: string) => {
+		const formatter = DATE_DIRECTIVES[directive];
+		if (!formatter) {
+			throw new Error(
+				`Invalid snippet: unsupported date directive %${directive}`,
+			);
+		}
+		return formatter(date);
+	});
+
+/**
+ * Lists the field names a snippet asks for, in order of first use.
+ * Pure function. Names are trimmed; repeats share one value.
+ */
+export const listSnippetFields = (content: string): string[] => {
+	const names = new Set<string>();
+	for (const match of content.matchAll(PLACEHOLDER_PATTERN)) {
+		if (match[3] !== undefined) names.add(match[3].trim());
+	}
+	return [...names];
+};
+
+/**
+ * Checks a snippet's placeholders before it is saved.
+ * Pure function.
+ *
+ * @throws if there is more than one `{cursor}`, more than 20 fields, a
+ *   field name that is empty or over 32 characters, or a date format with
+ *   an unsupported directive
+ */
+export const validateSnippetPlaceholders = (content: string): void => {
+	const cursors = content.match(/\{cursor\}/g)?.length ?? 0;
+	if (cursors > 1) {
+		throw new Error("Invalid snippet: only one {cursor} is allowed");
+	}
+	const fields = listSnippetFields(content);
+	if (fields.length > MAX_SNIPPET_FIELDS) {
+		throw new Error(
+			`Invalid snippet: at most ${MAX_SNIPPET_FIELDS} fields are allowed`,
+		);
+	}
+	if (
+		fields.some((name) => name === "" || name.length > MAX_FIELD_NAME_LENGTH)
+	) {
+		throw new Error(
+			`Invalid snippet: field names must be 1-${MAX_FIELD_NAME_LENGTH} characters`,
+		);
+	}
+	for (const match of content.matchAll(PLACEHOLDER_PATTERN)) {
+		if (match[2] !== undefined) formatSnippetDate(new Date(0), match[2]);
+	}
+};
+
+/**
+ * Validates field values sent with a paste request.
+ * Pure function. A missing value means no fields.
+ *
+ * @throws if it is not an object of strings up to 10000 characters
+ */
+export const parseSnippetFieldValues = (
+	input: unknown,
+): Record<string, string> => {
+	if (input === undefined || input === null) return {};
+	if (typeof input !== "object" || Array.isArray(input)) {
+		throw new Error("Invalid snippet fields: expected an object");
+	}
+	const fields: Record<string, string> = {};
+	for (const [name, value] of Object.entries(input)) {
+		if (typeof value !== "string" || value.length > MAX_FIELD_VALUE_LENGTH) {
+			throw new Error(
+				`Invalid snippet fields: "${name}" must be text of up to ${MAX_FIELD_VALUE_LENGTH} characters`,
+			);
+		}
+		fields[name.trim()] = value;
+	}
+	return fields;
+};
+
+/**
+ * Counts the Left arrow presses that cross some text: one per code point,
+ * with a CRLF line break counting once.
+ */
+const countCaretSteps = (text: string): number =>
+	[...text.replace(/\r\n/g, "\n")].length;
+
+/**
+ * Fills in a snippet's placeholders: `{clipboard}` with the clipboard
+ * text, `{field:Name}` with the value given for it, `{date}` and `{time}`
+ * with the paste time, `{counter}` with the snippet's next count and
+ * `{uuid}` with a random UUID (repeats share one value), and `{cursor}`
+ * with nothing, noting how far the caret should move back to it.
+ * Pure function apart from the context's callbacks.
+ *
+ * @throws if a field has no value
+ */
+export const resolveSnippet = (
+	content: string,
+	context: SnippetContext,
+): ResolvedSnippet => {
+	// Checked up front so a failed paste does not advance the counter
+	for (const name of listSnippetFields(content)) {
+		if (context.fields[name] === undefined) {
+			throw new Error(`Missing value for snippet field "${name}"`);
+		}
+	}
+
+	let text = "";
+	let cursorAt: number | null = null;
+	let counter: string | null = null;
+	let uuid: string | null = null;
+	let last = 0;
+	for (const match of content.matchAll(PLACEHOLDER_PATTERN)) {
+		const index = match.index ?? 0;
+		text += content.slice(last, index);
+		last = index + match[0].length;
+
+		if (match[1] === "cursor") {
+			cursorAt = text.length;
+		} else if (match[1] === "clipboard") {
+			text += context.clipboard;
+		} else if (match[1] === "time") {
+			text += formatSnippetDate(context.now, TIME_FORMAT);
+		} else if (match[1] === "counter") {
+			counter ??= String(context.nextCounter());
+			text += counter;
+		} else if (match[1] === "uuid") {
+			uuid ??= context.randomUUID();
+			text += uuid;
+		} else if (match[1].startsWith("date")) {
+			text += formatSnippetDate(context.now, match[2] || DEFAULT_DATE_FORMAT);
+		} else {
+			text += context.fields[match[3].trim()];
+		}
+	}
+	text += content.slice(last);
+
+	const caretSteps =
+		cursorAt === null ? 0 : countCaretSteps(text.slice(cursorAt));
+	return {
+		text,
+		caretSteps: caretSteps > MAX_CARET_STEPS ? 0 : caretSteps,
+	};
+};
@@ -0,0 +1,65 @@
+import { describe, expect, it } from "vitest";
+import { parseStartupOptions, withProfileArg } from "./startup-options.js";
+
+describe("parseStartupOptions", () => {
+	it("starts with windows by default", () => {
+		expect(parseStartupOptions(["/Applications/Clipboard Manager"])).toEqual({
+			headless: false,
+			profile: null,
+			dataDir: null,
+			portable: false,
+			readOnly: false,
+			copyItem: null,
+		});
+	});
+
+	it("reads --headless among other arguments", () => {
+		expect(
+			parseStartupOptions(["electron", ".", "--no-sandbox", "--headless"])
+				.headless,
+		).toBe(true);
+	});
+
+	it("reads --profile with or without =", () => {
+		expect(parseStartupOptions(["app", "--profile", "work"]).profile).toBe(
+			"work",
+		);
+		expect(parseStartupOptions(["app", "--profile=personal"]).profile).toBe(
+			"personal",
+		);
+		expect(parseStartupOptions(["app", "--profile"]).profile).toBeNull();
+	});
+
+	it("reads the data folder and portable mode", () => {
+		expect(
+			parseStartupOptions(["app", "--data-dir", "D:\\Clips", "--portable"]),
+		).toEqual({
+			headless: false,
+			profile: null,
+			dataDir: "D:\\Clips",
+			portable: true,
+			readOnly: false,
+			copyItem: null,
+		});
+	});
+
+	it("reads --read-only", () => {
+		expect(parseStartupOptions(["app", "--read-only"]).readOnly).toBe(true);
+	});
+
+	it("reads the item to copy from a jump list entry", () => {
+		expect(parseStartupOptions(["app", "--copy-item=12"]).copyItem).toBe(12);
+		expect(parseStartupOptions(["app", "--copy-item=x"]).copyItem).toBeNull();
+	});
+});
+
+describe("withProfileArg", () => {
+	it("replaces the profile and keeps other arguments", () => {
+		expect(
+			withProfileArg([".", "--profile", "work", "--headless"], "personal"),
+		).toEqual([".", "--headless", "--profile=personal"]);
+		expect(withProfileArg(["--profile=work"], "default")).toEqual([
+			"--profile=default",
+		]);
+	});
+});
@@ -0,0 +1,95 @@
+/**
+ * Options read from the command line at launch.
+ */
+export type StartupOptions = {
+	/**
+	 * Run without windows: capture, history, global shortcuts and the tray
+	 * keep working
+	 */
+	headless: boolean;
+	/** Profile named by `--profile <name>` or `--profile=<name>` */
+	profile: string | null;
+	/** Data folder given by `--data-dir <path>` or `--data-dir=<path>` */
+	dataDir: string | null;
+	/** Keep data next to the executable */
+	portable: boolean;
+	/** Start in read-only mode, whatever the saved setting */
+	readOnly: boolean;
+	/**
+	 * History item to copy, from `--copy-item=<id>`; the jump list uses it
+	 * because, unlike a `clipman://` link, a web page cannot pass it
+	 */
+	copyItem: number | null;
+};
+
+const PROFILE_FLAG = "--profile";
+const DATA_DIR_FLAG = "--data-dir";
+const COPY_ITEM_FLAG = "--copy-item";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Reads the value of a flag given as `flag value` or `flag=value`; the
+ * last one wins.
+ * Pure function.
+ */
+const readFlagValue = (
+	argv: readonly string[],
+	flag: string,
+): string | null => {
+	let value: string | null = null;
+	argv.forEach((arg, index) => {
+		if (arg.startsWith(`${flag}=`)) {
+			value = arg.slice(flag.length + 1);
+		} else if (arg === flag && index + 1 < argv.length) {
+			value = argv[index + 1];
+		}
+	});
+	return value;
+};
+
+/**
+ * Reads a positive integer flag value, or null if missing or invalid.
+ * Pure function.
+ */
+const readIdFlag = (argv: readonly string[], flag: string): number | null => {
+	const id = Number(readFlagValue(argv, flag) ?? Number.NaN);
+	return Number.isInteger(id) && id > 0 ? id : null;
+};
+
+/**
+ * Reads startup options from `process.argv`.
+ * Pure function. Unknown arguments (Electron's and Chromium's own, the app
+ * path in development) are ignored. The profile name and data folder are
+ * checked when they are opened.
+ */
+export const parseStartupOptions = (
+	argv: readonly string[],
+): StartupOptions => ({
+	headless: argv.includes("--headless"),
+	profile: readFlagValue(argv, PROFILE_FLAG),
+	dataDir: readFlagValue(argv, DATA_DIR_FLAG),
+	portable: argv.includes("--portable"),
+	readOnly: argv.includes("--read-only"),
+	copyItem: readIdFlag(argv, COPY_ITEM_FLAG),
+});
+
+/**
+ * Arguments relaunching the app in a profile: the current ones with any
+ * `--profile` replaced.
+ * Pure function.
+ */
+export const withProfileArg = (
+	args: readonly string[],
+	profile: string,
+): string[] => {
+	const kept = args.filter(
+		(arg, index) =>
+			arg !== PROFILE_FLAG &&
+			args[index - 1] !== PROFILE_FLAG &&
+			!arg.startsWith(`${PROFILE_FLAG}=`),
+	);
+	return [...kept, `${PROFILE_FLAG}=${profile}`];
+};
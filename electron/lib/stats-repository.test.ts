@@ -0,0 +1,43 @@
+import { describe, expect, it } from "vitest";
+import { parseHeatmapYear, parseStatsOptions } from "./stats-repository.js";
+
+describe("parseStatsOptions", () => {
+	it("fills in defaults for missing options", () => {
+		expect(parseStatsOptions(undefined)).toEqual({
+			days: 30,
+			weeks: 12,
+			topApps: 10,
+		});
+		expect(parseStatsOptions({ days: 7, topApps: 3 })).toEqual({
+			days: 7,
+			weeks: 12,
+			topApps: 3,
+		});
+	});
+
+	it("rejects options out of range", () => {
+		expect(() => parseStatsOptions({ days: 0 })).toThrow(
+			"Invalid stats options: days must be an integer from 1 to 366",
+		);
+		expect(() => parseStatsOptions({ weeks: 1.5 })).toThrow(
+			"weeks must be an integer from 1 to 104",
+		);
+		expect(() => parseStatsOptions({ topApps: "5" })).toThrow("topApps");
+		expect(() => parseStatsOptions("all")).toThrow("expected an object");
+	});
+});
+
+describe("parseHeatmapYear", () => {
+	it("accepts calendar years", () => {
+		expect(parseHeatmapYear(2025)).toBe(2025);
+	});
+
+	it("rejects other values", () => {
+		expect(() => parseHeatmapYear(2025.5)).toThrow(
+			"Invalid heatmap year: 2025.5",
+		);
+		expect(() => parseHeatmapYear(1969)).toThrow("Invalid heatmap year");
+		expect(() => parseHeatmapYear("2025")).toThrow("Invalid heatmap year");
+		expect(() => parseHeatmapYear(undefined)).toThrow("Invalid heatmap year");
+	});
+});
@@ -0,0 +1,250 @@
+import type Database from "better-sqlite3";
+import type { SourceAppCount } from "./history-repository.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * How far back the stats look, and how many source apps they list.
+ */
+export type StatsOptions = {
+	/** Days covered by `itemsPerDay`, including today */
+	days: number;
+	/** Weeks covered by `itemsPerWeek`, including this one */
+	weeks: number;
+	/** Source apps listed in `topSourceApps` */
+	topApps: number;
+};
+
+/**
+ * Items captured in one day or week.
+ */
+export type PeriodCount = {
+	/** `YYYY-MM-DD` local date of the day, or of the week's Monday */
+	period: string;
+	count: number;
+};
+
+/**
+ * Aggregates over history for the stats dashboard.
+ */
+export type HistoryStats = {
+	totalItems: number;
+	/** Days without items are left out */
+	itemsPerDay: PeriodCount[];
+	/** Weeks without items are left out */
+	itemsPerWeek: PeriodCount[];
+	/** Most items first; names differing only in case are grouped */
+	topSourceApps: SourceAppCount[];
+	/** Items per type (`text`, `image`, `files`), most items first */
+	types: { type: string; count: number }[];
+	/** Text items per content kind, most items first */
+	kinds: { kind: string; count: number }[];
+	/** Average text or image size in bytes; null if no item has one */
+	averageItemBytes: number | null;
+	/** Database file plus image files on disk, in bytes */
+	storageBytes: { database: number; attachments: number; total: number };
+};
+
+/**
+ * Items captured per day of one calendar year, for an activity calendar.
+ */
+export type ActivityHeatmap = {
+	year: number;
+	/** Days without items are left out */
+	days: PeriodCount[];
+	total: number;
+	/** Largest count of a single day, for scaling colors; 0 if none */
+	maxCount: number;
+};
+
+const DEFAULT_STATS_OPTIONS: StatsOptions = {
+	days: 30,
+	weeks: 12,
+	topApps: 10,
+};
+
+/**
+ * Upper bound of each option.
+ */
+const MAX_STATS_OPTIONS: StatsOptions = {
+	days: 366,
+	weeks: 104,
+	topApps: 100,
+};
+
+const STATS_OPTION_KEYS = ["days", "weeks", "topApps"] as const;
+
+/**
+ * Years an activity heatmap can cover.
+ */
+const MIN_HEATMAP_YEAR = 1970;
+const MAX_HEATMAP_YEAR = 2999;
+
+/**
+ * SQL for the local date of a time, as in `PeriodCount.period`.
+ */
+const localDay = (time: string): string => `date(${time}, 'localtime')`;
+
+/**
+ * SQL for the local date of the Monday starting the week of a time.
+ */
+const localWeek = (time: string): string =>
+	`date(${time}, 'localtime', 'weekday 0', '-6 days')`;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a stats request.
+ * Pure function. Missing options take their default (30 days, 12 weeks,
+ * 10 apps).
+ *
+ * @throws if an option is not a positive integer within its limit
+ */
+export const parseStatsOptions = (input: unknown): StatsOptions => {
+	if (input === undefined || input === null) {
+		return { ...DEFAULT_STATS_OPTIONS };
+	}
+	if (typeof input !== "object") {
+		throw new Error("Invalid stats options: expected an object");
+	}
+
+	const values = input as Partial<Record<keyof StatsOptions, unknown>>;
+	const options = { ...DEFAULT_STATS_OPTIONS };
+	for (const key of STATS_OPTION_KEYS) {
+		const value = values[key];
+		if (value === undefined) continue;
+		if (
+			typeof value !== "number" ||
+			!Number.isInteger(value) ||
+			value < 1 ||
+			value > MAX_STATS_OPTIONS[key]
+		) {
+			throw new Error(
+				`Invalid stats options: ${key} must be an integer from 1 to ${MAX_STATS_OPTIONS[key]}`,
+			);
+		}
+		options[key] = value;
+	}
+	return options;
+};
+
+/**
+ * Validates the year of an activity heatmap.
+ * Pure function.
+ *
+ * @throws if it is not an integer from 1970 to 2999
+ */
+export const parseHeatmapYear = (year: unknown): number => {
+	if (
+		typeof year !== "number" ||
+		!Number.isInteger(year) ||
+		year < MIN_HEATMAP_YEAR ||
+		year > MAX_HEATMAP_YEAR
+	) {
+		throw new Error(`Invalid heatmap year: ${year}`);
+	}
+	return year;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the stats repository.
+ * Read-only aggregates over history, computed in SQL. Items are counted
+ * by when they were last copied, in local time, since a repeat copy moves
+ * an item rather than adding one.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createStatsRepository = (getDb: () => Database.Database) => {
+	/**
+	 * Counts items per period, from the start of the period `daysBack`
+	 * days before the current one.
+	 */
+	const countPerPeriod = (
+		toPeriod: (time: string) => string,
+		daysBack: number,
+	): PeriodCount[] =>
+		getDb()
+			.prepare(
+				`SELECT ${toPeriod("created_at")} AS period, COUNT(*) AS count FROM history WHERE created_at >= datetime(${toPeriod("'now'")}, ?, 'utc') GROUP BY period ORDER BY period`,
+			)
+			.all(`-${daysBack} days`) as PeriodCount[];
+
+	const getStats = (input?: unknown): HistoryStats => {
+		const options = parseStatsOptions(input);
+		const db = getDb();
+
+		const totals = db
+			.prepare(
+				"SELECT COUNT(*) AS count, AVG(byte_size) AS average FROM history",
+			)
+			.get() as { count: number; average: number | null };
+		const database =
+			(db.pragma("page_count", { simple: true }) as number) *
+			(db.pragma("page_size", { simple: true }) as number);
+		const attachments = db
+			.prepare("SELECT COALESCE(SUM(size), 0) FROM attachments")
+			.pluck()
+			.get() as number;
+
+		return {
+			totalItems: totals.count,
+			itemsPerDay: countPerPeriod(localDay, options.days - 1),
+			itemsPerWeek: countPerPeriod(localWeek, (options.weeks - 1) * 7),
+			topSourceApps: db
+				.prepare(
+					"SELECT source_app AS name, COUNT(*) AS count FROM history WHERE source_app IS NOT NULL GROUP BY source_app COLLATE NOCASE ORDER BY count DESC, name COLLATE NOCASE LIMIT ?",
+				)
+				.all(options.topApps) as SourceAppCount[],
+			types: db
+				.prepare(
+					"SELECT type, COUNT(*) AS count FROM history GROUP BY type ORDER BY count DESC, type",
+				)
+				.all() as HistoryStats["types"],
+			kinds: db
+				.prepare(
+					"SELECT content_kind AS kind, COUNT(*) AS count FROM history WHERE content_kind IS NOT NULL GROUP BY content_kind ORDER BY count DESC, kind",
+				)
+				.all() as HistoryStats["kinds"],
+			averageItemBytes:
+				totals.average === null ? null : Math.round(totals.average),
+			storageBytes: {
+				database,
+				attachments,
+				total: database + attachments,
+			},
+		};
+	};
+
+	/**
+	 * Counts items per local day of a calendar year, so the UI can draw a
+	 * calendar without loading rows.
+	 */
+	const getActivityHeatmap = (input: unknown): ActivityHeatmap => {
+		const year = parseHeatmapYear(input);
+		const start = `${year}-01-01`;
+		const days = getDb()
+			.prepare(
+				`SELECT ${localDay("created_at")} AS period, COUNT(*) AS count FROM history WHERE created_at >= datetime(?, 'utc') AND created_at < datetime(?, '+1 year', 'utc') GROUP BY period ORDER BY period`,
+			)
+			.all(start, start) as PeriodCount[];
+
+		return {
+			year,
+			days,
+			total: days.reduce((sum, day) => sum + day.count, 0),
+			maxCount: days.reduce((max, day) => Math.max(max, day.count), 0),
+		};
+	};
+
+	return { getStats, getActivityHeatmap };
+};
+
+export type StatsRepository = ReturnType<typeof createStatsRepository>;
@@ -0,0 +1,113 @@
+import { describe, expect, it } from "vitest";
+import {
+	derivePairingKey,
+	formatSyncMessage,
+	generatePairingCode,
+	normalizePairingCode,
+	parseSyncMessage,
+	SYNC_BATCH_SIZE,
+} from "./sync-protocol.js";
+
+const item = {
+	content: "hello",
+	rtf: null,
+	html: "<b>hello</b>",
+	created_at: "2024-01-01 12:00:00",
+};
+
+const tombstone = { hash: "0f".repeat(32), deleted_at: "2024-01-02 08:30:00" };
+
+describe("pairing codes", () => {
+	it("generates six-digit codes", () => {
+		for (let i = 0; i < 20; i++) {
+			expect(generatePairingCode()).toMatch(/^\d{6}$/);
+		}
+	});
+
+	it("accepts codes typed with spaces or dashes", () => {
+		expect(normalizePairingCode(" 123 456 ")).toBe("123456");
+		expect(normalizePairingCode("123-456")).toBe("123456");
+		expect(normalizePairingCode("12345")).toBeNull();
+		expect(normalizePairingCode("12345a")).toBeNull();
+	});
+
+	it("derives the same key from the same code on both devices", () => {
+		const key = derivePairingKey("123456");
+		expect(key).toHaveLength(32);
+		expect(derivePairingKey("123456").equals(key)).toBe(true);
+		expect(derivePairingKey("654321").equals(key)).toBe(false);
+	});
+});
+
+describe("parseSyncMessage", () => {
+	it("round-trips every message type", () => {
+		const messages = [
+			{ type: "pair", deviceId: "0123456789abcdef", name: "Laptop" },
+			{
+				type: "paired",
+				deviceId: "fedcba9876543210",
+				name: "Desktop",
+				key: "ab".repeat(32),
+			},
+			{ type: "items", items: [item] },
+			{ type: "deleted", tombstones: [tombstone] },
+			{ type: "ok", count: 1 },
+			{ type: "error", message: "History is locked" },
+		] as const;
+
+		for (const message of messages) {
+			const line = formatSyncMessage(message);
+			expect(line.endsWith("\n")).toBe(true);
+			expect(parseSyncMessage(line.trim())).toEqual(message);
+		}
+	});
+
+	it("rejects malformed messages", () => {
+		expect(() => parseSyncMessage("nope")).toThrow("expected JSON");
+		expect(() => parseSyncMessage('{"type":"hello"}')).toThrow(
+			"Unknown message: hello",
+		);
+		expect(() =>
+			parseSyncMessage('{"type":"pair","deviceId":"xyz","name":"A"}'),
+		).toThrow("expected deviceId and name");
+		expect(() =>
+			parseSyncMessage(
+				JSON.stringify({
+					type: "paired",
+					deviceId: "0123456789abcdef",
+					name: "A",
+					key: "short",
+				}),
+			),
+		).toThrow("expected a 32-byte hex key");
+		expect(() =>
+			parseSyncMessage(
+				JSON.stringify({ type: "items", items: [{ content: 1 }] }),
+			),
+		).toThrow("Invalid item");
+		expect(() =>
+			parseSyncMessage(
+				JSON.stringify({
+					type: "items",
+					items: Array(SYNC_BATCH_SIZE + 1).fill(item),
+				}),
+			),
+		).toThrow(`expected at most ${SYNC_BATCH_SIZE} items`);
+		expect(() =>
+			parseSyncMessage(
+				JSON.stringify({
+					type: "deleted",
+					tombstones: [{ ...tombstone, hash: "abc" }],
+				}),
+			),
+		).toThrow("expected a content hash");
+		expect(() =>
+			parseSyncMessage(
+				JSON.stringify({
+					type: "deleted",
+					tombstones: [{ ...tombstone, deleted_at: "yesterday" }],
+				}),
+			),
+		).toThrow("expected deleted_at");
+	});
+});
@@ -0,0 +1,268 @@
+import crypto from "node:crypto";
+
+/**
+ * mDNS service type sync peers advertise.
+ */
+export const SYNC_SERVICE_TYPE = "_clipman-sync._tcp.local";
+
+export const DEFAULT_SYNC_PORT = 47814;
+
+/**
+ * TLS 1.2 pre-shared key cipher suite used for every sync connection.
+ * ECDHE gives forward secrecy, so a recorded session cannot be decrypted
+ * later, and keeps a passive observer from testing pairing code guesses
+ * offline.
+ */
+export const SYNC_CIPHERS = "ECDHE-PSK-CHACHA20-POLY1305";
+
+/**
+ * PSK identity of a device connecting with a pairing code rather than a
+ * paired device's key.
+ */
+export const PAIRING_IDENTITY = "pair";
+
+/**
+ * Longest message line accepted (characters); room for a full batch of
+ * the largest clips history stores once JSON-escaped.
+ */
+export const MAX_SYNC_MESSAGE_LENGTH = 64_000_000;
+
+/**
+ * Items sent per `items` message.
+ */
+export const SYNC_BATCH_SIZE = 20;
+
+/**
+ * Deletions sent per `deleted` message.
+ */
+export const SYNC_TOMBSTONE_BATCH_SIZE = 500;
+
+const PAIRING_CODE_DIGITS = 6;
+const PAIRING_SALT = "clipman-sync-pairing";
+const KEY_BYTES = 32;
+const MAX_DEVICE_NAME_LENGTH = 64;
+const MAX_ITEM_CHARS = 1_000_000;
+
+/**
+ * A history item as sent to a peer. Only text items sync.
+ */
+export type SyncItem = {
+	content: string;
+	rtf: string | null;
+	html: string | null;
+	created_at: string;
+};
+
+/**
+ * A deleted item, identified by its content hash. Copies of the content
+ * made before `deleted_at` are deleted too; later copies win.
+ */
+export type SyncTombstone = {
+	hash: string;
+	deleted_at: string;
+};
+
+/**
+ * Messages between peers, one JSON object per line. The connecting device
+ * sends `pair`, `items` or `deleted`; the accepting device answers each
+ * with `paired`, `ok` or `error`.
+ */
+export type SyncMessage =
+	| { type: "pair"; deviceId: string; name: string }
+	| { type: "paired"; deviceId: string; name: string; key: string }
+	| { type: "items"; items: SyncItem[] }
+	| { type: "deleted"; tombstones: SyncTombstone[] }
+	| { type: "ok"; count: number }
+	| { type: "error"; message: string };
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Creates a random device id, used as the mDNS instance and PSK identity.
+ */
+export const generateDeviceId = (): string =>
+	crypto.randomBytes(8).toString("hex");
+
+/**
+ * Creates a random key for a newly paired device, hex-encoded.
+ */
+export const generatePeerKey = (): string =>
+	crypto.randomBytes(KEY_BYTES).toString("hex");
+
+/**
+ * Creates a random six-digit pairing code.
+ */
+export const generatePairingCode = (): string =>
+	crypto
+		.randomInt(0, 10 ** PAIRING_CODE_DIGITS)
+		.toString()
+		.padStart(PAIRING_CODE_DIGITS, "0");
+
+/**
+ * Strips the spaces and dashes people type to group a pairing code.
+ * Pure function.
+ *
+ * @returns the six digits, or null if the input is not a pairing code
+ */
+export const normalizePairingCode = (input: string): string | null => {
+	const code = input.replace(/[\s-]/g, "");
+	return new RegExp(`^\\d{${PAIRING_CODE_DIGITS}}$`).test(code) ? code : null;
+};
+
+/**
+ * Derives the pre-shared key both sides use while pairing.
+ * Pure function. scrypt makes each guess of a short code expensive.
+ */
+export const derivePairingKey = (code: string): Buffer =>
+	crypto.scryptSync(code, PAIRING_SALT, KEY_BYTES);
+
+/**
+ * Whether a string is a valid device id (16 hex characters).
+ * Pure function.
+ */
+export const isValidDeviceId = (value: unknown): value is string =>
+	typeof value === "string" && /^[0-9a-f]{16}$/.test(value);
+
+/**
+ * Trims a device name to what is shown and advertised.
+ * Pure function.
+ */
+export const normalizeDeviceName = (name: string): string =>
+	name.trim().slice(0, MAX_DEVICE_NAME_LENGTH);
+
+const isOptionalString = (value: unknown): value is string | null =>
+	value === null || typeof value === "string";
+
+/**
+ * Validates one received item.
+ * Pure function.
+ *
+ * @throws if the item is malformed or too large
+ */
+export const parseSyncItem = (input: unknown): SyncItem => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid item: expected an object");
+	}
+	const { content, rtf, html, created_at } = input as Record<string, unknown>;
+	if (
+		typeof content !== "string" ||
+		!isOptionalString(rtf) ||
+		!isOptionalString(html) ||
+		typeof created_at !== "string"
+	) {
+		throw new Error("Invalid item: expected content, rtf, html, created_at");
+	}
+	const size = content.length + (rtf?.length ?? 0) + (html?.length ?? 0);
+	if (size > MAX_ITEM_CHARS) {
+		throw new Error(`Invalid item: larger than ${MAX_ITEM_CHARS} chars`);
+	}
+	return { content, rtf, html, created_at };
+};
+
+/**
+ * Validates one received deletion.
+ * Pure function.
+ *
+ * @throws if the hash or timestamp is malformed
+ */
+export const parseSyncTombstone = (input: unknown): SyncTombstone => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid deletion: expected an object");
+	}
+	const { hash, deleted_at } = input as Record<string, unknown>;
+	if (typeof hash !== "string" || !/^[0-9a-f]{64}$/.test(hash)) {
+		throw new Error("Invalid deletion: expected a content hash");
+	}
+	if (
+		typeof deleted_at !== "string" ||
+		!/^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}$/.test(deleted_at)
+	) {
+		throw new Error(
+			"Invalid deletion: expected deleted_at as YYYY-MM-DD hh:mm:ss",
+		);
+	}
+	return { hash, deleted_at };
+};
+
+/**
+ * Validates one message line.
+ * Pure function.
+ *
+ * @throws with a message for the peer if the line is not a valid message
+ */
+export const parseSyncMessage = (line: string): SyncMessage => {
+	let input: unknown;
+	try {
+		input = JSON.parse(line);
+	} catch {
+		throw new Error("Invalid message: expected JSON");
+	}
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid message: expected an object");
+	}
+
+	const message = input as Record<string, unknown>;
+	switch (message.type) {
+		case "pair":
+		case "paired": {
+			const { deviceId, name, key } = message;
+			if (!isValidDeviceId(deviceId) || typeof name !== "string") {
+				throw new Error("Invalid message: expected deviceId and name");
+			}
+			if (message.type === "pair") {
+				return { type: "pair", deviceId, name: normalizeDeviceName(name) };
+			}
+			if (typeof key !== "string" || !/^[0-9a-f]{64}$/.test(key)) {
+				throw new Error("Invalid message: expected a 32-byte hex key");
+			}
+			return {
+				type: "paired",
+				deviceId,
+				name: normalizeDeviceName(name),
+				key,
+			};
+		}
+		case "items": {
+			const { items } = message;
+			if (!Array.isArray(items) || items.length > SYNC_BATCH_SIZE) {
+				throw new Error(
+					`Invalid message: expected at most ${SYNC_BATCH_SIZE} items`,
+				);
+			}
+			return { type: "items", items: items.map(parseSyncItem) };
+		}
+		case "deleted": {
+			const { tombstones } = message;
+			if (
+				!Array.isArray(tombstones) ||
+				tombstones.length > SYNC_TOMBSTONE_BATCH_SIZE
+			) {
+				throw new Error(
+					`Invalid message: expected at most ${SYNC_TOMBSTONE_BATCH_SIZE} deletions`,
+				);
+			}
+			return {
+				type: "deleted",
+				tombstones: tombstones.map(parseSyncTombstone),
+			};
+		}
+		case "ok":
+			if (typeof message.count !== "number") {
+				throw new Error("Invalid message: expected a count");
+			}
+			return { type: "ok", count: message.count };
+		case "error":
+			return { type: "error", message: String(message.message ?? "") };
+		default:
+			throw new Error(`Unknown message: ${String(message.type)}`);
+	}
+};
+
+/**
+ * Serializes a message as one line.
+ * Pure function.
+ */
+export const formatSyncMessage = (message: SyncMessage): string =>
+	`${JSON.stringify(message)}\n`;
@@ -0,0 +1,136 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import { DEFAULT_SYNC_PORT } from "./sync-protocol.js";
+import {
+	createSyncSettings,
+	getSyncSettingsPath,
+	parseSyncPeers,
+	parseSyncSettings,
+	type SyncSettings,
+} from "./sync-settings.js";
+
+const current: SyncSettings = {
+	enabled: false,
+	deviceId: "0123456789abcdef",
+	deviceName: "Laptop",
+	port: DEFAULT_SYNC_PORT,
+	peers: [],
+};
+
+const peer = {
+	deviceId: "fedcba9876543210",
+	name: "Desktop",
+	key: "ab".repeat(32),
+	lastSentId: 10,
+	lastSentTombstoneId: 2,
+};
+
+describe("parseSyncSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(parseSyncSettings({}, current)).toEqual(current);
+		expect(
+			parseSyncSettings({ enabled: true, deviceName: "  Work  " }, current),
+		).toEqual({ ...current, enabled: true, deviceName: "Work" });
+	});
+
+	it("ignores attempts to change the device id or peers", () => {
+		const input = { deviceId: "ffffffffffffffff", peers: [peer] };
+		expect(parseSyncSettings(input, current)).toEqual(current);
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseSyncSettings(null, current)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseSyncSettings({ enabled: "yes" }, current)).toThrow(
+			"enabled must be a boolean",
+		);
+		expect(() => parseSyncSettings({ deviceName: "  " }, current)).toThrow(
+			"deviceName must be a non-empty string",
+		);
+		expect(() => parseSyncSettings({ port: 22 }, current)).toThrow(
+			"port must be an integer from 1024 to 65535",
+		);
+	});
+});
+
+describe("parseSyncPeers", () => {
+	it("drops malformed peers", () => {
+		expect(
+			parseSyncPeers([
+				peer,
+				{ ...peer, deviceId: "nope" },
+				{ ...peer, key: "short" },
+				{ ...peer, lastSentId: -1 },
+				null,
+			]),
+		).toEqual([peer]);
+		// Paired before deletions synced
+		const older = { ...peer, lastSentTombstoneId: undefined };
+		expect(parseSyncPeers([older])).toEqual([
+			{ ...peer, lastSentTombstoneId: 0 },
+		]);
+		expect(parseSyncPeers("nope")).toEqual([]);
+	});
+});
+
+describe("createSyncSettings", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-sync-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("is off by default with a saved random device id", () => {
+		const userDataPath = createTempUserDataPath();
+		const deps = { userDataPath, defaultDeviceName: "laptop" };
+		const settings = createSyncSettings(deps).getSettings();
+
+		expect(settings).toMatchObject({
+			enabled: false,
+			deviceName: "laptop",
+			port: DEFAULT_SYNC_PORT,
+			peers: [],
+		});
+		expect(settings.deviceId).toMatch(/^[0-9a-f]{16}$/);
+		expect(createSyncSettings(deps).getSettings()).toEqual(settings);
+	});
+
+	it.skipIf(process.platform === "win32")(
+		"keeps the settings file private to the user",
+		() => {
+			const userDataPath = createTempUserDataPath();
+			createSyncSettings({ userDataPath, defaultDeviceName: "laptop" });
+
+			const { mode } = fs.statSync(getSyncSettingsPath(userDataPath));
+			expect(mode & 0o777).toBe(0o600);
+		},
+	);
+
+	it("saves paired devices and sync progress", () => {
+		const userDataPath = createTempUserDataPath();
+		const deps = { userDataPath, defaultDeviceName: "laptop" };
+		const store = createSyncSettings(deps);
+
+		store.addPeer(peer);
+		store.addPeer({ ...peer, name: "Renamed" });
+		store.setProgress(peer.deviceId, { lastSentId: 42 });
+		store.setProgress(peer.deviceId, { lastSentTombstoneId: 5 });
+		expect(createSyncSettings(deps).getSettings().peers).toEqual([
+			{ ...peer, name: "Renamed", lastSentId: 42, lastSentTombstoneId: 5 },
+		]);
+
+		store.removePeer(peer.deviceId);
+		expect(createSyncSettings(deps).getSettings().peers).toEqual([]);
+	});
+});
@@ -0,0 +1,263 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	DEFAULT_SYNC_PORT,
+	generateDeviceId,
+	isValidDeviceId,
+	normalizeDeviceName,
+} from "./sync-protocol.js";
+
+/**
+ * A device this one has paired with.
+ */
+export type SyncPeer = {
+	deviceId: string;
+	name: string;
+	/** Pre-shared key for connections in either direction, hex-encoded */
+	key: string;
+	/** Newest local history id already sent to this device */
+	lastSentId: number;
+	/** Newest local deletion already sent to this device */
+	lastSentTombstoneId: number;
+};
+
+/**
+ * Persisted settings for LAN sync.
+ */
+export type SyncSettings = {
+	/** Advertise, accept and send items; off until the user opts in */
+	enabled: boolean;
+	/** Random id identifying this device to its peers */
+	deviceId: string;
+	/** Shown to other devices while pairing */
+	deviceName: string;
+	port: number;
+	peers: SyncPeer[];
+};
+
+const MIN_PORT = 1024;
+const MAX_PORT = 65535;
+
+const SYNC_FILENAME = "sync.json";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a sync settings update. Only `enabled`, `deviceName` and
+ * `port` can be changed this way; peers change by pairing.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean, `deviceName` is not a non-empty
+ *   string, or `port` is not an integer from 1024 to 65535
+ */
+export const parseSyncSettings = (
+	input: unknown,
+	current: SyncSettings,
+): SyncSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid sync settings: expected an object");
+	}
+
+	const { enabled, deviceName, port } = input as Partial<
+		Record<keyof SyncSettings, unknown>
+	>;
+	const next: SyncSettings = { ...current };
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw new Error("Invalid sync settings: enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (deviceName !== undefined) {
+		const name =
+			typeof deviceName === "string" ? normalizeDeviceName(deviceName) : "";
+		if (!name) {
+			throw new Error(
+				"Invalid sync settings: deviceName must be a non-empty string",
+			);
+		}
+		next.deviceName = name;
+	}
+
+	if (port !== undefined) {
+		if (
+			typeof port !== "number" ||
+			!Number.isInteger(port) ||
+			port < MIN_PORT ||
+			port > MAX_PORT
+		) {
+			throw new Error(
+				`Invalid sync settings: port must be an integer from ${MIN_PORT} to ${MAX_PORT}`,
+			);
+		}
+		next.port = port;
+	}
+
+	return next;
+};
+
+const isProgressId = (value: unknown): value is number =>
+	typeof value === "number" && Number.isInteger(value) && value >= 0;
+
+/**
+ * Reads the stored peer list, dropping malformed entries. Peers paired
+ * before deletions synced start with no deletions sent.
+ * Pure function.
+ */
+export const parseSyncPeers = (input: unknown): SyncPeer[] => {
+	if (!Array.isArray(input)) return [];
+	return input.flatMap((entry): SyncPeer[] => {
+		if (typeof entry !== "object" || entry === null) return [];
+		const { deviceId, name, key, lastSentId, lastSentTombstoneId } =
+			entry as Record<string, unknown>;
+		if (
+			!isValidDeviceId(deviceId) ||
+			typeof name !== "string" ||
+			typeof key !== "string" ||
+			!/^[0-9a-f]{64}$/.test(key) ||
+			!isProgressId(lastSentId)
+		) {
+			return [];
+		}
+		return [
+			{
+				deviceId,
+				name,
+				key,
+				lastSentId,
+				lastSentTombstoneId: isProgressId(lastSentTombstoneId)
+					? lastSentTombstoneId
+					: 0,
+			},
+		];
+	});
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getSyncSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, SYNC_FILENAME);
+
+const writeSyncSettingsToFile = (filePath: string, settings: SyncSettings) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ * A device id is generated and saved when none is stored yet.
+ */
+const readSyncSettingsFromFile = (
+	filePath: string,
+	defaultDeviceName: string,
+): SyncSettings => {
+	const defaults: SyncSettings = {
+		enabled: false,
+		deviceId: "",
+		deviceName: normalizeDeviceName(defaultDeviceName) || "Clipboard Manager",
+		port: DEFAULT_SYNC_PORT,
+		peers: [],
+	};
+
+	let settings = defaults;
+	if (fs.existsSync(filePath)) {
+		try {
+			const raw = JSON.parse(fs.readFileSync(filePath, "utf-8"));
+			settings = {
+				...parseSyncSettings(raw, defaults),
+				deviceId: isValidDeviceId(raw.deviceId) ? raw.deviceId : "",
+				peers: parseSyncPeers(raw.peers),
+			};
+		} catch (error) {
+			console.error("Failed to read sync settings, using defaults:", error);
+		}
+	}
+
+	if (!settings.deviceId) {
+		settings = { ...settings, deviceId: generateDeviceId() };
+		writeSyncSettingsToFile(filePath, settings);
+	}
+	return settings;
+};
+
+// ============================================================================
+// Sync Settings Module
+// ============================================================================
+
+export type SyncSettingsDeps = {
+	userDataPath: string;
+	/** Device name until the user picks one, e.g. the host name */
+	defaultDeviceName: string;
+};
+
+/**
+ * Creates the sync settings store. The file is readable by the current
+ * user only, since it holds the peers' keys.
+ */
+export const createSyncSettings = (deps: SyncSettingsDeps) => {
+	const filePath = getSyncSettingsPath(deps.userDataPath);
+	let settings = readSyncSettingsFromFile(filePath, deps.defaultDeviceName);
+
+	const getSettings = (): SyncSettings => ({
+		...settings,
+		peers: settings.peers.map((peer) => ({ ...peer })),
+	});
+
+	const save = (next: SyncSettings): SyncSettings => {
+		writeSyncSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const updateSettings = (input: unknown): SyncSettings =>
+		save(parseSyncSettings(input, settings));
+
+	/**
+	 * Adds a paired device, replacing an earlier pairing with it.
+	 */
+	const addPeer = (peer: SyncPeer): SyncSettings =>
+		save({
+			...settings,
+			peers: [
+				...settings.peers.filter((other) => other.deviceId !== peer.deviceId),
+				peer,
+			],
+		});
+
+	/**
+	 * Forgets a paired device; its key stops working immediately.
+	 */
+	const removePeer = (deviceId: string): SyncSettings =>
+		save({
+			...settings,
+			peers: settings.peers.filter((peer) => peer.deviceId !== deviceId),
+		});
+
+	/**
+	 * Records how far history and deletions have been sent to a device.
+	 */
+	const setProgress = (
+		deviceId: string,
+		progress: Partial<Pick<SyncPeer, "lastSentId" | "lastSentTombstoneId">>,
+	): void => {
+		save({
+			...settings,
+			peers: settings.peers.map((peer) =>
+				peer.deviceId === deviceId ? { ...peer, ...progress } : peer,
+			),
+		});
+	};
+
+	return { getSettings, updateSettings, addPeer, removePeer, setProgress };
+};
+
+export type SyncSettingsStore = ReturnType<typeof createSyncSettings>;
@@ -0,0 +1,670 @@
+import tls from "node:tls";
+import { createMdnsDiscovery, type MdnsPeer } from "./mdns.js";
+import {
+	derivePairingKey,
+	formatSyncMessage,
+	generatePairingCode,
+	generatePeerKey,
+	isValidDeviceId,
+	MAX_SYNC_MESSAGE_LENGTH,
+	normalizePairingCode,
+	PAIRING_IDENTITY,
+	parseSyncMessage,
+	SYNC_BATCH_SIZE,
+	SYNC_CIPHERS,
+	SYNC_SERVICE_TYPE,
+	SYNC_TOMBSTONE_BATCH_SIZE,
+	type SyncItem,
+	type SyncMessage,
+	type SyncTombstone,
+} from "./sync-protocol.js";
+import type { SyncPeer, SyncSettingsStore } from "./sync-settings.js";
+
+/**
+ * What sync reads from and writes to history.
+ */
+export type SyncHandlers = {
+	/** Local items after `afterId` that may be sent, oldest first */
+	listItemsAfter: (
+		afterId: number,
+		limit: number,
+	) => Array<SyncItem & { id: number }>;
+	/** Newest local history id; a new peer is sent items after it */
+	getLastItemId: () => number;
+	/** Stores items received from a peer; returns how many were stored */
+	receiveItems: (items: SyncItem[], deviceId: string) => number;
+	/** Local deletions after `afterId` that may be sent, oldest first */
+	listTombstonesAfter: (
+		afterId: number,
+		limit: number,
+	) => Array<SyncTombstone & { id: number }>;
+	/** Newest local deletion id; a new peer is sent deletions after it */
+	getLastTombstoneId: () => number;
+	/** Applies deletions received from a peer; returns items deleted */
+	receiveTombstones: (tombstones: SyncTombstone[], deviceId: string) => number;
+};
+
+export type SyncDeps = {
+	settings: SyncSettingsStore;
+	handlers: SyncHandlers;
+	/** Host name given in the mDNS advertisement */
+	hostname: string;
+	/** Nothing is sent or accepted while the app is locked */
+	isLocked: () => boolean;
+};
+
+/**
+ * A device on the network or paired with this one.
+ */
+export type SyncDevice = {
+	deviceId: string;
+	name: string;
+	paired: boolean;
+	/** Currently advertising on the local network */
+	online: boolean;
+};
+
+/**
+ * Sync settings without peer keys, plus what is happening right now.
+ */
+export type SyncStatus = {
+	enabled: boolean;
+	deviceId: string;
+	deviceName: string;
+	port: number;
+	running: boolean;
+	devices: SyncDevice[];
+	/** Code another device must enter, while pairing is open */
+	pairingCode: string | null;
+	pairingExpiresAt: string | null;
+};
+
+/**
+ * How long a pairing code is accepted.
+ */
+const PAIRING_WINDOW_MS = 2 * 60 * 1000;
+
+/**
+ * Failed handshakes allowed before a pairing code is withdrawn, so the
+ * code cannot be guessed online.
+ */
+const MAX_PAIRING_FAILURES = 5;
+
+/**
+ * How long to wait for a peer to answer before giving up.
+ */
+const REQUEST_TIMEOUT_MS = 15_000;
+
+/**
+ * Delay between a capture and sending it to peers.
+ */
+const SEND_DELAY_MS = 5_000;
+
+type DiscoveredDevice = {
+	name: string;
+	address: string;
+	port: number;
+	expiresAt: number;
+};
+
+type Pairing = {
+	code: string;
+	key: Buffer;
+	expiresAt: number;
+	failures: number;
+};
+
+// ============================================================================
+// Connections
+// ============================================================================
+
+/**
+ * Throws unless a peer accepted a message.
+ */
+const assertAccepted = (reply: SyncMessage): void => {
+	if (reply.type !== "ok") {
+		throw new Error(
+			reply.type === "error" ? reply.message : "Unexpected reply",
+		);
+	}
+};
+
+/**
+ * Calls `onLine` for each newline-terminated line a socket sends,
+ * destroying the socket if a line grows past the message limit.
+ */
+const readLines = (
+	socket: tls.TLSSocket,
+	onLine: (line: string) => void,
+): void => {
+	let buffer = "";
+	socket.setEncoding("utf-8");
+	socket.on("data", (chunk: string) => {
+		buffer += chunk;
+		let newline = buffer.indexOf("\n");
+		while (newline !== -1) {
+			const line = buffer.slice(0, newline);
+			buffer = buffer.slice(newline + 1);
+			onLine(line);
+			newline = buffer.indexOf("\n");
+		}
+		if (buffer.length > MAX_SYNC_MESSAGE_LENGTH) socket.destroy();
+	});
+};
+
+/**
+ * Opens a TLS-PSK connection and returns a function sending one message
+ * at a time and resolving to the peer's answer.
+ */
+const connectToPeer = (
+	address: string,
+	port: number,
+	identity: string,
+	key: Buffer,
+) =>
+	new Promise<{
+		request: (message: SyncMessage) => Promise<SyncMessage>;
+		close: () => void;
+	}>((resolve, reject) => {
+		const socket = tls.connect({
+			host: address,
+			port,
+			ciphers: SYNC_CIPHERS,
+			minVersion: "TLSv1.2",
+			maxVersion: "TLSv1.2",
+			pskCallback: () => ({ psk: key, identity }),
+			// PSK suites authenticate with the key; there is no certificate
+			checkServerIdentity: () => undefined,
+		});
+		socket.setTimeout(REQUEST_TIMEOUT_MS, () => {
+			socket.destroy(new Error("Sync peer did not answer"));
+		});
+
+		let waiting: {
+			resolve: (message: SyncMessage) => void;
+			reject: (error: Error) => void;
+		} | null = null;
+		readLines(socket, (line) => {
+			const current = waiting;
+			waiting = null;
+			if (!current) return;
+			try {
+				current.resolve(parseSyncMessage(line));
+			} catch (error) {
+				current.reject(error as Error);
+			}
+		});
+		socket.on("error", (error) => {
+			waiting?.reject(error);
+			waiting = null;
+			reject(error);
+		});
+		socket.on("close", () => {
+			waiting?.reject(new Error("Sync connection closed"));
+			waiting = null;
+		});
+
+		socket.once("secureConnect", () => {
+			resolve({
+				request: (message) =>
+					new Promise((resolveReply, rejectReply) => {
+						waiting = { resolve: resolveReply, reject: rejectReply };
+						socket.write(formatSyncMessage(message));
+					}),
+				close: () => socket.end(),
+			});
+		});
+	});
+
+// ============================================================================
+// Sync
+// ============================================================================
+
+/**
+ * Creates LAN sync: advertises this device over mDNS, accepts TLS-PSK
+ * connections from paired devices, and sends them new history items and
+ * deletions. Each device pushes its own changes; received items and
+ * deletions are never sent on.
+ */
+export const createSync = (deps: SyncDeps) => {
+	let server: tls.Server | null = null;
+	let port: number | null = null;
+	let pairing: Pairing | null = null;
+	const discovered = new Map<string, DiscoveredDevice>();
+	/** Identity each accepted socket authenticated with */
+	const identities = new WeakMap<tls.TLSSocket, string>();
+	/** Peers being sent to, and whether another send was asked for meanwhile */
+	const sending = new Map<string, boolean>();
+	let sendTimer: NodeJS.Timeout | null = null;
+
+	const getPairing = (): Pairing | null => {
+		if (pairing && pairing.expiresAt <= Date.now()) pairing = null;
+		return pairing;
+	};
+
+	const getOnlineDevice = (deviceId: string): DiscoveredDevice | undefined => {
+		const device = discovered.get(deviceId);
+		if (device && device.expiresAt <= Date.now()) {
+			discovered.delete(deviceId);
+			return undefined;
+		}
+		return device;
+	};
+
+	const discovery = createMdnsDiscovery({
+		serviceType: SYNC_SERVICE_TYPE,
+		hostname: `${deps.hostname}.local`,
+		getAdvertisement: () => {
+			if (port === null) return null;
+			const { deviceId, deviceName } = deps.settings.getSettings();
+			return {
+				instance: deviceId,
+				port,
+				txt: { id: deviceId, name: deviceName },
+			};
+		},
+		onPeer: (peer: MdnsPeer) => {
+			const deviceId = peer.txt.id ?? peer.instance;
+			if (!isValidDeviceId(deviceId)) return;
+			if (peer.ttl === 0) {
+				discovered.delete(deviceId);
+				return;
+			}
+			const wasOnline = getOnlineDevice(deviceId) !== undefined;
+			discovered.set(deviceId, {
+				name: peer.txt.name || deviceId,
+				address: peer.address,
+				port: peer.port,
+				expiresAt: Date.now() + peer.ttl * 1000,
+			});
+			// Catch a peer up on what it missed while away
+			if (!wasOnline) sendToPeer(deviceId);
+		},
+	});
+
+	const findPeer = (deviceId: string): SyncPeer | undefined =>
+		deps.settings
+			.getSettings()
+			.peers.find((peer) => peer.deviceId === deviceId);
+
+	/**
+	 * Answers one message on an accepted connection.
+	 */
+	const answer = (identity: string, message: SyncMessage): SyncMessage => {
+		if (identity === PAIRING_IDENTITY) {
+			if (message.type !== "pair") {
+				throw new Error("Expected a pair message");
+			}
+			const key = generatePeerKey();
+			deps.settings.addPeer({
+				deviceId: message.deviceId,
+				name: message.name,
+				key,
+				lastSentId: deps.handlers.getLastItemId(),
+				lastSentTombstoneId: deps.handlers.getLastTombstoneId(),
+			});
+			pairing = null;
+			const { deviceId, deviceName } = deps.settings.getSettings();
+			return { type: "paired", deviceId, name: deviceName, key };
+		}
+
+		if (message.type !== "items" && message.type !== "deleted") {
+			throw new Error("Expected an items or deleted message");
+		}
+		if (deps.isLocked()) {
+			throw new Error("History is locked");
+		}
+		const count =
+			message.type === "items"
+				? deps.handlers.receiveItems(message.items, identity)
+				: deps.handlers.receiveTombstones(message.tombstones, identity);
+		return { type: "ok", count };
+	};
+
+	const handleConnection = (socket: tls.TLSSocket): void => {
+		const identity = identities.get(socket);
+		if (!identity) {
+			socket.destroy();
+			return;
+		}
+		socket.setTimeout(REQUEST_TIMEOUT_MS, () => socket.destroy());
+		socket.on("error", () => socket.destroy());
+
+		readLines(socket, (line) => {
+			let reply: SyncMessage;
+			try {
+				reply = answer(identity, parseSyncMessage(line));
+			} catch (error) {
+				reply = {
+					type: "error",
+					message: error instanceof Error ? error.message : String(error),
+				};
+			}
+			socket.write(formatSyncMessage(reply));
+			if (reply.type !== "ok") socket.end();
+		});
+	};
+
+	/**
+	 * Picks the key for a connecting device: the pairing key while pairing
+	 * is open, otherwise the paired device's key. Unknown identities get no
+	 * key, which fails the handshake.
+	 */
+	const lookupKey = (socket: tls.TLSSocket, identity: string) => {
+		if (deps.isLocked()) return null;
+		if (identity === PAIRING_IDENTITY) {
+			const current = getPairing();
+			if (!current) return null;
+			identities.set(socket, identity);
+			return current.key;
+		}
+		const peer = findPeer(identity);
+		if (!peer) return null;
+		identities.set(socket, identity);
+		return Buffer.from(peer.key, "hex");
+	};
+
+	const handleClientError = (_error: Error, socket: tls.TLSSocket): void => {
+		const current = getPairing();
+		if (!current || identities.get(socket) !== PAIRING_IDENTITY) return;
+		current.failures += 1;
+		if (current.failures >= MAX_PAIRING_FAILURES) {
+			console.error("Pairing code withdrawn after repeated failures");
+			pairing = null;
+		}
+	};
+
+	/**
+	 * Sends a peer every item and then every deletion it has not received
+	 * yet, in batches.
+	 */
+	const sendBacklog = async (
+		peer: SyncPeer,
+		device: DiscoveredDevice,
+	): Promise<void> => {
+		const { handlers } = deps;
+		let items = handlers.listItemsAfter(peer.lastSentId, SYNC_BATCH_SIZE);
+		let tombstones = handlers.listTombstonesAfter(
+			peer.lastSentTombstoneId,
+			SYNC_TOMBSTONE_BATCH_SIZE,
+		);
+		if (items.length === 0 && tombstones.length === 0) return;
+
+		const { deviceId } = deps.settings.getSettings();
+		const connection = await connectToPeer(
+			device.address,
+			device.port,
+			deviceId,
+			Buffer.from(peer.key, "hex"),
+		);
+		try {
+			while (items.length > 0 && !deps.isLocked()) {
+				const reply = await connection.request({
+					type: "items",
+					items: items.map(({ content, rtf, html, created_at }) => ({
+						content,
+						rtf,
+						html,
+						created_at,
+					})),
+				});
+				assertAccepted(reply);
+				const lastSentId = items[items.length - 1].id;
+				deps.settings.setProgress(peer.deviceId, { lastSentId });
+				items = handlers.listItemsAfter(lastSentId, SYNC_BATCH_SIZE);
+			}
+			while (tombstones.length > 0 && !deps.isLocked()) {
+				const reply = await connection.request({
+					type: "deleted",
+					tombstones: tombstones.map(({ hash, deleted_at }) => ({
+						hash,
+						deleted_at,
+					})),
+				});
+				assertAccepted(reply);
+				const lastSentTombstoneId = tombstones[tombstones.length - 1].id;
+				deps.settings.setProgress(peer.deviceId, { lastSentTombstoneId });
+				tombstones = handlers.listTombstonesAfter(
+					lastSentTombstoneId,
+					SYNC_TOMBSTONE_BATCH_SIZE,
+				);
+			}
+		} finally {
+			connection.close();
+		}
+	};
+
+	/**
+	 * Sends new items to a paired device if it is online. Sends to the same
+	 * device never overlap; one asked for meanwhile runs afterwards.
+	 */
+	const sendToPeer = (deviceId: string): void => {
+		if (sending.has(deviceId)) {
+			sending.set(deviceId, true);
+			return;
+		}
+		const peer = findPeer(deviceId);
+		const device = getOnlineDevice(deviceId);
+		if (port === null || !peer || !device || deps.isLocked()) return;
+
+		sending.set(deviceId, false);
+		sendBacklog(peer, device)
+			.catch((error) => {
+				console.error(`Failed to sync with ${peer.name}:`, error);
+			})
+			.finally(() => {
+				const again = sending.get(deviceId);
+				sending.delete(deviceId);
+				if (again) sendToPeer(deviceId);
+			});
+	};
+
+	/**
+	 * Sends new captures and deletions to every paired device that is
+	 * online, after a short delay that batches quick changes and leaves
+	 * time to mark an item local-only.
+	 */
+	const notifyLocalChange = (): void => {
+		if (sendTimer) return;
+		sendTimer = setTimeout(() => {
+			sendTimer = null;
+			for (const peer of deps.settings.getSettings().peers) {
+				sendToPeer(peer.deviceId);
+			}
+		}, SEND_DELAY_MS);
+	};
+
+	/**
+	 * Opens pairing on this device; another device pairs by entering the
+	 * returned code within two minutes.
+	 *
+	 * @throws if sync is not running
+	 */
+	const startPairing = (): { code: string; expiresAt: string } => {
+		if (port === null) {
+			throw new Error("Sync is not enabled");
+		}
+		const code = generatePairingCode();
+		const expiresAt = Date.now() + PAIRING_WINDOW_MS;
+		pairing = { code, key: derivePairingKey(code), expiresAt, failures: 0 };
+		return { code, expiresAt: new Date(expiresAt).toISOString() };
+	};
+
+	const cancelPairing = (): void => {
+		pairing = null;
+	};
+
+	/**
+	 * Pairs with a device on the network using the code it shows.
+	 *
+	 * @throws if the device is not online, the code is malformed or wrong,
+	 *   or the device is no longer pairing
+	 */
+	const pairWith = async (deviceId: string, input: string): Promise<void> => {
+		const device = getOnlineDevice(deviceId);
+		if (port === null || !device) {
+			throw new Error("Device not found on the network");
+		}
+		const code = normalizePairingCode(input);
+		if (!code) {
+			throw new Error("Pairing code must be six digits");
+		}
+
+		const connection = await connectToPeer(
+			device.address,
+			device.port,
+			PAIRING_IDENTITY,
+			derivePairingKey(code),
+		).catch(() => {
+			throw new Error("Pairing failed: check the code and try again");
+		});
+		try {
+			const settings = deps.settings.getSettings();
+			const reply = await connection.request({
+				type: "pair",
+				deviceId: settings.deviceId,
+				name: settings.deviceName,
+			});
+			if (reply.type === "error") {
+				throw new Error(`Pairing failed: ${reply.message}`);
+			}
+			if (reply.type !== "paired" || reply.deviceId !== deviceId) {
+				throw new Error("Pairing failed: unexpected reply");
+			}
+			deps.settings.addPeer({
+				deviceId,
+				name: reply.name,
+				key: reply.key,
+				lastSentId: deps.handlers.getLastItemId(),
+				lastSentTombstoneId: deps.handlers.getLastTombstoneId(),
+			});
+		} finally {
+			connection.close();
+		}
+	};
+
+	/**
+	 * Forgets a paired device. It keeps its copy of the pairing until it
+	 * is unpaired there too, but can no longer connect.
+	 */
+	const unpair = (deviceId: string): void => {
+		deps.settings.removePeer(deviceId);
+	};
+
+	const stop = (): Promise<void> =>
+		new Promise((resolve) => {
+			const current = server;
+			if (sendTimer) {
+				clearTimeout(sendTimer);
+				sendTimer = null;
+			}
+			discovery.stop();
+			server = null;
+			port = null;
+			pairing = null;
+			discovered.clear();
+			if (!current) {
+				resolve();
+				return;
+			}
+			current.close(() => resolve());
+		});
+
+	/**
+	 * Starts accepting connections on `port` and advertising this device,
+	 * restarting if it was already running on another port.
+	 */
+	const start = async (nextPort: number): Promise<void> => {
+		if (server && port === nextPort) return;
+		await stop();
+
+		const next = tls.createServer(
+			{
+				ciphers: SYNC_CIPHERS,
+				minVersion: "TLSv1.2",
+				maxVersion: "TLSv1.2",
+				pskCallback: lookupKey,
+			},
+			handleConnection,
+		);
+		next.on("tlsClientError", handleClientError);
+		await new Promise<void>((resolve, reject) => {
+			next.once("error", reject);
+			next.listen(nextPort, () => {
+				next.off("error", reject);
+				resolve();
+			});
+		});
+		next.on("error", (error) => {
+			console.error("Sync server error:", error);
+		});
+		server = next;
+		port = nextPort;
+
+		// Sync still works with devices that find us, so a busy mDNS port
+		// is not fatal
+		discovery.start().catch((error) => {
+			console.error("Failed to start mDNS discovery:", error);
+		});
+	};
+
+	/**
+	 * Starts, restarts or stops sync to match its settings.
+	 */
+	const apply = async (): Promise<void> => {
+		const settings = deps.settings.getSettings();
+		if (settings.enabled) {
+			await start(settings.port);
+			// A new name is announced right away
+			discovery.announce();
+		} else {
+			await stop();
+		}
+	};
+
+	const getStatus = (): SyncStatus => {
+		const settings = deps.settings.getSettings();
+		const devices: SyncDevice[] = settings.peers.map((peer) => ({
+			deviceId: peer.deviceId,
+			name: peer.name,
+			paired: true,
+			online: getOnlineDevice(peer.deviceId) !== undefined,
+		}));
+		for (const [id, device] of discovered) {
+			if (devices.some((known) => known.deviceId === id)) continue;
+			if (!getOnlineDevice(id)) continue;
+			devices.push({
+				deviceId: id,
+				name: device.name,
+				paired: false,
+				online: true,
+			});
+		}
+		const current = getPairing();
+		return {
+			enabled: settings.enabled,
+			deviceId: settings.deviceId,
+			deviceName: settings.deviceName,
+			port: settings.port,
+			running: port !== null,
+			devices,
+			pairingCode: current?.code ?? null,
+			pairingExpiresAt: current
+				? new Date(current.expiresAt).toISOString()
+				: null,
+		};
+	};
+
+	return {
+		apply,
+		stop,
+		getStatus,
+		startPairing,
+		cancelPairing,
+		pairWith,
+		unpair,
+		notifyLocalChange,
+	};
+};
+
+export type Sync = ReturnType<typeof createSync>;
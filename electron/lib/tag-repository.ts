@@ -0,0 +1,112 @@
+import type Database from "better-sqlite3";
+import { isValidId } from "./history-repository.js";
+import { assertValidTagName } from "./tags.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * A tag with the number of history items carrying it.
+ */
+export type TagSummary = {
+	name: string;
+	count: number;
+};
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/**
+ * Creates the tag repository.
+ * Owns every SQL statement against the `tags` and `history_tags` tables.
+ * Filtering history by tag, and exporting and importing items with their
+ * tags, live in the history repository.
+ *
+ * @param getDb - Accessor for the open database connection
+ */
+export const createTagRepository = (getDb: () => Database.Database) => {
+	const assertItemExists = (itemId: unknown): number => {
+		if (!isValidId(itemId)) {
+			throw new Error(`Invalid history item id: ${itemId}`);
+		}
+		const existing = getDb()
+			.prepare("SELECT id FROM history WHERE id = ?")
+			.get(itemId);
+		if (!existing) {
+			throw new Error(`History item not found: ${itemId}`);
+		}
+		return itemId;
+	};
+
+	/**
+	 * Lists the tags of one item, alphabetically.
+	 */
+	const listItemTags = (itemId: number): string[] => {
+		if (!isValidId(itemId)) {
+			throw new Error(`Invalid history item id: ${itemId}`);
+		}
+		const rows = getDb()
+			.prepare(
+				"SELECT t.name FROM history_tags ht JOIN tags t ON t.id = ht.tag_id WHERE ht.history_id = ? ORDER BY t.name",
+			)
+			.all(itemId) as Array<{ name: string }>;
+		return rows.map((row) => row.name);
+	};
+
+	/**
+	 * Adds a tag to an item, creating the tag if needed.
+	 * Adding a tag the item already has is a no-op.
+	 * @returns the item's tags after the change
+	 */
+	const addTag = (itemId: number, name: string): string[] => {
+		const validId = assertItemExists(itemId);
+		const tagName = assertValidTagName(name);
+		const db = getDb();
+
+		db.transaction(() => {
+			db.prepare("INSERT OR IGNORE INTO tags (name) VALUES (?)").run(tagName);
+			db.prepare(
+				"INSERT OR IGNORE INTO history_tags (history_id, tag_id) SELECT ?, id FROM tags WHERE name = ?",
+			).run(validId, tagName);
+		})();
+
+		return listItemTags(validId);
+	};
+
+	/**
+	 * Removes a tag from an item.
+	 * @returns the item's tags after the change
+	 */
+	const removeTag = (itemId: number, name: string): string[] => {
+		const validId = assertItemExists(itemId);
+		getDb()
+			.prepare(
+				"DELETE FROM history_tags WHERE history_id = ? AND tag_id = (SELECT id FROM tags WHERE name = ?)",
+			)
+			.run(validId, assertValidTagName(name));
+
+		return listItemTags(validId);
+	};
+
+	/**
+	 * Lists every tag in use with its item count, alphabetically.
+	 * Tags no longer on any item are left out.
+	 */
+	const listTags = (): TagSummary[] =>
+		getDb()
+			.prepare(
+				"SELECT t.name, COUNT(ht.history_id) AS count FROM tags t JOIN history_tags ht ON ht.tag_id = t.id GROUP BY t.id ORDER BY t.name",
+			)
+			.all() as TagSummary[];
+
+	return {
+		addTag,
+		removeTag,
+		listItemTags,
+		listTags,
+	};
+};
+
+export type TagRepository = ReturnType<typeof createTagRepository>;
@@ -0,0 +1,21 @@
+import { describe, expect, it } from "vitest";
+import { assertValidTagName, normalizeTagName } from "./tags.js";
+
+describe("normalizeTagName", () => {
+	it("trims, lowercases, and collapses whitespace", () => {
+		expect(normalizeTagName("  Work  Stuff ")).toBe("work stuff");
+	});
+
+	it("rejects empty, oversized, and non-string names", () => {
+		expect(normalizeTagName("   ")).toBeNull();
+		expect(normalizeTagName("x".repeat(51))).toBeNull();
+		expect(normalizeTagName(42)).toBeNull();
+	});
+});
+
+describe("assertValidTagName", () => {
+	it("throws for names that cannot be normalized", () => {
+		expect(assertValidTagName("SQL")).toBe("sql");
+		expect(() => assertValidTagName("")).toThrow("Invalid tag name");
+	});
+});
@@ -0,0 +1,40 @@
+/**
+ * Tag name rules shared by the tag repository and tag-filtered history
+ * queries.
+ */
+
+/**
+ * Maximum tag name length (characters).
+ */
+const MAX_TAG_LENGTH = 50;
+
+/**
+ * Normalizes a tag name for storage and lookup.
+ * Pure function.
+ *
+ * Names are trimmed, lowercased, and have internal whitespace collapsed,
+ * so "Work" and " work " are the same tag.
+ *
+ * @returns The normalized name, or null if it is empty or too long
+ */
+export const normalizeTagName = (name: unknown): string | null => {
+	if (typeof name !== "string") return null;
+	const normalized = name.trim().replace(/\s+/g, " ").toLowerCase();
+	if (normalized.length === 0 || normalized.length > MAX_TAG_LENGTH) {
+		return null;
+	}
+	return normalized;
+};
+
+/**
+ * Throws if a tag name cannot be normalized.
+ */
+export const assertValidTagName = (name: unknown): string => {
+	const normalized = normalizeTagName(name);
+	if (normalized === null) {
+		throw new Error(
+			`Invalid tag name: must be 1-${MAX_TAG_LENGTH} characters`,
+		);
+	}
+	return normalized;
+};
@@ -0,0 +1,142 @@
+import fs from "node:fs";
+import path from "node:path";
+import {
+	DatabaseSync,
+	type SQLInputValue,
+	type SQLOutputValue,
+	type StatementSync,
+} from "node:sqlite";
+import { fileURLToPath } from "node:url";
+import type Database from "better-sqlite3";
+import { registerTextCompression } from "./text-compression.js";
+
+const MIGRATIONS_DIR = path.join(
+	path.dirname(fileURLToPath(import.meta.url)),
+	"../migrations",
+);
+
+type Row = Record<string, SQLOutputValue>;
+
+type TestStatement = {
+	pluck: (toggle?: boolean) => TestStatement;
+	run: (...params: unknown[]) => ReturnType<StatementSync["run"]>;
+	get: (...params: unknown[]) => unknown;
+	all: (...params: unknown[]) => unknown[];
+	iterate: (...params: unknown[]) => Iterable<unknown>;
+};
+
+/**
+ * Copies a result row, turning blobs into Buffers as better-sqlite3
+ * returns them.
+ * Pure function.
+ */
+const toRow = (row: Row): Row =>
+	Object.fromEntries(
+		Object.entries(row).map(([name, value]) => [
+			name,
+			value instanceof Uint8Array ? Buffer.from(value) : value,
+		]),
+	);
+
+/**
+ * Flattens array parameters one level, as better-sqlite3 binds them.
+ * Pure function.
+ */
+const toParams = (params: unknown[]): SQLInputValue[] =>
+	params.flat() as SQLInputValue[];
+
+/**
+ * Gives a node:sqlite statement the better-sqlite3 methods the
+ * repositories use: `run`, `get`, `all`, `iterate` and `pluck`.
+ */
+const wrapStatement = (statement: StatementSync): TestStatement => {
+	let plucked = false;
+	const read = (row: unknown) => {
+		if (row === undefined) return undefined;
+		const copy = toRow(row as Row);
+		return plucked ? Object.values(copy)[0] : copy;
+	};
+
+	const wrapped: TestStatement = {
+		pluck: (toggle = true) => {
+			plucked = toggle;
+			return wrapped;
+		},
+		run: (...params: unknown[]) => statement.run(...toParams(params)),
+		get: (...params: unknown[]) => read(statement.get(...toParams(params))),
+		all: (...params: unknown[]) =>
+			statement.all(...toParams(params)).map(read),
+		*iterate(...params: unknown[]) {
+			for (const row of statement.iterate(...toParams(params))) {
+				yield read(row);
+			}
+		},
+	};
+	return wrapped;
+};
+
+/**
+ * Opens an in-memory history database with every migration applied, set
+ * up like the app's connection, for repository tests.
+ *
+ * better-sqlite3 is built for Electron and cannot load in the test
+ * runner, so queries go through Node's own SQLite behind the part of the
+ * better-sqlite3 API the repositories use. Nested transactions use
+ * savepoints, as in better-sqlite3; `serialize` is not available.
+ */
+export const openTestDatabase = (): Database.Database => {
+	const db = new DatabaseSync(":memory:");
+	let depth = 0;
+
+	const connection = {
+		exec: (sql: string) => db.exec(sql),
+		prepare: (sql: string) => wrapStatement(db.prepare(sql)),
+		pragma: (source: string, options?: { simple?: boolean }) => {
+			const rows = db.prepare(`PRAGMA ${source}`).all();
+			if (!options?.simple) return rows.map((row) => toRow(row as Row));
+			return rows.length > 0 ? Object.values(rows[0])[0] : undefined;
+		},
+		function: (
+			name: string,
+			options: { deterministic?: boolean },
+			fn: (...args: never[]) => unknown,
+		) =>
+			db.function(
+				name,
+				options,
+				fn as (...args: SQLOutputValue[]) => SQLInputValue,
+			),
+		transaction:
+			<A extends unknown[], R>(fn: (...args: A) => R) =>
+			(...args: A): R => {
+				const savepoint = `test_tx_${depth}`;
+				db.exec(depth === 0 ? "BEGIN" : `SAVEPOINT ${savepoint}`);
+				depth += 1;
+				try {
+					const result = fn(...args);
+					depth -= 1;
+					db.exec(depth === 0 ? "COMMIT" : `RELEASE ${savepoint}`);
+					return result;
+				} catch (error) {
+					depth -= 1;
+					db.exec(
+						depth === 0
+							? "ROLLBACK"
+							: `ROLLBACK TO ${savepoint}; RELEASE ${savepoint}`,
+					);
+					throw error;
+				}
+			},
+		close: () => db.close(),
+	};
+
+	connection.pragma("foreign_keys = ON");
+	for (const file of fs.readdirSync(MIGRATIONS_DIR).sort()) {
+		if (!file.endsWith(".sql")) continue;
+		db.exec(fs.readFileSync(path.join(MIGRATIONS_DIR, file), "utf-8"));
+	}
+
+	const adapted = connection as unknown as Database.Database;
+	registerTextCompression(adapted);
+	return adapted;
+};
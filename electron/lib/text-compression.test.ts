@@ -0,0 +1,55 @@
+import type Database from "better-sqlite3";
+import { describe, expect, it } from "vitest";
+import {
+	COMPRESS_MIN_CHARS,
+	compressText,
+	decompressText,
+	fullTextExpression,
+	registerTextCompression,
+} from "./text-compression.js";
+
+describe("compressText", () => {
+	it("keeps short text as it is", () => {
+		expect(compressText("hello")).toEqual({
+			content: "hello",
+			compressed: null,
+			size: null,
+		});
+	});
+
+	it("compresses long text and keeps its start searchable", () => {
+		const text = "log line ".repeat(COMPRESS_MIN_CHARS);
+		const stored = compressText(text);
+
+		expect(stored.compressed?.length).toBeLessThan(text.length / 10);
+		expect(stored.size).toBe(Buffer.byteLength(text));
+		expect(text.startsWith(stored.content)).toBe(true);
+		expect(stored.content).toHaveLength(4096);
+		expect(decompressText(stored.compressed as Buffer)).toBe(text);
+	});
+
+	it("does not split a surrogate pair in the plain prefix", () => {
+		const text = `${"a".repeat(4095)}${"😀".repeat(COMPRESS_MIN_CHARS)}`;
+		expect(compressText(text).content).toBe("a".repeat(4095));
+	});
+});
+
+describe("registerTextCompression", () => {
+	it("reads compressed text back and passes NULL through", () => {
+		const functions = new Map<string, (data: unknown) => unknown>();
+		const db = {
+			function: (name: string, _options: unknown, fn: () => unknown) =>
+				functions.set(name, fn),
+		} as unknown as Database.Database;
+		registerTextCompression(db);
+
+		const text = "x".repeat(COMPRESS_MIN_CHARS + 1);
+		const stored = compressText(text);
+		const decompress = functions.get("zstd_text");
+		expect(decompress?.(stored.compressed)).toBe(text);
+		expect(decompress?.(null)).toBeNull();
+		expect(fullTextExpression("h.")).toBe(
+			"COALESCE(zstd_text(h.content_zstd), h.content)",
+		);
+	});
+});
@@ -0,0 +1,74 @@
+import zlib from "node:zlib";
+import type Database from "better-sqlite3";
+
+/**
+ * Text longer than this many characters is stored zstd-compressed.
+ */
+export const COMPRESS_MIN_CHARS = 64 * 1024;
+
+/**
+ * Leading characters of compressed text kept plain in `content`, where
+ * substring search and the full-text index see them.
+ */
+const PLAIN_PREFIX_CHARS = 4096;
+
+/**
+ * Name of the SQL function that reads compressed text back.
+ */
+const DECOMPRESS_FUNCTION = "zstd_text";
+
+/**
+ * Item text as stored: `content` holds the whole text, or only its start
+ * when `compressed` holds all of it.
+ */
+export type StoredText = {
+	content: string;
+	compressed: Buffer | null;
+	/** UTF-8 size of the original text in bytes, when compressed */
+	size: number | null;
+};
+
+/**
+ * Splits text into the columns it is stored in, compressing text over
+ * 64K characters unless zstd cannot make it smaller.
+ */
+export const compressText = (text: string): StoredText => {
+	if (text.length <= COMPRESS_MIN_CHARS) {
+		return { content: text, compressed: null, size: null };
+	}
+
+	const data = Buffer.from(text, "utf-8");
+	const compressed = zlib.zstdCompressSync(data);
+	if (compressed.length >= data.length) {
+		return { content: text, compressed: null, size: null };
+	}
+
+	// Do not cut a surrogate pair in half
+	const end = /[\uD800-\uDBFF]/.test(text[PLAIN_PREFIX_CHARS - 1])
+		? PLAIN_PREFIX_CHARS - 1
+		: PLAIN_PREFIX_CHARS;
+	return { content: text.slice(0, end), compressed, size: data.length };
+};
+
+export const decompressText = (data: Buffer): string =>
+	zlib.zstdDecompressSync(data).toString("utf-8");
+
+/**
+ * SQL expression for an item's full text. `prefix` qualifies the columns,
+ * e.g. `h.`.
+ */
+export const fullTextExpression = (prefix = ""): string =>
+	`COALESCE(${DECOMPRESS_FUNCTION}(${prefix}content_zstd), ${prefix}content)`;
+
+/**
+ * Registers the SQL function `fullTextExpression` relies on. Needed on
+ * every connection before history is read.
+ */
+export const registerTextCompression = (db: Database.Database): void => {
+	db.function(
+		DECOMPRESS_FUNCTION,
+		{ deterministic: true },
+		(data: unknown): string | null =>
+			data instanceof Uint8Array ? decompressText(Buffer.from(data)) : null,
+	);
+};
@@ -0,0 +1,86 @@
+import { describe, expect, it } from "vitest";
+import { diffTexts } from "./text-diff.js";
+
+const lines = (count: number, prefix = "line") =>
+	Array.from({ length: count }, (_, i) => `${prefix} ${i + 1}`);
+
+describe("diffTexts", () => {
+	it("finds no hunks in equal texts, whatever their line endings", () => {
+		expect(diffTexts("a\r\nb", "a\nb")).toEqual({
+			hunks: [],
+			added: 0,
+			removed: 0,
+		});
+	});
+
+	it("pairs a replaced line and marks the changed words", () => {
+		const diff = diffTexts("host: a\nport: 80\n", "host: a\nport: 8080\n");
+
+		expect(diff).toMatchObject({ added: 1, removed: 1 });
+		expect(diff.hunks).toHaveLength(1);
+		expect(diff.hunks[0]).toMatchObject({
+			oldStart: 1,
+			oldLines: 3,
+			newStart: 1,
+			newLines: 3,
+		});
+		expect(diff.hunks[0].lines[1]).toEqual({
+			type: "delete",
+			text: "port: 80",
+			oldLine: 2,
+			newLine: null,
+			segments: [
+				{ type: "equal", text: "port: " },
+				{ type: "delete", text: "80" },
+			],
+		});
+		expect(diff.hunks[0].lines[2].segments).toEqual([
+			{ type: "equal", text: "port: " },
+			{ type: "insert", text: "8080" },
+		]);
+	});
+
+	it("keeps three lines of context and splits distant changes", () => {
+		const before = lines(20);
+		const after = [...before];
+		after[1] = "changed";
+		after.splice(15, 1);
+
+		const [oldText, newText] = [before.join("\n"), after.join("\n")];
+
+		const diff = diffTexts(oldText, newText);
+
+		expect(
+			diff.hunks.map(({ oldStart, oldLines, newStart, newLines }) => [
+				oldStart,
+				oldLines,
+				newStart,
+				newLines,
+			]),
+		).toEqual([
+			[1, 5, 1, 5],
+			[13, 7, 13, 6],
+		]);
+		expect(diffTexts(oldText, newText, 7).hunks).toHaveLength(1);
+	});
+
+	it("numbers a side without lines from the line before", () => {
+		const diff = diffTexts("a\nb", "a\nb\nc", 0);
+
+		expect(diff.hunks).toEqual([
+			{
+				oldStart: 2,
+				oldLines: 0,
+				newStart: 3,
+				newLines: 1,
+				lines: [{ type: "insert", text: "c", oldLine: null, newLine: 3 }],
+			},
+		]);
+	});
+
+	it("rejects texts with too many changed lines", () => {
+		expect(() =>
+			diffTexts(lines(1500, "a").join("\n"), lines(1500, "b").join("\n")),
+		).toThrow("Texts are too different to compare");
+	});
+});
@@ -0,0 +1,295 @@
+/**
+ * Unchanged lines shown around each change.
+ */
+export const DIFF_CONTEXT_LINES = 3;
+
+/**
+ * Most line insertions and deletions a diff is computed for; texts that
+ * differ more are rejected rather than compared slowly. Replaced lines
+ * that differ in more words are shown without word changes.
+ */
+export const MAX_DIFF_EDITS = 2000;
+
+export type DiffOperation = "equal" | "insert" | "delete";
+
+/**
+ * A run of a line's words, spaces and punctuation that was kept, added
+ * or removed.
+ */
+export type DiffSegment = { type: DiffOperation; text: string };
+
+/**
+ * A line of a hunk with its 1-based number in each text it belongs to.
+ * A changed line paired with its counterpart in the other text has
+ * `segments` marking which words changed.
+ */
+export type DiffLine = {
+	type: DiffOperation;
+	text: string;
+	oldLine: number | null;
+	newLine: number | null;
+	segments?: DiffSegment[];
+};
+
+/**
+ * Changed lines and their context, numbered like a unified diff hunk
+ * (`@@ -oldStart,oldLines +newStart,newLines @@`).
+ */
+export type DiffHunk = {
+	oldStart: number;
+	oldLines: number;
+	newStart: number;
+	newLines: number;
+	lines: DiffLine[];
+};
+
+export type TextDiff = {
+	hunks: DiffHunk[];
+	/** Lines only in the second text */
+	added: number;
+	/** Lines only in the first text */
+	removed: number;
+};
+
+/** Words, runs of whitespace, and single punctuation characters */
+const WORD_PATTERN = /\s+|[\p{L}\p{N}_]+|[^\s\p{L}\p{N}_]/gu;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Whether the path to diagonal `k` after `d` edits comes down from
+ * diagonal `k + 1` (an insertion) rather than across from `k - 1`.
+ */
+const comesDown = (prev: Int32Array, d: number, k: number): boolean =>
+	k === -d || (k !== d && prev[k - 1 + d - 1] < prev[k + 1 + d - 1]);
+
+/**
+ * The forward pass of Myers' algorithm: `trace[d][k + d]` is the
+ * furthest item of `a` reached on diagonal `k` with `d` edits.
+ *
+ * @returns the trace up to the end of both sequences, or null if more
+ *   than `maxEdits` edits are needed
+ */
+const traceEdits = (
+	a: readonly string[],
+	b: readonly string[],
+	maxEdits: number,
+): Int32Array[] | null => {
+	const n = a.length;
+	const m = b.length;
+	const trace: Int32Array[] = [];
+	for (let d = 0; d <= Math.min(n + m, maxEdits); d++) {
+		const prev = trace[d - 1];
+		const current = new Int32Array(2 * d + 1);
+		trace.push(current);
+		for (let k = -d; k <= d; k += 2) {
+			let x = 0;
+			if (d > 0) {
+				x = comesDown(prev, d, k)
+					? prev[k + 1 + d - 1]
+					: prev[k - 1 + d - 1] + 1;
+			}
+			let y = x - k;
+			while (x < n && y < m && a[x] === b[y]) {
+				x++;
+				y++;
+			}
+			current[k + d] = x;
+			if (x >= n && y >= m) return trace;
+		}
+	}
+	return null;
+};
+
+/**
+ * Shortest edit script between two sequences (Myers' algorithm), as
+ * operations on their items in order.
+ * Pure function.
+ *
+ * @returns the edits, or null if more than `maxEdits` are needed
+ */
+const diffSequences = (
+	a: readonly string[],
+	b: readonly string[],
+	maxEdits: number,
+): Array<{ type: DiffOperation; value: string }> | null => {
+	const trace = traceEdits(a, b, maxEdits);
+	if (!trace) return null;
+
+	const edits: Array<{ type: DiffOperation; value: string }> = [];
+	let x = a.length;
+	let y = b.length;
+	for (let d = trace.length - 1; d > 0; d--) {
+		const prev = trace[d - 1];
+		const k = x - y;
+		const prevK = comesDown(prev, d, k) ? k + 1 : k - 1;
+		const prevX = prev[prevK + d - 1];
+		const prevY = prevX - prevK;
+		while (x > prevX && y > prevY) {
+			edits.push({ type: "equal", value: a[--x] });
+			y--;
+		}
+		if (prevK === k + 1) {
+			edits.push({ type: "insert", value: b[--y] });
+		} else {
+			edits.push({ type: "delete", value: a[--x] });
+		}
+	}
+	while (x > 0) {
+		edits.push({ type: "equal", value: a[--x] });
+	}
+	return edits.reverse();
+};
+
+/**
+ * Marks the words that differ between a removed line and the line that
+ * replaced it, joining neighboring runs of the same kind.
+ * Pure function.
+ *
+ * @returns the segments, or null if the lines differ in more than
+ *   {@link MAX_DIFF_EDITS} words
+ */
+const diffWords = (
+	oldText: string,
+	newText: string,
+): { old: DiffSegment[]; new: DiffSegment[] } | null => {
+	const edits = diffSequences(
+		oldText.match(WORD_PATTERN) ?? [],
+		newText.match(WORD_PATTERN) ?? [],
+		MAX_DIFF_EDITS,
+	);
+	if (!edits) return null;
+	const append = (
+		segments: DiffSegment[],
+		type: DiffOperation,
+		text: string,
+	) => {
+		const last = segments[segments.length - 1];
+		if (last?.type === type) {
+			last.text += text;
+		} else {
+			segments.push({ type, text });
+		}
+	};
+	const result = { old: [] as DiffSegment[], new: [] as DiffSegment[] };
+	for (const edit of edits) {
+		if (edit.type !== "insert") append(result.old, edit.type, edit.value);
+		if (edit.type !== "delete") append(result.new, edit.type, edit.value);
+	}
+	return result;
+};
+
+/**
+ * Pairs each run of removed lines with the added lines that follow it,
+ * in order, and gives both sides word segments.
+ */
+const markChangedWords = (lines: DiffLine[]): void => {
+	let i = 0;
+	while (i < lines.length) {
+		if (lines[i].type !== "delete") {
+			i++;
+			continue;
+		}
+		const deleteStart = i;
+		while (i < lines.length && lines[i].type === "delete") i++;
+		const insertStart = i;
+		while (i < lines.length && lines[i].type === "insert") i++;
+		const pairs = Math.min(insertStart - deleteStart, i - insertStart);
+		for (let p = 0; p < pairs; p++) {
+			const removed = lines[deleteStart + p];
+			const added = lines[insertStart + p];
+			const segments = diffWords(removed.text, added.text);
+			if (!segments) continue;
+			removed.segments = segments.old;
+			added.segments = segments.new;
+		}
+	}
+};
+
+/**
+ * Groups changed lines into hunks with up to `context` unchanged lines
+ * on each side; changes closer than twice that share a hunk.
+ */
+const toHunks = (lines: DiffLine[], context: number): DiffHunk[] => {
+	const hunks: DiffHunk[] = [];
+	let index = 0;
+	while (index < lines.length) {
+		if (lines[index].type === "equal") {
+			index++;
+			continue;
+		}
+		const start = Math.max(0, index - context);
+		let end = index;
+		let lastChange = index;
+		while (end < lines.length && end - lastChange <= 2 * context + 1) {
+			if (lines[end].type !== "equal") lastChange = end;
+			end++;
+		}
+		end = Math.min(lines.length, lastChange + context + 1);
+
+		const hunkLines = lines.slice(start, end);
+		const oldLines = hunkLines.filter((line) => line.type !== "insert");
+		const newLines = hunkLines.filter((line) => line.type !== "delete");
+		// Like a unified diff, a side without lines starts at the line
+		// before the hunk
+		const before = lines.slice(0, start);
+		hunks.push({
+			oldStart:
+				before.filter((line) => line.type !== "insert").length +
+				(oldLines.length > 0 ? 1 : 0),
+			oldLines: oldLines.length,
+			newStart:
+				before.filter((line) => line.type !== "delete").length +
+				(newLines.length > 0 ? 1 : 0),
+			newLines: newLines.length,
+			lines: hunkLines,
+		});
+		index = end;
+	}
+	return hunks;
+};
+
+/**
+ * Compares two texts line by line, marking the changed words of each
+ * replaced line. Line endings are compared as line breaks, so CRLF and
+ * LF copies of the same text are equal.
+ * Pure function.
+ *
+ * @param context - Unchanged lines kept around each change
+ * @throws if the texts need more than {@link MAX_DIFF_EDITS} line edits
+ */
+export const diffTexts = (
+	oldText: string,
+	newText: string,
+	context = DIFF_CONTEXT_LINES,
+): TextDiff => {
+	const edits = diffSequences(
+		oldText.split(/\r\n|\r|\n/),
+		newText.split(/\r\n|\r|\n/),
+		MAX_DIFF_EDITS,
+	);
+	if (!edits) {
+		throw new Error(
+			`Texts are too different to compare: more than ${MAX_DIFF_EDITS} lines changed`,
+		);
+	}
+
+	let oldLine = 0;
+	let newLine = 0;
+	const lines = edits.map(
+		({ type, value }): DiffLine => ({
+			type,
+			text: value,
+			oldLine: type === "insert" ? null : ++oldLine,
+			newLine: type === "delete" ? null : ++newLine,
+		}),
+	);
+	markChangedWords(lines);
+	return {
+		hunks: toHunks(lines, context),
+		added: lines.filter((line) => line.type === "insert").length,
+		removed: lines.filter((line) => line.type === "delete").length,
+	};
+};
@@ -0,0 +1,173 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import type { KeyHook, TypedKey } from "./key-hook.js";
+import {
+	appendTypedKey,
+	createTextExpander,
+	findAbbreviation,
+	getTextExpanderPath,
+	parseTextExpanderSettings,
+} from "./text-expander.js";
+
+const abbreviations = [
+	{ id: 1, abbreviation: ";addr" },
+	{ id: 2, abbreviation: ";addr2" },
+];
+
+const type = (text: string, typed = ""): string =>
+	[...text].reduce(
+		(current, char) => appendTypedKey(current, { type: "char", text: char }),
+		typed,
+	);
+
+describe("parseTextExpanderSettings", () => {
+	it("applies an update and drops duplicate apps", () => {
+		expect(
+			parseTextExpanderSettings({
+				excludedApps: [" KeePassXC.exe ", "keepassxc"],
+			}),
+		).toEqual({ enabled: false, excludedApps: ["KeePassXC.exe"] });
+	});
+
+	it("rejects invalid settings", () => {
+		expect(() => parseTextExpanderSettings({ enabled: "yes" })).toThrow(
+			"Invalid text expander settings: enabled must be a boolean",
+		);
+		expect(() => parseTextExpanderSettings({ excludedApps: [""] })).toThrow(
+			"app names must be 1-256 characters",
+		);
+	});
+});
+
+describe("appendTypedKey", () => {
+	it("keeps the last 32 characters typed", () => {
+		expect(type(";ad")).toBe(";ad");
+		expect(appendTypedKey(";ad", { type: "backspace" })).toBe(";a");
+		expect(appendTypedKey(";ad", { type: "reset" })).toBe("");
+		expect(type("y", "x".repeat(32))).toBe(`${"x".repeat(31)}y`);
+	});
+});
+
+describe("findAbbreviation", () => {
+	it("prefers the longest abbreviation typed", () => {
+		expect(findAbbreviation("hi ;addr", abbreviations)?.id).toBe(1);
+		expect(findAbbreviation("hi ;addr2", abbreviations)?.id).toBe(2);
+		expect(findAbbreviation(";add", abbreviations)).toBeNull();
+	});
+});
+
+describe("createTextExpander", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-expander-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	/**
+	 * A hook whose keys are sent by the test.
+	 */
+	const createFakeHook = () => {
+		let listener: ((key: TypedKey) => void) | null = null;
+		const hook: KeyHook = {
+			start: async (onKey) => {
+				listener = onKey;
+			},
+			stop: () => {
+				listener = null;
+			},
+		};
+		const typeText = (text: string) => {
+			for (const char of text) listener?.({ type: "char", text: char });
+		};
+		return { hook, typeText, isListening: () => listener !== null };
+	};
+
+	afterEach(() => {
+		vi.restoreAllMocks();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("expands abbreviations outside excluded apps", async () => {
+		const { hook, typeText, isListening } = createFakeHook();
+		let app = "Slack";
+		const expand = vi.fn().mockResolvedValue(undefined);
+		const expander = createTextExpander({
+			userDataPath: createTempUserDataPath(),
+			hook,
+			listAbbreviations: () => abbreviations,
+			getForegroundApp: async () => app,
+			expand,
+		});
+
+		await expander.start();
+		expect(isListening()).toBe(false);
+		await expander.updateSettings({
+			enabled: true,
+			excludedApps: ["Terminal"],
+		});
+
+		typeText("x;addr");
+		await vi.waitFor(() => expect(expand).toHaveBeenCalledWith(1, 5));
+		app = "Terminal";
+		typeText(";addr");
+		await new Promise((resolve) => setTimeout(resolve, 0));
+		expect(expand).toHaveBeenCalledTimes(1);
+
+		expect((await expander.updateSettings({ enabled: false })).running).toBe(
+			false,
+		);
+		expect(isListening()).toBe(false);
+	});
+
+	it("reports failures in the status", async () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const { hook, typeText } = createFakeHook();
+		const expander = createTextExpander({
+			userDataPath: createTempUserDataPath(),
+			hook,
+			listAbbreviations: () => abbreviations,
+			getForegroundApp: async () => null,
+			expand: vi.fn().mockRejectedValue(new Error("History is locked")),
+		});
+		await expander.updateSettings({ enabled: true });
+
+		typeText(";addr");
+
+		await vi.waitFor(() =>
+			expect(expander.getStatus().lastError).toBe("History is locked"),
+		);
+	});
+
+	it("stays off when the listener cannot start", async () => {
+		vi.spyOn(console, "error").mockImplementation(() => {});
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(
+			getTextExpanderPath(userDataPath),
+			JSON.stringify({ enabled: true, excludedApps: [] }),
+		);
+		const expander = createTextExpander({
+			userDataPath,
+			hook: {
+				start: vi.fn().mockRejectedValue(new Error("xinput not found")),
+				stop: vi.fn(),
+			},
+			listAbbreviations: () => [],
+			getForegroundApp: async () => null,
+			expand: vi.fn(),
+		});
+
+		await expander.start();
+
+		expect(expander.getStatus()).toMatchObject({
+			enabled: true,
+			running: false,
+			lastError: "xinput not found",
+		});
+	});
+});
@@ -0,0 +1,309 @@
+import fs from "node:fs";
+import path from "node:path";
+import { isExcludedApp, normalizeAppName } from "./app-exclusions.js";
+import type { KeyHook, TypedKey } from "./key-hook.js";
+
+/**
+ * Persisted text expander settings.
+ */
+export type TextExpanderSettings = {
+	/** Off by default: listening to every keystroke is opt-in */
+	enabled: boolean;
+	/** Process or app names where abbreviations are left as typed */
+	excludedApps: string[];
+};
+
+/**
+ * Settings plus what the listener is doing, as shown in settings.
+ */
+export type TextExpanderStatus = TextExpanderSettings & {
+	running: boolean;
+	/** Why the listener stopped or the last expansion failed */
+	lastError: string | null;
+};
+
+/**
+ * A snippet the expander watches for.
+ */
+export type Abbreviation = {
+	id: number;
+	abbreviation: string;
+};
+
+const DEFAULT_TEXT_EXPANDER_SETTINGS: TextExpanderSettings = {
+	enabled: false,
+	excludedApps: [],
+};
+
+const TEXT_EXPANDER_FILENAME = "text-expander.json";
+
+const MAX_EXCLUDED_APPS = 100;
+
+const MAX_APP_NAME_LENGTH = 256;
+
+/**
+ * Characters of recent typing kept for matching; abbreviations are at
+ * most 32 characters.
+ */
+const MAX_TYPED_LENGTH = 32;
+
+const invalid = (message: string) =>
+	new Error(`Invalid text expander settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a text expander settings update.
+ * Pure function. App names are trimmed and duplicates (after normalizing)
+ * are dropped. Missing keys keep their current value.
+ *
+ * @throws if `enabled` is not a boolean, or `excludedApps` is not a list
+ *   of up to 100 names of 1-256 characters
+ */
+export const parseTextExpanderSettings = (
+	input: unknown,
+	current: TextExpanderSettings = DEFAULT_TEXT_EXPANDER_SETTINGS,
+): TextExpanderSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { enabled, excludedApps } = input as Partial<
+		Record<keyof TextExpanderSettings, unknown>
+	>;
+	const next: TextExpanderSettings = {
+		enabled: current.enabled,
+		excludedApps: [...current.excludedApps],
+	};
+
+	if (enabled !== undefined) {
+		if (typeof enabled !== "boolean") {
+			throw invalid("enabled must be a boolean");
+		}
+		next.enabled = enabled;
+	}
+
+	if (excludedApps !== undefined) {
+		if (!Array.isArray(excludedApps)) {
+			throw invalid("excludedApps must be a list");
+		}
+		const seen = new Set<string>();
+		const names: string[] = [];
+		for (const entry of excludedApps) {
+			const name = typeof entry === "string" ? entry.trim() : "";
+			if (name === "" || name.length > MAX_APP_NAME_LENGTH) {
+				throw invalid(`app names must be 1-${MAX_APP_NAME_LENGTH} characters`);
+			}
+			const key = normalizeAppName(name);
+			if (seen.has(key)) continue;
+			seen.add(key);
+			names.push(name);
+		}
+		if (names.length > MAX_EXCLUDED_APPS) {
+			throw invalid(`at most ${MAX_EXCLUDED_APPS} apps can be excluded`);
+		}
+		next.excludedApps = names;
+	}
+
+	return next;
+};
+
+/**
+ * Applies a keystroke to the recently typed text.
+ * Pure function. Backspace removes the last character; keys that move
+ * the caret or run shortcuts forget everything typed so far.
+ */
+export const appendTypedKey = (typed: string, key: TypedKey): string => {
+	switch (key.type) {
+		case "char":
+			return [...(typed + key.text)].slice(-MAX_TYPED_LENGTH).join("");
+		case "backspace":
+			return [...typed].slice(0, -1).join("");
+		case "copy":
+		case "reset":
+			return "";
+	}
+};
+
+/**
+ * Finds the abbreviation the typed text ends with; if several match,
+ * the longest wins, so `;addr2` is not cut short by `;addr`.
+ * Pure function.
+ */
+export const findAbbreviation = (
+	typed: string,
+	abbreviations: readonly Abbreviation[],
+): Abbreviation | null => {
+	let found: Abbreviation | null = null;
+	for (const entry of abbreviations) {
+		if (
+			typed.endsWith(entry.abbreviation) &&
+			entry.abbreviation.length > (found?.abbreviation.length ?? 0)
+		) {
+			found = entry;
+		}
+	}
+	return found;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getTextExpanderPath = (userDataPath: string) =>
+	path.join(userDataPath, TEXT_EXPANDER_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readTextExpanderFromFile = (filePath: string): TextExpanderSettings => {
+	if (!fs.existsSync(filePath)) {
+		return parseTextExpanderSettings({});
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseTextExpanderSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read text expander settings, using defaults:",
+			error,
+		);
+		return parseTextExpanderSettings({});
+	}
+};
+
+const writeTextExpanderToFile = (
+	filePath: string,
+	settings: TextExpanderSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Text Expander Module
+// ============================================================================
+
+export type TextExpanderDeps = {
+	userDataPath: string;
+	hook: KeyHook;
+	/** Snippets that have an abbreviation */
+	listAbbreviations: () => Abbreviation[];
+	/** Name of the app typing goes to, or null if unknown */
+	getForegroundApp: () => Promise<string | null>;
+	/**
+	 * Erases the `typedLength` characters of the abbreviation just typed
+	 * and pastes the snippet in their place
+	 */
+	expand: (snippetId: number, typedLength: number) => Promise<void>;
+};
+
+/**
+ * Creates the text expander: while enabled, a keyboard listener tracks
+ * what is typed in any app and replaces an abbreviation such as `;addr`
+ * with its snippet as soon as the last character is typed.
+ * Keystrokes are only kept in memory, and only the last 32 characters.
+ */
+export const createTextExpander = (deps: TextExpanderDeps) => {
+	const filePath = getTextExpanderPath(deps.userDataPath);
+	let settings = readTextExpanderFromFile(filePath);
+	let abbreviations: Abbreviation[] = [];
+	let typed = "";
+	let running = false;
+	let expanding = false;
+	let lastError: string | null = null;
+
+	const getStatus = (): TextExpanderStatus => ({
+		enabled: settings.enabled,
+		excludedApps: [...settings.excludedApps],
+		running,
+		lastError,
+	});
+
+	/**
+	 * Reloads the abbreviations; call after snippets change.
+	 */
+	const reloadAbbreviations = () => {
+		try {
+			abbreviations = deps.listAbbreviations();
+		} catch (error) {
+			console.error("Failed to load snippet abbreviations:", error);
+			abbreviations = [];
+		}
+	};
+
+	const expand = async (match: Abbreviation): Promise<void> => {
+		expanding = true;
+		try {
+			const app = await deps.getForegroundApp();
+			if (isExcludedApp(app, settings.excludedApps)) return;
+			await deps.expand(match.id, [...match.abbreviation].length);
+		} catch (error) {
+			lastError = error instanceof Error ? error.message : String(error);
+			console.error("Failed to expand abbreviation:", error);
+		} finally {
+			// Keys seen while the snippet was pasted are our own
+			typed = "";
+			expanding = false;
+		}
+	};
+
+	const onKey = (key: TypedKey) => {
+		if (expanding) return;
+		typed = appendTypedKey(typed, key);
+		if (key.type !== "char") return;
+		const match = findAbbreviation(typed, abbreviations);
+		if (match) void expand(match);
+	};
+
+	const onExit = (error: Error) => {
+		running = false;
+		lastError = error.message;
+		console.error("Text expander stopped:", error);
+	};
+
+	/**
+	 * Starts listening if enabled. Failures are reported in the status.
+	 */
+	const start = async (): Promise<void> => {
+		if (!settings.enabled || running) return;
+		reloadAbbreviations();
+		typed = "";
+		try {
+			running = true;
+			await deps.hook.start(onKey, onExit);
+			lastError = null;
+		} catch (error) {
+			running = false;
+			lastError = error instanceof Error ? error.message : String(error);
+			console.error("Failed to start text expander:", error);
+		}
+	};
+
+	const stop = () => {
+		deps.hook.stop();
+		running = false;
+		typed = "";
+	};
+
+	const updateSettings = async (
+		input: unknown,
+	): Promise<TextExpanderStatus> => {
+		const next = parseTextExpanderSettings(input, settings);
+		writeTextExpanderToFile(filePath, next);
+		settings = next;
+		if (settings.enabled) {
+			await start();
+		} else {
+			stop();
+		}
+		return getStatus();
+	};
+
+	return { getStatus, updateSettings, reloadAbbreviations, start, stop };
+};
+
+export type TextExpander = ReturnType<typeof createTextExpander>;
@@ -0,0 +1,28 @@
+import { describe, expect, it } from "vitest";
+import { computeTextStats } from "./text-stats.js";
+
+describe("computeTextStats", () => {
+	it("counts bytes, characters, words and lines", () => {
+		expect(computeTextStats("héllo wörld\n  second line 👋")).toEqual({
+			bytes: 32,
+			chars: 27,
+			words: 5,
+			lines: 2,
+		});
+	});
+
+	it("does not count a line after a final line break", () => {
+		expect(computeTextStats("one\r\ntwo\r\n").lines).toBe(2);
+		expect(computeTextStats("one\rtwo").lines).toBe(2);
+		expect(computeTextStats("\n\n").lines).toBe(2);
+	});
+
+	it("gives zeros for empty text", () => {
+		expect(computeTextStats("")).toEqual({
+			bytes: 0,
+			chars: 0,
+			words: 0,
+			lines: 0,
+		});
+	});
+});
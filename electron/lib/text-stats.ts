@@ -0,0 +1,42 @@
+/**
+ * Size and counts of a text, stored with text items so lists can show
+ * them without reading the text.
+ */
+export type TextStats = {
+	/** UTF-8 size in bytes */
+	bytes: number;
+	/** Unicode code points, so an emoji counts once */
+	chars: number;
+	/** Runs of non-whitespace, as `wc -w` counts them */
+	words: number;
+	/** Lines, not counting an empty one after a final line break */
+	lines: number;
+};
+
+/**
+ * Counts the bytes, characters, words and lines of a text.
+ * Pure function.
+ */
+export const computeTextStats = (text: string): TextStats => {
+	let surrogatePairs = 0;
+	let lineBreaks = 0;
+	for (let i = 0; i < text.length; i++) {
+		const code = text.charCodeAt(i);
+		if (code >= 0xdc00 && code <= 0xdfff) {
+			const previous = text.charCodeAt(i - 1);
+			if (previous >= 0xd800 && previous <= 0xdbff) surrogatePairs++;
+		} else if (code === 0x0a) {
+			lineBreaks++;
+		} else if (code === 0x0d && text.charCodeAt(i + 1) !== 0x0a) {
+			lineBreaks++;
+		}
+	}
+	const endsWithBreak = /[\r\n]$/.test(text);
+
+	return {
+		bytes: Buffer.byteLength(text, "utf8"),
+		chars: text.length - surrogatePairs,
+		words: text.match(/\S+/g)?.length ?? 0,
+		lines: text === "" ? 0 : lineBreaks + (endsWithBreak ? 0 : 1),
+	};
+};
@@ -0,0 +1,110 @@
+import { describe, expect, it } from "vitest";
+import {
+	applyTextTransform,
+	parseTextTransformRequest,
+} from "./text-transforms.js";
+
+describe("parseTextTransformRequest", () => {
+	it("defaults the target to a new item", () => {
+		expect(parseTextTransformRequest({ transform: "trim" })).toEqual({
+			transform: "trim",
+			target: "item",
+		});
+		expect(
+			parseTextTransformRequest({ transform: "trim", target: "clipboard" }),
+		).toEqual({ transform: "trim", target: "clipboard" });
+	});
+
+	it("rejects unknown transforms and targets", () => {
+		expect(() => parseTextTransformRequest({ transform: "reverse" })).toThrow(
+			"Invalid text transformation: transform must be one of uppercase",
+		);
+		expect(() =>
+			parseTextTransformRequest({ transform: "trim", target: "file" }),
+		).toThrow("target must be one of item, clipboard");
+		expect(() => parseTextTransformRequest("trim")).toThrow(
+			"expected an object",
+		);
+	});
+});
+
+describe("applyTextTransform", () => {
+	it("changes case", () => {
+		expect(applyTextTransform("Hello World", "uppercase")).toBe("HELLO WORLD");
+		expect(applyTextTransform("Hello World", "lowercase")).toBe("hello world");
+		expect(
+			applyTextTransform("the QUICK brown fox doesn't stop-over", "titleCase"),
+		).toBe("The Quick Brown Fox Doesn't Stop-Over");
+		expect(applyTextTransform("élan über", "titleCase")).toBe("Élan Über");
+	});
+
+	it("trims and collapses whitespace", () => {
+		expect(applyTextTransform("  padded \n", "trim")).toBe("padded");
+		expect(
+			applyTextTransform("a  \t b   \n\n\n\nc  \n", "collapseWhitespace"),
+		).toBe("a b\n\nc\n");
+	});
+
+	it("joins lines with single spaces", () => {
+		expect(
+			applyTextTransform("wrapped \n  paragraph\r\ntext\n", "removeLineBreaks"),
+		).toBe("wrapped paragraph text");
+	});
+
+	it("sorts lines in natural order, keeping the final line break", () => {
+		expect(
+			applyTextTransform("item10\nItem2\nbanana\napple\n", "sortLines"),
+		).toBe("apple\nbanana\nItem2\nitem10\n");
+	});
+
+	it("removes repeated lines, keeping Windows line endings", () => {
+		expect(applyTextTransform("b\r\na\r\nb\r\n", "dedupeLines")).toBe(
+			"b\r\na\r\n",
+		);
+	});
+
+	it("encodes and decodes the UTF-8 bytes of Base64", () => {
+		expect(applyTextTransform("héllo ✓", "base64Encode")).toBe(
+			"aMOpbGxvIOKckw==",
+		);
+		expect(applyTextTransform("aMOpbGxv\nIOKckw==\n", "base64Decode")).toBe(
+			"héllo ✓",
+		);
+		expect(applyTextTransform("PDw_Pz4-", "base64Decode")).toBe("<<??>>");
+	});
+
+	it("rejects invalid and binary Base64", () => {
+		expect(() => applyTextTransform("not base64!", "base64Decode")).toThrow(
+			"Not valid Base64",
+		);
+		expect(() => applyTextTransform("abcde", "base64Decode")).toThrow(
+			"Not valid Base64",
+		);
+		expect(() => applyTextTransform("/9j/4A==", "base64Decode")).toThrow(
+			"Base64 data is binary, not UTF-8 text",
+		);
+	});
+
+	it("percent-encodes and decodes Unicode text", () => {
+		expect(applyTextTransform("a b&c=ü/✓", "urlEncode")).toBe(
+			"a%20b%26c%3D%C3%BC%2F%E2%9C%93",
+		);
+		expect(applyTextTransform("a%20b+c%C3%BC", "urlDecode")).toBe("a b+cü");
+		expect(() => applyTextTransform("100%", "urlDecode")).toThrow(
+			"Not valid percent-encoding",
+		);
+	});
+
+	it("splits a query string into key=value lines", () => {
+		expect(
+			applyTextTransform(
+				"https://example.com/search?q=caf%C3%A9+au+lait&tag=a&tag=b&empty=#top",
+				"splitQueryString",
+			),
+		).toBe("q=café au lait\ntag=a\ntag=b\nempty=");
+		expect(applyTextTransform("?page=2", "splitQueryString")).toBe("page=2");
+		expect(() =>
+			applyTextTransform("https://example.com/", "splitQueryString"),
+		).toThrow("No query string found");
+	});
+});
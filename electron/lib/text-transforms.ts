@@ -0,0 +1,205 @@
+const TEXT_TRANSFORMS = [
+	"uppercase",
+	"lowercase",
+	"titleCase",
+	"trim",
+	"collapseWhitespace",
+	"removeLineBreaks",
+	"sortLines",
+	"dedupeLines",
+	"base64Encode",
+	"base64Decode",
+	"urlEncode",
+	"urlDecode",
+	"splitQueryString",
+] as const;
+
+export type TextTransform = (typeof TEXT_TRANSFORMS)[number];
+
+/**
+ * Where a transformed text goes: a new history item, or the system
+ * clipboard (which records it like any other copy).
+ */
+const TRANSFORM_TARGETS = ["item", "clipboard"] as const;
+
+export type TransformTarget = (typeof TRANSFORM_TARGETS)[number];
+
+/**
+ * Whether a value names a transform target.
+ * Pure function.
+ */
+export const isTransformTarget = (value: unknown): value is TransformTarget =>
+	TRANSFORM_TARGETS.includes(value as TransformTarget);
+
+/**
+ * A validated request to transform a text item.
+ */
+export type TextTransformRequest = {
+	transform: TextTransform;
+	target: TransformTarget;
+};
+
+/**
+ * A letter starting a word: one not preceded by a letter, digit or
+ * apostrophe, so `don't` stays one word.
+ */
+const WORD_START_PATTERN = /(^|[^\p{L}\p{N}'’])(\p{L})/gu;
+
+/**
+ * Base64 in the standard or URL-safe alphabet, with optional padding.
+ * Whitespace is removed before matching, so wrapped output decodes.
+ */
+const BASE64_PATTERN = /^[A-Za-z0-9+/_-]*={0,2}$/;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid text transformation: ${message}`);
+
+/**
+ * Validates a transformation request from the renderer.
+ * Pure function. `target` defaults to a new item.
+ *
+ * @throws if the transform or target is unknown
+ */
+export const parseTextTransformRequest = (
+	value: unknown,
+): TextTransformRequest => {
+	if (typeof value !== "object" || value === null) {
+		throw invalid("expected an object");
+	}
+	const { transform, target = "item" } = value as Record<string, unknown>;
+	if (!TEXT_TRANSFORMS.includes(transform as TextTransform)) {
+		throw invalid(`transform must be one of ${TEXT_TRANSFORMS.join(", ")}`);
+	}
+	if (!isTransformTarget(target)) {
+		throw invalid(`target must be one of ${TRANSFORM_TARGETS.join(", ")}`);
+	}
+	return { transform: transform as TextTransform, target };
+};
+
+/**
+ * Applies `fn` to the lines of `text`, keeping its line ending style and
+ * any final line break.
+ */
+const mapLines = (text: string, fn: (lines: string[]) => string[]): string => {
+	const newline = text.includes("\r\n") ? "\r\n" : "\n";
+	const trailing = text.endsWith("\n") ? newline : "";
+	const body = trailing ? text.replace(/\r?\n$/, "") : text;
+	return fn(body.split(/\r?\n/)).join(newline) + trailing;
+};
+
+const toTitleCase = (text: string): string =>
+	text
+		.toLowerCase()
+		.replace(
+			WORD_START_PATTERN,
+			(_match, before: string, letter: string) =>
+				before + letter.toUpperCase(),
+		);
+
+/**
+ * Decodes Base64 to the UTF-8 text it encodes.
+ *
+ * @throws if the input is not Base64, or decodes to bytes that are not
+ *   UTF-8 text
+ */
+const decodeBase64 = (text: string): string => {
+	const data = text.replace(/\s+/g, "");
+	if (data === "" || !BASE64_PATTERN.test(data) || data.length % 4 === 1) {
+		throw new Error("Not valid Base64");
+	}
+	try {
+		return new TextDecoder("utf-8", { fatal: true }).decode(
+			Buffer.from(data, "base64"),
+		);
+	} catch {
+		throw new Error("Base64 data is binary, not UTF-8 text");
+	}
+};
+
+const decodePercent = (text: string): string => {
+	try {
+		return decodeURIComponent(text);
+	} catch {
+		throw new Error("Not valid percent-encoding");
+	}
+};
+
+/**
+ * Lists the parameters of a URL or bare query string (`a=1&b=2`) as
+ * decoded `key=value` lines, in order; any `#fragment` is ignored.
+ *
+ * @throws if the text has no parameters
+ */
+const splitQueryString = (text: string): string => {
+	const trimmed = text.trim().replace(/#.*$/s, "");
+	const questionMark = trimmed.indexOf("?");
+	const query = questionMark === -1 ? trimmed : trimmed.slice(questionMark + 1);
+	if (!query.includes("=")) {
+		throw new Error("No query string found");
+	}
+	return [...new URLSearchParams(query)]
+		.map(([key, value]) => `${key}=${value}`)
+		.join("\n");
+};
+
+/**
+ * Runs one transformation over a text.
+ * Pure function.
+ * - collapseWhitespace: runs of spaces and tabs become one space, trailing
+ *   spaces go and runs of blank lines become one
+ * - removeLineBreaks: lines are joined with single spaces
+ * - sortLines: natural order ignoring case, so `item2` precedes `item10`
+ * - dedupeLines: keeps the first of identical lines
+ * - base64Encode / base64Decode: the UTF-8 bytes of the text; decoding
+ *   accepts URL-safe and line-wrapped input
+ * - urlEncode / urlDecode: percent-encoding of a URL component; `+` is
+ *   left as is when decoding
+ * - splitQueryString: one decoded `key=value` line per parameter, where
+ *   `+` is a space
+ *
+ * @throws if base64Decode, urlDecode or splitQueryString get invalid input
+ */
+export const applyTextTransform = (
+	text: string,
+	transform: TextTransform,
+): string => {
+	switch (transform) {
+		case "uppercase":
+			return text.toUpperCase();
+		case "lowercase":
+			return text.toLowerCase();
+		case "titleCase":
+			return toTitleCase(text);
+		case "trim":
+			return text.trim();
+		case "collapseWhitespace":
+			return text
+				.replace(/[^\S\r\n]+/g, " ")
+				.replace(/ +(?=\r?\n|$)/g, "")
+				.replace(/(\r?\n){3,}/g, "$1$1");
+		case "removeLineBreaks":
+			return text.replace(/[^\S\r\n]*(?:\r?\n[^\S\r\n]*)+/g, " ").trim();
+		case "sortLines":
+			return mapLines(text, (lines) =>
+				[...lines].sort((a, b) =>
+					a.localeCompare(b, undefined, { numeric: true, sensitivity: "base" }),
+				),
+			);
+		case "dedupeLines":
+			return mapLines(text, (lines) => [...new Set(lines)]);
+		case "base64Encode":
+			return Buffer.from(text, "utf8").toString("base64");
+		case "base64Decode":
+			return decodeBase64(text);
+		case "urlEncode":
+			return encodeURIComponent(text);
+		case "urlDecode":
+			return decodePercent(text);
+		case "splitQueryString":
+			return splitQueryString(text);
+	}
+};
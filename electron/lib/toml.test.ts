@@ -0,0 +1,71 @@
+import { describe, expect, it } from "vitest";
+import { formatTomlKey, formatTomlValue, parseToml } from "./toml.js";
+
+describe("parseToml", () => {
+	it("reads top-level keys and tables", () => {
+		const document = parseToml(
+			[
+				"# Settings",
+				"version = 1",
+				"",
+				"[shortcuts] # bindings",
+				'togglePicker = "CommandOrControl+Shift+V"',
+				"'odd key' = 'C:\\path'",
+				"",
+				"[retention]",
+				"maxItems = 1_000",
+				"ratio = -1.5e3",
+				"enabled = true",
+			].join("\n"),
+		);
+
+		expect(document).toEqual({
+			version: 1,
+			shortcuts: {
+				togglePicker: "CommandOrControl+Shift+V",
+				"odd key": "C:\\path",
+			},
+			retention: { maxItems: 1000, ratio: -1500, enabled: true },
+		});
+	});
+
+	it("reads arrays spanning lines with comments and a trailing comma", () => {
+		const document = parseToml(
+			'apps = [\r\n  "1Password", # vault\r\n  "caf\\u00e9",\r\n]\r\nnone = []',
+		);
+
+		expect(document).toEqual({ apps: ["1Password", "café"], none: [] });
+	});
+
+	it("reports the line of invalid or unsupported syntax", () => {
+		expect(() => parseToml("a = 1\nb")).toThrow(
+			"Invalid TOML on line 2: expected = after b",
+		);
+		expect(() => parseToml("[a]\n\n[a]")).toThrow("line 3: duplicate table a");
+		expect(() => parseToml("a = 1\na = 2")).toThrow("duplicate key a");
+		expect(() => parseToml("a = 01")).toThrow("expected a value");
+		expect(() => parseToml('a = "open')).toThrow("unterminated string");
+		expect(() => parseToml("a = [1 2]")).toThrow("expected , or ]");
+		expect(() => parseToml("a.b = 1")).toThrow("dotted keys");
+		expect(() => parseToml("a = { b = 1 }")).toThrow("inline tables");
+		expect(() => parseToml("[[a]]")).toThrow("arrays of tables");
+	});
+
+	it("rejects keys that would change an object's prototype", () => {
+		expect(() => parseToml("__proto__ = 1")).toThrow("reserved key");
+		expect(() => parseToml('["__proto__"]')).toThrow("reserved key");
+	});
+});
+
+describe("formatTomlValue", () => {
+	it("writes values that parse back unchanged", () => {
+		const value = ['quote " and\nnewline', 42, -1.5, true, []];
+
+		expect(parseToml(`a = ${formatTomlValue(value)}`)).toEqual({ a: value });
+	});
+
+	it("quotes keys that are not bare", () => {
+		expect(formatTomlKey("maxItems")).toBe("maxItems");
+		expect(formatTomlKey("Google Chrome")).toBe('"Google Chrome"');
+	});
+});
@@ -0,0 +1,244 @@
+/**
+ * Minimal TOML: enough of the spec for the settings file. Supports
+ * comments, `[table]` headers, bare and quoted keys, basic and literal
+ * strings, integers, floats, booleans and arrays (which may span lines).
+ * Dotted keys, inline tables, arrays of tables, multi-line strings and
+ * dates are rejected.
+ */
+
+export type TomlValue = string | number | boolean | TomlValue[];
+
+/**
+ * A parsed document: top-level keys and one object per table.
+ */
+export type TomlDocument = Record<
+	string,
+	TomlValue | Record<string, TomlValue>
+>;
+
+const BARE_KEY_PATTERN = /^[A-Za-z0-9_-]+$/;
+
+const NUMBER_PATTERN =
+	/^[+-]?(?:0|[1-9](?:_?\d)*)(?:\.\d(?:_?\d)*)?(?:[eE][+-]?\d(?:_?\d)*)?/;
+
+/**
+ * Names that would change an object's prototype if assigned.
+ */
+const RESERVED_KEYS = new Set(["__proto__", "constructor", "prototype"]);
+
+const hasKey = (object: object, key: string): boolean =>
+	Object.prototype.hasOwnProperty.call(object, key);
+
+const ESCAPES: Record<string, string> = {
+	b: "\b",
+	t: "\t",
+	n: "\n",
+	f: "\f",
+	r: "\r",
+	'"': '"',
+	"\\": "\\",
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Parses a TOML document.
+ * Pure function.
+ *
+ * @throws with the line number if the text is not valid TOML or uses an
+ *   unsupported feature
+ */
+export const parseToml = (text: string): TomlDocument => {
+	const document: TomlDocument = {};
+	let table: Record<string, TomlValue> | TomlDocument = document;
+	let pos = 0;
+	let line = 1;
+
+	const fail = (message: string): never => {
+		throw new Error(`Invalid TOML on line ${line}: ${message}`);
+	};
+
+	const skipSpaces = () => {
+		while (text[pos] === " " || text[pos] === "\t") pos++;
+	};
+
+	const skipComment = () => {
+		if (text[pos] !== "#") return;
+		while (pos < text.length && text[pos] !== "\n") pos++;
+	};
+
+	/** Skips whitespace, comments and line breaks, e.g. inside arrays */
+	const skipBlank = () => {
+		for (;;) {
+			skipSpaces();
+			skipComment();
+			if (text[pos] === "\r" && text[pos + 1] === "\n") pos++;
+			if (text[pos] !== "\n") return;
+			pos++;
+			line++;
+		}
+	};
+
+	const expectLineEnd = () => {
+		skipSpaces();
+		skipComment();
+		if (text[pos] === "\r" && text[pos + 1] === "\n") pos++;
+		if (pos < text.length && text[pos] !== "\n") {
+			fail(`unexpected "${text[pos]}"`);
+		}
+	};
+
+	const parseBasicString = (): string => {
+		pos++;
+		let value = "";
+		for (;;) {
+			const char = text[pos];
+			if (char === undefined || char === "\n") fail("unterminated string");
+			pos++;
+			if (char === '"') return value;
+			if (char !== "\\") {
+				value += char;
+				continue;
+			}
+			const code = text[pos++];
+			if (code === "u" || code === "U") {
+				const length = code === "u" ? 4 : 8;
+				const hex = text.slice(pos, pos + length);
+				const point = /^[0-9A-Fa-f]+$/.test(hex)
+					? Number.parseInt(hex, 16)
+					: -1;
+				if (hex.length !== length || point < 0 || point > 0x10ffff) {
+					fail("invalid unicode escape");
+				}
+				value += String.fromCodePoint(point);
+				pos += length;
+			} else if (ESCAPES[code] !== undefined) {
+				value += ESCAPES[code];
+			} else {
+				fail(`invalid escape \\${code}`);
+			}
+		}
+	};
+
+	const parseLiteralString = (): string => {
+		const end = text.indexOf("'", pos + 1);
+		const newline = text.indexOf("\n", pos + 1);
+		if (end === -1 || (newline !== -1 && newline < end)) {
+			fail("unterminated string");
+		}
+		const value = text.slice(pos + 1, end);
+		pos = end + 1;
+		return value;
+	};
+
+	const parseKey = (): string => {
+		if (text[pos] === '"' || text[pos] === "'") {
+			const key =
+				text[pos] === '"' ? parseBasicString() : parseLiteralString();
+			if (RESERVED_KEYS.has(key)) fail(`reserved key ${key}`);
+			return key;
+		}
+		const start = pos;
+		while (pos < text.length && /[A-Za-z0-9_-]/.test(text[pos])) pos++;
+		if (pos === start) fail("expected a key");
+		const key = text.slice(start, pos);
+		if (RESERVED_KEYS.has(key)) fail(`reserved key ${key}`);
+		return key;
+	};
+
+	const parseValue = (): TomlValue => {
+		const char = text[pos];
+		if (text.startsWith('"""', pos) || text.startsWith("'''", pos)) {
+			fail("multi-line strings are not supported");
+		}
+		if (char === '"') return parseBasicString();
+		if (char === "'") return parseLiteralString();
+		if (char === "[") {
+			pos++;
+			const values: TomlValue[] = [];
+			for (;;) {
+				skipBlank();
+				if (text[pos] === "]") break;
+				values.push(parseValue());
+				skipBlank();
+				if (text[pos] === ",") {
+					pos++;
+				} else if (text[pos] !== "]") {
+					fail("expected , or ] in array");
+				}
+			}
+			pos++;
+			return values;
+		}
+		for (const word of ["true", "false"]) {
+			const after = text[pos + word.length] ?? "";
+			if (text.startsWith(word, pos) && !/[\w-]/.test(after)) {
+				pos += word.length;
+				return word === "true";
+			}
+		}
+		const number = NUMBER_PATTERN.exec(text.slice(pos));
+		if (number && !/[\w.:-]/.test(text[pos + number[0].length] ?? "")) {
+			pos += number[0].length;
+			return Number(number[0].replace(/_/g, ""));
+		}
+		if (char === "{") fail("inline tables are not supported");
+		return fail("expected a value");
+	};
+
+	for (;;) {
+		skipBlank();
+		if (pos >= text.length) return document;
+
+		if (text[pos] === "[") {
+			if (text[pos + 1] === "[") fail("arrays of tables are not supported");
+			pos++;
+			skipSpaces();
+			const name = parseKey();
+			skipSpaces();
+			if (text[pos] === ".") fail("dotted keys are not supported");
+			if (text[pos] !== "]") fail("expected ] after table name");
+			pos++;
+			if (hasKey(document, name)) fail(`duplicate table ${name}`);
+			const next: Record<string, TomlValue> = {};
+			document[name] = next;
+			table = next;
+		} else {
+			const key = parseKey();
+			skipSpaces();
+			if (text[pos] === ".") fail("dotted keys are not supported");
+			if (text[pos] !== "=") fail(`expected = after ${key}`);
+			pos++;
+			skipSpaces();
+			const value = parseValue();
+			if (hasKey(table, key)) fail(`duplicate key ${key}`);
+			table[key] = value;
+		}
+		expectLineEnd();
+	}
+};
+
+/**
+ * Formats a key, quoting it unless it is a bare key.
+ * Pure function.
+ */
+export const formatTomlKey = (key: string): string =>
+	BARE_KEY_PATTERN.test(key) ? key : JSON.stringify(key);
+
+/**
+ * Formats a value as it appears after `key = `.
+ * Pure function. JSON string escapes are also valid TOML escapes.
+ *
+ * @throws if a number is not finite
+ */
+export const formatTomlValue = (value: TomlValue): string => {
+	if (Array.isArray(value)) {
+		return `[${value.map(formatTomlValue).join(", ")}]`;
+	}
+	if (typeof value === "number" && !Number.isFinite(value)) {
+		throw new Error(`Cannot write ${value} as TOML`);
+	}
+	return JSON.stringify(value);
+};
@@ -0,0 +1,68 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import { createTrayClicks, parseTrayClickSettings } from "./tray-clicks.js";
+
+describe("parseTrayClickSettings", () => {
+	it("opens the menu on a click by default", () => {
+		expect(parseTrayClickSettings({})).toEqual({
+			click: "menu",
+			doubleClick: "none",
+			middleClick: "none",
+		});
+	});
+
+	it("rejects unknown actions", () => {
+		expect(() => parseTrayClickSettings({ middleClick: "quit" })).toThrow(
+			"middleClick must be one of menu, none, toggleWindow",
+		);
+	});
+});
+
+describe("createTrayClicks", () => {
+	let userDataPath: string;
+
+	beforeEach(() => {
+		vi.useFakeTimers();
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "tray-clicks-"));
+	});
+
+	afterEach(() => {
+		vi.useRealTimers();
+		fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("runs each click's action", () => {
+		const run = vi.fn();
+		const clicks = createTrayClicks({ userDataPath, run });
+		clicks.updateSettings({
+			click: "toggleQuickPicker",
+			middleClick: "toggleCapture",
+		});
+
+		clicks.handle("click");
+		clicks.handle("middleClick");
+		clicks.handle("doubleClick");
+
+		expect(run.mock.calls).toEqual([["toggleQuickPicker"], ["toggleCapture"]]);
+	});
+
+	it("holds a click back until it is not a double-click", () => {
+		const run = vi.fn();
+		const clicks = createTrayClicks({ userDataPath, run, doubleClickMs: 200 });
+		clicks.updateSettings({
+			click: "toggleQuickPicker",
+			doubleClick: "pasteLastItem",
+		});
+
+		clicks.handle("click");
+		clicks.handle("doubleClick");
+		vi.advanceTimersByTime(200);
+		expect(run.mock.calls).toEqual([["pasteLastItem"]]);
+
+		clicks.handle("click");
+		vi.advanceTimersByTime(200);
+		expect(run).toHaveBeenLastCalledWith("toggleQuickPicker");
+	});
+});
@@ -0,0 +1,185 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Clicks on the tray icon that can run an action.
+ */
+export const TRAY_CLICKS = ["click", "doubleClick", "middleClick"] as const;
+
+export type TrayClick = (typeof TRAY_CLICKS)[number];
+
+/**
+ * What a tray click does: open the tray menu, nothing, or one of the
+ * actions the global shortcuts also offer.
+ */
+export const TRAY_CLICK_ACTIONS = [
+	"menu",
+	"none",
+	"toggleWindow",
+	"toggleQuickPicker",
+	"toggleCapture",
+	"pasteLastItem",
+] as const;
+
+export type TrayClickAction = (typeof TRAY_CLICK_ACTIONS)[number];
+
+/**
+ * Persisted action per tray click.
+ */
+export type TrayClickSettings = Record<TrayClick, TrayClickAction>;
+
+/**
+ * A click opens the menu and nothing else happens, as before clicks
+ * were configurable.
+ */
+const DEFAULT_TRAY_CLICK_SETTINGS: TrayClickSettings = {
+	click: "menu",
+	doubleClick: "none",
+	middleClick: "none",
+};
+
+const TRAY_CLICKS_FILENAME = "tray-clicks.json";
+
+/**
+ * How long a click waits to see whether it becomes a double-click (ms),
+ * when a double-click action is set.
+ */
+const DOUBLE_CLICK_MS = 300;
+
+const invalid = (message: string) =>
+	new Error(`Invalid tray click settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a tray click settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if an action is unknown
+ */
+export const parseTrayClickSettings = (
+	input: unknown,
+	current: TrayClickSettings = DEFAULT_TRAY_CLICK_SETTINGS,
+): TrayClickSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const values = input as Partial<Record<TrayClick, unknown>>;
+	const next: TrayClickSettings = { ...current };
+
+	for (const click of TRAY_CLICKS) {
+		const value = values[click];
+		if (value === undefined) continue;
+		if (!TRAY_CLICK_ACTIONS.includes(value as TrayClickAction)) {
+			throw invalid(`${click} must be one of ${TRAY_CLICK_ACTIONS.join(", ")}`);
+		}
+		next[click] = value as TrayClickAction;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getTrayClicksPath = (userDataPath: string) =>
+	path.join(userDataPath, TRAY_CLICKS_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readTrayClicksFromFile = (filePath: string): TrayClickSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_TRAY_CLICK_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseTrayClickSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read tray click settings:", error);
+		return { ...DEFAULT_TRAY_CLICK_SETTINGS };
+	}
+};
+
+const writeTrayClicksToFile = (
+	filePath: string,
+	settings: TrayClickSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Tray Clicks Module
+// ============================================================================
+
+export type TrayClicksDeps = {
+	userDataPath: string;
+	/** Runs a click's action; never called with `none` */
+	run: (action: Exclude<TrayClickAction, "none">) => void;
+	doubleClickMs?: number;
+};
+
+/**
+ * Creates the tray clicks module, mapping clicks on the tray icon to
+ * actions. While a double-click action is set, a single click waits
+ * briefly and is dropped if it turns into a double-click, so
+ * double-clicking does not also run the click action twice.
+ */
+export const createTrayClicks = (deps: TrayClicksDeps) => {
+	const filePath = getTrayClicksPath(deps.userDataPath);
+	const doubleClickMs = deps.doubleClickMs ?? DOUBLE_CLICK_MS;
+	let settings = readTrayClicksFromFile(filePath);
+	let pendingClick: ReturnType<typeof setTimeout> | null = null;
+
+	const cancelPendingClick = (): void => {
+		if (pendingClick) {
+			clearTimeout(pendingClick);
+			pendingClick = null;
+		}
+	};
+
+	const run = (action: TrayClickAction): void => {
+		if (action !== "none") deps.run(action);
+	};
+
+	/**
+	 * Handles a click on the tray icon.
+	 */
+	const handle = (click: TrayClick): void => {
+		const action = settings[click];
+		if (click === "doubleClick") {
+			cancelPendingClick();
+			run(action);
+			return;
+		}
+		if (click === "click" && settings.doubleClick !== "none") {
+			cancelPendingClick();
+			pendingClick = setTimeout(() => {
+				pendingClick = null;
+				run(action);
+			}, doubleClickMs);
+			return;
+		}
+		run(action);
+	};
+
+	const getSettings = (): TrayClickSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): TrayClickSettings => {
+		const next = parseTrayClickSettings(input, settings);
+		writeTrayClicksToFile(filePath, next);
+		settings = next;
+		cancelPendingClick();
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings, handle };
+};
+
+export type TrayClicks = ReturnType<typeof createTrayClicks>;
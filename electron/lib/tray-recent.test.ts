@@ -0,0 +1,101 @@
+import { describe, expect, it } from "vitest";
+import { formatTrayPreview, formatTrayTooltip } from "./tray-recent.js";
+
+const textItem = (content: string, secret_match: string | null = null) => ({
+	type: "text",
+	content,
+	image_width: null,
+	image_height: null,
+	secret_match,
+});
+
+describe("formatTrayPreview", () => {
+	it("shows text on one line", () => {
+		expect(formatTrayPreview(textItem("  hello\n\tworld  "), "darwin")).toBe(
+			"hello world",
+		);
+	});
+
+	it("truncates long text with an ellipsis", () => {
+		const preview = formatTrayPreview(textItem("x".repeat(100)), "linux");
+
+		expect(preview).toHaveLength(40);
+		expect(preview.endsWith("…")).toBe(true);
+	});
+
+	it("describes images and files", () => {
+		expect(
+			formatTrayPreview(
+				{
+					type: "image",
+					content: "",
+					image_width: 640,
+					image_height: 480,
+					secret_match: null,
+				},
+				"linux",
+			),
+		).toBe("Image 640×480");
+		expect(
+			formatTrayPreview(
+				{
+					type: "files",
+					content: "/home/me/report.pdf\n/home/me/notes.txt",
+					image_width: null,
+					image_height: null,
+					secret_match: null,
+				},
+				"linux",
+			),
+		).toBe("report.pdf +1 more");
+	});
+
+	it("masks flagged secrets", () => {
+		expect(
+			formatTrayPreview(textItem("ghp_abcdef123456", "GitHub token"), "linux"),
+		).toBe("ghp_****");
+	});
+
+	it("escapes ampersands on Windows only", () => {
+		expect(formatTrayPreview(textItem("R&D"), "win32")).toBe("R&&D");
+		expect(formatTrayPreview(textItem("R&D"), "darwin")).toBe("R&D");
+	});
+});
+
+describe("formatTrayTooltip", () => {
+	it("adds the newest item and the item count", () => {
+		expect(
+			formatTrayTooltip("Clipboard Manager", {
+				latest: textItem("SELECT *\nFROM users"),
+				count: 42,
+			}),
+		).toBe("Clipboard Manager\nLatest: SELECT * FROM users\n42 items");
+	});
+
+	it("omits what may not be shown", () => {
+		expect(
+			formatTrayTooltip("Clipboard Manager", { latest: null, count: 0 }),
+		).toBe("Clipboard Manager\n0 items");
+		expect(formatTrayTooltip("Clipboard Manager (capture paused)", null)).toBe(
+			"Clipboard Manager (capture paused)",
+		);
+	});
+
+	it("does not escape ampersands", () => {
+		expect(
+			formatTrayTooltip("Clipboard Manager", {
+				latest: textItem("R&D"),
+				count: 1,
+			}),
+		).toBe("Clipboard Manager\nLatest: R&D\n1 item");
+	});
+
+	it("masks a flagged secret", () => {
+		expect(
+			formatTrayTooltip("Clipboard Manager", {
+				latest: textItem("ghp_abcdef123456", "GitHub token"),
+				count: 1,
+			}),
+		).toBe("Clipboard Manager\nLatest: ghp_****\n1 item");
+	});
+});
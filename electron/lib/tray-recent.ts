@@ -0,0 +1,79 @@
+import path from "node:path";
+import { parseStoredFileList } from "./file-lists.js";
+import type { HistoryRow } from "./history-repository.js";
+import { type Translate, translateEnglish } from "./i18n.js";
+import { maskSecretPreview } from "./secret-scanning.js";
+
+/**
+ * Number of recent items listed in the tray menu.
+ */
+export const TRAY_RECENT_ITEMS = 10;
+
+/**
+ * Longest tray menu label (characters), including the ellipsis.
+ */
+const MAX_PREVIEW_LENGTH = 40;
+
+type PreviewedItem = Pick<
+	HistoryRow,
+	"type" | "content" | "image_width" | "image_height" | "secret_match"
+>;
+
+/**
+ * Describes a history item on one line: its text, the image size, or the
+ * first copied file name. Long previews end in an ellipsis. Also used for
+ * `clipctl list`, the HTTP API and D-Bus.
+ * Pure function. Flagged secrets are masked.
+ */
+export const describeItem = (item: PreviewedItem): string => {
+	let preview: string;
+	if (item.secret_match !== null) {
+		preview = maskSecretPreview(item.content);
+	} else if (item.type === "image") {
+		preview =
+			item.image_width && item.image_height
+				? `Image ${item.image_width}×${item.image_height}`
+				: "Image";
+	} else if (item.type === "files") {
+		const [first, ...rest] = parseStoredFileList(item.content);
+		const name = first ? path.basename(first) : "Files";
+		preview = rest.length > 0 ? `${name} +${rest.length} more` : name;
+	} else {
+		preview = item.content.trim().replace(/\s+/g, " ");
+	}
+
+	if (preview.length > MAX_PREVIEW_LENGTH) {
+		preview = `${preview.slice(0, MAX_PREVIEW_LENGTH - 1).trimEnd()}…`;
+	}
+	return preview;
+};
+
+/**
+ * Builds the tray menu label for a history item.
+ * Pure function. On Windows `&` is doubled so it is not taken as a menu
+ * mnemonic.
+ */
+export const formatTrayPreview = (
+	item: PreviewedItem,
+	platform: NodeJS.Platform,
+): string => {
+	const preview = describeItem(item);
+	return platform === "win32" ? preview.replace(/&/g, "&&") : preview;
+};
+
+/**
+ * Builds the tray tooltip: the status line, then the newest item and the
+ * number of items in history when they may be shown.
+ * Pure function.
+ */
+export const formatTrayTooltip = (
+	status: string,
+	summary: { latest: PreviewedItem | null; count: number } | null,
+	t: Translate = translateEnglish,
+): string => {
+	if (!summary) return status;
+	const count = t("tray.itemCount", { count: summary.count });
+	if (!summary.latest) return `${status}\n${count}`;
+	const latest = t("tray.latest", { preview: describeItem(summary.latest) });
+	return `${status}\n${latest}\n${count}`;
+};
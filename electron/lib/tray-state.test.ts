@@ -0,0 +1,53 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	createTrayActivity,
+	formatUnreadBadge,
+	getTrayIconState,
+} from "./tray-state.js";
+
+describe("getTrayIconState", () => {
+	const stateOf = (paused: boolean, syncing: boolean, unread: number) =>
+		getTrayIconState({ paused, syncing, unread });
+
+	it("prefers paused, then syncing, then unread", () => {
+		expect(stateOf(true, true, 3)).toBe("paused");
+		expect(stateOf(false, true, 3)).toBe("syncing");
+		expect(stateOf(false, false, 3)).toBe("unread");
+		expect(stateOf(false, false, 0)).toBe("normal");
+	});
+});
+
+describe("formatUnreadBadge", () => {
+	it("caps the count", () => {
+		expect(formatUnreadBadge(0)).toBe("");
+		expect(formatUnreadBadge(7)).toBe("7");
+		expect(formatUnreadBadge(120)).toBe("99+");
+	});
+});
+
+describe("createTrayActivity", () => {
+	it("counts captures until cleared", () => {
+		const activity = createTrayActivity();
+		activity.recordCapture();
+		activity.recordCapture(3);
+
+		expect(activity.getStatus()).toEqual({ syncing: false, unread: 4 });
+		activity.clearUnread();
+		expect(activity.getStatus().unread).toBe(0);
+	});
+
+	it("notifies only on changes", () => {
+		const onChange = vi.fn();
+		const activity = createTrayActivity({ onChange });
+
+		activity.clearUnread();
+		activity.setSyncing(true);
+		activity.setSyncing(true);
+		activity.recordCapture();
+
+		expect(onChange.mock.calls).toEqual([
+			[{ syncing: true, unread: 0 }],
+			[{ syncing: true, unread: 1 }],
+		]);
+	});
+});
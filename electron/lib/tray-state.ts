@@ -0,0 +1,96 @@
+/**
+ * States the tray icon can show.
+ */
+export type TrayIconState = "normal" | "paused" | "syncing" | "unread";
+
+/**
+ * Tray activity reported alongside capture pause.
+ */
+export type TrayActivityStatus = {
+	/** Whether a sync is in progress */
+	syncing: boolean;
+	/** Items captured since the window was last shown */
+	unread: number;
+};
+
+/**
+ * Icon file per state, in `public/` during development and in the app
+ * resources when packaged.
+ */
+export const TRAY_ICON_FILES: Record<TrayIconState, string> = {
+	normal: "tray-icon.png",
+	paused: "tray-icon-paused.png",
+	syncing: "tray-icon-syncing.png",
+	unread: "tray-icon-unread.png",
+};
+
+/**
+ * Picks the tray icon state.
+ * Pure function. Paused wins, since nothing is being recorded, then an
+ * ongoing sync, then unread captures.
+ */
+export const getTrayIconState = (
+	status: TrayActivityStatus & { paused: boolean },
+): TrayIconState => {
+	if (status.paused) return "paused";
+	if (status.syncing) return "syncing";
+	if (status.unread > 0) return "unread";
+	return "normal";
+};
+
+/**
+ * Badge text for unread captures: the count, "99+" beyond 99, or empty
+ * when there are none.
+ * Pure function.
+ */
+export const formatUnreadBadge = (count: number): string => {
+	if (count <= 0) return "";
+	return count > 99 ? "99+" : String(count);
+};
+
+export type TrayActivityDeps = {
+	/** Called whenever the syncing flag or unread count changes */
+	onChange?: (status: TrayActivityStatus) => void;
+};
+
+/**
+ * Creates the tray activity tracker behind the syncing and unread icons.
+ * Held in the main process and not persisted.
+ */
+export const createTrayActivity = (deps: TrayActivityDeps = {}) => {
+	let syncing = false;
+	let unread = 0;
+
+	const getStatus = (): TrayActivityStatus => ({ syncing, unread });
+
+	const notify = (): void => {
+		deps.onChange?.(getStatus());
+	};
+
+	/**
+	 * Counts captures made while the window was hidden.
+	 */
+	const recordCapture = (count = 1): void => {
+		unread += count;
+		notify();
+	};
+
+	/**
+	 * Resets the unread count, e.g. when the window is shown.
+	 */
+	const clearUnread = (): void => {
+		if (unread === 0) return;
+		unread = 0;
+		notify();
+	};
+
+	const setSyncing = (value: boolean): void => {
+		if (syncing === value) return;
+		syncing = value;
+		notify();
+	};
+
+	return { getStatus, recordCapture, clearUnread, setSyncing };
+};
+
+export type TrayActivity = ReturnType<typeof createTrayActivity>;
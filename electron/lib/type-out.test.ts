@@ -0,0 +1,157 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	buildMacTypeScript,
+	buildWindowsTypeScript,
+	createTypeOut,
+	getTypeOutCommand,
+	getTypeOutSettingsPath,
+	parseTypeOutSettings,
+} from "./type-out.js";
+
+describe("parseTypeOutSettings", () => {
+	it("keeps the current delay when missing", () => {
+		expect(parseTypeOutSettings({}, { delayMs: 50 })).toEqual({ delayMs: 50 });
+		expect(parseTypeOutSettings({ delayMs: 0 })).toEqual({ delayMs: 0 });
+	});
+
+	it("rejects invalid delays", () => {
+		expect(() => parseTypeOutSettings(null)).toThrow("expected an object");
+		for (const delayMs of [-1, 1.5, 1001, "10"]) {
+			expect(() => parseTypeOutSettings({ delayMs })).toThrow(
+				"delayMs must be 0-1000",
+			);
+		}
+	});
+});
+
+describe("buildMacTypeScript", () => {
+	it("types each character with a pause between keystrokes", () => {
+		expect(buildMacTypeScript('a"\\', 20)).toBe(
+			[
+				'tell application "System Events"',
+				'keystroke "a"',
+				"delay 0.02",
+				'keystroke "\\""',
+				"delay 0.02",
+				'keystroke "\\\\"',
+				"end tell",
+			].join("\n"),
+		);
+	});
+
+	it("sends newlines and tabs as keys", () => {
+		expect(buildMacTypeScript("a\r\n\t", 0)).toBe(
+			[
+				'tell application "System Events"',
+				'keystroke "a"',
+				"key code 36",
+				"key code 48",
+				"end tell",
+			].join("\n"),
+		);
+	});
+});
+
+describe("buildWindowsTypeScript", () => {
+	it("escapes SendKeys and PowerShell specials", () => {
+		const script = buildWindowsTypeScript("a+'\n", 5);
+
+		expect(script).toContain("@('a','{+}','''','{ENTER}')");
+		expect(script).toContain("Start-Sleep -Milliseconds 5");
+	});
+});
+
+describe("getTypeOutCommand", () => {
+	it("passes the delay to xdotool on Linux", () => {
+		expect(getTypeOutCommand("linux", "-rf", 12)).toEqual({
+			command: "xdotool",
+			args: ["type", "--delay", "12", "--", "-rf"],
+		});
+	});
+
+	it("is unsupported elsewhere", () => {
+		expect(getTypeOutCommand("freebsd", "text", 10)).toBeNull();
+	});
+});
+
+describe("createTypeOut", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-type-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("types with the configured delay", async () => {
+		const run = vi.fn().mockResolvedValue(undefined);
+		const typeOut = createTypeOut({
+			userDataPath: createTempUserDataPath(),
+			platform: "linux",
+			run,
+		});
+
+		typeOut.updateSettings({ delayMs: 40 });
+		await typeOut.typeText("hello");
+
+		expect(run).toHaveBeenCalledWith("xdotool", [
+			"type",
+			"--delay",
+			"40",
+			"--",
+			"hello",
+		]);
+	});
+
+	it("rejects text that is too long", async () => {
+		const run = vi.fn();
+		const typeOut = createTypeOut({
+			userDataPath: createTempUserDataPath(),
+			platform: "linux",
+			run,
+		});
+
+		await expect(typeOut.typeText("x".repeat(4001))).rejects.toThrow(
+			"Text too long to type out",
+		);
+		expect(run).not.toHaveBeenCalled();
+	});
+
+	it("rejects unsupported platforms", async () => {
+		const typeOut = createTypeOut({
+			userDataPath: createTempUserDataPath(),
+			platform: "freebsd",
+			run: vi.fn(),
+		});
+
+		await expect(typeOut.typeText("hello")).rejects.toThrow(
+			"not supported on freebsd",
+		);
+	});
+
+	it("persists the delay and falls back to defaults when invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		createTypeOut({ userDataPath, platform: "linux" }).updateSettings({
+			delayMs: 25,
+		});
+		expect(
+			createTypeOut({ userDataPath, platform: "linux" }).getSettings(),
+		).toEqual({ delayMs: 25 });
+
+		fs.writeFileSync(getTypeOutSettingsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+		expect(
+			createTypeOut({ userDataPath, platform: "linux" }).getSettings(),
+		).toEqual({ delayMs: 10 });
+		errorSpy.mockRestore();
+	});
+});
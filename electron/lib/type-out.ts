@@ -0,0 +1,257 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs";
+import path from "node:path";
+import type { KeystrokeCommandRunner } from "./paste-keystroke.js";
+
+/**
+ * Persisted "type it out" settings.
+ */
+export type TypeOutSettings = {
+	/** Pause between synthetic keystrokes (ms) */
+	delayMs: number;
+};
+
+const DEFAULT_TYPE_OUT_SETTINGS: TypeOutSettings = {
+	delayMs: 10,
+};
+
+const TYPE_OUT_FILENAME = "type-out.json";
+
+const MAX_DELAY_MS = 1000;
+
+/**
+ * Longest text that can be typed out (characters); keeps the generated
+ * script within Windows' command line limit.
+ */
+const MAX_TYPE_OUT_LENGTH = 4000;
+
+/**
+ * SendKeys characters that must be wrapped in braces to be typed literally.
+ */
+const SENDKEYS_SPECIAL = new Set([
+	"+",
+	"^",
+	"%",
+	"~",
+	"(",
+	")",
+	"{",
+	"}",
+	"[",
+	"]",
+]);
+
+const runCommand: KeystrokeCommandRunner = (command, args) =>
+	new Promise((resolve, reject) => {
+		execFile(command, args, (error) => (error ? reject(error) : resolve()));
+	});
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a type-out settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `delayMs` is not an integer from 0 to 1000
+ */
+export const parseTypeOutSettings = (
+	input: unknown,
+	current: TypeOutSettings = DEFAULT_TYPE_OUT_SETTINGS,
+): TypeOutSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid type-out settings: expected an object");
+	}
+
+	const { delayMs } = input as Partial<Record<keyof TypeOutSettings, unknown>>;
+	const next: TypeOutSettings = { ...current };
+
+	if (delayMs !== undefined) {
+		if (
+			typeof delayMs !== "number" ||
+			!Number.isInteger(delayMs) ||
+			delayMs < 0 ||
+			delayMs > MAX_DELAY_MS
+		) {
+			throw new Error(
+				`Invalid type-out settings: delayMs must be 0-${MAX_DELAY_MS}`,
+			);
+		}
+		next.delayMs = delayMs;
+	}
+
+	return next;
+};
+
+/**
+ * Splits text into the characters to type; `\r\n` counts as one newline.
+ * Pure function.
+ */
+const toTypedCharacters = (text: string): string[] =>
+	Array.from(text.replace(/\r\n/g, "\n"));
+
+/**
+ * Builds an AppleScript that types `text` through System Events, one
+ * keystroke at a time. Newlines and tabs are sent as Return and Tab.
+ * Pure function.
+ */
+export const buildMacTypeScript = (text: string, delayMs: number): string => {
+	const pause = `delay ${delayMs / 1000}`;
+	const steps = toTypedCharacters(text).map((char) => {
+		if (char === "\n") return "key code 36";
+		if (char === "\t") return "key code 48";
+		return `keystroke "${char.replace(/\\/g, "\\\\").replace(/"/g, '\\"')}"`;
+	});
+	const body = delayMs > 0 ? steps.join(`\n${pause}\n`) : steps.join("\n");
+	return `tell application "System Events"\n${body}\nend tell`;
+};
+
+/**
+ * Builds a PowerShell script that types `text` through SendKeys, one key
+ * at a time.
+ * Pure function.
+ */
+export const buildWindowsTypeScript = (
+	text: string,
+	delayMs: number,
+): string => {
+	const keys = toTypedCharacters(text).map((char) => {
+		if (char === "\n") return "{ENTER}";
+		if (char === "\t") return "{TAB}";
+		if (SENDKEYS_SPECIAL.has(char)) return `{${char}}`;
+		return char;
+	});
+	const list = keys.map((key) => `'${key.replace(/'/g, "''")}'`).join(",");
+	const pause = delayMs > 0 ? `; Start-Sleep -Milliseconds ${delayMs}` : "";
+	return `Add-Type -AssemblyName System.Windows.Forms; foreach ($k in @(${list})) { [System.Windows.Forms.SendKeys]::SendWait($k)${pause} }`;
+};
+
+/**
+ * Command that types `text` into the focused application, or null if the
+ * platform is unsupported.
+ * - macOS: System Events keystrokes (needs Accessibility permission)
+ * - Windows: SendKeys
+ * - Linux (X11): `xdotool type`
+ * Pure function.
+ */
+export const getTypeOutCommand = (
+	platform: NodeJS.Platform,
+	text: string,
+	delayMs: number,
+): { command: string; args: string[] } | null => {
+	switch (platform) {
+		case "darwin":
+			return {
+				command: "osascript",
+				args: ["-e", buildMacTypeScript(text, delayMs)],
+			};
+		case "win32":
+			return {
+				command: "powershell.exe",
+				args: [
+					"-NoProfile",
+					"-NonInteractive",
+					"-Command",
+					buildWindowsTypeScript(text, delayMs),
+				],
+			};
+		case "linux":
+			return {
+				command: "xdotool",
+				args: ["type", "--delay", String(delayMs), "--", text],
+			};
+		default:
+			return null;
+	}
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getTypeOutSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, TYPE_OUT_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readTypeOutSettingsFromFile = (filePath: string): TypeOutSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_TYPE_OUT_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseTypeOutSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read type-out settings, using defaults:", error);
+		return { ...DEFAULT_TYPE_OUT_SETTINGS };
+	}
+};
+
+const writeTypeOutSettingsToFile = (
+	filePath: string,
+	settings: TypeOutSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Type-Out Module
+// ============================================================================
+
+export type TypeOutDeps = {
+	userDataPath: string;
+	platform: NodeJS.Platform;
+	run?: KeystrokeCommandRunner;
+};
+
+/**
+ * Creates the "type it out" paste mode: text is typed into the focused
+ * application with synthetic key events, for fields and remote sessions
+ * that block Cmd/Ctrl+V.
+ */
+export const createTypeOut = (deps: TypeOutDeps) => {
+	const filePath = getTypeOutSettingsPath(deps.userDataPath);
+	const run = deps.run ?? runCommand;
+	let settings = readTypeOutSettingsFromFile(filePath);
+
+	const getSettings = (): TypeOutSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): TypeOutSettings => {
+		const next = parseTypeOutSettings(input, settings);
+		writeTypeOutSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	/**
+	 * Types text into the focused application.
+	 *
+	 * @throws if the text is longer than 4000 characters or the platform
+	 *   is unsupported
+	 */
+	const typeText = async (text: string): Promise<void> => {
+		if (text.length > MAX_TYPE_OUT_LENGTH) {
+			throw new Error(
+				`Text too long to type out: ${text.length} characters (max: ${MAX_TYPE_OUT_LENGTH})`,
+			);
+		}
+		const typeCommand = getTypeOutCommand(
+			deps.platform,
+			text,
+			settings.delayMs,
+		);
+		if (!typeCommand) {
+			throw new Error(`Typing out is not supported on ${deps.platform}`);
+		}
+		if (text === "") return;
+		await run(typeCommand.command, typeCommand.args);
+	};
+
+	return { getSettings, updateSettings, typeText };
+};
+
+export type TypeOut = ReturnType<typeof createTypeOut>;
@@ -0,0 +1,76 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createUniversalClipboard,
+	decideRemoteCopy,
+	getUniversalClipboardPath,
+	parseUniversalClipboardSettings,
+} from "./universal-clipboard.js";
+
+describe("parseUniversalClipboardSettings", () => {
+	it("normalizes the tag and keeps missing keys", () => {
+		expect(
+			parseUniversalClipboardSettings(
+				{ tag: "  From iPhone " },
+				{ action: "tag", tag: "handoff" },
+			),
+		).toEqual({ action: "tag", tag: "from iphone" });
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseUniversalClipboardSettings(null)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseUniversalClipboardSettings({ action: "drop" })).toThrow(
+			"action must be one of keep, skip, tag",
+		);
+		expect(() => parseUniversalClipboardSettings({ tag: " " })).toThrow(
+			"Invalid Universal Clipboard settings: Invalid tag name",
+		);
+	});
+});
+
+describe("decideRemoteCopy", () => {
+	it("only acts on copies from another device", () => {
+		const tag = { action: "tag", tag: "handoff" } as const;
+		expect(decideRemoteCopy(false, tag)).toEqual({ skip: false, tags: [] });
+		expect(decideRemoteCopy(true, tag)).toEqual({
+			skip: false,
+			tags: ["handoff"],
+		});
+		expect(decideRemoteCopy(true, { ...tag, action: "skip" })).toEqual({
+			skip: true,
+			tags: [],
+		});
+		expect(decideRemoteCopy(true, { ...tag, action: "keep" })).toEqual({
+			skip: false,
+			tags: [],
+		});
+	});
+});
+
+describe("createUniversalClipboard", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("keeps remote copies until told otherwise, and saves the choice", () => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-remote-"));
+		tempDirs.push(dir);
+		const universalClipboard = createUniversalClipboard({ userDataPath: dir });
+		expect(universalClipboard.decide(true).skip).toBe(false);
+
+		universalClipboard.updateSettings({ action: "skip" });
+		expect(universalClipboard.decide(true).skip).toBe(true);
+		expect(
+			createUniversalClipboard({ userDataPath: dir }).getSettings(),
+		).toEqual({ action: "skip", tag: "handoff" });
+		expect(fs.existsSync(getUniversalClipboardPath(dir))).toBe(true);
+	});
+});
@@ -0,0 +1,166 @@
+import fs from "node:fs";
+import path from "node:path";
+import { assertValidTagName } from "./tags.js";
+
+/**
+ * What happens to copies another Apple device hands over through
+ * Universal Clipboard: recorded like any copy, skipped, or recorded with
+ * a tag.
+ */
+export type RemoteCopyAction = "keep" | "skip" | "tag";
+
+/**
+ * Persisted Universal Clipboard settings.
+ */
+export type UniversalClipboardSettings = {
+	action: RemoteCopyAction;
+	/** Tag added to remote copies with the `tag` action */
+	tag: string;
+};
+
+/**
+ * What to do with one copy.
+ */
+export type RemoteCopyDecision = {
+	skip: boolean;
+	tags: string[];
+};
+
+/**
+ * Pasteboard type macOS adds to copies made on another device.
+ */
+export const REMOTE_CLIPBOARD_FORMAT = "com.apple.is-remote-clipboard";
+
+const DEFAULT_UNIVERSAL_CLIPBOARD_SETTINGS: UniversalClipboardSettings = {
+	action: "keep",
+	tag: "handoff",
+};
+
+const UNIVERSAL_CLIPBOARD_FILENAME = "universal-clipboard.json";
+
+const ACTIONS: readonly RemoteCopyAction[] = ["keep", "skip", "tag"];
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a Universal Clipboard settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `action` is unknown or `tag` is not a valid tag name
+ */
+export const parseUniversalClipboardSettings = (
+	input: unknown,
+	current: UniversalClipboardSettings = DEFAULT_UNIVERSAL_CLIPBOARD_SETTINGS,
+): UniversalClipboardSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error(
+			"Invalid Universal Clipboard settings: expected an object",
+		);
+	}
+
+	const { action, tag } = input as Partial<
+		Record<keyof UniversalClipboardSettings, unknown>
+	>;
+	const next: UniversalClipboardSettings = { ...current };
+
+	if (action !== undefined) {
+		if (!ACTIONS.includes(action as RemoteCopyAction)) {
+			throw new Error(
+				`Invalid Universal Clipboard settings: action must be one of ${ACTIONS.join(", ")}`,
+			);
+		}
+		next.action = action as RemoteCopyAction;
+	}
+
+	if (tag !== undefined) {
+		try {
+			next.tag = assertValidTagName(tag);
+		} catch (error) {
+			throw new Error(
+				`Invalid Universal Clipboard settings: ${(error as Error).message}`,
+			);
+		}
+	}
+
+	return next;
+};
+
+/**
+ * Decides what to do with a copy, given whether it came from another
+ * device.
+ * Pure function.
+ */
+export const decideRemoteCopy = (
+	remote: boolean,
+	settings: UniversalClipboardSettings,
+): RemoteCopyDecision => {
+	if (!remote || settings.action === "keep") return { skip: false, tags: [] };
+	if (settings.action === "skip") return { skip: true, tags: [] };
+	return { skip: false, tags: [settings.tag] };
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getUniversalClipboardPath = (userDataPath: string) =>
+	path.join(userDataPath, UNIVERSAL_CLIPBOARD_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readUniversalClipboardFromFile = (
+	filePath: string,
+): UniversalClipboardSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_UNIVERSAL_CLIPBOARD_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseUniversalClipboardSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read Universal Clipboard settings:", error);
+		return { ...DEFAULT_UNIVERSAL_CLIPBOARD_SETTINGS };
+	}
+};
+
+const writeUniversalClipboardToFile = (
+	filePath: string,
+	settings: UniversalClipboardSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Universal Clipboard Module
+// ============================================================================
+
+/**
+ * Creates the Universal Clipboard filter. On macOS, a copy made on an
+ * iPhone or another Mac nearby lands on the pasteboard through Handoff
+ * and would be recorded like a local one; this keeps, skips or tags it.
+ */
+export const createUniversalClipboard = (deps: { userDataPath: string }) => {
+	const filePath = getUniversalClipboardPath(deps.userDataPath);
+	let settings = readUniversalClipboardFromFile(filePath);
+
+	const getSettings = (): UniversalClipboardSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): UniversalClipboardSettings => {
+		const next = parseUniversalClipboardSettings(input, settings);
+		writeUniversalClipboardToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	const decide = (remote: boolean): RemoteCopyDecision =>
+		decideRemoteCopy(remote, settings);
+
+	return { getSettings, updateSettings, decide };
+};
+
+export type UniversalClipboard = ReturnType<typeof createUniversalClipboard>;
@@ -0,0 +1,308 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	compareVersions,
+	createUpdater,
+	describeUpdateMenuItem,
+	parseUpdaterSettings,
+	pickUpdateRelease,
+	selectUpdateAsset,
+	type UpdaterStatus,
+	verifyUpdateSignature,
+} from "./updater.js";
+
+const { publicKey, privateKey } = crypto.generateKeyPairSync("ed25519");
+const PUBLIC_KEY_PEM = publicKey
+	.export({ type: "spki", format: "pem" })
+	.toString();
+
+const sign = (data: Buffer) =>
+	crypto.sign(null, data, privateKey).toString("base64");
+
+/**
+ * A GitHub release with signed installers for the given file names.
+ */
+const createRelease = (
+	tag: string,
+	names: string[],
+	options: { prerelease?: boolean; draft?: boolean } = {},
+) => ({
+	tag_name: tag,
+	body: `Notes for ${tag}`,
+	published_at: "2026-10-01T12:00:00Z",
+	prerelease: options.prerelease ?? false,
+	draft: options.draft ?? false,
+	assets: names.flatMap((name) => [
+		{ name, browser_download_url: `https://example.test/${name}`, size: 7 },
+		{
+			name: `${name}.sig`,
+			browser_download_url: `https://example.test/${name}.sig`,
+			size: 88,
+		},
+	]),
+});
+
+describe("compareVersions", () => {
+	it("orders versions by semantic versioning precedence", () => {
+		expect(compareVersions("1.2.0", "v1.10.0")).toBeLessThan(0);
+		expect(compareVersions("2.0.0", "2.0.0-beta.2")).toBeGreaterThan(0);
+		expect(compareVersions("2.0.0-beta.10", "2.0.0-beta.2")).toBeGreaterThan(
+			0,
+		);
+		expect(compareVersions("2.0.0-alpha", "2.0.0-alpha.1")).toBeLessThan(0);
+		expect(compareVersions("1.0.0+build.5", "1.0.0")).toBe(0);
+		expect(() => compareVersions("1.0", "1.0.0")).toThrow(
+			"Invalid version: 1.0",
+		);
+	});
+});
+
+describe("selectUpdateAsset", () => {
+	const names = [
+		"Clipboard Manager-1.1.0.dmg",
+		"Clipboard Manager-1.1.0-arm64.dmg",
+		"Clipboard Manager-1.1.0-arm64.dmg.blockmap",
+		"Clipboard Manager-1.1.0.AppImage",
+		"Clipboard Manager-1.1.0-arm64.AppImage",
+		"../evil.exe",
+	];
+
+	it("matches the platform and architecture", () => {
+		expect(selectUpdateAsset(names, "darwin", "arm64")).toBe(
+			"Clipboard Manager-1.1.0-arm64.dmg",
+		);
+		expect(selectUpdateAsset(names, "darwin", "x64")).toBe(
+			"Clipboard Manager-1.1.0.dmg",
+		);
+		expect(selectUpdateAsset(names, "linux", "arm64")).toBe(
+			"Clipboard Manager-1.1.0-arm64.AppImage",
+		);
+		expect(selectUpdateAsset(names, "win32", "x64")).toBeNull();
+		expect(selectUpdateAsset(names, "darwin", "ia32")).toBeNull();
+	});
+});
+
+describe("pickUpdateRelease", () => {
+	const options = {
+		currentVersion: "1.0.0",
+		platform: "darwin" as const,
+		arch: "arm64",
+	};
+	const releases = [
+		createRelease("v1.2.0-beta.1", ["app-1.2.0-beta.1-arm64.dmg"], {
+			prerelease: true,
+		}),
+		createRelease("v1.3.0", ["app-1.3.0-arm64.dmg"], { draft: true }),
+		createRelease("v1.1.0", ["app-1.1.0-arm64.dmg"]),
+		createRelease("v0.9.0", ["app-0.9.0-arm64.dmg"]),
+	];
+
+	it("offers prereleases on the beta channel only", () => {
+		expect(
+			pickUpdateRelease(releases, { ...options, channel: "stable" }),
+		).toEqual({
+			version: "1.1.0",
+			notes: "Notes for v1.1.0",
+			publishedAt: "2026-10-01T12:00:00Z",
+			assetName: "app-1.1.0-arm64.dmg",
+			assetUrl: "https://example.test/app-1.1.0-arm64.dmg",
+			signatureUrl: "https://example.test/app-1.1.0-arm64.dmg.sig",
+			size: 7,
+		});
+		expect(
+			pickUpdateRelease(releases, { ...options, channel: "beta" })?.version,
+		).toBe("1.2.0-beta.1");
+	});
+
+	it("skips releases without a signed installer for this platform", () => {
+		const unsigned = createRelease("v1.1.0", []);
+		unsigned.assets.push({
+			name: "app-1.1.0-arm64.dmg",
+			browser_download_url: "https://example.test/app-1.1.0-arm64.dmg",
+			size: 7,
+		});
+
+		expect(
+			pickUpdateRelease([unsigned, createRelease("v1.2.0", ["app.exe"])], {
+				...options,
+				channel: "stable",
+			}),
+		).toBeNull();
+		expect(() =>
+			pickUpdateRelease({}, { ...options, channel: "stable" }),
+		).toThrow("expected an array");
+	});
+});
+
+describe("verifyUpdateSignature", () => {
+	it("accepts only the signed bytes", () => {
+		const data = Buffer.from("installer");
+
+		expect(verifyUpdateSignature(data, sign(data), PUBLIC_KEY_PEM)).toBe(true);
+		expect(
+			verifyUpdateSignature(
+				Buffer.from("tampered"),
+				sign(data),
+				PUBLIC_KEY_PEM,
+			),
+		).toBe(false);
+		expect(verifyUpdateSignature(data, "not base64!", PUBLIC_KEY_PEM)).toBe(
+			false,
+		);
+	});
+});
+
+describe("describeUpdateMenuItem", () => {
+	const status: UpdaterStatus = {
+		channel: "stable",
+		autoCheck: true,
+		configured: true,
+		currentVersion: "1.0.0",
+		state: "idle",
+		available: null,
+		progress: null,
+		lastCheckedAt: null,
+		lastError: null,
+	};
+
+	it("labels the next step", () => {
+		expect(describeUpdateMenuItem(status)).toEqual({
+			label: "Check for Updates…",
+			enabled: true,
+			action: "check",
+		});
+		expect(
+			describeUpdateMenuItem({
+				...status,
+				state: "downloading",
+				available: { version: "1.1.0", notes: "", publishedAt: null },
+				progress: { received: 50, total: 200 },
+			}),
+		).toEqual({
+			label: "Downloading Update… 25%",
+			enabled: false,
+			action: null,
+		});
+		expect(describeUpdateMenuItem({ ...status, configured: false })).toBeNull();
+	});
+});
+
+describe("parseUpdaterSettings", () => {
+	it("validates the channel and automatic checks", () => {
+		expect(parseUpdaterSettings({ channel: "beta" })).toEqual({
+			channel: "beta",
+			autoCheck: true,
+		});
+		expect(() => parseUpdaterSettings({ channel: "nightly" })).toThrow(
+			"channel must be one of stable, beta",
+		);
+		expect(() => parseUpdaterSettings({ autoCheck: "yes" })).toThrow(
+			"autoCheck must be a boolean",
+		);
+	});
+});
+
+describe("createUpdater", () => {
+	const tempDirs: string[] = [];
+
+	const createTempDir = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-updater-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	/**
+	 * An updater for a running AppImage, served a 1.1.0 release whose
+	 * installer is `installer` and whose signature covers `signed`.
+	 */
+	const createAppImageUpdater = (installer: string, signed = installer) => {
+		const dir = createTempDir();
+		const appImagePath = path.join(dir, "Clipboard Manager.AppImage");
+		fs.writeFileSync(appImagePath, "old");
+		const name = "Clipboard Manager-1.1.0.AppImage";
+		const files: Record<string, string> = {
+			[`https://example.test/${name}`]: installer,
+			[`https://example.test/${name}.sig`]: sign(Buffer.from(signed)),
+		};
+		const fetch = vi.fn(async (url: string | URL | Request) => {
+			if (url === "https://releases.test") {
+				return Response.json([createRelease("v1.1.0", [name])]);
+			}
+			const body = files[String(url)];
+			return body === undefined
+				? new Response(null, { status: 404 })
+				: new Response(body);
+		});
+		const changes: UpdaterStatus[] = [];
+		const relaunch = vi.fn();
+		const updater = createUpdater({
+			userDataPath: path.join(dir, "userData"),
+			currentVersion: "1.0.0",
+			platform: "linux",
+			arch: "x64",
+			publicKey: PUBLIC_KEY_PEM,
+			appImagePath,
+			openPath: vi.fn(async () => ""),
+			quit: vi.fn(),
+			relaunch,
+			onChange: (status) => changes.push(status),
+			releasesUrl: "https://releases.test",
+			fetch,
+		});
+		return { updater, appImagePath, changes, relaunch };
+	};
+
+	it("downloads, verifies and swaps in the AppImage", async () => {
+		const { updater, appImagePath, changes, relaunch } =
+			createAppImageUpdater("new app");
+
+		expect((await updater.check()).available?.version).toBe("1.1.0");
+		expect((await updater.download()).state).toBe("ready");
+		expect(changes.map((status) => status.state)).toContain("downloading");
+
+		await updater.install();
+		expect(fs.readFileSync(appImagePath, "utf-8")).toBe("new app");
+		expect(relaunch).toHaveBeenCalledOnce();
+	});
+
+	it("deletes a download whose signature does not match", async () => {
+		const { updater, appImagePath } = createAppImageUpdater(
+			"new app",
+			"other app",
+		);
+
+		await updater.check();
+		const status = await updater.download();
+
+		expect(status.state).toBe("available");
+		expect(status.lastError).toContain("signature");
+		await expect(updater.install()).rejects.toThrow("No downloaded update");
+		expect(fs.readFileSync(appImagePath, "utf-8")).toBe("old");
+	});
+
+	it("refuses to check without a signing key", async () => {
+		const updater = createUpdater({
+			userDataPath: createTempDir(),
+			currentVersion: "1.0.0",
+			platform: "darwin",
+			arch: "arm64",
+			publicKey: null,
+			appImagePath: null,
+			openPath: vi.fn(async () => ""),
+			quit: vi.fn(),
+			relaunch: vi.fn(),
+		});
+
+		expect(updater.getStatus().configured).toBe(false);
+		await expect(updater.check()).rejects.toThrow("not configured");
+	});
+});
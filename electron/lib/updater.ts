@@ -0,0 +1,771 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+import { type Translate, translateEnglish } from "./i18n.js";
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/**
+ * Which releases are offered: stable ones, or prereleases as well.
+ */
+export type UpdateChannel = "stable" | "beta";
+
+/**
+ * Persisted updater settings.
+ */
+export type UpdaterSettings = {
+	channel: UpdateChannel;
+	/** Check at startup and every few hours */
+	autoCheck: boolean;
+};
+
+/**
+ * A release newer than the running version, with this platform's
+ * installer and its detached signature.
+ */
+export type UpdateRelease = {
+	version: string;
+	notes: string;
+	publishedAt: string | null;
+	assetName: string;
+	assetUrl: string;
+	signatureUrl: string;
+	/** Installer size in bytes, as listed by the release */
+	size: number;
+};
+
+export type UpdaterState =
+	| "idle"
+	| "checking"
+	| "up-to-date"
+	| "available"
+	| "downloading"
+	| "ready"
+	| "installing"
+	| "error";
+
+/**
+ * Updater settings plus what the updater is doing.
+ */
+export type UpdaterStatus = UpdaterSettings & {
+	/** False in builds without an update signing key, which never update */
+	configured: boolean;
+	currentVersion: string;
+	state: UpdaterState;
+	/** Release found by the last check; null if none */
+	available: Pick<UpdateRelease, "version" | "notes" | "publishedAt"> | null;
+	/** Bytes downloaded so far; null outside a download */
+	progress: { received: number; total: number } | null;
+	lastCheckedAt: string | null;
+	/** Why the last check, download or install failed; null after a success */
+	lastError: string | null;
+};
+
+/**
+ * Tray entry for the updater's next step.
+ */
+export type UpdateMenuItem = {
+	label: string;
+	enabled: boolean;
+	action: "check" | "download" | "install" | null;
+};
+
+const UPDATE_CHANNELS: readonly UpdateChannel[] = ["stable", "beta"];
+
+const DEFAULT_UPDATER_SETTINGS: UpdaterSettings = {
+	channel: "stable",
+	autoCheck: true,
+};
+
+const UPDATER_FILENAME = "updater.json";
+
+/**
+ * Folder under userData holding the downloaded installer.
+ */
+const DOWNLOAD_DIRECTORY = "updates";
+
+/**
+ * Releases of the app, newest first.
+ */
+const RELEASES_URL =
+	"https://api.github.com/repos/erezsob/clipboard-manager/releases";
+
+/**
+ * Extension of a detached Ed25519 signature published next to each
+ * installer: the installer's bytes signed with the release key, base64.
+ */
+const SIGNATURE_EXTENSION = ".sig";
+
+/**
+ * First automatic check after startup (ms), once the app has settled.
+ */
+const STARTUP_CHECK_DELAY_MS = 30_000;
+
+const CHECK_INTERVAL_MS = 6 * 60 * 60 * 1000;
+
+/**
+ * Upper bound on fetching the release list or a signature (ms).
+ */
+const REQUEST_TIMEOUT_MS = 15_000;
+
+/**
+ * Installer kinds electron-builder produces, by platform.
+ */
+const INSTALLER_EXTENSIONS: Partial<Record<NodeJS.Platform, string>> = {
+	darwin: ".dmg",
+	win32: ".exe",
+	linux: ".AppImage",
+};
+
+/**
+ * How installer names spell each architecture. electron-builder leaves
+ * the x64 build's name untagged on macOS and Windows.
+ */
+const ARCH_TOKENS: Record<string, readonly string[]> = {
+	x64: ["x64", "x86_64", "amd64"],
+	arm64: ["arm64", "aarch64"],
+	ia32: ["ia32", "i386"],
+};
+
+const VERSION_PATTERN =
+	/^v?(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?(?:\+[0-9A-Za-z.-]+)?$/;
+
+type ParsedVersion = {
+	core: [number, number, number];
+	prerelease: string[];
+};
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const parseVersion = (version: string): ParsedVersion | null => {
+	const match = VERSION_PATTERN.exec(version.trim());
+	if (!match) return null;
+	return {
+		core: [Number(match[1]), Number(match[2]), Number(match[3])],
+		prerelease: match[4] ? match[4].split(".") : [],
+	};
+};
+
+const compareIdentifiers = (a: string, b: string): number => {
+	const numericA = /^\d+$/.test(a);
+	const numericB = /^\d+$/.test(b);
+	if (numericA && numericB) return Number(a) - Number(b);
+	if (numericA !== numericB) return numericA ? -1 : 1;
+	return a < b ? -1 : a > b ? 1 : 0;
+};
+
+/**
+ * Orders two versions by semantic versioning precedence; a leading `v`
+ * is allowed. Negative if `a` is older, positive if newer.
+ * Pure function.
+ *
+ * @throws if either is not a semantic version
+ */
+export const compareVersions = (a: string, b: string): number => {
+	const left = parseVersion(a);
+	const right = parseVersion(b);
+	if (!left || !right) {
+		throw new Error(`Invalid version: ${left ? b : a}`);
+	}
+	for (let i = 0; i < 3; i++) {
+		const difference = left.core[i] - right.core[i];
+		if (difference !== 0) return difference;
+	}
+	// A prerelease comes before its release
+	if (left.prerelease.length === 0 || right.prerelease.length === 0) {
+		return right.prerelease.length - left.prerelease.length;
+	}
+	const length = Math.min(left.prerelease.length, right.prerelease.length);
+	for (let i = 0; i < length; i++) {
+		const difference = compareIdentifiers(
+			left.prerelease[i],
+			right.prerelease[i],
+		);
+		if (difference !== 0) return difference;
+	}
+	return left.prerelease.length - right.prerelease.length;
+};
+
+/**
+ * Whether a file name is a plain name, safe to join to a folder.
+ */
+const isPlainFileName = (name: string): boolean =>
+	name !== "" && !name.startsWith(".") && path.basename(name) === name;
+
+/**
+ * Picks this platform's installer from a release's file names.
+ * Pure function. Returns null if the release has none.
+ */
+export const selectUpdateAsset = (
+	names: readonly string[],
+	platform: NodeJS.Platform,
+	arch: string,
+): string | null => {
+	const extension = INSTALLER_EXTENSIONS[platform];
+	if (!extension) return null;
+	const installers = names.filter(
+		(name) => isPlainFileName(name) && name.endsWith(extension),
+	);
+	const tokensOf = (name: string) =>
+		name.slice(0, -extension.length).toLowerCase().split(/[-_.\s]/);
+
+	const tagged = installers.find((name) =>
+		tokensOf(name).some((token) => ARCH_TOKENS[arch]?.includes(token)),
+	);
+	if (tagged || arch !== "x64") return tagged ?? null;
+	const allTokens = Object.values(ARCH_TOKENS).flat();
+	return (
+		installers.find(
+			(name) => !tokensOf(name).some((token) => allTokens.includes(token)),
+		) ?? null
+	);
+};
+
+/**
+ * Finds the newest release on a channel that is newer than the running
+ * version and ships a signed installer for this platform.
+ * Pure function. Drafts are skipped, as are prereleases on the stable
+ * channel; the beta channel offers whichever is newest.
+ *
+ * @throws if the input is not a list of releases
+ */
+export const pickUpdateRelease = (
+	input: unknown,
+	options: {
+		channel: UpdateChannel;
+		currentVersion: string;
+		platform: NodeJS.Platform;
+		arch: string;
+	},
+): UpdateRelease | null => {
+	if (!Array.isArray(input)) {
+		throw new Error("Invalid release list: expected an array");
+	}
+
+	let newest: UpdateRelease | null = null;
+	for (const entry of input) {
+		if (typeof entry !== "object" || entry === null) continue;
+		const release = entry as Record<string, unknown>;
+		const version = release.tag_name;
+		if (typeof version !== "string" || !parseVersion(version)) continue;
+		const prerelease =
+			release.prerelease === true ||
+			(parseVersion(version)?.prerelease.length ?? 0) > 0;
+		if (release.draft === true) continue;
+		if (prerelease && options.channel === "stable") continue;
+		if (compareVersions(version, options.currentVersion) <= 0) continue;
+		if (newest && compareVersions(version, newest.version) <= 0) continue;
+
+		const assets = new Map<string, { url: string; size: number }>();
+		for (const asset of Array.isArray(release.assets) ? release.assets : []) {
+			const { name, browser_download_url: url, size } = (asset ?? {}) as {
+				name?: unknown;
+				browser_download_url?: unknown;
+				size?: unknown;
+			};
+			if (typeof name !== "string" || typeof url !== "string") continue;
+			assets.set(name, { url, size: typeof size === "number" ? size : 0 });
+		}
+		const assetName = selectUpdateAsset(
+			[...assets.keys()],
+			options.platform,
+			options.arch,
+		);
+		const installer = assetName ? assets.get(assetName) : undefined;
+		const signature = assetName
+			? assets.get(`${assetName}${SIGNATURE_EXTENSION}`)
+			: undefined;
+		if (!assetName || !installer || !signature) continue;
+
+		newest = {
+			version: version.replace(/^v/, ""),
+			notes: typeof release.body === "string" ? release.body : "",
+			publishedAt:
+				typeof release.published_at === "string"
+					? release.published_at
+					: null,
+			assetName,
+			assetUrl: installer.url,
+			signatureUrl: signature.url,
+			size: installer.size,
+		};
+	}
+	return newest;
+};
+
+/**
+ * Checks a downloaded installer against its detached Ed25519 signature.
+ * Pure function. A malformed signature or key fails verification.
+ */
+export const verifyUpdateSignature = (
+	data: Buffer,
+	signature: string,
+	publicKey: string,
+): boolean => {
+	try {
+		return crypto.verify(
+			null,
+			data,
+			publicKey,
+			Buffer.from(signature.trim(), "base64"),
+		);
+	} catch {
+		return false;
+	}
+};
+
+/**
+ * Labels the tray entry for the updater's next step.
+ * Pure function. Returns null when updates are not configured.
+ */
+export const describeUpdateMenuItem = (
+	status: UpdaterStatus,
+	t: Translate = translateEnglish,
+): UpdateMenuItem | null => {
+	if (!status.configured) return null;
+	const version = status.available?.version;
+	switch (status.state) {
+		case "checking":
+			return {
+				label: t("update.checking"),
+				enabled: false,
+				action: null,
+			};
+		case "available":
+			return {
+				label: t("update.download", { version: version ?? "" }),
+				enabled: true,
+				action: "download",
+			};
+		case "downloading": {
+			const { received = 0, total = 0 } = status.progress ?? {};
+			const percent = total > 0 ? Math.floor((received / total) * 100) : 0;
+			return {
+				label: t("update.downloading", { percent }),
+				enabled: false,
+				action: null,
+			};
+		}
+		case "ready":
+			return {
+				label: t("update.install", { version: version ?? "" }),
+				enabled: true,
+				action: "install",
+			};
+		case "installing":
+			return {
+				label: t("update.installing"),
+				enabled: false,
+				action: null,
+			};
+		default:
+			return {
+				label: t("update.check"),
+				enabled: true,
+				action: "check",
+			};
+	}
+};
+
+/**
+ * Validates an updater settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `channel` is not a known channel or `autoCheck` not a
+ *   boolean
+ */
+export const parseUpdaterSettings = (
+	input: unknown,
+	current: UpdaterSettings = DEFAULT_UPDATER_SETTINGS,
+): UpdaterSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid updater settings: expected an object");
+	}
+
+	const { channel, autoCheck } = input as Partial<
+		Record<keyof UpdaterSettings, unknown>
+	>;
+	const next: UpdaterSettings = { ...current };
+
+	if (channel !== undefined) {
+		if (!UPDATE_CHANNELS.includes(channel as UpdateChannel)) {
+			throw new Error(
+				`Invalid updater settings: channel must be one of ${UPDATE_CHANNELS.join(", ")}`,
+			);
+		}
+		next.channel = channel as UpdateChannel;
+	}
+	if (autoCheck !== undefined) {
+		if (typeof autoCheck !== "boolean") {
+			throw new Error("Invalid updater settings: autoCheck must be a boolean");
+		}
+		next.autoCheck = autoCheck;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+const getUpdaterSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, UPDATER_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readUpdaterSettingsFromFile = (filePath: string): UpdaterSettings => {
+	if (!fs.existsSync(filePath)) return { ...DEFAULT_UPDATER_SETTINGS };
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseUpdaterSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read updater settings, using defaults:", error);
+		return { ...DEFAULT_UPDATER_SETTINGS };
+	}
+};
+
+const writeUpdaterSettingsToFile = (
+	filePath: string,
+	settings: UpdaterSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Updater Module
+// ============================================================================
+
+export type UpdaterDeps = {
+	userDataPath: string;
+	currentVersion: string;
+	platform: NodeJS.Platform;
+	arch: string;
+	/** PEM Ed25519 key releases are signed with; null disables updates */
+	publicKey: string | null;
+	/** Running AppImage, replaced in place; null elsewhere */
+	appImagePath: string | null;
+	/** Opens a downloaded installer; resolves to an error message or "" */
+	openPath: (filePath: string) => Promise<string>;
+	/** Quits so an opened installer can replace the app */
+	quit: () => void;
+	/** Restarts into the replaced AppImage */
+	relaunch: () => void;
+	/** Called whenever the status changes, for progress events */
+	onChange?: (status: UpdaterStatus) => void;
+	releasesUrl?: string;
+	/** Sends requests; injectable for tests */
+	fetch?: typeof fetch;
+};
+
+/**
+ * Creates the updater. A check reads the app's GitHub releases for the
+ * chosen channel; the installer is downloaded into userData and only
+ * kept once its Ed25519 signature matches the key built into the app.
+ * Installing swaps a running AppImage in place and relaunches; on macOS
+ * and Windows the installer is opened and the app quits so it can be
+ * replaced. Nothing is downloaded without being asked.
+ */
+export const createUpdater = (deps: UpdaterDeps) => {
+	const filePath = getUpdaterSettingsPath(deps.userDataPath);
+	const downloadPath = path.join(deps.userDataPath, DOWNLOAD_DIRECTORY);
+	const send = deps.fetch ?? fetch;
+	let settings = readUpdaterSettingsFromFile(filePath);
+	let state: UpdaterState = "idle";
+	let release: UpdateRelease | null = null;
+	/** Verified installer of `release`, once downloaded */
+	let downloaded: string | null = null;
+	let progress: UpdaterStatus["progress"] = null;
+	let lastCheckedAt: string | null = null;
+	let lastError: string | null = null;
+	let startupTimer: NodeJS.Timeout | null = null;
+	let intervalTimer: NodeJS.Timeout | null = null;
+
+	const getStatus = (): UpdaterStatus => ({
+		...settings,
+		configured: deps.publicKey !== null,
+		currentVersion: deps.currentVersion,
+		state,
+		available: release && {
+			version: release.version,
+			notes: release.notes,
+			publishedAt: release.publishedAt,
+		},
+		progress: progress && { ...progress },
+		lastCheckedAt,
+		lastError,
+	});
+
+	const notify = (): void => {
+		deps.onChange?.(getStatus());
+	};
+
+	const fail = (error: unknown): UpdaterStatus => {
+		state = "error";
+		progress = null;
+		lastError = error instanceof Error ? error.message : String(error);
+		notify();
+		return getStatus();
+	};
+
+	const requireConfigured = (): string => {
+		if (deps.publicKey === null) {
+			throw new Error("Updates are not configured for this build");
+		}
+		return deps.publicKey;
+	};
+
+	/**
+	 * Deletes any downloaded installer.
+	 */
+	const clearDownload = (): void => {
+		downloaded = null;
+		fs.rmSync(downloadPath, { recursive: true, force: true });
+	};
+
+	const get = async (url: string, accept: string): Promise<Response> => {
+		const response = await send(url, {
+			headers: { accept, "user-agent": "clipboard-manager-updater" },
+			redirect: "follow",
+			signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+		});
+		if (!response.ok) throw new Error(`HTTP ${response.status} from ${url}`);
+		return response;
+	};
+
+	const isBusy = (): boolean =>
+		state === "checking" || state === "downloading" || state === "installing";
+
+	/**
+	 * Looks for a newer release on the current channel. A release already
+	 * downloaded stays ready unless a newer one turns up.
+	 */
+	const check = async (): Promise<UpdaterStatus> => {
+		requireConfigured();
+		if (isBusy()) return getStatus();
+		state = "checking";
+		notify();
+		try {
+			const response = await get(
+				deps.releasesUrl ?? RELEASES_URL,
+				"application/vnd.github+json",
+			);
+			const found = pickUpdateRelease(await response.json(), {
+				channel: settings.channel,
+				currentVersion: deps.currentVersion,
+				platform: deps.platform,
+				arch: deps.arch,
+			});
+			lastCheckedAt = new Date().toISOString();
+			lastError = null;
+			if (downloaded && found?.version === release?.version) {
+				state = "ready";
+			} else {
+				if (downloaded) clearDownload();
+				release = found;
+				state = found ? "available" : "up-to-date";
+			}
+			notify();
+			return getStatus();
+		} catch (error) {
+			console.error("Failed to check for updates:", error);
+			return fail(error);
+		}
+	};
+
+	/**
+	 * Streams the installer to a partial file, reporting each whole
+	 * percent of progress.
+	 */
+	const downloadTo = async (
+		target: UpdateRelease,
+		partialPath: string,
+	): Promise<void> => {
+		const response = await send(target.assetUrl, {
+			headers: { "user-agent": "clipboard-manager-updater" },
+			redirect: "follow",
+		});
+		if (!response.ok) {
+			throw new Error(`HTTP ${response.status} from ${target.assetUrl}`);
+		}
+		const total =
+			target.size || Number(response.headers.get("content-length")) || 0;
+		const current = { received: 0, total };
+		progress = current;
+		notify();
+
+		const file = fs.openSync(partialPath, "w");
+		try {
+			const reader = response.body?.getReader();
+			let reportedPercent = 0;
+			while (reader) {
+				const { done, value } = await reader.read();
+				if (done) break;
+				fs.writeSync(file, value);
+				current.received += value.byteLength;
+				const percent =
+					total > 0 ? Math.floor((current.received / total) * 100) : 0;
+				if (percent > reportedPercent) {
+					reportedPercent = percent;
+					notify();
+				}
+			}
+		} finally {
+			fs.closeSync(file);
+		}
+		if (target.size > 0 && current.received !== target.size) {
+			throw new Error(
+				`Downloaded ${current.received} bytes of ${target.assetName}, expected ${target.size}`,
+			);
+		}
+	};
+
+	/**
+	 * Downloads and verifies the release found by the last check. A
+	 * download whose signature does not match is deleted.
+	 */
+	const download = async (): Promise<UpdaterStatus> => {
+		const publicKey = requireConfigured();
+		if (isBusy()) return getStatus();
+		const target = release;
+		if (!target) throw new Error("No update available");
+		if (downloaded) return getStatus();
+
+		state = "downloading";
+		lastError = null;
+		notify();
+		try {
+			const signature = await (
+				await get(target.signatureUrl, "application/octet-stream")
+			).text();
+			clearDownload();
+			fs.mkdirSync(downloadPath, { recursive: true });
+			const installerPath = path.join(downloadPath, target.assetName);
+			const partialPath = `${installerPath}.partial`;
+			await downloadTo(target, partialPath);
+
+			if (
+				!verifyUpdateSignature(
+					fs.readFileSync(partialPath),
+					signature,
+					publicKey,
+				)
+			) {
+				throw new Error(
+					`The signature of ${target.assetName} does not match; it was deleted`,
+				);
+			}
+			fs.renameSync(partialPath, installerPath);
+			downloaded = installerPath;
+			state = "ready";
+			progress = null;
+			notify();
+			return getStatus();
+		} catch (error) {
+			console.error("Failed to download update:", error);
+			clearDownload();
+			state = "available";
+			progress = null;
+			lastError = error instanceof Error ? error.message : String(error);
+			notify();
+			return getStatus();
+		}
+	};
+
+	/**
+	 * Installs the downloaded release. The app quits or relaunches on
+	 * success.
+	 *
+	 * @throws if no verified download is ready
+	 */
+	const install = async (): Promise<UpdaterStatus> => {
+		requireConfigured();
+		const installerPath = downloaded;
+		if (state !== "ready" || !installerPath) {
+			throw new Error("No downloaded update to install");
+		}
+		state = "installing";
+		notify();
+		try {
+			if (deps.appImagePath && deps.platform === "linux") {
+				// Written beside the AppImage first, so the rename is atomic
+				const staged = `${deps.appImagePath}.update`;
+				fs.copyFileSync(installerPath, staged);
+				fs.chmodSync(staged, 0o755);
+				fs.renameSync(staged, deps.appImagePath);
+				clearDownload();
+				deps.relaunch();
+			} else {
+				const error = await deps.openPath(installerPath);
+				if (error) throw new Error(error);
+				deps.quit();
+			}
+			return getStatus();
+		} catch (error) {
+			console.error("Failed to install update:", error);
+			state = "ready";
+			lastError = error instanceof Error ? error.message : String(error);
+			notify();
+			return getStatus();
+		}
+	};
+
+	const runScheduledCheck = (): void => {
+		check().catch((error) => {
+			console.error("Failed to check for updates:", error);
+		});
+	};
+
+	const stop = (): void => {
+		if (startupTimer) clearTimeout(startupTimer);
+		if (intervalTimer) clearInterval(intervalTimer);
+		startupTimer = null;
+		intervalTimer = null;
+	};
+
+	/**
+	 * Starts automatic checks if they are on and updates are configured,
+	 * and deletes installers left from an earlier run.
+	 */
+	const start = (): void => {
+		stop();
+		if (!downloaded) clearDownload();
+		if (!settings.autoCheck || deps.publicKey === null) return;
+		startupTimer = setTimeout(runScheduledCheck, STARTUP_CHECK_DELAY_MS);
+		intervalTimer = setInterval(runScheduledCheck, CHECK_INTERVAL_MS);
+	};
+
+	/**
+	 * Saves settings. Changing channel forgets the release found on the
+	 * old one.
+	 */
+	const updateSettings = (input: unknown): UpdaterStatus => {
+		const next = parseUpdaterSettings(input, settings);
+		writeUpdaterSettingsToFile(filePath, next);
+		const channelChanged = next.channel !== settings.channel;
+		const autoCheckChanged = next.autoCheck !== settings.autoCheck;
+		settings = next;
+		if (channelChanged && !isBusy()) {
+			clearDownload();
+			release = null;
+			state = "idle";
+		}
+		if (autoCheckChanged) {
+			if (next.autoCheck) start();
+			else stop();
+		}
+		notify();
+		return getStatus();
+	};
+
+	return { getStatus, check, download, install, updateSettings, start, stop };
+};
+
+export type Updater = ReturnType<typeof createUpdater>;
@@ -0,0 +1,198 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	assertCallbacksAllowed,
+	buildCallbackUrl,
+	changesClipboard,
+	createUrlActionSettings,
+	findUrlArgs,
+	getUrlActionPrompt,
+	getUrlActionsPath,
+	parseUrlAction,
+	parseUrlActionSettings,
+	sendsContent,
+} from "./url-actions.js";
+
+describe("parseUrlAction", () => {
+	it("parses x-callback-url and short forms", () => {
+		expect(
+			parseUrlAction(
+				"clipboard-manager://x-callback-url/latest?x-success=shortcuts%3A%2F%2Fok",
+			),
+		).toEqual({ action: "latest", success: "shortcuts://ok", error: null });
+		expect(parseUrlAction("clipboard-manager://copy?id=4")).toEqual({
+			action: "copy",
+			id: 4,
+			success: null,
+			error: null,
+		});
+	});
+
+	it("parses clipman:// deep links with path arguments", () => {
+		expect(parseUrlAction("clipman://paste/12")).toEqual({
+			action: "paste",
+			id: 12,
+			success: null,
+			error: null,
+		});
+		expect(parseUrlAction("clipman://toggle-capture")).toMatchObject({
+			action: "toggle-capture",
+		});
+		expect(
+			parseUrlAction("clipboard-manager://x-callback-url/copy/3"),
+		).toMatchObject({ action: "copy", id: 3 });
+	});
+
+	it("reads search parameters", () => {
+		expect(
+			parseUrlAction("clipboard-manager://search?q=hello%20world&limit=5"),
+		).toMatchObject({ action: "search", query: "hello world", limit: 5 });
+		expect(parseUrlAction("clipboard-manager://search")).toMatchObject({
+			query: "",
+			limit: 20,
+		});
+	});
+
+	it("rejects invalid URLs", () => {
+		expect(() => parseUrlAction("https://example.com/latest")).toThrow(
+			"Not a clipboard-manager or clipman URL",
+		);
+		expect(() => parseUrlAction("clipboard-manager://delete")).toThrow(
+			"Unknown action: delete",
+		);
+		expect(() => parseUrlAction("clipboard-manager://copy?id=x")).toThrow(
+			"Invalid id",
+		);
+		expect(() =>
+			parseUrlAction("clipboard-manager://search?limit=1000"),
+		).toThrow("Invalid limit");
+	});
+});
+
+describe("findUrlArgs", () => {
+	it("picks automation URLs out of command-line arguments", () => {
+		expect(
+			findUrlArgs([
+				"/opt/Clipboard Manager/clipboard-manager",
+				"--no-sandbox",
+				"clipman://search?q=x",
+				"Clipboard-Manager://latest",
+			]),
+		).toEqual(["clipman://search?q=x", "Clipboard-Manager://latest"]);
+	});
+});
+
+describe("buildCallbackUrl", () => {
+	it("appends percent-encoded parameters", () => {
+		expect(buildCallbackUrl("shortcuts://callback", { result: "a b" })).toBe(
+			"shortcuts://callback?result=a%20b",
+		);
+		expect(buildCallbackUrl("app://x?id=1", { result: "&" })).toBe(
+			"app://x?id=1&result=%26",
+		);
+	});
+});
+
+describe("parseUrlActionSettings", () => {
+	it("sends no content and only calls back to Shortcuts by default", () => {
+		expect(parseUrlActionSettings({})).toEqual({
+			sendContent: false,
+			allowClipboardActions: false,
+			callbackSchemes: ["shortcuts"],
+		});
+		expect(
+			parseUrlActionSettings({ callbackSchemes: ["Raycast:", "raycast"] }),
+		).toMatchObject({ callbackSchemes: ["raycast"] });
+	});
+
+	it("rejects web, file and own schemes", () => {
+		for (const scheme of ["https", "http", "file", "javascript", "clipman"]) {
+			expect(() =>
+				parseUrlActionSettings({ callbackSchemes: [scheme] }),
+			).toThrow(`${scheme} callbacks are not allowed`);
+		}
+		expect(() =>
+			parseUrlActionSettings({ callbackSchemes: ["not a scheme"] }),
+		).toThrow("not a URL scheme");
+		expect(() => parseUrlActionSettings({ sendContent: "yes" })).toThrow(
+			"sendContent must be a boolean",
+		);
+		expect(() =>
+			parseUrlActionSettings({ allowClipboardActions: 1 }),
+		).toThrow("allowClipboardActions must be a boolean");
+	});
+});
+
+describe("assertCallbacksAllowed", () => {
+	it("refuses callbacks outside the allowed schemes", () => {
+		const allowed = parseUrlAction(
+			"clipman://latest?x-success=shortcuts%3A%2F%2Fok&x-error=shortcuts%3A%2F%2Ffail",
+		);
+		expect(() => assertCallbacksAllowed(allowed, ["shortcuts"])).not.toThrow();
+
+		const web = parseUrlAction(
+			"clipman://latest?x-success=https%3A%2F%2Fattacker.example%2F",
+		);
+		expect(() => assertCallbacksAllowed(web, ["shortcuts", "https"])).toThrow(
+			"Callback scheme not allowed",
+		);
+		const other = parseUrlAction("clipman://copy/1?x-error=raycast%3A%2F%2Fx");
+		expect(() => assertCallbacksAllowed(other, ["shortcuts"])).toThrow(
+			"Callback scheme not allowed",
+		);
+	});
+});
+
+describe("getUrlActionPrompt", () => {
+	const prompt = (url: string) => getUrlActionPrompt(parseUrlAction(url));
+
+	it("asks before actions that read or paste history", () => {
+		expect(prompt("clipman://latest")).toContain("latest copy");
+		expect(prompt("clipman://paste/12")).toContain("item 12");
+		expect(prompt("clipman://search?q=x")).toBeNull();
+		expect(prompt("clipman://toggle-capture")).toBeNull();
+	});
+});
+
+describe("changesClipboard", () => {
+	it("is true for copy and paste", () => {
+		expect(changesClipboard(parseUrlAction("clipman://copy/1"))).toBe(true);
+		expect(changesClipboard(parseUrlAction("clipman://paste/1"))).toBe(true);
+		expect(changesClipboard(parseUrlAction("clipman://latest"))).toBe(false);
+	});
+});
+
+describe("sendsContent", () => {
+	const sends = (url: string) => sendsContent(parseUrlAction(url));
+
+	it("is true for actions that send item text to a callback", () => {
+		expect(sends("clipman://latest")).toBe(true);
+		expect(sends("clipman://search?x-success=shortcuts%3A")).toBe(true);
+		expect(sends("clipman://search?q=x")).toBe(false);
+		expect(sends("clipman://copy/1")).toBe(false);
+	});
+});
+
+describe("createUrlActionSettings", () => {
+	let userDataPath: string;
+
+	afterEach(() => {
+		if (userDataPath) fs.rmSync(userDataPath, { recursive: true, force: true });
+	});
+
+	it("saves settings across restarts", () => {
+		userDataPath = fs.mkdtempSync(path.join(os.tmpdir(), "url-actions-"));
+		createUrlActionSettings({ userDataPath }).updateSettings({
+			sendContent: true,
+		});
+
+		expect(createUrlActionSettings({ userDataPath }).getSettings()).toEqual({
+			sendContent: true,
+			allowClipboardActions: false,
+			callbackSchemes: ["shortcuts"],
+		});
+		expect(fs.existsSync(getUrlActionsPath(userDataPath))).toBe(true);
+	});
+});
@@ -0,0 +1,372 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * URL schemes registered for automations and deep links, e.g. from
+ * Shortcuts.app, AppleScript's `open location`, or a launcher. `clipman`
+ * is the short alias.
+ */
+export const URL_SCHEMES = ["clipboard-manager", "clipman"] as const;
+
+const DEFAULT_SEARCH_LIMIT = 20;
+const MAX_SEARCH_LIMIT = 200;
+
+/**
+ * Schemes a callback may never use: a web page or script would receive
+ * the result, and our own schemes would start another action.
+ */
+const BLOCKED_CALLBACK_SCHEMES: readonly string[] = [
+	"http",
+	"https",
+	"file",
+	"ftp",
+	"javascript",
+	"data",
+	"blob",
+	"about",
+	...URL_SCHEMES,
+];
+
+const SCHEME_PATTERN = /^[a-z][a-z0-9+.-]*$/;
+
+/**
+ * An automation request, with the x-callback-url callbacks it carries.
+ */
+export type UrlAction = (
+	| { action: "latest" }
+	| { action: "search"; query: string; limit: number }
+	| { action: "copy"; id: number }
+	| { action: "paste"; id: number }
+	| { action: "toggle-capture" }
+) & {
+	/** Opened with `result=<value>` on success */
+	success: string | null;
+	/** Opened with `errorCode` and `errorMessage` on failure */
+	error: string | null;
+};
+
+/**
+ * Persisted automation URL settings.
+ */
+export type UrlActionSettings = {
+	/**
+	 * Off by default: `latest` and `search` send item text to their
+	 * `x-success` callback only while this is on
+	 */
+	sendContent: boolean;
+	/** Off by default: `copy` and `paste` are refused unless this is on */
+	allowClipboardActions: boolean;
+	/** Schemes `x-success` and `x-error` may open, e.g. `shortcuts` */
+	callbackSchemes: string[];
+};
+
+const DEFAULT_URL_ACTION_SETTINGS: UrlActionSettings = {
+	sendContent: false,
+	allowClipboardActions: false,
+	callbackSchemes: ["shortcuts"],
+};
+
+const URL_ACTIONS_FILENAME = "url-actions.json";
+
+const invalid = (message: string) =>
+	new Error(`Invalid automation URL settings: ${message}`);
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Reads an item id given as a path segment (`paste/12`) or `?id=12`.
+ */
+const parseIdParam = (segment: string | undefined, params: URLSearchParams) => {
+	const id = Number(segment ?? params.get("id"));
+	if (!Number.isInteger(id) || id <= 0) {
+		throw new Error("Invalid id: must be a positive integer");
+	}
+	return id;
+};
+
+/**
+ * Parses `clipboard-manager://x-callback-url/<action>?...` or the short
+ * form `clipboard-manager://<action>?...` (or `clipman://`):
+ *
+ * - `latest`: text of the most recently copied item
+ * - `search?q=&limit=`: matching items as JSON, or with no `x-success`,
+ *   opens the history window searching for `q`
+ * - `copy/<id>` or `copy?id=`: places an item on the clipboard
+ * - `paste/<id>`: places an item on the clipboard and pastes it
+ * - `toggle-capture`: pauses or resumes capture
+ *
+ * Pure function.
+ *
+ * @throws if the URL is not a valid action
+ */
+export const parseUrlAction = (url: string): UrlAction => {
+	let parsed: URL;
+	try {
+		parsed = new URL(url);
+	} catch {
+		throw new Error(`Invalid URL: ${url}`);
+	}
+	const scheme = parsed.protocol.replace(/:$/, "");
+	if (!(URL_SCHEMES as readonly string[]).includes(scheme)) {
+		throw new Error(`Not a ${URL_SCHEMES.join(" or ")} URL: ${url}`);
+	}
+
+	const path = parsed.pathname.split("/").filter(Boolean);
+	const [name, segment] =
+		parsed.hostname === "x-callback-url" ? path : [parsed.hostname, ...path];
+	const params = parsed.searchParams;
+	const callbacks = {
+		success: params.get("x-success"),
+		error: params.get("x-error"),
+	};
+
+	switch (name) {
+		case "latest":
+			return { action: "latest", ...callbacks };
+		case "search": {
+			const rawLimit = params.get("limit");
+			const limit = rawLimit === null ? DEFAULT_SEARCH_LIMIT : Number(rawLimit);
+			if (!Number.isInteger(limit) || limit < 1 || limit > MAX_SEARCH_LIMIT) {
+				throw new Error(
+					`Invalid limit: must be an integer from 1 to ${MAX_SEARCH_LIMIT}`,
+				);
+			}
+			return {
+				action: "search",
+				query: params.get("q") ?? "",
+				limit,
+				...callbacks,
+			};
+		}
+		case "copy":
+		case "paste":
+			return { action: name, id: parseIdParam(segment, params), ...callbacks };
+		case "toggle-capture":
+			return { action: name, ...callbacks };
+		default:
+			throw new Error(`Unknown action: ${name || "(none)"}`);
+	}
+};
+
+/**
+ * Validates an automation URL settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `sendContent` or `allowClipboardActions` is not a boolean, or
+ * a callback scheme is malformed or one that is never allowed (web, file
+ * or our own)
+ */
+export const parseUrlActionSettings = (
+	input: unknown,
+	current: UrlActionSettings = DEFAULT_URL_ACTION_SETTINGS,
+): UrlActionSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const { sendContent, allowClipboardActions, callbackSchemes } =
+		input as Partial<Record<keyof UrlActionSettings, unknown>>;
+	const next: UrlActionSettings = {
+		...current,
+		callbackSchemes: [...current.callbackSchemes],
+	};
+
+	if (sendContent !== undefined) {
+		if (typeof sendContent !== "boolean") {
+			throw invalid("sendContent must be a boolean");
+		}
+		next.sendContent = sendContent;
+	}
+
+	if (allowClipboardActions !== undefined) {
+		if (typeof allowClipboardActions !== "boolean") {
+			throw invalid("allowClipboardActions must be a boolean");
+		}
+		next.allowClipboardActions = allowClipboardActions;
+	}
+
+	if (callbackSchemes !== undefined) {
+		if (!Array.isArray(callbackSchemes)) {
+			throw invalid("callbackSchemes must be an array");
+		}
+		const schemes: string[] = [];
+		for (const value of callbackSchemes) {
+			if (typeof value !== "string") {
+				throw invalid("callbackSchemes must be strings");
+			}
+			const scheme = value.trim().toLowerCase().replace(/:$/, "");
+			if (!SCHEME_PATTERN.test(scheme)) {
+				throw invalid(`not a URL scheme: ${value}`);
+			}
+			if (BLOCKED_CALLBACK_SCHEMES.includes(scheme)) {
+				throw invalid(`${scheme} callbacks are not allowed`);
+			}
+			if (!schemes.includes(scheme)) schemes.push(scheme);
+		}
+		next.callbackSchemes = schemes;
+	}
+
+	return next;
+};
+
+/**
+ * Checks that the action's callbacks use an allowed scheme, so a link on
+ * a web page cannot have results sent back to that page.
+ * Pure function.
+ *
+ * @throws naming the first callback that is not allowed
+ */
+export const assertCallbacksAllowed = (
+	action: UrlAction,
+	callbackSchemes: readonly string[],
+): void => {
+	for (const callback of [action.success, action.error]) {
+		if (callback === null) continue;
+		const scheme = callback.split(":", 1)[0].toLowerCase();
+		if (
+			!callback.includes(":") ||
+			BLOCKED_CALLBACK_SCHEMES.includes(scheme) ||
+			!callbackSchemes.includes(scheme)
+		) {
+			throw new Error(`Callback scheme not allowed: ${callback}`);
+		}
+	}
+};
+
+/**
+ * Whether an action sends item text to its callback.
+ * Pure function.
+ */
+export const sendsContent = (action: UrlAction): boolean =>
+	action.action === "latest" ||
+	(action.action === "search" && action.success !== null);
+
+/**
+ * Whether an action replaces the clipboard (and for `paste`, types it
+ * into the active app).
+ * Pure function.
+ */
+export const changesClipboard = (action: UrlAction): boolean =>
+	action.action === "copy" || action.action === "paste";
+
+/**
+ * The question to ask before running an action, or null for actions that
+ * reveal and change nothing (pausing capture, opening the window on a
+ * search).
+ * Pure function.
+ */
+export const getUrlActionPrompt = (action: UrlAction): string | null => {
+	switch (action.action) {
+		case "latest":
+			return "Send the text of your latest copy to another app?";
+		case "search":
+			return action.success === null
+				? null
+				: `Send clipboard items matching "${action.query}" to another app?`;
+		case "copy":
+			return `Put clipboard item ${action.id} on the clipboard?`;
+		case "paste":
+			return `Paste clipboard item ${action.id} into the active app?`;
+		case "toggle-capture":
+			return null;
+	}
+};
+
+/**
+ * Picks automation URLs out of command-line arguments; Windows and Linux
+ * pass a clicked link to the app this way instead of `open-url`.
+ * Pure function.
+ */
+export const findUrlArgs = (argv: string[]): string[] =>
+	argv.filter((arg) =>
+		URL_SCHEMES.some((scheme) => arg.toLowerCase().startsWith(`${scheme}:`)),
+	);
+
+/**
+ * Appends parameters to an x-callback-url callback, percent-encoded (not
+ * `+` for spaces, which some apps pass through literally).
+ * Pure function.
+ */
+export const buildCallbackUrl = (
+	callback: string,
+	params: Record<string, string>,
+): string => {
+	const query = Object.entries(params)
+		.map(([key, value]) => `${key}=${encodeURIComponent(value)}`)
+		.join("&");
+	if (!query) return callback;
+	return `${callback}${callback.includes("?") ? "&" : "?"}${query}`;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getUrlActionsPath = (userDataPath: string) =>
+	path.join(userDataPath, URL_ACTIONS_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readUrlActionsFromFile = (filePath: string): UrlActionSettings => {
+	if (!fs.existsSync(filePath)) {
+		return parseUrlActionSettings({});
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseUrlActionSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read automation URL settings, using defaults:",
+			error,
+		);
+		return parseUrlActionSettings({});
+	}
+};
+
+const writeUrlActionsToFile = (
+	filePath: string,
+	settings: UrlActionSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// URL Action Settings Module
+// ============================================================================
+
+export type UrlActionSettingsDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the automation URL settings: which apps may receive results,
+ * whether item text may be sent to them at all, and whether links may
+ * copy and paste items.
+ */
+export const createUrlActionSettings = (deps: UrlActionSettingsDeps) => {
+	const filePath = getUrlActionsPath(deps.userDataPath);
+	let settings = readUrlActionsFromFile(filePath);
+
+	const getSettings = (): UrlActionSettings => ({
+		...settings,
+		callbackSchemes: [...settings.callbackSchemes],
+	});
+
+	const updateSettings = (input: unknown): UrlActionSettings => {
+		const next = parseUrlActionSettings(input, settings);
+		writeUrlActionsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings };
+};
+
+export type UrlActionSettingsStore = ReturnType<
+	typeof createUrlActionSettings
+>;
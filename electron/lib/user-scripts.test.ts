@@ -0,0 +1,200 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createUserScripts,
+	getUserScriptsPath,
+	parseScriptRunRequest,
+	parseUserScript,
+	runUserScript,
+} from "./user-scripts.js";
+
+const text = (value: string) => ({ type: "text" as const, text: value });
+
+describe("parseUserScript", () => {
+	it("trims the name and defaults onCapture to false", () => {
+		expect(
+			parseUserScript({ name: " Shout ", source: "return item.text + '!'" }),
+		).toEqual({
+			name: "Shout",
+			source: "return item.text + '!'",
+			onCapture: false,
+		});
+	});
+
+	it("rejects empty names and code that does not compile", () => {
+		expect(() => parseUserScript({ name: "", source: "return 1" })).toThrow(
+			"Invalid script: names must be 1-64 characters",
+		);
+		expect(() => parseUserScript({ name: "Bad", source: "return (" })).toThrow(
+			"Invalid script: Unexpected",
+		);
+		// Source that would close the sandbox wrapper early
+		const breakout = "}})(); process.exit(); ((() => {{";
+		expect(() => parseUserScript({ name: "Bad", source: breakout })).toThrow(
+			"Invalid script",
+		);
+		expect(() =>
+			parseUserScript({ name: "Bad", source: "return 1", onCapture: "yes" }),
+		).toThrow("onCapture must be a boolean");
+	});
+});
+
+describe("parseScriptRunRequest", () => {
+	it("defaults the target to a new item", () => {
+		expect(parseScriptRunRequest({ name: "Shout" })).toEqual({
+			name: "Shout",
+			target: "item",
+		});
+		expect(() =>
+			parseScriptRunRequest({ name: "Shout", target: "file" }),
+		).toThrow("target must be one of item, clipboard");
+	});
+});
+
+describe("runUserScript", () => {
+	it("replaces, keeps or rejects the item", () => {
+		expect(
+			runUserScript("return item.text.toUpperCase()", text("hi")),
+		).toEqual({ rejected: false, text: "HI" });
+		expect(runUserScript("if (item.text) return;", text("hi"))).toEqual({
+			rejected: false,
+			text: "hi",
+		});
+		expect(
+			runUserScript("return !/^\\d{6}$/.test(item.text)", text("123456")),
+		).toEqual({ rejected: true, text: "123456" });
+	});
+
+	it("only rewrites text items", () => {
+		expect(runUserScript("return 'x'", { type: "image", text: "" })).toEqual({
+			rejected: false,
+			text: "",
+		});
+	});
+
+	it("gives scripts no way out of the sandbox", () => {
+		expect(
+			runUserScript("return typeof require + typeof process", text("")),
+		).toEqual({ rejected: false, text: "undefinedundefined" });
+		expect(() =>
+			runUserScript(
+				'return globalThis.constructor.constructor("return process")()',
+				text(""),
+			),
+		).toThrow("Script failed");
+		expect(() =>
+			runUserScript(
+				'return item.constructor.constructor("return process")()',
+				text(""),
+			),
+		).toThrow("Script failed");
+	});
+
+	it("stops scripts that fail or run too long", () => {
+		expect(() => runUserScript("throw new Error('nope')", text(""))).toThrow(
+			"Script failed: nope",
+		);
+		expect(() => runUserScript("for (;;) {}", text(""))).toThrow(
+			"Script timed out",
+		);
+	});
+});
+
+describe("createUserScripts", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-scripts-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("installs, replaces and removes scripts, persisting them", () => {
+		const userDataPath = createTempUserDataPath();
+		const scripts = createUserScripts({ userDataPath });
+
+		scripts.install({ name: "Trim", source: "return item.text.trim()" });
+		scripts.install({ name: "Shout", source: "return item.text + '!'" });
+		scripts.install({
+			name: "Trim",
+			source: "return item.text.trimEnd()",
+			onCapture: true,
+		});
+
+		expect(createUserScripts({ userDataPath }).list()).toEqual([
+			{ name: "Trim", source: "return item.text.trimEnd()", onCapture: true },
+			{ name: "Shout", source: "return item.text + '!'", onCapture: false },
+		]);
+		expect(scripts.remove("Shout")).toHaveLength(1);
+		expect(() => scripts.remove("Shout")).toThrow("Script not found: Shout");
+	});
+
+	it("tests saved scripts and unsaved source", () => {
+		const scripts = createUserScripts({
+			userDataPath: createTempUserDataPath(),
+		});
+		scripts.install({ name: "Shout", source: "return item.text + '!'" });
+
+		expect(scripts.test({ name: "Shout", text: "hi" })).toEqual({
+			rejected: false,
+			text: "hi!",
+		});
+		expect(
+			scripts.test({
+				source: "return item.type !== 'image'",
+				text: "",
+				type: "image",
+			}),
+		).toEqual({ rejected: true, text: "" });
+		expect(() => scripts.test({ name: "Missing", text: "" })).toThrow(
+			"Script not found: Missing",
+		);
+	});
+
+	it("chains capture scripts and skips ones that fail", () => {
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const scripts = createUserScripts({
+			userDataPath: createTempUserDataPath(),
+		});
+		scripts.install({
+			name: "Trim",
+			source: "return item.text.trim()",
+			onCapture: true,
+		});
+		scripts.install({
+			name: "Broken",
+			source: "return item.missing.value",
+			onCapture: true,
+		});
+		scripts.install({ name: "Manual", source: "return null" });
+		scripts.install({
+			name: "No OTPs",
+			source: "return !/^\\d{6}$/.test(item.text)",
+			onCapture: true,
+		});
+
+		expect(scripts.runOnCapture(text("  hello "))).toEqual({
+			rejected: false,
+			text: "hello",
+		});
+		expect(scripts.runOnCapture(text(" 123456 ")).rejected).toBe(true);
+		vi.restoreAllMocks();
+	});
+
+	it("starts with no scripts when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getUserScriptsPath(userDataPath), "not json");
+		vi.spyOn(console, "error").mockImplementation(() => {});
+
+		expect(createUserScripts({ userDataPath }).list()).toEqual([]);
+		vi.restoreAllMocks();
+	});
+});
@@ -0,0 +1,395 @@
+import fs from "node:fs";
+import path from "node:path";
+import vm from "node:vm";
+import type { CaptureItemType, CaptureSubject } from "./capture-rules.js";
+import { isTransformTarget, type TransformTarget } from "./text-transforms.js";
+
+/**
+ * A user script: the body of a function given `item` (`{ type, text }`).
+ * Returning a string replaces the text, returning `false` or `null`
+ * rejects the item, and anything else keeps it as is.
+ */
+export type UserScript = {
+	name: string;
+	source: string;
+	/** Whether the script runs on every copy before it is recorded */
+	onCapture: boolean;
+};
+
+/**
+ * Persisted user scripts, run on capture in list order.
+ */
+export type UserScriptSettings = {
+	scripts: UserScript[];
+};
+
+/**
+ * What a script made of an item.
+ */
+export type ScriptResult = {
+	rejected: boolean;
+	/** The text after the script; the input text if it was rejected */
+	text: string;
+};
+
+/**
+ * A validated request to run a saved script on a text item.
+ */
+export type ScriptRunRequest = {
+	name: string;
+	target: TransformTarget;
+};
+
+const USER_SCRIPTS_FILENAME = "user-scripts.json";
+
+const MAX_USER_SCRIPTS = 50;
+
+const MAX_SCRIPT_NAME_LENGTH = 64;
+
+const MAX_SCRIPT_SOURCE_LENGTH = 20_000;
+
+/**
+ * Time one script may run on an item (ms) before it is interrupted.
+ */
+const SCRIPT_TIMEOUT_MS = 100;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) => new Error(`Invalid script: ${message}`);
+
+/**
+ * What the wrapped script hands back: its return value, or the message of
+ * what it threw, read inside the sandbox so no script code runs later.
+ */
+type WrappedResult =
+	| { failed: false; value: unknown }
+	| { failed: true; message: string };
+
+/**
+ * Wraps a script body so it runs as a strict function of a frozen item
+ * built inside the sandbox, leaving no outside objects within reach.
+ */
+const wrapSource = (source: string): string =>
+	[
+		"(function () {",
+		'"use strict";',
+		"try {",
+		"const value = (function (item) {",
+		source,
+		"})(Object.freeze({ type: __type, text: __text }));",
+		"return { failed: false, value };",
+		"} catch (error) {",
+		'let message = "unknown error";',
+		"try { message = String(error instanceof Error ? error.message : error); } catch {}",
+		"return { failed: true, message };",
+		"}",
+		"})()",
+	].join("\n");
+
+const compileScript = (source: string): vm.Script => {
+	try {
+		// Parses the source as exactly one function body, without running
+		// it, so it cannot close the wrapper early and run outside it
+		new Function("item", source);
+		return new vm.Script(wrapSource(source), { filename: "user-script.js" });
+	} catch (error) {
+		throw invalid(error instanceof Error ? error.message : String(error));
+	}
+};
+
+const parseScriptName = (name: unknown): string => {
+	if (
+		typeof name !== "string" ||
+		name.trim() === "" ||
+		name.trim().length > MAX_SCRIPT_NAME_LENGTH
+	) {
+		throw invalid(`names must be 1-${MAX_SCRIPT_NAME_LENGTH} characters`);
+	}
+	return name.trim();
+};
+
+const parseScriptSource = (source: unknown): string => {
+	if (
+		typeof source !== "string" ||
+		source.trim() === "" ||
+		source.length > MAX_SCRIPT_SOURCE_LENGTH
+	) {
+		throw invalid(
+			`source must be 1-${MAX_SCRIPT_SOURCE_LENGTH} characters of code`,
+		);
+	}
+	compileScript(source);
+	return source;
+};
+
+/**
+ * Validates a script to install.
+ * Pure function. The name is trimmed and `onCapture` defaults to false.
+ *
+ * @throws if the name is empty or over 64 characters, or the source is
+ *   empty, over 20000 characters or does not compile
+ */
+export const parseUserScript = (input: unknown): UserScript => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+	const { name, source, onCapture = false } = input as Record<
+		string,
+		unknown
+	>;
+	if (typeof onCapture !== "boolean") {
+		throw invalid("onCapture must be a boolean");
+	}
+	return {
+		name: parseScriptName(name),
+		source: parseScriptSource(source),
+		onCapture,
+	};
+};
+
+/**
+ * Validates a saved script list, e.g. read back from disk.
+ * Pure function.
+ *
+ * @throws if a script is invalid, names repeat, or there are more than 50
+ */
+export const parseUserScriptSettings = (input: unknown): UserScriptSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+	const { scripts } = input as Partial<
+		Record<keyof UserScriptSettings, unknown>
+	>;
+	if (!Array.isArray(scripts)) {
+		throw invalid("scripts must be an array");
+	}
+	if (scripts.length > MAX_USER_SCRIPTS) {
+		throw invalid(`at most ${MAX_USER_SCRIPTS} scripts are allowed`);
+	}
+	const parsed = scripts.map(parseUserScript);
+	const names = new Set(parsed.map((script) => script.name));
+	if (names.size !== parsed.length) {
+		throw invalid("script names must be unique");
+	}
+	return { scripts: parsed };
+};
+
+/**
+ * Validates a script run request from the renderer.
+ * Pure function. `target` defaults to a new item.
+ *
+ * @throws if the name or target is invalid
+ */
+export const parseScriptRunRequest = (value: unknown): ScriptRunRequest => {
+	if (typeof value !== "object" || value === null) {
+		throw invalid("expected an object");
+	}
+	const { name, target = "item" } = value as Record<string, unknown>;
+	if (!isTransformTarget(target)) {
+		throw invalid("target must be one of item, clipboard");
+	}
+	return { name: parseScriptName(name), target };
+};
+
+/**
+ * Runs a script on an item in a fresh sandbox with no Node or Electron
+ * APIs and no string or WebAssembly code generation.
+ * The sandbox guards against mistakes such as endless loops; scripts are
+ * the user's own code, not untrusted input.
+ *
+ * @throws "Script timed out" if it runs over 100 ms, or "Script failed"
+ *   with the error it threw
+ */
+export const runUserScript = (
+	source: string,
+	subject: CaptureSubject,
+): ScriptResult => {
+	const script = compileScript(source);
+	// A null-prototype global keeps the outside Function constructor out
+	// of reach through `globalThis.constructor`
+	const context = vm.createContext(
+		Object.assign(Object.create(null), {
+			__type: subject.type,
+			__text: subject.text,
+		}),
+		{ codeGeneration: { strings: false, wasm: false } },
+	);
+	let wrapped: WrappedResult;
+	try {
+		wrapped = script.runInContext(context, { timeout: SCRIPT_TIMEOUT_MS });
+	} catch {
+		// Everything the script throws is caught inside; only the timeout
+		// interrupts it from outside
+		throw new Error("Script timed out");
+	}
+	if (wrapped.failed) {
+		throw new Error(`Script failed: ${wrapped.message}`);
+	}
+	const result = wrapped.value;
+	if (result === false || result === null) {
+		return { rejected: true, text: subject.text };
+	}
+	return {
+		rejected: false,
+		text:
+			typeof result === "string" && subject.type === "text"
+				? result
+				: subject.text,
+	};
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getUserScriptsPath = (userDataPath: string) =>
+	path.join(userDataPath, USER_SCRIPTS_FILENAME);
+
+/**
+ * Reads saved scripts, falling back to none if missing or invalid.
+ */
+const readUserScriptsFromFile = (filePath: string): UserScriptSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { scripts: [] };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseUserScriptSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error("Failed to read user scripts, using none:", error);
+		return { scripts: [] };
+	}
+};
+
+const writeUserScriptsToFile = (
+	filePath: string,
+	settings: UserScriptSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// User Scripts Module
+// ============================================================================
+
+export type UserScriptsDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the user scripts store.
+ * Capture scripts run on every copy after the capture rules and may
+ * rewrite or reject it; any script can also be run on a saved item.
+ */
+export const createUserScripts = (deps: UserScriptsDeps) => {
+	const filePath = getUserScriptsPath(deps.userDataPath);
+	let settings = readUserScriptsFromFile(filePath);
+
+	const save = (scripts: UserScript[]) => {
+		const next = { scripts };
+		writeUserScriptsToFile(filePath, next);
+		settings = next;
+	};
+
+	const list = (): UserScript[] =>
+		settings.scripts.map((script) => ({ ...script }));
+
+	const find = (name: string): UserScript => {
+		const script = settings.scripts.find((entry) => entry.name === name);
+		if (!script) {
+			throw new Error(`Script not found: ${name}`);
+		}
+		return script;
+	};
+
+	/**
+	 * Saves a script, replacing any with the same name in place.
+	 * @returns the updated list
+	 */
+	const install = (input: unknown): UserScript[] => {
+		const script = parseUserScript(input);
+		const index = settings.scripts.findIndex(
+			(entry) => entry.name === script.name,
+		);
+		if (index === -1 && settings.scripts.length >= MAX_USER_SCRIPTS) {
+			throw invalid(`at most ${MAX_USER_SCRIPTS} scripts are allowed`);
+		}
+		save(
+			index === -1
+				? [...settings.scripts, script]
+				: settings.scripts.map((entry, i) => (i === index ? script : entry)),
+		);
+		return list();
+	};
+
+	/**
+	 * @returns the updated list
+	 * @throws if no script has the name
+	 */
+	const remove = (name: string): UserScript[] => {
+		const script = find(name);
+		save(settings.scripts.filter((entry) => entry !== script));
+		return list();
+	};
+
+	/**
+	 * Runs a saved script (by `name`) or unsaved `source` on sample text,
+	 * so a script can be tried before it is installed.
+	 *
+	 * @throws if the request is invalid or the script fails
+	 */
+	const test = (input: unknown): ScriptResult => {
+		if (typeof input !== "object" || input === null) {
+			throw invalid("expected an object");
+		}
+		const { name, source, text, type = "text" } = input as Record<
+			string,
+			unknown
+		>;
+		if (typeof text !== "string") {
+			throw invalid("text must be a string");
+		}
+		if (type !== "text" && type !== "image" && type !== "files") {
+			throw invalid("type must be one of text, image, files");
+		}
+		const code =
+			name !== undefined
+				? find(parseScriptName(name)).source
+				: parseScriptSource(source);
+		return runUserScript(code, { type: type as CaptureItemType, text });
+	};
+
+	/**
+	 * Runs a saved script on an item's text.
+	 * @throws if no script has the name or the script fails
+	 */
+	const run = (name: string, subject: CaptureSubject): ScriptResult =>
+		runUserScript(find(name).source, subject);
+
+	/**
+	 * Runs the capture scripts on a copy in list order, stopping at the
+	 * first that rejects it. A script that fails is skipped.
+	 */
+	const runOnCapture = (subject: CaptureSubject): ScriptResult => {
+		let text = subject.text;
+		for (const script of settings.scripts) {
+			if (!script.onCapture) continue;
+			try {
+				const result = runUserScript(script.source, { ...subject, text });
+				if (result.rejected) return result;
+				text = result.text;
+			} catch (error) {
+				console.warn(`Capture script "${script.name}" skipped:`, error);
+			}
+		}
+		return { rejected: false, text };
+	};
+
+	return { list, install, remove, test, run, runOnCapture };
+};
+
+export type UserScripts = ReturnType<typeof createUserScripts>;
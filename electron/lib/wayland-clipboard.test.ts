@@ -0,0 +1,74 @@
+import { describe, expect, it, vi } from "vitest";
+import {
+	createWaylandClipboard,
+	isWaylandSession,
+	parseTypeList,
+	pickType,
+} from "./wayland-clipboard.js";
+
+describe("wayland clipboard helpers", () => {
+	it("detects Wayland sessions", () => {
+		expect(isWaylandSession({ XDG_SESSION_TYPE: "wayland" })).toBe(true);
+		expect(isWaylandSession({ WAYLAND_DISPLAY: "wayland-1" })).toBe(true);
+		expect(isWaylandSession({ XDG_SESSION_TYPE: "x11" })).toBe(false);
+	});
+
+	it("picks the preferred offered type", () => {
+		const types = parseTypeList("text/html\nTEXT\n\nText/Plain\n");
+		expect(types).toEqual(["text/html", "TEXT", "Text/Plain"]);
+		expect(pickType(types, ["text/plain;charset=utf-8", "text/plain"])).toBe(
+			"Text/Plain",
+		);
+		expect(pickType(types, ["image/png"])).toBeNull();
+	});
+});
+
+describe("createWaylandClipboard", () => {
+	it("reads only the types the selection offers", () => {
+		const run = vi.fn((args: string[]) => {
+			if (args[0] === "--list-types") {
+				return Buffer.from("text/html\nUTF8_STRING\nimage/png\n");
+			}
+			return Buffer.from(`data of ${args[2]}`);
+		});
+		const selection = createWaylandClipboard({ run }).readSelection();
+
+		expect(selection?.availableFormats()).toEqual([
+			"text/html",
+			"UTF8_STRING",
+			"image/png",
+		]);
+		expect(selection?.readText()).toBe("data of UTF8_STRING");
+		expect(selection?.readHTML()).toBe("data of text/html");
+		expect(selection?.readRTF()).toBe("");
+		expect(selection?.readImage().toString()).toBe("data of image/png");
+		expect(selection?.readBuffer("text/uri-list").length).toBe(0);
+		expect(run).toHaveBeenCalledTimes(4);
+	});
+
+	it("treats an empty clipboard as offering nothing", () => {
+		const run = vi.fn(() => {
+			throw new Error("No selection");
+		});
+		const selection = createWaylandClipboard({ run }).readSelection();
+
+		expect(selection?.availableFormats()).toEqual([]);
+		expect(selection?.readText()).toBe("");
+		expect(run).toHaveBeenCalledTimes(1);
+	});
+
+	it("stops trying once wl-paste turns out to be missing", () => {
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const run = vi.fn(() => {
+			throw Object.assign(new Error("spawn wl-paste ENOENT"), {
+				code: "ENOENT",
+			});
+		});
+		const waylandClipboard = createWaylandClipboard({ run });
+
+		expect(waylandClipboard.readSelection()).toBeNull();
+		expect(waylandClipboard.readSelection()).toBeNull();
+		expect(run).toHaveBeenCalledTimes(1);
+		vi.restoreAllMocks();
+	});
+});
@@ -0,0 +1,164 @@
+import { execFileSync } from "node:child_process";
+
+/**
+ * Runs `wl-paste` with the given arguments and returns its output;
+ * throws if it fails. Injectable for tests.
+ */
+export type WlPasteRunner = (args: string[]) => Buffer;
+
+/**
+ * Plain text types in order of preference: UTF-8 first, then the older
+ * X11 atoms that Xwayland clients still offer.
+ */
+const TEXT_TYPES = [
+	"text/plain;charset=utf-8",
+	"UTF8_STRING",
+	"text/plain",
+	"STRING",
+	"TEXT",
+];
+
+const HTML_TYPES = ["text/html"];
+
+const RTF_TYPES = ["text/rtf", "application/rtf"];
+
+/**
+ * Image types Electron's `nativeImage` can decode, in order of preference.
+ */
+const IMAGE_TYPES = ["image/png", "image/jpeg"];
+
+/**
+ * How long a single `wl-paste` read may take (ms). The source app
+ * writes the data through a pipe, so a hung app would stall the read.
+ */
+const READ_TIMEOUT_MS = 5000;
+
+/**
+ * Largest read accepted from `wl-paste` (bytes); matches the longest
+ * text kept as an attachment.
+ */
+const MAX_READ_BYTES = 256 * 1024 * 1024;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Checks whether the session runs on a Wayland compositor.
+ * Pure function.
+ */
+export const isWaylandSession = (env: NodeJS.ProcessEnv): boolean =>
+	env.XDG_SESSION_TYPE === "wayland" || Boolean(env.WAYLAND_DISPLAY);
+
+/**
+ * Parses `wl-paste --list-types` output, one MIME type per line.
+ * Pure function.
+ */
+export const parseTypeList = (output: string): string[] =>
+	output
+		.split("\n")
+		.map((line) => line.trim())
+		.filter((line) => line.length > 0);
+
+/**
+ * The offered type matching the first of `candidates` the selection
+ * offers, compared case-insensitively, or null.
+ * Pure function.
+ */
+export const pickType = (
+	offered: readonly string[],
+	candidates: readonly string[],
+): string | null => {
+	for (const candidate of candidates) {
+		const match = offered.find(
+			(type) => type.toLowerCase() === candidate.toLowerCase(),
+		);
+		if (match) return match;
+	}
+	return null;
+};
+
+// ============================================================================
+// Wayland Clipboard Module
+// ============================================================================
+
+const runWlPaste: WlPasteRunner = (args) =>
+	execFileSync("wl-paste", args, {
+		timeout: READ_TIMEOUT_MS,
+		maxBuffer: MAX_READ_BYTES,
+		stdio: ["ignore", "pipe", "ignore"],
+	});
+
+const isMissingCommand = (error: unknown): boolean =>
+	(error as NodeJS.ErrnoException | null)?.code === "ENOENT";
+
+/**
+ * Creates the Wayland clipboard reader. Wayland only hands the selection
+ * to the focused client, and Xwayland only syncs it while one of its
+ * windows has focus, so Electron's clipboard misses copies made while
+ * the app is in the background. `wl-paste` reads the selection over the
+ * data-control protocol (wlroots compositors such as Sway and Hyprland,
+ * KDE) without needing focus. When `wl-paste` is not installed the
+ * Electron clipboard is used instead.
+ */
+export const createWaylandClipboard = (deps: { run?: WlPasteRunner } = {}) => {
+	const run = deps.run ?? runWlPaste;
+	let available = true;
+
+	/**
+	 * Reads one type from the selection.
+	 * @returns an empty buffer if it could not be read
+	 */
+	const readType = (type: string | null): Buffer => {
+		if (!type) return Buffer.alloc(0);
+		try {
+			return run(["--no-newline", "--type", type]);
+		} catch (error) {
+			console.error(
+				`Failed to read ${type} from the Wayland clipboard:`,
+				error,
+			);
+			return Buffer.alloc(0);
+		}
+	};
+
+	/**
+	 * Lists the types the current selection offers once, and returns
+	 * readers in the shape of Electron's clipboard that only ask
+	 * `wl-paste` for types that are offered.
+	 * @returns null if `wl-paste` is missing
+	 */
+	const readSelection = () => {
+		if (!available) return null;
+
+		let types: string[];
+		try {
+			types = parseTypeList(run(["--list-types"]).toString("utf8"));
+		} catch (error) {
+			if (isMissingCommand(error)) {
+				available = false;
+				console.warn(
+					"wl-paste not found; install wl-clipboard to capture copies from Wayland apps",
+				);
+				return null;
+			}
+			// wl-paste exits with an error when nothing is copied
+			types = [];
+		}
+
+		const readText = (candidates: readonly string[]): string =>
+			readType(pickType(types, candidates)).toString("utf8");
+
+		return {
+			availableFormats: () => [...types],
+			readText: () => readText(TEXT_TYPES),
+			readHTML: () => readText(HTML_TYPES),
+			readRTF: () => readText(RTF_TYPES),
+			readBuffer: (format: string) => readType(pickType(types, [format])),
+			/** Encoded PNG or JPEG data, empty if no image is offered */
+			readImage: () => readType(pickType(types, IMAGE_TYPES)),
+		};
+	};
+
+	return { readSelection };
+};
@@ -0,0 +1,290 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createWebhooks,
+	getRetryDelay,
+	getWebhookSettingsPath,
+	parseWebhookSettings,
+	shouldDeliverEvent,
+	signWebhookBody,
+	toWebhookBody,
+	type WebhookEvent,
+	type WebhookSettings,
+} from "./webhooks.js";
+
+const settings = (overrides: Partial<WebhookSettings>): WebhookSettings => ({
+	enabled: true,
+	url: "https://hooks.example.com/clip",
+	secret: "0123456789abcdef",
+	events: ["item.added"],
+	itemTypes: [],
+	kinds: [],
+	includeContent: false,
+	...overrides,
+});
+
+const added = (
+	item: Partial<Extract<WebhookEvent, { type: "item.added" }>["item"]>,
+): WebhookEvent => ({
+	type: "item.added",
+	item: {
+		id: 1,
+		type: "text",
+		preview: "https://example.com",
+		created_at: "2024-01-01 00:00:00",
+		kind: "url",
+		content: "https://example.com",
+		...item,
+	},
+});
+
+describe("parseWebhookSettings", () => {
+	it("applies an update and keeps missing keys", () => {
+		const url = "http://homeassistant.local:8123/api/webhook/clip";
+
+		expect(
+			parseWebhookSettings(
+				{ url: ` ${url} `, kinds: ["url", "url"] },
+				settings({ enabled: false, url: "" }),
+			),
+		).toEqual(settings({ enabled: false, url, kinds: ["url"] }));
+	});
+
+	it("rejects invalid settings", () => {
+		const current = settings({ enabled: false, url: "" });
+
+		expect(() => parseWebhookSettings({ enabled: true }, current)).toThrow(
+			"Invalid webhook settings: url is required to enable the webhook",
+		);
+		expect(() =>
+			parseWebhookSettings({ url: "ftp://example.com" }, current),
+		).toThrow("url must be an http or https URL");
+		expect(() => parseWebhookSettings({ secret: "short" }, current)).toThrow(
+			"secret must be 16-256 characters",
+		);
+		expect(() =>
+			parseWebhookSettings({ events: ["item.copied"] }, current),
+		).toThrow("events must be a list of item.added");
+		expect(() => parseWebhookSettings({ kinds: ["music"] }, current)).toThrow(
+			"kinds must be a list of",
+		);
+	});
+});
+
+describe("shouldDeliverEvent", () => {
+	it("filters new items by type and kind", () => {
+		const urlsOnly = settings({ kinds: ["url"] });
+
+		expect(shouldDeliverEvent(added({}), urlsOnly)).toBe(true);
+		expect(shouldDeliverEvent(added({ kind: "email" }), urlsOnly)).toBe(false);
+		expect(
+			shouldDeliverEvent(added({ type: "image", kind: null }), urlsOnly),
+		).toBe(false);
+		expect(
+			shouldDeliverEvent(
+				added({ type: "image", kind: null }),
+				settings({ itemTypes: ["text"] }),
+			),
+		).toBe(false);
+	});
+
+	it("only sends the chosen events", () => {
+		const deleted: WebhookEvent = { type: "item.deleted", id: 1 };
+
+		expect(shouldDeliverEvent(deleted, settings({}))).toBe(false);
+		expect(
+			shouldDeliverEvent(
+				deleted,
+				settings({ events: ["item.deleted"], kinds: ["url"] }),
+			),
+		).toBe(true);
+		expect(shouldDeliverEvent(added({}), settings({ events: [] }))).toBe(
+			false,
+		);
+	});
+});
+
+describe("toWebhookBody", () => {
+	it("leaves out item text unless asked for", () => {
+		const preview = JSON.parse(toWebhookBody(added({}), false));
+		const full = JSON.parse(toWebhookBody(added({}), true));
+
+		expect(preview.item).not.toHaveProperty("content");
+		expect(full.item.content).toBe("https://example.com");
+	});
+});
+
+describe("signWebhookBody", () => {
+	it("signs the timestamp and body with HMAC-SHA256", () => {
+		const expected = crypto
+			.createHmac("sha256", "secret")
+			.update('1700000000.{"type":"ping"}')
+			.digest("hex");
+
+		expect(signWebhookBody("secret", 1700000000, '{"type":"ping"}')).toBe(
+			expected,
+		);
+	});
+});
+
+describe("getRetryDelay", () => {
+	it("doubles up to a minute", () => {
+		expect(getRetryDelay(1)).toBe(2000);
+		expect(getRetryDelay(3)).toBe(8000);
+		expect(getRetryDelay(10)).toBe(60_000);
+	});
+});
+
+describe("createWebhooks", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-webhooks-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		vi.useRealTimers();
+		vi.restoreAllMocks();
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("generates and keeps a secret, off by default", () => {
+		const userDataPath = createTempUserDataPath();
+		const status = createWebhooks({ userDataPath }).getStatus();
+
+		expect(status).toMatchObject({
+			enabled: false,
+			events: ["item.added"],
+			pending: 0,
+		});
+		expect(status.secret).toMatch(/^[0-9a-f]{64}$/);
+		expect(createWebhooks({ userDataPath }).getStatus().secret).toBe(
+			status.secret,
+		);
+	});
+
+	it("posts signed events that pass the filters", async () => {
+		const fetch = vi.fn().mockResolvedValue(new Response(null));
+		const webhooks = createWebhooks({
+			userDataPath: createTempUserDataPath(),
+			fetch,
+		});
+		const { secret } = webhooks.updateSettings({
+			enabled: true,
+			url: "https://hooks.example.com/clip",
+			kinds: ["url"],
+		});
+
+		webhooks.publish(added({ kind: "json" }));
+		webhooks.publish(added({}));
+		await vi.waitFor(() => expect(webhooks.getStatus().pending).toBe(0));
+
+		expect(fetch).toHaveBeenCalledTimes(1);
+		const [url, init] = fetch.mock.calls[0];
+		expect(url).toBe("https://hooks.example.com/clip");
+		expect(init.headers["X-Clipman-Event"]).toBe("item.added");
+		const timestamp = Number(init.headers["X-Clipman-Timestamp"]);
+		expect(init.headers["X-Clipman-Signature"]).toBe(
+			`sha256=${signWebhookBody(secret, timestamp, init.body)}`,
+		);
+		expect(JSON.parse(init.body).item).toEqual({
+			id: 1,
+			type: "text",
+			preview: "https://example.com",
+			created_at: "2024-01-01 00:00:00",
+			kind: "url",
+		});
+		expect(webhooks.getStatus().lastDeliveredAt).not.toBeNull();
+	});
+
+	it("retries with backoff and gives up on client errors", async () => {
+		vi.useFakeTimers();
+		vi.spyOn(console, "warn").mockImplementation(() => {});
+		const fetch = vi
+			.fn()
+			.mockRejectedValueOnce(new Error("connect ECONNREFUSED"))
+			.mockResolvedValueOnce(new Response(null, { status: 503 }))
+			.mockResolvedValueOnce(new Response(null))
+			.mockResolvedValueOnce(new Response(null, { status: 404 }));
+		const webhooks = createWebhooks({
+			userDataPath: createTempUserDataPath(),
+			fetch,
+		});
+		webhooks.updateSettings({ enabled: true, url: "https://a.example" });
+
+		webhooks.publish(added({ id: 1 }));
+		webhooks.publish(added({ id: 2 }));
+		await vi.advanceTimersByTimeAsync(1999);
+		expect(fetch).toHaveBeenCalledTimes(1);
+		await vi.advanceTimersByTimeAsync(1);
+		expect(fetch).toHaveBeenCalledTimes(2);
+		await vi.advanceTimersByTimeAsync(4000);
+
+		expect(fetch).toHaveBeenCalledTimes(4);
+		// Each retry is the same delivery; the next event waits its turn
+		const deliveries = fetch.mock.calls.map(
+			([, init]) => init.headers["X-Clipman-Delivery"],
+		);
+		expect(new Set(deliveries.slice(0, 3)).size).toBe(1);
+		expect(JSON.parse(fetch.mock.calls[3][1].body).item.id).toBe(2);
+		expect(webhooks.getStatus()).toMatchObject({
+			pending: 0,
+			lastError: "Webhook answered 404",
+		});
+	});
+
+	it("drops queued events when the webhook is turned off", async () => {
+		vi.useFakeTimers();
+		const fetch = vi.fn().mockRejectedValue(new Error("offline"));
+		const webhooks = createWebhooks({
+			userDataPath: createTempUserDataPath(),
+			fetch,
+		});
+		webhooks.updateSettings({ enabled: true, url: "https://a.example" });
+
+		webhooks.publish(added({}));
+		await vi.advanceTimersByTimeAsync(0);
+		expect(webhooks.getStatus().pending).toBe(1);
+
+		webhooks.updateSettings({ enabled: false });
+		await vi.advanceTimersByTimeAsync(60_000);
+		expect(fetch).toHaveBeenCalledTimes(1);
+		expect(webhooks.getStatus().pending).toBe(0);
+	});
+
+	it("sends a test ping once and reports failures", async () => {
+		const fetch = vi
+			.fn()
+			.mockResolvedValueOnce(new Response(null))
+			.mockResolvedValueOnce(new Response(null, { status: 500 }));
+		const webhooks = createWebhooks({
+			userDataPath: createTempUserDataPath(),
+			fetch,
+		});
+
+		await expect(webhooks.test()).rejects.toThrow("Set a webhook URL first");
+		webhooks.updateSettings({ url: "https://a.example" });
+		await webhooks.test();
+		expect(fetch.mock.calls[0][1].body).toBe('{"type":"ping"}');
+		await expect(webhooks.test()).rejects.toThrow("Webhook answered 500");
+		expect(fetch).toHaveBeenCalledTimes(2);
+	});
+
+	it("uses defaults when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getWebhookSettingsPath(userDataPath), "not json");
+		vi.spyOn(console, "error").mockImplementation(() => {});
+
+		expect(createWebhooks({ userDataPath }).getStatus()).toMatchObject({
+			enabled: false,
+			url: "",
+		});
+	});
+});
@@ -0,0 +1,502 @@
+import crypto from "node:crypto";
+import fs from "node:fs";
+import path from "node:path";
+import type { CaptureItemType } from "./capture-rules.js";
+import { CONTENT_KINDS, type ContentKind } from "./content-kind.js";
+import type { HistoryEvent } from "./event-stream.js";
+import type { HttpApiItem } from "./http-api-server.js";
+
+export type WebhookEventType = HistoryEvent["type"];
+
+/**
+ * A new item as a webhook describes it.
+ */
+export type WebhookItem = HttpApiItem & {
+	/** What a text item holds, e.g. `url`; null for other types */
+	kind: ContentKind | null;
+	/**
+	 * The text (one path per line for files); null for images and items
+	 * flagged by secret scanning. Only sent with `includeContent`.
+	 */
+	content: string | null;
+};
+
+/**
+ * An event posted to the webhook: a history change, or `ping` when the
+ * user sends a test.
+ */
+export type WebhookEvent =
+	| { type: "item.added"; item: WebhookItem }
+	| Exclude<HistoryEvent, { type: "item.added" }>
+	| { type: "ping" };
+
+/**
+ * Persisted settings for the outbound webhook.
+ */
+export type WebhookSettings = {
+	/** Post events to `url`; off until the user opts in */
+	enabled: boolean;
+	/** http or https endpoint, e.g. an n8n or Home Assistant webhook */
+	url: string;
+	/** Key the HMAC-SHA256 signature of each request is made with */
+	secret: string;
+	/** History events to post */
+	events: WebhookEventType[];
+	/** New items of these types only; empty for all */
+	itemTypes: CaptureItemType[];
+	/** New text items of these kinds only, e.g. `url`; empty for all */
+	kinds: ContentKind[];
+	/** Send new items' text, not just a preview */
+	includeContent: boolean;
+};
+
+/**
+ * Webhook settings plus how delivery is going.
+ */
+export type WebhookStatus = WebhookSettings & {
+	/** Events waiting to be sent or retried */
+	pending: number;
+	/** When an event was last delivered; null if none has been */
+	lastDeliveredAt: string | null;
+	/** Why the most recent event that gave up failed; null if none has */
+	lastError: string | null;
+};
+
+/**
+ * What one request of a delivery came to.
+ */
+export type WebhookAttempt =
+	| { ok: true }
+	| { ok: false; retry: boolean; error: string };
+
+const WEBHOOK_EVENT_TYPES: readonly WebhookEventType[] = [
+	"item.added",
+	"item.deleted",
+	"item.pinned",
+	"history.cleared",
+];
+
+const ITEM_TYPES: readonly CaptureItemType[] = ["text", "image", "files"];
+
+const MIN_SECRET_LENGTH = 16;
+const MAX_SECRET_LENGTH = 256;
+const MAX_URL_LENGTH = 2048;
+
+/**
+ * Attempts per event, including the first.
+ */
+const MAX_ATTEMPTS = 6;
+
+/**
+ * Wait before the first retry (ms); it doubles for each one after.
+ */
+const RETRY_BASE_DELAY_MS = 2000;
+
+const MAX_RETRY_DELAY_MS = 60_000;
+
+/**
+ * Events kept while the endpoint is unreachable; the oldest are dropped
+ * beyond this.
+ */
+const MAX_PENDING_EVENTS = 100;
+
+/**
+ * How long to wait for the endpoint before a request counts as failed.
+ */
+const REQUEST_TIMEOUT_MS = 10_000;
+
+const WEBHOOKS_FILENAME = "webhooks.json";
+
+/**
+ * Creates a random signing secret.
+ */
+const generateSecret = (): string => crypto.randomBytes(32).toString("hex");
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+const invalid = (message: string) =>
+	new Error(`Invalid webhook settings: ${message}`);
+
+const parseList = <T extends string>(
+	value: unknown,
+	allowed: readonly T[],
+	key: string,
+): T[] => {
+	if (
+		!Array.isArray(value) ||
+		!value.every((entry) => allowed.includes(entry as T))
+	) {
+		throw invalid(`${key} must be a list of ${allowed.join(", ")}`);
+	}
+	return [...new Set(value as T[])];
+};
+
+/**
+ * Validates a webhook settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if a value is invalid, the URL is not http or https, the secret
+ *   is not 16-256 characters, or the webhook would be enabled without a
+ *   URL
+ */
+export const parseWebhookSettings = (
+	input: unknown,
+	current: WebhookSettings,
+): WebhookSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw invalid("expected an object");
+	}
+
+	const values = input as Partial<Record<keyof WebhookSettings, unknown>>;
+	const next: WebhookSettings = { ...current };
+
+	for (const key of ["enabled", "includeContent"] as const) {
+		const value = values[key];
+		if (value === undefined) continue;
+		if (typeof value !== "boolean") {
+			throw invalid(`${key} must be a boolean`);
+		}
+		next[key] = value;
+	}
+
+	if (values.url !== undefined) {
+		const url = typeof values.url === "string" ? values.url.trim() : null;
+		if (
+			url === null ||
+			url.length > MAX_URL_LENGTH ||
+			(url && !/^https?:\/\/[^/]/i.test(url))
+		) {
+			throw invalid("url must be an http or https URL");
+		}
+		next.url = url;
+	}
+
+	if (values.secret !== undefined) {
+		if (
+			typeof values.secret !== "string" ||
+			values.secret.length < MIN_SECRET_LENGTH ||
+			values.secret.length > MAX_SECRET_LENGTH
+		) {
+			throw invalid(
+				`secret must be ${MIN_SECRET_LENGTH}-${MAX_SECRET_LENGTH} characters`,
+			);
+		}
+		next.secret = values.secret;
+	}
+
+	if (values.events !== undefined) {
+		next.events = parseList(values.events, WEBHOOK_EVENT_TYPES, "events");
+	}
+	if (values.itemTypes !== undefined) {
+		next.itemTypes = parseList(values.itemTypes, ITEM_TYPES, "itemTypes");
+	}
+	if (values.kinds !== undefined) {
+		next.kinds = parseList(values.kinds, CONTENT_KINDS, "kinds");
+	}
+
+	if (next.enabled && !next.url) {
+		throw invalid("url is required to enable the webhook");
+	}
+
+	return next;
+};
+
+/**
+ * Whether the settings ask for an event. Item filters only apply to new
+ * items; a kind filter leaves out everything but text items.
+ * Pure function.
+ */
+export const shouldDeliverEvent = (
+	event: WebhookEvent,
+	settings: WebhookSettings,
+): boolean => {
+	if (event.type === "ping") return true;
+	if (!settings.events.includes(event.type)) return false;
+	if (event.type !== "item.added") return true;
+	const { type, kind } = event.item;
+	if (
+		settings.itemTypes.length > 0 &&
+		!settings.itemTypes.includes(type as CaptureItemType)
+	) {
+		return false;
+	}
+	return (
+		settings.kinds.length === 0 ||
+		(kind !== null && settings.kinds.includes(kind))
+	);
+};
+
+/**
+ * The JSON body posted for an event, without item text unless
+ * `includeContent` is set.
+ * Pure function.
+ */
+export const toWebhookBody = (
+	event: WebhookEvent,
+	includeContent: boolean,
+): string => {
+	if (event.type !== "item.added" || includeContent) {
+		return JSON.stringify(event);
+	}
+	const { content: _content, ...item } = event.item;
+	return JSON.stringify({ ...event, item });
+};
+
+/**
+ * Signs a request: the hex HMAC-SHA256 of `<timestamp>.<body>`, so a
+ * receiver can check both that it came from the app and when.
+ * Pure function.
+ */
+export const signWebhookBody = (
+	secret: string,
+	timestamp: number,
+	body: string,
+): string =>
+	crypto
+		.createHmac("sha256", secret)
+		.update(`${timestamp}.${body}`)
+		.digest("hex");
+
+/**
+ * How long to wait before retry `attempt` (1 for the first retry).
+ * Pure function.
+ */
+export const getRetryDelay = (attempt: number): number =>
+	Math.min(RETRY_BASE_DELAY_MS * 2 ** (attempt - 1), MAX_RETRY_DELAY_MS);
+
+/**
+ * Whether a response status is worth retrying: timeouts, rate limits and
+ * server errors. Other errors would fail again the same way.
+ * Pure function.
+ */
+export const isRetryableStatus = (status: number): boolean =>
+	status === 408 || status === 429 || status >= 500;
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getWebhookSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, WEBHOOKS_FILENAME);
+
+const writeWebhookSettingsToFile = (
+	filePath: string,
+	settings: WebhookSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), {
+		encoding: "utf-8",
+		mode: 0o600,
+	});
+};
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ * A fresh secret is generated and saved when none is stored yet.
+ */
+const readWebhookSettingsFromFile = (filePath: string): WebhookSettings => {
+	const defaults: WebhookSettings = {
+		enabled: false,
+		url: "",
+		secret: "",
+		events: ["item.added"],
+		itemTypes: [],
+		kinds: [],
+		includeContent: false,
+	};
+
+	let settings = defaults;
+	if (fs.existsSync(filePath)) {
+		try {
+			const raw = fs.readFileSync(filePath, "utf-8");
+			settings = parseWebhookSettings(JSON.parse(raw), defaults);
+		} catch (error) {
+			console.error("Failed to read webhook settings, using defaults:", error);
+		}
+	}
+
+	if (!settings.secret) {
+		settings = { ...settings, secret: generateSecret() };
+		writeWebhookSettingsToFile(filePath, settings);
+	}
+	return settings;
+};
+
+// ============================================================================
+// Webhooks Module
+// ============================================================================
+
+export type WebhooksDeps = {
+	userDataPath: string;
+	/** Sends requests; injectable for tests */
+	fetch?: typeof fetch;
+};
+
+type Delivery = {
+	id: string;
+	type: WebhookEvent["type"];
+	url: string;
+	secret: string;
+	body: string;
+};
+
+/**
+ * Creates the outbound webhook. Events are posted one at a time in the
+ * order they happened; one that fails with a network error, timeout or
+ * retryable status is retried with exponential backoff before the next
+ * is sent. The settings file is readable by the current user only,
+ * since it holds the secret.
+ */
+export const createWebhooks = (deps: WebhooksDeps) => {
+	const filePath = getWebhookSettingsPath(deps.userDataPath);
+	const send = deps.fetch ?? fetch;
+	let settings = readWebhookSettingsFromFile(filePath);
+	let queue: Delivery[] = [];
+	let sending = false;
+	let lastDeliveredAt: string | null = null;
+	let lastError: string | null = null;
+
+	const getStatus = (): WebhookStatus => ({
+		...settings,
+		events: [...settings.events],
+		itemTypes: [...settings.itemTypes],
+		kinds: [...settings.kinds],
+		pending: queue.length,
+		lastDeliveredAt,
+		lastError,
+	});
+
+	const save = (next: WebhookSettings): WebhookStatus => {
+		writeWebhookSettingsToFile(filePath, next);
+		settings = next;
+		// Events queued for an endpoint or secret no longer configured
+		// would go to the wrong place or fail to verify
+		queue = queue.filter(
+			(delivery) =>
+				next.enabled &&
+				delivery.url === next.url &&
+				delivery.secret === next.secret,
+		);
+		return getStatus();
+	};
+
+	const createDelivery = (event: WebhookEvent): Delivery => ({
+		id: crypto.randomUUID(),
+		type: event.type,
+		url: settings.url,
+		secret: settings.secret,
+		body: toWebhookBody(event, settings.includeContent),
+	});
+
+	/**
+	 * Sends one request for a delivery, signed with the current time.
+	 */
+	const attempt = async (delivery: Delivery): Promise<WebhookAttempt> => {
+		const timestamp = Math.floor(Date.now() / 1000);
+		const signature = signWebhookBody(delivery.secret, timestamp, delivery.body);
+		try {
+			const response = await send(delivery.url, {
+				method: "POST",
+				headers: {
+					"Content-Type": "application/json",
+					"User-Agent": "clipman-webhook",
+					"X-Clipman-Event": delivery.type,
+					"X-Clipman-Delivery": delivery.id,
+					"X-Clipman-Timestamp": String(timestamp),
+					"X-Clipman-Signature": `sha256=${signature}`,
+				},
+				body: delivery.body,
+				redirect: "error",
+				signal: AbortSignal.timeout(REQUEST_TIMEOUT_MS),
+			});
+			if (response.ok) return { ok: true };
+			return {
+				ok: false,
+				retry: isRetryableStatus(response.status),
+				error: `Webhook answered ${response.status}`,
+			};
+		} catch (error) {
+			const message = error instanceof Error ? error.message : String(error);
+			return { ok: false, retry: true, error: `Webhook failed: ${message}` };
+		}
+	};
+
+	const wait = (ms: number) =>
+		new Promise<void>((resolve) => setTimeout(resolve, ms));
+
+	/**
+	 * Sends queued events until the queue is empty. Settings changes may
+	 * drop the event being retried, so each step checks it is still next.
+	 */
+	const drain = async (): Promise<void> => {
+		if (sending) return;
+		sending = true;
+		try {
+			while (queue.length > 0) {
+				const delivery = queue[0];
+				for (let tries = 1; ; tries++) {
+					const result = await attempt(delivery);
+					if (queue[0] !== delivery) break;
+					if (result.ok) {
+						lastDeliveredAt = new Date().toISOString();
+					} else if (result.retry && tries < MAX_ATTEMPTS) {
+						await wait(getRetryDelay(tries));
+						if (queue[0] !== delivery) break;
+						continue;
+					} else {
+						lastError = result.error;
+						console.warn(`Webhook gave up on ${delivery.type}:`, result.error);
+					}
+					queue.shift();
+					break;
+				}
+			}
+		} finally {
+			sending = false;
+		}
+	};
+
+	/**
+	 * Queues an event if the webhook is on and it passes the filters.
+	 */
+	const publish = (event: WebhookEvent): void => {
+		if (!settings.enabled || !shouldDeliverEvent(event, settings)) return;
+		queue.push(createDelivery(event));
+		if (queue.length > MAX_PENDING_EVENTS) {
+			// The oldest is kept while it may be in flight
+			const [dropped] = queue.splice(1, 1);
+			console.warn(`Webhook queue full, dropped ${dropped.type}`);
+		}
+		void drain();
+	};
+
+	const updateSettings = (input: unknown): WebhookStatus =>
+		save(parseWebhookSettings(input, settings));
+
+	/**
+	 * Replaces the secret, so receivers checking the old one reject new
+	 * requests.
+	 */
+	const regenerateSecret = (): WebhookStatus =>
+		save({ ...settings, secret: generateSecret() });
+
+	/**
+	 * Posts a `ping` event once, without retrying, whether or not the
+	 * webhook is enabled.
+	 *
+	 * @throws if no URL is set or the request fails
+	 */
+	const test = async (): Promise<void> => {
+		if (!settings.url) {
+			throw new Error("Set a webhook URL first");
+		}
+		const result = await attempt(createDelivery({ type: "ping" }));
+		if (!result.ok) throw new Error(result.error);
+	};
+
+	return { getStatus, updateSettings, regenerateSecret, publish, test };
+};
+
+export type Webhooks = ReturnType<typeof createWebhooks>;
@@ -0,0 +1,69 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it, vi } from "vitest";
+import {
+	createWindowBehavior,
+	getWindowBehaviorSettingsPath,
+	parseWindowBehaviorSettings,
+} from "./window-behavior.js";
+
+describe("parseWindowBehaviorSettings", () => {
+	it("keeps current values for missing keys", () => {
+		expect(parseWindowBehaviorSettings({}, { hideOnBlur: false })).toEqual({
+			hideOnBlur: false,
+		});
+		expect(parseWindowBehaviorSettings({ hideOnBlur: false })).toEqual({
+			hideOnBlur: false,
+		});
+	});
+
+	it("rejects invalid values", () => {
+		expect(() => parseWindowBehaviorSettings(null)).toThrow(
+			"expected an object",
+		);
+		expect(() => parseWindowBehaviorSettings({ hideOnBlur: "no" })).toThrow(
+			"hideOnBlur must be a boolean",
+		);
+	});
+});
+
+describe("createWindowBehavior", () => {
+	const tempDirs: string[] = [];
+
+	const createTempUserDataPath = (): string => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-window-"));
+		tempDirs.push(dir);
+		return dir;
+	};
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("hides on blur by default and persists the opt-out", () => {
+		const userDataPath = createTempUserDataPath();
+		const windowBehavior = createWindowBehavior({ userDataPath });
+
+		expect(windowBehavior.getSettings()).toEqual({ hideOnBlur: true });
+		expect(windowBehavior.updateSettings({ hideOnBlur: false })).toEqual({
+			hideOnBlur: false,
+		});
+		expect(createWindowBehavior({ userDataPath }).getSettings()).toEqual({
+			hideOnBlur: false,
+		});
+	});
+
+	it("falls back to defaults when the file is invalid", () => {
+		const userDataPath = createTempUserDataPath();
+		fs.writeFileSync(getWindowBehaviorSettingsPath(userDataPath), "not json");
+		const errorSpy = vi.spyOn(console, "error").mockImplementation(() => {});
+
+		expect(createWindowBehavior({ userDataPath }).getSettings()).toEqual({
+			hideOnBlur: true,
+		});
+		errorSpy.mockRestore();
+	});
+});
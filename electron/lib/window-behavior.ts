@@ -0,0 +1,118 @@
+import fs from "node:fs";
+import path from "node:path";
+
+/**
+ * Persisted window behavior settings.
+ */
+export type WindowBehaviorSettings = {
+	/** Hide the history and quick picker windows when they lose focus */
+	hideOnBlur: boolean;
+};
+
+const DEFAULT_WINDOW_BEHAVIOR_SETTINGS: WindowBehaviorSettings = {
+	hideOnBlur: true,
+};
+
+const WINDOW_BEHAVIOR_FILENAME = "window-behavior.json";
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Validates a window behavior settings update.
+ * Pure function. Missing keys keep their current value.
+ *
+ * @throws if `hideOnBlur` is not a boolean
+ */
+export const parseWindowBehaviorSettings = (
+	input: unknown,
+	current: WindowBehaviorSettings = DEFAULT_WINDOW_BEHAVIOR_SETTINGS,
+): WindowBehaviorSettings => {
+	if (typeof input !== "object" || input === null) {
+		throw new Error("Invalid window behavior settings: expected an object");
+	}
+
+	const { hideOnBlur } = input as Partial<
+		Record<keyof WindowBehaviorSettings, unknown>
+	>;
+	const next: WindowBehaviorSettings = { ...current };
+
+	if (hideOnBlur !== undefined) {
+		if (typeof hideOnBlur !== "boolean") {
+			throw new Error(
+				"Invalid window behavior settings: hideOnBlur must be a boolean",
+			);
+		}
+		next.hideOnBlur = hideOnBlur;
+	}
+
+	return next;
+};
+
+// ============================================================================
+// Settings File
+// ============================================================================
+
+export const getWindowBehaviorSettingsPath = (userDataPath: string) =>
+	path.join(userDataPath, WINDOW_BEHAVIOR_FILENAME);
+
+/**
+ * Reads saved settings, falling back to defaults if missing or invalid.
+ */
+const readWindowBehaviorSettingsFromFile = (
+	filePath: string,
+): WindowBehaviorSettings => {
+	if (!fs.existsSync(filePath)) {
+		return { ...DEFAULT_WINDOW_BEHAVIOR_SETTINGS };
+	}
+
+	try {
+		const raw = fs.readFileSync(filePath, "utf-8");
+		return parseWindowBehaviorSettings(JSON.parse(raw));
+	} catch (error) {
+		console.error(
+			"Failed to read window behavior settings, using defaults:",
+			error,
+		);
+		return { ...DEFAULT_WINDOW_BEHAVIOR_SETTINGS };
+	}
+};
+
+const writeWindowBehaviorSettingsToFile = (
+	filePath: string,
+	settings: WindowBehaviorSettings,
+) => {
+	fs.mkdirSync(path.dirname(filePath), { recursive: true });
+	fs.writeFileSync(filePath, JSON.stringify(settings, null, 2), "utf-8");
+};
+
+// ============================================================================
+// Window Behavior Module
+// ============================================================================
+
+export type WindowBehaviorDeps = {
+	userDataPath: string;
+};
+
+/**
+ * Creates the window behavior settings store. The window modules read it
+ * on every blur, so updates apply immediately.
+ */
+export const createWindowBehavior = (deps: WindowBehaviorDeps) => {
+	const filePath = getWindowBehaviorSettingsPath(deps.userDataPath);
+	let settings = readWindowBehaviorSettingsFromFile(filePath);
+
+	const getSettings = (): WindowBehaviorSettings => ({ ...settings });
+
+	const updateSettings = (input: unknown): WindowBehaviorSettings => {
+		const next = parseWindowBehaviorSettings(input, settings);
+		writeWindowBehaviorSettingsToFile(filePath, next);
+		settings = next;
+		return getSettings();
+	};
+
+	return { getSettings, updateSettings };
+};
+
+export type WindowBehavior = ReturnType<typeof createWindowBehavior>;
@@ -0,0 +1,70 @@
+import { describe, expect, it } from "vitest";
+import {
+	computePalettePosition,
+	computeWindowPosition,
+	PICKER_WINDOW_SIZE,
+} from "./window-position.js";
+
+const size = { width: 450, height: 600 };
+const workArea = { x: 0, y: 25, width: 1440, height: 875 };
+
+describe("computeWindowPosition", () => {
+	it("opens at the cursor when the window fits", () => {
+		expect(computeWindowPosition({ x: 300, y: 200 }, size, workArea)).toEqual({
+			x: 300,
+			y: 200,
+		});
+	});
+
+	it("keeps the window inside the work area", () => {
+		expect(computeWindowPosition({ x: 1400, y: 880 }, size, workArea)).toEqual(
+			{ x: 990, y: 300 },
+		);
+		expect(computeWindowPosition({ x: 10, y: 5 }, size, workArea)).toEqual({
+			x: 10,
+			y: 25,
+		});
+	});
+
+	it("uses the coordinates of secondary displays", () => {
+		const external = { x: -1920, y: -200, width: 1920, height: 1080 };
+
+		expect(computeWindowPosition({ x: -5, y: -300 }, size, external)).toEqual({
+			x: -450,
+			y: -200,
+		});
+	});
+
+	it("aligns oversized windows to the top-left corner", () => {
+		const small = { x: 0, y: 0, width: 400, height: 500 };
+
+		expect(computeWindowPosition({ x: 200, y: 200 }, size, small)).toEqual({
+			x: 0,
+			y: 0,
+		});
+	});
+
+	it("rounds fractional coordinates", () => {
+		expect(
+			computeWindowPosition({ x: 100.6, y: 100.2 }, size, workArea),
+		).toEqual({ x: 101, y: 100 });
+	});
+});
+
+describe("computePalettePosition", () => {
+	it("centers the palette near the top of the work area", () => {
+		expect(computePalettePosition(PICKER_WINDOW_SIZE, workArea)).toEqual({
+			x: 400,
+			y: 139,
+		});
+	});
+
+	it("aligns oversized palettes to the top-left corner", () => {
+		const small = { x: -1024, y: 0, width: 600, height: 400 };
+
+		expect(computePalettePosition(PICKER_WINDOW_SIZE, small)).toEqual({
+			x: -1024,
+			y: 0,
+		});
+	});
+});
@@ -0,0 +1,45 @@
+import type { Point, Rectangle, Size } from "electron";
+
+/**
+ * Places the window's top-left corner at the cursor, shifted back inside
+ * the work area (screen minus menu bar, dock, and taskbar) of the display
+ * the cursor is on. A window larger than the work area is aligned to its
+ * top-left corner.
+ * Pure function. Returns whole-pixel coordinates for `setPosition`.
+ */
+export const computeWindowPosition = (
+	cursor: Point,
+	windowSize: Size,
+	workArea: Rectangle,
+): Point => {
+	const clamp = (value: number, start: number, length: number, size: number) =>
+		Math.round(Math.max(start, Math.min(value, start + length - size)));
+
+	return {
+		x: clamp(cursor.x, workArea.x, workArea.width, windowSize.width),
+		y: clamp(cursor.y, workArea.y, workArea.height, windowSize.height),
+	};
+};
+
+/**
+ * Size of the quick picker window, a launcher-style palette.
+ */
+export const PICKER_WINDOW_SIZE: Size = { width: 640, height: 420 };
+
+/**
+ * Places a launcher palette horizontally centered, a quarter of the free
+ * space down from the top of the work area, like Spotlight. A window
+ * larger than the work area is aligned to its top-left corner.
+ * Pure function. Returns whole-pixel coordinates for `setPosition`.
+ */
+export const computePalettePosition = (
+	windowSize: Size,
+	workArea: Rectangle,
+): Point => ({
+	x: Math.round(
+		workArea.x + Math.max(0, (workArea.width - windowSize.width) / 2),
+	),
+	y: Math.round(
+		workArea.y + Math.max(0, (workArea.height - windowSize.height) / 4),
+	),
+});
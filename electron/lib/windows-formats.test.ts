@@ -0,0 +1,89 @@
+import { describe, expect, it } from "vitest";
+import {
+	packFormats,
+	readWindowsFormats,
+	toCfHtml,
+	toFormatWriterLine,
+	unpackFormats,
+} from "./windows-formats.js";
+
+describe("packFormats", () => {
+	it("round-trips formats through one buffer", () => {
+		const formats = [
+			{ format: "Art::GVML ClipFormat", data: Buffer.from([1, 2, 3]) },
+			{ format: "Csv", data: Buffer.from("a,b\r\n") },
+		];
+		expect(unpackFormats(packFormats(formats))).toEqual(formats);
+		expect(unpackFormats(Buffer.alloc(0))).toEqual([]);
+		const packed = packFormats(formats);
+		for (const length of [1, 20, 28]) {
+			expect(() => unpackFormats(packed.subarray(0, length))).toThrow(
+				"truncated",
+			);
+		}
+	});
+});
+
+describe("toCfHtml", () => {
+	it("points the offsets at the document and the fragment", () => {
+		const html = "<b>café</b>";
+		const data = toCfHtml(html);
+		const offset = (name: string) =>
+			Number(data.toString("utf8").match(new RegExp(`${name}:(\\d+)`))?.[1]);
+
+		expect(
+			data.toString("utf8", offset("StartHTML"), offset("StartHTML") + 6),
+		).toBe("<html>");
+		expect(
+			data.toString("utf8", offset("StartFragment"), offset("EndFragment")),
+		).toBe(html);
+		expect(offset("EndHTML")).toBe(data.length);
+	});
+});
+
+describe("toFormatWriterLine", () => {
+	it("encodes text, then name and data pairs", () => {
+		const fields = toFormatWriterLine({
+			text: "tab\tand\nline",
+			rtf: "{\\rtf1 hi}",
+			formats: [{ format: "Csv", data: Buffer.from("hi") }],
+		})
+			.split("\t")
+			.map((field) => Buffer.from(field, "base64").toString("utf8"));
+
+		expect(fields).toEqual([
+			"tab\tand\nline",
+			"Rich Text Format",
+			"{\\rtf1 hi}",
+			"Csv",
+			"hi",
+		]);
+	});
+});
+
+describe("readWindowsFormats", () => {
+	it("keeps the rich formats the clipboard holds", () => {
+		const held: Record<string, Buffer> = {
+			"XML Spreadsheet": Buffer.from("<Workbook/>"),
+			Csv: Buffer.from("a,b"),
+		};
+		const formats = readWindowsFormats({
+			readBuffer: (format) => held[format] ?? Buffer.alloc(0),
+		});
+
+		expect(formats.map(({ format }) => format)).toEqual([
+			"XML Spreadsheet",
+			"Csv",
+		]);
+	});
+
+	it("keeps none when they are over the size cap", () => {
+		const large = Buffer.alloc(9 * 1024 * 1024);
+		expect(
+			readWindowsFormats({
+				readBuffer: (format) =>
+					format === "Biff12" || format === "Csv" ? large : Buffer.alloc(0),
+			}),
+		).toEqual([]);
+	});
+});
@@ -0,0 +1,294 @@
+import { type ChildProcess, spawn } from "node:child_process";
+import readline from "node:readline";
+import type { ClipboardBuffers } from "./file-lists.js";
+
+/**
+ * A clipboard format kept as raw bytes, under its registered name.
+ */
+export type ClipboardFormat = {
+	format: string;
+	data: Buffer;
+};
+
+/**
+ * Starts the long-running writer process; injectable for tests.
+ */
+export type FormatWriterSpawner = (
+	command: string,
+	args: string[],
+) => ChildProcess;
+
+/**
+ * Registered Windows formats that carry more than text, HTML and RTF:
+ * Office drawing and sheet data, OLE embedding, and Chromium's custom
+ * web data. Windows has no way to list them through Electron, so only
+ * these are read.
+ */
+const WINDOWS_RICH_FORMATS = [
+	"Art::GVML ClipFormat",
+	"Office Drawing Shape Format",
+	"PowerPoint 12.0 Internal Shapes",
+	"Embed Source",
+	"Object Descriptor",
+	"Link Source",
+	"Link Source Descriptor",
+	"XML Spreadsheet",
+	"Biff12",
+	"Biff8",
+	"Csv",
+	"Chromium Web Custom MIME Data Format",
+];
+
+/**
+ * Largest total of extra formats kept per copy (bytes); a copy with more
+ * is stored with its text, HTML and RTF only.
+ */
+const MAX_FORMATS_BYTES = 16 * 1024 * 1024;
+
+/**
+ * Writes each line of formats to the clipboard as one data object, in C#
+ * compiled by PowerShell. A line holds tab-separated Base64 fields: the
+ * text, then name and data pairs. Prints `ready` once compiled, then
+ * `ok` or `error <message>` per line.
+ */
+const FORMAT_WRITER_SOURCE = `
+using System;
+using System.IO;
+using System.Text;
+using System.Windows.Forms;
+public static class ClipmanFormatWriter {
+	static void Emit(string line) {
+		try { Console.Out.WriteLine(line); Console.Out.Flush(); } catch { Environment.Exit(0); }
+	}
+	static byte[] Decode(string field) { return Convert.FromBase64String(field); }
+	public static void Run() {
+		Emit("ready");
+		string line;
+		while ((line = Console.In.ReadLine()) != null) {
+			try {
+				string[] fields = line.Split('\\t');
+				DataObject data = new DataObject();
+				data.SetData(DataFormats.UnicodeText, Encoding.UTF8.GetString(Decode(fields[0])));
+				for (int i = 1; i + 1 < fields.Length; i += 2) {
+					data.SetData(Encoding.UTF8.GetString(Decode(fields[i])), false, new MemoryStream(Decode(fields[i + 1])));
+				}
+				Clipboard.SetDataObject(data, true, 10, 50);
+				Emit("ok");
+			} catch (Exception error) {
+				Emit("error " + error.Message.Replace("\\r", " ").Replace("\\n", " "));
+			}
+		}
+	}
+}
+`;
+
+/**
+ * The writer script as `-EncodedCommand` (Base64 of UTF-16LE), which
+ * keeps its quotes and line breaks intact on the command line.
+ */
+const FORMAT_WRITER_COMMAND = Buffer.from(
+	`Add-Type -ReferencedAssemblies System.Windows.Forms -TypeDefinition @'\n${FORMAT_WRITER_SOURCE}\n'@\n[ClipmanFormatWriter]::Run()`,
+	"utf16le",
+).toString("base64");
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Packs formats into one buffer for storage: per format a 2-byte name
+ * length, the UTF-8 name, a 4-byte data length and the data.
+ * Pure function.
+ */
+export const packFormats = (formats: readonly ClipboardFormat[]): Buffer =>
+	Buffer.concat(
+		formats.flatMap(({ format, data }) => {
+			const name = Buffer.from(format, "utf8");
+			const header = Buffer.alloc(6 + name.length);
+			header.writeUInt16LE(name.length, 0);
+			name.copy(header, 2);
+			header.writeUInt32LE(data.length, 2 + name.length);
+			return [header, data];
+		}),
+	);
+
+/**
+ * Reads formats packed by `packFormats`.
+ * Pure function.
+ *
+ * @throws if the buffer is truncated
+ */
+export const unpackFormats = (packed: Buffer): ClipboardFormat[] => {
+	const formats: ClipboardFormat[] = [];
+	let offset = 0;
+	const truncated = () => new Error("Stored clipboard formats are truncated");
+	while (offset < packed.length) {
+		if (offset + 2 > packed.length) throw truncated();
+		const nameEnd = offset + 2 + packed.readUInt16LE(offset);
+		if (nameEnd + 4 > packed.length) throw truncated();
+		const dataEnd = nameEnd + 4 + packed.readUInt32LE(nameEnd);
+		if (dataEnd > packed.length) throw truncated();
+		formats.push({
+			format: packed.toString("utf8", offset + 2, nameEnd),
+			data: Buffer.from(packed.subarray(nameEnd + 4, dataEnd)),
+		});
+		offset = dataEnd;
+	}
+	return formats;
+};
+
+/**
+ * Wraps HTML in the `HTML Format` header Windows apps expect, with the
+ * byte offsets of the document and the fragment; the markup around it
+ * matches what Chromium writes, so reading it back gives the same HTML.
+ * Pure function.
+ */
+export const toCfHtml = (html: string): Buffer => {
+	const prefix = "<html>\r\n<body>\r\n<!--StartFragment-->";
+	const suffix = "<!--EndFragment-->\r\n</body>\r\n</html>";
+	// Offsets are padded to a fixed width, so the header length is known
+	const pad = (offset: number) => String(offset).padStart(10, "0");
+	const header = (start: number, end: number, from: number, to: number) =>
+		`Version:0.9\r\nStartHTML:${pad(start)}\r\nEndHTML:${pad(end)}\r\nStartFragment:${pad(from)}\r\nEndFragment:${pad(to)}\r\n`;
+	const start = Buffer.byteLength(header(0, 0, 0, 0));
+	const from = start + Buffer.byteLength(prefix);
+	const to = from + Buffer.byteLength(html);
+	const end = to + Buffer.byteLength(suffix);
+	return Buffer.from(
+		`${header(start, end, from, to)}${prefix}${html}${suffix}`,
+		"utf8",
+	);
+};
+
+/**
+ * The writer line placing text, HTML, RTF and extra formats on the
+ * clipboard together.
+ * Pure function.
+ */
+export const toFormatWriterLine = (clip: {
+	text: string;
+	html?: string;
+	rtf?: string;
+	formats: readonly ClipboardFormat[];
+}): string => {
+	const all: ClipboardFormat[] = [];
+	if (clip.html) {
+		all.push({ format: "HTML Format", data: toCfHtml(clip.html) });
+	}
+	if (clip.rtf) {
+		all.push({ format: "Rich Text Format", data: Buffer.from(clip.rtf) });
+	}
+	all.push(...clip.formats);
+	const encode = (value: string | Buffer) =>
+		Buffer.from(value).toString("base64");
+	return [
+		encode(clip.text),
+		...all.flatMap(({ format, data }) => [encode(format), encode(data)]),
+	].join("\t");
+};
+
+/**
+ * Reads the rich Windows formats the clipboard holds.
+ * @returns none if they add up to more than the size cap
+ */
+export const readWindowsFormats = (
+	clipboard: Pick<ClipboardBuffers, "readBuffer">,
+): ClipboardFormat[] => {
+	const formats: ClipboardFormat[] = [];
+	let total = 0;
+	for (const format of WINDOWS_RICH_FORMATS) {
+		const data = clipboard.readBuffer(format);
+		if (data.length === 0) continue;
+		total += data.length;
+		if (total > MAX_FORMATS_BYTES) return [];
+		formats.push({ format, data });
+	}
+	return formats;
+};
+
+// ============================================================================
+// Format Writer Module
+// ============================================================================
+
+const spawnWriter: FormatWriterSpawner = (command, args) =>
+	spawn(command, args, { stdio: ["pipe", "pipe", "ignore"] });
+
+/**
+ * Creates the Windows clipboard format writer. Electron replaces the
+ * clipboard on every `writeBuffer` call, so it cannot place custom
+ * formats next to the text; a PowerShell helper, started on first use
+ * and kept running, writes them all as one data object. Writes are
+ * answered in order.
+ */
+export const createFormatWriter = (
+	deps: { spawn?: FormatWriterSpawner } = {},
+) => {
+	const spawnProcess = deps.spawn ?? spawnWriter;
+	let child: ChildProcess | null = null;
+	const pending: Array<{
+		resolve: () => void;
+		reject: (error: Error) => void;
+	}> = [];
+
+	const failPending = (error: Error) => {
+		for (const write of pending.splice(0)) write.reject(error);
+	};
+
+	const ensureStarted = (): ChildProcess => {
+		if (child) return child;
+		const writer = spawnProcess("powershell.exe", [
+			"-NoProfile",
+			"-NonInteractive",
+			"-Sta",
+			"-EncodedCommand",
+			FORMAT_WRITER_COMMAND,
+		]);
+		child = writer;
+		if (writer.stdout) {
+			const lines = readline.createInterface({ input: writer.stdout });
+			lines.on("line", (line) => {
+				if (child !== writer) return;
+				const message = line.trim();
+				if (message === "ok") {
+					pending.shift()?.resolve();
+				} else if (message.startsWith("error ")) {
+					pending.shift()?.reject(new Error(message.slice(6)));
+				}
+			});
+		}
+		const exited = (error: Error) => {
+			if (child !== writer) return;
+			child = null;
+			failPending(error);
+		};
+		writer.stdin?.on("error", () => {});
+		writer.on("error", (error) =>
+			exited(new Error(`Clipboard format writer failed: ${error.message}`)),
+		);
+		writer.on("exit", (code) =>
+			exited(new Error(`Clipboard format writer exited with code ${code}`)),
+		);
+		return writer;
+	};
+
+	/**
+	 * Replaces the clipboard with the text, HTML, RTF and extra formats.
+	 */
+	const write = (clip: Parameters<typeof toFormatWriterLine>[0]) =>
+		new Promise<void>((resolve, reject) => {
+			const writer = ensureStarted();
+			pending.push({ resolve, reject });
+			writer.stdin?.write(`${toFormatWriterLine(clip)}\n`);
+		});
+
+	const stop = () => {
+		const writer = child;
+		child = null;
+		failPending(new Error("Clipboard format writer stopped"));
+		writer?.kill();
+	};
+
+	return { write, stop };
+};
+
+export type FormatWriter = ReturnType<typeof createFormatWriter>;
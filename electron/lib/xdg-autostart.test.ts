@@ -0,0 +1,89 @@
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import { afterEach, describe, expect, it } from "vitest";
+import {
+	createXdgAutostart,
+	formatAutostartEntry,
+	getAutostartPath,
+	isAutostartEntryEnabled,
+	quoteExecArg,
+} from "./xdg-autostart.js";
+
+describe("quoteExecArg", () => {
+	it("quotes only arguments that need it", () => {
+		expect(quoteExecArg("/usr/bin/clipboard-manager")).toBe(
+			"/usr/bin/clipboard-manager",
+		);
+		expect(quoteExecArg("/opt/Clipboard Manager/app")).toBe(
+			'"/opt/Clipboard Manager/app"',
+		);
+		expect(quoteExecArg('say "$HOME"')).toBe('"say \\"\\$HOME\\""');
+		expect(quoteExecArg("100%")).toBe("100%%");
+	});
+});
+
+describe("isAutostartEntryEnabled", () => {
+	it("reads entries turned off by the desktop", () => {
+		const entry = formatAutostartEntry({
+			name: "Clipboard Manager",
+			command: ["/usr/bin/clipboard-manager"],
+		});
+
+		expect(isAutostartEntryEnabled(entry)).toBe(true);
+		expect(isAutostartEntryEnabled(`${entry}Hidden=true\n`)).toBe(false);
+		expect(
+			isAutostartEntryEnabled(
+				entry.replace("Autostart-enabled=true", "Autostart-enabled=false"),
+			),
+		).toBe(false);
+	});
+});
+
+describe("getAutostartPath", () => {
+	it("uses XDG_CONFIG_HOME when set", () => {
+		expect(getAutostartPath({}, "/home/ada")).toBe(
+			path.join(
+				"/home/ada",
+				".config",
+				"autostart",
+				"clipboard-manager.desktop",
+			),
+		);
+		expect(getAutostartPath({ XDG_CONFIG_HOME: "/cfg" }, "/home/ada")).toBe(
+			path.join("/cfg", "autostart", "clipboard-manager.desktop"),
+		);
+	});
+});
+
+describe("createXdgAutostart", () => {
+	const tempDirs: string[] = [];
+
+	afterEach(() => {
+		for (const dir of tempDirs.splice(0)) {
+			fs.rmSync(dir, { recursive: true, force: true });
+		}
+	});
+
+	it("writes and removes the autostart entry", () => {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-autostart-"));
+		tempDirs.push(dir);
+		const filePath = path.join(dir, "autostart", "clipboard-manager.desktop");
+		const autostart = createXdgAutostart({
+			filePath,
+			name: "Clipboard Manager",
+			command: ["/opt/Clipboard Manager/clipboard-manager"],
+		});
+
+		expect(autostart.getLoginItemSettings()).toEqual({ openAtLogin: false });
+
+		autostart.setLoginItemSettings({ openAtLogin: true, openAsHidden: true });
+		expect(autostart.getLoginItemSettings()).toEqual({ openAtLogin: true });
+		expect(fs.readFileSync(filePath, "utf-8")).toContain(
+			'Exec="/opt/Clipboard Manager/clipboard-manager"\n',
+		);
+
+		autostart.setLoginItemSettings({ openAtLogin: false, openAsHidden: true });
+		expect(fs.existsSync(filePath)).toBe(false);
+	});
+});
@@ -0,0 +1,123 @@
+import fs from "node:fs";
+import path from "node:path";
+import type {
+	LoginItemSettingsReader,
+	LoginItemSettingsWriter,
+} from "./launch-at-login.js";
+
+/**
+ * Electron's login items cover macOS and Windows only; Linux desktops
+ * start the entries in the XDG autostart folder instead.
+ */
+
+const AUTOSTART_FILENAME = "clipboard-manager.desktop";
+
+/**
+ * Characters that make an `Exec` argument need quotes, per the Desktop
+ * Entry specification.
+ */
+const RESERVED_EXEC_CHARS = /[\s"'\\><~|&;$*?#()`]/;
+
+const DISABLED_ENTRY_PATTERN =
+	/^\s*(?:Hidden\s*=\s*true|X-GNOME-Autostart-enabled\s*=\s*false)\s*$/im;
+
+// ============================================================================
+// Pure Functions
+// ============================================================================
+
+/**
+ * Quotes an argument for an `Exec` line when needed, escaping `"`, `` ` ``,
+ * `$` and `\`; `%` is doubled, as it starts a field code.
+ * Pure function.
+ */
+export const quoteExecArg = (arg: string): string => {
+	const escaped = arg.replace(/%/g, "%%");
+	if (escaped !== "" && !RESERVED_EXEC_CHARS.test(escaped)) return escaped;
+	return `"${escaped.replace(/["`$\\]/g, "\\$&")}"`;
+};
+
+/**
+ * Formats the autostart entry launching the app.
+ * Pure function.
+ */
+export const formatAutostartEntry = (options: {
+	name: string;
+	command: readonly string[];
+}): string =>
+	[
+		"[Desktop Entry]",
+		"Type=Application",
+		`Name=${options.name}`,
+		`Exec=${options.command.map(quoteExecArg).join(" ")}`,
+		"Terminal=false",
+		"X-GNOME-Autostart-enabled=true",
+		"",
+	].join("\n");
+
+/**
+ * Whether an autostart entry is turned on: desktops skip entries marked
+ * `Hidden=true` or, on GNOME, `X-GNOME-Autostart-enabled=false`.
+ * Pure function.
+ */
+export const isAutostartEntryEnabled = (text: string): boolean =>
+	!DISABLED_ENTRY_PATTERN.test(text);
+
+// ============================================================================
+// Autostart Entry
+// ============================================================================
+
+/**
+ * Path of the app's entry in the autostart folder,
+ * `$XDG_CONFIG_HOME/autostart` (`~/.config/autostart` by default).
+ */
+export const getAutostartPath = (
+	env: NodeJS.ProcessEnv,
+	homePath: string,
+): string =>
+	path.join(
+		env.XDG_CONFIG_HOME || path.join(homePath, ".config"),
+		"autostart",
+		AUTOSTART_FILENAME,
+	);
+
+export type XdgAutostartDeps = {
+	/** Path of the entry, from `getAutostartPath` */
+	filePath: string;
+	name: string;
+	/** Executable and arguments that start the app */
+	command: readonly string[];
+};
+
+/**
+ * Creates login item accessors backed by an XDG autostart entry, for
+ * `createLaunchAtLoginModule` on Linux. `openAsHidden` is ignored, as
+ * the `startMinimized` preference decides whether the window shows.
+ */
+export const createXdgAutostart = (deps: XdgAutostartDeps) => {
+	const getLoginItemSettings: LoginItemSettingsReader = () => {
+		try {
+			const text = fs.readFileSync(deps.filePath, "utf-8");
+			return { openAtLogin: isAutostartEntryEnabled(text) };
+		} catch (error) {
+			if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+				return { openAtLogin: false };
+			}
+			throw error;
+		}
+	};
+
+	const setLoginItemSettings: LoginItemSettingsWriter = ({ openAtLogin }) => {
+		if (!openAtLogin) {
+			fs.rmSync(deps.filePath, { force: true });
+			return;
+		}
+		fs.mkdirSync(path.dirname(deps.filePath), { recursive: true });
+		fs.writeFileSync(
+			deps.filePath,
+			formatAutostartEntry({ name: deps.name, command: deps.command }),
+			"utf-8",
+		);
+	};
+
+	return { getLoginItemSettings, setLoginItemSettings };
+};
@@ -0,0 +1,62 @@
+import zlib from "node:zlib";
+import { describe, expect, it } from "vitest";
+import { createZipArchive } from "./zip.js";
+
+/**
+ * Reads the archive back through its central directory, as unzip does.
+ */
+const readZip = (archive: Buffer) => {
+	const end = archive.length - 22;
+	expect(archive.readUInt32LE(end)).toBe(0x06054b50);
+	const count = archive.readUInt16LE(end + 10);
+	let position = archive.readUInt32LE(end + 16);
+	const entries: { name: string; crc: number; text: string }[] = [];
+	for (let index = 0; index < count; index++) {
+		expect(archive.readUInt32LE(position)).toBe(0x02014b50);
+		const nameLength = archive.readUInt16LE(position + 28);
+		const name = archive.toString(
+			"utf-8",
+			position + 46,
+			position + 46 + nameLength,
+		);
+		const size = archive.readUInt32LE(position + 20);
+		const local = archive.readUInt32LE(position + 42);
+		expect(archive.readUInt32LE(local)).toBe(0x04034b50);
+		const start = local + 30 + archive.readUInt16LE(local + 26);
+		entries.push({
+			name,
+			crc: archive.readUInt32LE(position + 16),
+			text: zlib
+				.inflateRawSync(archive.subarray(start, start + size))
+				.toString("utf-8"),
+		});
+		position += 46 + nameLength;
+	}
+	return entries;
+};
+
+describe("createZipArchive", () => {
+	it("round-trips entries through the central directory", () => {
+		const archive = createZipArchive(
+			[
+				{ name: "check.txt", data: "123456789" },
+				{ name: "logs/main.log", data: Buffer.from("héllo\n") },
+			],
+			new Date(2026, 9, 14, 12, 30, 10),
+		);
+
+		expect(readZip(archive)).toEqual([
+			{ name: "check.txt", crc: 0xcbf43926, text: "123456789" },
+			{ name: "logs/main.log", crc: expect.any(Number), text: "héllo\n" },
+		]);
+		expect(archive.readUInt16LE(12)).toBe((46 << 9) | (10 << 5) | 14);
+	});
+
+	it("rejects names that leave the archive root", () => {
+		for (const name of ["", "/etc/passwd", "../up.txt", "a//b", "a\\b"]) {
+			expect(() => createZipArchive([{ name, data: "" }], new Date())).toThrow(
+				"Invalid zip entry",
+			);
+		}
+	});
+});
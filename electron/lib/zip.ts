@@ -0,0 +1,144 @@
+import zlib from "node:zlib";
+
+/**
+ * Minimal zip writer: enough to bundle a handful of text files into an
+ * archive every desktop can open. Entries are deflated, names are UTF-8,
+ * and archives past the classic 4 GB / 65535 entry limits (zip64) are not
+ * supported.
+ */
+
+export type ZipEntry = {
+	/** Path inside the archive, with forward slashes */
+	name: string;
+	data: Buffer | string;
+};
+
+const LOCAL_HEADER_SIGNATURE = 0x04034b50;
+const CENTRAL_HEADER_SIGNATURE = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE = 0x06054b50;
+
+/** Version 2.0: deflate and folders */
+const ZIP_VERSION = 20;
+/** General purpose flag bit 11: names are UTF-8 */
+const UTF8_FLAG = 0x0800;
+const DEFLATE_METHOD = 8;
+
+const MAX_ENTRIES = 0xffff;
+const MAX_SIZE = 0xffffffff;
+
+const CRC_TABLE = (() => {
+	const table = new Uint32Array(256);
+	for (let n = 0; n < 256; n++) {
+		let c = n;
+		for (let k = 0; k < 8; k++) {
+			c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+		}
+		table[n] = c >>> 0;
+	}
+	return table;
+})();
+
+const crc32 = (data: Buffer): number => {
+	let crc = 0xffffffff;
+	for (const byte of data) {
+		crc = CRC_TABLE[(crc ^ byte) & 0xff] ^ (crc >>> 8);
+	}
+	return (crc ^ 0xffffffff) >>> 0;
+};
+
+/**
+ * MS-DOS time and date fields, in local time as zip tools expect.
+ * Dates before 1980 cannot be represented and are clamped.
+ */
+const toDosDateTime = (date: Date): { time: number; date: number } => {
+	const year = Math.max(date.getFullYear(), 1980);
+	return {
+		time:
+			(date.getHours() << 11) |
+			(date.getMinutes() << 5) |
+			Math.floor(date.getSeconds() / 2),
+		date: ((year - 1980) << 9) | ((date.getMonth() + 1) << 5) | date.getDate(),
+	};
+};
+
+const assertValidEntryName = (name: string): void => {
+	if (
+		name.length === 0 ||
+		name.startsWith("/") ||
+		name.includes("\\") ||
+		name.split("/").some((part) => part === "" || part === "..")
+	) {
+		throw new Error(`Invalid zip entry: ${JSON.stringify(name)}`);
+	}
+};
+
+/**
+ * Builds a zip archive of the entries, all stamped with `modifiedAt`.
+ */
+export const createZipArchive = (
+	entries: readonly ZipEntry[],
+	modifiedAt: Date,
+): Buffer => {
+	if (entries.length > MAX_ENTRIES) {
+		throw new Error(`Invalid zip: more than ${MAX_ENTRIES} entries`);
+	}
+	const stamp = toDosDateTime(modifiedAt);
+	const parts: Buffer[] = [];
+	const centralHeaders: Buffer[] = [];
+	let offset = 0;
+
+	for (const entry of entries) {
+		assertValidEntryName(entry.name);
+		const name = Buffer.from(entry.name, "utf-8");
+		const data =
+			typeof entry.data === "string"
+				? Buffer.from(entry.data, "utf-8")
+				: entry.data;
+		const compressed = zlib.deflateRawSync(data);
+		if (data.length > MAX_SIZE || offset + compressed.length > MAX_SIZE) {
+			throw new Error("Invalid zip: larger than 4 GB");
+		}
+		const crc = crc32(data);
+
+		const local = Buffer.alloc(30);
+		local.writeUInt32LE(LOCAL_HEADER_SIGNATURE, 0);
+		local.writeUInt16LE(ZIP_VERSION, 4);
+		local.writeUInt16LE(UTF8_FLAG, 6);
+		local.writeUInt16LE(DEFLATE_METHOD, 8);
+		local.writeUInt16LE(stamp.time, 10);
+		local.writeUInt16LE(stamp.date, 12);
+		local.writeUInt32LE(crc, 14);
+		local.writeUInt32LE(compressed.length, 18);
+		local.writeUInt32LE(data.length, 22);
+		local.writeUInt16LE(name.length, 26);
+		local.writeUInt16LE(0, 28);
+
+		const central = Buffer.alloc(46);
+		central.writeUInt32LE(CENTRAL_HEADER_SIGNATURE, 0);
+		central.writeUInt16LE(ZIP_VERSION, 4);
+		central.writeUInt16LE(ZIP_VERSION, 6);
+		central.writeUInt16LE(UTF8_FLAG, 8);
+		central.writeUInt16LE(DEFLATE_METHOD, 10);
+		central.writeUInt16LE(stamp.time, 12);
+		central.writeUInt16LE(stamp.date, 14);
+		central.writeUInt32LE(crc, 16);
+		central.writeUInt32LE(compressed.length, 20);
+		central.writeUInt32LE(data.length, 24);
+		central.writeUInt16LE(name.length, 28);
+		central.writeUInt32LE(offset, 42);
+
+		parts.push(local, name, compressed);
+		centralHeaders.push(central, name);
+		offset += local.length + name.length + compressed.length;
+	}
+
+	const centralDirectory = Buffer.concat(centralHeaders);
+	const end = Buffer.alloc(22);
+	end.writeUInt32LE(END_OF_CENTRAL_DIRECTORY_SIGNATURE, 0);
+	end.writeUInt16LE(entries.length, 8);
+	end.writeUInt16LE(entries.length, 10);
+	end.writeUInt32LE(centralDirectory.length, 12);
+	end.writeUInt32LE(offset, 16);
+
+	return Buffer.concat([...parts, centralDirectory, end]);
+};
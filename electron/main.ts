@@ -1,3 +1,6 @@
+import { createHash, randomUUID } from "node:crypto";
+import fs from "node:fs";
+import os from "node:os";
 import path from "node:path";
 import { fileURLToPath } from "node:url";
 import Database from "better-sqlite3";
@@ -9,7 +12,15 @@ import {
 	globalShortcut,
 	ipcMain,
 	Menu,
+	type MenuItemConstructorOptions,
+	Notification,
 	nativeImage,
+	powerMonitor,
+	safeStorage,
+	screen,
+	type Size,
+	shell,
+	systemPreferences,
 	Tray,
 } from "electron";
 import {
@@ -17,10 +28,330 @@ import {
 	promptAccessibilityIfNeeded,
 	shouldSkipAccessibilityOnStartup,
 } from "./lib/accessibility-prompt.js";
-import { simulatePaste } from "./lib/applescript.js";
+import { createBoardSettings } from "./lib/board-settings.js";
+import { createBoards, pickLanAddress } from "./lib/boards.js";
+import { createCaptureBatcher } from "./lib/capture-batcher.js";
+import { createClipboardListener } from "./lib/clipboard-listener.js";
+import {
+	type CaptureLimits,
+	createCaptureLimits,
+	type LimitedClip,
+} from "./lib/capture-limits.js";
+import {
+	type CapturePause,
+	createCapturePause,
+	SNOOZE_PRESETS_MINUTES,
+} from "./lib/capture-pause.js";
+import {
+	type CaptureItemType,
+	type CaptureRuleOutcome,
+	createCaptureRules,
+} from "./lib/capture-rules.js";
+import {
+	type CaptureSounds,
+	createCaptureSounds,
+	isSoundEvent,
+} from "./lib/capture-sounds.js";
+import { createClipboardAutoClear } from "./lib/clipboard-auto-clear.js";
+import {
+	type ClipboardRestore,
+	createClipboardRestore,
+} from "./lib/clipboard-restore.js";
+import {
+	type ClipboardSnapshot,
+	createClipboardWatcher,
+	DEFAULT_WATCH_INTERVAL_MS,
+	hasSensitiveFormat,
+} from "./lib/clipboard-watcher.js";
+import {
+	type FileRestoreMode,
+	parseFileRestoreMode,
+	readFileContent,
+} from "./lib/file-contents.js";
+import {
+	formatStoredFileList,
+	parseStoredFileList,
+	readFileList,
+	writeFileList,
+} from "./lib/file-lists.js";
+import {
+	type CollectionRepository,
+	createCollectionRepository,
+	type MoveCollectionItemOptions,
+} from "./lib/collection-repository.js";
+import { COLOR_FORMATS, convertColor, isColorFormat } from "./lib/colors.js";
+import {
+	type CommandItem,
+	createCommandActions,
+} from "./lib/command-actions.js";
+import { resolveControlSocketPath } from "./lib/control-protocol.js";
+import {
+	type ControlHandlers,
+	createControlServer,
+} from "./lib/control-server.js";
+import { importCopyQ } from "./lib/copyq-import.js";
+import { createDataLocation } from "./lib/data-location.js";
+import { configureConnection } from "./lib/db-connection.js";
+import {
+	checkDatabase,
+	type DatabaseCheck,
+	type DatabaseRecovery,
+	recoverDatabase,
+} from "./lib/db-health.js";
+import { createDBusService, type DBusHandlers } from "./lib/dbus-service.js";
+import { createDiagnostics } from "./lib/diagnostics.js";
+import { importDitto } from "./lib/ditto-import.js";
+import { createDoubleCopy } from "./lib/double-copy.js";
+import type { HistoryEvent } from "./lib/event-stream.js";
+import { guardHandlers } from "./lib/handler-guards.js";
+import {
+	exportHistory,
+	parseHistoryExportRequest,
+} from "./lib/history-export.js";
+import {
+	type HistoryImportReport,
+	importHistory,
+} from "./lib/history-import.js";
+import {
+	type BatchOperation,
+	computeContentHash,
+	createHistoryRepository,
+	type HistoryRepository,
+	type HistoryRow,
+	type ImageSource,
+	isEmptyText,
+	type ListHistoryOptions,
+	type ListPageOptions,
+	type NewHistoryItem,
+	type SearchHistoryOptions,
+	type SelectionSource,
+} from "./lib/history-repository.js";
+import {
+	createHttpApiServer,
+	type HttpApiHandlers,
+	toApiItem,
+} from "./lib/http-api-server.js";
+import {
+	createHttpApiSettings,
+	type HttpApiSettings,
+} from "./lib/http-api-settings.js";
+import { createFaviconCache } from "./lib/favicons.js";
+import {
+	createForegroundAppResolver,
+	type SourceApp,
+} from "./lib/foreground-app.js";
+import { createFocusTracker } from "./lib/focus-restore.js";
+import { createKeyHook } from "./lib/key-hook.js";
+import { formatText, parseFormatRequest } from "./lib/formatters.js";
+import {
+	createI18n,
+	type I18n,
+	type Translate,
+	translateEnglish,
+} from "./lib/i18n.js";
+import {
+	createImageConverter,
+	imageFormatForPath,
+	parseImageConversion,
+	toImageClipboardFormat,
+} from "./lib/image-conversion.js";
+import {
+	fitWithin,
+	flattenOnWhite,
+	isOpaque,
+	PREVIEW_MAX_EDGE,
+	STREAMED_IMAGE_BYTES,
+	stripPngMetadata,
+	THUMBNAIL_JPEG_QUALITY,
+	THUMBNAIL_MAX_EDGE,
+} from "./lib/images.js";
+import { hashBytes, hashFile, parseHashAlgorithm } from "./lib/item-hash.js";
+import { createItemSummaries } from "./lib/item-summaries.js";
+import {
+	createJumpList,
+	JUMP_LIST_ITEMS,
+	type JumpList,
+} from "./lib/jump-list.js";
+import {
+	type AccessAction,
+	type AccessSurface,
+	createAccessLogRepository,
+	parseAccessLogQuery,
+} from "./lib/access-log-repository.js";
+import { createAppExclusions } from "./lib/app-exclusions.js";
+import { createAppLockModule } from "./lib/app-lock.js";
+import { type AppendCopy, createAppendCopy } from "./lib/append-copy.js";
+import {
+	createAttachmentStore,
+	getAttachmentKey,
+	type KeyProtector,
+} from "./lib/attachment-store.js";
+import { createBackupModule } from "./lib/backup.js";
+import { createClipboardStack } from "./lib/clipboard-stack.js";
 import { createLaunchAtLoginModule } from "./lib/launch-at-login.js";
+import {
+	createWipeConfirmation,
+	wipeHistoryDatabase,
+} from "./lib/history-wipe.js";
+import { createLinkPreviews } from "./lib/link-previews.js";
+import { createLogger } from "./lib/logger.js";
+import { importMaccy } from "./lib/maccy-import.js";
+import { createMaintenanceModule } from "./lib/maintenance.js";
+import {
+	htmlToMarkdown,
+	markdownToHtml,
+	parseMarkupConversionRequest,
+} from "./lib/markdown.js";
 import { runMigrations } from "./lib/migrations.js";
+import {
+	createNotificationRules,
+	type NotificationRules,
+} from "./lib/notification-rules.js";
+import { createOcrEngine, createRecognitionQueue } from "./lib/ocr.js";
+import { resolveOpenUrl, resolveRevealPath } from "./lib/open-item.js";
+import {
+	computeDifferenceHash,
+	PERCEPTUAL_HASH_GRID,
+	parseNearDuplicateDistance,
+} from "./lib/perceptual-hash.js";
+import { createPlatformAuthenticator } from "./lib/os-auth.js";
+import {
+	createKeyRepeater,
+	createPasteKeystroke,
+} from "./lib/paste-keystroke.js";
+import { createPasteQueue } from "./lib/paste-queue.js";
+import {
+	createPowerThrottle,
+	type PowerThrottle,
+	type PowerThrottleStatus,
+} from "./lib/power-throttle.js";
 import { createPreferencesStore } from "./lib/preferences.js";
+import {
+	createPrimarySelection,
+	type PrimarySelection,
+} from "./lib/primary-selection.js";
+import { createProfiles, type ProfilesStatus } from "./lib/profiles.js";
+import { createQrDecoder } from "./lib/qr-codes.js";
+import { encodeQr, renderQrBitmap } from "./lib/qr-encode.js";
+import { createQuickPaste } from "./lib/quick-paste.js";
+import {
+	createReadOnlyMode,
+	guardWrites,
+	type ReadOnlyMode,
+} from "./lib/read-only.js";
+import { createRegisterPrompt } from "./lib/register-prompt.js";
+import { createRegisterRepository } from "./lib/register-repository.js";
+import { createRemoteSync } from "./lib/remote-sync.js";
+import { createRemoteSyncSettings } from "./lib/remote-sync-settings.js";
+import { createRetentionModule } from "./lib/retention.js";
+import {
+	createSecretScanner,
+	maskSecretPreview,
+	redactSecret,
+} from "./lib/secret-scanning.js";
+import { createSemanticSearch } from "./lib/semantic-search.js";
+import {
+	createSettingsFile,
+	type ModuleSettings,
+} from "./lib/settings-file.js";
+import {
+	createChordManager,
+	getPasteRecentIndex,
+} from "./lib/shortcut-chords.js";
+import { createShortcutManager, isShortcutAction } from "./lib/shortcuts.js";
+import { SIMILAR_TEXT_MAX_DISTANCE } from "./lib/simhash.js";
+import {
+	exportSnippets,
+	parseSnippetExportRequest,
+} from "./lib/snippet-export.js";
+import {
+	createSnippetRepository,
+	type SnippetInput,
+	type SnippetRow,
+} from "./lib/snippet-repository.js";
+import {
+	listSnippetFields,
+	parseSnippetFieldValues,
+	resolveSnippet,
+	type SnippetContext,
+} from "./lib/snippets.js";
+import {
+	parseStartupOptions,
+	withProfileArg,
+} from "./lib/startup-options.js";
+import { createStatsRepository } from "./lib/stats-repository.js";
+import { createSync, type SyncHandlers } from "./lib/sync.js";
+import { createSyncSettings } from "./lib/sync-settings.js";
+import {
+	createTagRepository,
+	type TagRepository,
+} from "./lib/tag-repository.js";
+import { registerTextCompression } from "./lib/text-compression.js";
+import { diffTexts } from "./lib/text-diff.js";
+import { createTextExpander } from "./lib/text-expander.js";
+import {
+	applyTextTransform,
+	parseTextTransformRequest,
+	type TransformTarget,
+} from "./lib/text-transforms.js";
+import { createTrayClicks, type TrayClicks } from "./lib/tray-clicks.js";
+import {
+	formatTrayPreview,
+	formatTrayTooltip,
+	TRAY_RECENT_ITEMS,
+} from "./lib/tray-recent.js";
+import {
+	createTrayActivity,
+	formatUnreadBadge,
+	getTrayIconState,
+	TRAY_ICON_FILES,
+	type TrayActivity,
+	type TrayIconState,
+} from "./lib/tray-state.js";
+import { createTypeOut } from "./lib/type-out.js";
+import {
+	createUniversalClipboard,
+	REMOTE_CLIPBOARD_FORMAT,
+	type UniversalClipboard,
+} from "./lib/universal-clipboard.js";
+import {
+	createUpdater,
+	describeUpdateMenuItem,
+	type Updater,
+} from "./lib/updater.js";
+import {
+	assertCallbacksAllowed,
+	buildCallbackUrl,
+	changesClipboard,
+	createUrlActionSettings,
+	findUrlArgs,
+	getUrlActionPrompt,
+	parseUrlAction,
+	sendsContent,
+	URL_SCHEMES,
+	type UrlAction,
+	type UrlActionSettingsStore,
+} from "./lib/url-actions.js";
+import {
+	createUserScripts,
+	parseScriptRunRequest,
+} from "./lib/user-scripts.js";
+import { createWebhooks, type WebhookEvent } from "./lib/webhooks.js";
+import {
+	createWaylandClipboard,
+	isWaylandSession,
+} from "./lib/wayland-clipboard.js";
+import { createWindowBehavior } from "./lib/window-behavior.js";
+import {
+	computePalettePosition,
+	computeWindowPosition,
+	PICKER_WINDOW_SIZE,
+} from "./lib/window-position.js";
+import {
+	createFormatWriter,
+	readWindowsFormats,
+} from "./lib/windows-formats.js";
+import { createXdgAutostart, getAutostartPath } from "./lib/xdg-autostart.js";
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
@@ -29,153 +360,99 @@ const __dirname = path.dirname(__filename);
 // Types
 // ============================================================================
 
-/**
- * Represents a row from the history table.
- * Used for explicit typing of database query results.
- */
-type HistoryRow = {
-	id: number;
-	content: string;
-	type: string;
-	created_at: string;
-	is_favorite: number;
-	rtf: string | null;
-};
-
 /**
  * Clipboard data for reading/writing with optional RTF.
  */
 type ClipboardData = {
 	text: string;
 	rtf?: string;
+	html?: string;
 };
 
 // ============================================================================
-// Pure Functions
+// Constants
 // ============================================================================
 
 /**
- * Normalizes whitespace in text for near-duplicate detection.
- * Pure function - no side effects.
- *
- * @param text - Input text to normalize
- * @returns Text with trimmed edges and collapsed internal whitespace
+ * IPC channel used to tell the renderer that history rows changed.
  */
-export const normalizeWhitespace = (text: string): string =>
-	text.trim().replace(/\s+/g, " ");
+const HISTORY_CHANGED_CHANNEL = "history:changed";
 
 /**
- * Checks if new text is a near-duplicate of recent text.
- * Pure function - compares normalized versions.
- *
- * @param newText - New text to check
- * @param recentText - Recent text to compare against
- * @returns true if texts are duplicates (exact or near-duplicate)
+ * IPC channel used to tell the renderer which item was added, deleted or
+ * pinned, so it can update without refetching.
  */
-export const isNearDuplicate = (newText: string, recentText: string): boolean =>
-	newText === recentText ||
-	normalizeWhitespace(newText) === normalizeWhitespace(recentText);
+const HISTORY_EVENT_CHANNEL = "history:event";
 
 /**
- * Checks if text is empty or whitespace-only.
- * Pure function.
+ * IPC channel used to tell the renderer that the app lock engaged or released.
  */
-export const isEmptyText = (text: string | null | undefined): boolean =>
-	!text || text.trim().length === 0;
+const LOCK_CHANGED_CHANNEL = "lock:changed";
 
 /**
- * Maximum allowed clipboard content characters.
- * Prevents storing excessively large content.
+ * IPC channel used to tell the renderer that capture was paused or resumed.
  */
-const MAX_CLIP_CHARS = 1_000_000;
+const CAPTURE_CHANGED_CHANNEL = "capture:changed";
 
 /**
- * Delay in milliseconds before simulating paste after hiding window.
- * Allows time for focus to transfer to the previous application.
+ * IPC channel used to tell the renderer that read-only mode turned on or off.
  */
-const PASTE_DELAY_MS = 10;
+const READ_ONLY_CHANGED_CHANNEL = "readOnly:changed";
 
 /**
- * Validates that an id is a positive integer.
- * Pure function.
+ * IPC channel used to tell the renderer that the paste queue changed.
  */
-export const isValidId = (id: unknown): id is number =>
-	typeof id === "number" && Number.isInteger(id) && id > 0;
+const QUEUE_CHANGED_CHANNEL = "queue:changed";
 
 /**
- * Validates pagination parameters.
- * Pure function.
+ * IPC channel used to tell the renderer that the settings file changed.
  */
-export const isValidPaginationParams = (
-	limit: unknown,
-	offset: unknown,
-): boolean =>
-	(limit === undefined ||
-		(typeof limit === "number" && Number.isInteger(limit) && limit > 0)) &&
-	(offset === undefined ||
-		(typeof offset === "number" && Number.isInteger(offset) && offset >= 0));
+const SETTINGS_CHANGED_CHANNEL = "settings:changed";
 
 /**
- * Pagination limits for history queries.
+ * IPC channel used to tell the renderer that an update check, download or
+ * install progressed.
  */
-const DEFAULT_LIMIT = 50;
-const MIN_LIMIT = 1;
-const MAX_LIMIT = 200;
+const UPDATER_STATUS_CHANNEL = "updater:status";
 
 /**
- * Safely coerces a value to a non-negative integer.
- * Returns the default if the value is not a valid number.
- * Pure function.
+ * IPC channel used to tell the renderer which accelerator each global
+ * shortcut holds and which were taken by other applications.
  */
-const toSafeInt = (value: unknown, defaultValue: number): number => {
-	if (typeof value !== "number" || !Number.isFinite(value)) {
-		return defaultValue;
-	}
-	return Math.floor(value);
-};
+const SHORTCUTS_STATUS_CHANNEL = "shortcuts:status";
 
 /**
- * Builds a SQL query for history with optional filters.
- * Pure function - returns query string and params.
- *
- * Sanitizes pagination parameters:
- * - limit: coerced to integer, clamped to [1, 200], defaults to 50
- * - offset: coerced to integer, clamped to >= 0, defaults to 0
+ * IPC channel used to tell the renderer that a shared board got new items.
  */
-export const buildHistoryQuery = (options: {
-	query?: string;
-	limit?: number;
-	favoritesOnly?: boolean;
-	offset?: number;
-}): { sql: string; params: (string | number)[] } => {
-	const { query = "", favoritesOnly = false } = options;
-
-	// Coerce and clamp pagination parameters to safe values
-	const rawLimit = toSafeInt(options.limit, DEFAULT_LIMIT);
-	const rawOffset = toSafeInt(options.offset, 0);
-	const limit = Math.min(Math.max(rawLimit, MIN_LIMIT), MAX_LIMIT);
-	const offset = Math.max(rawOffset, 0);
-
-	const conditions: string[] = [];
-	const params: (string | number)[] = [];
+const BOARDS_UPDATED_CHANNEL = "boards:updated";
 
-	if (query.trim()) {
-		conditions.push("content LIKE ?");
-		params.push(`%${query}%`);
-	}
+/**
+ * Public key update installers are signed with, shipped with release
+ * builds; builds without it never update.
+ */
+const UPDATE_PUBLIC_KEY_FILE = "update-public-key.pem";
 
-	if (favoritesOnly) {
-		conditions.push("is_favorite = 1");
-	}
+/**
+ * IPC channel used to ask the history window to search for a query.
+ */
+const SEARCH_REQUESTED_CHANNEL = "search:requested";
 
-	const whereClause =
-		conditions.length > 0 ? ` WHERE ${conditions.join(" AND ")}` : "";
-	const sql = `SELECT id, content, type, created_at, is_favorite, rtf FROM history${whereClause} ORDER BY created_at DESC LIMIT ? OFFSET ?`;
+/**
+ * Delay in milliseconds before simulating paste after hiding window.
+ * Allows time for focus to transfer to the previous application.
+ */
+const PASTE_DELAY_MS = 10;
 
-	params.push(limit, offset);
+/**
+ * Pixels per module of a generated QR code; large enough for a phone
+ * camera to read it off the screen.
+ */
+const QR_MODULE_PIXELS = 8;
 
-	return { sql, params };
-};
+/**
+ * Characters of a captured text shown in a capture rule notification.
+ */
+const NOTIFICATION_PREVIEW_CHARS = 100;
 
 // ============================================================================
 // Database Module (encapsulated state)
@@ -187,12 +464,77 @@ export const buildHistoryQuery = (options: {
  */
 const createDbModule = () => {
 	let db: Database.Database | null = null;
+	let opened: {
+		dbPath: string;
+		connect: (file: string) => Database.Database;
+	} | null = null;
+	/** What the last recovery did; null until one was needed */
+	let lastRecovery: DatabaseRecovery | null = null;
 
-	const init = (dbPath: string): void => {
+	const prepare = (connection: Database.Database): Database.Database => {
+		configureConnection(connection);
+		// Tag links cascade when their history item is deleted
+		connection.pragma("foreign_keys = ON");
+		registerTextCompression(connection);
+		return connection;
+	};
+
+	/**
+	 * Repairs the open database in place, or salvages its rows into a
+	 * fresh file when that is not enough.
+	 */
+	const recover = (check: DatabaseCheck): DatabaseRecovery => {
+		if (!db || !opened) throw new Error("Database not initialized");
+		const { dbPath, connect } = opened;
+		const current = db;
+		// The connection is closed when salvaging; nothing may use it meanwhile
+		db = null;
+		let result: ReturnType<typeof recoverDatabase>;
+		try {
+			result = recoverDatabase({
+				db: current,
+				dbPath,
+				connect: (file) => prepare(connect(file)),
+				check,
+			});
+		} catch (error) {
+			if (current.open) db = current;
+			throw error;
+		}
+		db = result.db;
+		lastRecovery = result.recovery;
+		if (result.recovery.action === "salvaged") {
+			console.error(
+				`Database was damaged; salvaged rows into a fresh file and kept the old one at ${result.recovery.corruptPath}`,
+			);
+		} else {
+			console.warn("Database indexes were damaged and have been rebuilt");
+		}
+		return result.recovery;
+	};
+
+	/**
+	 * Opens the database, recovers it if the quick check finds damage,
+	 * and runs migrations.
+	 * @param connect - Opens the connection
+	 */
+	const init = (
+		dbPath: string,
+		connect = (file: string): Database.Database => new Database(file),
+	): void => {
 		if (db) throw new Error("Database already initialized");
 		try {
-			db = new Database(dbPath);
-			runMigrations(db);
+			opened = { dbPath, connect };
+			db = prepare(connect(dbPath));
+			const check = checkDatabase(db, { quick: true });
+			if (!check.ok) {
+				console.error("Database check failed:", [
+					...check.integrityErrors,
+					...check.ftsErrors,
+				]);
+				recover(check);
+			}
+			runMigrations(getDb());
 		} catch (error) {
 			console.error("Failed to initialize database:", error);
 			throw new Error(
@@ -215,7 +557,74 @@ const createDbModule = () => {
 
 	const isInitialized = (): boolean => db !== null;
 
-	return { init, getDb, close, isInitialized };
+	/**
+	 * Replaces the database file with a serialized database, such as a
+	 * restored backup, and reopens it. The data is checked before the
+	 * current file is touched.
+	 * @throws if the data is not a history database
+	 */
+	const replace = (data: Buffer): void => {
+		if (!db || !opened) throw new Error("Database not initialized");
+		const restored = new Database(data);
+		try {
+			const hasHistory = restored
+				.prepare(
+					"SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'history'",
+				)
+				.get();
+			if (!hasHistory) {
+				throw new Error("Invalid backup: not a clipboard history database");
+			}
+		} finally {
+			restored.close();
+		}
+
+		const { dbPath, connect } = opened;
+		const restoredPath = `${dbPath}.restore`;
+		fs.writeFileSync(restoredPath, data, { mode: 0o600 });
+		close();
+		for (const suffix of ["-wal", "-shm", "-journal"]) {
+			fs.rmSync(`${dbPath}${suffix}`, { force: true });
+		}
+		fs.renameSync(restoredPath, dbPath);
+		init(dbPath, connect);
+	};
+
+	/**
+	 * Runs the full integrity check.
+	 */
+	const check = (): DatabaseCheck => checkDatabase(getDb());
+
+	/**
+	 * Checks the database and recovers it if needed. Returns a recovery
+	 * with action `none` when it was healthy.
+	 */
+	const repair = (): DatabaseRecovery => {
+		const before = check();
+		if (before.ok) {
+			return {
+				action: "none",
+				before,
+				after: before,
+				salvaged: null,
+				corruptPath: null,
+			};
+		}
+		return recover(before);
+	};
+
+	const getLastRecovery = (): DatabaseRecovery | null => lastRecovery;
+
+	return {
+		init,
+		getDb,
+		close,
+		isInitialized,
+		replace,
+		check,
+		repair,
+		getLastRecovery,
+	};
 };
 
 // ============================================================================
@@ -225,18 +634,46 @@ const createDbModule = () => {
 /**
  * Creates a window module with encapsulated state.
  */
-const createWindowModule = (options?: { onShow?: () => void }) => {
+const createWindowModule = (options?: {
+	/** Renderer view to load (`?view=`); the history window when unset */
+	view?: "picker";
+	/** Window size in pixels; 450×600 when unset */
+	size?: Size;
+	/**
+	 * Where the window opens: at the cursor (default), or like a launcher
+	 * palette, centered near the top of the display under the cursor
+	 */
+	placement?: "cursor" | "palette";
+	/** Read on every blur; the window stays open when it returns false */
+	shouldHideOnBlur?: () => boolean;
+	/** Runs before a hidden window is shown, while the previous app has focus */
+	onBeforeShow?: () => Promise<void>;
+	onShow?: () => void;
+	onPinnedChange?: () => void;
+	/** Runs on every key, mouse and touch event the window receives */
+	onInput?: () => void;
+	/**
+	 * A tool window on Windows: no taskbar button, and shown on whichever
+	 * virtual desktop is active rather than the one it was created on
+	 */
+	toolWindow?: boolean;
+}) => {
 	let mainWindow: BrowserWindow | null = null;
 	let blurTimeout: NodeJS.Timeout | null = null;
+	/** Pinned windows float on every workspace and ignore blur */
+	let pinned = false;
 
 	const create = async (): Promise<void> => {
 		mainWindow = new BrowserWindow({
-			width: 450,
-			height: 600,
+			width: options?.size?.width ?? 450,
+			height: options?.size?.height ?? 600,
 			frame: false,
 			transparent: true,
 			alwaysOnTop: true,
 			show: false,
+			...(options?.toolWindow && process.platform === "win32"
+				? { type: "toolbar" }
+				: {}),
 			webPreferences: {
 				preload: path.join(__dirname, "preload.js"),
 				contextIsolation: true,
@@ -246,11 +683,14 @@ const createWindowModule = (options?: { onShow?: () => void }) => {
 
 		// Load the app
 		const isDev = process.env.NODE_ENV === "development" || !app.isPackaged;
+		const search = options?.view ? `?view=${options.view}` : "";
 		try {
 			if (isDev) {
-				await mainWindow.loadURL("http://localhost:5173");
+				await mainWindow.loadURL(`http://localhost:5173/${search}`);
 			} else {
-				await mainWindow.loadFile(path.join(__dirname, "../dist/index.html"));
+				await mainWindow.loadFile(path.join(__dirname, "../dist/index.html"), {
+					search,
+				});
 			}
 		} catch (error) {
 			console.error("Failed to load application:", error);
@@ -281,10 +721,13 @@ const createWindowModule = (options?: { onShow?: () => void }) => {
 			}
 		});
 
-		// Hide window when it loses focus (click outside)
+		mainWindow.webContents.on("input-event", () => options?.onInput?.());
+
+		// Hide window when it loses focus (click outside), unless opted out
 		// Small delay to avoid hiding when interacting with window controls
 		mainWindow.on("blur", () => {
-			if (!mainWindow?.isVisible()) return;
+			if (!mainWindow?.isVisible() || pinned) return;
+			if (options?.shouldHideOnBlur?.() === false) return;
 			// Clear any existing timeout
 			if (blurTimeout) clearTimeout(blurTimeout);
 			blurTimeout = setTimeout(() => {
@@ -318,10 +761,52 @@ const createWindowModule = (options?: { onShow?: () => void }) => {
 
 	const getWindow = (): BrowserWindow | null => mainWindow;
 
-	const show = (): void => {
+	/**
+	 * Moves the window to the cursor, or to the palette position, kept
+	 * within the work area of the display the cursor is on.
+	 */
+	const moveToCursor = (window: BrowserWindow): void => {
+		const cursor = screen.getCursorScreenPoint();
+		const { workArea } = screen.getDisplayNearestPoint(cursor);
+		const [width, height] = window.getSize();
+		const { x, y } =
+			options?.placement === "palette"
+				? computePalettePosition({ width, height }, workArea)
+				: computeWindowPosition(cursor, { width, height }, workArea);
+		window.setPosition(x, y);
+	};
+
+	/**
+	 * Shows the window on the active workspace. A hidden window otherwise
+	 * reappears on the macOS Space or Linux workspace it was last shown on,
+	 * switching away from the one the hotkey was pressed on; joining every
+	 * workspace while it is shown places it on the current one. Windows
+	 * has no workspaces API (see `toolWindow`), and a pinned window already
+	 * is on every workspace.
+	 */
+	const showOnActiveWorkspace = (window: BrowserWindow): void => {
+		if (pinned || process.platform === "win32") {
+			window.show();
+			return;
+		}
+		// Skipping the process type change keeps the Dock icon from flickering
+		const workspaceOptions = {
+			visibleOnFullScreen: true,
+			skipTransformProcessType: true,
+		};
+		window.setVisibleOnAllWorkspaces(true, workspaceOptions);
+		window.show();
+		window.setVisibleOnAllWorkspaces(false, workspaceOptions);
+	};
+
+	const show = async (): Promise<void> => {
 		if (mainWindow) {
-			mainWindow.center();
-			mainWindow.show();
+			if (!mainWindow.isVisible()) {
+				await options?.onBeforeShow?.();
+			}
+			// A pinned window stays where the user put it
+			if (!pinned) moveToCursor(mainWindow);
+			showOnActiveWorkspace(mainWindow);
 			mainWindow.focus();
 			options?.onShow?.();
 		}
@@ -337,18 +822,52 @@ const createWindowModule = (options?: { onShow?: () => void }) => {
 		}
 	};
 
-	const toggle = (): void => {
+	const toggle = async (): Promise<void> => {
 		if (mainWindow?.isVisible()) {
 			hide();
 		} else {
-			show();
+			await show();
 		}
 	};
 
 	const center = (): void => mainWindow?.center();
 	const isVisible = (): boolean => mainWindow?.isVisible() ?? false;
+	const isPinned = (): boolean => pinned;
+
+	/**
+	 * Pins the window above other apps, including full-screen ones, and on
+	 * every workspace (macOS and Linux; Windows has no workspaces API), so
+	 * it stays open for drag and drop. Pinning shows the window.
+	 *
+	 * @throws if `value` is not a boolean
+	 */
+	const setPinned = async (value: unknown): Promise<boolean> => {
+		if (typeof value !== "boolean") {
+			throw new Error("Invalid pinned state: expected a boolean");
+		}
+		if (!mainWindow || value === pinned) return pinned;
 
-	return { create, getWindow, show, hide, toggle, center, isVisible };
+		pinned = value;
+		mainWindow.setAlwaysOnTop(true, pinned ? "floating" : "normal");
+		mainWindow.setVisibleOnAllWorkspaces(pinned, {
+			visibleOnFullScreen: true,
+		});
+		if (pinned) await show();
+		options?.onPinnedChange?.();
+		return pinned;
+	};
+
+	return {
+		create,
+		getWindow,
+		show,
+		hide,
+		toggle,
+		center,
+		isVisible,
+		isPinned,
+		setPinned,
+	};
 };
 
 // ============================================================================
@@ -360,39 +879,264 @@ const createWindowModule = (options?: { onShow?: () => void }) => {
  */
 const createTrayModule = (
 	windowModule: ReturnType<typeof createWindowModule>,
+	capturePause: CapturePause,
+	deps: {
+		/** No window exists, so Open and Keep Window on Top are left out */
+		headless: boolean;
+		getAppendCopy: () => AppendCopy | null;
+		/** Syncing flag and unread captures shown by the icon */
+		activity: TrayActivity;
+		/**
+		 * Recent items for the menu and the total item count for the
+		 * tooltip; null while history is locked
+		 */
+		readHistory: () => { items: HistoryRow[]; count: number } | null;
+		/** Places a listed item on the clipboard */
+		copyItem: (id: number) => void;
+		getProfiles: () => ProfilesStatus;
+		/** Relaunches the app in another profile */
+		switchProfile: (name: string) => void;
+		getUpdater: () => Updater | null;
+		/** Rebuilt along with the menu */
+		jumpList: JumpList;
+		/** What clicks on the icon do; null until settings are loaded */
+		getTrayClicks: () => TrayClicks | null;
+		/** Translates labels; the menu is rebuilt when the language changes */
+		t: Translate;
+	},
 ) => {
 	let tray: Tray | null = null;
+	let contextMenu: Menu | null = null;
 
-	const create = (): void => {
-		// Load tray icon from public folder
+	/**
+	 * Whether a click opens the menu. On macOS a context menu opens on
+	 * every click, so it is only set then; otherwise right-click pops it
+	 * up.
+	 */
+	const menuOnClick = (): boolean =>
+		(deps.getTrayClicks()?.getSettings().click ?? "menu") === "menu";
+
+	/**
+	 * Loads the tray icon for a state from the public folder; the paused
+	 * and syncing icons are dimmed with a badge, the unread icon adds a dot.
+	 */
+	const loadIcon = (state: TrayIconState) => {
+		const fileName = TRAY_ICON_FILES[state];
 		const iconPath = app.isPackaged
-			? path.join(process.resourcesPath, "tray-icon.png")
-			: path.join(__dirname, "../public/tray-icon.png");
+			? path.join(process.resourcesPath, fileName)
+			: path.join(__dirname, "../public", fileName);
 		const icon = nativeImage.createFromPath(iconPath);
 		icon.setTemplateImage(true); // Adapts to macOS menu bar theme
-		tray = new Tray(icon.resize({ width: 16, height: 16 }));
+		return icon.resize({ width: 16, height: 16 });
+	};
 
-		// Guard against tray creation failure
+	/**
+	 * Reads the recent items and item count, or null while history is
+	 * unavailable (locked or not yet open).
+	 */
+	const readHistorySummary = (): ReturnType<typeof deps.readHistory> => {
+		try {
+			return deps.readHistory();
+		} catch (error) {
+			console.error("Failed to read history for the tray:", error);
+			return null;
+		}
+	};
+
+	/**
+	 * Menu entries for the most recent items, followed by a separator.
+	 */
+	const buildRecentItemsMenu = (
+		items: HistoryRow[],
+	): MenuItemConstructorOptions[] => {
+		if (items.length === 0) return [];
+
+		return [
+			...items.map((item) => ({
+				label: formatTrayPreview(item, process.platform),
+				click: () => deps.copyItem(item.id),
+			})),
+			{ type: "separator" as const },
+		];
+	};
+
+	/**
+	 * Picks the icon from capture pause and tray activity.
+	 */
+	const getIconState = (): TrayIconState =>
+		getTrayIconState({
+			// Read-only mode records nothing either
+			paused: capturePause.isPaused() || isReadOnly(),
+			...deps.activity.getStatus(),
+		});
+
+	/**
+	 * Syncs the icon, tooltip, and menu with the capture pause, tray
+	 * activity, and append-copy state and the latest history items, along
+	 * with the Windows jump list. Called whenever history changes.
+	 */
+	const update = (): void => {
 		if (!tray) return;
+		const { t } = deps;
+		const { paused, resumesAt } = capturePause.getStatus();
+		const appendCopy = deps.getAppendCopy();
+		const history = readHistorySummary();
+		const resumeTime =
+			resumesAt === null
+				? null
+				: new Date(resumesAt).toLocaleTimeString([], {
+						hour: "2-digit",
+						minute: "2-digit",
+					});
 
-		// Create context menu for tray icon
-		const contextMenu = Menu.buildFromTemplate([
+		const windowItems: MenuItemConstructorOptions[] = [
 			{
-				label: "Open",
+				label: t("tray.open"),
 				click: () => windowModule.show(),
 			},
 			{ type: "separator" },
+		];
+		const { active, profiles } = deps.getProfiles();
+		const profileItems: MenuItemConstructorOptions[] =
+			profiles.length > 1
+				? [
+						{
+							label: t("tray.profile", { name: active }),
+							submenu: profiles.map((name) => ({
+								label: name,
+								type: "radio" as const,
+								checked: name === active,
+								click: () => deps.switchProfile(name),
+							})),
+						},
+					]
+				: [];
+		const updater = deps.getUpdater();
+		const updateStep =
+			updater && describeUpdateMenuItem(updater.getStatus(), t);
+		const updateItems: MenuItemConstructorOptions[] =
+			updater && updateStep
+				? [
+						{
+							label: updateStep.label,
+							enabled: updateStep.enabled,
+							click: () => {
+								if (updateStep.action === null) return;
+								updater[updateStep.action]().catch((error) => {
+									console.error("Failed to update from the tray:", error);
+								});
+							},
+						},
+					]
+				: [];
+		const pinItem: MenuItemConstructorOptions = {
+			label: t("tray.keepOnTop"),
+			type: "checkbox",
+			checked: windowModule.isPinned(),
+			click: (menuItem) => {
+				void windowModule.setPinned(menuItem.checked);
+			},
+		};
+
+		// An enabled lock with no OS prompt leaves history open; say so
+		// rather than let the user believe it is protected
+		const lockItems: MenuItemConstructorOptions[] =
+			appLockModule?.getStatus().unprotected
+				? [{ label: t("tray.lockUnprotected"), enabled: false }]
+				: [];
+
+		contextMenu = Menu.buildFromTemplate([
+			...(deps.headless ? [] : windowItems),
+			...lockItems,
+			...buildRecentItemsMenu(history?.items ?? []),
+			{
+				label: t("tray.pauseCapture"),
+				type: "checkbox",
+				checked: paused,
+				click: () => capturePause.toggle(),
+			},
+			{
+				label: resumeTime
+					? t("tray.snoozedUntil", { time: resumeTime })
+					: t("tray.snoozeCapture"),
+				submenu: SNOOZE_PRESETS_MINUTES.map((minutes) => ({
+					label: t("tray.minutes", { count: minutes }),
+					click: () => capturePause.snooze(minutes),
+				})),
+			},
+			...(deps.headless ? [] : [pinItem]),
+			{
+				label: t("tray.appendCopies"),
+				type: "checkbox",
+				checked: appendCopy?.getStatus().active ?? false,
+				enabled: appendCopy !== null,
+				click: () => appendCopy?.toggle(),
+			},
+			...profileItems,
+			...updateItems,
+			{ type: "separator" },
 			{
-				label: "Quit",
+				label: t("tray.quit"),
 				click: () => app.quit(),
 			},
 		]);
 
-		tray.setContextMenu(contextMenu);
-		tray.setToolTip("Clipboard Manager");
+		tray.setImage(loadIcon(getIconState()));
+		// The menu bar can show the unread count next to the icon
+		if (process.platform === "darwin") {
+			tray.setTitle(formatUnreadBadge(deps.activity.getStatus().unread));
+		}
+		tray.setContextMenu(
+			process.platform === "darwin" && !menuOnClick() ? null : contextMenu,
+		);
+		const status = resumeTime
+			? t("tray.statusSnoozed", { time: resumeTime })
+			: paused
+				? t("tray.statusPaused")
+				: t("tray.status");
+		tray.setToolTip(
+			formatTrayTooltip(
+				status,
+				history && { latest: history.items[0] ?? null, count: history.count },
+				t,
+			),
+		);
+		deps.jumpList.update();
+	};
+
+	/**
+	 * Opens the menu at the icon, unless macOS already opens it on the
+	 * click itself.
+	 */
+	const popUpMenu = (): void => {
+		if (!tray || !contextMenu) return;
+		if (process.platform === "darwin" && menuOnClick()) return;
+		tray.popUpContextMenu(contextMenu);
+	};
+
+	const create = (): void => {
+		tray = new Tray(loadIcon(getIconState()));
+
+		// Guard against tray creation failure
+		if (!tray) return;
+
+		// Middle-click is reported on Windows only, double-click on macOS
+		// and Windows
+		tray.on("click", () => deps.getTrayClicks()?.handle("click"));
+		tray.on("double-click", () =>
+			deps.getTrayClicks()?.handle("doubleClick"),
+		);
+		tray.on("middle-click", () =>
+			deps.getTrayClicks()?.handle("middleClick"),
+		);
+		tray.on("right-click", () => {
+			if (process.platform === "darwin") popUpMenu();
+		});
+
+		update();
 	};
 
-	return { create };
+	return { create, update, popUpMenu };
 };
 
 // ============================================================================
@@ -400,225 +1144,3976 @@ const createTrayModule = (
 // ============================================================================
 
 /**
- * Creates clipboard IPC handlers
+ * The parts of Electron's clipboard the watcher reads.
  */
-const createClipboardHandlers = () => ({
-	read: (): ClipboardData => ({
-		text: clipboard.readText(),
-		rtf: clipboard.readRTF() || undefined,
-	}),
-	write: (_event: Electron.IpcMainInvokeEvent, data: ClipboardData) => {
-		clipboard.write({
-			text: data.text,
-			rtf: data.rtf || undefined,
-		});
-	},
-});
+type ClipboardSource = Pick<
+	Electron.Clipboard,
+	| "availableFormats"
+	| "readText"
+	| "readRTF"
+	| "readHTML"
+	| "readImage"
+	| "readBuffer"
+>;
 
 /**
- * Creates database IPC handlers
+ * Wayland sessions read the selection through `wl-paste`, since
+ * Electron's clipboard misses copies made while its window is in the
+ * background.
  */
-const createDbHandlers = (dbModule: ReturnType<typeof createDbModule>) => ({
-	getHistory: (
-		_event: Electron.IpcMainInvokeEvent,
-		options: {
-			query?: string;
-			limit?: number;
-			favoritesOnly?: boolean;
-			offset?: number;
-		} = {},
-	) => {
-		// Validate pagination parameters
-		if (!isValidPaginationParams(options.limit, options.offset)) {
-			throw new Error(
-				`Invalid pagination parameters: limit=${options.limit}, offset=${options.offset}`,
-			);
-		}
-
-		const db = dbModule.getDb();
-		const { sql, params } = buildHistoryQuery(options);
-		return db.prepare(sql).all(...params) as HistoryRow[];
-	},
-
-	addClip: (_event: Electron.IpcMainInvokeEvent, data: ClipboardData) => {
-		const { text, rtf } = data;
-		if (isEmptyText(text)) return;
-
-		// Validate combined content size (text + RTF)
-		const totalSize = text.length + (rtf?.length ?? 0);
-		if (totalSize > MAX_CLIP_CHARS) {
-			throw new Error(
-				`Clipboard content too large: ${totalSize} chars (max: ${MAX_CLIP_CHARS})`,
-			);
-		}
-
-		const db = dbModule.getDb();
-		const recent = db
-			.prepare(
-				"SELECT content, rtf FROM history ORDER BY created_at DESC LIMIT 1",
-			)
-			.get() as { content: string; rtf: string | null } | undefined;
+const waylandClipboard =
+	process.platform === "linux" && isWaylandSession(process.env)
+		? createWaylandClipboard()
+		: null;
 
-		if (recent) {
-			const sameText = isNearDuplicate(text, recent.content);
-			const sameRtf = (rtf ?? null) === (recent.rtf ?? null);
-			if (sameText && sameRtf) return;
-		}
+/**
+ * Windows writes items with extra formats back through a helper, since
+ * Electron cannot place custom formats next to the text.
+ */
+const formatWriter = process.platform === "win32" ? createFormatWriter() : null;
 
-		db.prepare("INSERT INTO history (content, type, rtf) VALUES (?, ?, ?)").run(
-			text,
-			"text",
-			rtf || null,
-		);
-	},
+/**
+ * The last restore's write of extra formats; a paste waits for it, so
+ * it pastes the copy with all its formats.
+ */
+let formatsWrite: Promise<void> | null = null;
 
-	deleteHistoryItem: (_event: Electron.IpcMainInvokeEvent, id: number) => {
-		// Validate id
-		if (!isValidId(id)) {
-			throw new Error(`Invalid history item id: ${id}`);
-		}
+/**
+ * Uses `wl-paste` only while the change listener runs: `wl-paste
+ * --watch` needs the data-control protocol, and without it every read
+ * would map a window to get focus, once a second while polling.
+ */
+const getClipboardSource = (): ClipboardSource => {
+	if (!clipboardWatcher.isListening()) return clipboard;
+	const selection = waylandClipboard?.readSelection();
+	if (!selection) return clipboard;
+	return {
+		...selection,
+		readImage: () => nativeImage.createFromBuffer(selection.readImage()),
+	};
+};
 
-		const db = dbModule.getDb();
-		db.prepare("DELETE FROM history WHERE id = ?").run(id);
-	},
+/**
+ * Whether a password manager marked the clipboard's contents as secret.
+ */
+const isMarkedSensitive = (source: ClipboardSource): boolean =>
+	hasSensitiveFormat(source.availableFormats(), (format) =>
+		source.readBuffer(format),
+	);
 
-	clearAllHistory: () => {
-		const db = dbModule.getDb();
-		db.prepare("DELETE FROM history").run();
-	},
+/**
+ * Reads the clipboard formats tracked by the watcher.
+ * Copied files take precedence over the file names file managers also
+ * place as text. Images are only captured when no text is present, so rich
+ * text copies that also carry a rendered image are still stored as text.
+ */
+const readClipboardSnapshot = (): ClipboardSnapshot => {
+	const source = getClipboardSource();
+	const text = source.readText();
+	const marks = {
+		sensitive: isMarkedSensitive(source),
+		// Copies handed over from another Apple device (Universal Clipboard)
+		remote:
+			process.platform === "darwin" && clipboard.has(REMOTE_CLIPBOARD_FORMAT),
+	};
+	const files = readFileList(source, process.platform);
+	if (files.length > 0) return { text, files, ...marks };
 
-	toggleFavorite: (_event: Electron.IpcMainInvokeEvent, id: number) => {
-		// Validate id
-		if (!isValidId(id)) {
-			throw new Error(`Invalid history item id: ${id}`);
-		}
+	const rtf = source.readRTF() || undefined;
+	const html = source.readHTML() || undefined;
+	if (text) {
+		// Office and other Windows apps keep their own formats next to it
+		const formats =
+			process.platform === "win32" ? readWindowsFormats(source) : undefined;
+		return { text, rtf, html, ...marks, formats };
+	}
 
-		const db = dbModule.getDb();
+	const image = source.readImage();
+	if (image.isEmpty()) return { text, rtf, html, ...marks };
 
-		// Check if item exists first
-		const existing = db
-			.prepare("SELECT id FROM history WHERE id = ?")
-			.get(id) as { id: number } | undefined;
+	const { width, height } = image.getSize();
+	return {
+		text,
+		rtf,
+		...marks,
+		image: {
+			hash: createHash("sha256").update(image.toBitmap()).digest("hex"),
+			width,
+			height,
+			toPNG: () => image.toPNG(),
+		},
+	};
+};
 
-		if (!existing) {
-			throw new Error(`History item not found: ${id}`);
+/**
+ * Clears the clipboard if it still holds one of the given items, so an
+ * item deleted on expiry cannot be pasted afterwards.
+ * @param hashes - Content hashes of the items
+ */
+const clearFromClipboard = (hashes: ReadonlyArray<string | null>): void => {
+	if (hashes.length === 0) return;
+	const source = getClipboardSource();
+	const text = source.readText();
+	let current: string | null = null;
+	if (text) {
+		current = computeContentHash("text", text);
+	} else {
+		const image = source.readImage();
+		if (!image.isEmpty()) {
+			current = computeContentHash("image", stripPngMetadata(image.toPNG()));
 		}
-
-		// Toggle favorite, treating NULL as 0 (not favorite)
-		// NULL -> 1, 0 -> 1, 1 -> 0
-		db.prepare(
-			"UPDATE history SET is_favorite = CASE WHEN COALESCE(is_favorite, 0) = 1 THEN 0 ELSE 1 END WHERE id = ?",
-		).run(id);
-
-		// Return the new favorite state
-		const result = db
-			.prepare("SELECT is_favorite FROM history WHERE id = ?")
-			.get(id) as { is_favorite: number } | undefined;
-		return Boolean(result?.is_favorite);
-	},
-});
+	}
+	if (current !== null && hashes.includes(current)) clipboard.clear();
+};
 
 /**
- * Creates window IPC handlers
+ * Decodes an image item scaled down to fit `maxEdge`. Large images on
+ * macOS and Windows are decrypted to a temporary file, one chunk at a
+ * time, for the system thumbnailer, which decodes them at the smaller
+ * size, so memory use stays flat; elsewhere they are decoded whole.
+ * @returns null if the image data could not be read
  */
-const createWindowHandlers = (
-	windowModule: ReturnType<typeof createWindowModule>,
-) => ({
-	center: () => windowModule.center(),
-	show: () => windowModule.show(),
-	hide: () => windowModule.hide(),
-	isVisible: () => windowModule.isVisible(),
-	hideAndPaste: async () => {
-		// Auto-paste only works on macOS (requires AppleScript)
-		if (process.platform !== "darwin") return;
-
-		await promptAccessibilityIfNeeded();
-		windowModule.hide();
-		// Wait for focus to transfer to previous application
-		await new Promise((resolve) => setTimeout(resolve, PASTE_DELAY_MS));
+const loadDownscaledImage = async (
+	source: ImageSource,
+	maxEdge: number,
+): Promise<Electron.NativeImage | null> => {
+	const size = fitWithin(source, maxEdge);
+	if (
+		source.size > STREAMED_IMAGE_BYTES &&
+		(process.platform === "darwin" || process.platform === "win32")
+	) {
+		const dir = fs.mkdtempSync(path.join(os.tmpdir(), "clipboard-image-"));
 		try {
-			await simulatePaste();
+			const filePath = path.join(dir, "image.png");
+			if (source.writeTo(filePath)) {
+				return await nativeImage.createThumbnailFromPath(filePath, size);
+			}
 		} catch (error) {
-			console.error("Failed to simulate paste:", error);
+			console.error("Failed to downscale image from file:", error);
+		} finally {
+			fs.rmSync(dir, { recursive: true, force: true });
 		}
-	},
-});
+	}
+	const png = source.read();
+	if (!png) return null;
+	return nativeImage.createFromBuffer(png).resize({ ...size, quality: "good" });
+};
 
-// ============================================================================
-// Application Bootstrap
-// ============================================================================
+/**
+ * Encodes a list thumbnail of an image as a data URL: JPEG when the image
+ * is opaque, PNG when it has transparency.
+ * @returns null if the image data could not be read
+ */
+const renderThumbnail = async (source: ImageSource): Promise<string | null> => {
+	const thumbnail = await loadDownscaledImage(source, THUMBNAIL_MAX_EDGE);
+	if (!thumbnail) return null;
+	if (!isOpaque(thumbnail.toBitmap())) return thumbnail.toDataURL();
+	const jpeg = thumbnail.toJPEG(THUMBNAIL_JPEG_QUALITY);
+	return `data:image/jpeg;base64,${jpeg.toString("base64")}`;
+};
 
-// Create module instances
-const dbModule = createDbModule();
-const windowModule = createWindowModule({
-	onShow: () => {
-		void promptAccessibilityIfNeeded();
+/**
+ * Re-encodes stored PNG images in the format a target app accepts.
+ * JPEG has no transparency, so images are flattened onto white first.
+ */
+const convertImage = createImageConverter({
+	toJpeg: (png, quality) => {
+		const image = nativeImage.createFromBuffer(png);
+		return nativeImage
+			.createFromBitmap(flattenOnWhite(image.toBitmap()), image.getSize())
+			.toJPEG(quality);
 	},
 });
-const trayModule = createTrayModule(windowModule);
-
-let launchAtLoginModule: ReturnType<typeof createLaunchAtLoginModule> | null =
-	null;
 
-// Create handlers
-const clipboardHandlers = createClipboardHandlers();
-const dbHandlers = createDbHandlers(dbModule);
-const windowHandlers = createWindowHandlers(windowModule);
+/**
+ * Adds an entry to the access log. A failed write is reported, not
+ * thrown, so it never blocks the access itself.
+ */
+const recordAccess = (
+	historyId: number | null,
+	action: AccessAction,
+	surface: AccessSurface,
+	detail?: string,
+): void => {
+	try {
+		accessLogRepository.record({ historyId, action, surface, detail });
+	} catch (error) {
+		console.error("Failed to record access:", error);
+	}
+};
+
+/**
+ * Places a stored history item back on the clipboard and counts the use.
+ * A files item goes back as references to its files, or with
+ * `files: "contents"` as the text or image in its only file.
+ * `countUse: false` leaves the use count alone, for restores the user
+ * did not ask for.
+ */
+const restoreHistoryItem = (
+	historyRepository: HistoryRepository,
+	id: number,
+	options: { files?: FileRestoreMode; countUse?: boolean } = {},
+): void => {
+	const item = historyRepository.getItem(id);
+	if (!item) {
+		throw new Error(`History item not found: ${id}`);
+	}
+
+	if (item.type === "image") {
+		const image = historyRepository.getImage(id);
+		if (!image) {
+			throw new Error(`Image data missing for history item: ${id}`);
+		}
+		clipboard.writeImage(nativeImage.createFromBuffer(image.png));
+	} else if (item.type === "files" && options.files === "contents") {
+		const content = readFileContent(parseStoredFileList(item.content));
+		if (content.type === "text") {
+			clipboard.write({ text: content.text });
+		} else {
+			const image = nativeImage.createFromBuffer(content.data);
+			if (image.isEmpty()) {
+				throw new Error(`Cannot read the image in history item: ${id}`);
+			}
+			clipboard.writeImage(image);
+		}
+	} else if (item.type === "files") {
+		writeFileList({
+			clipboard,
+			platform: process.platform,
+			paths: parseStoredFileList(item.content),
+		});
+	} else {
+		const clip = {
+			// Copies over the size limit are restored in full
+			text: historyRepository.getFullText(id) ?? item.content,
+			rtf: item.rtf || undefined,
+			html: item.html || undefined,
+		};
+		clipboard.write(clip);
+		const formats = formatWriter ? historyRepository.getFormats(id) : [];
+		if (formatWriter && formats.length > 0) {
+			// Rewrites the clipboard with everything once the helper is done;
+			// until then the text, HTML and RTF are already there
+			formatsWrite = formatWriter
+				.write({ ...clip, formats })
+				.catch((error) =>
+					console.error("Failed to restore clipboard formats:", error),
+				);
+		}
+	}
+	// Read-only mode keeps counts and one-time items as they are
+	if (options.countUse === false || isReadOnly()) return;
+	if (historyRepository.recordUse(id)) forgetOneTimeItem(id, true);
+};
+
+/**
+ * The history item the clipboard holds, found by content hash. Items
+ * flagged as secrets are left out, so they are not kept across restarts.
+ * @returns null if the clipboard is empty or holds nothing recorded
+ */
+const findClipboardItem = (
+	historyRepository: HistoryRepository,
+): number | null => {
+	const snapshot = readClipboardSnapshot();
+	let hash: string | null = null;
+	if (snapshot.files) {
+		hash = computeContentHash("files", formatStoredFileList(snapshot.files));
+	} else if (snapshot.image) {
+		hash = computeContentHash(
+			"image",
+			stripPngMetadata(snapshot.image.toPNG()),
+		);
+	} else if (snapshot.text) {
+		hash = computeContentHash("text", snapshot.text);
+	}
+	if (hash === null) return null;
+	const id = historyRepository.findIdByHash(hash);
+	if (id === undefined) return null;
+	return historyRepository.getItem(id)?.secret_match === null ? id : null;
+};
+
+/**
+ * Creates clipboard IPC handlers
+ */
+const createClipboardHandlers = (historyRepository: HistoryRepository) => ({
+	read: (): ClipboardData => ({
+		text: clipboard.readText(),
+		rtf: clipboard.readRTF() || undefined,
+		html: clipboard.readHTML() || undefined,
+	}),
+	write: (_event: Electron.IpcMainInvokeEvent, data: ClipboardData) => {
+		clipboard.write({
+			text: data.text,
+			rtf: data.rtf || undefined,
+			html: data.html || undefined,
+		});
+	},
+	/**
+	 * Places a stored history item back on the clipboard.
+	 * Needed for formats the renderer cannot write, such as images and files.
+	 * `files: "contents"` places the text or image inside a files item's
+	 * only file instead of the file itself.
+	 */
+	restoreItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		options?: { files?: unknown },
+	) => {
+		restoreHistoryItem(historyRepository, id, {
+			files: parseFileRestoreMode(options?.files),
+		});
+		recordAccess(id, "copy", "ui");
+	},
+});
+
+/**
+ * A text item, for commands that derive a new text from it.
+ *
+ * @throws if the item is missing or not text
+ */
+const getTextItem = (
+	historyRepository: HistoryRepository,
+	id: number,
+	action: string,
+): HistoryRow => {
+	const item = historyRepository.getItem(id);
+	if (!item) {
+		throw new Error(`History item not found: ${id}`);
+	}
+	if (item.type !== "text") {
+		throw new Error(`Only text items can be ${action}`);
+	}
+	return item;
+};
+
+/**
+ * The stored hex value of a color item.
+ *
+ * @throws if the item is missing or not a color
+ */
+const getColorValue = (
+	historyRepository: HistoryRepository,
+	id: number,
+): string => {
+	const item = historyRepository.getItem(id);
+	if (!item) {
+		throw new Error(`History item not found: ${id}`);
+	}
+	if (item.color_value === null) {
+		throw new Error(`History item is not a color: ${id}`);
+	}
+	return item.color_value;
+};
+
+/**
+ * The values of a text or files item that command action arguments use.
+ * A text item that is a path can be passed as `{path}`.
+ */
+const toCommandItem = (item: HistoryRow): CommandItem => {
+	if (item.type === "files") {
+		const paths = parseStoredFileList(item.content);
+		return { id: item.id, text: paths.join("\n"), paths };
+	}
+	const paths = item.content_kind === "path" ? [item.content.trim()] : [];
+	return { id: item.id, text: item.content, paths };
+};
+
+/**
+ * Adds a derived text, with optional rich text, as a new item, or places
+ * it on the clipboard for the watcher to record like any other copy.
+ */
+const deliverText = (text: string, target: TransformTarget, html?: string) => {
+	if (target === "clipboard") {
+		clipboard.write({ text, html });
+	} else {
+		recordSnapshot({ text, html }, null);
+	}
+};
+
+/**
+ * Creates database IPC handlers
+ * Thin adapters over the history repository, which owns validation and SQL.
+ * List and search results mask items flagged as secrets; `revealItem`
+ * returns one in full. Requests without a selection filter get the one
+ * `getListSelection` returns.
+ */
+const createDbHandlers = (
+	historyRepository: HistoryRepository,
+	getListSelection: () => SelectionSource | undefined = () => undefined,
+) => ({
+	getHistory: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: ListHistoryOptions = {},
+	) =>
+		historyRepository
+			.listItems({ selection: getListSelection(), ...options })
+			.map(redactSecret),
+
+	listPage: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: ListPageOptions = {},
+	) => {
+		const page = historyRepository.listPage({
+			selection: getListSelection(),
+			...options,
+		});
+		return { ...page, items: page.items.map(redactSecret) };
+	},
+
+	listGroupedPage: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: Omit<ListPageOptions, "order"> = {},
+	) => {
+		const page = historyRepository.listGroupedPage({
+			selection: getListSelection(),
+			...options,
+		});
+		return {
+			...page,
+			groups: page.groups.map((group) => ({
+				...group,
+				items: group.items.map(redactSecret),
+			})),
+		};
+	},
+
+	searchHistory: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: SearchHistoryOptions,
+	) =>
+		historyRepository
+			.searchItems({ selection: getListSelection(), ...options })
+			.map(redactSecret),
+
+	fuzzySearchHistory: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: SearchHistoryOptions,
+	) =>
+		historyRepository
+			.fuzzySearchItems({ selection: getListSelection(), ...options })
+			.map(redactSecret),
+
+	regexSearchHistory: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: SearchHistoryOptions,
+	) =>
+		historyRepository
+			.regexSearchItems({ selection: getListSelection(), ...options })
+			.map(redactSecret),
+
+	/**
+	 * Indexed text items closest in meaning to the query, best first, with
+	 * their cosine `similarity`.
+	 */
+	semanticSearch: async (
+		_event: Electron.IpcMainInvokeEvent,
+		query: string,
+		limit = 50,
+	) => {
+		if (!semanticSearch) {
+			throw new Error("Semantic search not initialized");
+		}
+		const matches = await semanticSearch.search(query, limit);
+		const scores = new Map(matches.map((match) => [match.id, match.score]));
+		return historyRepository
+			.getItemsByIds(matches.map((match) => match.id))
+			.map((item) => ({
+				...redactSecret(item),
+				similarity: scores.get(item.id) ?? 0,
+			}));
+	},
+
+	/**
+	 * Full content of one item, including one masked in lists.
+	 */
+	revealItem: (_event: Electron.IpcMainInvokeEvent, id: number) => {
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error(`History item not found: ${id}`);
+		}
+		recordAccess(id, "reveal", "ui");
+		return item;
+	},
+
+	deleteHistoryItem: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.deleteItem(id),
+
+	clearAllHistory: () => historyRepository.clearAll(),
+
+	listTrash: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: Pick<ListHistoryOptions, "limit" | "offset"> = {},
+	) => historyRepository.listTrash(options).map(redactSecret),
+
+	restoreFromTrash: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.restoreFromTrash(id),
+
+	emptyTrash: () => historyRepository.emptyTrash(),
+
+	batchUpdate: (
+		_event: Electron.IpcMainInvokeEvent,
+		ids: unknown,
+		operation: unknown,
+	) => historyRepository.batchUpdate(ids, operation),
+
+	/**
+	 * Groups image items that look alike, e.g. repeated screenshots.
+	 * Resolves to lists of item ids, newest first.
+	 */
+	findNearDuplicates: (
+		_event: Electron.IpcMainInvokeEvent,
+		maxDistance?: unknown,
+	) =>
+		historyRepository.findNearDuplicateImages(
+			parseNearDuplicateDistance(maxDistance),
+		),
+
+	/**
+	 * Groups text items that are nearly the same, e.g. successive edits of
+	 * one paragraph. Resolves to lists of item ids, newest first, so
+	 * merging a group keeps the newest.
+	 */
+	findNearDuplicateTexts: (
+		_event: Electron.IpcMainInvokeEvent,
+		maxDistance?: unknown,
+	) =>
+		historyRepository.findNearDuplicateTexts(
+			parseNearDuplicateDistance(maxDistance ?? SIMILAR_TEXT_MAX_DISTANCE),
+		),
+
+	/**
+	 * Keeps the first of several duplicate items and moves the rest to the
+	 * trash. Resolves to the kept item.
+	 */
+	mergeDuplicates: (_event: Electron.IpcMainInvokeEvent, ids: unknown) =>
+		historyRepository.mergeDuplicates(ids),
+
+	/**
+	 * Returns a downscaled PNG data URL for an image item, or null if none.
+	 */
+	getImagePreview: async (_event: Electron.IpcMainInvokeEvent, id: number) => {
+		const source = historyRepository.getImageSource(id);
+		if (!source) return null;
+
+		const preview = await loadDownscaledImage(source, PREVIEW_MAX_EDGE);
+		return preview?.toDataURL() ?? null;
+	},
+
+	/**
+	 * Returns a cached thumbnail data URL for an image item, or null if none.
+	 */
+	getThumbnail: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.getThumbnail(id, renderThumbnail),
+
+	/**
+	 * Copies the decoded text of the QR code in an image item.
+	 */
+	copyQrPayload: (_event: Electron.IpcMainInvokeEvent, id: number) => {
+		clipboard.writeText(historyRepository.getQrPayload(id));
+	},
+
+	/**
+	 * Renders the text of a text item as a QR code and returns it as a PNG
+	 * data URL; with `copy`, also places the image on the clipboard.
+	 */
+	generateQr: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		options?: { copy?: boolean },
+	) => {
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error(`History item not found: ${id}`);
+		}
+		if (item.type !== "text") {
+			throw new Error("Only text items can be shown as a QR code");
+		}
+
+		const bitmap = renderQrBitmap(encodeQr(item.content), QR_MODULE_PIXELS);
+		const image = nativeImage.createFromBitmap(bitmap.data, {
+			width: bitmap.width,
+			height: bitmap.height,
+		});
+		if (options?.copy) clipboard.writeImage(image);
+		return image.toDataURL();
+	},
+
+	/**
+	 * Places an image item on the clipboard re-encoded as PNG, JPEG or
+	 * WebP, for apps that only accept one of them.
+	 */
+	copyImageAs: async (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		conversion: unknown,
+	) => {
+		const request = parseImageConversion(conversion);
+		const image = historyRepository.getImage(id);
+		if (!image) {
+			throw new Error(`Image data missing for history item: ${id}`);
+		}
+
+		if (request.format === "png") {
+			clipboard.writeImage(nativeImage.createFromBuffer(image.png));
+			return;
+		}
+		clipboard.writeBuffer(
+			toImageClipboardFormat(process.platform, request.format),
+			await convertImage(image.png, request),
+		);
+	},
+
+	/**
+	 * A color item in hex, `rgb()` and `hsl()` notation, derived from its
+	 * stored hex value.
+	 *
+	 * @throws if the item is not a color
+	 */
+	getColorFormats: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		convertColor(getColorValue(historyRepository, id)),
+
+	/**
+	 * Places a color item on the clipboard as plain text in one notation.
+	 */
+	copyColorAs: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		format: unknown,
+	) => {
+		if (!isColorFormat(format)) {
+			throw new Error(
+				`Invalid color format: must be one of ${COLOR_FORMATS.join(", ")}`,
+			);
+		}
+		const formats = convertColor(getColorValue(historyRepository, id));
+		clipboard.writeText(formats[format]);
+		recordAccess(id, "copy", "ui", `as ${format}`);
+	},
+
+	/**
+	 * Compares two text items line by line, with the changed words of
+	 * replaced lines marked. Both are returned in full, secrets included,
+	 * so each counts as revealed.
+	 */
+	diffItems: (
+		_event: Electron.IpcMainInvokeEvent,
+		oldId: number,
+		newId: number,
+	) => {
+		const oldItem = getTextItem(historyRepository, oldId, "compared");
+		const newItem = getTextItem(historyRepository, newId, "compared");
+		const diff = diffTexts(oldItem.content, newItem.content);
+		recordAccess(oldId, "reveal", "ui", `compared with ${newId}`);
+		recordAccess(newId, "reveal", "ui", `compared with ${oldId}`);
+		return diff;
+	},
+
+	/**
+	 * Removes metadata such as EXIF from an image item before it is shared.
+	 * Resolves to false if there was none.
+	 */
+	stripMetadata: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.stripImageMetadata(id),
+
+	/**
+	 * Writes an image item to a file re-encoded as PNG, JPEG or WebP.
+	 */
+	saveImageAs: async (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		conversion: unknown,
+		filePath: unknown,
+	) => {
+		const request = parseImageConversion(conversion);
+		if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+			throw new Error("Invalid image conversion: path must be absolute");
+		}
+		const image = historyRepository.getImage(id);
+		if (!image) {
+			throw new Error(`Image data missing for history item: ${id}`);
+		}
+
+		await fs.promises.writeFile(
+			filePath,
+			await convertImage(image.png, request),
+		);
+	},
+
+	/**
+	 * Writes a text item as UTF-8, or an image item as PNG or re-encoded
+	 * in the format `conversion` or the file's extension asks for. An
+	 * existing file is only replaced with `overwrite`.
+	 *
+	 * @throws if the path is not absolute, the file exists, or the item
+	 *   is a file list
+	 */
+	saveItemToFile: async (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		filePath: unknown,
+		options: { conversion?: unknown; overwrite?: boolean } = {},
+	) => {
+		if (typeof filePath !== "string" || !path.isAbsolute(filePath)) {
+			throw new Error("Invalid save path: must be absolute");
+		}
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error(`History item not found: ${id}`);
+		}
+		let data: string | Buffer;
+		if (item.type === "text") {
+			data = item.content;
+		} else if (item.type === "image") {
+			const image = historyRepository.getImage(id);
+			if (!image) {
+				throw new Error(`Image data missing for history item: ${id}`);
+			}
+			const request = parseImageConversion(
+				options.conversion ?? { format: imageFormatForPath(filePath) },
+			);
+			data = await convertImage(image.png, request);
+		} else {
+			throw new Error("Only text and image items can be saved to a file");
+		}
+
+		try {
+			await fs.promises.writeFile(filePath, data, {
+				encoding: "utf-8",
+				flag: options.overwrite === true ? "w" : "wx",
+			});
+		} catch (error) {
+			if ((error as NodeJS.ErrnoException).code === "EEXIST") {
+				throw new Error(`File already exists: ${filePath}`);
+			}
+			throw error;
+		}
+		recordAccess(id, "export", "ui", `saved to ${filePath}`);
+	},
+
+	/**
+	 * Transforms the text of a text item (case, whitespace, line order) and
+	 * adds the result as a new item or places it on the clipboard.
+	 */
+	transformItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		request: unknown,
+	) => {
+		const { transform, target } = parseTextTransformRequest(request);
+		const item = getTextItem(historyRepository, id, "transformed");
+		const text = applyTextTransform(item.content, transform);
+		if (text.trim() === "") {
+			throw new Error("Transformation left no text");
+		}
+		deliverText(text, target);
+	},
+
+	/**
+	 * Runs a saved user script on a text item and adds the result as a new
+	 * item or places it on the clipboard.
+	 *
+	 * @throws if the script fails, rejects the item or leaves no text
+	 */
+	runScript: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		request: unknown,
+	) => {
+		if (!userScripts) {
+			throw new Error("User scripts not initialized");
+		}
+		const { name, target } = parseScriptRunRequest(request);
+		const item = getTextItem(historyRepository, id, "run through scripts");
+		const result = userScripts.run(name, { type: "text", text: item.content });
+		if (result.rejected) {
+			throw new Error(`Script "${name}" rejected the item`);
+		}
+		if (result.text.trim() === "") {
+			throw new Error("Script left no text");
+		}
+		deliverText(result.text, target);
+	},
+
+	/**
+	 * Runs a user-defined external command on a text or files item.
+	 * @returns the command's output, also added to history if the action
+	 *   captures output
+	 */
+	runCommandAction: async (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		name: string,
+	) => {
+		if (!commandActions) {
+			throw new Error("Command actions not initialized");
+		}
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error(`History item not found: ${id}`);
+		}
+		if (item.type === "image") {
+			throw new Error("Only text and files items can be passed to commands");
+		}
+		return commandActions.runAction(name, toCommandItem(item));
+	},
+
+	/**
+	 * Pretty-prints or minifies a JSON, XML or SQL text item and adds the
+	 * result as a new item or places it on the clipboard.
+	 *
+	 * @throws if the text does not parse as the requested language
+	 */
+	formatItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		request: unknown,
+	) => {
+		const { format, target } = parseFormatRequest(request);
+		const item = getTextItem(historyRepository, id, "formatted");
+		deliverText(formatText(item.content, format), target);
+	},
+
+	/**
+	 * Converts a text item between Markdown and HTML. Markdown becomes rich
+	 * text kept alongside the Markdown, so it pastes formatted into mail
+	 * and document editors; HTML (the item's rich text, or its text when it
+	 * has none) becomes Markdown.
+	 */
+	convertMarkup: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		request: unknown,
+	) => {
+		const { conversion, target } = parseMarkupConversionRequest(request);
+		const item = getTextItem(historyRepository, id, "converted");
+		if (conversion === "markdownToHtml") {
+			deliverText(item.content, target, markdownToHtml(item.content));
+			return;
+		}
+		const text = htmlToMarkdown(item.html ?? item.content);
+		if (text === "") {
+			throw new Error("Conversion left no text");
+		}
+		deliverText(text, target);
+	},
+
+	/**
+	 * Hex digest of an item's exact bytes: its UTF-8 text, the stored PNG
+	 * of an image, or the contents of a single copied file.
+	 *
+	 * @throws if a files item lists more than one path, or not a file
+	 */
+	hashItem: async (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		algorithm: unknown,
+	): Promise<string> => {
+		const validAlgorithm = parseHashAlgorithm(algorithm);
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error(`History item not found: ${id}`);
+		}
+
+		if (item.type === "image") {
+			const image = historyRepository.getImage(id);
+			if (!image) {
+				throw new Error(`Image data missing for history item: ${id}`);
+			}
+			return hashBytes(image.png, validAlgorithm);
+		}
+		if (item.type === "files") {
+			const paths = parseStoredFileList(item.content);
+			if (paths.length !== 1) {
+				throw new Error("Only items with a single file can be hashed");
+			}
+			return hashFile(paths[0], validAlgorithm);
+		}
+		return hashBytes(Buffer.from(item.content, "utf8"), validAlgorithm);
+	},
+
+	toggleFavorite: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.toggleFavorite(id),
+
+	togglePin: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.togglePin(id),
+
+	toggleSyncExcluded: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.toggleSyncExcluded(id),
+
+	togglePasteOnce: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.togglePasteOnce(id),
+
+	setItemNote: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		text: string,
+	) => historyRepository.setItemNote(id, text),
+
+	dismissSecret: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		historyRepository.dismissSecret(id),
+
+	/**
+	 * Deletes an item at the given ISO 8601 time, or keeps it if null. If
+	 * it is still on the clipboard then, the clipboard is cleared too.
+	 */
+	setItemExpiry: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		expiresAt: unknown,
+	) => historyRepository.setItemExpiry(id, expiresAt),
+
+	/**
+	 * Joins text items into a new history item and, if `copy` is set,
+	 * places it on the clipboard.
+	 */
+	mergeItems: (
+		_event: Electron.IpcMainInvokeEvent,
+		ids: unknown,
+		separator: unknown,
+		copy: unknown,
+	) => {
+		const merged = historyRepository.mergeItems(ids, separator);
+		if (copy === true) {
+			restoreHistoryItem(historyRepository, merged.id);
+			recordAccess(merged.id, "copy", "ui");
+		}
+		return merged;
+	},
+
+	listSourceApps: () => historyRepository.listSourceApps(),
+
+	/**
+	 * Writes matching history to a JSON or CSV file.
+	 * Resolves to the number of items exported.
+	 */
+	exportHistory: async (
+		_event: Electron.IpcMainInvokeEvent,
+		format: unknown,
+		filePath: unknown,
+		filters: unknown,
+	) => {
+		const request = parseHistoryExportRequest(format, filePath, filters);
+		const count = await exportHistory(
+			historyRepository.listExportItems,
+			request,
+		);
+		recordAccess(
+			null,
+			"export",
+			"ui",
+			`${request.format}: ${count} items to ${request.filePath}`,
+		);
+		return count;
+	},
+
+	/**
+	 * Writes the text of pinned items, or of one collection's items, as an
+	 * Alfred snippet collection or Raycast snippets JSON.
+	 * Resolves to the number of snippets exported.
+	 */
+	exportSnippets: async (
+		_event: Electron.IpcMainInvokeEvent,
+		format: unknown,
+		filePath: unknown,
+		source: unknown,
+	) => {
+		const request = parseSnippetExportRequest(format, filePath, source);
+		const items: HistoryRow[] = [];
+		if (request.source === "pinned") {
+			for (;;) {
+				const rows = historyRepository.listExportItems(
+					{ pinnedOnly: true },
+					items[items.length - 1]?.id ?? 0,
+					500,
+				);
+				if (rows.length === 0) break;
+				items.push(...rows);
+			}
+		} else {
+			items.push(
+				...collectionRepository.listCollectionItems(
+					request.source.collectionId,
+				),
+			);
+		}
+		const count = await exportSnippets(items, request);
+		recordAccess(
+			null,
+			"export",
+			"ui",
+			`${request.format} snippets: ${count} items to ${request.filePath}`,
+		);
+		return count;
+	},
+
+	/**
+	 * Reveals, copies, pastes and exports matching the filters, newest
+	 * first.
+	 */
+	listAccessLog: (_event: Electron.IpcMainInvokeEvent, query: unknown) =>
+		accessLogRepository.list(parseAccessLogQuery(query)),
+
+	/**
+	 * Adds the items of a JSON export that are not in history yet.
+	 * Resolves to how many were imported and skipped.
+	 */
+	importHistory: (_event: Electron.IpcMainInvokeEvent, filePath: unknown) =>
+		importHistory(historyRepository.importItems, filePath),
+
+	/**
+	 * Adds items from a CopyQ tab file, configuration directory or
+	 * synchronized tab directory.
+	 */
+	importCopyQ: (_event: Electron.IpcMainInvokeEvent, sourcePath: unknown) =>
+		importCopyQ(historyRepository.importItems, sourcePath),
+
+	/**
+	 * Adds text and image clips from a Ditto database, opened read-only.
+	 */
+	importDitto: (_event: Electron.IpcMainInvokeEvent, dbPath: unknown) =>
+		importDitto(
+			{
+				importItems: historyRepository.importItems,
+				connect: (file) =>
+					new Database(file, { readonly: true, fileMustExist: true }),
+				encodePng: ({ pixels, width, height }) =>
+					nativeImage.createFromBitmap(pixels, { width, height }).toPNG(),
+			},
+			dbPath,
+		),
+
+	/**
+	 * Adds text and image items from Maccy's store, opened read-only.
+	 */
+	importMaccy: (_event: Electron.IpcMainInvokeEvent, dbPath: unknown) =>
+		importMaccy(
+			{
+				importItems: historyRepository.importItems,
+				connect: (file) =>
+					new Database(file, { readonly: true, fileMustExist: true }),
+				decodeImage: (data) => {
+					const image = nativeImage.createFromBuffer(data);
+					if (image.isEmpty()) return null;
+					return { png: image.toPNG(), ...image.getSize() };
+				},
+			},
+			dbPath,
+		),
+});
+
+/**
+ * Creates tag IPC handlers
+ * Thin adapters over the tag repository
+ */
+const createTagHandlers = (tagRepository: TagRepository) => ({
+	addTag: (_event: Electron.IpcMainInvokeEvent, id: number, name: string) =>
+		tagRepository.addTag(id, name),
+
+	removeTag: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		name: string,
+	) => tagRepository.removeTag(id, name),
+
+	getItemTags: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		tagRepository.listItemTags(id),
+
+	listTags: () => tagRepository.listTags(),
+});
+
+/**
+ * Creates collection IPC handlers
+ * Thin adapters over the collection repository
+ */
+const createCollectionHandlers = (
+	collectionRepository: CollectionRepository,
+) => ({
+	list: () => collectionRepository.listCollections(),
+
+	create: (_event: Electron.IpcMainInvokeEvent, name: string) =>
+		collectionRepository.createCollection(name),
+
+	rename: (
+		_event: Electron.IpcMainInvokeEvent,
+		id: number,
+		name: string,
+	) => collectionRepository.renameCollection(id, name),
+
+	delete: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		collectionRepository.deleteCollection(id),
+
+	getItems: (_event: Electron.IpcMainInvokeEvent, id: number) =>
+		collectionRepository.listCollectionItems(id),
+
+	addItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		collectionId: number,
+		itemId: number,
+	) => collectionRepository.addItem(collectionId, itemId),
+
+	removeItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		collectionId: number,
+		itemId: number,
+	) => collectionRepository.removeItem(collectionId, itemId),
+
+	moveItem: (
+		_event: Electron.IpcMainInvokeEvent,
+		options: MoveCollectionItemOptions,
+	) => collectionRepository.moveItem(options),
+
+	reorderItems: (
+		_event: Electron.IpcMainInvokeEvent,
+		collectionId: number,
+		itemIds: number[],
+	) => collectionRepository.reorderItems(collectionId, itemIds),
+});
+
+/**
+ * Sends Cmd/Ctrl+V to the focused application.
+ */
+const simulatePaste = createPasteKeystroke({ platform: process.platform });
+
+/**
+ * Presses Left or Backspace repeatedly, for snippet carets and text
+ * expansion.
+ */
+const pressKeys = createKeyRepeater({ platform: process.platform });
+
+/**
+ * Remembers the window that had focus before the picker opened, so pastes
+ * go back to it.
+ */
+const focusTracker = createFocusTracker({ platform: process.platform });
+
+/**
+ * Hides the window and hands focus back to the previous application, ready
+ * for synthetic keystrokes.
+ */
+const hideAndRefocus = async (
+	windowModule: ReturnType<typeof createWindowModule>,
+): Promise<void> => {
+	// macOS needs Accessibility permission to send keystrokes
+	await promptAccessibilityIfNeeded();
+	// A pinned window stays up; restoring focus is enough to paste
+	if (!windowModule.isPinned()) windowModule.hide();
+	// Items can also be picked from the quick picker
+	pickerModule.hide();
+	await focusTracker.restore();
+	// Wait for focus to transfer to previous application
+	await new Promise((resolve) => setTimeout(resolve, PASTE_DELAY_MS));
+};
+
+/**
+ * Creates window IPC handlers
+ */
+const createWindowHandlers = (
+	windowModule: ReturnType<typeof createWindowModule>,
+) => ({
+	center: () => windowModule.center(),
+	show: () => windowModule.show(),
+	hide: () => windowModule.hide(),
+	isVisible: () => windowModule.isVisible(),
+	isPinned: () => windowModule.isPinned(),
+	setPinned: (_event: Electron.IpcMainInvokeEvent, pinned: unknown) =>
+		windowModule.setPinned(pinned),
+	hideAndPaste: async () => {
+		await hideAndRefocus(windowModule);
+		await formatsWrite;
+		try {
+			await simulatePaste();
+		} catch (error) {
+			console.error("Failed to simulate paste:", error);
+		}
+	},
+});
+
+// ============================================================================
+// Application Bootstrap
+// ============================================================================
+
+// Create module instances
+const startupOptions = parseStartupOptions(process.argv);
+const hasInstanceLock = app.requestSingleInstanceLock();
+const dataLocation = createDataLocation({
+	defaultPath: app.getPath("userData"),
+	// Windows portable builds run from a temporary folder and name the
+	// folder of the executable the user launched
+	executableDir:
+		process.env.PORTABLE_EXECUTABLE_DIR ?? path.dirname(app.getPath("exe")),
+	dataDirFlag: startupOptions.dataDir,
+	portable: startupOptions.portable,
+});
+// A move chosen in settings runs before anything opens the data folder
+if (hasInstanceLock) dataLocation.applyPendingMove();
+const profiles = createProfiles({
+	rootPath: dataLocation.getStatus().dataPath,
+});
+if (hasInstanceLock) {
+	let profile: string;
+	try {
+		profile = profiles.open(startupOptions.profile);
+	} catch (error) {
+		console.error("Ignoring --profile:", error);
+		profile = profiles.open(null);
+	}
+	// Everything under userData, Chromium's own data included, now belongs
+	// to the profile. The lock was taken first, so one instance runs at a
+	// time whatever the profile
+	app.setPath("userData", profiles.getDataPath(profile));
+}
+// Console output from here on is also kept in rotated log files in the
+// profile's data folder, named after the module that wrote it
+const logger = createLogger({ userDataPath: app.getPath("userData") });
+if (hasInstanceLock) logger.captureConsole(console);
+// Errors nothing caught, and renderer or helper processes that died, leave
+// a crash report with the log entries before them. The app keeps running,
+// as Electron does by default
+const diagnostics = createDiagnostics({
+	userDataPath: app.getPath("userData"),
+	logDirectory: logger.getLogDirectory(),
+	homeDir: os.homedir(),
+	system: {
+		appVersion: app.getVersion(),
+		electronVersion: process.versions.electron,
+		chromeVersion: process.versions.chrome,
+		platform: process.platform,
+		arch: process.arch,
+		osRelease: os.release(),
+	},
+	getRecentLogs: () => logger.getRecentLogs({ limit: 100 }),
+	// Settings that describe behavior; files holding tokens, hosts or user
+	// scripts stay out even though bundles are redacted
+	settingsFiles: [
+		"settings.toml",
+		"preferences.json",
+		"logging.json",
+		"window-behavior.json",
+		"shortcuts.json",
+		"retention.json",
+		"capture-limits.json",
+		"primary-selection.json",
+		"power-throttle.json",
+		"universal-clipboard.json",
+	],
+});
+process.on("uncaughtException", (error) => {
+	diagnostics.recordCrash("uncaughtException", error);
+	console.error("Uncaught exception:", error);
+});
+process.on("unhandledRejection", (reason) => {
+	diagnostics.recordCrash("unhandledRejection", reason);
+	console.error("Unhandled rejection:", reason);
+});
+app.on("render-process-gone", (_event, _webContents, details) => {
+	if (details.reason === "clean-exit") return;
+	diagnostics.recordCrash(
+		"renderer",
+		`Renderer ${details.reason} (exit code ${details.exitCode})`,
+	);
+});
+app.on("child-process-gone", (_event, details) => {
+	if (details.reason === "clean-exit") return;
+	diagnostics.recordCrash(
+		"childProcess",
+		`${details.type} process ${details.reason} (exit code ${details.exitCode})`,
+	);
+});
+const dbModule = createDbModule();
+/**
+ * Protects the attachment key with `safeStorage`, unless on Linux it
+ * found no secret service and would fall back to a fixed password.
+ */
+const attachmentKeyProtector: KeyProtector = {
+	isEncryptionAvailable: () =>
+		safeStorage.isEncryptionAvailable() &&
+		(process.platform !== "linux" ||
+			safeStorage.getSelectedStorageBackend() !== "basic_text"),
+	encryptString: (plainText) => safeStorage.encryptString(plainText),
+	decryptString: (encrypted) => safeStorage.decryptString(encrypted),
+};
+const attachmentStore = createAttachmentStore({
+	getDirectory: () => path.join(app.getPath("userData"), "attachments"),
+	getKey: () => getAttachmentKey(dbModule.getDb(), attachmentKeyProtector),
+});
+/**
+ * Refuses a write to history, tags, collections, registers or snippets
+ * while read-only mode is on. Retention is guarded too and its scheduled
+ * pass skips. Expiry is not: an item set to expire, such as a flagged
+ * secret, must not outlive its expiry because a guest is at the keyboard.
+ * Background jobs that only derive data (text recognition, embeddings)
+ * are not guarded either.
+ */
+const assertWritable = (): void => readOnlyMode?.assertWritable();
+const isReadOnly = (): boolean => readOnlyMode?.isActive() === true;
+const historyRepository = guardWrites(
+	createHistoryRepository(dbModule.getDb, attachmentStore),
+	[
+		"addItem",
+		"mergeItems",
+		"mergeDuplicates",
+		"stripImageMetadata",
+		"deleteItem",
+		"clearAll",
+		"restoreFromTrash",
+		"emptyTrash",
+		"batchUpdate",
+		"toggleFavorite",
+		"togglePin",
+		"toggleSyncExcluded",
+		"togglePasteOnce",
+		"setItemNote",
+		"dismissSecret",
+		"setItemExpiry",
+		"expireItem",
+		"pruneItems",
+		"importItems",
+		"addSyncedItems",
+		"applySyncTombstones",
+	],
+	assertWritable,
+);
+const tagRepository = guardWrites(
+	createTagRepository(dbModule.getDb),
+	["addTag", "removeTag"],
+	assertWritable,
+);
+const collectionRepository = guardWrites(
+	createCollectionRepository(dbModule.getDb),
+	[
+		"createCollection",
+		"renameCollection",
+		"deleteCollection",
+		"addItem",
+		"removeItem",
+		"moveItem",
+		"reorderItems",
+	],
+	assertWritable,
+);
+const registerRepository = guardWrites(
+	createRegisterRepository(dbModule.getDb),
+	["setRegister", "deleteRegister"],
+	assertWritable,
+);
+const snippetRepository = guardWrites(
+	createSnippetRepository(dbModule.getDb),
+	["createSnippet", "updateSnippet", "deleteSnippet"],
+	assertWritable,
+);
+const statsRepository = createStatsRepository(dbModule.getDb);
+const accessLogRepository = createAccessLogRepository(dbModule.getDb);
+const maintenanceModule = createMaintenanceModule({
+	getDb: dbModule.getDb,
+	getIdleSeconds: () => powerMonitor.getSystemIdleTime(),
+	// Passes already wait for idle time; only battery holds them back
+	shouldDefer: () => {
+		const status = powerThrottle?.getStatus();
+		return status?.deferJobs === true && status.reason === "battery";
+	},
+});
+const windowModule = createWindowModule({
+	shouldHideOnBlur: () => windowBehavior?.getSettings().hideOnBlur ?? true,
+	onBeforeShow: focusTracker.remember,
+	onShow: () => {
+		trayActivity.clearUnread();
+		void promptAccessibilityIfNeeded();
+	},
+	onPinnedChange: () => trayModule.update(),
+	onInput: () => appLockModule?.recordActivity(),
+});
+const pickerModule = createWindowModule({
+	view: "picker",
+	size: PICKER_WINDOW_SIZE,
+	placement: "palette",
+	toolWindow: true,
+	shouldHideOnBlur: () => windowBehavior?.getSettings().hideOnBlur ?? true,
+	onBeforeShow: focusTracker.remember,
+	onShow: () => {
+		trayActivity.clearUnread();
+		void promptAccessibilityIfNeeded();
+	},
+	onInput: () => appLockModule?.recordActivity(),
+});
+/**
+ * Whether capture was paused at the last change, so extending a snooze
+ * does not play the pause sound again.
+ */
+let wasCapturePaused = false;
+const capturePause = createCapturePause({
+	onChange: (status) => {
+		if (status.paused !== wasCapturePaused) {
+			captureSounds?.play(status.paused ? "pause" : "resume");
+			wasCapturePaused = status.paused;
+		}
+		trayModule.update();
+		windowModule
+			.getWindow()
+			?.webContents.send(CAPTURE_CHANGED_CHANNEL, status);
+	},
+});
+let i18n: I18n | null = null;
+// Backend strings are English until the language setting is loaded
+const t: Translate = (id, params) =>
+	i18n ? i18n.t(id, params) : translateEnglish(id, params);
+const trayActivity = createTrayActivity({
+	onChange: () => trayModule.update(),
+});
+const jumpList = createJumpList({
+	platform: process.platform,
+	setJumpList: (categories) => app.setJumpList(categories),
+	read: () => ({
+		items:
+			appLockModule?.getStatus().locked === false
+				? {
+						recent: historyRepository.listRecentItems(JUMP_LIST_ITEMS),
+						pinned: historyRepository
+							.listItems({ limit: JUMP_LIST_ITEMS })
+							.filter((item) => item.pinned === 1),
+					}
+				: null,
+		paused: capturePause.isPaused(),
+		launch: {
+			program: process.execPath,
+			// Development builds run the app folder through electron.exe
+			args: [
+				...(process.defaultApp ? [app.getAppPath()] : []),
+				...withProfileArg([], profiles.getStatus().active),
+			],
+		},
+	}),
+	t,
+});
+const trayModule = createTrayModule(windowModule, capturePause, {
+	headless: startupOptions.headless,
+	getAppendCopy: () => appendCopy,
+	activity: trayActivity,
+	readHistory: () =>
+		appLockModule?.getStatus().locked === false
+			? {
+					items: historyRepository.listRecentItems(TRAY_RECENT_ITEMS),
+					count: historyRepository.countItems(),
+				}
+			: null,
+	copyItem: (id) => {
+		try {
+			restoreHistoryItem(historyRepository, id);
+			recordAccess(id, "copy", "ui");
+		} catch (error) {
+			console.error("Failed to copy item from the tray:", error);
+		}
+	},
+	getProfiles: profiles.getStatus,
+	switchProfile: (name) => switchProfile(name),
+	getUpdater: () => updater,
+	getTrayClicks: () => trayClicks,
+	jumpList,
+	t,
+});
+const clipboardStack = createClipboardStack();
+const registerPrompt = createRegisterPrompt({ registrar: globalShortcut });
+const pasteQueue = createPasteQueue({
+	onChange: (itemIds) => {
+		windowModule.getWindow()?.webContents.send(QUEUE_CHANGED_CHANNEL, itemIds);
+	},
+});
+const wipeConfirmation = createWipeConfirmation();
+// Captured images are read in the background: their text for search,
+// and any QR code they hold
+const ocrQueue = createRecognitionQueue({
+	label: "Text recognition",
+	read: createOcrEngine({ platform: process.platform }),
+	nextCandidate: historyRepository.nextOcrCandidate,
+	save: historyRepository.setOcrText,
+	shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+});
+const qrQueue = createRecognitionQueue({
+	label: "QR code scanning",
+	read: createQrDecoder({ platform: process.platform }),
+	nextCandidate: historyRepository.nextQrCandidate,
+	save: historyRepository.setQrPayload,
+	shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+});
+const phashQueue = createRecognitionQueue({
+	label: "Image hashing",
+	read: async (png) =>
+		computeDifferenceHash(
+			nativeImage
+				.createFromBuffer(png)
+				.resize({ ...PERCEPTUAL_HASH_GRID, quality: "good" })
+				.toBitmap(),
+		),
+	nextCandidate: historyRepository.nextPhashCandidate,
+	save: historyRepository.setImagePhash,
+	shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+});
+
+let launchAtLoginModule: ReturnType<typeof createLaunchAtLoginModule> | null =
+	null;
+let preferencesStore: ReturnType<typeof createPreferencesStore> | null = null;
+let retentionModule: ReturnType<typeof createRetentionModule> | null = null;
+let backupModule: ReturnType<typeof createBackupModule> | null = null;
+let appLockModule: ReturnType<typeof createAppLockModule> | null = null;
+let clipboardAutoClear: ReturnType<typeof createClipboardAutoClear> | null =
+	null;
+let appExclusions: ReturnType<typeof createAppExclusions> | null = null;
+let secretScanner: ReturnType<typeof createSecretScanner> | null = null;
+let notificationRules: NotificationRules | null = null;
+let captureRules: ReturnType<typeof createCaptureRules> | null = null;
+let userScripts: ReturnType<typeof createUserScripts> | null = null;
+let commandActions: ReturnType<typeof createCommandActions> | null = null;
+let linkPreviews: ReturnType<typeof createLinkPreviews> | null = null;
+let semanticSearch: ReturnType<typeof createSemanticSearch> | null = null;
+let itemSummaries: ReturnType<typeof createItemSummaries> | null = null;
+let faviconCache: ReturnType<typeof createFaviconCache> | null = null;
+let appendCopy: ReturnType<typeof createAppendCopy> | null = null;
+let captureLimits: CaptureLimits | null = null;
+let captureSounds: CaptureSounds | null = null;
+let primarySelection: PrimarySelection | null = null;
+let powerThrottle: PowerThrottle | null = null;
+let universalClipboard: UniversalClipboard | null = null;
+let clipboardRestore: ClipboardRestore | null = null;
+let readOnlyMode: ReadOnlyMode | null = null;
+let shortcutManager: ReturnType<typeof createShortcutManager> | null = null;
+let quickPaste: ReturnType<typeof createQuickPaste> | null = null;
+let chordManager: ReturnType<typeof createChordManager> | null = null;
+let trayClicks: TrayClicks | null = null;
+let windowBehavior: ReturnType<typeof createWindowBehavior> | null = null;
+let settingsFile: ReturnType<typeof createSettingsFile> | null = null;
+let controlServer: ReturnType<typeof createControlServer> | null = null;
+let httpApiSettings: ReturnType<typeof createHttpApiSettings> | null = null;
+let urlActionSettings: UrlActionSettingsStore | null = null;
+let httpApiServer: ReturnType<typeof createHttpApiServer> | null = null;
+let webhooks: ReturnType<typeof createWebhooks> | null = null;
+let dbusService: ReturnType<typeof createDBusService> | null = null;
+let syncSettings: ReturnType<typeof createSyncSettings> | null = null;
+let sync: ReturnType<typeof createSync> | null = null;
+let remoteSyncSettings: ReturnType<typeof createRemoteSyncSettings> | null =
+	null;
+let remoteSync: ReturnType<typeof createRemoteSync> | null = null;
+let boardSettings: ReturnType<typeof createBoardSettings> | null = null;
+let boards: ReturnType<typeof createBoards> | null = null;
+let typeOut: ReturnType<typeof createTypeOut> | null = null;
+let textExpander: ReturnType<typeof createTextExpander> | null = null;
+let doubleCopy: ReturnType<typeof createDoubleCopy> | null = null;
+let updater: ReturnType<typeof createUpdater> | null = null;
+
+/**
+ * Throws while the app is locked; checked before every history, tag and
+ * collection handler.
+ */
+const assertUnlocked = (): void => {
+	if (!appLockModule) {
+		throw new Error("App lock module not initialized");
+	}
+	appLockModule.assertUnlocked();
+};
+
+// Create handlers
+const clipboardHandlers = createClipboardHandlers(historyRepository);
+const dbHandlers = guardHandlers(
+	createDbHandlers(historyRepository, () =>
+		primarySelection?.getListSelection(),
+	),
+	assertUnlocked,
+);
+const tagHandlers = guardHandlers(
+	createTagHandlers(tagRepository),
+	assertUnlocked,
+);
+const collectionHandlers = guardHandlers(
+	createCollectionHandlers(collectionRepository),
+	assertUnlocked,
+);
+const windowHandlers = createWindowHandlers(windowModule);
+
+/**
+ * Notifies the renderer that history changed so it can refetch, and
+ * rebuilds the tray's recent items.
+ */
+const notifyHistoryChanged = (): void => {
+	trayModule.update();
+	windowModule.getWindow()?.webContents.send(HISTORY_CHANGED_CHANNEL);
+	pickerModule.getWindow()?.webContents.send(HISTORY_CHANGED_CHANNEL);
+};
+
+/**
+ * What the clipboard held once a one-time item was placed on it, until
+ * something else is copied; recording it would bring the item back.
+ */
+let oneTimeClip: ClipboardSnapshot | null = null;
+
+/**
+ * Refreshes the windows, tray, event stream and sync after a one-time
+ * item was used and deleted.
+ * @param onClipboard - Whether the use placed it on the clipboard
+ */
+const forgetOneTimeItem = (id: number, onClipboard: boolean): void => {
+	if (onClipboard) oneTimeClip = readClipboardSnapshot();
+	notifyHistoryChanged();
+	publishHistoryEvent({ type: "item.deleted", id });
+	sync?.notifyLocalChange();
+	remoteSync?.notifyLocalChange();
+};
+
+/**
+ * Whether a clipboard change is the one-time item the app placed there.
+ * Rich formats are left out, as the Windows format writer rewrites them.
+ */
+const isOneTimeClip = (snapshot: ClipboardSnapshot): boolean =>
+	oneTimeClip !== null &&
+	snapshot.text === oneTimeClip.text &&
+	snapshot.image?.hash === oneTimeClip.image?.hash &&
+	(snapshot.files ?? []).join("\n") === (oneTimeClip.files ?? []).join("\n");
+
+/**
+ * A history change as the webhook posts it: new items also carry their
+ * kind and text, except the text of items flagged as secrets.
+ */
+const toWebhookEvent = (event: HistoryEvent): WebhookEvent => {
+	if (event.type !== "item.added") return event;
+	const item = historyRepository.getItem(event.item.id);
+	return {
+		type: "item.added",
+		item: {
+			...event.item,
+			kind: item?.content_kind ?? null,
+			content: item && !item.secret_match ? getItemText(item) : null,
+		},
+	};
+};
+
+/**
+ * A history change as the renderer receives it: new items come as list
+ * rows, masked if flagged as secrets.
+ */
+type RendererHistoryEvent =
+	| Exclude<HistoryEvent, { type: "item.added" }>
+	| { type: "item.added"; item: HistoryRow };
+
+/**
+ * Sends a history change to the main window and the picker.
+ */
+const sendHistoryEvent = (event: HistoryEvent): void => {
+	let message: RendererHistoryEvent;
+	if (event.type === "item.added") {
+		const item = historyRepository.getItem(event.item.id);
+		if (!item) return;
+		message = { type: "item.added", item: redactSecret(item) };
+	} else {
+		message = event;
+	}
+	windowModule.getWindow()?.webContents.send(HISTORY_EVENT_CHANNEL, message);
+	pickerModule.getWindow()?.webContents.send(HISTORY_EVENT_CHANNEL, message);
+};
+
+/**
+ * Sends a history change to the renderer, HTTP API event stream clients,
+ * the webhook and, for new items, D-Bus listeners; dropped while history
+ * is locked.
+ */
+const publishHistoryEvent = (event: HistoryEvent): void => {
+	if (!appLockModule || appLockModule.getStatus().locked) return;
+	sendHistoryEvent(event);
+	httpApiServer?.broadcast(event);
+	webhooks?.publish(toWebhookEvent(event));
+	if (event.type === "item.added") dbusService?.emitItemAdded(event.item);
+};
+
+/**
+ * Publishes the most recently copied item, after a sync.
+ */
+const publishNewestItem = (): void => {
+	const item = historyRepository.getRecentItem(0);
+	if (item) publishHistoryEvent({ type: "item.added", item: toApiItem(item) });
+};
+
+/**
+ * Places the item at `index` in copy order (0 = newest) on the clipboard,
+ * then pastes it into the focused app if `paste` is set.
+ * Does nothing while history is locked or has no such item.
+ */
+const restoreRecentItem = async (
+	index: number,
+	paste: boolean,
+): Promise<void> => {
+	if (!appLockModule || appLockModule.getStatus().locked) return;
+	const item = historyRepository.getRecentItem(index);
+	if (!item) return;
+	restoreHistoryItem(historyRepository, item.id);
+	recordAccess(item.id, paste ? "paste" : "copy", "ui");
+	if (paste) {
+		await windowHandlers.hideAndPaste();
+	}
+};
+
+/**
+ * Places a history item on the clipboard, hides the window and pastes it
+ * into the application that had focus before the picker opened.
+ */
+const pasteHistoryItem = async (
+	id: number,
+	options: { files?: FileRestoreMode } = {},
+): Promise<void> => {
+	restoreHistoryItem(historyRepository, id, options);
+	recordAccess(id, "paste", "ui");
+	await windowHandlers.hideAndPaste();
+};
+
+/**
+ * Pastes the next queued item into the focused app, skipping items deleted
+ * since they were queued. Does nothing while history is locked or the
+ * queue is empty.
+ */
+const pasteNextQueued = async (): Promise<void> => {
+	if (!appLockModule || appLockModule.getStatus().locked) return;
+	for (let id = pasteQueue.shift(); id !== null; id = pasteQueue.shift()) {
+		if (!historyRepository.getItem(id)) continue;
+		restoreHistoryItem(historyRepository, id);
+		recordAccess(id, "paste", "ui");
+		await windowHandlers.hideAndPaste();
+		return;
+	}
+};
+
+/**
+ * Pushes the current clipboard onto the clipboard stack. Skips empty
+ * clipboards and ones marked sensitive by a password manager.
+ */
+const pushClipboardToStack = (): void => {
+	if (isMarkedSensitive(clipboard)) return;
+	clipboardStack.push({
+		text: clipboard.readText(),
+		rtf: clipboard.readRTF() || undefined,
+		html: clipboard.readHTML() || undefined,
+	});
+};
+
+/**
+ * Pops the top of the clipboard stack onto the clipboard and pastes it into
+ * the focused app. Does nothing while history is locked or the stack is
+ * empty.
+ */
+const popClipboardFromStack = async (): Promise<void> => {
+	if (!appLockModule || appLockModule.getStatus().locked) return;
+	const entry = clipboardStack.pop();
+	if (!entry) return;
+	clipboard.write(entry);
+	await windowHandlers.hideAndPaste();
+};
+
+/**
+ * Saves the current clipboard's text, RTF and HTML into a named register.
+ */
+const copyClipboardToRegister = (name: string) => {
+	if (isMarkedSensitive(clipboard)) {
+		throw new Error("The clipboard is marked sensitive and was not saved");
+	}
+	return registerRepository.setRegister(name, {
+		text: clipboard.readText(),
+		rtf: clipboard.readRTF() || undefined,
+		html: clipboard.readHTML() || undefined,
+	});
+};
+
+/**
+ * Places a named register on the clipboard and pastes it into the focused
+ * app.
+ */
+const pasteRegister = async (name: string): Promise<void> => {
+	const register = registerRepository.getRegister(name);
+	if (!register) {
+		throw new Error(`Register "${name}" is empty`);
+	}
+	clipboard.write({
+		text: register.content,
+		rtf: register.rtf || undefined,
+		html: register.html || undefined,
+	});
+	await windowHandlers.hideAndPaste();
+};
+
+/**
+ * What a snippet's placeholders are filled with at paste time.
+ */
+const getSnippetContext = (
+	snippet: SnippetRow,
+	fields: Record<string, string>,
+): SnippetContext => ({
+	clipboard: clipboard.readText(),
+	fields,
+	now: new Date(),
+	nextCounter: () => snippetRepository.incrementCounter(snippet.id),
+	randomUUID,
+});
+
+/**
+ * Fills in a snippet's placeholders and pastes it into the focused app,
+ * then moves the caret back to its `{cursor}`.
+ * `{clipboard}` is the clipboard text from before the paste.
+ */
+const pasteSnippet = async (id: number, fields: unknown): Promise<void> => {
+	const snippet = snippetRepository.getSnippet(id);
+	if (!snippet) {
+		throw new Error(`Snippet not found: ${id}`);
+	}
+	const { text, caretSteps } = resolveSnippet(
+		snippet.content,
+		getSnippetContext(snippet, parseSnippetFieldValues(fields)),
+	);
+	clipboard.writeText(text);
+	await windowHandlers.hideAndPaste();
+	try {
+		await pressKeys("left", caretSteps);
+	} catch (error) {
+		console.error("Failed to move caret to snippet cursor:", error);
+	}
+};
+
+/**
+ * Replaces an abbreviation just typed in another app with its snippet:
+ * erases the abbreviation, pastes the snippet and moves the caret to its
+ * `{cursor}`. Snippets with fields cannot be expanded, since there is no
+ * window to ask for the values.
+ */
+const expandAbbreviation = async (
+	snippetId: number,
+	typedLength: number,
+): Promise<void> => {
+	if (!appLockModule) {
+		throw new Error("App lock module not initialized");
+	}
+	appLockModule.assertUnlocked();
+	const snippet = snippetRepository.getSnippet(snippetId);
+	if (!snippet) {
+		throw new Error(`Snippet not found: ${snippetId}`);
+	}
+	const { text, caretSteps } = resolveSnippet(
+		snippet.content,
+		getSnippetContext(snippet, {}),
+	);
+	await pressKeys("backspace", typedLength);
+	clipboard.writeText(text);
+	await simulatePaste();
+	await pressKeys("left", caretSteps);
+};
+
+/**
+ * Types a text item into the previous application key by key, for fields
+ * that block pasting.
+ */
+const typeHistoryItem = async (id: number): Promise<void> => {
+	if (!typeOut) {
+		throw new Error("Type-out not initialized");
+	}
+	const item = historyRepository.getItem(id);
+	if (!item) {
+		throw new Error(`History item not found: ${id}`);
+	}
+	if (item.type !== "text") {
+		throw new Error("Only text items can be typed out");
+	}
+	await hideAndRefocus(windowModule);
+	recordAccess(id, "paste", "ui", "typed out");
+	await typeOut.typeText(item.content);
+	if (isReadOnly()) return;
+	if (historyRepository.recordUse(id)) forgetOneTimeItem(id, false);
+};
+
+/**
+ * Replaces the clipboard with its plain text, dropping rich formats, then
+ * pastes it into the focused app. Does nothing if there is no text.
+ */
+const pastePlainText = async (): Promise<void> => {
+	const text = clipboard.readText();
+	if (!text) return;
+	clipboard.writeText(text);
+	await windowHandlers.hideAndPaste();
+};
+
+/**
+ * Resolves the app a copy was made in; runs as soon as a change is seen so
+ * the user has had little chance to switch away.
+ */
+const resolveSourceApp = createForegroundAppResolver({
+	platform: process.platform,
+});
+
+/**
+ * The history item type a snapshot is stored as.
+ */
+const getCaptureItemType = ({
+	text,
+	image,
+	files,
+}: ClipboardSnapshot): CaptureItemType => {
+	if (files && files.length > 0) return "files";
+	return image && isEmptyText(text) ? "image" : "text";
+};
+
+/**
+ * Notification text for a captured item; flagged secrets stay masked.
+ */
+const describeCapturedItem = (item: HistoryRow): string => {
+	if (item.type === "image") return "Copied an image";
+	if (item.type === "files") return "Copied files";
+	return item.secret_match !== null
+		? maskSecretPreview(item.content)
+		: item.content.slice(0, NOTIFICATION_PREVIEW_CHARS);
+};
+
+/**
+ * Applies the tags, expiry and notifications of the capture rules a copy
+ * matched, and any other tags it was given, to the item it was recorded
+ * as.
+ */
+const applyCaptureOutcome = (
+	id: number,
+	outcome: CaptureRuleOutcome | undefined,
+	extraTags: readonly string[],
+): void => {
+	const tags = [...(outcome?.tags ?? []), ...extraTags];
+	const expireAfterMinutes = outcome?.expireAfterMinutes ?? null;
+	const notify = outcome?.notify ?? [];
+	if (tags.length === 0 && expireAfterMinutes === null && notify.length === 0) {
+		return;
+	}
+	const item = historyRepository.getItem(id);
+	if (!item) return;
+	for (const tag of tags) {
+		tagRepository.addTag(item.id, tag);
+	}
+	if (expireAfterMinutes !== null) {
+		historyRepository.expireItem(item.id, expireAfterMinutes);
+	}
+	if (notify.length > 0) {
+		notificationRules?.notify({
+			rule: "captureRule",
+			ruleNames: notify,
+			preview: describeCapturedItem(item),
+		});
+	}
+};
+
+/**
+ * A clipboard change the capture rules and scripts let through, ready to
+ * be written.
+ */
+type PendingCapture = {
+	item: NewHistoryItem;
+	outcome: CaptureRuleOutcome | undefined;
+	/** Tags added besides the rules', such as for Universal Clipboard copies */
+	tags: string[];
+};
+
+/**
+ * Runs a clipboard change through the Universal Clipboard setting, size
+ * limits, capture rules and scripts.
+ * @returns what to record, or null if a setting, limit, rule or script
+ * dropped it
+ */
+const prepareCapture = (
+	snapshot: ClipboardSnapshot,
+	source: SourceApp | null,
+): PendingCapture | null => {
+	const { image, files } = snapshot;
+	const remote = universalClipboard?.decide(snapshot.remote === true);
+	if (remote?.skip) return null;
+	// Limits apply first, so rules and scripts never scan a huge copy
+	const limited: LimitedClip | null = captureLimits
+		? captureLimits.limitText(snapshot)
+		: snapshot;
+	if (!limited) {
+		console.warn("Skipped a copy over the capture size limits");
+		return null;
+	}
+	const type = getCaptureItemType(snapshot);
+	const outcome = captureRules?.evaluate({ type, text: limited.text });
+	if (outcome?.ignored) return null;
+	// Capture scripts see the text the rules left
+	const scripted = userScripts?.runOnCapture({
+		type,
+		text: outcome?.text ?? limited.text,
+	});
+	if (scripted?.rejected) return null;
+	const text = scripted?.text ?? outcome?.text ?? limited.text;
+	// Rich formats and the full text no longer match text a rule or
+	// script rewrote
+	const transformed = text !== limited.text;
+	const png = image?.toPNG();
+	const imageAllowed =
+		png !== undefined && (captureLimits?.allowsImage(png.length) ?? true);
+	if (png && !imageAllowed) {
+		console.warn(`Skipped a ${png.length} byte image over the size limit`);
+	}
+	// Extra formats only match the complete, unchanged copy
+	const formatsMatch =
+		!transformed && (limited.fullText ?? limited.text) === snapshot.text;
+	return {
+		item: {
+			text,
+			rtf: transformed ? undefined : limited.rtf,
+			html: transformed ? undefined : limited.html,
+			fullText: transformed ? undefined : limited.fullText,
+			formats: formatsMatch ? snapshot.formats : undefined,
+			files,
+			source,
+			secret: secretScanner?.scan(text),
+			image:
+				image && png && imageAllowed
+					? { png, width: image.width, height: image.height }
+					: undefined,
+		},
+		outcome,
+		tags: remote?.tags ?? [],
+	};
+};
+
+/**
+ * Adds captured clipboard changes to history in one transaction, then
+ * refreshes the windows, tray, event stream, sync and background queues
+ * once for the whole batch.
+ */
+const recordCaptures = (captures: PendingCapture[]): void => {
+	const added: HistoryRow[] = [];
+	let addedImage = false;
+	let addedText = false;
+	dbModule.getDb().transaction(() => {
+		for (const { item, outcome, tags } of captures) {
+			let id: number | null;
+			try {
+				id = historyRepository.addItem(item);
+			} catch (error) {
+				// One bad copy (too large) must not lose the rest of the batch
+				console.error("Failed to record clipboard change:", error);
+				continue;
+			}
+			if (id === null) continue;
+			applyCaptureOutcome(id, outcome, tags);
+			const recorded = historyRepository.getItem(id);
+			if (recorded) added.push(recorded);
+			if (item.image) {
+				addedImage = true;
+			} else if (item.text) {
+				addedText = true;
+			}
+		}
+	})();
+	if (added.length === 0) return;
+
+	captureSounds?.play("capture");
+	// Badge the tray for captures the user has not seen yet
+	if (!windowModule.isVisible() && !pickerModule.isVisible()) {
+		trayActivity.recordCapture(added.length);
+	}
+	notifyHistoryChanged();
+	for (const item of added) {
+		publishHistoryEvent({ type: "item.added", item: toApiItem(item) });
+		if (item.secret_match !== null) {
+			notificationRules?.notify({
+				rule: "secretCopied",
+				secretName: item.secret_match,
+			});
+		}
+	}
+	sync?.notifyLocalChange();
+	remoteSync?.notifyLocalChange();
+	if (addedImage) {
+		ocrQueue.schedule();
+		qrQueue.schedule();
+		phashQueue.schedule();
+	}
+	if (addedText) {
+		linkPreviews?.schedule();
+		semanticSearch?.schedule();
+		itemSummaries?.schedule();
+	}
+};
+
+/**
+ * Adds a clipboard change to history right away, after the capture rules
+ * and scripts have had their say.
+ */
+const recordSnapshot = (
+	snapshot: ClipboardSnapshot,
+	source: SourceApp | null,
+): void => {
+	const capture = prepareCapture(snapshot, source);
+	if (capture) recordCaptures([capture]);
+};
+
+/**
+ * Whether a capture holds the same content as the one before it, as when
+ * an app reports one copy several times.
+ */
+const isRepeatCapture = (
+	previous: PendingCapture,
+	next: PendingCapture,
+): boolean => {
+	const [a, b] = [previous.item, next.item];
+	if (a.text !== b.text || a.primary !== b.primary) return false;
+	if ((a.files ?? []).join("\n") !== (b.files ?? []).join("\n")) return false;
+	if (!a.image || !b.image) return a.image === b.image;
+	return a.image.png.equals(b.image.png);
+};
+
+// Copies from the watcher are written in batches: a burst of copies (a
+// scripted xclip loop) costs one transaction and one refresh, not one each.
+// The same content again right away is one copy reported twice and is
+// written once
+const captureBatcher = createCaptureBatcher<PendingCapture>({
+	flush: recordCaptures,
+	isRepeat: isRepeatCapture,
+});
+
+// Background clipboard capture runs in the main process so copies are
+// recorded even while the window is hidden and the renderer is throttled
+const clipboardWatcher = createClipboardWatcher({
+	readClipboard: readClipboardSnapshot,
+	listener: createClipboardListener({
+		platform: process.platform,
+		env: process.env,
+	}),
+	onChange: (snapshot) => {
+		// Password managers mark secrets "do not record"; never store them
+		if (snapshot.sensitive) {
+			clipboardAutoClear?.schedule(snapshot.text);
+			return;
+		}
+		// Our own append-copy write; the copy it was built from is recorded
+		if (appendCopy?.isCollected(snapshot.text)) return;
+		// A used one-time item stays out of history
+		if (isOneTimeClip(snapshot)) return;
+		oneTimeClip = null;
+		if (!snapshot.image && !snapshot.files) {
+			const combined = appendCopy?.append(snapshot.text);
+			if (combined) clipboard.writeText(combined);
+		}
+		// Paused (incognito) capture and read-only mode record nothing
+		if (capturePause.isPaused() || isReadOnly()) return;
+		void resolveSourceApp()
+			.then((source) => {
+				if (appExclusions?.isExcluded(source?.name ?? null)) return;
+				const capture = prepareCapture(snapshot, source);
+				if (capture) captureBatcher.push(capture);
+			})
+			.catch((error) => {
+				console.error("Failed to record clipboard change:", error);
+			});
+	},
+});
+
+/**
+ * Applies a change in power throttling: the watcher's polling interval,
+ * and the background jobs held back, which resume once they may run.
+ */
+const applyPowerThrottle = (status: PowerThrottleStatus): void => {
+	clipboardWatcher.setPollInterval(
+		status.pollIntervalMs ?? DEFAULT_WATCH_INTERVAL_MS,
+	);
+	if (status.deferJobs) return;
+	ocrQueue.schedule();
+	qrQueue.schedule();
+	phashQueue.schedule();
+	linkPreviews?.schedule();
+	semanticSearch?.schedule();
+	itemSummaries?.schedule();
+};
+
+/**
+ * Queues text selected in the PRIMARY selection like a copy, flagged so
+ * history can show it apart from the clipboard.
+ */
+const recordSelection = (text: string): void => {
+	if (capturePause.isPaused() || isReadOnly()) return;
+	void resolveSourceApp()
+		.then((source) => {
+			if (appExclusions?.isExcluded(source?.name ?? null)) return;
+			const capture = prepareCapture({ text }, source);
+			if (!capture) return;
+			captureBatcher.push({
+				...capture,
+				item: { ...capture.item, primary: true },
+			});
+		})
+		.catch((error) => {
+			console.error("Failed to record PRIMARY selection:", error);
+		});
+};
+
+/**
+ * Wraps an IPC handler that exposes history contents so it throws while
+ * the app lock is engaged.
+ */
+const requireUnlocked =
+	<Args extends unknown[], Result>(handler: (...args: Args) => Result) =>
+	(...args: Args): Result => {
+		assertUnlocked();
+		return handler(...args);
+	};
+
+/**
+ * Wraps an IPC handler that changes history outside the repositories, such
+ * as a wipe or a backup restore, so it throws while read-only mode is on.
+ */
+const requireWritable =
+	<Args extends unknown[], Result>(handler: (...args: Args) => Result) =>
+	(...args: Args): Result => {
+		assertWritable();
+		return handler(...args);
+	};
+
+/**
+ * Text of a history item as scripts see it: one path per line for files,
+ * null for images.
+ */
+const getItemText = (item: HistoryRow): string | null => {
+	if (item.type === "image") return null;
+	return item.type === "files"
+		? parseStoredFileList(item.content).join("\n")
+		: item.content;
+};
+
+/**
+ * Text of a stored item for `clipctl get` and D-Bus `Get`.
+ *
+ * @throws if the item does not exist or is an image
+ */
+const readItemText = (
+	historyRepository: HistoryRepository,
+	id: number,
+): string => {
+	const item = historyRepository.getItem(id);
+	if (!item) {
+		throw new Error(`History item not found: ${id}`);
+	}
+	const text = getItemText(item);
+	if (text === null) {
+		throw new Error(`Item ${id} is an image; use copy instead`);
+	}
+	return text;
+};
+
+/**
+ * Creates the handlers behind `clipctl`. Reading history requires the app
+ * to be unlocked; adding does not, like capture.
+ */
+const createControlHandlers = (
+	historyRepository: HistoryRepository,
+): ControlHandlers => ({
+	list: requireUnlocked((limit: number) =>
+		historyRepository.listRecentItems(limit).map(toApiItem),
+	),
+	get: requireUnlocked((id: number) => {
+		const text = readItemText(historyRepository, id);
+		recordAccess(id, "reveal", "cli");
+		return text;
+	}),
+	copy: requireUnlocked((id: number) => {
+		restoreHistoryItem(historyRepository, id);
+		recordAccess(id, "copy", "cli");
+	}),
+	add: (text: string) => {
+		const id = historyRepository.addItem({ text });
+		if (id === null) {
+			throw new Error("Nothing to add: the text is empty");
+		}
+		const item = historyRepository.getItem(id);
+		if (!item) {
+			throw new Error("Failed to add item");
+		}
+		notifyHistoryChanged();
+		publishHistoryEvent({ type: "item.added", item: toApiItem(item) });
+		sync?.notifyLocalChange();
+		remoteSync?.notifyLocalChange();
+		return id;
+	},
+	audit: requireUnlocked((limit: number, since: string | null) =>
+		accessLogRepository.list(parseAccessLogQuery({ limit, since })),
+	),
+});
+
+/**
+ * Creates the handlers behind the local HTTP API. The server itself
+ * refuses requests while the app is locked.
+ */
+const createHttpApiHandlers = (
+	historyRepository: HistoryRepository,
+): HttpApiHandlers => ({
+	search: (query, limit) => {
+		const items = query.trim()
+			? historyRepository.searchItems({ query, limit })
+			: historyRepository.listRecentItems(limit);
+		return items.map(toApiItem);
+	},
+	getItem: (id) => {
+		const item = historyRepository.getItem(id);
+		if (!item) return undefined;
+		recordAccess(id, "reveal", "api");
+		return { ...toApiItem(item), content: getItemText(item) };
+	},
+	copyItem: (id) => {
+		if (!historyRepository.getItem(id)) return false;
+		restoreHistoryItem(historyRepository, id);
+		recordAccess(id, "copy", "api");
+		return true;
+	},
+	setClipboardText: (text) => {
+		clipboard.writeText(text);
+	},
+});
+
+/**
+ * Creates the handlers behind the Linux D-Bus service; all of them
+ * require the app to be unlocked.
+ */
+const createDBusHandlers = (
+	historyRepository: HistoryRepository,
+): DBusHandlers => ({
+	list: requireUnlocked((limit: number) =>
+		historyRepository.listRecentItems(limit).map(toApiItem),
+	),
+	search: requireUnlocked((query: string, limit: number) =>
+		historyRepository.searchItems({ query, limit }).map(toApiItem),
+	),
+	get: requireUnlocked((id: number) => {
+		const text = readItemText(historyRepository, id);
+		recordAccess(id, "reveal", "dbus");
+		return text;
+	}),
+	copy: requireUnlocked((id: number) => {
+		restoreHistoryItem(historyRepository, id);
+		recordAccess(id, "copy", "dbus");
+	}),
+	paste: requireUnlocked(async (id: number) => {
+		restoreHistoryItem(historyRepository, id);
+		recordAccess(id, "paste", "dbus");
+		await windowHandlers.hideAndPaste();
+	}),
+});
+
+/**
+ * Creates the history access behind LAN and remote sync. Both send and
+ * accept nothing while the app is locked.
+ */
+const createSyncHandlers = (
+	historyRepository: HistoryRepository,
+): SyncHandlers => ({
+	listItemsAfter: historyRepository.listSyncItems,
+	getLastItemId: historyRepository.getLastItemId,
+	receiveItems: (items, deviceId) => {
+		const added = historyRepository.addSyncedItems(items, deviceId);
+		if (added > 0) {
+			notifyHistoryChanged();
+			publishNewestItem();
+		}
+		return added;
+	},
+	listTombstonesAfter: historyRepository.listSyncTombstones,
+	getLastTombstoneId: historyRepository.getLastTombstoneId,
+	receiveTombstones: (tombstones, deviceId) => {
+		const ids = historyRepository.applySyncTombstones(tombstones, deviceId);
+		for (const id of ids) publishHistoryEvent({ type: "item.deleted", id });
+		if (ids.length > 0) {
+			trayModule.update();
+			notifyHistoryChanged();
+		}
+		return ids.length;
+	},
+});
+
+/**
+ * Refuses an automation action that would send item text or change the
+ * clipboard while that is off, and asks before one that reads or pastes
+ * history: any web page can open a `clipboard-manager://` link.
+ *
+ * @throws if the action is turned off or the user declines
+ */
+const authorizeUrlAction = async (action: UrlAction): Promise<void> => {
+	if (!urlActionSettings) {
+		throw new Error("Automation URL settings not initialized");
+	}
+	const settings = urlActionSettings.getSettings();
+	if (sendsContent(action) && !settings.sendContent) {
+		throw new Error("Sending clipboard items to automations is turned off");
+	}
+	if (changesClipboard(action) && !settings.allowClipboardActions) {
+		throw new Error("Copying and pasting from automations is turned off");
+	}
+
+	const prompt = getUrlActionPrompt(action);
+	if (!prompt) return;
+	const { response } = await dialog.showMessageBox({
+		type: "question",
+		buttons: ["Allow", "Cancel"],
+		defaultId: 1,
+		cancelId: 1,
+		message: prompt,
+		detail:
+			"A clipboard-manager:// link asked for this. Only allow it if you started the automation yourself.",
+	});
+	if (response !== 0) {
+		throw new Error("Cancelled by the user");
+	}
+};
+
+/**
+ * Runs an automation action; resolves to its x-callback-url `result`.
+ */
+const runUrlAction = async (
+	historyRepository: HistoryRepository,
+	action: UrlAction,
+): Promise<string> => {
+	await authorizeUrlAction(action);
+	switch (action.action) {
+		case "latest": {
+			const item = historyRepository.getRecentItem(0);
+			if (!item) {
+				throw new Error("History is empty");
+			}
+			const text = readItemText(historyRepository, item.id);
+			recordAccess(item.id, "reveal", "url");
+			return text;
+		}
+		case "search": {
+			const { query, limit } = action;
+			// A plain deep link opens the history window on the results
+			if (!action.success) {
+				await windowModule.show();
+				windowModule
+					.getWindow()
+					?.webContents.send(SEARCH_REQUESTED_CHANNEL, query);
+				return "";
+			}
+			const items = query.trim()
+				? historyRepository.searchItems({ query, limit })
+				: historyRepository.listRecentItems(limit);
+			return JSON.stringify(items.map(toApiItem));
+		}
+		case "copy":
+		case "paste":
+			// Checked again: the app may have locked while the dialog was open
+			requireUnlocked(restoreHistoryItem)(historyRepository, action.id);
+			recordAccess(action.id, action.action, "url");
+			if (action.action === "paste") await windowHandlers.hideAndPaste();
+			return "";
+		case "toggle-capture":
+			return String(capturePause.toggle().paused);
+	}
+};
+
+/**
+ * Handles a `clipboard-manager://` or `clipman://` URL from Shortcuts,
+ * AppleScript, a link or a launcher, reporting back through its
+ * `x-success` or `x-error` callback.
+ */
+const handleAutomationUrl = async (url: string): Promise<void> => {
+	let action: UrlAction;
+	try {
+		action = parseUrlAction(url);
+		// Not reported through x-error: that callback is the untrusted part
+		assertCallbacksAllowed(
+			action,
+			urlActionSettings?.getSettings().callbackSchemes ?? [],
+		);
+	} catch (error) {
+		console.error("Ignoring automation URL:", error);
+		return;
+	}
+
+	try {
+		// Pausing capture reveals nothing, so it works while locked
+		const run =
+			action.action === "toggle-capture"
+				? runUrlAction
+				: requireUnlocked(runUrlAction);
+		const result = await run(historyRepository, action);
+		if (action.success) {
+			await shell.openExternal(buildCallbackUrl(action.success, { result }));
+		}
+	} catch (error) {
+		const errorMessage = error instanceof Error ? error.message : String(error);
+		if (!action.error) {
+			console.error("Automation URL failed:", error);
+			return;
+		}
+		await shell.openExternal(
+			buildCallbackUrl(action.error, { errorCode: "1", errorMessage }),
+		);
+	}
+};
+
+/**
+ * HTTP API settings plus whether the server is listening.
+ */
+type HttpApiStatus = HttpApiSettings & { running: boolean };
+
+const getHttpApiStatus = (): HttpApiStatus => {
+	if (!httpApiSettings || !httpApiServer) {
+		throw new Error("HTTP API not initialized");
+	}
+	return {
+		...httpApiSettings.getSettings(),
+		running: httpApiServer.getPort() !== null,
+	};
+};
+
+/**
+ * Sync status; throws before sync is set up.
+ */
+const getSyncStatus = () => {
+	if (!sync) {
+		throw new Error("Sync not initialized");
+	}
+	return sync.getStatus();
+};
+
+/**
+ * Remote sync status; throws before remote sync is set up.
+ */
+const getRemoteSyncStatus = () => {
+	if (!remoteSync) {
+		throw new Error("Remote sync not initialized");
+	}
+	return remoteSync.getStatus();
+};
+
+/**
+ * Shared board status; throws before boards are set up.
+ */
+const getBoardsStatus = () => {
+	if (!boards) {
+		throw new Error("Shared boards not initialized");
+	}
+	return boards.getStatus();
+};
+
+/**
+ * The settings file sections owned by other modules; throws before they
+ * are set up.
+ */
+const getModuleSettings = (): ModuleSettings => {
+	if (
+		!shortcutManager ||
+		!retentionModule ||
+		!appExclusions ||
+		!windowBehavior
+	) {
+		throw new Error("Settings not initialized");
+	}
+	return {
+		shortcuts: shortcutManager.getSettings(),
+		retention: retentionModule.getPolicy(),
+		exclusions: appExclusions.getSettings(),
+		window: windowBehavior.getSettings(),
+	};
+};
+
+/**
+ * Hands sections changed in the settings file to their modules; new
+ * shortcuts are registered and a new retention policy enforced at once.
+ */
+const applyModuleSettings = (changes: Partial<ModuleSettings>): void => {
+	if (changes.shortcuts) shortcutManager?.updateSettings(changes.shortcuts);
+	if (changes.retention) retentionModule?.updatePolicy(changes.retention);
+	if (changes.exclusions) appExclusions?.updateSettings(changes.exclusions);
+	if (changes.window) windowBehavior?.updateSettings(changes.window);
+};
+
+/**
+ * Relaunches the app in another profile, created if new. Windows,
+ * shortcuts and servers all belong to the running profile, so nothing is
+ * switched in place. Returns false if the profile is already running.
+ *
+ * @throws if the name is invalid
+ */
+const switchProfile = (name: unknown): boolean => {
+	const selected = profiles.select(name);
+	if (selected === profiles.getStatus().active) return false;
+	app.relaunch({ args: withProfileArg(process.argv.slice(1), selected) });
+	app.quit();
+	return true;
+};
+
+/**
+ * Reads the key update installers are verified with from the app's
+ * resources, or null if this build has none.
+ */
+const readUpdatePublicKey = (): string | null => {
+	try {
+		return fs.readFileSync(
+			path.join(process.resourcesPath, UPDATE_PUBLIC_KEY_FILE),
+			"utf-8",
+		);
+	} catch {
+		return null;
+	}
+};
+
+/**
+ * Starts, restarts or stops the HTTP API to match its settings.
+ */
+const applyHttpApiSettings = async (): Promise<void> => {
+	if (!httpApiSettings || !httpApiServer) return;
+	const { enabled, port } = httpApiSettings.getSettings();
+	if (enabled) {
+		await httpApiServer.start(port);
+	} else {
+		await httpApiServer.stop();
+	}
+};
+
+/**
+ * Refreshes open views and schedules sync once an import added items.
+ */
+const afterImport = (report: HistoryImportReport): HistoryImportReport => {
+	if (report.imported > 0) {
+		notifyHistoryChanged();
+		sync?.notifyLocalChange();
+		remoteSync?.notifyLocalChange();
+	}
+	return report;
+};
+
+// Register all IPC handlers
+const registerIpcHandlers = (): void => {
+	// Clipboard handlers
+	ipcMain.handle("clipboard:read", clipboardHandlers.read);
+	ipcMain.handle("clipboard:write", clipboardHandlers.write);
+	ipcMain.handle(
+		"clipboard:restoreItem",
+		requireUnlocked(clipboardHandlers.restoreItem),
+	);
+	ipcMain.handle(
+		"clipboard:pasteItem",
+		requireUnlocked(
+			(
+				_event: Electron.IpcMainInvokeEvent,
+				id: number,
+				options?: { files?: unknown },
+			) =>
+				pasteHistoryItem(id, { files: parseFileRestoreMode(options?.files) }),
+		),
+	);
+	ipcMain.handle(
+		"clipboard:typeItem",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, id: number) =>
+			typeHistoryItem(id),
+		),
+	);
+
+	// Database handlers
+	ipcMain.handle("db:getHistory", dbHandlers.getHistory);
+	ipcMain.handle("db:listPage", dbHandlers.listPage);
+	ipcMain.handle("db:listGroupedPage", dbHandlers.listGroupedPage);
+	ipcMain.handle("db:revealItem", dbHandlers.revealItem);
+	// Links open in the default browser or mail app, and only with an
+	// allowed scheme; files and paths are shown in the file manager
+	ipcMain.handle(
+		"db:openItem",
+		requireUnlocked(async (_event: Electron.IpcMainInvokeEvent, id: number) => {
+			const item = historyRepository.getItem(id);
+			if (!item) {
+				throw new Error(`History item not found: ${id}`);
+			}
+			await shell.openExternal(resolveOpenUrl(item));
+		}),
+	);
+	ipcMain.handle(
+		"db:showItemInFolder",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, id: number) => {
+			const item = historyRepository.getItem(id);
+			if (!item) {
+				throw new Error(`History item not found: ${id}`);
+			}
+			const target = resolveRevealPath(item, os.homedir());
+			if (!fs.existsSync(target)) {
+				throw new Error(`File not found: ${target}`);
+			}
+			shell.showItemInFolder(target);
+		}),
+	);
+	ipcMain.handle("db:searchHistory", dbHandlers.searchHistory);
+	ipcMain.handle("db:fuzzySearchHistory", dbHandlers.fuzzySearchHistory);
+	ipcMain.handle("db:regexSearchHistory", dbHandlers.regexSearchHistory);
+	ipcMain.handle("db:semanticSearch", dbHandlers.semanticSearch);
+	ipcMain.handle("db:deleteHistoryItem", (event, id: number) => {
+		dbHandlers.deleteHistoryItem(event, id);
+		trayModule.update();
+		publishHistoryEvent({ type: "item.deleted", id });
+		sync?.notifyLocalChange();
+		remoteSync?.notifyLocalChange();
+	});
+	ipcMain.handle("db:clearAllHistory", () => {
+		dbHandlers.clearAllHistory();
+		trayModule.update();
+		publishHistoryEvent({ type: "history.cleared" });
+		sync?.notifyLocalChange();
+		remoteSync?.notifyLocalChange();
+	});
+	ipcMain.handle("db:listTrash", dbHandlers.listTrash);
+	ipcMain.handle(
+		"db:restoreFromTrash",
+		requireUnlocked((event: Electron.IpcMainInvokeEvent, id: number) => {
+			const itemId = dbHandlers.restoreFromTrash(event, id);
+			notifyHistoryChanged();
+			const item = historyRepository.getItem(itemId);
+			if (item) {
+				publishHistoryEvent({ type: "item.added", item: toApiItem(item) });
+			}
+			sync?.notifyLocalChange();
+			remoteSync?.notifyLocalChange();
+			return itemId;
+		}),
+	);
+	ipcMain.handle("db:emptyTrash", dbHandlers.emptyTrash);
+	ipcMain.handle("db:requestWipe", requireUnlocked(wipeConfirmation.issue));
+	ipcMain.handle(
+		"db:wipeAllHistory",
+		requireUnlocked(
+			requireWritable(
+				(_event: Electron.IpcMainInvokeEvent, token: unknown) => {
+					if (!wipeConfirmation.confirm(token)) {
+						throw new Error(
+							"Wipe not confirmed: request a new token and retry",
+						);
+					}
+					wipeHistoryDatabase(dbModule.getDb());
+					historyRepository.collectAttachmentGarbage();
+					clipboard.clear();
+					clipboardStack.clear();
+					pasteQueue.clear();
+					trayModule.update();
+					publishHistoryEvent({ type: "history.cleared" });
+				},
+			),
+		),
+	);
+	ipcMain.handle("db:getImagePreview", dbHandlers.getImagePreview);
+	ipcMain.handle("db:getThumbnail", dbHandlers.getThumbnail);
+	ipcMain.handle("db:copyQrPayload", dbHandlers.copyQrPayload);
+	ipcMain.handle("db:generateQr", dbHandlers.generateQr);
+	ipcMain.handle("db:copyImageAs", dbHandlers.copyImageAs);
+	ipcMain.handle("db:saveImageAs", dbHandlers.saveImageAs);
+	ipcMain.handle("db:getColorFormats", dbHandlers.getColorFormats);
+	ipcMain.handle("db:copyColorAs", dbHandlers.copyColorAs);
+	ipcMain.handle("db:diffItems", dbHandlers.diffItems);
+	ipcMain.handle("db:saveItemToFile", dbHandlers.saveItemToFile);
+	ipcMain.handle("db:stripMetadata", dbHandlers.stripMetadata);
+	ipcMain.handle("db:transformItem", dbHandlers.transformItem);
+	ipcMain.handle("db:formatItem", dbHandlers.formatItem);
+	ipcMain.handle("db:runScript", dbHandlers.runScript);
+	ipcMain.handle("db:runCommandAction", dbHandlers.runCommandAction);
+	ipcMain.handle("db:convertMarkup", dbHandlers.convertMarkup);
+	ipcMain.handle("db:hashItem", dbHandlers.hashItem);
+	ipcMain.handle("db:toggleFavorite", dbHandlers.toggleFavorite);
+	ipcMain.handle("db:togglePin", (event, id: number) => {
+		const pinned = dbHandlers.togglePin(event, id);
+		publishHistoryEvent({ type: "item.pinned", id, pinned });
+		return pinned;
+	});
+	ipcMain.handle(
+		"db:batchUpdate",
+		(event, ids: number[], operation: BatchOperation) => {
+			const updated = dbHandlers.batchUpdate(event, ids, operation);
+			trayModule.update();
+			if (operation.type === "delete") {
+				for (const id of ids) publishHistoryEvent({ type: "item.deleted", id });
+				sync?.notifyLocalChange();
+				remoteSync?.notifyLocalChange();
+			} else if (operation.type === "pin") {
+				const { pinned } = operation;
+				for (const id of ids) {
+					publishHistoryEvent({ type: "item.pinned", id, pinned });
+				}
+			}
+			return updated;
+		},
+	);
+	ipcMain.handle("db:toggleSyncExcluded", dbHandlers.toggleSyncExcluded);
+	ipcMain.handle("db:togglePasteOnce", dbHandlers.togglePasteOnce);
+	ipcMain.handle("db:setItemNote", dbHandlers.setItemNote);
+	ipcMain.handle("db:dismissSecret", dbHandlers.dismissSecret);
+	ipcMain.handle("db:setItemExpiry", dbHandlers.setItemExpiry);
+	ipcMain.handle("db:mergeItems", dbHandlers.mergeItems);
+	ipcMain.handle("db:findNearDuplicates", dbHandlers.findNearDuplicates);
+	ipcMain.handle(
+		"db:findNearDuplicateTexts",
+		dbHandlers.findNearDuplicateTexts,
+	);
+	ipcMain.handle("db:mergeDuplicates", (event, ids: unknown) => {
+		const kept = dbHandlers.mergeDuplicates(event, ids);
+		trayModule.update();
+		for (const id of ids as number[]) {
+			if (id !== kept.id) publishHistoryEvent({ type: "item.deleted", id });
+		}
+		sync?.notifyLocalChange();
+		remoteSync?.notifyLocalChange();
+		return kept;
+	});
+	ipcMain.handle("db:listSourceApps", dbHandlers.listSourceApps);
+	ipcMain.handle("db:exportHistory", dbHandlers.exportHistory);
+	ipcMain.handle("db:exportSnippets", dbHandlers.exportSnippets);
+	ipcMain.handle("db:listAccessLog", dbHandlers.listAccessLog);
+	ipcMain.handle(
+		"db:importHistory",
+		requireUnlocked(
+			async (event: Electron.IpcMainInvokeEvent, filePath: unknown) =>
+				afterImport(await dbHandlers.importHistory(event, filePath)),
+		),
+	);
+	ipcMain.handle(
+		"db:importCopyQ",
+		requireUnlocked(
+			async (event: Electron.IpcMainInvokeEvent, sourcePath: unknown) =>
+				afterImport(await dbHandlers.importCopyQ(event, sourcePath)),
+		),
+	);
+	ipcMain.handle(
+		"db:importDitto",
+		requireUnlocked(
+			async (event: Electron.IpcMainInvokeEvent, dbPath: unknown) =>
+				afterImport(await dbHandlers.importDitto(event, dbPath)),
+		),
+	);
+	ipcMain.handle(
+		"db:importMaccy",
+		requireUnlocked(
+			async (event: Electron.IpcMainInvokeEvent, dbPath: unknown) =>
+				afterImport(await dbHandlers.importMaccy(event, dbPath)),
+		),
+	);
+	ipcMain.handle("db:runMaintenance", () => maintenanceModule.runNow());
+	ipcMain.handle("db:check", () => dbModule.check());
+	ipcMain.handle("db:repair", () => dbModule.repair());
+	ipcMain.handle("db:getLastRecovery", () => dbModule.getLastRecovery());
+
+	// Tag handlers
+	ipcMain.handle("db:addTag", tagHandlers.addTag);
+	ipcMain.handle("db:removeTag", tagHandlers.removeTag);
+	ipcMain.handle("db:getItemTags", tagHandlers.getItemTags);
+	ipcMain.handle("db:listTags", tagHandlers.listTags);
+
+	// Collection handlers
+	ipcMain.handle("collections:list", collectionHandlers.list);
+	ipcMain.handle("collections:create", collectionHandlers.create);
+	ipcMain.handle("collections:rename", collectionHandlers.rename);
+	ipcMain.handle("collections:delete", collectionHandlers.delete);
+	ipcMain.handle("collections:getItems", collectionHandlers.getItems);
+	ipcMain.handle("collections:addItem", collectionHandlers.addItem);
+	ipcMain.handle("collections:removeItem", collectionHandlers.removeItem);
+	ipcMain.handle("collections:moveItem", collectionHandlers.moveItem);
+	ipcMain.handle("collections:reorderItems", collectionHandlers.reorderItems);
+
+	// Register handlers
+	ipcMain.handle(
+		"registers:list",
+		requireUnlocked(registerRepository.listRegisters),
+	);
+	ipcMain.handle("registers:copy", (_event, name: string) =>
+		copyClipboardToRegister(name),
+	);
+	ipcMain.handle(
+		"registers:paste",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, name: string) =>
+			pasteRegister(name),
+		),
+	);
+	ipcMain.handle("registers:delete", (_event, name: string) =>
+		registerRepository.deleteRegister(name),
+	);
+
+	// Snippet handlers
+	ipcMain.handle(
+		"snippets:list",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, query?: string) =>
+			snippetRepository.listSnippets(query),
+		),
+	);
+	ipcMain.handle("snippets:getFields", (_event, content: string) =>
+		listSnippetFields(String(content ?? "")),
+	);
+	ipcMain.handle("snippets:create", (_event, input: SnippetInput) => {
+		const snippet = snippetRepository.createSnippet(input);
+		textExpander?.reloadAbbreviations();
+		return snippet;
+	});
+	ipcMain.handle(
+		"snippets:update",
+		(_event, id: number, input: Partial<SnippetInput>) => {
+			const snippet = snippetRepository.updateSnippet(id, input);
+			textExpander?.reloadAbbreviations();
+			return snippet;
+		},
+	);
+	ipcMain.handle("snippets:delete", (_event, id: number) => {
+		snippetRepository.deleteSnippet(id);
+		textExpander?.reloadAbbreviations();
+	});
+	ipcMain.handle(
+		"snippets:paste",
+		requireUnlocked(
+			(
+				_event: Electron.IpcMainInvokeEvent,
+				id: number,
+				fields?: Record<string, string>,
+			) => pasteSnippet(id, fields),
+		),
+	);
+
+	// Stats handlers
+	ipcMain.handle(
+		"stats:get",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, options?: unknown) =>
+			statsRepository.getStats(options),
+		),
+	);
+	ipcMain.handle(
+		"stats:heatmap",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, year: number) =>
+			statsRepository.getActivityHeatmap(year),
+		),
+	);
+
+	// Window handlers
+	ipcMain.handle("window:center", windowHandlers.center);
+	ipcMain.handle("window:show", windowHandlers.show);
+	ipcMain.handle("window:hide", windowHandlers.hide);
+	ipcMain.handle("window:isVisible", windowHandlers.isVisible);
+	ipcMain.handle("window:hideAndPaste", windowHandlers.hideAndPaste);
+	ipcMain.handle("window:isPinned", windowHandlers.isPinned);
+	ipcMain.handle("window:setPinned", windowHandlers.setPinned);
+
+	// Quick picker window, controlled independently of the history window
+	ipcMain.handle("picker:show", () => pickerModule.show());
+	ipcMain.handle("picker:hide", () => pickerModule.hide());
+	ipcMain.handle("picker:toggle", () => pickerModule.toggle());
+	ipcMain.handle("picker:isVisible", () => pickerModule.isVisible());
+
+	// App handlers
+	ipcMain.handle("app:quit", () => app.quit());
+	ipcMain.handle("app:getLaunchAtLogin", () => {
+		if (!launchAtLoginModule) {
+			throw new Error("Launch at login module not initialized");
+		}
+		return launchAtLoginModule.reconcileOnSettingsOpen();
+	});
+	ipcMain.handle("app:setLaunchAtLogin", (_event, enabled: unknown) => {
+		if (!launchAtLoginModule) {
+			throw new Error("Launch at login module not initialized");
+		}
+		if (typeof enabled !== "boolean") {
+			throw new Error(`Invalid launch at login value: ${String(enabled)}`);
+		}
+		return launchAtLoginModule.setLaunchAtLogin(enabled);
+	});
+	ipcMain.handle("app:getStartMinimized", () => {
+		if (!preferencesStore) {
+			throw new Error("Preferences not initialized");
+		}
+		return preferencesStore.readStartMinimized();
+	});
+	ipcMain.handle("app:setStartMinimized", (_event, enabled: unknown) => {
+		if (!preferencesStore) {
+			throw new Error("Preferences not initialized");
+		}
+		if (typeof enabled !== "boolean") {
+			throw new Error(`Invalid start minimized value: ${String(enabled)}`);
+		}
+		preferencesStore.writeStartMinimized(enabled);
+		return enabled;
+	});
+
+	// App lock handlers
+	ipcMain.handle("lock:getStatus", () => {
+		if (!appLockModule) {
+			throw new Error("App lock module not initialized");
+		}
+		return appLockModule.getStatus();
+	});
+	ipcMain.handle("lock:lock", () => {
+		if (!appLockModule) {
+			throw new Error("App lock module not initialized");
+		}
+		return appLockModule.lock();
+	});
+	ipcMain.handle("lock:unlock", () => {
+		if (!appLockModule) {
+			throw new Error("App lock module not initialized");
+		}
+		return appLockModule.unlock();
+	});
+	ipcMain.handle("lock:updateSettings", async (_event, settings: unknown) => {
+		if (!appLockModule) {
+			throw new Error("App lock module not initialized");
+		}
+		const status = await appLockModule.updateSettings(settings);
+		// Turning an unprotected lock off clears the tray's warning
+		trayModule.update();
+		return status;
+	});
+
+	// Read-only mode handlers
+	ipcMain.handle("readOnly:getStatus", () => {
+		if (!readOnlyMode) {
+			throw new Error("Read-only mode not initialized");
+		}
+		return readOnlyMode.getStatus();
+	});
+	ipcMain.handle(
+		"readOnly:updateSettings",
+		// Turning it off also asks for OS authentication, lock or no lock
+		requireUnlocked(
+			(_event: Electron.IpcMainInvokeEvent, settings: unknown) => {
+				if (!readOnlyMode) {
+					throw new Error("Read-only mode not initialized");
+				}
+				return readOnlyMode.updateSettings(settings);
+			},
+		),
+	);
+
+	// Capture pause handlers
+	ipcMain.handle("capture:getStatus", capturePause.getStatus);
+	ipcMain.handle("capture:setPaused", (_event, paused: unknown) =>
+		capturePause.setPaused(paused),
+	);
+	ipcMain.handle("capture:snooze", (_event, minutes: unknown) =>
+		capturePause.snooze(minutes),
+	);
+
+	// Capture sound handlers
+	ipcMain.handle("sounds:get", () => {
+		if (!captureSounds) {
+			throw new Error("Capture sounds not initialized");
+		}
+		return captureSounds.getSettings();
+	});
+	ipcMain.handle("sounds:update", (_event, settings: unknown) => {
+		if (!captureSounds) {
+			throw new Error("Capture sounds not initialized");
+		}
+		return captureSounds.updateSettings(settings);
+	});
+	ipcMain.handle("sounds:preview", (_event, soundEvent: unknown) => {
+		if (!captureSounds) {
+			throw new Error("Capture sounds not initialized");
+		}
+		if (!isSoundEvent(soundEvent)) {
+			throw new Error(`Unknown sound event: ${String(soundEvent)}`);
+		}
+		captureSounds.preview(soundEvent);
+	});
+
+	// Power throttle handlers
+	ipcMain.handle("powerThrottle:getSettings", () => {
+		if (!powerThrottle) {
+			throw new Error("Power throttle not initialized");
+		}
+		return powerThrottle.getSettings();
+	});
+	ipcMain.handle(
+		"powerThrottle:updateSettings",
+		(_event, settings: unknown) => {
+			if (!powerThrottle) {
+				throw new Error("Power throttle not initialized");
+			}
+			return powerThrottle.updateSettings(settings);
+		},
+	);
+	ipcMain.handle("powerThrottle:getStatus", () => {
+		if (!powerThrottle) {
+			throw new Error("Power throttle not initialized");
+		}
+		return powerThrottle.getStatus();
+	});
+
+	// Capture limits handlers
+	ipcMain.handle("captureLimits:getSettings", () => {
+		if (!captureLimits) {
+			throw new Error("Capture limits not initialized");
+		}
+		return captureLimits.getSettings();
+	});
+	ipcMain.handle(
+		"captureLimits:updateSettings",
+		(_event, settings: unknown) => {
+			if (!captureLimits) {
+				throw new Error("Capture limits not initialized");
+			}
+			return captureLimits.updateSettings(settings);
+		},
+	);
+
+	// PRIMARY selection handlers
+	ipcMain.handle("primarySelection:getSettings", () => {
+		if (!primarySelection) {
+			throw new Error("PRIMARY selection not initialized");
+		}
+		return primarySelection.getSettings();
+	});
+	ipcMain.handle(
+		"primarySelection:updateSettings",
+		(_event, settings: unknown) => {
+			if (!primarySelection) {
+				throw new Error("PRIMARY selection not initialized");
+			}
+			const next = primarySelection.updateSettings(settings);
+			notifyHistoryChanged();
+			return next;
+		},
+	);
+
+	// Universal Clipboard handlers
+	ipcMain.handle("universalClipboard:getSettings", () => {
+		if (!universalClipboard) {
+			throw new Error("Universal Clipboard not initialized");
+		}
+		return universalClipboard.getSettings();
+	});
+	ipcMain.handle(
+		"universalClipboard:updateSettings",
+		(_event, settings: unknown) => {
+			if (!universalClipboard) {
+				throw new Error("Universal Clipboard not initialized");
+			}
+			return universalClipboard.updateSettings(settings);
+		},
+	);
+
+	// Clipboard restore handlers
+	ipcMain.handle("clipboardRestore:getSettings", () => {
+		if (!clipboardRestore) {
+			throw new Error("Clipboard restore not initialized");
+		}
+		return clipboardRestore.getSettings();
+	});
+	ipcMain.handle(
+		"clipboardRestore:updateSettings",
+		(_event, settings: unknown) => {
+			if (!clipboardRestore) {
+				throw new Error("Clipboard restore not initialized");
+			}
+			return clipboardRestore.updateSettings(settings);
+		},
+	);
+
+	// Append-copy handlers
+	ipcMain.handle("appendCopy:getStatus", () => {
+		if (!appendCopy) {
+			throw new Error("Append-copy not initialized");
+		}
+		return appendCopy.getStatus();
+	});
+	ipcMain.handle("appendCopy:setActive", (_event, active: unknown) => {
+		if (!appendCopy) {
+			throw new Error("Append-copy not initialized");
+		}
+		return appendCopy.setActive(active);
+	});
+	ipcMain.handle("appendCopy:updateSettings", (_event, settings: unknown) => {
+		if (!appendCopy) {
+			throw new Error("Append-copy not initialized");
+		}
+		return appendCopy.updateSettings(settings);
+	});
+
+	// Paste queue handlers
+	ipcMain.handle("queue:get", pasteQueue.getItems);
+	ipcMain.handle("queue:toggle", (_event, itemId: unknown) =>
+		pasteQueue.toggle(itemId),
+	);
+	ipcMain.handle("queue:clear", pasteQueue.clear);
+	ipcMain.handle("queue:pasteNext", pasteNextQueued);
+
+	// Clipboard stack handlers
+	ipcMain.handle("stack:get", requireUnlocked(clipboardStack.getEntries));
+	ipcMain.handle("stack:clear", clipboardStack.clear);
+
+	// Auto-clear handlers
+	ipcMain.handle("autoClear:getStatus", () => {
+		if (!clipboardAutoClear) {
+			throw new Error("Clipboard auto-clear not initialized");
+		}
+		return clipboardAutoClear.getStatus();
+	});
+	ipcMain.handle("autoClear:updateSettings", (_event, settings: unknown) => {
+		if (!clipboardAutoClear) {
+			throw new Error("Clipboard auto-clear not initialized");
+		}
+		return clipboardAutoClear.updateSettings(settings);
+	});
+	ipcMain.handle("autoClear:cancel", () => {
+		if (!clipboardAutoClear) {
+			throw new Error("Clipboard auto-clear not initialized");
+		}
+		return clipboardAutoClear.cancel();
+	});
+
+	// App exclusion handlers
+	ipcMain.handle("exclusions:get", () => {
+		if (!appExclusions) {
+			throw new Error("App exclusions not initialized");
+		}
+		return appExclusions.getSettings();
+	});
+	ipcMain.handle("exclusions:update", (_event, settings: unknown) => {
+		if (!appExclusions) {
+			throw new Error("App exclusions not initialized");
+		}
+		const updated = appExclusions.updateSettings(settings);
+		settingsFile?.save();
+		return updated;
+	});
+
+	// Secret scanning handlers
+	ipcMain.handle("secrets:getSettings", () => {
+		if (!secretScanner) {
+			throw new Error("Secret scanner not initialized");
+		}
+		return secretScanner.getSettings();
+	});
+	ipcMain.handle("secrets:updateSettings", (_event, settings: unknown) => {
+		if (!secretScanner) {
+			throw new Error("Secret scanner not initialized");
+		}
+		return secretScanner.updateSettings(settings);
+	});
+
+	// Notification rule handlers
+	ipcMain.handle("notifications:get", () => {
+		if (!notificationRules) {
+			throw new Error("Notification rules not initialized");
+		}
+		return notificationRules.getSettings();
+	});
+	ipcMain.handle("notifications:update", (_event, settings: unknown) => {
+		if (!notificationRules) {
+			throw new Error("Notification rules not initialized");
+		}
+		return notificationRules.updateSettings(settings);
+	});
+
+	// Capture rule handlers
+	ipcMain.handle("captureRules:getSettings", () => {
+		if (!captureRules) {
+			throw new Error("Capture rules not initialized");
+		}
+		return captureRules.getSettings();
+	});
+	ipcMain.handle("captureRules:updateSettings", (_event, settings: unknown) => {
+		if (!captureRules) {
+			throw new Error("Capture rules not initialized");
+		}
+		return captureRules.updateSettings(settings);
+	});
+
+	// User script handlers
+	ipcMain.handle("scripts:list", () => {
+		if (!userScripts) {
+			throw new Error("User scripts not initialized");
+		}
+		return userScripts.list();
+	});
+	ipcMain.handle("scripts:install", (_event, script: unknown) => {
+		if (!userScripts) {
+			throw new Error("User scripts not initialized");
+		}
+		return userScripts.install(script);
+	});
+	ipcMain.handle("scripts:remove", (_event, name: string) => {
+		if (!userScripts) {
+			throw new Error("User scripts not initialized");
+		}
+		return userScripts.remove(name);
+	});
+	ipcMain.handle("scripts:test", (_event, request: unknown) => {
+		if (!userScripts) {
+			throw new Error("User scripts not initialized");
+		}
+		return userScripts.test(request);
+	});
+
+	// Command action handlers
+	ipcMain.handle("commandActions:getSettings", () => {
+		if (!commandActions) {
+			throw new Error("Command actions not initialized");
+		}
+		return commandActions.getSettings();
+	});
+	ipcMain.handle(
+		"commandActions:updateSettings",
+		(_event, settings: unknown) => {
+			if (!commandActions) {
+				throw new Error("Command actions not initialized");
+			}
+			return commandActions.updateSettings(settings);
+		},
+	);
+
+	// Link preview handlers
+	ipcMain.handle("linkPreviews:getSettings", () => {
+		if (!linkPreviews) {
+			throw new Error("Link previews not initialized");
+		}
+		return linkPreviews.getSettings();
+	});
+	ipcMain.handle("linkPreviews:updateSettings", (_event, settings: unknown) => {
+		if (!linkPreviews) {
+			throw new Error("Link previews not initialized");
+		}
+		return linkPreviews.updateSettings(settings);
+	});
+	ipcMain.handle("semanticSearch:getSettings", () => {
+		if (!semanticSearch) {
+			throw new Error("Semantic search not initialized");
+		}
+		return semanticSearch.getSettings();
+	});
+	ipcMain.handle(
+		"semanticSearch:updateSettings",
+		(_event, settings: unknown) => {
+			if (!semanticSearch) {
+				throw new Error("Semantic search not initialized");
+			}
+			return semanticSearch.updateSettings(settings);
+		},
+	);
+	ipcMain.handle("itemSummaries:getSettings", () => {
+		if (!itemSummaries) {
+			throw new Error("Item summaries not initialized");
+		}
+		return itemSummaries.getSettings();
+	});
+	ipcMain.handle(
+		"itemSummaries:updateSettings",
+		(_event, settings: unknown) => {
+			if (!itemSummaries) {
+				throw new Error("Item summaries not initialized");
+			}
+			return itemSummaries.updateSettings(settings);
+		},
+	);
+	ipcMain.handle("favicons:get", (_event, domain: unknown) => {
+		if (!faviconCache) {
+			throw new Error("Favicon cache not initialized");
+		}
+		return faviconCache.get(domain);
+	});
+
+	// Shortcut handlers
+	ipcMain.handle("shortcuts:get", () => {
+		if (!shortcutManager) {
+			throw new Error("Shortcut manager not initialized");
+		}
+		return shortcutManager.getSettings();
+	});
+	ipcMain.handle(
+		"shortcuts:set",
+		(_event, action: unknown, accelerator: unknown) => {
+			if (!shortcutManager) {
+				throw new Error("Shortcut manager not initialized");
+			}
+			const updated = shortcutManager.setShortcut(action, accelerator);
+			settingsFile?.save();
+			return updated;
+		},
+	);
+	ipcMain.handle("shortcuts:getStatus", () => {
+		if (!shortcutManager) {
+			throw new Error("Shortcut manager not initialized");
+		}
+		return shortcutManager.getStatus();
+	});
+	ipcMain.handle("shortcuts:getFallbacks", () => {
+		if (!shortcutManager) {
+			throw new Error("Shortcut manager not initialized");
+		}
+		return shortcutManager.getFallbacks();
+	});
+	ipcMain.handle(
+		"shortcuts:setFallbacks",
+		(_event, action: unknown, accelerators: unknown) => {
+			if (!shortcutManager) {
+				throw new Error("Shortcut manager not initialized");
+			}
+			return shortcutManager.setFallbacks(action, accelerators);
+		},
+	);
+
+	// Tray click handlers
+	ipcMain.handle("trayClicks:get", () => {
+		if (!trayClicks) {
+			throw new Error("Tray clicks not initialized");
+		}
+		return trayClicks.getSettings();
+	});
+	ipcMain.handle("trayClicks:update", (_event, settings: unknown) => {
+		if (!trayClicks) {
+			throw new Error("Tray clicks not initialized");
+		}
+		const updated = trayClicks.updateSettings(settings);
+		// Sets or clears the macOS context menu for the new click action
+		trayModule.update();
+		return updated;
+	});
+
+	// Language handlers
+	ipcMain.handle("language:get", () => {
+		if (!i18n) {
+			throw new Error("Language not initialized");
+		}
+		return i18n.getStatus();
+	});
+
+	ipcMain.handle("language:set", (_event, language: unknown) => {
+		if (!i18n) {
+			throw new Error("Language not initialized");
+		}
+		return i18n.setLanguage(language);
+	});
+
+	// Shortcut chord handlers
+	ipcMain.handle("chords:get", () => {
+		if (!chordManager) {
+			throw new Error("Chord manager not initialized");
+		}
+		return chordManager.getStatus();
+	});
+	ipcMain.handle("chords:update", (_event, settings: unknown) => {
+		if (!chordManager) {
+			throw new Error("Chord manager not initialized");
+		}
+		return chordManager.updateSettings(settings);
+	});
+
+	// Quick-paste handlers
+	ipcMain.handle("quickPaste:get", () => {
+		if (!quickPaste) {
+			throw new Error("Quick paste not initialized");
+		}
+		return quickPaste.getSettings();
+	});
+	ipcMain.handle("quickPaste:update", (_event, settings: unknown) => {
+		if (!quickPaste) {
+			throw new Error("Quick paste not initialized");
+		}
+		return quickPaste.updateSettings(settings);
+	});
+
+	// Window behavior handlers
+	ipcMain.handle("windowBehavior:get", () => {
+		if (!windowBehavior) {
+			throw new Error("Window behavior not initialized");
+		}
+		return windowBehavior.getSettings();
+	});
+	ipcMain.handle("windowBehavior:update", (_event, settings: unknown) => {
+		if (!windowBehavior) {
+			throw new Error("Window behavior not initialized");
+		}
+		const updated = windowBehavior.updateSettings(settings);
+		settingsFile?.save();
+		return updated;
+	});
+
+	// HTTP API handlers
+	ipcMain.handle("httpApi:get", () => getHttpApiStatus());
+	ipcMain.handle("httpApi:update", async (_event, settings: unknown) => {
+		if (!httpApiSettings) {
+			throw new Error("HTTP API not initialized");
+		}
+		httpApiSettings.updateSettings(settings);
+		await applyHttpApiSettings();
+		return getHttpApiStatus();
+	});
+	ipcMain.handle("httpApi:regenerateToken", () => {
+		if (!httpApiSettings) {
+			throw new Error("HTTP API not initialized");
+		}
+		httpApiSettings.regenerateToken();
+		return getHttpApiStatus();
+	});
+
+	// Automation URL handlers
+	ipcMain.handle("urlActions:getSettings", () => {
+		if (!urlActionSettings) {
+			throw new Error("Automation URL settings not initialized");
+		}
+		return urlActionSettings.getSettings();
+	});
+	ipcMain.handle(
+		"urlActions:updateSettings",
+		// Letting links read history is not something to grant while locked
+		requireUnlocked(
+			(_event: Electron.IpcMainInvokeEvent, settings: unknown) => {
+				if (!urlActionSettings) {
+					throw new Error("Automation URL settings not initialized");
+				}
+				return urlActionSettings.updateSettings(settings);
+			},
+		),
+	);
+
+	// Webhook handlers
+	ipcMain.handle("webhooks:get", () => {
+		if (!webhooks) {
+			throw new Error("Webhooks not initialized");
+		}
+		return webhooks.getStatus();
+	});
+	ipcMain.handle("webhooks:update", (_event, settings: unknown) => {
+		if (!webhooks) {
+			throw new Error("Webhooks not initialized");
+		}
+		return webhooks.updateSettings(settings);
+	});
+	ipcMain.handle("webhooks:regenerateSecret", () => {
+		if (!webhooks) {
+			throw new Error("Webhooks not initialized");
+		}
+		return webhooks.regenerateSecret();
+	});
+	ipcMain.handle("webhooks:test", () => {
+		if (!webhooks) {
+			throw new Error("Webhooks not initialized");
+		}
+		return webhooks.test();
+	});
+
+	// LAN sync handlers
+	ipcMain.handle("sync:get", () => getSyncStatus());
+	ipcMain.handle("sync:update", async (_event, settings: unknown) => {
+		if (!syncSettings || !sync) {
+			throw new Error("Sync not initialized");
+		}
+		syncSettings.updateSettings(settings);
+		await sync.apply();
+		return sync.getStatus();
+	});
+	ipcMain.handle(
+		"sync:startPairing",
+		requireUnlocked(() => {
+			if (!sync) {
+				throw new Error("Sync not initialized");
+			}
+			sync.startPairing();
+			return sync.getStatus();
+		}),
+	);
+	ipcMain.handle("sync:cancelPairing", () => {
+		if (!sync) {
+			throw new Error("Sync not initialized");
+		}
+		sync.cancelPairing();
+		return sync.getStatus();
+	});
+	ipcMain.handle(
+		"sync:pair",
+		requireUnlocked(
+			async (
+				_event: Electron.IpcMainInvokeEvent,
+				deviceId: string,
+				code: string,
+			) => {
+				if (!sync) {
+					throw new Error("Sync not initialized");
+				}
+				await sync.pairWith(deviceId, code);
+				return sync.getStatus();
+			},
+		),
+	);
+	ipcMain.handle("sync:unpair", (_event, deviceId: string) => {
+		if (!sync) {
+			throw new Error("Sync not initialized");
+		}
+		sync.unpair(deviceId);
+		return sync.getStatus();
+	});
+
+	// Remote sync handlers
+	ipcMain.handle("remoteSync:get", () => getRemoteSyncStatus());
+	ipcMain.handle("remoteSync:update", (_event, settings: unknown) => {
+		if (!remoteSyncSettings || !remoteSync) {
+			throw new Error("Remote sync not initialized");
+		}
+		remoteSyncSettings.updateSettings(settings);
+		remoteSync.apply();
+		return remoteSync.getStatus();
+	});
+	ipcMain.handle(
+		"remoteSync:syncNow",
+		requireUnlocked(async () => {
+			if (!remoteSync) {
+				throw new Error("Remote sync not initialized");
+			}
+			await remoteSync.syncNow();
+			return remoteSync.getStatus();
+		}),
+	);
+
+	// Shared board handlers
+	ipcMain.handle("boards:get", () => getBoardsStatus());
+	ipcMain.handle("boards:updateServer", async (_event, settings: unknown) => {
+		if (!boardSettings || !boards) {
+			throw new Error("Shared boards not initialized");
+		}
+		boardSettings.updateServer(settings);
+		await boards.apply();
+		return boards.getStatus();
+	});
+	ipcMain.handle(
+		"boards:createBoard",
+		requireUnlocked(
+			(
+				_event: Electron.IpcMainInvokeEvent,
+				name: unknown,
+				passphrase: unknown,
+			) => {
+				if (!boards) {
+					throw new Error("Shared boards not initialized");
+				}
+				const invite = boards.createBoard(name, passphrase);
+				return { invite, status: boards.getStatus() };
+			},
+		),
+	);
+	ipcMain.handle(
+		"boards:regenerateToken",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, boardId: unknown) => {
+			if (!boards) {
+				throw new Error("Shared boards not initialized");
+			}
+			const invite = boards.regenerateBoardToken(boardId);
+			return { invite, status: boards.getStatus() };
+		}),
+	);
+	ipcMain.handle("boards:deleteBoard", (_event, boardId: unknown) => {
+		if (!boards) {
+			throw new Error("Shared boards not initialized");
+		}
+		boards.deleteBoard(boardId);
+		return boards.getStatus();
+	});
+	ipcMain.handle(
+		"boards:join",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, input: unknown) => {
+			if (!boards) {
+				throw new Error("Shared boards not initialized");
+			}
+			boards.join(input);
+			return boards.getStatus();
+		}),
+	);
+	ipcMain.handle("boards:leave", (_event, boardId: unknown) => {
+		if (!boards) {
+			throw new Error("Shared boards not initialized");
+		}
+		boards.leave(boardId);
+		return boards.getStatus();
+	});
+	ipcMain.handle(
+		"boards:publish",
+		requireUnlocked(
+			async (
+				_event: Electron.IpcMainInvokeEvent,
+				boardId: unknown,
+				id: number,
+			) => {
+				if (!boards) {
+					throw new Error("Shared boards not initialized");
+				}
+				const item = getTextItem(historyRepository, id, "published");
+				const seq = await boards.publish(
+					boardId,
+					historyRepository.getFullText(id) ?? item.content,
+					item.note,
+				);
+				recordAccess(id, "export", "ui", `to board ${String(boardId)}`);
+				return seq;
+			},
+		),
+	);
+	ipcMain.handle(
+		"boards:listItems",
+		requireUnlocked((_event: Electron.IpcMainInvokeEvent, boardId: unknown) => {
+			if (!boards) {
+				throw new Error("Shared boards not initialized");
+			}
+			return boards.listItems(boardId);
+		}),
+	);
+	ipcMain.handle(
+		"boards:copyItem",
+		requireUnlocked(
+			(_event: Electron.IpcMainInvokeEvent, boardId: unknown, seq: unknown) => {
+				if (!boards) {
+					throw new Error("Shared boards not initialized");
+				}
+				clipboard.writeText(boards.getItem(boardId, seq).content);
+			},
+		),
+	);
 
-// Register all IPC handlers
-const registerIpcHandlers = (): void => {
-	// Clipboard handlers
-	ipcMain.handle("clipboard:read", clipboardHandlers.read);
-	ipcMain.handle("clipboard:write", clipboardHandlers.write);
+	// Type-out handlers
+	ipcMain.handle("typeOut:getSettings", () => {
+		if (!typeOut) {
+			throw new Error("Type-out not initialized");
+		}
+		return typeOut.getSettings();
+	});
+	ipcMain.handle("typeOut:updateSettings", (_event, settings: unknown) => {
+		if (!typeOut) {
+			throw new Error("Type-out not initialized");
+		}
+		return typeOut.updateSettings(settings);
+	});
 
-	// Database handlers
-	ipcMain.handle("db:getHistory", dbHandlers.getHistory);
-	ipcMain.handle("db:addClip", dbHandlers.addClip);
-	ipcMain.handle("db:deleteHistoryItem", dbHandlers.deleteHistoryItem);
-	ipcMain.handle("db:clearAllHistory", dbHandlers.clearAllHistory);
-	ipcMain.handle("db:toggleFavorite", dbHandlers.toggleFavorite);
+	// Text expander handlers
+	ipcMain.handle("textExpander:get", () => {
+		if (!textExpander) {
+			throw new Error("Text expander not initialized");
+		}
+		return textExpander.getStatus();
+	});
+	ipcMain.handle("textExpander:update", (_event, settings: unknown) => {
+		if (!textExpander) {
+			throw new Error("Text expander not initialized");
+		}
+		return textExpander.updateSettings(settings);
+	});
 
-	// Window handlers
-	ipcMain.handle("window:center", windowHandlers.center);
-	ipcMain.handle("window:show", windowHandlers.show);
-	ipcMain.handle("window:hide", windowHandlers.hide);
-	ipcMain.handle("window:isVisible", windowHandlers.isVisible);
-	ipcMain.handle("window:hideAndPaste", windowHandlers.hideAndPaste);
+	// Double-copy gesture handlers
+	ipcMain.handle("doubleCopy:get", () => {
+		if (!doubleCopy) {
+			throw new Error("Double-copy gesture not initialized");
+		}
+		return doubleCopy.getStatus();
+	});
+	ipcMain.handle("doubleCopy:update", (_event, settings: unknown) => {
+		if (!doubleCopy) {
+			throw new Error("Double-copy gesture not initialized");
+		}
+		return doubleCopy.updateSettings(settings);
+	});
 
-	// App handlers
-	ipcMain.handle("app:quit", () => app.quit());
-	ipcMain.handle("app:getLaunchAtLogin", () => {
-		if (!launchAtLoginModule) {
-			throw new Error("Launch at login module not initialized");
+	// Backup handlers
+	ipcMain.handle("backup:get", () => {
+		if (!backupModule) {
+			throw new Error("Backup module not initialized");
 		}
-		return launchAtLoginModule.reconcileOnSettingsOpen();
+		return backupModule.getStatus();
 	});
-	ipcMain.handle("app:setLaunchAtLogin", (_event, enabled: unknown) => {
-		if (!launchAtLoginModule) {
-			throw new Error("Launch at login module not initialized");
+	ipcMain.handle("backup:update", (_event, settings: unknown) => {
+		if (!backupModule) {
+			throw new Error("Backup module not initialized");
 		}
-		if (typeof enabled !== "boolean") {
-			throw new Error(`Invalid launch at login value: ${String(enabled)}`);
+		return backupModule.updateSettings(settings);
+	});
+	ipcMain.handle(
+		"backup:backupNow",
+		requireUnlocked(async () => {
+			if (!backupModule) {
+				throw new Error("Backup module not initialized");
+			}
+			await backupModule.backupNow();
+			return backupModule.getStatus();
+		}),
+	);
+	ipcMain.handle(
+		"backup:restore",
+		requireUnlocked(
+			requireWritable(
+				async (
+					_event: Electron.IpcMainInvokeEvent,
+					backupPath: unknown,
+					passphrase?: unknown,
+				) => {
+					if (!backupModule) {
+						throw new Error("Backup module not initialized");
+					}
+					await backupModule.restoreBackup(backupPath, passphrase);
+					notifyHistoryChanged();
+					return backupModule.getStatus();
+				},
+			),
+		),
+	);
+
+	// Retention handlers
+	ipcMain.handle("retention:getPolicy", () => {
+		if (!retentionModule) {
+			throw new Error("Retention module not initialized");
 		}
-		return launchAtLoginModule.setLaunchAtLogin(enabled);
+		return retentionModule.getPolicy();
+	});
+	ipcMain.handle("retention:updatePolicy", (_event, policy: unknown) => {
+		if (!retentionModule) {
+			throw new Error("Retention module not initialized");
+		}
+		const updated = retentionModule.updatePolicy(policy);
+		settingsFile?.save();
+		return updated;
+	});
+
+	// Settings file handlers
+	ipcMain.handle("settings:get", () => {
+		if (!settingsFile) {
+			throw new Error("Settings not initialized");
+		}
+		return settingsFile.getStatus();
+	});
+	ipcMain.handle("settings:update", (_event, settings: unknown) => {
+		if (!settingsFile) {
+			throw new Error("Settings not initialized");
+		}
+		return settingsFile.update(settings);
+	});
+
+	// Data location handlers
+	ipcMain.handle("dataLocation:get", () => dataLocation.getStatus());
+	ipcMain.handle("dataLocation:migrate", (_event, target: unknown) => {
+		const status = dataLocation.requestMove(target);
+		// The data moves at the next start, before anything opens it
+		if (status.pendingMove !== null) {
+			app.relaunch();
+			app.quit();
+		}
+		return status;
+	});
+
+	// Updater handlers
+	ipcMain.handle("updater:get", () => {
+		if (!updater) {
+			throw new Error("Updater not initialized");
+		}
+		return updater.getStatus();
+	});
+	ipcMain.handle("updater:updateSettings", (_event, settings: unknown) => {
+		if (!updater) {
+			throw new Error("Updater not initialized");
+		}
+		return updater.updateSettings(settings);
+	});
+	ipcMain.handle("updater:check", () => {
+		if (!updater) {
+			throw new Error("Updater not initialized");
+		}
+		return updater.check();
+	});
+	ipcMain.handle("updater:download", () => {
+		if (!updater) {
+			throw new Error("Updater not initialized");
+		}
+		return updater.download();
+	});
+	ipcMain.handle("updater:install", () => {
+		if (!updater) {
+			throw new Error("Updater not initialized");
+		}
+		return updater.install();
+	});
+
+	// Log handlers
+	ipcMain.handle("logs:getRecent", (_event, query: unknown) =>
+		logger.getRecentLogs(query),
+	);
+	ipcMain.handle("logs:getSettings", () => logger.getSettings());
+	ipcMain.handle("logs:updateSettings", (_event, settings: unknown) =>
+		logger.updateSettings(settings),
+	);
+	ipcMain.handle("logs:openFolder", async () => {
+		const logDirectory = logger.getLogDirectory();
+		fs.mkdirSync(logDirectory, { recursive: true });
+		const error = await shell.openPath(logDirectory);
+		if (error) throw new Error(error);
+	});
+
+	// Diagnostics handlers
+	ipcMain.handle("diagnostics:listCrashes", () =>
+		diagnostics.listCrashReports(),
+	);
+	ipcMain.handle("diagnostics:export", (_event, filePath: unknown) =>
+		diagnostics.exportBundle(filePath),
+	);
+
+	// Profile handlers
+	ipcMain.handle("profiles:get", () => profiles.getStatus());
+	ipcMain.handle("profiles:create", (_event, name: unknown) => {
+		const status = profiles.create(name);
+		trayModule.update();
+		return status;
+	});
+	ipcMain.handle("profiles:switch", (_event, name: unknown) => {
+		switchProfile(name);
+	});
+	ipcMain.handle("profiles:remove", (_event, name: unknown) => {
+		const status = profiles.remove(name);
+		trayModule.update();
+		return status;
 	});
 };
 
 // Application ready
 app.whenReady().then(async () => {
+	// Another instance is running and received our arguments
+	if (!hasInstanceLock) {
+		app.quit();
+		return;
+	}
+
 	try {
 		// Initialize database
 		const userDataPath = app.getPath("userData");
+		i18n = createI18n({
+			userDataPath,
+			getSystemLocales: () => app.getPreferredSystemLanguages(),
+			onChange: () => trayModule.update(),
+		});
 		const dbPath = path.join(userDataPath, "clipboard.db");
 		dbModule.init(dbPath);
+		if (
+			dbModule.getLastRecovery()?.action === "salvaged" &&
+			Notification.isSupported()
+		) {
+			new Notification({
+				title: t("notification.repaired.title"),
+				body: t("notification.repaired.body"),
+			}).show();
+		}
+		historyRepository.backfillContentHashes();
+		historyRepository.moveImagesToAttachments();
+		historyRepository.compressLargeItems();
+		historyRepository.classifyItems();
+		historyRepository.backfillTextStats();
+		historyRepository.backfillTextSimhashes();
+		historyRepository.collectAttachmentGarbage();
+		historyRepository.pruneSyncTombstones();
+		ocrQueue.schedule();
+		qrQueue.schedule();
+		phashQueue.schedule();
 
 		initAccessibilitySession();
 
-		const preferencesStore = createPreferencesStore(userDataPath);
+		preferencesStore = createPreferencesStore(userDataPath);
+		// Electron's login items cover macOS (a login item) and Windows (the
+		// Run registry key); Linux desktops read XDG autostart entries
+		const loginItems =
+			process.platform === "linux"
+				? createXdgAutostart({
+						filePath: getAutostartPath(process.env, app.getPath("home")),
+						name: app.getName(),
+						// An AppImage runs from a temporary mount; APPIMAGE is the file
+						command: [process.env.APPIMAGE ?? process.execPath],
+					})
+				: {
+						getLoginItemSettings: app.getLoginItemSettings.bind(app),
+						setLoginItemSettings: app.setLoginItemSettings.bind(app),
+					};
 		launchAtLoginModule = createLaunchAtLoginModule({
 			isPackaged: app.isPackaged,
-			getLoginItemSettings: app.getLoginItemSettings.bind(app),
-			setLoginItemSettings: app.setLoginItemSettings.bind(app),
+			...loginItems,
 			preferencesStore,
 			userDataPath,
 		});
@@ -631,31 +5126,505 @@ app.whenReady().then(async () => {
 			);
 		}
 
+		const authenticate = createPlatformAuthenticator({
+			platform: process.platform,
+			touchId: systemPreferences,
+		});
+
+		readOnlyMode = createReadOnlyMode({
+			userDataPath,
+			forced: startupOptions.readOnly,
+			authenticate,
+			onChange: (status) => {
+				trayModule.update();
+				windowModule
+					.getWindow()
+					?.webContents.send(READ_ONLY_CHANGED_CHANNEL, status);
+				pickerModule
+					.getWindow()
+					?.webContents.send(READ_ONLY_CHANGED_CHANNEL, status);
+			},
+		});
+
+		appLockModule = createAppLockModule({
+			userDataPath,
+			authenticate,
+			onLockChanged: (locked, reason) => {
+				trayModule.update();
+				if (!locked) clipboardRestore?.restore();
+				// Left open, an idle window would show history to whoever
+				// walks up; it comes back showing the locked state
+				if (reason === "idle") {
+					windowModule.hide();
+					pickerModule.hide();
+				}
+				windowModule
+					.getWindow()
+					?.webContents.send(LOCK_CHANGED_CHANNEL, locked, reason);
+				pickerModule
+					.getWindow()
+					?.webContents.send(LOCK_CHANGED_CHANNEL, locked, reason);
+			},
+		});
+
+		clipboardAutoClear = createClipboardAutoClear({
+			userDataPath,
+			readText: () => clipboard.readText(),
+			clear: () => clipboard.clear(),
+		});
+
+		appExclusions = createAppExclusions({ userDataPath });
+
+		captureRules = createCaptureRules({ userDataPath });
+
+		userScripts = createUserScripts({ userDataPath });
+
+		commandActions = createCommandActions({
+			userDataPath,
+			recordOutput: (text) => recordSnapshot({ text }, null),
+		});
+
+		captureSounds = createCaptureSounds({ userDataPath });
+
+		notificationRules = createNotificationRules({
+			userDataPath,
+			show: (content) => {
+				if (Notification.isSupported()) new Notification(content).show();
+			},
+			t,
+		});
+
+		secretScanner = createSecretScanner({
+			userDataPath,
+			expire: () => {
+				const expired = historyRepository.expireItems();
+				clearFromClipboard(expired);
+				return expired.length;
+			},
+			onExpired: notifyHistoryChanged,
+		});
+
+		// Pages of copied links are fetched in the background, if enabled,
+		// so history can show their titles
+		linkPreviews = createLinkPreviews({
+			userDataPath,
+			nextCandidate: historyRepository.nextLinkPreviewCandidate,
+			save: (id, preview) => {
+				historyRepository.setLinkPreview(id, preview);
+				notifyHistoryChanged();
+			},
+			shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+		});
+		linkPreviews.schedule();
+		// Text items are embedded in the background, if enabled, for
+		// searching by meaning
+		semanticSearch = createSemanticSearch({
+			userDataPath,
+			nextCandidates: historyRepository.nextEmbeddingCandidates,
+			save: historyRepository.saveEmbedding,
+			listEmbeddings: historyRepository.listEmbeddings,
+			shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+		});
+		semanticSearch.schedule();
+		// Long text items get a generated title, if enabled, which the list
+		// can show in place of their first lines
+		itemSummaries = createItemSummaries({
+			userDataPath,
+			nextCandidate: historyRepository.nextSummaryCandidate,
+			save: (id, summary) => {
+				historyRepository.setItemSummary(id, summary);
+				notifyHistoryChanged();
+			},
+			shouldDefer: () => powerThrottle?.shouldDeferJobs() ?? false,
+		});
+		itemSummaries.schedule();
+		// Site icons follow the same switch: only cached ones are shown
+		// while link previews are off
+		faviconCache = createFaviconCache({
+			userDataPath,
+			isEnabled: () => linkPreviews?.getSettings().enabled ?? false,
+		});
+
+		captureLimits = createCaptureLimits({ userDataPath });
+		universalClipboard = createUniversalClipboard({ userDataPath });
+		clipboardRestore = createClipboardRestore({
+			userDataPath,
+			findClipboardItem: () => findClipboardItem(historyRepository),
+			isClipboardEmpty: () => clipboard.availableFormats().length === 0,
+			restoreItem: (id) => {
+				if (!historyRepository.getItem(id)) return false;
+				restoreHistoryItem(historyRepository, id, { countUse: false });
+				return true;
+			},
+		});
+
+		powerThrottle = createPowerThrottle({
+			userDataPath,
+			isOnBattery: () => powerMonitor.isOnBatteryPower(),
+			getIdleSeconds: () => powerMonitor.getSystemIdleTime(),
+			onChange: applyPowerThrottle,
+		});
+
+		primarySelection = createPrimarySelection({
+			userDataPath,
+			platform: process.platform,
+			readSelection: () => clipboard.readText("selection"),
+			onSelection: recordSelection,
+		});
+
+		appendCopy = createAppendCopy({
+			userDataPath,
+			readText: () => clipboard.readText(),
+			onChange: () => trayModule.update(),
+		});
+
+		trayClicks = createTrayClicks({
+			userDataPath,
+			run: (action) => {
+				switch (action) {
+					case "menu":
+						trayModule.popUpMenu();
+						break;
+					case "toggleWindow":
+						void windowModule.toggle();
+						break;
+					case "toggleQuickPicker":
+						void pickerModule.toggle();
+						break;
+					case "toggleCapture":
+						capturePause.toggle();
+						break;
+					case "pasteLastItem":
+						restoreRecentItem(0, true).catch((error) => {
+							console.error("Failed to paste last item:", error);
+						});
+						break;
+				}
+			},
+		});
+
+		// Shared by the global shortcuts and the chords that reuse their actions
+		const shortcutHandlers = {
+			togglePicker: () => windowModule.toggle(),
+			toggleQuickPicker: () => pickerModule.toggle(),
+			pasteLastItem: () => {
+				restoreRecentItem(0, true).catch((error) => {
+					console.error("Failed to paste last item:", error);
+				});
+			},
+			pastePlainText: () => {
+				pastePlainText().catch((error) => {
+					console.error("Failed to paste as plain text:", error);
+				});
+			},
+			pasteNextQueued: () => {
+				pasteNextQueued().catch((error) => {
+					console.error("Failed to paste next queued item:", error);
+				});
+			},
+			pushClipboard: pushClipboardToStack,
+			popClipboard: () => {
+				popClipboardFromStack().catch((error) => {
+					console.error("Failed to pop clipboard stack:", error);
+				});
+			},
+			copyToRegister: () => {
+				registerPrompt.arm((name) => {
+					try {
+						copyClipboardToRegister(name);
+					} catch (error) {
+						console.error("Failed to copy to register:", error);
+					}
+				});
+			},
+			pasteRegister: () => {
+				if (!appLockModule || appLockModule.getStatus().locked) return;
+				registerPrompt.arm((name) => {
+					pasteRegister(name).catch((error) => {
+						console.error("Failed to paste register:", error);
+					});
+				});
+			},
+			toggleAppendCopy: () => {
+				appendCopy?.toggle();
+			},
+			toggleCapture: () => {
+				capturePause.toggle();
+			},
+		};
+
+		shortcutManager = createShortcutManager({
+			userDataPath,
+			registrar: globalShortcut,
+			handlers: shortcutHandlers,
+			onStatusChanged: (status) => {
+				windowModule
+					.getWindow()
+					?.webContents.send(SHORTCUTS_STATUS_CHANNEL, status);
+			},
+		});
+
+		typeOut = createTypeOut({ userDataPath, platform: process.platform });
+
+		textExpander = createTextExpander({
+			userDataPath,
+			hook: createKeyHook({ platform: process.platform }),
+			listAbbreviations: snippetRepository.listAbbreviations,
+			getForegroundApp: async () => (await resolveSourceApp())?.name ?? null,
+			expand: expandAbbreviation,
+		});
+
+		// A listener of its own, so either feature can be turned on alone
+		doubleCopy = createDoubleCopy({
+			userDataPath,
+			hook: createKeyHook({ platform: process.platform }),
+			onDoubleCopy: () => {
+				void pickerModule.show();
+			},
+		});
+
+		windowBehavior = createWindowBehavior({ userDataPath });
+
+		httpApiSettings = createHttpApiSettings({ userDataPath });
+		urlActionSettings = createUrlActionSettings({ userDataPath });
+		httpApiServer = createHttpApiServer({
+			handlers: createHttpApiHandlers(historyRepository),
+			getToken: () => httpApiSettings?.getSettings().token ?? "",
+			isLocked: () => appLockModule?.getStatus().locked ?? true,
+		});
+
+		webhooks = createWebhooks({ userDataPath });
+
+		const appImagePath = process.env.APPIMAGE ?? null;
+		updater = createUpdater({
+			// Shared by every profile
+			userDataPath: dataLocation.getStatus().dataPath,
+			currentVersion: app.getVersion(),
+			platform: process.platform,
+			arch: process.arch,
+			publicKey: app.isPackaged ? readUpdatePublicKey() : null,
+			appImagePath,
+			openPath: (filePath) => shell.openPath(filePath),
+			quit: () => app.quit(),
+			relaunch: () => {
+				app.relaunch(appImagePath ? { execPath: appImagePath } : undefined);
+				app.quit();
+			},
+			onChange: (status) => {
+				trayModule.update();
+				windowModule
+					.getWindow()
+					?.webContents.send(UPDATER_STATUS_CHANNEL, status);
+			},
+		});
+
+		quickPaste = createQuickPaste({
+			userDataPath,
+			registrar: globalShortcut,
+			onSlot: (index, paste) => {
+				restoreRecentItem(index, paste).catch((error) => {
+					console.error("Failed to quick-paste item:", error);
+				});
+			},
+		});
+
+		chordManager = createChordManager({
+			userDataPath,
+			registrar: globalShortcut,
+			run: (action) => {
+				if (isShortcutAction(action)) {
+					shortcutHandlers[action]();
+					return;
+				}
+				const index = getPasteRecentIndex(action);
+				if (index === null) return;
+				restoreRecentItem(index, true).catch((error) => {
+					console.error("Failed to paste recent item:", error);
+				});
+			},
+		});
+
+		retentionModule = createRetentionModule({
+			userDataPath,
+			prune: (policy) =>
+				isReadOnly() ? 0 : historyRepository.pruneItems(policy),
+			onPruned: notifyHistoryChanged,
+		});
+
+		settingsFile = createSettingsFile({
+			userDataPath,
+			getModuleSettings,
+			applyModuleSettings,
+			onChange: (status) => {
+				windowModule
+					.getWindow()
+					?.webContents.send(SETTINGS_CHANGED_CHANNEL, status);
+			},
+		});
+
+		backupModule = createBackupModule({
+			userDataPath,
+			snapshot: historyRepository.serializeWithImages,
+			replaceDatabase: (data) => {
+				dbModule.replace(data);
+				historyRepository.moveImagesToAttachments();
+				historyRepository.classifyItems();
+				historyRepository.backfillTextStats();
+				historyRepository.backfillTextSimhashes();
+				historyRepository.collectAttachmentGarbage();
+				ocrQueue.schedule();
+				qrQueue.schedule();
+				phashQueue.schedule();
+				linkPreviews?.schedule();
+				semanticSearch?.schedule();
+				itemSummaries?.schedule();
+			},
+		});
+
 		// Register IPC handlers before creating window (renderer needs them immediately)
 		registerIpcHandlers();
 
-		// Create window and tray
-		await windowModule.create();
+		// Local socket for the clipctl companion CLI
+		controlServer = createControlServer({
+			socketPath: resolveControlSocketPath(),
+			handlers: createControlHandlers(historyRepository),
+			platform: process.platform,
+		});
+		controlServer.start().catch((error) => {
+			console.error("Failed to start the clipctl server:", error);
+		});
+
+		// Opt-in local HTTP API for launchers and other tools
+		applyHttpApiSettings().catch((error) => {
+			console.error("Failed to start the HTTP API:", error);
+		});
+
+		// Opt-in LAN sync with paired devices
+		syncSettings = createSyncSettings({
+			userDataPath,
+			defaultDeviceName: os.hostname(),
+		});
+		sync = createSync({
+			settings: syncSettings,
+			handlers: createSyncHandlers(historyRepository),
+			hostname: os.hostname(),
+			// Sync pauses in read-only mode too, as items would arrive
+			isLocked: () =>
+				(appLockModule?.getStatus().locked ?? true) || isReadOnly(),
+		});
+		sync.apply().catch((error) => {
+			console.error("Failed to start LAN sync:", error);
+		});
+		remoteSyncSettings = createRemoteSyncSettings({ userDataPath });
+		remoteSync = createRemoteSync({
+			settings: remoteSyncSettings,
+			handlers: createSyncHandlers(historyRepository),
+			isLocked: () =>
+				(appLockModule?.getStatus().locked ?? true) || isReadOnly(),
+			onSynced: (received) =>
+				notificationRules?.notify({ rule: "syncCompleted", received }),
+		});
+		remoteSync.apply();
+
+		// Opt-in shared boards, hosted here or on a teammate's device
+		boardSettings = createBoardSettings({ userDataPath });
+		boards = createBoards({
+			settings: boardSettings,
+			storagePath: path.join(userDataPath, "boards"),
+			deviceName: os.hostname(),
+			getLanAddress: () => pickLanAddress(os.networkInterfaces()),
+			isLocked: () => appLockModule?.getStatus().locked ?? true,
+			onUpdated: (boardId) => {
+				windowModule
+					.getWindow()
+					?.webContents.send(BOARDS_UPDATED_CHANNEL, boardId);
+			},
+		});
+		boards.apply().catch((error) => {
+			console.error("Failed to start shared boards:", error);
+		});
+
+		// org.clipboardmanager on the session bus for GNOME/KDE scripts
+		if (process.platform === "linux") {
+			dbusService = createDBusService({
+				handlers: createDBusHandlers(historyRepository),
+				env: process.env,
+				uid: process.getuid?.() ?? 0,
+			});
+			dbusService.start().catch((error) => {
+				console.error("Failed to start the D-Bus service:", error);
+			});
+		}
+
+		// Create windows (skipped in headless mode) and tray
+		if (!startupOptions.headless) {
+			await windowModule.create();
+			await pickerModule.create();
+		}
 		trayModule.create();
+		// Windows are created hidden; only the tray shows unless the user
+		// turned off starting minimized
+		if (!startupOptions.headless && !preferencesStore.readStartMinimized()) {
+			void windowModule.show();
+		}
+
+		// Last session's copy goes back before the watcher reads the
+		// clipboard, so it is not recorded again; while locked it waits
+		// for the unlock
+		if (!appLockModule.getStatus().locked) clipboardRestore.restore();
+		clipboardWatcher.start();
+		primarySelection?.start();
+		powerThrottle?.start();
+		powerMonitor.on("on-battery", () => powerThrottle?.refresh());
+		powerMonitor.on("on-ac", () => powerThrottle?.refresh());
+		retentionModule.start();
+		secretScanner.start();
+		backupModule.start();
+		maintenanceModule.start();
+		appLockModule.start();
+		if (appLockModule.getStatus().unprotected && Notification.isSupported()) {
+			new Notification({
+				title: t("notification.lockUnprotected.title"),
+				body: t("notification.lockUnprotected.body"),
+			}).show();
+		}
+		void textExpander.start();
+		void doubleCopy.start();
+		// Lock as soon as the user steps away from the machine
+		powerMonitor.on("lock-screen", () => appLockModule?.lock("system"));
+		powerMonitor.on("suspend", () => appLockModule?.lock("system"));
 
 		if (!shouldSkipAccessibilityOnStartup()) {
 			await promptAccessibilityIfNeeded();
 		}
 
-		// Register global shortcut Cmd+Shift+V (or Ctrl+Shift+V on Windows/Linux)
-		const shortcut = "CommandOrControl+Shift+V";
-		const registered = globalShortcut.register(shortcut, () =>
-			windowModule.toggle(),
-		);
-		if (!registered) {
-			console.error(
-				`Global shortcut registration failed for "${shortcut}". ` +
-					"The shortcut may already be in use by another application.",
-			);
+		// Default picker shortcut is Cmd+Shift+V (Ctrl+Shift+V on Windows/Linux);
+		// one taken by another application falls back to its alternatives
+		shortcutManager.registerAll();
+		quickPaste.registerAll();
+		chordManager.registerAll();
+		// Edits made to settings.toml while the app was closed apply now
+		settingsFile.start();
+		updater.start();
+
+		// Automation URLs received while starting up
+		for (const scheme of URL_SCHEMES) {
+			app.setAsDefaultProtocolClient(scheme);
+		}
+		const startupUrls = pendingAutomationUrls ?? [];
+		pendingAutomationUrls = null;
+		for (const url of startupUrls) {
+			void handleAutomationUrl(url);
+		}
+		if (startupOptions.copyItem !== null) {
+			copyJumpListItem(startupOptions.copyItem);
 		}
 
 		app.on("activate", () => {
-			if (BrowserWindow.getAllWindows().length === 0) {
+			if (
+				!startupOptions.headless &&
+				BrowserWindow.getAllWindows().length === 0
+			) {
 				windowModule.create().catch((error) => {
 					console.error("Failed to create window on activate:", error);
 				});
@@ -671,6 +5640,63 @@ app.whenReady().then(async () => {
 	}
 });
 
+// macOS delivers automation URLs as open-url events, possibly before the
+// app is ready; those wait until startup finishes
+let pendingAutomationUrls: string[] | null = findUrlArgs(process.argv);
+const receiveAutomationUrl = (url: string): void => {
+	if (pendingAutomationUrls) {
+		pendingAutomationUrls.push(url);
+	} else {
+		void handleAutomationUrl(url);
+	}
+};
+app.on("open-url", (event, url) => {
+	event.preventDefault();
+	receiveAutomationUrl(url);
+});
+
+/**
+ * Copies the item of a clicked jump list entry (`--copy-item`).
+ */
+const copyJumpListItem = (id: number): void => {
+	try {
+		requireUnlocked(restoreHistoryItem)(historyRepository, id);
+		recordAccess(id, "copy", "ui");
+	} catch (error) {
+		console.error("Failed to copy the jump list item:", error);
+	}
+};
+
+/**
+ * Handles the arguments of a second launch, which quits once it hands
+ * them over, so only one watcher and tray icon ever run. Launching with
+ * another `--profile` switches to it; `--copy-item` (a jump list entry)
+ * copies an item; a launch with neither nor automation URLs brings the
+ * history window forward.
+ */
+const receiveSecondLaunch = (argv: string[]): void => {
+	const urls = findUrlArgs(argv);
+	for (const url of urls) receiveAutomationUrl(url);
+
+	const { profile, copyItem } = parseStartupOptions(argv);
+	try {
+		if (profile !== null && switchProfile(profile)) return;
+	} catch (error) {
+		console.error("Failed to switch profile:", error);
+	}
+	if (copyItem !== null) {
+		copyJumpListItem(copyItem);
+		return;
+	}
+	if (urls.length === 0 && !startupOptions.headless) {
+		void windowModule.show();
+	}
+};
+
+// On Windows and Linux a clicked link also starts a second instance, with
+// the URL in its arguments
+app.on("second-instance", (_event, argv) => receiveSecondLaunch(argv));
+
 app.on("window-all-closed", () => {
 	// On macOS, keep the app running even when all windows are closed
 	// The tray icon keeps the app alive for background clipboard monitoring
@@ -680,6 +5706,30 @@ app.on("window-all-closed", () => {
 });
 
 app.on("will-quit", () => {
+	clipboardWatcher.stop();
+	primarySelection?.stop();
+	powerThrottle?.stop();
+	formatWriter?.stop();
+	captureBatcher.flush();
+	// After the flush, so the last copy is in history to be found
+	clipboardRestore?.save();
+	retentionModule?.stop();
+	settingsFile?.stop();
+	updater?.stop();
+	secretScanner?.stop();
+	backupModule?.stop();
+	maintenanceModule.stop();
+	appLockModule?.stop();
+	clipboardAutoClear?.cancel();
+	capturePause.stop();
+	controlServer?.stop();
+	httpApiServer?.stop();
+	textExpander?.stop();
+	doubleCopy?.stop();
+	dbusService?.stop();
+	sync?.stop();
+	remoteSync?.stop();
+	boards?.stop();
 	globalShortcut.unregisterAll();
 	dbModule.close();
 });
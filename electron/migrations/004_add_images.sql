@@ -0,0 +1,4 @@
+-- Migration 004: Add image clipboard support
+ALTER TABLE history ADD COLUMN image BLOB;
+ALTER TABLE history ADD COLUMN image_width INTEGER;
+ALTER TABLE history ADD COLUMN image_height INTEGER;
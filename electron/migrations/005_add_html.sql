@@ -0,0 +1,2 @@
+-- Migration 005: Add HTML clipboard support
+ALTER TABLE history ADD COLUMN html TEXT;
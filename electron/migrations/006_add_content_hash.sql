@@ -0,0 +1,5 @@
+-- Migration 006: Content-hash deduplication
+-- Hashes are computed in the main process; existing rows are backfilled on startup
+ALTER TABLE history ADD COLUMN content_hash TEXT;
+ALTER TABLE history ADD COLUMN use_count INTEGER NOT NULL DEFAULT 1;
+CREATE INDEX IF NOT EXISTS idx_history_content_hash ON history(content_hash);
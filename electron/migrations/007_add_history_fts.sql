@@ -0,0 +1,24 @@
+-- Migration 007: Full-text search over history content
+-- External-content FTS5 index kept in sync with history by triggers
+CREATE VIRTUAL TABLE IF NOT EXISTS history_fts USING fts5(
+    content,
+    content = 'history',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS history_fts_after_insert AFTER INSERT ON history BEGIN
+    INSERT INTO history_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_fts_after_delete AFTER DELETE ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content) VALUES ('delete', old.id, old.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_fts_after_update AFTER UPDATE OF content ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content) VALUES ('delete', old.id, old.content);
+    INSERT INTO history_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+-- Index rows stored before this migration
+INSERT INTO history_fts(history_fts) VALUES ('rebuild');
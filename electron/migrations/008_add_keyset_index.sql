@@ -0,0 +1,2 @@
+-- Migration 008: Index for keyset (cursor) pagination
+CREATE INDEX IF NOT EXISTS idx_history_created_at_id ON history(created_at DESC, id DESC);
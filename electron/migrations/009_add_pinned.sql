@@ -0,0 +1,5 @@
+-- Migration 009: Pinned items
+-- Pinned items sort above everything else, so the keyset index leads with them
+ALTER TABLE history ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+DROP INDEX IF EXISTS idx_history_created_at_id;
+CREATE INDEX IF NOT EXISTS idx_history_pinned_created_at_id ON history(pinned DESC, created_at DESC, id DESC);
@@ -0,0 +1,12 @@
+-- Migration 010: Tags
+-- Names are stored normalized (trimmed, lowercase); links cascade with items
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS history_tags (
+    history_id INTEGER NOT NULL REFERENCES history(id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (history_id, tag_id)
+);
+CREATE INDEX IF NOT EXISTS idx_history_tags_tag_id ON history_tags(tag_id);
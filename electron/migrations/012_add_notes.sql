@@ -0,0 +1,31 @@
+-- Migration 012: Item notes
+-- Rebuilds the full-text index so notes are searched alongside content
+ALTER TABLE history ADD COLUMN note TEXT;
+
+DROP TRIGGER IF EXISTS history_fts_after_insert;
+DROP TRIGGER IF EXISTS history_fts_after_delete;
+DROP TRIGGER IF EXISTS history_fts_after_update;
+DROP TABLE IF EXISTS history_fts;
+
+CREATE VIRTUAL TABLE history_fts USING fts5(
+    content,
+    note,
+    content = 'history',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER history_fts_after_insert AFTER INSERT ON history BEGIN
+    INSERT INTO history_fts(rowid, content, note) VALUES (new.id, new.content, new.note);
+END;
+
+CREATE TRIGGER history_fts_after_delete AFTER DELETE ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content, note) VALUES ('delete', old.id, old.content, old.note);
+END;
+
+CREATE TRIGGER history_fts_after_update AFTER UPDATE OF content, note ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content, note) VALUES ('delete', old.id, old.content, old.note);
+    INSERT INTO history_fts(rowid, content, note) VALUES (new.id, new.content, new.note);
+END;
+
+INSERT INTO history_fts(history_fts) VALUES ('rebuild');
@@ -0,0 +1,6 @@
+-- Migration 013: Source application metadata
+-- Where each copy came from; the index backs the "copied from" filter
+ALTER TABLE history ADD COLUMN source_app TEXT;
+ALTER TABLE history ADD COLUMN source_title TEXT;
+ALTER TABLE history ADD COLUMN source_url TEXT;
+CREATE INDEX IF NOT EXISTS idx_history_source_app ON history(source_app COLLATE NOCASE);
@@ -0,0 +1,9 @@
+-- Migration 014: Named registers
+-- Vim-style clipboards; each holds its own copy, independent of history
+CREATE TABLE IF NOT EXISTS registers (
+    name TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    rtf TEXT,
+    html TEXT,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
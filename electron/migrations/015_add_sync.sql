@@ -0,0 +1,4 @@
+-- Migration 015: LAN sync
+-- Items can be kept local-only; synced_from records the paired device an item came from, so it is never sent back
+ALTER TABLE history ADD COLUMN sync_excluded INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE history ADD COLUMN synced_from TEXT;
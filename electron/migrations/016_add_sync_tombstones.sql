@@ -0,0 +1,8 @@
+-- Migration 016: Sync tombstones
+-- Deleted items are remembered by content hash so sync deletes them on other devices and refuses older copies; synced_from is the device a deletion came from, so it is never sent back
+CREATE TABLE IF NOT EXISTS sync_tombstones (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    content_hash TEXT NOT NULL UNIQUE,
+    deleted_at TEXT NOT NULL,
+    synced_from TEXT
+);
@@ -0,0 +1,27 @@
+-- Migration 017: Trash
+-- Deleted items are moved here until restored or purged; tags holds the item's tag names and collections its collection ids, as JSON arrays
+CREATE TABLE IF NOT EXISTS trash (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    content TEXT NOT NULL,
+    type TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    is_favorite INTEGER DEFAULT 0,
+    pinned INTEGER NOT NULL DEFAULT 0,
+    rtf TEXT,
+    html TEXT,
+    image BLOB,
+    image_width INTEGER,
+    image_height INTEGER,
+    content_hash TEXT,
+    use_count INTEGER NOT NULL DEFAULT 1,
+    note TEXT,
+    source_app TEXT,
+    source_title TEXT,
+    source_url TEXT,
+    sync_excluded INTEGER NOT NULL DEFAULT 0,
+    synced_from TEXT,
+    tags TEXT NOT NULL DEFAULT '[]',
+    collections TEXT NOT NULL DEFAULT '[]',
+    deleted_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_trash_deleted_at ON trash(deleted_at);
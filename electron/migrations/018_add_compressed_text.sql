@@ -0,0 +1,6 @@
+-- Migration 018: Compressed text
+-- Text over 64K characters is kept zstd-compressed in content_zstd, with its first 4,096 characters left in content for search; content_size is the original size in bytes
+ALTER TABLE history ADD COLUMN content_zstd BLOB;
+ALTER TABLE history ADD COLUMN content_size INTEGER;
+ALTER TABLE trash ADD COLUMN content_zstd BLOB;
+ALTER TABLE trash ADD COLUMN content_size INTEGER;
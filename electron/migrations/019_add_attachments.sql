@@ -0,0 +1,42 @@
+-- Migration 019: Attachment store
+-- Image data moves out of the database into encrypted files named by content hash; attachments records each file's size and how many history and trash rows use it, kept up to date by triggers
+CREATE TABLE IF NOT EXISTS attachments (
+    hash TEXT PRIMARY KEY,
+    size INTEGER NOT NULL,
+    ref_count INTEGER NOT NULL DEFAULT 0
+);
+
+-- Single row holding the key attachment files are encrypted with
+CREATE TABLE IF NOT EXISTS attachment_key (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    key BLOB NOT NULL
+);
+
+ALTER TABLE history ADD COLUMN image_hash TEXT;
+ALTER TABLE trash ADD COLUMN image_hash TEXT;
+
+CREATE TRIGGER IF NOT EXISTS history_attachment_after_insert AFTER INSERT ON history WHEN new.image_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.image_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_attachment_after_delete AFTER DELETE ON history WHEN old.image_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.image_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_attachment_after_update AFTER UPDATE OF image_hash ON history BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.image_hash;
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.image_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_attachment_after_insert AFTER INSERT ON trash WHEN new.image_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.image_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_attachment_after_delete AFTER DELETE ON trash WHEN old.image_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.image_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_attachment_after_update AFTER UPDATE OF image_hash ON trash BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.image_hash;
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.image_hash;
+END;
@@ -0,0 +1,4 @@
+-- Migration 020: Image thumbnails
+-- Small encoded thumbnails of image items as data URLs, created on first request so list views never decode the original
+ALTER TABLE history ADD COLUMN thumbnail TEXT;
+ALTER TABLE trash ADD COLUMN thumbnail TEXT;
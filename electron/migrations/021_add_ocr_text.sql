@@ -0,0 +1,33 @@
+-- Migration 021: Text recognized in images
+-- ocr_text holds text found in an image item (empty when none; NULL until recognized); the full-text index is rebuilt to search it
+ALTER TABLE history ADD COLUMN ocr_text TEXT;
+ALTER TABLE trash ADD COLUMN ocr_text TEXT;
+
+DROP TRIGGER IF EXISTS history_fts_after_insert;
+DROP TRIGGER IF EXISTS history_fts_after_delete;
+DROP TRIGGER IF EXISTS history_fts_after_update;
+DROP TABLE IF EXISTS history_fts;
+
+CREATE VIRTUAL TABLE history_fts USING fts5(
+    content,
+    note,
+    ocr_text,
+    content = 'history',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER history_fts_after_insert AFTER INSERT ON history BEGIN
+    INSERT INTO history_fts(rowid, content, note, ocr_text) VALUES (new.id, new.content, new.note, new.ocr_text);
+END;
+
+CREATE TRIGGER history_fts_after_delete AFTER DELETE ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content, note, ocr_text) VALUES ('delete', old.id, old.content, old.note, old.ocr_text);
+END;
+
+CREATE TRIGGER history_fts_after_update AFTER UPDATE OF content, note, ocr_text ON history BEGIN
+    INSERT INTO history_fts(history_fts, rowid, content, note, ocr_text) VALUES ('delete', old.id, old.content, old.note, old.ocr_text);
+    INSERT INTO history_fts(rowid, content, note, ocr_text) VALUES (new.id, new.content, new.note, new.ocr_text);
+END;
+
+INSERT INTO history_fts(history_fts) VALUES ('rebuild');
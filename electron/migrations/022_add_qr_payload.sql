@@ -0,0 +1,4 @@
+-- Migration 022: QR codes in images
+-- qr_payload holds the decoded text of a QR code found in an image item (empty when none; NULL until scanned)
+ALTER TABLE history ADD COLUMN qr_payload TEXT;
+ALTER TABLE trash ADD COLUMN qr_payload TEXT;
@@ -0,0 +1,4 @@
+-- Migration 023: Perceptual hashes of images
+-- image_phash holds a 64-bit difference hash (16 hex digits) of an image item, for finding near-duplicates (empty if the image could not be hashed; NULL until hashed)
+ALTER TABLE history ADD COLUMN image_phash TEXT;
+ALTER TABLE trash ADD COLUMN image_phash TEXT;
@@ -0,0 +1,6 @@
+-- Migration 024: Content kinds
+-- content_kind records what a text item holds (url, email, phone, path, color, json, code, ip or text); NULL for other item types and until classified
+ALTER TABLE history ADD COLUMN content_kind TEXT;
+ALTER TABLE trash ADD COLUMN content_kind TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_history_content_kind ON history(content_kind);
@@ -0,0 +1,9 @@
+-- Migration 025: Code languages
+-- code_language holds the programming language guessed for a code item (content_kind = 'code'); NULL for other items or when no language stood out
+ALTER TABLE history ADD COLUMN code_language TEXT;
+ALTER TABLE trash ADD COLUMN code_language TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_history_code_language ON history(code_language);
+
+-- Code items classified before languages were detected are classified again at startup
+UPDATE history SET content_kind = NULL WHERE content_kind = 'code';
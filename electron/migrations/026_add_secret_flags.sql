@@ -0,0 +1,9 @@
+-- Migration 026: Secret flags
+-- secret_match names the secret scanning rule a text capture matched (e.g. AWS access key); NULL if none
+-- expires_at is when a flagged item is deleted; NULL keeps it
+ALTER TABLE history ADD COLUMN secret_match TEXT;
+ALTER TABLE history ADD COLUMN expires_at TEXT;
+ALTER TABLE trash ADD COLUMN secret_match TEXT;
+ALTER TABLE trash ADD COLUMN expires_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_history_expires_at ON history(expires_at);
@@ -0,0 +1,9 @@
+-- Migration 027: Link previews
+-- link_title is the title of the page a URL item links to; empty if it had none or could not be fetched, NULL until fetched
+-- link_description and link_image (an absolute image URL) come from the page's Open Graph or description tags
+ALTER TABLE history ADD COLUMN link_title TEXT;
+ALTER TABLE history ADD COLUMN link_description TEXT;
+ALTER TABLE history ADD COLUMN link_image TEXT;
+ALTER TABLE trash ADD COLUMN link_title TEXT;
+ALTER TABLE trash ADD COLUMN link_description TEXT;
+ALTER TABLE trash ADD COLUMN link_image TEXT;
@@ -0,0 +1,11 @@
+-- Migration 028: Text stats
+-- byte_size is the UTF-8 size of a text item or the PNG size of an image item; char_count, word_count and line_count are for text items only
+-- NULL until computed (at capture, or by a backfill for older items)
+ALTER TABLE history ADD COLUMN byte_size INTEGER;
+ALTER TABLE history ADD COLUMN char_count INTEGER;
+ALTER TABLE history ADD COLUMN word_count INTEGER;
+ALTER TABLE history ADD COLUMN line_count INTEGER;
+ALTER TABLE trash ADD COLUMN byte_size INTEGER;
+ALTER TABLE trash ADD COLUMN char_count INTEGER;
+ALTER TABLE trash ADD COLUMN word_count INTEGER;
+ALTER TABLE trash ADD COLUMN line_count INTEGER;
@@ -0,0 +1,4 @@
+-- Migration 030: Snippet abbreviations
+-- Typed text the text expander replaces with the snippet, e.g. ";addr"; unique among snippets that have one
+ALTER TABLE snippets ADD COLUMN abbreviation TEXT;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_snippets_abbreviation ON snippets(abbreviation) WHERE abbreviation IS NOT NULL;
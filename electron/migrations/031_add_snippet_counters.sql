@@ -0,0 +1,3 @@
+-- Migration 031: Snippet counters
+-- counter is the last value pasted for {counter} in the snippet; each paste that uses it adds one
+ALTER TABLE snippets ADD COLUMN counter INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+-- Migration 032: Paste counts
+-- paste_count counts the times an item was restored, pasted or typed out from history, and last_used_at holds when it last was (NULL if never)
+ALTER TABLE history ADD COLUMN paste_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE history ADD COLUMN last_used_at TEXT;
+ALTER TABLE trash ADD COLUMN paste_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE trash ADD COLUMN last_used_at TEXT;
@@ -0,0 +1,30 @@
+-- Migration 033: Text attachments
+-- Text over the capture size limit can keep its complete version in the attachment store; history keeps a preview and the attachment's hash, counted in attachments like image_hash
+ALTER TABLE history ADD COLUMN text_hash TEXT;
+ALTER TABLE trash ADD COLUMN text_hash TEXT;
+
+CREATE TRIGGER IF NOT EXISTS history_text_attachment_after_insert AFTER INSERT ON history WHEN new.text_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.text_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_text_attachment_after_delete AFTER DELETE ON history WHEN old.text_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.text_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS history_text_attachment_after_update AFTER UPDATE OF text_hash ON history BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.text_hash;
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.text_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_text_attachment_after_insert AFTER INSERT ON trash WHEN new.text_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.text_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_text_attachment_after_delete AFTER DELETE ON trash WHEN old.text_hash IS NOT NULL BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.text_hash;
+END;
+
+CREATE TRIGGER IF NOT EXISTS trash_text_attachment_after_update AFTER UPDATE OF text_hash ON trash BEGIN
+    UPDATE attachments SET ref_count = ref_count - 1 WHERE hash = old.text_hash;
+    UPDATE attachments SET ref_count = ref_count + 1 WHERE hash = new.text_hash;
+END;
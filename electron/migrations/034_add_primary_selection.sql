@@ -0,0 +1,4 @@
+-- Migration 034: PRIMARY selection
+-- is_primary is 1 for text captured from the X11 PRIMARY selection (selected, not copied) on Linux; copying the same text to the clipboard clears it
+ALTER TABLE history ADD COLUMN is_primary INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE trash ADD COLUMN is_primary INTEGER NOT NULL DEFAULT 0;
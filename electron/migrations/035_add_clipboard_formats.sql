@@ -0,0 +1,4 @@
+-- Migration 035: Extra clipboard formats
+-- formats holds the registered Windows formats of a text copy (Office drawing, sheet and OLE data), packed by electron/lib/windows-formats.ts, so restoring it keeps its fidelity; NULL elsewhere
+ALTER TABLE history ADD COLUMN formats BLOB;
+ALTER TABLE trash ADD COLUMN formats BLOB;
@@ -0,0 +1,4 @@
+-- Migration 036: Similarity hashes of text
+-- text_simhash holds a 64-bit simhash (16 hex digits) of a text item, for finding near-duplicate edits of the same text (empty if the text is too short to compare; NULL until hashed)
+ALTER TABLE history ADD COLUMN text_simhash TEXT;
+ALTER TABLE trash ADD COLUMN text_simhash TEXT;
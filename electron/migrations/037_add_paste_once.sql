@@ -0,0 +1,4 @@
+-- Migration 037: One-time items
+-- paste_once marks an item deleted from history (skipping the trash) the first time it is restored, pasted or typed out, e.g. a password or 2FA code
+ALTER TABLE history ADD COLUMN paste_once INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE trash ADD COLUMN paste_once INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+-- Migration 038: Text embeddings
+-- Semantic search vectors of text items, one per item, from the embedding model named in model (little-endian 32-bit floats, unit length)
+CREATE TABLE IF NOT EXISTS history_embeddings (
+    history_id INTEGER PRIMARY KEY REFERENCES history(id) ON DELETE CASCADE,
+    model TEXT NOT NULL,
+    vector BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_history_embeddings_model ON history_embeddings(model);
@@ -0,0 +1,7 @@
+-- Migration 039: Item summaries
+-- summary_title is a short title a language model wrote for a long text item; empty if it gave none, NULL until summarized
+-- summary_text is the model's one-sentence summary of the item
+ALTER TABLE history ADD COLUMN summary_title TEXT;
+ALTER TABLE history ADD COLUMN summary_text TEXT;
+ALTER TABLE trash ADD COLUMN summary_title TEXT;
+ALTER TABLE trash ADD COLUMN summary_text TEXT;
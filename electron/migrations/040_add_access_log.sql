@@ -0,0 +1,19 @@
+-- Migration 040: Access log
+-- Append-only record of items revealed, copied, pasted and exported: history_id is the item (NULL for an export), surface where the request came from (ui, cli, api, dbus or url), detail what an export wrote
+CREATE TABLE IF NOT EXISTS access_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    history_id INTEGER,
+    action TEXT NOT NULL,
+    surface TEXT NOT NULL,
+    detail TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+CREATE INDEX IF NOT EXISTS idx_access_log_created_at ON access_log(created_at);
+
+CREATE TRIGGER IF NOT EXISTS access_log_no_update BEFORE UPDATE ON access_log BEGIN
+    SELECT RAISE(ABORT, 'access_log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS access_log_no_delete BEFORE DELETE ON access_log BEGIN
+    SELECT RAISE(ABORT, 'access_log is append-only');
+END;
@@ -0,0 +1,7 @@
+-- Migration 041: Color values
+-- color_value holds a color item's canonical lowercase hex (#rrggbb, or #rrggbbaa when translucent); NULL for other items. Its rgb() and hsl() forms are derived from it
+ALTER TABLE history ADD COLUMN color_value TEXT;
+ALTER TABLE trash ADD COLUMN color_value TEXT;
+
+-- Color items classified before values were stored are classified again at startup
+UPDATE history SET content_kind = NULL WHERE content_kind = 'color';
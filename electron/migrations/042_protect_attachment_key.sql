@@ -0,0 +1,3 @@
+-- Migration 042: Protected attachment key
+-- protected is 1 once the attachment key is stored encrypted by the OS keychain (Electron's safeStorage) rather than as is
+ALTER TABLE attachment_key ADD COLUMN protected INTEGER NOT NULL DEFAULT 0;
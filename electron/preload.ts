@@ -1,40 +1,1809 @@
 import { contextBridge, ipcRenderer } from "electron";
 
+/**
+ * What a text item holds, detected on capture.
+ */
+type ContentKind =
+	| "url"
+	| "email"
+	| "phone"
+	| "path"
+	| "color"
+	| "json"
+	| "code"
+	| "ip"
+	| "text";
+
+/**
+ * Programming language guessed for a code item.
+ */
+type CodeLanguage =
+	| "javascript"
+	| "typescript"
+	| "python"
+	| "ruby"
+	| "php"
+	| "shell"
+	| "go"
+	| "rust"
+	| "java"
+	| "kotlin"
+	| "csharp"
+	| "swift"
+	| "c"
+	| "cpp"
+	| "sql"
+	| "html"
+	| "css";
+
+/**
+ * How history is ordered after pinned items.
+ */
+type HistoryOrder = "recent" | "frecency" | "usage";
+
+/**
+ * Where a text item was captured from: the clipboard or, on Linux, the
+ * PRIMARY selection.
+ */
+type SelectionSource = "clipboard" | "primary";
+
+/**
+ * Date group of an item in a grouped history page.
+ */
+type DateBucket = "pinned" | "today" | "yesterday" | "thisWeek" | "older";
+
+/**
+ * History row as returned by the main process.
+ */
+type HistoryRow = {
+	id: number;
+	content: string;
+	type: string;
+	created_at: string;
+	is_favorite: number;
+	pinned: number;
+	rtf: string | null;
+	html: string | null;
+	image_width: number | null;
+	image_height: number | null;
+	use_count: number;
+	paste_count: number;
+	last_used_at: string | null;
+	note: string | null;
+	source_app: string | null;
+	source_title: string | null;
+	source_url: string | null;
+	sync_excluded: number;
+	content_size: number | null;
+	qr_payload: string | null;
+	content_kind: ContentKind | null;
+	code_language: CodeLanguage | null;
+	color_value: string | null;
+	secret_match: string | null;
+	expires_at: string | null;
+	link_title: string | null;
+	link_description: string | null;
+	link_image: string | null;
+	summary_title: string | null;
+	summary_text: string | null;
+	byte_size: number | null;
+	char_count: number | null;
+	word_count: number | null;
+	line_count: number | null;
+	is_primary: number;
+	paste_once: number;
+};
+
+/**
+ * Search result as returned by the main process, with where the query
+ * matched its content.
+ */
+type SearchResultRow = HistoryRow & { match_ranges: [number, number][] };
+
+/**
+ * Trash entry as returned by the main process; `id` is the entry's.
+ */
+type TrashRow = HistoryRow & { deleted_at: string; tags: string[] };
+
+/**
+ * Change applied to every item of a batch update.
+ */
+type BatchOperation =
+	| { type: "delete" }
+	| { type: "pin"; pinned: boolean }
+	| { type: "tag"; name: string }
+	| { type: "move"; collectionId: number; fromCollectionId?: number };
+
+/**
+ * Collection row as returned by the main process.
+ */
+type CollectionRow = {
+	id: number;
+	name: string;
+	created_at: string;
+	item_count: number;
+};
+
+/**
+ * Named register as returned by the main process.
+ */
+type RegisterRow = {
+	name: string;
+	content: string;
+	rtf: string | null;
+	html: string | null;
+	updated_at: string;
+};
+
+/**
+ * Saved snippet as returned by the main process.
+ */
+type SnippetRow = {
+	id: number;
+	name: string;
+	content: string;
+	abbreviation: string | null;
+	counter: number;
+	created_at: string;
+	updated_at: string;
+};
+
+/**
+ * Fields of a snippet to create, or to change on update.
+ */
+type SnippetInput = {
+	name: string;
+	content: string;
+	abbreviation?: string | null;
+};
+
+/**
+ * Items captured in one local day, or week starting `period`.
+ */
+type PeriodCount = {
+	period: string;
+	count: number;
+};
+
+/**
+ * History aggregates as returned by the main process.
+ */
+type HistoryStats = {
+	totalItems: number;
+	itemsPerDay: PeriodCount[];
+	itemsPerWeek: PeriodCount[];
+	topSourceApps: { name: string; count: number }[];
+	types: { type: string; count: number }[];
+	kinds: { kind: string; count: number }[];
+	averageItemBytes: number | null;
+	storageBytes: { database: number; attachments: number; total: number };
+};
+
+/**
+ * Items per day of one year as returned by the main process.
+ */
+type ActivityHeatmap = {
+	year: number;
+	days: PeriodCount[];
+	total: number;
+	maxCount: number;
+};
+
+/**
+ * Retention limits as returned by the main process.
+ */
+type RetentionPolicy = {
+	maxItems: number | null;
+	maxAgeDays: number | null;
+	maxDatabaseMb: number | null;
+	trashDays: number | null;
+	keepPastedAtLeast: number | null;
+};
+
+/**
+ * Backup settings and state returned by the main process.
+ */
+type BackupStatus = {
+	enabled: boolean;
+	directory: string;
+	hasPassphrase: boolean;
+	intervalHours: number;
+	keep: number;
+	lastBackupAt: string | null;
+	running: boolean;
+	lastError: string | null;
+};
+
+/**
+ * How to re-encode an image item; quality applies to JPEG and WebP.
+ */
+type ImageConversion = {
+	format: "png" | "jpeg" | "webp";
+	quality?: number;
+};
+
+/**
+ * Whether a files item is restored as its files or the contents of its
+ * only file.
+ */
+type FileRestoreMode = "paths" | "contents";
+
+/**
+ * Notation a color item is copied in.
+ */
+type ColorFormat = "hex" | "rgb" | "hsl";
+
+type DiffOperation = "equal" | "insert" | "delete";
+
+/**
+ * Line-by-line comparison of two text items, in unified diff hunks.
+ */
+type TextDiff = {
+	hunks: Array<{
+		oldStart: number;
+		oldLines: number;
+		newStart: number;
+		newLines: number;
+		lines: Array<{
+			type: DiffOperation;
+			text: string;
+			oldLine: number | null;
+			newLine: number | null;
+			segments?: Array<{ type: DiffOperation; text: string }>;
+		}>;
+	}>;
+	added: number;
+	removed: number;
+};
+
+/**
+ * A text transformation and where its result goes.
+ */
+type TextTransformRequest = {
+	transform:
+		| "uppercase"
+		| "lowercase"
+		| "titleCase"
+		| "trim"
+		| "collapseWhitespace"
+		| "removeLineBreaks"
+		| "sortLines"
+		| "dedupeLines"
+		| "base64Encode"
+		| "base64Decode"
+		| "urlEncode"
+		| "urlDecode"
+		| "splitQueryString";
+	target?: "item" | "clipboard";
+};
+
+/**
+ * A Markdown or HTML conversion and where its result goes.
+ */
+type MarkupConversionRequest = {
+	conversion: "markdownToHtml" | "htmlToMarkdown";
+	target?: "item" | "clipboard";
+};
+
+/**
+ * A structured formatter and where its result goes.
+ */
+type FormatRequest = {
+	format:
+		| "json-pretty"
+		| "json-minify"
+		| "xml-pretty"
+		| "xml-minify"
+		| "sql-pretty"
+		| "sql-minify";
+	target?: "item" | "clipboard";
+};
+
+/**
+ * Database check result as returned by the main process.
+ */
+type DatabaseCheck = {
+	ok: boolean;
+	integrityErrors: string[];
+	ftsErrors: string[];
+	quick: boolean;
+	durationMs: number;
+};
+
+/**
+ * Database recovery result as returned by the main process.
+ */
+type DatabaseRecovery = {
+	action: "none" | "repaired" | "salvaged";
+	before: DatabaseCheck;
+	after: DatabaseCheck;
+	salvaged: { name: string; rows: number; error: string | null }[] | null;
+	corruptPath: string | null;
+};
+
+/**
+ * App lock state as returned by the main process.
+ */
+type AppLockStatus = {
+	enabled: boolean;
+	autoLockMinutes: number | null;
+	locked: boolean;
+	available: boolean;
+	unprotected: boolean;
+};
+
+type LockChangeReason = "unlock" | "manual" | "system" | "idle";
+
+/**
+ * Clipboard auto-clear state as returned by the main process.
+ */
+type AutoClearStatus = {
+	enabled: boolean;
+	clearAfterSeconds: number;
+	pendingClearAt: number | null;
+};
+
+/**
+ * A history change pushed by the main process.
+ */
+type HistoryEvent =
+	| { type: "item.added"; item: HistoryRow }
+	| { type: "item.deleted"; id: number }
+	| { type: "item.pinned"; id: number; pinned: boolean }
+	| { type: "history.cleared" };
+
+/**
+ * Capture pause state as returned by the main process.
+ */
+type CapturePauseStatus = {
+	paused: boolean;
+	resumesAt: number | null;
+};
+
+/**
+ * Read-only mode state as returned by the main process.
+ */
+type ReadOnlyStatus = {
+	enabled: boolean;
+	forced: boolean;
+	active: boolean;
+};
+
+/**
+ * Capture sound settings as returned by the main process.
+ */
+type SoundEvent = "capture" | "pause" | "resume";
+type CaptureSoundSettings = {
+	enabled: boolean;
+	sounds: Record<SoundEvent, string>;
+};
+
+/**
+ * Append-copy state as returned by the main process.
+ */
+type AppendCopyStatus = {
+	active: boolean;
+	separator: string;
+};
+
+/**
+ * Capture size limits as returned by the main process.
+ */
+type CaptureLimitSettings = {
+	text: { maxBytes: number; policy: "skip" | "truncate" | "attachment" };
+	richText: { maxBytes: number; policy: "skip" | "drop" };
+	image: { maxBytes: number };
+};
+
+/**
+ * Power-aware throttling settings as returned by the main process.
+ */
+type PowerThrottleSettings = {
+	onBattery: boolean;
+	idleMinutes: number;
+	pollIntervalMs: number;
+	deferJobs: boolean;
+};
+
+/**
+ * Current power throttling state.
+ */
+type PowerThrottleStatus = {
+	reason: "battery" | "idle" | null;
+	pollIntervalMs: number | null;
+	deferJobs: boolean;
+};
+
+/**
+ * Universal Clipboard settings as returned by the main process.
+ */
+type UniversalClipboardSettings = {
+	action: "keep" | "skip" | "tag";
+	tag: string;
+};
+
+/**
+ * Clipboard restore settings as returned by the main process.
+ */
+type ClipboardRestoreSettings = {
+	enabled: boolean;
+};
+
+/**
+ * PRIMARY selection capture settings as returned by the main process.
+ */
+type PrimarySelectionSettings = {
+	enabled: boolean;
+	mode: "merged" | "separate";
+};
+
+/**
+ * Per-application exclusion rules as returned by the main process.
+ */
+type AppExclusionSettings = {
+	excludedApps: string[];
+};
+
+/**
+ * Secret scanning settings as returned by the main process.
+ */
+type SecretScanningSettings = {
+	enabled: boolean;
+	expireAfterMinutes: number | null;
+	rules: Array<{ name: string; pattern: string }>;
+};
+
+/**
+ * Which events show an OS notification, returned by the main process.
+ */
+type NotificationSettings = Record<
+	"captureRule" | "secretCopied" | "syncCompleted",
+	boolean
+>;
+
+/**
+ * A user script as returned by the main process.
+ */
+type UserScript = {
+	name: string;
+	source: string;
+	onCapture: boolean;
+};
+
+/**
+ * What a script made of an item.
+ */
+type ScriptResult = {
+	rejected: boolean;
+	text: string;
+};
+
+/**
+ * External command actions as returned by the main process.
+ */
+type CommandActionSettings = {
+	actions: Array<{
+		name: string;
+		command: string;
+		args: string[];
+		stdin: boolean;
+		captureOutput: boolean;
+		timeoutSeconds: number;
+	}>;
+};
+
+/**
+ * Capture rules as returned by the main process.
+ */
+type CaptureRuleSettings = {
+	rules: Array<{
+		name: string;
+		enabled: boolean;
+		priority: number;
+		conditions: {
+			pattern?: string;
+			ignoreCase?: boolean;
+			type?: "text" | "image" | "files";
+			kind?: ContentKind;
+		};
+		actions: Array<
+			| { type: "tag"; tag: string }
+			| { type: "ignore" }
+			| { type: "transform"; transform: TextTransformRequest["transform"] }
+			| { type: "expire"; minutes: number }
+			| { type: "notify" }
+		>;
+	}>;
+};
+
+/**
+ * Link preview settings as returned by the main process.
+ */
+type LinkPreviewSettings = {
+	enabled: boolean;
+};
+
+/**
+ * Semantic search settings as returned by the main process.
+ */
+type SemanticSearchSettings = {
+	enabled: boolean;
+	endpoint: string;
+	model: string;
+	apiKey: string;
+};
+
+/**
+ * Item summary settings as returned by the main process.
+ */
+type ItemSummarySettings = {
+	enabled: boolean;
+	endpoint: string;
+	model: string;
+	apiKey: string;
+	minChars: number;
+};
+
+type AccessAction = "reveal" | "copy" | "paste" | "export";
+
+type AccessSurface = "ui" | "cli" | "api" | "dbus" | "url";
+
+/**
+ * Access log entry as returned by the main process.
+ */
+type AccessLogEntry = {
+	id: number;
+	history_id: number | null;
+	action: AccessAction;
+	surface: AccessSurface;
+	detail: string | null;
+	created_at: string;
+};
+
+/**
+ * Global shortcut bindings returned by the main process.
+ */
+type ShortcutAction =
+	| "togglePicker"
+	| "toggleQuickPicker"
+	| "pasteLastItem"
+	| "pastePlainText"
+	| "pasteNextQueued"
+	| "pushClipboard"
+	| "popClipboard"
+	| "copyToRegister"
+	| "pasteRegister"
+	| "toggleAppendCopy"
+	| "toggleCapture";
+type ShortcutSettings = Record<ShortcutAction, string | null>;
+type ShortcutFallbacks = Record<ShortcutAction, string[]>;
+
+/**
+ * Two-step shortcuts returned by the main process.
+ */
+type ChordAction =
+	| ShortcutAction
+	| `pasteRecent${1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9}`;
+type ShortcutChord = { prefix: string; key: string; action: ChordAction };
+type ChordStatus = {
+	chords: ShortcutChord[];
+	unavailablePrefixes: string[];
+};
+
+/**
+ * What each tray click does, returned by the main process.
+ */
+type TrayClickAction =
+	| "menu"
+	| "none"
+	| "toggleWindow"
+	| "toggleQuickPicker"
+	| "toggleCapture"
+	| "pasteLastItem";
+type TrayClickSettings = Record<
+	"click" | "doubleClick" | "middleClick",
+	TrayClickAction
+>;
+
+/**
+ * Language of the tray, menus and notifications, returned by the main
+ * process.
+ */
+type LanguageSetting = "system" | "en" | "de" | "fr" | "es";
+type LanguageStatus = {
+	language: LanguageSetting;
+	active: Exclude<LanguageSetting, "system">;
+};
+type ShortcutStatus = Record<
+	ShortcutAction,
+	{ active: string | null; conflicts: string[] }
+>;
+
+/**
+ * "Type it out" settings returned by the main process.
+ */
+type TypeOutSettings = {
+	delayMs: number;
+};
+
+/**
+ * Text expander settings and listener state returned by the main process.
+ */
+type TextExpanderStatus = {
+	enabled: boolean;
+	excludedApps: string[];
+	running: boolean;
+	lastError: string | null;
+};
+
+/**
+ * Double-copy gesture settings and listener state returned by the main
+ * process.
+ */
+type DoubleCopyStatus = {
+	enabled: boolean;
+	intervalMs: number;
+	running: boolean;
+	lastError: string | null;
+};
+
+/**
+ * Quick-paste hotkey settings returned by the main process.
+ */
+type QuickPasteSettings = {
+	enabled: boolean;
+	pasteAfterCopy: boolean;
+};
+
+/**
+ * Window behavior settings returned by the main process.
+ */
+type WindowBehaviorSettings = {
+	hideOnBlur: boolean;
+};
+
+/**
+ * Settings file contents returned by the main process.
+ */
+type AppSettings = {
+	shortcuts: ShortcutSettings;
+	retention: RetentionPolicy;
+	exclusions: AppExclusionSettings;
+	window: WindowBehaviorSettings;
+	appearance: { theme: "system" | "light" | "dark"; compactList: boolean };
+};
+
+/**
+ * Data folder location returned by the main process.
+ */
+type DataLocationStatus = {
+	dataPath: string;
+	source: "flag" | "portable" | "config" | "default";
+	pendingMove: string | null;
+	lastError: string | null;
+};
+
+/**
+ * Profiles returned by the main process.
+ */
+type ProfilesStatus = {
+	active: string;
+	profiles: string[];
+};
+
+/**
+ * Updater settings and progress returned by the main process.
+ */
+type UpdaterStatus = {
+	channel: "stable" | "beta";
+	autoCheck: boolean;
+	configured: boolean;
+	currentVersion: string;
+	state:
+		| "idle"
+		| "checking"
+		| "up-to-date"
+		| "available"
+		| "downloading"
+		| "ready"
+		| "installing"
+		| "error";
+	available: {
+		version: string;
+		notes: string;
+		publishedAt: string | null;
+	} | null;
+	progress: { received: number; total: number } | null;
+	lastCheckedAt: string | null;
+	lastError: string | null;
+};
+
+type LogLevel = "error" | "warn" | "info" | "debug";
+
+/**
+ * Log entry returned by the main process.
+ */
+type LogEntry = {
+	time: string;
+	level: LogLevel;
+	module: string;
+	message: string;
+};
+
+/**
+ * Log levels returned by the main process.
+ */
+type LoggingSettings = {
+	level: LogLevel;
+	modules: Record<string, LogLevel>;
+};
+
+type CrashKind =
+	| "uncaughtException"
+	| "unhandledRejection"
+	| "renderer"
+	| "childProcess";
+
+/**
+ * Crash report summary returned by the main process.
+ */
+type CrashReportSummary = {
+	fileName: string;
+	time: string;
+	kind: CrashKind;
+	message: string;
+};
+
+/**
+ * Settings file state returned by the main process.
+ */
+type SettingsFileStatus = {
+	path: string;
+	settings: AppSettings;
+	lastError: string | null;
+};
+
+/**
+ * Local HTTP API settings and state returned by the main process.
+ */
+type HttpApiStatus = {
+	enabled: boolean;
+	port: number;
+	token: string;
+	running: boolean;
+};
+
+/**
+ * Automation URL settings as returned by the main process.
+ */
+type UrlActionSettings = {
+	sendContent: boolean;
+	allowClipboardActions: boolean;
+	callbackSchemes: string[];
+};
+
+/**
+ * Outbound webhook settings and delivery state returned by the main
+ * process.
+ */
+type WebhookStatus = {
+	enabled: boolean;
+	url: string;
+	secret: string;
+	events: Array<
+		"item.added" | "item.deleted" | "item.pinned" | "history.cleared"
+	>;
+	itemTypes: Array<"text" | "image" | "files">;
+	kinds: string[];
+	includeContent: boolean;
+	pending: number;
+	lastDeliveredAt: string | null;
+	lastError: string | null;
+};
+
+/**
+ * Device on the network or paired for LAN sync.
+ */
+type SyncDevice = {
+	deviceId: string;
+	name: string;
+	paired: boolean;
+	online: boolean;
+};
+
+/**
+ * LAN sync settings and state returned by the main process.
+ */
+type SyncStatus = {
+	enabled: boolean;
+	deviceId: string;
+	deviceName: string;
+	port: number;
+	running: boolean;
+	devices: SyncDevice[];
+	pairingCode: string | null;
+	pairingExpiresAt: string | null;
+};
+
+/**
+ * Remote sync settings and state returned by the main process.
+ */
+type RemoteSyncStatus = {
+	enabled: boolean;
+	backend: "webdav" | "s3";
+	url: string;
+	bucket: string;
+	region: string;
+	prefix: string;
+	username: string;
+	hasPassword: boolean;
+	hasPassphrase: boolean;
+	intervalMinutes: number;
+	deviceId: string;
+	syncing: boolean;
+	lastSyncAt: string | null;
+	lastError: string | null;
+};
+
+/**
+ * Shared board settings and state returned by the main process.
+ */
+type BoardsStatus = {
+	server: {
+		enabled: boolean;
+		port: number;
+		running: boolean;
+		boards: Array<{ id: string; name: string; createdAt: string }>;
+	};
+	memberships: Array<{
+		boardId: string;
+		name: string;
+		url: string;
+		connected: boolean;
+		lastError: string | null;
+		itemCount: number;
+	}>;
+};
+
+/**
+ * Item received from a shared board.
+ */
+type BoardListItem = {
+	seq: number;
+	content: string;
+	note: string | null;
+	publishedBy: string;
+	publishedAt: string;
+};
+
+/**
+ * Clipboard stack entry returned by the main process.
+ */
+type ClipboardStackEntry = {
+	text: string;
+	rtf?: string;
+	html?: string;
+};
+
 contextBridge.exposeInMainWorld("electronAPI", {
 	clipboard: {
 		read: () =>
 			ipcRenderer.invoke("clipboard:read") as Promise<{
 				text: string;
 				rtf?: string;
+				html?: string;
 			}>,
-		write: (data: { text: string; rtf?: string }) =>
+		write: (data: { text: string; rtf?: string; html?: string }) =>
 			ipcRenderer.invoke("clipboard:write", data) as Promise<void>,
+		restoreItem: (id: number, options?: { files?: FileRestoreMode }) =>
+			ipcRenderer.invoke("clipboard:restoreItem", id, options) as Promise<void>,
+		pasteItem: (id: number, options?: { files?: FileRestoreMode }) =>
+			ipcRenderer.invoke("clipboard:pasteItem", id, options) as Promise<void>,
+		typeItem: (id: number) =>
+			ipcRenderer.invoke("clipboard:typeItem", id) as Promise<void>,
 	},
 	db: {
 		getHistory: (options?: {
 			query?: string;
 			limit?: number;
+			order?: HistoryOrder;
 			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
 			offset?: number;
 		}) =>
 			ipcRenderer.invoke("db:getHistory", options ?? {}) as Promise<
-				Array<{
-					id: number;
-					content: string;
-					type: string;
-					created_at: string;
-					is_favorite: number;
-					rtf: string | null;
-				}>
-			>,
-		addClip: (data: { text: string; rtf?: string }) =>
-			ipcRenderer.invoke("db:addClip", data) as Promise<void>,
+				HistoryRow[]
+			>,
+		listPage: (options?: {
+			cursor?: string | null;
+			pageSize?: number;
+			order?: HistoryOrder;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
+		}) =>
+			ipcRenderer.invoke("db:listPage", options ?? {}) as Promise<{
+				items: HistoryRow[];
+				nextCursor: string | null;
+			}>,
+		listGroupedPage: (options?: {
+			cursor?: string | null;
+			pageSize?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
+		}) =>
+			ipcRenderer.invoke("db:listGroupedPage", options ?? {}) as Promise<{
+				groups: { bucket: DateBucket; items: HistoryRow[] }[];
+				nextCursor: string | null;
+			}>,
+		revealItem: (id: number) =>
+			ipcRenderer.invoke("db:revealItem", id) as Promise<HistoryRow>,
+		openItem: (id: number) =>
+			ipcRenderer.invoke("db:openItem", id) as Promise<void>,
+		showItemInFolder: (id: number) =>
+			ipcRenderer.invoke("db:showItemInFolder", id) as Promise<void>,
+		searchHistory: (options: {
+			query: string;
+			limit?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
+			offset?: number;
+		}) =>
+			ipcRenderer.invoke("db:searchHistory", options) as Promise<
+				SearchResultRow[]
+			>,
+		fuzzySearchHistory: (options: {
+			query: string;
+			limit?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
+			offset?: number;
+		}) =>
+			ipcRenderer.invoke("db:fuzzySearchHistory", options) as Promise<
+				SearchResultRow[]
+			>,
+		regexSearchHistory: (options: {
+			query: string;
+			limit?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKind;
+			language?: CodeLanguage;
+			selection?: SelectionSource;
+			offset?: number;
+		}) =>
+			ipcRenderer.invoke("db:regexSearchHistory", options) as Promise<
+				SearchResultRow[]
+			>,
+		semanticSearch: (query: string, limit?: number) =>
+			ipcRenderer.invoke("db:semanticSearch", query, limit) as Promise<
+				Array<HistoryRow & { similarity: number }>
+			>,
 		deleteHistoryItem: (id: number) =>
 			ipcRenderer.invoke("db:deleteHistoryItem", id) as Promise<void>,
 		clearAllHistory: () =>
 			ipcRenderer.invoke("db:clearAllHistory") as Promise<void>,
+		listTrash: (options?: { limit?: number; offset?: number }) =>
+			ipcRenderer.invoke("db:listTrash", options) as Promise<TrashRow[]>,
+		restoreFromTrash: (id: number) =>
+			ipcRenderer.invoke("db:restoreFromTrash", id) as Promise<number>,
+		emptyTrash: () => ipcRenderer.invoke("db:emptyTrash") as Promise<number>,
+		requestWipe: () =>
+			ipcRenderer.invoke("db:requestWipe") as Promise<{
+				token: string;
+				expiresAt: string;
+			}>,
+		wipeAllHistory: (token: string) =>
+			ipcRenderer.invoke("db:wipeAllHistory", token) as Promise<void>,
+		getImagePreview: (id: number) =>
+			ipcRenderer.invoke("db:getImagePreview", id) as Promise<string | null>,
+		getThumbnail: (id: number) =>
+			ipcRenderer.invoke("db:getThumbnail", id) as Promise<string | null>,
+		copyQrPayload: (id: number) =>
+			ipcRenderer.invoke("db:copyQrPayload", id) as Promise<void>,
+		generateQr: (id: number, options?: { copy?: boolean }) =>
+			ipcRenderer.invoke("db:generateQr", id, options) as Promise<string>,
+		copyImageAs: (id: number, conversion: ImageConversion) =>
+			ipcRenderer.invoke("db:copyImageAs", id, conversion) as Promise<void>,
+		stripMetadata: (id: number) =>
+			ipcRenderer.invoke("db:stripMetadata", id) as Promise<boolean>,
+		saveImageAs: (id: number, conversion: ImageConversion, filePath: string) =>
+			ipcRenderer.invoke(
+				"db:saveImageAs",
+				id,
+				conversion,
+				filePath,
+			) as Promise<void>,
+		getColorFormats: (id: number) =>
+			ipcRenderer.invoke("db:getColorFormats", id) as Promise<
+				Record<ColorFormat, string>
+			>,
+		copyColorAs: (id: number, format: ColorFormat) =>
+			ipcRenderer.invoke("db:copyColorAs", id, format) as Promise<void>,
+		diffItems: (oldId: number, newId: number) =>
+			ipcRenderer.invoke("db:diffItems", oldId, newId) as Promise<TextDiff>,
+		saveItemToFile: (
+			id: number,
+			filePath: string,
+			options?: { conversion?: ImageConversion; overwrite?: boolean },
+		) =>
+			ipcRenderer.invoke(
+				"db:saveItemToFile",
+				id,
+				filePath,
+				options,
+			) as Promise<void>,
+		transformItem: (id: number, request: TextTransformRequest) =>
+			ipcRenderer.invoke("db:transformItem", id, request) as Promise<void>,
+		formatItem: (id: number, request: FormatRequest) =>
+			ipcRenderer.invoke("db:formatItem", id, request) as Promise<void>,
+		convertMarkup: (id: number, request: MarkupConversionRequest) =>
+			ipcRenderer.invoke("db:convertMarkup", id, request) as Promise<void>,
+		runScript: (
+			id: number,
+			request: { name: string; target?: "item" | "clipboard" },
+		) => ipcRenderer.invoke("db:runScript", id, request) as Promise<void>,
+		runCommandAction: (id: number, name: string) =>
+			ipcRenderer.invoke("db:runCommandAction", id, name) as Promise<{
+				output: string;
+				recorded: boolean;
+			}>,
+		hashItem: (id: number, algorithm: "md5" | "sha1" | "sha256" | "blake3") =>
+			ipcRenderer.invoke("db:hashItem", id, algorithm) as Promise<string>,
 		toggleFavorite: (id: number) =>
 			ipcRenderer.invoke("db:toggleFavorite", id) as Promise<boolean>,
+		togglePin: (id: number) =>
+			ipcRenderer.invoke("db:togglePin", id) as Promise<boolean>,
+		batchUpdate: (ids: number[], operation: BatchOperation) =>
+			ipcRenderer.invoke("db:batchUpdate", ids, operation) as Promise<number>,
+		toggleSyncExcluded: (id: number) =>
+			ipcRenderer.invoke("db:toggleSyncExcluded", id) as Promise<boolean>,
+		togglePasteOnce: (id: number) =>
+			ipcRenderer.invoke("db:togglePasteOnce", id) as Promise<boolean>,
+		setItemNote: (id: number, text: string) =>
+			ipcRenderer.invoke("db:setItemNote", id, text) as Promise<void>,
+		setItemExpiry: (id: number, expiresAt: string | null) =>
+			ipcRenderer.invoke("db:setItemExpiry", id, expiresAt) as Promise<void>,
+		dismissSecret: (id: number) =>
+			ipcRenderer.invoke("db:dismissSecret", id) as Promise<boolean>,
+		mergeItems: (ids: number[], separator: string, copy = false) =>
+			ipcRenderer.invoke(
+				"db:mergeItems",
+				ids,
+				separator,
+				copy,
+			) as Promise<HistoryRow>,
+		findNearDuplicates: (maxDistance?: number) =>
+			ipcRenderer.invoke("db:findNearDuplicates", maxDistance) as Promise<
+				number[][]
+			>,
+		findNearDuplicateTexts: (maxDistance?: number) =>
+			ipcRenderer.invoke("db:findNearDuplicateTexts", maxDistance) as Promise<
+				number[][]
+			>,
+		mergeDuplicates: (ids: number[]) =>
+			ipcRenderer.invoke("db:mergeDuplicates", ids) as Promise<HistoryRow>,
+		listSourceApps: () =>
+			ipcRenderer.invoke("db:listSourceApps") as Promise<
+				Array<{ name: string; count: number }>
+			>,
+		exportHistory: (
+			format: "json" | "csv",
+			filePath: string,
+			filters?: {
+				pinnedOnly?: boolean;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+			},
+		) =>
+			ipcRenderer.invoke(
+				"db:exportHistory",
+				format,
+				filePath,
+				filters ?? {},
+			) as Promise<number>,
+		exportSnippets: (
+			format: "alfred" | "raycast",
+			filePath: string,
+			source: "pinned" | { collectionId: number },
+		) =>
+			ipcRenderer.invoke(
+				"db:exportSnippets",
+				format,
+				filePath,
+				source,
+			) as Promise<number>,
+		listAccessLog: (query?: {
+			since?: string;
+			action?: AccessAction;
+			surface?: AccessSurface;
+			historyId?: number;
+			limit?: number;
+		}) =>
+			ipcRenderer.invoke("db:listAccessLog", query ?? {}) as Promise<
+				AccessLogEntry[]
+			>,
+		importHistory: (filePath: string) =>
+			ipcRenderer.invoke("db:importHistory", filePath) as Promise<{
+				imported: number;
+				skipped: number;
+			}>,
+		importCopyQ: (sourcePath: string) =>
+			ipcRenderer.invoke("db:importCopyQ", sourcePath) as Promise<{
+				imported: number;
+				skipped: number;
+			}>,
+		importDitto: (dbPath: string) =>
+			ipcRenderer.invoke("db:importDitto", dbPath) as Promise<{
+				imported: number;
+				skipped: number;
+			}>,
+		importMaccy: (dbPath: string) =>
+			ipcRenderer.invoke("db:importMaccy", dbPath) as Promise<{
+				imported: number;
+				skipped: number;
+			}>,
+		runMaintenance: () =>
+			ipcRenderer.invoke("db:runMaintenance") as Promise<{
+				sizeBefore: number;
+				sizeAfter: number;
+				reclaimedBytes: number;
+				durationMs: number;
+			}>,
+		checkDatabase: () =>
+			ipcRenderer.invoke("db:check") as Promise<DatabaseCheck>,
+		repairDatabase: () =>
+			ipcRenderer.invoke("db:repair") as Promise<DatabaseRecovery>,
+		getLastRecovery: () =>
+			ipcRenderer.invoke(
+				"db:getLastRecovery",
+			) as Promise<DatabaseRecovery | null>,
+		addTag: (id: number, name: string) =>
+			ipcRenderer.invoke("db:addTag", id, name) as Promise<string[]>,
+		removeTag: (id: number, name: string) =>
+			ipcRenderer.invoke("db:removeTag", id, name) as Promise<string[]>,
+		getItemTags: (id: number) =>
+			ipcRenderer.invoke("db:getItemTags", id) as Promise<string[]>,
+		listTags: () =>
+			ipcRenderer.invoke("db:listTags") as Promise<
+				Array<{ name: string; count: number }>
+			>,
+	},
+	collections: {
+		list: () =>
+			ipcRenderer.invoke("collections:list") as Promise<CollectionRow[]>,
+		create: (name: string) =>
+			ipcRenderer.invoke("collections:create", name) as Promise<CollectionRow>,
+		rename: (id: number, name: string) =>
+			ipcRenderer.invoke("collections:rename", id, name) as Promise<
+				CollectionRow
+			>,
+		delete: (id: number) =>
+			ipcRenderer.invoke("collections:delete", id) as Promise<void>,
+		getItems: (id: number) =>
+			ipcRenderer.invoke("collections:getItems", id) as Promise<HistoryRow[]>,
+		addItem: (collectionId: number, itemId: number) =>
+			ipcRenderer.invoke(
+				"collections:addItem",
+				collectionId,
+				itemId,
+			) as Promise<void>,
+		removeItem: (collectionId: number, itemId: number) =>
+			ipcRenderer.invoke(
+				"collections:removeItem",
+				collectionId,
+				itemId,
+			) as Promise<void>,
+		moveItem: (options: {
+			itemId: number;
+			fromCollectionId: number;
+			toCollectionId: number;
+		}) => ipcRenderer.invoke("collections:moveItem", options) as Promise<void>,
+		reorderItems: (collectionId: number, itemIds: number[]) =>
+			ipcRenderer.invoke(
+				"collections:reorderItems",
+				collectionId,
+				itemIds,
+			) as Promise<void>,
+	},
+	retention: {
+		getPolicy: () =>
+			ipcRenderer.invoke("retention:getPolicy") as Promise<RetentionPolicy>,
+		updatePolicy: (policy: Partial<RetentionPolicy>) =>
+			ipcRenderer.invoke(
+				"retention:updatePolicy",
+				policy,
+			) as Promise<RetentionPolicy>,
+	},
+	backup: {
+		get: () => ipcRenderer.invoke("backup:get") as Promise<BackupStatus>,
+		update: (settings: {
+			enabled?: boolean;
+			directory?: string;
+			passphrase?: string;
+			intervalHours?: number;
+			keep?: number;
+		}) =>
+			ipcRenderer.invoke("backup:update", settings) as Promise<BackupStatus>,
+		backupNow: () =>
+			ipcRenderer.invoke("backup:backupNow") as Promise<BackupStatus>,
+		restore: (backupPath: string, passphrase?: string) =>
+			ipcRenderer.invoke(
+				"backup:restore",
+				backupPath,
+				passphrase,
+			) as Promise<BackupStatus>,
+	},
+	lock: {
+		getStatus: () =>
+			ipcRenderer.invoke("lock:getStatus") as Promise<AppLockStatus>,
+		lock: () => ipcRenderer.invoke("lock:lock") as Promise<AppLockStatus>,
+		unlock: () => ipcRenderer.invoke("lock:unlock") as Promise<AppLockStatus>,
+		updateSettings: (settings: {
+			enabled?: boolean;
+			autoLockMinutes?: number | null;
+		}) =>
+			ipcRenderer.invoke(
+				"lock:updateSettings",
+				settings,
+			) as Promise<AppLockStatus>,
+	},
+	capture: {
+		getStatus: () =>
+			ipcRenderer.invoke(
+				"capture:getStatus",
+			) as Promise<CapturePauseStatus>,
+		setPaused: (paused: boolean) =>
+			ipcRenderer.invoke(
+				"capture:setPaused",
+				paused,
+			) as Promise<CapturePauseStatus>,
+		snooze: (minutes: number) =>
+			ipcRenderer.invoke(
+				"capture:snooze",
+				minutes,
+			) as Promise<CapturePauseStatus>,
+	},
+	readOnly: {
+		getStatus: () =>
+			ipcRenderer.invoke("readOnly:getStatus") as Promise<ReadOnlyStatus>,
+		updateSettings: (settings: { enabled?: boolean }) =>
+			ipcRenderer.invoke(
+				"readOnly:updateSettings",
+				settings,
+			) as Promise<ReadOnlyStatus>,
+	},
+	sounds: {
+		get: () =>
+			ipcRenderer.invoke("sounds:get") as Promise<CaptureSoundSettings>,
+		update: (settings: {
+			enabled?: boolean;
+			sounds?: Partial<Record<SoundEvent, string>>;
+		}) =>
+			ipcRenderer.invoke(
+				"sounds:update",
+				settings,
+			) as Promise<CaptureSoundSettings>,
+		preview: (event: SoundEvent) =>
+			ipcRenderer.invoke("sounds:preview", event) as Promise<void>,
+	},
+	captureLimits: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"captureLimits:getSettings",
+			) as Promise<CaptureLimitSettings>,
+		updateSettings: (settings: {
+			text?: Partial<CaptureLimitSettings["text"]>;
+			richText?: Partial<CaptureLimitSettings["richText"]>;
+			image?: Partial<CaptureLimitSettings["image"]>;
+		}) =>
+			ipcRenderer.invoke(
+				"captureLimits:updateSettings",
+				settings,
+			) as Promise<CaptureLimitSettings>,
+	},
+	primarySelection: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"primarySelection:getSettings",
+			) as Promise<PrimarySelectionSettings>,
+		updateSettings: (settings: Partial<PrimarySelectionSettings>) =>
+			ipcRenderer.invoke(
+				"primarySelection:updateSettings",
+				settings,
+			) as Promise<PrimarySelectionSettings>,
+	},
+	powerThrottle: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"powerThrottle:getSettings",
+			) as Promise<PowerThrottleSettings>,
+		updateSettings: (settings: Partial<PowerThrottleSettings>) =>
+			ipcRenderer.invoke(
+				"powerThrottle:updateSettings",
+				settings,
+			) as Promise<PowerThrottleSettings>,
+		getStatus: () =>
+			ipcRenderer.invoke(
+				"powerThrottle:getStatus",
+			) as Promise<PowerThrottleStatus>,
+	},
+	universalClipboard: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"universalClipboard:getSettings",
+			) as Promise<UniversalClipboardSettings>,
+		updateSettings: (settings: Partial<UniversalClipboardSettings>) =>
+			ipcRenderer.invoke(
+				"universalClipboard:updateSettings",
+				settings,
+			) as Promise<UniversalClipboardSettings>,
+	},
+	clipboardRestore: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"clipboardRestore:getSettings",
+			) as Promise<ClipboardRestoreSettings>,
+		updateSettings: (settings: Partial<ClipboardRestoreSettings>) =>
+			ipcRenderer.invoke(
+				"clipboardRestore:updateSettings",
+				settings,
+			) as Promise<ClipboardRestoreSettings>,
+	},
+	appendCopy: {
+		getStatus: () =>
+			ipcRenderer.invoke("appendCopy:getStatus") as Promise<AppendCopyStatus>,
+		setActive: (active: boolean) =>
+			ipcRenderer.invoke(
+				"appendCopy:setActive",
+				active,
+			) as Promise<AppendCopyStatus>,
+		updateSettings: (settings: { separator?: string }) =>
+			ipcRenderer.invoke(
+				"appendCopy:updateSettings",
+				settings,
+			) as Promise<AppendCopyStatus>,
+	},
+	autoClear: {
+		getStatus: () =>
+			ipcRenderer.invoke("autoClear:getStatus") as Promise<AutoClearStatus>,
+		updateSettings: (settings: {
+			enabled?: boolean;
+			clearAfterSeconds?: number;
+		}) =>
+			ipcRenderer.invoke(
+				"autoClear:updateSettings",
+				settings,
+			) as Promise<AutoClearStatus>,
+		cancel: () =>
+			ipcRenderer.invoke("autoClear:cancel") as Promise<AutoClearStatus>,
+	},
+	exclusions: {
+		get: () =>
+			ipcRenderer.invoke("exclusions:get") as Promise<AppExclusionSettings>,
+		update: (settings: { excludedApps?: string[] }) =>
+			ipcRenderer.invoke(
+				"exclusions:update",
+				settings,
+			) as Promise<AppExclusionSettings>,
+	},
+	secrets: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"secrets:getSettings",
+			) as Promise<SecretScanningSettings>,
+		updateSettings: (settings: Partial<SecretScanningSettings>) =>
+			ipcRenderer.invoke(
+				"secrets:updateSettings",
+				settings,
+			) as Promise<SecretScanningSettings>,
+	},
+	notifications: {
+		get: () =>
+			ipcRenderer.invoke("notifications:get") as Promise<NotificationSettings>,
+		update: (settings: Partial<NotificationSettings>) =>
+			ipcRenderer.invoke(
+				"notifications:update",
+				settings,
+			) as Promise<NotificationSettings>,
+	},
+	scripts: {
+		list: () => ipcRenderer.invoke("scripts:list") as Promise<UserScript[]>,
+		install: (script: { name: string; source: string; onCapture?: boolean }) =>
+			ipcRenderer.invoke("scripts:install", script) as Promise<UserScript[]>,
+		remove: (name: string) =>
+			ipcRenderer.invoke("scripts:remove", name) as Promise<UserScript[]>,
+		test: (request: {
+			name?: string;
+			source?: string;
+			text: string;
+			type?: "text" | "image" | "files";
+		}) => ipcRenderer.invoke("scripts:test", request) as Promise<ScriptResult>,
+	},
+	commandActions: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"commandActions:getSettings",
+			) as Promise<CommandActionSettings>,
+		updateSettings: (settings: Partial<CommandActionSettings>) =>
+			ipcRenderer.invoke(
+				"commandActions:updateSettings",
+				settings,
+			) as Promise<CommandActionSettings>,
+	},
+	captureRules: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"captureRules:getSettings",
+			) as Promise<CaptureRuleSettings>,
+		updateSettings: (settings: Partial<CaptureRuleSettings>) =>
+			ipcRenderer.invoke(
+				"captureRules:updateSettings",
+				settings,
+			) as Promise<CaptureRuleSettings>,
+	},
+	linkPreviews: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"linkPreviews:getSettings",
+			) as Promise<LinkPreviewSettings>,
+		updateSettings: (settings: Partial<LinkPreviewSettings>) =>
+			ipcRenderer.invoke(
+				"linkPreviews:updateSettings",
+				settings,
+			) as Promise<LinkPreviewSettings>,
+	},
+	semanticSearch: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"semanticSearch:getSettings",
+			) as Promise<SemanticSearchSettings>,
+		updateSettings: (settings: Partial<SemanticSearchSettings>) =>
+			ipcRenderer.invoke(
+				"semanticSearch:updateSettings",
+				settings,
+			) as Promise<SemanticSearchSettings>,
+	},
+	itemSummaries: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"itemSummaries:getSettings",
+			) as Promise<ItemSummarySettings>,
+		updateSettings: (settings: Partial<ItemSummarySettings>) =>
+			ipcRenderer.invoke(
+				"itemSummaries:updateSettings",
+				settings,
+			) as Promise<ItemSummarySettings>,
+	},
+	favicons: {
+		get: (domain: string) =>
+			ipcRenderer.invoke("favicons:get", domain) as Promise<string | null>,
+	},
+	shortcuts: {
+		get: () => ipcRenderer.invoke("shortcuts:get") as Promise<ShortcutSettings>,
+		set: (action: ShortcutAction, accelerator: string | null) =>
+			ipcRenderer.invoke(
+				"shortcuts:set",
+				action,
+				accelerator,
+			) as Promise<ShortcutSettings>,
+		getStatus: () =>
+			ipcRenderer.invoke("shortcuts:getStatus") as Promise<ShortcutStatus>,
+		getFallbacks: () =>
+			ipcRenderer.invoke(
+				"shortcuts:getFallbacks",
+			) as Promise<ShortcutFallbacks>,
+		setFallbacks: (action: ShortcutAction, accelerators: string[]) =>
+			ipcRenderer.invoke(
+				"shortcuts:setFallbacks",
+				action,
+				accelerators,
+			) as Promise<ShortcutFallbacks>,
+	},
+	chords: {
+		get: () => ipcRenderer.invoke("chords:get") as Promise<ChordStatus>,
+		update: (settings: { chords?: ShortcutChord[] }) =>
+			ipcRenderer.invoke("chords:update", settings) as Promise<ChordStatus>,
+	},
+	trayClicks: {
+		get: () =>
+			ipcRenderer.invoke("trayClicks:get") as Promise<TrayClickSettings>,
+		update: (settings: Partial<TrayClickSettings>) =>
+			ipcRenderer.invoke(
+				"trayClicks:update",
+				settings,
+			) as Promise<TrayClickSettings>,
+	},
+	language: {
+		get: () => ipcRenderer.invoke("language:get") as Promise<LanguageStatus>,
+		set: (language: LanguageSetting) =>
+			ipcRenderer.invoke("language:set", language) as Promise<LanguageStatus>,
+	},
+	queue: {
+		get: () => ipcRenderer.invoke("queue:get") as Promise<number[]>,
+		toggle: (itemId: number) =>
+			ipcRenderer.invoke("queue:toggle", itemId) as Promise<number[]>,
+		clear: () => ipcRenderer.invoke("queue:clear") as Promise<number[]>,
+		pasteNext: () => ipcRenderer.invoke("queue:pasteNext") as Promise<void>,
+	},
+	registers: {
+		list: () => ipcRenderer.invoke("registers:list") as Promise<RegisterRow[]>,
+		copy: (name: string) =>
+			ipcRenderer.invoke("registers:copy", name) as Promise<RegisterRow>,
+		paste: (name: string) =>
+			ipcRenderer.invoke("registers:paste", name) as Promise<void>,
+		delete: (name: string) =>
+			ipcRenderer.invoke("registers:delete", name) as Promise<void>,
+	},
+	snippets: {
+		list: (query?: string) =>
+			ipcRenderer.invoke("snippets:list", query) as Promise<SnippetRow[]>,
+		getFields: (content: string) =>
+			ipcRenderer.invoke("snippets:getFields", content) as Promise<string[]>,
+		create: (input: SnippetInput) =>
+			ipcRenderer.invoke("snippets:create", input) as Promise<SnippetRow>,
+		update: (id: number, input: Partial<SnippetInput>) =>
+			ipcRenderer.invoke("snippets:update", id, input) as Promise<SnippetRow>,
+		delete: (id: number) =>
+			ipcRenderer.invoke("snippets:delete", id) as Promise<void>,
+		paste: (id: number, fields?: Record<string, string>) =>
+			ipcRenderer.invoke("snippets:paste", id, fields) as Promise<void>,
+	},
+	stats: {
+		get: (options?: { days?: number; weeks?: number; topApps?: number }) =>
+			ipcRenderer.invoke("stats:get", options) as Promise<HistoryStats>,
+		heatmap: (year: number) =>
+			ipcRenderer.invoke("stats:heatmap", year) as Promise<ActivityHeatmap>,
+	},
+	stack: {
+		get: () =>
+			ipcRenderer.invoke("stack:get") as Promise<ClipboardStackEntry[]>,
+		clear: () =>
+			ipcRenderer.invoke("stack:clear") as Promise<ClipboardStackEntry[]>,
+	},
+	typeOut: {
+		getSettings: () =>
+			ipcRenderer.invoke("typeOut:getSettings") as Promise<TypeOutSettings>,
+		updateSettings: (settings: { delayMs?: number }) =>
+			ipcRenderer.invoke(
+				"typeOut:updateSettings",
+				settings,
+			) as Promise<TypeOutSettings>,
+	},
+	textExpander: {
+		get: () =>
+			ipcRenderer.invoke("textExpander:get") as Promise<TextExpanderStatus>,
+		update: (settings: { enabled?: boolean; excludedApps?: string[] }) =>
+			ipcRenderer.invoke(
+				"textExpander:update",
+				settings,
+			) as Promise<TextExpanderStatus>,
+	},
+	doubleCopy: {
+		get: () =>
+			ipcRenderer.invoke("doubleCopy:get") as Promise<DoubleCopyStatus>,
+		update: (settings: { enabled?: boolean; intervalMs?: number }) =>
+			ipcRenderer.invoke(
+				"doubleCopy:update",
+				settings,
+			) as Promise<DoubleCopyStatus>,
+	},
+	quickPaste: {
+		get: () =>
+			ipcRenderer.invoke("quickPaste:get") as Promise<QuickPasteSettings>,
+		update: (settings: { enabled?: boolean; pasteAfterCopy?: boolean }) =>
+			ipcRenderer.invoke(
+				"quickPaste:update",
+				settings,
+			) as Promise<QuickPasteSettings>,
+	},
+	windowBehavior: {
+		get: () =>
+			ipcRenderer.invoke(
+				"windowBehavior:get",
+			) as Promise<WindowBehaviorSettings>,
+		update: (settings: { hideOnBlur?: boolean }) =>
+			ipcRenderer.invoke(
+				"windowBehavior:update",
+				settings,
+			) as Promise<WindowBehaviorSettings>,
+	},
+	settings: {
+		get: () =>
+			ipcRenderer.invoke("settings:get") as Promise<SettingsFileStatus>,
+		update: (settings: {
+			[Section in keyof AppSettings]?: Partial<AppSettings[Section]>;
+		}) =>
+			ipcRenderer.invoke(
+				"settings:update",
+				settings,
+			) as Promise<SettingsFileStatus>,
+	},
+	dataLocation: {
+		get: () =>
+			ipcRenderer.invoke("dataLocation:get") as Promise<DataLocationStatus>,
+		migrate: (target: string) =>
+			ipcRenderer.invoke(
+				"dataLocation:migrate",
+				target,
+			) as Promise<DataLocationStatus>,
+	},
+	profiles: {
+		get: () => ipcRenderer.invoke("profiles:get") as Promise<ProfilesStatus>,
+		create: (name: string) =>
+			ipcRenderer.invoke("profiles:create", name) as Promise<ProfilesStatus>,
+		switch: (name: string) =>
+			ipcRenderer.invoke("profiles:switch", name) as Promise<void>,
+		remove: (name: string) =>
+			ipcRenderer.invoke("profiles:remove", name) as Promise<ProfilesStatus>,
+	},
+	logs: {
+		getRecent: (query?: {
+			limit?: number;
+			level?: LogLevel | null;
+			module?: string | null;
+		}) => ipcRenderer.invoke("logs:getRecent", query) as Promise<LogEntry[]>,
+		getSettings: () =>
+			ipcRenderer.invoke("logs:getSettings") as Promise<LoggingSettings>,
+		updateSettings: (settings: {
+			level?: LogLevel;
+			modules?: Record<string, LogLevel | null>;
+		}) =>
+			ipcRenderer.invoke(
+				"logs:updateSettings",
+				settings,
+			) as Promise<LoggingSettings>,
+		openFolder: () => ipcRenderer.invoke("logs:openFolder") as Promise<void>,
+	},
+	diagnostics: {
+		listCrashes: () =>
+			ipcRenderer.invoke("diagnostics:listCrashes") as Promise<
+				CrashReportSummary[]
+			>,
+		export: (filePath: string) =>
+			ipcRenderer.invoke("diagnostics:export", filePath) as Promise<{
+				filePath: string;
+				files: string[];
+			}>,
+	},
+	updater: {
+		get: () => ipcRenderer.invoke("updater:get") as Promise<UpdaterStatus>,
+		updateSettings: (
+			settings: Partial<Pick<UpdaterStatus, "channel" | "autoCheck">>,
+		) =>
+			ipcRenderer.invoke(
+				"updater:updateSettings",
+				settings,
+			) as Promise<UpdaterStatus>,
+		check: () => ipcRenderer.invoke("updater:check") as Promise<UpdaterStatus>,
+		download: () =>
+			ipcRenderer.invoke("updater:download") as Promise<UpdaterStatus>,
+		install: () =>
+			ipcRenderer.invoke("updater:install") as Promise<UpdaterStatus>,
+	},
+	httpApi: {
+		get: () => ipcRenderer.invoke("httpApi:get") as Promise<HttpApiStatus>,
+		update: (settings: { enabled?: boolean; port?: number; token?: string }) =>
+			ipcRenderer.invoke("httpApi:update", settings) as Promise<HttpApiStatus>,
+		regenerateToken: () =>
+			ipcRenderer.invoke("httpApi:regenerateToken") as Promise<HttpApiStatus>,
+	},
+	urlActions: {
+		getSettings: () =>
+			ipcRenderer.invoke(
+				"urlActions:getSettings",
+			) as Promise<UrlActionSettings>,
+		updateSettings: (settings: Partial<UrlActionSettings>) =>
+			ipcRenderer.invoke(
+				"urlActions:updateSettings",
+				settings,
+			) as Promise<UrlActionSettings>,
+	},
+	webhooks: {
+		get: () => ipcRenderer.invoke("webhooks:get") as Promise<WebhookStatus>,
+		update: (
+			settings: Partial<
+				Omit<WebhookStatus, "pending" | "lastDeliveredAt" | "lastError">
+			>,
+		) =>
+			ipcRenderer.invoke("webhooks:update", settings) as Promise<WebhookStatus>,
+		regenerateSecret: () =>
+			ipcRenderer.invoke("webhooks:regenerateSecret") as Promise<WebhookStatus>,
+		test: () => ipcRenderer.invoke("webhooks:test") as Promise<void>,
+	},
+	sync: {
+		get: () => ipcRenderer.invoke("sync:get") as Promise<SyncStatus>,
+		update: (settings: {
+			enabled?: boolean;
+			deviceName?: string;
+			port?: number;
+		}) => ipcRenderer.invoke("sync:update", settings) as Promise<SyncStatus>,
+		startPairing: () =>
+			ipcRenderer.invoke("sync:startPairing") as Promise<SyncStatus>,
+		cancelPairing: () =>
+			ipcRenderer.invoke("sync:cancelPairing") as Promise<SyncStatus>,
+		pair: (deviceId: string, code: string) =>
+			ipcRenderer.invoke("sync:pair", deviceId, code) as Promise<SyncStatus>,
+		unpair: (deviceId: string) =>
+			ipcRenderer.invoke("sync:unpair", deviceId) as Promise<SyncStatus>,
+	},
+	remoteSync: {
+		get: () =>
+			ipcRenderer.invoke("remoteSync:get") as Promise<RemoteSyncStatus>,
+		update: (settings: {
+			enabled?: boolean;
+			backend?: "webdav" | "s3";
+			url?: string;
+			bucket?: string;
+			region?: string;
+			prefix?: string;
+			username?: string;
+			password?: string;
+			passphrase?: string;
+			intervalMinutes?: number;
+		}) =>
+			ipcRenderer.invoke(
+				"remoteSync:update",
+				settings,
+			) as Promise<RemoteSyncStatus>,
+		syncNow: () =>
+			ipcRenderer.invoke("remoteSync:syncNow") as Promise<RemoteSyncStatus>,
+	},
+	boards: {
+		get: () => ipcRenderer.invoke("boards:get") as Promise<BoardsStatus>,
+		updateServer: (settings: { enabled?: boolean; port?: number }) =>
+			ipcRenderer.invoke(
+				"boards:updateServer",
+				settings,
+			) as Promise<BoardsStatus>,
+		createBoard: (name: string, passphrase: string) =>
+			ipcRenderer.invoke("boards:createBoard", name, passphrase) as Promise<{
+				invite: string;
+				status: BoardsStatus;
+			}>,
+		regenerateToken: (boardId: string) =>
+			ipcRenderer.invoke("boards:regenerateToken", boardId) as Promise<{
+				invite: string;
+				status: BoardsStatus;
+			}>,
+		deleteBoard: (boardId: string) =>
+			ipcRenderer.invoke(
+				"boards:deleteBoard",
+				boardId,
+			) as Promise<BoardsStatus>,
+		join: (input: { invite: string; passphrase: string; name?: string }) =>
+			ipcRenderer.invoke("boards:join", input) as Promise<BoardsStatus>,
+		leave: (boardId: string) =>
+			ipcRenderer.invoke("boards:leave", boardId) as Promise<BoardsStatus>,
+		publish: (boardId: string, id: number) =>
+			ipcRenderer.invoke("boards:publish", boardId, id) as Promise<number>,
+		listItems: (boardId: string) =>
+			ipcRenderer.invoke("boards:listItems", boardId) as Promise<
+				BoardListItem[]
+			>,
+		copyItem: (boardId: string, seq: number) =>
+			ipcRenderer.invoke("boards:copyItem", boardId, seq) as Promise<void>,
 	},
 	window: {
 		center: () => ipcRenderer.invoke("window:center") as Promise<void>,
@@ -43,6 +1812,123 @@ contextBridge.exposeInMainWorld("electronAPI", {
 		isVisible: () => ipcRenderer.invoke("window:isVisible") as Promise<boolean>,
 		hideAndPaste: () =>
 			ipcRenderer.invoke("window:hideAndPaste") as Promise<void>,
+		isPinned: () => ipcRenderer.invoke("window:isPinned") as Promise<boolean>,
+		setPinned: (pinned: boolean) =>
+			ipcRenderer.invoke("window:setPinned", pinned) as Promise<boolean>,
+	},
+	picker: {
+		show: () => ipcRenderer.invoke("picker:show") as Promise<void>,
+		hide: () => ipcRenderer.invoke("picker:hide") as Promise<void>,
+		toggle: () => ipcRenderer.invoke("picker:toggle") as Promise<void>,
+		isVisible: () => ipcRenderer.invoke("picker:isVisible") as Promise<boolean>,
+	},
+	events: {
+		onHistoryChanged: (callback: () => void) => {
+			const listener = () => callback();
+			ipcRenderer.on("history:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("history:changed", listener);
+			};
+		},
+		onHistoryEvent: (callback: (event: HistoryEvent) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				event: HistoryEvent,
+			) => callback(event);
+			ipcRenderer.on("history:event", listener);
+			return () => {
+				ipcRenderer.removeListener("history:event", listener);
+			};
+		},
+		onLockChanged: (
+			callback: (locked: boolean, reason: LockChangeReason) => void,
+		) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				locked: boolean,
+				reason: LockChangeReason,
+			) => callback(locked, reason);
+			ipcRenderer.on("lock:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("lock:changed", listener);
+			};
+		},
+		onCaptureChanged: (callback: (status: CapturePauseStatus) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				status: CapturePauseStatus,
+			) => callback(status);
+			ipcRenderer.on("capture:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("capture:changed", listener);
+			};
+		},
+		onReadOnlyChanged: (callback: (status: ReadOnlyStatus) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				status: ReadOnlyStatus,
+			) => callback(status);
+			ipcRenderer.on("readOnly:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("readOnly:changed", listener);
+			};
+		},
+		onQueueChanged: (callback: (itemIds: number[]) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				itemIds: number[],
+			) => callback(itemIds);
+			ipcRenderer.on("queue:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("queue:changed", listener);
+			};
+		},
+		onSettingsChanged: (callback: (status: SettingsFileStatus) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				status: SettingsFileStatus,
+			) => callback(status);
+			ipcRenderer.on("settings:changed", listener);
+			return () => {
+				ipcRenderer.removeListener("settings:changed", listener);
+			};
+		},
+		onUpdaterStatus: (callback: (status: UpdaterStatus) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				status: UpdaterStatus,
+			) => callback(status);
+			ipcRenderer.on("updater:status", listener);
+			return () => {
+				ipcRenderer.removeListener("updater:status", listener);
+			};
+		},
+		onShortcutsStatus: (callback: (status: ShortcutStatus) => void) => {
+			const listener = (
+				_event: Electron.IpcRendererEvent,
+				status: ShortcutStatus,
+			) => callback(status);
+			ipcRenderer.on("shortcuts:status", listener);
+			return () => {
+				ipcRenderer.removeListener("shortcuts:status", listener);
+			};
+		},
+		onSearchRequested: (callback: (query: string) => void) => {
+			const listener = (_event: Electron.IpcRendererEvent, query: string) =>
+				callback(query);
+			ipcRenderer.on("search:requested", listener);
+			return () => {
+				ipcRenderer.removeListener("search:requested", listener);
+			};
+		},
+		onBoardsUpdated: (callback: (boardId: string) => void) => {
+			const listener = (_event: Electron.IpcRendererEvent, boardId: string) =>
+				callback(boardId);
+			ipcRenderer.on("boards:updated", listener);
+			return () => {
+				ipcRenderer.removeListener("boards:updated", listener);
+			};
+		},
 	},
 	app: {
 		quit: () => ipcRenderer.invoke("app:quit") as Promise<void>,
@@ -53,5 +1939,9 @@ contextBridge.exposeInMainWorld("electronAPI", {
 				success: boolean;
 				error?: string;
 			}>,
+		getStartMinimized: () =>
+			ipcRenderer.invoke("app:getStartMinimized") as Promise<boolean>,
+		setStartMinimized: (enabled: boolean) =>
+			ipcRenderer.invoke("app:setStartMinimized", enabled) as Promise<boolean>,
 	},
 });
@@ -2,9 +2,12 @@ import { useCallback, useEffect, useRef, useState } from "react";
 import { ErrorBanner, Footer, SearchBar } from "./components/common";
 import { HistoryList } from "./components/history";
 import { useClipboardMonitor } from "./hooks/queries";
+import { useCapturePause } from "./hooks/useCapturePause";
 import { useHistoryActions } from "./hooks/useHistoryActions";
 import { useHistorySearch } from "./hooks/useHistorySearch";
 import { useKeyboardNavigation } from "./hooks/useKeyboardNavigation";
+import { usePasteQueue } from "./hooks/usePasteQueue";
+import { useSearchRequests } from "./hooks/useSearchRequests";
 import { useWindowVisibility } from "./hooks/useWindowVisibility";
 import type { HistoryItem } from "./lib/db";
 import { tryCatchAsync } from "./lib/fp";
@@ -19,6 +22,9 @@ export function App() {
 
 	const [isSettingsMenuOpen, setIsSettingsMenuOpen] = useState(false);
 	const [launchAtLogin, setLaunchAtLogin] = useState(true);
+	const { capturePaused, captureResumesAt, toggleCapturePaused } =
+		useCapturePause();
+	const { queue, toggleQueued, clearQueue } = usePasteQueue();
 	const searchInputRef = useRef<HTMLInputElement>(null);
 	const settingsMenuRef = useRef<HTMLDivElement>(null);
 	const itemRefs = useRef<(HTMLDivElement | null)[]>([]);
@@ -42,6 +48,8 @@ export function App() {
 		setSearchQuery,
 		favoritesOnly,
 		setFavoritesOnly,
+		searchMode,
+		setSearchMode,
 		filteredHistory,
 		isLoadingMore,
 		hasMore,
@@ -50,6 +58,9 @@ export function App() {
 		loadMore,
 	} = useHistorySearch();
 
+	// Deep links like clipman://search?q= open the window on a search
+	useSearchRequests(setSearchQuery);
+
 	// Callback to hide window and reset search state (used for Escape key)
 	const hideWindow = useCallback(async () => {
 		await tryCatchAsync(
@@ -84,18 +95,44 @@ export function App() {
 		setSearchQuery("");
 	}, [setIsVisible, setSearchQuery]);
 
-	// Stable callback for copying item and hiding window (used by keyboard navigation)
+	// Stable callback for pasting item into the previous app (used by keyboard navigation)
 	const handleEnterKey = useCallback(
 		async (item: HistoryItem) => {
-			if (window.electronAPI) {
-				await window.electronAPI.clipboard.write({
-					text: item.content,
-					rtf: item.rtf || undefined,
-				});
-			}
-			await hideWindowAndPaste();
+			await tryCatchAsync(
+				async () => {
+					if (window.electronAPI) {
+						await window.electronAPI.clipboard.pasteItem(item.id);
+					}
+				},
+				(error) => {
+					console.error("Failed to paste item:", error);
+				},
+			);
+			// Always reset UI state to prevent inconsistency
+			setIsVisible(false);
+			setSearchQuery("");
 		},
-		[hideWindowAndPaste],
+		[setIsVisible, setSearchQuery],
+	);
+
+	// Stable callback for typing item into the previous app (Shift+Enter)
+	const handleShiftEnterKey = useCallback(
+		async (item: HistoryItem) => {
+			await tryCatchAsync(
+				async () => {
+					if (window.electronAPI) {
+						await window.electronAPI.clipboard.typeItem(item.id);
+					}
+				},
+				(error) => {
+					console.error("Failed to type out item:", error);
+				},
+			);
+			// Always reset UI state to prevent inconsistency
+			setIsVisible(false);
+			setSearchQuery("");
+		},
+		[setIsVisible, setSearchQuery],
 	);
 
 	// Stable callback for scrolling to item
@@ -112,6 +149,7 @@ export function App() {
 		filteredHistory,
 		onEscape: hideWindow,
 		onEnter: handleEnterKey,
+		onShiftEnter: handleShiftEnterKey,
 		onScrollToIndex: handleScrollToIndex,
 	});
 
@@ -119,12 +157,14 @@ export function App() {
 		setSelectedIndex(0);
 	}, [setSelectedIndex]);
 
-	// History actions hook manages item operations (copy, delete, favorite, clear)
+	// History actions hook manages item operations (copy, delete, favorite, pin, clear)
 	const {
 		error: actionError,
 		setError: setActionError,
 		handleItemClick,
+		handleCopyQrPayload,
 		handleToggleFavorite,
+		handleTogglePin,
 		handleDeleteItem,
 		handleClearAll,
 	} = useHistoryActions({
@@ -229,6 +269,45 @@ export function App() {
 		}
 	}, [launchAtLogin, setActionError]);
 
+	/**
+	 * Handles pausing or resuming clipboard capture
+	 */
+	const handleCapturePauseToggle = useCallback(async () => {
+		await tryCatchAsync(toggleCapturePaused, (error) => {
+			console.error("Failed to toggle capture pause:", error);
+			setActionError("Couldn't pause or resume capture");
+		});
+	}, [toggleCapturePaused, setActionError]);
+
+	/**
+	 * Handles adding an item to, or removing it from, the paste queue
+	 */
+	const handleToggleQueued = useCallback(
+		async (e: React.MouseEvent, itemId: number) => {
+			e.stopPropagation();
+			await tryCatchAsync(
+				() => toggleQueued(itemId),
+				(error) => {
+					console.error("Failed to update paste queue:", error);
+					setActionError(
+						error instanceof Error ? error.message : "Couldn't update queue",
+					);
+				},
+			);
+		},
+		[toggleQueued, setActionError],
+	);
+
+	/**
+	 * Handles emptying the paste queue
+	 */
+	const handleClearQueue = useCallback(async () => {
+		await tryCatchAsync(clearQueue, (error) => {
+			console.error("Failed to clear paste queue:", error);
+			setActionError("Couldn't clear the paste queue");
+		});
+	}, [clearQueue, setActionError]);
+
 	/**
 	 * Handles clear all from settings menu (closes menu first)
 	 */
@@ -259,6 +338,8 @@ export function App() {
 				onSearchChange={setSearchQuery}
 				favoritesOnly={favoritesOnly}
 				onFavoritesToggle={() => setFavoritesOnly(!favoritesOnly)}
+				searchMode={searchMode}
+				onSearchModeChange={setSearchMode}
 			/>
 
 			{/* History List */}
@@ -272,7 +353,11 @@ export function App() {
 				isLoadingMore={isLoadingMore}
 				onItemClick={handleItemClick}
 				onToggleFavorite={handleToggleFavorite}
+				onTogglePin={handleTogglePin}
 				onDelete={handleDeleteItem}
+				queue={queue}
+				onToggleQueued={handleToggleQueued}
+				onCopyQrPayload={handleCopyQrPayload}
 				onLoadMore={handleLoadMore}
 				onJumpToTop={handleJumpToTop}
 			/>
@@ -283,6 +368,11 @@ export function App() {
 				onSettingsToggle={handleSettingsToggle}
 				launchAtLogin={launchAtLogin}
 				onLaunchAtLoginToggle={handleLaunchAtLoginToggle}
+				capturePaused={capturePaused}
+				captureResumesAt={captureResumesAt}
+				onCapturePauseToggle={handleCapturePauseToggle}
+				pasteQueueLength={queue.length}
+				onPasteQueueClear={handleClearQueue}
 				onClearAll={handleSettingsClearAll}
 				onQuit={handleQuit}
 				settingsMenuRef={settingsMenuRef}
@@ -0,0 +1,210 @@
+import { useCallback, useEffect, useRef } from "react";
+import { ErrorBanner, SearchBar } from "./components/common";
+import { HistoryList } from "./components/history";
+import { useClipboardMonitor } from "./hooks/queries";
+import { useHistoryActions } from "./hooks/useHistoryActions";
+import { useHistorySearch } from "./hooks/useHistorySearch";
+import { useKeyboardNavigation } from "./hooks/useKeyboardNavigation";
+import { useWindowVisibility } from "./hooks/useWindowVisibility";
+import type { HistoryItem } from "./lib/db";
+import { tryCatchAsync } from "./lib/fp";
+
+/**
+ * Quick picker: a launcher-style palette shown in its own window
+ * Search and pick an item to paste; settings and bulk actions stay in the
+ * history window
+ */
+export function PickerApp() {
+	// Keep history in sync with background capture
+	useClipboardMonitor();
+
+	const searchInputRef = useRef<HTMLInputElement>(null);
+	const itemRefs = useRef<(HTMLDivElement | null)[]>([]);
+	const listContainerRef = useRef<HTMLDivElement>(null);
+
+	// Ref to hold the refresh callback (set after useHistorySearch is called)
+	const refreshOnVisibleRef = useRef<(() => Promise<void>) | null>(null);
+
+	const { isVisible, setIsVisible } = useWindowVisibility({
+		searchInputRef,
+		window: "picker",
+		onBecomeVisible: () => {
+			refreshOnVisibleRef.current?.();
+		},
+	});
+
+	const {
+		searchQuery,
+		setSearchQuery,
+		favoritesOnly,
+		setFavoritesOnly,
+		searchMode,
+		setSearchMode,
+		filteredHistory,
+		isLoadingMore,
+		hasMore,
+		searchError,
+		refetchHistory,
+		loadMore,
+	} = useHistorySearch();
+
+	// Resets UI state once the picker has been hidden by the main process
+	const resetAfterHide = useCallback(() => {
+		setIsVisible(false);
+		setSearchQuery("");
+	}, [setIsVisible, setSearchQuery]);
+
+	// Callback to hide the picker without pasting (used for Escape key)
+	const hidePicker = useCallback(async () => {
+		await tryCatchAsync(
+			async () => {
+				if (window.electronAPI) {
+					await window.electronAPI.picker.hide();
+				}
+			},
+			(error) => {
+				console.error("Failed to hide picker:", error);
+			},
+		);
+		resetAfterHide();
+	}, [resetAfterHide]);
+
+	// Callback to hide the picker and paste (used after item selection)
+	const hidePickerAndPaste = useCallback(async () => {
+		await tryCatchAsync(
+			async () => {
+				if (window.electronAPI) {
+					await window.electronAPI.window.hideAndPaste();
+				}
+			},
+			(error) => {
+				console.error("Failed to hide picker and paste:", error);
+			},
+		);
+		resetAfterHide();
+	}, [resetAfterHide]);
+
+	const handleEnterKey = useCallback(
+		async (item: HistoryItem) => {
+			await tryCatchAsync(
+				async () => {
+					if (window.electronAPI) {
+						await window.electronAPI.clipboard.pasteItem(item.id);
+					}
+				},
+				(error) => {
+					console.error("Failed to paste item:", error);
+				},
+			);
+			resetAfterHide();
+		},
+		[resetAfterHide],
+	);
+
+	const handleShiftEnterKey = useCallback(
+		async (item: HistoryItem) => {
+			await tryCatchAsync(
+				async () => {
+					if (window.electronAPI) {
+						await window.electronAPI.clipboard.typeItem(item.id);
+					}
+				},
+				(error) => {
+					console.error("Failed to type out item:", error);
+				},
+			);
+			resetAfterHide();
+		},
+		[resetAfterHide],
+	);
+
+	const handleScrollToIndex = useCallback((index: number) => {
+		itemRefs.current[index]?.scrollIntoView({
+			block: "nearest",
+			behavior: "smooth",
+		});
+	}, []);
+
+	const { selectedIndex, setSelectedIndex } = useKeyboardNavigation({
+		isVisible,
+		filteredHistory,
+		onEscape: hidePicker,
+		onEnter: handleEnterKey,
+		onShiftEnter: handleShiftEnterKey,
+		onScrollToIndex: handleScrollToIndex,
+	});
+
+	const {
+		error: actionError,
+		setError: setActionError,
+		handleItemClick,
+		handleToggleFavorite,
+		handleTogglePin,
+		handleDeleteItem,
+	} = useHistoryActions({
+		filteredHistory,
+		selectedIndex,
+		setSelectedIndex,
+		onHideWindow: hidePickerAndPaste,
+	});
+
+	const error = searchError || actionError;
+	const clearError = useCallback(() => {
+		setActionError(null);
+	}, [setActionError]);
+
+	refreshOnVisibleRef.current = useCallback(async () => {
+		listContainerRef.current?.scrollTo({ top: 0 });
+		await refetchHistory();
+		setSelectedIndex(0);
+	}, [refetchHistory, setSelectedIndex]);
+
+	// Reset selected index when search or filter changes
+	const searchFilterKey = `${searchQuery}-${favoritesOnly}`;
+	// biome-ignore lint/correctness/useExhaustiveDependencies: searchFilterKey is intentionally used to trigger reset when search/filter changes
+	useEffect(() => {
+		setSelectedIndex(0);
+	}, [searchFilterKey, setSelectedIndex]);
+
+	const handleLoadMore = useCallback(async () => {
+		try {
+			setActionError(null);
+			await loadMore();
+		} catch (err) {
+			const message = err instanceof Error ? err.message : String(err);
+			setActionError(`Failed to load more items: ${message}`);
+			console.error("Failed to load more items", err);
+		}
+	}, [setActionError, loadMore]);
+
+	return (
+		<div className="flex flex-col h-screen bg-gray-900 text-white overflow-hidden rounded-xl border border-gray-700">
+			{error && <ErrorBanner message={error} onDismiss={clearError} />}
+
+			<SearchBar
+				inputRef={searchInputRef}
+				searchQuery={searchQuery}
+				onSearchChange={setSearchQuery}
+				favoritesOnly={favoritesOnly}
+				onFavoritesToggle={() => setFavoritesOnly(!favoritesOnly)}
+				searchMode={searchMode}
+				onSearchModeChange={setSearchMode}
+			/>
+
+			<HistoryList
+				items={filteredHistory}
+				searchQuery={searchQuery}
+				selectedIndex={selectedIndex}
+				itemRefs={itemRefs}
+				containerRef={listContainerRef}
+				hasMore={hasMore}
+				isLoadingMore={isLoadingMore}
+				onItemClick={handleItemClick}
+				onToggleFavorite={handleToggleFavorite}
+				onTogglePin={handleTogglePin}
+				onDelete={handleDeleteItem}
+				onLoadMore={handleLoadMore}
+			/>
+		</div>
+	);
+}
@@ -8,16 +8,26 @@ describe("Footer", () => {
 	const mockHandlers = {
 		onSettingsToggle: vi.fn(),
 		onLaunchAtLoginToggle: vi.fn(),
+		onCapturePauseToggle: vi.fn(),
+		onPasteQueueClear: vi.fn(),
 		onClearAll: vi.fn(),
 		onQuit: vi.fn(),
 	} as const satisfies Omit<
 		ComponentProps<typeof Footer>,
-		"isSettingsMenuOpen" | "settingsMenuRef" | "launchAtLogin"
+		| "isSettingsMenuOpen"
+		| "settingsMenuRef"
+		| "launchAtLogin"
+		| "capturePaused"
+		| "captureResumesAt"
+		| "pasteQueueLength"
 	>;
 
 	const defaultProps = {
 		isSettingsMenuOpen: false,
 		launchAtLogin: true,
+		capturePaused: false,
+		captureResumesAt: null,
+		pasteQueueLength: 0,
 		settingsMenuRef: mockSettingsMenuRef,
 		...mockHandlers,
 	} as const satisfies ComponentProps<typeof Footer>;
@@ -34,6 +44,38 @@ describe("Footer", () => {
 		expect(screen.getByText(/Esc Close/)).toBeInTheDocument();
 	});
 
+	it("shows a badge while capture is paused", () => {
+		const { rerender } = render(<Footer {...defaultProps} />);
+		expect(screen.queryByText("Capture paused")).not.toBeInTheDocument();
+
+		rerender(<Footer {...defaultProps} capturePaused={true} />);
+
+		expect(screen.getByText("Capture paused")).toBeInTheDocument();
+	});
+
+	it("shows the paste queue length with a clear button", () => {
+		const { rerender } = render(<Footer {...defaultProps} />);
+		expect(screen.queryByText(/queued/)).not.toBeInTheDocument();
+
+		rerender(<Footer {...defaultProps} pasteQueueLength={3} />);
+		fireEvent.click(screen.getByRole("button", { name: "Clear paste queue" }));
+
+		expect(screen.getByText("3 queued")).toBeInTheDocument();
+		expect(mockHandlers.onPasteQueueClear).toHaveBeenCalledTimes(1);
+	});
+
+	it("shows when a snooze ends", () => {
+		render(
+			<Footer
+				{...defaultProps}
+				capturePaused={true}
+				captureResumesAt={Date.now() + 15 * 60 * 1000}
+			/>,
+		);
+
+		expect(screen.getByText(/^Capture paused until /)).toBeInTheDocument();
+	});
+
 	it("renders settings button", () => {
 		render(<Footer {...defaultProps} />);
 
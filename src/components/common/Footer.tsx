@@ -1,4 +1,6 @@
+import { ListOrdered, Pause, X } from "lucide-react";
 import type { RefObject } from "react";
+import { formatTime } from "../../lib/utils";
 import { SettingsMenu } from "./SettingsMenu";
 
 interface FooterProps {
@@ -10,6 +12,16 @@ interface FooterProps {
 	launchAtLogin: boolean;
 	/** Callback when Launch at login is toggled */
 	onLaunchAtLoginToggle: () => void;
+	/** Whether clipboard capture is paused */
+	capturePaused: boolean;
+	/** When a snooze ends (epoch ms), or null */
+	captureResumesAt: number | null;
+	/** Callback when Pause capture is toggled */
+	onCapturePauseToggle: () => void;
+	/** Number of items waiting in the paste queue */
+	pasteQueueLength: number;
+	/** Callback to empty the paste queue */
+	onPasteQueueClear: () => void;
 	/** Callback when Clear All is clicked */
 	onClearAll: () => void;
 	/** Callback when Quit is clicked */
@@ -21,12 +33,20 @@ interface FooterProps {
 /**
  * Footer bar with keyboard hints and settings menu
  * Displays navigation hints and provides access to settings
+ * Shows a "Capture paused" badge (with the resume time while snoozed) while
+ * nothing is being recorded, and a "N queued" badge while the paste queue
+ * holds items
  */
 export function Footer({
 	isSettingsMenuOpen,
 	onSettingsToggle,
 	launchAtLogin,
 	onLaunchAtLoginToggle,
+	capturePaused,
+	captureResumesAt,
+	onCapturePauseToggle,
+	pasteQueueLength,
+	onPasteQueueClear,
 	onClearAll,
 	onQuit,
 	settingsMenuRef,
@@ -37,12 +57,37 @@ export function Footer({
 				<div className="flex items-center gap-2 text-xs text-gray-400">
 					<span>↑↓ Navigate • Enter Copy • Esc Close</span>
 					<span className="hidden sm:inline">Cmd+Shift+V Toggle</span>
+					{capturePaused && (
+						<span className="flex items-center gap-1 text-yellow-300">
+							<Pause className="w-3 h-3" aria-hidden="true" />
+							{captureResumesAt === null
+								? "Capture paused"
+								: `Capture paused until ${formatTime(captureResumesAt)}`}
+						</span>
+					)}
+					{pasteQueueLength > 0 && (
+						<span className="flex items-center gap-1 text-blue-300">
+							<ListOrdered className="w-3 h-3" aria-hidden="true" />
+							{pasteQueueLength} queued
+							<button
+								type="button"
+								onClick={onPasteQueueClear}
+								className="p-0.5 rounded hover:bg-gray-700"
+								aria-label="Clear paste queue"
+								title="Clear paste queue"
+							>
+								<X className="w-3 h-3" aria-hidden="true" />
+							</button>
+						</span>
+					)}
 				</div>
 				<SettingsMenu
 					isOpen={isSettingsMenuOpen}
 					onToggle={onSettingsToggle}
 					launchAtLogin={launchAtLogin}
 					onLaunchAtLoginToggle={onLaunchAtLoginToggle}
+					capturePaused={capturePaused}
+					onCapturePauseToggle={onCapturePauseToggle}
 					onClearAll={onClearAll}
 					onQuit={onQuit}
 					menuRef={settingsMenuRef}
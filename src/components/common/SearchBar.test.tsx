@@ -8,12 +8,14 @@ describe("SearchBar", () => {
 	const mockHandlers = {
 		onSearchChange: vi.fn(),
 		onFavoritesToggle: vi.fn(),
+		onSearchModeChange: vi.fn(),
 	};
 
 	const defaultProps = {
 		inputRef: mockInputRef,
 		searchQuery: "",
 		favoritesOnly: false,
+		searchMode: "text" as const,
 		...mockHandlers,
 	};
 
@@ -123,4 +125,14 @@ describe("SearchBar", () => {
 		mockInputRef.current?.focus();
 		expect(document.activeElement).toBe(input);
 	});
+
+	it("calls onSearchModeChange when a search mode is selected", () => {
+		render(<SearchBar {...defaultProps} />);
+
+		fireEvent.change(screen.getByRole("combobox", { name: "Search mode" }), {
+			target: { value: "fuzzy" },
+		});
+
+		expect(mockHandlers.onSearchModeChange).toHaveBeenCalledWith("fuzzy");
+	});
 });
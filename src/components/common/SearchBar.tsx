@@ -1,5 +1,6 @@
 import { Search, Star, X } from "lucide-react";
 import type { RefObject } from "react";
+import type { SearchMode } from "../../lib/queryKeys";
 
 interface SearchBarProps {
 	/** Ref to the search input element for focus management */
@@ -12,10 +13,24 @@ interface SearchBarProps {
 	favoritesOnly: boolean;
 	/** Callback to toggle favorites filter */
 	onFavoritesToggle: () => void;
+	/** How the search query is matched */
+	searchMode: SearchMode;
+	/** Callback when the search mode changes */
+	onSearchModeChange: (mode: SearchMode) => void;
 }
 
 /**
- * Search bar with input field, clear button, and favorites filter toggle
+ * Labels for the search mode selector, in display order
+ */
+const SEARCH_MODE_OPTIONS: { value: SearchMode; label: string }[] = [
+	{ value: "text", label: "Text" },
+	{ value: "fuzzy", label: "Fuzzy" },
+	{ value: "regex", label: "Regex" },
+];
+
+/**
+ * Search bar with input field, clear button, search mode selector, and
+ * favorites filter toggle
  * Provides search and filtering functionality for clipboard history
  */
 export function SearchBar({
@@ -24,6 +39,8 @@ export function SearchBar({
 	onSearchChange,
 	favoritesOnly,
 	onFavoritesToggle,
+	searchMode,
+	onSearchModeChange,
 }: SearchBarProps) {
 	return (
 		<div className="sticky top-0 z-10 bg-gray-800 border-b border-gray-700 p-3">
@@ -49,6 +66,20 @@ export function SearchBar({
 						</button>
 					)}
 				</div>
+				{/* Search Mode Selector */}
+				<select
+					value={searchMode}
+					onChange={(e) => onSearchModeChange(e.target.value as SearchMode)}
+					className="py-2 px-2 bg-gray-700 text-gray-200 text-sm rounded-lg border border-gray-600 focus:outline-none focus:ring-2 focus:ring-blue-500"
+					aria-label="Search mode"
+					title="Search mode"
+				>
+					{SEARCH_MODE_OPTIONS.map(({ value, label }) => (
+						<option key={value} value={value}>
+							{label}
+						</option>
+					))}
+				</select>
 				{/* Favorites Filter Toggle */}
 				<button
 					type="button"
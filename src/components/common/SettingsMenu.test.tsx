@@ -8,6 +8,7 @@ describe("SettingsMenu", () => {
 	const handlers = {
 		onToggle: vi.fn(),
 		onLaunchAtLoginToggle: vi.fn(),
+		onCapturePauseToggle: vi.fn(),
 		onClearAll: vi.fn(),
 		onQuit: vi.fn(),
 	};
@@ -17,6 +18,7 @@ describe("SettingsMenu", () => {
 			<SettingsMenu
 				isOpen={true}
 				launchAtLogin={true}
+				capturePaused={false}
 				menuRef={menuRef}
 				{...handlers}
 			/>,
@@ -33,6 +35,7 @@ describe("SettingsMenu", () => {
 			<SettingsMenu
 				isOpen={true}
 				launchAtLogin={false}
+				capturePaused={false}
 				menuRef={menuRef}
 				{...handlers}
 			/>,
@@ -44,4 +47,24 @@ describe("SettingsMenu", () => {
 
 		expect(handlers.onLaunchAtLoginToggle).toHaveBeenCalledTimes(1);
 	});
+
+	it("calls onCapturePauseToggle when pause capture is clicked", () => {
+		render(
+			<SettingsMenu
+				isOpen={true}
+				launchAtLogin={false}
+				capturePaused={true}
+				menuRef={menuRef}
+				{...handlers}
+			/>,
+		);
+
+		const pauseItem = screen.getByRole("menuitemcheckbox", {
+			name: "Pause capture",
+		});
+		expect(pauseItem).toHaveAttribute("aria-checked", "true");
+		fireEvent.click(pauseItem);
+
+		expect(handlers.onCapturePauseToggle).toHaveBeenCalledTimes(1);
+	});
 });
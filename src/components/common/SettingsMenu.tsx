@@ -10,6 +10,10 @@ interface SettingsMenuProps {
 	launchAtLogin: boolean;
 	/** Callback when Launch at login is toggled */
 	onLaunchAtLoginToggle: () => void;
+	/** Whether clipboard capture is paused */
+	capturePaused: boolean;
+	/** Callback when Pause capture is toggled */
+	onCapturePauseToggle: () => void;
 	/** Callback when Clear All is clicked */
 	onClearAll: () => void;
 	/** Callback when Quit is clicked */
@@ -19,7 +23,8 @@ interface SettingsMenuProps {
 }
 
 /**
- * Settings dropdown menu with launch at login, Clear All, and Quit options
+ * Settings dropdown menu with launch at login, pause capture, Clear All,
+ * and Quit options
  * Positioned in the bottom right corner of the footer
  */
 export function SettingsMenu({
@@ -27,6 +32,8 @@ export function SettingsMenu({
 	onToggle,
 	launchAtLogin,
 	onLaunchAtLoginToggle,
+	capturePaused,
+	onCapturePauseToggle,
 	onClearAll,
 	onQuit,
 	menuRef,
@@ -63,6 +70,20 @@ export function SettingsMenu({
 						</span>
 						Launch at login
 					</button>
+					<button
+						type="button"
+						onClick={onCapturePauseToggle}
+						className="w-full px-4 py-2 text-left text-sm text-white hover:bg-gray-600 transition-colors flex items-center gap-2"
+						role="menuitemcheckbox"
+						aria-checked={capturePaused}
+					>
+						<span className="w-4 h-4 flex items-center justify-center">
+							{capturePaused && (
+								<Check className="w-4 h-4" aria-hidden="true" />
+							)}
+						</span>
+						Pause capture
+					</button>
 					<button
 						type="button"
 						onClick={onClearAll}
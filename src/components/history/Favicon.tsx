@@ -0,0 +1,25 @@
+import { Globe } from "lucide-react";
+import { useFaviconQuery } from "../../hooks/queries";
+
+interface FaviconProps {
+	/** Host name of the linked site */
+	domain: string;
+}
+
+/**
+ * Icon of the site a URL item links to
+ * Falls back to a globe while loading or if the site has no icon
+ */
+export function Favicon({ domain }: FaviconProps) {
+	const { data: src } = useFaviconQuery(domain);
+
+	return src ? (
+		<img
+			src={src}
+			alt=""
+			className="w-4 h-4 mt-0.5 shrink-0 rounded-sm object-contain"
+		/>
+	) : (
+		<Globe className="w-4 h-4 mt-0.5 shrink-0 opacity-60" aria-hidden="true" />
+	);
+}
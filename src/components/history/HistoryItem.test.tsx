@@ -1,8 +1,11 @@
-import { render, screen } from "@testing-library/react";
+import { QueryClientProvider } from "@tanstack/react-query";
+import { render, screen, waitFor } from "@testing-library/react";
 import userEvent from "@testing-library/user-event";
 import type { ComponentProps } from "react";
 import { afterEach, describe, expect, it, vi } from "vitest";
 import { createMockHistoryItem } from "../../test/mocks/history";
+import { getMockElectronAPI } from "../../test/setup";
+import { createTestQueryClient } from "../../test/utils";
 import { HistoryItem } from "./HistoryItem";
 
 describe("HistoryItem", () => {
@@ -17,6 +20,7 @@ describe("HistoryItem", () => {
 	const mockHandlers = {
 		onItemClick: vi.fn(),
 		onToggleFavorite: vi.fn(),
+		onTogglePin: vi.fn(),
 		onDelete: vi.fn(),
 	} as const satisfies Omit<
 		ComponentProps<typeof HistoryItem>,
@@ -129,6 +133,116 @@ describe("HistoryItem", () => {
 		);
 	});
 
+	it("calls onTogglePin when pin button is clicked", async () => {
+		const user = userEvent.setup();
+		render(
+			<HistoryItem item={mockItem} isSelected={false} {...mockHandlers} />,
+		);
+
+		await user.click(screen.getByRole("button", { name: "Pin to top" }));
+
+		expect(mockHandlers.onTogglePin).toHaveBeenCalledWith(
+			expect.any(Object),
+			mockItem.id,
+		);
+	});
+
+	it("marks pinned items", () => {
+		const pinnedItem = createMockHistoryItem({ pinned: 1 });
+
+		render(
+			<HistoryItem item={pinnedItem} isSelected={false} {...mockHandlers} />,
+		);
+
+		expect(screen.getByRole("img", { name: "Pinned" })).toBeInTheDocument();
+		expect(
+			screen.getByRole("button", { name: "Unpin item" }),
+		).toBeInTheDocument();
+	});
+
+	it("warns about items that look like a secret", () => {
+		const flagged = createMockHistoryItem({ secret_match: "AWS access key" });
+
+		render(<HistoryItem item={flagged} isSelected={false} {...mockHandlers} />);
+
+		expect(
+			screen.getByRole("img", { name: "Looks like a secret: AWS access key" }),
+		).toBeInTheDocument();
+	});
+
+	it("shows the title of a linked page above its URL", () => {
+		const link = createMockHistoryItem({
+			content: "https://github.com/org/repo/pull/12",
+			content_kind: "url",
+			link_title: "Fix capture on Wayland by org · Pull Request #12",
+		});
+
+		render(
+			<QueryClientProvider client={createTestQueryClient()}>
+				<HistoryItem item={link} isSelected={false} {...mockHandlers} />
+			</QueryClientProvider>,
+		);
+
+		expect(
+			screen.getByText("Fix capture on Wayland by org · Pull Request #12"),
+		).toBeInTheDocument();
+		expect(
+			screen.getByText("https://github.com/org/repo/pull/12"),
+		).toBeInTheDocument();
+	});
+
+	it("shows the icon of a linked site", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.favicons.get.mockResolvedValue("data:image/png;base64,AA==");
+		const link = createMockHistoryItem({
+			content: "https://github.com/org/repo",
+			content_kind: "url",
+		});
+
+		const { container } = render(
+			<QueryClientProvider client={createTestQueryClient()}>
+				<HistoryItem item={link} isSelected={false} {...mockHandlers} />
+			</QueryClientProvider>,
+		);
+
+		await waitFor(() =>
+			expect(container.querySelector("img")).toHaveAttribute(
+				"src",
+				"data:image/png;base64,AA==",
+			),
+		);
+		expect(mockApi.favicons.get).toHaveBeenCalledWith("github.com");
+	});
+
+	it("shows the item's note", () => {
+		const annotated = createMockHistoryItem({
+			note: "Staging API key - rotate monthly",
+		});
+
+		render(
+			<HistoryItem item={annotated} isSelected={false} {...mockHandlers} />,
+		);
+
+		expect(
+			screen.getByText("Staging API key - rotate monthly"),
+		).toBeInTheDocument();
+	});
+
+	it("shows the app the item was copied from", () => {
+		const copied = createMockHistoryItem({
+			source_app: "Safari",
+			source_title: "Release notes",
+			source_url: "https://example.com/notes",
+		});
+
+		render(<HistoryItem item={copied} isSelected={false} {...mockHandlers} />);
+
+		expect(screen.getByText("· Safari")).toHaveAttribute(
+			"title",
+			"Release notes\nhttps://example.com/notes",
+		);
+	});
+
 	it("calls onDelete when trash button is clicked", async () => {
 		const user = userEvent.setup();
 		render(
@@ -224,4 +338,85 @@ describe("HistoryItem", () => {
 
 		expect(mockHandlers.onItemClick).toHaveBeenCalledWith(mockItem);
 	});
+
+	it("renders copied files by name", () => {
+		const filesItem = createMockHistoryItem({
+			type: "files",
+			content: "/home/me/report.pdf\n/home/me/data.csv",
+		});
+
+		render(
+			<HistoryItem item={filesItem} isSelected={false} {...mockHandlers} />,
+		);
+
+		expect(screen.getByText("report.pdf + 1 more")).toBeInTheDocument();
+	});
+
+	it("renders a thumbnail for image items", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.db.getThumbnail.mockResolvedValue("data:image/jpeg;base64,AA==");
+		const imageItem = createMockHistoryItem({
+			id: 7,
+			content: "",
+			type: "image",
+			image_width: 640,
+			image_height: 480,
+		});
+
+		render(
+			<QueryClientProvider client={createTestQueryClient()}>
+				<HistoryItem item={imageItem} isSelected={false} {...mockHandlers} />
+			</QueryClientProvider>,
+		);
+
+		const image = await screen.findByRole("img", {
+			name: "Copied image (640 × 480)",
+		});
+		expect(image).toHaveAttribute("src", "data:image/jpeg;base64,AA==");
+		expect(mockApi.db.getThumbnail).toHaveBeenCalledWith(7);
+	});
+
+	it("offers to copy a decoded QR code", async () => {
+		const user = userEvent.setup();
+		const onCopyQrPayload = vi.fn();
+		const imageItem = createMockHistoryItem({
+			id: 9,
+			content: "",
+			type: "image",
+			qr_payload: "https://example.com",
+		});
+
+		render(
+			<QueryClientProvider client={createTestQueryClient()}>
+				<HistoryItem
+					item={imageItem}
+					isSelected={false}
+					{...mockHandlers}
+					onCopyQrPayload={onCopyQrPayload}
+				/>
+			</QueryClientProvider>,
+		);
+		await user.click(
+			screen.getByRole("button", { name: "Copy decoded QR code text" }),
+		);
+
+		expect(onCopyQrPayload).toHaveBeenCalledWith(expect.anything(), 9);
+	});
+
+	it("hides the QR code action for images without one", () => {
+		render(
+			<QueryClientProvider client={createTestQueryClient()}>
+				<HistoryItem
+					item={createMockHistoryItem({ type: "image", qr_payload: "" })}
+					isSelected={false}
+					{...mockHandlers}
+					onCopyQrPayload={vi.fn()}
+				/>
+			</QueryClientProvider>,
+		);
+
+		expect(
+			screen.queryByRole("button", { name: "Copy decoded QR code text" }),
+		).not.toBeInTheDocument();
+	});
 });
@@ -1,6 +1,23 @@
-import { Copy, Star, Trash2 } from "lucide-react";
+import {
+	Copy,
+	Files,
+	ListMinus,
+	ListPlus,
+	Pin,
+	QrCode,
+	ShieldAlert,
+	Star,
+	Trash2,
+} from "lucide-react";
 import type { HistoryItem as HistoryItemType } from "../../lib/db";
-import { formatDate, truncateText } from "../../lib/utils";
+import {
+	formatDate,
+	formatFileListLabel,
+	getLinkDomain,
+	truncateText,
+} from "../../lib/utils";
+import { Favicon } from "./Favicon";
+import { ImagePreview } from "./ImagePreview";
 
 interface HistoryItemProps {
 	/** The history item to display */
@@ -11,21 +28,42 @@ interface HistoryItemProps {
 	onItemClick: (item: HistoryItemType) => void;
 	/** Callback to toggle favorite status */
 	onToggleFavorite: (e: React.MouseEvent, itemId: number) => void;
+	/** Callback to pin or unpin the item */
+	onTogglePin: (e: React.MouseEvent, itemId: number) => void;
 	/** Callback to delete the item */
 	onDelete: (e: React.MouseEvent, itemId: number) => void;
+	/** 1-based position in the paste queue, or null if not queued */
+	queuePosition?: number | null;
+	/** Callback to add the item to, or remove it from, the paste queue */
+	onToggleQueued?: (e: React.MouseEvent, itemId: number) => void;
+	/** Callback to copy the decoded QR code of an image item */
+	onCopyQrPayload?: (e: React.MouseEvent, itemId: number) => void;
 }
 
 /**
  * A single history item with content preview, date, and action buttons
- * Displays clipboard content with copy, favorite, and delete actions
+ * Displays clipboard content with copy, pin, favorite, and delete actions
+ * Pinned items show a pin marker next to their date and text that looks
+ * like a secret a warning marker, followed by the app the item was copied
+ * from and the item's place in the paste queue
  */
 export function HistoryItem({
 	item,
 	isSelected,
 	onItemClick,
 	onToggleFavorite,
+	onTogglePin,
 	onDelete,
+	queuePosition = null,
+	onToggleQueued,
+	onCopyQrPayload,
 }: HistoryItemProps) {
+	const pinLabel = item.pinned ? "Unpin item" : "Pin to top";
+	const queueLabel =
+		queuePosition === null ? "Add to paste queue" : "Remove from paste queue";
+	const linkDomain =
+		item.content_kind === "url" ? getLinkDomain(item.content) : null;
+
 	return (
 		// biome-ignore lint/a11y/useSemanticElements: Need div with role="button" to allow nested buttons (Copy/Delete)
 		<div
@@ -50,14 +88,97 @@ export function HistoryItem({
 		>
 			<div className="flex items-start justify-between gap-2">
 				<div className="flex-1 min-w-0">
-					<p className="text-sm break-words">{truncateText(item.content)}</p>
+					{item.type === "image" ? (
+						<ImagePreview
+							itemId={item.id}
+							width={item.image_width}
+							height={item.image_height}
+						/>
+					) : item.type === "files" ? (
+						<p
+							className="flex items-center gap-2 text-sm break-all"
+							title={item.content}
+						>
+							<Files className="w-4 h-4 shrink-0" aria-hidden="true" />
+							{formatFileListLabel(item.content)}
+						</p>
+					) : linkDomain ? (
+						<div
+							className="flex items-start gap-2"
+							title={[item.content, item.link_description]
+								.filter(Boolean)
+								.join("\n")}
+						>
+							<Favicon domain={linkDomain} />
+							{item.link_title ? (
+								<div className="min-w-0">
+									<p className="text-sm break-words">
+										{truncateText(item.link_title)}
+									</p>
+									<p
+										className={`text-xs truncate ${
+											isSelected ? "text-blue-100" : "text-gray-400"
+										}`}
+									>
+										{item.content}
+									</p>
+								</div>
+							) : (
+								<p className="text-sm break-all min-w-0">
+									{truncateText(item.content)}
+								</p>
+							)}
+						</div>
+					) : (
+						<p className="text-sm break-words">{truncateText(item.content)}</p>
+					)}
+					{item.note && (
+						<p
+							className={`text-xs italic break-words mt-1 ${
+								isSelected ? "text-blue-100" : "text-gray-300"
+							}`}
+						>
+							{truncateText(item.note)}
+						</p>
+					)}
 					<span
 						className={`
-							text-xs whitespace-nowrap block mt-1
+							text-xs whitespace-nowrap flex items-center gap-1 mt-1
 							${isSelected ? "text-blue-100" : "text-gray-400"}
 						`}
 					>
+						{item.pinned === 1 && (
+							<Pin
+								className="w-3 h-3 fill-current"
+								aria-label="Pinned"
+								role="img"
+							/>
+						)}
+						{item.secret_match && (
+							<ShieldAlert
+								className={`w-3 h-3 ${isSelected ? "" : "text-amber-400"}`}
+								aria-label={`Looks like a secret: ${item.secret_match}`}
+								role="img"
+							/>
+						)}
 						{formatDate(item.created_at)}
+						{item.source_app && (
+							<span
+								className="truncate"
+								title={
+									[item.source_title, item.source_url]
+										.filter(Boolean)
+										.join("\n") || undefined
+								}
+							>
+								· {item.source_app}
+							</span>
+						)}
+						{queuePosition !== null && (
+							<span className="whitespace-nowrap">
+								· Queued #{queuePosition}
+							</span>
+						)}
 					</span>
 				</div>
 
@@ -67,6 +188,24 @@ export function HistoryItem({
 						isSelected ? "opacity-100" : "opacity-0 group-hover:opacity-100"
 					}`}
 				>
+					<button
+						type="button"
+						onClick={(e) => onTogglePin(e, item.id)}
+						className={`
+							p-1.5 rounded transition-colors
+							${
+								isSelected
+									? "hover:bg-blue-500 text-white"
+									: item.pinned
+										? "hover:bg-gray-600 text-blue-400 hover:text-blue-300"
+										: "hover:bg-gray-600 text-gray-400 hover:text-white"
+							}
+						`}
+						title={pinLabel}
+						aria-label={pinLabel}
+					>
+						<Pin className={`w-4 h-4 ${item.pinned ? "fill-current" : ""}`} />
+					</button>
 					<button
 						type="button"
 						onClick={(e) => onToggleFavorite(e, item.id)}
@@ -93,6 +232,46 @@ export function HistoryItem({
 							className={`w-4 h-4 ${item.is_favorite ? "fill-current" : ""}`}
 						/>
 					</button>
+					{onToggleQueued && (
+						<button
+							type="button"
+							onClick={(e) => onToggleQueued(e, item.id)}
+							className={`
+								p-1.5 rounded transition-colors
+								${
+									isSelected
+										? "hover:bg-blue-500 text-white"
+										: "hover:bg-gray-600 text-gray-400 hover:text-white"
+								}
+							`}
+							title={queueLabel}
+							aria-label={queueLabel}
+						>
+							{queuePosition === null ? (
+								<ListPlus className="w-4 h-4" />
+							) : (
+								<ListMinus className="w-4 h-4" />
+							)}
+						</button>
+					)}
+					{onCopyQrPayload && item.qr_payload && (
+						<button
+							type="button"
+							onClick={(e) => onCopyQrPayload(e, item.id)}
+							className={`
+								p-1.5 rounded transition-colors
+								${
+									isSelected
+										? "hover:bg-blue-500 text-white"
+										: "hover:bg-gray-600 text-gray-400 hover:text-white"
+								}
+							`}
+							title={`Copy QR code text: ${truncateText(item.qr_payload)}`}
+							aria-label="Copy decoded QR code text"
+						>
+							<QrCode className="w-4 h-4" />
+						</button>
+					)}
 					<button
 						type="button"
 						onClick={(e) => {
@@ -14,6 +14,7 @@ describe("HistoryList", () => {
 	const mockHandlers = {
 		onItemClick: vi.fn(),
 		onToggleFavorite: vi.fn(),
+		onTogglePin: vi.fn(),
 		onDelete: vi.fn(),
 		onLoadMore: vi.fn(),
 		onJumpToTop: vi.fn(),
@@ -22,8 +22,16 @@ interface HistoryListProps {
 	onItemClick: (item: HistoryItemType) => void;
 	/** Callback to toggle favorite status */
 	onToggleFavorite: (e: React.MouseEvent, itemId: number) => void;
+	/** Callback to pin or unpin an item */
+	onTogglePin: (e: React.MouseEvent, itemId: number) => void;
 	/** Callback to delete an item */
 	onDelete: (e: React.MouseEvent, itemId: number) => void;
+	/** Item ids in the paste queue, in paste order */
+	queue?: number[];
+	/** Callback to add an item to, or remove it from, the paste queue */
+	onToggleQueued?: (e: React.MouseEvent, itemId: number) => void;
+	/** Callback to copy the decoded QR code of an image item */
+	onCopyQrPayload?: (e: React.MouseEvent, itemId: number) => void;
 	/** Callback to load more items */
 	onLoadMore: () => void;
 	/** Callback when jump-to-top is triggered */
@@ -44,7 +52,11 @@ export function HistoryList({
 	isLoadingMore,
 	onItemClick,
 	onToggleFavorite,
+	onTogglePin,
 	onDelete,
+	queue = [],
+	onToggleQueued,
+	onCopyQrPayload,
 	onLoadMore,
 	onJumpToTop,
 }: HistoryListProps) {
@@ -89,7 +101,13 @@ export function HistoryList({
 						isSelected={index === selectedIndex}
 						onItemClick={onItemClick}
 						onToggleFavorite={onToggleFavorite}
+						onTogglePin={onTogglePin}
 						onDelete={onDelete}
+						queuePosition={
+							queue.includes(item.id) ? queue.indexOf(item.id) + 1 : null
+						}
+						onToggleQueued={onToggleQueued}
+						onCopyQrPayload={onCopyQrPayload}
 					/>
 				</div>
 			))}
@@ -0,0 +1,35 @@
+import { ImageIcon } from "lucide-react";
+import { useThumbnailQuery } from "../../hooks/queries";
+
+interface ImagePreviewProps {
+	/** ID of the image history item */
+	itemId: number;
+	/** Original image width in pixels */
+	width: number | null;
+	/** Original image height in pixels */
+	height: number | null;
+}
+
+/**
+ * Thumbnail of an image history item with its original dimensions
+ * Falls back to a placeholder icon while loading or if the thumbnail fails
+ */
+export function ImagePreview({ itemId, width, height }: ImagePreviewProps) {
+	const { data: src } = useThumbnailQuery(itemId);
+	const dimensions = width && height ? `${width} × ${height}` : "Image";
+
+	return (
+		<div className="flex items-center gap-2">
+			{src ? (
+				<img
+					src={src}
+					alt={`Copied image (${dimensions})`}
+					className="max-h-24 max-w-full rounded object-contain"
+				/>
+			) : (
+				<ImageIcon className="w-8 h-8 opacity-60" aria-hidden="true" />
+			)}
+			<span className="text-xs opacity-80">{dimensions}</span>
+		</div>
+	);
+}
@@ -11,6 +11,7 @@ import {
 	useClearHistoryMutation,
 	useDeleteItemMutation,
 	useToggleFavoriteMutation,
+	useTogglePinMutation,
 } from "./history";
 
 describe("useDeleteItemMutation", () => {
@@ -49,7 +50,7 @@ describe("useDeleteItemMutation", () => {
 		const queryClient = createTestQueryClient();
 		const mockItems = createMockHistoryItems(3);
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: mockItems, nextOffset: undefined }],
+			pages: [{ items: mockItems, nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
@@ -87,7 +88,7 @@ describe("useDeleteItemMutation", () => {
 		const queryClient = createTestQueryClient();
 		const mockItems = createMockHistoryItems(3);
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: [...mockItems], nextOffset: undefined }],
+			pages: [{ items: [...mockItems], nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
@@ -150,7 +151,7 @@ describe("useToggleFavoriteMutation", () => {
 		const mockItems = createMockHistoryItems(3);
 		mockItems[0].is_favorite = 0;
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: mockItems, nextOffset: undefined }],
+			pages: [{ items: mockItems, nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
@@ -186,7 +187,7 @@ describe("useToggleFavoriteMutation", () => {
 		const mockItems = createMockHistoryItems(3);
 		mockItems[0].is_favorite = 1;
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: mockItems, nextOffset: undefined }],
+			pages: [{ items: mockItems, nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
@@ -222,7 +223,7 @@ describe("useToggleFavoriteMutation", () => {
 		const mockItems = createMockHistoryItems(3);
 		mockItems[0].is_favorite = 0;
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: mockItems, nextOffset: undefined }],
+			pages: [{ items: mockItems, nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
@@ -255,6 +256,60 @@ describe("useToggleFavoriteMutation", () => {
 	});
 });
 
+describe("useTogglePinMutation", () => {
+	const createWrapper = (queryClient = createTestQueryClient()) => {
+		return ({ children }: { children: ReactNode }) => (
+			<QueryClientProvider client={queryClient}>{children}</QueryClientProvider>
+		);
+	};
+
+	afterEach(() => {
+		vi.clearAllMocks();
+	});
+
+	it("optimistically pins the item and refetches", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.db.togglePin.mockImplementation(
+			() => new Promise((resolve) => setTimeout(() => resolve(true), 100)),
+		);
+
+		const queryClient = createTestQueryClient();
+		const invalidateSpy = vi.spyOn(queryClient, "invalidateQueries");
+		const mockItems = createMockHistoryItems(3);
+		const initialData: InfiniteHistoryData = {
+			pages: [{ items: mockItems, nextPageParam: undefined }],
+			pageParams: [""],
+		};
+
+		queryClient.setQueryData(
+			historyKeys.list({ searchQuery: "", favoritesOnly: false }),
+			initialData,
+		);
+
+		const { result } = renderHook(() => useTogglePinMutation(), {
+			wrapper: createWrapper(queryClient),
+		});
+
+		act(() => {
+			result.current.mutate(mockItems[2].id);
+		});
+
+		await waitFor(() => {
+			const cachedData = queryClient.getQueryData<InfiniteHistoryData>(
+				historyKeys.list({ searchQuery: "", favoritesOnly: false }),
+			);
+			expect(cachedData?.pages[0].items[2].pinned).toBe(1);
+		});
+		expect(mockApi.db.togglePin).toHaveBeenCalledWith(mockItems[2].id);
+
+		await waitFor(() => {
+			expect(invalidateSpy).toHaveBeenCalledWith({
+				queryKey: historyKeys.all,
+			});
+		});
+	});
+});
+
 describe("useClearHistoryMutation", () => {
 	const createWrapper = (queryClient = createTestQueryClient()) => {
 		return ({ children }: { children: ReactNode }) => (
@@ -288,7 +343,7 @@ describe("useClearHistoryMutation", () => {
 		const queryClient = createTestQueryClient();
 		const mockItems = createMockHistoryItems(3);
 		const initialData: InfiniteHistoryData = {
-			pages: [{ items: mockItems, nextOffset: undefined }],
+			pages: [{ items: mockItems, nextPageParam: undefined }],
 			pageParams: [0],
 		};
 
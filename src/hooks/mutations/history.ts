@@ -3,6 +3,7 @@ import {
 	clearAllHistoryResult,
 	deleteHistoryItemResult,
 	toggleFavoriteResult,
+	togglePinResult,
 } from "../../lib/db";
 import { historyKeys } from "../../lib/queryKeys";
 import type { InfiniteHistoryData } from "../queries/types";
@@ -11,6 +12,7 @@ import {
 	clearAllPages,
 	removeItemFromPages,
 	toggleItemFavorite,
+	toggleItemPinned,
 } from "../queries/utils";
 
 /**
@@ -107,6 +109,49 @@ export function useToggleFavoriteMutation() {
 	});
 }
 
+/**
+ * Hook for pinning or unpinning an item with optimistic update
+ * Updates item in cache immediately; the refetch moves it into place
+ */
+export function useTogglePinMutation() {
+	const queryClient = useQueryClient();
+
+	return useMutation({
+		mutationFn: async (itemId: number) => {
+			const result = await togglePinResult(itemId);
+			if (!result.ok) {
+				console.error("Failed to toggle pin:", result.error.message);
+				throw result.error;
+			}
+		},
+		onMutate: async (itemId) => {
+			await queryClient.cancelQueries({ queryKey: historyKeys.all });
+
+			const previousData = queryClient.getQueriesData<InfiniteHistoryData>({
+				queryKey: historyKeys.all,
+			});
+
+			queryClient.setQueriesData<InfiniteHistoryData>(
+				{ queryKey: historyKeys.all },
+				applyTransform(toggleItemPinned(itemId)),
+			);
+
+			return { previousData };
+		},
+		onError: (_err, _itemId, context) => {
+			if (context?.previousData) {
+				for (const [queryKey, data] of context.previousData) {
+					queryClient.setQueryData(queryKey, data);
+				}
+			}
+		},
+		onSettled: () => {
+			// Refetch so pinned items sort to the top
+			queryClient.invalidateQueries({ queryKey: historyKeys.all });
+		},
+	});
+}
+
 /**
  * Hook for clearing all history
  * Invalidates all history queries on success
@@ -2,4 +2,5 @@ export {
 	useClearHistoryMutation,
 	useDeleteItemMutation,
 	useToggleFavoriteMutation,
+	useTogglePinMutation,
 } from "./history";
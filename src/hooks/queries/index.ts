@@ -1,3 +1,5 @@
 export type { HistoryPage, InfiniteHistoryData } from "./types";
 export { useClipboardMonitor } from "./useClipboardMonitor";
+export { useFaviconQuery } from "./useFaviconQuery";
 export { flattenHistoryPages, useHistoryQuery } from "./useHistoryQuery";
+export { useThumbnailQuery } from "./useThumbnailQuery";
@@ -1,13 +1,19 @@
 import type { HistoryItem } from "../../lib/db";
 
+/**
+ * Parameter for fetching a page: an offset for search results,
+ * or an opaque cursor for the history list
+ */
+export type HistoryPageParam = number | string;
+
 /**
  * Represents a single page of history items from infinite query
  */
 export interface HistoryPage {
 	/** History items in this page */
 	items: HistoryItem[];
-	/** Next page offset, or undefined if no more pages */
-	nextOffset: number | undefined;
+	/** Parameter for the next page, or undefined if no more pages */
+	nextPageParam: HistoryPageParam | undefined;
 }
 
 /**
@@ -17,5 +23,5 @@ export interface InfiniteHistoryData {
 	/** Array of loaded pages */
 	pages: HistoryPage[];
 	/** Page parameters used for each page */
-	pageParams: number[];
+	pageParams: HistoryPageParam[];
 }
@@ -0,0 +1,51 @@
+import { QueryClientProvider } from "@tanstack/react-query";
+import { renderHook, waitFor } from "@testing-library/react";
+import type { ReactNode } from "react";
+import { describe, expect, it, vi } from "vitest";
+import { historyKeys } from "../../lib/queryKeys";
+import { getMockElectronAPI } from "../../test/setup";
+import { createTestQueryClient } from "../../test/utils";
+import { useClipboardMonitor } from "./useClipboardMonitor";
+
+describe("useClipboardMonitor", () => {
+	const createWrapper = () => {
+		const queryClient = createTestQueryClient();
+		const wrapper = ({ children }: { children: ReactNode }) => (
+			<QueryClientProvider client={queryClient}>{children}</QueryClientProvider>
+		);
+		return { queryClient, wrapper };
+	};
+
+	it("invalidates history queries when the main process reports a change", async () => {
+		const mockApi = getMockElectronAPI();
+		const { queryClient, wrapper } = createWrapper();
+		const invalidateSpy = vi.spyOn(queryClient, "invalidateQueries");
+
+		renderHook(() => useClipboardMonitor(), { wrapper });
+
+		await waitFor(() =>
+			expect(mockApi.events.onHistoryChanged).toHaveBeenCalled(),
+		);
+		const [notifyChange] = mockApi.events.onHistoryChanged.mock.calls[0];
+		notifyChange();
+
+		expect(invalidateSpy).toHaveBeenCalledWith({
+			queryKey: historyKeys.all,
+		});
+	});
+
+	it("unsubscribes on unmount", async () => {
+		const mockApi = getMockElectronAPI();
+		const unsubscribe = vi.fn();
+		mockApi.events.onHistoryChanged.mockReturnValue(unsubscribe);
+		const { wrapper } = createWrapper();
+
+		const { unmount } = renderHook(() => useClipboardMonitor(), { wrapper });
+		await waitFor(() =>
+			expect(mockApi.events.onHistoryChanged).toHaveBeenCalled(),
+		);
+		unmount();
+
+		expect(unsubscribe).toHaveBeenCalled();
+	});
+});
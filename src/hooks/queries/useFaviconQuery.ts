@@ -0,0 +1,32 @@
+import { useQuery } from "@tanstack/react-query";
+import { getFaviconResult } from "../../lib/db";
+import { faviconKeys } from "../../lib/queryKeys";
+
+/**
+ * Fetches the icon of a site from the main process cache
+ * @param domain - Host name of the site
+ */
+async function fetchFavicon(domain: string): Promise<string | null> {
+	const result = await getFaviconResult(domain);
+	if (!result.ok) {
+		console.error("Failed to load site icon:", result.error.message);
+		throw result.error;
+	}
+	return result.value;
+}
+
+/**
+ * Hook for loading the icon of a linked site as a data URL
+ * Icons rarely change, so each site is looked up once per session
+ *
+ * @param domain - Host name of the site
+ * @returns Query result with the icon data URL (null if none)
+ */
+export function useFaviconQuery(domain: string) {
+	return useQuery({
+		queryKey: faviconKeys.domain(domain),
+		queryFn: () => fetchFavicon(domain),
+		staleTime: Number.POSITIVE_INFINITY,
+		retry: false,
+	});
+}
@@ -22,7 +22,7 @@ describe("useHistoryQuery", () => {
 	it("returns loading state initially", () => {
 		const mockApi = getMockElectronAPI();
 		// Make the API call hang so we can observe loading state
-		mockApi.db.getHistory.mockImplementation(
+		mockApi.db.listPage.mockImplementation(
 			() => new Promise(() => {}), // Never resolves
 		);
 
@@ -38,7 +38,10 @@ describe("useHistoryQuery", () => {
 	it("returns data after successful fetch", async () => {
 		const mockItems = createMockHistoryItems(3);
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue(mockItems);
+		mockApi.db.listPage.mockResolvedValue({
+			items: mockItems,
+			nextCursor: null,
+		});
 
 		const { result } = renderHook(
 			() => useHistoryQuery({ searchQuery: "", favoritesOnly: false }),
@@ -53,9 +56,13 @@ describe("useHistoryQuery", () => {
 		expect(result.current.data?.pages[0].items).toEqual(mockItems);
 	});
 
-	it("passes search query to getHistory", async () => {
+	it("passes search query to searchHistory", async () => {
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue([]);
+		const mockItems = createMockHistoryItems(2).map((item) => ({
+			...item,
+			match_ranges: [],
+		}));
+		mockApi.db.searchHistory.mockResolvedValue(mockItems);
 
 		const { result } = renderHook(
 			() =>
@@ -67,17 +74,65 @@ describe("useHistoryQuery", () => {
 			expect(result.current.isSuccess).toBe(true);
 		});
 
-		expect(mockApi.db.getHistory).toHaveBeenCalledWith({
+		expect(mockApi.db.searchHistory).toHaveBeenCalledWith({
 			query: "test search",
 			limit: 100, // INITIAL_LOAD_COUNT
 			favoritesOnly: false,
 			offset: 0,
 		});
+		expect(mockApi.db.listPage).not.toHaveBeenCalled();
+		expect(result.current.data?.pages[0].items).toEqual(mockItems);
+	});
+
+	it("uses fuzzy search in fuzzy mode", async () => {
+		const mockApi = getMockElectronAPI();
+
+		const { result } = renderHook(
+			() =>
+				useHistoryQuery({
+					searchQuery: "gthb tok",
+					favoritesOnly: false,
+					searchMode: "fuzzy",
+				}),
+			{ wrapper: createWrapper() },
+		);
+
+		await waitFor(() => {
+			expect(result.current.isSuccess).toBe(true);
+		});
+
+		expect(mockApi.db.fuzzySearchHistory).toHaveBeenCalledWith({
+			query: "gthb tok",
+			limit: 100,
+			favoritesOnly: false,
+			offset: 0,
+		});
+		expect(mockApi.db.searchHistory).not.toHaveBeenCalled();
+	});
+
+	it("skips the search for an incomplete regex", async () => {
+		const mockApi = getMockElectronAPI();
+
+		const { result } = renderHook(
+			() =>
+				useHistoryQuery({
+					searchQuery: "(10\\.",
+					favoritesOnly: false,
+					searchMode: "regex",
+				}),
+			{ wrapper: createWrapper() },
+		);
+
+		await waitFor(() => {
+			expect(result.current.isSuccess).toBe(true);
+		});
+
+		expect(result.current.data?.pages[0].items).toEqual([]);
+		expect(mockApi.db.regexSearchHistory).not.toHaveBeenCalled();
 	});
 
 	it("trims whitespace from search query", async () => {
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue([]);
 
 		const { result } = renderHook(
 			() => useHistoryQuery({ searchQuery: "  test  ", favoritesOnly: false }),
@@ -88,7 +143,7 @@ describe("useHistoryQuery", () => {
 			expect(result.current.isSuccess).toBe(true);
 		});
 
-		expect(mockApi.db.getHistory).toHaveBeenCalledWith({
+		expect(mockApi.db.searchHistory).toHaveBeenCalledWith({
 			query: "test",
 			limit: 100,
 			favoritesOnly: false,
@@ -96,9 +151,8 @@ describe("useHistoryQuery", () => {
 		});
 	});
 
-	it("passes favoritesOnly to getHistory", async () => {
+	it("passes favoritesOnly to listPage", async () => {
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue([]);
 
 		const { result } = renderHook(
 			() => useHistoryQuery({ searchQuery: "", favoritesOnly: true }),
@@ -109,19 +163,40 @@ describe("useHistoryQuery", () => {
 			expect(result.current.isSuccess).toBe(true);
 		});
 
-		expect(mockApi.db.getHistory).toHaveBeenCalledWith({
-			query: "",
-			limit: 100,
+		expect(mockApi.db.listPage).toHaveBeenCalledWith({
+			cursor: null,
+			pageSize: 100,
 			favoritesOnly: true,
-			offset: 0,
 		});
 	});
 
+	it("filters by tag: terms in the query", async () => {
+		const mockApi = getMockElectronAPI();
+
+		const { result } = renderHook(
+			() => useHistoryQuery({ searchQuery: "tag:sql", favoritesOnly: false }),
+			{ wrapper: createWrapper() },
+		);
+
+		await waitFor(() => {
+			expect(result.current.isSuccess).toBe(true);
+		});
+
+		expect(mockApi.db.listPage).toHaveBeenCalledWith({
+			cursor: null,
+			pageSize: 100,
+			favoritesOnly: false,
+			tags: ["sql"],
+		});
+		expect(mockApi.db.searchHistory).not.toHaveBeenCalled();
+	});
+
 	it("determines hasNextPage correctly when more items exist", async () => {
-		// Return exactly INITIAL_LOAD_COUNT items to indicate there may be more
-		const mockItems = createMockHistoryItems(100);
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue(mockItems);
+		mockApi.db.listPage.mockResolvedValue({
+			items: createMockHistoryItems(100),
+			nextCursor: "cursor-1",
+		});
 
 		const { result } = renderHook(
 			() => useHistoryQuery({ searchQuery: "", favoritesOnly: false }),
@@ -136,10 +211,32 @@ describe("useHistoryQuery", () => {
 	});
 
 	it("determines hasNextPage correctly when no more items", async () => {
-		// Return fewer items than batch size to indicate no more items
-		const mockItems = createMockHistoryItems(50);
 		const mockApi = getMockElectronAPI();
-		mockApi.db.getHistory.mockResolvedValue(mockItems);
+		mockApi.db.listPage.mockResolvedValue({
+			items: createMockHistoryItems(50),
+			nextCursor: null,
+		});
+
+		const { result } = renderHook(
+			() => useHistoryQuery({ searchQuery: "", favoritesOnly: false }),
+			{ wrapper: createWrapper() },
+		);
+
+		await waitFor(() => {
+			expect(result.current.isSuccess).toBe(true);
+		});
+
+		expect(result.current.hasNextPage).toBe(false);
+	});
+
+	it("fetches the next page with the returned cursor", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.db.listPage
+			.mockResolvedValueOnce({
+				items: createMockHistoryItems(100),
+				nextCursor: "cursor-1",
+			})
+			.mockResolvedValueOnce({ items: [], nextCursor: null });
 
 		const { result } = renderHook(
 			() => useHistoryQuery({ searchQuery: "", favoritesOnly: false }),
@@ -149,7 +246,16 @@ describe("useHistoryQuery", () => {
 		await waitFor(() => {
 			expect(result.current.isSuccess).toBe(true);
 		});
+		await result.current.fetchNextPage();
 
+		await waitFor(() => {
+			expect(result.current.data?.pages).toHaveLength(2);
+		});
+		expect(mockApi.db.listPage).toHaveBeenLastCalledWith({
+			cursor: "cursor-1",
+			pageSize: 100,
+			favoritesOnly: false,
+		});
 		expect(result.current.hasNextPage).toBe(false);
 	});
 });
@@ -167,7 +273,7 @@ describe("flattenHistoryPages", () => {
 
 	it("flattens single page correctly", () => {
 		const items = createMockHistoryItems(3);
-		const pages = [{ items, nextOffset: undefined }];
+		const pages = [{ items, nextPageParam: undefined }];
 
 		const result = flattenHistoryPages(pages);
 
@@ -178,8 +284,8 @@ describe("flattenHistoryPages", () => {
 		const items1 = createMockHistoryItems(2);
 		const items2 = createMockHistoryItems(2);
 		const pages = [
-			{ items: items1, nextOffset: 2 },
-			{ items: items2, nextOffset: undefined },
+			{ items: items1, nextPageParam: 2 },
+			{ items: items2, nextPageParam: undefined },
 		];
 
 		const result = flattenHistoryPages(pages);
@@ -191,8 +297,8 @@ describe("flattenHistoryPages", () => {
 	it("handles pages with empty items", () => {
 		const items1 = createMockHistoryItems(2);
 		const pages = [
-			{ items: items1, nextOffset: 2 },
-			{ items: [], nextOffset: undefined },
+			{ items: items1, nextPageParam: 2 },
+			{ items: [], nextPageParam: undefined },
 		];
 
 		const result = flattenHistoryPages(pages);
@@ -1,42 +1,103 @@
 import { useInfiniteQuery } from "@tanstack/react-query";
 import { INITIAL_LOAD_COUNT, PAGINATION_BATCH_SIZE } from "../../lib/constants";
-import { getHistoryResult, type HistoryItem } from "../../lib/db";
-import { type HistoryQueryFilters, historyKeys } from "../../lib/queryKeys";
-import { hasMoreItems } from "../../lib/utils";
-import type { HistoryPage } from "./types";
+import {
+	fuzzySearchHistoryResult,
+	type HistoryItem,
+	listPageResult,
+	regexSearchHistoryResult,
+	searchHistoryResult,
+} from "../../lib/db";
+import {
+	type HistoryQueryFilters,
+	historyKeys,
+	type SearchMode,
+} from "../../lib/queryKeys";
+import {
+	hasMoreItems,
+	isValidRegexPattern,
+	parseSearchFilters,
+} from "../../lib/utils";
+import type { HistoryPage, HistoryPageParam } from "./types";
 
 interface UseHistoryQueryOptions {
 	/** Search query string */
 	searchQuery: string;
 	/** Whether to show only favorites */
 	favoritesOnly: boolean;
+	/** How the search query is matched (default: text) */
+	searchMode?: SearchMode;
 }
 
+/**
+ * Search command used for each search mode
+ */
+const SEARCH_BY_MODE = {
+	text: searchHistoryResult,
+	fuzzy: fuzzySearchHistoryResult,
+	regex: regexSearchHistoryResult,
+} as const satisfies Record<SearchMode, typeof searchHistoryResult>;
+
 interface FetchHistoryPageOptions {
 	/** Search and filter parameters */
 	filters: HistoryQueryFilters;
-	/** Page offset for pagination */
-	offset: number;
+	/** Offset for search results, or cursor for the history list */
+	pageParam: HistoryPageParam;
 	/** Number of items to fetch */
 	limit: number;
 }
 
 /**
  * Fetches a page of history items based on filters
- * @param options - Fetch options including filters, offset, and limit
+ * Searches are ranked best match first using the selected search mode and
+ * paginated by offset; otherwise items are listed newest first and
+ * paginated by cursor so new captures don't shift later pages.
+ * `tag:name`, `app:name`, `kind:name` and `lang:name` terms in the query
+ * filter by tag, source app, content kind and code language instead of
+ * being searched
+ * @param options - Fetch options including filters, page param, and limit
  */
 async function fetchHistoryPage({
 	filters,
-	offset,
+	pageParam,
 	limit,
 }: FetchHistoryPageOptions): Promise<HistoryPage> {
-	const { searchQuery, favoritesOnly } = filters;
+	const { searchQuery, favoritesOnly, searchMode = "text" } = filters;
+	const { query, tags, sourceApp, kind, language } =
+		parseSearchFilters(searchQuery);
+
+	if (!query) {
+		const result = await listPageResult({
+			cursor: typeof pageParam === "string" && pageParam ? pageParam : null,
+			pageSize: limit,
+			favoritesOnly,
+			tags: tags.length > 0 ? tags : undefined,
+			sourceApp,
+			kind,
+			language,
+		});
+		if (!result.ok) {
+			console.error("Failed to get history:", result.error.message);
+			throw result.error;
+		}
+		const { items, nextCursor } = result.value;
+		return { items, nextPageParam: nextCursor ?? undefined };
+	}
 
-	const result = await getHistoryResult({
+	// Skip the round-trip while a regex is still being typed
+	if (searchMode === "regex" && !isValidRegexPattern(query)) {
+		return { items: [], nextPageParam: undefined };
+	}
+
+	const offset = typeof pageParam === "number" ? pageParam : 0;
+	const result = await SEARCH_BY_MODE[searchMode]({
+		query,
 		limit,
 		offset,
-		query: searchQuery.trim(),
 		favoritesOnly,
+		tags: tags.length > 0 ? tags : undefined,
+		sourceApp,
+		kind,
+		language,
 	});
 
 	if (!result.ok) {
@@ -46,9 +107,9 @@ async function fetchHistoryPage({
 	const items = result.value;
 	// Determine if there are more items to load
 	const hasMore = hasMoreItems(items.length, limit);
-	const nextOffset = hasMore ? offset + limit : undefined;
+	const nextPageParam = hasMore ? offset + limit : undefined;
 
-	return { items, nextOffset };
+	return { items, nextPageParam };
 }
 
 /**
@@ -61,19 +122,31 @@ async function fetchHistoryPage({
 export function useHistoryQuery({
 	searchQuery,
 	favoritesOnly,
+	searchMode = "text",
 }: UseHistoryQueryOptions) {
-	const filters: HistoryQueryFilters = { searchQuery, favoritesOnly };
+	const filters: HistoryQueryFilters = {
+		searchQuery,
+		favoritesOnly,
+		searchMode,
+	};
+
+	// Search results page by offset; the history list pages by cursor
+	const hasSearchText = parseSearchFilters(searchQuery).query !== "";
+	const initialPageParam: HistoryPageParam = hasSearchText ? 0 : "";
 
 	return useInfiniteQuery({
 		queryKey: historyKeys.list(filters),
-		queryFn: ({ pageParam = 0 }) =>
+		queryFn: ({ pageParam }) =>
 			fetchHistoryPage({
 				filters,
-				offset: pageParam,
-				limit: pageParam === 0 ? INITIAL_LOAD_COUNT : PAGINATION_BATCH_SIZE,
+				pageParam,
+				limit:
+					pageParam === initialPageParam
+						? INITIAL_LOAD_COUNT
+						: PAGINATION_BATCH_SIZE,
 			}),
-		initialPageParam: 0,
-		getNextPageParam: (lastPage) => lastPage.nextOffset,
+		initialPageParam,
+		getNextPageParam: (lastPage) => lastPage.nextPageParam,
 		// Keep previous data while fetching new data on filter change
 		placeholderData: (previousData) => previousData,
 	});
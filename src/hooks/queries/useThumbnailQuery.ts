@@ -0,0 +1,31 @@
+import { useQuery } from "@tanstack/react-query";
+import { getThumbnailResult } from "../../lib/db";
+import { historyKeys } from "../../lib/queryKeys";
+
+/**
+ * Fetches the list thumbnail of an image item
+ * @param id - The history item ID
+ */
+async function fetchThumbnail(id: number): Promise<string | null> {
+	const result = await getThumbnailResult(id);
+	if (!result.ok) {
+		console.error("Failed to load thumbnail:", result.error.message);
+		throw result.error;
+	}
+	return result.value;
+}
+
+/**
+ * Hook for loading an image item's thumbnail as a data URL
+ * Stored images never change, so thumbnails are cached for the session
+ *
+ * @param id - The history item ID
+ * @returns Query result with the thumbnail data URL (null if none)
+ */
+export function useThumbnailQuery(id: number) {
+	return useQuery({
+		queryKey: historyKeys.thumbnail(id),
+		queryFn: () => fetchThumbnail(id),
+		staleTime: Number.POSITIVE_INFINITY,
+	});
+}
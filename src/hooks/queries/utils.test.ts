@@ -0,0 +1,64 @@
+import { describe, expect, it } from "vitest";
+import { createMockHistoryItem } from "../../test/mocks/history";
+import { getMockElectronAPI } from "../../test/setup";
+import { writeItemToClipboardWithRetry } from "./utils";
+
+describe("writeItemToClipboardWithRetry", () => {
+	it("restores text items with their RTF and HTML formats", async () => {
+		const mockApi = getMockElectronAPI();
+		const item = createMockHistoryItem({
+			content: "Quarterly report",
+			rtf: "{\\rtf1 \\b Quarterly report}",
+			html: "<b>Quarterly report</b>",
+		});
+
+		const result = await writeItemToClipboardWithRetry(item);
+
+		expect(result.ok).toBe(true);
+		expect(mockApi.clipboard.write).toHaveBeenCalledWith({
+			text: "Quarterly report",
+			rtf: "{\\rtf1 \\b Quarterly report}",
+			html: "<b>Quarterly report</b>",
+		});
+	});
+
+	it("omits formats that were not captured", async () => {
+		const mockApi = getMockElectronAPI();
+
+		await writeItemToClipboardWithRetry(
+			createMockHistoryItem({ content: "plain" }),
+		);
+
+		expect(mockApi.clipboard.write).toHaveBeenCalledWith({
+			text: "plain",
+			rtf: undefined,
+			html: undefined,
+		});
+	});
+
+	it("asks the main process to restore non-text items", async () => {
+		const mockApi = getMockElectronAPI();
+
+		await writeItemToClipboardWithRetry(
+			createMockHistoryItem({ id: 9, type: "image", content: "" }),
+		);
+
+		expect(mockApi.clipboard.restoreItem).toHaveBeenCalledWith(9);
+		expect(mockApi.clipboard.write).not.toHaveBeenCalled();
+	});
+
+	it("restores masked secrets from the main process", async () => {
+		const mockApi = getMockElectronAPI();
+
+		await writeItemToClipboardWithRetry(
+			createMockHistoryItem({
+				id: 4,
+				content: "ghp_****",
+				secret_match: "GitHub token",
+			}),
+		);
+
+		expect(mockApi.clipboard.restoreItem).toHaveBeenCalledWith(4);
+		expect(mockApi.clipboard.write).not.toHaveBeenCalled();
+	});
+});
@@ -4,39 +4,11 @@ import {
 	clipboardWriteFailed,
 	getErrorMessage,
 } from "../../lib/errors";
-import { err, none, type Option, ok, type Result, some } from "../../lib/fp";
+import type { HistoryItem } from "../../lib/db";
+import { err, ok, type Result } from "../../lib/fp";
 import { retryWithBackoff } from "../../lib/utils";
 import type { InfiniteHistoryData } from "./types";
 
-/**
- * Detects if clipboard content has changed from previous value.
- * Pure function - no side effects.
- *
- * @param current - Current clipboard text (may be empty string)
- * @param previous - Previous clipboard text
- * @returns Option containing the new text if changed, none otherwise
- *
- * @example
- * detectClipboardChange("hello", "")      // some("hello")
- * detectClipboardChange("hello", "hello") // none
- * detectClipboardChange("", "hello")      // none (empty is not a change)
- */
-export const detectClipboardChange = (
-	current: string,
-	previous: string,
-): Option<string> => (current && current !== previous ? some(current) : none);
-
-/**
- * Normalizes clipboard text for comparison.
- * Pure function - handles null/undefined gracefully.
- *
- * @param text - Raw text from clipboard (may be null/undefined)
- * @returns Normalized string (empty string if input is falsy)
- */
-export const normalizeClipboardText = (
-	text: string | null | undefined,
-): string => text || "";
-
 /**
  * Calculates the new selected index after an item is deleted.
  * Pure function - no side effects.
@@ -73,19 +45,14 @@ export const formatActionError = (
 };
 
 /**
- * Clipboard data for writing.
- */
-interface ClipboardWriteData {
-	text: string;
-	rtf?: string | null;
-}
-
-/**
- * Writes text (and optionally RTF) to clipboard with retry logic.
+ * Restores a history item to the clipboard with retry logic.
+ * Image and file items are restored by the main process, which owns the
+ * platform formats, and so are secrets, whose rows only hold a masked
+ * preview; other text (with optional RTF and HTML) is written directly.
  * Returns a Result for explicit error handling.
  */
-export async function writeToClipboardWithRetry(
-	data: ClipboardWriteData,
+export async function writeItemToClipboardWithRetry(
+	item: HistoryItem,
 ): Promise<Result<void, ClipboardError>> {
 	if (!window.electronAPI) {
 		return err(clipboardApiNotAvailable());
@@ -93,9 +60,14 @@ export async function writeToClipboardWithRetry(
 
 	const result = await retryWithBackoff({
 		operation: async () => {
+			if (item.type !== "text" || item.secret_match) {
+				await window.electronAPI.clipboard.restoreItem(item.id);
+				return;
+			}
 			await window.electronAPI.clipboard.write({
-				text: data.text,
-				rtf: data.rtf || undefined,
+				text: item.content,
+				rtf: item.rtf || undefined,
+				html: item.html || undefined,
 			});
 		},
 	});
@@ -149,6 +121,28 @@ export const toggleItemFavorite =
 		})),
 	});
 
+/**
+ * Creates a transformer that toggles the pinned status of an item by ID.
+ * Pure function - no side effects, returns new data structure.
+ * The item keeps its position until the next refetch re-sorts the list.
+ *
+ * @param itemId - The ID of the item to toggle
+ * @returns A function that transforms InfiniteHistoryData by toggling the item's pinned status
+ */
+export const toggleItemPinned =
+	(itemId: number) =>
+	(data: InfiniteHistoryData): InfiniteHistoryData => ({
+		...data,
+		pages: data.pages.map((page) => ({
+			...page,
+			items: page.items.map((item) =>
+				item.id === itemId
+					? { ...item, pinned: item.pinned === 1 ? 0 : 1 }
+					: item,
+			),
+		})),
+	});
+
 /**
  * Creates empty history data structure.
  * Pure function - no side effects.
@@ -160,7 +154,7 @@ export const clearAllPages = (
 	data: InfiniteHistoryData,
 ): InfiniteHistoryData => ({
 	...data,
-	pages: [{ items: [], nextOffset: undefined }],
+	pages: [{ items: [], nextPageParam: undefined }],
 	pageParams: data.pageParams,
 });
 
@@ -175,3 +169,4 @@ export const applyTransform =
 	(transform: (data: InfiniteHistoryData) => InfiniteHistoryData) =>
 	(old: InfiniteHistoryData | undefined): InfiniteHistoryData | undefined =>
 		old ? transform(old) : old;
+
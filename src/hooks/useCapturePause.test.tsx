@@ -0,0 +1,41 @@
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it } from "vitest";
+import { getMockElectronAPI } from "../test/setup";
+import { useCapturePause } from "./useCapturePause";
+
+describe("useCapturePause", () => {
+	it("loads the current state from the main process", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.capture.getStatus.mockResolvedValue({
+			paused: true,
+			resumesAt: null,
+		});
+
+		const { result } = renderHook(() => useCapturePause());
+
+		await waitFor(() => expect(result.current.capturePaused).toBe(true));
+	});
+
+	it("follows changes made from the tray", async () => {
+		const mockApi = getMockElectronAPI();
+		const { result } = renderHook(() => useCapturePause());
+
+		await waitFor(() => expect(mockApi.capture.getStatus).toHaveBeenCalled());
+		const [notifyChange] = mockApi.events.onCaptureChanged.mock.calls[0];
+		act(() => notifyChange({ paused: true, resumesAt: 1_700_000_000_000 }));
+
+		expect(result.current.capturePaused).toBe(true);
+		expect(result.current.captureResumesAt).toBe(1_700_000_000_000);
+	});
+
+	it("toggles capture", async () => {
+		const mockApi = getMockElectronAPI();
+		const { result } = renderHook(() => useCapturePause());
+		await waitFor(() => expect(mockApi.capture.getStatus).toHaveBeenCalled());
+
+		await act(() => result.current.toggleCapturePaused());
+
+		expect(mockApi.capture.setPaused).toHaveBeenCalledWith(true);
+		expect(result.current.capturePaused).toBe(true);
+	});
+});
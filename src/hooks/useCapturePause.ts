@@ -0,0 +1,52 @@
+import { useCallback, useEffect, useState } from "react";
+import { waitForElectronAPIResult } from "../lib/utils";
+
+/**
+ * Hook that tracks the main process capture pause (incognito) state
+ * Stays in sync when capture is paused or snoozed from the tray menu, and
+ * when a snooze ends
+ * @returns The pause state, when a snooze ends, and a callback to toggle it
+ */
+export function useCapturePause() {
+	const [status, setStatus] = useState<CapturePauseStatusRecord>({
+		paused: false,
+		resumesAt: null,
+	});
+
+	useEffect(() => {
+		let unsubscribe: (() => void) | null = null;
+		let isCancelled = false;
+
+		const subscribe = async () => {
+			const result = await waitForElectronAPIResult();
+			if (!result.ok || isCancelled) return;
+
+			unsubscribe = window.electronAPI.events.onCaptureChanged(setStatus);
+			const current = await window.electronAPI.capture.getStatus();
+			if (!isCancelled) setStatus(current);
+		};
+
+		subscribe().catch((error) => {
+			console.error("Failed to load capture pause state:", error);
+		});
+
+		return () => {
+			isCancelled = true;
+			unsubscribe?.();
+		};
+	}, []);
+
+	/**
+	 * Pauses or resumes capture (ending any snooze); rejects if the main
+	 * process refuses
+	 */
+	const toggleCapturePaused = useCallback(async () => {
+		setStatus(await window.electronAPI.capture.setPaused(!status.paused));
+	}, [status.paused]);
+
+	return {
+		capturePaused: status.paused,
+		captureResumesAt: status.resumesAt,
+		toggleCapturePaused,
+	};
+}
@@ -1,14 +1,15 @@
 import { useCallback, useState } from "react";
-import type { HistoryItem } from "../lib/db";
+import { copyQrPayloadResult, type HistoryItem } from "../lib/db";
 import {
 	useClearHistoryMutation,
 	useDeleteItemMutation,
 	useToggleFavoriteMutation,
+	useTogglePinMutation,
 } from "./mutations";
 import {
 	calculateIndexAfterDelete,
 	formatActionError,
-	writeToClipboardWithRetry,
+	writeItemToClipboardWithRetry,
 } from "./queries/utils";
 
 interface UseHistoryActionsOptions {
@@ -29,8 +30,12 @@ interface UseHistoryActionsReturn {
 	setError: (error: string | null) => void;
 	/** Copy item to clipboard and hide window */
 	handleItemClick: (item: HistoryItem) => Promise<void>;
+	/** Copy the decoded QR code of an image item and hide window */
+	handleCopyQrPayload: (e: React.MouseEvent, itemId: number) => Promise<void>;
 	/** Toggle favorite status of an item */
 	handleToggleFavorite: (e: React.MouseEvent, itemId: number) => Promise<void>;
+	/** Pin or unpin an item at the top of history */
+	handleTogglePin: (e: React.MouseEvent, itemId: number) => Promise<void>;
 	/** Delete a history item */
 	handleDeleteItem: (e: React.MouseEvent, itemId: number) => Promise<void>;
 	/** Clear all history with confirmation */
@@ -38,7 +43,7 @@ interface UseHistoryActionsReturn {
 }
 
 /**
- * Hook that manages history item actions (copy, delete, favorite, clear)
+ * Hook that manages history item actions (copy, delete, favorite, pin, clear)
  * Uses TanStack Query mutations for data operations with optimistic updates
  *
  * @param options - Configuration options for the hook
@@ -55,6 +60,7 @@ export function useHistoryActions({
 	// TanStack Query mutations
 	const deleteItemMutation = useDeleteItemMutation();
 	const toggleFavoriteMutation = useToggleFavoriteMutation();
+	const togglePinMutation = useTogglePinMutation();
 	const clearHistoryMutation = useClearHistoryMutation();
 
 	/**
@@ -73,10 +79,7 @@ export function useHistoryActions({
 		async (item: HistoryItem) => {
 			setError(null);
 
-			const result = await writeToClipboardWithRetry({
-				text: item.content,
-				rtf: item.rtf,
-			});
+			const result = await writeItemToClipboardWithRetry(item);
 
 			if (result.ok) {
 				await onHideWindow();
@@ -88,6 +91,26 @@ export function useHistoryActions({
 		[handleError, onHideWindow, setSelectedIndex],
 	);
 
+	/**
+	 * Handles copying the decoded text of an image item's QR code
+	 */
+	const handleCopyQrPayload = useCallback(
+		async (e: React.MouseEvent, itemId: number) => {
+			e.stopPropagation();
+			setError(null);
+
+			const result = await copyQrPayloadResult(itemId);
+
+			if (result.ok) {
+				await onHideWindow();
+				setSelectedIndex(0);
+			} else {
+				handleError(result.error, "Failed to copy QR code text");
+			}
+		},
+		[handleError, onHideWindow, setSelectedIndex],
+	);
+
 	/**
 	 * Handles toggling favorite status of a history item
 	 */
@@ -104,6 +127,22 @@ export function useHistoryActions({
 		[handleError, toggleFavoriteMutation],
 	);
 
+	/**
+	 * Handles pinning or unpinning a history item
+	 */
+	const handleTogglePin = useCallback(
+		async (e: React.MouseEvent, itemId: number) => {
+			e.stopPropagation();
+			try {
+				setError(null);
+				await togglePinMutation.mutateAsync(itemId);
+			} catch (err) {
+				handleError(err, "Failed to toggle pin");
+			}
+		},
+		[handleError, togglePinMutation],
+	);
+
 	/**
 	 * Handles deleting a history item
 	 */
@@ -159,7 +198,9 @@ export function useHistoryActions({
 		error,
 		setError,
 		handleItemClick,
+		handleCopyQrPayload,
 		handleToggleFavorite,
+		handleTogglePin,
 		handleDeleteItem,
 		handleClearAll,
 	};
@@ -1,5 +1,6 @@
 import { useCallback, useState } from "react";
 import type { HistoryItem } from "../lib/db";
+import type { SearchMode } from "../lib/queryKeys";
 import { flattenHistoryPages, useHistoryQuery } from "./queries";
 
 interface UseHistorySearchReturn {
@@ -11,6 +12,10 @@ interface UseHistorySearchReturn {
 	favoritesOnly: boolean;
 	/** Update the favorites filter */
 	setFavoritesOnly: (value: boolean) => void;
+	/** How the search query is matched */
+	searchMode: SearchMode;
+	/** Update the search mode */
+	setSearchMode: (mode: SearchMode) => void;
 	/** Flattened array of history items from all loaded pages */
 	filteredHistory: HistoryItem[];
 	/** Whether more pages are being loaded */
@@ -34,6 +39,7 @@ interface UseHistorySearchReturn {
 export function useHistorySearch(): UseHistorySearchReturn {
 	const [searchQuery, setSearchQuery] = useState("");
 	const [favoritesOnly, setFavoritesOnly] = useState(false);
+	const [searchMode, setSearchMode] = useState<SearchMode>("text");
 
 	// Use TanStack Query for data fetching
 	const {
@@ -46,6 +52,7 @@ export function useHistorySearch(): UseHistorySearchReturn {
 	} = useHistoryQuery({
 		searchQuery,
 		favoritesOnly,
+		searchMode,
 	});
 
 	// Flatten paginated data into a single array
@@ -76,6 +83,8 @@ export function useHistorySearch(): UseHistorySearchReturn {
 		setSearchQuery,
 		favoritesOnly,
 		setFavoritesOnly,
+		searchMode,
+		setSearchMode,
 		filteredHistory,
 		isLoadingMore: isFetchingNextPage,
 		hasMore: hasNextPage ?? false,
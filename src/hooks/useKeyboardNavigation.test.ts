@@ -86,6 +86,23 @@ describe("useKeyboardNavigation", () => {
 		expect(defaultOptions.onEnter).toHaveBeenCalledWith(mockItems[1]);
 	});
 
+	it("calls onShiftEnter instead of onEnter on Shift+Enter", async () => {
+		const onShiftEnter = vi.fn().mockResolvedValue(undefined);
+		renderHook(() =>
+			useKeyboardNavigation({ ...defaultOptions, onShiftEnter }),
+		);
+
+		act(() => {
+			window.dispatchEvent(
+				new KeyboardEvent("keydown", { key: "Enter", shiftKey: true }),
+			);
+		});
+		await act(async () => {});
+
+		expect(onShiftEnter).toHaveBeenCalledWith(mockItems[0]);
+		expect(defaultOptions.onEnter).not.toHaveBeenCalled();
+	});
+
 	it("ignores keys when not visible", () => {
 		const { result } = renderHook(() =>
 			useKeyboardNavigation({ ...defaultOptions, isVisible: false }),
@@ -10,6 +10,8 @@ interface UseKeyboardNavigationOptions {
 	onEscape?: () => void;
 	/** Callback when Enter key is pressed with selected item */
 	onEnter?: (item: HistoryItem) => Promise<void>;
+	/** Callback when Shift+Enter is pressed with selected item (type it out) */
+	onShiftEnter?: (item: HistoryItem) => Promise<void>;
 	/** Optional callback when selected index changes */
 	onSelectedIndexChange?: (index: number) => void;
 	/** Optional callback to scroll to a specific index */
@@ -26,6 +28,7 @@ export function useKeyboardNavigation({
 	filteredHistory,
 	onEscape,
 	onEnter,
+	onShiftEnter,
 	onSelectedIndexChange,
 	onScrollToIndex,
 }: UseKeyboardNavigationOptions) {
@@ -52,11 +55,12 @@ export function useKeyboardNavigation({
 				return;
 			}
 
-			// Enter: Copy selected item and hide
+			// Enter: Copy selected item and hide; Shift+Enter types it out instead
 			if (e.key === "Enter" && filteredHistory.length > 0) {
 				const selectedItem = filteredHistory[selectedIndex];
-				if (selectedItem && onEnter) {
-					await onEnter(selectedItem);
+				const handler = e.shiftKey && onShiftEnter ? onShiftEnter : onEnter;
+				if (selectedItem && handler) {
+					await handler(selectedItem);
 					updateSelectedIndex(0);
 				}
 				return;
@@ -89,6 +93,7 @@ export function useKeyboardNavigation({
 			selectedIndex,
 			onEscape,
 			onEnter,
+			onShiftEnter,
 			updateSelectedIndex,
 			onScrollToIndex,
 		],
@@ -0,0 +1,40 @@
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it } from "vitest";
+import { getMockElectronAPI } from "../test/setup";
+import { usePasteQueue } from "./usePasteQueue";
+
+describe("usePasteQueue", () => {
+	it("loads the current queue from the main process", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.queue.get.mockResolvedValue([4, 2]);
+
+		const { result } = renderHook(() => usePasteQueue());
+
+		await waitFor(() => expect(result.current.queue).toEqual([4, 2]));
+	});
+
+	it("follows items consumed by the hotkey", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.queue.get.mockResolvedValue([4, 2]);
+		const { result } = renderHook(() => usePasteQueue());
+
+		await waitFor(() => expect(result.current.queue).toEqual([4, 2]));
+		const [notifyChange] = mockApi.events.onQueueChanged.mock.calls[0];
+		act(() => notifyChange([2]));
+
+		expect(result.current.queue).toEqual([2]);
+	});
+
+	it("toggles and clears items", async () => {
+		const mockApi = getMockElectronAPI();
+		const { result } = renderHook(() => usePasteQueue());
+		await waitFor(() => expect(mockApi.queue.get).toHaveBeenCalled());
+
+		await act(() => result.current.toggleQueued(7));
+		expect(mockApi.queue.toggle).toHaveBeenCalledWith(7);
+		expect(result.current.queue).toEqual([7]);
+
+		await act(() => result.current.clearQueue());
+		expect(result.current.queue).toEqual([]);
+	});
+});
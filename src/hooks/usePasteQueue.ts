@@ -0,0 +1,49 @@
+import { useCallback, useEffect, useState } from "react";
+import { waitForElectronAPIResult } from "../lib/utils";
+
+/**
+ * Hook that tracks the main process paste queue
+ * Stays in sync when the paste-next hotkey consumes items while the window
+ * is hidden
+ * @returns The queued item ids in paste order and callbacks to change them
+ */
+export function usePasteQueue() {
+	const [queue, setQueue] = useState<number[]>([]);
+
+	useEffect(() => {
+		let unsubscribe: (() => void) | null = null;
+		let isCancelled = false;
+
+		const subscribe = async () => {
+			const result = await waitForElectronAPIResult();
+			if (!result.ok || isCancelled) return;
+
+			unsubscribe = window.electronAPI.events.onQueueChanged(setQueue);
+			const current = await window.electronAPI.queue.get();
+			if (!isCancelled) setQueue(current);
+		};
+
+		subscribe().catch((error) => {
+			console.error("Failed to load paste queue:", error);
+		});
+
+		return () => {
+			isCancelled = true;
+			unsubscribe?.();
+		};
+	}, []);
+
+	/**
+	 * Adds an item to the end of the queue, or removes it if already queued;
+	 * rejects if the main process refuses (e.g. the queue is full)
+	 */
+	const toggleQueued = useCallback(async (itemId: number) => {
+		setQueue(await window.electronAPI.queue.toggle(itemId));
+	}, []);
+
+	const clearQueue = useCallback(async () => {
+		setQueue(await window.electronAPI.queue.clear());
+	}, []);
+
+	return { queue, toggleQueued, clearQueue };
+}
@@ -0,0 +1,34 @@
+import { renderHook, waitFor } from "@testing-library/react";
+import { describe, expect, it, vi } from "vitest";
+import { getMockElectronAPI } from "../test/setup";
+import { useSearchRequests } from "./useSearchRequests";
+
+describe("useSearchRequests", () => {
+	it("passes requested queries to the callback", async () => {
+		const mockApi = getMockElectronAPI();
+		const onSearchRequested = vi.fn();
+		renderHook(() => useSearchRequests(onSearchRequested));
+
+		await waitFor(() =>
+			expect(mockApi.events.onSearchRequested).toHaveBeenCalled(),
+		);
+		const [notifyRequest] = mockApi.events.onSearchRequested.mock.calls[0];
+		notifyRequest("invoice");
+
+		expect(onSearchRequested).toHaveBeenCalledWith("invoice");
+	});
+
+	it("unsubscribes on unmount", async () => {
+		const mockApi = getMockElectronAPI();
+		const unsubscribe = vi.fn();
+		mockApi.events.onSearchRequested.mockReturnValue(unsubscribe);
+		const { unmount } = renderHook(() => useSearchRequests(vi.fn()));
+
+		await waitFor(() =>
+			expect(mockApi.events.onSearchRequested).toHaveBeenCalled(),
+		);
+		unmount();
+
+		expect(unsubscribe).toHaveBeenCalled();
+	});
+});
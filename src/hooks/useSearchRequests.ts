@@ -0,0 +1,35 @@
+import { useEffect, useRef } from "react";
+import { waitForElectronAPIResult } from "../lib/utils";
+
+/**
+ * Hook that applies searches requested by the main process, e.g. from a
+ * `clipman://search?q=` deep link
+ * @param onSearchRequested - Called with each requested query
+ */
+export function useSearchRequests(onSearchRequested: (query: string) => void) {
+	const onSearchRequestedRef = useRef(onSearchRequested);
+	onSearchRequestedRef.current = onSearchRequested;
+
+	useEffect(() => {
+		let unsubscribe: (() => void) | null = null;
+		let isCancelled = false;
+
+		const subscribe = async () => {
+			const result = await waitForElectronAPIResult();
+			if (!result.ok || isCancelled) return;
+
+			unsubscribe = window.electronAPI.events.onSearchRequested((query) =>
+				onSearchRequestedRef.current(query),
+			);
+		};
+
+		subscribe().catch((error) => {
+			console.error("Failed to subscribe to search requests:", error);
+		});
+
+		return () => {
+			isCancelled = true;
+			unsubscribe?.();
+		};
+	}, []);
+}
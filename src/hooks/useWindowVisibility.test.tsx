@@ -184,4 +184,24 @@ describe("useWindowVisibility", () => {
 
 		expect(mockFocus).toHaveBeenCalled();
 	});
+
+	it("tracks the quick picker window when asked to", async () => {
+		const mockApi = getMockElectronAPI();
+		mockApi.window.isVisible.mockResolvedValue(false);
+		mockApi.picker.isVisible.mockResolvedValue(true);
+
+		const { result } = renderHook(() =>
+			useWindowVisibility({
+				searchInputRef: createMockSearchInputRef(),
+				window: "picker",
+			}),
+		);
+
+		await act(async () => {
+			await vi.advanceTimersByTimeAsync(0);
+		});
+
+		expect(result.current.isVisible).toBe(true);
+		expect(mockApi.window.isVisible).not.toHaveBeenCalled();
+	});
 });
@@ -9,6 +9,8 @@ interface UseWindowVisibilityOptions {
 	searchInputRef: React.RefObject<HTMLInputElement | null>;
 	/** Optional callback when window becomes visible */
 	onBecomeVisible?: () => void;
+	/** Which window to track; the history window unless set */
+	window?: "main" | "picker";
 }
 
 /**
@@ -19,6 +21,7 @@ interface UseWindowVisibilityOptions {
 export function useWindowVisibility({
 	searchInputRef,
 	onBecomeVisible,
+	window: trackedWindow = "main",
 }: UseWindowVisibilityOptions) {
 	const [isVisible, setIsVisible] = useState(false);
 	const wasVisibleRef = useRef(false);
@@ -33,7 +36,10 @@ export function useWindowVisibility({
 			if (!window.electronAPI) return;
 
 			try {
-				const visible = await window.electronAPI.window.isVisible();
+				const visible =
+					trackedWindow === "picker"
+						? await window.electronAPI.picker.isVisible()
+						: await window.electronAPI.window.isVisible();
 				setIsVisible(visible);
 
 				// Detect transition from hidden to visible
@@ -58,7 +64,7 @@ export function useWindowVisibility({
 		checkVisibility();
 
 		return () => clearInterval(interval);
-	}, [searchInputRef]);
+	}, [searchInputRef, trackedWindow]);
 
 	// Manual setter that also updates the ref
 	const setVisibleState = useCallback((visible: boolean) => {
@@ -17,10 +17,58 @@ export const SEARCH_FOCUS_DELAY = 100;
 /** Maximum text length before truncation */
 export const MAX_TEXT_DISPLAY_LENGTH = 100;
 
-// TanStack Query configuration constants
+/** Content kinds accepted by the `kind:` search filter */
+export const CONTENT_KINDS: readonly ContentKindRecord[] = [
+	"url",
+	"email",
+	"phone",
+	"path",
+	"color",
+	"json",
+	"code",
+	"ip",
+	"text",
+];
 
-/** Interval for polling clipboard changes (ms) */
-export const CLIPBOARD_POLL_INTERVAL = 1000;
+/**
+ * Names accepted by the `lang:` search filter, including common short
+ * forms, mapped to the language they stand for
+ */
+export const CODE_LANGUAGE_NAMES: ReadonlyMap<string, CodeLanguageRecord> =
+	new Map([
+	["javascript", "javascript"],
+	["typescript", "typescript"],
+	["python", "python"],
+	["ruby", "ruby"],
+	["php", "php"],
+	["shell", "shell"],
+	["go", "go"],
+	["rust", "rust"],
+	["java", "java"],
+	["kotlin", "kotlin"],
+	["csharp", "csharp"],
+	["swift", "swift"],
+	["c", "c"],
+	["cpp", "cpp"],
+	["sql", "sql"],
+	["html", "html"],
+	["css", "css"],
+	["js", "javascript"],
+	["ts", "typescript"],
+	["py", "python"],
+	["rb", "ruby"],
+	["sh", "shell"],
+	["bash", "shell"],
+	["zsh", "shell"],
+	["golang", "go"],
+	["rs", "rust"],
+	["kt", "kotlin"],
+	["cs", "csharp"],
+	["c#", "csharp"],
+	["c++", "cpp"],
+	]);
+
+// TanStack Query configuration constants
 
 /** Time before data is considered stale and eligible for background refetch (ms) */
 export const QUERY_STALE_TIME = 30_000;
@@ -2,10 +2,11 @@ import { afterEach, describe, expect, it, vi } from "vitest";
 import { createMockHistoryItems } from "../test/mocks/history";
 import { getMockElectronAPI } from "../test/setup";
 import {
-	addClipResult,
 	clearAllHistoryResult,
 	deleteHistoryItemResult,
-	getHistoryResult,
+	getFaviconResult,
+	getThumbnailResult,
+	listPageResult,
 	toggleFavoriteResult,
 } from "./db";
 
@@ -14,133 +15,87 @@ describe("db", () => {
 		vi.clearAllMocks();
 	});
 
-	describe("addClipResult", () => {
-		it("returns ok result when successful", async () => {
+	describe("listPageResult", () => {
+		it("returns the page when successful", async () => {
 			const mockApi = getMockElectronAPI();
+			const page = { items: createMockHistoryItems(3), nextCursor: "abc" };
+			mockApi.db.listPage.mockResolvedValue(page);
 
-			const result = await addClipResult({ text: "test content" });
+			const result = await listPageResult();
 
 			expect(result.ok).toBe(true);
-			expect(mockApi.db.addClip).toHaveBeenCalledWith({ text: "test content" });
+			if (result.ok) {
+				expect(result.value).toEqual(page);
+			}
+			expect(mockApi.db.listPage).toHaveBeenCalledWith({});
+			expect.assertions(3);
 		});
 
-		it("returns ok result with RTF when provided", async () => {
+		it("passes cursor, page size and favorites filter", async () => {
 			const mockApi = getMockElectronAPI();
 
-			const result = await addClipResult({
-				text: "test content",
-				rtf: "{\\rtf1 test}",
+			await listPageResult({
+				cursor: "abc",
+				pageSize: 25,
+				favoritesOnly: true,
 			});
 
-			expect(result.ok).toBe(true);
-			expect(mockApi.db.addClip).toHaveBeenCalledWith({
-				text: "test content",
-				rtf: "{\\rtf1 test}",
+			expect(mockApi.db.listPage).toHaveBeenCalledWith({
+				cursor: "abc",
+				pageSize: 25,
+				favoritesOnly: true,
 			});
 		});
 
 		it("returns error result when API call fails", async () => {
 			const mockApi = getMockElectronAPI();
-			mockApi.db.addClip.mockRejectedValue(new Error("Add failed"));
+			mockApi.db.listPage.mockRejectedValue(new Error("Query failed"));
 
-			const result = await addClipResult({ text: "test content" });
+			const result = await listPageResult();
 
 			expect(result.ok).toBe(false);
 			if (!result.ok) {
 				expect(result.error.type).toBe("QUERY_FAILED");
-				expect(result.error.message).toBe("Failed to add clip");
+				expect(result.error.message).toBe("Failed to get history");
 			}
 			expect.assertions(3);
 		});
 	});
 
-	describe("getHistoryResult", () => {
-		it("returns ok result with items when successful", async () => {
-			const mockApi = getMockElectronAPI();
-			const mockItems = createMockHistoryItems(3);
-			mockApi.db.getHistory.mockResolvedValue(mockItems);
-
-			const result = await getHistoryResult();
-
-			expect(result.ok).toBe(true);
-			if (result.ok) {
-				expect(result.value).toEqual(mockItems);
-			}
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({});
-			expect.assertions(3);
-		});
-
-		it("passes query parameter correctly", async () => {
-			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockResolvedValue([]);
-
-			await getHistoryResult({ query: "search term" });
-
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({
-				query: "search term",
-			});
-		});
-
-		it("passes limit parameter correctly", async () => {
-			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockResolvedValue([]);
-
-			await getHistoryResult({ limit: 100 });
-
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({ limit: 100 });
-		});
-
-		it("passes favoritesOnly parameter correctly", async () => {
+	describe("getThumbnailResult", () => {
+		it("returns the thumbnail data URL when successful", async () => {
 			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockResolvedValue([]);
+			mockApi.db.getThumbnail.mockResolvedValue("data:image/png;base64,AA==");
 
-			await getHistoryResult({ favoritesOnly: true });
+			const result = await getThumbnailResult(5);
 
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({
-				favoritesOnly: true,
-			});
+			expect(result).toEqual({ ok: true, value: "data:image/png;base64,AA==" });
+			expect(mockApi.db.getThumbnail).toHaveBeenCalledWith(5);
 		});
 
-		it("passes offset parameter correctly", async () => {
-			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockResolvedValue([]);
-
-			await getHistoryResult({ offset: 50 });
-
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({ offset: 50 });
-		});
-
-		it("passes all parameters correctly", async () => {
+		it("returns error result when API call fails", async () => {
 			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockResolvedValue([]);
+			mockApi.db.getThumbnail.mockRejectedValue(new Error("Decode failed"));
 
-			await getHistoryResult({
-				query: "test",
-				limit: 25,
-				favoritesOnly: true,
-				offset: 100,
-			});
+			const result = await getThumbnailResult(5);
 
-			expect(mockApi.db.getHistory).toHaveBeenCalledWith({
-				query: "test",
-				limit: 25,
-				favoritesOnly: true,
-				offset: 100,
-			});
+			expect(result.ok).toBe(false);
+			if (!result.ok) {
+				expect(result.error.message).toBe("Failed to load thumbnail");
+			}
+			expect.assertions(2);
 		});
+	});
 
-		it("returns error result when API call fails", async () => {
+	describe("getFaviconResult", () => {
+		it("returns the site icon data URL when successful", async () => {
 			const mockApi = getMockElectronAPI();
-			mockApi.db.getHistory.mockRejectedValue(new Error("Query failed"));
+			mockApi.favicons.get.mockResolvedValue("data:image/png;base64,AA==");
 
-			const result = await getHistoryResult();
+			const result = await getFaviconResult("github.com");
 
-			expect(result.ok).toBe(false);
-			if (!result.ok) {
-				expect(result.error.type).toBe("QUERY_FAILED");
-				expect(result.error.message).toBe("Failed to get history");
-			}
-			expect.assertions(3);
+			expect(result).toEqual({ ok: true, value: "data:image/png;base64,AA==" });
+			expect(mockApi.favicons.get).toHaveBeenCalledWith("github.com");
 		});
 	});
 
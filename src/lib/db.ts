@@ -2,20 +2,17 @@ import { type DbError, queryFailed } from "./errors";
 import { type Result, tryCatchAsync } from "./fp";
 import { waitForElectronAPIResult } from "./utils";
 
-export interface HistoryItem {
-	id: number;
-	content: string;
-	type: string;
-	created_at: string;
-	is_favorite: number;
-	rtf: string | null;
-}
+export type HistoryItem = HistoryRecord;
 
-export interface GetHistoryOptions {
-	query?: string;
-	limit?: number;
+export interface ListPageOptions {
+	/** Opaque cursor from a previous page; omit for the newest items */
+	cursor?: string | null;
+	pageSize?: number;
 	favoritesOnly?: boolean;
-	offset?: number;
+	tags?: string[];
+	sourceApp?: string;
+	kind?: ContentKindRecord;
+	language?: CodeLanguageRecord;
 }
 
 async function withElectronAPI<T>(
@@ -29,15 +26,86 @@ async function withElectronAPI<T>(
 }
 
 /**
- * Get history items with pagination support.
+ * Get a page of history items, newest first, using keyset pagination.
  * Returns a Result for explicit error handling.
  */
-export const getHistoryResult = (options: GetHistoryOptions = {}) =>
+export const listPageResult = (options: ListPageOptions = {}) =>
 	withElectronAPI(
-		() => window.electronAPI.db.getHistory(options),
+		() => window.electronAPI.db.listPage(options),
 		"Failed to get history",
 	);
 
+export interface SearchHistoryOptions {
+	query: string;
+	limit?: number;
+	favoritesOnly?: boolean;
+	tags?: string[];
+	sourceApp?: string;
+	kind?: ContentKindRecord;
+	language?: CodeLanguageRecord;
+	offset?: number;
+}
+
+/**
+ * Full-text search over history content, best match first.
+ * Returns a Result for explicit error handling.
+ */
+export const searchHistoryResult = (options: SearchHistoryOptions) =>
+	withElectronAPI(
+		() => window.electronAPI.db.searchHistory(options),
+		"Failed to search history",
+	);
+
+/**
+ * Fuzzy (subsequence) search over history content, best match first.
+ * Returns a Result for explicit error handling.
+ */
+export const fuzzySearchHistoryResult = (options: SearchHistoryOptions) =>
+	withElectronAPI(
+		() => window.electronAPI.db.fuzzySearchHistory(options),
+		"Failed to search history",
+	);
+
+/**
+ * Regex search over history content (query is the pattern), newest first.
+ * Returns a Result for explicit error handling.
+ */
+export const regexSearchHistoryResult = (options: SearchHistoryOptions) =>
+	withElectronAPI(
+		() => window.electronAPI.db.regexSearchHistory(options),
+		"Failed to search history",
+	);
+
+/**
+ * Get the cached list thumbnail of an image history item.
+ * Returns a Result containing a data URL, or null if the item has no image.
+ */
+export const getThumbnailResult = (id: number) =>
+	withElectronAPI(
+		() => window.electronAPI.db.getThumbnail(id),
+		"Failed to load thumbnail",
+	);
+
+/**
+ * Get the cached icon of a site.
+ * Returns a Result containing a data URL, or null if the site has none.
+ */
+export const getFaviconResult = (domain: string) =>
+	withElectronAPI(
+		() => window.electronAPI.favicons.get(domain),
+		"Failed to load site icon",
+	);
+
+/**
+ * Copy the decoded text of the QR code in an image history item.
+ * Returns a Result for explicit error handling.
+ */
+export const copyQrPayloadResult = (id: number) =>
+	withElectronAPI(
+		() => window.electronAPI.db.copyQrPayload(id),
+		"Failed to copy QR code text",
+	);
+
 /**
  * Delete a history item by ID.
  * Returns a Result for explicit error handling.
@@ -76,15 +144,14 @@ export async function toggleFavoriteResult(
 }
 
 /**
- * Add a clipboard entry to the database.
- * Deduplication is performed in the database layer (electron/main.ts).
- * Returns a Result for explicit error handling.
+ * Pin or unpin a history item.
+ * Returns a Result containing the new pinned state.
  */
-export async function addClipResult(
-	data: ClipboardData,
-): Promise<Result<void, DbError>> {
+export async function togglePinResult(
+	id: number,
+): Promise<Result<boolean, DbError>> {
 	return withElectronAPI(
-		() => window.electronAPI.db.addClip(data),
-		"Failed to add clip",
+		() => window.electronAPI.db.togglePin(id),
+		"Failed to toggle pin",
 	);
 }
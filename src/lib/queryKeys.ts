@@ -3,11 +3,21 @@
  * Provides consistent query keys for cache management
  */
 
+/**
+ * How the search query is matched against history content
+ * - text: full-text word/prefix search
+ * - fuzzy: ordered subsequence match ("gthb" finds "GitHub")
+ * - regex: case-insensitive regular expression
+ */
+export type SearchMode = "text" | "fuzzy" | "regex";
+
 export interface HistoryQueryFilters {
 	/** Search query string */
 	searchQuery: string;
 	/** Whether to show only favorites */
 	favoritesOnly: boolean;
+	/** How the search query is matched (default: text) */
+	searchMode?: SearchMode;
 }
 
 /**
@@ -32,4 +42,25 @@ export const historyKeys = {
 	 * @param id - The history item ID
 	 */
 	detail: (id: number) => [...historyKeys.all, "detail", id] as const,
+
+	/**
+	 * Key for the list thumbnail of an image item
+	 * @param id - The history item ID
+	 */
+	thumbnail: (id: number) => [...historyKeys.all, "thumbnail", id] as const,
+} as const;
+
+/**
+ * Query key factory for site icons
+ * Kept apart from history keys so history changes do not refetch them
+ */
+export const faviconKeys = {
+	/** Base key for all site icon queries */
+	all: ["favicons"] as const,
+
+	/**
+	 * Key for the icon of one site
+	 * @param domain - Host name of the site
+	 */
+	domain: (domain: string) => [...faviconKeys.all, domain] as const,
 } as const;
@@ -2,7 +2,12 @@ import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
 import { MAX_TEXT_DISPLAY_LENGTH } from "./constants";
 import {
 	formatDate,
+	formatFileListLabel,
+	getLinkDomain,
 	hasMoreItems,
+	isValidRegexPattern,
+	parseSearchFilters,
+	parseTagFilters,
 	retryWithBackoff,
 	truncateText,
 } from "./utils";
@@ -244,6 +249,108 @@ describe("retryWithBackoff", () => {
 	});
 });
 
+describe("formatFileListLabel", () => {
+	it("shows the file name of a single file", () => {
+		expect(formatFileListLabel("/home/me/report.pdf")).toBe("report.pdf");
+		expect(formatFileListLabel("C:\\Users\\me\\notes.txt")).toBe("notes.txt");
+	});
+
+	it("counts additional files", () => {
+		expect(formatFileListLabel("/tmp/a.png\n/tmp/b.png\n/tmp/photos/")).toBe(
+			"a.png + 2 more",
+		);
+	});
+});
+
+describe("getLinkDomain", () => {
+	it("returns the host of web links", () => {
+		expect(getLinkDomain("https://GitHub.com/org/repo")).toBe("github.com");
+		expect(getLinkDomain(" www.example.com/page ")).toBe("www.example.com");
+	});
+
+	it("returns null for other links", () => {
+		expect(getLinkDomain("ftp://example.com/file")).toBeNull();
+		expect(getLinkDomain("not a url")).toBeNull();
+	});
+});
+
+describe("isValidRegexPattern", () => {
+	it("accepts valid patterns and rejects incomplete ones", () => {
+		expect(isValidRegexPattern("\\d+\\.\\d+")).toBe(true);
+		expect(isValidRegexPattern("(10\\.")).toBe(false);
+		expect(isValidRegexPattern("[a-")).toBe(false);
+	});
+});
+
+describe("parseTagFilters", () => {
+	it("extracts tag filters and keeps the remaining query", () => {
+		expect(parseTagFilters("tag:SQL select tag:work")).toEqual({
+			query: "select",
+			tags: ["sql", "work"],
+		});
+	});
+
+	it("ignores tag: inside other words", () => {
+		expect(parseTagFilters("html-tag:x")).toEqual({
+			query: "html-tag:x",
+			tags: [],
+		});
+	});
+});
+
+describe("parseSearchFilters", () => {
+	it("extracts app and tag filters", () => {
+		expect(parseSearchFilters("app:Slack tag:work deploy")).toEqual({
+			query: "deploy",
+			tags: ["work"],
+			sourceApp: "Slack",
+		});
+	});
+
+	it("accepts quoted app names with spaces", () => {
+		expect(parseSearchFilters('app:"VS Code" fn')).toEqual({
+			query: "fn",
+			tags: [],
+			sourceApp: "VS Code",
+		});
+	});
+
+	it("leaves queries without an app filter unchanged", () => {
+		expect(parseSearchFilters("webapp:x")).toEqual({
+			query: "webapp:x",
+			tags: [],
+			sourceApp: undefined,
+		});
+	});
+
+	it("extracts known content kinds", () => {
+		expect(parseSearchFilters("kind:URL github")).toEqual({
+			query: "github",
+			tags: [],
+			sourceApp: undefined,
+			kind: "url",
+		});
+		expect(parseSearchFilters("kind:recipe soup")).toEqual({
+			query: "kind:recipe soup",
+			tags: [],
+			sourceApp: undefined,
+			kind: undefined,
+		});
+	});
+
+	it("extracts code languages by name or short form", () => {
+		expect(parseSearchFilters("lang:py retry").language).toBe("python");
+		expect(parseSearchFilters("lang:C++ vector").language).toBe("cpp");
+		expect(parseSearchFilters("lang:cobol x")).toEqual({
+			query: "lang:cobol x",
+			tags: [],
+			sourceApp: undefined,
+			kind: undefined,
+			language: undefined,
+		});
+	});
+});
+
 describe("hasMoreItems", () => {
 	it("returns true when results count equals batch size", () => {
 		expect(hasMoreItems(100, 100)).toBe(true);
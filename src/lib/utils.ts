@@ -1,6 +1,10 @@
 import { format } from "date-fns";
 import { waitForCondition } from "../utils";
-import { MAX_TEXT_DISPLAY_LENGTH } from "./constants";
+import {
+	CODE_LANGUAGE_NAMES,
+	CONTENT_KINDS,
+	MAX_TEXT_DISPLAY_LENGTH,
+} from "./constants";
 import {
 	type DbError,
 	dbNotReady,
@@ -40,6 +44,15 @@ export function formatDate(dateString: string): string {
 	}
 }
 
+/**
+ * Formats a timestamp as a local time of day
+ * @param timestamp - Epoch milliseconds
+ * @returns Localized time (e.g., "2:35 PM")
+ */
+export function formatTime(timestamp: number): string {
+	return format(timestamp, "p");
+}
+
 /**
  * Truncates text to a maximum length with ellipsis
  * @param text - Text to truncate
@@ -54,6 +67,117 @@ export function truncateText(
 	return `${text.substring(0, maxLength)}...`;
 }
 
+/**
+ * Builds a short label for a stored file-list item
+ * @param content - Newline-separated paths as stored in the history row
+ * @returns The first file name, with a count of any additional files
+ */
+export function formatFileListLabel(content: string): string {
+	const paths = content.split("\n").filter(Boolean);
+	if (paths.length === 0) return "";
+
+	const firstName = paths[0].split(/[\\/]/).filter(Boolean).pop() ?? paths[0];
+	const remaining = paths.length - 1;
+	return remaining > 0 ? `${firstName} + ${remaining} more` : firstName;
+}
+
+/**
+ * Gets the host name a copied link points to
+ * @param url - Content of a URL item, e.g. `https://github.com/x`
+ * @returns The lowercased host name, or null if the link has none
+ */
+export function getLinkDomain(url: string): string | null {
+	const trimmed = url.trim();
+	try {
+		const { protocol, hostname } = new URL(
+			/^www\./i.test(trimmed) ? `https://${trimmed}` : trimmed,
+		);
+		return /^https?:$/.test(protocol) && hostname ? hostname : null;
+	} catch {
+		return null;
+	}
+}
+
+/**
+ * Checks whether a string compiles as a regular expression
+ * @param pattern - Pattern typed by the user
+ * @returns true if the pattern is a valid regular expression
+ */
+export function isValidRegexPattern(pattern: string): boolean {
+	try {
+		new RegExp(pattern, "i");
+		return true;
+	} catch {
+		return false;
+	}
+}
+
+/**
+ * Splits `tag:name` filters out of a search query
+ * e.g. "tag:sql select" searches for "select" among items tagged "sql"
+ * @param input - Search query typed by the user
+ * @returns The remaining search text and the tag names to filter by
+ */
+export function parseTagFilters(input: string): {
+	query: string;
+	tags: string[];
+} {
+	const tags: string[] = [];
+	const query = input
+		.replace(/(^|\s)tag:(\S+)/gi, (_match, prefix: string, tag: string) => {
+			tags.push(tag.toLowerCase());
+			return prefix;
+		})
+		.trim();
+	return { query, tags };
+}
+
+/**
+ * Splits `tag:name`, `app:name`, `kind:name` and `lang:name` filters out
+ * of a search query
+ * e.g. `app:slack deploy` searches for "deploy" among items copied from
+ * Slack; quote names with spaces (`app:"VS Code"`). `kind:url` keeps
+ * only links and `lang:py` only Python code; unknown kinds and languages
+ * stay in the search text. The last `app:`, `kind:` and `lang:` win.
+ * @param input - Search query typed by the user
+ * @returns The remaining search text, tag names, source app, content
+ * kind and code language to filter by
+ */
+export function parseSearchFilters(input: string): {
+	query: string;
+	tags: string[];
+	sourceApp: string | undefined;
+	kind: ContentKindRecord | undefined;
+	language: CodeLanguageRecord | undefined;
+} {
+	let sourceApp: string | undefined;
+	let kind: ContentKindRecord | undefined;
+	let language: CodeLanguageRecord | undefined;
+	const { query, tags } = parseTagFilters(
+		input
+			.replace(
+				/(^|\s)app:(?:"([^"]+)"|(\S+))/gi,
+				(_match, prefix: string, quoted?: string, bare?: string) => {
+					sourceApp = (quoted ?? bare)?.trim() || sourceApp;
+					return prefix;
+				},
+			)
+			.replace(/(^|\s)kind:(\S+)/gi, (match, prefix: string, name: string) => {
+				const known = CONTENT_KINDS.find((k) => k === name.toLowerCase());
+				if (!known) return match;
+				kind = known;
+				return prefix;
+			})
+			.replace(/(^|\s)lang:(\S+)/gi, (match, prefix: string, name: string) => {
+				const known = CODE_LANGUAGE_NAMES.get(name.toLowerCase());
+				if (!known) return match;
+				language = known;
+				return prefix;
+			}),
+	);
+	return { query, tags, sourceApp, kind, language };
+}
+
 export interface RetryOperationOptions<T> {
 	operation: () => Promise<T>;
 	maxRetries?: number;
@@ -4,6 +4,7 @@ import ReactDOM from "react-dom/client";
 import { App } from "./App";
 import "./index.css";
 import { QUERY_GC_TIME, QUERY_STALE_TIME } from "./lib/constants";
+import { PickerApp } from "./PickerApp";
 
 /**
  * TanStack Query client with optimized settings for clipboard manager
@@ -19,10 +20,16 @@ const queryClient = new QueryClient({
 	},
 });
 
+/**
+ * The main process loads the quick picker window with `?view=picker`
+ */
+const isPicker =
+	new URLSearchParams(window.location.search).get("view") === "picker";
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
 	<React.StrictMode>
 		<QueryClientProvider client={queryClient}>
-			<App />
+			{isPicker ? <PickerApp /> : <App />}
 		</QueryClientProvider>
 	</React.StrictMode>,
 );
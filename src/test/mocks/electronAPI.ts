@@ -1,5 +1,6 @@
 import type { Mock } from "vitest";
 import { vi } from "vitest";
+import { createMockHistoryItem } from "./history";
 
 /**
  * Mock implementation of the ElectronAPI interface
@@ -9,29 +10,718 @@ export interface MockElectronAPI {
 	clipboard: {
 		read: Mock<() => Promise<ClipboardData>>;
 		write: Mock<(data: ClipboardData) => Promise<void>>;
+		restoreItem: Mock<
+			(id: number, options?: { files?: FileRestoreModeRecord }) => Promise<void>
+		>;
+		pasteItem: Mock<
+			(id: number, options?: { files?: FileRestoreModeRecord }) => Promise<void>
+		>;
+		typeItem: Mock<(id: number) => Promise<void>>;
 	};
 	db: {
 		getHistory: Mock<
 			(options?: {
 				query?: string;
 				limit?: number;
+				order?: HistoryOrderRecord;
 				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
 				offset?: number;
-			}) => Promise<
-				Array<{
-					id: number;
-					content: string;
-					type: string;
-					created_at: string;
-					is_favorite: number;
-					rtf: string | null;
-				}>
-			>
-		>;
-		addClip: Mock<(data: ClipboardData) => Promise<void>>;
+			}) => Promise<HistoryRecord[]>
+		>;
+		listPage: Mock<
+			(options?: {
+				cursor?: string | null;
+				pageSize?: number;
+				order?: HistoryOrderRecord;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
+			}) => Promise<{ items: HistoryRecord[]; nextCursor: string | null }>
+		>;
+		listGroupedPage: Mock<
+			(options?: {
+				cursor?: string | null;
+				pageSize?: number;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
+			}) => Promise<{ groups: HistoryGroupRecord[]; nextCursor: string | null }>
+		>;
+		revealItem: Mock<(id: number) => Promise<HistoryRecord>>;
+		openItem: Mock<(id: number) => Promise<void>>;
+		showItemInFolder: Mock<(id: number) => Promise<void>>;
+		searchHistory: Mock<
+			(options: {
+				query: string;
+				limit?: number;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
+				offset?: number;
+			}) => Promise<SearchResultRecord[]>
+		>;
+		fuzzySearchHistory: Mock<
+			(options: {
+				query: string;
+				limit?: number;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
+				offset?: number;
+			}) => Promise<SearchResultRecord[]>
+		>;
+		regexSearchHistory: Mock<
+			(options: {
+				query: string;
+				limit?: number;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+				kind?: ContentKindRecord;
+				language?: CodeLanguageRecord;
+				selection?: SelectionSourceRecord;
+				offset?: number;
+			}) => Promise<SearchResultRecord[]>
+		>;
+		semanticSearch: Mock<
+			(query: string, limit?: number) => Promise<SemanticSearchRecord[]>
+		>;
 		deleteHistoryItem: Mock<(id: number) => Promise<void>>;
 		clearAllHistory: Mock<() => Promise<void>>;
+		listTrash: Mock<
+			(options?: { limit?: number; offset?: number }) => Promise<TrashRecord[]>
+		>;
+		restoreFromTrash: Mock<(id: number) => Promise<number>>;
+		emptyTrash: Mock<() => Promise<number>>;
+		requestWipe: Mock<() => Promise<{ token: string; expiresAt: string }>>;
+		wipeAllHistory: Mock<(token: string) => Promise<void>>;
+		getImagePreview: Mock<(id: number) => Promise<string | null>>;
+		getThumbnail: Mock<(id: number) => Promise<string | null>>;
+		copyQrPayload: Mock<(id: number) => Promise<void>>;
+		generateQr: Mock<
+			(id: number, options?: { copy?: boolean }) => Promise<string>
+		>;
+		copyImageAs: Mock<
+			(id: number, conversion: ImageConversionRecord) => Promise<void>
+		>;
+		stripMetadata: Mock<(id: number) => Promise<boolean>>;
+		saveImageAs: Mock<
+			(
+				id: number,
+				conversion: ImageConversionRecord,
+				filePath: string,
+			) => Promise<void>
+		>;
+		saveItemToFile: Mock<
+			(
+				id: number,
+				filePath: string,
+				options?: { conversion?: ImageConversionRecord; overwrite?: boolean },
+			) => Promise<void>
+		>;
+		getColorFormats: Mock<
+			(id: number) => Promise<Record<ColorFormatRecord, string>>
+		>;
+		copyColorAs: Mock<(id: number, format: ColorFormatRecord) => Promise<void>>;
+		diffItems: Mock<(oldId: number, newId: number) => Promise<TextDiffRecord>>;
+		transformItem: Mock<
+			(id: number, request: TextTransformRequestRecord) => Promise<void>
+		>;
+		formatItem: Mock<
+			(id: number, request: FormatRequestRecord) => Promise<void>
+		>;
+		convertMarkup: Mock<
+			(id: number, request: MarkupConversionRequestRecord) => Promise<void>
+		>;
+		runScript: Mock<
+			(
+				id: number,
+				request: { name: string; target?: "item" | "clipboard" },
+			) => Promise<void>
+		>;
+		runCommandAction: Mock<
+			(
+				id: number,
+				name: string,
+			) => Promise<{ output: string; recorded: boolean }>
+		>;
+		hashItem: Mock<
+			(id: number, algorithm: HashAlgorithmRecord) => Promise<string>
+		>;
 		toggleFavorite: Mock<(id: number) => Promise<boolean>>;
+		togglePin: Mock<(id: number) => Promise<boolean>>;
+		batchUpdate: Mock<
+			(ids: number[], operation: BatchOperationRecord) => Promise<number>
+		>;
+		toggleSyncExcluded: Mock<(id: number) => Promise<boolean>>;
+		togglePasteOnce: Mock<(id: number) => Promise<boolean>>;
+		setItemNote: Mock<(id: number, text: string) => Promise<void>>;
+		setItemExpiry: Mock<
+			(id: number, expiresAt: string | null) => Promise<void>
+		>;
+		dismissSecret: Mock<(id: number) => Promise<boolean>>;
+		mergeItems: Mock<
+			(
+				ids: number[],
+				separator: string,
+				copy?: boolean,
+			) => Promise<HistoryRecord>
+		>;
+		findNearDuplicates: Mock<(maxDistance?: number) => Promise<number[][]>>;
+		findNearDuplicateTexts: Mock<(maxDistance?: number) => Promise<number[][]>>;
+		mergeDuplicates: Mock<(ids: number[]) => Promise<HistoryRecord>>;
+		listSourceApps: Mock<
+			() => Promise<Array<{ name: string; count: number }>>
+		>;
+		exportHistory: Mock<
+			(
+				format: "json" | "csv",
+				filePath: string,
+				filters?: {
+					pinnedOnly?: boolean;
+					favoritesOnly?: boolean;
+					tags?: string[];
+					sourceApp?: string;
+				},
+			) => Promise<number>
+		>;
+		exportSnippets: Mock<
+			(
+				format: "alfred" | "raycast",
+				filePath: string,
+				source: "pinned" | { collectionId: number },
+			) => Promise<number>
+		>;
+		listAccessLog: Mock<
+			(query?: AccessLogQueryRecord) => Promise<AccessLogEntryRecord[]>
+		>;
+		importHistory: Mock<
+			(filePath: string) => Promise<{ imported: number; skipped: number }>
+		>;
+		importCopyQ: Mock<
+			(sourcePath: string) => Promise<{ imported: number; skipped: number }>
+		>;
+		importDitto: Mock<
+			(dbPath: string) => Promise<{ imported: number; skipped: number }>
+		>;
+		importMaccy: Mock<
+			(dbPath: string) => Promise<{ imported: number; skipped: number }>
+		>;
+		runMaintenance: Mock<
+			() => Promise<{
+				sizeBefore: number;
+				sizeAfter: number;
+				reclaimedBytes: number;
+				durationMs: number;
+			}>
+		>;
+		checkDatabase: Mock<() => Promise<DatabaseCheckRecord>>;
+		repairDatabase: Mock<() => Promise<DatabaseRecoveryRecord>>;
+		getLastRecovery: Mock<() => Promise<DatabaseRecoveryRecord | null>>;
+		addTag: Mock<(id: number, name: string) => Promise<string[]>>;
+		removeTag: Mock<(id: number, name: string) => Promise<string[]>>;
+		getItemTags: Mock<(id: number) => Promise<string[]>>;
+		listTags: Mock<() => Promise<Array<{ name: string; count: number }>>>;
+	};
+	collections: {
+		list: Mock<() => Promise<CollectionRecord[]>>;
+		create: Mock<(name: string) => Promise<CollectionRecord>>;
+		rename: Mock<(id: number, name: string) => Promise<CollectionRecord>>;
+		delete: Mock<(id: number) => Promise<void>>;
+		getItems: Mock<(id: number) => Promise<HistoryRecord[]>>;
+		addItem: Mock<(collectionId: number, itemId: number) => Promise<void>>;
+		removeItem: Mock<(collectionId: number, itemId: number) => Promise<void>>;
+		moveItem: Mock<
+			(options: {
+				itemId: number;
+				fromCollectionId: number;
+				toCollectionId: number;
+			}) => Promise<void>
+		>;
+		reorderItems: Mock<
+			(collectionId: number, itemIds: number[]) => Promise<void>
+		>;
+	};
+	retention: {
+		getPolicy: Mock<() => Promise<RetentionPolicyRecord>>;
+		updatePolicy: Mock<
+			(policy: Partial<RetentionPolicyRecord>) => Promise<RetentionPolicyRecord>
+		>;
+	};
+	backup: {
+		get: Mock<() => Promise<BackupStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				directory?: string;
+				passphrase?: string;
+				intervalHours?: number;
+				keep?: number;
+			}) => Promise<BackupStatusRecord>
+		>;
+		backupNow: Mock<() => Promise<BackupStatusRecord>>;
+		restore: Mock<
+			(backupPath: string, passphrase?: string) => Promise<BackupStatusRecord>
+		>;
+	};
+	lock: {
+		getStatus: Mock<() => Promise<AppLockStatusRecord>>;
+		lock: Mock<() => Promise<AppLockStatusRecord>>;
+		unlock: Mock<() => Promise<AppLockStatusRecord>>;
+		updateSettings: Mock<
+			(settings: {
+				enabled?: boolean;
+				autoLockMinutes?: number | null;
+			}) => Promise<AppLockStatusRecord>
+		>;
+	};
+	capture: {
+		getStatus: Mock<() => Promise<CapturePauseStatusRecord>>;
+		setPaused: Mock<(paused: boolean) => Promise<CapturePauseStatusRecord>>;
+		snooze: Mock<(minutes: number) => Promise<CapturePauseStatusRecord>>;
+	};
+	readOnly: {
+		getStatus: Mock<() => Promise<ReadOnlyStatusRecord>>;
+		updateSettings: Mock<
+			(settings: { enabled?: boolean }) => Promise<ReadOnlyStatusRecord>
+		>;
+	};
+	sounds: {
+		get: Mock<() => Promise<CaptureSoundSettingsRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				sounds?: Partial<Record<SoundEventRecord, string>>;
+			}) => Promise<CaptureSoundSettingsRecord>
+		>;
+		preview: Mock<(event: SoundEventRecord) => Promise<void>>;
+	};
+	captureLimits: {
+		getSettings: Mock<() => Promise<CaptureLimitSettingsRecord>>;
+		updateSettings: Mock<
+			(settings: {
+				text?: Partial<CaptureLimitSettingsRecord["text"]>;
+				richText?: Partial<CaptureLimitSettingsRecord["richText"]>;
+				image?: Partial<CaptureLimitSettingsRecord["image"]>;
+			}) => Promise<CaptureLimitSettingsRecord>
+		>;
+	};
+	primarySelection: {
+		getSettings: Mock<() => Promise<PrimarySelectionSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<PrimarySelectionSettingsRecord>,
+			) => Promise<PrimarySelectionSettingsRecord>
+		>;
+	};
+	powerThrottle: {
+		getSettings: Mock<() => Promise<PowerThrottleSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<PowerThrottleSettingsRecord>,
+			) => Promise<PowerThrottleSettingsRecord>
+		>;
+		getStatus: Mock<() => Promise<PowerThrottleStatusRecord>>;
+	};
+	universalClipboard: {
+		getSettings: Mock<() => Promise<UniversalClipboardSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<UniversalClipboardSettingsRecord>,
+			) => Promise<UniversalClipboardSettingsRecord>
+		>;
+	};
+	clipboardRestore: {
+		getSettings: Mock<() => Promise<ClipboardRestoreSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<ClipboardRestoreSettingsRecord>,
+			) => Promise<ClipboardRestoreSettingsRecord>
+		>;
+	};
+	appendCopy: {
+		getStatus: Mock<() => Promise<AppendCopyStatusRecord>>;
+		setActive: Mock<(active: boolean) => Promise<AppendCopyStatusRecord>>;
+		updateSettings: Mock<
+			(settings: { separator?: string }) => Promise<AppendCopyStatusRecord>
+		>;
+	};
+	autoClear: {
+		getStatus: Mock<() => Promise<AutoClearStatusRecord>>;
+		updateSettings: Mock<
+			(settings: {
+				enabled?: boolean;
+				clearAfterSeconds?: number;
+			}) => Promise<AutoClearStatusRecord>
+		>;
+		cancel: Mock<() => Promise<AutoClearStatusRecord>>;
+	};
+	exclusions: {
+		get: Mock<() => Promise<AppExclusionSettingsRecord>>;
+		update: Mock<
+			(settings: {
+				excludedApps?: string[];
+			}) => Promise<AppExclusionSettingsRecord>
+		>;
+	};
+	secrets: {
+		getSettings: Mock<() => Promise<SecretScanningSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<SecretScanningSettingsRecord>,
+			) => Promise<SecretScanningSettingsRecord>
+		>;
+	};
+	notifications: {
+		get: Mock<() => Promise<NotificationSettingsRecord>>;
+		update: Mock<
+			(
+				settings: Partial<NotificationSettingsRecord>,
+			) => Promise<NotificationSettingsRecord>
+		>;
+	};
+	scripts: {
+		list: Mock<() => Promise<UserScriptRecord[]>>;
+		install: Mock<
+			(script: {
+				name: string;
+				source: string;
+				onCapture?: boolean;
+			}) => Promise<UserScriptRecord[]>
+		>;
+		remove: Mock<(name: string) => Promise<UserScriptRecord[]>>;
+		test: Mock<
+			(request: {
+				name?: string;
+				source?: string;
+				text: string;
+				type?: "text" | "image" | "files";
+			}) => Promise<ScriptResultRecord>
+		>;
+	};
+	commandActions: {
+		getSettings: Mock<() => Promise<CommandActionSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<CommandActionSettingsRecord>,
+			) => Promise<CommandActionSettingsRecord>
+		>;
+	};
+	captureRules: {
+		getSettings: Mock<() => Promise<CaptureRuleSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<CaptureRuleSettingsRecord>,
+			) => Promise<CaptureRuleSettingsRecord>
+		>;
+	};
+	linkPreviews: {
+		getSettings: Mock<() => Promise<LinkPreviewSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<LinkPreviewSettingsRecord>,
+			) => Promise<LinkPreviewSettingsRecord>
+		>;
+	};
+	semanticSearch: {
+		getSettings: Mock<() => Promise<SemanticSearchSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<SemanticSearchSettingsRecord>,
+			) => Promise<SemanticSearchSettingsRecord>
+		>;
+	};
+	itemSummaries: {
+		getSettings: Mock<() => Promise<ItemSummarySettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<ItemSummarySettingsRecord>,
+			) => Promise<ItemSummarySettingsRecord>
+		>;
+	};
+	favicons: {
+		get: Mock<(domain: string) => Promise<string | null>>;
+	};
+	shortcuts: {
+		get: Mock<() => Promise<ShortcutSettingsRecord>>;
+		set: Mock<
+			(
+				action: ShortcutActionRecord,
+				accelerator: string | null,
+			) => Promise<ShortcutSettingsRecord>
+		>;
+		getStatus: Mock<() => Promise<ShortcutStatusRecord>>;
+		getFallbacks: Mock<() => Promise<ShortcutFallbacksRecord>>;
+		setFallbacks: Mock<
+			(
+				action: ShortcutActionRecord,
+				accelerators: string[],
+			) => Promise<ShortcutFallbacksRecord>
+		>;
+	};
+	chords: {
+		get: Mock<() => Promise<ChordStatusRecord>>;
+		update: Mock<
+			(settings: {
+				chords?: ShortcutChordRecord[];
+			}) => Promise<ChordStatusRecord>
+		>;
+	};
+	trayClicks: {
+		get: Mock<() => Promise<TrayClickSettingsRecord>>;
+		update: Mock<
+			(
+				settings: Partial<TrayClickSettingsRecord>,
+			) => Promise<TrayClickSettingsRecord>
+		>;
+	};
+	language: {
+		get: Mock<() => Promise<LanguageStatusRecord>>;
+		set: Mock<
+			(language: LanguageSettingRecord) => Promise<LanguageStatusRecord>
+		>;
+	};
+	queue: {
+		get: Mock<() => Promise<number[]>>;
+		toggle: Mock<(itemId: number) => Promise<number[]>>;
+		clear: Mock<() => Promise<number[]>>;
+		pasteNext: Mock<() => Promise<void>>;
+	};
+	registers: {
+		list: Mock<() => Promise<RegisterRecord[]>>;
+		copy: Mock<(name: string) => Promise<RegisterRecord>>;
+		paste: Mock<(name: string) => Promise<void>>;
+		delete: Mock<(name: string) => Promise<void>>;
+	};
+	snippets: {
+		list: Mock<(query?: string) => Promise<SnippetRecord[]>>;
+		getFields: Mock<(content: string) => Promise<string[]>>;
+		create: Mock<(input: SnippetInputRecord) => Promise<SnippetRecord>>;
+		update: Mock<
+			(
+				id: number,
+				input: Partial<SnippetInputRecord>,
+			) => Promise<SnippetRecord>
+		>;
+		delete: Mock<(id: number) => Promise<void>>;
+		paste: Mock<(id: number, fields?: Record<string, string>) => Promise<void>>;
+	};
+	stats: {
+		get: Mock<
+			(options?: {
+				days?: number;
+				weeks?: number;
+				topApps?: number;
+			}) => Promise<HistoryStatsRecord>
+		>;
+		heatmap: Mock<(year: number) => Promise<ActivityHeatmapRecord>>;
+	};
+	stack: {
+		get: Mock<() => Promise<ClipboardData[]>>;
+		clear: Mock<() => Promise<ClipboardData[]>>;
+	};
+	typeOut: {
+		getSettings: Mock<() => Promise<TypeOutSettingsRecord>>;
+		updateSettings: Mock<
+			(settings: { delayMs?: number }) => Promise<TypeOutSettingsRecord>
+		>;
+	};
+	textExpander: {
+		get: Mock<() => Promise<TextExpanderStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				excludedApps?: string[];
+			}) => Promise<TextExpanderStatusRecord>
+		>;
+	};
+	doubleCopy: {
+		get: Mock<() => Promise<DoubleCopyStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				intervalMs?: number;
+			}) => Promise<DoubleCopyStatusRecord>
+		>;
+	};
+	quickPaste: {
+		get: Mock<() => Promise<QuickPasteSettingsRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				pasteAfterCopy?: boolean;
+			}) => Promise<QuickPasteSettingsRecord>
+		>;
+	};
+	windowBehavior: {
+		get: Mock<() => Promise<WindowBehaviorSettingsRecord>>;
+		update: Mock<
+			(settings: {
+				hideOnBlur?: boolean;
+			}) => Promise<WindowBehaviorSettingsRecord>
+		>;
+	};
+	settings: {
+		get: Mock<() => Promise<SettingsFileStatusRecord>>;
+		update: Mock<
+			(settings: {
+				[Section in keyof AppSettingsRecord]?: Partial<
+					AppSettingsRecord[Section]
+				>;
+			}) => Promise<SettingsFileStatusRecord>
+		>;
+	};
+	dataLocation: {
+		get: Mock<() => Promise<DataLocationStatusRecord>>;
+		migrate: Mock<(target: string) => Promise<DataLocationStatusRecord>>;
+	};
+	profiles: {
+		get: Mock<() => Promise<ProfilesStatusRecord>>;
+		create: Mock<(name: string) => Promise<ProfilesStatusRecord>>;
+		switch: Mock<(name: string) => Promise<void>>;
+		remove: Mock<(name: string) => Promise<ProfilesStatusRecord>>;
+	};
+	logs: {
+		getRecent: Mock<
+			(query?: {
+				limit?: number;
+				level?: LogLevelRecord | null;
+				module?: string | null;
+			}) => Promise<LogEntryRecord[]>
+		>;
+		getSettings: Mock<() => Promise<LoggingSettingsRecord>>;
+		updateSettings: Mock<
+			(settings: {
+				level?: LogLevelRecord;
+				modules?: Record<string, LogLevelRecord | null>;
+			}) => Promise<LoggingSettingsRecord>
+		>;
+		openFolder: Mock<() => Promise<void>>;
+	};
+	diagnostics: {
+		listCrashes: Mock<() => Promise<CrashReportSummaryRecord[]>>;
+		export: Mock<(filePath: string) => Promise<DiagnosticsExportRecord>>;
+	};
+	updater: {
+		get: Mock<() => Promise<UpdaterStatusRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<Pick<UpdaterStatusRecord, "channel" | "autoCheck">>,
+			) => Promise<UpdaterStatusRecord>
+		>;
+		check: Mock<() => Promise<UpdaterStatusRecord>>;
+		download: Mock<() => Promise<UpdaterStatusRecord>>;
+		install: Mock<() => Promise<UpdaterStatusRecord>>;
+	};
+	httpApi: {
+		get: Mock<() => Promise<HttpApiStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				port?: number;
+				token?: string;
+			}) => Promise<HttpApiStatusRecord>
+		>;
+		regenerateToken: Mock<() => Promise<HttpApiStatusRecord>>;
+	};
+	urlActions: {
+		getSettings: Mock<() => Promise<UrlActionSettingsRecord>>;
+		updateSettings: Mock<
+			(
+				settings: Partial<UrlActionSettingsRecord>,
+			) => Promise<UrlActionSettingsRecord>
+		>;
+	};
+	webhooks: {
+		get: Mock<() => Promise<WebhookStatusRecord>>;
+		update: Mock<
+			(
+				settings: Partial<WebhookSettingsRecord>,
+			) => Promise<WebhookStatusRecord>
+		>;
+		regenerateSecret: Mock<() => Promise<WebhookStatusRecord>>;
+		test: Mock<() => Promise<void>>;
+	};
+	sync: {
+		get: Mock<() => Promise<SyncStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				deviceName?: string;
+				port?: number;
+			}) => Promise<SyncStatusRecord>
+		>;
+		startPairing: Mock<() => Promise<SyncStatusRecord>>;
+		cancelPairing: Mock<() => Promise<SyncStatusRecord>>;
+		pair: Mock<(deviceId: string, code: string) => Promise<SyncStatusRecord>>;
+		unpair: Mock<(deviceId: string) => Promise<SyncStatusRecord>>;
+	};
+	remoteSync: {
+		get: Mock<() => Promise<RemoteSyncStatusRecord>>;
+		update: Mock<
+			(settings: {
+				enabled?: boolean;
+				backend?: "webdav" | "s3";
+				url?: string;
+				bucket?: string;
+				region?: string;
+				prefix?: string;
+				username?: string;
+				password?: string;
+				passphrase?: string;
+				intervalMinutes?: number;
+			}) => Promise<RemoteSyncStatusRecord>
+		>;
+		syncNow: Mock<() => Promise<RemoteSyncStatusRecord>>;
+	};
+	boards: {
+		get: Mock<() => Promise<BoardsStatusRecord>>;
+		updateServer: Mock<
+			(settings: {
+				enabled?: boolean;
+				port?: number;
+			}) => Promise<BoardsStatusRecord>
+		>;
+		createBoard: Mock<
+			(
+				name: string,
+				passphrase: string,
+			) => Promise<{ invite: string; status: BoardsStatusRecord }>
+		>;
+		regenerateToken: Mock<
+			(
+				boardId: string,
+			) => Promise<{ invite: string; status: BoardsStatusRecord }>
+		>;
+		deleteBoard: Mock<(boardId: string) => Promise<BoardsStatusRecord>>;
+		join: Mock<
+			(input: {
+				invite: string;
+				passphrase: string;
+				name?: string;
+			}) => Promise<BoardsStatusRecord>
+		>;
+		leave: Mock<(boardId: string) => Promise<BoardsStatusRecord>>;
+		publish: Mock<(boardId: string, id: number) => Promise<number>>;
+		listItems: Mock<(boardId: string) => Promise<BoardItemRecord[]>>;
+		copyItem: Mock<(boardId: string, seq: number) => Promise<void>>;
 	};
 	window: {
 		center: Mock<() => Promise<void>>;
@@ -39,6 +729,43 @@ export interface MockElectronAPI {
 		hide: Mock<() => Promise<void>>;
 		isVisible: Mock<() => Promise<boolean>>;
 		hideAndPaste: Mock<() => Promise<void>>;
+		isPinned: Mock<() => Promise<boolean>>;
+		setPinned: Mock<(pinned: boolean) => Promise<boolean>>;
+	};
+	picker: {
+		show: Mock<() => Promise<void>>;
+		hide: Mock<() => Promise<void>>;
+		toggle: Mock<() => Promise<void>>;
+		isVisible: Mock<() => Promise<boolean>>;
+	};
+	events: {
+		onHistoryChanged: Mock<(callback: () => void) => () => void>;
+		onHistoryEvent: Mock<
+			(callback: (event: HistoryEventRecord) => void) => () => void
+		>;
+		onLockChanged: Mock<
+			(
+				callback: (locked: boolean, reason: LockChangeReasonRecord) => void,
+			) => () => void
+		>;
+		onCaptureChanged: Mock<
+			(callback: (status: CapturePauseStatusRecord) => void) => () => void
+		>;
+		onReadOnlyChanged: Mock<
+			(callback: (status: ReadOnlyStatusRecord) => void) => () => void
+		>;
+		onQueueChanged: Mock<(callback: (itemIds: number[]) => void) => () => void>;
+		onSettingsChanged: Mock<
+			(callback: (status: SettingsFileStatusRecord) => void) => () => void
+		>;
+		onUpdaterStatus: Mock<
+			(callback: (status: UpdaterStatusRecord) => void) => () => void
+		>;
+		onShortcutsStatus: Mock<
+			(callback: (status: ShortcutStatusRecord) => void) => () => void
+		>;
+		onSearchRequested: Mock<(callback: (query: string) => void) => () => void>;
+		onBoardsUpdated: Mock<(callback: (boardId: string) => void) => () => void>;
 	};
 	app: {
 		quit: Mock<() => Promise<void>>;
@@ -46,6 +773,275 @@ export interface MockElectronAPI {
 		setLaunchAtLogin: Mock<
 			(enabled: boolean) => Promise<{ success: boolean; error?: string }>
 		>;
+		getStartMinimized: Mock<() => Promise<boolean>>;
+		setStartMinimized: Mock<(enabled: boolean) => Promise<boolean>>;
+	};
+}
+
+/**
+ * Creates a mock LAN sync status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A sync status with sync turned off
+ */
+function createMockSyncStatus(
+	overrides: Partial<SyncStatusRecord> = {},
+): SyncStatusRecord {
+	return {
+		enabled: false,
+		deviceId: "0123456789abcdef",
+		deviceName: "Test Device",
+		port: 47814,
+		running: false,
+		devices: [],
+		pairingCode: null,
+		pairingExpiresAt: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock webhook status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A webhook status with the webhook turned off
+ */
+function createMockWebhookStatus(
+	overrides: Partial<WebhookStatusRecord> = {},
+): WebhookStatusRecord {
+	return {
+		enabled: false,
+		url: "",
+		secret: "test-secret-0123456789",
+		events: ["item.added"],
+		itemTypes: [],
+		kinds: [],
+		includeContent: false,
+		pending: 0,
+		lastDeliveredAt: null,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates mock history stats for testing
+ * @param overrides - Properties to override on the default stats
+ * @returns Stats of an empty history
+ */
+function createMockHistoryStats(
+	overrides: Partial<HistoryStatsRecord> = {},
+): HistoryStatsRecord {
+	return {
+		totalItems: 0,
+		itemsPerDay: [],
+		itemsPerWeek: [],
+		topSourceApps: [],
+		types: [],
+		kinds: [],
+		averageItemBytes: null,
+		storageBytes: { database: 4096, attachments: 0, total: 4096 },
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock text expander status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A text expander status with expansion turned off
+ */
+function createMockTextExpanderStatus(
+	overrides: Partial<TextExpanderStatusRecord> = {},
+): TextExpanderStatusRecord {
+	return {
+		enabled: false,
+		excludedApps: [],
+		running: false,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock double-copy status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A double-copy status with the gesture turned off
+ */
+function createMockDoubleCopyStatus(
+	overrides: Partial<DoubleCopyStatusRecord> = {},
+): DoubleCopyStatusRecord {
+	return {
+		enabled: false,
+		intervalMs: 400,
+		running: false,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock backup status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A backup status with scheduled backups turned off
+ */
+function createMockBackupStatus(
+	overrides: Partial<BackupStatusRecord> = {},
+): BackupStatusRecord {
+	return {
+		enabled: false,
+		directory: "",
+		hasPassphrase: false,
+		intervalHours: 24,
+		keep: 7,
+		lastBackupAt: null,
+		running: false,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock remote sync status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A remote sync status with sync turned off
+ */
+function createMockRemoteSyncStatus(
+	overrides: Partial<RemoteSyncStatusRecord> = {},
+): RemoteSyncStatusRecord {
+	return {
+		enabled: false,
+		backend: "webdav",
+		url: "",
+		bucket: "",
+		region: "",
+		prefix: "clipman",
+		username: "",
+		hasPassword: false,
+		hasPassphrase: false,
+		intervalMinutes: 5,
+		deviceId: "fedcba9876543210",
+		syncing: false,
+		lastSyncAt: null,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock shared board status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns A status with hosting off and no boards joined
+ */
+function createMockBoardsStatus(
+	overrides: Partial<BoardsStatusRecord> = {},
+): BoardsStatusRecord {
+	return {
+		server: { enabled: false, port: 47815, running: false, boards: [] },
+		memberships: [],
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock settings file status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns The status of a settings file holding the defaults
+ */
+function createMockSettingsFileStatus(
+	overrides: Partial<SettingsFileStatusRecord> = {},
+): SettingsFileStatusRecord {
+	return {
+		path: "/mock/userData/settings.toml",
+		settings: {
+			shortcuts: {
+				togglePicker: "CommandOrControl+Shift+V",
+				toggleQuickPicker: "CommandOrControl+Shift+Space",
+				pasteLastItem: null,
+				pastePlainText: null,
+				pasteNextQueued: "CommandOrControl+Alt+V",
+				pushClipboard: null,
+				popClipboard: null,
+				copyToRegister: null,
+				pasteRegister: null,
+				toggleAppendCopy: null,
+				toggleCapture: null,
+			},
+			retention: {
+				maxItems: null,
+				maxAgeDays: null,
+				maxDatabaseMb: null,
+				trashDays: 30,
+				keepPastedAtLeast: null,
+			},
+			exclusions: { excludedApps: [] },
+			window: { hideOnBlur: true },
+			appearance: { theme: "system", compactList: false },
+		},
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock updater status for testing
+ * @param overrides - Properties to override on the default status
+ * @returns The status of an updater that has not checked yet
+ */
+function createMockUpdaterStatus(
+	overrides: Partial<UpdaterStatusRecord> = {},
+): UpdaterStatusRecord {
+	return {
+		channel: "stable",
+		autoCheck: true,
+		configured: true,
+		currentVersion: "0.1.0",
+		state: "idle",
+		available: null,
+		progress: null,
+		lastCheckedAt: null,
+		lastError: null,
+		...overrides,
+	};
+}
+
+/**
+ * Creates a mock database check for testing
+ * @param overrides - Properties to override on the default check
+ * @returns The check of a healthy database
+ */
+function createMockDatabaseCheck(
+	overrides: Partial<DatabaseCheckRecord> = {},
+): DatabaseCheckRecord {
+	return {
+		ok: true,
+		integrityErrors: [],
+		ftsErrors: [],
+		quick: false,
+		durationMs: 0,
+		...overrides,
+	};
+}
+
+/**
+ * Creates mock capture size limits for testing
+ * @returns The default limits
+ */
+function createMockCaptureLimits(): CaptureLimitSettingsRecord {
+	return {
+		text: { maxBytes: 1_000_000, policy: "attachment" },
+		richText: { maxBytes: 1_000_000, policy: "drop" },
+		image: { maxBytes: 50 * 1024 * 1024 },
+	};
+}
+
+/**
+ * Creates mock power throttle settings for testing
+ * @returns The default settings
+ */
+function createMockPowerThrottle(): PowerThrottleSettingsRecord {
+	return {
+		onBattery: true,
+		idleMinutes: 10,
+		pollIntervalMs: 5000,
+		deferJobs: true,
 	};
 }
 
@@ -58,13 +1054,856 @@ export function createMockElectronAPI(): MockElectronAPI {
 		clipboard: {
 			read: vi.fn().mockResolvedValue({ text: "", rtf: undefined }),
 			write: vi.fn().mockResolvedValue(undefined),
+			restoreItem: vi.fn().mockResolvedValue(undefined),
+			pasteItem: vi.fn().mockResolvedValue(undefined),
+			typeItem: vi.fn().mockResolvedValue(undefined),
 		},
 		db: {
 			getHistory: vi.fn().mockResolvedValue([]),
-			addClip: vi.fn().mockResolvedValue(undefined),
+			listPage: vi.fn().mockResolvedValue({ items: [], nextCursor: null }),
+			listGroupedPage: vi.fn().mockResolvedValue({ groups: [], nextCursor: null }),
+			revealItem: vi.fn().mockResolvedValue(createMockHistoryItem()),
+			openItem: vi.fn().mockResolvedValue(undefined),
+			showItemInFolder: vi.fn().mockResolvedValue(undefined),
+			searchHistory: vi.fn().mockResolvedValue([]),
+			fuzzySearchHistory: vi.fn().mockResolvedValue([]),
+			regexSearchHistory: vi.fn().mockResolvedValue([]),
+			semanticSearch: vi.fn().mockResolvedValue([]),
 			deleteHistoryItem: vi.fn().mockResolvedValue(undefined),
 			clearAllHistory: vi.fn().mockResolvedValue(undefined),
+			listTrash: vi.fn().mockResolvedValue([]),
+			restoreFromTrash: vi.fn().mockResolvedValue(1),
+			emptyTrash: vi.fn().mockResolvedValue(0),
+			requestWipe: vi.fn().mockResolvedValue({
+				token: "wipe-token",
+				expiresAt: "2024-01-01T00:01:00.000Z",
+			}),
+			wipeAllHistory: vi.fn().mockResolvedValue(undefined),
+			getImagePreview: vi.fn().mockResolvedValue(null),
+			getThumbnail: vi.fn().mockResolvedValue(null),
+			copyQrPayload: vi.fn().mockResolvedValue(undefined),
+			generateQr: vi.fn().mockResolvedValue("data:image/png;base64,"),
+			copyImageAs: vi.fn().mockResolvedValue(undefined),
+			stripMetadata: vi.fn().mockResolvedValue(false),
+			saveImageAs: vi.fn().mockResolvedValue(undefined),
+			saveItemToFile: vi.fn().mockResolvedValue(undefined),
+			getColorFormats: vi.fn().mockResolvedValue({
+				hex: "#ff0000",
+				rgb: "rgb(255, 0, 0)",
+				hsl: "hsl(0, 100%, 50%)",
+			}),
+			copyColorAs: vi.fn().mockResolvedValue(undefined),
+			diffItems: vi.fn().mockResolvedValue({ hunks: [], added: 0, removed: 0 }),
+			transformItem: vi.fn().mockResolvedValue(undefined),
+			formatItem: vi.fn().mockResolvedValue(undefined),
+			convertMarkup: vi.fn().mockResolvedValue(undefined),
+			runScript: vi.fn().mockResolvedValue(undefined),
+			runCommandAction: vi
+				.fn()
+				.mockResolvedValue({ output: "", recorded: false }),
+			hashItem: vi.fn().mockResolvedValue(""),
 			toggleFavorite: vi.fn().mockResolvedValue(true),
+			togglePin: vi.fn().mockResolvedValue(true),
+			batchUpdate: vi
+				.fn()
+				.mockImplementation(async (ids: number[]) => ids.length),
+			toggleSyncExcluded: vi.fn().mockResolvedValue(true),
+			togglePasteOnce: vi.fn().mockResolvedValue(true),
+			setItemNote: vi.fn().mockResolvedValue(undefined),
+			setItemExpiry: vi.fn().mockResolvedValue(undefined),
+			dismissSecret: vi.fn().mockResolvedValue(true),
+			mergeItems: vi.fn().mockResolvedValue(createMockHistoryItem()),
+			findNearDuplicates: vi.fn().mockResolvedValue([]),
+			findNearDuplicateTexts: vi.fn().mockResolvedValue([]),
+			mergeDuplicates: vi.fn().mockResolvedValue(createMockHistoryItem()),
+			listSourceApps: vi.fn().mockResolvedValue([]),
+			exportHistory: vi.fn().mockResolvedValue(0),
+			exportSnippets: vi.fn().mockResolvedValue(0),
+			listAccessLog: vi.fn().mockResolvedValue([]),
+			importHistory: vi.fn().mockResolvedValue({ imported: 0, skipped: 0 }),
+			importCopyQ: vi.fn().mockResolvedValue({ imported: 0, skipped: 0 }),
+			importDitto: vi.fn().mockResolvedValue({ imported: 0, skipped: 0 }),
+			importMaccy: vi.fn().mockResolvedValue({ imported: 0, skipped: 0 }),
+			runMaintenance: vi.fn().mockResolvedValue({
+				sizeBefore: 0,
+				sizeAfter: 0,
+				reclaimedBytes: 0,
+				durationMs: 0,
+			}),
+			checkDatabase: vi.fn().mockResolvedValue(createMockDatabaseCheck()),
+			repairDatabase: vi.fn().mockResolvedValue({
+				action: "none",
+				before: createMockDatabaseCheck(),
+				after: createMockDatabaseCheck(),
+				salvaged: null,
+				corruptPath: null,
+			}),
+			getLastRecovery: vi.fn().mockResolvedValue(null),
+			addTag: vi.fn().mockResolvedValue([]),
+			removeTag: vi.fn().mockResolvedValue([]),
+			getItemTags: vi.fn().mockResolvedValue([]),
+			listTags: vi.fn().mockResolvedValue([]),
+		},
+		collections: {
+			list: vi.fn().mockResolvedValue([]),
+			create: vi.fn().mockImplementation(async (name: string) => ({
+				id: 1,
+				name,
+				created_at: new Date().toISOString(),
+				item_count: 0,
+			})),
+			rename: vi.fn().mockImplementation(async (id: number, name: string) => ({
+				id,
+				name,
+				created_at: new Date().toISOString(),
+				item_count: 0,
+			})),
+			delete: vi.fn().mockResolvedValue(undefined),
+			getItems: vi.fn().mockResolvedValue([]),
+			addItem: vi.fn().mockResolvedValue(undefined),
+			removeItem: vi.fn().mockResolvedValue(undefined),
+			moveItem: vi.fn().mockResolvedValue(undefined),
+			reorderItems: vi.fn().mockResolvedValue(undefined),
+		},
+		retention: {
+			getPolicy: vi.fn().mockResolvedValue({
+				maxItems: null,
+				maxAgeDays: null,
+				maxDatabaseMb: null,
+				trashDays: 30,
+				keepPastedAtLeast: null,
+			}),
+			updatePolicy: vi.fn().mockImplementation(
+				async (policy: Partial<RetentionPolicyRecord>) => ({
+					maxItems: null,
+					maxAgeDays: null,
+					maxDatabaseMb: null,
+					trashDays: 30,
+					keepPastedAtLeast: null,
+					...policy,
+				}),
+			),
+		},
+		backup: {
+			get: vi.fn().mockResolvedValue(createMockBackupStatus()),
+			update: vi.fn().mockImplementation(
+				async ({
+					passphrase,
+					...settings
+				}: Partial<BackupStatusRecord> & { passphrase?: string }) => ({
+					...createMockBackupStatus(),
+					...settings,
+					hasPassphrase: Boolean(passphrase),
+				}),
+			),
+			backupNow: vi.fn().mockResolvedValue(
+				createMockBackupStatus({
+					hasPassphrase: true,
+					lastBackupAt: "2024-01-01T00:00:00.000Z",
+				}),
+			),
+			restore: vi.fn().mockResolvedValue(createMockBackupStatus()),
+		},
+		lock: {
+			getStatus: vi.fn().mockResolvedValue({
+				enabled: false,
+				autoLockMinutes: 5,
+				locked: false,
+				available: true,
+				unprotected: false,
+			}),
+			lock: vi.fn().mockResolvedValue({
+				enabled: true,
+				autoLockMinutes: 5,
+				locked: true,
+				available: true,
+				unprotected: false,
+			}),
+			unlock: vi.fn().mockResolvedValue({
+				enabled: true,
+				autoLockMinutes: 5,
+				locked: false,
+				available: true,
+				unprotected: false,
+			}),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: {
+					enabled?: boolean;
+					autoLockMinutes?: number | null;
+				}) => ({
+					enabled: false,
+					autoLockMinutes: 5,
+					locked: false,
+					available: true,
+					unprotected: false,
+					...settings,
+				}),
+			),
+		},
+		capture: {
+			getStatus: vi.fn().mockResolvedValue({ paused: false, resumesAt: null }),
+			setPaused: vi
+				.fn()
+				.mockImplementation(async (paused: boolean) => ({
+					paused,
+					resumesAt: null,
+				})),
+			snooze: vi.fn().mockImplementation(async (minutes: number) => ({
+				paused: true,
+				resumesAt: Date.now() + minutes * 60 * 1000,
+			})),
+		},
+		readOnly: {
+			getStatus: vi
+				.fn()
+				.mockResolvedValue({ enabled: false, forced: false, active: false }),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(async (settings: { enabled?: boolean }) => {
+					const enabled = settings.enabled ?? false;
+					return { enabled, forced: false, active: enabled };
+				}),
+		},
+		sounds: {
+			get: vi.fn().mockResolvedValue({
+				enabled: false,
+				sounds: { capture: "default", pause: "default", resume: "default" },
+			}),
+			update: vi.fn().mockImplementation(
+				async (settings: {
+					enabled?: boolean;
+					sounds?: Partial<Record<SoundEventRecord, string>>;
+				}) => ({
+					enabled: settings.enabled ?? false,
+					sounds: {
+						capture: "default",
+						pause: "default",
+						resume: "default",
+						...settings.sounds,
+					},
+				}),
+			),
+			preview: vi.fn().mockResolvedValue(undefined),
+		},
+		captureLimits: {
+			getSettings: vi.fn().mockResolvedValue(createMockCaptureLimits()),
+			updateSettings: vi.fn().mockResolvedValue(createMockCaptureLimits()),
+		},
+		primarySelection: {
+			getSettings: vi
+				.fn()
+				.mockResolvedValue({ enabled: false, mode: "separate" }),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(
+					async (settings: Partial<PrimarySelectionSettingsRecord>) => ({
+						enabled: false,
+						mode: "separate",
+						...settings,
+					}),
+				),
+		},
+		powerThrottle: {
+			getSettings: vi.fn().mockResolvedValue(createMockPowerThrottle()),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(
+					async (settings: Partial<PowerThrottleSettingsRecord>) => ({
+						...createMockPowerThrottle(),
+						...settings,
+					}),
+				),
+			getStatus: vi.fn().mockResolvedValue({
+				reason: null,
+				pollIntervalMs: null,
+				deferJobs: false,
+			}),
+		},
+		universalClipboard: {
+			getSettings: vi
+				.fn()
+				.mockResolvedValue({ action: "keep", tag: "handoff" }),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(
+					async (settings: Partial<UniversalClipboardSettingsRecord>) => ({
+						action: "keep",
+						tag: "handoff",
+						...settings,
+					}),
+				),
+		},
+		clipboardRestore: {
+			getSettings: vi.fn().mockResolvedValue({ enabled: false }),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(
+					async (settings: Partial<ClipboardRestoreSettingsRecord>) => ({
+						enabled: false,
+						...settings,
+					}),
+				),
+		},
+		appendCopy: {
+			getStatus: vi.fn().mockResolvedValue({ active: false, separator: "\n" }),
+			setActive: vi
+				.fn()
+				.mockImplementation(async (active: boolean) => ({
+					active,
+					separator: "\n",
+				})),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(async (settings: { separator?: string }) => ({
+					active: false,
+					separator: settings.separator ?? "\n",
+				})),
+		},
+		autoClear: {
+			getStatus: vi.fn().mockResolvedValue({
+				enabled: true,
+				clearAfterSeconds: 30,
+				pendingClearAt: null,
+			}),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: {
+					enabled?: boolean;
+					clearAfterSeconds?: number;
+				}) => ({
+					enabled: true,
+					clearAfterSeconds: 30,
+					pendingClearAt: null,
+					...settings,
+				}),
+			),
+			cancel: vi.fn().mockResolvedValue({
+				enabled: true,
+				clearAfterSeconds: 30,
+				pendingClearAt: null,
+			}),
+		},
+		exclusions: {
+			get: vi.fn().mockResolvedValue({ excludedApps: [] }),
+			update: vi.fn().mockImplementation(
+				async (settings: { excludedApps?: string[] }) => ({
+					excludedApps: settings.excludedApps ?? [],
+				}),
+			),
+		},
+		secrets: {
+			getSettings: vi.fn().mockResolvedValue({
+				enabled: true,
+				expireAfterMinutes: null,
+				rules: [],
+			}),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<SecretScanningSettingsRecord>) => ({
+					enabled: true,
+					expireAfterMinutes: null,
+					rules: [],
+					...settings,
+				}),
+			),
+		},
+		notifications: {
+			get: vi.fn().mockResolvedValue({
+				captureRule: true,
+				secretCopied: true,
+				syncCompleted: false,
+			}),
+			update: vi.fn().mockImplementation(
+				async (settings: Partial<NotificationSettingsRecord>) => ({
+					captureRule: true,
+					secretCopied: true,
+					syncCompleted: false,
+					...settings,
+				}),
+			),
+		},
+		scripts: {
+			list: vi.fn().mockResolvedValue([]),
+			install: vi.fn().mockImplementation(
+				async (script: { name: string; source: string }) => [
+					{ onCapture: false, ...script },
+				],
+			),
+			remove: vi.fn().mockResolvedValue([]),
+			test: vi.fn().mockImplementation(async (request: { text: string }) => ({
+				rejected: false,
+				text: request.text,
+			})),
+		},
+		commandActions: {
+			getSettings: vi.fn().mockResolvedValue({ actions: [] }),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<CommandActionSettingsRecord>) => ({
+					actions: [],
+					...settings,
+				}),
+			),
+		},
+		captureRules: {
+			getSettings: vi.fn().mockResolvedValue({ rules: [] }),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<CaptureRuleSettingsRecord>) => ({
+					rules: [],
+					...settings,
+				}),
+			),
+		},
+		linkPreviews: {
+			getSettings: vi.fn().mockResolvedValue({ enabled: false }),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<LinkPreviewSettingsRecord>) => ({
+					enabled: false,
+					...settings,
+				}),
+			),
+		},
+		semanticSearch: {
+			getSettings: vi.fn().mockResolvedValue({
+				enabled: false,
+				endpoint: "http://127.0.0.1:11434/api/embed",
+				model: "nomic-embed-text",
+				apiKey: "",
+			}),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<SemanticSearchSettingsRecord>) => ({
+					enabled: false,
+					endpoint: "http://127.0.0.1:11434/api/embed",
+					model: "nomic-embed-text",
+					apiKey: "",
+					...settings,
+				}),
+			),
+		},
+		itemSummaries: {
+			getSettings: vi.fn().mockResolvedValue({
+				enabled: false,
+				endpoint: "http://127.0.0.1:11434/v1/chat/completions",
+				model: "llama3.2",
+				apiKey: "",
+				minChars: 500,
+			}),
+			updateSettings: vi.fn().mockImplementation(
+				async (settings: Partial<ItemSummarySettingsRecord>) => ({
+					enabled: false,
+					endpoint: "http://127.0.0.1:11434/v1/chat/completions",
+					model: "llama3.2",
+					apiKey: "",
+					minChars: 500,
+					...settings,
+				}),
+			),
+		},
+		favicons: {
+			get: vi.fn().mockResolvedValue(null),
+		},
+		shortcuts: {
+			get: vi.fn().mockResolvedValue({
+				togglePicker: "CommandOrControl+Shift+V",
+				toggleQuickPicker: "CommandOrControl+Shift+Space",
+				pasteLastItem: null,
+				pastePlainText: null,
+				pasteNextQueued: "CommandOrControl+Alt+V",
+				pushClipboard: null,
+				popClipboard: null,
+				copyToRegister: null,
+				pasteRegister: null,
+				toggleAppendCopy: null,
+				toggleCapture: null,
+			}),
+			set: vi.fn().mockImplementation(
+				async (action: ShortcutActionRecord, accelerator: string | null) => ({
+					togglePicker: "CommandOrControl+Shift+V",
+					toggleQuickPicker: "CommandOrControl+Shift+Space",
+					pasteLastItem: null,
+					pastePlainText: null,
+					pasteNextQueued: "CommandOrControl+Alt+V",
+					pushClipboard: null,
+					popClipboard: null,
+					copyToRegister: null,
+					pasteRegister: null,
+					toggleAppendCopy: null,
+					toggleCapture: null,
+					[action]: accelerator,
+				}),
+			),
+			getStatus: vi.fn().mockResolvedValue({
+				togglePicker: { active: "CommandOrControl+Shift+V", conflicts: [] },
+				toggleQuickPicker: {
+					active: "CommandOrControl+Shift+Space",
+					conflicts: [],
+				},
+				pasteLastItem: { active: null, conflicts: [] },
+				pastePlainText: { active: null, conflicts: [] },
+				pasteNextQueued: { active: "CommandOrControl+Alt+V", conflicts: [] },
+				pushClipboard: { active: null, conflicts: [] },
+				popClipboard: { active: null, conflicts: [] },
+				copyToRegister: { active: null, conflicts: [] },
+				pasteRegister: { active: null, conflicts: [] },
+				toggleAppendCopy: { active: null, conflicts: [] },
+				toggleCapture: { active: null, conflicts: [] },
+			}),
+			getFallbacks: vi.fn().mockResolvedValue({
+				togglePicker: ["CommandOrControl+Alt+Shift+V"],
+				toggleQuickPicker: ["CommandOrControl+Alt+Space"],
+				pasteLastItem: [],
+				pastePlainText: [],
+				pasteNextQueued: ["CommandOrControl+Alt+Shift+N"],
+				pushClipboard: [],
+				popClipboard: [],
+				copyToRegister: [],
+				pasteRegister: [],
+				toggleAppendCopy: [],
+				toggleCapture: [],
+			}),
+			setFallbacks: vi.fn().mockImplementation(
+				async (action: ShortcutActionRecord, accelerators: string[]) => ({
+					togglePicker: ["CommandOrControl+Alt+Shift+V"],
+					toggleQuickPicker: ["CommandOrControl+Alt+Space"],
+					pasteLastItem: [],
+					pastePlainText: [],
+					pasteNextQueued: ["CommandOrControl+Alt+Shift+N"],
+					pushClipboard: [],
+					popClipboard: [],
+					copyToRegister: [],
+					pasteRegister: [],
+					toggleAppendCopy: [],
+					toggleCapture: [],
+					[action]: accelerators,
+				}),
+			),
+		},
+		chords: {
+			get: vi.fn().mockResolvedValue({ chords: [], unavailablePrefixes: [] }),
+			update: vi.fn().mockImplementation(
+				async (settings: { chords?: ShortcutChordRecord[] }) => ({
+					chords: settings.chords ?? [],
+					unavailablePrefixes: [],
+				}),
+			),
+		},
+		trayClicks: {
+			get: vi.fn().mockResolvedValue({
+				click: "menu",
+				doubleClick: "none",
+				middleClick: "none",
+			}),
+			update: vi.fn().mockImplementation(
+				async (settings: Partial<TrayClickSettingsRecord>) => ({
+					click: "menu",
+					doubleClick: "none",
+					middleClick: "none",
+					...settings,
+				}),
+			),
+		},
+		language: {
+			get: vi.fn().mockResolvedValue({ language: "system", active: "en" }),
+			set: vi.fn().mockImplementation(
+				async (language: LanguageSettingRecord) => ({
+					language,
+					active: language === "system" ? "en" : language,
+				}),
+			),
+		},
+		queue: {
+			get: vi.fn().mockResolvedValue([]),
+			toggle: vi.fn().mockImplementation(async (itemId: number) => [itemId]),
+			clear: vi.fn().mockResolvedValue([]),
+			pasteNext: vi.fn().mockResolvedValue(undefined),
+		},
+		registers: {
+			list: vi.fn().mockResolvedValue([]),
+			copy: vi.fn().mockImplementation(async (name: string) => ({
+				name,
+				content: "",
+				rtf: null,
+				html: null,
+				updated_at: new Date().toISOString(),
+			})),
+			paste: vi.fn().mockResolvedValue(undefined),
+			delete: vi.fn().mockResolvedValue(undefined),
+		},
+		snippets: {
+			list: vi.fn().mockResolvedValue([]),
+			getFields: vi.fn().mockResolvedValue([]),
+			create: vi
+				.fn()
+				.mockImplementation(async (input: SnippetInputRecord) => ({
+					id: 1,
+					name: input.name,
+					content: input.content,
+					abbreviation: input.abbreviation ?? null,
+					counter: 0,
+					created_at: new Date().toISOString(),
+					updated_at: new Date().toISOString(),
+				})),
+			update: vi.fn().mockImplementation(
+				async (id: number, input: Partial<SnippetInputRecord>) => ({
+					id,
+					name: input.name ?? "",
+					content: input.content ?? "",
+					abbreviation: input.abbreviation ?? null,
+					counter: 0,
+					created_at: new Date().toISOString(),
+					updated_at: new Date().toISOString(),
+				}),
+			),
+			delete: vi.fn().mockResolvedValue(undefined),
+			paste: vi.fn().mockResolvedValue(undefined),
+		},
+		stats: {
+			get: vi.fn().mockResolvedValue(createMockHistoryStats()),
+			heatmap: vi.fn().mockImplementation(async (year: number) => ({
+				year,
+				days: [],
+				total: 0,
+				maxCount: 0,
+			})),
+		},
+		stack: {
+			get: vi.fn().mockResolvedValue([]),
+			clear: vi.fn().mockResolvedValue([]),
+		},
+		typeOut: {
+			getSettings: vi.fn().mockResolvedValue({ delayMs: 10 }),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(async (settings: { delayMs?: number }) => ({
+					delayMs: settings.delayMs ?? 10,
+				})),
+		},
+		textExpander: {
+			get: vi.fn().mockResolvedValue(createMockTextExpanderStatus()),
+			update: vi.fn().mockImplementation(
+				async (settings: { enabled?: boolean; excludedApps?: string[] }) =>
+					createMockTextExpanderStatus(settings),
+			),
+		},
+		doubleCopy: {
+			get: vi.fn().mockResolvedValue(createMockDoubleCopyStatus()),
+			update: vi.fn().mockImplementation(
+				async (settings: { enabled?: boolean; intervalMs?: number }) =>
+					createMockDoubleCopyStatus(settings),
+			),
+		},
+		quickPaste: {
+			get: vi.fn().mockResolvedValue({ enabled: true, pasteAfterCopy: false }),
+			update: vi.fn().mockImplementation(
+				async (settings: { enabled?: boolean; pasteAfterCopy?: boolean }) => ({
+					enabled: true,
+					pasteAfterCopy: false,
+					...settings,
+				}),
+			),
+		},
+		windowBehavior: {
+			get: vi.fn().mockResolvedValue({ hideOnBlur: true }),
+			update: vi
+				.fn()
+				.mockImplementation(async (settings: { hideOnBlur?: boolean }) => ({
+					hideOnBlur: settings.hideOnBlur ?? true,
+				})),
+		},
+		settings: {
+			get: vi.fn().mockResolvedValue(createMockSettingsFileStatus()),
+			update: vi.fn().mockResolvedValue(createMockSettingsFileStatus()),
+		},
+		dataLocation: {
+			get: vi.fn().mockResolvedValue({
+				dataPath: "/mock/userData",
+				source: "default",
+				pendingMove: null,
+				lastError: null,
+			}),
+			migrate: vi.fn().mockImplementation(async (target: string) => ({
+				dataPath: "/mock/userData",
+				source: "default",
+				pendingMove: target,
+				lastError: null,
+			})),
+		},
+		profiles: {
+			get: vi.fn().mockResolvedValue({
+				active: "default",
+				profiles: ["default"],
+			}),
+			create: vi.fn().mockImplementation(async (name: string) => ({
+				active: "default",
+				profiles: ["default", name],
+			})),
+			switch: vi.fn().mockResolvedValue(undefined),
+			remove: vi.fn().mockResolvedValue({
+				active: "default",
+				profiles: ["default"],
+			}),
+		},
+		logs: {
+			getRecent: vi.fn().mockResolvedValue([]),
+			getSettings: vi.fn().mockResolvedValue({ level: "info", modules: {} }),
+			updateSettings: vi.fn().mockResolvedValue({ level: "info", modules: {} }),
+			openFolder: vi.fn().mockResolvedValue(undefined),
+		},
+		diagnostics: {
+			listCrashes: vi.fn().mockResolvedValue([]),
+			export: vi.fn().mockImplementation(async (filePath: string) => ({
+				filePath,
+				files: [],
+			})),
+		},
+		updater: {
+			get: vi.fn().mockResolvedValue(createMockUpdaterStatus()),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(async (settings: Partial<UpdaterStatusRecord>) =>
+					createMockUpdaterStatus(settings),
+				),
+			check: vi.fn().mockResolvedValue(
+				createMockUpdaterStatus({ state: "up-to-date" }),
+			),
+			download: vi.fn().mockResolvedValue(
+				createMockUpdaterStatus({ state: "ready" }),
+			),
+			install: vi.fn().mockResolvedValue(
+				createMockUpdaterStatus({ state: "installing" }),
+			),
+		},
+		httpApi: {
+			get: vi.fn().mockResolvedValue({
+				enabled: false,
+				port: 47813,
+				token: "test-token-0123456789",
+				running: false,
+			}),
+			update: vi.fn().mockImplementation(
+				async (settings: {
+					enabled?: boolean;
+					port?: number;
+					token?: string;
+				}) => ({
+					enabled: false,
+					port: 47813,
+					token: "test-token-0123456789",
+					...settings,
+					running: settings.enabled ?? false,
+				}),
+			),
+			regenerateToken: vi.fn().mockResolvedValue({
+				enabled: false,
+				port: 47813,
+				token: "test-token-9876543210",
+				running: false,
+			}),
+		},
+		urlActions: {
+			getSettings: vi.fn().mockResolvedValue({
+				sendContent: false,
+				allowClipboardActions: false,
+				callbackSchemes: ["shortcuts"],
+			}),
+			updateSettings: vi
+				.fn()
+				.mockImplementation(
+					async (settings: Partial<UrlActionSettingsRecord>) => ({
+						sendContent: false,
+						allowClipboardActions: false,
+						callbackSchemes: ["shortcuts"],
+						...settings,
+					}),
+				),
+		},
+		webhooks: {
+			get: vi.fn().mockResolvedValue(createMockWebhookStatus()),
+			update: vi.fn().mockImplementation(
+				async (settings: Partial<WebhookSettingsRecord>) =>
+					createMockWebhookStatus(settings),
+			),
+			regenerateSecret: vi.fn().mockResolvedValue(
+				createMockWebhookStatus({ secret: "test-secret-9876543210" }),
+			),
+			test: vi.fn().mockResolvedValue(undefined),
+		},
+		sync: {
+			get: vi.fn().mockResolvedValue(createMockSyncStatus()),
+			update: vi.fn().mockImplementation(
+				async (settings: {
+					enabled?: boolean;
+					deviceName?: string;
+					port?: number;
+				}) => ({
+					...createMockSyncStatus(),
+					...settings,
+					running: settings.enabled ?? false,
+				}),
+			),
+			startPairing: vi.fn().mockResolvedValue(
+				createMockSyncStatus({
+					enabled: true,
+					running: true,
+					pairingCode: "123456",
+					pairingExpiresAt: "2024-01-01T00:02:00.000Z",
+				}),
+			),
+			cancelPairing: vi.fn().mockResolvedValue(createMockSyncStatus()),
+			pair: vi.fn().mockResolvedValue(createMockSyncStatus()),
+			unpair: vi.fn().mockResolvedValue(createMockSyncStatus()),
+		},
+		remoteSync: {
+			get: vi.fn().mockResolvedValue(createMockRemoteSyncStatus()),
+			update: vi.fn().mockImplementation(
+				async ({
+					password,
+					passphrase,
+					...settings
+				}: Partial<RemoteSyncStatusRecord> & {
+					password?: string;
+					passphrase?: string;
+				}) => ({
+					...createMockRemoteSyncStatus(),
+					...settings,
+					hasPassword: Boolean(password),
+					hasPassphrase: Boolean(passphrase),
+				}),
+			),
+			syncNow: vi.fn().mockResolvedValue(
+				createMockRemoteSyncStatus({
+					enabled: true,
+					hasPassphrase: true,
+					lastSyncAt: "2024-01-01T00:00:00.000Z",
+				}),
+			),
+		},
+		boards: {
+			get: vi.fn().mockResolvedValue(createMockBoardsStatus()),
+			updateServer: vi.fn().mockImplementation(
+				async (settings: { enabled?: boolean; port?: number }) => {
+					const status = createMockBoardsStatus();
+					return {
+						...status,
+						server: {
+							...status.server,
+							...settings,
+							running: settings.enabled ?? false,
+						},
+					};
+				},
+			),
+			createBoard: vi.fn().mockResolvedValue({
+				invite: "clipman-board:e30",
+				status: createMockBoardsStatus(),
+			}),
+			regenerateToken: vi.fn().mockResolvedValue({
+				invite: "clipman-board:e30",
+				status: createMockBoardsStatus(),
+			}),
+			deleteBoard: vi.fn().mockResolvedValue(createMockBoardsStatus()),
+			join: vi.fn().mockResolvedValue(createMockBoardsStatus()),
+			leave: vi.fn().mockResolvedValue(createMockBoardsStatus()),
+			publish: vi.fn().mockResolvedValue(1),
+			listItems: vi.fn().mockResolvedValue([]),
+			copyItem: vi.fn().mockResolvedValue(undefined),
 		},
 		window: {
 			center: vi.fn().mockResolvedValue(undefined),
@@ -72,11 +1911,36 @@ export function createMockElectronAPI(): MockElectronAPI {
 			hide: vi.fn().mockResolvedValue(undefined),
 			isVisible: vi.fn().mockResolvedValue(false),
 			hideAndPaste: vi.fn().mockResolvedValue(undefined),
+			isPinned: vi.fn().mockResolvedValue(false),
+			setPinned: vi.fn().mockImplementation(async (pinned: boolean) => pinned),
+		},
+		picker: {
+			show: vi.fn().mockResolvedValue(undefined),
+			hide: vi.fn().mockResolvedValue(undefined),
+			toggle: vi.fn().mockResolvedValue(undefined),
+			isVisible: vi.fn().mockResolvedValue(false),
+		},
+		events: {
+			onHistoryChanged: vi.fn().mockReturnValue(vi.fn()),
+			onHistoryEvent: vi.fn().mockReturnValue(vi.fn()),
+			onLockChanged: vi.fn().mockReturnValue(vi.fn()),
+			onCaptureChanged: vi.fn().mockReturnValue(vi.fn()),
+			onReadOnlyChanged: vi.fn().mockReturnValue(vi.fn()),
+			onQueueChanged: vi.fn().mockReturnValue(vi.fn()),
+			onSettingsChanged: vi.fn().mockReturnValue(vi.fn()),
+			onUpdaterStatus: vi.fn().mockReturnValue(vi.fn()),
+			onShortcutsStatus: vi.fn().mockReturnValue(vi.fn()),
+			onSearchRequested: vi.fn().mockReturnValue(vi.fn()),
+			onBoardsUpdated: vi.fn().mockReturnValue(vi.fn()),
 		},
 		app: {
 			quit: vi.fn().mockResolvedValue(undefined),
 			getLaunchAtLogin: vi.fn().mockResolvedValue(true),
 			setLaunchAtLogin: vi.fn().mockResolvedValue({ success: true }),
+			getStartMinimized: vi.fn().mockResolvedValue(true),
+			setStartMinimized: vi
+				.fn()
+				.mockImplementation(async (enabled: boolean) => enabled),
 		},
 	};
 }
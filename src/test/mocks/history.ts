@@ -4,22 +4,45 @@
  * @returns A history item object
  */
 export function createMockHistoryItem(
-	overrides: Partial<{
-		id: number;
-		content: string;
-		type: string;
-		created_at: string;
-		is_favorite: number;
-		rtf: string | null;
-	}> = {},
-) {
+	overrides: Partial<HistoryRecord> = {},
+): HistoryRecord {
 	return {
 		id: 1,
 		content: "Test clipboard content",
 		type: "text",
 		created_at: new Date().toISOString(),
 		is_favorite: 0,
+		pinned: 0,
 		rtf: null,
+		html: null,
+		image_width: null,
+		image_height: null,
+		use_count: 1,
+		paste_count: 0,
+		last_used_at: null,
+		note: null,
+		source_app: null,
+		source_title: null,
+		source_url: null,
+		sync_excluded: 0,
+		content_size: null,
+		qr_payload: null,
+		content_kind: null,
+		code_language: null,
+		color_value: null,
+		secret_match: null,
+		expires_at: null,
+		link_title: null,
+		link_description: null,
+		link_image: null,
+		summary_title: null,
+		summary_text: null,
+		byte_size: null,
+		char_count: null,
+		word_count: null,
+		line_count: null,
+		is_primary: 0,
+		paste_once: 0,
 		...overrides,
 	};
 }
@@ -32,11 +55,9 @@ export function createMockHistoryItem(
  */
 export function createMockHistoryItems(
 	count: number,
-	baseOverrides: Partial<{
-		type: string;
-		is_favorite: number;
-		rtf: string | null;
-	}> = {},
+	baseOverrides: Partial<
+		Omit<HistoryRecord, "id" | "content" | "created_at">
+	> = {},
 ) {
 	return Array.from({ length: count }, (_, index) =>
 		createMockHistoryItem({
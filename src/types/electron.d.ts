@@ -3,33 +3,2197 @@
 interface ClipboardData {
 	text: string;
 	rtf?: string;
+	html?: string;
+}
+
+/**
+ * What a text item holds, detected on capture.
+ * Mirrors `CONTENT_KINDS` in `electron/lib/content-kind.ts`.
+ */
+type ContentKindRecord =
+	| "url"
+	| "email"
+	| "phone"
+	| "path"
+	| "color"
+	| "json"
+	| "code"
+	| "ip"
+	| "text";
+
+/**
+ * Programming language guessed for a code item.
+ * Mirrors `CODE_LANGUAGES` in `electron/lib/code-language.ts`.
+ */
+type CodeLanguageRecord =
+	| "javascript"
+	| "typescript"
+	| "python"
+	| "ruby"
+	| "php"
+	| "shell"
+	| "go"
+	| "rust"
+	| "java"
+	| "kotlin"
+	| "csharp"
+	| "swift"
+	| "c"
+	| "cpp"
+	| "sql"
+	| "html"
+	| "css";
+
+/**
+ * How history is ordered after pinned items: newest first, items copied
+ * or pasted often and lately first, or items pasted most often first.
+ * Mirrors `HistoryOrder` in `electron/lib/history-repository.ts`.
+ */
+type HistoryOrderRecord = "recent" | "frecency" | "usage";
+
+/**
+ * Where a text item was captured from: the clipboard or, on Linux, the
+ * PRIMARY selection that holds selected text.
+ * Mirrors `SelectionSource` in `electron/lib/history-repository.ts`.
+ */
+type SelectionSourceRecord = "clipboard" | "primary";
+
+/**
+ * Where an item falls in a date-grouped list: pinned, then by local copy
+ * date (this week starts on Monday).
+ * Mirrors `DateBucket` in `electron/lib/history-repository.ts`.
+ */
+type DateBucketRecord =
+	| "pinned"
+	| "today"
+	| "yesterday"
+	| "thisWeek"
+	| "older";
+
+/**
+ * Consecutive items of one date bucket.
+ * Mirrors `HistoryGroup` in `electron/lib/history-repository.ts`.
+ */
+interface HistoryGroupRecord {
+	bucket: DateBucketRecord;
+	items: HistoryRecord[];
+}
+
+/**
+ * History row as returned by the main process.
+ * Mirrors `HistoryRow` in `electron/lib/history-repository.ts`.
+ */
+interface HistoryRecord {
+	id: number;
+	content: string;
+	type: string;
+	created_at: string;
+	is_favorite: number;
+	/** 1 if pinned above the rest of history */
+	pinned: number;
+	rtf: string | null;
+	html: string | null;
+	image_width: number | null;
+	image_height: number | null;
+	/** Number of times this content has been copied */
+	use_count: number;
+	/** Number of times the item was restored, pasted or typed out */
+	paste_count: number;
+	/** When the item was last restored, pasted or typed out (UTC) */
+	last_used_at: string | null;
+	/** User annotation, searched along with content */
+	note: string | null;
+	/** App the newest copy was made in */
+	source_app: string | null;
+	/** Window title of the source app at copy time */
+	source_title: string | null;
+	/** Page URL when copied from a supported browser */
+	source_url: string | null;
+	/** 1 if LAN sync keeps this item on this device */
+	sync_excluded: number;
+	/** Original size in bytes of text stored compressed; null otherwise */
+	content_size: number | null;
+	/** Text of a QR code in an image item; empty if none, null until scanned */
+	qr_payload: string | null;
+	/** What a text item holds, e.g. `url` or `json`; null for other types */
+	content_kind: ContentKindRecord | null;
+	/** Programming language guessed for a code item; null if unknown */
+	code_language: CodeLanguageRecord | null;
+	/** Lowercase hex form of a color item (`#rrggbb` or `#rrggbbaa`) */
+	color_value: string | null;
+	/** Secret scanning rule a text capture matched; null if none */
+	secret_match: string | null;
+	/** When the item is deleted (UTC); null keeps it */
+	expires_at: string | null;
+	/**
+	 * Title of the page a URL item links to; empty if none was found, null
+	 * until fetched
+	 */
+	link_title: string | null;
+	link_description: string | null;
+	/** Preview image URL of the linked page */
+	link_image: string | null;
+	/**
+	 * Short title a language model wrote for a long text item; empty if
+	 * it gave none, null until summarized
+	 */
+	summary_title: string | null;
+	/** One-sentence summary from the same model */
+	summary_text: string | null;
+	/** UTF-8 size of a text item, or PNG size of an image item */
+	byte_size: number | null;
+	/** Character, word and line counts of a text item */
+	char_count: number | null;
+	word_count: number | null;
+	line_count: number | null;
+	/**
+	 * 1 if captured from the PRIMARY selection (Linux) and not copied to
+	 * the clipboard since
+	 */
+	is_primary: number;
+	/** 1 if the item is deleted the first time it is used */
+	paste_once: number;
+}
+
+/**
+ * Matched part of an item's content: UTF-16 offsets, end exclusive.
+ * Mirrors `MatchRange` in `electron/lib/match-ranges.ts`.
+ */
+type MatchRangeRecord = [start: number, end: number];
+
+/**
+ * Search result with where the query matched its content, for
+ * highlighting; masked secrets have none.
+ * Mirrors `SearchResultRow` in `electron/lib/history-repository.ts`.
+ */
+interface SearchResultRecord extends HistoryRecord {
+	match_ranges: MatchRangeRecord[];
+}
+
+/**
+ * Deleted item in the trash; `id` identifies the trash entry.
+ * Mirrors `TrashRow` in `electron/lib/history-repository.ts`.
+ */
+interface TrashRecord extends HistoryRecord {
+	deleted_at: string;
+	tags: string[];
+}
+
+/**
+ * Change applied to every item of a batch update; `move` appends the items
+ * to a collection and removes them from `fromCollectionId` if set.
+ * Mirrors `BatchOperation` in `electron/lib/history-repository.ts`.
+ */
+type BatchOperationRecord =
+	| { type: "delete" }
+	| { type: "pin"; pinned: boolean }
+	| { type: "tag"; name: string }
+	| { type: "move"; collectionId: number; fromCollectionId?: number };
+
+/**
+ * Collection row as returned by the main process.
+ * Mirrors `CollectionRow` in `electron/lib/collection-repository.ts`.
+ */
+interface CollectionRecord {
+	id: number;
+	name: string;
+	created_at: string;
+	item_count: number;
+}
+
+/**
+ * Named register as returned by the main process.
+ * Mirrors `RegisterRow` in `electron/lib/register-repository.ts`.
+ */
+interface RegisterRecord {
+	name: string;
+	content: string;
+	rtf: string | null;
+	html: string | null;
+	updated_at: string;
+}
+
+/**
+ * Saved snippet as returned by the main process.
+ * Mirrors `SnippetRow` in `electron/lib/snippet-repository.ts`.
+ */
+interface SnippetRecord {
+	id: number;
+	name: string;
+	/** Text with placeholders such as `{clipboard}` or `{date:%Y-%m-%d}` */
+	content: string;
+	/** Typed text the text expander replaces with the snippet, e.g. `;addr` */
+	abbreviation: string | null;
+	/** Last value pasted for `{counter}` */
+	counter: number;
+	created_at: string;
+	updated_at: string;
+}
+
+/**
+ * Fields of a snippet to create, or to change on update.
+ * Mirrors `SnippetInput` in `electron/lib/snippet-repository.ts`.
+ */
+interface SnippetInputRecord {
+	name: string;
+	content: string;
+	/** 2-32 characters without spaces; null removes it */
+	abbreviation?: string | null;
+}
+
+/**
+ * Items captured in one local day, or in the week starting on that
+ * Monday (`YYYY-MM-DD`).
+ * Mirrors `PeriodCount` in `electron/lib/stats-repository.ts`.
+ */
+interface PeriodCountRecord {
+	period: string;
+	count: number;
+}
+
+/**
+ * History aggregates for the stats dashboard; items count by when they
+ * were last copied.
+ * Mirrors `HistoryStats` in `electron/lib/stats-repository.ts`.
+ */
+interface HistoryStatsRecord {
+	totalItems: number;
+	/** Days without items are left out */
+	itemsPerDay: PeriodCountRecord[];
+	/** Weeks without items are left out */
+	itemsPerWeek: PeriodCountRecord[];
+	topSourceApps: { name: string; count: number }[];
+	types: { type: string; count: number }[];
+	kinds: { kind: ContentKindRecord; count: number }[];
+	/** Average text or image size in bytes */
+	averageItemBytes: number | null;
+	/** Database file plus image files on disk */
+	storageBytes: { database: number; attachments: number; total: number };
+}
+
+/**
+ * Items per day of one calendar year, for an activity calendar.
+ * Mirrors `ActivityHeatmap` in `electron/lib/stats-repository.ts`.
+ */
+interface ActivityHeatmapRecord {
+	year: number;
+	/** Days without items are left out */
+	days: PeriodCountRecord[];
+	total: number;
+	/** Largest count of a single day; 0 if none */
+	maxCount: number;
+}
+
+/**
+ * Result of checking the database.
+ * Mirrors `DatabaseCheck` in `electron/lib/db-health.ts`.
+ */
+interface DatabaseCheckRecord {
+	ok: boolean;
+	/** Problems SQLite's integrity check found; empty when ok */
+	integrityErrors: string[];
+	/** Full-text indexes that no longer match their content */
+	ftsErrors: string[];
+	/** Whether only the quick check ran, as at startup */
+	quick: boolean;
+	durationMs: number;
+}
+
+/**
+ * What recovering the database did.
+ * Mirrors `DatabaseRecovery` in `electron/lib/db-health.ts`.
+ */
+interface DatabaseRecoveryRecord {
+	/** Indexes rebuilt in place, or readable rows copied into a fresh file */
+	action: "none" | "repaired" | "salvaged";
+	before: DatabaseCheckRecord;
+	after: DatabaseCheckRecord;
+	/** Rows copied per table, with why copying stopped early */
+	salvaged: { name: string; rows: number; error: string | null }[] | null;
+	/** Where the damaged file was kept */
+	corruptPath: string | null;
+}
+
+/**
+ * App lock state.
+ * Mirrors `AppLockStatus` in `electron/lib/app-lock.ts`.
+ */
+interface AppLockStatusRecord {
+	enabled: boolean;
+	/** Minutes of inactivity before history locks; null never auto-locks */
+	autoLockMinutes: number | null;
+	locked: boolean;
+	/** Whether Touch ID / Windows Hello / polkit is available */
+	available: boolean;
+	/** Enabled, but left open because no OS prompt is available */
+	unprotected: boolean;
+}
+
+/**
+ * Why the app lock engaged or released; `idle` also hid the windows.
+ * Mirrors `LockChangeReason` in `electron/lib/app-lock.ts`.
+ */
+type LockChangeReasonRecord = "unlock" | "manual" | "system" | "idle";
+
+/**
+ * Clipboard auto-clear state.
+ * Mirrors `AutoClearStatus` in `electron/lib/clipboard-auto-clear.ts`.
+ */
+interface AutoClearStatusRecord {
+	enabled: boolean;
+	/** Seconds a sensitive copy stays on the system clipboard */
+	clearAfterSeconds: number;
+	/** When the pending clear fires (epoch ms), or null */
+	pendingClearAt: number | null;
+}
+
+/**
+ * A history change pushed by the main process; new items come as list
+ * rows, masked if flagged as secrets.
+ * Mirrors `RendererHistoryEvent` in `electron/main.ts`.
+ */
+type HistoryEventRecord =
+	| { type: "item.added"; item: HistoryRecord }
+	| { type: "item.deleted"; id: number }
+	| { type: "item.pinned"; id: number; pinned: boolean }
+	| { type: "history.cleared" };
+
+/**
+ * Capture pause (incognito) state.
+ * Mirrors `CapturePauseStatus` in `electron/lib/capture-pause.ts`.
+ */
+interface CapturePauseStatusRecord {
+	paused: boolean;
+	/** When a snooze ends (epoch ms), or null if paused indefinitely or capturing */
+	resumesAt: number | null;
+}
+
+/**
+ * Read-only (guest) mode state.
+ * Mirrors `ReadOnlyStatus` in `electron/lib/read-only.ts`.
+ */
+interface ReadOnlyStatusRecord {
+	/** Saved setting */
+	enabled: boolean;
+	/** Started with `--read-only`; the mode stays on until a restart */
+	forced: boolean;
+	/** Whether capture is off and history cannot be changed */
+	active: boolean;
+}
+
+type SoundEventRecord = "capture" | "pause" | "resume";
+
+/**
+ * Sounds played on capture and when capture is paused or resumed.
+ * Mirrors `CaptureSoundSettings` in `electron/lib/capture-sounds.ts`.
+ */
+interface CaptureSoundSettingsRecord {
+	/** Off by default */
+	enabled: boolean;
+	/** `default`, `none` or an absolute path to a sound file */
+	sounds: Record<SoundEventRecord, string>;
+}
+
+/**
+ * Append-copy mode state.
+ * Mirrors `AppendCopyStatus` in `electron/lib/append-copy.ts`.
+ */
+interface AppendCopyStatusRecord {
+	/** While true, each new text copy is appended to the clipboard */
+	active: boolean;
+	/** Placed between the collected text and each appended copy */
+	separator: string;
+}
+
+/**
+ * Size limits applied to copies before they are recorded.
+ * Mirrors `CaptureLimitSettings` in `electron/lib/capture-limits.ts`.
+ */
+interface CaptureLimitSettingsRecord {
+	/**
+	 * Longer text is skipped, truncated with a marker, or stored as a
+	 * preview with the full text kept as an attachment
+	 */
+	text: { maxBytes: number; policy: "skip" | "truncate" | "attachment" };
+	/** A longer HTML or RTF version skips the copy or is dropped */
+	richText: { maxBytes: number; policy: "skip" | "drop" };
+	/** Larger images are not recorded */
+	image: { maxBytes: number };
+}
+
+/**
+ * When capture is throttled to save power.
+ * Mirrors `PowerThrottleSettings` in `electron/lib/power-throttle.ts`.
+ */
+interface PowerThrottleSettingsRecord {
+	/** Throttle while the computer runs on battery */
+	onBattery: boolean;
+	/** Throttle once the system is idle this long (minutes); 0 never */
+	idleMinutes: number;
+	/** Clipboard polling interval while throttled (1000-60000 ms) */
+	pollIntervalMs: number;
+	/**
+	 * Hold back OCR, QR scanning, link previews and (on battery)
+	 * database maintenance while throttled
+	 */
+	deferJobs: boolean;
+}
+
+/**
+ * Mirrors `PowerThrottleStatus` in `electron/lib/power-throttle.ts`.
+ */
+interface PowerThrottleStatusRecord {
+	/** Why capture is throttled; null while it is not */
+	reason: "battery" | "idle" | null;
+	/** Polling interval in use while throttled, null otherwise */
+	pollIntervalMs: number | null;
+	/** Whether background jobs are waiting */
+	deferJobs: boolean;
+}
+
+/**
+ * What happens to copies made on another Apple device (macOS).
+ * Mirrors `UniversalClipboardSettings` in
+ * `electron/lib/universal-clipboard.ts`.
+ */
+interface UniversalClipboardSettingsRecord {
+	/** Recorded like any copy, skipped, or recorded with `tag` */
+	action: "keep" | "skip" | "tag";
+	tag: string;
+}
+
+/**
+ * Whether the last copy is put back on the clipboard after a restart.
+ * Mirrors `ClipboardRestoreSettings` in `electron/lib/clipboard-restore.ts`.
+ */
+interface ClipboardRestoreSettingsRecord {
+	enabled: boolean;
+}
+
+/**
+ * PRIMARY selection capture (Linux): selected text is recorded, not only
+ * copied text.
+ * Mirrors `PrimarySelectionSettings` in `electron/lib/primary-selection.ts`.
+ */
+interface PrimarySelectionSettingsRecord {
+	enabled: boolean;
+	/**
+	 * `separate` leaves selections out of lists that ask for no
+	 * `selection`; `merged` shows them with copies
+	 */
+	mode: "merged" | "separate";
+}
+
+/**
+ * Per-application exclusion rules.
+ * Mirrors `AppExclusionSettings` in `electron/lib/app-exclusions.ts`.
+ */
+interface AppExclusionSettingsRecord {
+	/** Process or app names (e.g. `keepassxc.exe`) whose copies are never recorded */
+	excludedApps: string[];
+}
+
+/**
+ * Secret scanning rules and expiry.
+ * Mirrors `SecretScanningSettings` in `electron/lib/secret-scanning.ts`.
+ */
+interface SecretScanningSettingsRecord {
+	enabled: boolean;
+	/** Minutes a flagged item is kept before it is deleted; null keeps it */
+	expireAfterMinutes: number | null;
+	/** Case-sensitive regular expressions, checked in order */
+	rules: Array<{ name: string; pattern: string }>;
+}
+
+/**
+ * Whether each event shows an OS notification: capture rules with a
+ * `notify` action, copies flagged as secrets, and remote syncs that
+ * brought new items.
+ * Mirrors `NotificationSettings` in `electron/lib/notification-rules.ts`.
+ */
+type NotificationSettingsRecord = Record<
+	"captureRule" | "secretCopied" | "syncCompleted",
+	boolean
+>;
+
+/**
+ * Whether the pages of copied links are fetched for previews.
+ * Mirrors `LinkPreviewSettings` in `electron/lib/link-previews.ts`.
+ */
+interface LinkPreviewSettingsRecord {
+	enabled: boolean;
+}
+
+/**
+ * Where text items are embedded for searching by meaning.
+ * Mirrors `SemanticSearchSettings` in `electron/lib/semantic-search.ts`.
+ */
+interface SemanticSearchSettingsRecord {
+	/** Off by default; enabling starts indexing text items */
+	enabled: boolean;
+	/** Ollama `/api/embed` or an OpenAI-compatible `/v1/embeddings` URL */
+	endpoint: string;
+	model: string;
+	/** Bearer token for hosted endpoints; empty for none */
+	apiKey: string;
+}
+
+/**
+ * Where long text items are sent for a generated title and summary.
+ * Mirrors `ItemSummarySettings` in `electron/lib/item-summaries.ts`.
+ */
+interface ItemSummarySettingsRecord {
+	/** Off by default; enabling starts on items captured before too */
+	enabled: boolean;
+	/** OpenAI-compatible `/v1/chat/completions` or Ollama `/api/chat` URL */
+	endpoint: string;
+	model: string;
+	/** Bearer token for hosted endpoints; empty for none */
+	apiKey: string;
+	/** Shortest text summarized (characters, 100-100,000) */
+	minChars: number;
+}
+
+/**
+ * A reveal, copy, paste or export of history.
+ * Mirrors `AccessLogEntry` in `electron/lib/access-log-repository.ts`.
+ */
+interface AccessLogEntryRecord {
+	id: number;
+	/** The item accessed; null for a history export */
+	history_id: number | null;
+	action: "reveal" | "copy" | "paste" | "export";
+	/** The app itself, `clipctl`, the HTTP API, D-Bus or an automation URL */
+	surface: "ui" | "cli" | "api" | "dbus" | "url";
+	/** What an export wrote, e.g. `json: 120 items to /tmp/clips.json` */
+	detail: string | null;
+	/** UTC, `YYYY-MM-DD HH:MM:SS` */
+	created_at: string;
+}
+
+/**
+ * Access log filters; missing ones match everything.
+ */
+interface AccessLogQueryRecord {
+	/** ISO 8601 time of the oldest entry wanted */
+	since?: string;
+	action?: AccessLogEntryRecord["action"];
+	surface?: AccessLogEntryRecord["surface"];
+	historyId?: number;
+	/** 1-1000, default 50 */
+	limit?: number;
+}
+
+/**
+ * Semantic search result.
+ */
+interface SemanticSearchRecord extends HistoryRecord {
+	/** Cosine similarity to the query, 1 for the same meaning */
+	similarity: number;
+}
+
+/**
+ * Actions that can be bound to a global shortcut.
+ * Mirrors `ShortcutAction` in `electron/lib/shortcuts.ts`.
+ */
+type ShortcutActionRecord =
+	| "togglePicker"
+	| "toggleQuickPicker"
+	| "pasteLastItem"
+	| "pastePlainText"
+	| "pasteNextQueued"
+	| "pushClipboard"
+	| "popClipboard"
+	| "copyToRegister"
+	| "pasteRegister"
+	| "toggleAppendCopy"
+	| "toggleCapture";
+
+/**
+ * Accelerator per action (e.g. `CommandOrControl+Shift+V`); null is unbound.
+ * Mirrors `ShortcutSettings` in `electron/lib/shortcuts.ts`.
+ */
+type ShortcutSettingsRecord = Record<ShortcutActionRecord, string | null>;
+
+/**
+ * Accelerators tried in order when an action's own is taken by another
+ * application.
+ * Mirrors `ShortcutFallbacks` in `electron/lib/shortcuts.ts`.
+ */
+type ShortcutFallbacksRecord = Record<ShortcutActionRecord, string[]>;
+
+/**
+ * What a chord does: a global shortcut action, or paste the Nth most
+ * recent item.
+ * Mirrors `ChordAction` in `electron/lib/shortcut-chords.ts`.
+ */
+type ChordActionRecord =
+	| ShortcutActionRecord
+	| `pasteRecent${1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9}`;
+
+/**
+ * A two-step shortcut, e.g. `CommandOrControl+Alt+V` then `3`.
+ * Mirrors `ShortcutChord` in `electron/lib/shortcut-chords.ts`.
+ */
+interface ShortcutChordRecord {
+	prefix: string;
+	/** Key pressed next; modifiers are optional */
+	key: string;
+	action: ChordActionRecord;
+}
+
+/**
+ * Chords plus the prefixes that could not be registered.
+ * Mirrors `ChordStatus` in `electron/lib/shortcut-chords.ts`.
+ */
+interface ChordStatusRecord {
+	chords: ShortcutChordRecord[];
+	unavailablePrefixes: string[];
+}
+
+/**
+ * What a tray click does: open the menu, nothing, or a shortcut action.
+ * Mirrors `TrayClickAction` in `electron/lib/tray-clicks.ts`.
+ */
+type TrayClickActionRecord =
+	| "menu"
+	| "none"
+	| "toggleWindow"
+	| "toggleQuickPicker"
+	| "toggleCapture"
+	| "pasteLastItem";
+
+/**
+ * Action per click on the tray icon.
+ * Mirrors `TrayClickSettings` in `electron/lib/tray-clicks.ts`.
+ */
+type TrayClickSettingsRecord = Record<
+	"click" | "doubleClick" | "middleClick",
+	TrayClickActionRecord
+>;
+
+/**
+ * Language of the tray, menus and notifications: the system's, or a
+ * fixed one.
+ * Mirrors `LanguageSetting` in `electron/lib/i18n.ts`.
+ */
+type LanguageSettingRecord = "system" | "en" | "de" | "fr" | "es";
+
+/**
+ * The saved language and the one it resolved to.
+ * Mirrors `LanguageStatus` in `electron/lib/i18n.ts`.
+ */
+type LanguageStatusRecord = {
+	language: LanguageSettingRecord;
+	active: Exclude<LanguageSettingRecord, "system">;
+};
+
+/**
+ * What each action's shortcut ended up as with the OS: the accelerator
+ * held (its binding, a fallback or null) and those taken by other
+ * applications.
+ * Mirrors `ShortcutStatus` in `electron/lib/shortcuts.ts`.
+ */
+type ShortcutStatusRecord = Record<
+	ShortcutActionRecord,
+	{ active: string | null; conflicts: string[] }
+>;
+
+/**
+ * "Type it out" paste mode settings.
+ * Mirrors `TypeOutSettings` in `electron/lib/type-out.ts`.
+ */
+interface TypeOutSettingsRecord {
+	/** Pause between synthetic keystrokes (0-1000 ms) */
+	delayMs: number;
+}
+
+/**
+ * Text expander settings and listener state.
+ * Mirrors `TextExpanderStatus` in `electron/lib/text-expander.ts`.
+ */
+interface TextExpanderStatusRecord {
+	/** Off by default; needs the Accessibility permission on macOS */
+	enabled: boolean;
+	/** Apps where abbreviations are left as typed, e.g. `KeePassXC.exe` */
+	excludedApps: string[];
+	running: boolean;
+	/** Why the listener stopped or the last expansion failed */
+	lastError: string | null;
+}
+
+/**
+ * Double-copy gesture settings and listener state.
+ * Mirrors `DoubleCopyStatus` in `electron/lib/double-copy.ts`.
+ */
+interface DoubleCopyStatusRecord {
+	/** Off by default; needs the Accessibility permission on macOS */
+	enabled: boolean;
+	/** Longest gap between the two copies (150-1000 ms) */
+	intervalMs: number;
+	running: boolean;
+	/** Why the listener stopped or could not start */
+	lastError: string | null;
+}
+
+/**
+ * Cmd/Ctrl+Alt+1..9 quick-paste hotkeys.
+ * Mirrors `QuickPasteSettings` in `electron/lib/quick-paste.ts`.
+ */
+interface QuickPasteSettingsRecord {
+	enabled: boolean;
+	/** Paste into the focused app after placing the item on the clipboard */
+	pasteAfterCopy: boolean;
+}
+
+/**
+ * History and quick picker window behavior.
+ * Mirrors `WindowBehaviorSettings` in `electron/lib/window-behavior.ts`.
+ */
+interface WindowBehaviorSettingsRecord {
+	/** Hide the windows when they lose focus */
+	hideOnBlur: boolean;
+}
+
+/**
+ * Everything the settings file holds, one section per table.
+ * Mirrors `AppSettings` in `electron/lib/settings-file.ts`.
+ */
+interface AppSettingsRecord {
+	shortcuts: ShortcutSettingsRecord;
+	retention: RetentionPolicyRecord;
+	exclusions: AppExclusionSettingsRecord;
+	window: WindowBehaviorSettingsRecord;
+	appearance: {
+		theme: "system" | "light" | "dark";
+		/** Tighter list rows, fitting more items on screen */
+		compactList: boolean;
+	};
+}
+
+/**
+ * Folder holding every profile's database, attachments and settings.
+ * Mirrors `DataLocationStatus` in `electron/lib/data-location.ts`.
+ */
+interface DataLocationStatusRecord {
+	dataPath: string;
+	/** `--data-dir`, portable mode, a folder chosen in settings, or userData */
+	source: "flag" | "portable" | "config" | "default";
+	/** Folder the data moves to on the next start */
+	pendingMove: string | null;
+	/** Why the last move failed */
+	lastError: string | null;
+}
+
+/**
+ * Named profiles, each with its own database and settings.
+ * Mirrors `ProfilesStatus` in `electron/lib/profiles.ts`.
+ */
+interface ProfilesStatusRecord {
+	/** Profile this instance runs */
+	active: string;
+	/** Every profile, `default` first */
+	profiles: string[];
+}
+
+type LogLevelRecord = "error" | "warn" | "info" | "debug";
+
+/**
+ * One main process log entry.
+ * Mirrors `LogEntry` in `electron/lib/logger.ts`.
+ */
+interface LogEntryRecord {
+	time: string;
+	level: LogLevelRecord;
+	/** File the entry came from, such as `sync` or `main` */
+	module: string;
+	message: string;
+}
+
+/**
+ * Log levels, overall and per module.
+ * Mirrors `LoggingSettings` in `electron/lib/logger.ts`.
+ */
+interface LoggingSettingsRecord {
+	level: LogLevelRecord;
+	modules: Record<string, LogLevelRecord>;
+}
+
+type CrashKindRecord =
+	| "uncaughtException"
+	| "unhandledRejection"
+	| "renderer"
+	| "childProcess";
+
+/**
+ * A crash report, without its stack and log entries.
+ * Mirrors `CrashReportSummary` in `electron/lib/diagnostics.ts`.
+ */
+interface CrashReportSummaryRecord {
+	fileName: string;
+	time: string;
+	kind: CrashKindRecord;
+	/** Redacted like the bundle */
+	message: string;
+}
+
+/**
+ * Result of writing a diagnostics bundle.
+ * Mirrors `DiagnosticsExport` in `electron/lib/diagnostics.ts`.
+ */
+interface DiagnosticsExportRecord {
+	filePath: string;
+	/** Names of the files in the zip */
+	files: string[];
+}
+
+/**
+ * Updater settings and what it is doing.
+ * Mirrors `UpdaterStatus` in `electron/lib/updater.ts`.
+ */
+interface UpdaterStatusRecord {
+	/** `beta` also offers prereleases */
+	channel: "stable" | "beta";
+	/** Check at startup and every six hours */
+	autoCheck: boolean;
+	/** False in builds without an update signing key */
+	configured: boolean;
+	currentVersion: string;
+	state:
+		| "idle"
+		| "checking"
+		| "up-to-date"
+		| "available"
+		| "downloading"
+		| "ready"
+		| "installing"
+		| "error";
+	/** Release found by the last check */
+	available: {
+		version: string;
+		/** Release notes, in Markdown */
+		notes: string;
+		publishedAt: string | null;
+	} | null;
+	/** Bytes downloaded so far, during a download */
+	progress: { received: number; total: number } | null;
+	lastCheckedAt: string | null;
+	lastError: string | null;
+}
+
+/**
+ * Settings with the TOML file they live in.
+ * Mirrors `SettingsFileStatus` in `electron/lib/settings-file.ts`.
+ */
+interface SettingsFileStatusRecord {
+	path: string;
+	settings: AppSettingsRecord;
+	/** Why the file was last rejected; null once it loads */
+	lastError: string | null;
+}
+
+/**
+ * Local HTTP API settings and whether the server is listening.
+ * Mirrors `HttpApiSettings` in `electron/lib/http-api-settings.ts`.
+ */
+interface HttpApiStatusRecord {
+	/** Serve the API on localhost */
+	enabled: boolean;
+	port: number;
+	/** Bearer token clients must send */
+	token: string;
+	running: boolean;
+}
+
+/**
+ * What `clipboard-manager://` automation links may do.
+ * Mirrors `UrlActionSettings` in `electron/lib/url-actions.ts`.
+ */
+interface UrlActionSettingsRecord {
+	/** Off by default: `latest` and `search` send item text to callbacks */
+	sendContent: boolean;
+	/** Off by default: lets `copy` and `paste` links change the clipboard */
+	allowClipboardActions: boolean;
+	/** Schemes `x-success` and `x-error` may open; never http(s) or file */
+	callbackSchemes: string[];
+}
+
+/**
+ * Outbound webhook settings and how delivery is going.
+ * Mirrors `WebhookStatus` in `electron/lib/webhooks.ts`.
+ */
+interface WebhookStatusRecord {
+	/** Post history events to `url` */
+	enabled: boolean;
+	url: string;
+	/**
+	 * Key for the `X-Clipman-Signature` header: `sha256=` and the hex
+	 * HMAC-SHA256 of `<X-Clipman-Timestamp>.<body>`
+	 */
+	secret: string;
+	events: Array<
+		"item.added" | "item.deleted" | "item.pinned" | "history.cleared"
+	>;
+	/** New items of these types only; empty for all */
+	itemTypes: Array<"text" | "image" | "files">;
+	/** New text items of these content kinds only; empty for all */
+	kinds: ContentKindRecord[];
+	/** Send new items' text, except items flagged as secrets */
+	includeContent: boolean;
+	/** Events waiting to be sent or retried */
+	pending: number;
+	lastDeliveredAt: string | null;
+	/** Why the last event that was given up on failed */
+	lastError: string | null;
+}
+
+type WebhookSettingsRecord = Omit<
+	WebhookStatusRecord,
+	"pending" | "lastDeliveredAt" | "lastError"
+>;
+
+/**
+ * A device on the network or paired for LAN sync.
+ * Mirrors `SyncDevice` in `electron/lib/sync.ts`.
+ */
+interface SyncDeviceRecord {
+	deviceId: string;
+	name: string;
+	paired: boolean;
+	/** Currently advertising on the local network */
+	online: boolean;
+}
+
+/**
+ * LAN sync settings, without peer keys, and what sync is doing.
+ * Mirrors `SyncStatus` in `electron/lib/sync.ts`.
+ */
+interface SyncStatusRecord {
+	/** Advertise, accept and send items */
+	enabled: boolean;
+	deviceId: string;
+	/** Shown to other devices while pairing */
+	deviceName: string;
+	port: number;
+	running: boolean;
+	devices: SyncDeviceRecord[];
+	/** Code another device must enter, while pairing is open */
+	pairingCode: string | null;
+	pairingExpiresAt: string | null;
+}
+
+/**
+ * Remote sync settings, without the password and passphrase, and what
+ * sync is doing.
+ * Mirrors `RemoteSyncStatus` in `electron/lib/remote-sync.ts`.
+ */
+interface RemoteSyncStatusRecord {
+	/** Upload and download the encrypted changelog */
+	enabled: boolean;
+	backend: "webdav" | "s3";
+	/** WebDAV collection URL, or the S3 endpoint */
+	url: string;
+	/** S3 only */
+	bucket: string;
+	/** S3 only */
+	region: string;
+	/** Folder (WebDAV) or key prefix (S3) */
+	prefix: string;
+	/** WebDAV user name, or S3 access key id */
+	username: string;
+	hasPassword: boolean;
+	hasPassphrase: boolean;
+	intervalMinutes: number;
+	deviceId: string;
+	syncing: boolean;
+	lastSyncAt: string | null;
+	/** Why the last sync failed, or null if it succeeded */
+	lastError: string | null;
+}
+
+/**
+ * A board this device is a member of and whether its server answers.
+ * Mirrors `BoardMembershipStatus` in `electron/lib/boards.ts`.
+ */
+interface BoardMembershipRecord {
+	boardId: string;
+	name: string;
+	/** Board server address from the invite */
+	url: string;
+	connected: boolean;
+	/** Why the last request failed, or why items could not be decrypted */
+	lastError: string | null;
+	itemCount: number;
+}
+
+/**
+ * Shared board settings, without tokens or passphrases, and what the
+ * board server is doing.
+ * Mirrors `BoardsStatus` in `electron/lib/boards.ts`.
+ */
+interface BoardsStatusRecord {
+	server: {
+		/** Host boards for other devices */
+		enabled: boolean;
+		port: number;
+		running: boolean;
+		/** Boards hosted on this device */
+		boards: Array<{ id: string; name: string; createdAt: string }>;
+	};
+	memberships: BoardMembershipRecord[];
+}
+
+/**
+ * An item received from a shared board, decrypted.
+ * Mirrors `BoardListItem` in `electron/lib/boards.ts`.
+ */
+interface BoardItemRecord {
+	/** Position on the board; newer items have higher numbers */
+	seq: number;
+	content: string;
+	note: string | null;
+	/** Device name of the member who published it */
+	publishedBy: string;
+	publishedAt: string;
+}
+
+/**
+ * History retention limits; null means unlimited.
+ * Mirrors `RetentionPolicy` in `electron/lib/retention.ts`.
+ */
+interface RetentionPolicyRecord {
+	maxItems: number | null;
+	maxAgeDays: number | null;
+	maxDatabaseMb: number | null;
+	/** Days deleted items stay in the trash */
+	trashDays: number | null;
+	/** Items pasted at least this many times are never pruned */
+	keepPastedAtLeast: number | null;
+}
+
+/**
+ * Backup settings, without the passphrase, and what backup is doing.
+ * Mirrors `BackupStatus` in `electron/lib/backup.ts`.
+ */
+interface BackupStatusRecord {
+	/** Write backups on a schedule */
+	enabled: boolean;
+	/** Absolute path of the backup folder */
+	directory: string;
+	hasPassphrase: boolean;
+	intervalHours: number;
+	/** Backups kept; older ones are deleted */
+	keep: number;
+	lastBackupAt: string | null;
+	/** A backup or restore is in progress */
+	running: boolean;
+	/** Why the last backup failed, or null if it succeeded */
+	lastError: string | null;
+}
+
+/**
+ * How to re-encode an image item.
+ * Mirrors `ImageConversion` in `electron/lib/image-conversion.ts`.
+ */
+interface ImageConversionRecord {
+	format: "png" | "jpeg" | "webp";
+	/** Quality of JPEG and WebP, 1-100; defaults to 90 */
+	quality?: number;
+}
+
+/**
+ * A text transformation and where its result goes.
+ * Mirrors `TextTransformRequest` in `electron/lib/text-transforms.ts`.
+ */
+interface TextTransformRequestRecord {
+	transform:
+		| "uppercase"
+		| "lowercase"
+		| "titleCase"
+		| "trim"
+		| "collapseWhitespace"
+		| "removeLineBreaks"
+		| "sortLines"
+		| "dedupeLines"
+		| "base64Encode"
+		| "base64Decode"
+		| "urlEncode"
+		| "urlDecode"
+		| "splitQueryString";
+	/** A new history item (default) or the system clipboard */
+	target?: "item" | "clipboard";
+}
+
+/**
+ * A saved user script: the body of a function given `item`
+ * (`{ type, text }`). Returning a string replaces the text, `false` or
+ * `null` rejects the item, and anything else keeps it.
+ * Mirrors `UserScript` in `electron/lib/user-scripts.ts`.
+ */
+interface UserScriptRecord {
+	name: string;
+	source: string;
+	/** Runs on every copy, after the capture rules */
+	onCapture: boolean;
+}
+
+/**
+ * Mirrors `ScriptResult` in `electron/lib/user-scripts.ts`.
+ */
+interface ScriptResultRecord {
+	rejected: boolean;
+	/** The text after the script; the input text if it was rejected */
+	text: string;
+}
+
+/**
+ * An external command an item can be handed to, run without a shell.
+ * Mirrors `CommandAction` in `electron/lib/command-actions.ts`.
+ */
+interface CommandActionRecord {
+	name: string;
+	/** Executable name or path */
+	command: string;
+	/**
+	 * One argument each: `{text}` (the text, or a files item's paths one
+	 * per line), `{path}` (its single file path), `{id}`, or an argument
+	 * of just `{paths}` for one argument per path
+	 */
+	args: string[];
+	/** Also write the text to the command's stdin */
+	stdin: boolean;
+	/** Add the command's output to history */
+	captureOutput: boolean;
+	/** 1-300 (default 30) */
+	timeoutSeconds: number;
+}
+
+/**
+ * Mirrors `CommandActionSettings` in `electron/lib/command-actions.ts`.
+ */
+interface CommandActionSettingsRecord {
+	actions: CommandActionRecord[];
+}
+
+/**
+ * A rule run on each copy before it is recorded.
+ * Mirrors `CaptureRule` in `electron/lib/capture-rules.ts`.
+ */
+interface CaptureRuleRecord {
+	name: string;
+	enabled: boolean;
+	/** Rules with a higher priority run first; ties keep list order */
+	priority: number;
+	/** Every condition given must hold; at least one is required */
+	conditions: {
+		/** Regular expression matched against the text */
+		pattern?: string;
+		ignoreCase?: boolean;
+		type?: "text" | "image" | "files";
+		kind?: ContentKindRecord;
+	};
+	/** Applied in order; `ignore` drops the copy and stops evaluation */
+	actions: Array<
+		| { type: "tag"; tag: string }
+		| { type: "ignore" }
+		| { type: "transform"; transform: TextTransformRequestRecord["transform"] }
+		| { type: "expire"; minutes: number }
+		| { type: "notify" }
+	>;
+}
+
+/**
+ * Mirrors `CaptureRuleSettings` in `electron/lib/capture-rules.ts`.
+ */
+interface CaptureRuleSettingsRecord {
+	rules: CaptureRuleRecord[];
+}
+
+/**
+ * Digest algorithms for `hashItem`.
+ * Mirrors `HashAlgorithm` in `electron/lib/item-hash.ts`.
+ */
+type HashAlgorithmRecord = "md5" | "sha1" | "sha256" | "blake3";
+
+/**
+ * A Markdown or HTML conversion and where its result goes.
+ * Mirrors `MarkupConversionRequest` in `electron/lib/markdown.ts`.
+ */
+interface MarkupConversionRequestRecord {
+	conversion: "markdownToHtml" | "htmlToMarkdown";
+	/** A new history item (default) or the system clipboard */
+	target?: "item" | "clipboard";
+}
+
+/**
+ * A structured formatter and where its result goes.
+ * Mirrors `FormatRequest` in `electron/lib/formatters.ts`.
+ */
+interface FormatRequestRecord {
+	format:
+		| "json-pretty"
+		| "json-minify"
+		| "xml-pretty"
+		| "xml-minify"
+		| "sql-pretty"
+		| "sql-minify";
+	/** A new history item (default) or the system clipboard */
+	target?: "item" | "clipboard";
+}
+
+/**
+ * How a files item is restored: the file references, or the text or
+ * image in its only file.
+ * Mirrors `FileRestoreMode` in `electron/lib/file-contents.ts`.
+ */
+type FileRestoreModeRecord = "paths" | "contents";
+
+/**
+ * Notation a color item is copied in.
+ * Mirrors `ColorFormat` in `electron/lib/colors.ts`.
+ */
+type ColorFormatRecord = "hex" | "rgb" | "hsl";
+
+/**
+ * A run of a changed line's words that was kept, added or removed.
+ * Mirrors `DiffSegment` in `electron/lib/text-diff.ts`.
+ */
+interface DiffSegmentRecord {
+	type: "equal" | "insert" | "delete";
+	text: string;
+}
+
+/**
+ * A line of a diff hunk with its 1-based number in each text it is in.
+ * Mirrors `DiffLine` in `electron/lib/text-diff.ts`.
+ */
+interface DiffLineRecord {
+	type: "equal" | "insert" | "delete";
+	text: string;
+	oldLine: number | null;
+	newLine: number | null;
+	/** Changed words, on a replaced line paired with its replacement */
+	segments?: DiffSegmentRecord[];
+}
+
+/**
+ * Changed lines and up to three unchanged lines around them, numbered
+ * like a unified diff hunk.
+ * Mirrors `DiffHunk` in `electron/lib/text-diff.ts`.
+ */
+interface DiffHunkRecord {
+	oldStart: number;
+	oldLines: number;
+	newStart: number;
+	newLines: number;
+	lines: DiffLineRecord[];
+}
+
+/**
+ * Mirrors `TextDiff` in `electron/lib/text-diff.ts`.
+ */
+interface TextDiffRecord {
+	hunks: DiffHunkRecord[];
+	/** Lines only in the second item */
+	added: number;
+	/** Lines only in the first item */
+	removed: number;
 }
 
 interface ElectronAPI {
 	clipboard: {
 		read: () => Promise<ClipboardData>;
 		write: (data: ClipboardData) => Promise<void>;
+		/**
+		 * Places a stored history item (any format) back on the clipboard.
+		 * `files: "contents"` restores the text or image inside a files
+		 * item's only file (up to 5 MB) instead of the file itself
+		 */
+		restoreItem: (
+			id: number,
+			options?: { files?: FileRestoreModeRecord },
+		) => Promise<void>;
+		/** Restores an item, hides the window and pastes it into the previous app */
+		pasteItem: (
+			id: number,
+			options?: { files?: FileRestoreModeRecord },
+		) => Promise<void>;
+		/** Types a text item into the previous app key by key, for paste-blocking fields */
+		typeItem: (id: number) => Promise<void>;
 	};
 	db: {
 		getHistory: (options?: {
 			query?: string;
 			limit?: number;
+			order?: HistoryOrderRecord;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
+			offset?: number;
+		}) => Promise<HistoryRecord[]>;
+		/**
+		 * Keyset-paginated history, newest first or in a ranked order;
+		 * later pages must ask for the same order as the first
+		 */
+		listPage: (options?: {
+			cursor?: string | null;
+			pageSize?: number;
+			order?: HistoryOrderRecord;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
+		}) => Promise<{ items: HistoryRecord[]; nextCursor: string | null }>;
+		/**
+		 * Keyset-paginated history, newest first, grouped into date buckets
+		 * in local time; a bucket cut off by the page size continues as the
+		 * first group of the next page
+		 */
+		listGroupedPage: (options?: {
+			cursor?: string | null;
+			pageSize?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
+		}) => Promise<{ groups: HistoryGroupRecord[]; nextCursor: string | null }>;
+		/**
+		 * Full content of an item; list and search results show items
+		 * flagged as secrets masked (e.g. `ghp_****`) without RTF or HTML
+		 */
+		revealItem: (id: number) => Promise<HistoryRecord>;
+		/**
+		 * Opens a link item in the default browser or an email item in the
+		 * mail app; rejects schemes other than http, https, mailto and ftp
+		 */
+		openItem: (id: number) => Promise<void>;
+		/** Shows a files item's first file, or a path item, in the file manager */
+		showItemInFolder: (id: number) => Promise<void>;
+		/** Full-text search over history content, best match first */
+		searchHistory: (options: {
+			query: string;
+			limit?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
+			offset?: number;
+		}) => Promise<SearchResultRecord[]>;
+		/** Fuzzy (subsequence) search over history content, best match first */
+		fuzzySearchHistory: (options: {
+			query: string;
+			limit?: number;
+			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
+			offset?: number;
+		}) => Promise<SearchResultRecord[]>;
+		/** Regex search (query is the pattern), newest first */
+		regexSearchHistory: (options: {
+			query: string;
+			limit?: number;
 			favoritesOnly?: boolean;
+			tags?: string[];
+			sourceApp?: string;
+			kind?: ContentKindRecord;
+			language?: CodeLanguageRecord;
+			/** Both when omitted, unless PRIMARY selections are kept separate */
+			selection?: SelectionSourceRecord;
 			offset?: number;
-		}) => Promise<
-			Array<{
-				id: number;
-				content: string;
-				type: string;
-				created_at: string;
-				is_favorite: number;
-				rtf: string | null;
-			}>
-		>;
-		addClip: (data: ClipboardData) => Promise<void>;
+		}) => Promise<SearchResultRecord[]>;
+		/**
+		 * Indexed text items closest in meaning to the query, best first
+		 * (1-100, default 50); rejects while semantic search is off
+		 */
+		semanticSearch: (
+			query: string,
+			limit?: number,
+		) => Promise<SemanticSearchRecord[]>;
 		deleteHistoryItem: (id: number) => Promise<void>;
 		clearAllHistory: () => Promise<void>;
+		/** Lists deleted items, most recently deleted first */
+		listTrash: (options?: {
+			limit?: number;
+			offset?: number;
+		}) => Promise<TrashRecord[]>;
+		/**
+		 * Moves a trash entry back into history with its tags and
+		 * collections; returns the restored item's id
+		 */
+		restoreFromTrash: (id: number) => Promise<number>;
+		/** Permanently deletes the trash; returns the number of entries */
+		emptyTrash: () => Promise<number>;
+		/**
+		 * Issues the single-use token, valid for a minute, that confirms a
+		 * wipe; call when the user opens the wipe confirmation
+		 */
+		requestWipe: () => Promise<{ token: string; expiresAt: string }>;
+		/**
+		 * Deletes all history, tags, collections and registers, vacuums the
+		 * database and clears the clipboard; rejects without a valid token
+		 */
+		wipeAllHistory: (token: string) => Promise<void>;
+		/** Returns a downscaled data URL preview for an image item */
+		getImagePreview: (id: number) => Promise<string | null>;
+		/** Returns a small cached data URL thumbnail for an image item */
+		getThumbnail: (id: number) => Promise<string | null>;
+		/** Copies the decoded text of the QR code in an image item */
+		copyQrPayload: (id: number) => Promise<void>;
+		/**
+		 * Renders a text item as a QR code PNG data URL; with `copy`, also
+		 * places the image on the clipboard
+		 */
+		generateQr: (id: number, options?: { copy?: boolean }) => Promise<string>;
+		/**
+		 * Places an image item on the clipboard re-encoded as PNG, JPEG or
+		 * WebP (WebP needs the `cwebp` command)
+		 */
+		copyImageAs: (
+			id: number,
+			conversion: ImageConversionRecord,
+		) => Promise<void>;
+		/**
+		 * Removes metadata such as EXIF from an image item; resolves to false
+		 * if there was none
+		 */
+		stripMetadata: (id: number) => Promise<boolean>;
+		/** Writes an image item re-encoded as PNG, JPEG or WebP to a file */
+		saveImageAs: (
+			id: number,
+			conversion: ImageConversionRecord,
+			filePath: string,
+		) => Promise<void>;
+		/**
+		 * Writes a text item as UTF-8 or an image item to a file, as PNG or
+		 * in the format `conversion` or the extension asks for; rejects if
+		 * the file exists unless `overwrite` is set
+		 */
+		saveItemToFile: (
+			id: number,
+			filePath: string,
+			options?: { conversion?: ImageConversionRecord; overwrite?: boolean },
+		) => Promise<void>;
+		/**
+		 * A color item as hex (`#ff0000`), `rgb(255, 0, 0)` and
+		 * `hsl(0, 100%, 50%)`; rejects other items
+		 */
+		getColorFormats: (id: number) => Promise<Record<ColorFormatRecord, string>>;
+		/** Copies a color item as plain text in one notation */
+		copyColorAs: (id: number, format: ColorFormatRecord) => Promise<void>;
+		/**
+		 * Compares two text items line by line; rejects texts with more
+		 * than 2000 changed lines
+		 */
+		diffItems: (oldId: number, newId: number) => Promise<TextDiffRecord>;
+		/**
+		 * Changes the case, whitespace or line order of a text item and adds
+		 * the result as a new item or places it on the clipboard
+		 */
+		transformItem: (
+			id: number,
+			request: TextTransformRequestRecord,
+		) => Promise<void>;
+		/**
+		 * Pretty-prints or minifies a JSON, XML or SQL text item; rejects
+		 * text that does not parse as the chosen language
+		 */
+		formatItem: (id: number, request: FormatRequestRecord) => Promise<void>;
+		/**
+		 * Turns a Markdown item into rich text (keeping the Markdown as plain
+		 * text), or an item's HTML into Markdown
+		 */
+		convertMarkup: (
+			id: number,
+			request: MarkupConversionRequestRecord,
+		) => Promise<void>;
+		/**
+		 * Runs a saved script on a text item and adds the result as a new
+		 * item (default) or places it on the clipboard
+		 */
+		runScript: (
+			id: number,
+			request: { name: string; target?: "item" | "clipboard" },
+		) => Promise<void>;
+		/**
+		 * Runs a command action on a text or files item; rejects if the
+		 * command fails or times out, or an item value starting with `-`
+		 * would begin an argument before `--`
+		 */
+		runCommandAction: (
+			id: number,
+			name: string,
+		) => Promise<{ output: string; recorded: boolean }>;
+		/**
+		 * Lowercase hex digest of an item's exact bytes: its text as UTF-8,
+		 * an image's PNG, or the contents of a single copied file
+		 */
+		hashItem: (id: number, algorithm: HashAlgorithmRecord) => Promise<string>;
 		toggleFavorite: (id: number) => Promise<boolean>;
+		/** Pins or unpins an item; returns the new pinned state */
+		togglePin: (id: number) => Promise<boolean>;
+		/**
+		 * Deletes, pins or unpins, tags, or moves many items in one
+		 * transaction; nothing changes if any item is missing. Returns the
+		 * number of items changed
+		 */
+		batchUpdate: (
+			ids: number[],
+			operation: BatchOperationRecord,
+		) => Promise<number>;
+		/** Keeps an item off (or lets it onto) paired devices; resolves to the new excluded state */
+		toggleSyncExcluded: (id: number) => Promise<boolean>;
+		/**
+		 * Marks an item one-time (or not): the first restore, paste or
+		 * type-out deletes it, skipping the trash, and leaves it unrecorded
+		 * on the clipboard; resolves to the new state
+		 */
+		togglePasteOnce: (id: number) => Promise<boolean>;
+		/** Sets an item's note; blank text clears it */
+		setItemNote: (id: number, text: string) => Promise<void>;
+		/**
+		 * Deletes an item at an ISO 8601 time in the future, e.g. an hour
+		 * from now, clearing the clipboard if it still holds the item;
+		 * null keeps it. Pinned items and items in a collection expire too
+		 */
+		setItemExpiry: (id: number, expiresAt: string | null) => Promise<void>;
+		/**
+		 * Clears an item's secret flag and expiry; false if it was not
+		 * flagged
+		 */
+		dismissSecret: (id: number) => Promise<boolean>;
+		/**
+		 * Joins 2-100 text items, in the order given, into a new item at the
+		 * top of history; `copy` also places it on the clipboard
+		 */
+		mergeItems: (
+			ids: number[],
+			separator: string,
+			copy?: boolean,
+		) => Promise<HistoryRecord>;
+		/**
+		 * Groups image items that look alike (perceptual hashes at most
+		 * `maxDistance` bits apart, default 5); ids are newest first
+		 */
+		findNearDuplicates: (maxDistance?: number) => Promise<number[][]>;
+		/**
+		 * Groups text items that are nearly the same, e.g. edits of one
+		 * paragraph (similarity hashes at most `maxDistance` bits apart,
+		 * default 8); ids are newest first, so `mergeDuplicates` keeps the
+		 * newest
+		 */
+		findNearDuplicateTexts: (maxDistance?: number) => Promise<number[][]>;
+		/**
+		 * Keeps the first of 2-100 duplicate items and moves the rest to the
+		 * trash; the kept item gains their tags, favorite and pinned flags
+		 */
+		mergeDuplicates: (ids: number[]) => Promise<HistoryRecord>;
+		/** Apps items were copied from, most items first */
+		listSourceApps: () => Promise<Array<{ name: string; count: number }>>;
+		/**
+		 * Writes matching history, oldest first, to a JSON or CSV file at an
+		 * absolute path; resolves to the number of items exported
+		 */
+		exportHistory: (
+			format: "json" | "csv",
+			filePath: string,
+			filters?: {
+				pinnedOnly?: boolean;
+				favoritesOnly?: boolean;
+				tags?: string[];
+				sourceApp?: string;
+			},
+		) => Promise<number>;
+		/**
+		 * Writes the text items among pinned items, or in a collection, as
+		 * an Alfred snippet collection (`.alfredsnippets`) or Raycast
+		 * snippets JSON; resolves to the number of snippets exported
+		 */
+		exportSnippets: (
+			format: "alfred" | "raycast",
+			filePath: string,
+			source: "pinned" | { collectionId: number },
+		) => Promise<number>;
+		/**
+		 * Reveals, copies, pastes and exports from every surface, newest
+		 * first. The log is append-only and outlives the items it names
+		 */
+		listAccessLog: (
+			query?: AccessLogQueryRecord,
+		) => Promise<AccessLogEntryRecord[]>;
+		/**
+		 * Adds the items of a JSON export (absolute path) that are not in
+		 * history yet; image items and malformed entries are skipped
+		 */
+		importHistory: (
+			filePath: string,
+		) => Promise<{ imported: number; skipped: number }>;
+		/**
+		 * Adds CopyQ items with their tags, notes and pinned state from a tab
+		 * file (`copyq_tab_*.dat`), CopyQ's configuration directory, or a
+		 * synchronized tab directory (absolute paths)
+		 */
+		importCopyQ: (
+			sourcePath: string,
+		) => Promise<{ imported: number; skipped: number }>;
+		/**
+		 * Adds text and image clips from a Ditto database (`Ditto.db`,
+		 * absolute path), keeping their original copy times
+		 */
+		importDitto: (
+			dbPath: string,
+		) => Promise<{ imported: number; skipped: number }>;
+		/**
+		 * Adds text and image items from Maccy's store (`Storage.sqlite`,
+		 * absolute path), keeping copy times, counts and pins
+		 */
+		importMaccy: (
+			dbPath: string,
+		) => Promise<{ imported: number; skipped: number }>;
+		/** Vacuums, reindexes, and optimizes the database now; sizes in bytes */
+		runMaintenance: () => Promise<{
+			sizeBefore: number;
+			sizeAfter: number;
+			reclaimedBytes: number;
+			durationMs: number;
+		}>;
+		/** Runs SQLite's full integrity check and the full-text index checks */
+		checkDatabase: () => Promise<DatabaseCheckRecord>;
+		/** Checks, then rebuilds indexes or salvages rows if anything is wrong */
+		repairDatabase: () => Promise<DatabaseRecoveryRecord>;
+		/** The recovery run at startup or by `repairDatabase`; null if none */
+		getLastRecovery: () => Promise<DatabaseRecoveryRecord | null>;
+		/** Tags an item; returns the item's tags after the change */
+		addTag: (id: number, name: string) => Promise<string[]>;
+		/** Untags an item; returns the item's tags after the change */
+		removeTag: (id: number, name: string) => Promise<string[]>;
+		getItemTags: (id: number) => Promise<string[]>;
+		/** Every tag in use, alphabetically, with its item count */
+		listTags: () => Promise<Array<{ name: string; count: number }>>;
+	};
+	collections: {
+		/** Every collection, alphabetically, with its item count */
+		list: () => Promise<CollectionRecord[]>;
+		create: (name: string) => Promise<CollectionRecord>;
+		rename: (id: number, name: string) => Promise<CollectionRecord>;
+		/** Deletes a collection; its items stay in history */
+		delete: (id: number) => Promise<void>;
+		/** Items of a collection in their saved order */
+		getItems: (id: number) => Promise<HistoryRecord[]>;
+		/** Copies an item onto the end of a collection */
+		addItem: (collectionId: number, itemId: number) => Promise<void>;
+		removeItem: (collectionId: number, itemId: number) => Promise<void>;
+		moveItem: (options: {
+			itemId: number;
+			fromCollectionId: number;
+			toCollectionId: number;
+		}) => Promise<void>;
+		/** Saves a new order; must list every item in the collection once */
+		reorderItems: (collectionId: number, itemIds: number[]) => Promise<void>;
+	};
+	retention: {
+		getPolicy: () => Promise<RetentionPolicyRecord>;
+		/**
+		 * Saves the given limits and prunes history to match; pinned items
+		 * and items in a collection are kept
+		 */
+		updatePolicy: (
+			policy: Partial<RetentionPolicyRecord>,
+		) => Promise<RetentionPolicyRecord>;
+	};
+	backup: {
+		get: () => Promise<BackupStatusRecord>;
+		/** The passphrase encrypts every backup and is needed to restore one */
+		update: (settings: {
+			enabled?: boolean;
+			directory?: string;
+			passphrase?: string;
+			intervalHours?: number;
+			keep?: number;
+		}) => Promise<BackupStatusRecord>;
+		/** Writes a backup now, then deletes ones beyond `keep` */
+		backupNow: () => Promise<BackupStatusRecord>;
+		/**
+		 * Replaces history with a backup file (absolute path). The saved
+		 * passphrase is used unless another is given
+		 */
+		restore: (
+			backupPath: string,
+			passphrase?: string,
+		) => Promise<BackupStatusRecord>;
+	};
+	lock: {
+		getStatus: () => Promise<AppLockStatusRecord>;
+		/** Locks history now (no-op unless the lock is enabled) */
+		lock: () => Promise<AppLockStatusRecord>;
+		/** Prompts for OS authentication; rejects if it fails */
+		unlock: () => Promise<AppLockStatusRecord>;
+		/** Requires an unlocked history; disabling prompts for authentication */
+		updateSettings: (settings: {
+			enabled?: boolean;
+			autoLockMinutes?: number | null;
+		}) => Promise<AppLockStatusRecord>;
+	};
+	/** Incognito mode: while paused, nothing is recorded */
+	capture: {
+		getStatus: () => Promise<CapturePauseStatusRecord>;
+		/** Pauses or resumes indefinitely, cancelling any snooze */
+		setPaused: (paused: boolean) => Promise<CapturePauseStatusRecord>;
+		/** Pauses capture for 1-1440 minutes, then resumes automatically */
+		snooze: (minutes: number) => Promise<CapturePauseStatusRecord>;
+	};
+	/** Guest mode: history can be copied and pasted but not changed */
+	readOnly: {
+		getStatus: () => Promise<ReadOnlyStatusRecord>;
+		/**
+		 * Throws "History is locked" while the app lock is engaged; turning
+		 * it off asks for OS authentication first
+		 */
+		updateSettings: (settings: {
+			enabled?: boolean;
+		}) => Promise<ReadOnlyStatusRecord>;
+	};
+	sounds: {
+		get: () => Promise<CaptureSoundSettingsRecord>;
+		/** Single events in `sounds` can be updated on their own */
+		update: (settings: {
+			enabled?: boolean;
+			sounds?: Partial<Record<SoundEventRecord, string>>;
+		}) => Promise<CaptureSoundSettingsRecord>;
+		/** Plays an event's sound even while sounds are off */
+		preview: (event: SoundEventRecord) => Promise<void>;
+	};
+	captureLimits: {
+		getSettings: () => Promise<CaptureLimitSettingsRecord>;
+		/** Limits are in bytes: 1024 up to 1,000,000, or 50 MiB for images */
+		updateSettings: (settings: {
+			text?: Partial<CaptureLimitSettingsRecord["text"]>;
+			richText?: Partial<CaptureLimitSettingsRecord["richText"]>;
+			image?: Partial<CaptureLimitSettingsRecord["image"]>;
+		}) => Promise<CaptureLimitSettingsRecord>;
+	};
+	primarySelection: {
+		getSettings: () => Promise<PrimarySelectionSettingsRecord>;
+		/** Turning capture on fails anywhere but Linux */
+		updateSettings: (
+			settings: Partial<PrimarySelectionSettingsRecord>,
+		) => Promise<PrimarySelectionSettingsRecord>;
+	};
+	/** Polls less and holds back background jobs on battery or when idle */
+	powerThrottle: {
+		getSettings: () => Promise<PowerThrottleSettingsRecord>;
+		updateSettings: (
+			settings: Partial<PowerThrottleSettingsRecord>,
+		) => Promise<PowerThrottleSettingsRecord>;
+		getStatus: () => Promise<PowerThrottleStatusRecord>;
+	};
+	/** Keeps, skips or tags copies handed over through Handoff */
+	universalClipboard: {
+		getSettings: () => Promise<UniversalClipboardSettingsRecord>;
+		updateSettings: (
+			settings: Partial<UniversalClipboardSettingsRecord>,
+		) => Promise<UniversalClipboardSettingsRecord>;
+	};
+	/** Puts the clipboard from before a quit or reboot back at startup */
+	clipboardRestore: {
+		getSettings: () => Promise<ClipboardRestoreSettingsRecord>;
+		updateSettings: (
+			settings: Partial<ClipboardRestoreSettingsRecord>,
+		) => Promise<ClipboardRestoreSettingsRecord>;
+	};
+	/** Collects several copies into one clipboard instead of replacing it */
+	appendCopy: {
+		getStatus: () => Promise<AppendCopyStatusRecord>;
+		/** Switching on starts from the current clipboard text */
+		setActive: (active: boolean) => Promise<AppendCopyStatusRecord>;
+		/** The separator may be empty and at most 20 characters */
+		updateSettings: (settings: {
+			separator?: string;
+		}) => Promise<AppendCopyStatusRecord>;
+	};
+	autoClear: {
+		getStatus: () => Promise<AutoClearStatusRecord>;
+		updateSettings: (settings: {
+			enabled?: boolean;
+			clearAfterSeconds?: number;
+		}) => Promise<AutoClearStatusRecord>;
+		/** Keeps the current sensitive copy on the clipboard */
+		cancel: () => Promise<AutoClearStatusRecord>;
+	};
+	exclusions: {
+		get: () => Promise<AppExclusionSettingsRecord>;
+		/** Matching ignores case and a `.exe` suffix; duplicates are dropped */
+		update: (settings: {
+			excludedApps?: string[];
+		}) => Promise<AppExclusionSettingsRecord>;
+	};
+	secrets: {
+		getSettings: () => Promise<SecretScanningSettingsRecord>;
+		/** `rules` replaces the whole list; each pattern must compile */
+		updateSettings: (
+			settings: Partial<SecretScanningSettingsRecord>,
+		) => Promise<SecretScanningSettingsRecord>;
+	};
+	notifications: {
+		get: () => Promise<NotificationSettingsRecord>;
+		update: (
+			settings: Partial<NotificationSettingsRecord>,
+		) => Promise<NotificationSettingsRecord>;
+	};
+	scripts: {
+		list: () => Promise<UserScriptRecord[]>;
+		/** Replaces a script with the same name; the source must compile */
+		install: (script: {
+			name: string;
+			source: string;
+			onCapture?: boolean;
+		}) => Promise<UserScriptRecord[]>;
+		remove: (name: string) => Promise<UserScriptRecord[]>;
+		/** Runs a saved script (`name`) or unsaved `source` on sample text */
+		test: (request: {
+			name?: string;
+			source?: string;
+			text: string;
+			type?: "text" | "image" | "files";
+		}) => Promise<ScriptResultRecord>;
+	};
+	commandActions: {
+		getSettings: () => Promise<CommandActionSettingsRecord>;
+		/** `actions` replaces the whole list; placeholders are validated */
+		updateSettings: (
+			settings: Partial<CommandActionSettingsRecord>,
+		) => Promise<CommandActionSettingsRecord>;
+	};
+	captureRules: {
+		getSettings: () => Promise<CaptureRuleSettingsRecord>;
+		/** `rules` replaces the whole list; each rule is validated */
+		updateSettings: (
+			settings: Partial<CaptureRuleSettingsRecord>,
+		) => Promise<CaptureRuleSettingsRecord>;
+	};
+	linkPreviews: {
+		getSettings: () => Promise<LinkPreviewSettingsRecord>;
+		/** Enabling starts fetching links not fetched yet */
+		updateSettings: (
+			settings: Partial<LinkPreviewSettingsRecord>,
+		) => Promise<LinkPreviewSettingsRecord>;
+	};
+	semanticSearch: {
+		getSettings: () => Promise<SemanticSearchSettingsRecord>;
+		/** Enabling or changing the model indexes items not embedded yet */
+		updateSettings: (
+			settings: Partial<SemanticSearchSettingsRecord>,
+		) => Promise<SemanticSearchSettingsRecord>;
+	};
+	itemSummaries: {
+		getSettings: () => Promise<ItemSummarySettingsRecord>;
+		/** Enabling or lowering `minChars` summarizes items captured before */
+		updateSettings: (
+			settings: Partial<ItemSummarySettingsRecord>,
+		) => Promise<ItemSummarySettingsRecord>;
+	};
+	favicons: {
+		/**
+		 * Icon of a site as a data URL, from the on-disk cache or, while link
+		 * previews are enabled, fetched once; null if it has none
+		 */
+		get: (domain: string) => Promise<string | null>;
+	};
+	shortcuts: {
+		get: () => Promise<ShortcutSettingsRecord>;
+		/**
+		 * Rebinds an action immediately; null unbinds it. Rejects if the
+		 * accelerator is invalid, bound to another action or taken by
+		 * another application.
+		 */
+		set: (
+			action: ShortcutActionRecord,
+			accelerator: string | null,
+		) => Promise<ShortcutSettingsRecord>;
+		getStatus: () => Promise<ShortcutStatusRecord>;
+		getFallbacks: () => Promise<ShortcutFallbacksRecord>;
+		/**
+		 * Replaces an action's fallbacks (at most 3); an action left without
+		 * a shortcut by a conflict tries them at once.
+		 */
+		setFallbacks: (
+			action: ShortcutActionRecord,
+			accelerators: string[],
+		) => Promise<ShortcutFallbacksRecord>;
+	};
+	/** Prefix hotkeys followed by a second key, e.g. Ctrl+Alt+V then 3 */
+	chords: {
+		get: () => Promise<ChordStatusRecord>;
+		/** Replaces every chord and re-registers the prefixes at once */
+		update: (settings: {
+			chords?: ShortcutChordRecord[];
+		}) => Promise<ChordStatusRecord>;
+	};
+	/**
+	 * What clicking the tray icon does. Double-click is reported on macOS
+	 * and Windows, middle-click on Windows only.
+	 */
+	trayClicks: {
+		get: () => Promise<TrayClickSettingsRecord>;
+		update: (
+			settings: Partial<TrayClickSettingsRecord>,
+		) => Promise<TrayClickSettingsRecord>;
+	};
+	/**
+	 * Language of the tray, menus and notifications; `system` follows the
+	 * preferred system languages and falls back to English. The tray menu
+	 * is rebuilt when it changes.
+	 */
+	language: {
+		get: () => Promise<LanguageStatusRecord>;
+		set: (language: LanguageSettingRecord) => Promise<LanguageStatusRecord>;
+	};
+	/** Item ids lined up to be pasted in order, one per paste */
+	queue: {
+		get: () => Promise<number[]>;
+		/** Appends an item, or removes it if already queued */
+		toggle: (itemId: number) => Promise<number[]>;
+		clear: () => Promise<number[]>;
+		/** Consumes the next item and pastes it into the previous app */
+		pasteNext: () => Promise<void>;
+	};
+	/** Clipboards pushed with the push hotkey, newest first; popped by the pop hotkey */
+	/** Vim-style named clipboards a-z and 0-9, kept across restarts */
+	registers: {
+		/** Filled registers, letters before digits */
+		list: () => Promise<RegisterRecord[]>;
+		/** Saves the current clipboard into a register, replacing it */
+		copy: (name: string) => Promise<RegisterRecord>;
+		/** Puts a register on the clipboard and pastes it into the previous app */
+		paste: (name: string) => Promise<void>;
+		delete: (name: string) => Promise<void>;
+	};
+	/** Saved text with placeholders, kept apart from history */
+	snippets: {
+		/** Snippets by name, optionally those whose name or content matches */
+		list: (query?: string) => Promise<SnippetRecord[]>;
+		/** Field names the content asks for, to prompt for before pasting */
+		getFields: (content: string) => Promise<string[]>;
+		create: (input: SnippetInputRecord) => Promise<SnippetRecord>;
+		update: (
+			id: number,
+			input: Partial<SnippetInputRecord>,
+		) => Promise<SnippetRecord>;
+		delete: (id: number) => Promise<void>;
+		/** Fills in the placeholders and pastes into the previous app */
+		paste: (id: number, fields?: Record<string, string>) => Promise<void>;
+	};
+	/** Aggregates for a stats dashboard, computed in SQL */
+	stats: {
+		/** Defaults to the last 30 days, 12 weeks and top 10 apps */
+		get: (options?: {
+			days?: number;
+			weeks?: number;
+			topApps?: number;
+		}) => Promise<HistoryStatsRecord>;
+		/** Items per local day of a calendar year */
+		heatmap: (year: number) => Promise<ActivityHeatmapRecord>;
+	};
+	stack: {
+		get: () => Promise<ClipboardData[]>;
+		clear: () => Promise<ClipboardData[]>;
+	};
+	typeOut: {
+		getSettings: () => Promise<TypeOutSettingsRecord>;
+		updateSettings: (settings: {
+			delayMs?: number;
+		}) => Promise<TypeOutSettingsRecord>;
+	};
+	/** Replaces abbreviations typed in any app with their snippets */
+	textExpander: {
+		get: () => Promise<TextExpanderStatusRecord>;
+		update: (settings: {
+			enabled?: boolean;
+			excludedApps?: string[];
+		}) => Promise<TextExpanderStatusRecord>;
+	};
+	/** Opens the quick picker when Cmd/Ctrl+C is pressed twice quickly */
+	doubleCopy: {
+		get: () => Promise<DoubleCopyStatusRecord>;
+		update: (settings: {
+			enabled?: boolean;
+			intervalMs?: number;
+		}) => Promise<DoubleCopyStatusRecord>;
+	};
+	quickPaste: {
+		get: () => Promise<QuickPasteSettingsRecord>;
+		update: (settings: {
+			enabled?: boolean;
+			pasteAfterCopy?: boolean;
+		}) => Promise<QuickPasteSettingsRecord>;
+	};
+	windowBehavior: {
+		get: () => Promise<WindowBehaviorSettingsRecord>;
+		update: (settings: {
+			hideOnBlur?: boolean;
+		}) => Promise<WindowBehaviorSettingsRecord>;
+	};
+	settings: {
+		get: () => Promise<SettingsFileStatusRecord>;
+		/** Updates any sections and rewrites the file */
+		update: (settings: {
+			[Section in keyof AppSettingsRecord]?: Partial<
+				AppSettingsRecord[Section]
+			>;
+		}) => Promise<SettingsFileStatusRecord>;
+	};
+	dataLocation: {
+		get: () => Promise<DataLocationStatusRecord>;
+		/** Moves the data to an empty folder; the app relaunches to do it */
+		migrate: (target: string) => Promise<DataLocationStatusRecord>;
+	};
+	profiles: {
+		get: () => Promise<ProfilesStatusRecord>;
+		create: (name: string) => Promise<ProfilesStatusRecord>;
+		/** Relaunches the app in the profile */
+		switch: (name: string) => Promise<void>;
+		/** Deletes a profile and all of its data */
+		remove: (name: string) => Promise<ProfilesStatusRecord>;
+	};
+	logs: {
+		/** Newest entries, oldest first; 200 unless a limit is given */
+		getRecent: (query?: {
+			limit?: number;
+			/** Least severe level returned */
+			level?: LogLevelRecord | null;
+			module?: string | null;
+		}) => Promise<LogEntryRecord[]>;
+		getSettings: () => Promise<LoggingSettingsRecord>;
+		/** `modules` replaces the per-module levels; null clears one */
+		updateSettings: (settings: {
+			level?: LogLevelRecord;
+			modules?: Record<string, LogLevelRecord | null>;
+		}) => Promise<LoggingSettingsRecord>;
+		openFolder: () => Promise<void>;
+	};
+	diagnostics: {
+		/** Newest first; the newest 20 are kept */
+		listCrashes: () => Promise<CrashReportSummaryRecord[]>;
+		/** Writes a redacted zip of logs, crash reports and settings */
+		export: (filePath: string) => Promise<DiagnosticsExportRecord>;
+	};
+	updater: {
+		get: () => Promise<UpdaterStatusRecord>;
+		updateSettings: (
+			settings: Partial<Pick<UpdaterStatusRecord, "channel" | "autoCheck">>,
+		) => Promise<UpdaterStatusRecord>;
+		check: () => Promise<UpdaterStatusRecord>;
+		/** Downloads the release found and verifies its signature */
+		download: () => Promise<UpdaterStatusRecord>;
+		/** Installs the downloaded release; the app quits or relaunches */
+		install: () => Promise<UpdaterStatusRecord>;
+	};
+	httpApi: {
+		get: () => Promise<HttpApiStatusRecord>;
+		update: (settings: {
+			enabled?: boolean;
+			port?: number;
+			token?: string;
+		}) => Promise<HttpApiStatusRecord>;
+		regenerateToken: () => Promise<HttpApiStatusRecord>;
+	};
+	urlActions: {
+		getSettings: () => Promise<UrlActionSettingsRecord>;
+		/** Rejects while locked */
+		updateSettings: (
+			settings: Partial<UrlActionSettingsRecord>,
+		) => Promise<UrlActionSettingsRecord>;
+	};
+	webhooks: {
+		get: () => Promise<WebhookStatusRecord>;
+		update: (
+			settings: Partial<WebhookSettingsRecord>,
+		) => Promise<WebhookStatusRecord>;
+		/** Replaces the secret; receivers must be given the new one */
+		regenerateSecret: () => Promise<WebhookStatusRecord>;
+		/** Posts a `ping` event once; rejects if it is not delivered */
+		test: () => Promise<void>;
+	};
+	sync: {
+		get: () => Promise<SyncStatusRecord>;
+		update: (settings: {
+			enabled?: boolean;
+			deviceName?: string;
+			port?: number;
+		}) => Promise<SyncStatusRecord>;
+		/** Opens pairing for two minutes; the code is in the returned status */
+		startPairing: () => Promise<SyncStatusRecord>;
+		cancelPairing: () => Promise<SyncStatusRecord>;
+		/** Pairs with a device on the network using the code it shows */
+		pair: (deviceId: string, code: string) => Promise<SyncStatusRecord>;
+		unpair: (deviceId: string) => Promise<SyncStatusRecord>;
+	};
+	remoteSync: {
+		get: () => Promise<RemoteSyncStatusRecord>;
+		/** `password` is the S3 secret key; the passphrase encrypts uploads */
+		update: (settings: {
+			enabled?: boolean;
+			backend?: "webdav" | "s3";
+			url?: string;
+			bucket?: string;
+			region?: string;
+			prefix?: string;
+			username?: string;
+			password?: string;
+			passphrase?: string;
+			intervalMinutes?: number;
+		}) => Promise<RemoteSyncStatusRecord>;
+		/** Syncs now; a failure is reported in `lastError` */
+		syncNow: () => Promise<RemoteSyncStatusRecord>;
+	};
+	boards: {
+		get: () => Promise<BoardsStatusRecord>;
+		/** Turns hosting on or off, or moves it to another port */
+		updateServer: (settings: {
+			enabled?: boolean;
+			port?: number;
+		}) => Promise<BoardsStatusRecord>;
+		/**
+		 * Hosts a new board and joins it. The invite holds the board's
+		 * token and is only returned now; the passphrase is shared with
+		 * members separately.
+		 */
+		createBoard: (
+			name: string,
+			passphrase: string,
+		) => Promise<{ invite: string; status: BoardsStatusRecord }>;
+		/** Revokes a hosted board's invite and returns a new one */
+		regenerateToken: (
+			boardId: string,
+		) => Promise<{ invite: string; status: BoardsStatusRecord }>;
+		deleteBoard: (boardId: string) => Promise<BoardsStatusRecord>;
+		join: (input: {
+			invite: string;
+			passphrase: string;
+			name?: string;
+		}) => Promise<BoardsStatusRecord>;
+		leave: (boardId: string) => Promise<BoardsStatusRecord>;
+		/** Publishes a text item; resolves to its position on the board */
+		publish: (boardId: string, id: number) => Promise<number>;
+		/** Items received from a board, newest first */
+		listItems: (boardId: string) => Promise<BoardItemRecord[]>;
+		copyItem: (boardId: string, seq: number) => Promise<void>;
 	};
 	window: {
 		center: () => Promise<void>;
@@ -37,6 +2201,53 @@ interface ElectronAPI {
 		hide: () => Promise<void>;
 		isVisible: () => Promise<boolean>;
 		hideAndPaste: () => Promise<void>;
+		isPinned: () => Promise<boolean>;
+		/** Keeps the window on top and on every workspace; resolves to the new state */
+		setPinned: (pinned: boolean) => Promise<boolean>;
+	};
+	picker: {
+		show: () => Promise<void>;
+		hide: () => Promise<void>;
+		toggle: () => Promise<void>;
+		isVisible: () => Promise<boolean>;
+	};
+	events: {
+		/** Subscribes to history changes made by the main process; returns an unsubscribe function */
+		onHistoryChanged: (callback: () => void) => () => void;
+		/** Subscribes to items being added, deleted or pinned (also sent as history changes); returns an unsubscribe function */
+		onHistoryEvent: (
+			callback: (event: HistoryEventRecord) => void,
+		) => () => void;
+		/** Subscribes to app lock changes; returns an unsubscribe function */
+		onLockChanged: (
+			callback: (locked: boolean, reason: LockChangeReasonRecord) => void,
+		) => () => void;
+		/** Subscribes to capture pause changes (including from the tray); returns an unsubscribe function */
+		onCaptureChanged: (
+			callback: (status: CapturePauseStatusRecord) => void,
+		) => () => void;
+		/** Subscribes to read-only mode turning on or off; returns an unsubscribe function */
+		onReadOnlyChanged: (
+			callback: (status: ReadOnlyStatusRecord) => void,
+		) => () => void;
+		/** Subscribes to paste queue changes (including items consumed by the hotkey); returns an unsubscribe function */
+		onQueueChanged: (callback: (itemIds: number[]) => void) => () => void;
+		/** Subscribes to settings changes and errors from the settings file; returns an unsubscribe function */
+		onSettingsChanged: (
+			callback: (status: SettingsFileStatusRecord) => void,
+		) => () => void;
+		/** Subscribes to update checks and download progress; returns an unsubscribe function */
+		onUpdaterStatus: (
+			callback: (status: UpdaterStatusRecord) => void,
+		) => () => void;
+		/** Subscribes to global shortcut registrations and conflicts; returns an unsubscribe function */
+		onShortcutsStatus: (
+			callback: (status: ShortcutStatusRecord) => void,
+		) => () => void;
+		/** Subscribes to searches requested by deep links; returns an unsubscribe function */
+		onSearchRequested: (callback: (query: string) => void) => () => void;
+		/** A shared board got new items */
+		onBoardsUpdated: (callback: (boardId: string) => void) => () => void;
 	};
 	app: {
 		quit: () => Promise<void>;
@@ -44,6 +2255,9 @@ interface ElectronAPI {
 		setLaunchAtLogin: (
 			enabled: boolean,
 		) => Promise<{ success: boolean; error?: string }>;
+		/** Whether the app starts with only its tray icon (the default) */
+		getStartMinimized: () => Promise<boolean>;
+		setStartMinimized: (enabled: boolean) => Promise<boolean>;
 	};
 }
 